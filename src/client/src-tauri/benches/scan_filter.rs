@@ -0,0 +1,72 @@
+//! Next-scan filter throughput: the per-value compare_values loop against the
+//! typed rayon kernels. Run with `cargo bench --bench scan_filter`.
+
+use std::time::{Duration, Instant};
+
+use dyna_dbg_lib::scan_kernel::{filter_indices, filter_indices_per_value, FilterSpec};
+
+const VALUES: usize = 16 * 1024 * 1024;
+const ROUNDS: u32 = 3;
+
+/// xorshift, so runs are reproducible without a rand dependency
+fn values(seed: u64, modulo: u32) -> Vec<u8> {
+    let mut state = seed;
+    let mut out = Vec::with_capacity(VALUES * 4);
+    for _ in 0..VALUES {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        out.extend_from_slice(&((state as u32) % modulo).to_le_bytes());
+    }
+    out
+}
+
+fn best_of(mut run: impl FnMut() -> usize) -> (Duration, usize) {
+    let mut best = Duration::MAX;
+    let mut kept = 0;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        kept = std::hint::black_box(run());
+        best = best.min(start.elapsed());
+    }
+    (best, kept)
+}
+
+fn bench(name: &str, spec: FilterSpec, new_values: &[u8], old_values: &[u8], data_size: usize) {
+    let (per_value, expected) = best_of(|| filter_indices_per_value(&spec, new_values, old_values, data_size).len());
+    let (kernel, kept) = best_of(|| filter_indices(&spec, new_values, old_values, data_size).len());
+    assert_eq!(kept, expected, "{}: kernel and per-value results differ", name);
+    println!(
+        "{:<24} per-value {:>9.2?}  kernel {:>9.2?}  speedup {:>5.1}x  ({} kept)",
+        name,
+        per_value,
+        kernel,
+        per_value.as_secs_f64() / kernel.as_secs_f64(),
+        kept,
+    );
+}
+
+fn main() {
+    let new_values = values(0x9E37_79B9_7F4A_7C15, 1000);
+    let old_values = values(0xD1B5_4A32_D192_ED03, 1000);
+    let floats: Vec<u8> = new_values.chunks_exact(4)
+        .flat_map(|v| (u32::from_le_bytes(v.try_into().unwrap()) as f32 / 10.0).to_le_bytes())
+        .collect();
+    let pattern = 500i32.to_le_bytes();
+    let pattern_max = 600i32.to_le_bytes();
+    let float_pattern = 50.0f32.to_le_bytes();
+
+    println!("{} values per filter, best of {}", VALUES, ROUNDS);
+    let spec = |data_type, filter_method, pattern, pattern_max| FilterSpec {
+        data_type,
+        filter_method,
+        pattern,
+        pattern_max,
+        fuzzy: None,
+    };
+    bench("int32 exact", spec("int32", "exact", &pattern, None), &new_values, &old_values, 4);
+    bench("int32 range", spec("int32", "range", &pattern, Some(&pattern_max)), &new_values, &old_values, 4);
+    bench("int32 increased", spec("int32", "increased", &[], None), &new_values, &old_values, 4);
+    bench("int32 unchanged", spec("int32", "unchanged", &[], None), &new_values, &old_values, 4);
+    bench("float greater_or_equal", spec("float", "greater_or_equal", &float_pattern, None), &floats, &old_values, 4);
+}
@@ -0,0 +1,7 @@
+// Headless command-line front end; shares the backend code with the GUI
+// through dynadbg-core and does not link Tauri
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::exit(dynadbg_core::headless::run(args))
+}
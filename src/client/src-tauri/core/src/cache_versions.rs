@@ -0,0 +1,31 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+/// What cached entries of a module were derived from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheVersion {
+    pub module_hash: Option<String>,    // Build-id / UUID / PDB signature when identified
+    pub analyzed_at: Option<i64>,       // Unix seconds of the last Ghidra analysis
+    pub patch_counter: i64,             // Patches applied or reverted since tracking began
+    pub stamp: String,                  // Stored with every cache row written under this version
+}
+
+pub fn version(conn: &Connection, target_os: &str, module_name: &str) -> CacheVersion {
+    let module_hash: Option<String> = conn.query_row(
+        "SELECT build_id FROM module_build_ids WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+        |row| row.get(0),
+    ).ok();
+    let (analyzed_at, patch_counter) = conn.query_row(
+        "SELECT analyzed_at, patch_counter FROM module_cache_versions WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+        |row| Ok((row.get::<_, Option<i64>>(0)?, row.get::<_, i64>(1)?)),
+    ).unwrap_or((None, 0));
+    let stamp = format!("{}:{}:{}", module_hash.as_deref().unwrap_or(""), analyzed_at.unwrap_or(0), patch_counter);
+    CacheVersion { module_hash, analyzed_at, patch_counter, stamp }
+}
+
+/// Version stamp to store with (and compare against) a module's cache rows
+pub fn stamp(conn: &Connection, target_os: &str, module_name: &str) -> String {
+    version(conn, target_os, module_name).stamp
+}
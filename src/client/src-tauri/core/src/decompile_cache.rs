@@ -0,0 +1,70 @@
+use rusqlite::{params, Connection};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::{cache_versions, secure_store};
+
+/// Stored row of a cached decompilation; code and line mapping may be sealed
+pub struct CachedRow {
+    pub function_name: String,
+    pub decompiled_code: String,
+    pub line_mapping_json: Option<String>,
+}
+
+/// Directory holding the Ghidra projects and ghidra_cache.db
+pub fn projects_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("DynaDbg")
+        .join("ghidra_projects")
+}
+
+/// Open the cache database the app maintains, without migrating it, and load
+/// its encryption key
+pub fn open() -> Result<Connection, String> {
+    let path = projects_dir().join("ghidra_cache.db");
+    if !path.exists() {
+        return Err(format!("No decompile cache at {}", path.display()));
+    }
+    let conn = Connection::open(&path).map_err(|e| e.to_string())?;
+    conn.busy_timeout(Duration::from_secs(5)).map_err(|e| e.to_string())?;
+    secure_store::init(&conn)?;
+    Ok(conn)
+}
+
+/// Cached row for a function, None on a miss or when the module's cache
+/// version has moved on since it was written
+pub fn cached_row(conn: &Connection, target_os: &str, module_name: &str, function_address: &str) -> Option<CachedRow> {
+    conn.query_row(
+        "SELECT function_name, decompiled_code, line_mapping_json FROM ghidra_decompile_cache
+         WHERE target_os = ?1 AND module_name = ?2 AND function_address = ?3
+         AND (cache_version IS NULL OR cache_version = ?4)",
+        params![target_os, module_name, function_address, cache_versions::stamp(conn, target_os, module_name)],
+        |row| Ok(CachedRow { function_name: row.get(0)?, decompiled_code: row.get(1)?, line_mapping_json: row.get(2)? }),
+    ).ok()
+}
+
+/// (address, name) of the module's cached functions, limited to those whose
+/// decompiled code contains `search` when given
+pub fn search(conn: &Connection, target_os: &str, module_name: &str, search: Option<&str>) -> Result<Vec<(String, String)>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT function_address, function_name, decompiled_code FROM ghidra_decompile_cache
+         WHERE target_os = ?1 AND module_name = ?2 ORDER BY function_address",
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![target_os, module_name], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+    }).map_err(|e| e.to_string())?;
+
+    let mut matches = Vec::new();
+    for row in rows {
+        let (address, name, code) = row.map_err(|e| e.to_string())?;
+        if let Some(search) = search {
+            let code = secure_store::open_value("ghidra_decompile_cache", "decompiled_code", code)?;
+            if !code.contains(search) {
+                continue;
+            }
+        }
+        matches.push((address, name));
+    }
+    Ok(matches)
+}
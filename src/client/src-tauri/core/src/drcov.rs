@@ -0,0 +1,31 @@
+use std::io::Write;
+
+/// Module table entry of a DrCov file
+pub struct DrcovModule {
+    pub base: u64,
+    pub end: u64,
+    pub path: String,
+}
+
+/// Write a DrCov v2 log (the format Lighthouse and bncov load). Blocks are
+/// (module index, module offset, size).
+pub fn write_drcov<W: Write>(out: &mut W, modules: &[DrcovModule], blocks: &[(u16, u32, u16)]) -> std::io::Result<()> {
+    writeln!(out, "DRCOV VERSION: 2")?;
+    writeln!(out, "DRCOV FLAVOR: drcov")?;
+    writeln!(out, "Module Table: version 2, count {}", modules.len())?;
+    writeln!(out, "Columns: id, base, end, entry, checksum, timestamp, path")?;
+    for (id, module) in modules.iter().enumerate() {
+        writeln!(
+            out,
+            "{:3}, 0x{:016x}, 0x{:016x}, 0x{:016x}, 0x{:08x}, 0x{:08x}, {}",
+            id, module.base, module.end, 0, 0, 0, module.path
+        )?;
+    }
+    writeln!(out, "BB Table: {} bbs", blocks.len())?;
+    for &(module_id, offset, size) in blocks {
+        out.write_all(&offset.to_le_bytes())?;
+        out.write_all(&size.to_le_bytes())?;
+        out.write_all(&module_id.to_le_bytes())?;
+    }
+    Ok(())
+}
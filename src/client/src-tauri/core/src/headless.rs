@@ -0,0 +1,315 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+use crate::drcov::{write_drcov, DrcovModule};
+use crate::scan_store::{clear_scan, read_scan_hits, run_aob_scan, run_exact_scan, AobScanRequest, ExactScanRequest};
+use crate::server_connection::SERVER_CONFIG;
+use crate::{decompile_cache, secure_store, value_codec};
+
+const USAGE: &str = "Usage: dynadbg-cli <command> [options]
+
+Commands:
+  scan                   First scan of the target through a dbgsrv
+      --host <host>          Server host (or DYNADBG_HOST)
+      --port <port>          Server port (default 3030, or DYNADBG_PORT)
+      --token <token>        Auth token (or DYNADBG_TOKEN)
+      --type <type> --value <value>
+                             Exact value scan (int8..uint64, float, double)
+      --aob <pattern>        Array-of-bytes scan (\"48 8B ?? 05\")
+      --ranges <s-e,...>     Hex address ranges (default: writable regions)
+      --alignment <n>
+      --limit <n>            Results to print (default 100)
+      --json                 Print the results as JSON
+      --keep                 Keep the scan files and print their directory
+
+  decompile-cache-query  Query the Ghidra decompile cache
+      --target-os <os> --module <name>
+      --function <address>   Print the cached decompilation of one function
+      --search <text>        List functions whose code contains <text>
+
+  export-coverage        Unique instruction addresses of a .dyntrace file
+      --trace <file>
+      --output <file>        Default: stdout
+      --format <text|csv|drcov>
+                             csv adds hit counts; drcov writes a Lighthouse
+                             coverage file (needs --base, --output)
+      --base <address>       Write offsets relative to this address
+      --module <path>        drcov: module path (default: module)
+      --size <bytes>         drcov: module size (default: up to the last hit)
+";
+
+// Header and entry layout of the .dyntrace format (see traceFileParser.ts)
+const TRACE_MAGIC: &[u8] = b"DYNATRC";
+const TRACE_HEADER_SIZE: usize = 32;
+const TRACE_ENTRY_SIZE: usize = 1920;
+const TRACE_PC_OFFSET: usize = 8;
+
+type Options = HashMap<String, String>;
+
+/// `--key value` pairs; a key followed by another key (or nothing) is a flag
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::new();
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        let key = arg.strip_prefix("--").ok_or_else(|| format!("Unexpected argument '{}'", arg))?;
+        let value = match iter.peek() {
+            Some(next) if !next.starts_with("--") => iter.next().cloned().unwrap_or_default(),
+            _ => "true".to_string(),
+        };
+        options.insert(key.to_string(), value);
+    }
+    Ok(options)
+}
+
+fn required<'a>(options: &'a Options, key: &str) -> Result<&'a str, String> {
+    options.get(key).map(String::as_str).ok_or_else(|| format!("Missing --{}", key))
+}
+
+fn parse_u64(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => text.parse(),
+    }
+    .map_err(|e| format!("Invalid number '{}': {}", text, e))
+}
+
+/// Hex ranges "0x1000-0x2000,0x5000-0x6000"
+fn parse_ranges(text: &str) -> Result<Vec<(u64, u64)>, String> {
+    text.split(',')
+        .filter(|r| !r.trim().is_empty())
+        .map(|r| {
+            let (start, end) = r.split_once('-').ok_or_else(|| format!("Invalid range '{}'", r))?;
+            let (start, end) = (parse_u64(start)?, parse_u64(end)?);
+            if end <= start {
+                return Err(format!("Empty range '{}'", r));
+            }
+            Ok((start, end))
+        })
+        .collect()
+}
+
+fn configure_server(options: &Options) -> Result<(), String> {
+    let host = options.get("host").cloned()
+        .or_else(|| std::env::var("DYNADBG_HOST").ok())
+        .ok_or("Missing --host")?;
+    let port = match options.get("port").cloned().or_else(|| std::env::var("DYNADBG_PORT").ok()) {
+        Some(port) => port.parse::<u16>().map_err(|e| format!("Invalid port: {}", e))?,
+        None => 3030,
+    };
+    let token = options.get("token").cloned().or_else(|| std::env::var("DYNADBG_TOKEN").ok());
+
+    let mut config = SERVER_CONFIG.write().map_err(|e| e.to_string())?;
+    config.host = host;
+    config.port = port;
+    config.auth_token = token;
+    Ok(())
+}
+
+async fn cmd_scan(options: &Options) -> Result<(), String> {
+    configure_server(options)?;
+    let address_ranges = match options.get("ranges") {
+        Some(ranges) => parse_ranges(ranges)?,
+        None => Vec::new(),
+    };
+    let alignment = options.get("alignment").map(|a| parse_u64(a)).transpose()?.unwrap_or(0) as usize;
+    let limit = options.get("limit").map(|l| parse_u64(l)).transpose()?.unwrap_or(100) as usize;
+
+    let response = if let Some(pattern) = options.get("aob") {
+        run_aob_scan(None, AobScanRequest {
+            pattern: pattern.clone(),
+            address_ranges,
+            alignment,
+            scan_id: None,
+        }).await?
+    } else {
+        let data_type = options.get("type").map(String::as_str).unwrap_or("int32");
+        let value = required(options, "value")?;
+        run_exact_scan(None, ExactScanRequest {
+            pattern: hex::encode(value_codec::encode(data_type, value)?),
+            data_type: data_type.to_string(),
+            address_ranges,
+            alignment,
+            scan_id: None,
+        }).await?
+    };
+    if !response.success {
+        return Err(response.error.unwrap_or_else(|| "Scan failed".to_string()));
+    }
+
+    let (hits, _) = read_scan_hits(&response.scan_id, 0, limit)?;
+    if options.contains_key("json") {
+        let output = serde_json::json!({
+            "scan_id": response.scan_id,
+            "total_addresses": response.total_addresses,
+            "results": hits,
+        });
+        println!("{}", serde_json::to_string_pretty(&output).map_err(|e| e.to_string())?);
+    } else {
+        eprintln!("{} addresses found", response.total_addresses);
+        for hit in &hits {
+            println!("0x{:x}\t{}", hit.address, hex::encode(&hit.value));
+        }
+    }
+
+    if options.contains_key("keep") {
+        eprintln!("Scan files kept in {}", response.temp_dir);
+    } else {
+        clear_scan(&response.scan_id)?;
+    }
+    Ok(())
+}
+
+async fn cmd_decompile_cache_query(options: &Options) -> Result<(), String> {
+    let target_os = required(options, "target-os")?.to_string();
+    let module_name = required(options, "module")?.to_string();
+    let function = options.get("function").cloned();
+    let search = options.get("search").cloned();
+
+    tokio::task::spawn_blocking(move || {
+        let conn = decompile_cache::open()?;
+        if let Some(function) = function {
+            let row = decompile_cache::cached_row(&conn, &target_os, &module_name, &function)
+                .ok_or_else(|| format!("No cached decompilation for {}", function))?;
+            let code = secure_store::open_value("ghidra_decompile_cache", "decompiled_code", row.decompiled_code)?;
+            if !row.function_name.is_empty() {
+                eprintln!("// {}", row.function_name);
+            }
+            println!("{}", code);
+            return Ok(());
+        }
+
+        for (address, name) in decompile_cache::search(&conn, &target_os, &module_name, search.as_deref())? {
+            println!("{}\t{}", address, name);
+        }
+        Ok(())
+    }).await.map_err(|e| e.to_string())?
+}
+
+/// Hit count per instruction address of a .dyntrace file
+fn trace_coverage(data: &[u8]) -> Result<BTreeMap<u64, u64>, String> {
+    if data.len() < TRACE_HEADER_SIZE || !data.starts_with(TRACE_MAGIC) {
+        return Err("Not a DynaDbg trace file".to_string());
+    }
+    let entry_count = u32::from_le_bytes(data[12..16].try_into().unwrap()) as usize;
+    let available = (data.len() - TRACE_HEADER_SIZE) / TRACE_ENTRY_SIZE;
+    if available < entry_count {
+        eprintln!("Trace file is truncated: {} of {} entries present", available, entry_count);
+    }
+
+    let mut hits = BTreeMap::new();
+    for entry in data[TRACE_HEADER_SIZE..].chunks_exact(TRACE_ENTRY_SIZE).take(entry_count) {
+        let pc = u64::from_le_bytes(entry[TRACE_PC_OFFSET..TRACE_PC_OFFSET + 8].try_into().unwrap());
+        *hits.entry(pc).or_insert(0u64) += 1;
+    }
+    Ok(hits)
+}
+
+/// Trace coverage as a DrCov file; each hit address is a one-instruction block
+fn write_drcov_coverage(options: &Options, hits: &BTreeMap<u64, u64>, base: Option<u64>) -> Result<(), String> {
+    let base = base.ok_or("drcov output needs --base")?;
+    let output = required(options, "output")?;
+    let offsets: Vec<u64> = hits.keys().filter(|&&a| a >= base).map(|a| a - base).collect();
+    let size = match options.get("size") {
+        Some(size) => parse_u64(size)?,
+        None => offsets.last().map_or(0, |last| last + 4),
+    };
+    let blocks: Vec<(u16, u32, u16)> = offsets.iter()
+        .filter(|&&offset| offset < size && offset <= u32::MAX as u64)
+        .map(|&offset| (0, offset as u32, 4))
+        .collect();
+    let module = DrcovModule {
+        base,
+        end: base + size,
+        path: options.get("module").cloned().unwrap_or_else(|| "module".to_string()),
+    };
+    let file = std::fs::File::create(output).map_err(|e| format!("Failed to create {}: {}", output, e))?;
+    let mut out = std::io::BufWriter::new(file);
+    write_drcov(&mut out, &[module], &blocks)
+        .and_then(|_| out.flush())
+        .map_err(|e| format!("Failed to write {}: {}", output, e))?;
+    eprintln!("{} blocks written to {}", blocks.len(), output);
+    Ok(())
+}
+
+fn cmd_export_coverage(options: &Options) -> Result<(), String> {
+    let path = required(options, "trace")?;
+    let data = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let hits = trace_coverage(&data)?;
+    let base = options.get("base").map(|b| parse_u64(b)).transpose()?;
+    let csv = match options.get("format").map(String::as_str) {
+        None | Some("text") => false,
+        Some("csv") => true,
+        Some("drcov") => return write_drcov_coverage(options, &hits, base),
+        Some(other) => return Err(format!("Unknown format '{}'", other)),
+    };
+
+    let mut out = String::new();
+    if csv {
+        out.push_str(if base.is_some() { "offset,hits\n" } else { "address,hits\n" });
+    }
+    for (address, count) in &hits {
+        let address = match base {
+            Some(base) if *address < base => continue,
+            Some(base) => address - base,
+            None => *address,
+        };
+        if csv {
+            out.push_str(&format!("0x{:x},{}\n", address, count));
+        } else {
+            out.push_str(&format!("0x{:x}\n", address));
+        }
+    }
+
+    match options.get("output") {
+        Some(output) => {
+            std::fs::write(output, out).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+            eprintln!("{} unique addresses written to {}", hits.len(), output);
+        }
+        None => std::io::stdout().write_all(out.as_bytes()).map_err(|e| e.to_string())?,
+    }
+    Ok(())
+}
+
+/// Run one CLI command; returns the process exit code
+pub fn run(args: Vec<String>) -> i32 {
+    let Some((command, rest)) = args.split_first() else {
+        eprint!("{}", USAGE);
+        return 2;
+    };
+    if matches!(command.as_str(), "help" | "--help" | "-h") {
+        print!("{}", USAGE);
+        return 0;
+    }
+    let options = match parse_options(rest) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("error: Failed to start runtime: {}", e);
+            return 1;
+        }
+    };
+    let result = runtime.block_on(async {
+        match command.as_str() {
+            "scan" => cmd_scan(&options).await,
+            "decompile-cache-query" => cmd_decompile_cache_query(&options).await,
+            "export-coverage" => cmd_export_coverage(&options),
+            other => Err(format!("Unknown command '{}'\n\n{}", other, USAGE)),
+        }
+    });
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
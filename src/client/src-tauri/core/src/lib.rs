@@ -0,0 +1,13 @@
+// Backend pieces that do not need a window: server access, the native scan
+// store, value encoding and the Ghidra cache reader. The Tauri app wraps them
+// in commands; the dynadbg-cli binary uses them directly.
+
+pub mod cache_versions;
+pub mod decompile_cache;
+pub mod drcov;
+pub mod headless;
+pub mod memory_reader;
+pub mod scan_store;
+pub mod secure_store;
+pub mod server_connection;
+pub mod value_codec;
@@ -0,0 +1,146 @@
+use bytes::Bytes;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::server_connection::{self, SERVER_CONFIG};
+
+/// Memory region as reported by /api/memory/regions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteMemoryRegion {
+    pub start: u64,
+    pub end: u64,
+    pub protection: String,
+    pub file_path: Option<String>,
+}
+
+/// Offline memory source (a loaded memory dump) consulted before the server;
+/// each returns None when no dump is loaded
+pub struct LocalMemory {
+    pub read: fn(u64, usize) -> Option<Vec<u8>>,
+    pub regions: fn() -> Option<Vec<RemoteMemoryRegion>>,
+}
+
+static LOCAL_MEMORY: OnceCell<LocalMemory> = OnceCell::new();
+
+/// Register the offline memory source; only the first registration is kept
+pub fn set_local_memory(source: LocalMemory) {
+    let _ = LOCAL_MEMORY.set(source);
+}
+
+// Memory read protocol of the connected server: binary GET, or the hex JSON
+// body of older servers. Probed on the first read after connecting.
+const READ_PROTOCOL_UNKNOWN: u8 = 0;
+const READ_PROTOCOL_BINARY: u8 = 1;
+const READ_PROTOCOL_JSON: u8 = 2;
+static READ_PROTOCOL: AtomicU8 = AtomicU8::new(READ_PROTOCOL_UNKNOWN);
+
+/// Probe the read protocol again on the next read (the server changed)
+pub fn reset_read_protocol() {
+    READ_PROTOCOL.store(READ_PROTOCOL_UNKNOWN, Ordering::Relaxed);
+}
+
+/// Read target memory as raw bytes (empty when the server could not read it)
+pub async fn read_memory_bytes(host: &str, port: u16, address: u64, size: usize) -> Result<Bytes, String> {
+    if let Some(data) = LOCAL_MEMORY.get().and_then(|local| (local.read)(address, size)) {
+        return Ok(Bytes::from(data));
+    }
+    let client = server_connection::client()?;
+    let base = server_connection::base_url(host, port);
+
+    if READ_PROTOCOL.load(Ordering::Relaxed) != READ_PROTOCOL_JSON {
+        let url = format!("{}/api/memory/read?address={}&size={}", base, address, size);
+        let response = server_connection::send(client.get(&url)).await?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::METHOD_NOT_ALLOWED {
+            READ_PROTOCOL.store(READ_PROTOCOL_JSON, Ordering::Relaxed);
+        } else if !status.is_success() {
+            return Err(format!("Server error: {}", status));
+        } else {
+            READ_PROTOCOL.store(READ_PROTOCOL_BINARY, Ordering::Relaxed);
+            return response.bytes().await.map_err(|e| format!("Failed to read response: {}", e));
+        }
+    }
+
+    let url = format!("{}/api/memory/read", base);
+    let request = client.post(&url).json(&serde_json::json!({ "address": address, "size": size }));
+    let response = server_connection::send(request).await?;
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    let data = json.get("data").and_then(|v| v.as_str())
+        .ok_or("Invalid response format - no data field")?;
+    let hex_clean: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    hex::decode(hex_clean).map(Bytes::from).map_err(|e| format!("Invalid hex data: {}", e))
+}
+
+/// Read (address, size) chunks concurrently, each bounded by `timeout`.
+/// Results follow the input order; None for failed or timed-out reads.
+pub async fn read_chunks_parallel(host: &str, port: u16, chunks: &[(u64, usize)], timeout: std::time::Duration) -> Vec<Option<Bytes>> {
+    let tasks: Vec<_> = chunks.iter()
+        .map(|&(address, size)| {
+            let host = host.to_string();
+            tokio::spawn(async move {
+                tokio::time::timeout(timeout, read_memory_bytes(&host, port, address, size)).await.ok()?.ok()
+            })
+        })
+        .collect();
+    let mut results = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        results.push(task.await.ok().flatten());
+    }
+    results
+}
+
+pub async fn fetch_memory_regions_from_server(host: &str, port: u16) -> Result<Vec<RemoteMemoryRegion>, String> {
+    if let Some(regions) = LOCAL_MEMORY.get().and_then(|local| (local.regions)()) {
+        return Ok(regions);
+    }
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/memory/regions?include_file_path=true", server_connection::base_url(host, port));
+
+    let mut request_builder = client.get(&url);
+    if let Some(token) = auth_token {
+        request_builder = request_builder.header("Authorization", format!("Bearer {}", token));
+    }
+    let response = server_connection::send(request_builder).await?;
+
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse regions: {}", e))?;
+    let regions = json["regions"].as_array().cloned().unwrap_or_default();
+
+    Ok(regions.iter().filter_map(|r| {
+        Some(RemoteMemoryRegion {
+            start: u64::from_str_radix(r["start_address"].as_str()?, 16).ok()?,
+            end: u64::from_str_radix(r["end_address"].as_str()?, 16).ok()?,
+            protection: r["protection"].as_str().unwrap_or("").to_string(),
+            file_path: r["file_path"].as_str().filter(|p| !p.is_empty()).map(|p| p.to_string()),
+        })
+    }).collect())
+}
+
+/// Scan ranges to use when the caller did not supply any: all readable and
+/// writable regions (the usual value-scan target) of the current memory map
+pub async fn resolve_scan_ranges(host: &str, port: u16, address_ranges: &[(u64, u64)]) -> Result<Vec<(u64, u64)>, String> {
+    if !address_ranges.is_empty() {
+        return Ok(address_ranges.to_vec());
+    }
+    let mut ranges: Vec<(u64, u64)> = fetch_memory_regions_from_server(host, port).await?
+        .into_iter()
+        .filter(|r| r.end > r.start)
+        .filter(|r| {
+            let protection = r.protection.to_lowercase();
+            protection.contains('r') && protection.contains('w')
+        })
+        .map(|r| (r.start, r.end))
+        .collect();
+    ranges.sort_unstable();
+    Ok(ranges)
+}
@@ -0,0 +1,743 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use bytes::Bytes;
+
+use crate::memory_reader::{read_chunks_parallel, resolve_scan_ranges};
+use crate::server_connection::SERVER_CONFIG;
+use crate::value_codec;
+
+/// Unknown scan progress structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnknownScanProgress {
+    pub scan_id: String,
+    pub progress_percentage: f64,
+    pub processed_bytes: u64,
+    pub total_bytes: u64,
+    pub found_count: u64,
+    pub is_scanning: bool,
+    pub current_region: Option<String>,
+    #[serde(default)]
+    pub is_cancelled: bool,               // Stopped by cancel_unknown_scan; partial results were kept
+}
+
+/// Unknown scan response - returns scan metadata (results stored in temp files)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnknownScanResponse {
+    pub success: bool,
+    pub scan_id: String,
+    pub total_addresses: usize,
+    pub temp_dir: String,
+    #[serde(default)]
+    pub generation: u32,                  // 0 = first scan, incremented by each native next-scan
+    pub error: Option<String>,
+}
+
+// Global storage for unknown scan progress
+pub static UNKNOWN_SCAN_PROGRESS: Lazy<RwLock<HashMap<String, UnknownScanProgress>>> = Lazy::new(|| {
+    RwLock::new(HashMap::new())
+});
+
+// Cancellation flags for running native scans, keyed by scan ID
+pub static UNKNOWN_SCAN_CANCEL: Lazy<RwLock<HashMap<String, std::sync::Arc<std::sync::atomic::AtomicBool>>>> = Lazy::new(|| {
+    RwLock::new(HashMap::new())
+});
+
+/// Get (or create) the cancellation flag for a scan. A flag created by
+/// cancel_unknown_scan before the scan started is reused, so the scan stops at once.
+pub fn get_scan_cancel_flag(scan_id: &str) -> std::sync::Arc<std::sync::atomic::AtomicBool> {
+    let mut flags = UNKNOWN_SCAN_CANCEL.write().unwrap();
+    flags.entry(scan_id.to_string())
+        .or_insert_with(|| std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)))
+        .clone()
+}
+
+/// Receives every progress update of a native scan (the app forwards them as
+/// events; the CLI passes none)
+pub type ProgressSink = std::sync::Arc<dyn Fn(&UnknownScanProgress) + Send + Sync>;
+
+fn emit_progress(progress: Option<&ProgressSink>, scan_id: &str) {
+    let Some(progress) = progress else {
+        return;
+    };
+    if let Some(current) = UNKNOWN_SCAN_PROGRESS.read().ok().and_then(|m| m.get(scan_id).cloned()) {
+        progress(&current);
+    }
+}
+
+/// Get data size for a given data type
+pub fn get_data_size(data_type: &str) -> usize {
+    match data_type {
+        "int8" | "uint8" => 1,
+        "int16" | "uint16" => 2,
+        "int32" | "uint32" | "float" => 4,
+        "int64" | "uint64" | "double" => 8,
+        _ => value_codec::data_size(data_type).unwrap_or(1),
+    }
+}
+
+/// Scan ID for scans started without one: the time plus a per-process counter,
+/// so two scans started in the same millisecond still get separate directories
+pub fn new_scan_id(prefix: &str) -> String {
+    static NEXT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    format!("{}_{}_{}", prefix, millis, NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
+}
+
+/// Scan IDs name directories and files under the scan temp dir; callers pass
+/// them in, so only plain names are accepted
+pub fn validate_scan_id(scan_id: &str) -> Result<(), String> {
+    if scan_id.is_empty() || !scan_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(format!("Invalid scan ID: {}", scan_id));
+    }
+    Ok(())
+}
+
+/// Get temp directory for unknown scan data
+pub fn get_unknown_scan_temp_dir(scan_id: &str) -> PathBuf {
+    let temp_dir = std::env::temp_dir();
+    temp_dir.join("dynadbg_unknown_scan").join(scan_id)
+}
+
+/// Write a region file: header (data_size u32, alignment u32, start_addr u64),
+/// then count u64 followed by lz4-compressed addresses and values
+pub fn write_scan_region_file(
+    path: &std::path::Path,
+    data_size: usize,
+    alignment: usize,
+    start_addr: u64,
+    addresses: &[u64],
+    values: &[u8],
+) -> std::io::Result<()> {
+    use std::io::Write;
+    let file = std::fs::File::create(path)?;
+    let mut writer = std::io::BufWriter::with_capacity(1024 * 1024, file);
+
+    writer.write_all(&(data_size as u32).to_le_bytes())?;
+    writer.write_all(&(alignment as u32).to_le_bytes())?;
+    writer.write_all(&start_addr.to_le_bytes())?;
+
+    if !addresses.is_empty() {
+        writer.write_all(&(addresses.len() as u64).to_le_bytes())?;
+
+        let addr_bytes: Vec<u8> = addresses.iter().flat_map(|a| a.to_le_bytes()).collect();
+        let compressed_addrs = lz4_flex::compress_prepend_size(&addr_bytes);
+        writer.write_all(&(compressed_addrs.len() as u64).to_le_bytes())?;
+        writer.write_all(&compressed_addrs)?;
+
+        let compressed_data = lz4_flex::compress_prepend_size(values);
+        writer.write_all(&(compressed_data.len() as u64).to_le_bytes())?;
+        writer.write_all(&compressed_data)?;
+    }
+
+    writer.flush()
+}
+
+/// Predicate applied to each aligned value during a native scan
+pub type ScanMatcher = std::sync::Arc<dyn Fn(&[u8]) -> bool + Send + Sync>;
+
+/// Shared engine for native first scans: reads the given ranges with parallel
+/// chunked reads, keeps every aligned value accepted by `matcher` (all values
+/// when None) and writes one lz4-compressed region file per sub-region.
+/// With `partial_tail`, positions closer than `data_size` to the end of a
+/// region are offered to `matcher` as shorter values (stored zero-padded), for
+/// variable-length matches such as strings. Returns the number of stored addresses.
+#[allow(clippy::too_many_arguments)]
+async fn scan_ranges_to_temp_files(
+    progress: Option<ProgressSink>,
+    host: String,
+    port: u16,
+    scan_id: &str,
+    address_ranges: &[(u64, u64)],
+    data_size: usize,
+    alignment: usize,
+    matcher: Option<ScanMatcher>,
+    partial_tail: bool,
+) -> Result<u64, String> {
+    let scan_id = scan_id.to_string();
+    // No ranges from the caller: scan the writable regions of the memory map
+    let address_ranges = &resolve_scan_ranges(&host, port, address_ranges).await?;
+
+    // Calculate total bytes to scan for progress
+    let total_bytes: u64 = address_ranges.iter()
+        .map(|(start, end)| end - start)
+        .sum();
+
+    // A first scan starts over: generations left from an earlier scan with
+    // this ID would otherwise be picked up by the next filter
+    let temp_dir = get_unknown_scan_temp_dir(&scan_id);
+    if temp_dir.exists() {
+        std::fs::remove_dir_all(&temp_dir)
+            .map_err(|e| format!("Failed to clear previous scan data: {}", e))?;
+    }
+    std::fs::create_dir_all(&temp_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    // Initialize progress
+    {
+        let mut progress_map = UNKNOWN_SCAN_PROGRESS.write().unwrap();
+        progress_map.insert(scan_id.clone(), UnknownScanProgress {
+            scan_id: scan_id.clone(),
+            progress_percentage: 0.0,
+            processed_bytes: 0,
+            total_bytes,
+            found_count: 0,
+            is_scanning: true,
+            current_region: Some("Starting scan...".to_string()),
+            is_cancelled: false,
+        });
+    }
+    emit_progress(progress.as_ref(), &scan_id);
+
+    // Maximum chunk size for reading (4MB per read for efficiency)
+    const MAX_READ_CHUNK: usize = 4 * 1024 * 1024;
+    // Maximum sub-region size (64MB) - split large regions to avoid memory issues
+    const MAX_SUB_REGION: u64 = 64 * 1024 * 1024;
+    // Number of parallel reads
+    const PARALLEL_READS: usize = 8;
+
+    let total_found = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let processed_bytes = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let cancelled = get_scan_cancel_flag(&scan_id);
+
+    // Split large regions into smaller sub-regions (max 64MB each).
+    // The third element is the end of the original region, so reads may
+    // overlap into the next sub-region for values straddling the boundary.
+    let mut sub_regions: Vec<(u64, u64, u64)> = Vec::new();
+    for (range_start, range_end) in address_ranges {
+        let mut current = *range_start;
+        while current < *range_end {
+            let sub_end = (current + MAX_SUB_REGION).min(*range_end);
+            sub_regions.push((current, sub_end, *range_end));
+            current = sub_end;
+        }
+    }
+
+    // Process sub-regions in parallel (up to 4 at a time)
+    for sub_region_batch in sub_regions.chunks(4) {
+        if cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+            break;
+        }
+        let mut region_tasks = Vec::new();
+
+        for &(range_start, range_end, region_limit) in sub_region_batch {
+            let host = host.clone();
+            let scan_id = scan_id.clone();
+            let temp_dir = temp_dir.clone();
+            let total_found = total_found.clone();
+            let processed_bytes = processed_bytes.clone();
+            let matcher = matcher.clone();
+            let cancelled = cancelled.clone();
+            let progress = progress.clone();
+
+            let task = tokio::spawn(async move {
+                let mut current_addr = range_start;
+
+                // Align start address
+                if current_addr % alignment as u64 != 0 {
+                    current_addr = (current_addr / alignment as u64 + 1) * alignment as u64;
+                }
+
+                let mut all_addresses: Vec<u64> = Vec::new();
+                let mut all_data: Vec<u8> = Vec::new();
+
+                // Split sub-region into chunks for parallel reading
+                let mut chunks_to_read: Vec<(u64, usize)> = Vec::new();
+
+                let mut chunk_start = current_addr;
+                while chunk_start < range_end {
+                    let remaining = (range_end - chunk_start) as usize;
+                    let chunk_size = remaining.min(MAX_READ_CHUNK);
+                    chunks_to_read.push((chunk_start, chunk_size));
+                    chunk_start += chunk_size as u64;
+                }
+
+                // Process chunks in parallel batches
+                for chunk_batch in chunks_to_read.chunks(PARALLEL_READS) {
+                    // On cancel, stop reading but still write what was found so far
+                    if cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+                        break;
+                    }
+                    // Read data_size - 1 extra bytes so values crossing the chunk end are not lost
+                    let reads: Vec<(u64, usize)> = chunk_batch.iter()
+                        .map(|&(addr, size)| (addr, (size as u64 + data_size as u64 - 1).min(region_limit - addr) as usize))
+                        .collect();
+                    // Timeout prevents hanging on unresponsive regions
+                    let data = read_chunks_parallel(&host, port, &reads, std::time::Duration::from_secs(2)).await;
+                    let mut results: Vec<(u64, Option<Bytes>, usize)> = chunk_batch.iter()
+                        .zip(data)
+                        .map(|(&(addr, size), data)| (addr, data, size))
+                        .collect();
+
+                    // Sort by address to maintain order
+                    results.sort_by_key(|(addr, _, _)| *addr);
+
+                    for (addr, data_opt, chunk_size) in results {
+                        // Unreadable chunks only count towards progress
+                        if let Some(chunk_data) = data_opt {
+                            // Extract values at aligned positions; only a read that
+                            // reached the region end may yield shorter tail values
+                            let at_region_end = addr + chunk_data.len() as u64 >= region_limit;
+                            let mut offset: usize = 0;
+                            while offset < chunk_size && offset < chunk_data.len() {
+                                let value = &chunk_data[offset..(offset + data_size).min(chunk_data.len())];
+                                if value.len() < data_size && !(partial_tail && at_region_end) {
+                                    break;
+                                }
+                                if matcher.as_ref().is_none_or(|m| m(value)) {
+                                    all_addresses.push(addr + offset as u64);
+                                    all_data.extend_from_slice(value);
+                                    all_data.resize(all_data.len() + data_size - value.len(), 0);
+                                }
+                                offset += alignment;
+                            }
+                        }
+
+                        // Update progress after each chunk
+                        processed_bytes.fetch_add(chunk_size as u64, std::sync::atomic::Ordering::Relaxed);
+                        let current_processed = processed_bytes.load(std::sync::atomic::Ordering::Relaxed);
+                        let percentage = if total_bytes > 0 {
+                            (current_processed as f64 / total_bytes as f64) * 100.0
+                        } else {
+                            0.0
+                        };
+
+                        if let Ok(mut progress_map) = UNKNOWN_SCAN_PROGRESS.write() {
+                            if let Some(p) = progress_map.get_mut(&scan_id) {
+                                p.progress_percentage = percentage;
+                                p.processed_bytes = current_processed;
+                                p.found_count = total_found.load(std::sync::atomic::Ordering::Relaxed) + all_addresses.len() as u64;
+                            }
+                        }
+                        emit_progress(progress.as_ref(), &scan_id);
+                    }
+                }
+
+                // Compress and write region data using lz4
+                let region_file_path = temp_dir.join(format!("region_{:016x}_{:016x}.bin", range_start, range_end));
+                if let Err(e) = write_scan_region_file(&region_file_path, data_size, alignment, range_start, &all_addresses, &all_data) {
+                    eprintln!("[Native Scan] Failed to write region file: {}", e);
+                    return 0u64;
+                }
+
+                all_addresses.len() as u64
+            });
+
+            region_tasks.push(task);
+        }
+
+        // Wait for all region tasks in this batch
+        for task in region_tasks {
+            if let Ok(found) = task.await {
+                total_found.fetch_add(found, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+    }
+
+    let final_found = total_found.load(std::sync::atomic::Ordering::Relaxed);
+    let was_cancelled = cancelled.load(std::sync::atomic::Ordering::Relaxed);
+    if let Ok(mut flags) = UNKNOWN_SCAN_CANCEL.write() {
+        flags.remove(&scan_id);
+    }
+
+    // Mark scan as complete (or cancelled, keeping the partial progress)
+    {
+        let mut progress_map = UNKNOWN_SCAN_PROGRESS.write().unwrap();
+        if let Some(p) = progress_map.get_mut(&scan_id) {
+            if !was_cancelled {
+                p.progress_percentage = 100.0;
+                p.processed_bytes = total_bytes;
+            }
+            p.found_count = final_found;
+            p.is_scanning = false;
+            p.is_cancelled = was_cancelled;
+            p.current_region = None;
+        }
+    }
+    emit_progress(progress.as_ref(), &scan_id);
+
+    Ok(final_found)
+}
+
+/// Value layout and predicate of a native first scan
+pub struct NativeScan {
+    pub data_size: usize,                  // Bytes stored per hit
+    pub alignment: usize,
+    pub matcher: Option<ScanMatcher>,      // None keeps every value (unknown initial value)
+    pub partial_tail: bool,                // See scan_ranges_to_temp_files
+}
+
+fn failed_scan(scan_id: String, error: String) -> UnknownScanResponse {
+    UnknownScanResponse {
+        success: false,
+        scan_id,
+        total_addresses: 0,
+        temp_dir: String::new(),
+        generation: 0,
+        error: Some(error),
+    }
+}
+
+/// Run a native first scan against the configured server. Scan failures
+/// (no server, unreadable memory map, ...) are reported in the response.
+pub async fn run_native_scan(
+    progress: Option<ProgressSink>,
+    scan_id: String,
+    address_ranges: &[(u64, u64)],
+    scan: NativeScan,
+) -> Result<UnknownScanResponse, String> {
+    validate_scan_id(&scan_id)?;
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+    if host.is_empty() {
+        return Ok(failed_scan(scan_id, "No server connection configured".to_string()));
+    }
+
+    let temp_dir = get_unknown_scan_temp_dir(&scan_id);
+    let NativeScan { data_size, alignment, matcher, partial_tail } = scan;
+    match scan_ranges_to_temp_files(progress, host, port, &scan_id, address_ranges, data_size, alignment, matcher, partial_tail).await {
+        Ok(found) => Ok(UnknownScanResponse {
+            success: true,
+            scan_id,
+            total_addresses: found as usize,
+            temp_dir: temp_dir.to_string_lossy().to_string(),
+            generation: 0,
+            error: None,
+        }),
+        Err(e) => Ok(failed_scan(scan_id, e)),
+    }
+}
+
+/// Exact-value first scan request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExactScanRequest {
+    pub pattern: String,                   // Hex-encoded little-endian value to search for
+    pub data_type: String,                 // Same data types as UnknownScanRequest
+    #[serde(default)]
+    pub address_ranges: Vec<(u64, u64)>,   // [(start, end), ...]
+    pub alignment: usize,                  // Alignment for scanning (0 = data size)
+    #[serde(default)]
+    pub scan_id: Option<String>,           // Optional caller-chosen ID, generated when omitted
+}
+
+/// Native exact-value first scan - same storage layout as the unknown scan,
+/// so results can be paged like unknown scan results
+pub async fn run_exact_scan(progress: Option<ProgressSink>, request: ExactScanRequest) -> Result<UnknownScanResponse, String> {
+    let scan_id = request.scan_id.clone().unwrap_or_else(|| new_scan_id("exact"));
+
+    let data_size = get_data_size(&request.data_type);
+    let pattern = hex::decode(&request.pattern)
+        .map_err(|e| format!("Invalid hex pattern: {}", e))?;
+    if pattern.len() != data_size {
+        return Err(format!(
+            "Pattern is {} bytes but {} values are {} bytes",
+            pattern.len(), request.data_type, data_size
+        ));
+    }
+
+    let alignment = if request.alignment > 0 { request.alignment } else { data_size };
+    let matcher: ScanMatcher = std::sync::Arc::new(move |value: &[u8]| value == pattern.as_slice());
+
+    run_native_scan(progress, scan_id, &request.address_ranges, NativeScan {
+        data_size,
+        alignment,
+        matcher: Some(matcher),
+        partial_tail: false,
+    }).await
+}
+
+/// AOB (array-of-bytes) scan request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AobScanRequest {
+    pub pattern: String,                   // e.g. "48 8B ?? ?? 05" ("?" / "??" = any byte, "4?" = nibble wildcard)
+    #[serde(default)]
+    pub address_ranges: Vec<(u64, u64)>,   // [(start, end), ...]
+    #[serde(default)]
+    pub alignment: usize,                  // Alignment for match start addresses (0 = 1)
+    #[serde(default)]
+    pub scan_id: Option<String>,           // Optional caller-chosen ID, generated when omitted
+}
+
+/// Parse an AOB pattern into (value, mask) byte pairs
+pub fn parse_aob_pattern(pattern: &str) -> Result<Vec<(u8, u8)>, String> {
+    let tokens: Vec<String> = if pattern.contains(char::is_whitespace) {
+        pattern.split_whitespace().map(|t| t.to_string()).collect()
+    } else {
+        // Compact form "488B????05"
+        let chars: Vec<char> = pattern.chars().collect();
+        if !chars.len().is_multiple_of(2) {
+            return Err("AOB pattern without spaces must have an even number of characters".to_string());
+        }
+        chars.chunks(2).map(|c| c.iter().collect()).collect()
+    };
+
+    if tokens.is_empty() {
+        return Err("AOB pattern is empty".to_string());
+    }
+
+    tokens
+        .iter()
+        .map(|token| {
+            let token = if token == "?" { "??" } else { token.as_str() };
+            let nibbles: Vec<char> = token.chars().collect();
+            if nibbles.len() != 2 {
+                return Err(format!("Invalid AOB token: {}", token));
+            }
+            let mut value = 0u8;
+            let mut mask = 0u8;
+            for (i, c) in nibbles.iter().enumerate() {
+                let shift = if i == 0 { 4 } else { 0 };
+                if *c == '?' {
+                    continue;
+                }
+                let digit = c.to_digit(16).ok_or_else(|| format!("Invalid AOB token: {}", token))? as u8;
+                value |= digit << shift;
+                mask |= 0x0f << shift;
+            }
+            Ok((value, mask))
+        })
+        .collect()
+}
+
+/// Native AOB scan with wildcards - matches are stored like unknown scan
+/// results (value = matched bytes)
+pub async fn run_aob_scan(progress: Option<ProgressSink>, request: AobScanRequest) -> Result<UnknownScanResponse, String> {
+    let scan_id = request.scan_id.clone().unwrap_or_else(|| new_scan_id("aob"));
+
+    let pattern = parse_aob_pattern(&request.pattern)?;
+    if pattern.iter().all(|(_, mask)| *mask == 0) {
+        return Err("AOB pattern must contain at least one non-wildcard byte".to_string());
+    }
+
+    let data_size = pattern.len();
+    let alignment = request.alignment.max(1);
+    let matcher: ScanMatcher = std::sync::Arc::new(move |value: &[u8]| {
+        value.iter().zip(pattern.iter()).all(|(b, (v, m))| b & m == *v)
+    });
+
+    run_native_scan(progress, scan_id, &request.address_ranges, NativeScan {
+        data_size,
+        alignment,
+        matcher: Some(matcher),
+        partial_tail: false,
+    }).await
+}
+
+/// Decoded contents of one region_*.bin scan file
+pub struct ScanRegionData {
+    pub data_size: usize,
+    pub alignment: usize,
+    pub start_addr: u64,
+    pub addresses: Vec<u64>,
+    pub values: Vec<u8>,
+}
+
+/// Decompress an lz4 block with a prepended size, refusing sizes above `max_len`
+/// so a corrupt or crafted file cannot make us allocate gigabytes
+fn decompress_bounded(data: &[u8], max_len: usize) -> Option<Vec<u8>> {
+    let size = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    if size > max_len {
+        return None;
+    }
+    lz4_flex::decompress_size_prepended(data).ok()
+}
+
+/// Read and decompress a region file written by write_scan_region_file
+pub fn read_scan_region_file(path: &std::path::Path) -> Option<ScanRegionData> {
+    parse_scan_region_data(&std::fs::read(path).ok()?)
+}
+
+/// Parse the contents of a region file. Lengths come from the file (imported
+/// .ddscan archives included), so every offset is checked.
+pub fn parse_scan_region_data(file_data: &[u8]) -> Option<ScanRegionData> {
+    let read_u32 = |pos: usize| -> Option<u32> {
+        file_data.get(pos..pos.checked_add(4)?).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let read_u64 = |pos: usize| -> Option<u64> {
+        file_data.get(pos..pos.checked_add(8)?).map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+    };
+
+    let data_size = read_u32(0)? as usize;
+    let alignment = read_u32(4)? as usize;
+    let start_addr = read_u64(8)?;
+
+    // Regions without any hits only carry the header
+    if file_data.len() < 28 {
+        return Some(ScanRegionData { data_size, alignment, start_addr, addresses: Vec::new(), values: Vec::new() });
+    }
+
+    let count = usize::try_from(read_u64(16)?).ok()?;
+    let mut pos = 24;
+    let addr_len = usize::try_from(read_u64(pos)?).ok()?;
+    pos += 8;
+    let addr_bytes = decompress_bounded(file_data.get(pos..pos.checked_add(addr_len)?)?, count.checked_mul(8)?)?;
+    pos += addr_len;
+    let value_len = usize::try_from(read_u64(pos)?).ok()?;
+    pos = pos.checked_add(8)?;
+    let values = decompress_bounded(file_data.get(pos..pos.checked_add(value_len)?)?, count.checked_mul(data_size)?)?;
+
+    let addresses: Vec<u64> = addr_bytes
+        .chunks_exact(8)
+        .take(count)
+        .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        .collect();
+
+    Some(ScanRegionData { data_size, alignment, start_addr, addresses, values })
+}
+
+/// Directory holding the result files of a scan generation.
+/// Generation 0 is the first scan itself, later generations live in gen_N subdirectories.
+pub fn get_scan_generation_dir(scan_id: &str, generation: u32) -> PathBuf {
+    let base = get_unknown_scan_temp_dir(scan_id);
+    if generation == 0 {
+        base
+    } else {
+        base.join(format!("gen_{}", generation))
+    }
+}
+
+/// Highest generation that has been written for a scan
+pub fn get_latest_scan_generation(scan_id: &str) -> u32 {
+    std::fs::read_dir(get_unknown_scan_temp_dir(scan_id))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| e.file_name().to_string_lossy().strip_prefix("gen_").and_then(|n| n.parse::<u32>().ok()))
+                .max()
+                .unwrap_or(0)
+        })
+        .unwrap_or(0)
+}
+
+/// Sorted list of region files in a scan generation directory
+pub fn list_scan_region_files(dir: &std::path::Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "bin"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Address and stored value of one scan hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanHit {
+    pub address: u64,
+    pub value: Vec<u8>,
+}
+
+/// Page of hits of a scan's latest generation (`offset`/`limit` over all
+/// region files in address order) and the total hit count
+pub fn read_scan_hits(scan_id: &str, offset: usize, limit: usize) -> Result<(Vec<ScanHit>, usize), String> {
+    validate_scan_id(scan_id)?;
+    let temp_dir = get_scan_generation_dir(scan_id, get_latest_scan_generation(scan_id));
+    if !temp_dir.exists() {
+        return Err("Scan data not found".to_string());
+    }
+
+    let mut hits: Vec<ScanHit> = Vec::new();
+    let mut total_count: usize = 0;
+
+    for path in list_scan_region_files(&temp_dir) {
+        let file_data = match std::fs::read(&path) {
+            Ok(d) => d,
+            Err(_) => continue,
+        };
+
+        if file_data.len() < 28 {
+            continue;
+        }
+
+        // Read number of addresses
+        let addr_count = u64::from_le_bytes([
+            file_data[16], file_data[17], file_data[18], file_data[19],
+            file_data[20], file_data[21], file_data[22], file_data[23]
+        ]) as usize;
+
+        total_count = total_count.saturating_add(addr_count);
+
+        // Skip if we haven't reached offset yet
+        if total_count <= offset {
+            continue;
+        }
+
+        let Some(region) = parse_scan_region_data(&file_data) else {
+            continue;
+        };
+
+        let data_size = region.data_size;
+        let region_start = total_count - addr_count;
+        let start_idx = offset.saturating_sub(region_start);
+        let end_idx = start_idx.saturating_add(limit - hits.len()).min(region.addresses.len());
+
+        for i in start_idx..end_idx {
+            let Some(value) = i.checked_mul(data_size)
+                .and_then(|val_offset| region.values.get(val_offset..val_offset.checked_add(data_size)?))
+            else {
+                break;
+            };
+            hits.push(ScanHit { address: region.addresses[i], value: value.to_vec() });
+        }
+
+        if hits.len() >= limit {
+            break;
+        }
+    }
+
+    Ok((hits, total_count))
+}
+
+/// Delete a scan's temp files and forget its progress
+pub fn clear_scan(scan_id: &str) -> Result<(), String> {
+    validate_scan_id(scan_id)?;
+    let temp_dir = get_unknown_scan_temp_dir(scan_id);
+    if temp_dir.exists() {
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    if let Ok(mut progress_map) = UNKNOWN_SCAN_PROGRESS.write() {
+        progress_map.remove(scan_id);
+    }
+    if let Ok(mut flags) = UNKNOWN_SCAN_CANCEL.write() {
+        flags.remove(scan_id);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_file_rejects_corrupt_lengths() {
+        let path = std::env::temp_dir().join(format!("dynadbg_region_test_{}.bin", std::process::id()));
+        write_scan_region_file(&path, 4, 4, 0x1000, &[0x1000, 0x1008], &[1, 0, 0, 0, 2, 0, 0, 0]).unwrap();
+        let mut data = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let region = parse_scan_region_data(&data).unwrap();
+        assert_eq!(region.addresses, vec![0x1000, 0x1008]);
+        assert_eq!(region.values.len(), 8);
+
+        // Compressed length that overflows the offset arithmetic
+        let mut overflow = data.clone();
+        overflow[24..32].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(parse_scan_region_data(&overflow).is_none());
+
+        // Prepended size larger than `count` addresses can need
+        data[32..36].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(parse_scan_region_data(&data).is_none());
+    }
+}
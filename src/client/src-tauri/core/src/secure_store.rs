@@ -0,0 +1,237 @@
+use base64::{engine::general_purpose, Engine as _};
+use once_cell::sync::Lazy;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use rusqlite::{params, Connection};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+// Prefix of sealed column values; anything else is read as plaintext, so
+// partially migrated databases stay readable
+pub const SEALED_PREFIX: &str = "dde1:";
+const KEY_LEN: usize = 32;
+pub const KEY_CHECK_PLAINTEXT: &str = "DynaDbg key check";
+const KEYCHAIN_SERVICE: &str = "DynaDbg";
+const KEYCHAIN_ACCOUNT: &str = "db-encryption";
+
+/// Columns that hold sensitive data (decompiled code, analysis results, config)
+pub const ENCRYPTABLE_COLUMNS: &[(&str, &[&str])] = &[
+    ("ghidra_functions_cache", &["functions_json"]),
+    ("ghidra_decompile_cache", &["decompiled_code", "line_mapping_json"]),
+    ("ghidra_xref_cache", &["xrefs_json"]),
+    ("ghidra_callgraph_cache", &["callgraph_json"]),
+    ("ghidra_data_cache", &["data_json"]),
+    ("ghidra_search_cache", &["results_json"]),
+    ("struct_definitions", &["definition_json"]),
+    ("app_settings", &["value"]),
+];
+
+pub struct CryptoState {
+    pub key: Option<[u8; KEY_LEN]>,
+    pub key_source: Option<String>,
+    pub tables: Vec<String>,
+}
+
+pub static DB_CRYPTO: Lazy<RwLock<CryptoState>> = Lazy::new(|| {
+    RwLock::new(CryptoState { key: None, key_source: None, tables: Vec::new() })
+});
+
+fn key_file_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("DynaDbg")
+        .join("db_encryption.key")
+}
+
+fn parse_hex_key(text: &str) -> Option<[u8; KEY_LEN]> {
+    hex::decode(text.trim()).ok()?.try_into().ok()
+}
+
+fn keychain_load() -> Option<[u8; KEY_LEN]> {
+    let output = if cfg!(target_os = "macos") {
+        std::process::Command::new("security")
+            .args(["find-generic-password", "-s", KEYCHAIN_SERVICE, "-a", KEYCHAIN_ACCOUNT, "-w"])
+            .output()
+    } else if cfg!(target_os = "linux") {
+        std::process::Command::new("secret-tool")
+            .args(["lookup", "service", KEYCHAIN_SERVICE, "account", KEYCHAIN_ACCOUNT])
+            .output()
+    } else {
+        return None;
+    };
+    let output = output.ok().filter(|o| o.status.success())?;
+    parse_hex_key(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Run `program args`, writing `input` to its stdin
+fn run_with_stdin(program: &str, args: &[&str], input: &str) -> bool {
+    use std::io::Write;
+    let child = std::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // Dropped at the end of the block, closing stdin
+        if stdin.write_all(input.as_bytes()).is_err() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return false;
+        }
+    }
+    child.wait().is_ok_and(|s| s.success())
+}
+
+// The key goes through stdin, never argv, where other processes could read it
+fn keychain_store(key: &[u8; KEY_LEN]) -> bool {
+    let hex_key = hex::encode(key);
+    if cfg!(target_os = "macos") {
+        // `security -i` reads commands from stdin
+        let command = format!(
+            "add-generic-password -U -s {} -a {} -w {}\n",
+            KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT, hex_key,
+        );
+        run_with_stdin("security", &["-i"], &command) && keychain_load() == Some(*key)
+    } else if cfg!(target_os = "linux") {
+        // secret-tool reads the secret from stdin
+        run_with_stdin(
+            "secret-tool",
+            &["store", "--label=DynaDbg database key", "service", KEYCHAIN_SERVICE, "account", KEYCHAIN_ACCOUNT],
+            &hex_key,
+        )
+    } else {
+        false
+    }
+}
+
+fn key_file_store(key: &[u8; KEY_LEN]) -> Result<(), String> {
+    let path = key_file_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, hex::encode(key)).map_err(|e| format!("Failed to write key file: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Look up the key: DYNADBG_DB_KEY (hex), then the OS keychain (macOS
+/// Keychain / Secret Service), then the key file next to the database
+fn load_key() -> Option<([u8; KEY_LEN], &'static str)> {
+    if let Some(key) = std::env::var("DYNADBG_DB_KEY").ok().and_then(|v| parse_hex_key(&v)) {
+        return Some((key, "env"));
+    }
+    if let Some(key) = keychain_load() {
+        return Some((key, "keychain"));
+    }
+    let text = std::fs::read_to_string(key_file_path()).ok()?;
+    parse_hex_key(&text).map(|key| (key, "file"))
+}
+
+pub fn load_or_create_key() -> Result<([u8; KEY_LEN], &'static str), String> {
+    if let Some(found) = load_key() {
+        return Ok(found);
+    }
+    let mut key = [0u8; KEY_LEN];
+    SystemRandom::new().fill(&mut key).map_err(|_| "Failed to generate encryption key".to_string())?;
+    if keychain_store(&key) {
+        return Ok((key, "keychain"));
+    }
+    key_file_store(&key)?;
+    Ok((key, "file"))
+}
+
+pub fn seal_with(key: &[u8; KEY_LEN], aad: &str, plaintext: &str) -> Result<String, String> {
+    let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).map_err(|_| "Invalid encryption key")?);
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new().fill(&mut nonce).map_err(|_| "Failed to generate nonce")?;
+    let mut buf = plaintext.as_bytes().to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(aad.as_bytes()), &mut buf)
+        .map_err(|_| "Encryption failed")?;
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&buf);
+    Ok(format!("{}{}", SEALED_PREFIX, general_purpose::STANDARD.encode(sealed)))
+}
+
+pub fn open_with(key: &[u8; KEY_LEN], aad: &str, sealed: &str) -> Result<String, String> {
+    let data = general_purpose::STANDARD.decode(&sealed[SEALED_PREFIX.len()..])
+        .map_err(|e| format!("Corrupt encrypted value: {}", e))?;
+    if data.len() < NONCE_LEN {
+        return Err("Corrupt encrypted value".to_string());
+    }
+    let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).map_err(|_| "Invalid encryption key")?);
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| "Corrupt encrypted value")?;
+    let mut buf = ciphertext.to_vec();
+    let plaintext = key.open_in_place(nonce, Aad::from(aad.as_bytes()), &mut buf)
+        .map_err(|_| "Failed to decrypt value (wrong key?)".to_string())?;
+    String::from_utf8(plaintext.to_vec()).map_err(|e| e.to_string())
+}
+
+/// Encrypt a value about to be stored in `table.column` if that table is
+/// designated for encryption; otherwise return it unchanged
+pub fn seal_value(table: &str, column: &str, value: &str) -> Result<String, String> {
+    let crypto = DB_CRYPTO.read().map_err(|e| e.to_string())?;
+    if !crypto.tables.iter().any(|t| t == table) {
+        return Ok(value.to_string());
+    }
+    // Never fall back to plaintext for a designated table
+    let key = crypto.key.as_ref().ok_or("Database encryption key unavailable")?;
+    seal_with(key, &format!("{}.{}", table, column), value)
+}
+
+/// Decrypt a value read from `table.column`; plaintext values pass through
+pub fn open_value(table: &str, column: &str, value: String) -> Result<String, String> {
+    if !value.starts_with(SEALED_PREFIX) {
+        return Ok(value);
+    }
+    let crypto = DB_CRYPTO.read().map_err(|e| e.to_string())?;
+    let key = crypto.key.as_ref().ok_or("Database encryption key unavailable")?;
+    open_with(key, &format!("{}.{}", table, column), &value)
+}
+
+/// Create the settings tables and load the encryption configuration; called
+/// when the cache database is opened (by the app or the CLI)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS db_encryption_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+
+    let setting = |key: &str| -> Option<String> {
+        conn.query_row("SELECT value FROM db_encryption_settings WHERE key = ?1", params![key], |row| row.get(0)).ok()
+    };
+    let tables: Vec<String> = setting("tables")
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    if tables.is_empty() {
+        return Ok(());
+    }
+
+    let mut crypto = DB_CRYPTO.write().map_err(|e| e.to_string())?;
+    crypto.tables = tables;
+    match load_key() {
+        Some((key, source)) => {
+            let check_ok = setting("key_check")
+                .is_none_or(|check| open_with(&key, "key_check", &check).is_ok_and(|p| p == KEY_CHECK_PLAINTEXT));
+            if check_ok {
+                crypto.key = Some(key);
+                crypto.key_source = Some(source.to_string());
+            } else {
+                eprintln!("[SECURE_STORE] Encryption key from {} does not match this database", source);
+            }
+        }
+        None => eprintln!("[SECURE_STORE] Database is encrypted but no key was found"),
+    }
+    Ok(())
+}
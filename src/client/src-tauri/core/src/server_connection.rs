@@ -0,0 +1,379 @@
+use once_cell::sync::Lazy;
+use ring::digest::{digest, SHA256};
+use rusqlite::Connection;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Connection settings of the debug server
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    pub auth_token: Option<String>,
+    pub use_tls: bool,              // https:// instead of http://
+    pub accept_self_signed: bool,   // Trust a pinned self-signed certificate
+}
+
+pub static SERVER_CONFIG: Lazy<RwLock<ServerConfig>> = Lazy::new(|| {
+    RwLock::new(ServerConfig {
+        host: String::new(),
+        port: 3030,
+        auth_token: None,
+        use_tls: false,
+        accept_self_signed: false,
+    })
+});
+
+// Timeout for library downloads/uploads, which can be far larger than the default
+pub const TRANSFER_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Connection pool, timeout and retry policy for requests to the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpSettings {
+    pub timeout_ms: u64,             // Whole-request timeout
+    pub connect_timeout_ms: u64,
+    pub max_concurrent: usize,       // Requests in flight to the server at once
+    pub max_retries: u32,            // Extra attempts after a transient failure
+    pub retry_backoff_ms: u64,       // First retry delay; doubles on every attempt
+    pub pool_max_idle: usize,        // Keep-alive connections kept open while idle
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 30_000,
+            connect_timeout_ms: 5_000,
+            max_concurrent: 16,
+            max_retries: 2,
+            retry_backoff_ms: 100,
+            pool_max_idle: 32,
+        }
+    }
+}
+
+static SETTINGS: Lazy<RwLock<HttpSettings>> = Lazy::new(|| RwLock::new(HttpSettings::default()));
+
+// Client shared by every server request of the session; rebuilt after the
+// connection, TLS pins or settings change
+static CLIENT: Lazy<RwLock<Option<reqwest::Client>>> = Lazy::new(|| RwLock::new(None));
+
+static LIMITER: Lazy<RwLock<Arc<Semaphore>>> = Lazy::new(|| {
+    RwLock::new(Arc::new(Semaphore::new(HttpSettings::default().max_concurrent)))
+});
+
+// Pinned fingerprint per (host, port); loaded with the database and kept in
+// step with server_certificate_pins by pin / unpin
+static PINS: Lazy<RwLock<HashMap<(String, u16), String>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+// Plain pooled client for the local Ghidra servers
+static LOCAL_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .pool_idle_timeout(Duration::from_secs(90))
+        .build()
+        .unwrap_or_default()
+});
+
+// Client for external services (symbol servers, debuginfod); rebuilt after
+// the settings change
+static EXTERNAL_CLIENT: Lazy<RwLock<Option<reqwest::Client>>> = Lazy::new(|| RwLock::new(None));
+
+/// Certificate presented by a server, for the user to confirm before pinning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerCertificate {
+    pub host: String,
+    pub port: u16,
+    pub fingerprint: String,         // SHA-256 of the DER certificate, "AB:CD:..."
+    pub pinned: bool,                // Matches the fingerprint pinned for host:port
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedCertificate {
+    pub host: String,
+    pub port: u16,
+    pub fingerprint: String,
+    pub pinned_at: String,
+}
+
+pub fn fingerprint(der: &[u8]) -> String {
+    digest(&SHA256, der).as_ref().iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+pub fn normalize_fingerprint(text: &str) -> String {
+    let hex: String = text.chars().filter(|c| c.is_ascii_hexdigit()).collect::<String>().to_uppercase();
+    hex.as_bytes().chunks(2)
+        .map(|pair| String::from_utf8_lossy(pair).into_owned())
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Fill the pin cache from server_certificate_pins (after the database opens)
+pub fn load_pins(conn: &Connection) -> Result<(), String> {
+    let mut stmt = conn.prepare("SELECT host, port, fingerprint FROM server_certificate_pins")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], |row| Ok(((row.get::<_, String>(0)?, row.get::<_, u16>(1)?), row.get::<_, String>(2)?)))
+        .map_err(|e| e.to_string())?;
+    let pins = rows.collect::<Result<HashMap<_, _>, _>>().map_err(|e| e.to_string())?;
+    *PINS.write().map_err(|e| e.to_string())? = pins;
+    reset_client();
+    Ok(())
+}
+
+/// Fingerprint pinned for host:port
+pub fn pinned(host: &str, port: u16) -> Option<String> {
+    PINS.read().ok()?.get(&(host.to_string(), port)).cloned()
+}
+
+/// Trusts exactly the pinned certificate: the SHA-256 of the server's
+/// end-entity certificate must equal the pin, so chain and host name are not
+/// checked. Handshake signatures are still verified, proving the server holds
+/// the certificate's key.
+#[derive(Debug)]
+struct PinnedCertVerifier {
+    fingerprint: String,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let presented = fingerprint(end_entity.as_ref());
+        if presented == self.fingerprint {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::General(format!("Certificate fingerprint mismatch: server presented {}", presented)))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// rustls configuration that accepts only the certificate with `fingerprint`
+fn pinned_tls_config(fingerprint: String) -> Result<rustls::ClientConfig, String> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let verifier = PinnedCertVerifier { fingerprint, provider: provider.clone() };
+    Ok(rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("Failed to configure TLS: {}", e))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth())
+}
+
+/// "http://host:port" or "https://host:port" for the configured server
+pub fn base_url(host: &str, port: u16) -> String {
+    let use_tls = SERVER_CONFIG.read().map(|config| config.use_tls).unwrap_or(false);
+    format!("{}://{}:{}", if use_tls { "https" } else { "http" }, host, port)
+}
+
+pub fn settings() -> HttpSettings {
+    SETTINGS.read().map(|s| s.clone()).unwrap_or_default()
+}
+
+/// Drop the shared client so the next request builds one from the current
+/// connection settings
+pub fn reset_client() {
+    if let Ok(mut client) = CLIENT.write() {
+        *client = None;
+    }
+}
+
+/// HTTP client for the configured server, shared across requests. With TLS
+/// and a pinned certificate only a certificate with the pinned SHA-256
+/// fingerprint is trusted; self-signed certificates are refused until pinned.
+pub fn client() -> Result<reqwest::Client, String> {
+    if let Some(client) = CLIENT.read().ok().and_then(|c| c.clone()) {
+        return Ok(client);
+    }
+    let client = build_client()?;
+    if let Ok(mut shared) = CLIENT.write() {
+        *shared = Some(client.clone());
+    }
+    Ok(client)
+}
+
+pub fn local_client() -> reqwest::Client {
+    LOCAL_CLIENT.clone()
+}
+
+/// Shared client for downloads from external services. Uses the configured
+/// connect timeout; the request timeout applies to stalled reads, and whole
+/// transfers are bounded by TRANSFER_TIMEOUT since debug files can be large.
+pub fn external_client() -> reqwest::Client {
+    if let Some(client) = EXTERNAL_CLIENT.read().ok().and_then(|c| c.clone()) {
+        return client;
+    }
+    let settings = settings();
+    let client = reqwest::Client::builder()
+        .timeout(TRANSFER_TIMEOUT)
+        .read_timeout(Duration::from_millis(settings.timeout_ms))
+        .connect_timeout(Duration::from_millis(settings.connect_timeout_ms))
+        .pool_max_idle_per_host(settings.pool_max_idle)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .build()
+        .unwrap_or_default();
+    if let Ok(mut shared) = EXTERNAL_CLIENT.write() {
+        *shared = Some(client.clone());
+    }
+    client
+}
+
+fn build_client() -> Result<reqwest::Client, String> {
+    let (host, port, use_tls, accept_self_signed) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port, config.use_tls, config.accept_self_signed)
+    };
+    let settings = settings();
+    let mut builder = reqwest::Client::builder()
+        .timeout(Duration::from_millis(settings.timeout_ms))
+        .connect_timeout(Duration::from_millis(settings.connect_timeout_ms))
+        .pool_max_idle_per_host(settings.pool_max_idle)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .tcp_keepalive(Duration::from_secs(60));
+    if use_tls {
+        match pinned(&host, port) {
+            Some(fingerprint) => {
+                builder = builder.use_preconfigured_tls(pinned_tls_config(fingerprint)?);
+            }
+            None if accept_self_signed => {
+                return Err(format!("No certificate pinned for {}:{}; pin the server certificate first", host, port));
+            }
+            None => {}
+        }
+    }
+    builder.build().map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Send a server request under the concurrency limit, retrying transient
+/// failures with exponential backoff: connection failures always (nothing
+/// reached the server), timeouts and 502/503/504 only for GET/HEAD.
+/// Returns the response and the attempts made.
+pub async fn send_with_attempts(request: reqwest::RequestBuilder) -> Result<(reqwest::Response, u32), String> {
+    let settings = settings();
+    let limiter = LIMITER.read().map_err(|e| e.to_string())?.clone();
+    let _permit = limiter.acquire_owned().await.map_err(|e| e.to_string())?;
+
+    let mut backoff = settings.retry_backoff_ms;
+    let mut attempt = 1;
+    loop {
+        // Streaming bodies cannot be replayed: send those once
+        let Some(current) = request.try_clone() else {
+            return request.send().await
+                .map(|response| (response, attempt))
+                .map_err(|e| format!("Network error: {}", e));
+        };
+        let (client, built) = current.build_split();
+        let built = built.map_err(|e| format!("Invalid request: {}", e))?;
+        let idempotent = matches!(*built.method(), reqwest::Method::GET | reqwest::Method::HEAD);
+        let can_retry = attempt <= settings.max_retries;
+        match client.execute(built).await {
+            Ok(response) if can_retry && idempotent && matches!(response.status().as_u16(), 502..=504) => {}
+            Ok(response) => return Ok((response, attempt)),
+            Err(e) if can_retry && (e.is_connect() || (idempotent && e.is_timeout())) => {}
+            Err(e) => return Err(format!("Network error: {}", e)),
+        }
+        tokio::time::sleep(Duration::from_millis(backoff)).await;
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+pub async fn send(request: reqwest::RequestBuilder) -> Result<reqwest::Response, String> {
+    send_with_attempts(request).await.map(|(response, _)| response)
+}
+
+
+/// Replace the pool/timeout/retry settings; the shared clients are rebuilt
+pub fn apply_settings(settings: HttpSettings) -> Result<(), String> {
+    if settings.max_concurrent == 0 {
+        return Err("max_concurrent must be at least 1".to_string());
+    }
+    *LIMITER.write().map_err(|e| e.to_string())? = Arc::new(Semaphore::new(settings.max_concurrent));
+    *SETTINGS.write().map_err(|e| e.to_string())? = settings;
+    reset_client();
+    *EXTERNAL_CLIENT.write().map_err(|e| e.to_string())? = None;
+    Ok(())
+}
+
+/// Trust `fingerprint` for host:port from now on (the caller stores the pin)
+pub fn pin(host: &str, port: u16, fingerprint: &str) -> Result<(), String> {
+    PINS.write().map_err(|e| e.to_string())?.insert((host.to_string(), port), fingerprint.to_string());
+    reset_client();
+    Ok(())
+}
+
+pub fn unpin(host: &str, port: u16) -> Result<(), String> {
+    PINS.write().map_err(|e| e.to_string())?.remove(&(host.to_string(), port));
+    reset_client();
+    Ok(())
+}
+
+/// Fetch the certificate a server presents (without validating it)
+pub async fn fetch_certificate(host: &str, port: u16) -> Result<Vec<u8>, String> {
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .tls_info(true)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client.get(format!("https://{}:{}/api/server/info", host, port))
+        .send()
+        .await
+        .map_err(|e| format!("TLS connection failed: {}", e))?;
+    response.extensions()
+        .get::<reqwest::tls::TlsInfo>()
+        .and_then(|info| info.peer_certificate())
+        .map(|der| der.to_vec())
+        .ok_or_else(|| "Server did not present a certificate".to_string())
+}
+
+/// Host and port of the configured server
+pub fn server_address() -> Result<(String, u16), String> {
+    let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+    if config.host.is_empty() {
+        return Err("No server connection configured".to_string());
+    }
+    Ok((config.host.clone(), config.port))
+}
+
+/// Host, port and auth token of the configured server
+pub fn server_address_with_token() -> Result<(String, u16, Option<String>), String> {
+    let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+    if config.host.is_empty() {
+        return Err("No server connection configured".to_string());
+    }
+    Ok((config.host.clone(), config.port, config.auth_token.clone()))
+}
@@ -0,0 +1,349 @@
+use std::cmp::Ordering;
+
+// Virtual address bits kept when stripping a pointer-auth pointer without an explicit width
+const DEFAULT_PAC_VA_BITS: u32 = 48;
+
+/// Data types beyond the little-endian ints / float / double handled inline
+/// by compare_values. Spelled in data_type strings as:
+/// "float16", "int16be".."uint64be", "pointer_pac" / "pointer_pac:<va bits>",
+/// "bitfield:<bit offset>:<width>", "fixed:<int>.<frac>" / "ufixed:<int>.<frac>"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueType {
+    Float16,
+    BigEndian { size: usize, signed: bool },
+    PacPointer { va_bits: u32 },         // ARM64 pointer with the PAC / tag bits above va_bits cleared
+    Bitfield { offset: u32, width: u32 }, // Unsigned, LSB-first within a little-endian container
+    Fixed { int_bits: u32, frac_bits: u32, signed: bool },
+}
+
+/// Decoded value of any numeric data type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+impl Number {
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Int(a), Number::Int(b)) => Some(a.cmp(b)),
+            (a, b) => a.as_f64().partial_cmp(&b.as_f64()),
+        }
+    }
+
+    fn as_f64(&self) -> f64 {
+        match *self {
+            Number::Int(v) => v as f64,
+            Number::Float(v) => v,
+        }
+    }
+}
+
+impl ValueType {
+    pub fn parse(data_type: &str) -> Option<ValueType> {
+        let (name, args) = data_type.split_once(':').unwrap_or((data_type, ""));
+        Some(match (name, args) {
+            ("float16", "") => ValueType::Float16,
+            ("int16be", "") => ValueType::BigEndian { size: 2, signed: true },
+            ("uint16be", "") => ValueType::BigEndian { size: 2, signed: false },
+            ("int32be", "") => ValueType::BigEndian { size: 4, signed: true },
+            ("uint32be", "") => ValueType::BigEndian { size: 4, signed: false },
+            ("int64be", "") => ValueType::BigEndian { size: 8, signed: true },
+            ("uint64be", "") => ValueType::BigEndian { size: 8, signed: false },
+            ("pointer_pac", "") => ValueType::PacPointer { va_bits: DEFAULT_PAC_VA_BITS },
+            ("pointer_pac", bits) => {
+                let va_bits = bits.parse().ok().filter(|b| (32..=56).contains(b))?;
+                ValueType::PacPointer { va_bits }
+            }
+            ("bitfield", args) => {
+                let (offset, width) = args.split_once(':')?;
+                let (offset, width): (u32, u32) = (offset.parse().ok()?, width.parse().ok()?);
+                if width == 0 || offset + width > 64 {
+                    return None;
+                }
+                ValueType::Bitfield { offset, width }
+            }
+            ("fixed" | "ufixed", args) => {
+                let (int_bits, frac_bits) = args.split_once('.')?;
+                let (int_bits, frac_bits): (u32, u32) = (int_bits.parse().ok()?, frac_bits.parse().ok()?);
+                if !matches!(int_bits + frac_bits, 8 | 16 | 32 | 64) {
+                    return None;
+                }
+                ValueType::Fixed { int_bits, frac_bits, signed: name == "fixed" }
+            }
+            _ => return None,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        match *self {
+            ValueType::Float16 => 2,
+            ValueType::BigEndian { size, .. } => size,
+            ValueType::PacPointer { .. } => 8,
+            ValueType::Bitfield { offset, width } => (offset + width).div_ceil(8) as usize,
+            ValueType::Fixed { int_bits, frac_bits, .. } => ((int_bits + frac_bits) / 8) as usize,
+        }
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> Option<Number> {
+        let bytes = bytes.get(..self.size())?;
+        Some(match *self {
+            ValueType::Float16 => Number::Float(f16_to_f32(u16::from_le_bytes([bytes[0], bytes[1]])) as f64),
+            ValueType::BigEndian { size, signed } => {
+                let mut raw = [0u8; 8];
+                raw[8 - size..].copy_from_slice(bytes);
+                Number::Int(extend(u64::from_be_bytes(raw), size as u32 * 8, signed))
+            }
+            ValueType::PacPointer { va_bits } => Number::Int((read_le(bytes) & ((1u64 << va_bits) - 1)) as i128),
+            ValueType::Bitfield { offset, width } => Number::Int(((read_le(bytes) >> offset) & mask(width)) as i128),
+            ValueType::Fixed { int_bits, frac_bits, signed } => {
+                let raw = extend(read_le(bytes), int_bits + frac_bits, signed);
+                Number::Float(raw as f64 / (1u128 << frac_bits) as f64)
+            }
+        })
+    }
+
+    /// Bytes of `text` in this type. A bitfield encodes to its container with
+    /// only the field's bits set; merge under the field mask before writing.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        let size = self.size();
+        Ok(match *self {
+            ValueType::Float16 => f32_to_f16(parse_float(text)? as f32).to_le_bytes().to_vec(),
+            ValueType::BigEndian { size, signed } => {
+                let value = parse_integer(text, size as u32 * 8, signed)?;
+                value.to_be_bytes()[8 - size..].to_vec()
+            }
+            ValueType::PacPointer { va_bits } => {
+                let value = parse_integer(text, 64, false)?;
+                if value >> va_bits != 0 {
+                    return Err(format!("Pointer {} does not fit in {} address bits", text.trim(), va_bits));
+                }
+                value.to_le_bytes().to_vec()
+            }
+            ValueType::Bitfield { offset, width } => (parse_integer(text, width, false)? << offset).to_le_bytes()[..size].to_vec(),
+            ValueType::Fixed { int_bits, frac_bits, signed } => {
+                let bits = int_bits + frac_bits;
+                let scaled = (parse_float(text)? * (1u128 << frac_bits) as f64).round();
+                let (min, max) = if signed {
+                    (-((1i128 << (bits - 1)) as f64), ((1i128 << (bits - 1)) - 1) as f64)
+                } else {
+                    (0.0, ((1i128 << bits) - 1) as f64)
+                };
+                if !(min..=max).contains(&scaled) {
+                    return Err(format!("{} is out of range for {}.{} fixed-point", text.trim(), int_bits, frac_bits));
+                }
+                ((scaled as i128) as u64 & mask(bits)).to_le_bytes()[..size].to_vec()
+            }
+        })
+    }
+
+    fn format(&self, value: Number) -> String {
+        match (self, value) {
+            (ValueType::PacPointer { .. }, Number::Int(v)) => format!("0x{:x}", v),
+            (ValueType::Float16, Number::Float(v)) => (v as f32).to_string(),
+            (_, Number::Int(v)) => v.to_string(),
+            (_, Number::Float(v)) => v.to_string(),
+        }
+    }
+}
+
+fn mask(bits: u32) -> u64 {
+    if bits >= 64 { u64::MAX } else { (1u64 << bits) - 1 }
+}
+
+fn read_le(bytes: &[u8]) -> u64 {
+    let mut raw = [0u8; 8];
+    raw[..bytes.len().min(8)].copy_from_slice(&bytes[..bytes.len().min(8)]);
+    u64::from_le_bytes(raw)
+}
+
+/// Sign- or zero-extend the low `bits` bits of `raw`
+fn extend(raw: u64, bits: u32, signed: bool) -> i128 {
+    let raw = raw & mask(bits);
+    if signed && bits < 128 && raw >> (bits - 1) & 1 == 1 {
+        raw as i128 - (1i128 << bits)
+    } else {
+        raw as i128
+    }
+}
+
+/// Decimal or 0x-prefixed hex, optionally negative
+fn parse_i128(text: &str) -> Result<i128, String> {
+    let text = text.trim();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let magnitude = match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        Some(hex) => i128::from_str_radix(hex, 16),
+        None => digits.parse::<i128>(),
+    }.map_err(|_| format!("Invalid integer '{}'", text))?;
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+/// Decimal or 0x-prefixed hex; negative values are stored two's complement
+fn parse_integer(text: &str, bits: u32, signed: bool) -> Result<u64, String> {
+    let value = parse_i128(text)?;
+    let negative = value < 0;
+    // Accept both the signed and unsigned spelling of the same bit pattern
+    let min = if signed || negative { -(1i128 << (bits - 1)) } else { 0 };
+    let max = (1i128 << bits) - 1;
+    if value < min || value > max {
+        return Err(format!("{} does not fit in {} bits", text.trim(), bits));
+    }
+    Ok(value as u64 & mask(bits))
+}
+
+fn parse_float(text: &str) -> Result<f64, String> {
+    text.trim().parse::<f64>().map_err(|_| format!("Invalid number '{}'", text.trim()))
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    sign * match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        31 if mantissa == 0.0 => f32::INFINITY,
+        31 => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+fn f32_to_f16(value: f32) -> u16 {
+    let sign = ((value.to_bits() >> 16) & 0x8000) as u16;
+    let magnitude = value.abs();
+    if value.is_nan() {
+        return sign | 0x7e00;
+    }
+    if magnitude < 2f32.powi(-14) {
+        // Subnormal; a mantissa that rounds up to 1024 becomes the smallest normal
+        return sign | (magnitude / 2f32.powi(-24)).round() as u16;
+    }
+    let mut exponent = ((magnitude.to_bits() >> 23) & 0xff) as i32 - 127;
+    let mut mantissa = ((magnitude / 2f32.powi(exponent) - 1.0) * 1024.0).round() as u16;
+    if mantissa == 1024 {
+        mantissa = 0;
+        exponent += 1;
+    }
+    if exponent > 15 {
+        return sign | 0x7c00;
+    }
+    sign | (((exponent + 15) as u16) << 10) | mantissa
+}
+
+/// Size in bytes of an extended data type
+pub fn data_size(data_type: &str) -> Option<usize> {
+    ValueType::parse(data_type).map(|t| t.size())
+}
+
+/// Decode a value of any numeric data type
+pub fn decode_number(data_type: &str, bytes: &[u8]) -> Option<Number> {
+    if let Some(value_type) = ValueType::parse(data_type) {
+        return value_type.decode(bytes);
+    }
+    Some(match data_type {
+        "int8" => Number::Int(*bytes.first()? as i8 as i128),
+        "uint8" => Number::Int(*bytes.first()? as i128),
+        "int16" => Number::Int(i16::from_le_bytes(bytes.get(..2)?.try_into().ok()?) as i128),
+        "uint16" => Number::Int(u16::from_le_bytes(bytes.get(..2)?.try_into().ok()?) as i128),
+        "int32" => Number::Int(i32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as i128),
+        "uint32" => Number::Int(u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as i128),
+        "int64" => Number::Int(i64::from_le_bytes(bytes.get(..8)?.try_into().ok()?) as i128),
+        "uint64" => Number::Int(u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?) as i128),
+        "float" => Number::Float(f32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as f64),
+        "double" => Number::Float(f64::from_le_bytes(bytes.get(..8)?.try_into().ok()?)),
+        _ => return None,
+    })
+}
+
+/// Text of a value of any numeric data type
+pub fn decode(data_type: &str, bytes: &[u8]) -> Result<String, String> {
+    let value = decode_number(data_type, bytes)
+        .ok_or_else(|| format!("Cannot decode {} bytes as '{}'", bytes.len(), data_type))?;
+    Ok(match ValueType::parse(data_type) {
+        Some(value_type) => value_type.format(value),
+        None => match value {
+            Number::Int(v) => v.to_string(),
+            Number::Float(v) if data_type == "float" => (v as f32).to_string(),
+            Number::Float(v) => v.to_string(),
+        },
+    })
+}
+
+/// Bytes of `text` in any numeric data type
+pub fn encode(data_type: &str, text: &str) -> Result<Vec<u8>, String> {
+    if let Some(value_type) = ValueType::parse(data_type) {
+        return value_type.encode(text);
+    }
+    match data_type {
+        "float" => Ok((parse_float(text)? as f32).to_le_bytes().to_vec()),
+        "double" => Ok(parse_float(text)?.to_le_bytes().to_vec()),
+        "int8" | "uint8" | "int16" | "uint16" | "int32" | "uint32" | "int64" | "uint64" => {
+            encode_int(data_type, parse_i128(text)?)
+                .ok_or_else(|| format!("{} does not fit in {}", text.trim(), data_type))
+        }
+        other => Err(format!("Unsupported data type '{}'", other)),
+    }
+}
+
+/// Little-endian bytes of an int8..uint64 value, None when it fits neither
+/// the signed nor the unsigned type of that width (both spellings of the same
+/// bit pattern are accepted)
+fn encode_int(data_type: &str, value: i128) -> Option<Vec<u8>> {
+    Some(match data_type {
+        "int8" | "uint8" => i8::try_from(value).map(|v| v as u8).or_else(|_| u8::try_from(value)).ok()?.to_le_bytes().to_vec(),
+        "int16" | "uint16" => i16::try_from(value).map(|v| v as u16).or_else(|_| u16::try_from(value)).ok()?.to_le_bytes().to_vec(),
+        "int32" | "uint32" => i32::try_from(value).map(|v| v as u32).or_else(|_| u32::try_from(value)).ok()?.to_le_bytes().to_vec(),
+        "int64" | "uint64" => i64::try_from(value).map(|v| v as u64).or_else(|_| u64::try_from(value)).ok()?.to_le_bytes().to_vec(),
+        _ => return None,
+    })
+}
+
+/// compare_values for extended types; values are compared decoded, so a
+/// bitfield ignores its neighbours and a PAC pointer its signature bits
+pub fn compare(
+    value_type: &ValueType,
+    new_val: &[u8],
+    old_val: &[u8],
+    pattern: &[u8],
+    pattern_max: Option<&[u8]>,
+    filter_method: &str,
+) -> bool {
+    let Some(new) = value_type.decode(new_val) else { return false };
+    let ordering = |other: &[u8]| value_type.decode(other).and_then(|o| new.partial_cmp(&o));
+    match filter_method {
+        "exact" => ordering(pattern) == Some(Ordering::Equal),
+        "range" => {
+            let Some(max) = pattern_max else { return false };
+            matches!(ordering(pattern), Some(Ordering::Greater | Ordering::Equal))
+                && matches!(ordering(max), Some(Ordering::Less | Ordering::Equal))
+        }
+        "greater_or_equal" => matches!(ordering(pattern), Some(Ordering::Greater | Ordering::Equal)),
+        "less_than" => ordering(pattern) == Some(Ordering::Less),
+        "changed" => value_type.decode(old_val) != Some(new),
+        "unchanged" => value_type.decode(old_val) == Some(new),
+        "increased" => ordering(old_val) == Some(Ordering::Greater),
+        "decreased" => ordering(old_val) == Some(Ordering::Less),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_checks_integer_range() {
+        assert_eq!(encode("int8", "-1").unwrap(), vec![0xff]);
+        assert_eq!(encode("uint8", "255").unwrap(), vec![0xff]);
+        assert_eq!(encode("int16", "0x1234").unwrap(), vec![0x34, 0x12]);
+        assert_eq!(encode("uint64", "-1").unwrap(), vec![0xff; 8]);
+        assert!(encode("uint8", "256").is_err());
+        assert!(encode("int8", "-129").is_err());
+        assert!(encode("int32", "0x100000000").is_err());
+        assert!(encode("uint64", "0x10000000000000000").is_err());
+        assert!(encode("int32", "ten").is_err());
+        assert!(encode("int24", "1").is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::state::AppState;
+use crate::{read_memory_from_server, server_address};
+
+const READ_CHUNK: u64 = 0x10_0000;
+const DEFAULT_INTERVAL_MS: u64 = 250;
+const MIN_INTERVAL_MS: u64 = 20;
+const MAX_REGION_SIZE: u64 = 64 * 1024 * 1024;
+const MAX_CELLS: u64 = 1 << 20;
+const DEFAULT_HOTTEST: usize = 64;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeatmapRegion {
+    pub address: u64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessHeatmapStatus {
+    pub running: bool,
+    pub address: u64,
+    pub size: u64,
+    pub granularity: u64,               // Bytes per cell
+    pub interval_ms: u64,
+    pub samples: u64,                   // Snapshots compared against their predecessor
+    pub last_sample_at: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeatmapCell {
+    pub address: u64,
+    pub changes: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessHeatmap {
+    pub status: AccessHeatmapStatus,
+    pub cells: Vec<u8>,                 // Per cell: 255 = changed in every sample, 0 = never
+    pub hottest: Vec<HeatmapCell>,      // Most frequently changed cells, hottest first
+}
+
+struct Heatmap {
+    region: HeatmapRegion,
+    granularity: u64,
+    interval_ms: u64,
+    running: Arc<AtomicBool>,
+    snapshot: Vec<Option<Vec<u8>>>,     // Previous bytes per READ_CHUNK; None when unreadable
+    changes: Vec<u32>,
+    samples: u64,
+    last_sample_at: Option<u64>,
+    last_error: Option<String>,
+}
+
+impl Heatmap {
+    fn status(&self) -> AccessHeatmapStatus {
+        AccessHeatmapStatus {
+            running: self.running.load(Ordering::Relaxed),
+            address: self.region.address,
+            size: self.region.size,
+            granularity: self.granularity,
+            interval_ms: self.interval_ms,
+            samples: self.samples,
+            last_sample_at: self.last_sample_at,
+            last_error: self.last_error.clone(),
+        }
+    }
+}
+
+static HEATMAP: Lazy<Mutex<Heatmap>> = Lazy::new(|| {
+    Mutex::new(Heatmap {
+        region: HeatmapRegion::default(),
+        granularity: 1,
+        interval_ms: DEFAULT_INTERVAL_MS,
+        running: Arc::new(AtomicBool::new(false)),
+        snapshot: Vec::new(),
+        changes: Vec::new(),
+        samples: 0,
+        last_sample_at: None,
+        last_error: None,
+    })
+});
+
+fn lock_heatmap() -> Result<std::sync::MutexGuard<'static, Heatmap>, String> {
+    HEATMAP.lock().map_err(|e| e.to_string())
+}
+
+/// Snapshot the region and count the cells that differ from the previous
+/// snapshot. The first pass (and chunks that were unreadable) only record.
+async fn sample() -> Result<(), String> {
+    let (host, port) = server_address()?;
+    let (region, granularity) = {
+        let heatmap = lock_heatmap()?;
+        (heatmap.region.clone(), heatmap.granularity)
+    };
+    let end = region.address + region.size;
+    let mut chunks = Vec::new();
+    let mut chunk_start = region.address;
+    while chunk_start < end {
+        let chunk_size = (end - chunk_start).min(READ_CHUNK);
+        chunks.push(read_memory_from_server(&host, port, chunk_start, chunk_size as usize).await.ok());
+        chunk_start += chunk_size;
+    }
+
+    let mut guard = lock_heatmap()?;
+    // Through a plain reference, so snapshot and changes borrow separately
+    let heatmap = &mut *guard;
+    let compared = heatmap.snapshot.len() == chunks.len();
+    if compared {
+        // Cells are visited in address order, so each is counted once per sample
+        let mut last_cell = None;
+        for (chunk, (previous, current)) in heatmap.snapshot.iter().zip(&chunks).enumerate() {
+            let (Some(previous), Some(current)) = (previous, current) else {
+                continue;
+            };
+            let chunk_offset = chunk as u64 * READ_CHUNK;
+            for (i, (a, b)) in previous.iter().zip(current).enumerate() {
+                if a == b {
+                    continue;
+                }
+                let cell = ((chunk_offset + i as u64) / granularity) as usize;
+                if last_cell != Some(cell) {
+                    last_cell = Some(cell);
+                    if let Some(count) = heatmap.changes.get_mut(cell) {
+                        *count = count.saturating_add(1);
+                    }
+                }
+            }
+        }
+    }
+    let unreadable = chunks.iter().filter(|c| c.is_none()).count();
+    heatmap.snapshot = chunks;
+    if compared {
+        heatmap.samples += 1;
+    }
+    heatmap.last_sample_at = Some(AppState::current_timestamp());
+    heatmap.last_error = (unreadable > 0).then(|| format!("{} of {} chunks unreadable", unreadable, heatmap.snapshot.len()));
+    Ok(())
+}
+
+/// Periodically snapshot `region` and count, per `granularity`-byte cell,
+/// how often its bytes changed between snapshots. Restarting clears the counts.
+#[tauri::command]
+pub async fn start_access_heatmap(
+    region: HeatmapRegion,
+    granularity: u64,
+    interval_ms: Option<u64>,
+) -> Result<AccessHeatmapStatus, String> {
+    server_address()?;
+    if region.size == 0 || region.size > MAX_REGION_SIZE {
+        return Err(format!("Region size must be between 1 and {} bytes", MAX_REGION_SIZE));
+    }
+    let granularity = granularity.max(1);
+    if region.size.div_ceil(granularity) > MAX_CELLS {
+        return Err(format!("Granularity too fine: more than {} cells", MAX_CELLS));
+    }
+    let interval_ms = interval_ms.unwrap_or(DEFAULT_INTERVAL_MS).max(MIN_INTERVAL_MS);
+    let running = Arc::new(AtomicBool::new(true));
+    let status = {
+        let mut heatmap = lock_heatmap()?;
+        heatmap.running.store(false, Ordering::Relaxed);
+        heatmap.running = running.clone();
+        heatmap.interval_ms = interval_ms;
+        heatmap.granularity = granularity;
+        heatmap.changes = vec![0; region.size.div_ceil(granularity) as usize];
+        heatmap.region = region;
+        heatmap.snapshot.clear();
+        heatmap.samples = 0;
+        heatmap.last_sample_at = None;
+        heatmap.last_error = None;
+        heatmap.status()
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        while running.load(Ordering::Relaxed) {
+            interval.tick().await;
+            if !running.load(Ordering::Relaxed) {
+                break;
+            }
+            if let Err(e) = sample().await {
+                if let Ok(mut heatmap) = HEATMAP.lock() {
+                    heatmap.last_error = Some(e);
+                }
+            }
+        }
+    });
+
+    Ok(status)
+}
+
+/// Stop sampling; the counts are kept for get_access_heatmap
+#[tauri::command]
+pub fn stop_access_heatmap() -> Result<AccessHeatmapStatus, String> {
+    let heatmap = lock_heatmap()?;
+    heatmap.running.store(false, Ordering::Relaxed);
+    Ok(heatmap.status())
+}
+
+/// Change frequency of every cell scaled to a byte, plus the `top` hottest
+/// cells (default 64)
+#[tauri::command]
+pub fn get_access_heatmap(top: Option<usize>) -> Result<AccessHeatmap, String> {
+    let heatmap = lock_heatmap()?;
+    let samples = heatmap.samples.max(1);
+    let cells = heatmap.changes.iter()
+        .map(|&changes| (changes as u64 * 255 / samples).min(255) as u8)
+        .collect();
+    let mut hottest: Vec<HeatmapCell> = heatmap.changes.iter()
+        .enumerate()
+        .filter(|(_, &changes)| changes > 0)
+        .map(|(cell, &changes)| HeatmapCell { address: heatmap.region.address + cell as u64 * heatmap.granularity, changes })
+        .collect();
+    hottest.sort_by(|a, b| b.changes.cmp(&a.changes).then(a.address.cmp(&b.address)));
+    hottest.truncate(top.unwrap_or(DEFAULT_HOTTEST));
+    Ok(AccessHeatmap { status: heatmap.status(), cells, hottest })
+}
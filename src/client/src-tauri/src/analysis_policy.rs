@@ -0,0 +1,278 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::state::AppStateType;
+use crate::{db, ghidra_batch, start_ghidra_server, GHIDRA_SERVER_PORTS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalysisMode {
+    Never,                            // Refuse headless analysis
+    Symbols,                          // Import without auto-analysis (symbol table only)
+    Full,                             // Full Ghidra auto-analysis
+}
+
+impl AnalysisMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AnalysisMode::Never => "never",
+            AnalysisMode::Symbols => "symbols",
+            AnalysisMode::Full => "full",
+        }
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        match text {
+            "never" => Some(AnalysisMode::Never),
+            "symbols" => Some(AnalysisMode::Symbols),
+            "full" => Some(AnalysisMode::Full),
+            _ => None,
+        }
+    }
+}
+
+/// How a module is treated by the Ghidra pipeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleAnalysisPolicy {
+    pub target_os: String,            // "*" applies to every OS
+    pub module_pattern: String,       // Module file name; '*' and '?' wildcards, case-insensitive
+    pub mode: AnalysisMode,
+    pub auto_start_server: bool,      // Start the Ghidra server once the module is analyzed
+    pub prefetch_decompiles: bool,    // Decompile every function into the cache in the background
+    pub builtin: bool,                // Default policy, not stored
+    pub updated_at: Option<String>,
+}
+
+// System libraries that are huge and rarely worth more than their exports
+const SYSTEM_LIBRARIES: &[&str] = &[
+    "libc.so*", "libc++*.so", "libm.so*", "libdl.so*", "ld-linux*", "linker64", "libart.so",
+    "libandroid_runtime.so", "libhwui.so", "libicu*", "libskia*", "libwebviewchromium.so",
+    "libsystem_*.dylib", "libobjc*.dylib", "libswiftcore.dylib", "corefoundation", "foundation",
+    "uikitcore", "appkit", "webkit", "javascriptcore",
+    "ntdll.dll", "kernel32.dll", "kernelbase.dll", "user32.dll", "gdi32*.dll", "ucrtbase.dll",
+    "msvcp*.dll", "vcruntime*.dll", "combase.dll", "d3d*.dll", "dxgi.dll",
+];
+
+/// Create the policy table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS module_analysis_policies (
+            target_os TEXT NOT NULL,
+            module_pattern TEXT NOT NULL,
+            mode TEXT NOT NULL,
+            auto_start_server INTEGER NOT NULL DEFAULT 0,
+            prefetch_decompiles INTEGER NOT NULL DEFAULT 0,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_pattern)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Case-insensitive wildcard match ('*' any run, '?' one character)
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+pub fn file_name(module_name: &str) -> &str {
+    module_name.rsplit(['/', '\\']).next().unwrap_or(module_name)
+}
+
+fn builtin_policy(target_os: &str, module_name: &str) -> ModuleAnalysisPolicy {
+    let name = file_name(module_name);
+    let system = SYSTEM_LIBRARIES.iter().find(|p| wildcard_match(p, name));
+    ModuleAnalysisPolicy {
+        target_os: target_os.to_string(),
+        module_pattern: system.map(|p| p.to_string()).unwrap_or_else(|| "*".to_string()),
+        mode: if system.is_some() { AnalysisMode::Symbols } else { AnalysisMode::Full },
+        auto_start_server: false,
+        prefetch_decompiles: false,
+        builtin: true,
+        updated_at: None,
+    }
+}
+
+fn row_to_policy(row: &rusqlite::Row) -> rusqlite::Result<ModuleAnalysisPolicy> {
+    let mode: String = row.get(2)?;
+    Ok(ModuleAnalysisPolicy {
+        target_os: row.get(0)?,
+        module_pattern: row.get(1)?,
+        mode: AnalysisMode::parse(&mode).unwrap_or(AnalysisMode::Full),
+        auto_start_server: row.get::<_, i64>(3)? != 0,
+        prefetch_decompiles: row.get::<_, i64>(4)? != 0,
+        builtin: false,
+        updated_at: row.get(5)?,
+    })
+}
+
+fn stored_policies(conn: &Connection) -> Result<Vec<ModuleAnalysisPolicy>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT target_os, module_pattern, mode, auto_start_server, prefetch_decompiles, updated_at
+         FROM module_analysis_policies ORDER BY target_os, module_pattern",
+    ).map_err(|e| e.to_string())?;
+    let policies = stmt.query_map([], row_to_policy)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(policies)
+}
+
+/// Policy for a module: the most specific stored pattern for the OS (an exact
+/// name beats wildcards, longer patterns beat shorter ones, the OS beats "*"),
+/// falling back to the built-in defaults
+pub async fn resolve(target_os: &str, module_name: &str) -> ModuleAnalysisPolicy {
+    let name = file_name(module_name);
+    let specificity = |p: &ModuleAnalysisPolicy| {
+        let literal = !p.module_pattern.contains(['*', '?']);
+        (literal, p.target_os != "*", p.module_pattern.chars().filter(|c| *c != '*').count())
+    };
+    db::run(stored_policies).await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|p| p.target_os == "*" || p.target_os == target_os)
+        .filter(|p| wildcard_match(&p.module_pattern, name))
+        .max_by_key(specificity)
+        .unwrap_or_else(|| builtin_policy(target_os, module_name))
+}
+
+/// Decompile every function of a running server's program into the cache
+async fn prefetch(target_os: &str, module_name: &str, project_path: &str, port: u16) -> Result<usize, String> {
+    let mut functions = None;
+    // analyzeHeadless needs a while to open the project before the server answers
+    for _ in 0..120 {
+        if let Ok(list) = ghidra_batch::server_functions(port).await {
+            functions = Some(list);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+    let functions = functions.ok_or("Ghidra server did not come up")?;
+    let mut progress = ghidra_batch::DecompileAllProgress {
+        project_path: project_path.to_string(),
+        module_name: module_name.to_string(),
+        ..Default::default()
+    };
+    ghidra_batch::decompile_into_cache(&mut progress, target_os, functions, true, |_| {}).await;
+    Ok(progress.decompiled)
+}
+
+/// Run the post-analysis steps a policy asks for: start the Ghidra server
+/// and optionally fill the decompile cache in the background
+pub async fn apply_post_analysis(
+    policy: &ModuleAnalysisPolicy,
+    target_os: &str,
+    module_name: &str,
+    project_path: &str,
+    ghidra_path: &str,
+) -> Result<Option<u16>, String> {
+    if !policy.auto_start_server && !policy.prefetch_decompiles {
+        return Ok(None);
+    }
+    let existing = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?.get(project_path).copied();
+    let port = match existing {
+        Some(port) => port,
+        None => start_ghidra_server(project_path.to_string(), module_name.to_string(), ghidra_path.to_string(), None).await?,
+    };
+    if policy.prefetch_decompiles {
+        let (target_os, module_name, project_path) = (target_os.to_string(), module_name.to_string(), project_path.to_string());
+        tokio::spawn(async move {
+            match prefetch(&target_os, &module_name, &project_path, port).await {
+                Ok(count) => println!("Prefetched {} decompiled functions of {}", count, module_name),
+                Err(e) => eprintln!("Decompile prefetch for {} failed: {}", module_name, e),
+            }
+        });
+    }
+    Ok(Some(port))
+}
+
+fn current_target_os(state: &AppStateType) -> String {
+    state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.target_os.clone()))
+        .unwrap_or_default()
+}
+
+/// Policy that applies to a module (target_os defaults to the connected target's)
+#[tauri::command]
+pub async fn get_module_analysis_policy(
+    state: tauri::State<'_, AppStateType>,
+    module_name: String,
+    target_os: Option<String>,
+) -> Result<ModuleAnalysisPolicy, String> {
+    let target_os = target_os.unwrap_or_else(|| current_target_os(state.inner()));
+    Ok(resolve(&target_os, &module_name).await)
+}
+
+#[tauri::command]
+pub async fn set_module_analysis_policy(
+    target_os: String,
+    module_pattern: String,
+    mode: AnalysisMode,
+    auto_start_server: Option<bool>,
+    prefetch_decompiles: Option<bool>,
+) -> Result<ModuleAnalysisPolicy, String> {
+    let module_pattern = module_pattern.trim().to_string();
+    if module_pattern.is_empty() {
+        return Err("Module pattern cannot be empty".to_string());
+    }
+    let target_os = if target_os.is_empty() { "*".to_string() } else { target_os };
+    // Nothing to serve or prefetch from a module that is never analyzed
+    let analyzed = mode != AnalysisMode::Never;
+    db::run(move |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO module_analysis_policies
+             (target_os, module_pattern, mode, auto_start_server, prefetch_decompiles, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))",
+            params![
+                target_os,
+                module_pattern,
+                mode.as_str(),
+                (analyzed && auto_start_server.unwrap_or(false)) as i64,
+                (analyzed && prefetch_decompiles.unwrap_or(false)) as i64,
+            ],
+        ).map_err(|e| e.to_string())?;
+        stored_policies(conn)?
+            .into_iter()
+            .find(|p| p.target_os == target_os && p.module_pattern == module_pattern)
+            .ok_or_else(|| "Failed to store policy".to_string())
+    }).await
+}
+
+#[tauri::command]
+pub async fn delete_module_analysis_policy(target_os: String, module_pattern: String) -> Result<bool, String> {
+    db::run(move |conn| {
+        let deleted = conn.execute(
+            "DELETE FROM module_analysis_policies WHERE target_os = ?1 AND module_pattern = ?2",
+            params![target_os, module_pattern],
+        ).map_err(|e| e.to_string())?;
+        Ok(deleted > 0)
+    }).await
+}
+
+/// Stored policies followed by the built-in system library defaults
+#[tauri::command]
+pub async fn list_module_analysis_policies() -> Result<Vec<ModuleAnalysisPolicy>, String> {
+    let mut policies = db::run(stored_policies).await?;
+    policies.extend(SYSTEM_LIBRARIES.iter().map(|pattern| builtin_policy("*", pattern)));
+    Ok(policies)
+}
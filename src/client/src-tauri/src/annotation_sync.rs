@@ -0,0 +1,432 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::state::AppStateType;
+use crate::struct_dissector::StructDefinition;
+use crate::{annotations, cache_versions, db, ghidra_edits, secure_store, struct_dissector, symbolizer};
+
+pub const FORMAT: &str = "dynadbg-annotations";
+pub const VERSION: u32 = 1;
+pub const MERGE_STRATEGIES: [&str; 3] = ["ours", "theirs", "newest"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameRecord {
+    pub offset: String,
+    pub name: String,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentRecord {
+    pub offset: String,
+    #[serde(default)]
+    pub title: String,
+    pub body: String,                 // Markdown
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookmarkRecord {
+    pub offset: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructRecord {
+    pub name: String,
+    #[serde(default)]
+    pub global: bool,                 // Stored for any module rather than this one
+    pub definition: StructDefinition,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// Sync file for sharing a module's reverse-engineering knowledge (JSON, UTF-8):
+///
+/// ```json
+/// {
+///   "format": "dynadbg-annotations",
+///   "version": 1,
+///   "exported_at": "2026-10-16 09:30:00",
+///   "target_os": "linux",
+///   "module_name": "libgame.so",
+///   "module_hash": "9f3c…",
+///   "names":     [{ "offset": "0x1a2b0", "name": "Player_update", "updated_at": "…" }],
+///   "comments":  [{ "offset": "0x1a2c4", "title": "Damage", "body": "markdown", "updated_at": "…" }],
+///   "bookmarks": [{ "offset": "0x1a2b0", "title": "Hot path", "color": "#e5c07b", "updated_at": "…" }],
+///   "structs":   [{ "name": "Player", "global": false, "definition": { "name": "Player", "fields": [] }, "updated_at": "…" }]
+/// }
+/// ```
+///
+/// - Offsets are hex, relative to the module's image base.
+/// - Timestamps are UTC `YYYY-MM-DD HH:MM:SS`.
+/// - `names` holds user-given function names only; auto names (FUN_…) are left out.
+/// - `comments` are the module's notes.
+/// - `structs` use the dissect_memory definition format. `global` marks
+///   definitions not tied to a module.
+/// - `module_hash` is the build-id when known; a mismatch on import is
+///   reported, since offsets may not line up.
+///
+/// Imported names go to DynaDbg's caches, not to the Ghidra project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotationFile {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    pub target_os: String,
+    pub module_name: String,
+    #[serde(default)]
+    pub module_hash: Option<String>,
+    #[serde(default)]
+    pub names: Vec<NameRecord>,
+    #[serde(default)]
+    pub comments: Vec<CommentRecord>,
+    #[serde(default)]
+    pub bookmarks: Vec<BookmarkRecord>,
+    #[serde(default)]
+    pub structs: Vec<StructRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotationExportSummary {
+    pub path: String,
+    pub target_os: String,
+    pub module_name: String,
+    pub names: usize,
+    pub comments: usize,
+    pub bookmarks: usize,
+    pub structs: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MergeCounts {
+    pub added: usize,
+    pub updated: usize,                // Conflicts resolved in favour of the file
+    pub skipped: usize,                // Identical, kept ours, or nothing to attach to
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotationImportResult {
+    pub target_os: String,
+    pub module_name: String,
+    pub names: MergeCounts,
+    pub comments: MergeCounts,
+    pub bookmarks: MergeCounts,
+    pub structs: MergeCounts,
+    pub warnings: Vec<String>,
+}
+
+fn format_offset(offset: u64) -> String {
+    format!("0x{:x}", offset)
+}
+
+fn parse_offset(text: &str) -> Result<u64, String> {
+    ghidra_edits::parse_offset(text).ok_or_else(|| format!("Invalid offset '{}'", text))
+}
+
+/// Whether a conflicting record from the file replaces ours
+fn take_theirs(strategy: &str, ours_updated: &str, theirs_updated: Option<&str>) -> bool {
+    match strategy {
+        "theirs" => true,
+        "newest" => theirs_updated.is_some_and(|theirs| theirs > ours_updated),
+        _ => false,
+    }
+}
+
+/// (functions, updated_at) of the module's cached function list
+fn load_functions(conn: &Connection, target_os: &str, module_name: &str) -> Result<Option<(Vec<serde_json::Value>, String)>, String> {
+    let row: Option<(String, String)> = conn.query_row(
+        "SELECT functions_json, updated_at FROM ghidra_functions_cache WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).optional().map_err(|e| e.to_string())?;
+    let Some((sealed, updated_at)) = row else { return Ok(None) };
+    let json = secure_store::open_value("ghidra_functions_cache", "functions_json", sealed)?;
+    let functions = serde_json::from_str(&json).map_err(|e| format!("Failed to parse functions JSON: {}", e))?;
+    Ok(Some((functions, updated_at)))
+}
+
+// (module_name, name) → (definition, updated_at)
+type StoredStructs = HashMap<(String, String), (serde_json::Value, String)>;
+
+/// Struct definitions of the module and of the global scope
+fn load_structs(conn: &Connection, target_os: &str, module_name: &str) -> Result<StoredStructs, String> {
+    let mut stmt = conn.prepare(
+        "SELECT module_name, name, definition_json, updated_at FROM struct_definitions
+         WHERE target_os = ?1 AND (module_name = ?2 OR module_name = '')",
+    ).map_err(|e| e.to_string())?;
+    let rows: Vec<(String, String, String, String)> = stmt
+        .query_map(params![target_os, module_name], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    let mut structs = HashMap::new();
+    for (scope, name, sealed, updated_at) in rows {
+        let json = secure_store::open_value("struct_definitions", "definition_json", sealed)?;
+        // Parsed and re-serialized so it compares equal to the file's form
+        let definition: StructDefinition = serde_json::from_str(&json)
+            .map_err(|e| format!("Invalid struct definition '{}': {}", name, e))?;
+        structs.insert((scope, name), (serde_json::to_value(definition).map_err(|e| e.to_string())?, updated_at));
+    }
+    Ok(structs)
+}
+
+fn build_export(conn: &Connection, target_os: &str, module_name: &str) -> Result<AnnotationFile, String> {
+    let exported_at: String = conn.query_row("SELECT datetime('now')", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut names = Vec::new();
+    if let Some((functions, updated_at)) = load_functions(conn, target_os, module_name)? {
+        for function in &functions {
+            let (Some(address), Some(name)) = (function["address"].as_str(), function["name"].as_str()) else { continue };
+            if name.is_empty() || symbolizer::AUTO_NAME_PREFIXES.iter().any(|p| name.starts_with(p)) {
+                continue;
+            }
+            let Some(offset) = ghidra_edits::parse_offset(address) else { continue };
+            names.push(NameRecord { offset: format_offset(offset), name: name.to_string(), updated_at: Some(updated_at.clone()) });
+        }
+    }
+
+    let mut comments = Vec::new();
+    let mut bookmarks = Vec::new();
+    for annotation in annotations::load(conn, target_os, Some(module_name))? {
+        let offset = format_offset(annotation.module_offset);
+        let updated_at = Some(annotation.updated_at);
+        if annotation.kind == "note" {
+            comments.push(CommentRecord { offset, title: annotation.title, body: annotation.body, updated_at });
+        } else {
+            bookmarks.push(BookmarkRecord { offset, title: annotation.title, color: annotation.color, updated_at });
+        }
+    }
+
+    let mut structs: Vec<StructRecord> = load_structs(conn, target_os, module_name)?
+        .into_iter()
+        .map(|((scope, name), (definition, updated_at))| {
+            let definition = serde_json::from_value(definition).map_err(|e| format!("Invalid struct definition '{}': {}", name, e))?;
+            Ok(StructRecord { name, global: scope.is_empty(), definition, updated_at: Some(updated_at) })
+        })
+        .collect::<Result<_, String>>()?;
+    structs.sort_by(|a, b| (a.global, &a.name).cmp(&(b.global, &b.name)));
+
+    Ok(AnnotationFile {
+        format: FORMAT.to_string(),
+        version: VERSION,
+        exported_at,
+        target_os: target_os.to_string(),
+        module_name: module_name.to_string(),
+        module_hash: cache_versions::version(conn, target_os, module_name).module_hash,
+        names,
+        comments,
+        bookmarks,
+        structs,
+    })
+}
+
+fn merge_names(conn: &Connection, file: &AnnotationFile, strategy: &str, warnings: &mut Vec<String>) -> Result<MergeCounts, String> {
+    let mut counts = MergeCounts::default();
+    if file.names.is_empty() {
+        return Ok(counts);
+    }
+    let Some((functions, ours_updated)) = load_functions(conn, &file.target_os, &file.module_name)? else {
+        counts.skipped = file.names.len();
+        warnings.push(format!("No function list cached for {}; analyze it to import function names", file.module_name));
+        return Ok(counts);
+    };
+    let ours: HashMap<u64, &str> = functions.iter()
+        .filter_map(|f| Some((ghidra_edits::parse_offset(f["address"].as_str()?)?, f["name"].as_str()?)))
+        .collect();
+
+    let mut renames = Vec::new();
+    let mut unmatched = 0;
+    for record in &file.names {
+        let offset = parse_offset(&record.offset)?;
+        let Some(&current) = ours.get(&offset) else {
+            unmatched += 1;
+            counts.skipped += 1;
+            continue;
+        };
+        if current == record.name {
+            counts.skipped += 1;
+        } else if current.is_empty() || symbolizer::AUTO_NAME_PREFIXES.iter().any(|p| current.starts_with(p)) {
+            counts.added += 1;
+            renames.push((format_offset(offset), record.name.clone()));
+        } else if take_theirs(strategy, &ours_updated, record.updated_at.as_deref()) {
+            counts.updated += 1;
+            renames.push((format_offset(offset), record.name.clone()));
+        } else {
+            counts.skipped += 1;
+        }
+    }
+    if unmatched > 0 {
+        warnings.push(format!("{} function name(s) matched no function in {}", unmatched, file.module_name));
+    }
+    if !renames.is_empty() {
+        // Decompilations and xrefs mention the old names
+        cache_versions::invalidate(conn, &file.target_os, &file.module_name, "rename")?;
+        ghidra_edits::rename_functions(conn, &file.target_os, &file.module_name, &renames)?;
+    }
+    Ok(counts)
+}
+
+/// Merge the file's notes and bookmarks; returns their (comments, bookmarks) counts
+fn merge_annotations(
+    conn: &Connection,
+    file: &AnnotationFile,
+    strategy: &str,
+) -> Result<(MergeCounts, MergeCounts), String> {
+    let ours: HashMap<(String, u64), annotations::Annotation> = annotations::load(conn, &file.target_os, Some(&file.module_name))?
+        .into_iter()
+        .map(|a| ((a.kind.clone(), a.module_offset), a))
+        .collect();
+    let records = file.comments.iter()
+        .map(|c| ("note", &c.offset, &c.title, c.body.as_str(), None, &c.updated_at))
+        .chain(file.bookmarks.iter().map(|b| ("bookmark", &b.offset, &b.title, "", b.color.as_deref(), &b.updated_at)));
+
+    let (mut comments, mut bookmarks) = (MergeCounts::default(), MergeCounts::default());
+    for (kind, offset, title, body, color, updated_at) in records {
+        let offset = parse_offset(offset)?;
+        let counts = if kind == "note" { &mut comments } else { &mut bookmarks };
+        let current = ours.get(&(kind.to_string(), offset));
+        match current {
+            None => counts.added += 1,
+            Some(a) if a.title == *title && a.body == body && a.color.as_deref() == color => {
+                counts.skipped += 1;
+                continue;
+            }
+            Some(a) if take_theirs(strategy, &a.updated_at, updated_at.as_deref()) => counts.updated += 1,
+            Some(_) => {
+                counts.skipped += 1;
+                continue;
+            }
+        }
+        annotations::upsert(conn, &file.target_os, &file.module_name, offset, (kind, title, body, color))?;
+    }
+    Ok((comments, bookmarks))
+}
+
+fn merge_structs(conn: &Connection, file: &AnnotationFile, strategy: &str) -> Result<MergeCounts, String> {
+    let ours = load_structs(conn, &file.target_os, &file.module_name)?;
+    let mut counts = MergeCounts::default();
+    for record in &file.structs {
+        let scope = if record.global { String::new() } else { file.module_name.clone() };
+        let theirs = serde_json::to_value(&record.definition).map_err(|e| e.to_string())?;
+        match ours.get(&(scope.clone(), record.name.clone())) {
+            None => counts.added += 1,
+            Some((definition, _)) if *definition == theirs => {
+                counts.skipped += 1;
+                continue;
+            }
+            Some((_, ours_updated)) if take_theirs(strategy, ours_updated, record.updated_at.as_deref()) => counts.updated += 1,
+            Some(_) => {
+                counts.skipped += 1;
+                continue;
+            }
+        }
+        let json = serde_json::to_string(&record.definition).map_err(|e| e.to_string())?;
+        struct_dissector::store_definition(conn, &file.target_os, &scope, &record.name, &json)?;
+    }
+    Ok(counts)
+}
+
+fn apply_import(conn: &Connection, file: &AnnotationFile, strategy: &str) -> Result<AnnotationImportResult, String> {
+    let mut warnings = Vec::new();
+    let local_hash = cache_versions::version(conn, &file.target_os, &file.module_name).module_hash;
+    if let (Some(ours), Some(theirs)) = (&local_hash, &file.module_hash) {
+        if ours != theirs {
+            warnings.push(format!(
+                "Exported from a different build of {} ({} here, {} in the file); offsets may not line up",
+                file.module_name, ours, theirs
+            ));
+        }
+    }
+
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let names = merge_names(&tx, file, strategy, &mut warnings)?;
+    let (comments, bookmarks) = merge_annotations(&tx, file, strategy)?;
+    let structs = merge_structs(&tx, file, strategy)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(AnnotationImportResult {
+        target_os: file.target_os.clone(),
+        module_name: file.module_name.clone(),
+        names,
+        comments,
+        bookmarks,
+        structs,
+        warnings,
+    })
+}
+
+fn connected_os(state: &AppStateType) -> String {
+    state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.target_os.clone()))
+        .unwrap_or_default()
+}
+
+/// Write a module's function names, notes, bookmarks and struct definitions
+/// to `path` in the sync format
+#[tauri::command]
+pub async fn export_annotations(
+    state: tauri::State<'_, AppStateType>,
+    module_name: String,
+    path: String,
+    target_os: Option<String>,
+) -> Result<AnnotationExportSummary, String> {
+    let target_os = target_os.unwrap_or_else(|| connected_os(state.inner()));
+    db::run(move |conn| {
+        let file = build_export(conn, &target_os, &module_name)?;
+        let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        Ok(AnnotationExportSummary {
+            path,
+            target_os,
+            module_name,
+            names: file.names.len(),
+            comments: file.comments.len(),
+            bookmarks: file.bookmarks.len(),
+            structs: file.structs.len(),
+        })
+    }).await
+}
+
+/// Merge a sync file into the local database. On conflicts `merge_strategy`
+/// keeps ours, takes theirs, or keeps whichever was updated last ("newest").
+/// `module_name` / `target_os` override the file's, e.g. for a renamed library.
+#[tauri::command]
+pub async fn import_annotations(
+    path: String,
+    merge_strategy: String,
+    module_name: Option<String>,
+    target_os: Option<String>,
+) -> Result<AnnotationImportResult, String> {
+    let strategy = merge_strategy.trim().to_lowercase();
+    if !MERGE_STRATEGIES.contains(&strategy.as_str()) {
+        return Err(format!("Unknown merge strategy '{}' (expected one of {})", merge_strategy, MERGE_STRATEGIES.join(", ")));
+    }
+    let json = tokio::fs::read_to_string(&path).await
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut file: AnnotationFile = serde_json::from_str(&json)
+        .map_err(|e| format!("Not a valid annotation file: {}", e))?;
+    if file.format != FORMAT {
+        return Err(format!("Not a DynaDbg annotation file (format '{}')", file.format));
+    }
+    if file.version > VERSION {
+        return Err(format!("Annotation file version {} is newer than this build supports ({})", file.version, VERSION));
+    }
+    if let Some(module_name) = module_name {
+        file.module_name = module_name;
+    }
+    if let Some(target_os) = target_os {
+        file.target_os = target_os;
+    }
+    db::run(move |conn| apply_import(conn, &file, &strategy)).await
+}
@@ -0,0 +1,245 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::state::{AppStateType, ModuleInfo};
+use crate::db;
+
+pub const KINDS: [&str; 2] = ["bookmark", "note"];
+
+/// A bookmark or markdown note anchored to an address; module-relative so it
+/// survives ASLR and restarts. One of each kind per address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Annotation {
+    pub id: i64,
+    pub target_os: String,
+    pub module_name: String,          // Empty for annotations outside any module
+    pub module_offset: u64,           // Absolute address when module_name is empty
+    pub address: Option<u64>,         // In the attached process; None while the module is not loaded
+    pub kind: String,                 // "bookmark" | "note"
+    pub title: String,
+    pub body: String,                 // Markdown
+    pub color: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotationInput {
+    #[serde(default)]
+    pub address: Option<u64>,         // Resolved against the attached modules
+    #[serde(default)]
+    pub module_name: Option<String>,  // With module_offset, for modules not loaded
+    #[serde(default)]
+    pub module_offset: Option<u64>,
+    pub kind: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Create the annotations table (migration; see db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS annotations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            module_offset INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            title TEXT NOT NULL,
+            body TEXT NOT NULL,
+            color TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            UNIQUE(target_os, module_name, module_offset, kind)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+const SELECT_COLUMNS: &str = "id, target_os, module_name, module_offset, kind, title, body, color, created_at, updated_at";
+
+fn row_to_annotation(row: &rusqlite::Row) -> rusqlite::Result<Annotation> {
+    Ok(Annotation {
+        id: row.get(0)?,
+        target_os: row.get(1)?,
+        module_name: row.get(2)?,
+        module_offset: row.get::<_, i64>(3)? as u64,
+        address: None,
+        kind: row.get(4)?,
+        title: row.get(5)?,
+        body: row.get(6)?,
+        color: row.get(7)?,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
+/// (target_os, attached modules)
+fn target_info(state: &AppStateType) -> Result<(String, Vec<ModuleInfo>), String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    let target_os = state_guard.server_info.as_ref()
+        .map(|info| info.target_os.clone())
+        .unwrap_or_default();
+    Ok((target_os, state_guard.attached_modules.clone()))
+}
+
+fn with_address(mut annotation: Annotation, modules: &[ModuleInfo]) -> Annotation {
+    annotation.address = if annotation.module_name.is_empty() {
+        Some(annotation.module_offset)
+    } else {
+        modules.iter()
+            .find(|m| m.modulename == annotation.module_name)
+            .map(|m| m.base + annotation.module_offset)
+    };
+    annotation
+}
+
+/// (module_name, module_offset) an input points at, if it names a location
+fn anchor(input: &AnnotationInput, modules: &[ModuleInfo]) -> Option<(String, u64)> {
+    if let Some(address) = input.address {
+        return Some(modules.iter()
+            .find(|m| address >= m.base && address < m.base.saturating_add(m.size))
+            .map(|m| (m.modulename.clone(), address - m.base))
+            .unwrap_or_else(|| (String::new(), address)));
+    }
+    Some((input.module_name.clone().unwrap_or_default(), input.module_offset?))
+}
+
+/// Normalized (kind, title, body, color) of an input
+fn validate(input: &AnnotationInput) -> Result<(String, String, String, Option<String>), String> {
+    let kind = input.kind.trim().to_lowercase();
+    if !KINDS.contains(&kind.as_str()) {
+        return Err(format!("Unknown annotation kind '{}' (expected one of {})", input.kind, KINDS.join(", ")));
+    }
+    let title = input.title.as_deref().unwrap_or_default().trim().to_string();
+    let body = input.body.clone().unwrap_or_default();
+    if kind == "note" && body.trim().is_empty() && title.is_empty() {
+        return Err("A note needs a title or text".to_string());
+    }
+    let color = input.color.as_deref().map(str::trim).filter(|c| !c.is_empty()).map(str::to_string);
+    Ok((kind, title, body, color))
+}
+
+fn get_annotation(conn: &Connection, id: i64) -> Result<Annotation, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM annotations WHERE id = ?1", SELECT_COLUMNS),
+        params![id],
+        row_to_annotation,
+    ).map_err(|e| format!("Annotation {} not found: {}", id, e))
+}
+
+/// Insert or replace the annotation of `kind` at a location (import uses
+/// this too); returns its id
+pub fn upsert(
+    conn: &Connection,
+    target_os: &str,
+    module_name: &str,
+    module_offset: u64,
+    fields: (&str, &str, &str, Option<&str>),
+) -> Result<i64, String> {
+    let (kind, title, body, color) = fields;
+    conn.query_row(
+        "INSERT INTO annotations (target_os, module_name, module_offset, kind, title, body, color, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'), datetime('now'))
+         ON CONFLICT(target_os, module_name, module_offset, kind) DO UPDATE SET
+             title = excluded.title, body = excluded.body, color = excluded.color, updated_at = excluded.updated_at
+         RETURNING id",
+        params![target_os, module_name, module_offset as i64, kind, title, body, color],
+        |row| row.get(0),
+    ).map_err(|e| e.to_string())
+}
+
+/// Annotations of one target OS, optionally of one module, ordered by location
+pub fn load(conn: &Connection, target_os: &str, module_name: Option<&str>) -> Result<Vec<Annotation>, String> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM annotations WHERE target_os = ?1 AND (?2 IS NULL OR module_name = ?2)
+         ORDER BY module_name, module_offset, kind",
+        SELECT_COLUMNS
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![target_os, module_name], row_to_annotation)
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+/// Add a bookmark or note at `address` (or module + offset). A location
+/// holds one of each kind; adding again replaces it.
+#[tauri::command]
+pub async fn add_annotation(state: tauri::State<'_, AppStateType>, annotation: AnnotationInput) -> Result<Annotation, String> {
+    let (kind, title, body, color) = validate(&annotation)?;
+    let (target_os, modules) = target_info(state.inner())?;
+    let (module_name, module_offset) = anchor(&annotation, &modules)
+        .ok_or("Give an address, or a module name and offset")?;
+    db::run(move |conn| {
+        let id = upsert(conn, &target_os, &module_name, module_offset, (&kind, &title, &body, color.as_deref()))?;
+        Ok(with_address(get_annotation(conn, id)?, &modules))
+    }).await
+}
+
+/// Change the text of an annotation; giving a location moves it
+#[tauri::command]
+pub async fn update_annotation(
+    state: tauri::State<'_, AppStateType>,
+    id: i64,
+    annotation: AnnotationInput,
+) -> Result<Annotation, String> {
+    let (kind, title, body, color) = validate(&annotation)?;
+    let (_, modules) = target_info(state.inner())?;
+    db::run(move |conn| {
+        let current = get_annotation(conn, id)?;
+        let (module_name, module_offset) = anchor(&annotation, &modules)
+            .unwrap_or((current.module_name, current.module_offset));
+        conn.execute(
+            "UPDATE annotations SET module_name = ?1, module_offset = ?2, kind = ?3, title = ?4, body = ?5, color = ?6,
+                    updated_at = datetime('now')
+             WHERE id = ?7",
+            params![module_name, module_offset as i64, kind, title, body, color, id],
+        ).map_err(|e| match e {
+            rusqlite::Error::SqliteFailure(ref f, _) if f.code == rusqlite::ErrorCode::ConstraintViolation => {
+                format!("There already is a {} at that location", kind)
+            }
+            e => e.to_string(),
+        })?;
+        Ok(with_address(get_annotation(conn, id)?, &modules))
+    }).await
+}
+
+#[tauri::command]
+pub async fn delete_annotation(id: i64) -> Result<bool, String> {
+    db::run(move |conn| {
+        let deleted = conn.execute("DELETE FROM annotations WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        Ok(deleted > 0)
+    }).await
+}
+
+/// Annotations for inline markers. With `module_name`, `start`..`end` are
+/// module offsets; without, they are addresses in the attached process (and
+/// annotations of modules not loaded are left out).
+#[tauri::command]
+pub async fn list_annotations(
+    state: tauri::State<'_, AppStateType>,
+    module_name: Option<String>,
+    start: Option<u64>,
+    end: Option<u64>,
+    kind: Option<String>,
+    target_os: Option<String>,
+) -> Result<Vec<Annotation>, String> {
+    let (connected_os, modules) = target_info(state.inner())?;
+    let target_os = target_os.unwrap_or(connected_os);
+    let filter_module = module_name.clone();
+    let annotations = db::run(move |conn| load(conn, &target_os, filter_module.as_deref())).await?;
+    let in_range = |position: Option<u64>| match (start, end) {
+        (None, None) => true,
+        _ => position.is_some_and(|p| start.is_none_or(|s| p >= s) && end.is_none_or(|e| p < e)),
+    };
+    Ok(annotations.into_iter()
+        .map(|a| with_address(a, &modules))
+        .filter(|a| kind.as_ref().is_none_or(|k| &a.kind == k))
+        .filter(|a| in_range(if module_name.is_some() { Some(a.module_offset) } else { a.address }))
+        .collect())
+}
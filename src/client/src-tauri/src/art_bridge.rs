@@ -0,0 +1,646 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::breakpoints::{self, BreakpointDefinition};
+use crate::build_id::{u16_at, u32_at, u64_at};
+use crate::memory_regions::{self, MemoryRegion};
+use crate::state::AppStateType;
+use crate::symbolizer::Symbolizer;
+use crate::{read_chunks_parallel, server_address};
+
+const READ_CHUNK: usize = 1024 * 1024;
+const PARALLEL_READS: usize = 8;
+const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const DEX_HEADER_SIZE: usize = 0x70;
+const MAX_DEX_SIZE: u32 = 256 * 1024 * 1024;
+// Per mapping searched for embedded dex images (apk / vdex / jar)
+const MAX_CONTAINER_SCAN: u64 = 512 * 1024 * 1024;
+// Total bytes of LinearAlloc and image spaces read when searching ArtMethods
+const MAX_ART_SCAN: u64 = 1024 * 1024 * 1024;
+const ENDIAN_CONSTANT: u32 = 0x1234_5678;
+const ACC_NATIVE: u32 = 0x0100;
+// Method access flags as they appear in the dex file; ART keeps them in the
+// low bits of ArtMethod::access_flags_ and adds runtime flags above
+const DEX_METHOD_FLAGS: u32 = 0x0FFF;
+
+/// Dex image found in the target's memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexFileInfo {
+    pub address: u64,
+    pub size: u32,
+    pub version: String,                 // "035".."041", or "cdex" for compact dex
+    pub location: Option<String>,        // Mapping it was found in
+    pub class_count: u32,
+    pub method_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexMethod {
+    pub method_index: u32,
+    pub name: String,
+    pub signature: String,               // "(ILjava/lang/String;)V"
+    pub access_flags: u32,
+    pub code_offset: Option<u32>,        // None for abstract and native methods
+    pub is_direct: bool,                 // Static, private or constructor
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexClassSummary {
+    pub descriptor: String,              // "Lcom/example/Foo;"
+    pub name: String,                    // "com.example.Foo"
+    pub superclass: Option<String>,
+    pub access_flags: u32,
+    pub method_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexClass {
+    pub dex_address: u64,
+    pub descriptor: String,
+    pub name: String,
+    pub superclass: Option<String>,
+    pub access_flags: u32,
+    pub methods: Vec<DexMethod>,
+}
+
+/// Runtime state of a java method. `entry_point` is where ART jumps to run it;
+/// for interpreted methods that is a bridge shared by every such method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaMethodEntry {
+    pub class_name: String,
+    pub method: DexMethod,
+    pub dex_address: u64,
+    pub art_method: Option<u64>,
+    pub entry_point: Option<u64>,
+    pub entry_kind: String,              // "aot" | "jit" | "interpreter" | "nterp" | "jni_trampoline" | "resolution" | "compiled" | "unresolved"
+    pub entry_symbol: Option<String>,
+    pub jni_function: Option<u64>,       // Registered native implementation of a native method
+    pub candidates: usize,               // ArtMethods that matched; more than one is ambiguous
+}
+
+struct ParsedDex {
+    classes: Vec<DexClass>,
+}
+
+// Pid -> dex images found by the last enumeration
+type DexFileList = Option<(u32, Vec<DexFileInfo>)>;
+// (pid, dex address) -> parsed image
+type ParsedDexCache = HashMap<(u32, u64), Arc<ParsedDex>>;
+
+static DEX_FILES: Lazy<Mutex<DexFileList>> = Lazy::new(|| Mutex::new(None));
+static PARSED: Lazy<Mutex<ParsedDexCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn uleb128(data: &[u8], pos: &mut usize) -> Option<u32> {
+    let mut result = 0u32;
+    for shift in (0..35).step_by(7) {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        result |= ((byte & 0x7F) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+    }
+    None
+}
+
+/// Dex header at the start of `header`: (version, file size, class count, method count)
+fn dex_header(header: &[u8]) -> Option<(String, u32, u32, u32)> {
+    let version = match header.get(0..8)? {
+        [b'd', b'e', b'x', b'\n', v @ .., 0] => String::from_utf8_lossy(v).to_string(),
+        [b'c', b'd', b'e', b'x', ..] => "cdex".to_string(),
+        _ => return None,
+    };
+    let file_size = u32_at(header, 32, false)?;
+    if u32_at(header, 40, false)? != ENDIAN_CONSTANT || file_size < DEX_HEADER_SIZE as u32 || file_size > MAX_DEX_SIZE {
+        return None;
+    }
+    Some((version, file_size, u32_at(header, 96, false)?, u32_at(header, 88, false)?))
+}
+
+struct DexReader<'a> {
+    data: &'a [u8],
+    strings: Vec<Option<String>>,
+}
+
+impl<'a> DexReader<'a> {
+    fn header(&self, offset: usize) -> usize {
+        u32_at(self.data, offset, false).unwrap_or(0) as usize
+    }
+
+    /// MUTF-8 string; the rare surrogate pairs decode as replacement characters
+    fn string(&mut self, index: u32) -> String {
+        let index = index as usize;
+        if let Some(Some(cached)) = self.strings.get(index) {
+            return cached.clone();
+        }
+        let (count, table) = (self.header(56), self.header(60));
+        if index >= count {
+            return String::new();
+        }
+        let mut pos = u32_at(self.data, table + index * 4, false).unwrap_or(0) as usize;
+        let _utf16_len = uleb128(self.data, &mut pos);
+        let bytes = self.data.get(pos..).unwrap_or_default();
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        let value = String::from_utf8_lossy(&bytes[..end]).to_string();
+        if self.strings.len() < count {
+            self.strings.resize(count, None);
+        }
+        self.strings[index] = Some(value.clone());
+        value
+    }
+
+    fn type_name(&mut self, index: u32) -> String {
+        if index as usize >= self.header(64) {
+            return String::new();
+        }
+        let descriptor = u32_at(self.data, self.header(68) + index as usize * 4, false).unwrap_or(u32::MAX);
+        self.string(descriptor)
+    }
+
+    fn proto_signature(&mut self, index: u16) -> String {
+        let proto = self.header(76) + index as usize * 12;
+        let return_type = u32_at(self.data, proto + 4, false).unwrap_or(u32::MAX);
+        let parameters = u32_at(self.data, proto + 8, false).unwrap_or(0) as usize;
+        let mut signature = String::from("(");
+        if parameters != 0 {
+            let count = u32_at(self.data, parameters, false).unwrap_or(0) as usize;
+            for i in 0..count.min(255) {
+                let type_index = u16_at(self.data, parameters + 4 + i * 2, false).unwrap_or(u16::MAX);
+                signature.push_str(&self.type_name(type_index as u32));
+            }
+        }
+        signature.push(')');
+        signature.push_str(&self.type_name(return_type));
+        signature
+    }
+
+    fn method(&mut self, method_index: u32, access_flags: u32, code_offset: u32, is_direct: bool) -> DexMethod {
+        let method_id = self.header(92) + method_index as usize * 8;
+        let proto = u16_at(self.data, method_id + 2, false).unwrap_or(0);
+        let name = u32_at(self.data, method_id + 4, false).unwrap_or(u32::MAX);
+        DexMethod {
+            method_index,
+            name: self.string(name),
+            signature: self.proto_signature(proto),
+            access_flags,
+            code_offset: (code_offset != 0).then_some(code_offset),
+            is_direct,
+        }
+    }
+
+    fn class_methods(&mut self, class_data: usize) -> Vec<DexMethod> {
+        let data = self.data;
+        let mut pos = class_data;
+        let mut sizes = [0u32; 4];
+        for size in sizes.iter_mut() {
+            *size = uleb128(data, &mut pos).unwrap_or(0);
+        }
+        // Static and instance fields come first: (field_idx_diff, access_flags)
+        for _ in 0..(sizes[0] as u64 + sizes[1] as u64) * 2 {
+            if uleb128(data, &mut pos).is_none() {
+                return Vec::new();
+            }
+        }
+        let mut methods = Vec::new();
+        for (count, is_direct) in [(sizes[2], true), (sizes[3], false)] {
+            let mut method_index = 0u32;
+            for _ in 0..count {
+                let (Some(diff), Some(flags), Some(code)) = (uleb128(data, &mut pos), uleb128(data, &mut pos), uleb128(data, &mut pos)) else {
+                    return methods;
+                };
+                method_index = method_index.wrapping_add(diff);
+                methods.push(self.method(method_index, flags, code, is_direct));
+            }
+        }
+        methods
+    }
+}
+
+fn java_name(descriptor: &str) -> String {
+    descriptor.strip_prefix('L').and_then(|d| d.strip_suffix(';')).unwrap_or(descriptor).replace('/', ".")
+}
+
+/// "com.example.Foo", "com/example/Foo" or "Lcom/example/Foo;" as a descriptor
+fn to_descriptor(class_name: &str) -> String {
+    let name = class_name.trim();
+    if name.starts_with('L') && name.ends_with(';') {
+        return name.to_string();
+    }
+    format!("L{};", name.replace('.', "/"))
+}
+
+fn parse_dex(address: u64, data: &[u8]) -> Result<ParsedDex, String> {
+    let (version, _, class_count, _) = dex_header(data).ok_or("Not a dex image")?;
+    if version == "cdex" {
+        return Err("Compact dex (cdex) images are not supported".to_string());
+    }
+    let mut reader = DexReader { data, strings: Vec::new() };
+    let class_defs = reader.header(100);
+    let mut classes = Vec::with_capacity(class_count as usize);
+    for i in 0..class_count as usize {
+        let def = class_defs + i * 32;
+        let (Some(class_index), Some(access_flags), Some(super_index), Some(class_data)) = (
+            u32_at(data, def, false),
+            u32_at(data, def + 4, false),
+            u32_at(data, def + 8, false),
+            u32_at(data, def + 24, false),
+        ) else {
+            break;
+        };
+        let descriptor = reader.type_name(class_index);
+        classes.push(DexClass {
+            dex_address: address,
+            name: java_name(&descriptor),
+            descriptor,
+            // NO_INDEX (0xffffffff) for java.lang.Object
+            superclass: (super_index != u32::MAX).then(|| java_name(&reader.type_name(super_index))),
+            access_flags,
+            methods: if class_data != 0 { reader.class_methods(class_data as usize) } else { Vec::new() },
+        });
+    }
+    Ok(ParsedDex { classes })
+}
+
+/// Pid of the attached Android process
+fn ensure_android(state: &AppStateType) -> Result<u32, String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    let target_os = state_guard.server_info.as_ref().map(|info| info.target_os.as_str()).unwrap_or_default();
+    if target_os != "android" {
+        return Err("Java method resolution is only available for Android targets".to_string());
+    }
+    state_guard.attached_process.as_ref().map(|p| p.pid).ok_or_else(|| "No process attached".to_string())
+}
+
+/// Read `[start, start + size)` in parallel chunks, calling `visit(address, bytes)`
+/// for each readable chunk in address order. Each chunk after the first is
+/// prefixed with the last `overlap` bytes of the previous one.
+async fn read_range(
+    host: &str,
+    port: u16,
+    start: u64,
+    size: u64,
+    overlap: usize,
+    mut visit: impl FnMut(u64, &[u8]),
+) {
+    let chunks: Vec<(u64, usize)> = (0..size.div_ceil(READ_CHUNK as u64))
+        .map(|i| (start + i * READ_CHUNK as u64, (size - i * READ_CHUNK as u64).min(READ_CHUNK as u64) as usize))
+        .collect();
+    let mut tail: Vec<u8> = Vec::new();
+    let mut tail_end = 0u64;
+    for batch in chunks.chunks(PARALLEL_READS) {
+        let results = read_chunks_parallel(host, port, batch, READ_TIMEOUT).await;
+        for (&(address, _), data) in batch.iter().zip(results) {
+            let Some(data) = data else {
+                tail.clear();
+                continue;
+            };
+            if !tail.is_empty() && tail_end == address {
+                let mut joined = std::mem::take(&mut tail);
+                let joined_start = address - joined.len() as u64;
+                joined.extend_from_slice(&data);
+                visit(joined_start, &joined);
+            } else {
+                visit(address, &data);
+            }
+            tail = data[data.len().saturating_sub(overlap)..].to_vec();
+            tail_end = address + data.len() as u64;
+        }
+    }
+}
+
+async fn read_exact(host: &str, port: u16, address: u64, size: usize) -> Option<Vec<u8>> {
+    let mut out = vec![0u8; size];
+    let mut covered = 0;
+    read_range(host, port, address, size as u64, 0, |at, bytes| {
+        let from = (at - address) as usize;
+        out[from..from + bytes.len()].copy_from_slice(bytes);
+        covered += bytes.len();
+    }).await;
+    (covered == size).then_some(out)
+}
+
+/// Mappings that can hold dex images: dex/apk/jar/vdex files and ART's
+/// anonymous "dalvik-DEX data" / in-memory dex mappings
+fn is_dex_container(region: &MemoryRegion) -> bool {
+    let Some(path) = region.mapped_file.as_deref() else { return false };
+    let lower = path.to_ascii_lowercase();
+    [".dex", ".apk", ".jar", ".vdex", ".odex", "dalvik-dex", "dalvik-classes"].iter().any(|s| lower.contains(s))
+}
+
+/// Find dex images in the target's memory: mapped dex files and dex data
+/// embedded in apk / jar / vdex mappings
+#[tauri::command]
+pub async fn list_dex_files(state: tauri::State<'_, AppStateType>, refresh: Option<bool>) -> Result<Vec<DexFileInfo>, String> {
+    let pid = ensure_android(state.inner())?;
+    if !refresh.unwrap_or(false) {
+        if let Some((cached_pid, cached)) = DEX_FILES.lock().map_err(|e| e.to_string())?.as_ref() {
+            if *cached_pid == pid {
+                return Ok(cached.clone());
+            }
+        }
+    }
+    let (host, port) = server_address()?;
+    let regions = memory_regions::get_cached_regions(Some(state.inner()), true).await?;
+
+    let mut candidates: Vec<(u64, Option<String>)> = Vec::new();
+    for region in regions.iter().filter(|r| r.readable && is_dex_container(r)) {
+        let dex = memchr::memmem::Finder::new(b"dex\n0");
+        // Compact dex only appears in vdex files
+        let cdex = memchr::memmem::Finder::new(b"cdex");
+        let is_vdex = region.mapped_file.as_deref().is_some_and(|p| p.ends_with(".vdex"));
+        let mut found = Vec::new();
+        read_range(&host, port, region.base, region.size.min(MAX_CONTAINER_SCAN), 8, |at, bytes| {
+            // Dex images in containers are at least 4-byte aligned
+            found.extend(dex.find_iter(bytes).map(|i| at + i as u64).filter(|a| a % 4 == 0));
+            if is_vdex {
+                found.extend(cdex.find_iter(bytes).map(|i| at + i as u64).filter(|a| a % 4 == 0));
+            }
+        }).await;
+        found.sort_unstable();
+        found.dedup();
+        candidates.extend(found.into_iter().map(|a| (a, region.mapped_file.clone())));
+    }
+
+    let mut dex_files: Vec<DexFileInfo> = Vec::new();
+    for (address, location) in candidates {
+        // Skip magic bytes inside an image already found
+        if dex_files.last().is_some_and(|d| address < d.address + d.size as u64) {
+            continue;
+        }
+        let Some(header) = read_exact(&host, port, address, DEX_HEADER_SIZE).await else { continue };
+        let Some((version, size, class_count, method_count)) = dex_header(&header) else { continue };
+        dex_files.push(DexFileInfo { address, size, version, location, class_count, method_count });
+    }
+    *DEX_FILES.lock().map_err(|e| e.to_string())? = Some((pid, dex_files.clone()));
+    Ok(dex_files)
+}
+
+async fn parsed_dex(pid: u32, dex_address: u64) -> Result<Arc<ParsedDex>, String> {
+    if let Some(parsed) = PARSED.lock().map_err(|e| e.to_string())?.get(&(pid, dex_address)) {
+        return Ok(parsed.clone());
+    }
+    let (host, port) = server_address()?;
+    let header = read_exact(&host, port, dex_address, DEX_HEADER_SIZE).await
+        .ok_or_else(|| format!("Failed to read the dex header at 0x{:x}", dex_address))?;
+    let (_, size, _, _) = dex_header(&header).ok_or_else(|| format!("No dex image at 0x{:x}", dex_address))?;
+    let data = read_exact(&host, port, dex_address, size as usize).await
+        .ok_or_else(|| format!("Failed to read the dex image at 0x{:x}", dex_address))?;
+    let parsed = Arc::new(tokio::task::spawn_blocking(move || parse_dex(dex_address, &data))
+        .await
+        .map_err(|e| e.to_string())??);
+    PARSED.lock().map_err(|e| e.to_string())?.insert((pid, dex_address), parsed.clone());
+    Ok(parsed)
+}
+
+/// Classes of one dex image, optionally filtered by a case-insensitive name substring
+#[tauri::command]
+pub async fn list_dex_classes(
+    state: tauri::State<'_, AppStateType>,
+    dex_address: u64,
+    filter: Option<String>,
+) -> Result<Vec<DexClassSummary>, String> {
+    let parsed = parsed_dex(ensure_android(state.inner())?, dex_address).await?;
+    let filter = filter.map(|f| f.to_lowercase()).filter(|f| !f.is_empty());
+    Ok(parsed.classes.iter()
+        .filter(|c| filter.as_ref().is_none_or(|f| c.name.to_lowercase().contains(f)))
+        .map(|c| DexClassSummary {
+            descriptor: c.descriptor.clone(),
+            name: c.name.clone(),
+            superclass: c.superclass.clone(),
+            access_flags: c.access_flags,
+            method_count: c.methods.len(),
+        })
+        .collect())
+}
+
+/// Definition of a class in the dex images, searching `dex_address` or every found image
+async fn find_class(state: &tauri::State<'_, AppStateType>, class_name: &str, dex_address: Option<u64>) -> Result<DexClass, String> {
+    let pid = ensure_android(state.inner())?;
+    let descriptor = to_descriptor(class_name);
+    let addresses = match dex_address {
+        Some(address) => vec![address],
+        None => list_dex_files(state.clone(), None).await?.iter().filter(|d| d.version != "cdex").map(|d| d.address).collect(),
+    };
+    for address in addresses {
+        let Ok(parsed) = parsed_dex(pid, address).await else { continue };
+        if let Some(class) = parsed.classes.iter().find(|c| c.descriptor == descriptor) {
+            return Ok(class.clone());
+        }
+    }
+    Err(format!("Class {} not found in the loaded dex images", java_name(&descriptor)))
+}
+
+#[tauri::command]
+pub async fn get_dex_class(
+    state: tauri::State<'_, AppStateType>,
+    class_name: String,
+    dex_address: Option<u64>,
+) -> Result<DexClass, String> {
+    find_class(&state, &class_name, dex_address).await
+}
+
+/// ArtMethod field offsets of the supported runtime layouts (64-bit)
+struct ArtLayout {
+    method_index: usize,
+    code_item_offset: Option<usize>,     // dex_code_item_offset_ (Android 9-11)
+    data: usize,                         // Code item pointer (12+), JNI function for native methods
+    entry_point: usize,
+}
+
+const ART_LAYOUTS: [ArtLayout; 2] = [
+    // Android 12+: declaring_class_, access_flags_, dex_method_index_, method_index_, hotness, data_, entry_point_
+    ArtLayout { method_index: 8, code_item_offset: None, data: 16, entry_point: 24 },
+    // Android 9-11: dex_code_item_offset_ before dex_method_index_
+    ArtLayout { method_index: 12, code_item_offset: Some(8), data: 24, entry_point: 32 },
+];
+
+struct ArtCandidate {
+    address: u64,
+    data: u64,
+    entry_point: u64,
+}
+
+/// Whether the ArtMethod at `pos` of `bytes` belongs to `method` of the dex at `dex_address`
+fn art_method_matches(bytes: &[u8], pos: usize, layout: &ArtLayout, dex_address: u64, method: &DexMethod) -> bool {
+    if u32_at(bytes, pos + layout.method_index, false) != Some(method.method_index) {
+        return false;
+    }
+    let Some(flags) = u32_at(bytes, pos + 4, false) else { return false };
+    if flags & DEX_METHOD_FLAGS != method.access_flags & DEX_METHOD_FLAGS {
+        return false;
+    }
+    match (method.code_offset, layout.code_item_offset) {
+        (Some(code), Some(offset)) => u32_at(bytes, pos + offset, false) == Some(code),
+        // Low bit marks a compact dex code item
+        (Some(code), None) => u64_at(bytes, pos + layout.data, false).map(|d| d & !1) == Some(dex_address + code as u64),
+        (None, _) => true,
+    }
+}
+
+/// Search ART's LinearAlloc and image spaces for the ArtMethods of `methods`
+/// (all of one dex image). Methods with code are matched by their code item,
+/// which is unique; the others by index and flags alone.
+async fn find_art_methods(
+    regions: &[MemoryRegion],
+    dex_address: u64,
+    methods: &[DexMethod],
+) -> Result<Vec<Vec<ArtCandidate>>, String> {
+    let (host, port) = server_address()?;
+    let executable = |address: u64| regions.iter().any(|r| r.executable && address >= r.base && address < r.base + r.size);
+    let mut found: Vec<Vec<ArtCandidate>> = methods.iter().map(|_| Vec::new()).collect();
+    let mut budget = MAX_ART_SCAN;
+    let spaces = regions.iter().filter(|r| {
+        let path = r.mapped_file.as_deref().unwrap_or_default().to_ascii_lowercase();
+        r.readable && (path.contains("linearalloc") || path.contains("linear-alloc") || path.ends_with(".art") || path.contains(".art]"))
+    });
+    for region in spaces {
+        let size = region.size.min(budget);
+        budget -= size;
+        read_range(&host, port, region.base, size, 64, |at, bytes| {
+            // ArtMethods hold pointer-sized fields, so they are 8-byte aligned
+            let first = ((8 - at % 8) % 8) as usize;
+            for pos in (first..bytes.len().saturating_sub(40)).step_by(8) {
+                for layout in &ART_LAYOUTS {
+                    for (i, method) in methods.iter().enumerate() {
+                        if !art_method_matches(bytes, pos, layout, dex_address, method) {
+                            continue;
+                        }
+                        let entry_point = u64_at(bytes, pos + layout.entry_point, false).unwrap_or(0);
+                        let address = at + pos as u64;
+                        if executable(entry_point) && !found[i].iter().any(|c| c.address == address) {
+                            let data = u64_at(bytes, pos + layout.data, false).unwrap_or(0);
+                            found[i].push(ArtCandidate { address, data, entry_point });
+                        }
+                    }
+                }
+            }
+        }).await;
+        if budget == 0 {
+            break;
+        }
+    }
+    Ok(found)
+}
+
+fn entry_kind(symbol: Option<&str>, region: Option<&MemoryRegion>) -> &'static str {
+    let symbol = symbol.unwrap_or_default();
+    if symbol.contains("art_quick_to_interpreter_bridge") {
+        return "interpreter";
+    }
+    if symbol.contains("Nterp") || symbol.contains("nterp") {
+        return "nterp";
+    }
+    if symbol.contains("art_quick_generic_jni_trampoline") {
+        return "jni_trampoline";
+    }
+    if symbol.contains("art_quick_resolution_trampoline") {
+        return "resolution";
+    }
+    let path = region.and_then(|r| r.mapped_file.as_deref()).unwrap_or_default().to_ascii_lowercase();
+    if path.contains("jit-code-cache") || path.contains("jit-cache") {
+        "jit"
+    } else if path.ends_with(".oat") || path.ends_with(".odex") {
+        "aot"
+    } else {
+        "compiled"
+    }
+}
+
+/// Shared stubs: a breakpoint there fires for every method that goes through them
+fn is_shared_entry(kind: &str) -> bool {
+    matches!(kind, "interpreter" | "nterp" | "jni_trampoline" | "resolution")
+}
+
+async fn resolve_methods(
+    state: &tauri::State<'_, AppStateType>,
+    class_name: &str,
+    method_name: &str,
+    signature: Option<&str>,
+    dex_address: Option<u64>,
+) -> Result<Vec<JavaMethodEntry>, String> {
+    let class = find_class(state, class_name, dex_address).await?;
+    let methods: Vec<DexMethod> = class.methods.iter()
+        .filter(|m| m.name == method_name && signature.is_none_or(|s| m.signature == s))
+        .cloned()
+        .collect();
+    if methods.is_empty() {
+        return Err(format!("{}.{}{} not found", class.name, method_name, signature.unwrap_or_default()));
+    }
+
+    let regions = memory_regions::get_cached_regions(Some(state.inner()), false).await?;
+    let candidates = find_art_methods(&regions, class.dex_address, &methods).await?;
+    let symbolizer = Symbolizer::load(state.inner()).await.ok();
+    Ok(methods.into_iter().zip(candidates)
+        .map(|(method, candidates)| {
+            let chosen = candidates.first();
+            let entry_point = chosen.map(|c| c.entry_point);
+            let entry_symbol = entry_point
+                .and_then(|a| symbolizer.as_ref()?.resolve(a))
+                .and_then(|s| s.function_name);
+            let region = entry_point.and_then(|a| regions.iter().find(|r| a >= r.base && a < r.base + r.size));
+            let is_native = method.access_flags & ACC_NATIVE != 0;
+            JavaMethodEntry {
+                class_name: class.name.clone(),
+                dex_address: class.dex_address,
+                art_method: chosen.map(|c| c.address),
+                entry_point,
+                entry_kind: if chosen.is_some() { entry_kind(entry_symbol.as_deref(), region) } else { "unresolved" }.to_string(),
+                entry_symbol,
+                jni_function: chosen.filter(|c| is_native && c.data != 0).map(|c| c.data),
+                candidates: candidates.len(),
+                method,
+            }
+        })
+        .collect())
+}
+
+/// Resolve a java method (every overload unless `signature` is given) to its
+/// ArtMethod and current native entry point. Dex images are found and parsed
+/// from the target's memory, so no agent has to be injected.
+#[tauri::command]
+pub async fn resolve_java_method(
+    state: tauri::State<'_, AppStateType>,
+    class_name: String,
+    method_name: String,
+    signature: Option<String>,
+    dex_address: Option<u64>,
+) -> Result<Vec<JavaMethodEntry>, String> {
+    resolve_methods(&state, &class_name, &method_name, signature.as_deref(), dex_address).await
+}
+
+/// Set a breakpoint on the compiled code of a java method, or on the JNI
+/// function of a native one. Interpreted methods enter through a bridge shared
+/// by all interpreted methods; breaking there needs `force`.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn set_java_breakpoint(
+    state: tauri::State<'_, AppStateType>,
+    class_name: String,
+    method_name: String,
+    signature: Option<String>,
+    dex_address: Option<u64>,
+    hit_count: Option<i32>,
+    condition: Option<String>,
+    force: Option<bool>,
+) -> Result<BreakpointDefinition, String> {
+    let entries = resolve_methods(&state, &class_name, &method_name, signature.as_deref(), dex_address).await?;
+    if entries.len() > 1 {
+        let overloads: Vec<String> = entries.iter().map(|e| e.method.signature.clone()).collect();
+        return Err(format!("{} is overloaded; pass a signature: {}", method_name, overloads.join(", ")));
+    }
+    let entry = &entries[0];
+    let address = match (entry.jni_function, entry.entry_point) {
+        (Some(jni), _) => jni,
+        (None, Some(entry_point)) if !is_shared_entry(&entry.entry_kind) || force.unwrap_or(false) => entry_point,
+        (None, Some(_)) => {
+            return Err(format!(
+                "{}.{} has no compiled code yet (entry is the shared {} stub); call it until it is JIT-compiled, or force",
+                entry.class_name, entry.method.name, entry.entry_kind
+            ));
+        }
+        (None, None) => return Err(format!("No ArtMethod found for {}.{}", entry.class_name, entry.method.name)),
+    };
+    breakpoints::set_breakpoint(state, address, hit_count, condition, None, None, None).await
+}
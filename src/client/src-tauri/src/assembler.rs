@@ -0,0 +1,726 @@
+use serde::{Deserialize, Serialize};
+
+use crate::disassembly::build_capstone;
+use crate::patches;
+use crate::state::AppStateType;
+use crate::{read_memory_from_server, SERVER_CONFIG};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssembleRequest {
+    pub architecture: String,
+    pub address: u64,
+    pub source: String,             // One instruction per line (or separated by ';')
+    #[serde(default)]
+    pub pad_with_nops: bool,        // Fill up to the end of the last overwritten instruction
+    #[serde(default)]
+    pub write: bool,                // Write the patch through the server (tracked in patches)
+    #[serde(default)]
+    pub force: bool,                // Write even if the patch ends inside an instruction
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssembledInstruction {
+    pub address: u64,
+    pub text: String,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssembleResponse {
+    pub bytes: Vec<u8>,
+    pub instructions: Vec<AssembledInstruction>,
+    pub padding: usize,             // NOP bytes appended
+    pub original_size: Option<usize>, // Bytes of the original instructions the patch covers
+    pub original_bytes: Option<Vec<u8>>,
+    pub fits: Option<bool>,         // Patch ends on an original instruction boundary
+    pub written: bool,
+    pub warnings: Vec<String>,
+}
+
+fn parse_imm(text: &str) -> Result<i64, String> {
+    let t = text.trim().trim_start_matches('#');
+    let (negative, t) = match t.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, t),
+    };
+    let value = match t.strip_prefix("0x").or_else(|| t.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => t.parse::<u64>(),
+    }
+    .map_err(|_| format!("Invalid immediate '{}'", text.trim()))? as i64;
+    Ok(if negative { value.wrapping_neg() } else { value })
+}
+
+/// Split "mnemonic op1, op2" keeping bracketed memory operands together
+fn split_instruction(text: &str) -> (String, Vec<String>) {
+    let text = text.trim();
+    let (mnemonic, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    let mut operands = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in rest.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                operands.push(current.trim().to_lowercase());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        operands.push(current.trim().to_lowercase());
+    }
+    (mnemonic.to_lowercase(), operands)
+}
+
+fn branch_offset(address: u64, target: &str) -> Result<i64, String> {
+    Ok((parse_imm(target)? as u64).wrapping_sub(address) as i64)
+}
+
+fn expect_operands(mnemonic: &str, operands: &[String], count: usize) -> Result<(), String> {
+    if operands.len() != count {
+        return Err(format!("'{}' expects {} operand(s), got {}", mnemonic, count, operands.len()));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// ARM64
+// ---------------------------------------------------------------------------
+
+/// (register number, is 64-bit, is sp)
+fn arm64_reg(name: &str) -> Result<(u32, bool, bool), String> {
+    let name = name.trim();
+    match name {
+        "sp" => return Ok((31, true, true)),
+        "wsp" => return Ok((31, false, true)),
+        "xzr" => return Ok((31, true, false)),
+        "wzr" => return Ok((31, false, false)),
+        "lr" => return Ok((30, true, false)),
+        "fp" => return Ok((29, true, false)),
+        _ => {}
+    }
+    let (is_64, number) = match name.split_at_checked(1) {
+        Some(("x", n)) => (true, n),
+        Some(("w", n)) => (false, n),
+        _ => return Err(format!("Invalid register '{}'", name)),
+    };
+    let number: u32 = number.parse().map_err(|_| format!("Invalid register '{}'", name))?;
+    if number > 30 {
+        return Err(format!("Invalid register '{}'", name));
+    }
+    Ok((number, is_64, false))
+}
+
+fn arm64_cond(cond: &str) -> Option<u32> {
+    Some(match cond {
+        "eq" => 0, "ne" => 1, "cs" | "hs" => 2, "cc" | "lo" => 3, "mi" => 4, "pl" => 5, "vs" => 6,
+        "vc" => 7, "hi" => 8, "ls" => 9, "ge" => 10, "lt" => 11, "gt" => 12, "le" => 13, "al" => 14,
+        _ => return None,
+    })
+}
+
+fn arm64_branch_imm(address: u64, target: &str, bits: u32) -> Result<u32, String> {
+    let offset = branch_offset(address, target)?;
+    if offset % 4 != 0 {
+        return Err("Branch target is not 4-byte aligned".to_string());
+    }
+    let words = offset >> 2;
+    let limit = 1i64 << (bits - 1);
+    if words < -limit || words >= limit {
+        return Err("Branch target out of range".to_string());
+    }
+    Ok((words as u32) & ((1u32 << bits) - 1))
+}
+
+/// "[xn]" / "[xn, #imm]" -> (base register, offset)
+fn arm64_mem(operand: &str) -> Result<(u32, i64), String> {
+    let inner = operand.trim().strip_prefix('[').and_then(|o| o.strip_suffix(']'))
+        .ok_or_else(|| format!("Unsupported memory operand '{}' (use [xn, #imm])", operand))?;
+    let (base, offset) = match inner.split_once(',') {
+        Some((base, offset)) => (base, parse_imm(offset)?),
+        None => (inner, 0),
+    };
+    let (n, is_64, _) = arm64_reg(base)?;
+    if !is_64 {
+        return Err("Base register must be 64-bit".to_string());
+    }
+    Ok((n, offset))
+}
+
+fn encode_arm64(address: u64, text: &str) -> Result<Vec<u8>, String> {
+    let (mnemonic, ops) = split_instruction(text);
+    let sf = |is_64: bool| if is_64 { 1u32 << 31 } else { 0 };
+
+    let word: u32 = match mnemonic.as_str() {
+        "nop" => 0xD503201F,
+        "ret" => {
+            let n = match ops.first() { Some(r) => arm64_reg(r)?.0, None => 30 };
+            0xD65F0000 | (n << 5)
+        }
+        "br" | "blr" => {
+            expect_operands(&mnemonic, &ops, 1)?;
+            let base = if mnemonic == "br" { 0xD61F0000 } else { 0xD63F0000 };
+            base | (arm64_reg(&ops[0])?.0 << 5)
+        }
+        "b" | "bl" => {
+            expect_operands(&mnemonic, &ops, 1)?;
+            let base = if mnemonic == "b" { 0x14000000 } else { 0x94000000 };
+            base | arm64_branch_imm(address, &ops[0], 26)?
+        }
+        m if m.starts_with("b.") => {
+            expect_operands(m, &ops, 1)?;
+            let cond = arm64_cond(&m[2..]).ok_or_else(|| format!("Unknown condition '{}'", &m[2..]))?;
+            0x54000000 | (arm64_branch_imm(address, &ops[0], 19)? << 5) | cond
+        }
+        "cbz" | "cbnz" => {
+            expect_operands(&mnemonic, &ops, 2)?;
+            let (t, is_64, _) = arm64_reg(&ops[0])?;
+            let base = if mnemonic == "cbz" { 0x34000000 } else { 0x35000000 };
+            sf(is_64) | base | (arm64_branch_imm(address, &ops[1], 19)? << 5) | t
+        }
+        "svc" | "brk" => {
+            expect_operands(&mnemonic, &ops, 1)?;
+            let imm = parse_imm(&ops[0])?;
+            if !(0..=0xFFFF).contains(&imm) {
+                return Err("Immediate must fit in 16 bits".to_string());
+            }
+            let base = if mnemonic == "svc" { 0xD4000001 } else { 0xD4200000 };
+            base | ((imm as u32) << 5)
+        }
+        "mov" | "movz" | "movn" | "movk" => {
+            if ops.len() < 2 {
+                return Err(format!("'{}' expects 2 operands", mnemonic));
+            }
+            let (d, is_64, d_sp) = arm64_reg(&ops[0])?;
+            if mnemonic == "mov" && !ops[1].starts_with('#') && parse_imm(&ops[1]).is_err() {
+                // Register move
+                let (m, m_64, m_sp) = arm64_reg(&ops[1])?;
+                if m_64 != is_64 {
+                    return Err("Register sizes differ".to_string());
+                }
+                if d_sp || m_sp {
+                    sf(is_64) | 0x11000000 | (m << 5) | d
+                } else {
+                    sf(is_64) | 0x2A0003E0 | (m << 16) | d
+                }
+            } else {
+                let imm = parse_imm(&ops[1])?;
+                let mut hw = match ops.get(2) {
+                    Some(shift) => {
+                        let amount = parse_imm(shift.trim_start_matches("lsl").trim())?;
+                        if amount % 16 != 0 || amount > if is_64 { 48 } else { 16 } {
+                            return Err("Shift must be lsl #0/16/32/48".to_string());
+                        }
+                        (amount / 16) as u32
+                    }
+                    None => 0,
+                };
+                let (opcode, value) = match mnemonic.as_str() {
+                    "movz" => (0x52800000u32, imm as u64),
+                    "movn" => (0x12800000, imm as u64),
+                    "movk" => (0x72800000, imm as u64),
+                    _ => {
+                        // mov #imm: movz for a (shifted) 16-bit value, movn for its inverse
+                        let width = if is_64 { 64 } else { 32 };
+                        let value = if is_64 { imm as u64 } else { imm as u64 & 0xFFFF_FFFF };
+                        let inverted = if is_64 { !value } else { !value & 0xFFFF_FFFF };
+                        let fits = |v: u64| (0..width / 16).find(|h| v & !(0xFFFFu64 << (h * 16)) == 0);
+                        if let Some(h) = fits(value) {
+                            hw = h;
+                            (0x52800000, value >> (h * 16))
+                        } else if let Some(h) = fits(inverted) {
+                            hw = h;
+                            (0x12800000, inverted >> (h * 16))
+                        } else {
+                            return Err(format!("Immediate 0x{:x} needs a movz/movk sequence", value));
+                        }
+                    }
+                };
+                if value > 0xFFFF {
+                    return Err("Immediate must fit in 16 bits".to_string());
+                }
+                sf(is_64) | opcode | (hw << 21) | ((value as u32) << 5) | d
+            }
+        }
+        "add" | "sub" | "adds" | "subs" | "cmp" | "cmn" => {
+            // cmp/cmn are subs/adds with the zero register as destination
+            let (ops, op_bits) = match mnemonic.as_str() {
+                "cmp" => (std::iter::once(if ops.first().is_some_and(|o| o.starts_with('w')) { "wzr".to_string() } else { "xzr".to_string() }).chain(ops).collect::<Vec<_>>(), 0b11u32),
+                "cmn" => (std::iter::once(if ops.first().is_some_and(|o| o.starts_with('w')) { "wzr".to_string() } else { "xzr".to_string() }).chain(ops).collect::<Vec<_>>(), 0b01),
+                "add" => (ops, 0b00),
+                "adds" => (ops, 0b01),
+                "sub" => (ops, 0b10),
+                _ => (ops, 0b11),
+            };
+            expect_operands(&mnemonic, &ops, 3)?;
+            let (d, is_64, _) = arm64_reg(&ops[0])?;
+            let (n, _, _) = arm64_reg(&ops[1])?;
+            if let Ok(imm) = parse_imm(&ops[2]) {
+                let (imm, shift) = if (0..=0xFFF).contains(&imm) {
+                    (imm as u32, 0)
+                } else if imm & 0xFFF == 0 && (0..=0xFFF000).contains(&imm) {
+                    ((imm >> 12) as u32, 1)
+                } else {
+                    return Err("Immediate must fit in 12 bits (optionally shifted by 12)".to_string());
+                };
+                sf(is_64) | (op_bits << 29) | 0x11000000 | (shift << 22) | (imm << 10) | (n << 5) | d
+            } else {
+                let (m, _, _) = arm64_reg(&ops[2])?;
+                sf(is_64) | (op_bits << 29) | 0x0B000000 | (m << 16) | (n << 5) | d
+            }
+        }
+        "ldr" | "str" | "ldrb" | "strb" | "ldrh" | "strh" => {
+            expect_operands(&mnemonic, &ops, 2)?;
+            let (t, is_64, _) = arm64_reg(&ops[0])?;
+            let (n, offset) = arm64_mem(&ops[1])?;
+            let (base, scale): (u32, i64) = match (mnemonic.as_str(), is_64) {
+                ("ldr", true) => (0xF9400000, 8),
+                ("str", true) => (0xF9000000, 8),
+                ("ldr", false) => (0xB9400000, 4),
+                ("str", false) => (0xB9000000, 4),
+                ("ldrb", _) => (0x39400000, 1),
+                ("strb", _) => (0x39000000, 1),
+                ("ldrh", _) => (0x79400000, 2),
+                _ => (0x79000000, 2),
+            };
+            if offset < 0 || offset % scale != 0 || offset / scale > 0xFFF {
+                return Err(format!("Offset must be a non-negative multiple of {} below {}", scale, scale * 0x1000));
+            }
+            base | (((offset / scale) as u32) << 10) | (n << 5) | t
+        }
+        _ => return Err(format!("Unsupported ARM64 instruction '{}'", mnemonic)),
+    };
+    Ok(word.to_le_bytes().to_vec())
+}
+
+// ---------------------------------------------------------------------------
+// x86 / x86_64
+// ---------------------------------------------------------------------------
+
+/// (register number, size in bytes)
+fn x86_reg(name: &str, is_64bit_mode: bool) -> Result<(u8, u8), String> {
+    const REG64: [&str; 16] = ["rax", "rcx", "rdx", "rbx", "rsp", "rbp", "rsi", "rdi", "r8", "r9", "r10", "r11", "r12", "r13", "r14", "r15"];
+    const REG32: [&str; 16] = ["eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi", "r8d", "r9d", "r10d", "r11d", "r12d", "r13d", "r14d", "r15d"];
+    let name = name.trim();
+    let (number, size) = if let Some(i) = REG64.iter().position(|r| *r == name) {
+        (i as u8, 8)
+    } else if let Some(i) = REG32.iter().position(|r| *r == name) {
+        (i as u8, 4)
+    } else {
+        return Err(format!("Invalid register '{}'", name));
+    };
+    if !is_64bit_mode && (size == 8 || number >= 8) {
+        return Err(format!("Register '{}' is not available in 32-bit mode", name));
+    }
+    Ok((number, size))
+}
+
+fn x86_cond(cond: &str) -> Option<u8> {
+    Some(match cond {
+        "o" => 0, "no" => 1, "b" | "c" | "nae" => 2, "ae" | "nb" | "nc" => 3, "e" | "z" => 4,
+        "ne" | "nz" => 5, "be" | "na" => 6, "a" | "nbe" => 7, "s" => 8, "ns" => 9, "p" | "pe" => 10,
+        "np" | "po" => 11, "l" | "nge" => 12, "ge" | "nl" => 13, "le" | "ng" => 14, "g" | "nle" => 15,
+        _ => return None,
+    })
+}
+
+/// REX prefix (if needed) for operand size / ModRM reg / ModRM rm extensions
+fn rex(w: bool, r: u8, b: u8) -> Option<u8> {
+    let value = 0x40 | ((w as u8) << 3) | ((r >> 3) << 2) | (b >> 3);
+    (value != 0x40).then_some(value)
+}
+
+/// Relative jump/call with short and near forms; `short` is None for call
+fn x86_relative(address: u64, target: &str, short: Option<Vec<u8>>, near: Vec<u8>) -> Result<Vec<u8>, String> {
+    let target = parse_imm(target)? as u64;
+    if let Some(short) = short {
+        let end = address.wrapping_add(short.len() as u64 + 1);
+        let rel = target.wrapping_sub(end) as i64;
+        if (-128..=127).contains(&rel) {
+            let mut bytes = short;
+            bytes.push(rel as i8 as u8);
+            return Ok(bytes);
+        }
+    }
+    let end = address.wrapping_add(near.len() as u64 + 4);
+    let rel = target.wrapping_sub(end) as i64;
+    if rel < i32::MIN as i64 || rel > i32::MAX as i64 {
+        return Err("Branch target out of rel32 range".to_string());
+    }
+    let mut bytes = near;
+    bytes.extend_from_slice(&(rel as i32).to_le_bytes());
+    Ok(bytes)
+}
+
+fn encode_x86(address: u64, text: &str, is_64bit_mode: bool) -> Result<Vec<u8>, String> {
+    let (mnemonic, ops) = split_instruction(text);
+    let reg = |name: &str| x86_reg(name, is_64bit_mode);
+
+    let bytes = match mnemonic.as_str() {
+        "nop" => vec![0x90],
+        "int3" => vec![0xCC],
+        "leave" => vec![0xC9],
+        "ret" => match ops.first() {
+            Some(imm) => {
+                let imm = parse_imm(imm)?;
+                if !(0..=0xFFFF).contains(&imm) {
+                    return Err("ret immediate must fit in 16 bits".to_string());
+                }
+                let mut bytes = vec![0xC2];
+                bytes.extend_from_slice(&(imm as u16).to_le_bytes());
+                bytes
+            }
+            None => vec![0xC3],
+        },
+        "push" | "pop" => {
+            expect_operands(&mnemonic, &ops, 1)?;
+            let (r, size) = reg(&ops[0])?;
+            if is_64bit_mode && size != 8 {
+                return Err(format!("'{}' takes a 64-bit register", mnemonic));
+            }
+            let base = if mnemonic == "push" { 0x50 } else { 0x58 };
+            rex(false, 0, r).into_iter().chain([base + (r & 7)]).collect()
+        }
+        "jmp" | "call" => {
+            expect_operands(&mnemonic, &ops, 1)?;
+            if let Ok((r, _)) = reg(&ops[0]) {
+                // Indirect through a register: FF /4 (jmp), FF /2 (call)
+                let ext = if mnemonic == "jmp" { 4 } else { 2 };
+                rex(false, 0, r).into_iter().chain([0xFF, 0xC0 | (ext << 3) | (r & 7)]).collect()
+            } else if mnemonic == "jmp" {
+                x86_relative(address, &ops[0], Some(vec![0xEB]), vec![0xE9])?
+            } else {
+                x86_relative(address, &ops[0], None, vec![0xE8])?
+            }
+        }
+        m if m.starts_with('j') && x86_cond(&m[1..]).is_some() => {
+            expect_operands(m, &ops, 1)?;
+            let cc = x86_cond(&m[1..]).unwrap();
+            x86_relative(address, &ops[0], Some(vec![0x70 + cc]), vec![0x0F, 0x80 + cc])?
+        }
+        "mov" => {
+            expect_operands(&mnemonic, &ops, 2)?;
+            let (d, size) = reg(&ops[0])?;
+            if let Ok((s, s_size)) = reg(&ops[1]) {
+                if s_size != size {
+                    return Err("Register sizes differ".to_string());
+                }
+                rex(size == 8, s, d).into_iter().chain([0x89, 0xC0 | ((s & 7) << 3) | (d & 7)]).collect()
+            } else {
+                let imm = parse_imm(&ops[1])?;
+                if size == 4 {
+                    if imm < i32::MIN as i64 || imm > u32::MAX as i64 {
+                        return Err("Immediate does not fit in 32 bits".to_string());
+                    }
+                    let mut bytes: Vec<u8> = rex(false, 0, d).into_iter().chain([0xB8 + (d & 7)]).collect();
+                    bytes.extend_from_slice(&(imm as u32).to_le_bytes());
+                    bytes
+                } else if imm >= i32::MIN as i64 && imm <= i32::MAX as i64 {
+                    // Sign-extended imm32: REX.W C7 /0
+                    let mut bytes: Vec<u8> = rex(true, 0, d).into_iter().chain([0xC7, 0xC0 | (d & 7)]).collect();
+                    bytes.extend_from_slice(&(imm as i32).to_le_bytes());
+                    bytes
+                } else {
+                    let mut bytes: Vec<u8> = rex(true, 0, d).into_iter().chain([0xB8 + (d & 7)]).collect();
+                    bytes.extend_from_slice(&(imm as u64).to_le_bytes());
+                    bytes
+                }
+            }
+        }
+        "add" | "or" | "and" | "sub" | "xor" | "cmp" | "test" => {
+            expect_operands(&mnemonic, &ops, 2)?;
+            let (d, size) = reg(&ops[0])?;
+            if let Ok((s, s_size)) = reg(&ops[1]) {
+                if s_size != size {
+                    return Err("Register sizes differ".to_string());
+                }
+                let opcode = match mnemonic.as_str() {
+                    "add" => 0x01, "or" => 0x09, "and" => 0x21, "sub" => 0x29, "xor" => 0x31, "cmp" => 0x39, _ => 0x85,
+                };
+                rex(size == 8, s, d).into_iter().chain([opcode, 0xC0 | ((s & 7) << 3) | (d & 7)]).collect()
+            } else {
+                if mnemonic == "test" {
+                    return Err("test with an immediate is not supported".to_string());
+                }
+                let imm = parse_imm(&ops[1])?;
+                if imm < i32::MIN as i64 || imm > i32::MAX as i64 {
+                    return Err("Immediate does not fit in 32 bits".to_string());
+                }
+                let ext = match mnemonic.as_str() {
+                    "add" => 0, "or" => 1, "and" => 4, "sub" => 5, "xor" => 6, _ => 7,
+                };
+                let mut bytes: Vec<u8> = rex(size == 8, 0, d).into_iter().collect();
+                if (-128..=127).contains(&imm) {
+                    bytes.extend_from_slice(&[0x83, 0xC0 | (ext << 3) | (d & 7), imm as i8 as u8]);
+                } else {
+                    bytes.extend_from_slice(&[0x81, 0xC0 | (ext << 3) | (d & 7)]);
+                    bytes.extend_from_slice(&(imm as i32).to_le_bytes());
+                }
+                bytes
+            }
+        }
+        _ => return Err(format!("Unsupported x86 instruction '{}'", mnemonic)),
+    };
+    Ok(bytes)
+}
+
+/// Encode one instruction at `address`
+fn encode_instruction(architecture: &str, address: u64, text: &str) -> Result<Vec<u8>, String> {
+    match architecture {
+        "arm64" | "aarch64" => encode_arm64(address, text),
+        "x86_64" => encode_x86(address, text, true),
+        "x86" => encode_x86(address, text, false),
+        _ => Err(format!("Assembling for '{}' is not supported", architecture)),
+    }
+}
+
+fn nop_bytes(architecture: &str) -> &'static [u8] {
+    match architecture {
+        "arm64" | "aarch64" => &[0x1F, 0x20, 0x03, 0xD5],
+        _ => &[0x90],
+    }
+}
+
+/// Assemble instructions at an address, check the patch against the original
+/// instruction boundaries and optionally write it
+#[tauri::command]
+pub async fn assemble_instructions(
+    state: tauri::State<'_, AppStateType>,
+    request: AssembleRequest,
+) -> Result<AssembleResponse, String> {
+    let mut instructions = Vec::new();
+    let mut bytes = Vec::new();
+    let mut address = request.address;
+    for line in request.source.split(['\n', ';']) {
+        // "//" starts a comment (';' already separates instructions)
+        let line = line.split("//").next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let encoded = encode_instruction(&request.architecture, address, line)
+            .map_err(|e| format!("{}: {}", line, e))?;
+        instructions.push(AssembledInstruction { address, text: line.to_string(), bytes: encoded.clone() });
+        address += encoded.len() as u64;
+        bytes.extend(encoded);
+    }
+    if bytes.is_empty() {
+        return Err("Nothing to assemble".to_string());
+    }
+
+    // Round-trip through Capstone to catch encoder mistakes
+    let mut warnings = Vec::new();
+    let cs = build_capstone(&request.architecture)?;
+    for insn in &instructions {
+        let decoded = cs.disasm_count(&insn.bytes, insn.address, 1).ok()
+            .and_then(|d| d.iter().next().map(|i| (i.mnemonic().unwrap_or("").to_string(), i.bytes().len())));
+        // Capstone prints the preferred alias (movz/movn -> mov, movabs for imm64)
+        let canonical = |m: &str| match m {
+            "movz" | "movn" | "movabs" => "mov".to_string(),
+            _ => m.to_string(),
+        };
+        let expected = canonical(&split_instruction(&insn.text).0);
+        match decoded {
+            Some((mnemonic, len)) if len == insn.bytes.len() => {
+                if canonical(&mnemonic.to_lowercase()) != expected {
+                    warnings.push(format!("0x{:x}: '{}' decodes as '{}'", insn.address, insn.text, mnemonic));
+                }
+            }
+            _ => warnings.push(format!("0x{:x}: '{}' does not decode back", insn.address, insn.text)),
+        }
+    }
+
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+
+    // Compare against the instructions being overwritten
+    let mut padding = 0;
+    let mut original_size = None;
+    let mut original_bytes = None;
+    let mut fits = None;
+    if !host.is_empty() {
+        match read_memory_from_server(&host, port, request.address, bytes.len() + 16).await {
+            Ok(original) => {
+                let mut covered = 0usize;
+                if let Ok(decoded) = cs.disasm_all(&original, request.address) {
+                    for insn in decoded.iter() {
+                        if covered >= bytes.len() {
+                            break;
+                        }
+                        covered += insn.bytes().len();
+                    }
+                }
+                if covered >= bytes.len() {
+                    let nop = nop_bytes(&request.architecture);
+                    if request.pad_with_nops {
+                        while bytes.len() + nop.len() <= covered {
+                            bytes.extend_from_slice(nop);
+                            padding += nop.len();
+                        }
+                    }
+                    fits = Some(bytes.len() == covered);
+                    original_size = Some(covered);
+                    original_bytes = Some(original[..covered].to_vec());
+                    if covered > bytes.len() {
+                        warnings.push(format!(
+                            "Patch is {} bytes but overwrites part of an instruction ending at +{}",
+                            bytes.len(), covered
+                        ));
+                    }
+                } else {
+                    warnings.push("Original instructions could not be decoded".to_string());
+                }
+            }
+            Err(e) => warnings.push(format!("Failed to read original bytes: {}", e)),
+        }
+    } else if request.write {
+        return Err("No server connection configured".to_string());
+    }
+
+    let mut written = false;
+    if request.write {
+        if fits != Some(true) && !request.force {
+            return Err("Patch does not end on an instruction boundary (use pad_with_nops or force)".to_string());
+        }
+        let description = instructions.iter().map(|i| i.text.as_str()).collect::<Vec<_>>().join("; ");
+        patches::apply(state.inner(), request.address, &bytes, Some(description)).await?;
+        written = true;
+    }
+
+    Ok(AssembleResponse {
+        bytes,
+        instructions,
+        padding,
+        original_size,
+        original_bytes,
+        fits,
+        written,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: u64 = 0x1000;
+
+    /// Encode `source` at ADDRESS and disassemble it back with Capstone
+    fn round_trip(architecture: &str, source: &str) -> String {
+        let bytes = encode_instruction(architecture, ADDRESS, source).unwrap();
+        let cs = build_capstone(architecture).unwrap();
+        let decoded = cs.disasm_all(&bytes, ADDRESS).unwrap();
+        assert_eq!(decoded.len(), 1, "'{}' decodes as {} instructions", source, decoded.len());
+        let insn = decoded.iter().next().unwrap();
+        assert_eq!(insn.bytes().len(), bytes.len(), "'{}' leaves trailing bytes", source);
+        format!("{} {}", insn.mnemonic().unwrap_or(""), insn.op_str().unwrap_or("")).trim().to_string()
+    }
+
+    fn check(architecture: &str, cases: &[(&str, &str)]) {
+        for (source, expected) in cases {
+            assert_eq!(round_trip(architecture, source), *expected, "{}: '{}'", architecture, source);
+        }
+    }
+
+    #[test]
+    fn x86_64_round_trips() {
+        check("x86_64", &[
+            ("nop", "nop"),
+            ("int3", "int3"),
+            ("leave", "leave"),
+            ("ret", "ret"),
+            ("ret 8", "ret 8"),
+            ("push rbp", "push rbp"),
+            ("push r12", "push r12"),
+            ("pop r15", "pop r15"),
+            ("jmp rax", "jmp rax"),
+            ("call r11", "call r11"),
+            ("jmp 0x1010", "jmp 0x1010"),
+            ("jmp 0x2000", "jmp 0x2000"),
+            ("call 0x2000", "call 0x2000"),
+            ("je 0x1010", "je 0x1010"),
+            ("jne 0x2000", "jne 0x2000"),
+            ("mov rax, rbx", "mov rax, rbx"),
+            ("mov r8d, eax", "mov r8d, eax"),
+            ("mov eax, 0x1234", "mov eax, 0x1234"),
+            ("mov r9d, 0xffffffff", "mov r9d, 0xffffffff"),
+            ("mov rax, -1", "mov rax, 0xffffffffffffffff"),
+            ("mov rax, 0x123456789", "movabs rax, 0x123456789"),
+            ("add rax, rbx", "add rax, rbx"),
+            ("or ecx, edx", "or ecx, edx"),
+            ("and r10, r11", "and r10, r11"),
+            ("sub rsi, rdi", "sub rsi, rdi"),
+            ("xor eax, eax", "xor eax, eax"),
+            ("cmp rax, r8", "cmp rax, r8"),
+            ("test rdx, rdx", "test rdx, rdx"),
+            ("add rax, 8", "add rax, 8"),
+            ("sub rsp, 0x1000", "sub rsp, 0x1000"),
+            ("cmp r12d, -1", "cmp r12d, -1"),
+        ]);
+        // Short forms are used when the target is in rel8 range
+        assert_eq!(encode_instruction("x86_64", ADDRESS, "jmp 0x1010").unwrap().len(), 2);
+        assert_eq!(encode_instruction("x86_64", ADDRESS, "jne 0x2000").unwrap().len(), 6);
+    }
+
+    #[test]
+    fn x86_round_trips() {
+        check("x86", &[
+            ("push ebp", "push ebp"),
+            ("pop edi", "pop edi"),
+            ("mov eax, ebx", "mov eax, ebx"),
+            ("mov ecx, 0x10", "mov ecx, 0x10"),
+            ("jmp 0x1010", "jmp 0x1010"),
+            ("call 0x2000", "call 0x2000"),
+            ("xor edx, 0x80", "xor edx, 0x80"),
+        ]);
+        assert!(encode_instruction("x86", ADDRESS, "push rax").is_err());
+    }
+
+    #[test]
+    fn arm64_round_trips() {
+        check("arm64", &[
+            ("nop", "nop"),
+            ("ret", "ret"),
+            ("ret x1", "ret x1"),
+            ("br x16", "br x16"),
+            ("blr x8", "blr x8"),
+            ("b 0x1010", "b #0x1010"),
+            ("bl 0x800", "bl #0x800"),
+            ("b.ne 0x1020", "b.ne #0x1020"),
+            ("cbz x0, 0x1040", "cbz x0, #0x1040"),
+            ("cbnz w3, 0xff0", "cbnz w3, #0xff0"),
+            ("svc #0", "svc #0"),
+            ("brk #1", "brk #1"),
+            ("mov x0, x1", "mov x0, x1"),
+            ("mov w2, w3", "mov w2, w3"),
+            ("mov x0, sp", "mov x0, sp"),
+            ("mov x0, #0x10", "mov x0, #0x10"),
+            ("mov x0, #0x10000", "mov x0, #0x10000"),
+            ("mov x0, #-1", "mov x0, #-1"),
+            ("movz w1, #0x1234, lsl #16", "mov w1, #0x12340000"),
+            ("movn x2, #5", "mov x2, #-6"),
+            ("movk x0, #0x1234, lsl #32", "movk x0, #0x1234, lsl #32"),
+            ("add x0, x1, #0x10", "add x0, x1, #0x10"),
+            ("add sp, sp, #0x1000", "add sp, sp, #1, lsl #12"),
+            ("sub x0, x1, x2", "sub x0, x1, x2"),
+            ("adds w0, w1, #1", "adds w0, w1, #1"),
+            ("subs x3, x4, #8", "subs x3, x4, #8"),
+            ("cmp x0, #1", "cmp x0, #1"),
+            ("cmn w1, #2", "cmn w1, #2"),
+            ("ldr x0, [x1, #8]", "ldr x0, [x1, #8]"),
+            ("str w0, [sp, #4]", "str w0, [sp, #4]"),
+            ("ldrb w1, [x2]", "ldrb w1, [x2]"),
+            ("strb w1, [x2, #3]", "strb w1, [x2, #3]"),
+            ("ldrh w3, [x4, #6]", "ldrh w3, [x4, #6]"),
+            ("strh w3, [x4, #2]", "strh w3, [x4, #2]"),
+        ]);
+        assert!(encode_instruction("arm64", ADDRESS, "mov x0, #0x12345").is_err());
+        assert!(encode_instruction("arm64", ADDRESS, "ldr x0, [x1, #4]").is_err());
+    }
+}
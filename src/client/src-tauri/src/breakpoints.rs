@@ -0,0 +1,726 @@
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::expression::{self, RegisterSet, Symbols};
+use crate::state::{AppStateType, ExceptionData, ModuleInfo};
+use crate::symbolizer;
+use crate::{continue_execution_on_server, db, remove_breakpoint_on_server, server_address, set_breakpoint_on_server};
+
+/// Persisted breakpoint; module-relative so it can be restored after reattaching
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakpointDefinition {
+    pub id: i64,
+    pub target_os: String,
+    pub module_name: String,          // Empty for breakpoints outside any module
+    pub module_offset: u64,           // Absolute address when module_name is empty
+    pub hit_count: i32,               // 0 = until removed
+    pub condition: Option<String>,    // Expression over registers / memory; hits where it is 0 are resumed
+    pub action: String,               // "break", "log" (record the hit and resume) or "continue" (count and resume)
+    pub enabled: bool,
+    pub is_software: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    pub address: Option<u64>,         // Resolved against the loaded modules
+    pub installed: bool,              // Currently set on the server for the attached process
+    pub condition_passes: u64,        // Hits forwarded since the app started
+    pub condition_skips: u64,         // Hits auto-continued because the condition was false
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakpointRestoreResult {
+    pub id: i64,
+    pub address: Option<u64>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+// Breakpoint id -> (pid, address) it was installed at
+type InstalledBreakpoints = HashMap<i64, (Option<u32>, u64)>;
+
+static INSTALLED: Lazy<Mutex<InstalledBreakpoints>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+// Breakpoint id -> (passes, skips) of its condition
+static CONDITION_STATS: Lazy<Mutex<HashMap<i64, (u64, u64)>>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+/// Create the breakpoints table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS breakpoints (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            module_offset INTEGER NOT NULL,
+            hit_count INTEGER NOT NULL DEFAULT 0,
+            condition TEXT,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            is_software INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            action TEXT NOT NULL DEFAULT 'break',
+            UNIQUE(target_os, module_name, module_offset)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Migration for databases created before breakpoint actions
+pub fn add_action_column(conn: &Connection) -> Result<(), String> {
+    db::add_column(conn, "breakpoints", "action", "TEXT NOT NULL DEFAULT 'break'")
+}
+
+const ACTIONS: [&str; 3] = ["break", "log", "continue"];
+
+fn validate_action(action: Option<String>) -> Result<String, String> {
+    let action = action.map(|a| a.trim().to_lowercase()).filter(|a| !a.is_empty()).unwrap_or_else(|| "break".to_string());
+    if !ACTIONS.contains(&action.as_str()) {
+        return Err(format!("Unknown breakpoint action '{}' (expected break, log or continue)", action));
+    }
+    Ok(action)
+}
+
+struct TargetInfo {
+    target_os: String,
+    pid: Option<u32>,
+    modules: Vec<ModuleInfo>,
+}
+
+fn target_info(state: &AppStateType) -> Result<TargetInfo, String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    Ok(TargetInfo {
+        target_os: state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default(),
+        pid: state_guard.attached_process.as_ref().map(|p| p.pid),
+        modules: state_guard.attached_modules.clone(),
+    })
+}
+
+const SELECT_COLUMNS: &str = "id, target_os, module_name, module_offset, hit_count, condition, enabled, is_software, created_at, updated_at, action";
+
+fn row_to_definition(row: &rusqlite::Row) -> rusqlite::Result<BreakpointDefinition> {
+    Ok(BreakpointDefinition {
+        id: row.get(0)?,
+        target_os: row.get(1)?,
+        module_name: row.get(2)?,
+        module_offset: row.get::<_, i64>(3)? as u64,
+        hit_count: row.get(4)?,
+        condition: row.get(5)?,
+        action: row.get(10)?,
+        enabled: row.get::<_, i64>(6)? != 0,
+        is_software: row.get::<_, i64>(7)? != 0,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+        address: None,
+        installed: false,
+        condition_passes: 0,
+        condition_skips: 0,
+    })
+}
+
+fn resolve_address(definition: &BreakpointDefinition, modules: &[ModuleInfo]) -> Option<u64> {
+    if definition.module_name.is_empty() {
+        return Some(definition.module_offset);
+    }
+    modules.iter()
+        .find(|m| m.modulename == definition.module_name)
+        .map(|m| m.base + definition.module_offset)
+}
+
+/// Fill the runtime fields (address, installed) of a stored definition
+fn annotate(mut definition: BreakpointDefinition, target: &TargetInfo) -> BreakpointDefinition {
+    definition.address = resolve_address(&definition, &target.modules);
+    definition.installed = INSTALLED.lock().ok()
+        .and_then(|installed| installed.get(&definition.id).copied())
+        .is_some_and(|(pid, _)| pid == target.pid);
+    if let Some((passes, skips)) = CONDITION_STATS.lock().ok().and_then(|s| s.get(&definition.id).copied()) {
+        definition.condition_passes = passes;
+        definition.condition_skips = skips;
+    }
+    definition
+}
+
+fn load_definition(conn: &Connection, id: i64) -> Result<BreakpointDefinition, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM breakpoints WHERE id = ?1", SELECT_COLUMNS),
+        params![id],
+        row_to_definition,
+    ).map_err(|e| format!("Breakpoint {} not found: {}", id, e))
+}
+
+/// Enabled and disabled definitions for a target OS
+pub fn load_definitions(conn: &Connection, target_os: &str) -> Result<Vec<BreakpointDefinition>, String> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM breakpoints WHERE target_os = ?1 ORDER BY module_name, module_offset",
+        SELECT_COLUMNS
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![target_os], row_to_definition)
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+async fn definition(id: i64) -> Result<BreakpointDefinition, String> {
+    db::run(move |conn| load_definition(conn, id)).await
+}
+
+async fn definitions(target_os: &str) -> Result<Vec<BreakpointDefinition>, String> {
+    let target_os = target_os.to_string();
+    db::run(move |conn| load_definitions(conn, &target_os)).await
+}
+
+/// Installed definition for an absolute address in the attached process
+async fn definition_at(target: &TargetInfo, address: u64) -> Option<BreakpointDefinition> {
+    let id = INSTALLED.lock().ok()?.iter()
+        .find(|(_, (pid, installed_at))| *installed_at == address && *pid == target.pid)
+        .map(|(id, _)| *id)?;
+    definition(id).await.ok()
+}
+
+/// Evaluate breakpoint conditions and actions for incoming stops. Hits whose
+/// condition is false, and hits of "log" / "continue" breakpoints, are resumed
+/// on the server; only "log" hits are still returned for the record.
+/// A condition that fails to evaluate lets the hit through.
+pub async fn filter_exceptions(state: &AppStateType, exceptions: Vec<ExceptionData>) -> Vec<ExceptionData> {
+    let Ok(target) = target_info(state) else {
+        return exceptions;
+    };
+    let Ok((host, port)) = server_address() else {
+        return exceptions;
+    };
+    let symbols = Symbols::new(&target.target_os, &target.modules);
+
+    let mut forwarded = Vec::with_capacity(exceptions.len());
+    for exception in exceptions {
+        let pc = exception.pc
+            .or_else(|| u64::from_str_radix(exception.address.trim_start_matches("0x"), 16).ok());
+        let definition = match pc.filter(|_| exception.exception_type == "breakpoint") {
+            Some(pc) => definition_at(&target, pc).await,
+            None => None,
+        }
+        .filter(|d| d.condition.is_some() || d.action != "break");
+        let Some(definition) = definition else {
+            forwarded.push(exception);
+            continue;
+        };
+        let id = definition.id;
+
+        let pass = match &definition.condition {
+            Some(condition) => {
+                let registers = RegisterSet::from_json(&exception.registers);
+                let result = match expression::parse(condition) {
+                    Ok(expr) => expression::evaluate(&expr, &registers, &symbols, &host, port).await,
+                    Err(e) => Err(e),
+                };
+                let pass = match result {
+                    Ok(value) => value.is_true(),
+                    Err(e) => {
+                        eprintln!("Breakpoint {} condition '{}' failed: {}", id, condition, e);
+                        true
+                    }
+                };
+                if let Ok(mut stats) = CONDITION_STATS.lock() {
+                    let entry = stats.entry(id).or_insert((0, 0));
+                    if pass { entry.0 += 1 } else { entry.1 += 1 }
+                }
+                pass
+            }
+            None => true,
+        };
+
+        let resume = !pass || definition.action != "break";
+        let keep = pass && definition.action != "continue";
+        resume_or_forward(&host, port, exception, resume, keep, &mut forwarded).await;
+    }
+    forwarded
+}
+
+/// Resume the thread of a filtered stop (if `resume`) and forward the stop
+/// (if `keep`). A stop whose thread could not be resumed is forwarded anyway,
+/// rather than leaving the thread hanging unseen.
+pub async fn resume_or_forward(
+    host: &str,
+    port: u16,
+    exception: ExceptionData,
+    resume: bool,
+    keep: bool,
+    forwarded: &mut Vec<ExceptionData>,
+) {
+    let stuck = resume && continue_execution_on_server(host, port, exception.thread_id).await.is_err();
+    if keep || stuck {
+        forwarded.push(exception);
+    }
+}
+
+fn set_state_breakpoint(state: &AppStateType, address: u64, is_software: bool, active: bool) {
+    let Ok(mut state_guard) = state.lock() else {
+        return;
+    };
+    let key = format!("0x{:x}", address);
+    let list = if is_software { &mut state_guard.software_breakpoints } else { &mut state_guard.active_breakpoints };
+    list.retain(|a| *a != key);
+    if active {
+        list.push(key);
+    }
+    state_guard.touch();
+}
+
+async fn install(state: &AppStateType, definition: &BreakpointDefinition, target: &TargetInfo) -> Result<u64, String> {
+    let address = resolve_address(definition, &target.modules)
+        .ok_or_else(|| format!("Module '{}' is not loaded", definition.module_name))?;
+    let (host, port) = server_address()?;
+    set_breakpoint_on_server(&host, port, address, definition.hit_count, definition.is_software).await?;
+    INSTALLED.lock().map_err(|e| e.to_string())?.insert(definition.id, (target.pid, address));
+    set_state_breakpoint(state, address, definition.is_software, true);
+    Ok(address)
+}
+
+async fn uninstall(state: &AppStateType, definition: &BreakpointDefinition, target: &TargetInfo) -> Result<(), String> {
+    let installed = INSTALLED.lock().map_err(|e| e.to_string())?.remove(&definition.id);
+    let Some((_, address)) = installed.filter(|(pid, _)| *pid == target.pid) else {
+        return Ok(());
+    };
+    let (host, port) = server_address()?;
+    remove_breakpoint_on_server(&host, port, address).await?;
+    set_state_breakpoint(state, address, definition.is_software, false);
+    Ok(())
+}
+
+/// What updating an installed breakpoint requires on the server
+#[derive(Debug, PartialEq, Eq)]
+enum InstalledChange {
+    Keep,
+    Uninstall,  // Disabled
+    Reinstall,  // Kind or hit count changed; the server keeps the old ones otherwise
+}
+
+fn installed_change(previous: &BreakpointDefinition, updated: &BreakpointDefinition) -> InstalledChange {
+    if !updated.enabled {
+        InstalledChange::Uninstall
+    } else if previous.is_software != updated.is_software || previous.hit_count != updated.hit_count {
+        InstalledChange::Reinstall
+    } else {
+        InstalledChange::Keep
+    }
+}
+
+/// Install the enabled breakpoints whose module is loaded; called when the
+/// attached module list changes
+pub async fn restore(state: &AppStateType) -> Result<Vec<BreakpointRestoreResult>, String> {
+    let target = target_info(state)?;
+    if target.pid.is_none() {
+        return Ok(Vec::new());
+    }
+    let mut results = Vec::new();
+    for definition in definitions(&target.target_os).await? {
+        let definition = annotate(definition, &target);
+        if !definition.enabled || definition.installed || definition.address.is_none() {
+            continue;
+        }
+        let outcome = install(state, &definition, &target).await;
+        results.push(BreakpointRestoreResult {
+            id: definition.id,
+            address: definition.address,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+    Ok(results)
+}
+
+/// Create (or update) a breakpoint definition and install it when enabled
+#[tauri::command]
+pub async fn set_breakpoint(
+    state: tauri::State<'_, AppStateType>,
+    address: u64,
+    hit_count: Option<i32>,
+    condition: Option<String>,
+    is_software: Option<bool>,
+    enabled: Option<bool>,
+    action: Option<String>,
+) -> Result<BreakpointDefinition, String> {
+    let action = validate_action(action)?;
+    let target = target_info(state.inner())?;
+    let (module_name, module_offset) = target.modules.iter()
+        .find(|m| address >= m.base && address < m.base.saturating_add(m.size))
+        .map(|m| (m.modulename.clone(), address - m.base))
+        .unwrap_or_else(|| (String::new(), address));
+    let condition = condition.filter(|c| !c.trim().is_empty());
+    if let Some(condition) = &condition {
+        expression::parse(condition).map_err(|e| format!("Invalid condition: {}", e))?;
+    }
+
+    let target_os = target.target_os.clone();
+    let (previous, definition) = db::run(move |conn| {
+        let existing: Option<i64> = conn.query_row(
+            "SELECT id FROM breakpoints WHERE target_os = ?1 AND module_name = ?2 AND module_offset = ?3",
+            params![target_os, module_name, module_offset as i64],
+            |row| row.get(0),
+        ).optional().map_err(|e| e.to_string())?;
+        let previous = existing.map(|id| load_definition(conn, id)).transpose()?;
+        let id = match existing {
+            Some(id) => {
+                conn.execute(
+                    "UPDATE breakpoints SET hit_count = ?1, condition = ?2, enabled = ?3, is_software = ?4, action = ?5,
+                     updated_at = datetime('now') WHERE id = ?6",
+                    params![hit_count.unwrap_or(0), condition, enabled.unwrap_or(true) as i64, is_software.unwrap_or(false) as i64, action, id],
+                ).map_err(|e| e.to_string())?;
+                id
+            }
+            None => {
+                conn.execute(
+                    "INSERT INTO breakpoints (target_os, module_name, module_offset, hit_count, condition, enabled, is_software, action, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, datetime('now'), datetime('now'))",
+                    params![target_os, module_name, module_offset as i64, hit_count.unwrap_or(0), condition,
+                            enabled.unwrap_or(true) as i64, is_software.unwrap_or(false) as i64, action],
+                ).map_err(|e| e.to_string())?;
+                conn.last_insert_rowid()
+            }
+        };
+        Ok((previous, load_definition(conn, id)?))
+    }).await?;
+
+    if let Some(previous) = previous.map(|d| annotate(d, &target)).filter(|d| d.installed) {
+        if installed_change(&previous, &definition) != InstalledChange::Keep {
+            uninstall(state.inner(), &previous, &target).await?;
+        }
+    }
+    let definition = annotate(definition, &target);
+    if definition.enabled && !definition.installed && target.pid.is_some() {
+        install(state.inner(), &definition, &target).await?;
+    }
+    Ok(annotate(definition, &target))
+}
+
+/// Remove a breakpoint (by id or absolute address) from the server and the store
+#[tauri::command]
+pub async fn remove_breakpoint(
+    state: tauri::State<'_, AppStateType>,
+    id: Option<i64>,
+    address: Option<u64>,
+) -> Result<bool, String> {
+    let target = target_info(state.inner())?;
+    let definition = match (id, address) {
+        (Some(id), _) => Some(definition(id).await?),
+        (None, Some(address)) => definitions(&target.target_os).await?.into_iter()
+            .find(|d| resolve_address(d, &target.modules) == Some(address)),
+        (None, None) => return Err("Either id or address is required".to_string()),
+    };
+    let Some(definition) = definition else {
+        // Not tracked here; still clear it on the server
+        if let Some(address) = address {
+            let (host, port) = server_address()?;
+            remove_breakpoint_on_server(&host, port, address).await?;
+            return Ok(true);
+        }
+        return Ok(false);
+    };
+
+    uninstall(state.inner(), &definition, &target).await?;
+    let id = definition.id;
+    db::run(move |conn| {
+        let deleted = conn.execute("DELETE FROM breakpoints WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        Ok(deleted > 0)
+    }).await
+}
+
+/// Stored breakpoints of the current target OS with their resolved addresses
+#[tauri::command]
+pub async fn list_breakpoints(state: tauri::State<'_, AppStateType>) -> Result<Vec<BreakpointDefinition>, String> {
+    let target = target_info(state.inner())?;
+    Ok(definitions(&target.target_os).await?
+        .into_iter()
+        .map(|d| annotate(d, &target))
+        .collect())
+}
+
+/// Enable / disable a breakpoint (flips it when `enabled` is omitted)
+#[tauri::command]
+pub async fn toggle_breakpoint(
+    state: tauri::State<'_, AppStateType>,
+    id: i64,
+    enabled: Option<bool>,
+) -> Result<BreakpointDefinition, String> {
+    let target = target_info(state.inner())?;
+    let definition = annotate(definition(id).await?, &target);
+    let enabled = enabled.unwrap_or(!definition.enabled);
+
+    if enabled && !definition.installed && target.pid.is_some() && definition.address.is_some() {
+        install(state.inner(), &definition, &target).await?;
+    } else if !enabled {
+        uninstall(state.inner(), &definition, &target).await?;
+    }
+
+    let definition = db::run(move |conn| {
+        conn.execute(
+            "UPDATE breakpoints SET enabled = ?1, updated_at = datetime('now') WHERE id = ?2",
+            params![enabled as i64, id],
+        ).map_err(|e| e.to_string())?;
+        load_definition(conn, id)
+    }).await?;
+    Ok(annotate(definition, &target))
+}
+
+/// Install stored breakpoints for the current module set
+#[tauri::command]
+pub async fn restore_breakpoints(state: tauri::State<'_, AppStateType>) -> Result<Vec<BreakpointRestoreResult>, String> {
+    restore(state.inner()).await
+}
+
+const EXPORT_VERSION: u32 = 1;
+
+/// Portable breakpoint: located by module + offset, with the function symbol
+/// as a fallback for rebuilt modules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakpointExportEntry {
+    #[serde(default)]
+    pub module_name: String,          // Empty for absolute addresses
+    pub module_offset: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,       // "function" or "function+0x10" within module_name
+    #[serde(default)]
+    pub condition: Option<String>,
+    #[serde(default)]
+    pub action: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub hit_count: i32,
+    #[serde(default)]
+    pub is_software: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakpointExport {
+    pub version: u32,
+    pub target_os: String,
+    pub breakpoints: Vec<BreakpointExportEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BreakpointImportResult {
+    pub imported: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub errors: Vec<String>,          // One line per skipped entry
+    pub restored: Vec<BreakpointRestoreResult>,
+}
+
+/// Write the stored breakpoints of a target OS (optionally only `ids`) as
+/// JSON; returns the JSON and also writes it to `output_path` when given
+#[tauri::command]
+pub async fn export_breakpoints(
+    state: tauri::State<'_, AppStateType>,
+    target_os: Option<String>,
+    ids: Option<Vec<i64>>,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let target_os = match target_os {
+        Some(target_os) => target_os,
+        None => target_info(state.inner())?.target_os,
+    };
+    let selected: Vec<_> = definitions(&target_os).await?
+        .into_iter()
+        .filter(|d| ids.as_ref().is_none_or(|ids| ids.contains(&d.id)))
+        .collect();
+    symbolizer::preload(&target_os, selected.iter().map(|d| d.module_name.clone()).collect()).await;
+    let breakpoints = selected
+        .into_iter()
+        .map(|d| BreakpointExportEntry {
+            symbol: (!d.module_name.is_empty())
+                .then(|| symbolizer::symbol_for_offset(&target_os, &d.module_name, d.module_offset))
+                .flatten(),
+            module_name: d.module_name,
+            module_offset: Some(d.module_offset),
+            condition: d.condition,
+            action: Some(d.action),
+            enabled: d.enabled,
+            hit_count: d.hit_count,
+            is_software: d.is_software,
+        })
+        .collect();
+
+    let content = serde_json::to_string_pretty(&BreakpointExport { version: EXPORT_VERSION, target_os, breakpoints })
+        .map_err(|e| e.to_string())?;
+    if let Some(path) = output_path {
+        tokio::fs::write(&path, &content)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    Ok(content)
+}
+
+/// Module offset an imported entry refers to; the symbol wins over a stale
+/// offset when it resolves
+fn import_location(target_os: &str, entry: &BreakpointExportEntry) -> Result<u64, String> {
+    let by_symbol = entry.symbol.as_ref()
+        .filter(|_| !entry.module_name.is_empty())
+        .and_then(|symbol| symbolizer::offset_for_symbol(target_os, &entry.module_name, symbol));
+    by_symbol.or(entry.module_offset).ok_or_else(|| match &entry.symbol {
+        Some(symbol) => format!("Symbol {} not found in {}", symbol, entry.module_name),
+        None => "Entry has neither module_offset nor symbol".to_string(),
+    })
+}
+
+/// Import breakpoints exported by `export_breakpoints` (from `json` or `path`)
+/// and install the enabled ones whose module is loaded. Entries matching an
+/// existing location are updated; `replace` first removes every stored
+/// breakpoint of the target OS.
+#[tauri::command]
+pub async fn import_breakpoints(
+    state: tauri::State<'_, AppStateType>,
+    json: Option<String>,
+    path: Option<String>,
+    target_os: Option<String>,
+    replace: Option<bool>,
+) -> Result<BreakpointImportResult, String> {
+    let content = match (json, path) {
+        (Some(json), _) => json,
+        (None, Some(path)) => tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?,
+        (None, None) => return Err("Either json or path is required".to_string()),
+    };
+    let export: BreakpointExport = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid breakpoint export: {}", e))?;
+    if export.version > EXPORT_VERSION {
+        return Err(format!("Unsupported breakpoint export version {}", export.version));
+    }
+    let target = target_info(state.inner())?;
+    let target_os = target_os.unwrap_or_else(|| {
+        if target.target_os.is_empty() { export.target_os.clone() } else { target.target_os.clone() }
+    });
+
+    if replace.unwrap_or(false) {
+        for definition in definitions(&target_os).await? {
+            let definition = annotate(definition, &target);
+            if definition.installed {
+                uninstall(state.inner(), &definition, &target).await?;
+            }
+        }
+        let target_os = target_os.clone();
+        db::run(move |conn| {
+            conn.execute("DELETE FROM breakpoints WHERE target_os = ?1", params![target_os])
+                .map_err(|e| e.to_string())
+        }).await?;
+    }
+
+    let (mut result, changed) = {
+        let target_os = target_os.clone();
+        db::run(move |conn| {
+            let mut result = BreakpointImportResult::default();
+            let mut changed = Vec::new();
+            for (index, entry) in export.breakpoints.iter().enumerate() {
+                let label = entry.symbol.clone()
+                    .or_else(|| entry.module_offset.map(|o| format!("0x{:x}", o)))
+                    .unwrap_or_default();
+                let checked = import_location(&target_os, entry).and_then(|offset| {
+                    let condition = entry.condition.clone().filter(|c| !c.trim().is_empty());
+                    if let Some(condition) = &condition {
+                        expression::parse(condition).map_err(|e| format!("Invalid condition: {}", e))?;
+                    }
+                    Ok((offset, condition, validate_action(entry.action.clone())?))
+                });
+                let (module_offset, condition, action) = match checked {
+                    Ok(checked) => checked,
+                    Err(e) => {
+                        result.skipped += 1;
+                        result.errors.push(format!("#{} {}{}: {}", index, entry.module_name, label, e));
+                        continue;
+                    }
+                };
+
+                let existing: Option<i64> = conn.query_row(
+                    "SELECT id FROM breakpoints WHERE target_os = ?1 AND module_name = ?2 AND module_offset = ?3",
+                    params![target_os, entry.module_name, module_offset as i64],
+                    |row| row.get(0),
+                ).optional().map_err(|e| e.to_string())?;
+                match existing {
+                    Some(id) => {
+                        conn.execute(
+                            "UPDATE breakpoints SET hit_count = ?1, condition = ?2, enabled = ?3, is_software = ?4, action = ?5,
+                             updated_at = datetime('now') WHERE id = ?6",
+                            params![entry.hit_count, condition, entry.enabled as i64, entry.is_software as i64, action, id],
+                        ).map_err(|e| e.to_string())?;
+                        result.updated += 1;
+                        changed.push(load_definition(conn, id)?);
+                    }
+                    None => {
+                        conn.execute(
+                            "INSERT INTO breakpoints (target_os, module_name, module_offset, hit_count, condition, enabled, is_software, action, created_at, updated_at)
+                             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, datetime('now'), datetime('now'))",
+                            params![target_os, entry.module_name, module_offset as i64, entry.hit_count, condition,
+                                    entry.enabled as i64, entry.is_software as i64, action],
+                        ).map_err(|e| e.to_string())?;
+                        result.imported += 1;
+                        changed.push(load_definition(conn, conn.last_insert_rowid())?);
+                    }
+                }
+            }
+            Ok((result, changed))
+        }).await?
+    };
+
+    // Updated definitions that are already installed keep their server state;
+    // disabled ones are taken off
+    for definition in changed {
+        let definition = annotate(definition, &target);
+        if !definition.enabled && definition.installed {
+            uninstall(state.inner(), &definition, &target).await?;
+        }
+    }
+    if target_os == target.target_os {
+        result.restored = restore(state.inner()).await?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(enabled: bool, is_software: bool, hit_count: i32) -> BreakpointDefinition {
+        BreakpointDefinition {
+            id: 1,
+            target_os: "linux".to_string(),
+            module_name: "libc.so.6".to_string(),
+            module_offset: 0x1000,
+            hit_count,
+            condition: None,
+            action: "break".to_string(),
+            enabled,
+            is_software,
+            created_at: String::new(),
+            updated_at: String::new(),
+            address: Some(0x7f00_0000_1000),
+            installed: true,
+            condition_passes: 0,
+            condition_skips: 0,
+        }
+    }
+
+    #[test]
+    fn disabling_uninstalls() {
+        let previous = definition(true, false, 0);
+        assert_eq!(installed_change(&previous, &definition(false, false, 0)), InstalledChange::Uninstall);
+        // Also when the kind changes in the same update
+        assert_eq!(installed_change(&previous, &definition(false, true, 3)), InstalledChange::Uninstall);
+    }
+
+    #[test]
+    fn kind_or_hit_count_change_reinstalls() {
+        let previous = definition(true, false, 0);
+        assert_eq!(installed_change(&previous, &definition(true, true, 0)), InstalledChange::Reinstall);
+        assert_eq!(installed_change(&previous, &definition(true, false, 5)), InstalledChange::Reinstall);
+        assert_eq!(installed_change(&previous, &definition(true, false, 0)), InstalledChange::Keep);
+    }
+}
@@ -0,0 +1,417 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::{db, read_memory_from_server, SERVER_CONFIG};
+
+/// A loaded module to identify
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildIdModuleRequest {
+    pub module_name: String,
+    pub base: u64,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub local_path: Option<String>,  // Downloaded/on-disk copy to compare against
+}
+
+/// Build-id / UUID / PDB signature of one module
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModuleBuildIdInfo {
+    pub module_name: String,
+    pub format: Option<String>,      // "elf" | "macho" | "pe"
+    pub memory_id: Option<String>,   // ID read from the loaded image
+    pub file_id: Option<String>,     // ID read from local_path
+    pub pdb_name: Option<String>,    // PE only: CodeView PDB file name
+    pub pdb_age: Option<u32>,        // PE only: CodeView age
+    pub matches: Option<bool>,       // Both IDs known: does the file match the loaded image?
+    pub error: Option<String>,
+}
+
+/// Identity extracted from an image header
+struct ImageIdentity {
+    format: &'static str,
+    id: String,
+    pdb_name: Option<String>,
+    pdb_age: Option<u32>,
+}
+
+/// Random-access view of an executable image, either a file on disk or a
+/// module mapped in the target (where offsets are virtual addresses relative to base)
+trait ImageSource {
+    fn is_memory(&self) -> bool;
+    async fn read_at(&self, offset: u64, size: usize) -> Option<Vec<u8>>;
+}
+
+struct FileImage(Vec<u8>);
+
+impl ImageSource for FileImage {
+    fn is_memory(&self) -> bool {
+        false
+    }
+
+    async fn read_at(&self, offset: u64, size: usize) -> Option<Vec<u8>> {
+        let start = usize::try_from(offset).ok()?;
+        let end = start.checked_add(size)?.min(self.0.len());
+        (start < end).then(|| self.0[start..end].to_vec())
+    }
+}
+
+struct MemoryImage {
+    host: String,
+    port: u16,
+    base: u64,
+}
+
+impl ImageSource for MemoryImage {
+    fn is_memory(&self) -> bool {
+        true
+    }
+
+    async fn read_at(&self, offset: u64, size: usize) -> Option<Vec<u8>> {
+        read_memory_from_server(&self.host, self.port, self.base + offset, size).await.ok()
+    }
+}
+
+pub(crate) fn u16_at(buf: &[u8], pos: usize, big_endian: bool) -> Option<u16> {
+    let b: [u8; 2] = buf.get(pos..pos + 2)?.try_into().ok()?;
+    Some(if big_endian { u16::from_be_bytes(b) } else { u16::from_le_bytes(b) })
+}
+
+pub(crate) fn u32_at(buf: &[u8], pos: usize, big_endian: bool) -> Option<u32> {
+    let b: [u8; 4] = buf.get(pos..pos + 4)?.try_into().ok()?;
+    Some(if big_endian { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) })
+}
+
+pub(crate) fn u64_at(buf: &[u8], pos: usize, big_endian: bool) -> Option<u64> {
+    let b: [u8; 8] = buf.get(pos..pos + 8)?.try_into().ok()?;
+    Some(if big_endian { u64::from_be_bytes(b) } else { u64::from_le_bytes(b) })
+}
+
+/// Largest ELF program header table accepted from an image (2048 Elf32 entries)
+const MAX_PHDR_TABLE: usize = 0x10000;
+
+/// Validate the program header table geometry of an untrusted ELF header and
+/// return the table size. Each entry must hold a full Elf32_Phdr / Elf64_Phdr.
+pub(crate) fn elf_phdr_table_size(is_64: bool, phentsize: usize, phnum: usize) -> Result<usize, String> {
+    let min_entry = if is_64 { 56 } else { 32 };
+    if phentsize < min_entry {
+        return Err(format!("Invalid ELF program header size {} (expected at least {})", phentsize, min_entry));
+    }
+    phentsize.checked_mul(phnum)
+        .filter(|size| *size <= MAX_PHDR_TABLE)
+        .ok_or_else(|| format!("ELF program header table too large ({} x {} bytes)", phnum, phentsize))
+}
+
+/// Header bytes fetched up front; enough for ELF program headers, Mach-O load
+/// commands and the PE section table in practically all binaries
+const HEADER_READ_SIZE: usize = 0x4000;
+
+async fn extract_identity<S: ImageSource>(source: &S) -> Result<ImageIdentity, String> {
+    let header = source.read_at(0, HEADER_READ_SIZE).await
+        .ok_or("Failed to read image header")?;
+
+    if header.starts_with(b"\x7fELF") {
+        extract_elf_build_id(source, &header).await
+    } else if header.starts_with(b"MZ") {
+        extract_pe_guid(source, &header).await
+    } else if let Some(identity) = extract_macho_uuid(source, &header).await {
+        Ok(identity)
+    } else {
+        Err("Unrecognized image format".to_string())
+    }
+}
+
+/// ELF: NT_GNU_BUILD_ID note from a PT_NOTE segment
+async fn extract_elf_build_id<S: ImageSource>(source: &S, header: &[u8]) -> Result<ImageIdentity, String> {
+    const PT_LOAD: u32 = 1;
+    const PT_NOTE: u32 = 4;
+    const NT_GNU_BUILD_ID: u32 = 3;
+
+    let is_64 = header.get(4) == Some(&2);
+    let be = header.get(5) == Some(&2);
+    let (phoff, phentsize, phnum) = if is_64 {
+        (u64_at(header, 0x20, be), u16_at(header, 0x36, be), u16_at(header, 0x38, be))
+    } else {
+        (u32_at(header, 0x1c, be).map(u64::from), u16_at(header, 0x2a, be), u16_at(header, 0x2c, be))
+    };
+    let (phoff, phentsize, phnum) = match (phoff, phentsize, phnum) {
+        (Some(o), Some(s), Some(n)) => (o, s as usize, n as usize),
+        _ => return Err("Truncated ELF header".to_string()),
+    };
+
+    let table_size = elf_phdr_table_size(is_64, phentsize, phnum)?;
+    let phdrs = source.read_at(phoff, table_size).await
+        .ok_or("Failed to read ELF program headers")?;
+
+    // (type, offset, vaddr, filesz)
+    let segments: Vec<(u32, u64, u64, u64)> = phdrs
+        .chunks_exact(phentsize)
+        .filter_map(|ph| {
+            if is_64 {
+                Some((u32_at(ph, 0, be)?, u64_at(ph, 8, be)?, u64_at(ph, 16, be)?, u64_at(ph, 32, be)?))
+            } else {
+                Some((u32_at(ph, 0, be)?, u32_at(ph, 4, be)? as u64, u32_at(ph, 8, be)? as u64, u32_at(ph, 16, be)? as u64))
+            }
+        })
+        .collect();
+
+    // In memory, segments live at (vaddr - lowest PT_LOAD vaddr) from the module base
+    let load_bias = segments.iter()
+        .filter(|s| s.0 == PT_LOAD)
+        .map(|s| s.2 & !0xfff)
+        .min()
+        .unwrap_or(0);
+
+    for &(_, offset, vaddr, filesz) in segments.iter().filter(|s| s.0 == PT_NOTE) {
+        let location = if source.is_memory() { vaddr.wrapping_sub(load_bias) } else { offset };
+        let Some(notes) = source.read_at(location, filesz.min(0x10000) as usize).await else {
+            continue;
+        };
+
+        let mut pos = 0;
+        while pos + 12 <= notes.len() {
+            let namesz = u32_at(&notes, pos, be).unwrap_or(0) as usize;
+            let descsz = u32_at(&notes, pos + 4, be).unwrap_or(0) as usize;
+            let note_type = u32_at(&notes, pos + 8, be).unwrap_or(0);
+            let name_start = pos + 12;
+            let desc_start = name_start + namesz.div_ceil(4) * 4;
+            let next = desc_start + descsz.div_ceil(4) * 4;
+
+            if note_type == NT_GNU_BUILD_ID && notes.get(name_start..name_start + namesz) == Some(b"GNU\0") {
+                if let Some(desc) = notes.get(desc_start..desc_start + descsz) {
+                    return Ok(ImageIdentity {
+                        format: "elf",
+                        id: hex::encode(desc),
+                        pdb_name: None,
+                        pdb_age: None,
+                    });
+                }
+            }
+            if next <= pos {
+                break;
+            }
+            pos = next;
+        }
+    }
+
+    Err("ELF image has no GNU build-id note".to_string())
+}
+
+/// Mach-O: LC_UUID load command (thin images, or the first slice of a fat file)
+async fn extract_macho_uuid<S: ImageSource>(source: &S, header: &[u8]) -> Option<ImageIdentity> {
+    const LC_UUID: u32 = 0x1b;
+
+    let mut header = header.to_vec();
+    if u32_at(&header, 0, true) == Some(0xcafebabe) && !source.is_memory() {
+        let slice_offset = u32_at(&header, 8 + 8, true)? as u64;
+        header = source.read_at(slice_offset, HEADER_READ_SIZE).await?;
+    }
+
+    let header_size = match u32_at(&header, 0, false)? {
+        0xfeedfacf => 32,
+        0xfeedface => 28,
+        _ => return None,
+    };
+    let ncmds = u32_at(&header, 16, false)? as usize;
+
+    let mut pos = header_size;
+    for _ in 0..ncmds {
+        let cmd = u32_at(&header, pos, false)?;
+        let cmdsize = u32_at(&header, pos + 4, false)? as usize;
+        if cmd == LC_UUID {
+            let uuid = header.get(pos + 8..pos + 24)?;
+            let h = hex::encode_upper(uuid);
+            return Some(ImageIdentity {
+                format: "macho",
+                id: format!("{}-{}-{}-{}-{}", &h[0..8], &h[8..12], &h[12..16], &h[16..20], &h[20..32]),
+                pdb_name: None,
+                pdb_age: None,
+            });
+        }
+        if cmdsize == 0 {
+            break;
+        }
+        pos += cmdsize;
+    }
+    None
+}
+
+/// PE: CodeView (RSDS) record from the debug directory
+async fn extract_pe_guid<S: ImageSource>(source: &S, header: &[u8]) -> Result<ImageIdentity, String> {
+    const IMAGE_DEBUG_TYPE_CODEVIEW: u32 = 2;
+
+    let pe = u32_at(header, 0x3c, false).ok_or("Truncated DOS header")? as usize;
+    if header.get(pe..pe + 4) != Some(b"PE\0\0") {
+        return Err("Missing PE signature".to_string());
+    }
+    let coff = pe + 4;
+    let section_count = u16_at(header, coff + 2, false).ok_or("Truncated COFF header")? as usize;
+    let optional_size = u16_at(header, coff + 16, false).ok_or("Truncated COFF header")? as usize;
+    let optional = coff + 20;
+    let data_dirs = match u16_at(header, optional, false) {
+        Some(0x10b) => optional + 96,
+        Some(0x20b) => optional + 112,
+        _ => return Err("Unknown PE optional header".to_string()),
+    };
+    let debug_rva = u32_at(header, data_dirs + 6 * 8, false).unwrap_or(0) as u64;
+    let debug_size = u32_at(header, data_dirs + 6 * 8 + 4, false).unwrap_or(0) as usize;
+    if debug_rva == 0 || debug_size == 0 {
+        return Err("PE image has no debug directory".to_string());
+    }
+
+    // (virtual_address, virtual_size, raw_pointer)
+    let sections_start = optional + optional_size;
+    let sections: Vec<(u64, u64, u64)> = (0..section_count)
+        .filter_map(|i| {
+            let s = sections_start + i * 40;
+            Some((
+                u32_at(header, s + 12, false)? as u64,
+                u32_at(header, s + 8, false)? as u64,
+                u32_at(header, s + 20, false)? as u64,
+            ))
+        })
+        .collect();
+    let rva_to_location = |rva: u64| -> Option<u64> {
+        if source.is_memory() {
+            return Some(rva);
+        }
+        sections.iter()
+            .find(|(va, size, _)| rva >= *va && rva < va + size)
+            .map(|(va, _, raw)| rva - va + raw)
+    };
+
+    let debug_dir = source.read_at(rva_to_location(debug_rva).ok_or("Debug directory outside sections")?, debug_size).await
+        .ok_or("Failed to read debug directory")?;
+
+    for entry in debug_dir.chunks_exact(28) {
+        if u32_at(entry, 12, false) != Some(IMAGE_DEBUG_TYPE_CODEVIEW) {
+            continue;
+        }
+        let size = u32_at(entry, 16, false).unwrap_or(0) as usize;
+        let location = if source.is_memory() {
+            u32_at(entry, 20, false).unwrap_or(0) as u64
+        } else {
+            u32_at(entry, 24, false).unwrap_or(0) as u64
+        };
+        let Some(cv) = source.read_at(location, size.min(0x1000)).await else {
+            continue;
+        };
+        if !cv.starts_with(b"RSDS") || cv.len() < 24 {
+            continue;
+        }
+
+        let guid = format!(
+            "{:08X}{:04X}{:04X}{}",
+            u32_at(&cv, 4, false).unwrap_or(0),
+            u16_at(&cv, 8, false).unwrap_or(0),
+            u16_at(&cv, 10, false).unwrap_or(0),
+            hex::encode_upper(&cv[12..20])
+        );
+        let age = u32_at(&cv, 20, false).unwrap_or(0);
+        let name_bytes = &cv[24..];
+        let name_end = name_bytes.iter().position(|&b| b == 0).unwrap_or(name_bytes.len());
+        let pdb_path = String::from_utf8_lossy(&name_bytes[..name_end]).to_string();
+        let pdb_name = pdb_path.rsplit(['\\', '/']).next().unwrap_or(&pdb_path).to_string();
+
+        return Ok(ImageIdentity {
+            format: "pe",
+            id: guid,
+            pdb_name: Some(pdb_name),
+            pdb_age: Some(age),
+        });
+    }
+
+    Err("PE image has no CodeView record".to_string())
+}
+
+/// Read build-ids (ELF), UUIDs (Mach-O) and PDB GUIDs (PE) of loaded modules
+/// from target memory and, when given, from their local copies. Results are
+/// stored in module_build_ids so caches and symbol lookups can be keyed by them.
+#[tauri::command]
+pub async fn get_module_build_ids(
+    target_os: String,
+    modules: Vec<BuildIdModuleRequest>,
+) -> Result<Vec<ModuleBuildIdInfo>, String> {
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+
+    let mut results = Vec::with_capacity(modules.len());
+    for module in modules {
+        let mut info = ModuleBuildIdInfo {
+            module_name: module.module_name.clone(),
+            ..Default::default()
+        };
+        let mut errors: Vec<String> = Vec::new();
+
+        if !host.is_empty() {
+            let image = MemoryImage { host: host.clone(), port, base: module.base };
+            match extract_identity(&image).await {
+                Ok(identity) => {
+                    info.format = Some(identity.format.to_string());
+                    info.memory_id = Some(identity.id);
+                    info.pdb_name = identity.pdb_name;
+                    info.pdb_age = identity.pdb_age;
+                }
+                Err(e) => errors.push(format!("memory: {}", e)),
+            }
+        }
+
+        if let Some(path) = &module.local_path {
+            match tokio::fs::read(path).await {
+                Ok(data) => match extract_identity(&FileImage(data)).await {
+                    Ok(identity) => {
+                        info.format.get_or_insert_with(|| identity.format.to_string());
+                        info.pdb_name = info.pdb_name.take().or(identity.pdb_name);
+                        info.pdb_age = info.pdb_age.or(identity.pdb_age);
+                        info.file_id = Some(identity.id);
+                    }
+                    Err(e) => errors.push(format!("file: {}", e)),
+                },
+                Err(e) => errors.push(format!("file: {}", e)),
+            }
+        }
+
+        if let (Some(memory_id), Some(file_id)) = (&info.memory_id, &info.file_id) {
+            info.matches = Some(memory_id == file_id);
+        }
+        if info.memory_id.is_none() && info.file_id.is_none() {
+            info.error = Some(if errors.is_empty() {
+                "No server connection configured".to_string()
+            } else {
+                errors.join("; ")
+            });
+        }
+
+        results.push(info);
+    }
+
+    db::run(move |conn| {
+        for info in &results {
+            let Some(build_id) = info.memory_id.as_ref().or(info.file_id.as_ref()) else { continue };
+            conn.execute(
+                "INSERT OR REPLACE INTO module_build_ids
+                 (target_os, module_name, format, build_id, pdb_name, pdb_age, file_matches, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'))",
+                params![target_os, info.module_name, info.format, build_id, info.pdb_name, info.pdb_age, info.matches],
+            ).map_err(|e| e.to_string())?;
+        }
+        Ok(results)
+    }).await
+}
+
+/// Stored build-id of a module, used as a stable cache key across renames/updates
+pub fn get_stored_build_id(conn: &Connection, target_os: &str, module_name: &str) -> Option<String> {
+    conn.query_row(
+        "SELECT build_id FROM module_build_ids WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+        |row| row.get(0),
+    ).ok()
+}
+
+/// Get the stored build-id for a module (None if never identified)
+#[tauri::command]
+pub async fn get_module_build_id(target_os: String, module_name: String) -> Result<Option<String>, String> {
+    db::run(move |conn| Ok(get_stored_build_id(conn, &target_os, &module_name))).await
+}
@@ -0,0 +1,177 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::state::{AppState, AppStateType};
+use crate::{data_overlay, db, ghidra_search, symbolizer};
+
+pub use dynadbg_core::cache_versions::{stamp, version, CacheVersion};
+
+// Cache tables whose rows carry the module's version stamp
+const STAMPED_TABLES: &[&str] = &[
+    "ghidra_functions_cache",
+    "ghidra_decompile_cache",
+    "ghidra_xref_cache",
+    "ghidra_callgraph_cache",
+    "ghidra_data_cache",
+];
+
+// Tables derived from the module's code; patches and renames drop them
+const CODE_TABLES: &[&str] = &["ghidra_decompile_cache", "ghidra_xref_cache", "ghidra_callgraph_cache"];
+
+pub const REASONS: [&str; 3] = ["patch", "rename", "reanalysis"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheTableStatus {
+    pub table: String,
+    pub entries: i64,
+    pub stale: i64,                     // Rows stamped with an older version (served as misses)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheStatus {
+    pub target_os: String,
+    pub module_name: String,
+    pub version: CacheVersion,
+    pub invalidations: i64,
+    pub last_reason: Option<String>,
+    pub invalidated_at: Option<String>,
+    pub tables: Vec<CacheTableStatus>,
+}
+
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS module_cache_versions (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            analyzed_at INTEGER,
+            patch_counter INTEGER NOT NULL DEFAULT 0,
+            invalidations INTEGER NOT NULL DEFAULT 0,
+            last_reason TEXT,
+            invalidated_at TEXT,
+            PRIMARY KEY(target_os, module_name)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Migration adding the stamp column. Rows from before stamping keep a NULL
+/// version and stay valid until invalidated.
+pub fn add_stamp_columns(conn: &Connection) -> Result<(), String> {
+    for table in STAMPED_TABLES {
+        db::add_column(conn, table, "cache_version", "TEXT")?;
+    }
+    Ok(())
+}
+
+/// Drop the module's cache entries that `reason` makes stale and advance its
+/// version: "patch" and "rename" drop decompilations, xrefs and the call
+/// graph; "reanalysis" drops everything. Returns the rows removed.
+pub fn invalidate(conn: &Connection, target_os: &str, module_name: &str, reason: &str) -> Result<usize, String> {
+    let dropped = match reason {
+        "patch" | "rename" => CODE_TABLES,
+        "reanalysis" => STAMPED_TABLES,
+        _ => return Err(format!("Unknown invalidation reason '{}' (expected one of {})", reason, REASONS.join(", "))),
+    };
+    let previous = stamp(conn, target_os, module_name);
+    let analyzed_at = (reason == "reanalysis").then(|| (AppState::current_timestamp() / 1000) as i64);
+    conn.execute(
+        "INSERT INTO module_cache_versions (target_os, module_name, analyzed_at, patch_counter, invalidations, last_reason, invalidated_at)
+         VALUES (?1, ?2, ?3, ?4, 1, ?5, datetime('now'))
+         ON CONFLICT(target_os, module_name) DO UPDATE SET
+             analyzed_at = COALESCE(excluded.analyzed_at, analyzed_at),
+             patch_counter = patch_counter + excluded.patch_counter,
+             invalidations = invalidations + 1,
+             last_reason = excluded.last_reason,
+             invalidated_at = excluded.invalidated_at",
+        params![target_os, module_name, analyzed_at, (reason == "patch") as i64, reason],
+    ).map_err(|e| e.to_string())?;
+
+    let mut removed = 0;
+    for table in dropped {
+        removed += conn.execute(
+            &format!("DELETE FROM {} WHERE target_os = ?1 AND module_name = ?2", table),
+            params![target_os, module_name],
+        ).map_err(|e| e.to_string())?;
+    }
+    // The kept rows were valid under the previous version and still are
+    let current = stamp(conn, target_os, module_name);
+    for table in STAMPED_TABLES.iter().filter(|t| !dropped.contains(t)) {
+        conn.execute(
+            &format!("UPDATE {} SET cache_version = ?3 WHERE target_os = ?1 AND module_name = ?2 AND cache_version = ?4", table),
+            params![target_os, module_name, current, previous],
+        ).map_err(|e| e.to_string())?;
+    }
+    removed += ghidra_search::invalidate_module(conn, target_os, module_name)?;
+    if reason == "reanalysis" {
+        symbolizer::invalidate_module(target_os, module_name);
+        data_overlay::invalidate_all();
+    }
+    Ok(removed)
+}
+
+fn status(conn: &Connection, target_os: &str, module_name: &str) -> Result<CacheStatus, String> {
+    let version = version(conn, target_os, module_name);
+    let (invalidations, last_reason, invalidated_at) = conn.query_row(
+        "SELECT invalidations, last_reason, invalidated_at FROM module_cache_versions WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    ).optional().map_err(|e| e.to_string())?.unwrap_or((0, None, None));
+    let mut tables = Vec::new();
+    for table in STAMPED_TABLES {
+        let (entries, stale) = conn.query_row(
+            &format!(
+                "SELECT COUNT(*), COALESCE(SUM(cache_version IS NOT NULL AND cache_version != ?3), 0)
+                 FROM {} WHERE target_os = ?1 AND module_name = ?2",
+                table
+            ),
+            params![target_os, module_name, version.stamp],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).map_err(|e| e.to_string())?;
+        tables.push(CacheTableStatus { table: table.to_string(), entries, stale });
+    }
+    Ok(CacheStatus {
+        target_os: target_os.to_string(),
+        module_name: module_name.to_string(),
+        version,
+        invalidations,
+        last_reason,
+        invalidated_at,
+        tables,
+    })
+}
+
+fn resolve_target_os(state: &AppStateType, target_os: Option<String>) -> String {
+    target_os.unwrap_or_else(|| state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.target_os.clone()))
+        .unwrap_or_default())
+}
+
+/// Version and per-table entry counts of a module's Ghidra caches
+#[tauri::command]
+pub async fn get_cache_status(
+    state: tauri::State<'_, AppStateType>,
+    module_name: String,
+    target_os: Option<String>,
+) -> Result<CacheStatus, String> {
+    let target_os = resolve_target_os(state.inner(), target_os);
+    db::run(move |conn| {
+        status(conn, &target_os, &module_name)
+    }).await
+}
+
+/// Invalidate a module's caches by hand ("patch" | "rename" | "reanalysis")
+#[tauri::command]
+pub async fn invalidate_caches(
+    state: tauri::State<'_, AppStateType>,
+    module_name: String,
+    reason: String,
+    target_os: Option<String>,
+) -> Result<CacheStatus, String> {
+    let target_os = resolve_target_os(state.inner(), target_os);
+    db::run(move |conn| {
+        invalidate(conn, &target_os, &module_name, &reason)?;
+        status(conn, &target_os, &module_name)
+    }).await
+}
@@ -0,0 +1,251 @@
+use layout::backends::svg::SVGWriter;
+use layout::core::base::Orientation;
+use layout::core::color::Color;
+use layout::core::geometry::Point;
+use layout::core::style::StyleAttr;
+use layout::std_shapes::shapes::{Arrow, Element, ShapeKind};
+use layout::topo::layout::VisualGraph;
+use std::collections::HashMap;
+
+use crate::{escape_markup, wasm_cfg, GhidraCfgBlock, GhidraCfgResult};
+
+// Node box metrics for the SVG layout (monospace text at FONT_SIZE)
+const FONT_SIZE: usize = 12;
+const CHAR_W: f64 = 7.2;
+const LINE_H: f64 = 14.0;
+const PAD: f64 = 8.0;
+
+/// Web color of an edge type; wasm CFGs use the same types plus "normal"
+fn edge_color(edge_type: &str) -> &'static str {
+    match edge_type {
+        "conditional-true" => "#2e7d32",
+        "conditional-false" => "#c62828",
+        "unconditional" => "#1565c0",
+        _ => "#616161",
+    }
+}
+
+fn header_color(block: &GhidraCfgBlock) -> &'static str {
+    if block.is_entry {
+        "#c8e6c9"
+    } else if block.is_exit {
+        "#ffcdd2"
+    } else {
+        "#e0e0e0"
+    }
+}
+
+/// layout-rs color (RGBA) of a "#rrggbb" web color
+fn rgba(web: &str) -> Color {
+    Color::new((u32::from_str_radix(&web[1..], 16).unwrap_or(0) << 8) | 0xff)
+}
+
+fn block_lines(block: &GhidraCfgBlock) -> Vec<String> {
+    let mut lines = vec![format!("{}:", block.start_address)];
+    for ins in &block.instructions {
+        if ins.operands.is_empty() {
+            lines.push(format!("{}  {}", ins.address, ins.opcode));
+        } else {
+            lines.push(format!("{}  {} {}", ins.address, ins.opcode, ins.operands));
+        }
+    }
+    lines
+}
+
+/// Quoted DOT identifier
+fn dot_id(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// Serialize a CFG to Graphviz DOT (one HTML-like label per basic block)
+fn to_dot(cfg: &GhidraCfgResult) -> String {
+    let name = cfg.function_name.as_deref().unwrap_or("function");
+    let mut out = String::new();
+    out.push_str(&format!("digraph {} {{\n", dot_id(name)));
+    out.push_str("    node [shape=plaintext, fontname=\"Courier\", fontsize=10];\n");
+    out.push_str("    edge [fontname=\"Courier\", fontsize=9];\n");
+
+    for block in &cfg.blocks {
+        let lines = block_lines(block);
+        out.push_str(&format!(
+            "    {} [label=<<TABLE BORDER=\"1\" CELLBORDER=\"0\" CELLSPACING=\"0\">",
+            dot_id(&block.id)
+        ));
+        out.push_str(&format!(
+            "<TR><TD BGCOLOR=\"{}\" ALIGN=\"LEFT\"><B>{}</B></TD></TR>",
+            header_color(block),
+            escape_markup(&lines[0])
+        ));
+        for line in &lines[1..] {
+            out.push_str(&format!("<TR><TD ALIGN=\"LEFT\">{}</TD></TR>", escape_markup(line)));
+        }
+        out.push_str("</TABLE>>];\n");
+    }
+
+    for edge in &cfg.edges {
+        out.push_str(&format!(
+            "    {} -> {} [color=\"{}\"];\n",
+            dot_id(&edge.from),
+            dot_id(&edge.to),
+            edge_color(&edge.edge_type)
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Render a CFG to a standalone SVG with the layout-rs layered layout; entry
+/// and exit blocks are filled like the graph view headers
+fn to_svg(cfg: &GhidraCfgResult) -> String {
+    if cfg.blocks.is_empty() {
+        return "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"0\" height=\"0\"></svg>\n".to_string();
+    }
+
+    let mut graph = VisualGraph::new(Orientation::TopToBottom);
+    let mut handles = HashMap::new();
+    for block in &cfg.blocks {
+        let lines = block_lines(block);
+        let max_chars = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let size = Point::new(max_chars as f64 * CHAR_W + PAD * 2.0, lines.len() as f64 * LINE_H + PAD * 2.0);
+        let look = StyleAttr::new(rgba("#424242"), 1, Some(rgba(header_color(block))), 0, FONT_SIZE);
+        let element = Element::create(ShapeKind::new_box(&lines.join("\n")), look, Orientation::LeftToRight, size);
+        handles.insert(block.id.as_str(), graph.add_node(element));
+    }
+
+    for edge in &cfg.edges {
+        let (Some(&from), Some(&to)) = (handles.get(edge.from.as_str()), handles.get(edge.to.as_str())) else {
+            continue;
+        };
+        let mut arrow = Arrow::simple("");
+        arrow.look.line_color = rgba(edge_color(&edge.edge_type));
+        arrow.look.line_width = 2;
+        graph.add_edge(arrow, from, to);
+    }
+
+    let mut svg = SVGWriter::new();
+    graph.do_it(false, false, false, &mut svg);
+    svg.finalize()
+}
+
+fn render(cfg: &GhidraCfgResult, format: &str) -> Result<String, String> {
+    match format.to_lowercase().as_str() {
+        "dot" | "gv" | "graphviz" => Ok(to_dot(cfg)),
+        "svg" => Ok(to_svg(cfg)),
+        other => Err(format!("Unsupported CFG export format: {}", other)),
+    }
+}
+
+/// Export a CFG (from ghidra_server_cfg or analyze_wasm_cfg) as DOT or SVG.
+/// Writes to `output_path` when given and returns the serialized graph
+/// either way.
+#[tauri::command]
+pub async fn export_cfg(
+    function: GhidraCfgResult,
+    format: String,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let content = tokio::task::spawn_blocking(move || render(&function, &format))
+        .await
+        .map_err(|e| e.to_string())??;
+    if let Some(path) = output_path {
+        tokio::fs::write(&path, &content)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    Ok(content)
+}
+
+/// Build the CFG of a wasm function and export it like `export_cfg`
+#[tauri::command]
+pub async fn export_wasm_cfg(
+    binary_data: Vec<u8>,
+    function_index: u32,
+    format: String,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let function = wasm_cfg::analyze_wasm_cfg(binary_data, function_index).await?;
+    export_cfg(function, format, output_path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GhidraCfgEdge, GhidraCfgInstruction};
+
+    fn block(id: &str, start: &str, is_entry: bool, is_exit: bool, instructions: &[(&str, &str)]) -> GhidraCfgBlock {
+        GhidraCfgBlock {
+            id: id.to_string(),
+            start_address: start.to_string(),
+            end_address: start.to_string(),
+            instructions: instructions.iter()
+                .map(|(opcode, operands)| GhidraCfgInstruction {
+                    address: start.to_string(),
+                    bytes: String::new(),
+                    opcode: opcode.to_string(),
+                    operands: operands.to_string(),
+                })
+                .collect(),
+            successors: Vec::new(),
+            predecessors: Vec::new(),
+            is_entry,
+            is_exit,
+        }
+    }
+
+    fn edge(from: &str, to: &str, edge_type: &str) -> GhidraCfgEdge {
+        GhidraCfgEdge { from: from.to_string(), to: to.to_string(), edge_type: edge_type.to_string() }
+    }
+
+    fn cfg(name: &str, blocks: Vec<GhidraCfgBlock>, edges: Vec<GhidraCfgEdge>) -> GhidraCfgResult {
+        GhidraCfgResult {
+            success: true,
+            function_name: Some(name.to_string()),
+            function_offset: Some("0x1000".to_string()),
+            blocks,
+            edges,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn dot_escapes_identifiers_and_labels() {
+        let graph = cfg(
+            "operator\"\"_x\\",
+            vec![block("a\"b", "0x1000", true, true, &[("call", "std::vector<int>::push_back&")])],
+            vec![edge("a\"b", "a\"b", "unconditional")],
+        );
+        let dot = to_dot(&graph);
+        assert!(dot.starts_with("digraph \"operator\\\"\\\"_x\\\\\" {\n"));
+        assert!(dot.contains("    \"a\\\"b\" [label=<"));
+        assert!(dot.contains("call std::vector&lt;int&gt;::push_back&amp;"));
+        assert!(!dot.contains("<int>"));
+        assert!(dot.contains("    \"a\\\"b\" -> \"a\\\"b\" [color=\"#1565c0\"];"));
+    }
+
+    #[test]
+    fn svg_renders_diamond() {
+        let graph = cfg(
+            "diamond",
+            vec![
+                block("A", "0x1000", true, false, &[("cmp", "x0, #0"), ("b.eq", "0x1010")]),
+                block("B", "0x1008", false, false, &[("mov", "x0, #1")]),
+                block("C", "0x1010", false, false, &[("mov", "x0, #2")]),
+                block("D", "0x1018", false, true, &[("ret", "")]),
+            ],
+            vec![
+                edge("A", "B", "conditional-false"),
+                edge("A", "C", "conditional-true"),
+                edge("B", "D", "unconditional"),
+                edge("C", "D", "normal"),
+            ],
+        );
+        let svg = render(&graph, "SVG").unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        for address in ["0x1000:", "0x1008:", "0x1010:", "0x1018:"] {
+            assert!(svg.contains(address), "missing block {}", address);
+        }
+        assert!(render(&graph, "png").is_err());
+    }
+}
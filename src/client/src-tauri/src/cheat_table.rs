@@ -0,0 +1,420 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{AppStateType, ModuleInfo};
+
+// `"game.exe"+0012AB30` or `game.exe+12AB30`
+static MODULE_ADDRESS: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^"?([^"+\[\]]+?)"?\s*\+\s*(?:0x)?([0-9A-Fa-f]+)$"#).unwrap());
+
+// Windows virtual-key codes (as CE stores them) and accelerator names
+const KEY_NAMES: &[(u32, &str)] = &[
+    (0x08, "Backspace"), (0x09, "Tab"), (0x0D, "Enter"), (0x10, "Shift"), (0x11, "Ctrl"), (0x12, "Alt"),
+    (0x13, "Pause"), (0x14, "CapsLock"), (0x1B, "Escape"), (0x20, "Space"), (0x21, "PageUp"), (0x22, "PageDown"),
+    (0x23, "End"), (0x24, "Home"), (0x25, "Left"), (0x26, "Up"), (0x27, "Right"), (0x28, "Down"),
+    (0x2D, "Insert"), (0x2E, "Delete"), (0x6A, "NumpadMultiply"), (0x6B, "NumpadAdd"), (0x6D, "NumpadSubtract"),
+    (0x6E, "NumpadDecimal"), (0x6F, "NumpadDivide"),
+];
+
+// CE hotkey actions and their DynaDbg names
+const HOTKEY_ACTIONS: &[(&str, &str)] = &[
+    ("Toggle Activation", "toggle_freeze"),
+    ("Activate", "freeze"),
+    ("Deactivate", "unfreeze"),
+    ("Set Value", "set_value"),
+    ("Increase Value", "increase_value"),
+    ("Decrease Value", "decrease_value"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheatTableHotkey {
+    pub keys: String,                       // Accelerator, e.g. "Ctrl+F1"
+    pub action: String,                     // "toggle_freeze" | "set_value" | ... (unknown CE actions kept verbatim)
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// One saved address, shaped like the address list's bookmarks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheatTableEntry {
+    pub description: String,
+    pub address: String,                    // "0x..." or pointer chain "base+0x10 → [0x18] → [0x20]"
+    #[serde(default)]
+    pub library_expression: Option<String>, // "module + 0x..." for addresses inside a module
+    pub value_type: String,                 // Scan data type; "ptr" for pointer chains
+    #[serde(default)]
+    pub ptr_value_type: Option<String>,     // Type at the end of a pointer chain
+    #[serde(default)]
+    pub size: Option<usize>,                // Length of string / bytes entries
+    #[serde(default)]
+    pub display_format: Option<String>,     // "dec" | "hex"
+    #[serde(default)]
+    pub hotkeys: Vec<CheatTableHotkey>,
+    #[serde(default)]
+    pub group: Option<String>,              // Description of the enclosing group header
+    #[serde(default)]
+    pub resolved: bool,                     // Address is absolute in the current process
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheatTableImport {
+    pub entries: Vec<CheatTableEntry>,
+    pub skipped: Vec<String>,               // Scripts and unsupported types, by description
+}
+
+fn attached_modules(state: &AppStateType) -> Vec<ModuleInfo> {
+    state.lock().map(|s| s.attached_modules.clone()).unwrap_or_default()
+}
+
+fn find_module<'a>(modules: &'a [ModuleInfo], name: &str) -> Option<&'a ModuleInfo> {
+    modules.iter().find(|m| m.modulename.eq_ignore_ascii_case(name))
+}
+
+fn parse_hex(text: &str) -> Option<u64> {
+    let text = text.trim();
+    u64::from_str_radix(text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")).unwrap_or(text), 16).ok()
+}
+
+/// Signed offset of a chain step ("0x10", "+0x10", "-8", CE's bare "10")
+fn parse_offset(text: &str) -> Option<i64> {
+    let text = text.trim().trim_start_matches('[').trim_end_matches(']').trim();
+    match text.strip_prefix('-') {
+        Some(rest) => parse_hex(rest).map(|v| -(v as i64)),
+        None => parse_hex(text.trim_start_matches('+')).map(|v| v as i64),
+    }
+}
+
+fn format_offset(offset: i64, prefix: &str) -> String {
+    if offset < 0 {
+        format!("-{}{:X}", prefix, offset.unsigned_abs())
+    } else {
+        format!("{}{:X}", prefix, offset)
+    }
+}
+
+fn text(node: roxmltree::Node, tag: &str) -> Option<String> {
+    node.children()
+        .find(|c| c.has_tag_name(tag))
+        .and_then(|c| c.text())
+        .map(|t| t.trim().to_string())
+}
+
+fn flag(node: roxmltree::Node, tag: &str) -> bool {
+    text(node, tag).as_deref() == Some("1")
+}
+
+fn vk_to_name(code: u32) -> String {
+    match code {
+        0x30..=0x39 | 0x41..=0x5A => char::from_u32(code).unwrap_or('?').to_string(),
+        0x60..=0x69 => format!("Numpad{}", code - 0x60),
+        0x70..=0x87 => format!("F{}", code - 0x6F),
+        _ => KEY_NAMES.iter().find(|(c, _)| *c == code).map(|(_, n)| n.to_string()).unwrap_or_else(|| format!("VK{}", code)),
+    }
+}
+
+fn name_to_vk(name: &str) -> Option<u32> {
+    let upper = name.to_ascii_uppercase();
+    if upper.len() == 1 && upper.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return upper.chars().next().map(|c| c as u32);
+    }
+    if let Some(n) = upper.strip_prefix("NUMPAD").and_then(|n| n.parse::<u32>().ok()) {
+        return Some(0x60 + n);
+    }
+    if let Some(n) = upper.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()).filter(|n| (1..=24).contains(n)) {
+        return Some(0x6F + n);
+    }
+    if let Some(code) = upper.strip_prefix("VK").and_then(|n| n.parse().ok()) {
+        return Some(code);
+    }
+    match upper.as_str() {
+        "CONTROL" | "CMDORCTRL" | "COMMANDORCONTROL" => Some(0x11),
+        "OPTION" => Some(0x12),
+        _ => KEY_NAMES.iter().find(|(_, n)| n.eq_ignore_ascii_case(name)).map(|(c, _)| *c),
+    }
+}
+
+/// (scan data type, size) of a CE variable type; None for scripts and
+/// types the scanner has no equivalent for
+fn import_type(entry: roxmltree::Node) -> Option<(String, Option<usize>)> {
+    let signed = flag(entry, "ShowAsSigned");
+    let integer = |bits: u32| Some((format!("{}int{}", if signed { "" } else { "u" }, bits), None));
+    match text(entry, "VariableType")?.as_str() {
+        "Byte" => integer(8),
+        "2 Bytes" => integer(16),
+        "4 Bytes" => integer(32),
+        "8 Bytes" => integer(64),
+        "Float" => Some(("float".to_string(), None)),
+        "Double" => Some(("double".to_string(), None)),
+        "String" => {
+            let length: usize = text(entry, "Length").and_then(|l| l.parse().ok()).unwrap_or(16);
+            let width = if flag(entry, "Unicode") { 2 } else { 1 };
+            Some(("string".to_string(), Some(length * width)))
+        }
+        "Array of byte" => Some(("bytes".to_string(), Some(text(entry, "ByteLength").and_then(|l| l.parse().ok()).unwrap_or(4)))),
+        _ => None,
+    }
+}
+
+/// (CE variable type, signed) of a scan data type
+fn export_type(value_type: &str) -> Result<(&'static str, bool), String> {
+    Ok(match value_type {
+        "int8" => ("Byte", true),
+        "uint8" => ("Byte", false),
+        "int16" => ("2 Bytes", true),
+        "uint16" => ("2 Bytes", false),
+        "int32" => ("4 Bytes", true),
+        "uint32" => ("4 Bytes", false),
+        "int64" => ("8 Bytes", true),
+        "uint64" => ("8 Bytes", false),
+        "float" => ("Float", false),
+        "double" => ("Double", false),
+        "string" => ("String", false),
+        "bytes" => ("Array of byte", false),
+        other => return Err(format!("Type '{}' has no Cheat Engine equivalent", other)),
+    })
+}
+
+fn import_hotkeys(entry: roxmltree::Node) -> Vec<CheatTableHotkey> {
+    let Some(hotkeys) = entry.children().find(|c| c.has_tag_name("Hotkeys")) else {
+        return Vec::new();
+    };
+    hotkeys.children().filter(|c| c.has_tag_name("Hotkey")).filter_map(|hotkey| {
+        let keys: Vec<String> = hotkey.children()
+            .find(|c| c.has_tag_name("Keys"))?
+            .children()
+            .filter(|c| c.has_tag_name("Key"))
+            .filter_map(|k| k.text()?.trim().parse().ok())
+            .map(vk_to_name)
+            .collect();
+        let action = text(hotkey, "Action").unwrap_or_default();
+        Some(CheatTableHotkey {
+            keys: keys.join("+"),
+            action: HOTKEY_ACTIONS.iter().find(|(ce, _)| *ce == action).map(|(_, a)| a.to_string()).unwrap_or(action),
+            value: text(hotkey, "Value"),
+            description: text(hotkey, "Description"),
+        })
+    }).collect()
+}
+
+/// (address, library expression, resolved) of a CE address; module
+/// addresses resolve against the attached modules
+fn import_address(address: &str, modules: &[ModuleInfo]) -> (String, Option<String>, bool) {
+    if let Some(captures) = MODULE_ADDRESS.captures(address) {
+        let module = captures[1].trim();
+        let offset = parse_hex(&captures[2]).unwrap_or(0);
+        let expression = format!("{} + 0x{:X}", module, offset);
+        return match find_module(modules, module) {
+            Some(m) => (format!("0x{:X}", m.base + offset), Some(expression), true),
+            None => (format!("{}+0x{:X}", module, offset), Some(expression), false),
+        };
+    }
+    match parse_hex(address) {
+        Some(absolute) => (format!("0x{:X}", absolute), None, true),
+        None => (address.to_string(), None, false), // Symbols and expressions are kept as written
+    }
+}
+
+fn import_entries(
+    parent: roxmltree::Node,
+    group: Option<&str>,
+    modules: &[ModuleInfo],
+    result: &mut CheatTableImport,
+) {
+    let Some(list) = parent.children().find(|c| c.has_tag_name("CheatEntries")) else {
+        return;
+    };
+    for entry in list.children().filter(|c| c.has_tag_name("CheatEntry")) {
+        let description = text(entry, "Description").unwrap_or_default().trim_matches('"').to_string();
+        if flag(entry, "GroupHeader") || (text(entry, "VariableType").is_none() && text(entry, "AssemblerScript").is_none()) {
+            import_entries(entry, Some(&description), modules, result);
+            continue;
+        }
+        let (Some((data_type, size)), Some(address)) = (import_type(entry), text(entry, "Address")) else {
+            result.skipped.push(description);
+            import_entries(entry, group, modules, result);
+            continue;
+        };
+        // CE lists offsets from the last dereference to the first
+        let offsets: Vec<i64> = entry.children()
+            .find(|c| c.has_tag_name("Offsets"))
+            .map(|o| o.children().filter(|c| c.has_tag_name("Offset")).filter_map(|c| parse_offset(c.text()?)).collect())
+            .unwrap_or_default();
+        let (resolved_address, library_expression, resolved) = import_address(&address, modules);
+        let mut imported = CheatTableEntry {
+            description,
+            address: resolved_address,
+            library_expression,
+            value_type: data_type,
+            ptr_value_type: None,
+            size,
+            display_format: flag(entry, "ShowAsHex").then(|| "hex".to_string()),
+            hotkeys: import_hotkeys(entry),
+            group: group.map(str::to_string),
+            resolved,
+        };
+        if !offsets.is_empty() {
+            // The chain base stays module-relative so it follows ASLR
+            let base = match &imported.library_expression {
+                Some(expression) => expression.replace(' ', ""),
+                None => imported.address.clone(),
+            };
+            let steps: Vec<String> = offsets.iter().rev().map(|o| format!("[{}]", format_offset(*o, "0x"))).collect();
+            imported.address = format!("{} → {}", base, steps.join(" → "));
+            imported.ptr_value_type = Some(std::mem::replace(&mut imported.value_type, "ptr".to_string()));
+            imported.library_expression = None;
+            imported.resolved = false;
+        }
+        result.entries.push(imported);
+        import_entries(entry, group, modules, result);
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// CE address of a base: `"module"+offset` when inside a loaded module
+fn export_address(address: &str, library_expression: Option<&str>, modules: &[ModuleInfo]) -> String {
+    let relative = library_expression.unwrap_or(address).replace(' ', "");
+    if let Some(captures) = MODULE_ADDRESS.captures(&relative) {
+        if !captures[1].starts_with("0x") {
+            return format!("\"{}\"+{:X}", captures[1].trim(), parse_hex(&captures[2]).unwrap_or(0));
+        }
+    }
+    match parse_hex(address) {
+        Some(absolute) => match modules.iter().find(|m| absolute >= m.base && absolute < m.base.saturating_add(m.size)) {
+            Some(m) => format!("\"{}\"+{:X}", m.modulename, absolute - m.base),
+            None => format!("{:X}", absolute),
+        },
+        None => address.to_string(),
+    }
+}
+
+fn export_hotkeys(hotkeys: &[CheatTableHotkey], indent: &str) -> String {
+    if hotkeys.is_empty() {
+        return String::new();
+    }
+    let mut xml = format!("{}<Hotkeys>\n", indent);
+    for (id, hotkey) in hotkeys.iter().enumerate() {
+        let action = HOTKEY_ACTIONS.iter().find(|(_, a)| *a == hotkey.action).map(|(ce, _)| *ce).unwrap_or(&hotkey.action);
+        xml.push_str(&format!("{}  <Hotkey>\n{}    <Action>{}</Action>\n{}    <Keys>\n", indent, indent, escape(action), indent));
+        for code in hotkey.keys.split('+').filter_map(|k| name_to_vk(k.trim())) {
+            xml.push_str(&format!("{}      <Key>{}</Key>\n", indent, code));
+        }
+        xml.push_str(&format!("{}    </Keys>\n", indent));
+        if let Some(value) = &hotkey.value {
+            xml.push_str(&format!("{}    <Value>{}</Value>\n", indent, escape(value)));
+        }
+        if let Some(description) = &hotkey.description {
+            xml.push_str(&format!("{}    <Description>{}</Description>\n", indent, escape(description)));
+        }
+        xml.push_str(&format!("{}    <ID>{}</ID>\n{}  </Hotkey>\n", indent, id, indent));
+    }
+    xml.push_str(&format!("{}</Hotkeys>\n", indent));
+    xml
+}
+
+fn export_entry(entry: &CheatTableEntry, id: usize, indent: &str, modules: &[ModuleInfo]) -> Result<String, String> {
+    let mut parts = entry.address.split('→').map(str::trim);
+    let base = parts.next().unwrap_or_default();
+    let offsets: Vec<i64> = parts
+        .map(|step| parse_offset(step).ok_or_else(|| format!("Invalid pointer step '{}' in '{}'", step, entry.description)))
+        .collect::<Result<_, _>>()?;
+    let value_type = match entry.value_type.as_str() {
+        "ptr" => entry.ptr_value_type.as_deref().unwrap_or(if offsets.is_empty() { "uint64" } else { "int32" }),
+        other => other,
+    };
+    let (variable_type, signed) = export_type(value_type)?;
+    let base_address = if offsets.is_empty() {
+        export_address(base, entry.library_expression.as_deref(), modules)
+    } else {
+        export_address(base, None, modules)
+    };
+
+    let mut xml = format!("{}<CheatEntry>\n", indent);
+    let inner = format!("{}  ", indent);
+    xml.push_str(&format!("{}<ID>{}</ID>\n", inner, id));
+    xml.push_str(&format!("{}<Description>\"{}\"</Description>\n", inner, escape(&entry.description)));
+    if entry.display_format.as_deref() == Some("hex") {
+        xml.push_str(&format!("{}<ShowAsHex>1</ShowAsHex>\n", inner));
+    }
+    if signed {
+        xml.push_str(&format!("{}<ShowAsSigned>1</ShowAsSigned>\n", inner));
+    }
+    xml.push_str(&format!("{}<VariableType>{}</VariableType>\n", inner, variable_type));
+    match variable_type {
+        "String" => xml.push_str(&format!("{}<Length>{}</Length>\n{}<Unicode>0</Unicode>\n", inner, entry.size.unwrap_or(16), inner)),
+        "Array of byte" => xml.push_str(&format!("{}<ByteLength>{}</ByteLength>\n", inner, entry.size.unwrap_or(4))),
+        _ => {}
+    }
+    xml.push_str(&format!("{}<Address>{}</Address>\n", inner, escape(&base_address)));
+    if !offsets.is_empty() {
+        xml.push_str(&format!("{}<Offsets>\n", inner));
+        for offset in offsets.iter().rev() {
+            xml.push_str(&format!("{}  <Offset>{}</Offset>\n", inner, format_offset(*offset, "")));
+        }
+        xml.push_str(&format!("{}</Offsets>\n", inner));
+    }
+    xml.push_str(&export_hotkeys(&entry.hotkeys, &inner));
+    xml.push_str(&format!("{}</CheatEntry>\n", indent));
+    Ok(xml)
+}
+
+/// Read a Cheat Engine table into address-list entries. Module addresses
+/// resolve against the attached process; pointer chains stay relative.
+#[tauri::command]
+pub async fn import_cheat_table(state: tauri::State<'_, AppStateType>, path: String) -> Result<CheatTableImport, String> {
+    let xml = tokio::fs::read_to_string(&path).await.map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let document = roxmltree::Document::parse(&xml).map_err(|e| format!("Invalid cheat table: {}", e))?;
+    let root = document.root_element();
+    if !root.has_tag_name("CheatTable") {
+        return Err("Not a Cheat Engine table (missing <CheatTable>)".to_string());
+    }
+    let modules = attached_modules(state.inner());
+    let mut result = CheatTableImport { entries: Vec::new(), skipped: Vec::new() };
+    import_entries(root, None, &modules, &mut result);
+    Ok(result)
+}
+
+/// Write address-list entries as a Cheat Engine table; addresses inside a
+/// loaded module are stored module-relative. Returns the entries written.
+#[tauri::command]
+pub async fn export_cheat_table(
+    state: tauri::State<'_, AppStateType>,
+    path: String,
+    entries: Vec<CheatTableEntry>,
+) -> Result<usize, String> {
+    let modules = attached_modules(state.inner());
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<CheatTable CheatEngineTableVersion=\"45\">\n  <CheatEntries>\n");
+    let mut groups: Vec<(&str, Vec<&CheatTableEntry>)> = Vec::new();
+    let mut id = 0;
+    for entry in &entries {
+        match entry.group.as_deref() {
+            Some(group) => match groups.iter_mut().find(|(name, _)| *name == group) {
+                Some((_, members)) => members.push(entry),
+                None => groups.push((group, vec![entry])),
+            },
+            None => {
+                xml.push_str(&export_entry(entry, id, "    ", &modules)?);
+                id += 1;
+            }
+        }
+    }
+    for (group, members) in groups {
+        xml.push_str(&format!(
+            "    <CheatEntry>\n      <ID>{}</ID>\n      <Description>\"{}\"</Description>\n      <GroupHeader>1</GroupHeader>\n      <CheatEntries>\n",
+            id, escape(group),
+        ));
+        id += 1;
+        for entry in members {
+            xml.push_str(&export_entry(entry, id, "        ", &modules)?);
+            id += 1;
+        }
+        xml.push_str("      </CheatEntries>\n    </CheatEntry>\n");
+    }
+    xml.push_str("  </CheatEntries>\n</CheatTable>\n");
+    tokio::fs::write(&path, xml).await.map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(entries.len())
+}
@@ -0,0 +1,230 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{server_connection, SERVER_CONFIG};
+
+// Offsets kept for the drift fit; older handshakes are dropped
+const MAX_SYNC_SAMPLES: usize = 32;
+const DEFAULT_ROUNDS: u32 = 8;
+// Below this span between handshakes the drift estimate is mostly noise
+const MIN_DRIFT_SPAN_US: i64 = 10_000_000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ClockSample {
+    pub local_us: i64,                // Midpoint of the round trip on the local clock
+    pub offset_us: i64,               // target - local
+    pub rtt_us: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockSyncStatus {
+    pub server: String,               // host:port the samples belong to
+    pub offset_us: i64,               // Current target - local estimate
+    pub drift_ppm: f64,               // Target clock rate error relative to the local clock
+    pub rtt_us: i64,                  // Best round trip of the latest handshake
+    pub samples: Vec<ClockSample>,
+    pub synced_at: u64,               // Local milliseconds
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvertedTimestamp {
+    pub target_ms: u64,
+    pub local_ms: u64,
+    pub local_iso: String,
+    pub offset_ms: f64,
+    pub synced: bool,                 // False when no handshake was made (identity conversion)
+}
+
+#[derive(Default)]
+struct ClockState {
+    server: String,
+    samples: Vec<ClockSample>,
+}
+
+static CLOCK: Lazy<RwLock<ClockState>> = Lazy::new(|| RwLock::new(ClockState::default()));
+
+fn now_us() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_micros() as i64).unwrap_or(0)
+}
+
+/// UTC ISO 8601 for a millisecond Unix timestamp (what the frontend stores in createdAt)
+pub fn iso_timestamp(ms: u64) -> String {
+    let days = (ms / 86_400_000) as i64;
+    let ms_of_day = ms % 86_400_000;
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year, month, day,
+        ms_of_day / 3_600_000, ms_of_day / 60_000 % 60, ms_of_day / 1000 % 60, ms_of_day % 1000
+    )
+}
+
+/// Milliseconds since the epoch of an RFC 3339 timestamp ("2024-05-01T12:00:00.123456+00:00",
+/// "...Z"); what dbgsrv puts in exception events
+pub fn parse_iso_timestamp(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let (date, time) = text.split_once(['T', ' '])?;
+    let mut date_parts = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date_parts.next()??, date_parts.next()??, date_parts.next()??);
+
+    // Split off the zone designator
+    let (clock, zone_minutes) = if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+        (clock, 0)
+    } else {
+        let index = time.rfind(['+', '-'])?;
+        let (clock, zone) = time.split_at(index);
+        let sign = if zone.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = zone[1..].split_once(':').unwrap_or((&zone[1..], "0"));
+        (clock, sign * (hours.parse::<i64>().ok()? * 60 + minutes.parse::<i64>().ok()?))
+    };
+    let (hms, fraction) = clock.split_once('.').unwrap_or((clock, ""));
+    let mut hms_parts = hms.splitn(3, ':').map(|p| p.parse::<i64>().ok());
+    let (hour, minute, second) = (hms_parts.next()??, hms_parts.next()??, hms_parts.next()??);
+    let millis = format!("{:0<3}", fraction.chars().take(3).collect::<String>()).parse::<i64>().ok()?;
+
+    // Days from civil (inverse of iso_timestamp)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let ms = ((days * 86_400 + hour * 3600 + minute * 60 + second - zone_minutes * 60) * 1000) + millis;
+    u64::try_from(ms).ok()
+}
+
+/// Least-squares fit of offset over local time: (offset at `at_us`, slope)
+fn fit(samples: &[ClockSample], at_us: i64) -> Option<(f64, f64)> {
+    let last = samples.last()?;
+    let span = last.local_us - samples.first()?.local_us;
+    if samples.len() < 2 || span < MIN_DRIFT_SPAN_US {
+        return Some((last.offset_us as f64, 0.0));
+    }
+    let n = samples.len() as f64;
+    // Center on the last sample to keep the sums small
+    let xs: Vec<f64> = samples.iter().map(|s| (s.local_us - last.local_us) as f64).collect();
+    let ys: Vec<f64> = samples.iter().map(|s| s.offset_us as f64).collect();
+    let (mean_x, mean_y) = (xs.iter().sum::<f64>() / n, ys.iter().sum::<f64>() / n);
+    let covariance: f64 = xs.iter().zip(&ys).map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    let slope = if variance > 0.0 { covariance / variance } else { 0.0 };
+    let x = (at_us - last.local_us) as f64;
+    Some((mean_y + slope * (x - mean_x), slope))
+}
+
+fn status(state: &ClockState) -> Option<ClockSyncStatus> {
+    let last = state.samples.last()?;
+    let (offset, slope) = fit(&state.samples, now_us())?;
+    Some(ClockSyncStatus {
+        server: state.server.clone(),
+        offset_us: offset.round() as i64,
+        drift_ppm: slope * 1e6,
+        rtt_us: last.rtt_us,
+        samples: state.samples.clone(),
+        synced_at: (last.local_us / 1000) as u64,
+    })
+}
+
+/// Local-clock time of a target wall-clock time (ms); None until a
+/// handshake has been made with the current server
+pub fn local_timestamp(target_ms: u64) -> Option<u64> {
+    let (local_ms, _, synced) = convert(target_ms);
+    synced.then_some(local_ms)
+}
+
+fn convert(target_ms: u64) -> (u64, f64, bool) {
+    let Ok(state) = CLOCK.read() else {
+        return (target_ms, 0.0, false);
+    };
+    let target_us = target_ms as i64 * 1000;
+    let first_guess = state.samples.last().map(|s| target_us - s.offset_us).unwrap_or(target_us);
+    match fit(&state.samples, first_guess) {
+        Some((offset, _)) => (((target_us as f64 - offset) / 1000.0).round().max(0.0) as u64, offset / 1000.0, true),
+        None => (target_ms, 0.0, false),
+    }
+}
+
+async fn measure(host: &str, port: u16, auth_token: Option<String>) -> Result<ClockSample, String> {
+    let client = server_connection::client()?;
+    let url = format!("{}/api/server/info", server_connection::base_url(host, port));
+    let mut request = client.get(&url);
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let sent = now_us();
+    let response = request.send().await.map_err(|e| format!("Network error: {}", e))?;
+    let received = now_us();
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse server info: {}", e))?;
+    let target_us = json["time_us"].as_i64()
+        .ok_or("Server does not report its clock; update dbgsrv")?;
+    let local_us = sent + (received - sent) / 2;
+    Ok(ClockSample { local_us, offset_us: target_us - local_us, rtt_us: received - sent })
+}
+
+/// Measure the offset to the target clock (best of `rounds` round trips) and
+/// refit the drift over the handshakes made so far
+#[tauri::command]
+pub async fn sync_target_clock(rounds: Option<u32>) -> Result<ClockSyncStatus, String> {
+    let (host, port, auth_token) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        if config.host.is_empty() {
+            return Err("No server connection configured".to_string());
+        }
+        (config.host.clone(), config.port, config.auth_token.clone())
+    };
+
+    let mut best: Option<ClockSample> = None;
+    for _ in 0..rounds.unwrap_or(DEFAULT_ROUNDS).clamp(1, 64) {
+        let sample = measure(&host, port, auth_token.clone()).await?;
+        if best.is_none_or(|b| sample.rtt_us < b.rtt_us) {
+            best = Some(sample);
+        }
+    }
+    let best = best.ok_or("No clock samples")?;
+
+    let mut state = CLOCK.write().map_err(|e| e.to_string())?;
+    let server = format!("{}:{}", host, port);
+    if state.server != server {
+        state.server = server;
+        state.samples.clear();
+    }
+    state.samples.push(best);
+    if state.samples.len() > MAX_SYNC_SAMPLES {
+        state.samples.remove(0);
+    }
+    status(&state).ok_or_else(|| "No clock samples".to_string())
+}
+
+#[tauri::command]
+pub fn get_clock_sync_status() -> Result<Option<ClockSyncStatus>, String> {
+    let state = CLOCK.read().map_err(|e| e.to_string())?;
+    Ok(status(&state))
+}
+
+/// Convert a target timestamp (milliseconds since the epoch or an RFC 3339
+/// string) to the local clock
+#[tauri::command]
+pub fn convert_target_timestamp(timestamp: serde_json::Value) -> Result<ConvertedTimestamp, String> {
+    let target_ms = match &timestamp {
+        serde_json::Value::Number(n) => n.as_u64(),
+        serde_json::Value::String(s) => s.trim().parse::<u64>().ok().or_else(|| parse_iso_timestamp(s)),
+        _ => None,
+    }
+    .ok_or_else(|| format!("Unrecognized timestamp: {}", timestamp))?;
+    let (local_ms, offset_ms, synced) = convert(target_ms);
+    Ok(ConvertedTimestamp { target_ms, local_ms, local_iso: iso_timestamp(local_ms), offset_ms, synced })
+}
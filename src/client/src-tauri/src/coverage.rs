@@ -0,0 +1,422 @@
+use once_cell::sync::Lazy;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::sync::Mutex;
+
+use dynadbg_core::drcov::{write_drcov, DrcovModule};
+
+use crate::state::{AppState, AppStateType, ExceptionData};
+use crate::symbolizer;
+use crate::{
+    cached_cfg_blocks, continue_execution_on_server, db, remove_breakpoint_on_server, server_address,
+    set_breakpoint_on_server,
+};
+
+// Breakpoints installed per session unless the caller asks for more
+const DEFAULT_MAX_POINTS: usize = 20_000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoverageOptions {
+    pub basic_blocks: Option<bool>,   // Default: blocks for functions whose CFG is cached
+    pub max_points: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CoveragePoint {
+    function_offset: u64,
+    size: u64,                        // Block size; 1 for function entries
+    hit_at: Option<u64>,              // Local milliseconds of the first hit
+    installed: bool,
+}
+
+struct CoverageSession {
+    module_name: String,
+    module_path: String,
+    module_base: u64,
+    module_size: u64,
+    pid: Option<u32>,
+    started_at: u64,
+    stopped_at: Option<u64>,
+    functions: BTreeMap<u64, (String, u64)>, // offset -> (name, size)
+    points: BTreeMap<u64, CoveragePoint>,    // Module offset -> point
+    install_failed: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCoverage {
+    pub name: String,
+    pub offset: u64,
+    pub blocks: usize,
+    pub blocks_hit: usize,
+    pub hit: bool,
+    pub first_hit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageReport {
+    pub module_name: String,
+    pub module_base: u64,
+    pub running: bool,
+    pub started_at: u64,
+    pub stopped_at: Option<u64>,
+    pub total_functions: usize,
+    pub hit_functions: usize,
+    pub function_percentage: f64,
+    pub total_blocks: usize,          // Coverage points, counting function entries as blocks
+    pub hit_blocks: usize,
+    pub block_percentage: f64,
+    pub pending: usize,               // Breakpoints still armed
+    pub install_failed: usize,        // Points the server could not set (slot limits)
+    pub functions: Vec<FunctionCoverage>,
+}
+
+// Sessions per module name
+static SESSIONS: Lazy<Mutex<HashMap<String, CoverageSession>>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+async fn project_path(target_os: &str, module_name: &str) -> Option<String> {
+    let (target_os, module_name) = (target_os.to_string(), module_name.to_string());
+    db::run(move |conn| {
+        Ok(conn.query_row(
+            "SELECT project_path FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
+            params![target_os, module_name],
+            |row| row.get(0),
+        ).ok())
+    }).await.ok().flatten()
+}
+
+fn percentage(part: usize, total: usize) -> f64 {
+    if total == 0 { 0.0 } else { part as f64 * 100.0 / total as f64 }
+}
+
+impl CoverageSession {
+    fn report(&self, include_functions: bool) -> CoverageReport {
+        let mut per_function: BTreeMap<u64, (usize, usize, Option<u64>)> = BTreeMap::new();
+        for point in self.points.values() {
+            let entry = per_function.entry(point.function_offset).or_insert((0, 0, None));
+            entry.0 += 1;
+            if let Some(hit_at) = point.hit_at {
+                entry.1 += 1;
+                entry.2 = Some(entry.2.map_or(hit_at, |first: u64| first.min(hit_at)));
+            }
+        }
+        let hit_functions = per_function.values().filter(|(_, hit, _)| *hit > 0).count();
+        let hit_blocks = self.points.values().filter(|p| p.hit_at.is_some()).count();
+        let functions = if include_functions {
+            per_function.iter()
+                .map(|(&offset, &(blocks, blocks_hit, first_hit))| FunctionCoverage {
+                    name: self.functions.get(&offset).map(|(name, _)| name.clone())
+                        .unwrap_or_else(|| format!("sub_{:x}", offset)),
+                    offset,
+                    blocks,
+                    blocks_hit,
+                    hit: blocks_hit > 0,
+                    first_hit,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        CoverageReport {
+            module_name: self.module_name.clone(),
+            module_base: self.module_base,
+            running: self.stopped_at.is_none(),
+            started_at: self.started_at,
+            stopped_at: self.stopped_at,
+            total_functions: per_function.len(),
+            hit_functions,
+            function_percentage: percentage(hit_functions, per_function.len()),
+            total_blocks: self.points.len(),
+            hit_blocks,
+            block_percentage: percentage(hit_blocks, self.points.len()),
+            pending: self.points.values().filter(|p| p.installed).count(),
+            install_failed: self.install_failed,
+            functions,
+        }
+    }
+}
+
+/// Record coverage hits and resume the threads that stopped on them
+/// (called from add_exceptions before the breakpoint filter). Each coverage
+/// breakpoint is removed on its first hit.
+pub async fn divert_exceptions(state: &AppStateType, exceptions: Vec<ExceptionData>) -> Vec<ExceptionData> {
+    let pid = state.lock().ok().and_then(|s| s.attached_process.as_ref().map(|p| p.pid));
+    let mut hits: Vec<(u64, Option<u64>)> = Vec::new();
+    let forwarded: Vec<ExceptionData> = {
+        let Ok(mut sessions) = SESSIONS.lock() else {
+            return exceptions;
+        };
+        if sessions.values().all(|s| s.stopped_at.is_some()) {
+            return exceptions;
+        }
+        let now = AppState::current_timestamp();
+        exceptions.into_iter()
+            .filter(|exception| {
+                if exception.exception_type != "breakpoint" {
+                    return true;
+                }
+                let Some(pc) = exception.pc
+                    .or_else(|| u64::from_str_radix(exception.address.trim_start_matches("0x"), 16).ok())
+                else {
+                    return true;
+                };
+                let point = sessions.values_mut()
+                    .filter(|s| s.pid == pid && pc >= s.module_base && pc < s.module_base + s.module_size)
+                    .find_map(|s| s.points.get_mut(&(pc - s.module_base)))
+                    .filter(|p| p.installed);
+                let Some(point) = point else {
+                    return true;
+                };
+                point.installed = false;
+                point.hit_at.get_or_insert(now);
+                hits.push((pc, exception.thread_id));
+                false
+            })
+            .collect()
+    };
+    if hits.is_empty() {
+        return forwarded;
+    }
+
+    let Ok((host, port)) = server_address() else {
+        return forwarded;
+    };
+    for (address, thread_id) in hits {
+        if let Err(e) = remove_breakpoint_on_server(&host, port, address).await {
+            eprintln!("Failed to remove coverage breakpoint at 0x{:x}: {}", address, e);
+        }
+        if let Err(e) = continue_execution_on_server(&host, port, thread_id).await {
+            eprintln!("Failed to resume thread after coverage hit at 0x{:x}: {}", address, e);
+        }
+    }
+    forwarded
+}
+
+/// Start collecting coverage of a loaded module: a one-shot software
+/// breakpoint at every function entry from the Ghidra function list, or at
+/// every basic block of the functions whose CFG has been fetched. Hits are
+/// recorded as the target runs and the breakpoint is removed on the first one.
+#[tauri::command]
+pub async fn start_coverage(
+    state: tauri::State<'_, AppStateType>,
+    module_name: String,
+    options: Option<CoverageOptions>,
+) -> Result<CoverageReport, String> {
+    let options = options.unwrap_or_default();
+    let (target_os, pid, module, user_breakpoints) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let module = state_guard.attached_modules.iter()
+            .find(|m| m.modulename == module_name)
+            .cloned()
+            .ok_or_else(|| format!("Module '{}' is not loaded", module_name))?;
+        let user_breakpoints: HashSet<String> = state_guard.software_breakpoints.iter()
+            .chain(&state_guard.active_breakpoints)
+            .map(|a| a.to_lowercase())
+            .collect();
+        (
+            state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default(),
+            state_guard.attached_process.as_ref().map(|p| p.pid),
+            module,
+            user_breakpoints,
+        )
+    };
+    if pid.is_none() {
+        return Err("No process attached".to_string());
+    }
+    if SESSIONS.lock().map_err(|e| e.to_string())?.get(&module_name).is_some_and(|s| s.stopped_at.is_none()) {
+        return Err(format!("Coverage of '{}' is already running", module_name));
+    }
+
+    symbolizer::preload(&target_os, vec![module_name.clone()]).await;
+    let functions: BTreeMap<u64, (String, u64)> = symbolizer::module_functions(&target_os, &module_name)
+        .into_iter()
+        .filter(|(offset, _, _)| *offset < module.size)
+        .map(|(offset, size, name)| (offset, (name, size)))
+        .collect();
+    if functions.is_empty() {
+        return Err(format!("No Ghidra function list for '{}'; analyze the module first", module_name));
+    }
+
+    let project = project_path(&target_os, &module_name).await;
+    let use_blocks = options.basic_blocks.unwrap_or(true);
+    let mut points: BTreeMap<u64, CoveragePoint> = BTreeMap::new();
+    for &function_offset in functions.keys() {
+        let blocks = project.as_deref()
+            .filter(|_| use_blocks)
+            .and_then(|project| cached_cfg_blocks(project, function_offset))
+            .filter(|blocks| !blocks.is_empty());
+        match blocks {
+            Some(blocks) => {
+                for (start, end) in blocks {
+                    points.insert(start, CoveragePoint {
+                        function_offset,
+                        size: end.saturating_sub(start) + 1,
+                        hit_at: None,
+                        installed: false,
+                    });
+                }
+            }
+            None => {
+                points.entry(function_offset).or_insert(CoveragePoint {
+                    function_offset,
+                    size: 1,
+                    hit_at: None,
+                    installed: false,
+                });
+            }
+        }
+    }
+    // Never take over the user's own breakpoints
+    points.retain(|offset, _| !user_breakpoints.contains(&format!("0x{:x}", module.base + offset)));
+    let max_points = options.max_points.unwrap_or(DEFAULT_MAX_POINTS);
+    if points.len() > max_points {
+        return Err(format!(
+            "{} coverage points exceed the limit of {}; pass max_points or use function entries",
+            points.len(), max_points
+        ));
+    }
+
+    let (host, port) = server_address()?;
+    let mut consecutive_failures = 0;
+    for (offset, point) in points.iter_mut() {
+        match set_breakpoint_on_server(&host, port, module.base + offset, 0, true).await {
+            Ok(()) => {
+                point.installed = true;
+                consecutive_failures = 0;
+            }
+            Err(e) => {
+                consecutive_failures += 1;
+                // Out of breakpoint slots: the rest would fail the same way
+                if consecutive_failures >= 16 {
+                    eprintln!("Coverage of '{}': giving up on breakpoints after: {}", module_name, e);
+                    break;
+                }
+            }
+        }
+    }
+    let install_failed = points.values().filter(|p| !p.installed).count();
+
+    let session = CoverageSession {
+        module_name: module_name.clone(),
+        module_path: module.path.clone().unwrap_or_else(|| module_name.clone()),
+        module_base: module.base,
+        module_size: module.size,
+        pid,
+        started_at: AppState::current_timestamp(),
+        stopped_at: None,
+        functions,
+        points,
+        install_failed,
+    };
+    let report = session.report(false);
+    SESSIONS.lock().map_err(|e| e.to_string())?.insert(module_name, session);
+    Ok(report)
+}
+
+/// Remove the breakpoints that were never hit and freeze the report
+#[tauri::command]
+pub async fn stop_coverage(module_name: String) -> Result<CoverageReport, String> {
+    let armed = {
+        let mut sessions = SESSIONS.lock().map_err(|e| e.to_string())?;
+        let session = sessions.get_mut(&module_name)
+            .ok_or_else(|| format!("No coverage session for '{}'", module_name))?;
+        disarm(session)
+    };
+    remove_breakpoints(armed).await;
+    get_coverage_report(module_name, Some(true))
+}
+
+/// Stop every running session (before detaching from the process)
+pub async fn stop_all() {
+    let armed: Vec<u64> = match SESSIONS.lock() {
+        Ok(mut sessions) => sessions.values_mut().flat_map(disarm).collect(),
+        Err(_) => return,
+    };
+    remove_breakpoints(armed).await;
+}
+
+/// Addresses of coverage breakpoints currently installed in the target
+pub fn installed_breakpoints() -> Vec<u64> {
+    let Ok(sessions) = SESSIONS.lock() else {
+        return Vec::new();
+    };
+    sessions.values()
+        .flat_map(|s| s.points.iter().filter(|(_, p)| p.installed).map(move |(offset, _)| s.module_base + offset))
+        .collect()
+}
+
+/// Mark a session stopped; returns the addresses still armed
+fn disarm(session: &mut CoverageSession) -> Vec<u64> {
+    session.stopped_at.get_or_insert_with(AppState::current_timestamp);
+    let base = session.module_base;
+    session.points.iter_mut()
+        .filter(|(_, p)| p.installed)
+        .map(|(offset, p)| {
+            p.installed = false;
+            base + offset
+        })
+        .collect()
+}
+
+async fn remove_breakpoints(addresses: Vec<u64>) {
+    if let Ok((host, port)) = server_address() {
+        for address in addresses {
+            let _ = remove_breakpoint_on_server(&host, port, address).await;
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_coverage_report(module_name: String, include_functions: Option<bool>) -> Result<CoverageReport, String> {
+    let sessions = SESSIONS.lock().map_err(|e| e.to_string())?;
+    let session = sessions.get(&module_name)
+        .ok_or_else(|| format!("No coverage session for '{}'", module_name))?;
+    Ok(session.report(include_functions.unwrap_or(true)))
+}
+
+#[tauri::command]
+pub fn list_coverage_sessions() -> Result<Vec<CoverageReport>, String> {
+    let sessions = SESSIONS.lock().map_err(|e| e.to_string())?;
+    let mut reports: Vec<CoverageReport> = sessions.values().map(|s| s.report(false)).collect();
+    reports.sort_by(|a, b| a.module_name.cmp(&b.module_name));
+    Ok(reports)
+}
+
+/// Forget a stopped session
+#[tauri::command]
+pub fn clear_coverage(module_name: String) -> Result<bool, String> {
+    let mut sessions = SESSIONS.lock().map_err(|e| e.to_string())?;
+    if sessions.get(&module_name).is_some_and(|s| s.stopped_at.is_none()) {
+        return Err(format!("Coverage of '{}' is still running; stop it first", module_name));
+    }
+    Ok(sessions.remove(&module_name).is_some())
+}
+
+/// Write the hit blocks of a session as a DrCov file for Lighthouse
+#[tauri::command]
+pub fn export_coverage_drcov(module_name: String, path: String) -> Result<usize, String> {
+    let (module, blocks) = {
+        let sessions = SESSIONS.lock().map_err(|e| e.to_string())?;
+        let session = sessions.get(&module_name)
+            .ok_or_else(|| format!("No coverage session for '{}'", module_name))?;
+        let blocks: Vec<(u16, u32, u16)> = session.points.iter()
+            .filter(|(_, p)| p.hit_at.is_some())
+            .map(|(&offset, p)| (0, offset as u32, p.size.min(u16::MAX as u64) as u16))
+            .collect();
+        let module = DrcovModule {
+            base: session.module_base,
+            end: session.module_base + session.module_size,
+            path: session.module_path.clone(),
+        };
+        (module, blocks)
+    };
+    let file = std::fs::File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut out = std::io::BufWriter::new(file);
+    write_drcov(&mut out, &[module], &blocks)
+        .and_then(|_| out.flush())
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(blocks.len())
+}
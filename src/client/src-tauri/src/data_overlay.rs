@@ -0,0 +1,163 @@
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::state::AppStateType;
+use crate::{cache_versions, db, memory_regions, secure_store, GhidraDataItem, MemoryFilterResult};
+
+/// Ghidra data item (global variable, string, ...) containing a scan hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataItemRef {
+    pub module_name: String,
+    pub item_offset: u64,             // Module offset of the item start
+    pub offset_in_item: u64,          // Hit address - item start
+    pub name: Option<String>,
+    pub data_type: String,
+    pub category: String,
+    pub value: Option<String>,
+}
+
+struct DataEntry {
+    offset: u64,
+    size: u64,
+    item: GhidraDataItem,
+}
+
+// (target_os, module_name) -> data items sorted by offset; empty when the
+// module has no cached /data output
+type DataIndex = HashMap<(String, String), Arc<Vec<DataEntry>>>;
+
+static DATA_INDEX: Lazy<Mutex<DataIndex>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+/// Store the /data output of a project's Ghidra server for every module
+/// analyzed into that project
+pub async fn save(project_path: &str, json: &str) -> Result<(), String> {
+    let (project_path, json) = (project_path.to_string(), json.to_string());
+    db::run(move |conn| save_with(conn, &project_path, &json)).await
+}
+
+fn save_with(conn: &Connection, project_path: &str, json: &str) -> Result<(), String> {
+    let mut stmt = conn.prepare("SELECT target_os, module_name FROM analyzed_modules WHERE project_path = ?1")
+        .map_err(|e| e.to_string())?;
+    let modules = stmt.query_map(params![project_path], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    let sealed = secure_store::seal_value("ghidra_data_cache", "data_json", json)?;
+    for (target_os, module_name) in modules {
+        conn.execute(
+            "INSERT OR REPLACE INTO ghidra_data_cache (target_os, module_name, data_json, updated_at, cache_version)
+             VALUES (?1, ?2, ?3, datetime('now'), ?4)",
+            params![target_os, module_name, sealed, cache_versions::stamp(conn, &target_os, &module_name)],
+        ).map_err(|e| e.to_string())?;
+        if let Ok(mut index) = DATA_INDEX.lock() {
+            index.remove(&(target_os, module_name));
+        }
+    }
+    Ok(())
+}
+
+/// Forget the in-memory index (after the cache tables were cleared)
+pub fn invalidate_all() {
+    if let Ok(mut index) = DATA_INDEX.lock() {
+        index.clear();
+    }
+}
+
+async fn load(target_os: &str, module_name: &str) -> Arc<Vec<DataEntry>> {
+    let key = (target_os.to_string(), module_name.to_string());
+    if let Some(entries) = DATA_INDEX.lock().ok().and_then(|index| index.get(&key).cloned()) {
+        return entries;
+    }
+
+    let json: Option<String> = {
+        let (target_os, module_name) = key.clone();
+        db::run(move |conn| {
+            Ok(conn.query_row(
+                "SELECT data_json FROM ghidra_data_cache WHERE target_os = ?1 AND module_name = ?2
+                 AND (cache_version IS NULL OR cache_version = ?3)",
+                params![target_os, module_name, cache_versions::stamp(conn, &target_os, &module_name)],
+                |row| row.get(0),
+            ).ok())
+        }).await.ok().flatten()
+    };
+    let items: Vec<GhidraDataItem> = json
+        .and_then(|json| secure_store::open_value("ghidra_data_cache", "data_json", json).ok())
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        .and_then(|value| serde_json::from_value(value["data"].clone()).ok())
+        .unwrap_or_default();
+    let mut entries: Vec<DataEntry> = items.into_iter()
+        .filter_map(|item| {
+            let offset = u64::from_str_radix(item.address.trim_start_matches("0x"), 16).ok()?;
+            Some(DataEntry { offset, size: item.size.max(1) as u64, item })
+        })
+        .collect();
+    entries.sort_by_key(|e| e.offset);
+
+    let entries = Arc::new(entries);
+    if let Ok(mut index) = DATA_INDEX.lock() {
+        index.insert(key, entries.clone());
+    }
+    entries
+}
+
+/// Cached data items of a module with their module offsets, sorted by offset
+pub async fn items(target_os: &str, module_name: &str) -> Vec<(u64, GhidraDataItem)> {
+    load(target_os, module_name).await.iter().map(|e| (e.offset, e.item.clone())).collect()
+}
+
+fn find(entries: &[DataEntry], offset: u64) -> Option<&DataEntry> {
+    let index = entries.partition_point(|e| e.offset <= offset).checked_sub(1)?;
+    entries.get(index).filter(|e| offset < e.offset + e.size)
+}
+
+/// Attach the enclosing Ghidra data item to results that fall inside a
+/// module's non-executable regions
+pub async fn annotate(state: &AppStateType, results: &mut [MemoryFilterResult]) {
+    if results.is_empty() {
+        return;
+    }
+    let Ok((target_os, modules)) = state.lock().map(|s| {
+        (
+            s.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default(),
+            s.attached_modules.clone(),
+        )
+    }) else {
+        return;
+    };
+    if modules.is_empty() {
+        return;
+    }
+    let regions = memory_regions::get_cached_regions(Some(state), false).await.unwrap_or_default();
+
+    for result in results.iter_mut() {
+        let address = result.address;
+        let Some(module) = modules.iter().find(|m| address >= m.base && address < m.base.saturating_add(m.size)) else {
+            continue;
+        };
+        // Skip code; without a memory map every module address is considered
+        let in_code = regions.iter()
+            .find(|r| address >= r.base && address < r.base + r.size)
+            .is_some_and(|r| r.executable);
+        if in_code {
+            continue;
+        }
+        let entries = load(&target_os, &module.modulename).await;
+        let offset = address - module.base;
+        if let Some(entry) = find(&entries, offset) {
+            result.data_item = Some(DataItemRef {
+                module_name: module.modulename.clone(),
+                item_offset: entry.offset,
+                offset_in_item: offset - entry.offset,
+                name: entry.item.name.clone(),
+                data_type: entry.item.data_type.clone(),
+                category: entry.item.category.clone(),
+                value: entry.item.value.clone(),
+            });
+        }
+    }
+}
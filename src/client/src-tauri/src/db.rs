@@ -0,0 +1,256 @@
+use rusqlite::Connection;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use crate::{annotations, breakpoints, cache_versions, secure_store};
+
+// Connections kept open for reuse; more are opened on demand under load
+const MAX_IDLE_CONNECTIONS: usize = 4;
+// How long a statement waits for another connection's write lock
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Migration = (&'static str, fn(&Connection) -> Result<(), String>);
+
+// Applied in order, each exactly once; PRAGMA user_version holds how many
+// have run. Append new schema changes here instead of editing earlier steps.
+// Version 1 is the schema from before versioning: its CREATE ... IF NOT
+// EXISTS steps also adopt databases older builds left at version 0, and the
+// later steps add what those databases lack.
+const MIGRATIONS: &[Migration] = &[
+    ("baseline schema", crate::create_ghidra_tables),
+    ("breakpoint actions", breakpoints::add_action_column),
+    ("cache version stamps", cache_versions::add_stamp_columns),
+    ("annotations", annotations::init),
+];
+
+/// Pool of connections to ghidra_cache.db. In WAL mode readers are not
+/// blocked by a writer, so a long cache write no longer stalls every lookup.
+pub struct Database {
+    path: RwLock<Option<PathBuf>>,
+    idle: Mutex<Vec<Connection>>,
+}
+
+/// A connection checked out of the pool, returned on drop. Derefs to None
+/// until the database is opened.
+pub struct PooledConnection<'a> {
+    database: &'a Database,
+    conn: Option<Connection>,
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Option<Connection>;
+
+    fn deref(&self) -> &Option<Connection> {
+        &self.conn
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        let Some(conn) = self.conn.take() else { return };
+        if let Ok(mut idle) = self.database.idle.lock() {
+            if idle.len() < MAX_IDLE_CONNECTIONS {
+                idle.push(conn);
+            }
+        }
+    }
+}
+
+fn connect(path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(path).map_err(|e| e.to_string())?;
+    conn.busy_timeout(BUSY_TIMEOUT).map_err(|e| e.to_string())?;
+    // Durable at checkpoints; a crash can only lose the last transactions
+    conn.pragma_update(None, "synchronous", "NORMAL").map_err(|e| e.to_string())?;
+    Ok(conn)
+}
+
+/// Add a column unless the table already has it
+pub fn add_column(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<(), String> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table)).map_err(|e| e.to_string())?;
+    let exists = stmt.query_map([], |row| row.get::<_, String>(1))
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .any(|name| name == column);
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn migrate(conn: &Connection) -> Result<(), String> {
+    let version = conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+        .map_err(|e| e.to_string())? as usize;
+    if version > MIGRATIONS.len() {
+        return Err(format!(
+            "Database schema version {} is newer than this build supports ({})",
+            version,
+            MIGRATIONS.len()
+        ));
+    }
+    for (index, (name, apply)) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        apply(&tx).map_err(|e| format!("Migration {} ({}) failed: {}", index + 1, name, e))?;
+        tx.pragma_update(None, "user_version", (index + 1) as i64).map_err(|e| e.to_string())?;
+        tx.commit().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+impl Database {
+    pub fn new() -> Self {
+        Database { path: RwLock::new(None), idle: Mutex::new(Vec::new()) }
+    }
+
+    /// Open (creating if needed) the database at `path`, switch it to WAL and
+    /// bring its schema up to date
+    pub fn open(&self, path: &Path) -> Result<(), String> {
+        let conn = connect(path)?;
+        let mode: String = conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+        if !mode.eq_ignore_ascii_case("wal") {
+            eprintln!("[DB] WAL unavailable for {}; using journal mode {}", path.display(), mode);
+        }
+        migrate(&conn)?;
+        // Not a migration: also loads the encryption key on every start
+        secure_store::init(&conn)?;
+
+        *self.path.write().map_err(|e| e.to_string())? = Some(path.to_path_buf());
+        let mut idle = self.idle.lock().map_err(|e| e.to_string())?;
+        idle.clear();
+        idle.push(conn);
+        Ok(())
+    }
+
+    /// Check out a connection, opening a new one when none is idle
+    pub fn get(&self) -> Result<PooledConnection<'_>, String> {
+        let Some(path) = self.path.read().map_err(|e| e.to_string())?.clone() else {
+            return Ok(PooledConnection { database: self, conn: None });
+        };
+        let idle = self.idle.lock().map_err(|e| e.to_string())?.pop();
+        let conn = match idle {
+            Some(conn) => conn,
+            None => connect(&path)?,
+        };
+        Ok(PooledConnection { database: self, conn: Some(conn) })
+    }
+}
+
+impl Default for Database {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run `f` with a pooled connection on the calling thread. Only for code
+/// that is already off the async runtime: sync commands, setup, the CLI and
+/// blocking tasks; async code uses `run`.
+pub fn run_sync<T, F>(f: F) -> Result<T, String>
+where
+    F: FnOnce(&Connection) -> Result<T, String>,
+{
+    let db_guard = crate::GHIDRA_DB.get()?;
+    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
+    f(conn)
+}
+
+/// Run `f` with a pooled connection on a blocking thread, keeping SQLite
+/// I/O off the async runtime
+pub async fn run<T, F>(f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&Connection) -> Result<T, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || run_sync(f))
+        .await
+        .map_err(|e| format!("Database task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_version(conn: &Connection) -> usize {
+        conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0)).unwrap() as usize
+    }
+
+    fn has_column(conn: &Connection, table: &str, column: &str) -> bool {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table)).unwrap();
+        let names: Vec<String> = stmt.query_map([], |row| row.get(1)).unwrap().flatten().collect();
+        names.iter().any(|name| name == column)
+    }
+
+    #[test]
+    fn migrates_empty_v0_database() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        assert_eq!(user_version(&conn), MIGRATIONS.len());
+        for table in ["analyzed_modules", "ghidra_decompile_cache", "breakpoints", "module_cache_versions", "annotations"] {
+            let found: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                [table],
+                |row| row.get(0),
+            ).unwrap();
+            assert_eq!(found, 1, "missing table {}", table);
+        }
+        assert!(has_column(&conn, "breakpoints", "action"));
+        assert!(has_column(&conn, "ghidra_decompile_cache", "cache_version"));
+    }
+
+    #[test]
+    fn migrates_legacy_v0_database() {
+        // Tables as a build from before versioning left them
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE breakpoints (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                target_os TEXT NOT NULL,
+                module_name TEXT NOT NULL,
+                module_offset INTEGER NOT NULL,
+                hit_count INTEGER NOT NULL DEFAULT 0,
+                condition TEXT,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                is_software INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                UNIQUE(target_os, module_name, module_offset)
+            );
+            CREATE TABLE ghidra_decompile_cache (
+                target_os TEXT NOT NULL,
+                module_name TEXT NOT NULL,
+                function_address TEXT NOT NULL,
+                function_name TEXT NOT NULL,
+                decompiled_code TEXT NOT NULL,
+                line_mapping_json TEXT,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY(target_os, module_name, function_address)
+            );
+            INSERT INTO breakpoints (target_os, module_name, module_offset, created_at, updated_at)
+                VALUES ('linux', 'libc.so.6', 4096, 'now', 'now');
+            INSERT INTO ghidra_decompile_cache (target_os, module_name, function_address, function_name, decompiled_code, updated_at)
+                VALUES ('linux', 'libc.so.6', '0x1000', 'main', 'int main() {}', 'now');",
+        ).unwrap();
+        assert_eq!(user_version(&conn), 0);
+
+        migrate(&conn).unwrap();
+        assert_eq!(user_version(&conn), MIGRATIONS.len());
+        let action: String = conn.query_row("SELECT action FROM breakpoints WHERE module_offset = 4096", [], |row| row.get(0)).unwrap();
+        assert_eq!(action, "break");
+        let code: String = conn.query_row("SELECT decompiled_code FROM ghidra_decompile_cache", [], |row| row.get(0)).unwrap();
+        assert_eq!(code, "int main() {}");
+        assert!(has_column(&conn, "ghidra_decompile_cache", "cache_version"));
+
+        // Already current: nothing runs again
+        migrate(&conn).unwrap();
+        assert_eq!(user_version(&conn), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn rejects_newer_schema() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", (MIGRATIONS.len() + 1) as i64).unwrap();
+        assert!(migrate(&conn).is_err());
+    }
+}
@@ -0,0 +1,561 @@
+use object::{Object, ObjectSection, ObjectSegment};
+use once_cell::sync::Lazy;
+use pdb::FallibleIterator;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use crate::state::AppStateType;
+use crate::{db, demangle_name, symbolizer};
+
+const PDB_MAGIC: &[u8] = b"Microsoft C/C++ MSF 7.00\r\n\x1aDS";
+// DW_AT_specification / DW_AT_abstract_origin hops when looking for a name
+const MAX_NAME_DEPTH: u32 = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,                     // 0 when unknown
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugSymbolsLoadResult {
+    pub module_name: String,
+    pub kind: String,                    // "pdb" | "dwarf"
+    pub function_count: usize,
+    pub line_count: usize,
+    pub source_file_count: usize,
+    pub type_count: usize,
+}
+
+struct DebugFunction {
+    offset: u64,                         // Module offset (RVA)
+    size: u64,                           // 0 when unknown (PDB publics)
+    name: String,
+}
+
+struct DebugType {
+    name: String,
+    kind: &'static str,                  // "struct" | "class" | "union" | "enum" | "interface"
+    size: u64,
+}
+
+struct LineRow {
+    offset: u64,
+    file: usize,                         // Index into `files`
+    line: u32,                           // 0 marks the end of a sequence
+    column: u32,
+}
+
+#[derive(Default)]
+struct DebugSymbols {
+    functions: Vec<DebugFunction>,
+    files: Vec<String>,
+    file_ids: HashMap<String, usize>,
+    lines: Vec<LineRow>,
+    types: Vec<DebugType>,
+}
+
+impl DebugSymbols {
+    fn file_index(&mut self, file: String) -> usize {
+        if let Some(&index) = self.file_ids.get(&file) {
+            return index;
+        }
+        self.files.push(file.clone());
+        self.file_ids.insert(file, self.files.len() - 1);
+        self.files.len() - 1
+    }
+
+    fn finish(&mut self) {
+        // Stable, so procedures pushed before publics win at the same offset
+        self.functions.sort_by_key(|f| f.offset);
+        self.functions.dedup_by_key(|f| f.offset);
+        // Sequence ends sort before a row starting at the same offset
+        self.lines.sort_by_key(|row| (row.offset, row.line > 0));
+        self.types.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+        self.types.dedup_by(|a, b| a.kind == b.kind && a.name == b.name);
+    }
+}
+
+/// Line table of a module as stored by `load_debug_symbols`
+struct LineTable {
+    files: Vec<String>,
+    rows: Vec<(u64, u32, u32, u32)>,     // (offset, file index, line, column)
+}
+
+type LineTables = HashMap<(String, String), Arc<LineTable>>;
+
+// Per (target_os, module_name); dropped when symbols are loaded again
+static LINE_TABLES: Lazy<RwLock<LineTables>> = Lazy::new(|| {
+    RwLock::new(HashMap::new())
+});
+
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS debug_symbol_sets (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            path TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS debug_functions (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            module_offset INTEGER NOT NULL,
+            size INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name, module_offset)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS debug_source_files (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            idx INTEGER NOT NULL,
+            path TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name, idx)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    // Rows ordered by module offset; line 0 ends a sequence
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS debug_lines (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            idx INTEGER NOT NULL,
+            module_offset INTEGER NOT NULL,
+            file_idx INTEGER NOT NULL,
+            line INTEGER NOT NULL,
+            column INTEGER NOT NULL,
+            PRIMARY KEY(target_os, module_name, idx)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS debug_types (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            PRIMARY KEY(target_os, module_name, kind, name)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Image base that module offsets are relative to, as in the decompiler
+fn image_base(file: &object::File) -> u64 {
+    match file.format() {
+        object::BinaryFormat::Pe => file.relative_address_base(),
+        _ => file.segments()
+            .filter(|s| s.file_range().1 > 0)
+            .map(|s| s.address())
+            .min()
+            .unwrap_or(0),
+    }
+}
+
+fn attr_string<R: gimli::Reader>(dwarf: &gimli::Dwarf<R>, unit: &gimli::Unit<R>, value: gimli::AttributeValue<R>) -> Option<String> {
+    dwarf.attr_string(unit, value).ok()?.to_string_lossy().ok().map(|s| s.into_owned())
+}
+
+/// Linkage name (demangled) or plain name of a DIE, following declarations
+/// and abstract origins within the unit
+fn die_name<R: gimli::Reader>(
+    dwarf: &gimli::Dwarf<R>,
+    unit: &gimli::Unit<R>,
+    entry: &gimli::DebuggingInformationEntry<R>,
+    depth: u32,
+) -> Option<String> {
+    for attr in [gimli::DW_AT_linkage_name, gimli::DW_AT_MIPS_linkage_name] {
+        if let Some(name) = entry.attr_value(attr).ok().flatten().and_then(|v| attr_string(dwarf, unit, v)) {
+            return Some(demangle_name(&name));
+        }
+    }
+    if let Some(name) = entry.attr_value(gimli::DW_AT_name).ok().flatten().and_then(|v| attr_string(dwarf, unit, v)) {
+        return Some(name);
+    }
+    if depth >= MAX_NAME_DEPTH {
+        return None;
+    }
+    for attr in [gimli::DW_AT_specification, gimli::DW_AT_abstract_origin] {
+        if let Ok(Some(gimli::AttributeValue::UnitRef(offset))) = entry.attr_value(attr) {
+            if let Some(name) = unit.entry(offset).ok().and_then(|origin| die_name(dwarf, unit, &origin, depth + 1)) {
+                return Some(name);
+            }
+        }
+    }
+    None
+}
+
+fn parse_dwarf(data: &[u8]) -> Result<DebugSymbols, String> {
+    let file = object::File::parse(data).map_err(|e| format!("Failed to parse binary: {}", e))?;
+    if file.section_by_name(".debug_info").is_none() {
+        return Err("No DWARF debug info in file".to_string());
+    }
+    let base = image_base(&file);
+    let endian = if file.is_little_endian() { gimli::RunTimeEndian::Little } else { gimli::RunTimeEndian::Big };
+    let sections = gimli::DwarfSections::load(|id| -> Result<Cow<[u8]>, gimli::Error> {
+        Ok(file.section_by_name(id.name())
+            .and_then(|section| section.uncompressed_data().ok())
+            .unwrap_or(Cow::Borrowed(&[])))
+    }).map_err(|e| e.to_string())?;
+    let dwarf = sections.borrow(|section| gimli::EndianSlice::new(section, endian));
+
+    let mut symbols = DebugSymbols::default();
+    let mut units = dwarf.units();
+    while let Some(header) = units.next().map_err(|e| format!("Invalid DWARF: {}", e))? {
+        let Ok(unit) = dwarf.unit(header) else { continue };
+        let mut entries = unit.entries();
+        while let Ok(Some((_, entry))) = entries.next_dfs() {
+            let kind = match entry.tag() {
+                gimli::DW_TAG_subprogram => {
+                    let Ok(mut ranges) = dwarf.die_ranges(&unit, entry) else { continue };
+                    let mut first: Option<gimli::Range> = None;
+                    while let Ok(Some(range)) = ranges.next() {
+                        if range.begin > 0 && first.is_none_or(|f| range.begin < f.begin) {
+                            first = Some(range);
+                        }
+                    }
+                    let (Some(range), Some(name)) = (first, die_name(&dwarf, &unit, entry, 0)) else { continue };
+                    if range.begin >= base {
+                        symbols.functions.push(DebugFunction { offset: range.begin - base, size: range.end - range.begin, name });
+                    }
+                    continue;
+                }
+                gimli::DW_TAG_structure_type => "struct",
+                gimli::DW_TAG_class_type => "class",
+                gimli::DW_TAG_union_type => "union",
+                gimli::DW_TAG_enumeration_type => "enum",
+                _ => continue,
+            };
+            if matches!(entry.attr_value(gimli::DW_AT_declaration), Ok(Some(gimli::AttributeValue::Flag(true)))) {
+                continue;
+            }
+            let Some(name) = entry.attr_value(gimli::DW_AT_name).ok().flatten().and_then(|v| attr_string(&dwarf, &unit, v)) else { continue };
+            let size = entry.attr_value(gimli::DW_AT_byte_size).ok().flatten().and_then(|v| v.udata_value()).unwrap_or(0);
+            symbols.types.push(DebugType { name, kind, size });
+        }
+
+        let Some(program) = unit.line_program.clone() else { continue };
+        let comp_dir = unit.comp_dir.map(|dir| dir.to_string_lossy().into_owned());
+        let mut paths: HashMap<u64, usize> = HashMap::new();
+        let mut rows = program.rows();
+        while let Ok(Some((header, row))) = rows.next_row() {
+            if row.address() < base {
+                continue;
+            }
+            let file = match paths.get(&row.file_index()) {
+                Some(&file) => file,
+                None => {
+                    let Some(entry) = row.file(header) else { continue };
+                    let mut path = attr_string(&dwarf, &unit, entry.path_name()).unwrap_or_default();
+                    if !path.starts_with('/') && !path.contains(":\\") {
+                        let directory = entry.directory(header).and_then(|d| attr_string(&dwarf, &unit, d));
+                        for dir in [directory, comp_dir.clone()].into_iter().flatten() {
+                            if !path.starts_with('/') && !path.contains(":\\") {
+                                path = format!("{}/{}", dir.trim_end_matches('/'), path);
+                            }
+                        }
+                    }
+                    let file = symbols.file_index(path);
+                    paths.insert(row.file_index(), file);
+                    file
+                }
+            };
+            let line = if row.end_sequence() { 0 } else { row.line().map_or(0, |l| l.get() as u32) };
+            let column = match row.column() {
+                gimli::ColumnType::LeftEdge => 0,
+                gimli::ColumnType::Column(column) => column.get() as u32,
+            };
+            symbols.lines.push(LineRow { offset: row.address() - base, file, line, column });
+        }
+    }
+    Ok(symbols)
+}
+
+fn parse_pdb(path: &str) -> Result<DebugSymbols, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let mut pdb = pdb::PDB::open(file).map_err(|e| format!("Invalid PDB: {}", e))?;
+    let address_map = pdb.address_map().map_err(|e| e.to_string())?;
+    let strings = pdb.string_table().ok();
+    let mut symbols = DebugSymbols::default();
+
+    let dbi = pdb.debug_information().map_err(|e| e.to_string())?;
+    let mut modules = dbi.modules().map_err(|e| e.to_string())?;
+    while let Some(module) = modules.next().map_err(|e| e.to_string())? {
+        let Ok(Some(info)) = pdb.module_info(&module) else { continue };
+        if let Ok(mut module_symbols) = info.symbols() {
+            while let Ok(Some(symbol)) = module_symbols.next() {
+                let Ok(pdb::SymbolData::Procedure(procedure)) = symbol.parse() else { continue };
+                let Some(rva) = procedure.offset.to_rva(&address_map) else { continue };
+                symbols.functions.push(DebugFunction { offset: rva.0 as u64, size: procedure.len as u64, name: procedure.name.to_string().into_owned() });
+            }
+        }
+        let Ok(program) = info.line_program() else { continue };
+        let mut lines = program.lines();
+        while let Ok(Some(line)) = lines.next() {
+            let Some(rva) = line.offset.to_rva(&address_map) else { continue };
+            let name = program.get_file_info(line.file_index).ok()
+                .and_then(|file| strings.as_ref().and_then(|s| file.name.to_string_lossy(s).ok()))
+                .map(|name| name.into_owned())
+                .unwrap_or_default();
+            let file = symbols.file_index(name);
+            let offset = rva.0 as u64;
+            symbols.lines.push(LineRow { offset, file, line: line.line_start, column: line.column_start.unwrap_or(0) });
+            if let Some(length) = line.length.filter(|&l| l > 0) {
+                symbols.lines.push(LineRow { offset: offset + length as u64, file, line: 0, column: 0 });
+            }
+        }
+    }
+
+    // Publics name functions of modules without private symbols
+    let globals = pdb.global_symbols().map_err(|e| e.to_string())?;
+    let mut globals = globals.iter();
+    while let Ok(Some(symbol)) = globals.next() {
+        let Ok(pdb::SymbolData::Public(public)) = symbol.parse() else { continue };
+        if !public.function && !public.code {
+            continue;
+        }
+        let Some(rva) = public.offset.to_rva(&address_map) else { continue };
+        symbols.functions.push(DebugFunction { offset: rva.0 as u64, size: 0, name: demangle_name(&public.name.to_string()) });
+    }
+
+    let type_information = pdb.type_information().map_err(|e| e.to_string())?;
+    let mut types = type_information.iter();
+    while let Ok(Some(item)) = types.next() {
+        let (name, kind, size) = match item.parse() {
+            Ok(pdb::TypeData::Class(class)) if !class.properties.forward_reference() => {
+                let kind = match class.kind {
+                    pdb::ClassKind::Class => "class",
+                    pdb::ClassKind::Struct => "struct",
+                    pdb::ClassKind::Interface => "interface",
+                };
+                (class.name, kind, class.size)
+            }
+            Ok(pdb::TypeData::Union(union)) if !union.properties.forward_reference() => (union.name, "union", union.size),
+            Ok(pdb::TypeData::Enumeration(enumeration)) if !enumeration.properties.forward_reference() => (enumeration.name, "enum", 0),
+            _ => continue,
+        };
+        symbols.types.push(DebugType { name: name.to_string().into_owned(), kind, size });
+    }
+    Ok(symbols)
+}
+
+fn store(conn: &Connection, target_os: &str, module_name: &str, kind: &str, path: &str, symbols: &DebugSymbols) -> Result<(), String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    for table in ["debug_functions", "debug_source_files", "debug_lines", "debug_types"] {
+        tx.execute(&format!("DELETE FROM {} WHERE target_os = ?1 AND module_name = ?2", table), params![target_os, module_name])
+            .map_err(|e| e.to_string())?;
+    }
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO debug_functions (target_os, module_name, module_offset, size, name) VALUES (?1, ?2, ?3, ?4, ?5)",
+        ).map_err(|e| e.to_string())?;
+        for function in &symbols.functions {
+            insert.execute(params![target_os, module_name, function.offset as i64, function.size as i64, function.name])
+                .map_err(|e| e.to_string())?;
+        }
+        let mut insert = tx.prepare(
+            "INSERT INTO debug_source_files (target_os, module_name, idx, path) VALUES (?1, ?2, ?3, ?4)",
+        ).map_err(|e| e.to_string())?;
+        for (idx, path) in symbols.files.iter().enumerate() {
+            insert.execute(params![target_os, module_name, idx as i64, path]).map_err(|e| e.to_string())?;
+        }
+        let mut insert = tx.prepare(
+            "INSERT INTO debug_lines (target_os, module_name, idx, module_offset, file_idx, line, column)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        ).map_err(|e| e.to_string())?;
+        for (idx, row) in symbols.lines.iter().enumerate() {
+            insert.execute(params![target_os, module_name, idx as i64, row.offset as i64, row.file as i64, row.line, row.column])
+                .map_err(|e| e.to_string())?;
+        }
+        let mut insert = tx.prepare(
+            "INSERT INTO debug_types (target_os, module_name, name, kind, size) VALUES (?1, ?2, ?3, ?4, ?5)",
+        ).map_err(|e| e.to_string())?;
+        for ty in &symbols.types {
+            insert.execute(params![target_os, module_name, ty.name, ty.kind, ty.size as i64]).map_err(|e| e.to_string())?;
+        }
+    }
+    tx.execute(
+        "INSERT OR REPLACE INTO debug_symbol_sets (target_os, module_name, kind, path, updated_at)
+         VALUES (?1, ?2, ?3, ?4, datetime('now'))",
+        params![target_os, module_name, kind, path],
+    ).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// (offset, size, name) of the functions loaded from debug info, by offset;
+/// the symbolizer lays them over the Ghidra function list
+pub fn stored_functions(conn: &Connection, target_os: &str, module_name: &str) -> Vec<(u64, u64, String)> {
+    conn.prepare(
+        "SELECT module_offset, size, name FROM debug_functions
+         WHERE target_os = ?1 AND module_name = ?2 ORDER BY module_offset",
+    )
+    .and_then(|mut stmt| {
+        let rows = stmt.query_map(params![target_os, module_name], |row| {
+            Ok((row.get::<_, i64>(0)? as u64, row.get::<_, i64>(1)? as u64, row.get::<_, String>(2)?))
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    })
+    .unwrap_or_default()
+}
+
+fn query_line_table(conn: &Connection, target_os: &str, module_name: &str) -> LineTable {
+    let mut table = LineTable { files: Vec::new(), rows: Vec::new() };
+    table.files = conn.prepare(
+        "SELECT path FROM debug_source_files WHERE target_os = ?1 AND module_name = ?2 ORDER BY idx",
+    )
+    .and_then(|mut stmt| {
+        let rows = stmt.query_map(params![target_os, module_name], |row| row.get::<_, String>(0))?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    })
+    .unwrap_or_default();
+    table.rows = conn.prepare(
+        "SELECT module_offset, file_idx, line, column FROM debug_lines
+         WHERE target_os = ?1 AND module_name = ?2 ORDER BY idx",
+    )
+    .and_then(|mut stmt| {
+        let rows = stmt.query_map(params![target_os, module_name], |row| {
+            Ok((row.get::<_, i64>(0)? as u64, row.get::<_, u32>(1)?, row.get::<_, u32>(2)?, row.get::<_, u32>(3)?))
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    })
+    .unwrap_or_default();
+    table
+}
+
+fn line_table(target_os: &str, module_name: &str) -> Arc<LineTable> {
+    let key = (target_os.to_string(), module_name.to_string());
+    if let Some(table) = LINE_TABLES.read().ok().and_then(|t| t.get(&key).cloned()) {
+        return table;
+    }
+    let table = db::run_sync(|conn| Ok(query_line_table(conn, target_os, module_name)))
+        .unwrap_or(LineTable { files: Vec::new(), rows: Vec::new() });
+    let table = Arc::new(table);
+    if let Ok(mut tables) = LINE_TABLES.write() {
+        tables.insert(key, table.clone());
+    }
+    table
+}
+
+pub fn line_table_cached(target_os: &str, module_name: &str) -> bool {
+    LINE_TABLES.read().map(|t| t.contains_key(&(target_os.to_string(), module_name.to_string()))).unwrap_or(false)
+}
+
+/// Cache the line table of a module unless it already is; for
+/// `symbolizer::preload`, which runs it on a blocking thread
+pub fn preload_line_table(conn: &Connection, target_os: &str, module_name: &str) {
+    if line_table_cached(target_os, module_name) {
+        return;
+    }
+    let table = Arc::new(query_line_table(conn, target_os, module_name));
+    if let Ok(mut tables) = LINE_TABLES.write() {
+        tables.insert((target_os.to_string(), module_name.to_string()), table);
+    }
+}
+
+/// Source line covering a module offset, and whether a line row starts exactly there
+pub fn source_location(target_os: &str, module_name: &str, offset: u64) -> Option<(SourceLocation, bool)> {
+    let table = line_table(target_os, module_name);
+    let index = table.rows.partition_point(|row| row.0 <= offset).checked_sub(1)?;
+    let (start, file, line, column) = table.rows[index];
+    if line == 0 {
+        return None;
+    }
+    let file = table.files.get(file as usize)?.clone();
+    Some((SourceLocation { file, line, column }, start == offset))
+}
+
+/// Source files of a module's line table with the number of distinct lines
+/// that have code, in line table order
+pub fn source_files(target_os: &str, module_name: &str) -> Vec<(String, usize)> {
+    let table = line_table(target_os, module_name);
+    let mut lines: Vec<HashSet<u32>> = vec![HashSet::new(); table.files.len()];
+    for &(_, file, line, _) in &table.rows {
+        if line > 0 {
+            if let Some(set) = lines.get_mut(file as usize) {
+                set.insert(line);
+            }
+        }
+    }
+    table.files.iter().cloned().zip(lines.iter().map(|set| set.len())).collect()
+}
+
+/// Parse and store the debug info of `module_path` for a module
+pub async fn load(target_os: &str, module_name: &str, module_path: &str) -> Result<DebugSymbolsLoadResult, String> {
+    let parse_path = module_path.to_string();
+    let (kind, mut symbols) = tokio::task::spawn_blocking(move || -> Result<(&'static str, DebugSymbols), String> {
+        let data = std::fs::read(&parse_path).map_err(|e| format!("Failed to read {}: {}", parse_path, e))?;
+        if data.starts_with(PDB_MAGIC) {
+            Ok(("pdb", parse_pdb(&parse_path)?))
+        } else {
+            Ok(("dwarf", parse_dwarf(&data)?))
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    symbols.finish();
+
+    let symbols = {
+        let (target_os, module_name, module_path) = (target_os.to_string(), module_name.to_string(), module_path.to_string());
+        db::run(move |conn| {
+            store(conn, &target_os, &module_name, kind, &module_path, &symbols)?;
+            Ok(symbols)
+        }).await?
+    };
+    if let Ok(mut tables) = LINE_TABLES.write() {
+        tables.remove(&(target_os.to_string(), module_name.to_string()));
+    }
+    symbolizer::invalidate_module(target_os, module_name);
+
+    Ok(DebugSymbolsLoadResult {
+        module_name: module_name.to_string(),
+        kind: kind.to_string(),
+        function_count: symbols.functions.len(),
+        line_count: symbols.lines.iter().filter(|row| row.line > 0).count(),
+        source_file_count: symbols.files.len(),
+        type_count: symbols.types.len(),
+    })
+}
+
+/// Parse PDB or DWARF debug info from a module or debug file (as downloaded
+/// by fetch_module_debug_info), store its functions, line table and types,
+/// and lay the function names over the module's Ghidra functions
+#[tauri::command]
+pub async fn load_debug_symbols(
+    state: tauri::State<'_, AppStateType>,
+    module_path: String,
+    module_name: Option<String>,
+) -> Result<DebugSymbolsLoadResult, String> {
+    let (target_os, attached): (String, Vec<String>) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        (
+            state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default(),
+            state_guard.attached_modules.iter().map(|m| m.modulename.clone()).collect(),
+        )
+    };
+    let path = Path::new(&module_path);
+    let module_name = module_name.unwrap_or_else(|| {
+        // foo.pdb belongs to the attached foo.dll / foo.exe
+        let stem = path.file_stem().map(|s| s.to_string_lossy().to_lowercase()).unwrap_or_default();
+        attached.iter()
+            .find(|name| Path::new(name).file_stem().is_some_and(|s| s.to_string_lossy().to_lowercase() == stem))
+            .cloned()
+            .unwrap_or_else(|| path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default())
+    });
+
+    load(&target_os, &module_name, &module_path).await
+}
@@ -0,0 +1,487 @@
+use object::{Object, ObjectSegment};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::process::Command;
+
+use crate::disassembly::{self, StructuredInstruction};
+use crate::state::AppStateType;
+use crate::{
+    db, download_library_file, get_ghidra_projects_dir, ghidra_decompile, ghidra_server_decompile, hide_console_window,
+    GhidraDecompileResult, GHIDRA_SERVER_PORTS,
+};
+
+// Tried in order when no radare2/rizin path is configured
+const RADARE2_TOOLS: &[&str] = &["r2", "radare2", "rizin"];
+// Upper bound of bytes the Capstone backend reads for one function
+const MAX_FUNCTION_BYTES: usize = 0x4000;
+
+/// Everything a backend may need to decompile one function
+pub struct DecompileTarget {
+    pub project_path: String,          // Ghidra project directory (also the settings key)
+    pub library_name: String,          // File name of the module
+    pub local_path: Option<String>,    // Downloaded copy of the module
+    pub function_offset: String,       // Hex, relative to the image base
+    pub ghidra_path: Option<String>,
+    pub tool_path: Option<String>,     // Configured radare2/rizin binary
+}
+
+type DecompileFuture<'a> = Pin<Box<dyn Future<Output = Result<GhidraDecompileResult, String>> + Send + 'a>>;
+
+/// A decompiler backend; results use the Ghidra result shape so the
+/// decompile view and cache work unchanged
+pub trait Decompiler: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Why the backend cannot run for `target`, or None when it can
+    fn unavailable_reason(&self, target: &DecompileTarget) -> Option<String>;
+    fn decompile<'a>(&'a self, target: &'a DecompileTarget) -> DecompileFuture<'a>;
+}
+
+fn failure(target: &DecompileTarget, error: String) -> GhidraDecompileResult {
+    GhidraDecompileResult {
+        success: false,
+        function_name: None,
+        address: Some(target.function_offset.clone()),
+        decompiled_code: None,
+        line_mapping: None,
+        tokens: None,
+        error: Some(error),
+        timing: None,
+    }
+}
+
+fn parse_offset(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches("0x"), 16).ok()
+}
+
+/// Running server for the project, else headless analyzeHeadless on an analyzed project
+pub struct GhidraDecompiler;
+
+impl Decompiler for GhidraDecompiler {
+    fn name(&self) -> &'static str {
+        "ghidra"
+    }
+
+    fn unavailable_reason(&self, target: &DecompileTarget) -> Option<String> {
+        if GHIDRA_SERVER_PORTS.lock().ok()?.contains_key(&target.project_path) {
+            return None;
+        }
+        if target.ghidra_path.as_deref().is_none_or(str::is_empty) {
+            return Some("Ghidra path not configured".to_string());
+        }
+        let analyzed = std::fs::read_dir(&target.project_path)
+            .map(|entries| entries.flatten().any(|e| e.path().extension().is_some_and(|ext| ext == "gpr")))
+            .unwrap_or(false);
+        (!analyzed).then(|| "Library not analyzed with Ghidra".to_string())
+    }
+
+    fn decompile<'a>(&'a self, target: &'a DecompileTarget) -> DecompileFuture<'a> {
+        Box::pin(async move {
+            let running = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?.contains_key(&target.project_path);
+            if running {
+                return ghidra_server_decompile(target.project_path.clone(), target.function_offset.clone()).await;
+            }
+            ghidra_decompile(
+                target.project_path.clone(),
+                target.library_name.clone(),
+                target.function_offset.clone(),
+                target.ghidra_path.clone().unwrap_or_default(),
+            ).await
+        })
+    }
+}
+
+/// radare2 or rizin: r2ghidra's `pdg` when the plugin is installed, else `pdc`
+pub struct Radare2Decompiler;
+
+impl Radare2Decompiler {
+    fn responds(tool: &str) -> bool {
+        hide_console_window(&mut Command::new(tool))
+            .arg("-v")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    fn tool(target: &DecompileTarget) -> Option<String> {
+        match target.tool_path.as_deref().filter(|p| !p.is_empty()) {
+            Some(path) => Self::responds(path).then(|| path.to_string()),
+            None => RADARE2_TOOLS.iter().find(|tool| Self::responds(tool)).map(|tool| tool.to_string()),
+        }
+    }
+
+    fn run(tool: &str, file: &str, offset: &str, command: &str) -> Result<String, String> {
+        // $B is the load base in both radare2 and rizin, matching Ghidra's image base
+        let script = format!("s $B+0x{:x}; af; {}", parse_offset(offset).unwrap_or(0), command);
+        let output = hide_console_window(&mut Command::new(tool))
+            .args(["-q", "-2", "-e", "scr.color=0", "-c", &script, file])
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", tool, e))?;
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+impl Decompiler for Radare2Decompiler {
+    fn name(&self) -> &'static str {
+        "radare2"
+    }
+
+    fn unavailable_reason(&self, target: &DecompileTarget) -> Option<String> {
+        Self::tool(target).is_none().then(|| "radare2/rizin not found".to_string())
+    }
+
+    fn decompile<'a>(&'a self, target: &'a DecompileTarget) -> DecompileFuture<'a> {
+        Box::pin(async move {
+            let tool = Self::tool(target).ok_or("radare2/rizin not found")?;
+            let file = target.local_path.clone().ok_or("Library file not available locally")?;
+            let offset = target.function_offset.clone();
+            let code = tokio::task::spawn_blocking(move || {
+                let code = Self::run(&tool, &file, &offset, "pdg")?;
+                if code.is_empty() || code.contains("Unknown command") || code.contains("Invalid command") {
+                    return Self::run(&tool, &file, &offset, "pdc");
+                }
+                Ok(code)
+            }).await.map_err(|e| e.to_string())??;
+            if code.is_empty() {
+                return Ok(failure(target, "No function at this offset".to_string()));
+            }
+            Ok(GhidraDecompileResult {
+                success: true,
+                function_name: None,
+                address: Some(target.function_offset.clone()),
+                decompiled_code: Some(code),
+                line_mapping: None,
+                tokens: None,
+                error: None,
+                timing: None,
+            })
+        })
+    }
+}
+
+/// Needs no external tool: disassembles the function from the library file
+/// and prints it as C-like pseudo-code, one statement per instruction
+pub struct CapstoneDecompiler;
+
+impl CapstoneDecompiler {
+    /// (architecture, image base, bytes from the function start) of a module file
+    fn function_bytes(data: &[u8], offset: u64) -> Result<(&'static str, u64, &[u8]), String> {
+        let file = object::File::parse(data).map_err(|e| format!("Failed to parse library: {}", e))?;
+        let architecture = match file.architecture() {
+            object::Architecture::X86_64 => "x86_64",
+            object::Architecture::I386 => "x86",
+            object::Architecture::Arm => "arm",
+            object::Architecture::Aarch64 => "arm64",
+            other => return Err(format!("Unsupported architecture {:?}", other)),
+        };
+        let base = match file.format() {
+            object::BinaryFormat::Pe => file.relative_address_base(),
+            _ => file.segments()
+                .filter(|s| s.file_range().1 > 0)
+                .map(|s| s.address())
+                .min()
+                .unwrap_or(0),
+        };
+        let address = base + offset;
+        let segment = file.segments()
+            .find(|s| address >= s.address() && address < s.address() + s.file_range().1)
+            .ok_or("Offset is outside the file's mapped segments")?;
+        let (file_offset, file_size) = segment.file_range();
+        let start = (file_offset + address - segment.address()) as usize;
+        let end = (file_offset + file_size).min(data.len() as u64) as usize;
+        let end = end.min(start + MAX_FUNCTION_BYTES);
+        Ok((architecture, base, data.get(start..end).ok_or("Offset is outside the file")?))
+    }
+
+    /// Instructions up to the return after which no branch jumps further
+    fn function_body(instructions: Vec<StructuredInstruction>) -> Vec<StructuredInstruction> {
+        let mut furthest = 0;
+        let mut body = Vec::new();
+        for instruction in instructions {
+            let (address, end) = (instruction.address, instruction.address + instruction.size as u64);
+            if instruction.is_branch && !instruction.is_call {
+                if let Some(target) = instruction.branch_target.filter(|&t| t > address) {
+                    furthest = furthest.max(target);
+                }
+            }
+            let stop = instruction.is_return && end > furthest;
+            body.push(instruction);
+            if stop {
+                break;
+            }
+        }
+        body
+    }
+
+    fn statement(instruction: &StructuredInstruction, labels: &BTreeSet<u64>, base: u64) -> String {
+        let operands: Vec<&str> = instruction.operands.split(", ").map(str::trim).collect();
+        let name = |target: u64| if labels.contains(&target) {
+            format!("LAB_{:x}", target - base)
+        } else {
+            format!("FUN_{:x}", target - base)
+        };
+        let mnemonic = instruction.mnemonic.as_str();
+        if instruction.is_return {
+            return "return;".to_string();
+        }
+        if instruction.is_call {
+            return match instruction.branch_target {
+                Some(target) => format!("{}();", name(target)),
+                None => format!("(*{})();", instruction.operands),
+            };
+        }
+        if instruction.is_branch {
+            let destination = instruction.branch_target.map(name).unwrap_or_else(|| format!("*{}", instruction.operands));
+            return if matches!(mnemonic, "jmp" | "b" | "br") {
+                format!("goto {};", destination)
+            } else {
+                format!("if ({}) goto {};", mnemonic, destination)
+            };
+        }
+        let operator = match mnemonic {
+            "add" => Some("+"),
+            "sub" => Some("-"),
+            "and" => Some("&"),
+            "or" | "orr" => Some("|"),
+            "xor" | "eor" => Some("^"),
+            "lsl" | "shl" => Some("<<"),
+            "lsr" | "shr" => Some(">>"),
+            "mul" | "imul" => Some("*"),
+            _ => None,
+        };
+        match (mnemonic, operator, operands.as_slice()) {
+            ("mov" | "movz" | "movabs" | "ldr" | "lea", _, [dest, source]) => format!("{} = {};", dest, source.trim_start_matches('#')),
+            ("str", _, [source, dest]) => format!("{} = {};", dest, source),
+            (_, Some(op), [dest, source]) => format!("{} {}= {};", dest, op, source.trim_start_matches('#')),
+            (_, Some(op), [dest, left, right]) => format!("{} = {} {} {};", dest, left, op, right.trim_start_matches('#')),
+            _ if instruction.operands.is_empty() => format!("__asm(\"{}\");", mnemonic),
+            _ => format!("__asm(\"{} {}\");", mnemonic, instruction.operands),
+        }
+    }
+}
+
+impl Decompiler for CapstoneDecompiler {
+    fn name(&self) -> &'static str {
+        "capstone"
+    }
+
+    fn unavailable_reason(&self, _target: &DecompileTarget) -> Option<String> {
+        None
+    }
+
+    fn decompile<'a>(&'a self, target: &'a DecompileTarget) -> DecompileFuture<'a> {
+        Box::pin(async move {
+            let file = target.local_path.as_ref().ok_or("Library file not available locally")?;
+            let offset = parse_offset(&target.function_offset).ok_or("Invalid function offset")?;
+            let data = tokio::fs::read(file).await.map_err(|e| format!("Failed to read library: {}", e))?;
+            let (architecture, base, bytes) = match Self::function_bytes(&data, offset) {
+                Ok(found) => found,
+                Err(e) => return Ok(failure(target, e)),
+            };
+            let body = Self::function_body(disassembly::disassemble_structured(bytes, base + offset, architecture)?);
+            let end = body.last().map(|i| i.address + i.size as u64).unwrap_or(base + offset);
+            let labels: BTreeSet<u64> = body.iter()
+                .filter(|i| i.is_branch && !i.is_call)
+                .filter_map(|i| i.branch_target)
+                .filter(|&t| t >= base + offset && t < end)
+                .collect();
+
+            let function_name = format!("FUN_{:x}", offset);
+            let mut lines = vec![format!("void {}(void)", function_name), "{".to_string()];
+            let mut line_mapping = HashMap::new();
+            for instruction in &body {
+                if labels.contains(&instruction.address) {
+                    lines.push(format!("LAB_{:x}:", instruction.address - base));
+                }
+                lines.push(format!("  {}", Self::statement(instruction, &labels, base)));
+                line_mapping.insert(lines.len().to_string(), format!("0x{:x}", instruction.address - base));
+            }
+            lines.push("}".to_string());
+            Ok(GhidraDecompileResult {
+                success: true,
+                function_name: Some(function_name),
+                address: Some(target.function_offset.clone()),
+                decompiled_code: Some(lines.join("\n")),
+                line_mapping: Some(line_mapping),
+                tokens: None,
+                error: None,
+                timing: None,
+            })
+        })
+    }
+}
+
+/// Backends in "auto" preference order
+fn backends() -> [&'static dyn Decompiler; 3] {
+    [&GhidraDecompiler, &Radare2Decompiler, &CapstoneDecompiler]
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecompilerBackendInfo {
+    pub name: String,
+    pub available: bool,
+    pub reason: Option<String>,       // Why it is unavailable
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecompilerSettings {
+    pub project_path: String,
+    pub backend: String,              // "auto" or a backend name
+    pub tool_path: Option<String>,    // radare2/rizin binary
+    pub backends: Vec<DecompilerBackendInfo>,
+}
+
+/// Create the per-project backend table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS decompiler_settings (
+            project_path TEXT PRIMARY KEY,
+            backend TEXT NOT NULL,
+            tool_path TEXT
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// (backend, tool_path) chosen for a project, "auto" when unset
+async fn load_settings(project_path: &str) -> (String, Option<String>) {
+    let project_path = project_path.to_string();
+    db::run(move |conn| {
+        Ok(conn.query_row(
+            "SELECT backend, tool_path FROM decompiler_settings WHERE project_path = ?1",
+            params![project_path],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).ok())
+    }).await
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| ("auto".to_string(), None))
+}
+
+/// Project path and local copy recorded for a module by analyze_with_ghidra,
+/// else the paths analysis would use
+async fn resolve_paths(state: &AppStateType, library_path: &str) -> (String, Option<String>) {
+    let library_name = crate::analysis_policy::file_name(library_path).to_string();
+    let target_os = state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.target_os.clone()))
+        .unwrap_or_default();
+    let recorded: Option<(String, String)> = {
+        let library_name = library_name.clone();
+        db::run(move |conn| {
+            Ok(conn.query_row(
+                "SELECT project_path, local_path FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
+                params![target_os, library_name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).ok())
+        }).await.ok().flatten()
+    };
+    if let Some((project_path, local_path)) = recorded {
+        return (project_path, Some(local_path).filter(|p| PathBuf::from(p).is_file()));
+    }
+    let stem = PathBuf::from(&library_name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let root = get_ghidra_projects_dir();
+    let local_path = root.join("libraries").join(&library_name);
+    (
+        root.join(stem).to_string_lossy().to_string(),
+        local_path.is_file().then(|| local_path.to_string_lossy().to_string()),
+    )
+}
+
+fn backend_infos(target: &DecompileTarget) -> Vec<DecompilerBackendInfo> {
+    backends().iter()
+        .map(|backend| {
+            let reason = backend.unavailable_reason(target);
+            DecompilerBackendInfo { name: backend.name().to_string(), available: reason.is_none(), reason }
+        })
+        .collect()
+}
+
+/// Backend selection for the module's project, with which backends can run
+#[tauri::command]
+pub async fn get_decompiler_settings(
+    state: tauri::State<'_, AppStateType>,
+    library_path: String,
+    project_path: Option<String>,
+    ghidra_path: Option<String>,
+) -> Result<DecompilerSettings, String> {
+    let (resolved, local_path) = resolve_paths(state.inner(), &library_path).await;
+    let project_path = project_path.unwrap_or(resolved);
+    let (backend, tool_path) = load_settings(&project_path).await;
+    let target = DecompileTarget {
+        project_path: project_path.clone(),
+        library_name: crate::analysis_policy::file_name(&library_path).to_string(),
+        local_path,
+        function_offset: String::new(),
+        ghidra_path,
+        tool_path: tool_path.clone(),
+    };
+    Ok(DecompilerSettings { backends: backend_infos(&target), project_path, backend, tool_path })
+}
+
+/// Choose the decompiler backend ("auto", "ghidra", "radare2", "capstone")
+/// for a project; `tool_path` points at a radare2/rizin binary not on PATH
+#[tauri::command]
+pub async fn set_decompiler_backend(project_path: String, backend: String, tool_path: Option<String>) -> Result<(), String> {
+    if backend != "auto" && !backends().iter().any(|b| b.name() == backend) {
+        return Err(format!("Unknown decompiler backend: {}", backend));
+    }
+    db::run(move |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO decompiler_settings (project_path, backend, tool_path) VALUES (?1, ?2, ?3)",
+            params![project_path, backend, tool_path.filter(|p| !p.is_empty())],
+        ).map_err(|e| e.to_string())?;
+        Ok(())
+    }).await
+}
+
+/// Decompile a function of a module with the project's backend (`backend`
+/// overrides it). "auto" uses Ghidra when the module is analyzed or its
+/// server runs, then radare2/rizin, then Capstone pseudo-code.
+#[tauri::command]
+pub async fn decompile_function(
+    state: tauri::State<'_, AppStateType>,
+    library_path: String,
+    function_address: String,
+    project_path: Option<String>,
+    local_path: Option<String>,
+    ghidra_path: Option<String>,
+    backend: Option<String>,
+) -> Result<GhidraDecompileResult, String> {
+    let (resolved_project, resolved_local) = resolve_paths(state.inner(), &library_path).await;
+    let project_path = project_path.unwrap_or(resolved_project);
+    let (saved_backend, tool_path) = load_settings(&project_path).await;
+    let mut target = DecompileTarget {
+        library_name: crate::analysis_policy::file_name(&library_path).to_string(),
+        local_path: local_path.filter(|p| PathBuf::from(p).is_file()).or(resolved_local),
+        project_path,
+        function_offset: function_address,
+        ghidra_path,
+        tool_path,
+    };
+
+    let selected = backend.unwrap_or(saved_backend);
+    let decompiler = if selected == "auto" {
+        *backends().iter()
+            .find(|b| b.unavailable_reason(&target).is_none())
+            .unwrap_or(&backends()[2])
+    } else {
+        *backends().iter()
+            .find(|b| b.name() == selected)
+            .ok_or_else(|| format!("Unknown decompiler backend: {}", selected))?
+    };
+    if let Some(reason) = decompiler.unavailable_reason(&target) {
+        return Ok(failure(&target, format!("{} backend unavailable: {}", decompiler.name(), reason)));
+    }
+    if decompiler.name() != "ghidra" && target.local_path.is_none() {
+        target.local_path = Some(download_library_file(library_path, None).await?);
+    }
+    decompiler.decompile(&target).await
+}
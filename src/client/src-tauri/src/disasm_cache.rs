@@ -0,0 +1,305 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::disassembly::{disassemble_structured, StructuredInstruction};
+use crate::state::AppStateType;
+use crate::{memory_regions, read_memory, virtual_addresses, MemoryReadResponse, SERVER_CONFIG};
+
+const PAGE_SIZE: u64 = 0x1000;
+// Blocks kept across all sessions; the least recently used is dropped first
+const MAX_BLOCKS: usize = 512;
+// Longest instruction of the supported architectures. A page's sweep runs up to
+// this far into the next page, so a write there also invalidates the page before.
+const MAX_INSN_LEN: u64 = 15;
+const DEFAULT_PREFETCH_PAGES: u64 = 4;
+const MAX_PREFETCH_PAGES: u64 = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BlockKey {
+    session: String,                     // Server and attached pid
+    page: u64,
+    version: u64,                        // Code version of the page when it was read
+}
+
+struct CachedBlock {
+    bytes: Vec<u8>,                      // Readable prefix of the page
+    decoded: HashMap<String, Vec<StructuredInstruction>>, // Per architecture, linear sweep from the page start
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Cache {
+    blocks: HashMap<BlockKey, CachedBlock>,
+    versions: HashMap<u64, u64>,         // Bumped by writes to the page
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl Cache {
+    fn key(&self, session: &str, page: u64) -> BlockKey {
+        BlockKey { session: session.to_string(), page, version: self.versions.get(&page).copied().unwrap_or(0) }
+    }
+
+    fn touch(&mut self, key: &BlockKey) -> Option<&mut CachedBlock> {
+        self.tick += 1;
+        let tick = self.tick;
+        let block = self.blocks.get_mut(key)?;
+        block.last_used = tick;
+        Some(block)
+    }
+
+    fn insert(&mut self, key: BlockKey, bytes: Vec<u8>) {
+        if !self.blocks.contains_key(&key) && self.blocks.len() >= MAX_BLOCKS {
+            if let Some(oldest) = self.blocks.iter().min_by_key(|(_, b)| b.last_used).map(|(k, _)| k.clone()) {
+                self.blocks.remove(&oldest);
+            }
+        }
+        self.tick += 1;
+        self.blocks.insert(key, CachedBlock { bytes, decoded: HashMap::new(), last_used: self.tick });
+    }
+}
+
+static CACHE: Lazy<Mutex<Cache>> = Lazy::new(|| Mutex::new(Cache::default()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisassemblyCacheStats {
+    pub blocks: usize,
+    pub hits: u64,                       // Pages served without a read
+    pub misses: u64,
+}
+
+fn session_key(state: &AppStateType) -> Result<String, String> {
+    let pid = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?
+        .attached_process.as_ref().map(|p| p.pid);
+    let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+    Ok(format!("{}:{}:{:?}", config.host, config.port, pid))
+}
+
+/// Bump the code version of every page whose decoded block covers
+/// `[address, address + size)`, and drop those blocks
+pub fn invalidate_range(address: u64, size: usize) {
+    if size == 0 {
+        return;
+    }
+    let Ok(mut cache) = CACHE.lock() else { return };
+    let first = address.saturating_sub(MAX_INSN_LEN) / PAGE_SIZE;
+    let last = address.saturating_add(size as u64 - 1) / PAGE_SIZE;
+    for page in first..=last {
+        *cache.versions.entry(page).or_insert(0) += 1;
+    }
+    cache.blocks.retain(|key, _| key.page < first || key.page > last);
+}
+
+/// Forget every block (the target's memory source changed)
+pub fn clear() {
+    if let Ok(mut cache) = CACHE.lock() {
+        cache.blocks.clear();
+    }
+}
+
+/// Read pages `first..=last` that are not cached yet, one read per run of
+/// missing pages. Versions are taken before the read, so a write landing
+/// while it is in flight leaves the stale block unreachable.
+async fn fill_pages(session: &str, first: u64, last: u64) -> Result<Option<MemoryReadResponse>, String> {
+    let missing: Vec<BlockKey> = {
+        let cache = CACHE.lock().map_err(|e| e.to_string())?;
+        (first..=last).map(|page| cache.key(session, page)).filter(|key| !cache.blocks.contains_key(key)).collect()
+    };
+    let mut failure = None;
+    let mut run_start = 0;
+    while run_start < missing.len() {
+        let mut run_end = run_start + 1;
+        while run_end < missing.len() && missing[run_end].page == missing[run_end - 1].page + 1 {
+            run_end += 1;
+        }
+        let start = missing[run_start].page * PAGE_SIZE;
+        let mut size = (run_end - run_start) as u64 * PAGE_SIZE;
+        if let Some(span) = memory_regions::readable_span(start) {
+            size = size.min(span);
+        }
+        let response = read_memory(start, size as usize).await?;
+        match response.data.as_ref().filter(|_| response.success) {
+            Some(data) => {
+                let mut cache = CACHE.lock().map_err(|e| e.to_string())?;
+                for (key, chunk) in missing[run_start..run_end].iter().zip(data.chunks(PAGE_SIZE as usize)) {
+                    cache.insert(key.clone(), chunk.to_vec());
+                }
+            }
+            None if missing[run_start].page == first => failure = Some(response),
+            None => {}
+        }
+        run_start = run_end;
+    }
+    if let Ok(mut cache) = CACHE.lock() {
+        cache.misses += missing.len() as u64;
+        cache.hits += (last - first + 1).saturating_sub(missing.len() as u64);
+    }
+    Ok(failure)
+}
+
+/// Bytes of `[address, address + size)` from cached pages, stopping at the
+/// first page that could not be read
+fn cached_bytes(session: &str, address: u64, size: usize) -> Result<Vec<u8>, String> {
+    let end = address.saturating_add(size as u64);
+    let mut cache = CACHE.lock().map_err(|e| e.to_string())?;
+    let mut data = Vec::with_capacity(size);
+    let mut page = address / PAGE_SIZE;
+    while page * PAGE_SIZE < end {
+        let key = cache.key(session, page);
+        let Some(block) = cache.touch(&key) else { break };
+        let page_start = page * PAGE_SIZE;
+        let from = address.saturating_sub(page_start) as usize;
+        let to = ((end - page_start) as usize).min(block.bytes.len());
+        if from < to {
+            data.extend_from_slice(&block.bytes[from..to]);
+        }
+        if block.bytes.len() < PAGE_SIZE as usize {
+            break;
+        }
+        page += 1;
+    }
+    Ok(data)
+}
+
+/// `read_memory` served from the page cache; misses are read a page at a time
+pub async fn read_code(state: &AppStateType, address: u64, size: usize) -> Result<MemoryReadResponse, String> {
+    if size == 0 || virtual_addresses::is_virtual(address) {
+        return read_memory(address, size).await;
+    }
+    let session = session_key(state)?;
+    let last = address.saturating_add(size as u64 - 1) / PAGE_SIZE;
+    if let Some(failure) = fill_pages(&session, address / PAGE_SIZE, last).await? {
+        return Ok(failure);
+    }
+    let data = cached_bytes(&session, address, size)?;
+    if data.is_empty() {
+        return Ok(MemoryReadResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to read memory at 0x{:x}", address)),
+            timing: None,
+        });
+    }
+    Ok(MemoryReadResponse { success: true, data: Some(data), error: None, timing: None })
+}
+
+/// Instructions decoded from the start of a cached page (running into the
+/// next page when it is cached), decoding them on first use
+fn page_instructions(session: &str, page: u64, architecture: &str) -> Result<Option<Vec<StructuredInstruction>>, String> {
+    let mut cache = CACHE.lock().map_err(|e| e.to_string())?;
+    let key = cache.key(session, page);
+    if let Some(decoded) = cache.touch(&key).and_then(|b| b.decoded.get(architecture)) {
+        return Ok(Some(decoded.clone()));
+    }
+    let Some(block) = cache.blocks.get(&key) else { return Ok(None) };
+    let mut bytes = block.bytes.clone();
+    if bytes.len() == PAGE_SIZE as usize {
+        let next = cache.key(session, page + 1);
+        if let Some(next) = cache.blocks.get(&next) {
+            bytes.extend_from_slice(&next.bytes[..next.bytes.len().min(MAX_INSN_LEN as usize)]);
+        }
+    }
+    let page_end = (page + 1) * PAGE_SIZE;
+    let mut decoded = disassemble_structured(&bytes, page * PAGE_SIZE, architecture)?;
+    decoded.retain(|insn| insn.address < page_end);
+    if let Some(block) = cache.blocks.get_mut(&key) {
+        block.decoded.insert(architecture.to_string(), decoded.clone());
+    }
+    Ok(Some(decoded))
+}
+
+/// Decode `[address, address + size)` from cached blocks. Blocks are reused
+/// while the sweep stays on their instruction boundaries; when it does not
+/// (e.g. an x86 address inside a block's instruction) the rest is decoded
+/// from the cached bytes. Same result as `disassemble_structured` on a fresh read.
+pub async fn disassemble_cached(
+    state: &AppStateType,
+    address: u64,
+    size: usize,
+    architecture: &str,
+) -> Result<Vec<StructuredInstruction>, String> {
+    let response = read_code(state, address, size).await?;
+    if !response.success {
+        return Err(response.error.unwrap_or_else(|| "Failed to read memory".to_string()));
+    }
+    let data = response.data.ok_or("No memory data received")?;
+    if virtual_addresses::is_virtual(address) {
+        return disassemble_structured(&data, address, architecture);
+    }
+
+    let session = session_key(state)?;
+    let end = address + data.len() as u64;
+    // Decoding a page looks into the next one
+    fill_pages(&session, end / PAGE_SIZE, end / PAGE_SIZE).await?;
+
+    let mut instructions = Vec::new();
+    let mut next = address;
+    while next < end {
+        let page = next / PAGE_SIZE;
+        let decoded = page_instructions(&session, page, architecture)?.unwrap_or_default();
+        let Some(position) = decoded.iter().position(|insn| insn.address == next) else { break };
+        for insn in &decoded[position..] {
+            if insn.address + insn.size as u64 > end {
+                return Ok(instructions);
+            }
+            next = insn.address + insn.size as u64;
+            instructions.push(insn.clone());
+        }
+        // The block's sweep ended inside its page; decode the rest below
+        if next < (page + 1) * PAGE_SIZE {
+            break;
+        }
+    }
+    if next < end {
+        let offset = (next - address) as usize;
+        instructions.append(&mut disassemble_structured(&data[offset..], next, architecture)?);
+    }
+    Ok(instructions)
+}
+
+/// Warm the pages around `center_address` in the background, decoding them
+/// for `architecture`. Returns the number of pages scheduled.
+#[tauri::command]
+pub async fn prefetch_disassembly(
+    state: tauri::State<'_, AppStateType>,
+    center_address: u64,
+    pages: Option<u64>,
+    architecture: String,
+) -> Result<u64, String> {
+    if virtual_addresses::is_virtual(center_address) {
+        return Ok(0);
+    }
+    let session = session_key(state.inner())?;
+    let pages = pages.unwrap_or(DEFAULT_PREFETCH_PAGES).min(MAX_PREFETCH_PAGES);
+    let center = center_address / PAGE_SIZE;
+    let first = center.saturating_sub(pages);
+    let last = center.saturating_add(pages);
+    tokio::spawn(async move {
+        // One page past the range so the last page decodes its trailing instruction
+        if fill_pages(&session, first, last + 1).await.is_err() {
+            return;
+        }
+        for page in first..=last {
+            let _ = page_instructions(&session, page, &architecture);
+        }
+    });
+    Ok(last - first + 1)
+}
+
+/// Drop cached disassembly for a range written outside the client (e.g. by a script on the server)
+#[tauri::command]
+pub fn invalidate_disassembly_cache(address: Option<u64>, size: Option<usize>) {
+    match address {
+        Some(address) => invalidate_range(address, size.unwrap_or(1)),
+        None => clear(),
+    }
+}
+
+#[tauri::command]
+pub fn get_disassembly_cache_stats() -> Result<DisassemblyCacheStats, String> {
+    let cache = CACHE.lock().map_err(|e| e.to_string())?;
+    Ok(DisassemblyCacheStats { blocks: cache.blocks.len(), hits: cache.hits, misses: cache.misses })
+}
@@ -0,0 +1,335 @@
+use once_cell::sync::Lazy;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::disassembly::StructuredInstruction;
+use crate::state::AppStateType;
+use crate::symbolizer::Symbolizer;
+use crate::{db, debug_symbols, jit_regions, read_memory_from_server, secure_store, SERVER_CONFIG};
+
+// Provider names, in the order their comments are attached
+pub const PROVIDER_SYMBOLS: &str = "symbols";
+pub const PROVIDER_PC_RELATIVE: &str = "pc_relative_data";
+pub const PROVIDER_BOOKMARKS: &str = "bookmarks";
+pub const PROVIDER_XREFS: &str = "xrefs";
+pub const PROVIDER_TRACE_HITS: &str = "trace_hits";
+pub const PROVIDER_SOURCE_LINES: &str = "source_lines";
+
+const MAX_CACHE_ENTRIES: usize = 100_000;
+// Memory reads per request; the rest of the PC-relative targets stay uncommented
+const MAX_DATA_READS: usize = 256;
+const MIN_STRING_LEN: usize = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentProviderConfig {
+    pub name: String,
+    pub enabled: bool,
+    pub ttl_ms: u64,             // How long a computed comment is reused
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstructionComment {
+    pub provider: String,
+    pub text: String,
+}
+
+static PROVIDERS: Lazy<RwLock<Vec<CommentProviderConfig>>> = Lazy::new(|| {
+    let provider = |name: &str, ttl_ms: u64| CommentProviderConfig { name: name.to_string(), enabled: true, ttl_ms };
+    RwLock::new(vec![
+        provider(PROVIDER_SYMBOLS, 60_000),
+        provider(PROVIDER_PC_RELATIVE, 1_000),   // Data can change under a running target
+        provider(PROVIDER_BOOKMARKS, 5_000),
+        provider(PROVIDER_XREFS, 60_000),
+        provider(PROVIDER_TRACE_HITS, 1_000),
+        provider(PROVIDER_SOURCE_LINES, 60_000),
+    ])
+});
+
+// (provider, address) -> (computed at, comment)
+type CommentCache = HashMap<(String, u64), (Instant, Option<String>)>;
+
+static CACHE: Lazy<Mutex<CommentCache>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+/// Everything the providers need, gathered once per request
+struct CommentContext {
+    symbolizer: Symbolizer,
+    target_os: String,
+    architecture: String,
+    host: String,
+    port: u16,
+    trace_hits: HashMap<u64, usize>,
+    bookmarks: HashMap<u64, String>,
+    xref_counts: HashMap<(String, u64), usize>,
+}
+
+fn parse_hex(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches('#').trim_start_matches("0x"), 16).ok()
+}
+
+/// Addresses referenced PC-relatively (x86 `[rip + d]`, ARM64 adr / adrp pairs /
+/// literal loads), indexed like `instructions`
+fn pc_relative_targets(instructions: &[StructuredInstruction], architecture: &str) -> Vec<Option<u64>> {
+    let mut pages: HashMap<String, u64> = HashMap::new();
+    instructions.iter().map(|insn| {
+        let operands: Vec<&str> = insn.operands.split(',').map(|o| o.trim()).collect();
+        match architecture {
+            "x86_64" => {
+                let start = insn.operands.find("[rip")?;
+                let inner = &insn.operands[start + 4..insn.operands[start..].find(']')? + start];
+                let inner = inner.replace(' ', "");
+                let next = insn.address + insn.size as u64;
+                match (inner.strip_prefix('+'), inner.strip_prefix('-')) {
+                    (Some(d), _) => Some(next.wrapping_add(parse_hex(d)?)),
+                    (_, Some(d)) => Some(next.wrapping_sub(parse_hex(d)?)),
+                    _ => Some(next),
+                }
+            }
+            "arm64" | "aarch64" => {
+                let mnemonic = insn.mnemonic.as_str();
+                let target = match mnemonic {
+                    "adrp" => {
+                        let page = parse_hex(operands.get(1)?)?;
+                        pages.insert(operands.first()?.to_string(), page);
+                        None
+                    }
+                    "adr" => parse_hex(operands.get(1)?),
+                    "add" if operands.len() == 3 => {
+                        let page = *pages.get(operands[1])?;
+                        Some(page + parse_hex(operands[2])?)
+                    }
+                    m if m.starts_with("ldr") || m.starts_with("str") => {
+                        let memory = operands[1..].join(",");
+                        if let Some(inner) = memory.strip_prefix('[').and_then(|m| m.split(']').next()) {
+                            let mut parts = inner.split(',').map(|p| p.trim());
+                            let page = *pages.get(parts.next()?)?;
+                            Some(page + parts.next().and_then(parse_hex).unwrap_or(0))
+                        } else {
+                            // Literal load: ldr x0, #0x1234
+                            parse_hex(operands.get(1)?)
+                        }
+                    }
+                    _ => None,
+                };
+                // A page register is only valid until something else writes it
+                if mnemonic != "adrp" {
+                    for reg in &insn.regs_write {
+                        pages.remove(reg);
+                    }
+                }
+                target
+            }
+            _ => None,
+        }
+    }).collect()
+}
+
+fn format_data(context: &CommentContext, data: &[u8]) -> Option<String> {
+    let printable = data.iter().take_while(|b| b.is_ascii_graphic() || **b == b' ').count();
+    if printable >= MIN_STRING_LEN {
+        return Some(format!("\"{}\"", String::from_utf8_lossy(&data[..printable])));
+    }
+    let value = u64::from_le_bytes(data.get(..8)?.try_into().ok()?);
+    match context.symbolizer.resolve(value) {
+        Some(symbol) => Some(format!("= 0x{:x} -> {}", value, symbol.display)),
+        None => Some(format!("= 0x{:x}", value)),
+    }
+}
+
+async fn compute(
+    context: &CommentContext,
+    provider: &str,
+    insn: &StructuredInstruction,
+    pc_target: Option<u64>,
+    reads: &mut usize,
+) -> Option<String> {
+    match provider {
+        PROVIDER_SYMBOLS => {
+            let target = insn.branch_target.or(pc_target)?;
+            context.symbolizer.resolve(target).map(|s| s.display)
+        }
+        PROVIDER_PC_RELATIVE => {
+            let target = pc_target?;
+            if *reads >= MAX_DATA_READS || context.host.is_empty() {
+                return None;
+            }
+            *reads += 1;
+            let data = read_memory_from_server(&context.host, context.port, target, 32).await.ok()?;
+            format_data(context, &data)
+        }
+        PROVIDER_BOOKMARKS => context.bookmarks.get(&insn.address).cloned(),
+        PROVIDER_XREFS => {
+            let symbol = context.symbolizer.resolve(insn.address)?;
+            if symbol.function_offset != Some(0) {
+                return None;
+            }
+            let count = context.xref_counts.get(&(symbol.module_name, symbol.module_offset))?;
+            Some(format!("{} xref{}", count, if *count == 1 { "" } else { "s" }))
+        }
+        PROVIDER_TRACE_HITS => {
+            let hits = context.trace_hits.get(&insn.address)?;
+            Some(format!("hit {}x in trace", hits))
+        }
+        PROVIDER_SOURCE_LINES => {
+            // Only where a line starts, so each source line is labelled once
+            let symbol = context.symbolizer.resolve(insn.address)?;
+            let (location, starts_here) = debug_symbols::source_location(&context.target_os, &symbol.module_name, symbol.module_offset)?;
+            let file = location.file.rsplit(['/', '\\']).next().unwrap_or(&location.file);
+            starts_here.then(|| format!("{}:{}", file, location.line))
+        }
+        _ => None,
+    }
+}
+
+/// Xref counts of the cached functions in the modules covered by `instructions`
+async fn load_xref_counts(context: &CommentContext, instructions: &[StructuredInstruction]) -> HashMap<(String, u64), usize> {
+    let mut modules: Vec<String> = instructions.iter()
+        .filter_map(|i| context.symbolizer.resolve(i.address).map(|s| s.module_name))
+        .collect();
+    modules.sort();
+    modules.dedup();
+
+    let target_os = context.target_os.clone();
+    db::run(move |conn| {
+        let mut counts = HashMap::new();
+        for module in modules {
+            let Ok(mut stmt) = conn.prepare(
+                "SELECT function_address, xrefs_json FROM ghidra_xref_cache WHERE target_os = ?1 AND module_name = ?2",
+            ) else {
+                continue;
+            };
+            let Ok(rows) = stmt.query_map(params![target_os, module], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            }) else {
+                continue;
+            };
+            for (address, json) in rows.filter_map(|r| r.ok()) {
+                let Some(offset) = parse_hex(&address) else {
+                    continue;
+                };
+                let count = secure_store::open_value("ghidra_xref_cache", "xrefs_json", json).ok()
+                    .and_then(|json| serde_json::from_str::<Vec<serde_json::Value>>(&json).ok())
+                    .map(|xrefs| xrefs.len())
+                    .unwrap_or(0);
+                counts.insert((module.clone(), offset), count);
+            }
+        }
+        Ok(counts)
+    }).await.unwrap_or_default()
+}
+
+/// Run the enabled providers over `instructions` and attach their comments
+pub async fn annotate(
+    state: &AppStateType,
+    instructions: &mut [StructuredInstruction],
+    architecture: &str,
+) -> Result<(), String> {
+    let providers: Vec<CommentProviderConfig> = PROVIDERS.read().map_err(|e| e.to_string())?
+        .iter().filter(|p| p.enabled).cloned().collect();
+    if providers.is_empty() || instructions.is_empty() {
+        return Ok(());
+    }
+    let enabled = |name: &str| providers.iter().any(|p| p.name == name);
+
+    let start = instructions.first().map(|i| i.address).unwrap_or(0);
+    let end = instructions.last().map(|i| i.address + i.size as u64).unwrap_or(0);
+    let (target_os, trace_hits) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let mut hits: HashMap<u64, usize> = HashMap::new();
+        if enabled(PROVIDER_TRACE_HITS) {
+            for entry in &state_guard.trace_store {
+                if let Some(address) = parse_hex(&entry.address).filter(|a| (start..end).contains(a)) {
+                    *hits.entry(address).or_insert(0) += 1;
+                }
+            }
+        }
+        let target_os = state_guard.server_info.as_ref().map(|i| i.target_os.clone()).unwrap_or_default();
+        (target_os, hits)
+    };
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+    let mut context = CommentContext {
+        symbolizer: Symbolizer::load(state).await?,
+        target_os,
+        architecture: architecture.to_string(),
+        host,
+        port,
+        trace_hits,
+        bookmarks: jit_regions::bookmarks_in_range(start, end).into_iter().collect(),
+        xref_counts: HashMap::new(),
+    };
+    if enabled(PROVIDER_XREFS) {
+        context.xref_counts = load_xref_counts(&context, instructions).await;
+    }
+
+    let pc_targets = pc_relative_targets(instructions, &context.architecture);
+    let mut reads = 0;
+    for (insn, pc_target) in instructions.iter_mut().zip(pc_targets) {
+        for provider in &providers {
+            let key = (provider.name.clone(), insn.address);
+            let ttl = Duration::from_millis(provider.ttl_ms);
+            let cached = CACHE.lock().ok()
+                .and_then(|cache| cache.get(&key).cloned())
+                .filter(|(at, _)| at.elapsed() < ttl)
+                .map(|(_, comment)| comment);
+            let comment = match cached {
+                Some(comment) => comment,
+                None => {
+                    let comment = compute(&context, &provider.name, insn, pc_target, &mut reads).await;
+                    if let Ok(mut cache) = CACHE.lock() {
+                        if cache.len() >= MAX_CACHE_ENTRIES {
+                            cache.clear();
+                        }
+                        cache.insert(key, (Instant::now(), comment.clone()));
+                    }
+                    comment
+                }
+            };
+            if let Some(text) = comment {
+                insn.comments.push(InstructionComment { provider: provider.name.clone(), text });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_comment_providers() -> Result<Vec<CommentProviderConfig>, String> {
+    Ok(PROVIDERS.read().map_err(|e| e.to_string())?.clone())
+}
+
+/// Enable / disable a provider or change how long its comments are cached
+#[tauri::command]
+pub fn set_comment_provider(
+    name: String,
+    enabled: Option<bool>,
+    ttl_ms: Option<u64>,
+) -> Result<Vec<CommentProviderConfig>, String> {
+    let mut providers = PROVIDERS.write().map_err(|e| e.to_string())?;
+    let provider = providers.iter_mut().find(|p| p.name == name)
+        .ok_or_else(|| format!("Unknown comment provider '{}'", name))?;
+    if let Some(enabled) = enabled {
+        provider.enabled = enabled;
+    }
+    if let Some(ttl_ms) = ttl_ms {
+        provider.ttl_ms = ttl_ms;
+    }
+    Ok(providers.clone())
+}
+
+/// Drop cached comments (of one provider, or all)
+#[tauri::command]
+pub fn clear_comment_cache(provider: Option<String>) -> Result<(), String> {
+    let mut cache = CACHE.lock().map_err(|e| e.to_string())?;
+    match provider {
+        Some(provider) => cache.retain(|(name, _), _| *name != provider),
+        None => cache.clear(),
+    }
+    Ok(())
+}
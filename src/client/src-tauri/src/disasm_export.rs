@@ -0,0 +1,244 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::disassembly::{disassemble_memory_structured, StructuredInstruction};
+use crate::state::AppStateType;
+use crate::symbolizer::Symbolizer;
+use crate::{escape_markup, DisassembleRequest};
+
+// Upper bound for one export; whole functions larger than this are truncated
+const MAX_EXPORT_BYTES: usize = 0x40000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportDisassemblyRequest {
+    pub address: u64,
+    pub size: Option<usize>,          // Bytes to export; defaults to the containing function
+    #[serde(default)]
+    pub whole_function: bool,         // Widen the range to the function containing `address`
+    pub format: String,               // "text", "json" or "html"
+    pub architecture: Option<String>, // Defaults to the connected target's
+    #[serde(default)]
+    pub comments: bool,
+    pub output_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonListing<'a> {
+    architecture: &'a str,
+    start: u64,
+    end: u64,
+    function: Option<String>,
+    instructions: &'a [StructuredInstruction],
+}
+
+struct Listing {
+    architecture: String,
+    start: u64,
+    end: u64,
+    function: Option<String>,
+    labels: Vec<Option<String>>,      // Per instruction: function starting at it
+    instructions: Vec<StructuredInstruction>,
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(" ")
+}
+
+fn trailing_comment(insn: &StructuredInstruction) -> Option<String> {
+    let mut parts: Vec<String> = insn.branch_symbol.iter().cloned().collect();
+    parts.extend(insn.comments.iter().map(|c| c.text.clone()));
+    (!parts.is_empty()).then(|| parts.join("; "))
+}
+
+fn to_text(listing: &Listing) -> String {
+    let width = listing.instructions.iter().map(|i| i.size).max().unwrap_or(0) * 3;
+    let mut out = format!(
+        "; {} disassembly 0x{:x}-0x{:x}{}\n",
+        listing.architecture,
+        listing.start,
+        listing.end,
+        listing.function.as_ref().map(|f| format!(" ({})", f)).unwrap_or_default()
+    );
+    for (insn, label) in listing.instructions.iter().zip(&listing.labels) {
+        if let Some(label) = label {
+            out.push_str(&format!("\n{}:\n", label));
+        }
+        let mut line = format!(
+            "0x{:016x}  {:<width$} {:<8} {}",
+            insn.address, hex_bytes(&insn.bytes), insn.mnemonic, insn.operands, width = width
+        );
+        if let Some(comment) = trailing_comment(insn) {
+            line = format!("{:<72} ; {}", line.trim_end(), comment);
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn is_immediate(token: &str) -> bool {
+    let token = token.trim_start_matches('#').trim_start_matches('-');
+    token.starts_with("0x") || token.chars().next().is_some_and(|c| c.is_ascii_digit())
+}
+
+/// Color registers and immediates of an operand string; direct branch
+/// targets inside the listing become links to their anchor
+fn highlight_operands(insn: &StructuredInstruction, anchors: &HashSet<u64>) -> String {
+    let registers: HashSet<String> = insn.regs_read.iter().chain(&insn.regs_write).map(|r| r.to_lowercase()).collect();
+    let mut out = String::new();
+    let mut token = String::new();
+    let flush = |token: &mut String, out: &mut String| {
+        if token.is_empty() {
+            return;
+        }
+        let escaped = escape_markup(token);
+        let linked = insn.branch_target
+            .filter(|t| anchors.contains(t) && u64::from_str_radix(token.trim_start_matches('#').trim_start_matches("0x"), 16).ok() == Some(*t));
+        if let Some(target) = linked {
+            out.push_str(&format!("<a class=\"imm\" href=\"#a{:x}\">{}</a>", target, escaped));
+        } else if registers.contains(&token.to_lowercase()) {
+            out.push_str(&format!("<span class=\"reg\">{}</span>", escaped));
+        } else if is_immediate(token) {
+            out.push_str(&format!("<span class=\"imm\">{}</span>", escaped));
+        } else {
+            out.push_str(&escaped);
+        }
+        token.clear();
+    };
+    for c in insn.operands.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '#' || (c == '-' && token.is_empty()) {
+            token.push(c);
+        } else {
+            flush(&mut token, &mut out);
+            out.push_str(&escape_markup(&c.to_string()));
+        }
+    }
+    flush(&mut token, &mut out);
+    out
+}
+
+fn to_html(listing: &Listing) -> String {
+    let anchors: HashSet<u64> = listing.instructions.iter().map(|i| i.address).collect();
+    let title = format!(
+        "{} 0x{:x}-0x{:x}",
+        listing.function.clone().unwrap_or_else(|| "Disassembly".to_string()),
+        listing.start,
+        listing.end
+    );
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape_markup(&title)));
+    out.push_str(
+        "<style>\n\
+         body { background: #1e1e1e; color: #d4d4d4; font: 13px/1.5 Menlo, Consolas, monospace; }\n\
+         .insn { white-space: pre; }\n\
+         .insn:target { background: #264f78; }\n\
+         .addr { color: #858585; } .addr a { color: inherit; text-decoration: none; }\n\
+         .bytes { color: #6a6a6a; }\n\
+         .mn { color: #569cd6; } .mn.call { color: #dcdcaa; } .mn.jump { color: #c586c0; } .mn.ret { color: #f44747; }\n\
+         .reg { color: #9cdcfe; } .imm { color: #b5cea8; } a.imm { text-decoration: underline; }\n\
+         .comment { color: #6a9955; }\n\
+         .label { color: #4ec9b0; margin-top: 1em; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    out.push_str(&format!("<h3>{} ({})</h3>\n", escape_markup(&title), escape_markup(&listing.architecture)));
+
+    let width = listing.instructions.iter().map(|i| i.size).max().unwrap_or(0) * 3;
+    for (insn, label) in listing.instructions.iter().zip(&listing.labels) {
+        if let Some(label) = label {
+            out.push_str(&format!("<div class=\"label\">{}:</div>\n", escape_markup(label)));
+        }
+        let kind = if insn.is_call {
+            " call"
+        } else if insn.is_return {
+            " ret"
+        } else if insn.is_branch {
+            " jump"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "<div class=\"insn\" id=\"a{addr:x}\"><span class=\"addr\"><a href=\"#a{addr:x}\">0x{addr:016x}</a></span>  <span class=\"bytes\">{bytes:<width$}</span> <span class=\"mn{kind}\">{mn:<8}</span> {ops}",
+            addr = insn.address,
+            bytes = hex_bytes(&insn.bytes),
+            width = width,
+            kind = kind,
+            mn = escape_markup(&insn.mnemonic),
+            ops = highlight_operands(insn, &anchors),
+        ));
+        if let Some(comment) = trailing_comment(insn) {
+            out.push_str(&format!("  <span class=\"comment\">; {}</span>", escape_markup(&comment)));
+        }
+        out.push_str("</div>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Export an address range or a whole function as a plain text listing,
+/// structured JSON or a standalone HTML page. Writes to `output_path` when
+/// given and returns the content either way.
+#[tauri::command]
+pub async fn export_disassembly(
+    state: tauri::State<'_, AppStateType>,
+    request: ExportDisassemblyRequest,
+) -> Result<String, String> {
+    let format = request.format.to_lowercase();
+    if !matches!(format.as_str(), "text" | "txt" | "json" | "html") {
+        return Err(format!("Unsupported disassembly export format: {}", request.format));
+    }
+    let architecture = match request.architecture.clone() {
+        Some(architecture) => architecture,
+        None => {
+            let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+            state_guard.server_info.as_ref().map(|s| s.arch.clone()).ok_or("Not connected to a server")?
+        }
+    };
+
+    let symbolizer = Symbolizer::load(state.inner()).await?;
+    let (start, end) = match request.size {
+        Some(size) if !request.whole_function => (request.address, request.address.saturating_add(size as u64)),
+        _ => symbolizer.function_range(request.address)
+            .ok_or_else(|| format!("No analyzed function contains 0x{:x}; pass a size instead", request.address))?,
+    };
+    let size = ((end - start) as usize).min(MAX_EXPORT_BYTES);
+
+    let instructions = disassemble_memory_structured(state.clone(), DisassembleRequest {
+        address: start,
+        size,
+        architecture: architecture.clone(),
+        symbolicate: true,
+        comments: request.comments,
+    }).await?;
+
+    let labels = instructions.iter()
+        .map(|insn| symbolizer.resolve(insn.address).filter(|s| s.function_offset == Some(0)).and_then(|s| s.function_name))
+        .collect();
+    let listing = Listing {
+        function: symbolizer.resolve(start).map(|s| s.display),
+        end: instructions.last().map(|i| i.address + i.size as u64).unwrap_or(start),
+        architecture,
+        start,
+        labels,
+        instructions,
+    };
+
+    let content = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&JsonListing {
+            architecture: &listing.architecture,
+            start: listing.start,
+            end: listing.end,
+            function: listing.function.clone(),
+            instructions: &listing.instructions,
+        }).map_err(|e| e.to_string())?,
+        "html" => to_html(&listing),
+        _ => to_text(&listing),
+    };
+
+    if let Some(path) = request.output_path {
+        tokio::fs::write(&path, &content)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    Ok(content)
+}
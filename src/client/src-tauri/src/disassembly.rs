@@ -0,0 +1,274 @@
+use capstone::arch::ArchOperand;
+use capstone::prelude::*;
+use capstone::{Insn, InsnGroupType};
+use serde::{Deserialize, Serialize};
+
+use crate::disasm_cache;
+use crate::disasm_comments::{self, InstructionComment};
+use crate::state::AppStateType;
+use crate::symbolizer::Symbolizer;
+use crate::{format_arm64_operands, memory_regions, wasm_disasm, DisassembleRequest};
+
+/// One decoded instruction with Capstone detail info
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructuredInstruction {
+    pub address: u64,
+    pub size: usize,
+    pub bytes: Vec<u8>,
+    pub mnemonic: String,
+    pub operands: String,
+    pub groups: Vec<String>,          // Capstone group names ("jump", "call", "ret", ...)
+    pub is_branch: bool,
+    pub is_call: bool,
+    pub is_return: bool,
+    pub branch_target: Option<u64>,   // Direct (immediate) targets only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_symbol: Option<String>, // Set when symbolication was requested
+    pub regs_read: Vec<String>,       // Implicit and explicit, including address registers
+    pub regs_write: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<InstructionComment>, // From the comment providers, when requested
+}
+
+/// Capstone engine with detail enabled for a DynaDbg architecture name
+pub fn build_capstone(architecture: &str) -> Result<Capstone, String> {
+    let cs = match architecture {
+        "x86" => Capstone::new().x86().mode(arch::x86::ArchMode::Mode32).detail(true).build(),
+        "x86_64" => Capstone::new().x86().mode(arch::x86::ArchMode::Mode64).detail(true).build(),
+        "arm" => Capstone::new().arm().mode(arch::arm::ArchMode::Arm).detail(true).build(),
+        "arm64" | "aarch64" => Capstone::new().arm64().mode(arch::arm64::ArchMode::Arm).detail(true).build(),
+        _ => {
+            eprintln!("Warning: Unsupported architecture '{}', defaulting to x86_64", architecture);
+            Capstone::new().x86().mode(arch::x86::ArchMode::Mode64).detail(true).build()
+        }
+    };
+    cs.map_err(|e| format!("Failed to create disassembler: {}", e))
+}
+
+fn push_reg(cs: &Capstone, regs: &mut Vec<String>, reg: RegId) {
+    if reg == RegId::INVALID_REG {
+        return;
+    }
+    if let Some(name) = cs.reg_name(reg) {
+        if !regs.contains(&name) {
+            regs.push(name);
+        }
+    }
+}
+
+/// ARM operands carry no access info in Capstone 4; the first register is the
+/// destination except for stores, compares and branches
+fn arm_first_reg_is_written(mnemonic: &str) -> bool {
+    let m = mnemonic.to_lowercase();
+    !(m.starts_with("st")
+        || m.starts_with("cmp")
+        || m.starts_with("cmn")
+        || m.starts_with("tst")
+        || m.starts_with("teq")
+        || m.starts_with("cb")
+        || m.starts_with("tb")
+        || m.starts_with("b")
+        || m.starts_with("ret")
+        || m.starts_with("push"))
+}
+
+pub fn describe_instruction(cs: &Capstone, insn: &Insn, architecture: &str) -> StructuredInstruction {
+    let mnemonic = insn.mnemonic().unwrap_or("???").to_string();
+    let op_str = insn.op_str().unwrap_or("");
+    let operands = match architecture {
+        "arm64" | "aarch64" if !op_str.is_empty() => format_arm64_operands(op_str),
+        _ => op_str.to_string(),
+    };
+
+    let mut groups = Vec::new();
+    let mut regs_read = Vec::new();
+    let mut regs_write = Vec::new();
+    let mut is_jump = false;
+    let mut is_call = false;
+    let mut is_return = false;
+    let mut branch_target = None;
+
+    if let Ok(detail) = cs.insn_detail(insn) {
+        for group in detail.groups() {
+            match group.0 as u32 {
+                InsnGroupType::CS_GRP_JUMP | InsnGroupType::CS_GRP_BRANCH_RELATIVE => is_jump = true,
+                InsnGroupType::CS_GRP_CALL => is_call = true,
+                InsnGroupType::CS_GRP_RET | InsnGroupType::CS_GRP_IRET => is_return = true,
+                _ => {}
+            }
+            if let Some(name) = cs.group_name(*group) {
+                groups.push(name);
+            }
+        }
+        for reg in detail.regs_read() {
+            push_reg(cs, &mut regs_read, *reg);
+        }
+        for reg in detail.regs_write() {
+            push_reg(cs, &mut regs_write, *reg);
+        }
+
+        let mut last_imm: Option<u64> = None;
+        let mut first_reg = true;
+        for operand in detail.arch_detail().operands() {
+            match operand {
+                ArchOperand::X86Operand(op) => match op.op_type {
+                    arch::x86::X86OperandType::Reg(reg) => {
+                        let access = op.access;
+                        if access.is_none_or(|a| a.is_readable()) {
+                            push_reg(cs, &mut regs_read, reg);
+                        }
+                        if access.is_some_and(|a| a.is_writable()) {
+                            push_reg(cs, &mut regs_write, reg);
+                        }
+                    }
+                    arch::x86::X86OperandType::Mem(mem) => {
+                        push_reg(cs, &mut regs_read, mem.base());
+                        push_reg(cs, &mut regs_read, mem.index());
+                    }
+                    arch::x86::X86OperandType::Imm(imm) => last_imm = Some(imm as u64),
+                    _ => {}
+                },
+                ArchOperand::Arm64Operand(op) => match op.op_type {
+                    arch::arm64::Arm64OperandType::Reg(reg) => {
+                        if first_reg && arm_first_reg_is_written(&mnemonic) {
+                            push_reg(cs, &mut regs_write, reg);
+                        } else {
+                            push_reg(cs, &mut regs_read, reg);
+                        }
+                        first_reg = false;
+                    }
+                    arch::arm64::Arm64OperandType::Mem(mem) => {
+                        push_reg(cs, &mut regs_read, mem.base());
+                        push_reg(cs, &mut regs_read, mem.index());
+                    }
+                    arch::arm64::Arm64OperandType::Imm(imm) => last_imm = Some(imm as u64),
+                    _ => {}
+                },
+                ArchOperand::ArmOperand(op) => match op.op_type {
+                    arch::arm::ArmOperandType::Reg(reg) => {
+                        if first_reg && arm_first_reg_is_written(&mnemonic) {
+                            push_reg(cs, &mut regs_write, reg);
+                        } else {
+                            push_reg(cs, &mut regs_read, reg);
+                        }
+                        first_reg = false;
+                    }
+                    arch::arm::ArmOperandType::Mem(mem) => {
+                        push_reg(cs, &mut regs_read, mem.base());
+                        push_reg(cs, &mut regs_read, mem.index());
+                    }
+                    arch::arm::ArmOperandType::Imm(imm) => last_imm = Some(imm as u32 as u64),
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        // The target is the last immediate (tbz/tbnz put the bit number first)
+        if is_jump || is_call {
+            branch_target = last_imm;
+        }
+    }
+
+    StructuredInstruction {
+        address: insn.address(),
+        size: insn.bytes().len(),
+        bytes: insn.bytes().to_vec(),
+        mnemonic,
+        operands,
+        groups,
+        is_branch: is_jump || is_call || is_return,
+        is_call,
+        is_return,
+        branch_target,
+        branch_symbol: None,
+        regs_read,
+        regs_write,
+        comments: Vec::new(),
+    }
+}
+
+/// Decode `data` at `address`, stopping at the first undecodable instruction
+pub fn disassemble_structured(data: &[u8], address: u64, architecture: &str) -> Result<Vec<StructuredInstruction>, String> {
+    if matches!(architecture, "wasm" | "wasm32") {
+        return Ok(wasm_disasm::structured(data, address, false));
+    }
+    let cs = build_capstone(architecture)?;
+    let instructions = cs.disasm_all(data, address)
+        .map_err(|e| format!("Disassembly failed: {}", e))?;
+    Ok(instructions.iter().map(|insn| describe_instruction(&cs, insn, architecture)).collect())
+}
+
+/// Decode all of `data` at `address`, for code mixed with data (JIT regions
+/// with constant pools). Undecodable bytes become ".byte" entries of one
+/// instruction slot and decoding resumes after them.
+pub fn disassemble_structured_skipping(data: &[u8], address: u64, architecture: &str) -> Result<Vec<StructuredInstruction>, String> {
+    if matches!(architecture, "wasm" | "wasm32") {
+        return Ok(wasm_disasm::structured(data, address, true));
+    }
+    let cs = build_capstone(architecture)?;
+    // Fixed-width ISAs resume at the next instruction slot
+    let slot = if matches!(architecture, "arm" | "arm64" | "aarch64") { 4 } else { 1 };
+    let mut instructions = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let start = address.wrapping_add(offset as u64);
+        let decoded = cs.disasm_all(&data[offset..], start)
+            .map_err(|e| format!("Disassembly failed: {}", e))?;
+        for insn in decoded.iter() {
+            instructions.push(describe_instruction(&cs, insn, architecture));
+            offset += insn.bytes().len();
+        }
+        if offset >= data.len() {
+            break;
+        }
+        let bytes = data[offset..(offset + slot).min(data.len())].to_vec();
+        instructions.push(StructuredInstruction {
+            address: address.wrapping_add(offset as u64),
+            size: bytes.len(),
+            mnemonic: ".byte".to_string(),
+            operands: bytes.iter().map(|b| format!("0x{:02x}", b)).collect::<Vec<_>>().join(", "),
+            bytes,
+            groups: Vec::new(),
+            is_branch: false,
+            is_call: false,
+            is_return: false,
+            branch_target: None,
+            branch_symbol: None,
+            regs_read: Vec::new(),
+            regs_write: Vec::new(),
+            comments: Vec::new(),
+        });
+        offset = (offset + slot).min(data.len());
+    }
+    Ok(instructions)
+}
+
+/// Same input as `disassemble_memory`, but returns instruction objects instead
+/// of pipe-delimited lines
+#[tauri::command]
+pub async fn disassemble_memory_structured(
+    state: tauri::State<'_, AppStateType>,
+    request: DisassembleRequest,
+) -> Result<Vec<StructuredInstruction>, String> {
+    let symbolizer = if request.symbolicate {
+        Some(Symbolizer::load(state.inner()).await?)
+    } else {
+        None
+    };
+
+    let size = match memory_regions::readable_span(request.address) {
+        Some(span) if span < request.size as u64 => span as usize,
+        _ => request.size,
+    };
+
+    let mut instructions = disasm_cache::disassemble_cached(state.inner(), request.address, size, &request.architecture).await?;
+    if let Some(symbolizer) = symbolizer {
+        for insn in instructions.iter_mut() {
+            insn.branch_symbol = insn.branch_target.and_then(|t| symbolizer.resolve(t)).map(|s| s.display);
+        }
+    }
+    if request.comments {
+        disasm_comments::annotate(state.inner(), &mut instructions, &request.architecture).await?;
+    }
+    Ok(instructions)
+}
@@ -0,0 +1,207 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+// Channels published by the backend
+pub const CHANNEL_TRACE: &str = "trace";
+pub const CHANNEL_TRACE_PROGRESS: &str = "trace-progress";
+pub const CHANNEL_NATIVE_TRACE: &str = "native-trace";
+pub const CHANNEL_EXCEPTIONS: &str = "exceptions";
+pub const CHANNEL_SCAN_PROGRESS: &str = "scan-progress";
+pub const CHANNEL_MEMORY_USAGE: &str = "memory-usage";
+pub const CHANNEL_SMC: &str = "smc";
+pub const CHANNEL_JIT: &str = "jit-regions";
+pub const CHANNEL_DUMP_PROGRESS: &str = "dump-progress";
+pub const CHANNEL_SYMBOL_PROGRESS: &str = "symbol-progress";
+
+const DEFAULT_FLUSH_INTERVAL_MS: u64 = 50;
+const DEFAULT_MAX_PENDING: usize = 10_000;
+// Flusher wake-up period; the effective flush interval is rounded up to this
+const TICK_MS: u64 = 10;
+
+/// Subscription options for one channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventChannelConfig {
+    pub flush_interval_ms: u64,
+    pub max_pending: usize,     // Oldest events are dropped beyond this
+    pub coalesce: bool,         // Keep only the latest event per key (progress-style channels)
+}
+
+/// Statistics for one channel, returned by the subscription commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventChannelInfo {
+    pub channel: String,
+    pub config: EventChannelConfig,
+    pub pending: usize,
+    pub next_sequence: u64,
+    pub emitted_events: u64,
+    pub dropped_total: u64,
+}
+
+/// Payload of a `batch://<channel>` event
+#[derive(Debug, Clone, Serialize)]
+pub struct EventBatch {
+    pub channel: String,
+    pub sequence: u64,              // Per channel, gaps never occur; use `dropped` for loss
+    pub events: Vec<serde_json::Value>,
+    pub dropped: u64,               // Events dropped since the previous batch
+}
+
+struct ChannelState {
+    config: EventChannelConfig,
+    pending: VecDeque<(Option<String>, serde_json::Value)>,
+    next_sequence: u64,
+    emitted_events: u64,
+    dropped_since_flush: u64,
+    dropped_total: u64,
+    last_flush: std::time::Instant,
+}
+
+impl ChannelState {
+    fn info(&self, channel: &str) -> EventChannelInfo {
+        EventChannelInfo {
+            channel: channel.to_string(),
+            config: self.config.clone(),
+            pending: self.pending.len(),
+            next_sequence: self.next_sequence,
+            emitted_events: self.emitted_events,
+            dropped_total: self.dropped_total,
+        }
+    }
+}
+
+// Only subscribed channels are buffered; everything else keeps the per-event emits
+static CHANNELS: Lazy<Mutex<HashMap<String, ChannelState>>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+/// Whether the frontend subscribed to batched delivery for `channel`
+pub fn is_subscribed(channel: &str) -> bool {
+    CHANNELS.lock().map(|c| c.contains_key(channel)).unwrap_or(false)
+}
+
+/// Queue an event for batched delivery. In coalescing channels an event with
+/// the same key replaces the pending one. Returns false when the channel is not
+/// subscribed, so the caller can fall back to emitting directly.
+pub fn publish<T: Serialize>(channel: &str, key: Option<&str>, payload: &T) -> bool {
+    let Ok(mut channels) = CHANNELS.lock() else {
+        return false;
+    };
+    let Some(state) = channels.get_mut(channel) else {
+        return false;
+    };
+    let Ok(value) = serde_json::to_value(payload) else {
+        return true;
+    };
+
+    if state.config.coalesce {
+        if let Some(existing) = state.pending.iter_mut().find(|(k, _)| k.as_deref() == key) {
+            existing.1 = value;
+            return true;
+        }
+    }
+    if state.pending.len() >= state.config.max_pending {
+        state.pending.pop_front();
+        state.dropped_since_flush += 1;
+        state.dropped_total += 1;
+    }
+    state.pending.push_back((key.map(str::to_string), value));
+    true
+}
+
+/// Emit every due channel as one `batch://<channel>` event
+fn flush_due(app: &AppHandle, force: bool) {
+    let batches: Vec<EventBatch> = {
+        let Ok(mut channels) = CHANNELS.lock() else {
+            return;
+        };
+        let now = std::time::Instant::now();
+        channels.iter_mut()
+            .filter(|(_, s)| !s.pending.is_empty() || s.dropped_since_flush > 0)
+            .filter(|(_, s)| force || now.duration_since(s.last_flush).as_millis() as u64 >= s.config.flush_interval_ms)
+            .map(|(channel, s)| {
+                let events: Vec<serde_json::Value> = s.pending.drain(..).map(|(_, v)| v).collect();
+                let batch = EventBatch {
+                    channel: channel.clone(),
+                    sequence: s.next_sequence,
+                    dropped: s.dropped_since_flush,
+                    events,
+                };
+                s.next_sequence += 1;
+                s.emitted_events += batch.events.len() as u64;
+                s.dropped_since_flush = 0;
+                s.last_flush = now;
+                batch
+            })
+            .collect()
+    };
+
+    for batch in batches {
+        if let Err(e) = app.emit(&format!("batch://{}", batch.channel), &batch) {
+            eprintln!("Failed to emit batch for channel {}: {}", batch.channel, e);
+        }
+    }
+}
+
+/// Background loop that delivers batches; started once from `setup`
+pub async fn run_flusher(app: AppHandle) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_millis(TICK_MS));
+    loop {
+        interval.tick().await;
+        flush_due(&app, false);
+    }
+}
+
+/// Subscribe to batched delivery of a channel (or update its options)
+#[tauri::command]
+pub fn subscribe_event_channel(
+    channel: String,
+    flush_interval_ms: Option<u64>,
+    max_pending: Option<usize>,
+    coalesce: Option<bool>,
+) -> Result<EventChannelInfo, String> {
+    let mut channels = CHANNELS.lock().map_err(|e| e.to_string())?;
+    let default_coalesce = channel == CHANNEL_SCAN_PROGRESS || channel == CHANNEL_TRACE_PROGRESS || channel == CHANNEL_DUMP_PROGRESS
+        || channel == CHANNEL_SYMBOL_PROGRESS;
+    let state = channels.entry(channel.clone()).or_insert_with(|| ChannelState {
+        config: EventChannelConfig {
+            flush_interval_ms: DEFAULT_FLUSH_INTERVAL_MS,
+            max_pending: DEFAULT_MAX_PENDING,
+            coalesce: default_coalesce,
+        },
+        pending: VecDeque::new(),
+        next_sequence: 0,
+        emitted_events: 0,
+        dropped_since_flush: 0,
+        dropped_total: 0,
+        last_flush: std::time::Instant::now(),
+    });
+    if let Some(interval) = flush_interval_ms {
+        state.config.flush_interval_ms = interval.max(TICK_MS);
+    }
+    if let Some(max) = max_pending {
+        state.config.max_pending = max.max(1);
+    }
+    if let Some(coalesce) = coalesce {
+        state.config.coalesce = coalesce;
+    }
+    Ok(state.info(&channel))
+}
+
+/// Flush what is pending and stop batching a channel
+#[tauri::command]
+pub fn unsubscribe_event_channel(app: AppHandle, channel: String) -> Result<bool, String> {
+    flush_due(&app, true);
+    let mut channels = CHANNELS.lock().map_err(|e| e.to_string())?;
+    Ok(channels.remove(&channel).is_some())
+}
+
+#[tauri::command]
+pub fn get_event_channel_stats() -> Result<Vec<EventChannelInfo>, String> {
+    let channels = CHANNELS.lock().map_err(|e| e.to_string())?;
+    let mut infos: Vec<EventChannelInfo> = channels.iter().map(|(name, s)| s.info(name)).collect();
+    infos.sort_by(|a, b| a.channel.cmp(&b.channel));
+    Ok(infos)
+}
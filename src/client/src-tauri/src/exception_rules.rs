@@ -0,0 +1,355 @@
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::state::{AppStateType, ExceptionData, ModuleInfo};
+use crate::{breakpoints, db, scripting, server_address};
+
+const ACTIONS: [&str; 4] = ["ignore", "log", "break", "script"];
+const EXCEPTION_TYPES: [&str; 10] = [
+    "breakpoint", "watchpoint", "singlestep", "signal", "sigsegv", "sigbus", "sigfpe", "sigill", "sigabrt", "sigtrap",
+];
+// A "script" rule's script is stopped, and the exception shown, after this long
+const SCRIPT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Stored rule; the first enabled rule (in sort order) that matches an
+/// incoming exception decides what happens to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExceptionRule {
+    pub id: i64,
+    pub name: String,
+    pub exception_types: Vec<String>, // Empty matches every type
+    pub module_name: String,          // Empty matches any address
+    pub address_start: Option<u64>,   // Module-relative when module_name is set
+    pub address_end: Option<u64>,     // Exclusive
+    pub action: String,               // "ignore" (resume, drop), "log" (resume, keep), "break" or "script"
+    pub script: Option<String>,       // Lua path or source for "script"; returning true keeps the thread stopped
+    pub enabled: bool,
+    pub sort_order: i64,
+    pub created_at: String,
+    pub updated_at: String,
+    pub hits: u64,                    // Exceptions matched since the app started
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExceptionRuleInput {
+    pub name: String,
+    #[serde(default)]
+    pub exception_types: Vec<String>,
+    #[serde(default)]
+    pub module_name: Option<String>,
+    #[serde(default)]
+    pub address_start: Option<u64>,
+    #[serde(default)]
+    pub address_end: Option<u64>,
+    pub action: String,
+    #[serde(default)]
+    pub script: Option<String>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+// Rules as stored, loaded on first use and dropped on every change
+static RULES: Lazy<RwLock<Option<Vec<ExceptionRule>>>> = Lazy::new(|| RwLock::new(None));
+
+static HITS: Lazy<Mutex<HashMap<i64, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Create the exception_rules table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS exception_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            exception_types TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            address_start INTEGER,
+            address_end INTEGER,
+            action TEXT NOT NULL,
+            script TEXT,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            sort_order INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+const SELECT_COLUMNS: &str = "id, name, exception_types, module_name, address_start, address_end, action, script, enabled, sort_order, created_at, updated_at";
+
+fn row_to_rule(row: &rusqlite::Row) -> rusqlite::Result<ExceptionRule> {
+    let exception_types: String = row.get(2)?;
+    Ok(ExceptionRule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        exception_types: exception_types.split(',').filter(|t| !t.is_empty()).map(str::to_string).collect(),
+        module_name: row.get(3)?,
+        address_start: row.get::<_, Option<i64>>(4)?.map(|a| a as u64),
+        address_end: row.get::<_, Option<i64>>(5)?.map(|a| a as u64),
+        action: row.get(6)?,
+        script: row.get(7)?,
+        enabled: row.get::<_, i64>(8)? != 0,
+        sort_order: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+        hits: 0,
+    })
+}
+
+async fn stored_rules() -> Result<Vec<ExceptionRule>, String> {
+    if let Some(rules) = RULES.read().map_err(|e| e.to_string())?.as_ref() {
+        return Ok(rules.clone());
+    }
+    let rules = db::run(move |conn| {
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM exception_rules ORDER BY sort_order, id", SELECT_COLUMNS))
+            .map_err(|e| e.to_string())?;
+        let rows = stmt.query_map([], row_to_rule).map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }).await?;
+    *RULES.write().map_err(|e| e.to_string())? = Some(rules.clone());
+    Ok(rules)
+}
+
+fn invalidate() {
+    if let Ok(mut rules) = RULES.write() {
+        *rules = None;
+    }
+}
+
+fn with_hits(mut rule: ExceptionRule) -> ExceptionRule {
+    rule.hits = HITS.lock().ok().and_then(|hits| hits.get(&rule.id).copied()).unwrap_or(0);
+    rule
+}
+
+/// Normalized columns of an input: (types, module, action, script)
+fn validate(rule: &ExceptionRuleInput) -> Result<(String, String, String, Option<String>), String> {
+    let mut types = Vec::new();
+    for exception_type in &rule.exception_types {
+        let exception_type = exception_type.trim().to_lowercase();
+        if !EXCEPTION_TYPES.contains(&exception_type.as_str()) {
+            return Err(format!("Unknown exception type '{}'", exception_type));
+        }
+        if !types.contains(&exception_type) {
+            types.push(exception_type);
+        }
+    }
+    let action = rule.action.trim().to_lowercase();
+    if !ACTIONS.contains(&action.as_str()) {
+        return Err(format!("Unknown rule action '{}' (expected ignore, log, break or script)", action));
+    }
+    let script = rule.script.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string);
+    if action == "script" && script.is_none() {
+        return Err("A script rule needs a script".to_string());
+    }
+    if let (Some(start), Some(end)) = (rule.address_start, rule.address_end) {
+        if start >= end {
+            return Err(format!("Empty address range 0x{:x}-0x{:x}", start, end));
+        }
+    }
+    let module_name = rule.module_name.as_deref().map(str::trim).unwrap_or_default().to_string();
+    Ok((types.join(","), module_name, action, script))
+}
+
+fn get_rule(conn: &Connection, id: i64) -> Result<ExceptionRule, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM exception_rules WHERE id = ?1", SELECT_COLUMNS),
+        params![id],
+        row_to_rule,
+    ).map_err(|e| format!("Exception rule {} not found: {}", id, e))
+}
+
+/// Whether a rule covers an exception of `exception_type` at `pc`
+fn matches(rule: &ExceptionRule, exception_type: &str, pc: Option<u64>, modules: &[ModuleInfo]) -> bool {
+    if !rule.exception_types.is_empty() && !rule.exception_types.iter().any(|t| t == exception_type) {
+        return false;
+    }
+    let ranged = rule.address_start.is_some() || rule.address_end.is_some();
+    if rule.module_name.is_empty() && !ranged {
+        return true;
+    }
+    let Some(pc) = pc else {
+        return false;
+    };
+    let offset = if rule.module_name.is_empty() {
+        pc
+    } else {
+        let module = modules.iter().find(|m| {
+            m.modulename.eq_ignore_ascii_case(&rule.module_name) && pc >= m.base && pc - m.base < m.size
+        });
+        match module {
+            Some(module) => pc - module.base,
+            None => return false,
+        }
+    };
+    rule.address_start.is_none_or(|start| offset >= start) && rule.address_end.is_none_or(|end| offset < end)
+}
+
+/// Whether a rule's script asked to keep the thread stopped. A script that
+/// fails or runs past SCRIPT_TIMEOUT does too, so the stop is not lost.
+async fn run_rule_script(app: &AppHandle, rule: &ExceptionRule, exception: &ExceptionData) -> bool {
+    let Some(script) = rule.script.clone() else {
+        return true;
+    };
+    let context = serde_json::to_value(exception).unwrap_or_default();
+    let started = match scripting::load_source(script).await {
+        Ok((source, _)) => scripting::start(app.clone(), source, format!("rule: {}", rule.name), vec![("exception".to_string(), context)]),
+        Err(e) => Err(e),
+    };
+    let (info, handle) = match started {
+        Ok(started) => started,
+        Err(e) => {
+            eprintln!("Exception rule {} script failed to start: {}", rule.id, e);
+            return true;
+        }
+    };
+    match tokio::time::timeout(SCRIPT_TIMEOUT, handle).await {
+        Ok(Ok(Some(finished))) if finished.state == scripting::ScriptState::Finished => {
+            finished.result == Some(serde_json::Value::Bool(true))
+        }
+        Ok(_) => true,
+        Err(_) => {
+            let _ = scripting::stop_script(info.id);
+            true
+        }
+    }
+}
+
+/// Apply the exception rules to incoming stops before they reach the store.
+/// Threads of ignored, logged and (unless the script returns true) scripted
+/// exceptions are resumed on the server; ignored and scripted ones are
+/// dropped. Exceptions no rule matches pass through unchanged.
+pub async fn filter_exceptions(app: &AppHandle, state: &AppStateType, exceptions: Vec<ExceptionData>) -> Vec<ExceptionData> {
+    let rules: Vec<ExceptionRule> = match stored_rules().await {
+        Ok(rules) => rules.into_iter().filter(|r| r.enabled).collect(),
+        Err(_) => return exceptions,
+    };
+    if rules.is_empty() {
+        return exceptions;
+    }
+    let Ok((host, port)) = server_address() else {
+        return exceptions;
+    };
+    let modules = match state.lock() {
+        Ok(state_guard) => state_guard.attached_modules.clone(),
+        Err(_) => return exceptions,
+    };
+
+    let mut forwarded = Vec::with_capacity(exceptions.len());
+    for exception in exceptions {
+        let pc = exception.pc
+            .or_else(|| u64::from_str_radix(exception.address.trim_start_matches("0x"), 16).ok());
+        let Some(rule) = rules.iter().find(|r| matches(r, &exception.exception_type, pc, &modules)) else {
+            forwarded.push(exception);
+            continue;
+        };
+        if let Ok(mut hits) = HITS.lock() {
+            *hits.entry(rule.id).or_insert(0) += 1;
+        }
+
+        let (resume, keep) = match rule.action.as_str() {
+            "ignore" => (true, false),
+            "log" => (true, true),
+            "script" => {
+                let stop = run_rule_script(app, rule, &exception).await;
+                (!stop, stop)
+            }
+            _ => (false, true),
+        };
+        breakpoints::resume_or_forward(&host, port, exception, resume, keep, &mut forwarded).await;
+    }
+    forwarded
+}
+
+/// Rules in evaluation order, with their hit counts
+#[tauri::command]
+pub async fn list_exception_rules() -> Result<Vec<ExceptionRule>, String> {
+    Ok(stored_rules().await?.into_iter().map(with_hits).collect())
+}
+
+/// Add a rule at the end of the evaluation order
+#[tauri::command]
+pub async fn add_exception_rule(rule: ExceptionRuleInput) -> Result<ExceptionRule, String> {
+    let (types, module_name, action, script) = validate(&rule)?;
+    let created = db::run(move |conn| {
+        conn.execute(
+            "INSERT INTO exception_rules (name, exception_types, module_name, address_start, address_end, action, script, enabled, sort_order, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8,
+                     (SELECT COALESCE(MAX(sort_order), -1) + 1 FROM exception_rules),
+                     datetime('now'), datetime('now'))",
+            params![
+                rule.name.trim(),
+                types,
+                module_name,
+                rule.address_start.map(|a| a as i64),
+                rule.address_end.map(|a| a as i64),
+                action,
+                script,
+                rule.enabled.unwrap_or(true) as i64,
+            ],
+        ).map_err(|e| e.to_string())?;
+        get_rule(conn, conn.last_insert_rowid())
+    }).await?;
+    invalidate();
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_exception_rule(id: i64, rule: ExceptionRuleInput) -> Result<ExceptionRule, String> {
+    let (types, module_name, action, script) = validate(&rule)?;
+    let updated = db::run(move |conn| {
+        let changed = conn.execute(
+            "UPDATE exception_rules SET name = ?1, exception_types = ?2, module_name = ?3, address_start = ?4, address_end = ?5,
+                    action = ?6, script = ?7, enabled = COALESCE(?8, enabled), updated_at = datetime('now')
+             WHERE id = ?9",
+            params![
+                rule.name.trim(),
+                types,
+                module_name,
+                rule.address_start.map(|a| a as i64),
+                rule.address_end.map(|a| a as i64),
+                action,
+                script,
+                rule.enabled.map(|e| e as i64),
+                id,
+            ],
+        ).map_err(|e| e.to_string())?;
+        if changed == 0 {
+            return Err(format!("Exception rule {} not found", id));
+        }
+        get_rule(conn, id)
+    }).await?;
+    invalidate();
+    Ok(with_hits(updated))
+}
+
+#[tauri::command]
+pub async fn delete_exception_rule(id: i64) -> Result<bool, String> {
+    let deleted = db::run(move |conn| {
+        conn.execute("DELETE FROM exception_rules WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())
+    }).await?;
+    invalidate();
+    if let Ok(mut hits) = HITS.lock() {
+        hits.remove(&id);
+    }
+    Ok(deleted > 0)
+}
+
+/// Store the evaluation order of the given rules (first id is tried first)
+#[tauri::command]
+pub async fn reorder_exception_rules(ids: Vec<i64>) -> Result<(), String> {
+    db::run(move |conn| {
+        for (position, id) in ids.iter().enumerate() {
+            conn.execute("UPDATE exception_rules SET sort_order = ?1 WHERE id = ?2", params![position as i64, id])
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }).await?;
+    invalidate();
+    Ok(())
+}
@@ -0,0 +1,754 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::state::{AppStateType, ModuleInfo};
+use crate::{module_symbols, read_memory_from_server, symbolizer, SERVER_CONFIG};
+
+/// Expression over registers, symbols and memory, e.g.
+/// `x0 == 0x1 && [x1+0x10]:u32 > 500` or `[[libgame.so+0x1234]+0x10]:f32`
+///
+/// Operators follow C precedence. `[expr]` dereferences memory (64-bit by
+/// default); a `:u8`..`:u64`, `:i8`..`:i64`, `:f32` / `:f64` or `:ptr` suffix
+/// picks the width and type, and casts any other operand. Names that are not
+/// registers resolve to a module base, a Ghidra function or a stored symbol
+/// (`module!symbol` to pick the module; quote names with other characters).
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Number(i128),
+    Float(f64),
+    Name(String),
+    Deref(Box<Expr>, MemoryType),
+    Cast(Box<Expr>, MemoryType),
+    Unary(char, Box<Expr>),
+    Binary(String, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryType {
+    pub size: usize,
+    pub signed: bool,
+    pub float: bool,
+}
+
+impl MemoryType {
+    const POINTER: Self = Self { size: 8, signed: false, float: false };
+
+    fn parse(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        match name.as_str() {
+            "ptr" | "pointer" => return Some(Self::POINTER),
+            "f32" | "float" => return Some(Self { size: 4, signed: true, float: true }),
+            "f64" | "double" => return Some(Self { size: 8, signed: true, float: true }),
+            _ => {}
+        }
+        let (signed, bits) = match name.split_at_checked(1)? {
+            ("u", bits) => (false, bits),
+            ("i", bits) => (true, bits),
+            _ => return None,
+        };
+        let size = match bits {
+            "8" => 1,
+            "16" => 2,
+            "32" => 4,
+            "64" => 8,
+            _ => return None,
+        };
+        Some(Self { size, signed, float: false })
+    }
+
+    fn name(&self) -> String {
+        match (self.float, self.signed) {
+            (true, _) => format!("f{}", self.size * 8),
+            (false, true) => format!("i{}", self.size * 8),
+            (false, false) => format!("u{}", self.size * 8),
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Value {
+        let mut buf = [0u8; 8];
+        buf[..self.size].copy_from_slice(&bytes[..self.size]);
+        let raw = u64::from_le_bytes(buf);
+        match (self.float, self.size) {
+            (true, 4) => Value::Float(f32::from_bits(raw as u32) as f64),
+            (true, _) => Value::Float(f64::from_bits(raw)),
+            (false, _) => Value::Int(self.truncate(raw as i128)),
+        }
+    }
+
+    /// Wrap an integer to this width, sign-extending signed types
+    fn truncate(&self, value: i128) -> i128 {
+        let shift = 64 - self.size * 8;
+        let raw = (value as u64) << shift >> shift;
+        if self.signed {
+            (((raw << shift) as i64) >> shift) as i128
+        } else {
+            raw as i128
+        }
+    }
+}
+
+/// Result of an evaluation; integer unless a float is read, cast or written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Int(i128),
+    Float(f64),
+}
+
+impl Value {
+    pub fn as_int(self) -> i128 {
+        match self {
+            Value::Int(value) => value,
+            Value::Float(value) => value as i128,
+        }
+    }
+
+    fn as_float(self) -> f64 {
+        match self {
+            Value::Int(value) => value as f64,
+            Value::Float(value) => value,
+        }
+    }
+
+    pub fn is_true(self) -> bool {
+        match self {
+            Value::Int(value) => value != 0,
+            Value::Float(value) => value != 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i128),
+    Float(f64),
+    Ident(String),
+    Op(String),
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$' | '@')
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    const OPS: [&str; 24] = [
+        "&&", "||", "==", "!=", "<=", ">=", "<<", ">>", "<", ">", "+", "-", "*", "/", "%", "&", "|", "^", "!", "~",
+        "(", ")", "[", "]",
+    ];
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_alphanumeric() {
+                i += 1;
+            }
+            // Decimal fraction, e.g. 1.5
+            if i + 1 < chars.len() && chars[i] == '.' && chars[i + 1].is_ascii_digit() && chars[start..i].iter().all(|c| c.is_ascii_digit()) {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let literal: String = chars[start..i].iter().collect();
+                tokens.push(Token::Float(literal.parse().map_err(|_| format!("Invalid number '{}'", literal))?));
+                continue;
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let value = match literal.strip_prefix("0x").or_else(|| literal.strip_prefix("0X")) {
+                Some(hex) => i128::from_str_radix(hex, 16),
+                None => literal.parse::<i128>(),
+            }
+            .map_err(|_| format!("Invalid number '{}'", literal))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            // Module and symbol names may contain '.', `module!symbol` and `Class::method`
+            let start = i;
+            loop {
+                while i < chars.len() && is_name_char(chars[i]) {
+                    i += 1;
+                }
+                let joined = match chars.get(i..i + 2) {
+                    Some([':', ':']) => 2,
+                    Some(['!', next]) if next.is_ascii_alphabetic() || *next == '_' => 1,
+                    _ => 0,
+                };
+                if joined == 0 || !chars.get(i + joined).is_some_and(|&c| is_name_char(c)) {
+                    break;
+                }
+                i += joined;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c == '"' || c == '`' {
+            let end = chars[i + 1..].iter().position(|&q| q == c)
+                .ok_or_else(|| format!("Unterminated name starting at {}", i))?;
+            tokens.push(Token::Ident(chars[i + 1..i + 1 + end].iter().collect()));
+            i += end + 2;
+        } else if c == ':' {
+            tokens.push(Token::Op(":".to_string()));
+            i += 1;
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let op = OPS.iter().find(|op| rest.starts_with(**op))
+                .ok_or_else(|| format!("Unexpected character '{}'", c))?;
+            tokens.push(Token::Op(op.to_string()));
+            i += op.len();
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,                          // Open brackets and unary operators around the current token
+}
+
+// Brackets and unary operators nested inside one another; each level recurses
+// through every precedence level, so this bounds the parser's stack use
+const MAX_PARSE_DEPTH: usize = 64;
+
+// Binary operators from lowest to highest precedence
+const LEVELS: [&[&str]; 9] = [
+    &["||"],
+    &["&&"],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<", "<=", ">", ">="],
+    &["<<", ">>"],
+    &["+", "-"],
+];
+
+impl Parser {
+    fn peek_op(&self) -> Option<&str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) => Some(op.as_str()),
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, op: &str) -> Result<(), String> {
+        if self.peek_op() == Some(op) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("Expected '{}'", op))
+        }
+    }
+
+    /// Run `parse` one nesting level deeper
+    fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Result<Expr, String>) -> Result<Expr, String> {
+        if self.depth == MAX_PARSE_DEPTH {
+            return Err(format!("Expression is nested too deeply (at most {} levels)", MAX_PARSE_DEPTH));
+        }
+        self.depth += 1;
+        let expr = parse(self);
+        self.depth -= 1;
+        expr
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, String> {
+        if level == LEVELS.len() {
+            return self.multiplicative();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(op) = self.peek_op().filter(|op| LEVELS[level].contains(op)).map(|op| op.to_string()) {
+            self.pos += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        while let Some(op) = self.peek_op().filter(|op| ["*", "/", "%"].contains(op)).map(|op| op.to_string()) {
+            self.pos += 1;
+            let right = self.unary()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.peek_op() {
+            Some(op @ ("!" | "-" | "~")) => {
+                let op = op.chars().next().unwrap();
+                self.pos += 1;
+                Ok(Expr::Unary(op, Box::new(self.nested(Self::unary)?)))
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("Unexpected end of expression")?;
+        self.pos += 1;
+        let expr = match token {
+            Token::Number(value) => Expr::Number(value),
+            Token::Float(value) => Expr::Float(value),
+            Token::Ident(name) => Expr::Name(name),
+            Token::Op(op) if op == "(" => {
+                let inner = self.nested(|parser| parser.binary(0))?;
+                self.expect(")")?;
+                inner
+            }
+            Token::Op(op) if op == "[" => {
+                let inner = self.nested(|parser| parser.binary(0))?;
+                self.expect("]")?;
+                Expr::Deref(Box::new(inner), MemoryType::POINTER)
+            }
+            Token::Op(op) => return Err(format!("Unexpected '{}'", op)),
+        };
+        if self.peek_op() != Some(":") {
+            return Ok(expr);
+        }
+        // A type suffix sets the width of a dereference and casts anything else
+        self.pos += 1;
+        let memory_type = match self.tokens.get(self.pos) {
+            Some(Token::Ident(name)) => MemoryType::parse(name)
+                .ok_or_else(|| format!("Unknown memory type '{}'", name))?,
+            _ => return Err("Expected a memory type after ':'".to_string()),
+        };
+        self.pos += 1;
+        Ok(match expr {
+            Expr::Deref(inner, _) => Expr::Deref(inner, memory_type),
+            other => Expr::Cast(Box::new(other), memory_type),
+        })
+    }
+}
+
+// Dereferences nested inside one another, as in `[[[base]+a]+b]`
+const MAX_DEREF_DEPTH: usize = 16;
+// Dereferences evaluated in total, nested or side by side
+const MAX_MEMORY_READS: usize = 256;
+
+fn deref_depth(expr: &Expr) -> usize {
+    match expr {
+        Expr::Number(_) | Expr::Float(_) | Expr::Name(_) => 0,
+        Expr::Deref(inner, _) => 1 + deref_depth(inner),
+        Expr::Cast(inner, _) | Expr::Unary(_, inner) => deref_depth(inner),
+        Expr::Binary(_, left, right) => deref_depth(left).max(deref_depth(right)),
+    }
+}
+
+pub fn parse(text: &str) -> Result<Expr, String> {
+    let mut parser = Parser { tokens: tokenize(text)?, pos: 0, depth: 0 };
+    let expr = parser.binary(0)?;
+    if parser.pos != parser.tokens.len() {
+        return Err("Unexpected trailing input".to_string());
+    }
+    if deref_depth(&expr) > MAX_DEREF_DEPTH {
+        return Err(format!("Too many nested memory reads (at most {})", MAX_DEREF_DEPTH));
+    }
+    Ok(expr)
+}
+
+/// Register values by lowercase name; sub-registers (w0, eax, ...) and common
+/// aliases are derived from the full registers
+#[derive(Default, Clone)]
+pub struct RegisterSet(HashMap<String, u64>);
+
+impl RegisterSet {
+    /// From an exception / register dump (numbers or hex strings)
+    pub fn from_json(value: &serde_json::Value) -> Self {
+        let mut registers = HashMap::new();
+        if let Some(map) = value.as_object() {
+            for (name, value) in map {
+                let parsed = value.as_u64()
+                    .or_else(|| value.as_i64().map(|v| v as u64))
+                    .or_else(|| value.as_f64().map(|v| v as u64))
+                    .or_else(|| value.as_str().and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok()));
+                if let Some(parsed) = parsed {
+                    registers.insert(name.to_lowercase(), parsed);
+                }
+            }
+        }
+        Self(registers)
+    }
+
+    pub fn get(&self, name: &str) -> Option<u64> {
+        if let Some(value) = self.0.get(name) {
+            return Some(*value);
+        }
+        let alias = match name {
+            "lr" => Some("x30"),
+            "fp" => Some("x29"),
+            "pc" => ["pc", "rip", "eip"].into_iter().find(|r| self.0.contains_key(*r)),
+            _ => None,
+        };
+        if let Some(alias) = alias {
+            return self.0.get(alias).copied();
+        }
+        // 32-bit views of 64-bit registers
+        let x86 = match name {
+            "eax" => Some("rax"), "ebx" => Some("rbx"), "ecx" => Some("rcx"), "edx" => Some("rdx"),
+            "esi" => Some("rsi"), "edi" => Some("rdi"), "ebp" => Some("rbp"), "esp" => Some("rsp"),
+            _ => None,
+        };
+        let full = match (x86, name.strip_prefix('w'), name.strip_suffix('d')) {
+            (Some(full), _, _) => Some(full.to_string()),
+            (_, Some(n), _) if n.parse::<u32>().is_ok() => Some(format!("x{}", n)),
+            (_, _, Some(r)) if r.starts_with('r') && r[1..].parse::<u32>().is_ok() => Some(r.to_string()),
+            _ => None,
+        }?;
+        self.0.get(&full).map(|v| v & 0xFFFF_FFFF)
+    }
+}
+
+/// Loaded modules and the symbol caches, for names that are not registers
+#[derive(Default, Clone)]
+pub struct Symbols {
+    target_os: String,
+    modules: Vec<ModuleInfo>,
+}
+
+impl Symbols {
+    pub fn new(target_os: &str, modules: &[ModuleInfo]) -> Self {
+        Self { target_os: target_os.to_string(), modules: modules.to_vec() }
+    }
+
+    pub fn from_state(state: &AppStateType) -> Result<Self, String> {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let target_os = state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default();
+        Ok(Self { target_os, modules: state_guard.attached_modules.clone() })
+    }
+
+    fn module(&self, name: &str) -> Option<&ModuleInfo> {
+        self.modules.iter().find(|m| m.modulename.eq_ignore_ascii_case(name))
+    }
+
+    fn symbol_in(&self, module: &ModuleInfo, symbol: &str) -> Option<u64> {
+        let offset = symbolizer::offset_for_symbol(&self.target_os, &module.modulename, symbol)
+            .or_else(|| module_symbols::stored_symbol_offset(&self.target_os, module, symbol))?;
+        Some(module.base.wrapping_add(offset))
+    }
+
+    /// Address of a module (its base), `module!symbol` / `module@symbol`, or a
+    /// symbol of any loaded module
+    pub fn resolve(&self, name: &str) -> Option<u64> {
+        if let Some(module) = self.module(name) {
+            return Some(module.base);
+        }
+        if let Some((module, symbol)) = name.split_once('!').or_else(|| name.split_once('@')) {
+            return self.symbol_in(self.module(module)?, symbol);
+        }
+        self.modules.iter().find_map(|module| self.symbol_in(module, name))
+    }
+}
+
+/// Names of an expression that are not registers, resolved once before evaluating
+fn resolve_names(expr: &Expr, registers: &RegisterSet, symbols: &Symbols, names: &mut HashMap<String, u64>) {
+    match expr {
+        Expr::Name(name) if registers.get(&name.to_lowercase()).is_none() && !names.contains_key(name) => {
+            if let Some(address) = symbols.resolve(name) {
+                names.insert(name.clone(), address);
+            }
+        }
+        Expr::Deref(inner, _) | Expr::Cast(inner, _) | Expr::Unary(_, inner) => resolve_names(inner, registers, symbols, names),
+        Expr::Binary(_, left, right) => {
+            resolve_names(left, registers, symbols, names);
+            resolve_names(right, registers, symbols, names);
+        }
+        _ => {}
+    }
+}
+
+struct Env<'a> {
+    registers: &'a RegisterSet,
+    names: HashMap<String, u64>,
+    memory: HashMap<(u64, usize), Vec<u8>>,
+}
+
+impl<'a> Env<'a> {
+    fn new(expr: &Expr, registers: &'a RegisterSet, symbols: &Symbols) -> Self {
+        let mut names = HashMap::new();
+        resolve_names(expr, registers, symbols, &mut names);
+        Self { registers, names, memory: HashMap::new() }
+    }
+
+    /// `new` for async callers: symbol lookups read SQLite, so the names are
+    /// resolved on a blocking thread
+    async fn resolve(expr: &Expr, registers: &'a RegisterSet, symbols: &Symbols) -> Result<Self, String> {
+        let (owned_expr, owned_registers, owned_symbols) = (expr.clone(), registers.clone(), symbols.clone());
+        let names = tokio::task::spawn_blocking(move || {
+            let mut names = HashMap::new();
+            resolve_names(&owned_expr, &owned_registers, &owned_symbols, &mut names);
+            names
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(Self { registers, names, memory: HashMap::new() })
+    }
+}
+
+enum EvalError {
+    NeedMemory(u64, usize),
+    Failed(String),
+}
+
+fn truthy(value: bool) -> Value {
+    Value::Int(value as i128)
+}
+
+fn eval(expr: &Expr, env: &Env) -> Result<Value, EvalError> {
+    Ok(match expr {
+        Expr::Number(value) => Value::Int(*value),
+        Expr::Float(value) => Value::Float(*value),
+        Expr::Name(name) => env.registers.get(&name.to_lowercase())
+            .or_else(|| env.names.get(name).copied())
+            .map(|value| Value::Int(value as i128))
+            .ok_or_else(|| EvalError::Failed(format!("Unknown register or symbol '{}'", name)))?,
+        Expr::Deref(inner, memory_type) => {
+            let address = eval(inner, env)?.as_int() as u64;
+            let bytes = env.memory.get(&(address, memory_type.size))
+                .ok_or(EvalError::NeedMemory(address, memory_type.size))?;
+            if bytes.len() < memory_type.size {
+                return Err(EvalError::Failed(format!("Failed to read memory at 0x{:x}", address)));
+            }
+            memory_type.decode(bytes)
+        }
+        Expr::Cast(inner, memory_type) => match (eval(inner, env)?, memory_type.float) {
+            (value, true) if memory_type.size == 4 => Value::Float(value.as_float() as f32 as f64),
+            (value, true) => Value::Float(value.as_float()),
+            (value, false) => Value::Int(memory_type.truncate(value.as_int())),
+        },
+        Expr::Unary(op, inner) => match (op, eval(inner, env)?) {
+            ('!', value) => truthy(!value.is_true()),
+            ('-', Value::Int(value)) => Value::Int(value.wrapping_neg()),
+            ('-', Value::Float(value)) => Value::Float(-value),
+            (_, Value::Int(value)) => Value::Int(!(value as u64) as i128),
+            (_, Value::Float(_)) => return Err(EvalError::Failed("'~' needs an integer".to_string())),
+        },
+        Expr::Binary(op, left, right) => {
+            let l = eval(left, env)?;
+            // Short-circuit so `ptr != 0 && [ptr] == 1` does not read address 0
+            match op.as_str() {
+                "&&" if !l.is_true() => return Ok(Value::Int(0)),
+                "||" if l.is_true() => return Ok(Value::Int(1)),
+                _ => {}
+            }
+            let r = eval(right, env)?;
+            if let (Value::Int(l), Value::Int(r)) = (l, r) {
+                return binary_int(op, l, r).map(Value::Int);
+            }
+            let (l, r) = (l.as_float(), r.as_float());
+            match op.as_str() {
+                "&&" | "||" => truthy(r != 0.0),
+                "==" => truthy(l == r),
+                "!=" => truthy(l != r),
+                "<" => truthy(l < r),
+                "<=" => truthy(l <= r),
+                ">" => truthy(l > r),
+                ">=" => truthy(l >= r),
+                "+" => Value::Float(l + r),
+                "-" => Value::Float(l - r),
+                "*" => Value::Float(l * r),
+                "/" => Value::Float(l / r),
+                "%" => Value::Float(l % r),
+                _ => return Err(EvalError::Failed(format!("'{}' needs integer operands", op))),
+            }
+        }
+    })
+}
+
+fn binary_int(op: &str, l: i128, r: i128) -> Result<i128, EvalError> {
+    Ok(match op {
+        "&&" | "||" => (r != 0) as i128,
+        "==" => (l == r) as i128,
+        "!=" => (l != r) as i128,
+        "<" => (l < r) as i128,
+        "<=" => (l <= r) as i128,
+        ">" => (l > r) as i128,
+        ">=" => (l >= r) as i128,
+        "|" => l | r,
+        "^" => l ^ r,
+        "&" => l & r,
+        "<<" => ((l as u64).wrapping_shl(r as u32)) as i128,
+        ">>" => ((l as u64).wrapping_shr(r as u32)) as i128,
+        "+" => l.wrapping_add(r),
+        "-" => l.wrapping_sub(r),
+        "*" => l.wrapping_mul(r),
+        "/" | "%" if r == 0 => return Err(EvalError::Failed("Division by zero".to_string())),
+        // Wrapping like the other operators: i128::MIN / -1 must not panic
+        "/" => l.wrapping_div(r),
+        "%" => l.wrapping_rem(r),
+        _ => return Err(EvalError::Failed(format!("Unknown operator '{}'", op))),
+    })
+}
+
+fn without_memory(result: Result<Value, EvalError>) -> Result<Value, String> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(EvalError::Failed(e)) => Err(e),
+        Err(EvalError::NeedMemory(address, _)) => Err(format!("Memory read at 0x{:x} not allowed here", address)),
+    }
+}
+
+/// Evaluate without memory access or symbols (dereferences are an error)
+pub fn evaluate_registers(expr: &Expr, registers: &RegisterSet) -> Result<i128, String> {
+    let env = Env::new(expr, registers, &Symbols::default());
+    without_memory(eval(expr, &env)).map(Value::as_int)
+}
+
+/// Evaluate against symbols only, without memory access
+pub fn evaluate_static(expr: &Expr, symbols: &Symbols) -> Result<Value, String> {
+    let registers = RegisterSet::default();
+    let env = Env::new(expr, &registers, symbols);
+    without_memory(eval(expr, &env))
+}
+
+/// Run `eval` until every dereference it needs has been read from the server
+async fn eval_reading(expr: &Expr, env: &mut Env<'_>, host: &str, port: u16) -> Result<Value, String> {
+    // Each pass resolves one more dereference; nesting depth is bounded by `parse`
+    for _ in 0..=MAX_MEMORY_READS {
+        match eval(expr, env) {
+            Ok(value) => return Ok(value),
+            Err(EvalError::Failed(e)) => return Err(e),
+            Err(EvalError::NeedMemory(address, size)) => {
+                let data = read_memory_from_server(host, port, address, size).await.unwrap_or_default();
+                env.memory.insert((address, size), data);
+            }
+        }
+    }
+    Err(format!("Too many memory reads (at most {})", MAX_MEMORY_READS))
+}
+
+/// Evaluate against registers and symbols, reading memory from the server as needed
+pub async fn evaluate(expr: &Expr, registers: &RegisterSet, symbols: &Symbols, host: &str, port: u16) -> Result<Value, String> {
+    let mut env = Env::resolve(expr, registers, symbols).await?;
+    eval_reading(expr, &mut env, host, port).await
+}
+
+/// Base expression and watchlist-style pointer offsets of `[[base]+a]+b`:
+/// start at base, then dereference and add each offset in turn. Expressions
+/// without a dereference chain come back whole with no offsets.
+pub fn pointer_chain(expr: &Expr) -> (&Expr, Vec<i64>) {
+    let (inner, offset) = match expr {
+        Expr::Binary(op, left, right) if op == "+" || op == "-" => match **right {
+            Expr::Number(n) => (&**left, if op == "+" { n as i64 } else { -(n as i64) }),
+            _ => (expr, 0),
+        },
+        _ => (expr, 0),
+    };
+    match inner {
+        Expr::Deref(address, memory_type) if *memory_type == MemoryType::POINTER => {
+            let (base, mut offsets) = pointer_chain(address);
+            offsets.push(offset);
+            (base, offsets)
+        }
+        _ => (expr, Vec::new()),
+    }
+}
+
+/// Evaluated expression for the UI: the address it refers to and its typed value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpressionResult {
+    pub address: Option<u64>,            // Operand of the outermost dereference, else the value if it is an integer address
+    pub value_type: String,              // "u32", "f32", ...; "int" / "float" without a dereference or cast
+    pub value: String,                   // Decimal (integers) or shortest round-trip float
+    pub hex: Option<String>,             // Integers only
+}
+
+/// Evaluate an expression from the goto box, watch editor or console.
+/// `registers` is an optional register dump (as in exceptions) for the current thread.
+#[tauri::command]
+pub async fn evaluate_expression(
+    state: tauri::State<'_, AppStateType>,
+    expr: String,
+    registers: Option<serde_json::Value>,
+) -> Result<ExpressionResult, String> {
+    let parsed = parse(&expr)?;
+    let registers = registers.map(|r| RegisterSet::from_json(&r)).unwrap_or_default();
+    let symbols = Symbols::from_state(state.inner())?;
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+
+    let mut env = Env::resolve(&parsed, &registers, &symbols).await?;
+    let value = eval_reading(&parsed, &mut env, &host, port).await?;
+    let (address, value_type) = match &parsed {
+        Expr::Deref(inner, memory_type) => (Some(eval_reading(inner, &mut env, &host, port).await?.as_int() as u64), memory_type.name()),
+        Expr::Cast(_, memory_type) => (None, memory_type.name()),
+        _ => (None, String::new()),
+    };
+    let (address, value_type, value, hex) = match value {
+        Value::Int(v) => (
+            address.or_else(|| u64::try_from(v).ok()),
+            if value_type.is_empty() { "int".to_string() } else { value_type },
+            v.to_string(),
+            Some(if v < 0 { format!("-0x{:x}", v.unsigned_abs()) } else { format!("0x{:x}", v) }),
+        ),
+        Value::Float(v) if value_type == "f32" => (address, value_type, (v as f32).to_string(), None),
+        Value::Float(v) => (address, if value_type.is_empty() { "float".to_string() } else { value_type }, v.to_string(), None),
+    };
+    Ok(ExpressionResult { address, value_type, value, hex })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval_str(text: &str) -> Result<Value, String> {
+        evaluate_static(&parse(text)?, &Symbols::default())
+    }
+
+    #[test]
+    fn casts_and_float_arithmetic() {
+        assert_eq!(eval_str("(0x1ff):u8"), Ok(Value::Int(0xff)));
+        assert_eq!(eval_str("(0xff):i8"), Ok(Value::Int(-1)));
+        assert_eq!(eval_str("1.5 * 2"), Ok(Value::Float(3.0)));
+        assert_eq!(eval_str("(7):f32 / 2 > 3"), Ok(Value::Int(1)));
+        assert!(eval_str("1.5 & 1").is_err());
+    }
+
+    #[test]
+    fn integer_overflow_wraps() {
+        // 2^63 * 2^63 * 2 wraps to i128::MIN
+        let min = "(0x8000000000000000 * 0x8000000000000000 * 2)";
+        assert_eq!(eval_str(min), Ok(Value::Int(i128::MIN)));
+        assert_eq!(eval_str(&format!("{} / -1", min)), Ok(Value::Int(i128::MIN)));
+        assert_eq!(eval_str(&format!("{} % -1", min)), Ok(Value::Int(0)));
+        assert_eq!(eval_str(&format!("-{}", min)), Ok(Value::Int(i128::MIN)));
+        assert!(eval_str("1 % 0").is_err());
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected() {
+        let depth = MAX_PARSE_DEPTH;
+        assert_eq!(eval_str(&format!("{}1{}", "(".repeat(depth), ")".repeat(depth))), Ok(Value::Int(1)));
+        assert!(parse(&format!("{}1{}", "(".repeat(depth + 1), ")".repeat(depth + 1))).is_err());
+        assert!(parse(&format!("{}1", "~".repeat(100_000))).is_err());
+        assert!(parse(&"(".repeat(100_000)).is_err());
+    }
+
+    #[test]
+    fn names_keep_module_and_symbol_syntax() {
+        let tokens = tokenize("libgame.so!Game::update+0x10 != \"libc++.so\"").unwrap();
+        assert_eq!(tokens[0], Token::Ident("libgame.so!Game::update".to_string()));
+        assert_eq!(tokens[4], Token::Ident("libc++.so".to_string()));
+        assert_eq!(eval_str("missing+1"), Err("Unknown register or symbol 'missing'".to_string()));
+    }
+
+    #[test]
+    fn pointer_chain_matches_watchlist_offsets() {
+        let expr = parse("[[libgame.so+0x1234]+0x10]-0x8").unwrap();
+        let (base, offsets) = pointer_chain(&expr);
+        assert!(matches!(base, Expr::Binary(op, _, _) if op == "+"));
+        assert_eq!(offsets, vec![0x10, -0x8]);
+        assert!(pointer_chain(&parse("[x0]:u32").unwrap()).1.is_empty());
+    }
+
+    #[test]
+    fn nesting_limit_counts_depth_not_reads() {
+        let side_by_side = vec!["[x0]"; MAX_DEREF_DEPTH + 4].join(" + ");
+        assert!(parse(&side_by_side).is_ok());
+        let nested = format!("{}x0{}", "[".repeat(MAX_DEREF_DEPTH + 1), "]".repeat(MAX_DEREF_DEPTH + 1));
+        assert!(parse(&nested).is_err());
+    }
+}
@@ -0,0 +1,431 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::oneshot;
+
+use crate::state::{AppState, AppStateType, ExceptionData, TraceEntryData};
+use crate::{clock_sync, hide_console_window, SERVER_CONFIG};
+
+// frida-server's default listening port
+const FRIDA_SERVER_PORT: u16 = 27042;
+// Prefix of the lines the hook script prints
+pub(crate) const LINE_PREFIX: &str = "DYNADBG_FRIDA ";
+const READY_TIMEOUT: Duration = Duration::from_secs(20);
+const DEFAULT_CAPTURED_ARGS: u32 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FridaProcess {
+    pub pid: u32,
+    pub name: String,
+}
+
+/// Hook-specific part of an exception recorded by a Frida hook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FridaHookHit {
+    pub hook_id: u32,
+    pub args: Vec<String>,
+    pub retval: Option<String>,
+    pub backtrace: Vec<String>,          // "0x... module!symbol+0x.." per frame
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FridaHookOptions {
+    pub capture_args: Option<u32>,       // Argument registers to record (default 4)
+    pub backtrace: Option<bool>,
+    pub trace_count: Option<u32>,        // Record hits as a trace session of this many entries instead of exceptions
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FridaHookInfo {
+    pub id: u32,
+    pub pid: u32,
+    pub device: String,
+    pub module_name: String,
+    pub offset: String,
+    pub address: Option<String>,         // Resolved in the target once the script loaded
+    pub hits: u64,
+    pub active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScriptMessage {
+    kind: String,                        // "ready" | "hit" | "error"
+    #[serde(default)]
+    address: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    thread_id: Option<u64>,
+    #[serde(default)]
+    timestamp: Option<u64>,
+    #[serde(default)]
+    registers: serde_json::Value,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    retval: Option<String>,
+    #[serde(default)]
+    backtrace: Vec<String>,
+}
+
+static NEXT_HOOK_ID: AtomicU32 = AtomicU32::new(1);
+struct ActiveHook {
+    info: FridaHookInfo,
+    stop: Option<oneshot::Sender<()>>,   // Taken once the frida process is gone
+}
+
+static HOOKS: Lazy<Mutex<HashMap<u32, ActiveHook>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// frida CLI arguments selecting a device: "local", "usb", "host:port", or
+/// (default) frida-server on the host DynaDbg is connected to
+fn device_args(device: Option<&str>) -> Result<(String, Vec<String>), String> {
+    match device.unwrap_or("remote") {
+        "local" => Ok(("local".to_string(), Vec::new())),
+        "usb" => Ok(("usb".to_string(), vec!["-U".to_string()])),
+        "remote" => {
+            let host = SERVER_CONFIG.read().map_err(|e| e.to_string())?.host.clone();
+            if host.is_empty() {
+                return Err("No server connection configured".to_string());
+            }
+            let address = format!("{}:{}", host, FRIDA_SERVER_PORT);
+            Ok((address.clone(), vec!["-H".to_string(), address]))
+        }
+        address => Ok((address.to_string(), vec!["-H".to_string(), address.to_string()])),
+    }
+}
+
+fn hook_script(module_name: &str, offset: u64, capture_args: u32, backtrace: bool) -> String {
+    let module_json = serde_json::to_string(module_name).unwrap_or_default();
+    format!(r#"
+function emit(kind, payload) {{
+    console.log("{prefix}" + JSON.stringify(Object.assign({{ kind: kind }}, payload)));
+}}
+try {{
+    const target = Process.getModuleByName({module}).base.add(ptr("0x{offset:x}"));
+    Interceptor.attach(target, {{
+        onEnter(args) {{
+            const hit = {{
+                address: target.toString(),
+                thread_id: this.threadId,
+                timestamp: Date.now(),
+                registers: JSON.parse(JSON.stringify(this.context)),
+                args: [],
+                backtrace: []
+            }};
+            for (let i = 0; i < {capture_args}; i++) {{
+                hit.args.push(args[i].toString());
+            }}
+            if ({backtrace}) {{
+                hit.backtrace = Thread.backtrace(this.context, Backtracer.ACCURATE)
+                    .map(address => DebugSymbol.fromAddress(address).toString());
+            }}
+            this.hit = hit;
+        }},
+        onLeave(retval) {{
+            this.hit.retval = retval.toString();
+            emit("hit", this.hit);
+        }}
+    }});
+    emit("ready", {{ address: target.toString() }});
+}} catch (e) {{
+    emit("error", {{ error: e.message }});
+}}
+"#, prefix = LINE_PREFIX, module = module_json, offset = offset, capture_args = capture_args, backtrace = backtrace)
+}
+
+fn parse_offset(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches("0x"), 16).ok()
+}
+
+fn set_inactive(id: u32) -> Option<FridaHookInfo> {
+    let mut hooks = HOOKS.lock().ok()?;
+    let hook = hooks.get_mut(&id)?;
+    hook.info.active = false;
+    hook.stop.take();
+    Some(hook.info.clone())
+}
+
+/// Record one hook hit in the exception store, or in the trace store when
+/// the hook was started with `trace_count`
+async fn record_hit(app: &AppHandle, info: &FridaHookInfo, as_trace: bool, message: ScriptMessage, index: u64) {
+    let address = message.address.unwrap_or_default();
+    let timestamp = message.timestamp.unwrap_or_else(AppState::current_timestamp);
+    let state = app.state::<AppStateType>();
+    if as_trace {
+        let entry = TraceEntryData {
+            id: index as u32,
+            address: address.clone(),
+            instruction: format!("frida hook {}+{}", info.module_name, info.offset),
+            opcode: "hook".to_string(),
+            operands: message.args.join(", "),
+            registers: message.registers,
+            depth: 0,
+            is_call: true,
+            is_return: false,
+            function_name: message.backtrace.first().cloned(),
+            timestamp,
+            library_expression: Some(format!("{}+{}", info.module_name, info.offset)),
+            target_address: address,
+            local_timestamp: None,
+        };
+        let _ = crate::state::add_trace_entry(app.clone(), state, entry).await;
+        return;
+    }
+    let exception = ExceptionData {
+        exception_type: "frida_hook".to_string(),
+        address: address.clone(),
+        instruction: Some(format!("frida hook {}+{}", info.module_name, info.offset)),
+        timestamp: clock_sync::iso_timestamp(timestamp),
+        thread_id: message.thread_id,
+        watchpoint_id: None,
+        memory_address: None,
+        singlestep_mode: None,
+        registers: message.registers,
+        bytecode: None,
+        opcode: None,
+        pc: parse_offset(&address),
+        local_timestamp: None,
+        hook: Some(FridaHookHit {
+            hook_id: info.id,
+            args: message.args,
+            retval: message.retval,
+            backtrace: message.backtrace,
+        }),
+    };
+    let _ = crate::state::add_exceptions(app.clone(), state, vec![exception]).await;
+}
+
+/// Load `script` into `pid`, returning the first payload it prints with
+/// LINE_PREFIX as `{ kind: "result", data }`; a `{ kind: "error" }` line
+/// fails. frida is stopped once the script has answered.
+pub(crate) async fn run_script_once(pid: u32, device: Option<&str>, script: &str, timeout: Duration) -> Result<serde_json::Value, String> {
+    let (_, mut args) = device_args(device)?;
+    let script_dir = std::env::temp_dir().join("dynadbg_frida");
+    std::fs::create_dir_all(&script_dir).map_err(|e| format!("Failed to create script directory: {}", e))?;
+    let script_path = script_dir.join(format!("once_{}.js", NEXT_HOOK_ID.fetch_add(1, Ordering::SeqCst)));
+    std::fs::write(&script_path, script).map_err(|e| format!("Failed to write script: {}", e))?;
+
+    args.extend(["-p".to_string(), pid.to_string(), "-l".to_string(), script_path.to_string_lossy().to_string()]);
+    args.extend(["-q".to_string(), "-t".to_string(), "inf".to_string()]);
+    let mut command = Command::new("frida");
+    hide_console_window(&mut command).args(&args).stdout(Stdio::piped()).stderr(Stdio::null());
+    let mut child = tokio::process::Command::from(command)
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run frida (is frida-tools installed?): {}", e))?;
+    let stdout = child.stdout.take().ok_or("Failed to capture frida output")?;
+
+    let read = async {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let Some(message) = line.strip_prefix(LINE_PREFIX).and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok()) else {
+                continue;
+            };
+            match message["kind"].as_str() {
+                Some("result") => return Ok(message["data"].clone()),
+                Some("error") => return Err(message["error"].as_str().unwrap_or("Script failed").to_string()),
+                _ => {}
+            }
+        }
+        Err("frida exited before the script answered".to_string())
+    };
+    let result = tokio::time::timeout(timeout, read).await
+        .unwrap_or_else(|_| Err("Timed out waiting for the frida script".to_string()));
+    let _ = child.kill().await;
+    let _ = std::fs::remove_file(&script_path);
+    result
+}
+
+/// Processes visible on a Frida device (see device_args)
+#[tauri::command]
+pub async fn frida_list_processes(device: Option<String>) -> Result<Vec<FridaProcess>, String> {
+    let (_, args) = device_args(device.as_deref())?;
+    let output = tokio::task::spawn_blocking(move || {
+        hide_console_window(&mut Command::new("frida-ps")).args(&args).output()
+    }).await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("Failed to run frida-ps (is frida-tools installed?): {}", e))?;
+    if !output.status.success() {
+        return Err(format!("frida-ps failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    // "  PID  Name" header, a dashed rule, then one process per line
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (pid, name) = line.trim().split_once(char::is_whitespace)?;
+            Some(FridaProcess { pid: pid.parse().ok()?, name: name.trim().to_string() })
+        })
+        .collect())
+}
+
+/// Attach to `pid` with the frida CLI and hook `module_name`+`offset`. Each
+/// return from the hooked function is recorded with its arguments, return
+/// value, registers and (optionally) backtrace; hook status changes are
+/// emitted as "frida://hook-status".
+#[tauri::command]
+pub async fn frida_hook(
+    app: AppHandle,
+    pid: u32,
+    module_name: String,
+    offset: String,
+    device: Option<String>,
+    options: Option<FridaHookOptions>,
+) -> Result<FridaHookInfo, String> {
+    let options = options.unwrap_or_default();
+    let offset_value = parse_offset(&offset).ok_or("Invalid offset")?;
+    let (device_name, mut args) = device_args(device.as_deref())?;
+    let id = NEXT_HOOK_ID.fetch_add(1, Ordering::SeqCst);
+
+    let script_dir = std::env::temp_dir().join("dynadbg_frida");
+    std::fs::create_dir_all(&script_dir).map_err(|e| format!("Failed to create script directory: {}", e))?;
+    let script_path = script_dir.join(format!("hook_{}.js", id));
+    let script = hook_script(
+        &module_name,
+        offset_value,
+        options.capture_args.unwrap_or(DEFAULT_CAPTURED_ARGS).min(8),
+        options.backtrace.unwrap_or(false),
+    );
+    std::fs::write(&script_path, script).map_err(|e| format!("Failed to write hook script: {}", e))?;
+
+    // Quiet mode with an infinite timeout keeps the script loaded until killed
+    args.extend(["-p".to_string(), pid.to_string(), "-l".to_string(), script_path.to_string_lossy().to_string()]);
+    args.extend(["-q".to_string(), "-t".to_string(), "inf".to_string()]);
+    let mut command = Command::new("frida");
+    hide_console_window(&mut command).args(&args).stdout(Stdio::piped()).stderr(Stdio::null());
+    let mut child = tokio::process::Command::from(command)
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run frida (is frida-tools installed?): {}", e))?;
+    let stdout = child.stdout.take().ok_or("Failed to capture frida output")?;
+
+    let info = FridaHookInfo {
+        id,
+        pid,
+        device: device_name,
+        module_name,
+        offset: format!("0x{:x}", offset_value),
+        address: None,
+        hits: 0,
+        active: true,
+    };
+    let (stop_tx, mut stop_rx) = oneshot::channel();
+    let (ready_tx, ready_rx) = oneshot::channel::<Result<String, String>>();
+    HOOKS.lock().map_err(|e| e.to_string())?.insert(id, ActiveHook { info: info.clone(), stop: Some(stop_tx) });
+
+    let task_app = app.clone();
+    let mut info_for_task = info.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut lines = BufReader::new(stdout).lines();
+        let mut ready_tx = Some(ready_tx);
+        let as_trace = options.trace_count.is_some();
+        loop {
+            let line = tokio::select! {
+                line = lines.next_line() => line,
+                _ = &mut stop_rx => break,
+            };
+            let Ok(Some(line)) = line else {
+                break; // frida exited (target gone or detached)
+            };
+            let Some(message) = line.strip_prefix(LINE_PREFIX).and_then(|json| serde_json::from_str::<ScriptMessage>(json).ok()) else {
+                continue;
+            };
+            match message.kind.as_str() {
+                "ready" => {
+                    let address = message.address.unwrap_or_default();
+                    info_for_task.address = Some(address.clone());
+                    if let Ok(mut hooks) = HOOKS.lock() {
+                        if let Some(hook) = hooks.get_mut(&id) {
+                            hook.info.address = Some(address.clone());
+                        }
+                    }
+                    if let Some(count) = options.trace_count {
+                        let _ = crate::state::start_trace_session(task_app.clone(), task_app.state(), address.clone(), count).await;
+                    }
+                    if let Some(tx) = ready_tx.take() {
+                        let _ = tx.send(Ok(address));
+                    }
+                }
+                "error" => {
+                    let error = message.error.unwrap_or_else(|| "Hook script failed".to_string());
+                    if let Some(tx) = ready_tx.take() {
+                        let _ = tx.send(Err(error));
+                    }
+                    break;
+                }
+                "hit" => {
+                    info_for_task.hits += 1;
+                    if let Ok(mut hooks) = HOOKS.lock() {
+                        if let Some(hook) = hooks.get_mut(&id) {
+                            hook.info.hits = info_for_task.hits;
+                        }
+                    }
+                    record_hit(&task_app, &info_for_task, as_trace, message, info_for_task.hits).await;
+                }
+                _ => {}
+            }
+        }
+        let _ = child.kill().await;
+        let _ = std::fs::remove_file(&script_path);
+        if let Some(tx) = ready_tx.take() {
+            let _ = tx.send(Err("frida exited before the hook was installed".to_string()));
+        }
+        if let Some(info) = set_inactive(id) {
+            let _ = task_app.emit("frida://hook-status", &info);
+        }
+    });
+
+    match tokio::time::timeout(READY_TIMEOUT, ready_rx).await {
+        Ok(Ok(Ok(address))) => {
+            let info = FridaHookInfo { address: Some(address), ..info };
+            let _ = app.emit("frida://hook-status", &info);
+            Ok(info)
+        }
+        Ok(Ok(Err(e))) => {
+            HOOKS.lock().map_err(|e| e.to_string())?.remove(&id);
+            Err(e)
+        }
+        _ => {
+            if let Some(ActiveHook { stop: Some(stop), .. }) = HOOKS.lock().map_err(|e| e.to_string())?.remove(&id) {
+                let _ = stop.send(());
+            }
+            Err("Timed out waiting for frida to attach".to_string())
+        }
+    }
+}
+
+/// Remove a hook (detaches frida from the process)
+#[tauri::command]
+pub fn frida_unhook(id: u32) -> Result<bool, String> {
+    let Some(hook) = HOOKS.lock().map_err(|e| e.to_string())?.remove(&id) else {
+        return Ok(false);
+    };
+    if let Some(stop) = hook.stop {
+        let _ = stop.send(());
+    }
+    Ok(true)
+}
+
+/// Installed hooks, including ones whose frida process has exited
+#[tauri::command]
+pub fn frida_list_hooks() -> Result<Vec<FridaHookInfo>, String> {
+    let mut hooks: Vec<FridaHookInfo> = HOOKS.lock().map_err(|e| e.to_string())?.values().map(|hook| hook.info.clone()).collect();
+    hooks.sort_by_key(|info| info.id);
+    Ok(hooks)
+}
+
+/// Detach every hook (on app exit)
+pub fn shutdown_all() {
+    if let Ok(mut hooks) = HOOKS.lock() {
+        for (_, hook) in hooks.drain() {
+            if let Some(stop) = hook.stop {
+                let _ = stop.send(());
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use once_cell::sync::Lazy;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+use crate::state::AppStateType;
+use crate::{cache_versions, db, ghidra_server_decompile, ghidra_supervisor, save_decompile_cache, server_connection, GHIDRA_SERVER_PORTS};
+
+const DEFAULT_PROGRESS_EVENT: &str = "ghidra://decompile-progress";
+
+// Project paths with a batch running / asked to stop
+static RUNNING: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+static CANCELLED: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Progress and final result of ghidra_server_decompile_all
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecompileAllProgress {
+    pub project_path: String,
+    pub module_name: String,
+    pub total: usize,                 // Functions selected (after max_functions)
+    pub done: usize,
+    pub decompiled: usize,
+    pub cached: usize,                // Already in the cache and not stale; skipped
+    pub failed: usize,
+    pub current: Option<String>,      // Function being decompiled
+    pub finished: bool,
+    pub cancelled: bool,
+}
+
+/// (name, offset) of every function the server's program defines
+pub async fn server_functions(port: u16) -> Result<Vec<(String, String)>, String> {
+    let info: serde_json::Value = server_connection::local_client()
+        .get(format!("http://127.0.0.1:{}/info", port))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    Ok(info["functions"].as_array()
+        .map(|functions| functions.iter()
+            .filter_map(|f| Some((f["name"].as_str().unwrap_or_default().to_string(), f["offset"].as_str()?.to_string())))
+            .collect())
+        .unwrap_or_default())
+}
+
+/// Function addresses with a fresh (not stale) decompile cache entry
+async fn cached_addresses(target_os: &str, module_name: &str) -> HashSet<String> {
+    let (target_os, module_name) = (target_os.to_string(), module_name.to_string());
+    db::run(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT function_address FROM ghidra_decompile_cache c
+             WHERE target_os = ?1 AND module_name = ?2 AND (cache_version IS NULL OR cache_version = ?3) AND NOT EXISTS (
+                 SELECT 1 FROM ghidra_stale_functions s
+                 WHERE s.target_os = c.target_os AND s.module_name = c.module_name AND s.function_address = c.function_address)",
+        ).map_err(|e| e.to_string())?;
+        let stamp = cache_versions::stamp(conn, &target_os, &module_name);
+        let rows = stmt.query_map(params![target_os, module_name, stamp], |row| row.get(0)).map_err(|e| e.to_string())?;
+        Ok(rows.flatten().collect())
+    }).await.unwrap_or_default()
+}
+
+/// Decompile `functions` one by one into ghidra_decompile_cache, calling
+/// `report` after each; stops early when the batch is cancelled
+pub async fn decompile_into_cache(
+    progress: &mut DecompileAllProgress,
+    target_os: &str,
+    functions: Vec<(String, String)>,
+    skip_cached: bool,
+    report: impl Fn(&DecompileAllProgress),
+) {
+    let cached = if skip_cached { cached_addresses(target_os, &progress.module_name).await } else { HashSet::new() };
+    progress.total = functions.len();
+    for (name, offset) in functions {
+        if CANCELLED.lock().map(|c| c.contains(&progress.project_path)).unwrap_or(false) {
+            progress.cancelled = true;
+            break;
+        }
+        progress.done += 1;
+        if cached.contains(&offset) {
+            progress.cached += 1;
+            continue;
+        }
+        progress.current = Some(name);
+        report(progress);
+        let saved = match ghidra_server_decompile(progress.project_path.clone(), offset.clone()).await {
+            Ok(crate::GhidraDecompileResult { success: true, decompiled_code: Some(code), function_name, line_mapping, .. }) => {
+                let line_mapping_json = line_mapping.and_then(|m| serde_json::to_string(&m).ok());
+                save_decompile_cache(target_os.to_string(), progress.module_name.clone(), offset, function_name.unwrap_or_default(), code, line_mapping_json).await.is_ok()
+            }
+            _ => false,
+        };
+        if saved {
+            progress.decompiled += 1;
+        } else {
+            progress.failed += 1;
+        }
+    }
+    progress.current = None;
+    progress.finished = true;
+    report(progress);
+}
+
+/// Module name the decompile cache uses for a project: the library the
+/// server was started for, else the analyzed module stored for the project
+pub async fn project_module_name(project_path: &str) -> Option<String> {
+    if let Some(name) = ghidra_supervisor::library_name(project_path) {
+        return Some(name);
+    }
+    let project_path = project_path.to_string();
+    db::run(move |conn| {
+        Ok(conn.query_row(
+            "SELECT module_name FROM analyzed_modules WHERE project_path = ?1",
+            params![project_path],
+            |row| row.get(0),
+        ).ok())
+    }).await.ok().flatten()
+}
+
+/// Decompile every function of the program a running Ghidra server has open
+/// (up to `max_functions`) into the decompile cache, emitting
+/// DecompileAllProgress on `progress_event` (default
+/// "ghidra://decompile-progress"). Functions with a fresh cache entry are
+/// skipped unless `refresh` is set.
+#[tauri::command]
+pub async fn ghidra_server_decompile_all(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+    project_path: String,
+    max_functions: Option<usize>,
+    progress_event: Option<String>,
+    refresh: Option<bool>,
+) -> Result<DecompileAllProgress, String> {
+    let port = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?
+        .get(&project_path)
+        .copied()
+        .ok_or("Ghidra server not running for this project")?;
+    let module_name = project_module_name(&project_path).await.ok_or("Unknown module for this project")?;
+    let target_os = state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.target_os.clone()))
+        .unwrap_or_default();
+
+    if !RUNNING.lock().map_err(|e| e.to_string())?.insert(project_path.clone()) {
+        return Err("Batch decompilation is already running for this project".to_string());
+    }
+    CANCELLED.lock().map_err(|e| e.to_string())?.remove(&project_path);
+
+    let mut progress = DecompileAllProgress { project_path: project_path.clone(), module_name, ..Default::default() };
+    let result = match server_functions(port).await {
+        Ok(mut functions) => {
+            functions.truncate(max_functions.unwrap_or(usize::MAX));
+            let event = progress_event.unwrap_or_else(|| DEFAULT_PROGRESS_EVENT.to_string());
+            decompile_into_cache(&mut progress, &target_os, functions, !refresh.unwrap_or(false), |p| {
+                let _ = app.emit(&event, p);
+            }).await;
+            Ok(progress)
+        }
+        Err(e) => Err(e),
+    };
+    RUNNING.lock().map_err(|e| e.to_string())?.remove(&project_path);
+    result
+}
+
+/// Stop a running ghidra_server_decompile_all after the current function
+#[tauri::command]
+pub fn cancel_ghidra_decompile_all(project_path: String) -> Result<bool, String> {
+    if !RUNNING.lock().map_err(|e| e.to_string())?.contains(&project_path) {
+        return Ok(false);
+    }
+    Ok(CANCELLED.lock().map_err(|e| e.to_string())?.insert(project_path))
+}
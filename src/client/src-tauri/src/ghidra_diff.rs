@@ -0,0 +1,252 @@
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{db, ghidra_batch, server_connection, GHIDRA_SERVER_PORTS};
+
+// Auto-generated names carry no identity across versions
+const DEFAULT_NAME_PREFIXES: &[&str] = &["FUN_", "thunk_FUN_", "sub_", "LAB_", "switchD_"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionFingerprint {
+    pub name: String,
+    pub offset: String,                  // Relative to the image base
+    pub size: u64,
+    #[serde(default)]
+    pub instructions: Option<u32>,
+    #[serde(default)]
+    pub blocks: Option<u32>,
+    #[serde(default)]
+    pub mnemonic_hash: Option<String>,   // Position-independent: survives relocation
+    #[serde(default)]
+    pub byte_hash: Option<String>,       // Exact bytes
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionMatch {
+    pub name_a: String,
+    pub name_b: String,
+    pub offset_a: String,
+    pub offset_b: String,
+    pub size_a: u64,
+    pub size_b: u64,
+    pub match_kind: String,              // "name" | "bytes" | "mnemonics" | "position"
+}
+
+/// Old function range and where it moved; `exact` entries have identical
+/// bytes, so any offset inside them translates by the same delta
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffsetTranslation {
+    pub offset_a: String,
+    pub offset_b: String,
+    pub size: u64,
+    pub exact: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDiffResult {
+    pub module_a: String,
+    pub module_b: String,
+    pub source: String,                  // "server" (hashes compared) or "database" (names and sizes only)
+    pub added: Vec<FunctionFingerprint>, // Only in project_b
+    pub removed: Vec<FunctionFingerprint>,
+    pub changed: Vec<FunctionMatch>,
+    pub unchanged: usize,
+    pub translations: Vec<OffsetTranslation>,
+}
+
+fn parse_offset(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches("0x"), 16).ok()
+}
+
+fn is_default_name(name: &str) -> bool {
+    DEFAULT_NAME_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Fingerprints from the project's running server, else the function list
+/// stored when the module was analyzed; bool is true for the former
+async fn load_fingerprints(project_path: &str) -> Result<(Vec<FunctionFingerprint>, bool), String> {
+    let port = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?.get(project_path).copied();
+    if let Some(port) = port {
+        let response: serde_json::Value = server_connection::local_client()
+            .get(format!("http://127.0.0.1:{}/fingerprints", port))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        if response["success"].as_bool() != Some(true) {
+            return Err(response["error"].as_str().unwrap_or("Fingerprinting failed").to_string());
+        }
+        let functions = serde_json::from_value(response["functions"].clone())
+            .map_err(|e| format!("Failed to parse fingerprints: {}", e))?;
+        return Ok((functions, true));
+    }
+
+    let project_path = project_path.to_string();
+    db::run(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT f.name, f.address, f.size FROM module_functions f JOIN analyzed_modules m ON f.module_id = m.id
+             WHERE m.project_path = ?1",
+        ).map_err(|e| e.to_string())?;
+        let functions: Vec<FunctionFingerprint> = stmt.query_map(params![project_path], |row| {
+            Ok(FunctionFingerprint {
+                name: row.get(0)?,
+                offset: row.get(1)?,
+                size: row.get::<_, i64>(2)? as u64,
+                instructions: None,
+                blocks: None,
+                mnemonic_hash: None,
+                byte_hash: None,
+            })
+        }).map_err(|e| e.to_string())?.flatten().collect();
+        if functions.is_empty() {
+            return Err(format!("No function list for {}: start its Ghidra server", project_path));
+        }
+        Ok((functions, false))
+    }).await
+}
+
+/// Identity of a function for one matching pass
+fn match_key(kind: &str, function: &FunctionFingerprint) -> Option<String> {
+    match kind {
+        "name" => (!is_default_name(&function.name)).then(|| function.name.clone()),
+        "bytes" => function.byte_hash.clone(),
+        _ => Some(format!("{}:{}", function.mnemonic_hash.as_ref()?, function.blocks?)),
+    }
+}
+
+/// Pair unmatched functions whose key is unique on both sides
+fn match_unique<K: std::hash::Hash + Eq>(
+    a: &[FunctionFingerprint],
+    b: &[FunctionFingerprint],
+    matched_a: &mut [Option<usize>],
+    matched_b: &mut [bool],
+    key: impl Fn(&FunctionFingerprint) -> Option<K>,
+) {
+    fn unique<K: std::hash::Hash + Eq>(
+        functions: &[FunctionFingerprint],
+        taken: impl Fn(usize) -> bool,
+        key: &impl Fn(&FunctionFingerprint) -> Option<K>,
+    ) -> HashMap<K, Option<usize>> {
+        let mut index: HashMap<K, Option<usize>> = HashMap::new();
+        for (i, function) in functions.iter().enumerate().filter(|(i, _)| !taken(*i)) {
+            if let Some(k) = key(function) {
+                index.entry(k).and_modify(|slot| *slot = None).or_insert(Some(i));
+            }
+        }
+        index
+    }
+    let index_b = unique(b, |i| matched_b[i], &key);
+    let index_a = unique(a, |i| matched_a[i].is_some(), &key);
+    for (k, slot) in index_a {
+        if let (Some(i), Some(Some(j))) = (slot, index_b.get(&k)) {
+            matched_a[i] = Some(*j);
+            matched_b[*j] = true;
+        }
+    }
+}
+
+/// Between consecutive matched anchors, pair leftover functions in address
+/// order when both gaps hold the same number of them
+fn match_by_position(matched_a: &mut [Option<usize>], matched_b: &mut [bool]) -> Vec<usize> {
+    let mut paired = Vec::new();
+    let mut anchors: Vec<(usize, usize)> = matched_a.iter().enumerate()
+        .filter_map(|(i, m)| m.map(|j| (i, j)))
+        .collect();
+    anchors.insert(0, (usize::MAX, usize::MAX));
+    anchors.push((matched_a.len(), matched_b.len()));
+    for pair in anchors.windows(2) {
+        let ((a0, b0), (a1, b1)) = (pair[0], pair[1]);
+        let (start_a, start_b) = (a0.wrapping_add(1), b0.wrapping_add(1));
+        if b1 < start_b || a1 < start_a {
+            continue; // Anchors cross: the functions were reordered
+        }
+        let gap_a: Vec<usize> = (start_a..a1).filter(|&i| matched_a[i].is_none()).collect();
+        let gap_b: Vec<usize> = (start_b..b1).filter(|&j| !matched_b[j]).collect();
+        if gap_a.is_empty() || gap_a.len() != gap_b.len() {
+            continue;
+        }
+        for (i, j) in gap_a.into_iter().zip(gap_b) {
+            matched_a[i] = Some(j);
+            matched_b[j] = true;
+            paired.push(i);
+        }
+    }
+    paired
+}
+
+/// Compare the functions of two analyzed versions of a library (e.g. before
+/// and after a game update). Functions are matched by non-default name, then
+/// identical bytes, then identical mnemonics and block count, then position
+/// between matched neighbours. Hashes need both projects' servers running;
+/// otherwise the stored names and sizes are compared.
+#[tauri::command]
+pub async fn ghidra_diff_modules(project_a: String, project_b: String) -> Result<ModuleDiffResult, String> {
+    let (mut a, server_a) = load_fingerprints(&project_a).await?;
+    let (mut b, server_b) = load_fingerprints(&project_b).await?;
+    for functions in [&mut a, &mut b] {
+        functions.retain(|f| parse_offset(&f.offset).is_some());
+        functions.sort_by_key(|f| parse_offset(&f.offset));
+        functions.dedup_by_key(|f| parse_offset(&f.offset));
+    }
+
+    let mut matched_a: Vec<Option<usize>> = vec![None; a.len()];
+    let mut matched_b = vec![false; b.len()];
+    let mut kinds: Vec<&str> = vec![""; a.len()];
+    for kind in ["name", "bytes", "mnemonics"] {
+        match_unique(&a, &b, &mut matched_a, &mut matched_b, |f| match_key(kind, f));
+        for (i, m) in matched_a.iter().enumerate() {
+            if m.is_some() && kinds[i].is_empty() {
+                kinds[i] = kind;
+            }
+        }
+    }
+    for i in match_by_position(&mut matched_a, &mut matched_b) {
+        kinds[i] = "position";
+    }
+
+    let mut result = ModuleDiffResult {
+        module_a: ghidra_batch::project_module_name(&project_a).await.unwrap_or_default(),
+        module_b: ghidra_batch::project_module_name(&project_b).await.unwrap_or_default(),
+        source: if server_a && server_b { "server" } else { "database" }.to_string(),
+        added: b.iter().zip(&matched_b).filter(|(_, m)| !**m).map(|(f, _)| f.clone()).collect(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        unchanged: 0,
+        translations: Vec::new(),
+    };
+    for (i, m) in matched_a.iter().enumerate() {
+        let fa = &a[i];
+        let Some(j) = *m else {
+            result.removed.push(fa.clone());
+            continue;
+        };
+        let fb = &b[j];
+        let same = match (&fa.byte_hash, &fb.byte_hash) {
+            (Some(x), Some(y)) => x == y,
+            _ => fa.size == fb.size,
+        };
+        result.translations.push(OffsetTranslation {
+            offset_a: format!("0x{:x}", parse_offset(&fa.offset).unwrap_or(0)),
+            offset_b: format!("0x{:x}", parse_offset(&fb.offset).unwrap_or(0)),
+            size: fa.size,
+            exact: same && fa.byte_hash.is_some(),
+        });
+        if same {
+            result.unchanged += 1;
+        } else {
+            result.changed.push(FunctionMatch {
+                name_a: fa.name.clone(),
+                name_b: fb.name.clone(),
+                offset_a: fa.offset.clone(),
+                offset_b: fb.offset.clone(),
+                size_a: fa.size,
+                size_b: fb.size,
+                match_kind: kinds[i].to_string(),
+            });
+        }
+    }
+    Ok(result)
+}
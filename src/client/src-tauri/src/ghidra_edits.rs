@@ -0,0 +1,214 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::state::AppStateType;
+use crate::{cache_versions, db, ghidra_batch, secure_store, server_connection, symbolizer, GHIDRA_SERVER_PORTS};
+
+/// Result of a write to a Ghidra project through its running server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GhidraEditResult {
+    pub success: bool,
+    #[serde(default)]
+    pub function_offset: Option<String>, // Entry of the affected function
+    #[serde(default)]
+    pub old_name: Option<String>,
+    #[serde(default)]
+    pub new_name: Option<String>,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub invalidated: usize,              // Cache rows dropped or rewritten
+}
+
+pub fn parse_offset(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches("0x"), 16).ok()
+}
+
+fn same_offset(a: &str, b: &str) -> bool {
+    match (parse_offset(a), parse_offset(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+async fn call_server(project_path: &str, endpoint: &str, query: &[(&str, &str)]) -> Result<GhidraEditResult, String> {
+    let port = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?
+        .get(project_path)
+        .copied()
+        .ok_or("Ghidra server not running for this project")?;
+    server_connection::local_client()
+        .get(format!("http://127.0.0.1:{}/{}", port, endpoint))
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+/// (target_os, module_name) the project's cache rows are stored under
+async fn cache_key(state: &AppStateType, project_path: &str) -> Result<(String, String), String> {
+    let target_os = state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.target_os.clone()))
+        .unwrap_or_default();
+    let module_name = ghidra_batch::project_module_name(project_path).await.ok_or("Unknown module for this project")?;
+    Ok((target_os, module_name))
+}
+
+/// Delete cache rows of `table` whose function_address is `offset`
+/// (addresses are compared numerically; callers store them in varying formats)
+fn delete_function_rows(conn: &Connection, table: &str, target_os: &str, module_name: &str, offset: &str) -> Result<usize, String> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT function_address FROM {} WHERE target_os = ?1 AND module_name = ?2", table,
+    )).map_err(|e| e.to_string())?;
+    let addresses: Vec<String> = stmt.query_map(params![target_os, module_name], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .filter(|address: &String| same_offset(address, offset))
+        .collect();
+    let mut deleted = 0;
+    for address in addresses {
+        deleted += conn.execute(
+            &format!("DELETE FROM {} WHERE target_os = ?1 AND module_name = ?2 AND function_address = ?3", table),
+            params![target_os, module_name, address],
+        ).map_err(|e| e.to_string())?;
+    }
+    Ok(deleted)
+}
+
+/// Rename functions in the module's cached function list and analysis
+/// records; `renames` holds (offset, new name) pairs. Returns entries changed.
+pub fn rename_functions(conn: &Connection, target_os: &str, module_name: &str, renames: &[(String, String)]) -> Result<usize, String> {
+    let mut changed = 0;
+    let new_name = |address: &str| renames.iter().find(|(offset, _)| same_offset(address, offset)).map(|(_, name)| name);
+
+    let functions_json: Option<String> = conn.query_row(
+        "SELECT functions_json FROM ghidra_functions_cache WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+        |row| row.get(0),
+    ).ok();
+    if let Some(sealed) = functions_json {
+        let mut functions: Vec<serde_json::Value> = serde_json::from_str(&secure_store::open_value("ghidra_functions_cache", "functions_json", sealed)?)
+            .map_err(|e| format!("Failed to parse functions JSON: {}", e))?;
+        for function in functions.iter_mut() {
+            if let Some(name) = function["address"].as_str().and_then(new_name) {
+                function["name"] = serde_json::json!(name);
+                changed += 1;
+            }
+        }
+        let json = serde_json::to_string(&functions).map_err(|e| e.to_string())?;
+        conn.execute(
+            "UPDATE ghidra_functions_cache SET functions_json = ?3, updated_at = datetime('now') WHERE target_os = ?1 AND module_name = ?2",
+            params![target_os, module_name, secure_store::seal_value("ghidra_functions_cache", "functions_json", &json)?],
+        ).map_err(|e| e.to_string())?;
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT f.id, f.address FROM module_functions f JOIN analyzed_modules m ON f.module_id = m.id
+         WHERE m.target_os = ?1 AND m.module_name = ?2",
+    ).map_err(|e| e.to_string())?;
+    let updates: Vec<(i64, String)> = stmt.query_map(params![target_os, module_name], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .filter_map(|(id, address)| new_name(&address).map(|name| (id, name.clone())))
+        .collect();
+    for (id, name) in updates {
+        changed += conn.execute("UPDATE module_functions SET name = ?1 WHERE id = ?2", params![name, id])
+            .map_err(|e| e.to_string())?;
+    }
+    symbolizer::invalidate_module(target_os, module_name);
+    Ok(changed)
+}
+
+/// A function name changed: callers' decompilations, xrefs and the call
+/// graph mention it, so those module caches are dropped; the function lists
+/// are renamed in place
+async fn invalidate_rename(target_os: String, module_name: String, offset: String, new_name: String) -> Result<usize, String> {
+    db::run(move |conn| {
+        let dropped = cache_versions::invalidate(conn, &target_os, &module_name, "rename")?;
+        Ok(dropped + rename_functions(conn, &target_os, &module_name, &[(offset, new_name)])?)
+    }).await
+}
+
+/// Only the edited function's decompilation changed
+async fn invalidate_decompile(target_os: String, module_name: String, offset: String) -> Result<usize, String> {
+    db::run(move |conn| delete_function_rows(conn, "ghidra_decompile_cache", &target_os, &module_name, &offset)).await
+}
+
+/// Rename the function containing `offset` (hex, relative to the image base)
+/// in the Ghidra project and save it
+#[tauri::command]
+pub async fn ghidra_rename_function(
+    state: tauri::State<'_, AppStateType>,
+    project_path: String,
+    offset: String,
+    new_name: String,
+) -> Result<GhidraEditResult, String> {
+    if new_name.trim().is_empty() {
+        return Err("Function name must not be empty".to_string());
+    }
+    let (target_os, module_name) = cache_key(state.inner(), &project_path).await?;
+    let mut result = call_server(&project_path, "rename_function", &[("offset", &offset), ("name", new_name.trim())]).await?;
+    if result.success {
+        let entry = result.function_offset.clone().unwrap_or(offset);
+        let name = result.new_name.clone().unwrap_or(new_name);
+        result.invalidated = invalidate_rename(target_os, module_name, entry, name).await?;
+    }
+    Ok(result)
+}
+
+/// Rename and/or retype (C type name, e.g. "char *") a local variable or
+/// parameter of the function containing `offset`, as named in its decompilation
+#[tauri::command]
+pub async fn ghidra_rename_variable(
+    state: tauri::State<'_, AppStateType>,
+    project_path: String,
+    offset: String,
+    old_name: String,
+    new_name: Option<String>,
+    new_type: Option<String>,
+) -> Result<GhidraEditResult, String> {
+    let new_name = new_name.unwrap_or_default();
+    let new_type = new_type.unwrap_or_default();
+    if new_name.trim().is_empty() && new_type.trim().is_empty() {
+        return Err("Nothing to change: give a new name or type".to_string());
+    }
+    let (target_os, module_name) = cache_key(state.inner(), &project_path).await?;
+    let mut result = call_server(&project_path, "rename_variable", &[
+        ("offset", &offset),
+        ("old_name", &old_name),
+        ("new_name", new_name.trim()),
+        ("type", new_type.trim()),
+    ]).await?;
+    if result.success {
+        let entry = result.function_offset.clone().unwrap_or(offset);
+        result.invalidated = invalidate_decompile(target_os, module_name, entry).await?;
+    }
+    Ok(result)
+}
+
+/// Set a comment at `offset` ("eol" | "pre" | "post" | "plate" | "repeatable";
+/// an empty comment clears it)
+#[tauri::command]
+pub async fn ghidra_set_comment(
+    state: tauri::State<'_, AppStateType>,
+    project_path: String,
+    offset: String,
+    comment: String,
+    comment_type: Option<String>,
+) -> Result<GhidraEditResult, String> {
+    let (target_os, module_name) = cache_key(state.inner(), &project_path).await?;
+    let comment_type = comment_type.unwrap_or_else(|| "eol".to_string());
+    let mut result = call_server(&project_path, "set_comment", &[
+        ("offset", &offset),
+        ("comment", &comment),
+        ("type", &comment_type),
+    ]).await?;
+    if result.success {
+        if let Some(entry) = result.function_offset.clone() {
+            result.invalidated = invalidate_decompile(target_os, module_name, entry).await?;
+        }
+    }
+    Ok(result)
+}
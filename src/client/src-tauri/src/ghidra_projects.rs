@@ -0,0 +1,223 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{data_overlay, db, get_ghidra_projects_dir, symbolizer, GHIDRA_SERVER_PORTS};
+
+// Per-module Ghidra cache tables keyed by (target_os, module_name)
+const MODULE_CACHE_TABLES: &[&str] = &[
+    "ghidra_functions_cache",
+    "ghidra_decompile_cache",
+    "ghidra_xref_cache",
+    "ghidra_callgraph_cache",
+    "ghidra_data_cache",
+    "ghidra_search_cache",
+    "ghidra_stale_functions",
+];
+
+/// One Ghidra project directory (a folder holding `<library>.gpr`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhidraProjectInfo {
+    pub name: String,                 // Relative to ghidra_projects, e.g. "libfoo" or "game/libfoo"
+    pub project_path: String,
+    pub size_bytes: u64,              // Project directory plus downloaded library files
+    pub last_used: i64,               // Unix seconds: newest file change or analysis
+    pub modules: Vec<String>,         // Analyzed modules stored in this project
+    pub server_running: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneResult {
+    pub deleted: Vec<String>,
+    pub freed_bytes: u64,
+    pub remaining_bytes: u64,
+    pub skipped: Vec<String>,         // Selected but in use by a running Ghidra server
+}
+
+fn to_unix(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0)
+}
+
+/// Total size and newest modification time of a file or directory tree
+fn disk_usage(path: &Path) -> (u64, i64) {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return (0, 0);
+    };
+    let modified = metadata.modified().map(to_unix).unwrap_or(0);
+    if !metadata.is_dir() {
+        return (metadata.len(), modified);
+    }
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return (0, modified);
+    };
+    entries.flatten()
+        .map(|entry| disk_usage(&entry.path()))
+        .fold((0, modified), |(size, newest), (s, m)| (size + s, newest.max(m)))
+}
+
+fn is_project_dir(dir: &Path) -> bool {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().any(|e| e.path().extension().is_some_and(|ext| ext == "gpr")))
+        .unwrap_or(false)
+}
+
+/// Project directories directly under ghidra_projects or one project-name
+/// level below it (see analyze_with_ghidra)
+fn find_projects(root: &Path) -> Vec<PathBuf> {
+    let mut projects = Vec::new();
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return projects;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || entry.file_name() == "libraries" {
+            continue;
+        }
+        if is_project_dir(&path) {
+            projects.push(path);
+        } else if let Ok(children) = std::fs::read_dir(&path) {
+            projects.extend(children.flatten().map(|c| c.path()).filter(|p| p.is_dir() && is_project_dir(p)));
+        }
+    }
+    projects
+}
+
+/// (target_os, module_name, local_path, analyzed_at) rows stored for a project directory
+fn analyzed_modules(conn: &Connection, project_path: &str) -> Vec<(String, String, String, i64)> {
+    let Ok(mut stmt) = conn.prepare(
+        "SELECT target_os, module_name, local_path, analyzed_at FROM analyzed_modules WHERE project_path = ?1",
+    ) else {
+        return Vec::new();
+    };
+    stmt.query_map(params![project_path], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Downloaded library file of an analyzed module, when it lives in our libraries folder
+fn owned_library(root: &Path, local_path: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(local_path);
+    (path.starts_with(root.join("libraries")) && path.is_file()).then_some(path)
+}
+
+fn collect_projects(conn: &Connection) -> Result<Vec<GhidraProjectInfo>, String> {
+    let root = get_ghidra_projects_dir();
+    let running: Vec<String> = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?.keys().cloned().collect();
+
+    let mut projects: Vec<GhidraProjectInfo> = find_projects(&root).into_iter()
+        .map(|dir| {
+            let project_path = dir.to_string_lossy().to_string();
+            let (mut size_bytes, mut last_used) = disk_usage(&dir);
+            let rows = analyzed_modules(conn, &project_path);
+            for (_, _, local_path, analyzed_at) in &rows {
+                last_used = last_used.max(*analyzed_at);
+                if let Some(library) = owned_library(&root, local_path) {
+                    size_bytes += disk_usage(&library).0;
+                }
+            }
+            GhidraProjectInfo {
+                name: dir.strip_prefix(&root).unwrap_or(&dir).to_string_lossy().replace('\\', "/"),
+                server_running: running.contains(&project_path),
+                project_path,
+                size_bytes,
+                last_used,
+                modules: rows.into_iter().map(|(_, module_name, _, _)| module_name).collect(),
+            }
+        })
+        .collect();
+    projects.sort_by(|a, b| b.last_used.cmp(&a.last_used).then(a.name.cmp(&b.name)));
+    Ok(projects)
+}
+
+/// Delete a project directory, its downloaded libraries and every cache row
+/// of the modules analyzed into it
+fn remove_project(conn: &Connection, project: &GhidraProjectInfo) -> Result<(), String> {
+    if project.server_running {
+        return Err(format!("Ghidra server is running for {}", project.name));
+    }
+    let root = get_ghidra_projects_dir();
+    let path = PathBuf::from(&project.project_path);
+    if !path.starts_with(&root) || path == root {
+        return Err(format!("Refusing to delete {} outside the projects folder", project.project_path));
+    }
+
+    for (target_os, module_name, local_path, _) in analyzed_modules(conn, &project.project_path) {
+        for table in MODULE_CACHE_TABLES {
+            conn.execute(
+                &format!("DELETE FROM {} WHERE target_os = ?1 AND module_name = ?2", table),
+                params![target_os, module_name],
+            ).map_err(|e| format!("Failed to clear {}: {}", table, e))?;
+        }
+        conn.execute(
+            "DELETE FROM module_functions WHERE module_id IN
+             (SELECT id FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2)",
+            params![target_os, module_name],
+        ).map_err(|e| format!("Failed to clear module functions: {}", e))?;
+        symbolizer::invalidate_module(&target_os, &module_name);
+        if let Some(library) = owned_library(&root, &local_path) {
+            let _ = std::fs::remove_file(library);
+        }
+    }
+    conn.execute("DELETE FROM analyzed_modules WHERE project_path = ?1", params![project.project_path])
+        .map_err(|e| format!("Failed to clear analyzed modules: {}", e))?;
+    conn.execute("DELETE FROM decompiler_settings WHERE project_path = ?1", params![project.project_path])
+        .map_err(|e| format!("Failed to clear decompiler settings: {}", e))?;
+    data_overlay::invalidate_all();
+
+    std::fs::remove_dir_all(&path).map_err(|e| format!("Failed to delete {}: {}", project.name, e))
+}
+
+/// Ghidra projects with their disk usage, most recently used first
+#[tauri::command]
+pub async fn list_ghidra_projects() -> Result<Vec<GhidraProjectInfo>, String> {
+    db::run(collect_projects).await
+}
+
+/// Delete one project (by name from list_ghidra_projects) and its cached analysis
+#[tauri::command]
+pub async fn delete_ghidra_project(name: String) -> Result<u64, String> {
+    db::run(move |conn| {
+        let project = collect_projects(conn)?
+            .into_iter()
+            .find(|p| p.name == name || p.project_path == name)
+            .ok_or_else(|| format!("Ghidra project not found: {}", name))?;
+        remove_project(conn, &project)?;
+        Ok(project.size_bytes)
+    }).await
+}
+
+/// Delete projects unused for `max_age_days`, then the least recently used
+/// ones until the total is within `max_total_bytes`. Projects with a running
+/// server are never deleted.
+#[tauri::command]
+pub async fn prune_ghidra_projects(max_age_days: Option<u32>, max_total_bytes: Option<u64>) -> Result<PruneResult, String> {
+    db::run(move |conn| {
+        let projects = collect_projects(conn)?;
+        let mut remaining_bytes: u64 = projects.iter().map(|p| p.size_bytes).sum();
+        let cutoff = max_age_days.map(|days| to_unix(SystemTime::now()) - days as i64 * 86_400);
+        let mut result = PruneResult { deleted: Vec::new(), freed_bytes: 0, remaining_bytes, skipped: Vec::new() };
+
+        // Oldest first, so the size limit evicts least recently used projects
+        for project in projects.iter().rev() {
+            let expired = cutoff.is_some_and(|cutoff| project.last_used < cutoff);
+            let over_budget = max_total_bytes.is_some_and(|max| remaining_bytes > max);
+            if !expired && !over_budget {
+                continue;
+            }
+            if project.server_running {
+                result.skipped.push(project.name.clone());
+                continue;
+            }
+            remove_project(conn, project)?;
+            remaining_bytes -= project.size_bytes;
+            result.freed_bytes += project.size_bytes;
+            result.deleted.push(project.name.clone());
+        }
+        if !result.deleted.is_empty() {
+            let _ = conn.execute("VACUUM", []);
+        }
+        result.remaining_bytes = remaining_bytes;
+        Ok(result)
+    }).await
+}
@@ -0,0 +1,141 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::state::AppStateType;
+use crate::{db, ghidra_batch, secure_store, server_connection, GHIDRA_SERVER_PORTS};
+
+const DEFAULT_LIMIT: usize = 500;
+const MAX_LIMIT: usize = 10_000;
+const SEARCH_KINDS: &[&str] = &["string", "function_name", "immediate_value", "byte_pattern"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchReference {
+    pub from_offset: String,
+    pub from_function: Option<String>,
+    pub ref_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub offset: String,                  // Relative to the image base
+    pub text: String,                    // String value, function name or instruction
+    pub function_name: Option<String>,   // Function containing the hit
+    pub function_offset: Option<String>,
+    #[serde(default)]
+    pub references: Vec<SearchReference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhidraSearchResult {
+    pub success: bool,
+    #[serde(default)]
+    pub results: Vec<SearchHit>,
+    #[serde(default)]
+    pub truncated: bool,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub cached: bool,
+}
+
+/// Create the search result cache table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ghidra_search_cache (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            query TEXT NOT NULL,
+            result_limit INTEGER NOT NULL,
+            results_json TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name, kind, query, result_limit)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Drop cached searches of a module (names or the program changed)
+pub fn invalidate_module(conn: &Connection, target_os: &str, module_name: &str) -> Result<usize, String> {
+    conn.execute(
+        "DELETE FROM ghidra_search_cache WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+    ).map_err(|e| e.to_string())
+}
+
+fn load_cached(conn: &Connection, target_os: &str, module_name: &str, kind: &str, query: &str, limit: usize) -> Option<GhidraSearchResult> {
+    let sealed: String = conn.query_row(
+        "SELECT results_json FROM ghidra_search_cache
+         WHERE target_os = ?1 AND module_name = ?2 AND kind = ?3 AND query = ?4 AND result_limit = ?5",
+        params![target_os, module_name, kind, query, limit as i64],
+        |row| row.get(0),
+    ).ok()?;
+    let json = secure_store::open_value("ghidra_search_cache", "results_json", sealed).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Search the program a running Ghidra server has open. `kind` is "string"
+/// (substring of defined strings, with the code referencing them),
+/// "function_name", "immediate_value" (decimal or 0x hex scalar operand) or
+/// "byte_pattern" (hex bytes, "??" wildcards). Results are cached per query.
+#[tauri::command]
+pub async fn ghidra_search(
+    state: tauri::State<'_, AppStateType>,
+    project_path: String,
+    query: String,
+    kind: String,
+    limit: Option<usize>,
+    refresh: Option<bool>,
+) -> Result<GhidraSearchResult, String> {
+    if !SEARCH_KINDS.contains(&kind.as_str()) {
+        return Err(format!("Unknown search kind: {} (expected one of {})", kind, SEARCH_KINDS.join(", ")));
+    }
+    if query.trim().is_empty() {
+        return Err("Search query must not be empty".to_string());
+    }
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let target_os = state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.target_os.clone()))
+        .unwrap_or_default();
+    let module_name = ghidra_batch::project_module_name(&project_path).await.ok_or("Unknown module for this project")?;
+
+    if !refresh.unwrap_or(false) {
+        let cached = {
+            let (target_os, module_name, kind, query) = (target_os.clone(), module_name.clone(), kind.clone(), query.clone());
+            db::run(move |conn| Ok(load_cached(conn, &target_os, &module_name, &kind, &query, limit))).await?
+        };
+        if let Some(mut result) = cached {
+            result.cached = true;
+            return Ok(result);
+        }
+    }
+
+    let port = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?
+        .get(&project_path)
+        .copied()
+        .ok_or("Ghidra server not running for this project")?;
+    let text = server_connection::local_client()
+        .get(format!("http://127.0.0.1:{}/search", port))
+        .query(&[("query", query.as_str()), ("kind", kind.as_str()), ("limit", &limit.to_string())])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to get response text: {}", e))?;
+    let result: GhidraSearchResult = serde_json::from_str(&text)
+        .map_err(|e| format!("Failed to parse search response: {}. Response was: {}", e, text.chars().take(500).collect::<String>()))?;
+
+    if result.success {
+        db::run(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO ghidra_search_cache (target_os, module_name, kind, query, result_limit, results_json, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'))",
+                params![target_os, module_name, kind, query, limit as i64, secure_store::seal_value("ghidra_search_cache", "results_json", &text)?],
+            ).map_err(|e| e.to_string())?;
+            Ok(())
+        }).await?;
+    }
+    Ok(result)
+}
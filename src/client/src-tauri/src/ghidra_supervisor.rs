@@ -0,0 +1,269 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Child;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::{reserve_ghidra_port, spawn_ghidra_server, GHIDRA_SERVERS, GHIDRA_SERVER_LOGS, GHIDRA_SERVER_PORTS};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+// A server that ran this long before exiting starts a fresh restart budget
+const STABLE_UPTIME: Duration = Duration::from_secs(120);
+// Log lines kept in the status of a server that exited
+const EXIT_LOG_LINES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerState {
+    Running,
+    Down,
+    Restarting,
+    Failed,      // Restart budget exhausted or restart failed
+}
+
+/// Supervised Ghidra server, reported by get_ghidra_server_status and the
+/// `ghidra-server://status` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhidraServerStatus {
+    pub project_path: String,
+    pub library_name: String,
+    pub port: u16,
+    pub state: ServerState,
+    pub exit_code: Option<i32>,
+    pub restarts: u32,
+    pub last_logs: Vec<String>,       // Tail of the log when the server exited
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoRestartSettings {
+    pub enabled: bool,
+    pub max_restarts: u32,
+    pub initial_backoff_ms: u64,      // Doubled after each consecutive restart
+}
+
+impl Default for AutoRestartSettings {
+    fn default() -> Self {
+        Self { enabled: true, max_restarts: 5, initial_backoff_ms: 1000 }
+    }
+}
+
+struct Supervised {
+    status: GhidraServerStatus,
+    ghidra_path: String,
+    started_at: Instant,
+    restart_at: Option<Instant>,
+}
+
+static SUPERVISED: Lazy<Mutex<HashMap<String, Supervised>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static SETTINGS: Lazy<Mutex<AutoRestartSettings>> = Lazy::new(|| Mutex::new(AutoRestartSettings::default()));
+
+/// Start supervising a server launched by start_ghidra_server
+pub fn register(project_path: &str, library_name: &str, ghidra_path: &str, port: u16) {
+    let mut supervised = SUPERVISED.lock().unwrap();
+    let restarts = supervised.get(project_path).map(|s| s.status.restarts).unwrap_or(0);
+    supervised.insert(project_path.to_string(), Supervised {
+        status: GhidraServerStatus {
+            project_path: project_path.to_string(),
+            library_name: library_name.to_string(),
+            port,
+            state: ServerState::Running,
+            exit_code: None,
+            restarts,
+            last_logs: Vec::new(),
+            message: None,
+        },
+        ghidra_path: ghidra_path.to_string(),
+        started_at: Instant::now(),
+        restart_at: None,
+    });
+}
+
+/// Stop supervising (the server is being stopped on purpose)
+pub fn unregister(project_path: &str) {
+    SUPERVISED.lock().unwrap().remove(project_path);
+}
+
+/// Library a supervised server was started for
+pub fn library_name(project_path: &str) -> Option<String> {
+    SUPERVISED.lock().unwrap().get(project_path).map(|s| s.status.library_name.clone())
+}
+
+/// Kill a server process. analyzeHeadless is a launcher script, so on Unix
+/// the whole process group (see hide_console_window) is signalled as well.
+pub fn kill_server(mut child: Child) {
+    #[cfg(unix)]
+    unsafe {
+        libc::kill(-(child.id() as i32), libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Remove exited servers from GHIDRA_SERVERS/PORTS/LOGS and mark them down;
+/// returns the statuses that changed
+pub fn reap() -> Vec<GhidraServerStatus> {
+    let exited: Vec<(String, Option<i32>)> = {
+        let Ok(mut servers) = GHIDRA_SERVERS.lock() else {
+            return Vec::new();
+        };
+        let exited: Vec<(String, Option<i32>)> = servers.iter_mut()
+            .filter_map(|(path, child)| match child.try_wait() {
+                Ok(Some(status)) => Some((path.clone(), status.code())),
+                Ok(None) => None,
+                Err(_) => Some((path.clone(), None)),
+            })
+            .collect();
+        for (path, _) in &exited {
+            servers.remove(path);
+        }
+        exited
+    };
+    if exited.is_empty() {
+        return Vec::new();
+    }
+
+    let settings = SETTINGS.lock().unwrap().clone();
+    let mut changed = Vec::new();
+    for (path, exit_code) in exited {
+        if let Ok(mut ports) = GHIDRA_SERVER_PORTS.lock() {
+            ports.remove(&path);
+        }
+        let last_logs = GHIDRA_SERVER_LOGS.lock().ok()
+            .and_then(|mut logs| logs.remove(&path))
+            .map(|lines| lines[lines.len().saturating_sub(EXIT_LOG_LINES)..].to_vec())
+            .unwrap_or_default();
+
+        let mut supervised = SUPERVISED.lock().unwrap();
+        let Some(entry) = supervised.get_mut(&path) else {
+            continue;
+        };
+        if entry.started_at.elapsed() >= STABLE_UPTIME {
+            entry.status.restarts = 0;
+        }
+        entry.status.exit_code = exit_code;
+        entry.status.last_logs = last_logs;
+        if settings.enabled && entry.status.restarts < settings.max_restarts {
+            let backoff = Duration::from_millis(settings.initial_backoff_ms.saturating_mul(1 << entry.status.restarts.min(16)));
+            entry.restart_at = Some(Instant::now() + backoff.min(MAX_BACKOFF));
+            entry.status.state = ServerState::Down;
+            entry.status.message = Some(format!("Server exited; restarting in {} ms", backoff.min(MAX_BACKOFF).as_millis()));
+        } else {
+            entry.status.state = ServerState::Failed;
+            entry.status.message = Some(if settings.enabled {
+                format!("Server exited; gave up after {} restarts", entry.status.restarts)
+            } else {
+                "Server exited".to_string()
+            });
+        }
+        changed.push(entry.status.clone());
+    }
+    changed
+}
+
+async fn restart_due(app: &AppHandle) {
+    let due: Vec<(GhidraServerStatus, String)> = {
+        let mut supervised = SUPERVISED.lock().unwrap();
+        supervised.values_mut()
+            .filter(|s| s.restart_at.is_some_and(|at| at <= Instant::now()))
+            .map(|s| {
+                s.restart_at = None;
+                s.status.restarts += 1;
+                s.status.state = ServerState::Restarting;
+                (s.status.clone(), s.ghidra_path.clone())
+            })
+            .collect()
+    };
+    for (status, ghidra_path) in due {
+        let _ = app.emit("ghidra-server://status", &status);
+        // The old port may have been taken meanwhile; reserve_ghidra_port falls back to a free one
+        let result = match reserve_ghidra_port(&status.project_path, Some(status.port)) {
+            Ok((port, true)) => Ok(port), // Started again by the user meanwhile
+            Ok((port, false)) => spawn_ghidra_server(&status.project_path, &status.library_name, &ghidra_path, port).await
+                .map(|()| port)
+                .inspect_err(|_| {
+                    if let Ok(mut ports) = GHIDRA_SERVER_PORTS.lock() {
+                        ports.remove(&status.project_path);
+                    }
+                }),
+            Err(e) => Err(e),
+        };
+
+        let mut supervised = SUPERVISED.lock().unwrap();
+        let Some(entry) = supervised.get_mut(&status.project_path) else {
+            continue; // Stopped while restarting
+        };
+        match result {
+            Ok(port) => {
+                entry.started_at = Instant::now();
+                entry.status.port = port;
+                entry.status.state = ServerState::Running;
+                entry.status.message = Some(format!("Restarted (attempt {})", entry.status.restarts));
+            }
+            Err(e) => {
+                entry.status.state = ServerState::Failed;
+                entry.status.message = Some(format!("Restart failed: {}", e));
+            }
+        }
+        let _ = app.emit("ghidra-server://status", &entry.status);
+    }
+}
+
+/// Supervisor loop, spawned from setup
+pub async fn run(app: AppHandle) {
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        for status in reap() {
+            let _ = app.emit("ghidra-server://status", &status);
+        }
+        restart_due(&app).await;
+    }
+}
+
+/// Kill every Ghidra server (on app exit)
+pub fn shutdown_all() {
+    SUPERVISED.lock().unwrap().clear();
+    let children: Vec<Child> = match GHIDRA_SERVERS.lock() {
+        Ok(mut servers) => servers.drain().map(|(_, child)| child).collect(),
+        Err(_) => return,
+    };
+    for child in children {
+        kill_server(child);
+    }
+    if let Ok(mut ports) = GHIDRA_SERVER_PORTS.lock() {
+        ports.clear();
+    }
+    if let Ok(mut logs) = GHIDRA_SERVER_LOGS.lock() {
+        logs.clear();
+    }
+}
+
+/// Supervised servers, including ones that exited and are waiting to restart
+#[tauri::command]
+pub fn get_ghidra_server_status() -> Vec<GhidraServerStatus> {
+    reap();
+    let mut statuses: Vec<GhidraServerStatus> = SUPERVISED.lock().unwrap().values().map(|s| s.status.clone()).collect();
+    statuses.sort_by(|a, b| a.project_path.cmp(&b.project_path));
+    statuses
+}
+
+/// Update the auto-restart policy; returns the effective settings
+#[tauri::command]
+pub fn set_ghidra_auto_restart(
+    enabled: bool,
+    max_restarts: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+) -> AutoRestartSettings {
+    let mut settings = SETTINGS.lock().unwrap();
+    settings.enabled = enabled;
+    if let Some(max_restarts) = max_restarts {
+        settings.max_restarts = max_restarts;
+    }
+    if let Some(initial_backoff_ms) = initial_backoff_ms {
+        settings.initial_backoff_ms = initial_backoff_ms.max(100);
+    }
+    settings.clone()
+}
@@ -0,0 +1,308 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rusqlite::{params, params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::{cache_versions, db, secure_store, GhidraDataItem};
+
+const DEFAULT_LIMIT: usize = 200;
+const MAX_LIMIT: usize = 5000;
+// Trigram index: shorter queries cannot match
+const MIN_QUERY_LENGTH: usize = 3;
+// Roughly characters of context, since trigram tokens are single characters
+const SNIPPET_TOKENS: i64 = 64;
+pub const KINDS: [&str; 5] = ["function", "decompiled", "comment", "data", "watch"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchEverythingHit {
+    pub kind: String,                  // One of KINDS
+    pub target_os: String,
+    pub module_name: String,
+    pub address: String,               // Module offset (hex)
+    pub name: Option<String>,          // Function, data label or watch label
+    pub snippet: String,               // Text around the match
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchEverythingResult {
+    pub hits: Vec<SearchEverythingHit>,
+    pub truncated: bool,
+    pub indexed: usize,                // Entries in the index after refreshing it
+}
+
+/// One row to index
+struct Entry {
+    module_name: String,
+    kind: &'static str,
+    address: String,
+    name: Option<String>,
+    text: String,
+}
+
+/// FTS5 index over the caches. It lives in memory so values sealed by
+/// secure_store never reach disk in plain text; sources are re-read when
+/// their fingerprint changes.
+struct SearchIndex {
+    conn: Connection,
+    fingerprints: HashMap<(String, String, String), String>, // (source, target_os, scope) -> fingerprint
+}
+
+static INDEX: Lazy<Mutex<Option<SearchIndex>>> = Lazy::new(|| Mutex::new(None));
+
+static COMMENT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)/\*(.*?)\*/|//([^\n]*)").unwrap());
+
+impl SearchIndex {
+    fn new() -> Result<Self, String> {
+        let conn = Connection::open_in_memory().map_err(|e| e.to_string())?;
+        conn.execute(
+            "CREATE VIRTUAL TABLE search_entries USING fts5(
+                source UNINDEXED,
+                target_os UNINDEXED,
+                scope UNINDEXED,
+                module_name UNINDEXED,
+                kind UNINDEXED,
+                address UNINDEXED,
+                name UNINDEXED,
+                text,
+                tokenize = 'trigram'
+            )",
+            [],
+        ).map_err(|e| e.to_string())?;
+        Ok(SearchIndex { conn, fingerprints: HashMap::new() })
+    }
+
+    fn replace(&self, source: &str, target_os: &str, scope: &str, entries: &[Entry]) -> Result<(), String> {
+        let tx = self.conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        tx.execute(
+            "DELETE FROM search_entries WHERE source = ?1 AND target_os = ?2 AND scope = ?3",
+            params![source, target_os, scope],
+        ).map_err(|e| e.to_string())?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO search_entries (source, target_os, scope, module_name, kind, address, name, text)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            ).map_err(|e| e.to_string())?;
+            for entry in entries {
+                stmt.execute(params![source, target_os, scope, entry.module_name, entry.kind, entry.address, entry.name, entry.text])
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        tx.commit().map_err(|e| e.to_string())
+    }
+
+    fn len(&self) -> usize {
+        self.conn.query_row("SELECT COUNT(*) FROM search_entries", [], |row| row.get::<_, i64>(0))
+            .unwrap_or(0) as usize
+    }
+}
+
+/// (target_os, scope, fingerprint) of every indexed unit of a source
+fn fingerprints(conn: &Connection, source: &str) -> Result<Vec<(String, String, String)>, String> {
+    let sql = match source {
+        "functions" => "SELECT target_os, module_name, updated_at || ':' || length(functions_json) FROM ghidra_functions_cache",
+        "decompile" => "SELECT target_os, module_name, COUNT(*) || ':' || MAX(updated_at) || ':' || SUM(length(decompiled_code))
+                        FROM ghidra_decompile_cache GROUP BY target_os, module_name",
+        "data" => "SELECT target_os, module_name, updated_at || ':' || length(data_json) FROM ghidra_data_cache",
+        // Watch labels are indexed per target OS; scope is empty
+        _ => "SELECT target_os, '', COUNT(*) || ':' || MAX(updated_at) || ':' || MAX(id) FROM watchlist GROUP BY target_os",
+    };
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, Option<String>>(2)?.unwrap_or_default())))
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+fn function_entries(conn: &Connection, target_os: &str, module_name: &str) -> Result<Vec<Entry>, String> {
+    let sealed: String = conn.query_row(
+        "SELECT functions_json FROM ghidra_functions_cache WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+        |row| row.get(0),
+    ).map_err(|e| e.to_string())?;
+    let functions: Vec<serde_json::Value> = secure_store::open_value("ghidra_functions_cache", "functions_json", sealed)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    Ok(functions.iter()
+        .filter_map(|f| Some(Entry {
+            module_name: module_name.to_string(),
+            kind: "function",
+            address: f["address"].as_str()?.to_string(),
+            name: None,
+            text: f["name"].as_str()?.to_string(),
+        }))
+        .collect())
+}
+
+/// Decompiled functions, plus the comments in them as separate entries
+fn decompile_entries(conn: &Connection, target_os: &str, module_name: &str) -> Result<Vec<Entry>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT function_address, function_name, decompiled_code FROM ghidra_decompile_cache
+         WHERE target_os = ?1 AND module_name = ?2 AND (cache_version IS NULL OR cache_version = ?3)",
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(
+        params![target_os, module_name, cache_versions::stamp(conn, target_os, module_name)],
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)),
+    ).map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for (address, function_name, sealed) in rows.flatten() {
+        // Undecryptable rows (key unavailable) are left out
+        let Ok(code) = secure_store::open_value("ghidra_decompile_cache", "decompiled_code", sealed) else {
+            continue;
+        };
+        let name = (!function_name.is_empty()).then_some(function_name);
+        for captures in COMMENT_RE.captures_iter(&code) {
+            let text = captures.get(1).or_else(|| captures.get(2)).map_or("", |m| m.as_str()).trim();
+            // Decompiler diagnostics, not comments
+            if text.is_empty() || text.starts_with("WARNING:") {
+                continue;
+            }
+            entries.push(Entry {
+                module_name: module_name.to_string(),
+                kind: "comment",
+                address: address.clone(),
+                name: name.clone(),
+                text: text.to_string(),
+            });
+        }
+        entries.push(Entry { module_name: module_name.to_string(), kind: "decompiled", address, name, text: code });
+    }
+    Ok(entries)
+}
+
+fn data_entries(conn: &Connection, target_os: &str, module_name: &str) -> Result<Vec<Entry>, String> {
+    let sealed: String = conn.query_row(
+        "SELECT data_json FROM ghidra_data_cache WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+        |row| row.get(0),
+    ).map_err(|e| e.to_string())?;
+    let items: Vec<GhidraDataItem> = secure_store::open_value("ghidra_data_cache", "data_json", sealed)
+        .ok()
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        .and_then(|value| serde_json::from_value(value["data"].clone()).ok())
+        .unwrap_or_default();
+    Ok(items.into_iter()
+        .filter(|item| item.value.is_some() || item.name.is_some())
+        .map(|item| Entry {
+            module_name: module_name.to_string(),
+            kind: "data",
+            text: [item.name.as_deref(), item.value.as_deref()].into_iter().flatten().collect::<Vec<_>>().join(" "),
+            address: item.address,
+            name: item.name,
+        })
+        .collect())
+}
+
+fn watch_entries(conn: &Connection, target_os: &str) -> Result<Vec<Entry>, String> {
+    let mut stmt = conn.prepare("SELECT module_name, module_offset, label FROM watchlist WHERE target_os = ?1")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![target_os], |row| {
+        let label: String = row.get(2)?;
+        Ok(Entry {
+            module_name: row.get(0)?,
+            kind: "watch",
+            address: format!("0x{:x}", row.get::<_, i64>(1)?),
+            name: Some(label.clone()),
+            text: label,
+        })
+    }).map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+/// Re-read every source whose fingerprint changed since it was indexed and
+/// drop the units that no longer exist
+fn refresh(conn: &Connection, index: &mut SearchIndex) -> Result<(), String> {
+    let mut seen = Vec::new();
+    for source in ["functions", "decompile", "data", "watchlist"] {
+        for (target_os, scope, fingerprint) in fingerprints(conn, source)? {
+            let key = (source.to_string(), target_os.clone(), scope.clone());
+            seen.push(key.clone());
+            if index.fingerprints.get(&key) == Some(&fingerprint) {
+                continue;
+            }
+            let entries = match source {
+                "functions" => function_entries(conn, &target_os, &scope)?,
+                "decompile" => decompile_entries(conn, &target_os, &scope)?,
+                "data" => data_entries(conn, &target_os, &scope)?,
+                _ => watch_entries(conn, &target_os)?,
+            };
+            index.replace(source, &target_os, &scope, &entries)?;
+            index.fingerprints.insert(key, fingerprint);
+        }
+    }
+    let gone: Vec<_> = index.fingerprints.keys().filter(|key| !seen.contains(key)).cloned().collect();
+    for key in gone {
+        index.replace(&key.0, &key.1, &key.2, &[])?;
+        index.fingerprints.remove(&key);
+    }
+    Ok(())
+}
+
+/// Find `query` as a substring (case-insensitive) in function names,
+/// decompiled code and its comments, defined data and watchlist labels of
+/// every cached module. Best matches first.
+#[tauri::command]
+pub async fn search_everything(
+    query: String,
+    kinds: Option<Vec<String>>,
+    module_name: Option<String>,
+    target_os: Option<String>,
+    limit: Option<usize>,
+) -> Result<SearchEverythingResult, String> {
+    let query = query.trim().to_string();
+    if query.chars().count() < MIN_QUERY_LENGTH {
+        return Err(format!("Search text must be at least {} characters", MIN_QUERY_LENGTH));
+    }
+    let kinds = kinds.unwrap_or_default();
+    if let Some(kind) = kinds.iter().find(|k| !KINDS.contains(&k.as_str())) {
+        return Err(format!("Unknown kind '{}' (expected one of {})", kind, KINDS.join(", ")));
+    }
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    db::run(move |conn| {
+        let mut guard = INDEX.lock().map_err(|e| e.to_string())?;
+        if guard.is_none() {
+            *guard = Some(SearchIndex::new()?);
+        }
+        let index = guard.as_mut().ok_or("Search index unavailable")?;
+        refresh(conn, index)?;
+
+        // A quoted phrase: FTS5 syntax in the query is matched literally
+        let mut values: Vec<String> = vec![format!("\"{}\"", query.replace('"', "\"\""))];
+        let mut sql = format!(
+            "SELECT kind, target_os, module_name, address, name, snippet(search_entries, 7, '', '', '…', {})
+             FROM search_entries WHERE search_entries MATCH ?1",
+            SNIPPET_TOKENS
+        );
+        if !kinds.is_empty() {
+            sql.push_str(&format!(" AND kind IN ({})", vec!["?"; kinds.len()].join(", ")));
+            values.extend(kinds);
+        }
+        if let Some(module_name) = module_name {
+            sql.push_str(" AND module_name = ?");
+            values.push(module_name);
+        }
+        if let Some(target_os) = target_os {
+            sql.push_str(" AND target_os = ?");
+            values.push(target_os);
+        }
+        sql.push_str(&format!(" ORDER BY rank LIMIT {}", limit + 1));
+
+        let mut stmt = index.conn.prepare(&sql).map_err(|e| e.to_string())?;
+        let mut hits = stmt.query_map(params_from_iter(values.iter()), |row| Ok(SearchEverythingHit {
+            kind: row.get(0)?,
+            target_os: row.get(1)?,
+            module_name: row.get(2)?,
+            address: row.get(3)?,
+            name: row.get(4)?,
+            snippet: row.get(5)?,
+        })).map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        let truncated = hits.len() > limit;
+        hits.truncate(limit);
+        Ok(SearchEverythingResult { hits, truncated, indexed: index.len() })
+    }).await
+}
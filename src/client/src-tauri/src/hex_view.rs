@@ -0,0 +1,136 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::patches::{self, PatchInfo};
+use crate::state::AppStateType;
+use crate::{read_memory_from_server, server_address, write_memory_to_server};
+
+const MAX_PAGE_SIZE: usize = 64 * 1024;
+// Pages kept for diffing; the least recently read is dropped first
+const MAX_TRACKED_PAGES: usize = 32;
+
+/// One refresh of a hex view page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HexPage {
+    pub address: u64,
+    pub size: usize,
+    pub generation: u64,                 // Pass back on the next read to get a diff
+    pub readable: bool,
+    pub data: Option<Vec<u8>>,           // None when nothing changed since `generation`
+    pub changed: Vec<u8>,                // Bitmap, bit (i % 8) of byte i / 8 set if byte i changed
+    pub changed_count: usize,
+}
+
+struct TrackedPage {
+    generation: u64,
+    data: Option<Vec<u8>>,               // None while unreadable
+    last_used: u64,
+}
+
+// Last contents of each (address, size) page, for diffing against the next read
+static PAGES: Lazy<Mutex<HashMap<(u64, usize), TrackedPage>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static TICK: Lazy<Mutex<u64>> = Lazy::new(|| Mutex::new(0));
+
+fn next_tick() -> Result<u64, String> {
+    let mut tick = TICK.lock().map_err(|e| e.to_string())?;
+    *tick += 1;
+    Ok(*tick)
+}
+
+fn diff_bitmap(previous: &[u8], current: &[u8]) -> (Vec<u8>, usize) {
+    let mut bitmap = vec![0u8; current.len().div_ceil(8)];
+    let mut count = 0;
+    for (i, (a, b)) in previous.iter().zip(current).enumerate() {
+        if a != b {
+            bitmap[i / 8] |= 1 << (i % 8);
+            count += 1;
+        }
+    }
+    (bitmap, count)
+}
+
+/// Read a hex view page. With the `generation` from the previous read of the
+/// same page, the result carries a bitmap of bytes changed since then and
+/// omits `data` when nothing changed; otherwise the full page is returned.
+#[tauri::command]
+pub async fn read_memory_page(address: u64, size: usize, generation: Option<u64>) -> Result<HexPage, String> {
+    if size == 0 || size > MAX_PAGE_SIZE {
+        return Err(format!("Page size must be between 1 and {} bytes", MAX_PAGE_SIZE));
+    }
+    let (host, port) = server_address()?;
+    let current = read_memory_from_server(&host, port, address, size).await.ok()
+        .filter(|data| data.len() == size);
+    let tick = next_tick()?;
+
+    let mut pages = PAGES.lock().map_err(|e| e.to_string())?;
+    let previous = pages.get(&(address, size)).filter(|p| Some(p.generation) == generation);
+    let (changed, changed_count, unchanged) = match (previous.and_then(|p| p.data.as_deref()), current.as_deref()) {
+        (Some(previous), Some(current)) => {
+            let (bitmap, count) = diff_bitmap(previous, current);
+            (bitmap, count, count == 0)
+        }
+        (None, None) if previous.is_some() => (vec![0u8; size.div_ceil(8)], 0, true),
+        _ => (vec![0u8; size.div_ceil(8)], 0, false),
+    };
+    let page_generation = match (pages.get(&(address, size)), unchanged) {
+        (Some(page), true) => page.generation,
+        (Some(page), false) => page.generation + 1,
+        (None, _) => 1,
+    };
+
+    if !pages.contains_key(&(address, size)) && pages.len() >= MAX_TRACKED_PAGES {
+        if let Some(oldest) = pages.iter().min_by_key(|(_, p)| p.last_used).map(|(key, _)| *key) {
+            pages.remove(&oldest);
+        }
+    }
+    pages.insert((address, size), TrackedPage { generation: page_generation, data: current.clone(), last_used: tick });
+
+    Ok(HexPage {
+        address,
+        size,
+        generation: page_generation,
+        readable: current.is_some(),
+        data: if unchanged { None } else { current },
+        changed,
+        changed_count,
+    })
+}
+
+/// Write bytes from the hex editor. With `record_patch` the write goes
+/// through the patch tracker so it can be reverted later.
+#[tauri::command]
+pub async fn write_memory_bytes(
+    state: tauri::State<'_, AppStateType>,
+    address: u64,
+    bytes: Vec<u8>,
+    record_patch: Option<bool>,
+) -> Result<Option<PatchInfo>, String> {
+    if bytes.is_empty() {
+        return Err("Nothing to write".to_string());
+    }
+    let patch = if record_patch.unwrap_or(false) {
+        Some(patches::apply(state.inner(), address, &bytes, Some("Hex editor".to_string())).await?)
+    } else {
+        let (host, port) = server_address()?;
+        write_memory_to_server(&host, port, address, &bytes).await?;
+        None
+    };
+
+    // Fold the edit into tracked pages so it is not reported as an external change
+    let end = address.saturating_add(bytes.len() as u64);
+    let mut pages = PAGES.lock().map_err(|e| e.to_string())?;
+    for (&(page_address, page_size), page) in pages.iter_mut() {
+        let Some(data) = page.data.as_mut() else { continue };
+        let page_end = page_address.saturating_add(page_size as u64);
+        if address >= page_end || end <= page_address {
+            continue;
+        }
+        let start = address.max(page_address);
+        let stop = end.min(page_end);
+        data[(start - page_address) as usize..(stop - page_address) as usize]
+            .copy_from_slice(&bytes[(start - address) as usize..(stop - address) as usize]);
+    }
+    Ok(patch)
+}
@@ -0,0 +1,594 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::build_id::{elf_phdr_table_size, u16_at, u32_at, u64_at};
+use crate::state::{AppStateType, ModuleInfo};
+use crate::{disassemble_bytes, read_memory_from_server, SERVER_CONFIG};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportHookScanRequest {
+    #[serde(default)]
+    pub module_names: Option<Vec<String>>,  // Modules to check (default: all attached modules)
+    #[serde(default)]
+    pub architecture: Option<String>,       // For hook disassembly (default: server arch)
+    #[serde(default)]
+    pub include_clean: bool,                // Also return entries that look normal
+}
+
+/// One GOT/IAT slot and where it currently points
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportHookEntry {
+    pub module_name: String,
+    pub kind: String,                     // "iat" | "got"
+    pub library: Option<String>,          // Importing DLL (PE) - ELF does not record it per symbol
+    pub symbol: String,
+    pub slot_address: u64,
+    pub target_address: u64,
+    pub target_module: Option<String>,
+    pub target_offset: Option<u64>,       // Offset inside target_module
+    pub status: String,                   // "ok" | "unresolved" | "redirected" | "hooked"
+    pub suspicious: bool,
+    pub hook_disassembly: Option<String>, // First instructions at the target for suspicious entries
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportHookScanResult {
+    pub success: bool,
+    pub scanned_modules: usize,
+    pub total_entries: usize,
+    pub suspicious_count: usize,
+    pub entries: Vec<ImportHookEntry>,
+    pub module_errors: Vec<String>,
+    pub error: Option<String>,
+}
+
+const PAGE_SIZE: u64 = 0x1000;
+// Upper bounds so a corrupted header cannot make the scan read forever
+const MAX_IMPORT_DESCRIPTORS: usize = 1024;
+const MAX_THUNKS: usize = 16384;
+const MAX_DYNAMIC_ENTRIES: usize = 1024;
+const MAX_EXPORT_NAMES: u64 = 65536;
+const MAX_FORWARD_HOPS: usize = 8;
+const HOOK_DISASM_BYTES: usize = 32;
+
+/// Page-cached reader for the target's memory; import tables and their names
+/// are usually clustered, so this keeps the number of server round trips low
+struct RemoteReader {
+    host: String,
+    port: u16,
+    pages: HashMap<u64, Option<Vec<u8>>>,
+}
+
+impl RemoteReader {
+    async fn read(&mut self, address: u64, size: usize) -> Option<Vec<u8>> {
+        let mut out = Vec::with_capacity(size);
+        let mut addr = address;
+        let end = address.checked_add(size as u64)?;
+        while addr < end {
+            let page = addr & !(PAGE_SIZE - 1);
+            if !self.pages.contains_key(&page) {
+                let data = read_memory_from_server(&self.host, self.port, page, PAGE_SIZE as usize).await.ok()
+                    .filter(|d| d.len() == PAGE_SIZE as usize);
+                self.pages.insert(page, data);
+            }
+            let data = self.pages.get(&page)?.as_ref()?;
+            let from = (addr - page) as usize;
+            let to = ((end - page) as usize).min(PAGE_SIZE as usize);
+            out.extend_from_slice(&data[from..to]);
+            addr = page + to as u64;
+        }
+        Some(out)
+    }
+
+    async fn u16(&mut self, address: u64) -> Option<u16> {
+        Some(u16::from_le_bytes(self.read(address, 2).await?.try_into().ok()?))
+    }
+
+    async fn u32(&mut self, address: u64) -> Option<u32> {
+        Some(u32::from_le_bytes(self.read(address, 4).await?.try_into().ok()?))
+    }
+
+    async fn u64(&mut self, address: u64) -> Option<u64> {
+        Some(u64::from_le_bytes(self.read(address, 8).await?.try_into().ok()?))
+    }
+
+    async fn ptr(&mut self, address: u64, is_64: bool) -> Option<u64> {
+        if is_64 {
+            self.u64(address).await
+        } else {
+            self.u32(address).await.map(u64::from)
+        }
+    }
+
+    async fn c_string(&mut self, address: u64) -> Option<String> {
+        let mut bytes = Vec::new();
+        let mut addr = address;
+        while bytes.len() < 1024 {
+            // Read up to the end of the current page to avoid faulting on the next one
+            let chunk = self.read(addr, (PAGE_SIZE - (addr % PAGE_SIZE)) as usize).await?;
+            if let Some(nul) = chunk.iter().position(|&b| b == 0) {
+                bytes.extend_from_slice(&chunk[..nul]);
+                return Some(String::from_utf8_lossy(&bytes).to_string());
+            }
+            bytes.extend_from_slice(&chunk);
+            addr += chunk.len() as u64;
+        }
+        Some(String::from_utf8_lossy(&bytes).to_string())
+    }
+}
+
+/// File name of a module path, lowercased for comparison
+fn module_key(name: &str) -> String {
+    name.rsplit(['/', '\\']).next().unwrap_or(name).to_lowercase()
+}
+
+/// Module containing `address`, if any
+fn classify_pointer(modules: &[ModuleInfo], address: u64) -> Option<&ModuleInfo> {
+    modules.iter().find(|m| address >= m.base && address < m.base.saturating_add(m.size))
+}
+
+/// Raw import slot before classification
+struct ImportSlot {
+    kind: &'static str,
+    library: Option<String>,
+    symbol: String,
+    slot_address: u64,
+    target_address: u64,
+}
+
+/// PE: (address of the data directory array, is 64-bit)
+async fn pe_data_directories(reader: &mut RemoteReader, base: u64) -> Result<(u64, bool), String> {
+    let e_lfanew = reader.u32(base + 0x3c).await.ok_or("Failed to read DOS header")? as u64;
+    let nt = base + e_lfanew;
+    if reader.u32(nt).await != Some(0x4550) {
+        return Err("Invalid PE signature".to_string());
+    }
+    let optional = nt + 24;
+    let is_64 = match reader.u16(optional).await {
+        Some(0x20b) => true,
+        Some(0x10b) => false,
+        _ => return Err("Unknown optional header magic".to_string()),
+    };
+    Ok((optional + if is_64 { 112 } else { 96 }, is_64))
+}
+
+/// PE export: an address, or a forwarder string ("KERNELBASE.Sleep", "NTDLL.#12")
+enum PeExport {
+    Address(u64),
+    Forward(String),
+}
+
+/// Look up `symbol` ("Name" or "Ordinal_N") in the export directory of the module at `base`
+async fn read_pe_export(reader: &mut RemoteReader, base: u64, symbol: &str) -> Option<PeExport> {
+    let (data_dirs, _) = pe_data_directories(reader, base).await.ok()?;
+    let export_rva = reader.u32(data_dirs).await? as u64;
+    let export_size = reader.u32(data_dirs + 4).await? as u64;
+    if export_rva == 0 {
+        return None;
+    }
+    let dir = base + export_rva;
+    let ordinal_base = reader.u32(dir + 16).await? as u64;
+    let function_count = reader.u32(dir + 20).await? as u64;
+    let name_count = (reader.u32(dir + 24).await? as u64).min(MAX_EXPORT_NAMES);
+    let functions = base + reader.u32(dir + 28).await? as u64;
+    let names = base + reader.u32(dir + 32).await? as u64;
+    let name_ordinals = base + reader.u32(dir + 36).await? as u64;
+
+    let index = if let Some(ordinal) = symbol.strip_prefix("Ordinal_") {
+        ordinal.parse::<u64>().ok()?.checked_sub(ordinal_base)?
+    } else {
+        // The name table is sorted, so binary search it
+        let (mut low, mut high) = (0, name_count);
+        loop {
+            if low >= high {
+                return None;
+            }
+            let mid = (low + high) / 2;
+            let name_rva = reader.u32(names + mid * 4).await? as u64;
+            let name = reader.c_string(base + name_rva).await?;
+            match name.as_str().cmp(symbol) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => break reader.u16(name_ordinals + mid * 2).await? as u64,
+            }
+        }
+    };
+    if index >= function_count {
+        return None;
+    }
+    let rva = reader.u32(functions + index * 4).await? as u64;
+    // An RVA inside the export directory points at a forwarder string
+    if rva >= export_rva && rva < export_rva + export_size {
+        Some(PeExport::Forward(reader.c_string(base + rva).await?))
+    } else {
+        Some(PeExport::Address(base + rva))
+    }
+}
+
+/// Address `library!symbol` resolves to after following export forwarders
+/// (kernel32 -> kernelbase, ...). API set names are looked up in `host`, the
+/// module the slot actually points into.
+async fn resolve_pe_import(
+    reader: &mut RemoteReader,
+    modules: &[ModuleInfo],
+    library: &str,
+    symbol: &str,
+    host: Option<&ModuleInfo>,
+) -> Option<u64> {
+    let mut library = module_key(library);
+    let mut symbol = symbol.to_string();
+    for _ in 0..MAX_FORWARD_HOPS {
+        let module = modules.iter()
+            .find(|m| module_key(&m.modulename) == library)
+            .or(if is_api_set(&library) { host } else { None })?;
+        match read_pe_export(reader, module.base, &symbol).await? {
+            PeExport::Address(address) => return Some(address),
+            PeExport::Forward(forward) => {
+                let (dll, name) = forward.rsplit_once('.')?;
+                library = dll.to_lowercase();
+                if !library.ends_with(".dll") {
+                    library.push_str(".dll");
+                }
+                symbol = match name.strip_prefix('#') {
+                    Some(ordinal) => format!("Ordinal_{}", ordinal),
+                    None => name.to_string(),
+                };
+            }
+        }
+    }
+    None
+}
+
+/// PE: walk the import directory and pair each IAT slot with its name from the INT
+async fn read_pe_imports(reader: &mut RemoteReader, base: u64) -> Result<Vec<ImportSlot>, String> {
+    let (data_dirs, is_64) = pe_data_directories(reader, base).await?;
+    let import_rva = reader.u32(data_dirs + 8).await.ok_or("Failed to read import directory")?;
+    if import_rva == 0 {
+        return Ok(Vec::new());
+    }
+
+    let ptr_size = if is_64 { 8 } else { 4 };
+    let ordinal_flag = if is_64 { 1u64 << 63 } else { 1u64 << 31 };
+    let mut slots = Vec::new();
+
+    for index in 0..MAX_IMPORT_DESCRIPTORS {
+        let desc = base + import_rva as u64 + index as u64 * 20;
+        let Some(raw) = reader.read(desc, 20).await else {
+            break;
+        };
+        let field = |off: usize| u32::from_le_bytes(raw[off..off + 4].try_into().unwrap()) as u64;
+        let (original_first_thunk, name_rva, first_thunk) = (field(0), field(12), field(16));
+        if name_rva == 0 && first_thunk == 0 {
+            break;
+        }
+        let library = reader.c_string(base + name_rva).await.unwrap_or_default();
+        // Bound or stripped imports have no INT; names are then unavailable
+        let name_table = if original_first_thunk != 0 { original_first_thunk } else { first_thunk };
+
+        for thunk in 0..MAX_THUNKS {
+            let slot_address = base + first_thunk + (thunk * ptr_size) as u64;
+            let Some(target_address) = reader.ptr(slot_address, is_64).await else {
+                break;
+            };
+            if target_address == 0 {
+                break;
+            }
+            let lookup = reader.ptr(base + name_table + (thunk * ptr_size) as u64, is_64).await.unwrap_or(0);
+            let symbol = if original_first_thunk == 0 {
+                format!("#{}", thunk)
+            } else if lookup & ordinal_flag != 0 {
+                format!("Ordinal_{}", lookup & 0xffff)
+            } else {
+                // IMAGE_IMPORT_BY_NAME: u16 hint followed by the name
+                reader.c_string(base + (lookup & 0x7fff_ffff) + 2).await.unwrap_or_default()
+            };
+            slots.push(ImportSlot {
+                kind: "iat",
+                library: Some(library.clone()),
+                symbol,
+                slot_address,
+                target_address,
+            });
+        }
+    }
+
+    Ok(slots)
+}
+
+/// ELF: resolve GLOB_DAT / JUMP_SLOT relocations from the dynamic section.
+/// Returns the slots and the DT_NEEDED library names.
+async fn read_elf_imports(reader: &mut RemoteReader, module: &ModuleInfo) -> Result<(Vec<ImportSlot>, Vec<String>), String> {
+    const PT_LOAD: u32 = 1;
+    const PT_DYNAMIC: u32 = 2;
+    const DT_NEEDED: u64 = 1;
+    const DT_PLTRELSZ: u64 = 2;
+    const DT_STRTAB: u64 = 5;
+    const DT_SYMTAB: u64 = 6;
+    const DT_RELA: u64 = 7;
+    const DT_RELASZ: u64 = 8;
+    const DT_REL: u64 = 17;
+    const DT_RELSZ: u64 = 18;
+    const DT_PLTREL: u64 = 20;
+    const DT_JMPREL: u64 = 23;
+
+    let base = module.base;
+    let header = reader.read(base, 64).await.ok_or("Failed to read ELF header")?;
+    let is_64 = header.get(4) == Some(&2);
+    if header.get(5) != Some(&1) {
+        return Err("Big-endian ELF is not supported".to_string());
+    }
+    let machine = u16_at(&header, 0x12, false);
+    let (phoff, phentsize, phnum) = if is_64 {
+        (u64_at(&header, 0x20, false), u16_at(&header, 0x36, false), u16_at(&header, 0x38, false))
+    } else {
+        (u32_at(&header, 0x1c, false).map(u64::from), u16_at(&header, 0x2a, false), u16_at(&header, 0x2c, false))
+    };
+    let (Some(machine), Some(phoff), Some(phentsize), Some(phnum)) = (machine, phoff, phentsize, phnum) else {
+        return Err("Truncated ELF header".to_string());
+    };
+    let (phentsize, phnum) = (phentsize as usize, phnum as usize);
+    let table_size = elf_phdr_table_size(is_64, phentsize, phnum)?;
+
+    // (GLOB_DAT, JUMP_SLOT) relocation types for the machine
+    let (glob_dat, jump_slot) = match machine {
+        62 | 3 => (6, 7),        // x86_64, i386
+        183 => (1025, 1026),     // aarch64
+        40 => (21, 22),          // arm
+        _ => return Err(format!("Unsupported ELF machine {}", machine)),
+    };
+
+    let phdrs = reader.read(base.wrapping_add(phoff), table_size).await
+        .ok_or("Failed to read program headers")?;
+    let mut min_vaddr = u64::MAX;
+    let mut dynamic_vaddr = None;
+    for ph in phdrs.chunks_exact(phentsize) {
+        let p_type = u32_at(ph, 0, false);
+        let p_vaddr = if is_64 { u64_at(ph, 16, false) } else { u32_at(ph, 8, false).map(u64::from) };
+        let (Some(p_type), Some(p_vaddr)) = (p_type, p_vaddr) else {
+            continue;
+        };
+        match p_type {
+            PT_LOAD => min_vaddr = min_vaddr.min(p_vaddr),
+            PT_DYNAMIC => dynamic_vaddr = Some(p_vaddr),
+            _ => {}
+        }
+    }
+    let bias = base.wrapping_sub(if min_vaddr == u64::MAX { 0 } else { min_vaddr });
+    let Some(dynamic_vaddr) = dynamic_vaddr else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+
+    // glibc rewrites some d_ptr values to absolute addresses, bionic does not
+    let to_absolute = |ptr: u64| {
+        if ptr >= base && ptr < base.saturating_add(module.size) { ptr } else { ptr.wrapping_add(bias) }
+    };
+
+    let entry_size = if is_64 { 16 } else { 8 };
+    let mut dynamic: Vec<(u64, u64)> = Vec::new();
+    for i in 0..MAX_DYNAMIC_ENTRIES as u64 {
+        let addr = dynamic_vaddr.wrapping_add(bias) + i * entry_size;
+        let (tag, value) = if is_64 {
+            (reader.u64(addr).await, reader.u64(addr + 8).await)
+        } else {
+            (reader.u32(addr).await.map(u64::from), reader.u32(addr + 4).await.map(u64::from))
+        };
+        let (Some(tag), Some(value)) = (tag, value) else {
+            break;
+        };
+        if tag == 0 {
+            break;
+        }
+        dynamic.push((tag, value));
+    }
+    let get = |tag: u64| dynamic.iter().find(|(t, _)| *t == tag).map(|(_, v)| *v);
+
+    let strtab = get(DT_STRTAB).map(to_absolute).ok_or("Missing DT_STRTAB")?;
+    let symtab = get(DT_SYMTAB).map(to_absolute).ok_or("Missing DT_SYMTAB")?;
+
+    let mut needed = Vec::new();
+    for (_, offset) in dynamic.iter().filter(|(t, _)| *t == DT_NEEDED) {
+        if let Some(name) = reader.c_string(strtab + offset).await {
+            needed.push(name);
+        }
+    }
+
+    // Relocation tables to walk: (address, size, is_rela)
+    let mut tables = Vec::new();
+    if let (Some(rela), Some(size)) = (get(DT_RELA), get(DT_RELASZ)) {
+        tables.push((to_absolute(rela), size, true));
+    }
+    if let (Some(rel), Some(size)) = (get(DT_REL), get(DT_RELSZ)) {
+        tables.push((to_absolute(rel), size, false));
+    }
+    if let (Some(jmprel), Some(size)) = (get(DT_JMPREL), get(DT_PLTRELSZ)) {
+        tables.push((to_absolute(jmprel), size, get(DT_PLTREL) == Some(DT_RELA)));
+    }
+
+    let ptr_size = if is_64 { 8 } else { 4 };
+    let sym_size = if is_64 { 24 } else { 16 };
+    let mut slots = Vec::new();
+    for (table, size, is_rela) in tables {
+        let reloc_size = ptr_size * if is_rela { 3 } else { 2 };
+        for i in 0..(size / reloc_size).min(MAX_THUNKS as u64) {
+            let entry = table + i * reloc_size;
+            let (Some(r_offset), Some(r_info)) = (reader.ptr(entry, is_64).await, reader.ptr(entry + ptr_size, is_64).await) else {
+                break;
+            };
+            let (sym_index, r_type) = if is_64 {
+                (r_info >> 32, r_info & 0xffff_ffff)
+            } else {
+                (r_info >> 8, r_info & 0xff)
+            };
+            if (r_type != glob_dat && r_type != jump_slot) || sym_index == 0 {
+                continue;
+            }
+            let slot_address = r_offset.wrapping_add(bias);
+            let Some(target_address) = reader.ptr(slot_address, is_64).await else {
+                continue;
+            };
+            let name_offset = reader.u32(symtab + sym_index * sym_size).await.unwrap_or(0);
+            let symbol = reader.c_string(strtab + name_offset as u64).await.unwrap_or_default();
+            slots.push(ImportSlot {
+                kind: "got",
+                library: None,
+                symbol,
+                slot_address,
+                target_address,
+            });
+        }
+    }
+
+    Ok((slots, needed))
+}
+
+fn is_api_set(library: &str) -> bool {
+    let lower = library.to_lowercase();
+    lower.starts_with("api-ms-") || lower.starts_with("ext-ms-")
+}
+
+/// Scan the GOT (ELF) / IAT (PE) of loaded modules and flag entries that
+/// point outside the library they are expected to come from.
+/// "redirected" = inside another loaded module (interposed library; PE export
+/// forwarders are followed and not flagged),
+/// "hooked" = outside every known module (typically injected trampoline code).
+#[tauri::command]
+pub async fn scan_import_hooks(
+    state: tauri::State<'_, AppStateType>,
+    request: ImportHookScanRequest,
+) -> Result<ImportHookScanResult, String> {
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+
+    let mut result = ImportHookScanResult {
+        success: false,
+        scanned_modules: 0,
+        total_entries: 0,
+        suspicious_count: 0,
+        entries: Vec::new(),
+        module_errors: Vec::new(),
+        error: None,
+    };
+
+    if host.is_empty() {
+        result.error = Some("No server connection configured".to_string());
+        return Ok(result);
+    }
+
+    let (modules, server_arch) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        (
+            state_guard.attached_modules.clone(),
+            state_guard.server_info.as_ref().map(|s| s.arch.clone()),
+        )
+    };
+    if modules.is_empty() {
+        result.error = Some("No module list available".to_string());
+        return Ok(result);
+    }
+    let architecture = request.architecture.clone().or(server_arch).unwrap_or_else(|| "x86_64".to_string());
+
+    let wanted: Option<Vec<String>> = request.module_names.as_ref()
+        .map(|names| names.iter().map(|n| module_key(n)).collect());
+    let targets: Vec<&ModuleInfo> = modules.iter()
+        .filter(|m| wanted.as_ref().is_none_or(|w| w.contains(&module_key(&m.modulename))))
+        .collect();
+
+    let mut reader = RemoteReader { host, port, pages: HashMap::new() };
+
+    for module in targets {
+        let Some(magic) = reader.read(module.base, 4).await else {
+            result.module_errors.push(format!("{}: failed to read header", module.modulename));
+            continue;
+        };
+        let imports = if magic.starts_with(b"MZ") {
+            read_pe_imports(&mut reader, module.base).await.map(|slots| (slots, Vec::new()))
+        } else if magic == b"\x7fELF" {
+            read_elf_imports(&mut reader, module).await
+        } else {
+            Err("Unsupported image format".to_string())
+        };
+        let (slots, needed) = match imports {
+            Ok(v) => v,
+            Err(e) => {
+                result.module_errors.push(format!("{}: {}", module.modulename, e));
+                continue;
+            }
+        };
+        result.scanned_modules += 1;
+        let needed: Vec<String> = needed.iter().map(|n| module_key(n)).collect();
+        let own_key = module_key(&module.modulename);
+
+        for slot in slots {
+            result.total_entries += 1;
+            let target = classify_pointer(&modules, slot.target_address);
+            let target_key = target.map(|m| module_key(&m.modulename));
+
+            let mut status = match (&target_key, &slot.library) {
+                (None, _) if slot.target_address == 0 => "unresolved",
+                (None, _) => "hooked",
+                // Lazy binding: the slot still points back at the module's own PLT
+                (Some(key), _) if *key == own_key && slot.kind == "got" => "unresolved",
+                (Some(key), Some(library)) => {
+                    if *key == module_key(library) || is_api_set(library) {
+                        "ok"
+                    } else {
+                        "redirected"
+                    }
+                }
+                (Some(key), None) => {
+                    // ELF symbols may resolve to any loaded library; accept
+                    // direct dependencies, the dynamic linker and libc
+                    if needed.contains(key) || key.starts_with("ld-") || key.starts_with("linker") || key.starts_with("libc.") {
+                        "ok"
+                    } else {
+                        "redirected"
+                    }
+                }
+            };
+            // A forwarded export legitimately lives in another module
+            if status == "redirected" && slot.kind == "iat" {
+                if let Some(library) = &slot.library {
+                    if resolve_pe_import(&mut reader, &modules, library, &slot.symbol, target).await == Some(slot.target_address) {
+                        status = "ok";
+                    }
+                }
+            }
+            let suspicious = matches!(status, "redirected" | "hooked");
+            if suspicious {
+                result.suspicious_count += 1;
+            } else if !request.include_clean {
+                continue;
+            }
+
+            let hook_disassembly = if suspicious {
+                match reader.read(slot.target_address, HOOK_DISASM_BYTES).await {
+                    Some(bytes) => disassemble_bytes(bytes, slot.target_address, architecture.clone(), None)
+                        .ok()
+                        .and_then(|r| r.disassembly),
+                    None => None,
+                }
+            } else {
+                None
+            };
+
+            result.entries.push(ImportHookEntry {
+                module_name: module.modulename.clone(),
+                kind: slot.kind.to_string(),
+                library: slot.library,
+                symbol: slot.symbol,
+                slot_address: slot.slot_address,
+                target_address: slot.target_address,
+                target_module: target.map(|m| m.modulename.clone()),
+                target_offset: target.map(|m| slot.target_address - m.base),
+                status: status.to_string(),
+                suspicious,
+                hook_disassembly,
+            });
+        }
+    }
+
+    result.success = true;
+    Ok(result)
+}
@@ -0,0 +1,187 @@
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::state::AppStateType;
+use crate::{db, region_snapshots, scripting, server_connection, watchlist, SERVER_CONFIG};
+
+/// Native action run when a global hotkey is pressed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HotkeyAction {
+    ToggleFreeze { entry_id: i64 },     // Watch list entry
+    RunScript { path: String },
+    Pause,
+    Resume,
+    Snapshot { address: u64, size: u64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    pub accelerator: String,             // e.g. "Ctrl+Shift+F1"
+    pub action: HotkeyAction,
+}
+
+/// Emitted as "hotkey://triggered" after each action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyTriggered {
+    pub accelerator: String,
+    pub action: HotkeyAction,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+// Registered bindings by shortcut id
+static BINDINGS: Lazy<Mutex<HashMap<u32, HotkeyBinding>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Create the hotkeys table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS hotkeys (
+            accelerator TEXT PRIMARY KEY,
+            action_json TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn parse_shortcut(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator.trim().parse::<Shortcut>().map_err(|e| format!("Invalid hotkey '{}': {}", accelerator, e))
+}
+
+fn stored_bindings(conn: &Connection) -> Result<Vec<HotkeyBinding>, String> {
+    let mut stmt = conn.prepare("SELECT accelerator, action_json FROM hotkeys ORDER BY accelerator")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?;
+    Ok(rows.flatten()
+        .filter_map(|(accelerator, json)| Some(HotkeyBinding { accelerator, action: serde_json::from_str(&json).ok()? }))
+        .collect())
+}
+
+/// Register the stored bindings with the OS (called from setup)
+pub fn restore(app: &AppHandle) {
+    let bindings = match db::run_sync(stored_bindings) {
+        Ok(bindings) => bindings,
+        Err(e) => {
+            eprintln!("Failed to load hotkeys: {}", e);
+            return;
+        }
+    };
+    for binding in bindings {
+        let registered = parse_shortcut(&binding.accelerator).and_then(|shortcut| {
+            app.global_shortcut().register(shortcut).map_err(|e| e.to_string())?;
+            BINDINGS.lock().map_err(|e| e.to_string())?.insert(shortcut.id(), binding.clone());
+            Ok(())
+        });
+        if let Err(e) = registered {
+            eprintln!("Failed to register hotkey {}: {}", binding.accelerator, e);
+        }
+    }
+}
+
+/// Pause (`running` = false) or resume the attached process
+async fn set_process_running(running: bool) -> Result<(), String> {
+    let (host, port, auth_token) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        if config.host.is_empty() {
+            return Err("No server connection configured".to_string());
+        }
+        (config.host.clone(), config.port, config.auth_token.clone())
+    };
+    let url = format!("{}/api/process/state", server_connection::base_url(&host, port));
+    let mut request = server_connection::client()?.put(&url).json(&serde_json::json!({ "do_play": running }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let response = server_connection::send(request).await?;
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    Ok(())
+}
+
+async fn run_action(app: &AppHandle, action: &HotkeyAction) -> Result<(), String> {
+    match action {
+        HotkeyAction::ToggleFreeze { entry_id } => {
+            watchlist::toggle_frozen(app.state::<AppStateType>().inner(), *entry_id).await.map(|_| ())
+        }
+        HotkeyAction::RunScript { path } => scripting::run_script(app.clone(), path.clone(), None).await.map(|_| ()),
+        HotkeyAction::Pause => set_process_running(false).await,
+        HotkeyAction::Resume => set_process_running(true).await,
+        HotkeyAction::Snapshot { address, size } => {
+            region_snapshots::snapshot_region(*address, *size, Some("Hotkey snapshot".to_string())).await.map(|_| ())
+        }
+    }
+}
+
+/// Global shortcut handler: runs the bound action even while the window is
+/// unfocused and reports the outcome as "hotkey://triggered"
+pub fn handle_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let Some(binding) = BINDINGS.lock().ok().and_then(|b| b.get(&shortcut.id()).cloned()) else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = run_action(&app, &binding.action).await;
+        let _ = app.emit("hotkey://triggered", &HotkeyTriggered {
+            accelerator: binding.accelerator,
+            action: binding.action,
+            success: result.is_ok(),
+            error: result.err(),
+        });
+    });
+}
+
+/// Bind a hotkey to an action, replacing any earlier binding of the same keys
+#[tauri::command]
+pub async fn bind_hotkey(app: AppHandle, accelerator: String, action: HotkeyAction) -> Result<HotkeyBinding, String> {
+    let accelerator = accelerator.trim().to_string();
+    let shortcut = parse_shortcut(&accelerator)?;
+    let binding = HotkeyBinding { accelerator: accelerator.clone(), action };
+    {
+        let mut bindings = BINDINGS.lock().map_err(|e| e.to_string())?;
+        if !bindings.contains_key(&shortcut.id()) {
+            app.global_shortcut().register(shortcut)
+                .map_err(|e| format!("Failed to register {} (in use by another application?): {}", accelerator, e))?;
+        }
+        bindings.insert(shortcut.id(), binding.clone());
+    }
+
+    let action_json = serde_json::to_string(&binding.action).map_err(|e| e.to_string())?;
+    db::run(move |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO hotkeys (accelerator, action_json, updated_at) VALUES (?1, ?2, datetime('now'))",
+            params![accelerator, action_json],
+        ).map_err(|e| e.to_string())
+    }).await?;
+    Ok(binding)
+}
+
+#[tauri::command]
+pub async fn unbind_hotkey(app: AppHandle, accelerator: String) -> Result<bool, String> {
+    let shortcut = parse_shortcut(&accelerator)?;
+    let removed = BINDINGS.lock().map_err(|e| e.to_string())?.remove(&shortcut.id());
+    if removed.is_some() {
+        app.global_shortcut().unregister(shortcut).map_err(|e| e.to_string())?;
+    }
+    let accelerator = removed.map(|b| b.accelerator).unwrap_or_else(|| accelerator.trim().to_string());
+    let deleted = db::run(move |conn| {
+        conn.execute("DELETE FROM hotkeys WHERE accelerator = ?1", params![accelerator]).map_err(|e| e.to_string())
+    }).await?;
+    Ok(deleted > 0)
+}
+
+#[tauri::command]
+pub async fn list_hotkeys() -> Result<Vec<HotkeyBinding>, String> {
+    db::run(stored_bindings).await
+}
@@ -0,0 +1,834 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::build_id::{u16_at, u32_at, u64_at};
+use crate::state::AppStateType;
+use crate::{cache_versions, db, objc_metadata, secure_store, symbolizer};
+
+const METADATA_SANITY: u32 = 0xFAB1_1BAF;
+const MIN_VERSION: i32 = 24;
+const MAX_VERSION: i32 = 31;
+const MAX_TYPE_NAME_DEPTH: u32 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Il2CppMethod {
+    pub name: String,
+    pub module_offset: Option<u64>,      // None for abstract, extern and uninstantiated generic methods
+    pub token: u32,
+    pub flags: u16,                      // MethodAttributes
+    pub parameter_count: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Il2CppField {
+    pub name: String,
+    pub type_name: Option<String>,       // Needs the metadata registration in the binary
+    pub offset: Option<i32>,             // Instance offset, or offset in the static fields block
+    pub is_static: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Il2CppClass {
+    pub type_index: u32,
+    pub full_name: String,               // Namespace.Outer.Name
+    pub namespace: String,
+    pub name: String,
+    pub image: String,                   // "Assembly-CSharp.dll"
+    pub parent: Option<String>,
+    pub flags: u32,                      // TypeAttributes
+    pub methods: Vec<Il2CppMethod>,
+    pub fields: Vec<Il2CppField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Il2CppClassSummary {
+    pub type_index: u32,
+    pub full_name: String,
+    pub image: String,
+    pub method_count: usize,
+    pub field_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Il2CppLoadResult {
+    pub module_name: String,
+    pub metadata_version: i32,
+    pub images: usize,
+    pub classes: usize,
+    pub methods: usize,
+    pub methods_with_address: usize,
+    pub fields: usize,
+    pub fields_with_offset: usize,
+    pub functions_added: usize,          // New or renamed entries of the function list
+    pub warnings: Vec<String>,
+}
+
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS il2cpp_classes (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            type_index INTEGER NOT NULL,
+            full_name TEXT NOT NULL,
+            namespace TEXT NOT NULL,
+            name TEXT NOT NULL,
+            image TEXT NOT NULL,
+            parent TEXT,
+            flags INTEGER NOT NULL,
+            PRIMARY KEY(target_os, module_name, type_index)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS il2cpp_methods (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            type_index INTEGER NOT NULL,
+            idx INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            module_offset INTEGER,
+            token INTEGER NOT NULL,
+            flags INTEGER NOT NULL,
+            parameter_count INTEGER NOT NULL,
+            PRIMARY KEY(target_os, module_name, type_index, idx)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS il2cpp_fields (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            type_index INTEGER NOT NULL,
+            idx INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            type_name TEXT,
+            field_offset INTEGER,
+            is_static INTEGER NOT NULL,
+            PRIMARY KEY(target_os, module_name, type_index, idx)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_il2cpp_classes_name ON il2cpp_classes(target_os, module_name, full_name)",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Loaded segment of the IL2CPP binary
+struct Segment {
+    vaddr: u64,
+    file_offset: u64,
+    size: u64,                           // Bytes present in the file
+    executable: bool,
+}
+
+/// 64-bit little-endian ELF, PE or Mach-O image, addressed by preferred virtual address
+struct Binary {
+    data: Vec<u8>,
+    segments: Vec<Segment>,
+    image_base: u64,                     // Module offsets are relative to this
+    relocations: HashMap<u64, u64>,      // ELF RELATIVE relocations: slot -> value
+    macho: bool,
+}
+
+impl Binary {
+    fn parse(data: Vec<u8>, warnings: &mut Vec<String>) -> Result<Self, String> {
+        match data.get(0..4) {
+            Some([0x7f, b'E', b'L', b'F']) => Self::parse_elf(data, warnings),
+            Some([b'M', b'Z', ..]) => Self::parse_pe(data),
+            Some([0xcf, 0xfa, 0xed, 0xfe]) | Some([0xca, 0xfe, 0xba, 0xbe]) | Some([0xca, 0xfe, 0xba, 0xbf]) => {
+                Self::parse_macho(objc_metadata::thin_slice(data)?)
+            }
+            _ => Err("Unsupported binary format (expected ELF, PE or Mach-O)".to_string()),
+        }
+    }
+
+    fn parse_elf(data: Vec<u8>, warnings: &mut Vec<String>) -> Result<Self, String> {
+        if data.get(4) != Some(&2) || data.get(5) != Some(&1) {
+            return Err("Only 64-bit little-endian ELF binaries are supported".to_string());
+        }
+        let phoff = u64_at(&data, 32, false).ok_or("Truncated ELF header")? as usize;
+        let phnum = u16_at(&data, 56, false).unwrap_or(0) as usize;
+        let mut segments = Vec::new();
+        let mut dynamic = None;
+        for i in 0..phnum {
+            let ph = phoff + i * 56;
+            let (Some(p_type), Some(flags), Some(offset), Some(vaddr), Some(filesz)) = (
+                u32_at(&data, ph, false),
+                u32_at(&data, ph + 4, false),
+                u64_at(&data, ph + 8, false),
+                u64_at(&data, ph + 16, false),
+                u64_at(&data, ph + 32, false),
+            ) else {
+                break;
+            };
+            match p_type {
+                1 => segments.push(Segment { vaddr, file_offset: offset, size: filesz, executable: flags & 1 != 0 }),
+                2 => dynamic = Some((offset as usize, filesz as usize)),
+                _ => {}
+            }
+        }
+        let image_base = segments.iter().map(|s| s.vaddr).min().unwrap_or(0);
+        let mut binary = Binary { data, segments, image_base, relocations: HashMap::new(), macho: false };
+
+        let Some((offset, size)) = dynamic else { return Ok(binary) };
+        let mut rela = (None, 0);
+        for entry in (offset..offset + size).step_by(16) {
+            let (Some(tag), Some(value)) = (u64_at(&binary.data, entry, false), u64_at(&binary.data, entry + 8, false)) else {
+                break;
+            };
+            match tag {
+                0 => break,
+                7 => rela.0 = Some(value),
+                8 => rela.1 = value,
+                0x6000_0011 => warnings.push("Android packed relocations (APS2) are not decoded; some pointers may be missed".to_string()),
+                _ => {}
+            }
+        }
+        // RELR slots already hold their target; only RELA needs the addend applied
+        if let Some(start) = rela.0.and_then(|vaddr| binary.file_offset(vaddr)) {
+            for entry in (start..start + rela.1 as usize).step_by(24) {
+                let (Some(slot), Some(info), Some(addend)) = (
+                    u64_at(&binary.data, entry, false),
+                    u64_at(&binary.data, entry + 8, false),
+                    u64_at(&binary.data, entry + 16, false),
+                ) else {
+                    break;
+                };
+                // R_AARCH64_RELATIVE, R_X86_64_RELATIVE
+                if matches!(info & 0xFFFF_FFFF, 1027 | 8) {
+                    binary.relocations.insert(slot, addend);
+                }
+            }
+        }
+        Ok(binary)
+    }
+
+    fn parse_pe(data: Vec<u8>) -> Result<Self, String> {
+        let pe = u32_at(&data, 0x3c, false).ok_or("Truncated PE header")? as usize;
+        if data.get(pe..pe + 4) != Some(b"PE\0\0") {
+            return Err("Invalid PE signature".to_string());
+        }
+        let sections = u16_at(&data, pe + 6, false).unwrap_or(0) as usize;
+        let optional_size = u16_at(&data, pe + 20, false).unwrap_or(0) as usize;
+        let optional = pe + 24;
+        if u16_at(&data, optional, false) != Some(0x20b) {
+            return Err("Only 64-bit (PE32+) binaries are supported".to_string());
+        }
+        let image_base = u64_at(&data, optional + 24, false).ok_or("Truncated PE optional header")?;
+        let segments = (0..sections)
+            .filter_map(|i| {
+                let section = optional + optional_size + i * 40;
+                Some(Segment {
+                    vaddr: image_base + u32_at(&data, section + 12, false)? as u64,
+                    size: u32_at(&data, section + 16, false)? as u64,
+                    file_offset: u32_at(&data, section + 20, false)? as u64,
+                    executable: u32_at(&data, section + 36, false)? & 0x2000_0000 != 0,
+                })
+            })
+            .collect();
+        Ok(Binary { data, segments, image_base, relocations: HashMap::new(), macho: false })
+    }
+
+    fn parse_macho(data: Vec<u8>) -> Result<Self, String> {
+        let ncmds = u32_at(&data, 16, false).ok_or("Truncated Mach-O header")?;
+        let mut segments = Vec::new();
+        let mut pos = 32;
+        for _ in 0..ncmds {
+            let (Some(cmd), Some(size)) = (u32_at(&data, pos, false), u32_at(&data, pos + 4, false)) else { break };
+            if cmd == 0x19 {
+                let field = |offset| u64_at(&data, pos + offset, false).unwrap_or(0);
+                let initprot = u32_at(&data, pos + 60, false).unwrap_or(0);
+                segments.push(Segment { vaddr: field(24), file_offset: field(40), size: field(48), executable: initprot & 4 != 0 });
+            }
+            if size < 8 {
+                break;
+            }
+            pos += size as usize;
+        }
+        let image_base = segments.iter()
+            .find(|s| s.file_offset == 0 && s.size > 0)
+            .map(|s| s.vaddr)
+            .ok_or("Mach-O image has no __TEXT segment")?;
+        Ok(Binary { data, segments, image_base, relocations: HashMap::new(), macho: true })
+    }
+
+    fn file_offset(&self, vaddr: u64) -> Option<usize> {
+        self.segments.iter()
+            .find(|s| vaddr >= s.vaddr && vaddr < s.vaddr + s.size)
+            .map(|s| (s.file_offset + (vaddr - s.vaddr)) as usize)
+    }
+
+    fn vaddr(&self, file_offset: usize) -> Option<u64> {
+        let offset = file_offset as u64;
+        self.segments.iter()
+            .find(|s| offset >= s.file_offset && offset < s.file_offset + s.size)
+            .map(|s| s.vaddr + (offset - s.file_offset))
+    }
+
+    fn u32(&self, vaddr: u64) -> Option<u32> {
+        u32_at(&self.data, self.file_offset(vaddr)?, false)
+    }
+
+    fn u64(&self, vaddr: u64) -> Option<u64> {
+        u64_at(&self.data, self.file_offset(vaddr)?, false)
+    }
+
+    /// Pointer stored at `vaddr`, as a preferred virtual address
+    fn pointer(&self, vaddr: u64) -> Option<u64> {
+        if let Some(&value) = self.relocations.get(&vaddr) {
+            return Some(value);
+        }
+        let raw = self.u64(vaddr)?;
+        if raw == 0 {
+            return None;
+        }
+        if self.macho {
+            // Chained fixup rebases keep the target in the low 36 bits,
+            // relative to the image for the *_OFFSET formats
+            let target = raw & 0xF_FFFF_FFFF;
+            return Some(if target < self.image_base { self.image_base + target } else { target });
+        }
+        Some(raw)
+    }
+
+    fn module_offset(&self, vaddr: u64) -> u64 {
+        vaddr - self.image_base
+    }
+
+    /// 8-byte aligned slots of the non-executable segments
+    fn data_slots(&self) -> impl Iterator<Item = u64> + '_ {
+        self.segments.iter()
+            .filter(|s| !s.executable)
+            .flat_map(|s| (s.vaddr.div_ceil(8) * 8..s.vaddr + s.size.saturating_sub(8)).step_by(8))
+    }
+}
+
+struct Metadata<'a> {
+    data: &'a [u8],
+    version: i32,
+}
+
+impl<'a> Metadata<'a> {
+    /// (offset, size in bytes) of header table `index`
+    fn table(&self, index: usize) -> (usize, usize) {
+        let at = 8 + index * 8;
+        (u32_at(self.data, at, false).unwrap_or(0) as usize, u32_at(self.data, at + 4, false).unwrap_or(0) as usize)
+    }
+
+    fn string(&self, index: u32) -> String {
+        let start = self.table(2).0 + index as usize;
+        let bytes = self.data.get(start..).unwrap_or_default();
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..end]).to_string()
+    }
+
+    fn i32(&self, offset: usize) -> i32 {
+        u32_at(self.data, offset, false).map(|v| v as i32).unwrap_or(-1)
+    }
+
+    fn u16(&self, offset: usize) -> u16 {
+        u16_at(self.data, offset, false).unwrap_or(0)
+    }
+
+    fn type_definition_size(&self) -> usize {
+        if self.version >= 27 { 88 } else { 92 }
+    }
+
+    fn method_definition_size(&self) -> usize {
+        if self.version >= 31 { 36 } else { 32 }
+    }
+}
+
+/// Il2CppTypeDefinition fields used here
+struct TypeDefinition {
+    name: String,
+    namespace: String,
+    parent_type: i32,                    // Index into the binary's type table
+    flags: u32,
+    field_start: i32,
+    method_start: i32,
+    nested_start: i32,
+    method_count: u16,
+    field_count: u16,
+    nested_count: u16,
+}
+
+fn type_definition(metadata: &Metadata, index: usize) -> TypeDefinition {
+    let at = metadata.table(19).0 + index * metadata.type_definition_size();
+    // byrefTypeIndex was dropped in v27
+    let d = if metadata.version >= 27 { 4 } else { 0 };
+    TypeDefinition {
+        name: metadata.string(metadata.i32(at) as u32),
+        namespace: metadata.string(metadata.i32(at + 4) as u32),
+        parent_type: metadata.i32(at + 20 - d),
+        flags: metadata.i32(at + 32 - d) as u32,
+        field_start: metadata.i32(at + 36 - d),
+        method_start: metadata.i32(at + 40 - d),
+        nested_start: metadata.i32(at + 52 - d),
+        method_count: metadata.u16(at + 68 - d),
+        field_count: metadata.u16(at + 72 - d),
+        nested_count: metadata.u16(at + 76 - d),
+    }
+}
+
+/// Il2CppMetadataRegistration fields used here
+struct Registration {
+    types: Vec<u64>,                     // Il2CppType pointers by type index
+    field_offsets: u64,                  // int32_t*[type count]
+}
+
+/// Metadata registration: fieldOffsetsCount and typeDefinitionsSizesCount
+/// both equal the type definition count and sit 16 bytes apart
+fn find_registration(binary: &Binary, type_count: u64) -> Option<Registration> {
+    binary.data_slots()
+        .filter(|&slot| slot >= binary.image_base + 80)
+        .find(|&slot| {
+            binary.u64(slot) == Some(type_count)
+                && binary.u64(slot + 16) == Some(type_count)
+                && binary.pointer(slot + 8).and_then(|p| binary.file_offset(p)).is_some()
+                && binary.u64(slot - 32).is_some_and(|types| types > 0 && types < 10_000_000)
+        })
+        .map(|slot| {
+            let base = slot - 80;
+            let type_count = binary.u64(base + 48).unwrap_or(0);
+            let types_array = binary.pointer(base + 56).unwrap_or(0);
+            Registration {
+                types: (0..type_count).map(|i| binary.pointer(types_array + i * 8).unwrap_or(0)).collect(),
+                field_offsets: binary.pointer(slot + 8).unwrap_or(0),
+            }
+        })
+}
+
+fn type_name(binary: &Binary, names: &[String], il2cpp_type: u64, depth: u32) -> String {
+    let (Some(data), Some(bits)) = (binary.u64(il2cpp_type), binary.u32(il2cpp_type + 8)) else {
+        return "?".to_string();
+    };
+    let element = |pointer: Option<u64>| match pointer {
+        Some(pointer) if depth < MAX_TYPE_NAME_DEPTH => type_name(binary, names, pointer, depth + 1),
+        _ => "?".to_string(),
+    };
+    match (bits >> 16) & 0xFF {
+        0x01 => "void".to_string(),
+        0x02 => "bool".to_string(),
+        0x03 => "char".to_string(),
+        0x04 => "sbyte".to_string(),
+        0x05 => "byte".to_string(),
+        0x06 => "short".to_string(),
+        0x07 => "ushort".to_string(),
+        0x08 => "int".to_string(),
+        0x09 => "uint".to_string(),
+        0x0a => "long".to_string(),
+        0x0b => "ulong".to_string(),
+        0x0c => "float".to_string(),
+        0x0d => "double".to_string(),
+        0x0e => "string".to_string(),
+        0x0f => format!("{}*", element(binary.pointer(il2cpp_type))),
+        0x11 | 0x12 => names.get(data as usize).cloned().unwrap_or_else(|| "?".to_string()),
+        0x13 | 0x1e => "T".to_string(),
+        0x14 => format!("{}[,]", element(binary.pointer(il2cpp_type).and_then(|array| binary.pointer(array)))),
+        0x15 => {
+            // Il2CppGenericClass starts with the definition's index (v24) or type (v27+)
+            let generic = binary.pointer(il2cpp_type);
+            let base = match generic.and_then(|g| binary.u64(g)) {
+                Some(index) if (index as usize) < names.len() => names[index as usize].clone(),
+                _ => element(generic.and_then(|g| binary.pointer(g))),
+            };
+            format!("{}<>", base)
+        }
+        0x18 => "IntPtr".to_string(),
+        0x19 => "UIntPtr".to_string(),
+        0x1c => "object".to_string(),
+        0x1d => format!("{}[]", element(binary.pointer(il2cpp_type))),
+        _ => "?".to_string(),
+    }
+}
+
+/// Method pointer arrays of each image (Il2CppCodeGenModule, v24.2+), found
+/// from the pointer to the module's name string; the array length must
+/// match the image's method count
+fn find_code_gen_modules(binary: &Binary, images: &[(String, usize)]) -> HashMap<usize, (u64, usize)> {
+    let mut name_addresses: HashMap<u64, usize> = HashMap::new();
+    for (index, (name, _)) in images.iter().enumerate() {
+        let needle = format!("\0{}\0", name);
+        for found in memchr::memmem::find_iter(&binary.data, needle.as_bytes()) {
+            if let Some(vaddr) = binary.vaddr(found + 1) {
+                name_addresses.insert(vaddr, index);
+            }
+        }
+    }
+    let mut modules = HashMap::new();
+    for slot in binary.data_slots() {
+        let Some(&index) = binary.pointer(slot).and_then(|p| name_addresses.get(&p)) else { continue };
+        let expected = images[index].1;
+        if modules.contains_key(&index) || binary.u32(slot + 8) != Some(expected as u32) {
+            continue;
+        }
+        if expected == 0 {
+            modules.insert(index, (0, 0));
+        } else if let Some(pointers) = binary.pointer(slot + 16) {
+            modules.insert(index, (pointers, expected));
+        }
+    }
+    modules
+}
+
+fn parse(metadata: &[u8], binary: Vec<u8>, warnings: &mut Vec<String>) -> Result<(i32, usize, Vec<Il2CppClass>), String> {
+    if u32_at(metadata, 0, false) != Some(METADATA_SANITY) {
+        return Err("Not a global-metadata.dat file (bad magic); it may be encrypted".to_string());
+    }
+    let version = u32_at(metadata, 4, false).unwrap_or(0) as i32;
+    if !(MIN_VERSION..=MAX_VERSION).contains(&version) {
+        return Err(format!("Unsupported metadata version {} (supported: {}-{})", version, MIN_VERSION, MAX_VERSION));
+    }
+    let metadata = Metadata { data: metadata, version };
+    let binary = Binary::parse(binary, warnings)?;
+
+    let type_count = metadata.table(19).1 / metadata.type_definition_size();
+    let definitions: Vec<TypeDefinition> = (0..type_count).map(|i| type_definition(&metadata, i)).collect();
+
+    // Nested type names are qualified by their declaring type
+    let (nested_table, _) = metadata.table(15);
+    let mut outer: HashMap<usize, usize> = HashMap::new();
+    for (index, definition) in definitions.iter().enumerate() {
+        for n in 0..definition.nested_count as usize {
+            let nested = metadata.i32(nested_table + (definition.nested_start as usize + n) * 4);
+            if nested >= 0 {
+                outer.insert(nested as usize, index);
+            }
+        }
+    }
+    let full_names: Vec<String> = (0..type_count)
+        .map(|index| {
+            let mut parts = vec![definitions[index].name.clone()];
+            let mut current = index;
+            while let Some(&parent) = outer.get(&current).filter(|_| parts.len() < 16) {
+                parts.push(definitions[parent].name.clone());
+                current = parent;
+            }
+            let namespace = &definitions[current].namespace;
+            if !namespace.is_empty() {
+                parts.push(namespace.clone());
+            }
+            parts.reverse();
+            parts.join(".")
+        })
+        .collect();
+
+    // Images own contiguous ranges of type definitions
+    let (images_table, images_size) = metadata.table(20);
+    let images: Vec<(String, usize, usize)> = (0..images_size / 40)
+        .map(|i| {
+            let at = images_table + i * 40;
+            (metadata.string(metadata.i32(at) as u32), metadata.i32(at + 8).max(0) as usize, metadata.i32(at + 12).max(0) as usize)
+        })
+        .collect();
+    let method_counts: Vec<(String, usize)> = images.iter()
+        .map(|(name, start, count)| {
+            let methods = definitions.iter().skip(*start).take(*count).map(|d| d.method_count as usize).sum();
+            (name.clone(), methods)
+        })
+        .collect();
+    let code_gen_modules = find_code_gen_modules(&binary, &method_counts);
+    if code_gen_modules.is_empty() {
+        warnings.push("No code generation modules found in the binary; methods have no addresses (Unity 2018 and older are not supported)".to_string());
+    } else if code_gen_modules.len() < images.len() {
+        warnings.push(format!("Method addresses found for {} of {} images", code_gen_modules.len(), images.len()));
+    }
+    let registration = find_registration(&binary, type_count as u64);
+    if registration.is_none() {
+        warnings.push("Metadata registration not found; fields have no offsets or types".to_string());
+    }
+
+    let (methods_table, _) = metadata.table(5);
+    let (fields_table, _) = metadata.table(11);
+    let method_size = metadata.method_definition_size();
+    // Method definition offsets after the name: v31 added returnParameterToken
+    let m = if version >= 31 { 4 } else { 0 };
+    let mut classes = Vec::with_capacity(type_count);
+    for (image_index, (image, start, count)) in images.iter().enumerate() {
+        let pointers = code_gen_modules.get(&image_index);
+        for type_index in *start..(*start + *count).min(type_count) {
+            let definition = &definitions[type_index];
+            let methods = (0..definition.method_count as usize)
+                .map(|i| {
+                    let at = methods_table + (definition.method_start as usize + i) * method_size;
+                    let token = metadata.i32(at + 20 + m) as u32;
+                    let rid = (token & 0x00FF_FFFF) as u64;
+                    let module_offset = pointers
+                        .filter(|(_, count)| rid >= 1 && rid <= *count as u64)
+                        .and_then(|(array, _)| binary.pointer(array + (rid - 1) * 8))
+                        .map(|pointer| binary.module_offset(pointer));
+                    Il2CppMethod {
+                        name: metadata.string(metadata.i32(at) as u32),
+                        module_offset,
+                        token,
+                        flags: metadata.u16(at + 24 + m),
+                        parameter_count: metadata.u16(at + 30 + m),
+                    }
+                })
+                .collect();
+            let offsets = registration.as_ref()
+                .and_then(|r| binary.pointer(r.field_offsets + type_index as u64 * 8));
+            let fields = (0..definition.field_count as usize)
+                .map(|i| {
+                    let at = fields_table + (definition.field_start as usize + i) * 12;
+                    let field_type = registration.as_ref()
+                        .and_then(|r| r.types.get(metadata.i32(at + 4) as usize).copied())
+                        .filter(|&t| t != 0);
+                    let attrs = field_type.and_then(|t| binary.u32(t + 8)).unwrap_or(0) & 0xFFFF;
+                    Il2CppField {
+                        name: metadata.string(metadata.i32(at) as u32),
+                        type_name: field_type.map(|t| type_name(&binary, &full_names, t, 0)),
+                        offset: offsets.and_then(|o| binary.u32(o + i as u64 * 4)).map(|v| v as i32),
+                        // FIELD_ATTRIBUTE_STATIC
+                        is_static: attrs & 0x10 != 0,
+                    }
+                })
+                .collect();
+            let parent = registration.as_ref()
+                .filter(|_| definition.parent_type >= 0)
+                .and_then(|r| {
+                    let parent_type = r.types.get(definition.parent_type as usize).copied().filter(|&t| t != 0)?;
+                    Some(type_name(&binary, &full_names, parent_type, 0))
+                });
+            classes.push(Il2CppClass {
+                type_index: type_index as u32,
+                full_name: full_names[type_index].clone(),
+                namespace: definition.namespace.clone(),
+                name: definition.name.clone(),
+                image: image.clone(),
+                parent,
+                flags: definition.flags,
+                methods,
+                fields,
+            });
+        }
+    }
+    Ok((version, images.len(), classes))
+}
+
+fn store(conn: &Connection, target_os: &str, module_name: &str, classes: &[Il2CppClass]) -> Result<(), String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    for table in ["il2cpp_classes", "il2cpp_methods", "il2cpp_fields"] {
+        tx.execute(&format!("DELETE FROM {} WHERE target_os = ?1 AND module_name = ?2", table), params![target_os, module_name])
+            .map_err(|e| e.to_string())?;
+    }
+    {
+        let mut insert_class = tx.prepare(
+            "INSERT INTO il2cpp_classes (target_os, module_name, type_index, full_name, namespace, name, image, parent, flags)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        ).map_err(|e| e.to_string())?;
+        let mut insert_method = tx.prepare(
+            "INSERT INTO il2cpp_methods (target_os, module_name, type_index, idx, name, module_offset, token, flags, parameter_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        ).map_err(|e| e.to_string())?;
+        let mut insert_field = tx.prepare(
+            "INSERT INTO il2cpp_fields (target_os, module_name, type_index, idx, name, type_name, field_offset, is_static)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        ).map_err(|e| e.to_string())?;
+        for class in classes {
+            insert_class.execute(params![
+                target_os, module_name, class.type_index, class.full_name, class.namespace, class.name, class.image, class.parent, class.flags,
+            ]).map_err(|e| e.to_string())?;
+            for (idx, method) in class.methods.iter().enumerate() {
+                insert_method.execute(params![
+                    target_os, module_name, class.type_index, idx as i64, method.name, method.module_offset.map(|o| o as i64),
+                    method.token, method.flags, method.parameter_count,
+                ]).map_err(|e| e.to_string())?;
+            }
+            for (idx, field) in class.fields.iter().enumerate() {
+                insert_field.execute(params![
+                    target_os, module_name, class.type_index, idx as i64, field.name, field.type_name, field.offset, field.is_static,
+                ]).map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Add the methods to the module's function list (ghidra_functions_cache):
+/// new offsets are appended and Ghidra's auto-generated names replaced.
+/// Sizes run to the next known method. Returns the entries added or renamed.
+fn merge_into_function_list(conn: &Connection, target_os: &str, module_name: &str, classes: &[Il2CppClass]) -> Result<usize, String> {
+    let mut methods: Vec<(u64, String)> = classes.iter()
+        .flat_map(|c| c.methods.iter().filter_map(move |m| Some((m.module_offset?, format!("{}$${}", c.full_name, m.name)))))
+        .filter(|(offset, _)| *offset != 0)
+        .collect();
+    methods.sort_by_key(|(offset, _)| *offset);
+    // Shared generic code: the first name stands for every method at the offset
+    methods.dedup_by_key(|(offset, _)| *offset);
+
+    let existing: Option<String> = conn.query_row(
+        "SELECT functions_json FROM ghidra_functions_cache WHERE target_os = ?1 AND module_name = ?2",
+        params![target_os, module_name],
+        |row| row.get(0),
+    ).ok();
+    let mut functions: Vec<serde_json::Value> = match existing {
+        Some(sealed) => serde_json::from_str(&secure_store::open_value("ghidra_functions_cache", "functions_json", sealed)?)
+            .map_err(|e| format!("Failed to parse functions JSON: {}", e))?,
+        None => Vec::new(),
+    };
+    let mut by_offset: HashMap<u64, usize> = HashMap::new();
+    for (i, function) in functions.iter().enumerate() {
+        let offset = function["address"].as_str()
+            .and_then(|a| u64::from_str_radix(a.trim().trim_start_matches("0x"), 16).ok());
+        if let Some(offset) = offset {
+            by_offset.insert(offset, i);
+        }
+    }
+
+    let mut changed = 0;
+    for (i, (offset, name)) in methods.iter().enumerate() {
+        match by_offset.get(offset) {
+            Some(&index) => {
+                let current = functions[index]["name"].as_str().unwrap_or_default();
+                if symbolizer::AUTO_NAME_PREFIXES.iter().any(|p| current.starts_with(p)) {
+                    functions[index]["name"] = serde_json::json!(name);
+                    changed += 1;
+                }
+            }
+            None => {
+                let size = methods.get(i + 1).map_or(0, |(next, _)| next - offset);
+                functions.push(serde_json::json!({ "name": name, "address": format!("0x{:x}", offset), "size": size }));
+                changed += 1;
+            }
+        }
+    }
+    let json = serde_json::to_string(&functions).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT OR REPLACE INTO ghidra_functions_cache (target_os, module_name, functions_json, updated_at, cache_version)
+         VALUES (?1, ?2, ?3, datetime('now'), ?4)",
+        params![
+            target_os,
+            module_name,
+            secure_store::seal_value("ghidra_functions_cache", "functions_json", &json)?,
+            cache_versions::stamp(conn, target_os, module_name),
+        ],
+    ).map_err(|e| e.to_string())?;
+    symbolizer::invalidate_module(target_os, module_name);
+    Ok(changed)
+}
+
+/// Parse a downloaded global-metadata.dat together with the IL2CPP binary
+/// (libil2cpp.so, GameAssembly.dll or UnityFramework), store the classes,
+/// methods and fields, and add the methods to the module's function list
+#[tauri::command]
+pub async fn load_il2cpp_metadata(
+    state: tauri::State<'_, AppStateType>,
+    metadata_path: String,
+    binary_path: String,
+    module_name: Option<String>,
+) -> Result<Il2CppLoadResult, String> {
+    let target_os = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_else(|| "unknown".to_string())
+    };
+    let module_name = module_name.unwrap_or_else(|| {
+        std::path::Path::new(&binary_path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+    });
+
+    db::run(move |conn| {
+        let metadata = std::fs::read(&metadata_path).map_err(|e| format!("Failed to read {}: {}", metadata_path, e))?;
+        let binary = std::fs::read(&binary_path).map_err(|e| format!("Failed to read {}: {}", binary_path, e))?;
+        let mut warnings = Vec::new();
+        let (metadata_version, images, classes) = parse(&metadata, binary, &mut warnings)?;
+        store(conn, &target_os, &module_name, &classes)?;
+        let functions_added = merge_into_function_list(conn, &target_os, &module_name, &classes)?;
+
+        let methods = classes.iter().flat_map(|c| &c.methods);
+        let fields = classes.iter().flat_map(|c| &c.fields);
+        Ok(Il2CppLoadResult {
+            metadata_version,
+            images,
+            classes: classes.len(),
+            methods: methods.clone().count(),
+            methods_with_address: methods.filter(|m| m.module_offset.is_some()).count(),
+            fields: fields.clone().count(),
+            fields_with_offset: fields.filter(|f| f.offset.is_some()).count(),
+            functions_added,
+            warnings,
+            module_name,
+        })
+    })
+    .await
+}
+
+/// Stored classes whose full name contains `query` (case-insensitive)
+#[tauri::command]
+pub async fn search_il2cpp_classes(
+    target_os: String,
+    module_name: String,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<Il2CppClassSummary>, String> {
+    db::run(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT c.type_index, c.full_name, c.image,
+                    (SELECT COUNT(*) FROM il2cpp_methods m WHERE m.target_os = c.target_os AND m.module_name = c.module_name AND m.type_index = c.type_index),
+                    (SELECT COUNT(*) FROM il2cpp_fields f WHERE f.target_os = c.target_os AND f.module_name = c.module_name AND f.type_index = c.type_index)
+             FROM il2cpp_classes c
+             WHERE c.target_os = ?1 AND c.module_name = ?2 AND c.full_name LIKE ?3 ESCAPE '\\'
+             ORDER BY c.full_name LIMIT ?4",
+        ).map_err(|e| e.to_string())?;
+        let pattern = format!("%{}%", query.replace('%', "\\%").replace('_', "\\_"));
+        let rows = stmt.query_map(params![target_os, module_name, pattern, limit.unwrap_or(500) as i64], |row| {
+            Ok(Il2CppClassSummary {
+                type_index: row.get(0)?,
+                full_name: row.get(1)?,
+                image: row.get(2)?,
+                method_count: row.get::<_, i64>(3)? as usize,
+                field_count: row.get::<_, i64>(4)? as usize,
+            })
+        }).map_err(|e| e.to_string())?;
+        Ok(rows.flatten().collect())
+    }).await
+}
+
+/// A stored class with its methods and fields, by full name
+#[tauri::command]
+pub async fn get_il2cpp_class(target_os: String, module_name: String, full_name: String) -> Result<Il2CppClass, String> {
+    db::run(move |conn| {
+        let mut class = conn.query_row(
+            "SELECT type_index, full_name, namespace, name, image, parent, flags FROM il2cpp_classes
+             WHERE target_os = ?1 AND module_name = ?2 AND full_name = ?3",
+            params![target_os, module_name, full_name],
+            |row| Ok(Il2CppClass {
+                type_index: row.get(0)?,
+                full_name: row.get(1)?,
+                namespace: row.get(2)?,
+                name: row.get(3)?,
+                image: row.get(4)?,
+                parent: row.get(5)?,
+                flags: row.get(6)?,
+                methods: Vec::new(),
+                fields: Vec::new(),
+            }),
+        ).map_err(|_| format!("Class {} not found", full_name))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT name, module_offset, token, flags, parameter_count FROM il2cpp_methods
+             WHERE target_os = ?1 AND module_name = ?2 AND type_index = ?3 ORDER BY idx",
+        ).map_err(|e| e.to_string())?;
+        class.methods = stmt.query_map(params![target_os, module_name, class.type_index], |row| {
+            Ok(Il2CppMethod {
+                name: row.get(0)?,
+                module_offset: row.get::<_, Option<i64>>(1)?.map(|o| o as u64),
+                token: row.get(2)?,
+                flags: row.get(3)?,
+                parameter_count: row.get(4)?,
+            })
+        }).map_err(|e| e.to_string())?.flatten().collect();
+
+        let mut stmt = conn.prepare(
+            "SELECT name, type_name, field_offset, is_static FROM il2cpp_fields
+             WHERE target_os = ?1 AND module_name = ?2 AND type_index = ?3 ORDER BY idx",
+        ).map_err(|e| e.to_string())?;
+        class.fields = stmt.query_map(params![target_os, module_name, class.type_index], |row| {
+            Ok(Il2CppField { name: row.get(0)?, type_name: row.get(1)?, offset: row.get(2)?, is_static: row.get(3)? })
+        }).map_err(|e| e.to_string())?.flatten().collect();
+        Ok(class)
+    }).await
+}
@@ -0,0 +1,403 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+use crate::disassembly::{disassemble_structured_skipping, StructuredInstruction};
+use crate::memory_regions::{self, MemoryRegion};
+use crate::state::{AppState, AppStateType};
+use crate::{event_bus, fnv1a, read_memory_from_server, SERVER_CONFIG};
+
+const DEFAULT_INTERVAL_MS: u64 = 1000;
+const MIN_INTERVAL_MS: u64 = 200;
+const MAX_SNAPSHOT_BYTES: u64 = 16 * 1024 * 1024;
+const MAX_VERSIONS_PER_REGION: usize = 32;
+const MAX_DIFF_ENTRIES: usize = 10_000;
+
+/// Anonymous executable region (JIT code cache, trampolines, unpacked code)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JitRegion {
+    pub base: u64,
+    pub size: u64,
+    pub protection: String,
+    pub mapped_file: Option<String>,     // "[anon:...]" / memfd names when the OS provides them
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub alive: bool,                     // Still present in the latest memory map
+    pub version_count: usize,
+    pub latest_version: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JitVersionInfo {
+    pub region_base: u64,
+    pub version: u32,
+    pub size: usize,
+    pub hash: String,
+    pub captured_at: u64,
+    pub label: Option<String>,
+    pub unchanged: bool,                 // Snapshot matched the previous version (nothing stored)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JitBookmark {
+    pub id: u64,
+    pub region_base: u64,
+    pub address: u64,
+    pub label: String,
+    pub version: Option<u32>,            // Version the bookmark was taken in
+    pub created_at: u64,
+}
+
+/// Instruction-level difference between two versions of a region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JitInstructionDiff {
+    pub address: u64,
+    pub status: String,                  // "added" | "removed" | "changed"
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JitVersionDiff {
+    pub region_base: u64,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub changed_bytes: usize,
+    pub instructions: Vec<JitInstructionDiff>,
+    pub truncated: bool,
+}
+
+struct StoredVersion {
+    info: JitVersionInfo,
+    data: Vec<u8>,                       // lz4, size-prepended
+}
+
+struct TrackedRegion {
+    region: JitRegion,
+    versions: Vec<StoredVersion>,
+    next_version: u32,
+}
+
+struct JitTracker {
+    running: Arc<AtomicBool>,
+    auto_snapshot: bool,
+    regions: BTreeMap<u64, TrackedRegion>,
+    bookmarks: Vec<JitBookmark>,
+}
+
+static TRACKER: Lazy<Mutex<JitTracker>> = Lazy::new(|| {
+    Mutex::new(JitTracker {
+        running: Arc::new(AtomicBool::new(false)),
+        auto_snapshot: false,
+        regions: BTreeMap::new(),
+        bookmarks: Vec::new(),
+    })
+});
+
+static NEXT_BOOKMARK_ID: AtomicU64 = AtomicU64::new(1);
+
+fn lock_tracker() -> Result<std::sync::MutexGuard<'static, JitTracker>, String> {
+    TRACKER.lock().map_err(|e| e.to_string())
+}
+
+fn is_jit_candidate(region: &MemoryRegion) -> bool {
+    if !region.executable || region.module_name.is_some() {
+        return false;
+    }
+    match region.mapped_file.as_deref() {
+        None => true,
+        Some(path) => {
+            let lower = path.to_lowercase();
+            path.starts_with("[anon:") || lower.contains("jit") || lower.contains("memfd:")
+        }
+    }
+}
+
+/// Refresh the memory map and update the tracked region list; returns regions
+/// seen for the first time
+async fn refresh(state: &AppStateType) -> Result<Vec<JitRegion>, String> {
+    let regions = memory_regions::get_cached_regions(Some(state), true).await?;
+    let now = AppState::current_timestamp();
+    let current: HashMap<u64, &MemoryRegion> = regions.iter()
+        .filter(|r| is_jit_candidate(r))
+        .map(|r| (r.base, r))
+        .collect();
+
+    let mut tracker = lock_tracker()?;
+    let mut appeared = Vec::new();
+    for tracked in tracker.regions.values_mut() {
+        tracked.region.alive = current.contains_key(&tracked.region.base);
+    }
+    for (base, region) in &current {
+        let tracked = tracker.regions.entry(*base).or_insert_with(|| {
+            let region = JitRegion {
+                base: *base,
+                size: region.size,
+                protection: region.protection.clone(),
+                mapped_file: region.mapped_file.clone(),
+                first_seen: now,
+                last_seen: now,
+                alive: true,
+                version_count: 0,
+                latest_version: None,
+            };
+            appeared.push(region.clone());
+            TrackedRegion { region, versions: Vec::new(), next_version: 1 }
+        });
+        tracked.region.size = region.size;
+        tracked.region.protection = region.protection.clone();
+        tracked.region.last_seen = now;
+    }
+    drop(tracker);
+
+    for region in &appeared {
+        event_bus::publish(event_bus::CHANNEL_JIT, None, region);
+    }
+    Ok(appeared)
+}
+
+/// Read a region and store it as a new version unless it is unchanged
+async fn snapshot(base: u64, label: Option<String>) -> Result<JitVersionInfo, String> {
+    let size = {
+        let tracker = lock_tracker()?;
+        let tracked = tracker.regions.get(&base).ok_or_else(|| format!("No JIT region at 0x{:x}", base))?;
+        tracked.region.size.min(MAX_SNAPSHOT_BYTES)
+    };
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+    let data = read_memory_from_server(&host, port, base, size as usize).await?;
+    let hash = format!("{:016x}", fnv1a(&data));
+
+    let mut tracker = lock_tracker()?;
+    let tracked = tracker.regions.get_mut(&base).ok_or_else(|| format!("No JIT region at 0x{:x}", base))?;
+    if let Some(latest) = tracked.versions.last().filter(|v| v.info.hash == hash) {
+        return Ok(JitVersionInfo { unchanged: true, ..latest.info.clone() });
+    }
+    let info = JitVersionInfo {
+        region_base: base,
+        version: tracked.next_version,
+        size: data.len(),
+        hash,
+        captured_at: AppState::current_timestamp(),
+        label,
+        unchanged: false,
+    };
+    tracked.next_version += 1;
+    tracked.versions.push(StoredVersion { info: info.clone(), data: lz4_flex::compress_prepend_size(&data) });
+    if tracked.versions.len() > MAX_VERSIONS_PER_REGION {
+        tracked.versions.remove(0);
+    }
+    tracked.region.version_count = tracked.versions.len();
+    tracked.region.latest_version = Some(info.version);
+    Ok(info)
+}
+
+fn version_data(base: u64, version: Option<u32>) -> Result<(u32, Vec<u8>), String> {
+    let tracker = lock_tracker()?;
+    let tracked = tracker.regions.get(&base).ok_or_else(|| format!("No JIT region at 0x{:x}", base))?;
+    let stored = match version {
+        Some(version) => tracked.versions.iter().find(|v| v.info.version == version),
+        None => tracked.versions.last(),
+    }
+    .ok_or("Version not found")?;
+    let data = lz4_flex::decompress_size_prepended(&stored.data).map_err(|e| e.to_string())?;
+    Ok((stored.info.version, data))
+}
+
+fn instruction_text(insn: &StructuredInstruction) -> String {
+    if insn.operands.is_empty() {
+        insn.mnemonic.clone()
+    } else {
+        format!("{} {}", insn.mnemonic, insn.operands)
+    }
+}
+
+/// Start polling the memory map for new anonymous executable regions.
+/// With `auto_snapshot`, new regions are captured as version 1 right away.
+#[tauri::command]
+pub async fn start_jit_tracking(
+    app: AppHandle,
+    interval_ms: Option<u64>,
+    auto_snapshot: Option<bool>,
+) -> Result<Vec<JitRegion>, String> {
+    let interval_ms = interval_ms.unwrap_or(DEFAULT_INTERVAL_MS).max(MIN_INTERVAL_MS);
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let mut tracker = lock_tracker()?;
+        tracker.running.store(false, Ordering::Relaxed);
+        tracker.running = running.clone();
+        tracker.auto_snapshot = auto_snapshot.unwrap_or(false);
+    }
+
+    // The first pass runs inline so the caller gets the current regions
+    let state = app.state::<AppStateType>();
+    refresh(state.inner()).await?;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+        interval.tick().await;
+        while running.load(Ordering::Relaxed) {
+            interval.tick().await;
+            let state = app.state::<AppStateType>();
+            let Ok(appeared) = refresh(state.inner()).await else {
+                continue;
+            };
+            let auto_snapshot = TRACKER.lock().map(|t| t.auto_snapshot).unwrap_or(false);
+            if auto_snapshot {
+                for region in appeared {
+                    let _ = snapshot(region.base, Some("first seen".to_string())).await;
+                }
+            }
+        }
+    });
+
+    list_jit_regions()
+}
+
+#[tauri::command]
+pub fn stop_jit_tracking() -> Result<(), String> {
+    lock_tracker()?.running.store(false, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Refresh once without the background poller; returns newly seen regions
+#[tauri::command]
+pub async fn refresh_jit_regions(state: tauri::State<'_, AppStateType>) -> Result<Vec<JitRegion>, String> {
+    refresh(state.inner()).await
+}
+
+#[tauri::command]
+pub fn list_jit_regions() -> Result<Vec<JitRegion>, String> {
+    Ok(lock_tracker()?.regions.values().map(|t| t.region.clone()).collect())
+}
+
+/// Capture the current bytes of a region as a new version
+#[tauri::command]
+pub async fn snapshot_jit_region(base: u64, label: Option<String>) -> Result<JitVersionInfo, String> {
+    snapshot(base, label).await
+}
+
+#[tauri::command]
+pub fn list_jit_versions(base: u64) -> Result<Vec<JitVersionInfo>, String> {
+    let tracker = lock_tracker()?;
+    let tracked = tracker.regions.get(&base).ok_or_else(|| format!("No JIT region at 0x{:x}", base))?;
+    Ok(tracked.versions.iter().map(|v| v.info.clone()).collect())
+}
+
+/// Disassemble a stored version (latest by default) from `offset`; data
+/// between instructions shows up as ".byte" entries
+#[tauri::command]
+pub fn disassemble_jit_version(
+    base: u64,
+    version: Option<u32>,
+    architecture: String,
+    offset: Option<u64>,
+    size: Option<usize>,
+) -> Result<Vec<StructuredInstruction>, String> {
+    let (_, data) = version_data(base, version)?;
+    let start = (offset.unwrap_or(0) as usize).min(data.len());
+    let end = size.map(|s| start.checked_add(s).unwrap_or(usize::MAX).min(data.len())).unwrap_or(data.len());
+    disassemble_structured_skipping(&data[start..end], base.wrapping_add(start as u64), &architecture)
+}
+
+/// Compare two versions instruction by instruction (by address)
+#[tauri::command]
+pub fn diff_jit_versions(
+    base: u64,
+    from_version: u32,
+    to_version: Option<u32>,
+    architecture: String,
+) -> Result<JitVersionDiff, String> {
+    let (from_version, old) = version_data(base, Some(from_version))?;
+    let (to_version, new) = version_data(base, to_version)?;
+    let changed_bytes = old.iter().zip(&new).filter(|(a, b)| a != b).count() + old.len().abs_diff(new.len());
+
+    let old_insns: BTreeMap<u64, StructuredInstruction> = disassemble_structured_skipping(&old, base, &architecture)?
+        .into_iter().map(|i| (i.address, i)).collect();
+    let new_insns: BTreeMap<u64, StructuredInstruction> = disassemble_structured_skipping(&new, base, &architecture)?
+        .into_iter().map(|i| (i.address, i)).collect();
+
+    let mut addresses: Vec<u64> = old_insns.keys().chain(new_insns.keys()).copied().collect();
+    addresses.sort_unstable();
+    addresses.dedup();
+
+    let mut instructions = Vec::new();
+    let mut truncated = false;
+    for address in addresses {
+        let entry = match (old_insns.get(&address), new_insns.get(&address)) {
+            (Some(a), Some(b)) if a.bytes == b.bytes => continue,
+            (Some(a), Some(b)) => ("changed", Some(instruction_text(a)), Some(instruction_text(b))),
+            (Some(a), None) => ("removed", Some(instruction_text(a)), None),
+            (None, Some(b)) => ("added", None, Some(instruction_text(b))),
+            (None, None) => continue,
+        };
+        if instructions.len() >= MAX_DIFF_ENTRIES {
+            truncated = true;
+            break;
+        }
+        instructions.push(JitInstructionDiff { address, status: entry.0.to_string(), old: entry.1, new: entry.2 });
+    }
+
+    Ok(JitVersionDiff { region_base: base, from_version, to_version, changed_bytes, instructions, truncated })
+}
+
+/// Bookmark an address inside a tracked region
+#[tauri::command]
+pub fn add_jit_bookmark(address: u64, label: String) -> Result<JitBookmark, String> {
+    let mut tracker = lock_tracker()?;
+    let tracked = tracker.regions.values()
+        .find(|t| address >= t.region.base && address < t.region.base + t.region.size)
+        .ok_or_else(|| format!("0x{:x} is not inside a tracked JIT region", address))?;
+    let bookmark = JitBookmark {
+        id: NEXT_BOOKMARK_ID.fetch_add(1, Ordering::Relaxed),
+        region_base: tracked.region.base,
+        address,
+        label,
+        version: tracked.region.latest_version,
+        created_at: AppState::current_timestamp(),
+    };
+    tracker.bookmarks.push(bookmark.clone());
+    Ok(bookmark)
+}
+
+#[tauri::command]
+pub fn remove_jit_bookmark(id: u64) -> Result<bool, String> {
+    let mut tracker = lock_tracker()?;
+    let before = tracker.bookmarks.len();
+    tracker.bookmarks.retain(|b| b.id != id);
+    Ok(tracker.bookmarks.len() != before)
+}
+
+#[tauri::command]
+pub fn list_jit_bookmarks(base: Option<u64>) -> Result<Vec<JitBookmark>, String> {
+    let tracker = lock_tracker()?;
+    Ok(tracker.bookmarks.iter()
+        .filter(|b| base.is_none_or(|base| b.region_base == base))
+        .cloned()
+        .collect())
+}
+
+/// Bookmark labels in [start, end) for the disassembly comment pipeline
+pub fn bookmarks_in_range(start: u64, end: u64) -> Vec<(u64, String)> {
+    let Ok(tracker) = TRACKER.lock() else {
+        return Vec::new();
+    };
+    tracker.bookmarks.iter()
+        .filter(|b| b.address >= start && b.address < end)
+        .map(|b| (b.address, b.label.clone()))
+        .collect()
+}
+
+/// Forget all regions, versions and bookmarks (e.g. after re-attaching)
+#[tauri::command]
+pub fn clear_jit_regions() -> Result<(), String> {
+    let mut tracker = lock_tracker()?;
+    tracker.regions.clear();
+    tracker.bookmarks.clear();
+    Ok(())
+}
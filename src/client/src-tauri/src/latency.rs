@@ -0,0 +1,193 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+// Breakdowns kept for get_latency_report; older ones are dropped
+const MAX_RECORDED: usize = 2000;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECORDED: Lazy<Mutex<VecDeque<LatencyBreakdown>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyStage {
+    pub name: String,                 // "network", "parse", "cache_lookup", "serialize", ...
+    pub us: u64,
+}
+
+/// Where the time of one interactive command went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyBreakdown {
+    pub operation: String,            // "read_memory", "disassemble_memory", ...
+    pub total_us: u64,                // Includes the serialize stage
+    pub stages: Vec<LatencyStage>,
+    pub bytes: Option<usize>,         // Payload size: bytes read, or the serialized response
+    pub cache_hit: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyStageSummary {
+    pub name: String,
+    pub count: usize,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub max_us: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyOperationSummary {
+    pub operation: String,
+    pub count: usize,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub max_us: u64,
+    pub cache_hits: usize,
+    pub cache_misses: usize,
+    pub stages: Vec<LatencyStageSummary>,  // In the order the stages first ran
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Splits one command into named stages. Does nothing (and allocates nothing)
+/// while instrumentation is off.
+pub struct Stopwatch {
+    operation: &'static str,
+    started: Option<Instant>,
+    last: Option<Instant>,
+    stages: Vec<LatencyStage>,
+    bytes: Option<usize>,
+    cache_hit: Option<bool>,
+}
+
+impl Stopwatch {
+    pub fn start(operation: &'static str) -> Self {
+        let now = enabled().then(Instant::now);
+        Stopwatch { operation, started: now, last: now, stages: Vec::new(), bytes: None, cache_hit: None }
+    }
+
+    /// Close the stage that ran since the previous mark
+    pub fn mark(&mut self, stage: &str) {
+        let Some(last) = self.last else {
+            return;
+        };
+        let now = Instant::now();
+        self.stages.push(LatencyStage { name: stage.to_string(), us: (now - last).as_micros() as u64 });
+        self.last = Some(now);
+    }
+
+    pub fn set_bytes(&mut self, bytes: usize) {
+        self.bytes = Some(bytes);
+    }
+
+    pub fn set_cache_hit(&mut self, hit: bool) {
+        self.cache_hit = Some(hit);
+    }
+
+    /// Breakdown to attach to the response, with a "serialize" stage timing the
+    /// JSON encoding of `response` as a stand-in for the IPC cost
+    pub fn finish<T: Serialize>(mut self, response: &T) -> Option<LatencyBreakdown> {
+        let started = self.started?;
+        self.last = Some(Instant::now());
+        let encoded = serde_json::to_vec(response).map(|v| v.len()).unwrap_or(0);
+        self.mark("serialize");
+        let breakdown = LatencyBreakdown {
+            operation: self.operation.to_string(),
+            total_us: started.elapsed().as_micros() as u64,
+            stages: self.stages,
+            bytes: self.bytes.or(Some(encoded)),
+            cache_hit: self.cache_hit,
+        };
+        if let Ok(mut recorded) = RECORDED.lock() {
+            if recorded.len() >= MAX_RECORDED {
+                recorded.pop_front();
+            }
+            recorded.push_back(breakdown.clone());
+        }
+        Some(breakdown)
+    }
+}
+
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    sorted[((sorted.len() - 1) as f64 * p).round() as usize]
+}
+
+fn summarize(operation: &str, breakdowns: &[&LatencyBreakdown]) -> LatencyOperationSummary {
+    let mut totals: Vec<u64> = breakdowns.iter().map(|b| b.total_us).collect();
+    totals.sort_unstable();
+    let mut stage_order: Vec<String> = Vec::new();
+    let mut stage_times: HashMap<String, Vec<u64>> = HashMap::new();
+    for breakdown in breakdowns {
+        for stage in &breakdown.stages {
+            let times = stage_times.entry(stage.name.clone()).or_insert_with(|| {
+                stage_order.push(stage.name.clone());
+                Vec::new()
+            });
+            times.push(stage.us);
+        }
+    }
+    let stages = stage_order.into_iter()
+        .map(|name| {
+            let mut times = stage_times.remove(&name).unwrap_or_default();
+            times.sort_unstable();
+            LatencyStageSummary {
+                count: times.len(),
+                p50_us: percentile(&times, 0.5),
+                p95_us: percentile(&times, 0.95),
+                max_us: times.last().copied().unwrap_or(0),
+                name,
+            }
+        })
+        .collect();
+    LatencyOperationSummary {
+        operation: operation.to_string(),
+        count: breakdowns.len(),
+        p50_us: percentile(&totals, 0.5),
+        p95_us: percentile(&totals, 0.95),
+        max_us: totals.last().copied().unwrap_or(0),
+        cache_hits: breakdowns.iter().filter(|b| b.cache_hit == Some(true)).count(),
+        cache_misses: breakdowns.iter().filter(|b| b.cache_hit == Some(false)).count(),
+        stages,
+    }
+}
+
+/// Turn per-command timing breakdowns on or off. While on, read_memory,
+/// disassemble_memory, get_decompile_cache and lookup_memory_native return a
+/// `timing` field and the breakdowns are kept for get_latency_report.
+#[tauri::command]
+pub fn set_latency_instrumentation(enabled: bool, clear: Option<bool>) -> Result<(), String> {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if clear.unwrap_or(false) {
+        RECORDED.lock().map_err(|e| format!("Failed to lock latency records: {}", e))?.clear();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_latency_instrumentation() -> bool {
+    enabled()
+}
+
+/// Per-operation and per-stage percentiles over the recorded breakdowns
+#[tauri::command]
+pub fn get_latency_report(operation: Option<String>) -> Result<Vec<LatencyOperationSummary>, String> {
+    let recorded = RECORDED.lock().map_err(|e| format!("Failed to lock latency records: {}", e))?;
+    let mut by_operation: HashMap<&str, Vec<&LatencyBreakdown>> = HashMap::new();
+    for breakdown in recorded.iter() {
+        if operation.as_deref().is_some_and(|op| op != breakdown.operation) {
+            continue;
+        }
+        by_operation.entry(breakdown.operation.as_str()).or_default().push(breakdown);
+    }
+    let mut summaries: Vec<LatencyOperationSummary> = by_operation.iter()
+        .map(|(operation, breakdowns)| summarize(operation, breakdowns))
+        .collect();
+    summaries.sort_by(|a, b| a.operation.cmp(&b.operation));
+    Ok(summaries)
+}
@@ -11,7 +11,18 @@ use tokio::io::AsyncWriteExt;
 use rusqlite::{Connection, params};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use wasmparser::{Parser, Payload, Operator};
+use wasmparser::{Parser, Payload};
+use dynadbg_core::decompile_cache;
+use dynadbg_core::memory_reader::{
+    self as memory_reader, fetch_memory_regions_from_server, read_chunks_parallel, read_memory_bytes, RemoteMemoryRegion,
+};
+use dynadbg_core::scan_store::{
+    clear_scan, get_data_size, get_latest_scan_generation, get_scan_cancel_flag, get_scan_generation_dir,
+    get_unknown_scan_temp_dir, list_scan_region_files, new_scan_id, read_scan_hits, read_scan_region_file, run_aob_scan,
+    run_exact_scan, run_native_scan, validate_scan_id, write_scan_region_file, AobScanRequest, ExactScanRequest, NativeScan,
+    ProgressSink, ScanMatcher, UnknownScanProgress, UnknownScanResponse, UNKNOWN_SCAN_CANCEL, UNKNOWN_SCAN_PROGRESS,
+};
+use dynadbg_core::server_connection::{server_address, server_address_with_token, SERVER_CONFIG};
 
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
@@ -50,11 +61,89 @@ fn hide_console_window(cmd: &mut Command) -> &mut Command {
 }
 
 mod state;
-
-// Global SQLite connection for Ghidra functions cache
-static GHIDRA_DB: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| {
-    Mutex::new(None)
-});
+mod symbol_server;
+mod build_id;
+mod module_diff;
+mod scan_session;
+mod scan_sampling;
+mod struct_dissector;
+mod memory_usage;
+mod memory_regions;
+mod hook_detector;
+mod event_bus;
+mod disassembly;
+mod secure_store;
+mod symbolizer;
+mod virtual_addresses;
+mod assembler;
+mod smc_monitor;
+mod jit_regions;
+mod patches;
+mod breakpoints;
+mod disasm_comments;
+mod expression;
+mod struct_profiler;
+mod watchpoints;
+mod signatures;
+mod data_overlay;
+mod registers;
+mod disasm_export;
+mod analysis_policy;
+mod threads;
+mod clock_sync;
+mod native_trace;
+mod object_heuristics;
+mod trace_sources;
+mod trace_diff;
+mod latency;
+mod trace_export;
+mod coverage;
+mod region_snapshots;
+mod process_control;
+mod module_symbols;
+mod server_connection;
+mod memory_dump;
+mod module_dump;
+mod ghidra_projects;
+mod ghidra_supervisor;
+mod ghidra_batch;
+mod ghidra_edits;
+mod ghidra_search;
+mod ghidra_diff;
+mod decompiler;
+mod frida;
+mod scripting;
+mod cheat_table;
+mod watchlist;
+mod hotkeys;
+mod remote_memory;
+mod hex_view;
+mod value_codec;
+pub mod scan_kernel;
+mod scan_generations;
+mod disasm_cache;
+mod objc_metadata;
+mod art_bridge;
+mod il2cpp;
+mod mono_runtime;
+mod wasm_debug_info;
+mod wasm_disasm;
+mod wasm_cfg;
+mod cfg_export;
+mod debug_symbols;
+mod source_view;
+mod exception_rules;
+mod access_heatmap;
+mod struct_inference;
+mod vtables;
+mod cache_versions;
+mod db;
+mod global_search;
+mod annotations;
+mod annotation_sync;
+
+// Global SQLite connection pool for the Ghidra cache database (see db)
+static GHIDRA_DB: Lazy<db::Database> = Lazy::new(db::Database::new);
 
 // Global Ghidra server processes (project_path -> child process)
 static GHIDRA_SERVERS: Lazy<Mutex<HashMap<String, Child>>> = Lazy::new(|| {
@@ -79,11 +168,12 @@ static PTRSCAN_CANCEL: Lazy<std::sync::atomic::AtomicBool> = Lazy::new(|| {
 fn init_ghidra_db() -> Result<(), String> {
     let ghidra_dir = get_ghidra_projects_dir();
     std::fs::create_dir_all(&ghidra_dir).map_err(|e| e.to_string())?;
-    
-    let db_path = ghidra_dir.join("ghidra_cache.db");
-    let conn = Connection::open(&db_path).map_err(|e| e.to_string())?;
-    
-    // Create tables
+    GHIDRA_DB.open(&ghidra_dir.join("ghidra_cache.db"))?;
+    db::run_sync(server_connection::load_pins)
+}
+
+/// Tables of the baseline schema (the first of db::MIGRATIONS)
+fn create_ghidra_tables(conn: &Connection) -> Result<(), String> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS analyzed_modules (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -97,7 +187,7 @@ fn init_ghidra_db() -> Result<(), String> {
         )",
         [],
     ).map_err(|e| e.to_string())?;
-    
+
     conn.execute(
         "CREATE TABLE IF NOT EXISTS module_functions (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -109,12 +199,12 @@ fn init_ghidra_db() -> Result<(), String> {
         )",
         [],
     ).map_err(|e| e.to_string())?;
-    
+
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_module_functions_module_id ON module_functions(module_id)",
         [],
     ).map_err(|e| e.to_string())?;
-    
+
     // Simple JSON cache table for frontend compatibility
     conn.execute(
         "CREATE TABLE IF NOT EXISTS ghidra_functions_cache (
@@ -126,7 +216,7 @@ fn init_ghidra_db() -> Result<(), String> {
         )",
         [],
     ).map_err(|e| e.to_string())?;
-    
+
     // Decompile cache table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS ghidra_decompile_cache (
@@ -141,7 +231,7 @@ fn init_ghidra_db() -> Result<(), String> {
         )",
         [],
     ).map_err(|e| e.to_string())?;
-    
+
     // Xref cache table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS ghidra_xref_cache (
@@ -155,17 +245,113 @@ fn init_ghidra_db() -> Result<(), String> {
         )",
         [],
     ).map_err(|e| e.to_string())?;
-    
-    *GHIDRA_DB.lock().unwrap() = Some(conn);
+
+    // Whole-program call graph cache
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ghidra_callgraph_cache (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            callgraph_json TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+
+    // Defined data items (/data output) per module
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ghidra_data_cache (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            data_json TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+
+    // Build-id / UUID / PDB signature per loaded module
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS module_build_ids (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            format TEXT,
+            build_id TEXT NOT NULL,
+            pdb_name TEXT,
+            pdb_age INTEGER,
+            file_matches INTEGER,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+
+    // User-defined struct layouts for dissect_memory (module_name '' = any module)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS struct_definitions (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            name TEXT NOT NULL,
+            definition_json TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name, name)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+
+    // Frontend settings (server profiles, tokens); see secure_store
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+
+    smc_monitor::init(conn)?;
+    patches::init(conn)?;
+    breakpoints::init(conn)?;
+    signatures::init(conn)?;
+    analysis_policy::init(conn)?;
+    module_symbols::init(conn)?;
+    server_connection::init(conn)?;
+    ghidra_search::init(conn)?;
+    decompiler::init(conn)?;
+    watchlist::init(conn)?;
+    hotkeys::init(conn)?;
+    il2cpp::init(conn)?;
+    mono_runtime::init(conn)?;
+    debug_symbols::init(conn)?;
+    source_view::init(conn)?;
+    symbol_server::init(conn)?;
+    exception_rules::init(conn)?;
+    vtables::init(conn)?;
+    cache_versions::init(conn)?;
     Ok(())
 }
 
+/// Append " ; symbol" for a call/jump with a direct target inside a known module
+fn append_branch_symbol(
+    line: &mut String,
+    symbolizer: &symbolizer::Symbolizer,
+    cs: &Capstone,
+    insn: &capstone::Insn,
+    architecture: &str,
+) {
+    let target = disassembly::describe_instruction(cs, insn, architecture).branch_target;
+    if let Some(symbol) = target.and_then(|t| symbolizer.resolve(t)) {
+        line.push_str(" ; ");
+        line.push_str(&symbol.display);
+    }
+}
+
 // Helper function to format ARM64 operands more clearly
 fn format_arm64_operands(op_str: &str) -> String {
     // Basic formatting for ARM64 operands
     // Add spaces around commas for better readability
     let formatted = op_str.replace(",", ", ");
-    
+
     // Handle common ARM64 addressing modes
     if formatted.contains("[") && formatted.contains("]") {
         // Memory addressing - keep as is but ensure proper spacing
@@ -184,6 +370,10 @@ pub struct DisassembleRequest {
     pub address: u64,
     pub size: usize,
     pub architecture: String,
+    #[serde(default)]
+    pub symbolicate: bool,    // Annotate call/jump targets with "; module!function+0x10"
+    #[serde(default)]
+    pub comments: bool,       // Structured output only: run the comment providers
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -192,6 +382,8 @@ pub struct DisassembleResponse {
     pub disassembly: Option<String>,
     pub instructions_count: usize,
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<latency::LatencyBreakdown>,  // Sub-operation timings when latency instrumentation is on
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -205,6 +397,8 @@ pub struct MemoryReadResponse {
     pub success: bool,
     pub data: Option<Vec<u8>>,
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<latency::LatencyBreakdown>,  // Sub-operation timings when latency instrumentation is on
 }
 
 // Ghidra integration structures
@@ -247,6 +441,8 @@ pub struct GhidraDecompileResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tokens: Option<Vec<GhidraTokenInfo>>, // Token information for syntax highlighting
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<latency::LatencyBreakdown>,  // Sub-operation timings when latency instrumentation is on
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -281,14 +477,50 @@ pub struct MemoryFilterRequest {
     pub old_values: Vec<Vec<u8>>,      // Previous values at those addresses (hex bytes)
     pub pattern: String,               // Hex-encoded pattern for comparison (min for range)
     pub pattern_max: Option<String>,   // Hex-encoded max pattern for range filter
-    pub data_type: String,             // "int8", "uint8", "int16", "uint16", "int32", "uint32", "int64", "uint64", "float", "double", "bytes", "string", "regex"
-    pub filter_method: String,         // "exact", "range", "greater_or_equal", "less_than", "changed", "unchanged", "increased", "decreased"
+    pub data_type: String,             // "int8", "uint8", "int16", "uint16", "int32", "uint32", "int64", "uint64", "float", "double", "bytes", "string", "utf16", "regex" or a value_codec type
+    pub filter_method: String,         // "exact", "range", "greater_or_equal", "less_than", "changed", "unchanged", "increased", "decreased", "fuzzy"
+    #[serde(default)]
+    pub case_insensitive: bool,        // For "string", "utf16" and "regex" data types
+    #[serde(default)]
+    pub max_length: Option<usize>,     // Bytes read per address for "regex" (default 256)
+    #[serde(default)]
+    pub fuzzy: Option<FuzzyFloatOptions>, // For "fuzzy"
+}
+
+/// Tolerance of the "fuzzy" filter for float / double scans. Without a
+/// rounding mode a value matches within max(epsilon, percent of the pattern).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FuzzyFloatOptions {
+    #[serde(default)]
+    pub epsilon: Option<f64>,          // Absolute band (default FUZZY_DEFAULT_EPSILON when percent is unset)
+    #[serde(default)]
+    pub percent: Option<f64>,          // Band relative to the pattern, e.g. 1.0 for +-1%
+    #[serde(default)]
+    pub rounding: Option<String>,      // "truncated": floor(value) == int(pattern), "rounded": round(value) == int(pattern)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryFilterResult {
     pub address: u64,
     pub value: Vec<u8>,  // New value at the address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<Vec<PointerDerefStep>>,  // Dereference chain when looked up as "pointer"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_item: Option<data_overlay::DataItemRef>,  // Ghidra data item containing the address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_hint: Option<object_heuristics::ObjectHint>,  // Likely containing object, when requested
+}
+
+/// One level of a pointer dereference in lookup results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointerDerefStep {
+    pub target: u64,
+    pub readable: bool,
+    pub protection: Option<String>,     // "rwx" style, from the remote memory map
+    pub module_name: Option<String>,
+    pub module_offset: Option<u64>,
+    pub preview_hex: Option<String>,
+    pub preview_ascii: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -297,23 +529,15 @@ pub struct MemoryFilterResponse {
     pub results: Vec<MemoryFilterResult>,
     pub total_processed: usize,
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<latency::LatencyBreakdown>,  // Sub-operation timings when latency instrumentation is on
 }
 
-// Global state to store server connection info
-struct ServerConfig {
-    host: String,
-    port: u16,
-    auth_token: Option<String>,
+/// FNV-1a; only used to notice that memory changed, not as a secure hash
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325u64, |h, b| (h ^ *b as u64).wrapping_mul(0x100000001b3))
 }
 
-static SERVER_CONFIG: Lazy<RwLock<ServerConfig>> = Lazy::new(|| {
-    RwLock::new(ServerConfig {
-        host: String::new(),
-        port: 3030,
-        auth_token: None,
-    })
-});
-
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -321,10 +545,20 @@ fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-async fn set_server_connection(host: String, port: u16) -> Result<(), String> {
+async fn set_server_connection(
+    host: String,
+    port: u16,
+    use_tls: Option<bool>,
+    accept_self_signed: Option<bool>,
+) -> Result<(), String> {
     let mut config = SERVER_CONFIG.write().map_err(|e| e.to_string())?;
     config.host = host;
     config.port = port;
+    config.use_tls = use_tls.unwrap_or(false);
+    config.accept_self_signed = accept_self_signed.unwrap_or(false);
+    drop(config);
+    server_connection::reset_client();
+    memory_reader::reset_read_protocol();
     Ok(())
 }
 
@@ -337,20 +571,348 @@ fn set_auth_token(token: Option<String>) -> Result<(), String> {
 
 /// Helper function to read memory from server
 async fn read_memory_from_server(host: &str, port: u16, address: u64, size: usize) -> Result<Vec<u8>, String> {
-    let client = reqwest::Client::new();
-    let url = format!("http://{}:{}/api/memory/read?address={}&size={}", host, port, address, size);
-    
-    let response = client.get(&url).send().await
-        .map_err(|e| format!("Network error: {}", e))?;
-    
+    read_memory_bytes(host, port, address, size).await.map(|bytes| bytes.to_vec())
+}
+
+/// Helper function to write memory through the server
+async fn write_memory_to_server(host: &str, port: u16, address: u64, data: &[u8]) -> Result<(), String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/memory/write", server_connection::base_url(host, port));
+
+    let mut request = client.post(&url).json(&serde_json::json!({
+        "address": address,
+        "buffer": data,
+    }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = server_connection::send(request).await?;
     if !response.status().is_success() {
         return Err(format!("Server error: {}", response.status()));
     }
-    
-    let bytes = response.bytes().await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-    
-    Ok(bytes.to_vec())
+    disasm_cache::invalidate_range(address, data.len());
+    Ok(())
+}
+
+/// Set a hardware watchpoint (`access` is "r", "w", "rw", ...; size 1/2/4/8)
+/// Returns the server's watchpoint id
+async fn set_watchpoint_on_server(host: &str, port: u16, address: u64, size: usize, access: &str) -> Result<String, String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/debug/watchpoint", server_connection::base_url(host, port));
+
+    let mut request = client.post(&url).json(&serde_json::json!({
+        "address": address,
+        "size": size,
+        "_type": access,
+    }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = server_connection::send(request).await?;
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    if json["success"].as_bool() != Some(true) {
+        return Err(json["message"].as_str().unwrap_or("Failed to set watchpoint").to_string());
+    }
+    Ok(json["watchpoint_id"].as_str()
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| format!("wp_{}_{}", address, state::AppState::current_timestamp())))
+}
+
+async fn remove_watchpoint_on_server(host: &str, port: u16, address: u64) -> Result<(), String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/debug/watchpoint", server_connection::base_url(host, port));
+
+    let mut request = client.delete(&url).json(&serde_json::json!({ "address": address }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = server_connection::send(request).await?;
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Set a breakpoint (`hit_count` 0 keeps it until removed)
+async fn set_breakpoint_on_server(host: &str, port: u16, address: u64, hit_count: i32, is_software: bool) -> Result<(), String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/debug/breakpoint", server_connection::base_url(host, port));
+
+    let mut request = client.post(&url).json(&serde_json::json!({
+        "address": address,
+        "hit_count": hit_count,
+        "is_software": is_software,
+    }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = server_connection::send(request).await?;
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    if json["success"].as_bool() != Some(true) {
+        return Err(json["message"].as_str().unwrap_or("Failed to set breakpoint").to_string());
+    }
+    Ok(())
+}
+
+async fn remove_breakpoint_on_server(host: &str, port: u16, address: u64) -> Result<(), String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/debug/breakpoint", server_connection::base_url(host, port));
+
+    let mut request = client.delete(&url).json(&serde_json::json!({ "address": address }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = server_connection::send(request).await?;
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Resume a stopped thread (all threads when `thread_id` is None)
+async fn continue_execution_on_server(host: &str, port: u16, thread_id: Option<u64>) -> Result<(), String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/debug/continue", server_connection::base_url(host, port));
+
+    let body = match thread_id {
+        Some(thread_id) => serde_json::json!({ "thread_id": thread_id }),
+        None => serde_json::json!({}),
+    };
+    let mut request = client.post(&url).json(&body);
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = server_connection::send(request).await?;
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    Ok(())
+}
+
+async fn read_register_from_server(host: &str, port: u16, thread_id: u64, name: &str) -> Result<u64, String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/debug/register/read", server_connection::base_url(host, port));
+
+    let mut request = client.post(&url).json(&serde_json::json!({
+        "thread_id": thread_id,
+        "register_name": name,
+    }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = server_connection::send(request).await?;
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    match json["value"].as_u64() {
+        Some(value) if json["success"].as_bool() == Some(true) => Ok(value),
+        _ => Err(json["message"].as_str().unwrap_or("Failed to read register").to_string()),
+    }
+}
+
+async fn write_register_to_server(host: &str, port: u16, thread_id: u64, name: &str, value: u64) -> Result<(), String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/debug/register/write", server_connection::base_url(host, port));
+
+    let mut request = client.post(&url).json(&serde_json::json!({
+        "thread_id": thread_id,
+        "register_name": name,
+        "value": value,
+    }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = server_connection::send(request).await?;
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    if json["success"].as_bool() != Some(true) {
+        return Err(json["message"].as_str().unwrap_or("Failed to write register").to_string());
+    }
+    Ok(())
+}
+
+async fn fetch_threads_from_server(host: &str, port: u16) -> Result<Vec<serde_json::Value>, String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/threads", server_connection::base_url(host, port));
+
+    let mut request_builder = client.get(&url);
+    if let Some(token) = auth_token {
+        request_builder = request_builder.header("Authorization", format!("Bearer {}", token));
+    }
+    let response = server_connection::send(request_builder).await?;
+
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse threads: {}", e))?;
+    if json["success"].as_bool() != Some(true) {
+        return Err(json["message"].as_str().unwrap_or("Failed to enumerate threads").to_string());
+    }
+    Ok(json["data"]["threads"].as_array().cloned().unwrap_or_default())
+}
+
+/// Single-step one thread; the resulting stop arrives as a single_step exception
+async fn single_step_on_server(host: &str, port: u16, thread_id: u64) -> Result<(), String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!("{}/api/debug/step", server_connection::base_url(host, port));
+
+    let mut request = client.post(&url).json(&serde_json::json!({ "thread_id": thread_id }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let response = server_connection::send(request).await?;
+    if !response.status().is_success() {
+        let json: serde_json::Value = response.json().await.unwrap_or_default();
+        return Err(json["message"].as_str().unwrap_or("Single step failed").to_string());
+    }
+    Ok(())
+}
+
+/// Drain queued exceptions of the given types (comma separated) from the
+/// server; exceptions of other types stay queued
+async fn fetch_exceptions_from_server(
+    host: &str,
+    port: u16,
+    exception_types: &str,
+    singlestep_modes: &str,
+) -> Result<Vec<serde_json::Value>, String> {
+    let auth_token = SERVER_CONFIG.read().map_err(|e| e.to_string())?.auth_token.clone();
+    let client = server_connection::client()?;
+    let url = format!(
+        "{}/api/debug/exception?exception_type={}&singlestep_mode={}",
+        server_connection::base_url(host, port), urlencoding::encode(exception_types), urlencoding::encode(singlestep_modes)
+    );
+
+    let mut request_builder = client.get(&url);
+    if let Some(token) = auth_token {
+        request_builder = request_builder.header("Authorization", format!("Bearer {}", token));
+    }
+    let response = server_connection::send(request_builder).await?;
+
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse exceptions: {}", e))?;
+    Ok(json["data"]["exceptions"].as_array().cloned().unwrap_or_default())
+}
+
+/// Follow a pointer value `depth` levels, annotating each target with the
+/// region protection, the module it points into and a short byte preview
+async fn dereference_pointer_chain(
+    host: &str,
+    port: u16,
+    regions: &[RemoteMemoryRegion],
+    module_bases: &HashMap<String, u64>,
+    value: &[u8],
+    pointer_size: usize,
+    depth: u32,
+) -> Vec<PointerDerefStep> {
+    const PREVIEW_SIZE: usize = 16;
+    let read_ptr = |bytes: &[u8]| -> Option<u64> {
+        if pointer_size == 4 {
+            bytes.get(..4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as u64)
+        } else {
+            bytes.get(..8).map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        }
+    };
+
+    let mut steps = Vec::new();
+    let mut visited = std::collections::HashSet::new();
+    let mut current = read_ptr(value);
+    for _ in 0..depth {
+        // Stop at null and at cycles (a self-referencing pointer would repeat forever)
+        let Some(target) = current.filter(|&t| t != 0 && visited.insert(t)) else {
+            break;
+        };
+        let region = regions.iter().find(|r| target >= r.start && target < r.end);
+        let readable = region.is_some_and(|r| r.protection.starts_with('r'));
+        let module_name = region.and_then(|r| r.file_path.as_ref()).map(|p| {
+            p.rsplit(['/', '\\']).next().unwrap_or(p).to_string()
+        });
+        let module_offset = region
+            .and_then(|r| r.file_path.as_ref())
+            .and_then(|p| module_bases.get(p))
+            .map(|base| target - base);
+
+        let preview = if readable {
+            read_memory_bytes(host, port, target, PREVIEW_SIZE.max(pointer_size)).await.ok()
+        } else {
+            None
+        };
+        steps.push(PointerDerefStep {
+            target,
+            readable: preview.is_some(),
+            protection: region.map(|r| r.protection.clone()),
+            module_name,
+            module_offset,
+            preview_hex: preview.as_ref().map(|b| hex::encode(&b[..b.len().min(PREVIEW_SIZE)])),
+            preview_ascii: preview.as_ref().map(|b| {
+                b.iter().take(PREVIEW_SIZE)
+                    .map(|&c| if c.is_ascii_graphic() || c == b' ' { c as char } else { '.' })
+                    .collect()
+            }),
+        });
+        current = preview.as_deref().and_then(read_ptr);
+    }
+    steps
+}
+
+// Band of the "fuzzy" filter when no tolerance is given: absorbs a whole
+// number shown in-game for a float that carries fractional noise
+const FUZZY_DEFAULT_EPSILON: f64 = 0.5;
+
+/// "fuzzy" filter for float / double values
+fn fuzzy_float_match(new_val: &[u8], pattern: &[u8], data_type: &str, options: Option<&FuzzyFloatOptions>) -> bool {
+    let decode = |bytes: &[u8]| -> Option<f64> {
+        match data_type {
+            "float" => Some(f32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as f64),
+            "double" => Some(f64::from_le_bytes(bytes.get(..8)?.try_into().ok()?)),
+            _ => None,
+        }
+    };
+    let (Some(value), Some(target)) = (decode(new_val), decode(pattern)) else {
+        return false;
+    };
+    if !value.is_finite() || !target.is_finite() {
+        return false;
+    }
+    let options = options.cloned().unwrap_or_default();
+    match options.rounding.as_deref() {
+        Some("truncated") => value.floor() == target.trunc(),
+        Some("rounded") => value.round() == target.trunc(),
+        _ => {
+            let relative = options.percent.map(|p| target.abs() * p.abs() / 100.0);
+            let absolute = match (options.epsilon, relative) {
+                (None, None) => Some(FUZZY_DEFAULT_EPSILON),
+                (epsilon, _) => epsilon.map(f64::abs),
+            };
+            let tolerance = absolute.unwrap_or(0.0).max(relative.unwrap_or(0.0));
+            (value - target).abs() <= tolerance
+        }
+    }
 }
 
 /// Compare two values based on data type and filter method
@@ -361,9 +923,14 @@ fn compare_values(
     pattern_max: Option<&[u8]>,
     data_type: &str,
     filter_method: &str,
+    fuzzy: Option<&FuzzyFloatOptions>,
 ) -> bool {
+    if let Some(value_type) = value_codec::ValueType::parse(data_type) {
+        return value_codec::compare(&value_type, new_val, old_val, pattern, pattern_max, filter_method);
+    }
     match filter_method {
         "exact" => new_val == pattern,
+        "fuzzy" => fuzzy_float_match(new_val, pattern, data_type, fuzzy),
         "range" => {
             let max_bytes = match pattern_max {
                 Some(b) => b,
@@ -534,12 +1101,12 @@ fn compare_values(
                 }
                 "int64" => {
                     if new_val.len() < 8 || old_val.len() < 8 { return false; }
-                    i64::from_le_bytes([new_val[0], new_val[1], new_val[2], new_val[3], new_val[4], new_val[5], new_val[6], new_val[7]]) > 
+                    i64::from_le_bytes([new_val[0], new_val[1], new_val[2], new_val[3], new_val[4], new_val[5], new_val[6], new_val[7]]) >
                     i64::from_le_bytes([old_val[0], old_val[1], old_val[2], old_val[3], old_val[4], old_val[5], old_val[6], old_val[7]])
                 }
                 "uint64" => {
                     if new_val.len() < 8 || old_val.len() < 8 { return false; }
-                    u64::from_le_bytes([new_val[0], new_val[1], new_val[2], new_val[3], new_val[4], new_val[5], new_val[6], new_val[7]]) > 
+                    u64::from_le_bytes([new_val[0], new_val[1], new_val[2], new_val[3], new_val[4], new_val[5], new_val[6], new_val[7]]) >
                     u64::from_le_bytes([old_val[0], old_val[1], old_val[2], old_val[3], old_val[4], old_val[5], old_val[6], old_val[7]])
                 }
                 "float" => {
@@ -585,12 +1152,12 @@ fn compare_values(
                 }
                 "int64" => {
                     if new_val.len() < 8 || old_val.len() < 8 { return false; }
-                    i64::from_le_bytes([new_val[0], new_val[1], new_val[2], new_val[3], new_val[4], new_val[5], new_val[6], new_val[7]]) < 
+                    i64::from_le_bytes([new_val[0], new_val[1], new_val[2], new_val[3], new_val[4], new_val[5], new_val[6], new_val[7]]) <
                     i64::from_le_bytes([old_val[0], old_val[1], old_val[2], old_val[3], old_val[4], old_val[5], old_val[6], old_val[7]])
                 }
                 "uint64" => {
                     if new_val.len() < 8 || old_val.len() < 8 { return false; }
-                    u64::from_le_bytes([new_val[0], new_val[1], new_val[2], new_val[3], new_val[4], new_val[5], new_val[6], new_val[7]]) < 
+                    u64::from_le_bytes([new_val[0], new_val[1], new_val[2], new_val[3], new_val[4], new_val[5], new_val[6], new_val[7]]) <
                     u64::from_le_bytes([old_val[0], old_val[1], old_val[2], old_val[3], old_val[4], old_val[5], old_val[6], old_val[7]])
                 }
                 "float" => {
@@ -612,14 +1179,86 @@ fn compare_values(
     }
 }
 
-/// Get data size for a given data type
-fn get_data_size(data_type: &str) -> usize {
-    match data_type {
-        "int8" | "uint8" => 1,
-        "int16" | "uint16" => 2,
-        "int32" | "uint32" | "float" => 4,
-        "int64" | "uint64" | "double" => 8,
-        _ => 1,
+/// Default number of bytes read per address for regex matching
+const DEFAULT_STRING_MAX_LENGTH: usize = 256;
+
+/// Compiled matcher for the "string" (UTF-8), "utf16" (UTF-16LE) and "regex" scan modes.
+/// Plain strings are compiled as escaped regexes so case-insensitive matching
+/// behaves the same in every mode.
+struct StringMatcher {
+    regex: regex::bytes::Regex,
+    utf16: bool,
+    is_regex: bool,
+    pattern_len: usize,  // Bytes a plain string match can span
+}
+
+impl StringMatcher {
+    fn new(pattern: &str, is_regex: bool, utf16: bool, case_insensitive: bool) -> Result<Self, String> {
+        if pattern.is_empty() {
+            return Err("Search string is empty".to_string());
+        }
+        let source = if is_regex { pattern.to_string() } else { regex::escape(pattern) };
+        let regex = regex::bytes::RegexBuilder::new(&format!("^(?:{})", source))
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|e| format!("Invalid regex: {}", e))?;
+        // Case folding can swap a character for one of another encoded length
+        // (k / U+212A KELVIN SIGN), so allow the widest encoding per character
+        let pattern_len = match (case_insensitive, utf16) {
+            (true, _) => pattern.chars().count() * 4,
+            (false, true) => pattern.encode_utf16().count() * 2,
+            (false, false) => pattern.len(),
+        };
+        Ok(Self { regex, utf16, is_regex, pattern_len })
+    }
+
+    /// Matcher for a filter data type, or None for numeric types and for
+    /// filters that compare against the old value (their pattern is empty)
+    fn for_data_type(data_type: &str, filter_method: &str, pattern: &str, case_insensitive: bool) -> Result<Option<Self>, String> {
+        if filter_method != "exact" {
+            return Ok(None);
+        }
+        match data_type {
+            "string" => Self::new(pattern, false, false, case_insensitive).map(Some),
+            "utf16" => Self::new(pattern, false, true, case_insensitive).map(Some),
+            "regex" => Self::new(pattern, true, false, case_insensitive).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Bytes to read per candidate address
+    fn read_size(&self, max_length: Option<usize>) -> usize {
+        if self.is_regex {
+            max_length.unwrap_or(DEFAULT_STRING_MAX_LENGTH).max(1)
+        } else {
+            self.pattern_len
+        }
+    }
+
+    /// Length in bytes of a non-empty match at the start of `data`
+    fn match_len(&self, data: &[u8]) -> Option<usize> {
+        if self.utf16 {
+            // Decode up to the first unpaired surrogate, match on the text and
+            // convert the match end back to UTF-16 code units
+            let units: Vec<u16> = data.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect();
+            let text: String = char::decode_utf16(units).map_while(|c| c.ok()).collect();
+            let m = self.regex.find(text.as_bytes())?;
+            let len = text[..m.end()].encode_utf16().count() * 2;
+            (len > 0).then_some(len)
+        } else {
+            let m = self.regex.find(data)?;
+            (m.end() > 0).then_some(m.end())
+        }
+    }
+}
+
+/// Text of a decoded filter pattern; a UTF-16 pattern arrives as UTF-16LE bytes
+fn filter_pattern_text(data_type: &str, pattern_bytes: &[u8]) -> String {
+    if data_type == "utf16" {
+        let units: Vec<u16> = pattern_bytes.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(pattern_bytes).into_owned()
     }
 }
 
@@ -631,64 +1270,97 @@ async fn filter_memory_native(request: MemoryFilterRequest) -> Result<MemoryFilt
         let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
         (config.host.clone(), config.port)
     };
-    
+
     if host.is_empty() {
         return Ok(MemoryFilterResponse {
             success: false,
             results: vec![],
             total_processed: 0,
             error: Some("No server connection configured".to_string()),
+            timing: None,
         });
     }
 
-    let data_size = get_data_size(&request.data_type);
     let pattern_bytes = hex::decode(&request.pattern).unwrap_or_default();
     let pattern_max_bytes = request.pattern_max.as_ref()
         .and_then(|p| hex::decode(p).ok());
 
+    // String types compare by text match instead of fixed-size values
+    let string_matcher = StringMatcher::for_data_type(
+        &request.data_type,
+        &request.filter_method,
+        &filter_pattern_text(&request.data_type, &pattern_bytes),
+        request.case_insensitive,
+    )?;
+    let is_string_type = matches!(request.data_type.as_str(), "string" | "utf16" | "regex");
+    let data_size = match &string_matcher {
+        Some(m) => m.read_size(request.max_length),
+        // Other string filters compare against the previously matched text
+        None if is_string_type => request.old_values.iter().map(|v| v.len()).max().unwrap_or(0),
+        None => get_data_size(&request.data_type),
+    };
+
+    // Returns how many bytes of the new value to keep when the address passes the filter
+    let filter_value = |new_val: &[u8], old_val: &[u8]| -> Option<usize> {
+        match &string_matcher {
+            Some(m) => m.match_len(new_val),
+            None if is_string_type => {
+                let new_val = new_val.get(..old_val.len()).filter(|_| !old_val.is_empty())?;
+                compare_values(new_val, old_val, &[], None, &request.data_type, &request.filter_method, None)
+                    .then_some(new_val.len())
+            }
+            _ => compare_values(
+                new_val,
+                old_val,
+                &pattern_bytes,
+                pattern_max_bytes.as_deref(),
+                &request.data_type,
+                &request.filter_method,
+                request.fuzzy.as_ref(),
+            ).then_some(new_val.len()),
+        }
+    };
+
     let addresses = &request.addresses;
     let old_values = &request.old_values;
-    
+
     if addresses.is_empty() {
         return Ok(MemoryFilterResponse {
             success: true,
             results: vec![],
             total_processed: 0,
             error: None,
+            timing: None,
         });
     }
 
     let mut results: Vec<MemoryFilterResult> = Vec::new();
-    
+
     // Optimization threshold: if more than 100 addresses and they span less than 1MB, read entire range
     const BULK_READ_THRESHOLD: usize = 100;
     const MAX_BULK_READ_SIZE: u64 = 1024 * 1024; // 1MB max for bulk read
-    
+
     let min_addr = *addresses.iter().min().unwrap();
     let max_addr = *addresses.iter().max().unwrap();
     let addr_range = max_addr - min_addr + data_size as u64;
-    
+
     if addresses.len() >= BULK_READ_THRESHOLD && addr_range <= MAX_BULK_READ_SIZE {
         // Bulk read: read the entire min-max range at once
-        match read_memory_from_server(&host, port, min_addr, addr_range as usize).await {
+        match read_memory_bytes(&host, port, min_addr, addr_range as usize).await {
             Ok(bulk_data) => {
                 for (i, &addr) in addresses.iter().enumerate() {
                     let offset = (addr - min_addr) as usize;
                     if offset + data_size <= bulk_data.len() {
                         let new_val = &bulk_data[offset..offset + data_size];
                         let old_val = if i < old_values.len() { &old_values[i] } else { &[] as &[u8] };
-                        
-                        if compare_values(
-                            new_val,
-                            old_val,
-                            &pattern_bytes,
-                            pattern_max_bytes.as_deref(),
-                            &request.data_type,
-                            &request.filter_method,
-                        ) {
+
+                        if let Some(len) = filter_value(new_val, old_val) {
                             results.push(MemoryFilterResult {
                                 address: addr,
-                                value: new_val.to_vec(),
+                                value: new_val[..len].to_vec(),
+                                pointer: None,
+                                data_item: None,
+                                object_hint: None,
                             });
                         }
                     }
@@ -700,6 +1372,7 @@ async fn filter_memory_native(request: MemoryFilterRequest) -> Result<MemoryFilt
                     results: vec![],
                     total_processed: 0,
                     error: Some(format!("Bulk memory read failed: {}", e)),
+                    timing: None,
                 });
             }
         }
@@ -708,16 +1381,16 @@ async fn filter_memory_native(request: MemoryFilterRequest) -> Result<MemoryFilt
         // and read each chunk separately
         const CHUNK_GAP_THRESHOLD: u64 = 4096; // If gap is more than 4KB, start a new chunk
         const MAX_CHUNK_SIZE: usize = 65536; // Max 64KB per chunk
-        
+
         // Sort addresses with their original indices
         let mut addr_indices: Vec<(u64, usize)> = addresses.iter().enumerate()
             .map(|(i, &a)| (a, i))
             .collect();
         addr_indices.sort_by_key(|&(a, _)| a);
-        
+
         // Group into chunks
         let mut chunks: Vec<(u64, usize, Vec<(u64, usize)>)> = Vec::new(); // (start_addr, size, [(addr, original_idx)])
-        
+
         for (addr, orig_idx) in addr_indices {
             if chunks.is_empty() {
                 chunks.push((addr, data_size, vec![(addr, orig_idx)]));
@@ -725,7 +1398,7 @@ async fn filter_memory_native(request: MemoryFilterRequest) -> Result<MemoryFilt
                 let last = chunks.last_mut().unwrap();
                 let gap = addr.saturating_sub(last.0 + last.1 as u64);
                 let new_size = (addr - last.0) as usize + data_size;
-                
+
                 if gap <= CHUNK_GAP_THRESHOLD && new_size <= MAX_CHUNK_SIZE {
                     // Extend current chunk
                     last.1 = new_size;
@@ -736,28 +1409,24 @@ async fn filter_memory_native(request: MemoryFilterRequest) -> Result<MemoryFilt
                 }
             }
         }
-        
+
         // Read and process each chunk
         for (chunk_start, chunk_size, chunk_addrs) in chunks {
-            match read_memory_from_server(&host, port, chunk_start, chunk_size).await {
+            match read_memory_bytes(&host, port, chunk_start, chunk_size).await {
                 Ok(chunk_data) => {
                     for (addr, orig_idx) in chunk_addrs {
                         let offset = (addr - chunk_start) as usize;
                         if offset + data_size <= chunk_data.len() {
                             let new_val = &chunk_data[offset..offset + data_size];
                             let old_val = if orig_idx < old_values.len() { &old_values[orig_idx] } else { &[] as &[u8] };
-                            
-                            if compare_values(
-                                new_val,
-                                old_val,
-                                &pattern_bytes,
-                                pattern_max_bytes.as_deref(),
-                                &request.data_type,
-                                &request.filter_method,
-                            ) {
+
+                            if let Some(len) = filter_value(new_val, old_val) {
                                 results.push(MemoryFilterResult {
                                     address: addr,
-                                    value: new_val.to_vec(),
+                                    value: new_val[..len].to_vec(),
+                                    pointer: None,
+                                    data_item: None,
+                                    object_hint: None,
                                 });
                             }
                         }
@@ -776,23 +1445,37 @@ async fn filter_memory_native(request: MemoryFilterRequest) -> Result<MemoryFilt
         results,
         total_processed: addresses.len(),
         error: None,
+        timing: None,
     })
 }
 
-/// Native lookup command - reads current values for a list of addresses
+// Deepest pointer chain lookup_memory_native follows; every level is a server read
+const MAX_POINTER_DEPTH: u32 = 8;
+
+/// Native lookup command - reads current values for a list of addresses.
+/// With data_type "pointer" each value is dereferenced `pointer_depth` levels
+/// (default 1, at most MAX_POINTER_DEPTH). `pointer_size` defaults to the server arch.
 #[tauri::command]
-async fn lookup_memory_native(addresses: Vec<u64>, data_type: String) -> Result<MemoryFilterResponse, String> {
+async fn lookup_memory_native(
+    state: tauri::State<'_, state::AppStateType>,
+    addresses: Vec<u64>,
+    data_type: String,
+    pointer_depth: Option<u32>,
+    pointer_size: Option<usize>,
+    detect_objects: Option<bool>,
+) -> Result<MemoryFilterResponse, String> {
     let (host, port) = {
         let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
         (config.host.clone(), config.port)
     };
-    
+
     if host.is_empty() {
         return Ok(MemoryFilterResponse {
             success: false,
             results: vec![],
             total_processed: 0,
             error: Some("No server connection configured".to_string()),
+            timing: None,
         });
     }
 
@@ -802,22 +1485,47 @@ async fn lookup_memory_native(addresses: Vec<u64>, data_type: String) -> Result<
             results: vec![],
             total_processed: 0,
             error: None,
+            timing: None,
         });
     }
 
-    let data_size = get_data_size(&data_type);
+    let is_pointer = data_type == "pointer";
+    let pointer_size = match pointer_size {
+        Some(size @ (4 | 8)) => size,
+        Some(size) => return Err(format!("Invalid pointer size {} (expected 4 or 8)", size)),
+        None => {
+            let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+            match state_guard.server_info.as_ref().map(|s| s.arch.as_str()) {
+                Some("x86") | Some("arm") => 4,
+                _ => 8,
+            }
+        }
+    };
+    let data_size = if is_pointer { pointer_size } else { get_data_size(&data_type) };
     let mut results: Vec<MemoryFilterResult> = Vec::new();
-    
+    let mut stopwatch = latency::Stopwatch::start("lookup_memory_native");
+
+    // Virtual addresses resolve one by one through their expressions
+    let total_processed = addresses.len();
+    let (virtual_addrs, addresses): (Vec<u64>, Vec<u64>) = addresses.into_iter()
+        .partition(|&a| virtual_addresses::is_virtual(a));
+    for addr in virtual_addrs {
+        if let Ok(value) = virtual_addresses::read(addr, data_size).await {
+            results.push(MemoryFilterResult { address: addr, value, pointer: None, data_item: None, object_hint: None });
+        }
+    }
+    stopwatch.mark("virtual");
+
     // Use same chunking strategy as filter
     const BULK_READ_THRESHOLD: usize = 100;
     const MAX_BULK_READ_SIZE: u64 = 1024 * 1024;
-    
-    let min_addr = *addresses.iter().min().unwrap();
-    let max_addr = *addresses.iter().max().unwrap();
+
+    let min_addr = addresses.iter().min().copied().unwrap_or(0);
+    let max_addr = addresses.iter().max().copied().unwrap_or(0);
     let addr_range = max_addr - min_addr + data_size as u64;
-    
+
     if addresses.len() >= BULK_READ_THRESHOLD && addr_range <= MAX_BULK_READ_SIZE {
-        match read_memory_from_server(&host, port, min_addr, addr_range as usize).await {
+        match read_memory_bytes(&host, port, min_addr, addr_range as usize).await {
             Ok(bulk_data) => {
                 for &addr in &addresses {
                     let offset = (addr - min_addr) as usize;
@@ -825,6 +1533,9 @@ async fn lookup_memory_native(addresses: Vec<u64>, data_type: String) -> Result<
                         results.push(MemoryFilterResult {
                             address: addr,
                             value: bulk_data[offset..offset + data_size].to_vec(),
+                            pointer: None,
+                            data_item: None,
+                            object_hint: None,
                         });
                     }
                 }
@@ -835,20 +1546,21 @@ async fn lookup_memory_native(addresses: Vec<u64>, data_type: String) -> Result<
                     results: vec![],
                     total_processed: 0,
                     error: Some(format!("Bulk memory read failed: {}", e)),
+                    timing: None,
                 });
             }
         }
     } else {
         const CHUNK_GAP_THRESHOLD: u64 = 4096;
         const MAX_CHUNK_SIZE: usize = 65536;
-        
+
         let mut addr_indices: Vec<(u64, usize)> = addresses.iter().enumerate()
             .map(|(i, &a)| (a, i))
             .collect();
         addr_indices.sort_by_key(|&(a, _)| a);
-        
+
         let mut chunks: Vec<(u64, usize, Vec<u64>)> = Vec::new();
-        
+
         for (addr, _) in addr_indices {
             if chunks.is_empty() {
                 chunks.push((addr, data_size, vec![addr]));
@@ -856,7 +1568,7 @@ async fn lookup_memory_native(addresses: Vec<u64>, data_type: String) -> Result<
                 let last = chunks.last_mut().unwrap();
                 let gap = addr.saturating_sub(last.0 + last.1 as u64);
                 let new_size = (addr - last.0) as usize + data_size;
-                
+
                 if gap <= CHUNK_GAP_THRESHOLD && new_size <= MAX_CHUNK_SIZE {
                     last.1 = new_size;
                     last.2.push(addr);
@@ -865,9 +1577,9 @@ async fn lookup_memory_native(addresses: Vec<u64>, data_type: String) -> Result<
                 }
             }
         }
-        
+
         for (chunk_start, chunk_size, chunk_addrs) in chunks {
-            match read_memory_from_server(&host, port, chunk_start, chunk_size).await {
+            match read_memory_bytes(&host, port, chunk_start, chunk_size).await {
                 Ok(chunk_data) => {
                     for addr in chunk_addrs {
                         let offset = (addr - chunk_start) as usize;
@@ -875,6 +1587,9 @@ async fn lookup_memory_native(addresses: Vec<u64>, data_type: String) -> Result<
                             results.push(MemoryFilterResult {
                                 address: addr,
                                 value: chunk_data[offset..offset + data_size].to_vec(),
+                                pointer: None,
+                                data_item: None,
+                                object_hint: None,
                             });
                         }
                     }
@@ -884,45 +1599,55 @@ async fn lookup_memory_native(addresses: Vec<u64>, data_type: String) -> Result<
         }
     }
 
-    Ok(MemoryFilterResponse {
+    stopwatch.mark("read");
+    stopwatch.set_bytes(results.iter().map(|r| r.value.len()).sum());
+
+    if is_pointer {
+        // The memory map is fetched once and shared by all results
+        let regions = fetch_memory_regions_from_server(&host, port).await.unwrap_or_default();
+        let mut module_bases: HashMap<String, u64> = HashMap::new();
+        for region in &regions {
+            if let Some(path) = &region.file_path {
+                let base = module_bases.entry(path.clone()).or_insert(region.start);
+                *base = (*base).min(region.start);
+            }
+        }
+        let depth = pointer_depth.unwrap_or(1).clamp(1, MAX_POINTER_DEPTH);
+        for result in results.iter_mut() {
+            result.pointer = Some(
+                dereference_pointer_chain(&host, port, &regions, &module_bases, &result.value, pointer_size, depth).await,
+            );
+        }
+        stopwatch.mark("pointer_deref");
+    }
+    data_overlay::annotate(state.inner(), &mut results).await;
+    stopwatch.mark("data_overlay");
+    if detect_objects.unwrap_or(false) {
+        object_heuristics::annotate(state.inner(), &mut results).await;
+        stopwatch.mark("object_hints");
+    }
+
+    let mut response = MemoryFilterResponse {
         success: true,
         results,
-        total_processed: addresses.len(),
+        total_processed,
         error: None,
-    })
+        timing: None,
+    };
+    response.timing = stopwatch.finish(&response);
+    Ok(response)
 }
 
 /// Unknown scan request structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnknownScanRequest {
+    #[serde(default)]
     pub address_ranges: Vec<(u64, u64)>,  // [(start, end), ...]
-    pub data_type: String,                 // "int8", "uint8", "int16", "uint16", "int32", "uint32", "int64", "uint64", "float", "double"
+    pub data_type: String,                 // "int8", "uint8", "int16", "uint16", "int32", "uint32", "int64", "uint64", "float", "double" or a value_codec type
     pub alignment: usize,                  // Alignment for scanning
     pub scan_id: String,                   // Unique scan ID for temp file storage
 }
 
-/// Unknown scan progress structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct UnknownScanProgress {
-    pub scan_id: String,
-    pub progress_percentage: f64,
-    pub processed_bytes: u64,
-    pub total_bytes: u64,
-    pub found_count: u64,
-    pub is_scanning: bool,
-    pub current_region: Option<String>,
-}
-
-/// Unknown scan response - returns scan metadata (results stored in temp files)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct UnknownScanResponse {
-    pub success: bool,
-    pub scan_id: String,
-    pub total_addresses: usize,
-    pub temp_dir: String,
-    pub error: Option<String>,
-}
-
 /// Unknown scan result for lookup
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnknownScanLookupResponse {
@@ -932,292 +1657,500 @@ pub struct UnknownScanLookupResponse {
     pub error: Option<String>,
 }
 
-// Global storage for unknown scan progress
-static UNKNOWN_SCAN_PROGRESS: Lazy<RwLock<HashMap<String, UnknownScanProgress>>> = Lazy::new(|| {
-    RwLock::new(HashMap::new())
-});
+/// Push scan progress to the webview as a `scan://progress` event, or onto
+/// the batched scan-progress channel when it is subscribed
+fn publish_scan_progress(app_handle: Option<&tauri::AppHandle>, progress: &UnknownScanProgress) {
+    if !event_bus::publish(event_bus::CHANNEL_SCAN_PROGRESS, Some(progress.scan_id.as_str()), progress) {
+        if let Some(app_handle) = app_handle {
+            let _ = app_handle.emit("scan://progress", progress);
+        }
+    }
+}
 
-/// Get temp directory for unknown scan data
-fn get_unknown_scan_temp_dir(scan_id: &str) -> PathBuf {
-    let temp_dir = std::env::temp_dir();
-    temp_dir.join("dynadbg_unknown_scan").join(scan_id)
+/// Push the current progress of a scan (see publish_scan_progress)
+fn emit_scan_progress(app_handle: Option<&tauri::AppHandle>, scan_id: &str) {
+    if let Some(progress) = UNKNOWN_SCAN_PROGRESS.read().ok().and_then(|m| m.get(scan_id).cloned()) {
+        publish_scan_progress(app_handle, &progress);
+    }
+}
+
+/// Progress sink for the native scan engine in dynadbg-core
+fn scan_progress_sink(app_handle: tauri::AppHandle) -> ProgressSink {
+    std::sync::Arc::new(move |progress: &UnknownScanProgress| publish_scan_progress(Some(&app_handle), progress))
 }
 
 /// Native unknown scan command - scans memory ranges and saves to temp files
-/// Progress can be queried via get_unknown_scan_progress
+/// Progress is pushed as `scan://progress` events and can also be queried via get_unknown_scan_progress
+#[tauri::command]
+async fn unknown_scan_native(app_handle: tauri::AppHandle, request: UnknownScanRequest) -> Result<UnknownScanResponse, String> {
+    let data_size = get_data_size(&request.data_type);
+    let alignment = if request.alignment > 0 { request.alignment } else { data_size };
+    run_native_scan(Some(scan_progress_sink(app_handle)), request.scan_id, &request.address_ranges, NativeScan {
+        data_size,
+        alignment,
+        matcher: None,
+        partial_tail: false,
+    }).await
+}
+
+/// Native exact-value first scan - same storage layout as the unknown scan,
+/// so results can be paged with load_unknown_scan_results
+#[tauri::command]
+async fn exact_scan_native(app_handle: tauri::AppHandle, request: ExactScanRequest) -> Result<UnknownScanResponse, String> {
+    run_exact_scan(Some(scan_progress_sink(app_handle)), request).await
+}
+
+/// Native AOB scan with wildcards - matches are stored like unknown scan results
+/// (value = matched bytes), so they can be paged with load_unknown_scan_results
+#[tauri::command]
+async fn aob_scan_native(app_handle: tauri::AppHandle, request: AobScanRequest) -> Result<UnknownScanResponse, String> {
+    run_aob_scan(Some(scan_progress_sink(app_handle)), request).await
+}
+
+/// Group (struct pattern) scan request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupScanRequest {
+    pub pattern: String,                   // e.g. "int32:100; float:1.0..5.0; skip:8; int16:7" ("type:*" = any value)
+    #[serde(default)]
+    pub address_ranges: Vec<(u64, u64)>,   // [(start, end), ...]
+    #[serde(default)]
+    pub alignment: usize,                  // Alignment of the base address (0 = size of the first item)
+    #[serde(default)]
+    pub scan_id: Option<String>,           // Optional caller-chosen ID, generated when omitted
+}
+
+/// One typed value of a group pattern, checked at `offset` from the base
+#[derive(Debug, Clone)]
+struct GroupScanItem {
+    offset: usize,
+    size: usize,
+    data_type: String,
+    min: Vec<u8>,
+    max: Option<Vec<u8>>,                  // Set for "min..max" items
+}
+
+/// Parse a group pattern into its constrained items and the total span in bytes
+fn parse_group_pattern(pattern: &str) -> Result<(Vec<GroupScanItem>, usize), String> {
+    let mut items = Vec::new();
+    let mut offset = 0usize;
+    for token in pattern.split(';').map(str::trim).filter(|t| !t.is_empty()) {
+        let (data_type, value) = token.split_once(':')
+            .map(|(t, v)| (t.trim(), v.trim()))
+            .ok_or_else(|| format!("Group item '{}' must be type:value", token))?;
+        if data_type == "skip" {
+            offset += value.parse::<usize>().map_err(|_| format!("Invalid skip count in '{}'", token))?;
+            continue;
+        }
+        // Codec types carry their own ':' parameters, e.g. "bitfield:3:4:7"
+        let (data_type, value) = match value.rsplit_once(':') {
+            Some((params, v)) if value_codec::ValueType::parse(&format!("{}:{}", data_type, params)).is_some() => {
+                (format!("{}:{}", data_type, params), v.trim())
+            }
+            _ => (data_type.to_string(), value),
+        };
+        let size = match data_type.as_str() {
+            "int8" | "uint8" | "int16" | "uint16" | "int32" | "uint32" | "int64" | "uint64" | "float" | "double" => get_data_size(&data_type),
+            other => value_codec::data_size(other).ok_or_else(|| format!("Unsupported group item type '{}'", other))?,
+        };
+        if value != "*" {
+            let (min, max) = match value.split_once("..") {
+                Some((min, max)) => (value_codec::encode(&data_type, min)?, Some(value_codec::encode(&data_type, max)?)),
+                None => (value_codec::encode(&data_type, value)?, None),
+            };
+            items.push(GroupScanItem { offset, size, data_type: data_type.clone(), min, max });
+        }
+        offset += size;
+    }
+    if items.is_empty() {
+        return Err("Group pattern must constrain at least one value".to_string());
+    }
+    Ok((items, offset))
+}
+
+/// Native group scan - finds base addresses where every item of the pattern
+/// holds at its offset. Hits are stored like unknown scan results with the
+/// whole group span as value.
+#[tauri::command]
+async fn group_scan_native(app_handle: tauri::AppHandle, request: GroupScanRequest) -> Result<UnknownScanResponse, String> {
+    let scan_id = request.scan_id.clone().unwrap_or_else(|| new_scan_id("group"));
+
+    let (items, data_size) = parse_group_pattern(&request.pattern)?;
+    let alignment = match request.alignment {
+        0 if items[0].offset == 0 => get_data_size(&items[0].data_type),
+        0 => 1,
+        a => a,
+    };
+    let matcher: ScanMatcher = std::sync::Arc::new(move |value: &[u8]| {
+        items.iter().all(|item| {
+            let method = if item.max.is_some() { "range" } else { "exact" };
+            value.get(item.offset..item.offset + item.size).is_some_and(|v| {
+                compare_values(v, &[], &item.min, item.max.as_deref(), &item.data_type, method, None)
+            })
+        })
+    });
+
+    run_native_scan(Some(scan_progress_sink(app_handle)), scan_id, &request.address_ranges, NativeScan {
+        data_size,
+        alignment,
+        matcher: Some(matcher),
+        partial_tail: false,
+    }).await
+}
+
+/// String scan request (UTF-8, UTF-16LE or regex)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StringScanRequest {
+    pub pattern: String,                   // Plain text, or a regex when is_regex is set
+    #[serde(default)]
+    pub encoding: String,                  // "utf8" (default) or "utf16"
+    #[serde(default)]
+    pub is_regex: bool,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    #[serde(default)]
+    pub max_length: Option<usize>,         // Bytes stored per regex hit (default 256)
+    #[serde(default)]
+    pub address_ranges: Vec<(u64, u64)>,   // [(start, end), ...]
+    #[serde(default)]
+    pub alignment: usize,                  // 0 = 1 for UTF-8, 2 for UTF-16
+    #[serde(default)]
+    pub scan_id: Option<String>,           // Optional caller-chosen ID, generated when omitted
+}
+
+/// Native string scan - hits are stored like unknown scan results with the
+/// matched window as value, so they can be paged with load_unknown_scan_results
+#[tauri::command]
+async fn string_scan_native(app_handle: tauri::AppHandle, request: StringScanRequest) -> Result<UnknownScanResponse, String> {
+    let scan_id = request.scan_id.clone().unwrap_or_else(|| new_scan_id("string"));
+
+    let utf16 = request.encoding.eq_ignore_ascii_case("utf16");
+    let string_matcher = StringMatcher::new(&request.pattern, request.is_regex, utf16, request.case_insensitive)?;
+    let data_size = string_matcher.read_size(request.max_length);
+    let alignment = if request.alignment > 0 { request.alignment } else if utf16 { 2 } else { 1 };
+    let matcher: ScanMatcher = std::sync::Arc::new(move |value: &[u8]| string_matcher.match_len(value).is_some());
+
+    run_native_scan(Some(scan_progress_sink(app_handle)), scan_id, &request.address_ranges, NativeScan {
+        data_size,
+        alignment,
+        matcher: Some(matcher),
+        partial_tail: true,
+    }).await
+}
+
+/// Native next-scan request against stored unknown/exact scan results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnknownScanFilterRequest {
+    pub scan_id: String,
+    pub filter_method: String,             // Same methods as MemoryFilterRequest
+    pub data_type: String,
+    pub pattern: String,                   // Hex-encoded pattern (min for range), empty for changed/unchanged/...
+    #[serde(default)]
+    pub pattern_max: Option<String>,       // Hex-encoded max pattern for range filter
+    #[serde(default)]
+    pub fuzzy: Option<FuzzyFloatOptions>,  // For "fuzzy"
+    #[serde(default)]
+    pub case_insensitive: bool,            // For "string", "utf16" and "regex" data types
+}
+
+/// Native next-scan - streams the latest generation's region files, re-reads
+/// current memory, and writes the surviving addresses as a new generation
 #[tauri::command]
-async fn unknown_scan_native(request: UnknownScanRequest) -> Result<UnknownScanResponse, String> {
+async fn filter_unknown_scan_native(app_handle: tauri::AppHandle, request: UnknownScanFilterRequest) -> Result<UnknownScanResponse, String> {
+    validate_scan_id(&request.scan_id)?;
     let (host, port) = {
         let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
         (config.host.clone(), config.port)
     };
-    
+
+    let scan_id = request.scan_id.clone();
     if host.is_empty() {
         return Ok(UnknownScanResponse {
             success: false,
-            scan_id: request.scan_id.clone(),
+            scan_id,
             total_addresses: 0,
             temp_dir: String::new(),
+            generation: 0,
             error: Some("No server connection configured".to_string()),
         });
     }
 
-    let data_size = get_data_size(&request.data_type);
-    let alignment = if request.alignment > 0 { request.alignment } else { data_size };
-    let scan_id = request.scan_id.clone();
-    
-    // Calculate total bytes to scan for progress
-    let total_bytes: u64 = request.address_ranges.iter()
-        .map(|(start, end)| end - start)
-        .sum();
-    
-    // Create temp directory
-    let temp_dir = get_unknown_scan_temp_dir(&scan_id);
-    if let Err(e) = std::fs::create_dir_all(&temp_dir) {
+    let current_generation = get_latest_scan_generation(&scan_id);
+    let source_dir = get_scan_generation_dir(&scan_id, current_generation);
+    if !source_dir.exists() {
         return Ok(UnknownScanResponse {
             success: false,
             scan_id,
             total_addresses: 0,
             temp_dir: String::new(),
-            error: Some(format!("Failed to create temp directory: {}", e)),
+            generation: current_generation,
+            error: Some("Scan data not found".to_string()),
         });
     }
-    
-    // Initialize progress
+
+    let pattern_bytes = hex::decode(&request.pattern)
+        .map_err(|e| format!("Invalid pattern hex: {}", e))?;
+    let pattern_max_bytes = request.pattern_max.as_ref()
+        .map(|p| hex::decode(p).map_err(|e| format!("Invalid pattern_max hex: {}", e)))
+        .transpose()?;
+
+    let next_generation = current_generation + 1;
+    let target_dir = get_scan_generation_dir(&scan_id, next_generation);
+    // Region files go to a staging directory that only becomes gen_N once
+    // every region was written, so a failed next scan leaves no partial generation
+    let staging_dir = get_unknown_scan_temp_dir(&scan_id).join(format!("gen_{}.partial", next_generation));
+    let _ = std::fs::remove_dir_all(&staging_dir);
+    std::fs::create_dir_all(&staging_dir)
+        .map_err(|e| format!("Failed to create generation directory: {}", e))?;
+    // Exact string filters match text within the stored window, as in
+    // filter_memory_native; everything else goes to the typed kernels
+    let string_matcher = StringMatcher::for_data_type(
+        &request.data_type,
+        &request.filter_method,
+        &filter_pattern_text(&request.data_type, &pattern_bytes),
+        request.case_insensitive,
+    )?.map(std::sync::Arc::new);
+    let filter = std::sync::Arc::new(scan_kernel::Filter {
+        data_type: request.data_type.clone(),
+        filter_method: request.filter_method.clone(),
+        pattern: pattern_bytes,
+        pattern_max: pattern_max_bytes,
+        fuzzy: request.fuzzy.clone(),
+    });
+    let region_files = list_scan_region_files(&source_dir);
+
+    // Progress is tracked per region file since the address count is only known after decompression
+    let total_regions = region_files.len() as u64;
     {
         let mut progress_map = UNKNOWN_SCAN_PROGRESS.write().unwrap();
         progress_map.insert(scan_id.clone(), UnknownScanProgress {
             scan_id: scan_id.clone(),
             progress_percentage: 0.0,
             processed_bytes: 0,
-            total_bytes,
+            total_bytes: total_regions,
             found_count: 0,
             is_scanning: true,
-            current_region: Some("Starting scan...".to_string()),
+            current_region: Some(format!("Filtering generation {}...", current_generation)),
+            is_cancelled: false,
         });
     }
-    
-    // Maximum chunk size for reading (4MB per read for efficiency)
-    const MAX_READ_CHUNK: usize = 4 * 1024 * 1024;
-    // Maximum sub-region size (64MB) - split large regions to avoid memory issues
-    const MAX_SUB_REGION: u64 = 64 * 1024 * 1024;
-    // Number of parallel reads
+    emit_scan_progress(Some(&app_handle), &scan_id);
+
+    // Addresses closer than this are read in a single request
+    const CHUNK_GAP_THRESHOLD: u64 = 4096;
+    const MAX_CHUNK_SIZE: usize = 1024 * 1024;
     const PARALLEL_READS: usize = 8;
-    
-    let total_found = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
-    let processed_bytes = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
-    let success_reads = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
-    let failed_reads = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
-    
-    // Split large regions into smaller sub-regions (max 64MB each)
-    let mut sub_regions: Vec<(u64, u64)> = Vec::new();
-    for (range_start, range_end) in &request.address_ranges {
-        let mut current = *range_start;
-        while current < *range_end {
-            let sub_end = (current + MAX_SUB_REGION).min(*range_end);
-            sub_regions.push((current, sub_end));
-            current = sub_end;
+
+    let mut total_found: u64 = 0;
+    let mut processed_regions: u64 = 0;
+    let cancelled = get_scan_cancel_flag(&scan_id);
+    let mut stats: Option<scan_sampling::ScanStatsAccumulator> = None;
+
+    for path in region_files {
+        if cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+            break;
         }
-    }
-    
-    eprintln!("[Unknown Scan] Starting scan: {} original regions -> {} sub-regions (max {}MB each), total_bytes: {}", 
-        request.address_ranges.len(), sub_regions.len(), MAX_SUB_REGION / 1024 / 1024, total_bytes);
-    
-    let total_sub_regions = sub_regions.len();
-    
-    // Process sub-regions in parallel (up to 4 at a time)
-    let sub_region_chunks: Vec<_> = sub_regions.iter().enumerate().collect::<Vec<_>>()
-        .chunks(4).map(|c| c.to_vec()).collect();
-    
-    for sub_region_batch in sub_region_chunks {
-        let mut region_tasks = Vec::new();
-        
-        for (_sub_region_index, (range_start, range_end)) in sub_region_batch {
-            let host = host.clone();
-            let scan_id = scan_id.clone();
-            let temp_dir = temp_dir.clone();
-            let total_found = total_found.clone();
-            let processed_bytes = processed_bytes.clone();
-            let success_reads = success_reads.clone();
-            let failed_reads = failed_reads.clone();
-            let range_start = *range_start;
-            let range_end = *range_end;
-            let _total_sub_regions = total_sub_regions;
-            
-            let task = tokio::spawn(async move {
-                let mut current_addr = range_start;
-                
-                // Align start address
-                if current_addr % alignment as u64 != 0 {
-                    current_addr = (current_addr / alignment as u64 + 1) * alignment as u64;
+        let Some(region) = read_scan_region_file(&path) else {
+            processed_regions += 1;
+            continue;
+        };
+        let data_size = region.data_size.max(1);
+
+        // Group sorted addresses into contiguous read chunks: (start, size, first_idx, end_idx)
+        let mut chunks: Vec<(u64, usize, usize, usize)> = Vec::new();
+        for (i, &addr) in region.addresses.iter().enumerate() {
+            // An address below the chunk start (unsorted file) starts a new chunk
+            if let Some((last, offset)) = chunks.last_mut().and_then(|last| addr.checked_sub(last.0).map(|o| (last, o))) {
+                let gap = addr.saturating_sub(last.0.saturating_add(last.1 as u64));
+                let new_size = (offset as usize).saturating_add(data_size);
+                if gap <= CHUNK_GAP_THRESHOLD && new_size <= MAX_CHUNK_SIZE {
+                    last.1 = new_size;
+                    last.3 = i + 1;
+                    continue;
                 }
-                
-                // Create file for this sub-region
-                let region_file_path = temp_dir.join(format!("region_{:016x}_{:016x}.bin", range_start, range_end));
-                let mut region_file = match std::fs::File::create(&region_file_path) {
-                    Ok(f) => std::io::BufWriter::with_capacity(1024 * 1024, f), // 1MB buffer
-                    Err(e) => {
-                        eprintln!("[Unknown Scan] Failed to create region file: {}", e);
-                        return (0u64, 0u64);
-                    }
+            }
+            chunks.push((addr, data_size, i, i + 1));
+        }
+
+        let mut kept_addresses: Vec<u64> = Vec::new();
+        let mut kept_values: Vec<u8> = Vec::new();
+
+        for chunk_batch in chunks.chunks(PARALLEL_READS) {
+            let mut read_tasks = Vec::new();
+            for &(start, size, first, end) in chunk_batch {
+                let host = host.clone();
+                let task = tokio::spawn(async move {
+                    tokio::time::timeout(
+                        std::time::Duration::from_secs(2),
+                        read_memory_bytes(&host, port, start, size),
+                    ).await.ok().and_then(|r| r.ok())
+                });
+                read_tasks.push((start, first, end, task));
+            }
+
+            // Pack the batch's current and previous values so the filter
+            // kernel runs once over the whole batch
+            let mut indices: Vec<usize> = Vec::new();
+            let mut new_values: Vec<u8> = Vec::new();
+            let mut old_values: Vec<u8> = Vec::new();
+            for (start, first, end, task) in read_tasks {
+                let Some(chunk_data) = task.await.ok().flatten() else {
+                    continue;
                 };
-                
-                // Write header: data_size (4 bytes) + alignment (4 bytes) + start_addr (8 bytes)
-                use std::io::Write;
-                let _ = region_file.write_all(&(data_size as u32).to_le_bytes());
-                let _ = region_file.write_all(&(alignment as u32).to_le_bytes());
-                let _ = region_file.write_all(&range_start.to_le_bytes());
-                
-                let mut all_addresses: Vec<u64> = Vec::new();
-                let mut all_data: Vec<u8> = Vec::new();
-                
-                // Split sub-region into chunks for parallel reading
-                let region_size = (range_end - current_addr) as usize;
-                let mut chunks_to_read: Vec<(u64, usize)> = Vec::new();
-                
-                let mut chunk_start = current_addr;
-                while chunk_start < range_end {
-                    let remaining = (range_end - chunk_start) as usize;
-                    let chunk_size = remaining.min(MAX_READ_CHUNK);
-                    chunks_to_read.push((chunk_start, chunk_size));
-                    chunk_start += chunk_size as u64;
-                }
-                
-                // Process chunks in parallel batches
-                for chunk_batch in chunks_to_read.chunks(PARALLEL_READS) {
-                    let mut read_tasks = Vec::new();
-                    
-                    for (addr, size) in chunk_batch.iter().cloned() {
-                        let host = host.clone();
-                        let read_task = tokio::spawn(async move {
-                            // Add timeout to prevent hanging on unresponsive regions
-                            match tokio::time::timeout(
-                                std::time::Duration::from_secs(2),
-                                read_memory_from_server(&host, port, addr, size)
-                            ).await {
-                                Ok(Ok(data)) => Some((addr, data)),
-                                Ok(Err(_)) => None,
-                                Err(_) => None, // Timeout
-                            }
-                        });
-                        read_tasks.push((addr, size, read_task));
-                    }
-                    
-                    // Collect results and maintain order
-                    let mut results: Vec<(u64, Option<Vec<u8>>, usize)> = Vec::new();
-                    for (addr, size, task) in read_tasks {
-                        match task.await {
-                            Ok(result) => results.push((addr, result.map(|(_, d)| d), size)),
-                            Err(_) => results.push((addr, None, size)),
-                        }
-                    }
-                    
-                    // Sort by address to maintain order
-                    results.sort_by_key(|(addr, _, _)| *addr);
-                    
-                    for (addr, data_opt, chunk_size) in results {
-                        if let Some(chunk_data) = data_opt {
-                            success_reads.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                            
-                            // Extract values at aligned positions
-                            let mut offset: usize = 0;
-                            while offset + data_size <= chunk_data.len() {
-                                let value_addr = addr + offset as u64;
-                                all_addresses.push(value_addr);
-                                all_data.extend_from_slice(&chunk_data[offset..offset + data_size]);
-                                offset += alignment;
-                            }
-                        } else {
-                            failed_reads.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                        }
-                        
-                        // Update progress after each chunk
-                        processed_bytes.fetch_add(chunk_size as u64, std::sync::atomic::Ordering::Relaxed);
-                        let current_processed = processed_bytes.load(std::sync::atomic::Ordering::Relaxed);
-                        let progress = if total_bytes > 0 {
-                            (current_processed as f64 / total_bytes as f64) * 100.0
-                        } else {
-                            0.0
-                        };
-                        
-                        if let Ok(mut progress_map) = UNKNOWN_SCAN_PROGRESS.write() {
-                            if let Some(p) = progress_map.get_mut(&scan_id) {
-                                p.progress_percentage = progress;
-                                p.processed_bytes = current_processed;
-                                p.found_count = total_found.load(std::sync::atomic::Ordering::Relaxed) + all_addresses.len() as u64;
-                            }
-                        }
-                    }
+                for i in first..end {
+                    let offset = (region.addresses[i] - start) as usize;
+                    let (Some(new_val), Some(old_val)) = (
+                        chunk_data.get(offset..offset + data_size),
+                        region.values.get(i * data_size..(i + 1) * data_size),
+                    ) else {
+                        continue;
+                    };
+                    indices.push(i);
+                    new_values.extend_from_slice(new_val);
+                    old_values.extend_from_slice(old_val);
                 }
-                
-                let region_found = all_addresses.len() as u64;
-                
-                // Compress and write region data using lz4
-                if !all_data.is_empty() {
-                    // Write number of addresses
-                    let _ = region_file.write_all(&(all_addresses.len() as u64).to_le_bytes());
-                    
-                    // Write addresses (compressed)
-                    let addr_bytes: Vec<u8> = all_addresses.iter()
-                        .flat_map(|a| a.to_le_bytes())
-                        .collect();
-                    let compressed_addrs = lz4_flex::compress_prepend_size(&addr_bytes);
-                    let _ = region_file.write_all(&(compressed_addrs.len() as u64).to_le_bytes());
-                    let _ = region_file.write_all(&compressed_addrs);
-                    
-                    // Write values (compressed)
-                    let compressed_data = lz4_flex::compress_prepend_size(&all_data);
-                    let _ = region_file.write_all(&(compressed_data.len() as u64).to_le_bytes());
-                    let _ = region_file.write_all(&compressed_data);
+            }
+            // The kernels are CPU-bound; keep them off the async runtime
+            let (filter, string_matcher) = (filter.clone(), string_matcher.clone());
+            let filtered = tokio::task::spawn_blocking(move || {
+                let kept = match &string_matcher {
+                    Some(m) => scan_kernel::matching_indices(&new_values, data_size, |value| m.match_len(value).is_some()),
+                    None => scan_kernel::filter_indices(&filter.spec(), &new_values, &old_values, data_size),
+                };
+                (kept, new_values)
+            }).await;
+            let (kept, new_values) = match filtered {
+                Ok(filtered) => filtered,
+                Err(e) => {
+                    abort_scan_filter(&app_handle, &scan_id, &staging_dir, false);
+                    return Err(format!("Filter task failed: {}", e));
                 }
-                
-                let _ = region_file.flush();
-                
-                (region_found, region_size as u64)
-            });
-            
-            region_tasks.push(task);
+            };
+            for k in kept {
+                kept_addresses.push(region.addresses[indices[k]]);
+                kept_values.extend_from_slice(&new_values[k * data_size..(k + 1) * data_size]);
+            }
         }
-        
-        // Wait for all region tasks in this batch
-        for task in region_tasks {
-            if let Ok((found, _)) = task.await {
-                total_found.fetch_add(found, std::sync::atomic::Ordering::Relaxed);
+
+        if let Some(file_name) = path.file_name() {
+            if let Err(e) = write_scan_region_file(
+                &staging_dir.join(file_name),
+                data_size,
+                region.alignment,
+                region.start_addr,
+                &kept_addresses,
+                &kept_values,
+            ) {
+                abort_scan_filter(&app_handle, &scan_id, &staging_dir, false);
+                return Err(format!("Failed to write region file: {}", e));
+            }
+        }
+
+        stats.get_or_insert_with(|| scan_sampling::ScanStatsAccumulator::new(&request.data_type, data_size))
+            .add_values(&kept_values);
+        total_found += kept_addresses.len() as u64;
+        processed_regions += 1;
+
+        if let Ok(mut progress_map) = UNKNOWN_SCAN_PROGRESS.write() {
+            if let Some(p) = progress_map.get_mut(&scan_id) {
+                p.progress_percentage = processed_regions as f64 / total_regions.max(1) as f64 * 100.0;
+                p.processed_bytes = processed_regions;
+                p.found_count = total_found;
             }
         }
+        emit_scan_progress(Some(&app_handle), &scan_id);
     }
-    
-    let final_found = total_found.load(std::sync::atomic::Ordering::Relaxed);
-    let final_success = success_reads.load(std::sync::atomic::Ordering::Relaxed);
-    let final_failed = failed_reads.load(std::sync::atomic::Ordering::Relaxed);
-    
-    eprintln!("[Unknown Scan] Completed: total_found={}, success_reads={}, failed_reads={}, temp_dir={}", 
-        final_found, final_success, final_failed, temp_dir.display());
-    
-    // Mark scan as complete
+
+    if let Ok(mut flags) = UNKNOWN_SCAN_CANCEL.write() {
+        flags.remove(&scan_id);
+    }
+
+    // A partially filtered generation would silently drop the unprocessed
+    // regions, so a cancelled next scan is discarded and the previous one kept
+    if cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+        abort_scan_filter(&app_handle, &scan_id, &staging_dir, true);
+        return Ok(UnknownScanResponse {
+            success: false,
+            scan_id,
+            total_addresses: 0,
+            temp_dir: source_dir.to_string_lossy().to_string(),
+            generation: current_generation,
+            error: Some("Scan cancelled".to_string()),
+        });
+    }
+
+    if let Err(e) = std::fs::rename(&staging_dir, &target_dir) {
+        abort_scan_filter(&app_handle, &scan_id, &staging_dir, false);
+        return Err(format!("Failed to store generation {}: {}", next_generation, e));
+    }
+
     {
         let mut progress_map = UNKNOWN_SCAN_PROGRESS.write().unwrap();
         if let Some(p) = progress_map.get_mut(&scan_id) {
             p.progress_percentage = 100.0;
-            p.processed_bytes = total_bytes;
-            p.found_count = final_found;
+            p.processed_bytes = total_regions;
+            p.found_count = total_found;
             p.is_scanning = false;
             p.current_region = None;
         }
     }
+    emit_scan_progress(Some(&app_handle), &scan_id);
+
+    // Summary statistics help choosing the next filter (see get_scan_statistics)
+    let stats = stats
+        .unwrap_or_else(|| scan_sampling::ScanStatsAccumulator::new(&request.data_type, 1))
+        .finish(&scan_id, next_generation);
+    if let Err(e) = scan_sampling::save_scan_statistics(&stats) {
+        eprintln!("[Native Filter] {}", e);
+    }
 
     Ok(UnknownScanResponse {
         success: true,
-        scan_id: scan_id.clone(),
-        total_addresses: final_found as usize,
-        temp_dir: temp_dir.to_string_lossy().to_string(),
+        scan_id,
+        total_addresses: total_found as usize,
+        temp_dir: target_dir.to_string_lossy().to_string(),
+        generation: next_generation,
         error: None,
     })
 }
 
-/// Initialize unknown scan progress (call before starting scan to prevent race condition)
+/// Drop the staging directory of a next scan that did not finish and mark the
+/// scan as idle again, so the previous generation stays the latest one
+fn abort_scan_filter(app_handle: &tauri::AppHandle, scan_id: &str, staging_dir: &std::path::Path, cancelled: bool) {
+    let _ = std::fs::remove_dir_all(staging_dir);
+    if let Ok(mut flags) = UNKNOWN_SCAN_CANCEL.write() {
+        flags.remove(scan_id);
+    }
+    if let Ok(mut progress_map) = UNKNOWN_SCAN_PROGRESS.write() {
+        if let Some(p) = progress_map.get_mut(scan_id) {
+            p.is_scanning = false;
+            p.is_cancelled = cancelled;
+            p.current_region = None;
+        }
+    }
+    emit_scan_progress(Some(app_handle), scan_id);
+}
+
+/// Request cancellation of a running native scan. Regions already read are
+/// written to the temp files, so the partial results can still be loaded.
+#[tauri::command]
+fn cancel_unknown_scan(scan_id: String) -> Result<bool, String> {
+    validate_scan_id(&scan_id)?;
+    // Only scans that are running (or initialized and about to start) can be cancelled
+    let mut progress_map = UNKNOWN_SCAN_PROGRESS.write().unwrap();
+    let Some(progress) = progress_map.get_mut(&scan_id).filter(|p| p.is_scanning) else {
+        return Ok(false);
+    };
+    progress.current_region = Some("Cancelling...".to_string());
+    get_scan_cancel_flag(&scan_id).store(true, std::sync::atomic::Ordering::Relaxed);
+    Ok(true)
+}
+
+/// Initialize unknown scan progress. Only needed by callers that poll
+/// get_unknown_scan_progress; listeners of `scan://progress` receive the
+/// initial state from the scan itself.
 #[tauri::command]
 fn init_unknown_scan_progress(scan_id: String, total_bytes: u64) -> Result<(), String> {
+    validate_scan_id(&scan_id)?;
     let mut progress_map = UNKNOWN_SCAN_PROGRESS.write().unwrap();
     progress_map.insert(scan_id.clone(), UnknownScanProgress {
         scan_id,
@@ -1227,6 +2160,7 @@ fn init_unknown_scan_progress(scan_id: String, total_bytes: u64) -> Result<(), S
         found_count: 0,
         is_scanning: true,
         current_region: Some("Initializing...".to_string()),
+        is_cancelled: false,
     });
     Ok(())
 }
@@ -1234,6 +2168,7 @@ fn init_unknown_scan_progress(scan_id: String, total_bytes: u64) -> Result<(), S
 /// Get unknown scan progress
 #[tauri::command]
 fn get_unknown_scan_progress(scan_id: String) -> Result<UnknownScanProgress, String> {
+    validate_scan_id(&scan_id)?;
     let progress_map = UNKNOWN_SCAN_PROGRESS.read().unwrap();
     if let Some(progress) = progress_map.get(&scan_id) {
         Ok(progress.clone())
@@ -1247,180 +2182,60 @@ fn get_unknown_scan_progress(scan_id: String) -> Result<UnknownScanProgress, Str
             found_count: 0,
             is_scanning: true,
             current_region: Some("Waiting for scan to start...".to_string()),
+            is_cancelled: false,
         })
     }
 }
 
-/// Load unknown scan results from temp files (for display/lookup)
+/// Load unknown scan results from temp files (for display/lookup), with
+/// hits inside known Ghidra data items labelled and, with `detect_objects`,
+/// the likely containing runtime object
 #[tauri::command]
-#[allow(unused_assignments)]
-async fn load_unknown_scan_results(scan_id: String, offset: usize, limit: usize) -> Result<UnknownScanLookupResponse, String> {
-    let temp_dir = get_unknown_scan_temp_dir(&scan_id);
-    
-    if !temp_dir.exists() {
-        return Ok(UnknownScanLookupResponse {
-            success: false,
-            results: vec![],
-            total_count: 0,
-            error: Some("Scan data not found".to_string()),
-        });
+async fn load_unknown_scan_results(
+    state: tauri::State<'_, state::AppStateType>,
+    scan_id: String,
+    offset: usize,
+    limit: usize,
+    detect_objects: Option<bool>,
+) -> Result<UnknownScanLookupResponse, String> {
+    let mut response = read_unknown_scan_results(scan_id, offset, limit).await?;
+    data_overlay::annotate(state.inner(), &mut response.results).await;
+    if detect_objects.unwrap_or(false) {
+        object_heuristics::annotate(state.inner(), &mut response.results).await;
     }
-    
-    let mut all_results: Vec<MemoryFilterResult> = Vec::new();
-    let mut total_count: usize = 0;
-    
-    // Read all region files
-    let entries = match std::fs::read_dir(&temp_dir) {
-        Ok(e) => e,
-        Err(e) => return Ok(UnknownScanLookupResponse {
+    Ok(response)
+}
+
+async fn read_unknown_scan_results(scan_id: String, offset: usize, limit: usize) -> Result<UnknownScanLookupResponse, String> {
+    validate_scan_id(&scan_id)?;
+    Ok(match read_scan_hits(&scan_id, offset, limit) {
+        Ok((hits, total_count)) => UnknownScanLookupResponse {
+            success: true,
+            results: hits.into_iter()
+                .map(|hit| MemoryFilterResult {
+                    address: hit.address,
+                    value: hit.value,
+                    pointer: None,
+                    data_item: None,
+                    object_hint: None,
+                })
+                .collect(),
+            total_count,
+            error: None,
+        },
+        Err(e) => UnknownScanLookupResponse {
             success: false,
             results: vec![],
             total_count: 0,
-            error: Some(format!("Failed to read temp directory: {}", e)),
-        }),
-    };
-    
-    let mut files: Vec<_> = entries.filter_map(|e| e.ok()).collect();
-    files.sort_by_key(|e| e.path());
-    
-    for entry in files {
-        let path = entry.path();
-        if !path.extension().map_or(false, |e| e == "bin") {
-            continue;
-        }
-        
-        let file_data = match std::fs::read(&path) {
-            Ok(d) => d,
-            Err(_) => continue,
-        };
-        
-        if file_data.len() < 28 {
-            continue;
-        }
-        
-        let mut pos = 0;
-        
-        // Read header
-        let _data_size = u32::from_le_bytes([file_data[pos], file_data[pos+1], file_data[pos+2], file_data[pos+3]]) as usize;
-        pos += 4;
-        let _alignment = u32::from_le_bytes([file_data[pos], file_data[pos+1], file_data[pos+2], file_data[pos+3]]) as usize;
-        pos += 4;
-        let _start_addr = u64::from_le_bytes([
-            file_data[pos], file_data[pos+1], file_data[pos+2], file_data[pos+3],
-            file_data[pos+4], file_data[pos+5], file_data[pos+6], file_data[pos+7]
-        ]);
-        pos += 8;
-        
-        // Read number of addresses
-        let addr_count = u64::from_le_bytes([
-            file_data[pos], file_data[pos+1], file_data[pos+2], file_data[pos+3],
-            file_data[pos+4], file_data[pos+5], file_data[pos+6], file_data[pos+7]
-        ]) as usize;
-        pos += 8;
-        
-        total_count += addr_count;
-        
-        // Skip if we haven't reached offset yet
-        if total_count <= offset {
-            // Skip compressed data
-            let compressed_addr_len = u64::from_le_bytes([
-                file_data[pos], file_data[pos+1], file_data[pos+2], file_data[pos+3],
-                file_data[pos+4], file_data[pos+5], file_data[pos+6], file_data[pos+7]
-            ]) as usize;
-            pos += 8 + compressed_addr_len;
-            
-            let compressed_data_len = u64::from_le_bytes([
-                file_data[pos], file_data[pos+1], file_data[pos+2], file_data[pos+3],
-                file_data[pos+4], file_data[pos+5], file_data[pos+6], file_data[pos+7]
-            ]) as usize;
-            pos += 8 + compressed_data_len;
-            continue;
-        }
-        
-        // Read compressed addresses
-        let compressed_addr_len = u64::from_le_bytes([
-            file_data[pos], file_data[pos+1], file_data[pos+2], file_data[pos+3],
-            file_data[pos+4], file_data[pos+5], file_data[pos+6], file_data[pos+7]
-        ]) as usize;
-        pos += 8;
-        
-        let compressed_addrs = &file_data[pos..pos + compressed_addr_len];
-        pos += compressed_addr_len;
-        
-        let addr_bytes = match lz4_flex::decompress_size_prepended(compressed_addrs) {
-            Ok(d) => d,
-            Err(_) => continue,
-        };
-        
-        // Read compressed values
-        let compressed_data_len = u64::from_le_bytes([
-            file_data[pos], file_data[pos+1], file_data[pos+2], file_data[pos+3],
-            file_data[pos+4], file_data[pos+5], file_data[pos+6], file_data[pos+7]
-        ]) as usize;
-        pos += 8;
-        
-        let compressed_values = &file_data[pos..pos + compressed_data_len];
-        
-        let value_bytes = match lz4_flex::decompress_size_prepended(compressed_values) {
-            Ok(d) => d,
-            Err(_) => continue,
-        };
-        
-        // Parse addresses and values
-        let data_size = _data_size;
-        let start_idx = if total_count - addr_count <= offset { offset - (total_count - addr_count) } else { 0 };
-        let end_idx = (start_idx + limit - all_results.len()).min(addr_count);
-        
-        for i in start_idx..end_idx {
-            if all_results.len() >= limit {
-                break;
-            }
-            
-            let addr_offset = i * 8;
-            if addr_offset + 8 <= addr_bytes.len() {
-                let addr = u64::from_le_bytes([
-                    addr_bytes[addr_offset], addr_bytes[addr_offset+1],
-                    addr_bytes[addr_offset+2], addr_bytes[addr_offset+3],
-                    addr_bytes[addr_offset+4], addr_bytes[addr_offset+5],
-                    addr_bytes[addr_offset+6], addr_bytes[addr_offset+7],
-                ]);
-                
-                let val_offset = i * data_size;
-                if val_offset + data_size <= value_bytes.len() {
-                    all_results.push(MemoryFilterResult {
-                        address: addr,
-                        value: value_bytes[val_offset..val_offset + data_size].to_vec(),
-                    });
-                }
-            }
-        }
-        
-        if all_results.len() >= limit {
-            break;
-        }
-    }
-    
-    Ok(UnknownScanLookupResponse {
-        success: true,
-        results: all_results,
-        total_count,
-        error: None,
+            error: Some(e),
+        },
     })
 }
 
 /// Clear unknown scan temp files
 #[tauri::command]
 fn clear_unknown_scan(scan_id: String) -> Result<bool, String> {
-    let temp_dir = get_unknown_scan_temp_dir(&scan_id);
-    if temp_dir.exists() {
-        let _ = std::fs::remove_dir_all(&temp_dir);
-    }
-    
-    // Remove progress entry
-    if let Ok(mut progress_map) = UNKNOWN_SCAN_PROGRESS.write() {
-        progress_map.remove(&scan_id);
-    }
-    
+    clear_scan(&scan_id)?;
     Ok(true)
 }
 
@@ -1434,22 +2249,23 @@ fn get_unknown_scan_data_file(scan_id: &str) -> PathBuf {
 /// Initialize unknown scan streaming file (creates fresh file)
 #[tauri::command]
 fn init_unknown_scan_file(scan_id: String, alignment: u32, data_size: u32) -> Result<String, String> {
+    validate_scan_id(&scan_id)?;
     let file_path = get_unknown_scan_data_file(&scan_id);
-    
+
     // Ensure parent directory exists
     if let Some(parent) = file_path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
     }
-    
+
     // Create file with header: alignment (4 bytes) + data_size (4 bytes) + chunk_count (8 bytes)
     let header = [
         alignment.to_le_bytes().as_slice(),
         data_size.to_le_bytes().as_slice(),
         0u64.to_le_bytes().as_slice(),  // chunk_count placeholder
     ].concat();
-    
+
     std::fs::write(&file_path, &header).map_err(|e| format!("Failed to create file: {}", e))?;
-    
+
     Ok(file_path.to_string_lossy().to_string())
 }
 
@@ -1462,68 +2278,71 @@ fn append_unknown_scan_chunk(
     compressed_data: Vec<u8>
 ) -> Result<bool, String> {
     use std::io::Write;
-    
+    validate_scan_id(&scan_id)?;
+
     let file_path = get_unknown_scan_data_file(&scan_id);
-    
+
     let mut file = std::fs::OpenOptions::new()
         .append(true)
         .open(&file_path)
         .map_err(|e| format!("Failed to open file: {}", e))?;
-    
+
     // Write: offset (8 bytes) + compressed_len (8 bytes) + compressed_data
     file.write_all(&offset.to_le_bytes()).map_err(|e| format!("Write offset failed: {}", e))?;
     file.write_all(&(compressed_data.len() as u64).to_le_bytes()).map_err(|e| format!("Write len failed: {}", e))?;
     file.write_all(&compressed_data).map_err(|e| format!("Write data failed: {}", e))?;
-    
+
     Ok(true)
 }
 
 /// Update the chunk count in file header
 #[tauri::command]
 fn finalize_unknown_scan_file(scan_id: String, chunk_count: u64) -> Result<bool, String> {
+    validate_scan_id(&scan_id)?;
     use std::io::{Seek, SeekFrom, Write};
-    
+
     let file_path = get_unknown_scan_data_file(&scan_id);
-    
+
     let mut file = std::fs::OpenOptions::new()
         .write(true)
         .open(&file_path)
         .map_err(|e| format!("Failed to open file: {}", e))?;
-    
+
     // Write chunk_count at offset 8 (after alignment + data_size)
     file.seek(SeekFrom::Start(8)).map_err(|e| format!("Seek failed: {}", e))?;
     file.write_all(&chunk_count.to_le_bytes()).map_err(|e| format!("Write chunk count failed: {}", e))?;
-    
+
     Ok(true)
 }
 
 /// Get unknown scan file info
 #[tauri::command]
 fn get_unknown_scan_file_info(scan_id: String) -> Result<serde_json::Value, String> {
+    validate_scan_id(&scan_id)?;
     let file_path = get_unknown_scan_data_file(&scan_id);
-    
+
     if !file_path.exists() {
         return Err("File not found".to_string());
     }
-    
+
     let metadata = std::fs::metadata(&file_path)
         .map_err(|e| format!("Failed to get metadata: {}", e))?;
-    
+
     // Read header
     let file_data = std::fs::read(&file_path)
         .map_err(|e| format!("Failed to read file: {}", e))?;
-    
+
     if file_data.len() < 16 {
         return Err("Invalid file header".to_string());
     }
-    
+
     let alignment = u32::from_le_bytes([file_data[0], file_data[1], file_data[2], file_data[3]]);
     let data_size = u32::from_le_bytes([file_data[4], file_data[5], file_data[6], file_data[7]]);
     let chunk_count = u64::from_le_bytes([
         file_data[8], file_data[9], file_data[10], file_data[11],
         file_data[12], file_data[13], file_data[14], file_data[15]
     ]);
-    
+
     Ok(serde_json::json!({
         "path": file_path.to_string_lossy(),
         "size": metadata.len(),
@@ -1535,826 +2354,70 @@ fn get_unknown_scan_file_info(scan_id: String) -> Result<serde_json::Value, Stri
 
 #[tauri::command]
 async fn read_memory(address: u64, size: usize) -> Result<MemoryReadResponse, String> {
+    if virtual_addresses::is_virtual(address) {
+        return Ok(match virtual_addresses::read(address, size).await {
+            Ok(data) => MemoryReadResponse { success: true, data: Some(data), error: None, timing: None },
+            Err(e) => MemoryReadResponse { success: false, data: None, error: Some(e), timing: None },
+        });
+    }
+
     let (host, port) = {
         let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
         (config.host.clone(), config.port)
     };
-    
-    if host.is_empty() {
+
+    if host.is_empty() && !memory_dump::is_loaded() {
         return Ok(MemoryReadResponse {
             success: false,
             data: None,
             error: Some("No server connection configured".to_string()),
+            timing: None,
         });
     }
 
-    let client = reqwest::Client::new();
-    let url = format!("http://{}:{}/api/memory/read", host, port);
-    
-    let request_body = serde_json::json!({
-        "address": address,
-        "size": size
-    });
-
-    match client.post(&url).json(&request_body).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.json::<serde_json::Value>().await {
-                    Ok(json_response) => {
-                        if let Some(data_str) = json_response.get("data").and_then(|v| v.as_str()) {
-                            // Convert hex string to bytes
-                            let hex_clean = data_str.replace(" ", "").replace("\n", "");
-                            let mut bytes = Vec::new();
-                            
-                            // Parse hex string in pairs
-                            for chunk in hex_clean.chars().collect::<Vec<char>>().chunks(2) {
-                                if chunk.len() == 2 {
-                                    let hex_str: String = chunk.iter().collect();
-                                    if let Ok(byte) = u8::from_str_radix(&hex_str, 16) {
-                                        bytes.push(byte);
-                                    }
-                                }
-                            }
-                            
-                            Ok(MemoryReadResponse {
-                                success: true,
-                                data: Some(bytes),
-                                error: None,
-                            })
-                        } else {
-                            Ok(MemoryReadResponse {
-                                success: false,
-                                data: None,
-                                error: Some("Invalid response format - no data field".to_string()),
-                            })
-                        }
-                    }
-                    Err(e) => Ok(MemoryReadResponse {
-                        success: false,
-                        data: None,
-                        error: Some(format!("Failed to parse response: {}", e)),
-                    })
-                }
-            } else {
-                Ok(MemoryReadResponse {
-                    success: false,
-                    data: None,
-                    error: Some(format!("Server error: {}", response.status())),
-                })
-            }
+    let mut stopwatch = latency::Stopwatch::start("read_memory");
+    match read_memory_bytes(&host, port, address, size).await {
+        Ok(bytes) if bytes.is_empty() && size > 0 => Ok(MemoryReadResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to read memory at 0x{:x}", address)),
+            timing: None,
+        }),
+        Ok(bytes) => {
+            stopwatch.mark("network");
+            stopwatch.set_bytes(bytes.len());
+            let mut response = MemoryReadResponse {
+                success: true,
+                data: Some(bytes.to_vec()),
+                error: None,
+                timing: None,
+            };
+            response.timing = stopwatch.finish(&response);
+            Ok(response)
         }
         Err(e) => Ok(MemoryReadResponse {
             success: false,
             data: None,
-            error: Some(format!("Network error: {}", e)),
+            error: Some(e),
+            timing: None,
         })
     }
 }
 
-/// WASM opcode to mnemonic mapping (basic opcodes)
-fn wasm_opcode_to_string(opcode: u8) -> (&'static str, usize) {
-    // Returns (mnemonic, instruction_length including opcode)
-    match opcode {
-        // Control instructions
-        0x00 => ("unreachable", 1),
-        0x01 => ("nop", 1),
-        0x02 => ("block", 2),      // + blocktype
-        0x03 => ("loop", 2),       // + blocktype
-        0x04 => ("if", 2),         // + blocktype
-        0x05 => ("else", 1),
-        0x0B => ("end", 1),
-        0x0C => ("br", 2),         // + labelidx
-        0x0D => ("br_if", 2),      // + labelidx
-        0x0E => ("br_table", 2),   // variable, simplified
-        0x0F => ("return", 1),
-        0x10 => ("call", 5),       // + funcidx (leb128)
-        0x11 => ("call_indirect", 6), // + typeidx + tableidx
-        
-        // Parametric instructions
-        0x1A => ("drop", 1),
-        0x1B => ("select", 1),
-        0x1C => ("select", 2),     // typed select
-        
-        // Variable instructions
-        0x20 => ("local.get", 2),
-        0x21 => ("local.set", 2),
-        0x22 => ("local.tee", 2),
-        0x23 => ("global.get", 2),
-        0x24 => ("global.set", 2),
-        
-        // Table instructions
-        0x25 => ("table.get", 2),
-        0x26 => ("table.set", 2),
-        
-        // Memory instructions
-        0x28 => ("i32.load", 3),
-        0x29 => ("i64.load", 3),
-        0x2A => ("f32.load", 3),
-        0x2B => ("f64.load", 3),
-        0x2C => ("i32.load8_s", 3),
-        0x2D => ("i32.load8_u", 3),
-        0x2E => ("i32.load16_s", 3),
-        0x2F => ("i32.load16_u", 3),
-        0x30 => ("i64.load8_s", 3),
-        0x31 => ("i64.load8_u", 3),
-        0x32 => ("i64.load16_s", 3),
-        0x33 => ("i64.load16_u", 3),
-        0x34 => ("i64.load32_s", 3),
-        0x35 => ("i64.load32_u", 3),
-        0x36 => ("i32.store", 3),
-        0x37 => ("i64.store", 3),
-        0x38 => ("f32.store", 3),
-        0x39 => ("f64.store", 3),
-        0x3A => ("i32.store8", 3),
-        0x3B => ("i32.store16", 3),
-        0x3C => ("i64.store8", 3),
-        0x3D => ("i64.store16", 3),
-        0x3E => ("i64.store32", 3),
-        0x3F => ("memory.size", 2),
-        0x40 => ("memory.grow", 2),
-        
-        // Numeric instructions - constants
-        0x41 => ("i32.const", 5),   // + i32 leb128
-        0x42 => ("i64.const", 9),   // + i64 leb128
-        0x43 => ("f32.const", 5),   // + f32
-        0x44 => ("f64.const", 9),   // + f64
-        
-        // Numeric instructions - comparison
-        0x45 => ("i32.eqz", 1),
-        0x46 => ("i32.eq", 1),
-        0x47 => ("i32.ne", 1),
-        0x48 => ("i32.lt_s", 1),
-        0x49 => ("i32.lt_u", 1),
-        0x4A => ("i32.gt_s", 1),
-        0x4B => ("i32.gt_u", 1),
-        0x4C => ("i32.le_s", 1),
-        0x4D => ("i32.le_u", 1),
-        0x4E => ("i32.ge_s", 1),
-        0x4F => ("i32.ge_u", 1),
-        
-        0x50 => ("i64.eqz", 1),
-        0x51 => ("i64.eq", 1),
-        0x52 => ("i64.ne", 1),
-        0x53 => ("i64.lt_s", 1),
-        0x54 => ("i64.lt_u", 1),
-        0x55 => ("i64.gt_s", 1),
-        0x56 => ("i64.gt_u", 1),
-        0x57 => ("i64.le_s", 1),
-        0x58 => ("i64.le_u", 1),
-        0x59 => ("i64.ge_s", 1),
-        0x5A => ("i64.ge_u", 1),
-        
-        0x5B => ("f32.eq", 1),
-        0x5C => ("f32.ne", 1),
-        0x5D => ("f32.lt", 1),
-        0x5E => ("f32.gt", 1),
-        0x5F => ("f32.le", 1),
-        0x60 => ("f32.ge", 1),
-        
-        0x61 => ("f64.eq", 1),
-        0x62 => ("f64.ne", 1),
-        0x63 => ("f64.lt", 1),
-        0x64 => ("f64.gt", 1),
-        0x65 => ("f64.le", 1),
-        0x66 => ("f64.ge", 1),
-        
-        // Numeric instructions - arithmetic
-        0x67 => ("i32.clz", 1),
-        0x68 => ("i32.ctz", 1),
-        0x69 => ("i32.popcnt", 1),
-        0x6A => ("i32.add", 1),
-        0x6B => ("i32.sub", 1),
-        0x6C => ("i32.mul", 1),
-        0x6D => ("i32.div_s", 1),
-        0x6E => ("i32.div_u", 1),
-        0x6F => ("i32.rem_s", 1),
-        0x70 => ("i32.rem_u", 1),
-        0x71 => ("i32.and", 1),
-        0x72 => ("i32.or", 1),
-        0x73 => ("i32.xor", 1),
-        0x74 => ("i32.shl", 1),
-        0x75 => ("i32.shr_s", 1),
-        0x76 => ("i32.shr_u", 1),
-        0x77 => ("i32.rotl", 1),
-        0x78 => ("i32.rotr", 1),
-        
-        0x79 => ("i64.clz", 1),
-        0x7A => ("i64.ctz", 1),
-        0x7B => ("i64.popcnt", 1),
-        0x7C => ("i64.add", 1),
-        0x7D => ("i64.sub", 1),
-        0x7E => ("i64.mul", 1),
-        0x7F => ("i64.div_s", 1),
-        0x80 => ("i64.div_u", 1),
-        0x81 => ("i64.rem_s", 1),
-        0x82 => ("i64.rem_u", 1),
-        0x83 => ("i64.and", 1),
-        0x84 => ("i64.or", 1),
-        0x85 => ("i64.xor", 1),
-        0x86 => ("i64.shl", 1),
-        0x87 => ("i64.shr_s", 1),
-        0x88 => ("i64.shr_u", 1),
-        0x89 => ("i64.rotl", 1),
-        0x8A => ("i64.rotr", 1),
-        
-        // Float operations
-        0x8B => ("f32.abs", 1),
-        0x8C => ("f32.neg", 1),
-        0x8D => ("f32.ceil", 1),
-        0x8E => ("f32.floor", 1),
-        0x8F => ("f32.trunc", 1),
-        0x90 => ("f32.nearest", 1),
-        0x91 => ("f32.sqrt", 1),
-        0x92 => ("f32.add", 1),
-        0x93 => ("f32.sub", 1),
-        0x94 => ("f32.mul", 1),
-        0x95 => ("f32.div", 1),
-        0x96 => ("f32.min", 1),
-        0x97 => ("f32.max", 1),
-        0x98 => ("f32.copysign", 1),
-        
-        0x99 => ("f64.abs", 1),
-        0x9A => ("f64.neg", 1),
-        0x9B => ("f64.ceil", 1),
-        0x9C => ("f64.floor", 1),
-        0x9D => ("f64.trunc", 1),
-        0x9E => ("f64.nearest", 1),
-        0x9F => ("f64.sqrt", 1),
-        0xA0 => ("f64.add", 1),
-        0xA1 => ("f64.sub", 1),
-        0xA2 => ("f64.mul", 1),
-        0xA3 => ("f64.div", 1),
-        0xA4 => ("f64.min", 1),
-        0xA5 => ("f64.max", 1),
-        0xA6 => ("f64.copysign", 1),
-        
-        // Conversions
-        0xA7 => ("i32.wrap_i64", 1),
-        0xA8 => ("i32.trunc_f32_s", 1),
-        0xA9 => ("i32.trunc_f32_u", 1),
-        0xAA => ("i32.trunc_f64_s", 1),
-        0xAB => ("i32.trunc_f64_u", 1),
-        0xAC => ("i64.extend_i32_s", 1),
-        0xAD => ("i64.extend_i32_u", 1),
-        0xAE => ("i64.trunc_f32_s", 1),
-        0xAF => ("i64.trunc_f32_u", 1),
-        0xB0 => ("i64.trunc_f64_s", 1),
-        0xB1 => ("i64.trunc_f64_u", 1),
-        0xB2 => ("f32.convert_i32_s", 1),
-        0xB3 => ("f32.convert_i32_u", 1),
-        0xB4 => ("f32.convert_i64_s", 1),
-        0xB5 => ("f32.convert_i64_u", 1),
-        0xB6 => ("f32.demote_f64", 1),
-        0xB7 => ("f64.convert_i32_s", 1),
-        0xB8 => ("f64.convert_i32_u", 1),
-        0xB9 => ("f64.convert_i64_s", 1),
-        0xBA => ("f64.convert_i64_u", 1),
-        0xBB => ("f64.promote_f32", 1),
-        0xBC => ("i32.reinterpret_f32", 1),
-        0xBD => ("i64.reinterpret_f64", 1),
-        0xBE => ("f32.reinterpret_i32", 1),
-        0xBF => ("f64.reinterpret_i64", 1),
-        
-        // Sign extension
-        0xC0 => ("i32.extend8_s", 1),
-        0xC1 => ("i32.extend16_s", 1),
-        0xC2 => ("i64.extend8_s", 1),
-        0xC3 => ("i64.extend16_s", 1),
-        0xC4 => ("i64.extend32_s", 1),
-        
-        // Multi-byte opcodes (FC prefix)
-        0xFC => ("(FC prefix)", 2),
-        // Multi-byte opcodes (FD prefix - SIMD)
-        0xFD => ("(FD simd)", 2),
-        
-        _ => ("unknown", 1),
-    }
-}
-
-/// Read unsigned LEB128 from bytes, returns (value, bytes_consumed)
-fn read_uleb128(data: &[u8], offset: usize) -> (u64, usize) {
-    let mut result: u64 = 0;
-    let mut shift = 0;
-    let mut idx = 0;
-    
-    while offset + idx < data.len() {
-        let byte = data[offset + idx];
-        result |= ((byte & 0x7f) as u64) << shift;
-        idx += 1;
-        if byte & 0x80 == 0 {
-            break;
-        }
-        shift += 7;
-        if shift >= 64 {
-            break;
-        }
-    }
-    
-    (result, idx)
-}
-
-/// Read signed LEB128 from bytes
-fn read_sleb128(data: &[u8], offset: usize) -> (i64, usize) {
-    let mut result: i64 = 0;
-    let mut shift = 0;
-    let mut idx = 0;
-    let mut byte = 0u8;
-    
-    while offset + idx < data.len() {
-        byte = data[offset + idx];
-        result |= ((byte & 0x7f) as i64) << shift;
-        shift += 7;
-        idx += 1;
-        if byte & 0x80 == 0 {
-            break;
-        }
-        if shift >= 64 {
-            break;
-        }
-    }
-    
-    // Sign extend if needed
-    if shift < 64 && (byte & 0x40) != 0 {
-        result |= !0i64 << shift;
-    }
-    
-    (result, idx)
-}
-
-/// Get WASM instruction info: (mnemonic, total_bytes, operand_string, is_branch, branch_target_offset)
-fn decode_wasm_instruction(data: &[u8], offset: usize, _base_address: u64) -> (String, usize, String, bool, Option<u64>) {
-    if offset >= data.len() {
-        return ("".to_string(), 0, "".to_string(), false, None);
-    }
-    
-    let opcode = data[offset];
-    let mut consumed = 1usize;
-    let mut operand = String::new();
-    let mut is_branch = false;
-    let mut branch_target: Option<u64> = None;
-    
-    let mnemonic = match opcode {
-        // Control instructions
-        0x00 => "unreachable",
-        0x01 => "nop",
-        0x02 => { // block
-            if offset + 1 < data.len() {
-                let blocktype = data[offset + 1] as i8;
-                consumed += 1;
-                operand = match blocktype {
-                    0x40 => "".to_string(),
-                    t => format!("{:02x}", t as u8),
-                };
-            }
-            "block"
-        }
-        0x03 => { // loop
-            if offset + 1 < data.len() {
-                let blocktype = data[offset + 1] as i8;
-                consumed += 1;
-                operand = match blocktype {
-                    0x40 => "".to_string(),
-                    t => format!("{:02x}", t as u8),
-                };
-            }
-            "loop"
-        }
-        0x04 => { // if
-            if offset + 1 < data.len() {
-                consumed += 1;
-            }
-            "if"
-        }
-        0x05 => "else",
-        0x0B => "end",
-        0x0C => { // br
-            let (labelidx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", labelidx);
-            is_branch = true;
-            "br"
-        }
-        0x0D => { // br_if
-            let (labelidx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", labelidx);
-            is_branch = true;
-            "br_if"
-        }
-        0x0E => { // br_table
-            let (count, len1) = read_uleb128(data, offset + 1);
-            consumed += len1;
-            let mut labels = Vec::new();
-            for _ in 0..=count {
-                let (label, len) = read_uleb128(data, offset + consumed);
-                labels.push(label);
-                consumed += len;
-            }
-            operand = labels.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(" ");
-            is_branch = true;
-            "br_table"
-        }
-        0x0F => "return",
-        0x10 => { // call
-            let (funcidx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("func[{}]", funcidx);
-            is_branch = true;
-            branch_target = Some(funcidx); // Function index as target
-            "call"
-        }
-        0x11 => { // call_indirect
-            let (typeidx, len1) = read_uleb128(data, offset + 1);
-            consumed += len1;
-            let (tableidx, len2) = read_uleb128(data, offset + 1 + len1);
-            consumed += len2;
-            operand = format!("type[{}] table[{}]", typeidx, tableidx);
-            "call_indirect"
-        }
-        
-        // Parametric
-        0x1A => "drop",
-        0x1B => "select",
-        
-        // Variable instructions
-        0x20 => { // local.get
-            let (idx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", idx);
-            "local.get"
-        }
-        0x21 => { // local.set
-            let (idx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", idx);
-            "local.set"
-        }
-        0x22 => { // local.tee
-            let (idx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", idx);
-            "local.tee"
-        }
-        0x23 => { // global.get
-            let (idx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", idx);
-            "global.get"
-        }
-        0x24 => { // global.set
-            let (idx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", idx);
-            "global.set"
-        }
-        
-        // Memory load/store
-        0x28..=0x3E => {
-            let (align, len1) = read_uleb128(data, offset + 1);
-            consumed += len1;
-            let (mem_offset, len2) = read_uleb128(data, offset + 1 + len1);
-            consumed += len2;
-            operand = format!("align={} offset={}", align, mem_offset);
-            match opcode {
-                0x28 => "i32.load",
-                0x29 => "i64.load",
-                0x2A => "f32.load",
-                0x2B => "f64.load",
-                0x2C => "i32.load8_s",
-                0x2D => "i32.load8_u",
-                0x2E => "i32.load16_s",
-                0x2F => "i32.load16_u",
-                0x30 => "i64.load8_s",
-                0x31 => "i64.load8_u",
-                0x32 => "i64.load16_s",
-                0x33 => "i64.load16_u",
-                0x34 => "i64.load32_s",
-                0x35 => "i64.load32_u",
-                0x36 => "i32.store",
-                0x37 => "i64.store",
-                0x38 => "f32.store",
-                0x39 => "f64.store",
-                0x3A => "i32.store8",
-                0x3B => "i32.store16",
-                0x3C => "i64.store8",
-                0x3D => "i64.store16",
-                0x3E => "i64.store32",
-                _ => "memory_op",
-            }
-        }
-        0x3F => { // memory.size
-            let (memidx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", memidx);
-            "memory.size"
-        }
-        0x40 => { // memory.grow
-            let (memidx, len) = read_uleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", memidx);
-            "memory.grow"
-        }
-        
-        // Constants
-        0x41 => { // i32.const
-            let (val, len) = read_sleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", val as i32);
-            "i32.const"
-        }
-        0x42 => { // i64.const
-            let (val, len) = read_sleb128(data, offset + 1);
-            consumed += len;
-            operand = format!("{}", val);
-            "i64.const"
-        }
-        0x43 => { // f32.const
-            if offset + 5 <= data.len() {
-                let bytes = [data[offset+1], data[offset+2], data[offset+3], data[offset+4]];
-                let val = f32::from_le_bytes(bytes);
-                operand = format!("{}", val);
-                consumed += 4;
-            }
-            "f32.const"
-        }
-        0x44 => { // f64.const
-            if offset + 9 <= data.len() {
-                let bytes = [data[offset+1], data[offset+2], data[offset+3], data[offset+4],
-                             data[offset+5], data[offset+6], data[offset+7], data[offset+8]];
-                let val = f64::from_le_bytes(bytes);
-                operand = format!("{}", val);
-                consumed += 8;
-            }
-            "f64.const"
-        }
-        
-        // Comparison i32
-        0x45 => "i32.eqz",
-        0x46 => "i32.eq",
-        0x47 => "i32.ne",
-        0x48 => "i32.lt_s",
-        0x49 => "i32.lt_u",
-        0x4A => "i32.gt_s",
-        0x4B => "i32.gt_u",
-        0x4C => "i32.le_s",
-        0x4D => "i32.le_u",
-        0x4E => "i32.ge_s",
-        0x4F => "i32.ge_u",
-        
-        // Comparison i64
-        0x50 => "i64.eqz",
-        0x51 => "i64.eq",
-        0x52 => "i64.ne",
-        0x53 => "i64.lt_s",
-        0x54 => "i64.lt_u",
-        0x55 => "i64.gt_s",
-        0x56 => "i64.gt_u",
-        0x57 => "i64.le_s",
-        0x58 => "i64.le_u",
-        0x59 => "i64.ge_s",
-        0x5A => "i64.ge_u",
-        
-        // Comparison f32
-        0x5B => "f32.eq",
-        0x5C => "f32.ne",
-        0x5D => "f32.lt",
-        0x5E => "f32.gt",
-        0x5F => "f32.le",
-        0x60 => "f32.ge",
-        
-        // Comparison f64
-        0x61 => "f64.eq",
-        0x62 => "f64.ne",
-        0x63 => "f64.lt",
-        0x64 => "f64.gt",
-        0x65 => "f64.le",
-        0x66 => "f64.ge",
-        
-        // Numeric i32
-        0x67 => "i32.clz",
-        0x68 => "i32.ctz",
-        0x69 => "i32.popcnt",
-        0x6A => "i32.add",
-        0x6B => "i32.sub",
-        0x6C => "i32.mul",
-        0x6D => "i32.div_s",
-        0x6E => "i32.div_u",
-        0x6F => "i32.rem_s",
-        0x70 => "i32.rem_u",
-        0x71 => "i32.and",
-        0x72 => "i32.or",
-        0x73 => "i32.xor",
-        0x74 => "i32.shl",
-        0x75 => "i32.shr_s",
-        0x76 => "i32.shr_u",
-        0x77 => "i32.rotl",
-        0x78 => "i32.rotr",
-        
-        // Numeric i64
-        0x79 => "i64.clz",
-        0x7A => "i64.ctz",
-        0x7B => "i64.popcnt",
-        0x7C => "i64.add",
-        0x7D => "i64.sub",
-        0x7E => "i64.mul",
-        0x7F => "i64.div_s",
-        0x80 => "i64.div_u",
-        0x81 => "i64.rem_s",
-        0x82 => "i64.rem_u",
-        0x83 => "i64.and",
-        0x84 => "i64.or",
-        0x85 => "i64.xor",
-        0x86 => "i64.shl",
-        0x87 => "i64.shr_s",
-        0x88 => "i64.shr_u",
-        0x89 => "i64.rotl",
-        0x8A => "i64.rotr",
-        
-        // Numeric f32
-        0x8B => "f32.abs",
-        0x8C => "f32.neg",
-        0x8D => "f32.ceil",
-        0x8E => "f32.floor",
-        0x8F => "f32.trunc",
-        0x90 => "f32.nearest",
-        0x91 => "f32.sqrt",
-        0x92 => "f32.add",
-        0x93 => "f32.sub",
-        0x94 => "f32.mul",
-        0x95 => "f32.div",
-        0x96 => "f32.min",
-        0x97 => "f32.max",
-        0x98 => "f32.copysign",
-        
-        // Numeric f64
-        0x99 => "f64.abs",
-        0x9A => "f64.neg",
-        0x9B => "f64.ceil",
-        0x9C => "f64.floor",
-        0x9D => "f64.trunc",
-        0x9E => "f64.nearest",
-        0x9F => "f64.sqrt",
-        0xA0 => "f64.add",
-        0xA1 => "f64.sub",
-        0xA2 => "f64.mul",
-        0xA3 => "f64.div",
-        0xA4 => "f64.min",
-        0xA5 => "f64.max",
-        0xA6 => "f64.copysign",
-        
-        // Conversions
-        0xA7 => "i32.wrap_i64",
-        0xA8 => "i32.trunc_f32_s",
-        0xA9 => "i32.trunc_f32_u",
-        0xAA => "i32.trunc_f64_s",
-        0xAB => "i32.trunc_f64_u",
-        0xAC => "i64.extend_i32_s",
-        0xAD => "i64.extend_i32_u",
-        0xAE => "i64.trunc_f32_s",
-        0xAF => "i64.trunc_f32_u",
-        0xB0 => "i64.trunc_f64_s",
-        0xB1 => "i64.trunc_f64_u",
-        0xB2 => "f32.convert_i32_s",
-        0xB3 => "f32.convert_i32_u",
-        0xB4 => "f32.convert_i64_s",
-        0xB5 => "f32.convert_i64_u",
-        0xB6 => "f32.demote_f64",
-        0xB7 => "f64.convert_i32_s",
-        0xB8 => "f64.convert_i32_u",
-        0xB9 => "f64.convert_i64_s",
-        0xBA => "f64.convert_i64_u",
-        0xBB => "f64.promote_f32",
-        0xBC => "i32.reinterpret_f32",
-        0xBD => "i64.reinterpret_f64",
-        0xBE => "f32.reinterpret_i32",
-        0xBF => "f64.reinterpret_i64",
-        
-        // Sign extension
-        0xC0 => "i32.extend8_s",
-        0xC1 => "i32.extend16_s",
-        0xC2 => "i64.extend8_s",
-        0xC3 => "i64.extend16_s",
-        0xC4 => "i64.extend32_s",
-        
-        // FC prefix (extended instructions)
-        0xFC => {
-            if offset + 1 < data.len() {
-                let (subop, len) = read_uleb128(data, offset + 1);
-                consumed += len;
-                match subop {
-                    0 => "i32.trunc_sat_f32_s",
-                    1 => "i32.trunc_sat_f32_u",
-                    2 => "i32.trunc_sat_f64_s",
-                    3 => "i32.trunc_sat_f64_u",
-                    4 => "i64.trunc_sat_f32_s",
-                    5 => "i64.trunc_sat_f32_u",
-                    6 => "i64.trunc_sat_f64_s",
-                    7 => "i64.trunc_sat_f64_u",
-                    8 => { // memory.init
-                        let (dataidx, len1) = read_uleb128(data, offset + consumed);
-                        consumed += len1;
-                        let (memidx, len2) = read_uleb128(data, offset + consumed);
-                        consumed += len2;
-                        operand = format!("data[{}] mem[{}]", dataidx, memidx);
-                        "memory.init"
-                    }
-                    9 => { // data.drop
-                        let (dataidx, len1) = read_uleb128(data, offset + consumed);
-                        consumed += len1;
-                        operand = format!("{}", dataidx);
-                        "data.drop"
-                    }
-                    10 => { // memory.copy
-                        let (dst, len1) = read_uleb128(data, offset + consumed);
-                        consumed += len1;
-                        let (src, len2) = read_uleb128(data, offset + consumed);
-                        consumed += len2;
-                        operand = format!("dst={} src={}", dst, src);
-                        "memory.copy"
-                    }
-                    11 => { // memory.fill
-                        let (memidx, len1) = read_uleb128(data, offset + consumed);
-                        consumed += len1;
-                        operand = format!("{}", memidx);
-                        "memory.fill"
-                    }
-                    _ => "fc_unknown",
-                }
-            } else {
-                "fc_prefix"
-            }
-        }
-        
-        // FD prefix (SIMD)
-        0xFD => {
-            if offset + 1 < data.len() {
-                let (subop, len) = read_uleb128(data, offset + 1);
-                consumed += len;
-                operand = format!("{}", subop);
-            }
-            "simd"
-        }
-        
-        _ => "unknown",
-    };
-    
-    (mnemonic.to_string(), consumed, operand, is_branch, branch_target)
-}
-
 /// Disassemble WASM bytecode with detailed output
 fn disassemble_wasm(memory_data: &[u8], base_address: u64) -> DisassembleResponse {
-    let mut lines = Vec::new();
-    let mut offset: usize = 0;
-    let max_instructions = 500;
-    
-    while offset < memory_data.len() && lines.len() < max_instructions {
-        let (mnemonic, consumed, operand, is_branch, _branch_target) = 
-            decode_wasm_instruction(memory_data, offset, base_address);
-        
-        if consumed == 0 {
-            break;
-        }
-        
-        let current_address = base_address + offset as u64;
-        let end_offset = std::cmp::min(offset + consumed, memory_data.len());
-        
-        // Format bytes (max 8 shown)
-        let bytes_display = memory_data[offset..end_offset]
-            .iter()
-            .take(8)
-            .map(|b| format!("{:02x}", b))
-            .collect::<Vec<_>>()
-            .join(" ");
-        let bytes_suffix = if consumed > 8 { ".." } else { "" };
-        
-        // Format branch arrow hint
-        let branch_hint = if is_branch { " <-" } else { "" };
-        
-        // Format: address|bytes|mnemonic operand (pipe-separated for parser)
-        // Only add space before operand if operand is not empty
-        let instruction_text = if operand.is_empty() {
-            format!("{}{}", mnemonic, branch_hint)
-        } else {
-            format!("{} {}{}", mnemonic, operand, branch_hint)
-        };
-        
-        let line = format!(
-            "0x{:08x}|{}{}|{}",
-            current_address,
-            bytes_display,
-            bytes_suffix,
-            instruction_text
-        );
-        lines.push(line);
-        
-        offset += consumed;
-    }
-    
+    let instructions: Vec<_> = wasm_disasm::decode(memory_data, 500, false)
+        .iter()
+        .map(|decoded| wasm_disasm::instruction_info(memory_data, decoded, None))
+        .collect();
+    let lines = wasm_disasm::format_lines(&instructions, base_address, false);
+
     DisassembleResponse {
         success: true,
         disassembly: Some(lines.join("\n")),
         instructions_count: lines.len(),
         error: None,
+        timing: None,
     }
 }
 
@@ -2380,18 +2443,9 @@ pub struct WasmFunctionInfo {
     pub local_count: u32,      // Number of locals
     pub param_count: u32,      // Number of parameters (from type)
     pub result_count: u32,     // Number of results (from type)
-    pub instructions: Vec<WasmInstructionInfo>,
-}
-
-/// WASM instruction info with structured details
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WasmInstructionInfo {
-    pub offset: u32,           // Byte offset within function
-    pub bytes: Vec<u8>,        // Raw instruction bytes
-    pub mnemonic: String,      // Instruction name
-    pub operands: String,      // Formatted operands
-    pub is_branch: bool,       // Is this a branch/control flow instruction
-    pub depth_change: i32,     // Block nesting change (+1 for block/loop/if, -1 for end)
+    pub local_names: HashMap<u32, String>, // From the name section
+    pub source: Option<wasm_debug_info::WasmSourceLocation>, // First source line of the body
+    pub instructions: Vec<wasm_disasm::WasmInstructionInfo>,
 }
 
 /// WASM module analysis result
@@ -2404,6 +2458,9 @@ pub struct WasmModuleAnalysis {
     pub memory_count: u32,
     pub table_count: u32,
     pub global_count: u32,
+    pub global_names: HashMap<u32, String>,
+    pub source_mapping_url: Option<String>, // From the sourceMappingURL section, for fetching the map
+    pub has_dwarf: bool,
 }
 
 /// Save WASM binary data to a .wasm file for Ghidra analysis
@@ -2422,28 +2479,28 @@ async fn save_wasm_binary(
         ghidra_dir.join("libraries")
     };
     std::fs::create_dir_all(&libs_dir).map_err(|e| format!("Failed to create libraries directory: {}", e))?;
-    
+
     // Sanitize module name for filename
     let safe_name = module_name
         .chars()
         .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
         .collect::<String>();
-    
+
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs();
-    
+
     let filename = format!("{}_{}.wasm", safe_name, timestamp);
     let file_path = libs_dir.join(&filename);
-    
+
     // Write the WASM binary
     std::fs::write(&file_path, &binary_data)
         .map_err(|e| format!("Failed to write WASM file: {}", e))?;
-    
+
     let path_str = file_path.to_string_lossy().to_string();
     println!("[WASM] Saved WASM binary to: {} ({} bytes)", path_str, binary_data.len());
-    
+
     Ok(path_str)
 }
 
@@ -2451,14 +2508,14 @@ async fn save_wasm_binary(
 #[tauri::command]
 async fn list_wasm_files() -> Result<Vec<String>, String> {
     let wasm_dir = get_wasm_modules_dir();
-    
+
     if !wasm_dir.exists() {
         return Ok(Vec::new());
     }
-    
+
     let entries = std::fs::read_dir(&wasm_dir)
         .map_err(|e| format!("Failed to read WASM directory: {}", e))?;
-    
+
     let mut files = Vec::new();
     for entry in entries {
         if let Ok(entry) = entry {
@@ -2468,228 +2525,21 @@ async fn list_wasm_files() -> Result<Vec<String>, String> {
             }
         }
     }
-    
+
     files.sort();
     Ok(files)
 }
 
-/// Format a wasmparser Operator to mnemonic and operands
-fn format_wasm_operator(op: &Operator) -> (String, String, bool, i32) {
-    use Operator::*;
-    
-    match op {
-        // Control flow
-        Unreachable => ("unreachable".into(), "".into(), false, 0),
-        Nop => ("nop".into(), "".into(), false, 0),
-        Block { blockty } => ("block".into(), format!("{:?}", blockty), false, 1),
-        Loop { blockty } => ("loop".into(), format!("{:?}", blockty), true, 1),
-        If { blockty } => ("if".into(), format!("{:?}", blockty), true, 1),
-        Else => ("else".into(), "".into(), false, 0),
-        End => ("end".into(), "".into(), false, -1),
-        Br { relative_depth } => ("br".into(), format!("{}", relative_depth), true, 0),
-        BrIf { relative_depth } => ("br_if".into(), format!("{}", relative_depth), true, 0),
-        BrTable { targets } => ("br_table".into(), format!("{} targets", targets.len()), true, 0),
-        Return => ("return".into(), "".into(), true, 0),
-        Call { function_index } => ("call".into(), format!("func[{}]", function_index), true, 0),
-        CallIndirect { type_index, table_index } => 
-            ("call_indirect".into(), format!("type[{}] table[{}]", type_index, table_index), true, 0),
-        
-        // Parametric
-        Drop => ("drop".into(), "".into(), false, 0),
-        Select => ("select".into(), "".into(), false, 0),
-        
-        // Variable access
-        LocalGet { local_index } => ("local.get".into(), format!("{}", local_index), false, 0),
-        LocalSet { local_index } => ("local.set".into(), format!("{}", local_index), false, 0),
-        LocalTee { local_index } => ("local.tee".into(), format!("{}", local_index), false, 0),
-        GlobalGet { global_index } => ("global.get".into(), format!("{}", global_index), false, 0),
-        GlobalSet { global_index } => ("global.set".into(), format!("{}", global_index), false, 0),
-        
-        // Memory operations
-        I32Load { memarg } => ("i32.load".into(), format!("offset={} align={}", memarg.offset, memarg.align), false, 0),
-        I64Load { memarg } => ("i64.load".into(), format!("offset={} align={}", memarg.offset, memarg.align), false, 0),
-        F32Load { memarg } => ("f32.load".into(), format!("offset={} align={}", memarg.offset, memarg.align), false, 0),
-        F64Load { memarg } => ("f64.load".into(), format!("offset={} align={}", memarg.offset, memarg.align), false, 0),
-        I32Load8S { memarg } => ("i32.load8_s".into(), format!("offset={}", memarg.offset), false, 0),
-        I32Load8U { memarg } => ("i32.load8_u".into(), format!("offset={}", memarg.offset), false, 0),
-        I32Load16S { memarg } => ("i32.load16_s".into(), format!("offset={}", memarg.offset), false, 0),
-        I32Load16U { memarg } => ("i32.load16_u".into(), format!("offset={}", memarg.offset), false, 0),
-        I64Load8S { memarg } => ("i64.load8_s".into(), format!("offset={}", memarg.offset), false, 0),
-        I64Load8U { memarg } => ("i64.load8_u".into(), format!("offset={}", memarg.offset), false, 0),
-        I64Load16S { memarg } => ("i64.load16_s".into(), format!("offset={}", memarg.offset), false, 0),
-        I64Load16U { memarg } => ("i64.load16_u".into(), format!("offset={}", memarg.offset), false, 0),
-        I64Load32S { memarg } => ("i64.load32_s".into(), format!("offset={}", memarg.offset), false, 0),
-        I64Load32U { memarg } => ("i64.load32_u".into(), format!("offset={}", memarg.offset), false, 0),
-        I32Store { memarg } => ("i32.store".into(), format!("offset={} align={}", memarg.offset, memarg.align), false, 0),
-        I64Store { memarg } => ("i64.store".into(), format!("offset={} align={}", memarg.offset, memarg.align), false, 0),
-        F32Store { memarg } => ("f32.store".into(), format!("offset={} align={}", memarg.offset, memarg.align), false, 0),
-        F64Store { memarg } => ("f64.store".into(), format!("offset={} align={}", memarg.offset, memarg.align), false, 0),
-        I32Store8 { memarg } => ("i32.store8".into(), format!("offset={}", memarg.offset), false, 0),
-        I32Store16 { memarg } => ("i32.store16".into(), format!("offset={}", memarg.offset), false, 0),
-        I64Store8 { memarg } => ("i64.store8".into(), format!("offset={}", memarg.offset), false, 0),
-        I64Store16 { memarg } => ("i64.store16".into(), format!("offset={}", memarg.offset), false, 0),
-        I64Store32 { memarg } => ("i64.store32".into(), format!("offset={}", memarg.offset), false, 0),
-        MemorySize { mem, .. } => ("memory.size".into(), format!("{}", mem), false, 0),
-        MemoryGrow { mem, .. } => ("memory.grow".into(), format!("{}", mem), false, 0),
-        
-        // Constants
-        I32Const { value } => ("i32.const".into(), format!("{}", value), false, 0),
-        I64Const { value } => ("i64.const".into(), format!("{}", value), false, 0),
-        F32Const { value } => ("f32.const".into(), format!("{:?}", value), false, 0),
-        F64Const { value } => ("f64.const".into(), format!("{:?}", value), false, 0),
-        
-        // Comparison
-        I32Eqz => ("i32.eqz".into(), "".into(), false, 0),
-        I32Eq => ("i32.eq".into(), "".into(), false, 0),
-        I32Ne => ("i32.ne".into(), "".into(), false, 0),
-        I32LtS => ("i32.lt_s".into(), "".into(), false, 0),
-        I32LtU => ("i32.lt_u".into(), "".into(), false, 0),
-        I32GtS => ("i32.gt_s".into(), "".into(), false, 0),
-        I32GtU => ("i32.gt_u".into(), "".into(), false, 0),
-        I32LeS => ("i32.le_s".into(), "".into(), false, 0),
-        I32LeU => ("i32.le_u".into(), "".into(), false, 0),
-        I32GeS => ("i32.ge_s".into(), "".into(), false, 0),
-        I32GeU => ("i32.ge_u".into(), "".into(), false, 0),
-        I64Eqz => ("i64.eqz".into(), "".into(), false, 0),
-        I64Eq => ("i64.eq".into(), "".into(), false, 0),
-        I64Ne => ("i64.ne".into(), "".into(), false, 0),
-        I64LtS => ("i64.lt_s".into(), "".into(), false, 0),
-        I64LtU => ("i64.lt_u".into(), "".into(), false, 0),
-        I64GtS => ("i64.gt_s".into(), "".into(), false, 0),
-        I64GtU => ("i64.gt_u".into(), "".into(), false, 0),
-        I64LeS => ("i64.le_s".into(), "".into(), false, 0),
-        I64LeU => ("i64.le_u".into(), "".into(), false, 0),
-        I64GeS => ("i64.ge_s".into(), "".into(), false, 0),
-        I64GeU => ("i64.ge_u".into(), "".into(), false, 0),
-        F32Eq => ("f32.eq".into(), "".into(), false, 0),
-        F32Ne => ("f32.ne".into(), "".into(), false, 0),
-        F32Lt => ("f32.lt".into(), "".into(), false, 0),
-        F32Gt => ("f32.gt".into(), "".into(), false, 0),
-        F32Le => ("f32.le".into(), "".into(), false, 0),
-        F32Ge => ("f32.ge".into(), "".into(), false, 0),
-        F64Eq => ("f64.eq".into(), "".into(), false, 0),
-        F64Ne => ("f64.ne".into(), "".into(), false, 0),
-        F64Lt => ("f64.lt".into(), "".into(), false, 0),
-        F64Gt => ("f64.gt".into(), "".into(), false, 0),
-        F64Le => ("f64.le".into(), "".into(), false, 0),
-        F64Ge => ("f64.ge".into(), "".into(), false, 0),
-        
-        // Arithmetic
-        I32Clz => ("i32.clz".into(), "".into(), false, 0),
-        I32Ctz => ("i32.ctz".into(), "".into(), false, 0),
-        I32Popcnt => ("i32.popcnt".into(), "".into(), false, 0),
-        I32Add => ("i32.add".into(), "".into(), false, 0),
-        I32Sub => ("i32.sub".into(), "".into(), false, 0),
-        I32Mul => ("i32.mul".into(), "".into(), false, 0),
-        I32DivS => ("i32.div_s".into(), "".into(), false, 0),
-        I32DivU => ("i32.div_u".into(), "".into(), false, 0),
-        I32RemS => ("i32.rem_s".into(), "".into(), false, 0),
-        I32RemU => ("i32.rem_u".into(), "".into(), false, 0),
-        I32And => ("i32.and".into(), "".into(), false, 0),
-        I32Or => ("i32.or".into(), "".into(), false, 0),
-        I32Xor => ("i32.xor".into(), "".into(), false, 0),
-        I32Shl => ("i32.shl".into(), "".into(), false, 0),
-        I32ShrS => ("i32.shr_s".into(), "".into(), false, 0),
-        I32ShrU => ("i32.shr_u".into(), "".into(), false, 0),
-        I32Rotl => ("i32.rotl".into(), "".into(), false, 0),
-        I32Rotr => ("i32.rotr".into(), "".into(), false, 0),
-        I64Clz => ("i64.clz".into(), "".into(), false, 0),
-        I64Ctz => ("i64.ctz".into(), "".into(), false, 0),
-        I64Popcnt => ("i64.popcnt".into(), "".into(), false, 0),
-        I64Add => ("i64.add".into(), "".into(), false, 0),
-        I64Sub => ("i64.sub".into(), "".into(), false, 0),
-        I64Mul => ("i64.mul".into(), "".into(), false, 0),
-        I64DivS => ("i64.div_s".into(), "".into(), false, 0),
-        I64DivU => ("i64.div_u".into(), "".into(), false, 0),
-        I64RemS => ("i64.rem_s".into(), "".into(), false, 0),
-        I64RemU => ("i64.rem_u".into(), "".into(), false, 0),
-        I64And => ("i64.and".into(), "".into(), false, 0),
-        I64Or => ("i64.or".into(), "".into(), false, 0),
-        I64Xor => ("i64.xor".into(), "".into(), false, 0),
-        I64Shl => ("i64.shl".into(), "".into(), false, 0),
-        I64ShrS => ("i64.shr_s".into(), "".into(), false, 0),
-        I64ShrU => ("i64.shr_u".into(), "".into(), false, 0),
-        I64Rotl => ("i64.rotl".into(), "".into(), false, 0),
-        I64Rotr => ("i64.rotr".into(), "".into(), false, 0),
-        F32Abs => ("f32.abs".into(), "".into(), false, 0),
-        F32Neg => ("f32.neg".into(), "".into(), false, 0),
-        F32Ceil => ("f32.ceil".into(), "".into(), false, 0),
-        F32Floor => ("f32.floor".into(), "".into(), false, 0),
-        F32Trunc => ("f32.trunc".into(), "".into(), false, 0),
-        F32Nearest => ("f32.nearest".into(), "".into(), false, 0),
-        F32Sqrt => ("f32.sqrt".into(), "".into(), false, 0),
-        F32Add => ("f32.add".into(), "".into(), false, 0),
-        F32Sub => ("f32.sub".into(), "".into(), false, 0),
-        F32Mul => ("f32.mul".into(), "".into(), false, 0),
-        F32Div => ("f32.div".into(), "".into(), false, 0),
-        F32Min => ("f32.min".into(), "".into(), false, 0),
-        F32Max => ("f32.max".into(), "".into(), false, 0),
-        F32Copysign => ("f32.copysign".into(), "".into(), false, 0),
-        F64Abs => ("f64.abs".into(), "".into(), false, 0),
-        F64Neg => ("f64.neg".into(), "".into(), false, 0),
-        F64Ceil => ("f64.ceil".into(), "".into(), false, 0),
-        F64Floor => ("f64.floor".into(), "".into(), false, 0),
-        F64Trunc => ("f64.trunc".into(), "".into(), false, 0),
-        F64Nearest => ("f64.nearest".into(), "".into(), false, 0),
-        F64Sqrt => ("f64.sqrt".into(), "".into(), false, 0),
-        F64Add => ("f64.add".into(), "".into(), false, 0),
-        F64Sub => ("f64.sub".into(), "".into(), false, 0),
-        F64Mul => ("f64.mul".into(), "".into(), false, 0),
-        F64Div => ("f64.div".into(), "".into(), false, 0),
-        F64Min => ("f64.min".into(), "".into(), false, 0),
-        F64Max => ("f64.max".into(), "".into(), false, 0),
-        F64Copysign => ("f64.copysign".into(), "".into(), false, 0),
-        
-        // Conversions
-        I32WrapI64 => ("i32.wrap_i64".into(), "".into(), false, 0),
-        I32TruncF32S => ("i32.trunc_f32_s".into(), "".into(), false, 0),
-        I32TruncF32U => ("i32.trunc_f32_u".into(), "".into(), false, 0),
-        I32TruncF64S => ("i32.trunc_f64_s".into(), "".into(), false, 0),
-        I32TruncF64U => ("i32.trunc_f64_u".into(), "".into(), false, 0),
-        I64ExtendI32S => ("i64.extend_i32_s".into(), "".into(), false, 0),
-        I64ExtendI32U => ("i64.extend_i32_u".into(), "".into(), false, 0),
-        I64TruncF32S => ("i64.trunc_f32_s".into(), "".into(), false, 0),
-        I64TruncF32U => ("i64.trunc_f32_u".into(), "".into(), false, 0),
-        I64TruncF64S => ("i64.trunc_f64_s".into(), "".into(), false, 0),
-        I64TruncF64U => ("i64.trunc_f64_u".into(), "".into(), false, 0),
-        F32ConvertI32S => ("f32.convert_i32_s".into(), "".into(), false, 0),
-        F32ConvertI32U => ("f32.convert_i32_u".into(), "".into(), false, 0),
-        F32ConvertI64S => ("f32.convert_i64_s".into(), "".into(), false, 0),
-        F32ConvertI64U => ("f32.convert_i64_u".into(), "".into(), false, 0),
-        F32DemoteF64 => ("f32.demote_f64".into(), "".into(), false, 0),
-        F64ConvertI32S => ("f64.convert_i32_s".into(), "".into(), false, 0),
-        F64ConvertI32U => ("f64.convert_i32_u".into(), "".into(), false, 0),
-        F64ConvertI64S => ("f64.convert_i64_s".into(), "".into(), false, 0),
-        F64ConvertI64U => ("f64.convert_i64_u".into(), "".into(), false, 0),
-        F64PromoteF32 => ("f64.promote_f32".into(), "".into(), false, 0),
-        I32ReinterpretF32 => ("i32.reinterpret_f32".into(), "".into(), false, 0),
-        I64ReinterpretF64 => ("i64.reinterpret_f64".into(), "".into(), false, 0),
-        F32ReinterpretI32 => ("f32.reinterpret_i32".into(), "".into(), false, 0),
-        F64ReinterpretI64 => ("f64.reinterpret_i64".into(), "".into(), false, 0),
-        
-        // Sign extension
-        I32Extend8S => ("i32.extend8_s".into(), "".into(), false, 0),
-        I32Extend16S => ("i32.extend16_s".into(), "".into(), false, 0),
-        I64Extend8S => ("i64.extend8_s".into(), "".into(), false, 0),
-        I64Extend16S => ("i64.extend16_s".into(), "".into(), false, 0),
-        I64Extend32S => ("i64.extend32_s".into(), "".into(), false, 0),
-        
-        // Reference types
-        RefNull { hty } => ("ref.null".into(), format!("{:?}", hty), false, 0),
-        RefIsNull => ("ref.is_null".into(), "".into(), false, 0),
-        RefFunc { function_index } => ("ref.func".into(), format!("{}", function_index), false, 0),
-        
-        // Catch-all for other instructions
-        _ => ("unknown".into(), format!("{:?}", op), false, 0),
-    }
-}
-
-/// Analyze WASM binary using wasmparser for structured disassembly
+/// Analyze WASM binary using wasmparser for structured disassembly. Names
+/// come from the name section; source lines from `source_map` (JSON or a
+/// path) or the module's DWARF.
 #[tauri::command]
 async fn analyze_wasm_binary(
     binary_data: Vec<u8>,
     _base_address: u64,
+    source_map: Option<String>,
 ) -> Result<WasmModuleAnalysis, String> {
+    let debug_info = wasm_debug_info::WasmDebugInfo::parse(&binary_data, source_map.as_deref());
     let parser = Parser::new(0);
     let mut functions: Vec<WasmFunctionInfo> = Vec::new();
     let mut import_count = 0u32;
@@ -2700,7 +2550,7 @@ async fn analyze_wasm_binary(
     let mut type_section_types: Vec<(u32, u32)> = Vec::new(); // (params, results)
     let mut function_type_indices: Vec<u32> = Vec::new();
     let mut func_index = 0u32;
-    
+
     for payload in parser.parse_all(&binary_data) {
         match payload {
             Ok(Payload::TypeSection(reader)) => {
@@ -2748,7 +2598,7 @@ async fn analyze_wasm_binary(
             Ok(Payload::CodeSectionEntry(body)) => {
                 let code_offset = body.range().start as u32;
                 let code_size = body.range().len() as u32;
-                
+
                 // Get type info
                 let local_func_idx = (func_index - import_count) as usize;
                 let (param_count, result_count) = if local_func_idx < function_type_indices.len() {
@@ -2761,7 +2611,7 @@ async fn analyze_wasm_binary(
                 } else {
                     (0, 0)
                 };
-                
+
                 // Count locals
                 let mut local_count = 0u32;
                 if let Ok(locals_reader) = body.get_locals_reader() {
@@ -2771,13 +2621,15 @@ async fn analyze_wasm_binary(
                         }
                     }
                 }
-                
+
                 // Parse instructions
-                let mut instructions = Vec::new();
+                let locals = debug_info.local_names.get(&func_index);
+                let mut instructions: Vec<wasm_disasm::WasmInstructionInfo> = Vec::new();
+                let mut last_source = None;
                 if let Ok(ops_reader) = body.get_operators_reader() {
                     let mut reader = ops_reader;
                     let base_offset = body.range().start;
-                    
+
                     while !reader.eof() {
                         let pos_before = reader.original_position();
                         match reader.read() {
@@ -2785,53 +2637,54 @@ async fn analyze_wasm_binary(
                                 let pos_after = reader.original_position();
                                 let _instr_size = pos_after - pos_before;
                                 let instr_offset = pos_before - base_offset;
-                                
+
                                 // Get raw bytes
                                 let bytes = if pos_before < binary_data.len() && pos_after <= binary_data.len() {
                                     binary_data[pos_before..pos_after].to_vec()
                                 } else {
                                     vec![]
                                 };
-                                
-                                let (mnemonic, operands, is_branch, depth_change) = format_wasm_operator(&op);
-                                
-                                instructions.push(WasmInstructionInfo {
+
+                                let (mnemonic, operands, is_branch, depth_change) = wasm_disasm::describe(&op);
+                                let operands = wasm_disasm::name_operands(&op, operands, locals, &debug_info);
+                                let location = debug_info.location(pos_before as u32);
+                                let source = if location != last_source { location.clone() } else { None };
+                                last_source = location;
+
+                                instructions.push(wasm_disasm::WasmInstructionInfo {
                                     offset: instr_offset as u32,
                                     bytes,
                                     mnemonic,
                                     operands,
                                     is_branch,
                                     depth_change,
+                                    source,
                                 });
                             }
                             Err(_) => break,
                         }
                     }
                 }
-                
+
                 functions.push(WasmFunctionInfo {
                     index: func_index,
-                    name: None, // Will be filled from name section if available
+                    name: debug_info.function_names.get(&func_index).cloned(),
                     code_offset,
                     code_size,
                     local_count,
                     param_count,
                     result_count,
+                    local_names: locals.cloned().unwrap_or_default(),
+                    source: instructions.iter().find_map(|insn| insn.source.clone()),
                     instructions,
                 });
-                
+
                 func_index += 1;
             }
-            Ok(Payload::CustomSection(reader)) => {
-                if reader.name() == "name" {
-                    // Parse name section for function names
-                    // This is optional and may not be present
-                }
-            }
             _ => {}
         }
     }
-    
+
     Ok(WasmModuleAnalysis {
         file_path: String::new(),
         functions,
@@ -2840,6 +2693,9 @@ async fn analyze_wasm_binary(
         memory_count,
         table_count,
         global_count,
+        global_names: debug_info.global_names,
+        source_mapping_url: debug_info.source_mapping_url,
+        has_dwarf: debug_info.has_dwarf,
     })
 }
 
@@ -2855,95 +2711,37 @@ async fn disassemble_wasm_function(
     if function_offset as usize >= binary_data.len() {
         return Err("Function offset out of bounds".to_string());
     }
-    
+
     let end_offset = std::cmp::min(
         function_offset as usize + function_size as usize,
         binary_data.len()
     );
-    
+
     let function_bytes = &binary_data[function_offset as usize..end_offset];
-    
-    // Try to parse as function body
-    let mut lines = Vec::new();
-    let mut offset = 0usize;
-    let mut block_depth = 0i32;
-    
-    // Skip locals encoding if present (for raw function bodies)
-    // First, read local count as LEB128
-    let (local_groups, consumed) = read_uleb128(function_bytes, 0);
-    offset += consumed;
-    
-    // Skip local declarations
+
+    // Skip the local declarations that precede the operators
+    let mut reader = wasmparser::BinaryReader::new(function_bytes, 0);
+    let local_groups = reader.read_var_u32().map_err(|e| format!("Invalid function body: {}", e))?;
     for _ in 0..local_groups {
-        let (_count, c1) = read_uleb128(function_bytes, offset);
-        offset += c1;
-        if offset < function_bytes.len() {
-            offset += 1; // Skip type byte
-        }
+        reader.read_var_u32().map_err(|e| format!("Invalid function body: {}", e))?;
+        reader.read::<wasmparser::ValType>().map_err(|e| format!("Invalid function body: {}", e))?;
     }
-    
-    // Now decode instructions
-    while offset < function_bytes.len() && lines.len() < 500 {
-        let (mnemonic, consumed, operand, is_branch, _) = 
-            decode_wasm_instruction(function_bytes, offset, base_address);
-        
-        if consumed == 0 {
-            break;
-        }
-        
-        // Track block depth for indentation
-        let indent = if mnemonic == "end" || mnemonic == "else" {
-            block_depth = (block_depth - 1).max(0);
-            "  ".repeat(block_depth as usize)
-        } else {
-            let indent = "  ".repeat(block_depth as usize);
-            if mnemonic == "block" || mnemonic == "loop" || mnemonic == "if" {
-                block_depth += 1;
-            }
-            indent
-        };
-        
-        let current_address = base_address + (function_offset as u64) + (offset as u64);
-        let end_off = std::cmp::min(offset + consumed, function_bytes.len());
-        
-        let bytes_display = function_bytes[offset..end_off]
-            .iter()
-            .take(8)
-            .map(|b| format!("{:02x}", b))
-            .collect::<Vec<_>>()
-            .join(" ");
-        let bytes_suffix = if consumed > 8 { ".." } else { "" };
-        let branch_hint = if is_branch { " <-" } else { "" };
-        
-        // Only add space before operand if operand is not empty
-        let instruction_text = if operand.is_empty() {
-            format!("{}{}{}", indent, mnemonic, branch_hint)
-        } else {
-            format!("{}{} {}{}", indent, mnemonic, operand, branch_hint)
-        };
-        
-        let line = format!(
-            "0x{:08x}|{}{}|{}",
-            current_address,
-            bytes_display,
-            bytes_suffix,
-            instruction_text
-        );
-        lines.push(line);
-        
-        offset += consumed;
-        
-        // Stop at function end
-        if mnemonic == "end" && block_depth == 0 {
-            break;
-        }
-    }
-    
+    let body_start = reader.original_position();
+    let body = &function_bytes[body_start..];
+
+    let debug_info = wasm_debug_info::WasmDebugInfo::names(&binary_data);
+    let instructions: Vec<_> = wasm_disasm::decode(body, 500, true)
+        .iter()
+        .map(|decoded| wasm_disasm::instruction_info(body, decoded, Some((&debug_info, None))))
+        .collect();
+    let lines = wasm_disasm::format_lines(&instructions, base_address + function_offset as u64 + body_start as u64, true);
+
     Ok(DisassembleResponse {
         success: true,
         disassembly: Some(lines.join("\n")),
         instructions_count: lines.len(),
         error: None,
+        timing: None,
     })
 }
 
@@ -2952,7 +2750,7 @@ async fn disassemble_wasm_function(
 async fn open_wasm_modules_directory() -> Result<String, String> {
     let wasm_dir = get_wasm_modules_dir();
     std::fs::create_dir_all(&wasm_dir).map_err(|e| format!("Failed to create directory: {}", e))?;
-    
+
     #[cfg(target_os = "windows")]
     {
         Command::new("explorer")
@@ -2960,7 +2758,7 @@ async fn open_wasm_modules_directory() -> Result<String, String> {
             .spawn()
             .map_err(|e| format!("Failed to open explorer: {}", e))?;
     }
-    
+
     #[cfg(target_os = "macos")]
     {
         Command::new("open")
@@ -2968,7 +2766,7 @@ async fn open_wasm_modules_directory() -> Result<String, String> {
             .spawn()
             .map_err(|e| format!("Failed to open Finder: {}", e))?;
     }
-    
+
     #[cfg(target_os = "linux")]
     {
         Command::new("xdg-open")
@@ -2976,15 +2774,33 @@ async fn open_wasm_modules_directory() -> Result<String, String> {
             .spawn()
             .map_err(|e| format!("Failed to open file manager: {}", e))?;
     }
-    
+
     Ok(wasm_dir.to_string_lossy().to_string())
 }
 
 #[tauri::command]
 async fn disassemble_memory_direct(
+    state: tauri::State<'_, state::AppStateType>,
+    memory_data: Vec<u8>,
+    address: u64,
+    architecture: String,
+    symbolicate: Option<bool>,
+) -> Result<DisassembleResponse, String> {
+    let symbolizer = if symbolicate.unwrap_or(false) {
+        Some(symbolizer::Symbolizer::load(state.inner()).await?)
+    } else {
+        None
+    };
+    disassemble_bytes(memory_data, address, architecture, symbolizer.as_ref())
+}
+
+/// Disassemble a byte buffer into pipe-delimited lines, emitting "???" for
+/// undecodable bytes
+fn disassemble_bytes(
     memory_data: Vec<u8>,
     address: u64,
     architecture: String,
+    symbolizer: Option<&symbolizer::Symbolizer>,
 ) -> Result<DisassembleResponse, String> {
     // Determine instruction size for the architecture (used for fallback on invalid bytes)
     let instruction_size: usize = match architecture.as_str() {
@@ -3022,7 +2838,7 @@ async fn disassemble_memory_direct(
                 .arm64()
                 .mode(arch::arm64::ArchMode::Arm)
                 .detail(true);
-            
+
             // Enable extra details for ARM64
             cs_builder.build()
         },
@@ -3036,7 +2852,7 @@ async fn disassemble_memory_direct(
                 .build()
         },
     };
-    
+
     let cs = match cs {
         Ok(cs) => cs,
         Err(e) => {
@@ -3045,6 +2861,7 @@ async fn disassemble_memory_direct(
                 disassembly: None,
                 instructions_count: 0,
                 error: Some(format!("Failed to create disassembler: {}", e)),
+                timing: None,
             });
         }
     };
@@ -3069,7 +2886,7 @@ async fn disassemble_memory_direct(
                     .join(" ");
                 let mnemonic = insn.mnemonic().unwrap_or("???");
                 let op_str = insn.op_str().unwrap_or("");
-                
+
                 // Enhanced formatting for ARM64
                 let formatted_operands = if !op_str.is_empty() {
                     match architecture.as_str() {
@@ -3082,11 +2899,14 @@ async fn disassemble_memory_direct(
                 } else {
                     String::new()
                 };
-                
-                // Format: address|bytes|mnemonic operands
-                let line = format!("{}|{}|{} {}", address_str, bytes, mnemonic, formatted_operands);
+
+                // Format: address|bytes|mnemonic operands[ ; symbol]
+                let mut line = format!("{}|{}|{} {}", address_str, bytes, mnemonic, formatted_operands);
+                if let Some(symbolizer) = symbolizer {
+                    append_branch_symbol(&mut line, symbolizer, &cs, insn, &architecture);
+                }
                 disassembly_lines.push(line);
-                
+
                 // Move offset by the instruction size
                 offset += insn.bytes().len();
             }
@@ -3098,12 +2918,12 @@ async fn disassemble_memory_direct(
                     .map(|b| format!("{:02x}", b))
                     .collect::<Vec<_>>()
                     .join(" ");
-                
+
                 let address_str = format!("0x{:x}", current_address);
                 // Show as "???" with .byte pseudo-instruction style, or just "???" for cleaner display
                 let line = format!("{}|{}|??? ", address_str, bytes_str);
                 disassembly_lines.push(line);
-                
+
                 // Move by instruction_size for fixed-width architectures, or 1 byte for variable-width
                 offset += bytes_to_show;
             }
@@ -3117,6 +2937,7 @@ async fn disassemble_memory_direct(
             disassembly: None,
             instructions_count: 0,
             error: Some("No data to disassemble".to_string()),
+            timing: None,
         })
     } else {
         Ok(DisassembleResponse {
@@ -3124,21 +2945,41 @@ async fn disassemble_memory_direct(
             disassembly: Some(disassembly_lines.join("\n")),
             instructions_count: disassembly_lines.len(),
             error: None,
+            timing: None,
         })
     }
 }
 
 #[tauri::command]
-async fn disassemble_memory(request: DisassembleRequest) -> Result<DisassembleResponse, String> {
+async fn disassemble_memory(
+    state: tauri::State<'_, state::AppStateType>,
+    request: DisassembleRequest,
+) -> Result<DisassembleResponse, String> {
+    let symbolizer = if request.symbolicate {
+        Some(symbolizer::Symbolizer::load(state.inner()).await?)
+    } else {
+        None
+    };
+
+
+    // Don't read past the end of the region; a partial page would fail the whole read
+    let size = match memory_regions::readable_span(request.address) {
+        Some(span) if span < request.size as u64 => span as usize,
+        _ => request.size,
+    };
+
     // First, read memory from the server
-    let memory_response = read_memory(request.address, request.size).await?;
-    
+    let mut stopwatch = latency::Stopwatch::start("disassemble_memory");
+    let memory_response = disasm_cache::read_code(state.inner(), request.address, size).await?;
+    stopwatch.mark("read");
+
     if !memory_response.success {
         return Ok(DisassembleResponse {
             success: false,
             disassembly: None,
             instructions_count: 0,
             error: memory_response.error,
+            timing: None,
         });
     }
 
@@ -3150,6 +2991,7 @@ async fn disassemble_memory(request: DisassembleRequest) -> Result<DisassembleRe
                 disassembly: None,
                 instructions_count: 0,
                 error: Some("No memory data received".to_string()),
+                timing: None,
             });
         }
     };
@@ -3177,7 +3019,7 @@ async fn disassemble_memory(request: DisassembleRequest) -> Result<DisassembleRe
                 .arm64()
                 .mode(arch::arm64::ArchMode::Arm)
                 .detail(true);
-            
+
             // Enable extra details for ARM64
             cs_builder.build()
         },
@@ -3191,7 +3033,7 @@ async fn disassemble_memory(request: DisassembleRequest) -> Result<DisassembleRe
                 .build()
         },
     };
-    
+
     let cs = match cs
     {
         Ok(cs) => cs,
@@ -3201,16 +3043,19 @@ async fn disassemble_memory(request: DisassembleRequest) -> Result<DisassembleRe
                 disassembly: None,
                 instructions_count: 0,
                 error: Some(format!("Failed to create disassembler: {}", e)),
+                timing: None,
             });
         }
     };
 
     // Disassemble the memory
     let instructions_result = cs.disasm_all(&memory_data, request.address);
+    stopwatch.mark("disassemble");
+    stopwatch.set_bytes(memory_data.len());
     match instructions_result {
         Ok(instructions) => {
             let mut disassembly_lines = Vec::new();
-            
+
             for insn in instructions.iter() {
                 let address = format!("0x{:x}", insn.address());
                 let bytes = insn.bytes().iter()
@@ -3219,7 +3064,7 @@ async fn disassemble_memory(request: DisassembleRequest) -> Result<DisassembleRe
                     .join(" ");
                 let mnemonic = insn.mnemonic().unwrap_or("???");
                 let op_str = insn.op_str().unwrap_or("");
-                
+
                 // Enhanced formatting for ARM64
                 let formatted_operands = if !op_str.is_empty() {
                     match request.architecture.as_str() {
@@ -3232,58 +3077,57 @@ async fn disassemble_memory(request: DisassembleRequest) -> Result<DisassembleRe
                 } else {
                     String::new()
                 };
-                
-                // Format: address|bytes|mnemonic operands
-                let line = format!("{}|{}|{} {}", address, bytes, mnemonic, formatted_operands);
+
+                // Format: address|bytes|mnemonic operands[ ; symbol]
+                let mut line = format!("{}|{}|{} {}", address, bytes, mnemonic, formatted_operands);
+                if let Some(symbolizer) = symbolizer.as_ref() {
+                    append_branch_symbol(&mut line, symbolizer, &cs, insn, &request.architecture);
+                }
                 disassembly_lines.push(line);
             }
+            stopwatch.mark(if symbolizer.is_some() { "format_symbolicate" } else { "format" });
 
-            Ok(DisassembleResponse {
+            let mut response = DisassembleResponse {
                 success: true,
                 disassembly: Some(disassembly_lines.join("\n")),
                 instructions_count: disassembly_lines.len(),
                 error: None,
-            })
+                timing: None,
+            };
+            response.timing = stopwatch.finish(&response);
+            Ok(response)
         }
         Err(e) => Ok(DisassembleResponse {
             success: false,
             disassembly: None,
             instructions_count: 0,
             error: Some(format!("Disassembly failed: {}", e)),
+            timing: None,
         })
     }
 }
 
+/// Demangle one symbol name (C++ or Rust), or return it unchanged
+fn demangle_name(name: &str) -> String {
+    // Try C++ demangling first
+    if let Ok(symbol) = CppSymbol::new(name) {
+        if let Ok(demangled) = symbol.demangle(&cpp_demangle::DemangleOptions::default()) {
+            return demangled;
+        }
+    }
+    // Try Rust demangling; Display returns the original if no demangling possible
+    rustc_demangle(name).to_string()
+}
+
 /// Demangle a list of symbol names (C++ and Rust)
 #[tauri::command]
 fn demangle_symbols(names: Vec<String>) -> Vec<String> {
-    names
-        .into_iter()
-        .map(|name| {
-            // Try C++ demangling first
-            if let Ok(symbol) = CppSymbol::new(&name) {
-                if let Ok(demangled) = symbol.demangle(&cpp_demangle::DemangleOptions::default()) {
-                    return demangled;
-                }
-            }
-            // Try Rust demangling
-            let demangled = rustc_demangle(&name).to_string();
-            if demangled != name {
-                return demangled;
-            }
-            // Return original if no demangling possible
-            name
-        })
-        .collect()
+    names.iter().map(|name| demangle_name(name)).collect()
 }
 
 /// Get the Ghidra projects directory for storing analysis data
 fn get_ghidra_projects_dir() -> PathBuf {
-    let data_dir = dirs::data_local_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("DynaDbg")
-        .join("ghidra_projects");
-    data_dir
+    decompile_cache::projects_dir()
 }
 
 /// Download a library file from the server and save it locally
@@ -3293,33 +3137,32 @@ async fn download_library_file(library_path: String, project_name: Option<String
         let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
         (config.host.clone(), config.port, config.auth_token.clone())
     };
-    
+
     if host.is_empty() {
         return Err("No server connection configured".to_string());
     }
 
-    let client = reqwest::Client::new();
+    let client = server_connection::client()?;
     let encoded_path = urlencoding::encode(&library_path);
-    let url = format!("http://{}:{}/api/utils/file?path={}", host, port, encoded_path);
-    
+    let url = format!("{}/api/utils/file?path={}", server_connection::base_url(&host, port), encoded_path);
+
     let mut request_builder = client.get(&url);
     if let Some(token) = auth_token {
         request_builder = request_builder.header("Authorization", format!("Bearer {}", token));
     }
-    
-    let response = request_builder
-        .send()
+
+    let response = server_connection::send(request_builder.timeout(server_connection::TRANSFER_TIMEOUT))
         .await
         .map_err(|e| format!("Failed to fetch library: {}", e))?;
-    
+
     if !response.status().is_success() {
         return Err(format!("Server returned error: {}", response.status()));
     }
-    
+
     let bytes = response.bytes()
         .await
         .map_err(|e| format!("Failed to read response: {}", e))?;
-    
+
     // Create local directory for libraries with optional project name subdirectory
     let ghidra_dir = get_ghidra_projects_dir();
     let libs_dir = if let Some(ref proj_name) = project_name {
@@ -3330,23 +3173,23 @@ async fn download_library_file(library_path: String, project_name: Option<String
     fs::create_dir_all(&libs_dir)
         .await
         .map_err(|e| format!("Failed to create directory: {}", e))?;
-    
+
     // Extract filename from path
     let filename = PathBuf::from(&library_path)
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| "unknown_library".to_string());
-    
+
     let local_path = libs_dir.join(&filename);
-    
+
     let mut file = fs::File::create(&local_path)
         .await
         .map_err(|e| format!("Failed to create file: {}", e))?;
-    
+
     file.write_all(&bytes)
         .await
         .map_err(|e| format!("Failed to write file: {}", e))?;
-    
+
     Ok(local_path.to_string_lossy().to_string())
 }
 
@@ -3357,50 +3200,49 @@ async fn download_server_file(remote_path: String) -> Result<String, String> {
         let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
         (config.host.clone(), config.port, config.auth_token.clone())
     };
-    
+
     if host.is_empty() {
         return Err("No server connection configured".to_string());
     }
 
-    let client = reqwest::Client::new();
+    let client = server_connection::client()?;
     let encoded_path = urlencoding::encode(&remote_path);
-    let url = format!("http://{}:{}/api/utils/file?path={}", host, port, encoded_path);
-    
+    let url = format!("{}/api/utils/file?path={}", server_connection::base_url(&host, port), encoded_path);
+
     let mut request_builder = client.get(&url);
     if let Some(token) = auth_token {
         request_builder = request_builder.header("Authorization", format!("Bearer {}", token));
     }
-    
-    let response = request_builder
-        .send()
+
+    let response = server_connection::send(request_builder.timeout(server_connection::TRANSFER_TIMEOUT))
         .await
         .map_err(|e| format!("Failed to fetch file: {}", e))?;
-    
+
     if !response.status().is_success() {
         return Err(format!("Server returned error: {}", response.status()));
     }
-    
+
     let bytes = response.bytes()
         .await
         .map_err(|e| format!("Failed to read response: {}", e))?;
-    
+
     // Create downloads directory in app data
     let downloads_dir = dirs::download_dir()
         .ok_or_else(|| "Could not find downloads directory".to_string())?
         .join("DynaDbg");
-    
+
     fs::create_dir_all(&downloads_dir)
         .await
         .map_err(|e| format!("Failed to create directory: {}", e))?;
-    
+
     // Extract filename from path
     let filename = PathBuf::from(&remote_path)
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| "downloaded_file".to_string());
-    
+
     let local_path = downloads_dir.join(&filename);
-    
+
     // Handle duplicate filenames
     let final_path = if local_path.exists() {
         let stem = local_path.file_stem()
@@ -3417,15 +3259,15 @@ async fn download_server_file(remote_path: String) -> Result<String, String> {
     } else {
         local_path
     };
-    
+
     let mut file = fs::File::create(&final_path)
         .await
         .map_err(|e| format!("Failed to create file: {}", e))?;
-    
+
     file.write_all(&bytes)
         .await
         .map_err(|e| format!("Failed to write file: {}", e))?;
-    
+
     Ok(final_path.to_string_lossy().to_string())
 }
 
@@ -3436,7 +3278,7 @@ async fn upload_file_to_server(local_path: String, remote_path: String) -> Resul
         let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
         (config.host.clone(), config.port)
     };
-    
+
     if host.is_empty() {
         return Err("No server connection configured".to_string());
     }
@@ -3451,36 +3293,38 @@ async fn upload_file_to_server(local_path: String, remote_path: String) -> Resul
         config.auth_token.clone()
     };
 
-    let client = reqwest::Client::new();
+    let client = server_connection::client()?;
     let encoded_path = urlencoding::encode(&remote_path);
-    let url = format!("http://{}:{}/api/utils/file?path={}", host, port, encoded_path);
-    
+    let url = format!("{}/api/utils/file?path={}", server_connection::base_url(&host, port), encoded_path);
+
     let mut request_builder = client.post(&url)
         .body(file_contents);
-    
+
     if let Some(token) = auth_token {
         request_builder = request_builder.header("Authorization", format!("Bearer {}", token));
     }
-    
-    let response = request_builder
-        .send()
+
+    let response = server_connection::send(request_builder.timeout(server_connection::TRANSFER_TIMEOUT))
         .await
         .map_err(|e| format!("Failed to upload file: {}", e))?;
-    
+
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
         return Err(format!("Server returned error: {}", error_text));
     }
-    
+
     Ok(remote_path)
 }
 
-/// Analyze a library file with Ghidra headless
+/// Analyze a library file with Ghidra headless, as far as the module's
+/// analysis policy allows (`force` overrides a "never" policy)
 #[tauri::command]
 async fn analyze_with_ghidra(
+    state: tauri::State<'_, state::AppStateType>,
     local_library_path: String,
     ghidra_path: String,
     project_name: Option<String>,
+    force: Option<bool>,
 ) -> Result<GhidraAnalysisStatus, String> {
     let library_path = PathBuf::from(&local_library_path);
     if !library_path.exists() {
@@ -3491,12 +3335,27 @@ async fn analyze_with_ghidra(
             error: Some("Library file not found".to_string()),
         });
     }
-    
+
+    let target_os = state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.target_os.clone()))
+        .unwrap_or_default();
+    let module_name = analysis_policy::file_name(&local_library_path).to_string();
+    let policy = analysis_policy::resolve(&target_os, &module_name).await;
+    if policy.mode == analysis_policy::AnalysisMode::Never && !force.unwrap_or(false) {
+        return Ok(GhidraAnalysisStatus {
+            library_path: local_library_path,
+            analyzed: false,
+            project_path: None,
+            error: Some(format!("Analysis of {} is disabled by module policy '{}'", module_name, policy.module_pattern)),
+        });
+    }
+
     let library_name = library_path
         .file_stem()
         .map(|s| s.to_string_lossy().to_string())
         .unwrap_or_else(|| "unknown".to_string());
-    
+
     // Create project directory with optional project name subdirectory
     let ghidra_dir = get_ghidra_projects_dir();
     let project_dir = if let Some(ref proj_name) = project_name {
@@ -3507,7 +3366,7 @@ async fn analyze_with_ghidra(
     fs::create_dir_all(&project_dir)
         .await
         .map_err(|e| format!("Failed to create project directory: {}", e))?;
-    
+
     // Build headless analyzer path
     let ghidra_base = PathBuf::from(&ghidra_path);
     let analyzer_path = if cfg!(windows) {
@@ -3515,7 +3374,7 @@ async fn analyze_with_ghidra(
     } else {
         ghidra_base.join("support").join("analyzeHeadless")
     };
-    
+
     if !analyzer_path.exists() {
         return Ok(GhidraAnalysisStatus {
             library_path: local_library_path,
@@ -3524,22 +3383,27 @@ async fn analyze_with_ghidra(
             error: Some(format!("Ghidra analyzeHeadless not found at: {}", analyzer_path.display())),
         });
     }
-    
-    // Run Ghidra headless analysis
-    let output = hide_console_window(&mut Command::new(&analyzer_path))
+
+    // Run Ghidra headless analysis (import only for symbol-only policies)
+    let mut command = Command::new(&analyzer_path);
+    hide_console_window(&mut command)
         .arg(project_dir.to_string_lossy().to_string())
         .arg(&library_name)
         .arg("-import")
         .arg(&local_library_path)
         .arg("-overwrite")
         .arg("-analysisTimeoutPerFile")
-        .arg("300")  // 5 minutes timeout
+        .arg("300");  // 5 minutes timeout
+    if policy.mode == analysis_policy::AnalysisMode::Symbols {
+        command.arg("-noanalysis");
+    }
+    let output = command
         .output()
         .map_err(|e| format!("Failed to run Ghidra: {}", e))?;
-    
+
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
-    
+
     if !output.status.success() {
         return Ok(GhidraAnalysisStatus {
             library_path: local_library_path,
@@ -3548,11 +3412,24 @@ async fn analyze_with_ghidra(
             error: Some(format!("Ghidra analysis failed: {}\n{}", stdout, stderr)),
         });
     }
-    
+
+    let project_path = project_dir.to_string_lossy().to_string();
+    // Entries derived from the previous analysis no longer apply
+    let invalidated = {
+        let (target_os, module_name) = (target_os.clone(), module_name.clone());
+        db::run(move |conn| cache_versions::invalidate(conn, &target_os, &module_name, "reanalysis")).await
+    };
+    if let Err(e) = invalidated {
+        eprintln!("Failed to invalidate caches of {}: {}", module_name, e);
+    }
+    if let Err(e) = analysis_policy::apply_post_analysis(&policy, &target_os, &module_name, &project_path, &ghidra_path).await {
+        eprintln!("Post-analysis steps for {} failed: {}", module_name, e);
+    }
+
     Ok(GhidraAnalysisStatus {
         library_path: local_library_path,
         analyzed: true,
-        project_path: Some(project_dir.to_string_lossy().to_string()),
+        project_path: Some(project_path),
         error: None,
     })
 }
@@ -3571,7 +3448,7 @@ async fn ghidra_decompile(
     } else {
         ghidra_base.join("support").join("analyzeHeadless")
     };
-    
+
     if !analyzer_path.exists() {
         return Ok(GhidraDecompileResult {
             success: false,
@@ -3581,14 +3458,15 @@ async fn ghidra_decompile(
             error: Some("Ghidra analyzeHeadless not found".to_string()),
             line_mapping: None,
             tokens: None,
+            timing: None,
         });
     }
-    
+
     // Create a temporary script to decompile the function
     let ghidra_dir = get_ghidra_projects_dir();
     let script_path = ghidra_dir.join("decompile_function.py");
     let output_path = ghidra_dir.join("decompile_output.txt");
-    
+
     // Ghidra Python script for decompilation with line-to-address mapping
     // The function_address is an offset from module base. We need to add Ghidra's image base.
     let script_content = format!(r#"#@runtime Jython
@@ -3602,12 +3480,12 @@ from java.util import ArrayList
 def get_line_address_mapping(clang_tokens, image_base):
     """Extract address mapping for each line from Clang tokens using flatten()"""
     line_addresses = {{}}
-    
+
     try:
         # Use flatten() to get all tokens as a flat list
         token_list = ArrayList()
         clang_tokens.flatten(token_list)
-        
+
         for token in token_list:
             try:
                 min_addr = token.getMinAddress()
@@ -3624,64 +3502,64 @@ def get_line_address_mapping(clang_tokens, image_base):
                 continue
     except Exception as e:
         print("DEBUG: Error in get_line_address_mapping: " + str(e))
-    
+
     return line_addresses
 
 def decompile_at_offset(offset_str):
     decompiler = DecompInterface()
     decompiler.openProgram(currentProgram)
-    
+
     # Get Ghidra's image base address
     image_base = currentProgram.getImageBase()
     print("DEBUG: Image base = " + str(image_base))
-    
+
     # Parse offset
     offset_str = offset_str.strip()
     if offset_str.startswith("0x"):
         offset_str = offset_str[2:]
-    
+
     try:
         offset = int(offset_str, 16)
     except:
         return "Error: Invalid offset format: " + offset_str
-    
+
     # Calculate actual address = image_base + offset
     addr = image_base.add(offset)
     print("DEBUG: Offset = 0x" + format(offset, 'x'))
     print("DEBUG: Calculated address = " + str(addr))
-    
+
     if addr is None:
         return "Error: Could not calculate address"
-    
+
     func = getFunctionContaining(addr)
     if func is None:
         # Try to get function exactly at address
         func = getFunctionAt(addr)
-    
+
     if func is None:
         return "Error: No function found at address " + str(addr) + " (offset 0x" + format(offset, 'x') + ")"
-    
+
     print("DEBUG: Found function: " + func.getName())
-    
+
     monitor = ConsoleTaskMonitor()
     results = decompiler.decompileFunction(func, 60, monitor)
-    
+
     if results and results.decompileCompleted():
         decomp = results.getDecompiledFunction()
         if decomp:
             code = decomp.getC()
-            
+
             # Get line-to-address mapping from ClangTokenGroup
             clang_tokens = results.getCCodeMarkup()
             line_mapping = {{}}
             if clang_tokens:
                 line_mapping = get_line_address_mapping(clang_tokens, image_base)
-            
+
             # Format line mapping as JSON-like string
             mapping_str = ";".join(["{{}}:0x{{:x}}".format(ln, addr) for ln, addr in sorted(line_mapping.items())])
-            
+
             return "FUNCTION_NAME:" + func.getName() + "\nLINE_MAPPING:" + mapping_str + "\n" + code
-    
+
     return "Error: Decompilation failed"
 
 # Get offset from script arguments
@@ -3692,18 +3570,18 @@ result = decompile_at_offset(offset)
 with open(r"{}", "w") as f:
     f.write(result)
 "#, function_address, output_path.to_string_lossy().replace("\\", "\\\\"));
-    
+
     fs::write(&script_path, &script_content)
         .await
         .map_err(|e| format!("Failed to write decompile script: {}", e))?;
-    
+
     // Clean library name (without extension)
     // Ghidra stores imported programs with file_stem (no extension) as the program name
     let clean_lib_name = PathBuf::from(&library_name)
         .file_stem()
         .map(|s| s.to_string_lossy().to_string())
         .unwrap_or(library_name.clone());
-    
+
     // Run Ghidra with the decompile script
     // Use clean_lib_name (without extension) for -process option
     // Ghidra stores imported programs without file extensions
@@ -3717,7 +3595,7 @@ with open(r"{}", "w") as f:
         .arg(script_path.to_string_lossy().to_string())
         .output()
         .map_err(|e| format!("Failed to run Ghidra: {}", e))?;
-    
+
     // Log Ghidra output for debugging
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -3734,9 +3612,10 @@ with open(r"{}", "w") as f:
             line_mapping: None,
             tokens: None,
             error: Some(format!("Ghidra process failed (exit code {:?}): \nStdout: {}\nStderr: {}", output.status.code(), stdout, stderr)),
+            timing: None,
         });
     }
-    
+
     // Read the output file
     let decompiled = match fs::read_to_string(&output_path).await {
         Ok(content) => content,
@@ -3756,14 +3635,15 @@ with open(r"{}", "w") as f:
                 line_mapping: None,
                 tokens: None,
                 error: Some(error_msg),
+                timing: None,
             });
         }
     };
-    
+
     // Clean up
     let _ = fs::remove_file(&script_path).await;
     let _ = fs::remove_file(&output_path).await;
-    
+
     // Parse result
     if decompiled.starts_with("Error:") {
         return Ok(GhidraDecompileResult {
@@ -3774,15 +3654,16 @@ with open(r"{}", "w") as f:
             line_mapping: None,
             tokens: None,
             error: Some(decompiled),
+            timing: None,
         });
     }
-    
+
     // Extract function name and line mapping from result
     let mut function_name = String::new();
     let mut line_mapping: std::collections::HashMap<String, String> = std::collections::HashMap::new();
     let mut code_lines: Vec<&str> = Vec::new();
     let mut in_code = false;
-    
+
     for line in decompiled.lines() {
         if line.starts_with("FUNCTION_NAME:") {
             function_name = line.replace("FUNCTION_NAME:", "");
@@ -3800,9 +3681,9 @@ with open(r"{}", "w") as f:
             code_lines.push(line);
         }
     }
-    
+
     let code = code_lines.join("\n");
-    
+
     if !output.status.success() && code.is_empty() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Ok(GhidraDecompileResult {
@@ -3813,15 +3694,16 @@ with open(r"{}", "w") as f:
             line_mapping: None,
             tokens: None,
             error: Some(format!("Ghidra decompilation process failed: {}", stderr)),
+            timing: None,
         });
     }
-    
+
     // NOTE: Do NOT format the code with clang-format because it changes line numbers
     // and breaks the line-to-address mapping from Ghidra.
     // If you want to enable formatting, you would need to also remap line numbers.
     // let formatted_code = format_cpp_code(&code).await.unwrap_or(code);
     let formatted_code = code;
-    
+
     Ok(GhidraDecompileResult {
         success: true,
         function_name: if function_name.is_empty() { None } else { Some(function_name) },
@@ -3830,6 +3712,7 @@ with open(r"{}", "w") as f:
         line_mapping: if line_mapping.is_empty() { None } else { Some(line_mapping) },
         tokens: None,
         error: None,
+        timing: None,
     })
 }
 
@@ -3849,6 +3732,7 @@ from ghidra.program.model.symbol import RefType
 from ghidra.util.task import ConsoleTaskMonitor
 from java.util import ArrayList
 import BaseHTTPServer
+import jarray
 import urlparse
 import json
 import threading
@@ -3893,26 +3777,26 @@ def get_line_address_mapping(clang_tokens, image_base):
 def get_token_info(clang_tokens, image_base, high_func):
     """Extract detailed token information from decompiled code"""
     from ghidra.app.decompiler import ClangFuncNameToken, ClangVariableToken, ClangTypeToken, ClangFieldToken
-    
+
     tokens_info = []
     try:
         token_list = ArrayList()
         clang_tokens.flatten(token_list)
-        
+
         for token in token_list:
             try:
                 token_text = token.toString()
                 if not token_text or len(token_text.strip()) == 0:
                     continue
-                
+
                 line_parent = token.getLineParent()
                 if line_parent is None:
                     continue
-                    
+
                 line_number = line_parent.getLineNumber()
                 if line_number is None or line_number <= 0:
                     continue
-                
+
                 # Get column position (character offset within the line)
                 col_start = 0
                 col_end = 0
@@ -3924,7 +3808,7 @@ def get_token_info(clang_tokens, image_base, high_func):
                         child = line_parent.Child(i)
                         if child:
                             siblings.append(child)
-                    
+
                     char_pos = 0
                     for sibling in siblings:
                         sib_text = sibling.toString() if sibling else ""
@@ -3935,7 +3819,7 @@ def get_token_info(clang_tokens, image_base, high_func):
                         char_pos += len(sib_text)
                 except:
                     pass
-                
+
                 token_info = {{
                     "text": token_text,
                     "line": line_number,
@@ -3943,7 +3827,7 @@ def get_token_info(clang_tokens, image_base, high_func):
                     "col_end": col_end,
                     "token_type": "unknown"
                 }}
-                
+
                 # Determine token type
                 if isinstance(token, ClangFuncNameToken):
                     token_info["token_type"] = "function"
@@ -3963,7 +3847,7 @@ def get_token_info(clang_tokens, image_base, high_func):
                                             token_info["target_name"] = called_func.getName()
                     except:
                         pass
-                        
+
                 elif isinstance(token, ClangVariableToken):
                     token_info["token_type"] = "variable"
                     try:
@@ -3978,13 +3862,13 @@ def get_token_info(clang_tokens, image_base, high_func):
                                 token_info["is_parameter"] = sym.isParameter()
                     except:
                         pass
-                        
+
                 elif isinstance(token, ClangTypeToken):
                     token_info["token_type"] = "type"
-                    
+
                 elif isinstance(token, ClangFieldToken):
                     token_info["token_type"] = "field"
-                
+
                 # Only add meaningful tokens
                 if token_info["token_type"] != "unknown" or token_text.startswith("FUN_") or token_text.startswith("DAT_"):
                     if token_text.startswith("FUN_"):
@@ -3992,38 +3876,38 @@ def get_token_info(clang_tokens, image_base, high_func):
                     elif token_text.startswith("DAT_"):
                         token_info["token_type"] = "data"
                     tokens_info.append(token_info)
-                    
+
             except:
                 continue
     except:
         pass
-    
+
     return tokens_info
 
 def decompile_function(offset_str):
     dec = init_decompiler()
     image_base = currentProgram.getImageBase()
-    
+
     offset_str = offset_str.strip()
     if offset_str.startswith("0x"):
         offset_str = offset_str[2:]
-    
+
     try:
         offset = int(offset_str, 16)
     except:
         return {{"success": False, "error": "Invalid offset format"}}
-    
+
     addr = image_base.add(offset)
     func = getFunctionContaining(addr)
     if func is None:
         func = getFunctionAt(addr)
-    
+
     if func is None:
         return {{"success": False, "error": "No function found at offset 0x" + format(offset, 'x')}}
-    
+
     monitor = ConsoleTaskMonitor()
     results = dec.decompileFunction(func, 60, monitor)
-    
+
     if results and results.decompileCompleted():
         decomp = results.getDecompiledFunction()
         high_func = results.getHighFunction()
@@ -4035,7 +3919,7 @@ def decompile_function(offset_str):
             if clang_tokens:
                 line_mapping = get_line_address_mapping(clang_tokens, image_base)
                 tokens_info = get_token_info(clang_tokens, image_base, high_func)
-            
+
             return {{
                 "success": True,
                 "function_name": func.getName(),
@@ -4045,7 +3929,7 @@ def decompile_function(offset_str):
                 "tokens": tokens_info,
                 "error": None
             }}
-    
+
     return {{"success": False, "error": "Decompilation failed"}}
 
 def get_data_items():
@@ -4053,28 +3937,28 @@ def get_data_items():
     image_base = currentProgram.getImageBase()
     listing = currentProgram.getListing()
     data_type_mgr = currentProgram.getDataTypeManager()
-    
+
     data_items = []
     max_items = 5000  # Limit to prevent overload
-    
+
     # Iterate through all defined data in the program
     data_iter = listing.getDefinedData(True)
     count = 0
-    
+
     while data_iter.hasNext() and count < max_items:
         data = data_iter.next()
         try:
             addr = data.getAddress()
             data_offset = addr.getOffset() - image_base.getOffset()
-            
+
             # Skip negative offsets (external references)
             if data_offset < 0:
                 continue
-            
+
             data_type = data.getDataType()
             type_name = data_type.getName() if data_type else "undefined"
             size = data.getLength()
-            
+
             # Get the data value as string representation
             value_str = None
             try:
@@ -4087,11 +3971,11 @@ def get_data_items():
                         value_str = str(value)[:100]
             except:
                 pass
-            
+
             # Get label/name if exists
             symbol = data.getPrimarySymbol()
             name = symbol.getName() if symbol else None
-            
+
             # Categorize the data type
             category = "other"
             type_lower = type_name.lower()
@@ -4107,7 +3991,7 @@ def get_data_items():
                 category = "struct"
             elif "array" in type_lower or "[" in type_name:
                 category = "array"
-            
+
             data_items.append({{
                 "address": "0x{{:x}}".format(data_offset),
                 "name": name,
@@ -4119,7 +4003,7 @@ def get_data_items():
             count += 1
         except:
             continue
-    
+
     return {{
         "success": True,
         "data": data_items,
@@ -4131,24 +4015,24 @@ def get_data_items():
 def get_xrefs(offset_str):
     image_base = currentProgram.getImageBase()
     listing = currentProgram.getListing()
-    
+
     offset_str = offset_str.strip()
     if offset_str.startswith("0x"):
         offset_str = offset_str[2:]
-    
+
     try:
         offset = int(offset_str, 16)
     except:
         return {{"success": False, "error": "Invalid offset format"}}
-    
+
     addr = image_base.add(offset)
     func = getFunctionContaining(addr)
     if func is None:
         func = getFunctionAt(addr)
-    
+
     if func is None:
         return {{"success": False, "error": "No function found at offset"}}
-    
+
     xrefs = []
     refs = getReferencesTo(func.getEntryPoint())
     for ref in refs:
@@ -4174,7 +4058,7 @@ def get_xrefs(offset_str):
             "ref_type": ref.getReferenceType().getName(),
             "instruction": instr_str
         }})
-    
+
     return {{
         "success": True,
         "target_function": func.getName(),
@@ -4188,30 +4072,30 @@ def get_function_info(offset_str):
     dec = init_decompiler()
     image_base = currentProgram.getImageBase()
     listing = currentProgram.getListing()
-    
+
     offset_str = offset_str.strip()
     if offset_str.startswith("0x"):
         offset_str = offset_str[2:]
-    
+
     try:
         offset = int(offset_str, 16)
     except:
         return {{"success": False, "error": "Invalid offset format", "variables": [], "called_functions": []}}
-    
+
     addr = image_base.add(offset)
     func = getFunctionContaining(addr)
     if func is None:
         func = getFunctionAt(addr)
-    
+
     if func is None:
         return {{"success": False, "error": "No function found at offset", "variables": [], "called_functions": []}}
-    
+
     monitor = ConsoleTaskMonitor()
     results = dec.decompileFunction(func, 60, monitor)
-    
+
     variables = []
     called_functions = []
-    
+
     if results and results.decompileCompleted():
         high_func = results.getHighFunction()
         if high_func:
@@ -4227,12 +4111,12 @@ def get_function_info(offset_str):
                         "size": sym.getSize()
                     }}
                     variables.append(var_info)
-    
+
     # Get called functions by scanning references from the function body
     func_body = func.getBody()
     ref_mgr = currentProgram.getReferenceManager()
     seen_funcs = set()
-    
+
     addr_iter = func_body.getAddresses(True)
     while addr_iter.hasNext():
         from_addr = addr_iter.next()
@@ -4249,9 +4133,9 @@ def get_function_info(offset_str):
                             "name": to_func.getName(),
                             "offset": "0x{{:x}}".format(to_offset)
                         }})
-    
+
     func_offset = func.getEntryPoint().getOffset() - image_base.getOffset()
-    
+
     return {{
         "success": True,
         "function_name": func.getName(),
@@ -4266,45 +4150,45 @@ def get_cfg(offset_str):
     image_base = currentProgram.getImageBase()
     listing = currentProgram.getListing()
     monitor = ConsoleTaskMonitor()
-    
+
     offset_str = offset_str.strip()
     if offset_str.startswith("0x"):
         offset_str = offset_str[2:]
-    
+
     try:
         offset = int(offset_str, 16)
     except:
         return {{"success": False, "error": "Invalid offset format", "blocks": [], "edges": []}}
-    
+
     addr = image_base.add(offset)
     func = getFunctionContaining(addr)
     if func is None:
         func = getFunctionAt(addr)
-    
+
     if func is None:
         return {{"success": False, "error": "No function found at offset", "blocks": [], "edges": []}}
-    
+
     # Use BasicBlockModel for CFG analysis
     block_model = BasicBlockModel(currentProgram)
     func_body = func.getBody()
-    
+
     blocks = []
     edges = []
     block_id_map = {{}}  # address -> block_id
-    
+
     # Get all basic blocks in the function
     block_iterator = block_model.getCodeBlocksContaining(func_body, monitor)
     block_index = 0
-    
+
     while block_iterator.hasNext():
         block = block_iterator.next()
         block_start = block.getFirstStartAddress()
         block_end_range = block.getMaxAddress()
-        
+
         # Calculate offsets
         start_offset = block_start.getOffset() - image_base.getOffset()
         end_offset = block_end_range.getOffset() - image_base.getOffset()
-        
+
         # Get instructions in this block
         instructions = []
         addr_set = block.getAddresses(True)
@@ -4316,30 +4200,30 @@ def get_cfg(offset_str):
                 # Get instruction bytes as hex string
                 instr_bytes = instruction.getBytes()
                 bytes_hex = "".join("{:02x}".format(b & 0xff) for b in instr_bytes)
-                
+
                 instructions.append({{
                     "address": "0x{{:x}}".format(instr_offset),
                     "bytes": bytes_hex,
                     "opcode": instruction.getMnemonicString(),
                     "operands": ", ".join(str(op) for op in instruction.getOpObjects(0) + instruction.getOpObjects(1) if op is not None) or str(instruction.getDefaultOperandRepresentation(0) or "")
                 }})
-        
+
         # Sort instructions by address
         instructions.sort(key=lambda x: int(x["address"], 16))
-        
+
         block_id = "block_0x{{:x}}".format(start_offset)
         block_id_map[block_start] = block_id
-        
+
         # Determine if this is entry/exit block
         is_entry = (block_start == func.getEntryPoint())
-        
+
         # Check if this block ends with a return instruction
         is_exit = False
         if instructions:
             last_opcode = instructions[-1]["opcode"].lower()
             if last_opcode in ["ret", "retn", "retf"]:
                 is_exit = True
-        
+
         blocks.append({{
             "id": block_id,
             "startAddress": "0x{{:x}}".format(start_offset),
@@ -4351,34 +4235,34 @@ def get_cfg(offset_str):
             "isExit": is_exit
         }})
         block_index += 1
-    
+
     # Build edges using block destinations
     block_iterator = block_model.getCodeBlocksContaining(func_body, monitor)
-    
+
     while block_iterator.hasNext():
         block = block_iterator.next()
         block_start = block.getFirstStartAddress()
         from_block_id = block_id_map.get(block_start)
-        
+
         if from_block_id is None:
             continue
-        
+
         # Get successors
         dest_iter = block.getDestinations(monitor)
         while dest_iter.hasNext():
             dest_ref = dest_iter.next()
             dest_addr = dest_ref.getDestinationAddress()
             dest_block = dest_ref.getDestinationBlock()
-            
+
             if dest_block is None:
                 continue
-                
+
             dest_block_start = dest_block.getFirstStartAddress()
             to_block_id = block_id_map.get(dest_block_start)
-            
+
             if to_block_id is None:
                 continue
-            
+
             # Determine edge type based on flow type
             flow_type = dest_ref.getFlowType()
             if flow_type.isConditional():
@@ -4393,27 +4277,27 @@ def get_cfg(offset_str):
                 edge_type = "unconditional"
             else:
                 edge_type = "normal"
-            
+
             edges.append({{
                 "from": from_block_id,
                 "to": to_block_id,
                 "type": edge_type
             }})
-            
+
             # Update successors/predecessors in blocks
             for b in blocks:
                 if b["id"] == from_block_id and to_block_id not in b["successors"]:
                     b["successors"].append(to_block_id)
                 if b["id"] == to_block_id and from_block_id not in b["predecessors"]:
                     b["predecessors"].append(from_block_id)
-    
+
     # Mark blocks with no successors as exit blocks
     for b in blocks:
         if not b["successors"]:
             b["isExit"] = True
-    
+
     func_offset_val = func.getEntryPoint().getOffset() - image_base.getOffset()
-    
+
     return {{
         "success": True,
         "function_name": func.getName(),
@@ -4428,27 +4312,27 @@ def analyze_reachability(func_offset_str, current_block_str, registers_json):
     image_base = currentProgram.getImageBase()
     listing = currentProgram.getListing()
     monitor = ConsoleTaskMonitor()
-    
+
     # Parse function offset
     func_offset_str = func_offset_str.strip()
     if func_offset_str.startswith("0x"):
         func_offset_str = func_offset_str[2:]
-    
+
     try:
         func_offset = int(func_offset_str, 16)
     except:
         return {{"success": False, "error": "Invalid function offset format", "blocks": []}}
-    
-    # Parse current block offset  
+
+    # Parse current block offset
     current_block_str = current_block_str.strip()
     if current_block_str.startswith("0x"):
         current_block_str = current_block_str[2:]
-    
+
     try:
         current_block_offset = int(current_block_str, 16)
     except:
         return {{"success": False, "error": "Invalid current block offset format", "blocks": []}}
-    
+
     # Parse register values
     registers = {{}}
     if registers_json:
@@ -4456,31 +4340,31 @@ def analyze_reachability(func_offset_str, current_block_str, registers_json):
             registers = json.loads(registers_json)
         except:
             pass
-    
+
     # Get function
     func_addr = image_base.add(func_offset)
     func = getFunctionContaining(func_addr)
     if func is None:
         func = getFunctionAt(func_addr)
-    
+
     if func is None:
         return {{"success": False, "error": "No function found at offset", "blocks": []}}
-    
+
     # Build CFG
     block_model = BasicBlockModel(currentProgram)
     func_body = func.getBody()
-    
+
     # Collect all blocks
     block_map = {{}}  # address -> block
     block_info = {{}}  # address -> info dict
-    
+
     block_iterator = block_model.getCodeBlocksContaining(func_body, monitor)
     while block_iterator.hasNext():
         block = block_iterator.next()
         block_start = block.getFirstStartAddress()
         start_offset = block_start.getOffset() - image_base.getOffset()
         end_offset = block.getMaxAddress().getOffset() - image_base.getOffset()
-        
+
         block_id = "block_0x{{:x}}".format(start_offset)
         block_map[block_start] = block
         block_info[block_start] = {{
@@ -4490,82 +4374,82 @@ def analyze_reachability(func_offset_str, current_block_str, registers_json):
             "status": "unknown",
             "condition": ""
         }}
-    
+
     # Find current block
     current_addr = image_base.add(current_block_offset)
     current_block = None
     current_block_start = None
-    
+
     for block_start, block in block_map.items():
         if block.contains(current_addr):
             current_block = block
             current_block_start = block_start
             break
-    
+
     if current_block is None:
         # Try exact match
         if current_addr in block_map:
             current_block = block_map[current_addr]
             current_block_start = current_addr
-    
+
     if current_block is None:
         return {{"success": False, "error": "Current block not found", "blocks": []}}
-    
+
     # Mark current block
     block_info[current_block_start]["status"] = "current"
-    
+
     # BFS to find reachable blocks
     from java.util import LinkedList, HashSet
-    
+
     queue = LinkedList()
     visited = HashSet()
     queue.add(current_block_start)
     visited.add(current_block_start)
-    
+
     while not queue.isEmpty():
         block_addr = queue.poll()
         block = block_map.get(block_addr)
-        
+
         if block is None:
             continue
-        
+
         # Get last instruction to determine branch type
         block_max = block.getMaxAddress()
         last_instr = listing.getInstructionAt(block_max)
-        
+
         # Get successors
         dest_iter = block.getDestinations(monitor)
         successors = []
-        
+
         while dest_iter.hasNext():
             dest_ref = dest_iter.next()
             dest_block = dest_ref.getDestinationBlock()
-            
+
             if dest_block is None:
                 continue
-                
+
             dest_start = dest_block.getFirstStartAddress()
             if dest_start not in block_map:
                 continue
-                
+
             flow_type = dest_ref.getFlowType()
             successors.append((dest_start, flow_type, dest_ref.getDestinationAddress()))
-        
+
         # Analyze reachability based on branch condition and registers
         is_conditional = last_instr is not None and any(ft.isConditional() for _, ft, _ in successors)
-        
+
         if is_conditional and last_instr is not None:
             mnemonic = last_instr.getMnemonicString().lower()
-            
+
             # Try to evaluate condition based on register values
             condition_result = evaluate_branch_condition(mnemonic, last_instr, registers)
-            
+
             for dest_start, flow_type, dest_addr in successors:
                 if visited.contains(dest_start):
                     continue
-                
+
                 info = block_info[dest_start]
-                
+
                 if condition_result is None:
                     # Can't determine - mark as conditional
                     info["status"] = "conditional"
@@ -4614,18 +4498,18 @@ def analyze_reachability(func_offset_str, current_block_str, registers_json):
             for dest_start, flow_type, _ in successors:
                 if visited.contains(dest_start):
                     continue
-                    
+
                 block_info[dest_start]["status"] = "reachable"
                 visited.add(dest_start)
                 queue.add(dest_start)
-    
+
     # Mark unvisited blocks as unreachable
     for block_start, info in block_info.items():
         if info["status"] == "unknown":
             info["status"] = "unreachable"
-    
+
     func_offset_val = func.getEntryPoint().getOffset() - image_base.getOffset()
-    
+
     return {{
         "success": True,
         "functionName": func.getName(),
@@ -4643,7 +4527,7 @@ def evaluate_branch_condition(mnemonic, instr, registers):
         op_objs = instr.getOpObjects(i)
         if op_objs:
             ops.extend([str(o).lower() for o in op_objs])
-    
+
     # cbz/cbnz - compare register with zero
     if mnemonic == "cbz" and ops:
         reg_name = ops[0]
@@ -4654,7 +4538,7 @@ def evaluate_branch_condition(mnemonic, instr, registers):
             except:
                 pass
         return None
-    
+
     if mnemonic == "cbnz" and ops:
         reg_name = ops[0]
         if reg_name in registers:
@@ -4664,7 +4548,7 @@ def evaluate_branch_condition(mnemonic, instr, registers):
             except:
                 pass
         return None
-    
+
     # tbz/tbnz - test bit and branch
     if mnemonic == "tbz" and len(ops) >= 2:
         reg_name = ops[0]
@@ -4676,7 +4560,7 @@ def evaluate_branch_condition(mnemonic, instr, registers):
         except:
             pass
         return None
-    
+
     if mnemonic == "tbnz" and len(ops) >= 2:
         reg_name = ops[0]
         try:
@@ -4687,19 +4571,328 @@ def evaluate_branch_condition(mnemonic, instr, registers):
         except:
             pass
         return None
-    
+
     # For flag-based conditions (b.eq, b.ne, etc.), we'd need NZCV flags
     # Return None to indicate we can't determine
     return None
 
+def get_call_graph():
+    """Whole-program call graph: direct call edges plus per-function indirect call counts"""
+    image_base = currentProgram.getImageBase()
+    listing = currentProgram.getListing()
+    func_mgr = currentProgram.getFunctionManager()
+    ref_mgr = currentProgram.getReferenceManager()
+
+    nodes = []
+    edges = []
+    for func in func_mgr.getFunctions(True):
+        func_offset = func.getEntryPoint().getOffset() - image_base.getOffset()
+        if func_offset < 0:
+            continue
+
+        indirect_calls = 0
+        seen = set()
+        instr_iter = listing.getInstructions(func.getBody(), True)
+        while instr_iter.hasNext():
+            instr = instr_iter.next()
+            flow = instr.getFlowType()
+            if not flow.isCall():
+                continue
+            resolved = False
+            for ref in instr.getReferencesFrom():
+                if not ref.getReferenceType().isCall():
+                    continue
+                to_func = getFunctionAt(ref.getToAddress())
+                if to_func is None:
+                    continue
+                resolved = True
+                to_offset = to_func.getEntryPoint().getOffset() - image_base.getOffset()
+                if to_offset >= 0 and to_offset not in seen:
+                    seen.add(to_offset)
+                    edges.append({{
+                        "from": "0x{{:x}}".format(func_offset),
+                        "to": "0x{{:x}}".format(to_offset)
+                    }})
+            if flow.isComputed() and not resolved:
+                indirect_calls += 1
+
+        # Functions referenced by data (vtables, callbacks) are possible indirect call targets
+        address_taken = False
+        for ref in ref_mgr.getReferencesTo(func.getEntryPoint()):
+            ref_type = ref.getReferenceType()
+            if not ref_type.isCall() and not ref_type.isFlow():
+                address_taken = True
+                break
+
+        nodes.append({{
+            "name": func.getName(),
+            "offset": "0x{{:x}}".format(func_offset),
+            "indirect_calls": indirect_calls,
+            "address_taken": address_taken
+        }})
+
+    return {{"success": True, "nodes": nodes, "edges": edges, "error": None}}
+
+def set_signature(offset, params_json):
+    """Replace the parameters of the function at offset with the given types"""
+    from ghidra.program.model.data import PointerDataType, IntegerDataType, LongLongDataType, FloatDataType, DoubleDataType
+    from ghidra.program.model.listing import Function, ParameterImpl
+    from ghidra.program.model.symbol import SourceType
+    try:
+        addr = currentProgram.getImageBase().add(int(offset, 16))
+        types = json.loads(params_json)
+        data_types = {{
+            "pointer": PointerDataType(),
+            "int": IntegerDataType(),
+            "long": LongLongDataType(),
+            "float": FloatDataType(),
+            "double": DoubleDataType()
+        }}
+        tx = currentProgram.startTransaction("DynaDbg inferred signature")
+        committed = False
+        try:
+            func = getFunctionAt(addr)
+            if func is None:
+                func = createFunction(addr, None)
+            if func is None:
+                return {{"success": False, "function_name": None, "error": "No function at " + offset}}
+            params = ArrayList()
+            for i, t in enumerate(types):
+                params.add(ParameterImpl("param_%d" % (i + 1), data_types.get(t, LongLongDataType()), currentProgram))
+            func.replaceParameters(params, Function.FunctionUpdateType.DYNAMIC_STORAGE_ALL_PARAMS, True, SourceType.ANALYSIS)
+            committed = True
+            return {{"success": True, "function_name": func.getName(), "error": None}}
+        finally:
+            currentProgram.endTransaction(tx, committed)
+    except Exception as e:
+        return {{"success": False, "function_name": None, "error": str(e)}}
+
+def resolve_offset(offset_str):
+    offset_str = offset_str.strip()
+    if offset_str.startswith("0x"):
+        offset_str = offset_str[2:]
+    return currentProgram.getImageBase().add(int(offset_str, 16))
+
+def function_offset(func):
+    if func is None:
+        return None
+    return "0x{{:x}}".format(func.getEntryPoint().getOffset() - currentProgram.getImageBase().getOffset())
+
+def run_edit(description, edit):
+    """Apply edit() in a transaction and save the program so the change survives restarts"""
+    tx = currentProgram.startTransaction(description)
+    committed = False
+    try:
+        result = edit()
+        committed = True
+    finally:
+        currentProgram.endTransaction(tx, committed)
+    currentProgram.save(description, ConsoleTaskMonitor())
+    return result
+
+def rename_function(offset_str, new_name):
+    from ghidra.program.model.symbol import SourceType
+    try:
+        addr = resolve_offset(offset_str)
+        func = getFunctionContaining(addr) or getFunctionAt(addr)
+        if func is None:
+            return {{"success": False, "error": "No function at " + offset_str}}
+        old_name = func.getName()
+        run_edit("DynaDbg rename function", lambda: func.setName(new_name, SourceType.USER_DEFINED))
+        return {{"success": True, "function_offset": function_offset(func), "old_name": old_name, "new_name": func.getName(), "error": None}}
+    except Exception as e:
+        return {{"success": False, "error": str(e)}}
+
+def rename_variable(offset_str, old_name, new_name, type_name):
+    """Rename and/or retype a local variable or parameter of the decompiled function"""
+    from ghidra.program.model.pcode import HighFunctionDBUtil
+    from ghidra.program.model.symbol import SourceType
+    from ghidra.util.data import DataTypeParser
+    try:
+        addr = resolve_offset(offset_str)
+        func = getFunctionContaining(addr) or getFunctionAt(addr)
+        if func is None:
+            return {{"success": False, "error": "No function at " + offset_str}}
+        results = init_decompiler().decompileFunction(func, 60, ConsoleTaskMonitor())
+        if not results or not results.decompileCompleted():
+            return {{"success": False, "error": "Decompilation failed"}}
+        symbol = None
+        for candidate in results.getHighFunction().getLocalSymbolMap().getSymbols():
+            if candidate.getName() == old_name:
+                symbol = candidate
+                break
+        if symbol is None:
+            return {{"success": False, "error": "No variable named " + old_name}}
+        data_type = None
+        if type_name:
+            parser = DataTypeParser(currentProgram.getDataTypeManager(), None, None, DataTypeParser.AllowedDataTypes.ALL)
+            data_type = parser.parse(type_name)
+        run_edit("DynaDbg update variable",
+                 lambda: HighFunctionDBUtil.updateDBVariable(symbol, new_name or None, data_type, SourceType.USER_DEFINED))
+        return {{"success": True, "function_offset": function_offset(func), "old_name": old_name, "new_name": new_name or old_name, "error": None}}
+    except Exception as e:
+        return {{"success": False, "error": str(e)}}
+
+def set_comment(offset_str, comment, comment_type):
+    """Set (or clear, when empty) a listing comment; shows up in the decompiler output"""
+    from ghidra.program.model.listing import CodeUnit
+    kinds = {{
+        "eol": CodeUnit.EOL_COMMENT,
+        "pre": CodeUnit.PRE_COMMENT,
+        "post": CodeUnit.POST_COMMENT,
+        "plate": CodeUnit.PLATE_COMMENT,
+        "repeatable": CodeUnit.REPEATABLE_COMMENT
+    }}
+    try:
+        addr = resolve_offset(offset_str)
+        kind = kinds.get(comment_type or "eol", CodeUnit.EOL_COMMENT)
+        run_edit("DynaDbg set comment", lambda: currentProgram.getListing().setComment(addr, kind, comment or None))
+        return {{"success": True, "function_offset": function_offset(getFunctionContaining(addr)), "error": None}}
+    except Exception as e:
+        return {{"success": False, "error": str(e)}}
+
+def describe_refs(addr, image_base, limit):
+    refs = []
+    for ref in currentProgram.getReferenceManager().getReferencesTo(addr):
+        if len(refs) >= limit:
+            break
+        from_addr = ref.getFromAddress()
+        from_func = getFunctionContaining(from_addr)
+        refs.append({{
+            "from_offset": "0x{{:x}}".format(from_addr.getOffset() - image_base.getOffset()),
+            "from_function": from_func.getName() if from_func else None,
+            "ref_type": str(ref.getReferenceType())
+        }})
+    return refs
+
+def search_program(query, kind, limit):
+    """Search strings, function names, scalar operands or byte patterns (?? wildcards)"""
+    from ghidra.program.util import DefinedDataIterator
+    from ghidra.program.model.scalar import Scalar
+    image_base = currentProgram.getImageBase()
+    results = []
+    truncated = False
+
+    def add(addr, text):
+        func = getFunctionContaining(addr)
+        results.append({{
+            "offset": "0x{{:x}}".format(addr.getOffset() - image_base.getOffset()),
+            "text": text,
+            "function_name": func.getName() if func else None,
+            "function_offset": function_offset(func),
+            "references": describe_refs(addr, image_base, 64)
+        }})
+
+    try:
+        if kind == "string":
+            needle = query.lower()
+            for data in DefinedDataIterator.definedStrings(currentProgram):
+                value = data.getValue()
+                if value is not None and needle in unicode(value).lower():
+                    if len(results) >= limit:
+                        truncated = True
+                        break
+                    add(data.getAddress(), unicode(value))
+        elif kind == "function_name":
+            needle = query.lower()
+            for func in currentProgram.getFunctionManager().getFunctions(True):
+                if needle in func.getName().lower():
+                    if len(results) >= limit:
+                        truncated = True
+                        break
+                    add(func.getEntryPoint(), func.getName())
+        elif kind == "immediate_value":
+            text = query.strip().lower()
+            value = int(text, 16) if text.startswith("0x") or text.startswith("-0x") else int(text)
+            for instr in currentProgram.getListing().getInstructions(True):
+                if truncated:
+                    break
+                for i in range(instr.getNumOperands()):
+                    hit = False
+                    for obj in instr.getOpObjects(i):
+                        if isinstance(obj, Scalar) and (obj.getValue() == value or obj.getUnsignedValue() == value):
+                            hit = True
+                            break
+                    if hit:
+                        if len(results) >= limit:
+                            truncated = True
+                            break
+                        add(instr.getAddress(), str(instr))
+                        break
+        elif kind == "byte_pattern":
+            tokens = query.replace(",", " ").split()
+            pattern = jarray.zeros(len(tokens), "b")
+            masks = jarray.zeros(len(tokens), "b")
+            for i, token in enumerate(tokens):
+                if token in ("?", "??"):
+                    continue
+                byte = int(token, 16)
+                pattern[i] = byte - 256 if byte > 127 else byte
+                masks[i] = -1
+            memory = currentProgram.getMemory()
+            for block in memory.getBlocks():
+                if not block.isInitialized() or truncated:
+                    continue
+                start = block.getStart()
+                while start is not None and start.compareTo(block.getEnd()) <= 0:
+                    found = memory.findBytes(start, block.getEnd(), pattern, masks, True, ConsoleTaskMonitor())
+                    if found is None:
+                        break
+                    if len(results) >= limit:
+                        truncated = True
+                        break
+                    add(found, query)
+                    start = found.next()
+        else:
+            return {{"success": False, "results": [], "truncated": False, "error": "Unknown search kind: " + kind}}
+    except Exception as e:
+        return {{"success": False, "results": results, "truncated": truncated, "error": str(e)}}
+    return {{"success": True, "results": results, "truncated": truncated, "error": None}}
+
+def function_fingerprints():
+    """Size, block count and hashes of every function, for diffing program versions"""
+    import hashlib
+    image_base = currentProgram.getImageBase()
+    listing = currentProgram.getListing()
+    block_model = BasicBlockModel(currentProgram)
+    monitor = ConsoleTaskMonitor()
+    funcs = []
+    for func in currentProgram.getFunctionManager().getFunctions(True):
+        offset = func.getEntryPoint().getOffset() - image_base.getOffset()
+        if offset < 0:
+            continue
+        body = func.getBody()
+        mnemonics = hashlib.md5()
+        raw = hashlib.md5()
+        count = 0
+        for instr in listing.getInstructions(body, True):
+            mnemonics.update(instr.getMnemonicString() + ";")
+            raw.update(bytearray(b & 0xff for b in instr.getBytes()))
+            count += 1
+        blocks = 0
+        block_iter = block_model.getCodeBlocksContaining(body, monitor)
+        while block_iter.hasNext():
+            block_iter.next()
+            blocks += 1
+        funcs.append({{
+            "name": func.getName(),
+            "offset": "0x{{:x}}".format(offset),
+            "size": body.getNumAddresses(),
+            "instructions": count,
+            "blocks": blocks,
+            "mnemonic_hash": mnemonics.hexdigest(),
+            "byte_hash": raw.hexdigest()
+        }})
+    return {{"success": True, "functions": funcs, "error": None}}
+
 class GhidraHandler(BaseHTTPServer.BaseHTTPRequestHandler):
     def log_message(self, format, *args):
         pass  # Suppress logging
-    
+
     def do_GET(self):
         parsed = urlparse.urlparse(self.path)
         params = urlparse.parse_qs(parsed.query)
-        
+
         if parsed.path == "/decompile":
             offset = params.get("offset", [""])[0]
             result = decompile_function(offset)
@@ -4712,6 +4905,8 @@ class GhidraHandler(BaseHTTPServer.BaseHTTPRequestHandler):
         elif parsed.path == "/cfg":
             offset = params.get("offset", [""])[0]
             result = get_cfg(offset)
+        elif parsed.path == "/callgraph":
+            result = get_call_graph()
         elif parsed.path == "/reachability":
             func_offset = params.get("func_offset", [""])[0]
             current_block = params.get("current_block", [""])[0]
@@ -4719,6 +4914,33 @@ class GhidraHandler(BaseHTTPServer.BaseHTTPRequestHandler):
             result = analyze_reachability(func_offset, current_block, registers)
         elif parsed.path == "/data":
             result = get_data_items()
+        elif parsed.path == "/set_signature":
+            offset = params.get("offset", [""])[0]
+            types = params.get("params", ["[]"])[0]
+            result = set_signature(offset, types)
+        elif parsed.path == "/rename_function":
+            result = rename_function(params.get("offset", [""])[0], params.get("name", [""])[0])
+        elif parsed.path == "/rename_variable":
+            result = rename_variable(
+                params.get("offset", [""])[0],
+                params.get("old_name", [""])[0],
+                params.get("new_name", [""])[0],
+                params.get("type", [""])[0])
+        elif parsed.path == "/set_comment":
+            result = set_comment(
+                params.get("offset", [""])[0],
+                params.get("comment", [""])[0],
+                params.get("type", ["eol"])[0])
+        elif parsed.path == "/search":
+            result = search_program(
+                params.get("query", [""])[0],
+                params.get("kind", ["string"])[0],
+                int(params.get("limit", ["500"])[0]))
+        elif parsed.path == "/fingerprints":
+            try:
+                result = function_fingerprints()
+            except Exception as e:
+                result = {{"success": False, "functions": [], "error": str(e)}}
         elif parsed.path == "/ping":
             result = {{"status": "ok", "program": currentProgram.getName()}}
         elif parsed.path == "/info":
@@ -4746,7 +4968,7 @@ class GhidraHandler(BaseHTTPServer.BaseHTTPRequestHandler):
             return
         else:
             result = {{"error": "Unknown endpoint"}}
-        
+
         self.send_response(200)
         self.send_header("Content-Type", "application/json")
         self.send_header("Access-Control-Allow-Origin", "*")
@@ -4761,49 +4983,97 @@ server.serve_forever()
 "#, port)
 }
 
-/// Start Ghidra server for a project
+// Ports probed for Ghidra servers when the requested one is unavailable
+const GHIDRA_PORT_RANGE: std::ops::Range<u16> = 18462..18562;
+
+fn ghidra_port_bindable(port: u16) -> bool {
+    std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// Claim a port for `project_path` in GHIDRA_SERVER_PORTS: `preferred` when
+/// it is free, else the first free port of GHIDRA_PORT_RANGE, else one
+/// assigned by the OS. Returns (port, already running).
+fn reserve_ghidra_port(project_path: &str, preferred: Option<u16>) -> Result<(u16, bool), String> {
+    let mut ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
+    if let Some(&port) = ports.get(project_path) {
+        return Ok((port, true));
+    }
+    let free = |port: &u16| !ports.values().any(|used| used == port) && ghidra_port_bindable(*port);
+    let port = preferred.into_iter().chain(GHIDRA_PORT_RANGE)
+        .find(free)
+        .or_else(|| {
+            std::net::TcpListener::bind(("127.0.0.1", 0)).ok()
+                .and_then(|listener| listener.local_addr().ok())
+                .map(|addr| addr.port())
+        })
+        .ok_or("No free port for the Ghidra server")?;
+    ports.insert(project_path.to_string(), port);
+    Ok((port, false))
+}
+
+/// Start Ghidra server for a project on `port` if it is free, otherwise on
+/// a free port picked here; returns the port the server listens on
 #[tauri::command]
 async fn start_ghidra_server(
     project_path: String,
     library_name: String,
     ghidra_path: String,
-    port: u16,
-) -> Result<bool, String> {
-    // Check if server is already running
-    {
-        let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
-        if ports.contains_key(&project_path) {
-            return Ok(true); // Already running
+    port: Option<u16>,
+) -> Result<u16, String> {
+    let (port, running) = reserve_ghidra_port(&project_path, port)?;
+    if running {
+        return Ok(port);
+    }
+
+    if let Err(e) = spawn_ghidra_server(&project_path, &library_name, &ghidra_path, port).await {
+        if let Ok(mut ports) = GHIDRA_SERVER_PORTS.lock() {
+            ports.remove(&project_path);
         }
+        return Err(e);
     }
-    
+    ghidra_supervisor::register(&project_path, &library_name, &ghidra_path, port);
+    Ok(port)
+}
+
+/// Launch the headless server process on a port reserved with
+/// reserve_ghidra_port and record it in GHIDRA_SERVERS/PORTS/LOGS (also used
+/// by ghidra_supervisor to restart a server that exited)
+async fn spawn_ghidra_server(
+    project_path: &str,
+    library_name: &str,
+    ghidra_path: &str,
+    port: u16,
+) -> Result<(), String> {
+    let project_path = project_path.to_string();
+    let library_name = library_name.to_string();
     let ghidra_base = PathBuf::from(&ghidra_path);
     let analyzer_path = if cfg!(windows) {
         ghidra_base.join("support").join("analyzeHeadless.bat")
     } else {
         ghidra_base.join("support").join("analyzeHeadless")
     };
-    
+
     if !analyzer_path.exists() {
         return Err("Ghidra analyzeHeadless not found".to_string());
     }
-    
+
     // Generate and save the server script
     let ghidra_dir = get_ghidra_projects_dir();
-    let script_path = ghidra_dir.join("ghidra_server.py");
+    // One script per port: servers starting concurrently must not overwrite each other's
+    let script_path = ghidra_dir.join(format!("ghidra_server_{}.py", port));
     let script_content = generate_ghidra_server_script(port);
-    
+
     fs::write(&script_path, &script_content)
         .await
         .map_err(|e| format!("Failed to write server script: {}", e))?;
-    
+
     // Clean library name (without extension)
     // Ghidra stores imported programs with file_stem (no extension) as the program name
     let clean_lib_name = PathBuf::from(&library_name)
         .file_stem()
         .map(|s| s.to_string_lossy().to_string())
         .unwrap_or(library_name.clone());
-    
+
     // Start Ghidra with the server script (non-blocking)
     // Use clean_lib_name (without extension) for -process option
     // Ghidra stores imported programs without file extensions
@@ -4819,12 +5089,12 @@ async fn start_ghidra_server(
         .stderr(Stdio::piped())
         .spawn()
         .map_err(|e| format!("Failed to start Ghidra server: {}", e))?;
-    
+
     // Spawn threads to consume stdout/stderr to prevent blocking
     let stdout = child.stdout.take();
     let stderr = child.stderr.take();
     let project_path_clone = project_path.clone();
-    
+
     if let Some(stdout) = stdout {
         std::thread::spawn(move || {
             use std::io::{BufRead, BufReader};
@@ -4839,7 +5109,7 @@ async fn start_ghidra_server(
             }
         });
     }
-    
+
     let project_path_clone2 = project_path.clone();
     if let Some(stderr) = stderr {
         std::thread::spawn(move || {
@@ -4855,7 +5125,7 @@ async fn start_ghidra_server(
             }
         });
     }
-    
+
     // Store the process and port
     {
         let mut servers = GHIDRA_SERVERS.lock().map_err(|e| e.to_string())?;
@@ -4869,28 +5139,30 @@ async fn start_ghidra_server(
         let mut logs = GHIDRA_SERVER_LOGS.lock().map_err(|e| e.to_string())?;
         logs.insert(project_path, Vec::new());
     }
-    
-    Ok(true)
+
+    Ok(())
 }
 
 /// Stop Ghidra server for a project
 #[tauri::command]
 async fn stop_ghidra_server(project_path: String) -> Result<bool, String> {
+    ghidra_supervisor::unregister(&project_path);
+
     // Try to send shutdown request first
     let port = {
         let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
         ports.get(&project_path).copied()
     };
-    
+
     if let Some(port) = port {
-        let _ = reqwest::get(&format!("http://127.0.0.1:{}/shutdown", port)).await;
+        let _ = server_connection::local_client().get(format!("http://127.0.0.1:{}/shutdown", port)).send().await;
     }
-    
+
     // Kill the process
     {
         let mut servers = GHIDRA_SERVERS.lock().map_err(|e| e.to_string())?;
-        if let Some(mut child) = servers.remove(&project_path) {
-            let _ = child.kill();
+        if let Some(child) = servers.remove(&project_path) {
+            ghidra_supervisor::kill_server(child);
         }
     }
     {
@@ -4901,21 +5173,22 @@ async fn stop_ghidra_server(project_path: String) -> Result<bool, String> {
         let mut logs = GHIDRA_SERVER_LOGS.lock().map_err(|e| e.to_string())?;
         logs.remove(&project_path);
     }
-    
+
     Ok(true)
 }
 
 /// Check if Ghidra server is running
 #[tauri::command]
 async fn check_ghidra_server(project_path: String) -> Result<Option<u16>, String> {
+    ghidra_supervisor::reap();
     let port = {
         let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
         ports.get(&project_path).copied()
     };
-    
+
     if let Some(port) = port {
         // Ping the server to check if it's responsive
-        match reqwest::get(&format!("http://127.0.0.1:{}/ping", port)).await {
+        match server_connection::local_client().get(format!("http://127.0.0.1:{}/ping", port)).send().await {
             Ok(resp) if resp.status().is_success() => Ok(Some(port)),
             _ => {
                 // Server not responding yet, but don't kill it - it might still be starting
@@ -4945,25 +5218,25 @@ async fn ghidra_server_decompile(
         let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
         ports.get(&project_path).copied()
     };
-    
+
     let port = port.ok_or("Ghidra server not running for this project")?;
-    
+
     let url = format!("http://127.0.0.1:{}/decompile?offset={}", port, function_address);
-    
-    let resp = reqwest::get(&url)
+
+    let resp = server_connection::local_client().get(&url).send()
         .await
         .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?;
-    
+
     // First get the raw text to see what we're receiving
     let text = resp
         .text()
         .await
         .map_err(|e| format!("Failed to get response text: {}", e))?;
-    
+
     // Try to parse the JSON with better error handling
     let result: GhidraDecompileResult = serde_json::from_str(&text)
         .map_err(|e| format!("Failed to parse response: {}. Response was: {}", e, text.chars().take(500).collect::<String>()))?;
-    
+
     Ok(result)
 }
 
@@ -4977,25 +5250,25 @@ async fn ghidra_server_xrefs(
         let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
         ports.get(&project_path).copied()
     };
-    
+
     let port = port.ok_or("Ghidra server not running for this project")?;
-    
+
     let url = format!("http://127.0.0.1:{}/xrefs?offset={}", port, function_address);
-    
-    let resp = reqwest::get(&url)
+
+    let resp = server_connection::local_client().get(&url).send()
         .await
         .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?;
-    
+
     // First get the raw text to see what we're receiving
     let text = resp
         .text()
         .await
         .map_err(|e| format!("Failed to get response text: {}", e))?;
-    
+
     // Try to parse the JSON with better error handling
     let result: GhidraXrefsResult = serde_json::from_str(&text)
         .map_err(|e| format!("Failed to parse response: {}. Response was: {}", e, text.chars().take(500).collect::<String>()))?;
-    
+
     Ok(result)
 }
 
@@ -5009,20 +5282,20 @@ async fn ghidra_server_function_info(
         let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
         ports.get(&project_path).copied()
     };
-    
+
     let port = port.ok_or("Ghidra server not running for this project")?;
-    
+
     let url = format!("http://127.0.0.1:{}/function_info?offset={}", port, function_address);
-    
-    let resp = reqwest::get(&url)
+
+    let resp = server_connection::local_client().get(&url).send()
         .await
         .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?;
-    
+
     let result: GhidraFunctionInfoResult = resp
         .json()
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
-    
+
     Ok(result)
 }
 
@@ -5072,6 +5345,22 @@ pub struct GhidraCfgResult {
     pub error: Option<String>,
 }
 
+// Basic block ranges (start, last byte; module offsets) of the CFGs fetched
+// from the Ghidra servers, per (project path, function offset)
+type CfgBlockCache = HashMap<(String, u64), Vec<(u64, u64)>>;
+static CFG_BLOCKS: Lazy<Mutex<CfgBlockCache>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+fn parse_hex_offset(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches("0x"), 16).ok()
+}
+
+/// Basic blocks of a function whose CFG was already fetched
+fn cached_cfg_blocks(project_path: &str, function_offset: u64) -> Option<Vec<(u64, u64)>> {
+    CFG_BLOCKS.lock().ok()?.get(&(project_path.to_string(), function_offset)).cloned()
+}
+
 /// Get CFG (Control Flow Graph) using running Ghidra server
 #[tauri::command]
 async fn ghidra_server_cfg(
@@ -5082,26 +5371,42 @@ async fn ghidra_server_cfg(
         let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
         ports.get(&project_path).copied()
     };
-    
+
     let port = port.ok_or("Ghidra server not running for this project")?;
-    
+
     let url = format!("http://127.0.0.1:{}/cfg?offset={}", port, function_address);
-    
-    let resp = reqwest::get(&url)
+
+    let resp = server_connection::local_client().get(&url).send()
         .await
         .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?;
-    
+
     let text = resp
         .text()
         .await
         .map_err(|e| format!("Failed to get response text: {}", e))?;
-    
+
     let result: GhidraCfgResult = serde_json::from_str(&text)
         .map_err(|e| format!("Failed to parse CFG response: {}. Response was: {}", e, text.chars().take(500).collect::<String>()))?;
-    
+
+    if let Some(function_offset) = result.function_offset.as_deref().filter(|_| result.success).and_then(parse_hex_offset) {
+        let blocks = result.blocks.iter()
+            .filter_map(|b| Some((parse_hex_offset(&b.start_address)?, parse_hex_offset(&b.end_address)?)))
+            .collect();
+        if let Ok(mut cache) = CFG_BLOCKS.lock() {
+            cache.insert((project_path, function_offset), blocks);
+        }
+    }
+
     Ok(result)
 }
 
+fn escape_markup(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 /// Ghidra Data item from analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GhidraDataItem {
@@ -5132,23 +5437,28 @@ async fn ghidra_server_data(
         let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
         ports.get(&project_path).copied()
     };
-    
+
     let port = port.ok_or("Ghidra server not running for this project")?;
-    
+
     let url = format!("http://127.0.0.1:{}/data", port);
-    
-    let resp = reqwest::get(&url)
+
+    let resp = server_connection::local_client().get(&url).send()
         .await
         .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?;
-    
+
     let text = resp
         .text()
         .await
         .map_err(|e| format!("Failed to get response text: {}", e))?;
-    
+
     let result: GhidraDataResult = serde_json::from_str(&text)
         .map_err(|e| format!("Failed to parse Data response: {}. Response was: {}", e, text.chars().take(500).collect::<String>()))?;
-    
+
+    // Cached per module so scan results can be matched against data items
+    if result.success {
+        data_overlay::save(&project_path, &text).await?;
+    }
+
     Ok(result)
 }
 
@@ -5188,7 +5498,9 @@ pub struct ReachabilityResult {
 
 /// Analyze block reachability using Z3 (via Ghidra headless Java script)
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn ghidra_analyze_reachability(
+    state: tauri::State<'_, state::AppStateType>,
     project_path: String,
     library_name: String,
     function_offset: String,
@@ -5205,7 +5517,7 @@ async fn ghidra_analyze_reachability(
     } else {
         ghidra_base.join("support").join("analyzeHeadless")
     };
-    
+
     if !analyzer_path.exists() {
         return Ok(ReachabilityResult {
             success: false,
@@ -5216,12 +5528,19 @@ async fn ghidra_analyze_reachability(
             error: Some(format!("Ghidra analyzeHeadless not found at: {:?}", analyzer_path)),
         });
     }
-    
+
     // Get script path from the application's scripts directory
     let script_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .join("scripts")
         .join("ReachabilityAnalysis.java");
-    
+
+    // The script looks registers up by canonical lowercase name (x29, rip, ...)
+    let arch = state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.arch.clone()))
+        .unwrap_or_default();
+    let registers_json = registers::normalize_json_str(&arch, &registers_json);
+
     if !script_path.exists() {
         return Ok(ReachabilityResult {
             success: false,
@@ -5232,22 +5551,22 @@ async fn ghidra_analyze_reachability(
             error: Some(format!("ReachabilityAnalysis.java script not found at: {:?}", script_path)),
         });
     }
-    
+
     // Clean library name for Ghidra project (stem without extension)
     // Ghidra stores imported programs with file_stem (no extension) as the program name
     let clean_lib_name = PathBuf::from(&library_name)
         .file_stem()
         .map(|s| s.to_string_lossy().to_string())
         .unwrap_or(library_name.clone());
-    
+
     // Run Ghidra headless with the Z3 reachability script
-    // Format: analyzeHeadless <project> <name> -process <file> -noanalysis 
+    // Format: analyzeHeadless <project> <name> -process <file> -noanalysis
     //         -scriptPath <dir> -preScript <script> <arg1> <arg2> <arg3>
     // Each argument must be a separate command-line argument
     // Use clean_lib_name (without extension) for -process option
     let script_name = script_path.file_name().unwrap().to_string_lossy().to_string();
     let script_dir = script_path.parent().unwrap_or(&script_path).to_string_lossy().to_string();
-    
+
     let output = hide_console_window(&mut Command::new(&analyzer_path))
         .arg(&project_path)
         .arg(&clean_lib_name)
@@ -5267,10 +5586,10 @@ async fn ghidra_analyze_reachability(
         .arg(&library_base_address)
         .output()
         .map_err(|e| format!("Failed to run Ghidra: {}", e))?;
-    
+
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
-    
+
     // Parse result from stdout - look for REACHABILITY_RESULT: marker
     for line in stdout.lines() {
         if line.starts_with("REACHABILITY_RESULT:") {
@@ -5297,7 +5616,7 @@ async fn ghidra_analyze_reachability(
             }
         }
     }
-    
+
     // No result found - return debug info with more context
     Ok(ReachabilityResult {
         success: false,
@@ -5313,6 +5632,378 @@ async fn ghidra_analyze_reachability(
     })
 }
 
+// ============================================================================
+// Call Graph Reachability (whole-program call graph from Ghidra server)
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallGraphNode {
+    pub name: String,
+    pub offset: String,
+    #[serde(default)]
+    pub indirect_calls: u32,   // Call sites whose target could not be resolved
+    #[serde(default)]
+    pub address_taken: bool,   // Referenced by data, so a possible indirect call target
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallGraphEdge {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhidraCallGraphResult {
+    pub success: bool,
+    pub nodes: Vec<CallGraphNode>,
+    pub edges: Vec<CallGraphEdge>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallPathStep {
+    pub name: String,
+    pub offset: String,
+    pub via_indirect: bool,  // Reached through an approximated indirect call
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallReachabilityResult {
+    pub success: bool,
+    pub reachable: bool,
+    pub uses_indirect: bool,  // True when the answer depends on indirect-call approximation
+    pub path: Vec<CallPathStep>,
+    pub error: Option<String>,
+}
+
+/// Load the module call graph from the SQLite cache, fetching it from the
+/// running Ghidra server on a miss
+async fn load_call_graph(
+    project_path: &str,
+    target_os: &str,
+    module_name: &str,
+) -> Result<GhidraCallGraphResult, String> {
+    let cached: Option<String> = {
+        let (target_os, module_name) = (target_os.to_string(), module_name.to_string());
+        db::run(move |conn| {
+            Ok(conn.query_row(
+                "SELECT callgraph_json FROM ghidra_callgraph_cache WHERE target_os = ?1 AND module_name = ?2
+                 AND (cache_version IS NULL OR cache_version = ?3)",
+                params![target_os, module_name, cache_versions::stamp(conn, &target_os, &module_name)],
+                |row| row.get(0),
+            ).ok())
+        }).await?
+    };
+    let cached = cached.and_then(|json| secure_store::open_value("ghidra_callgraph_cache", "callgraph_json", json).ok());
+    if let Some(graph) = cached.and_then(|json| serde_json::from_str::<GhidraCallGraphResult>(&json).ok()) {
+        return Ok(graph);
+    }
+
+    let port = {
+        let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
+        ports.get(project_path).copied()
+    };
+
+    let port = port.ok_or("Ghidra server not running for this project")?;
+
+    let url = format!("http://127.0.0.1:{}/callgraph", port);
+
+    let resp = server_connection::local_client().get(&url).send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?;
+
+    let text = resp
+        .text()
+        .await
+        .map_err(|e| format!("Failed to get response text: {}", e))?;
+
+    let graph: GhidraCallGraphResult = serde_json::from_str(&text)
+        .map_err(|e| format!("Failed to parse call graph response: {}. Response was: {}", e, text.chars().take(500).collect::<String>()))?;
+
+    if graph.success {
+        let sealed = secure_store::seal_value("ghidra_callgraph_cache", "callgraph_json", &text)?;
+        let (target_os, module_name) = (target_os.to_string(), module_name.to_string());
+        db::run(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO ghidra_callgraph_cache (target_os, module_name, callgraph_json, updated_at, cache_version)
+                 VALUES (?1, ?2, ?3, datetime('now'), ?4)",
+                params![target_os, module_name, sealed, cache_versions::stamp(conn, &target_os, &module_name)],
+            ).map_err(|e| e.to_string())
+        }).await?;
+    }
+
+    Ok(graph)
+}
+
+/// Resolve a function given as hex offset ("0x1234") or exact name to its node index
+fn resolve_call_graph_node(graph: &GhidraCallGraphResult, function: &str) -> Option<usize> {
+    let parse_offset = |s: &str| u64::from_str_radix(s.trim().trim_start_matches("0x"), 16).ok();
+    if let Some(offset) = parse_offset(function) {
+        if let Some(i) = graph.nodes.iter().position(|n| parse_offset(&n.offset) == Some(offset)) {
+            return Some(i);
+        }
+    }
+    graph.nodes.iter().position(|n| n.name == function)
+}
+
+/// Breadth-first search from `from` to `to`. Direct edges are tried first;
+/// only if that fails, functions with unresolved indirect calls are assumed
+/// to be able to call every address-taken function.
+fn find_call_path(graph: &GhidraCallGraphResult, from: usize, to: usize) -> Option<(Vec<(usize, bool)>, bool)> {
+    let index: HashMap<&str, usize> = graph.nodes.iter().enumerate().map(|(i, n)| (n.offset.as_str(), i)).collect();
+    let mut direct: Vec<Vec<usize>> = vec![Vec::new(); graph.nodes.len()];
+    for edge in &graph.edges {
+        if let (Some(&f), Some(&t)) = (index.get(edge.from.as_str()), index.get(edge.to.as_str())) {
+            direct[f].push(t);
+        }
+    }
+    let address_taken: Vec<usize> = graph.nodes.iter().enumerate()
+        .filter(|(_, n)| n.address_taken)
+        .map(|(i, _)| i)
+        .collect();
+
+    for allow_indirect in [false, true] {
+        // prev[node] = (predecessor, reached via indirect approximation)
+        let mut prev: Vec<Option<(usize, bool)>> = vec![None; graph.nodes.len()];
+        let mut visited = vec![false; graph.nodes.len()];
+        let mut queue = std::collections::VecDeque::new();
+        visited[from] = true;
+        queue.push_back(from);
+
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![(to, false)];
+                let mut cur = to;
+                while let Some((p, via_indirect)) = prev[cur] {
+                    path.last_mut().unwrap().1 = via_indirect;
+                    path.push((p, false));
+                    cur = p;
+                }
+                path.reverse();
+                let uses_indirect = path.iter().any(|(_, v)| *v);
+                return Some((path, uses_indirect));
+            }
+            let indirect_targets: &[usize] = if allow_indirect && graph.nodes[node].indirect_calls > 0 {
+                &address_taken
+            } else {
+                &[]
+            };
+            let next = direct[node].iter().map(|&t| (t, false))
+                .chain(indirect_targets.iter().map(|&t| (t, true)));
+            for (t, via_indirect) in next {
+                if !visited[t] {
+                    visited[t] = true;
+                    prev[t] = Some((node, via_indirect));
+                    queue.push_back(t);
+                }
+            }
+        }
+    }
+    None
+}
+
+async fn call_graph_query(
+    project_path: String,
+    target_os: String,
+    module_name: String,
+    from: String,
+    to: String,
+) -> Result<CallReachabilityResult, String> {
+    let graph = load_call_graph(&project_path, &target_os, &module_name).await?;
+    if !graph.success {
+        return Ok(CallReachabilityResult {
+            success: false,
+            reachable: false,
+            uses_indirect: false,
+            path: vec![],
+            error: graph.error,
+        });
+    }
+
+    let from_idx = resolve_call_graph_node(&graph, &from)
+        .ok_or_else(|| format!("Function not found in call graph: {}", from))?;
+    let to_idx = resolve_call_graph_node(&graph, &to)
+        .ok_or_else(|| format!("Function not found in call graph: {}", to))?;
+
+    match find_call_path(&graph, from_idx, to_idx) {
+        Some((path, uses_indirect)) => Ok(CallReachabilityResult {
+            success: true,
+            reachable: true,
+            uses_indirect,
+            path: path.into_iter().map(|(i, via_indirect)| CallPathStep {
+                name: graph.nodes[i].name.clone(),
+                offset: graph.nodes[i].offset.clone(),
+                via_indirect,
+            }).collect(),
+            error: None,
+        }),
+        None => Ok(CallReachabilityResult {
+            success: true,
+            reachable: false,
+            uses_indirect: false,
+            path: vec![],
+            error: None,
+        }),
+    }
+}
+
+/// Check whether function `from` can (transitively) call function `to`
+#[tauri::command]
+async fn is_reachable(
+    project_path: String,
+    target_os: String,
+    module_name: String,
+    from: String,
+    to: String,
+) -> Result<CallReachabilityResult, String> {
+    let mut result = call_graph_query(project_path, target_os, module_name, from, to).await?;
+    result.path.clear();
+    Ok(result)
+}
+
+/// Shortest call chain from function `from` to function `to`
+#[tauri::command]
+async fn shortest_call_path(
+    project_path: String,
+    target_os: String,
+    module_name: String,
+    from: String,
+    to: String,
+) -> Result<CallReachabilityResult, String> {
+    call_graph_query(project_path, target_os, module_name, from, to).await
+}
+
+// Nodes returned by ghidra_server_callgraph before the view is truncated
+const MAX_CALLGRAPH_VIEW_NODES: usize = 2000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallGraphViewNode {
+    pub name: String,
+    pub offset: String,
+    pub depth: u32,            // Call distance from the root (0 without a root)
+    pub indirect_calls: u32,
+    pub address_taken: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallGraphView {
+    pub success: bool,
+    pub root: Option<String>,
+    pub nodes: Vec<CallGraphViewNode>,
+    pub edges: Vec<CallGraphEdge>,   // Only edges between returned nodes
+    pub truncated: bool,             // Hit MAX_CALLGRAPH_VIEW_NODES
+    pub error: Option<String>,
+}
+
+/// Call graph for rendering: the whole module, or the functions within
+/// `depth` calls of `root_offset` (offset or name) following `direction`
+/// ("callees" default, "callers" or "both"). Uses the SQLite call graph
+/// cache; `refresh` refetches it from the server.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn ghidra_server_callgraph(
+    state: tauri::State<'_, state::AppStateType>,
+    project_path: String,
+    root_offset: Option<String>,
+    depth: Option<u32>,
+    direction: Option<String>,
+    target_os: Option<String>,
+    module_name: Option<String>,
+    refresh: Option<bool>,
+) -> Result<CallGraphView, String> {
+    let target_os = target_os.unwrap_or_else(|| state.lock()
+        .ok()
+        .and_then(|s| s.server_info.as_ref().map(|info| info.target_os.clone()))
+        .unwrap_or_default());
+    let module_name = match module_name {
+        Some(module_name) => module_name,
+        None => ghidra_batch::project_module_name(&project_path).await.ok_or("Unknown module for this project")?,
+    };
+    if refresh.unwrap_or(false) {
+        let (target_os, module_name) = (target_os.clone(), module_name.clone());
+        db::run(move |conn| {
+            conn.execute(
+                "DELETE FROM ghidra_callgraph_cache WHERE target_os = ?1 AND module_name = ?2",
+                params![target_os, module_name],
+            ).map_err(|e| e.to_string())
+        }).await?;
+    }
+
+    let graph = load_call_graph(&project_path, &target_os, &module_name).await?;
+    let mut view = CallGraphView { success: graph.success, root: None, nodes: vec![], edges: vec![], truncated: false, error: graph.error.clone() };
+    if !graph.success {
+        return Ok(view);
+    }
+
+    let index: HashMap<&str, usize> = graph.nodes.iter().enumerate().map(|(i, n)| (n.offset.as_str(), i)).collect();
+    let edges: Vec<(usize, usize)> = graph.edges.iter()
+        .filter_map(|e| Some((*index.get(e.from.as_str())?, *index.get(e.to.as_str())?)))
+        .collect();
+
+    // depth per selected node, in BFS order
+    let mut selected: Vec<(usize, u32)> = Vec::new();
+    match root_offset.as_deref() {
+        Some(root) => {
+            let root_idx = resolve_call_graph_node(&graph, root)
+                .ok_or_else(|| format!("Function not found in call graph: {}", root))?;
+            view.root = Some(graph.nodes[root_idx].offset.clone());
+            let direction = direction.as_deref().unwrap_or("callees");
+            let (callees, callers) = (direction != "callers", direction != "callees");
+            let max_depth = depth.unwrap_or(2);
+            let mut seen = vec![false; graph.nodes.len()];
+            let mut queue = std::collections::VecDeque::from([(root_idx, 0u32)]);
+            seen[root_idx] = true;
+            while let Some((node, d)) = queue.pop_front() {
+                if selected.len() >= MAX_CALLGRAPH_VIEW_NODES {
+                    view.truncated = true;
+                    break;
+                }
+                selected.push((node, d));
+                if d >= max_depth {
+                    continue;
+                }
+                for &(from, to) in &edges {
+                    let next = if callees && from == node {
+                        to
+                    } else if callers && to == node {
+                        from
+                    } else {
+                        continue;
+                    };
+                    if !seen[next] {
+                        seen[next] = true;
+                        queue.push_back((next, d + 1));
+                    }
+                }
+            }
+        }
+        None => {
+            view.truncated = graph.nodes.len() > MAX_CALLGRAPH_VIEW_NODES;
+            selected = (0..graph.nodes.len().min(MAX_CALLGRAPH_VIEW_NODES)).map(|i| (i, 0)).collect();
+        }
+    }
+
+    let mut included = vec![false; graph.nodes.len()];
+    for &(i, _) in &selected {
+        included[i] = true;
+    }
+    view.nodes = selected.iter().map(|&(i, depth)| {
+        let node = &graph.nodes[i];
+        CallGraphViewNode {
+            name: node.name.clone(),
+            offset: node.offset.clone(),
+            depth,
+            indirect_calls: node.indirect_calls,
+            address_taken: node.address_taken,
+        }
+    }).collect();
+    view.edges = edges.iter()
+        .filter(|&&(from, to)| included[from] && included[to])
+        .map(|&(from, to)| CallGraphEdge { from: graph.nodes[from].offset.clone(), to: graph.nodes[to].offset.clone() })
+        .collect();
+    Ok(view)
+}
+
 // ============================================================================
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -5349,7 +6040,7 @@ async fn ghidra_get_xrefs(
     } else {
         ghidra_base.join("support").join("analyzeHeadless")
     };
-    
+
     if !analyzer_path.exists() {
         return Ok(GhidraXrefsResult {
             success: false,
@@ -5359,11 +6050,11 @@ async fn ghidra_get_xrefs(
             error: Some("Ghidra analyzeHeadless not found".to_string()),
         });
     }
-    
+
     let ghidra_dir = get_ghidra_projects_dir();
     let script_path = ghidra_dir.join("get_xrefs.py");
     let output_path = ghidra_dir.join("xrefs_output.txt");
-    
+
     // Ghidra Python script to get xrefs
     let script_content = format!(r#"#@runtime Jython
 # @category DynaDbg
@@ -5371,37 +6062,37 @@ async fn ghidra_get_xrefs(
 
 def get_xrefs_at_offset(offset_str):
     image_base = currentProgram.getImageBase()
-    
+
     offset_str = offset_str.strip()
     if offset_str.startswith("0x"):
         offset_str = offset_str[2:]
-    
+
     try:
         offset = int(offset_str, 16)
     except:
         return "Error: Invalid offset format: " + offset_str
-    
+
     addr = image_base.add(offset)
-    
+
     func = getFunctionContaining(addr)
     if func is None:
         func = getFunctionAt(addr)
-    
+
     if func is None:
         return "Error: No function found at address " + str(addr)
-    
+
     func_name = func.getName()
     func_entry = func.getEntryPoint()
     func_offset = func_entry.getOffset() - image_base.getOffset()
-    
+
     result = "TARGET_FUNCTION:" + func_name + "\n"
     result += "TARGET_ADDRESS:0x{{:x}}\n".format(func_offset)
     result += "XREFS:\n"
-    
+
     # Get references TO this function
     ref_manager = currentProgram.getReferenceManager()
     refs = ref_manager.getReferencesTo(func_entry)
-    
+
     xref_count = 0
     for ref in refs:
         if xref_count >= 100:  # Limit to 100 xrefs
@@ -5409,24 +6100,24 @@ def get_xrefs_at_offset(offset_str):
         from_addr = ref.getFromAddress()
         from_offset = from_addr.getOffset() - image_base.getOffset()
         ref_type = str(ref.getReferenceType())
-        
+
         # Skip external references (negative offsets or special addresses)
         if from_offset < 0:
             continue
-        
+
         # Get function containing the reference
         from_func = getFunctionContaining(from_addr)
         from_func_name = from_func.getName() if from_func else "unknown"
-        
+
         # Calculate offset within the function
         from_func_offset = "unknown"
         if from_func:
             func_entry_offset = from_func.getEntryPoint().getOffset() - image_base.getOffset()
             from_func_offset = "0x{{:x}}".format(from_offset - func_entry_offset)
-        
+
         result += "0x{{:x}}|{{}}|{{}}||{{}}\n".format(from_offset, from_func_name, ref_type, from_func_offset)
         xref_count += 1
-    
+
     return result
 
 offset = "{}"
@@ -5435,18 +6126,18 @@ result = get_xrefs_at_offset(offset)
 with open(r"{}", "w") as f:
     f.write(result)
 "#, function_address, output_path.to_string_lossy().replace("\\", "\\\\"));
-    
+
     fs::write(&script_path, &script_content)
         .await
         .map_err(|e| format!("Failed to write xref script: {}", e))?;
-    
+
     // Clean library name (without extension)
     // Ghidra stores imported programs with file_stem (no extension) as the program name
     let clean_lib_name = PathBuf::from(&library_name)
         .file_stem()
         .map(|s| s.to_string_lossy().to_string())
         .unwrap_or(library_name.clone());
-    
+
     // Use clean_lib_name (without extension) for -process option
     // Ghidra stores imported programs without file extensions
     let output = hide_console_window(&mut Command::new(&analyzer_path))
@@ -5459,7 +6150,7 @@ with open(r"{}", "w") as f:
         .arg(script_path.to_string_lossy().to_string())
         .output()
         .map_err(|e| format!("Failed to run Ghidra: {}", e))?;
-    
+
     if !output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -5486,10 +6177,10 @@ with open(r"{}", "w") as f:
             });
         }
     };
-    
+
     let _ = fs::remove_file(&script_path).await;
     let _ = fs::remove_file(&output_path).await;
-    
+
     if xref_output.starts_with("Error:") {
         return Ok(GhidraXrefsResult {
             success: false,
@@ -5499,12 +6190,12 @@ with open(r"{}", "w") as f:
             error: Some(xref_output),
         });
     }
-    
+
     let mut target_function = String::new();
     let mut target_address = String::new();
     let mut xrefs: Vec<XrefEntry> = vec![];
     let mut in_xrefs = false;
-    
+
     for line in xref_output.lines() {
         if line.starts_with("TARGET_FUNCTION:") {
             target_function = line.replace("TARGET_FUNCTION:", "");
@@ -5525,7 +6216,7 @@ with open(r"{}", "w") as f:
             }
         }
     }
-    
+
     Ok(GhidraXrefsResult {
         success: true,
         target_function,
@@ -5562,7 +6253,7 @@ async fn ghidra_get_functions(
     } else {
         ghidra_base.join("support").join("analyzeHeadless")
     };
-    
+
     if !analyzer_path.exists() {
         return Ok(GhidraFunctionListResult {
             success: false,
@@ -5570,11 +6261,11 @@ async fn ghidra_get_functions(
             error: Some("Ghidra analyzeHeadless not found".to_string()),
         });
     }
-    
+
     let ghidra_dir = get_ghidra_projects_dir();
     let script_path = ghidra_dir.join("get_functions.py");
     let output_path = ghidra_dir.join("functions_output.txt");
-    
+
     // Ghidra Python script to get all functions
     let script_content = format!(r#"#@runtime Jython
 # @category DynaDbg
@@ -5583,19 +6274,19 @@ async fn ghidra_get_functions(
 def get_all_functions():
     image_base = currentProgram.getImageBase()
     func_manager = currentProgram.getFunctionManager()
-    
+
     result = "FUNCTIONS:\n"
-    
+
     for func in func_manager.getFunctions(True):  # True = forward iteration
         func_name = func.getName()
         entry_point = func.getEntryPoint()
         offset = entry_point.getOffset() - image_base.getOffset()
         body = func.getBody()
         size = body.getNumAddresses() if body else 0
-        
+
         # Format: name|offset|size
         result += "{{}}|0x{{:x}}|{{}}\n".format(func_name, offset, size)
-    
+
     return result
 
 result = get_all_functions()
@@ -5604,18 +6295,18 @@ import codecs
 with codecs.open(r"{}", "w", "utf-8") as f:
     f.write(result)
 "#, output_path.to_string_lossy().replace("\\", "\\\\"));
-    
+
     fs::write(&script_path, &script_content)
         .await
         .map_err(|e| format!("Failed to write functions script: {}", e))?;
-    
+
     // Clean library name (without extension)
     // Ghidra stores imported programs with file_stem (no extension) as the program name
     let clean_lib_name = PathBuf::from(&library_name)
         .file_stem()
         .map(|s| s.to_string_lossy().to_string())
         .unwrap_or(library_name.clone());
-    
+
     // Use clean_lib_name (without extension) for -process option
     // Ghidra stores imported programs without file extensions
     let output = hide_console_window(&mut Command::new(&analyzer_path))
@@ -5628,7 +6319,7 @@ with codecs.open(r"{}", "w", "utf-8") as f:
         .arg(script_path.to_string_lossy().to_string())
         .output()
         .map_err(|e| format!("Failed to run Ghidra: {}", e))?;
-    
+
     if !output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -5651,13 +6342,13 @@ with codecs.open(r"{}", "w", "utf-8") as f:
             });
         }
     };
-    
+
     let _ = fs::remove_file(&script_path).await;
     let _ = fs::remove_file(&output_path).await;
-    
+
     let mut functions: Vec<GhidraFunctionEntry> = vec![];
     let mut in_functions = false;
-    
+
     for line in func_output.lines() {
         if line.starts_with("FUNCTIONS:") {
             in_functions = true;
@@ -5672,7 +6363,7 @@ with codecs.open(r"{}", "w", "utf-8") as f:
             }
         }
     }
-    
+
     Ok(GhidraFunctionListResult {
         success: true,
         functions,
@@ -5682,7 +6373,7 @@ with codecs.open(r"{}", "w", "utf-8") as f:
 
 /// Save analyzed module and its functions to SQLite database
 #[tauri::command]
-fn save_ghidra_functions_to_db(
+async fn save_ghidra_functions_to_db(
     target_os: String,
     module_name: String,
     module_path: String,
@@ -5690,138 +6381,135 @@ fn save_ghidra_functions_to_db(
     project_path: String,
     functions: Vec<GhidraFunctionEntry>,
 ) -> Result<bool, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    let analyzed_at = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_secs() as i64)
-        .unwrap_or(0);
-    
-    // Insert or replace the module record
-    conn.execute(
-        "INSERT OR REPLACE INTO analyzed_modules (target_os, module_name, module_path, local_path, project_path, analyzed_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        params![target_os, module_name, module_path, local_path, project_path, analyzed_at],
-    ).map_err(|e| e.to_string())?;
-    
-    // Get the module ID
-    let module_id: i64 = conn.query_row(
-        "SELECT id FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
-        params![target_os, module_name],
-        |row| row.get(0),
-    ).map_err(|e| e.to_string())?;
-    
-    // Delete existing functions for this module
-    conn.execute(
-        "DELETE FROM module_functions WHERE module_id = ?1",
-        params![module_id],
-    ).map_err(|e| e.to_string())?;
-    
-    // Insert all functions
-    for func in &functions {
+    db::run(move |conn| {
+        let analyzed_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        // Insert or replace the module record
         conn.execute(
-            "INSERT INTO module_functions (module_id, name, address, size) VALUES (?1, ?2, ?3, ?4)",
-            params![module_id, func.name, func.address, func.size],
+            "INSERT OR REPLACE INTO analyzed_modules (target_os, module_name, module_path, local_path, project_path, analyzed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![target_os, module_name, module_path, local_path, project_path, analyzed_at],
         ).map_err(|e| e.to_string())?;
-    }
-    
-    Ok(true)
+
+        // Get the module ID
+        let module_id: i64 = conn.query_row(
+            "SELECT id FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
+            params![target_os, module_name],
+            |row| row.get(0),
+        ).map_err(|e| e.to_string())?;
+
+        // Delete existing functions for this module
+        conn.execute(
+            "DELETE FROM module_functions WHERE module_id = ?1",
+            params![module_id],
+        ).map_err(|e| e.to_string())?;
+
+        // Insert all functions
+        for func in &functions {
+            conn.execute(
+                "INSERT INTO module_functions (module_id, name, address, size) VALUES (?1, ?2, ?3, ?4)",
+                params![module_id, func.name, func.address, func.size],
+            ).map_err(|e| e.to_string())?;
+        }
+        symbolizer::invalidate_module(&target_os, &module_name);
+
+        Ok(true)
+    }).await
 }
 
 /// Get functions from SQLite database for a module
 #[tauri::command]
-fn get_ghidra_functions_from_db(
+async fn get_ghidra_functions_from_db(
     target_os: String,
     module_name: String,
 ) -> Result<GhidraFunctionListResult, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    // Get the module ID
-    let module_id: Result<i64, _> = conn.query_row(
-        "SELECT id FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
-        params![target_os, module_name],
-        |row| row.get(0),
-    );
-    
-    let module_id = match module_id {
-        Ok(id) => id,
-        Err(_) => {
-            return Ok(GhidraFunctionListResult {
-                success: false,
-                functions: vec![],
-                error: Some("Module not found in database".to_string()),
-            });
-        }
-    };
-    
-    // Get all functions for this module
-    let mut stmt = conn.prepare(
-        "SELECT name, address, size FROM module_functions WHERE module_id = ?1"
-    ).map_err(|e| e.to_string())?;
-    
-    let functions: Vec<GhidraFunctionEntry> = stmt.query_map(params![module_id], |row| {
-        Ok(GhidraFunctionEntry {
-            name: row.get(0)?,
-            address: row.get(1)?,
-            size: row.get(2)?,
+    db::run(move |conn| {
+        // Get the module ID
+        let module_id: Result<i64, _> = conn.query_row(
+            "SELECT id FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
+            params![target_os, module_name],
+            |row| row.get(0),
+        );
+
+        let module_id = match module_id {
+            Ok(id) => id,
+            Err(_) => {
+                return Ok(GhidraFunctionListResult {
+                    success: false,
+                    functions: vec![],
+                    error: Some("Module not found in database".to_string()),
+                });
+            }
+        };
+
+        // Get all functions for this module
+        let mut stmt = conn.prepare(
+            "SELECT name, address, size FROM module_functions WHERE module_id = ?1"
+        ).map_err(|e| e.to_string())?;
+
+        let functions: Vec<GhidraFunctionEntry> = stmt.query_map(params![module_id], |row| {
+            Ok(GhidraFunctionEntry {
+                name: row.get(0)?,
+                address: row.get(1)?,
+                size: row.get(2)?,
+            })
+        }).map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+
+        Ok(GhidraFunctionListResult {
+            success: true,
+            functions,
+            error: None,
         })
-    }).map_err(|e| e.to_string())?
-    .filter_map(|r| r.ok())
-    .collect();
-    
-    Ok(GhidraFunctionListResult {
-        success: true,
-        functions,
-        error: None,
-    })
+    }).await
 }
 
 /// Check if a module is analyzed in the database
 #[tauri::command]
-fn is_module_analyzed_in_db(
+async fn is_module_analyzed_in_db(
     target_os: String,
     module_name: String,
 ) -> Result<bool, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
-        params![target_os, module_name],
-        |row| row.get(0),
-    ).unwrap_or(0);
-    
-    Ok(count > 0)
+    db::run(move |conn| {
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
+            params![target_os, module_name],
+            |row| row.get(0),
+        ).unwrap_or(0);
+
+        Ok(count > 0)
+    }).await
 }
 
 /// Get module info from database
 #[tauri::command]
-fn get_module_info_from_db(
+async fn get_module_info_from_db(
     target_os: String,
     module_name: String,
 ) -> Result<Option<AnalyzedModuleInfo>, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    let result = conn.query_row(
-        "SELECT module_path, local_path, project_path, analyzed_at FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
-        params![target_os, module_name],
-        |row| {
-            Ok(AnalyzedModuleInfo {
-                module_path: row.get(0)?,
-                local_path: row.get(1)?,
-                project_path: row.get(2)?,
-                analyzed_at: row.get(3)?,
-            })
-        },
-    );
-    
-    match result {
-        Ok(info) => Ok(Some(info)),
-        Err(_) => Ok(None),
-    }
+    db::run(move |conn| {
+        let result = conn.query_row(
+            "SELECT module_path, local_path, project_path, analyzed_at FROM analyzed_modules WHERE target_os = ?1 AND module_name = ?2",
+            params![target_os, module_name],
+            |row| {
+                Ok(AnalyzedModuleInfo {
+                    module_path: row.get(0)?,
+                    local_path: row.get(1)?,
+                    project_path: row.get(2)?,
+                    analyzed_at: row.get(3)?,
+                })
+            },
+        );
+
+        match result {
+            Ok(info) => Ok(Some(info)),
+            Err(_) => Ok(None),
+        }
+    }).await
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -5834,51 +6522,55 @@ struct AnalyzedModuleInfo {
 
 /// Simple save functions to SQLite (JSON string version for frontend compatibility)
 #[tauri::command]
-fn save_ghidra_functions(
+async fn save_ghidra_functions(
     target_os: String,
     module_name: String,
     functions_json: String,
 ) -> Result<bool, String> {
     let _functions: Vec<GhidraFunctionEntry> = serde_json::from_str(&functions_json)
         .map_err(|e| format!("Failed to parse functions JSON: {}", e))?;
-    
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    // Use simple key-value style storage with JSON
-    conn.execute(
-        "INSERT OR REPLACE INTO ghidra_functions_cache (target_os, module_name, functions_json, updated_at)
-         VALUES (?1, ?2, ?3, datetime('now'))",
-        params![target_os, module_name, functions_json],
-    ).map_err(|e| e.to_string())?;
-    
-    Ok(true)
+
+    db::run(move |conn| {
+        // Use simple key-value style storage with JSON
+        conn.execute(
+            "INSERT OR REPLACE INTO ghidra_functions_cache (target_os, module_name, functions_json, updated_at, cache_version)
+             VALUES (?1, ?2, ?3, datetime('now'), ?4)",
+            params![
+                target_os,
+                module_name,
+                secure_store::seal_value("ghidra_functions_cache", "functions_json", &functions_json)?,
+                cache_versions::stamp(conn, &target_os, &module_name),
+            ],
+        ).map_err(|e| e.to_string())?;
+        symbolizer::invalidate_module(&target_os, &module_name);
+        Ok(true)
+    }).await
 }
 
 /// Simple get functions from SQLite (JSON string version for frontend compatibility)
 #[tauri::command]
-fn get_ghidra_functions(
+async fn get_ghidra_functions(
     target_os: String,
     module_name: String,
 ) -> Result<Option<String>, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    let result: Result<String, _> = conn.query_row(
-        "SELECT functions_json FROM ghidra_functions_cache WHERE target_os = ?1 AND module_name = ?2",
-        params![target_os, module_name],
-        |row| row.get(0),
-    );
-    
-    match result {
-        Ok(json) => Ok(Some(json)),
-        Err(_) => Ok(None),
-    }
+    db::run(move |conn| {
+        let result: Result<String, _> = conn.query_row(
+            "SELECT functions_json FROM ghidra_functions_cache WHERE target_os = ?1 AND module_name = ?2
+             AND (cache_version IS NULL OR cache_version = ?3)",
+            params![target_os, module_name, cache_versions::stamp(conn, &target_os, &module_name)],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(json) => secure_store::open_value("ghidra_functions_cache", "functions_json", json).map(Some),
+            Err(_) => Ok(None),
+        }
+    }).await
 }
 
 /// Save decompiled code to SQLite cache
 #[tauri::command]
-fn save_decompile_cache(
+async fn save_decompile_cache(
     target_os: String,
     module_name: String,
     function_address: String,
@@ -5886,144 +6578,194 @@ fn save_decompile_cache(
     decompiled_code: String,
     line_mapping_json: Option<String>,
 ) -> Result<bool, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    conn.execute(
-        "INSERT OR REPLACE INTO ghidra_decompile_cache 
-         (target_os, module_name, function_address, function_name, decompiled_code, line_mapping_json, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'))",
-        params![target_os, module_name, function_address, function_name, decompiled_code, line_mapping_json],
-    ).map_err(|e| e.to_string())?;
-    
-    Ok(true)
+    let decompiled_code = secure_store::seal_value("ghidra_decompile_cache", "decompiled_code", &decompiled_code)?;
+    let line_mapping_json = line_mapping_json
+        .map(|json| secure_store::seal_value("ghidra_decompile_cache", "line_mapping_json", &json))
+        .transpose()?;
+
+    db::run(move |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO ghidra_decompile_cache
+             (target_os, module_name, function_address, function_name, decompiled_code, line_mapping_json, updated_at, cache_version)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'), ?7)",
+            params![
+                target_os,
+                module_name,
+                function_address,
+                function_name,
+                decompiled_code,
+                line_mapping_json,
+                cache_versions::stamp(conn, &target_os, &module_name),
+            ],
+        ).map_err(|e| e.to_string())?;
+        smc_monitor::clear_stale_function(conn, &target_os, &module_name, &function_address);
+        Ok(true)
+    }).await
+}
+
+/// Cached decompilation of a function, None on a miss
+fn load_decompile_cache(
+    conn: &Connection,
+    target_os: &str,
+    module_name: &str,
+    function_address: &str,
+) -> Option<GhidraDecompileResult> {
+    let mut stopwatch = latency::Stopwatch::start("get_decompile_cache");
+
+    let row = decompile_cache::cached_row(conn, target_os, module_name, function_address);
+    stopwatch.mark("query");
+
+    let Some(decompile_cache::CachedRow { function_name, decompiled_code, line_mapping_json }) = row else {
+        stopwatch.set_cache_hit(false);
+        stopwatch.finish(&None::<GhidraDecompileResult>);
+        return None;
+    };
+
+    // Values that cannot be decrypted are treated as cache misses
+    let Ok(decompiled_code) = secure_store::open_value("ghidra_decompile_cache", "decompiled_code", decompiled_code) else {
+        stopwatch.set_cache_hit(false);
+        stopwatch.finish(&None::<GhidraDecompileResult>);
+        return None;
+    };
+    let line_mapping_json = line_mapping_json
+        .and_then(|json| secure_store::open_value("ghidra_decompile_cache", "line_mapping_json", json).ok());
+    stopwatch.mark("decrypt");
+
+    let line_mapping: Option<std::collections::HashMap<String, String>> = line_mapping_json
+        .and_then(|json| serde_json::from_str(&json).ok());
+    stopwatch.mark("parse");
+    stopwatch.set_cache_hit(true);
+
+    let mut result = GhidraDecompileResult {
+        success: true,
+        function_name: if function_name.is_empty() { None } else { Some(function_name) },
+        address: Some(function_address.to_string()),
+        decompiled_code: Some(decompiled_code),
+        line_mapping,
+        tokens: None,
+        error: None,
+        timing: None,
+    };
+    result.timing = stopwatch.finish(&result);
+    Some(result)
 }
 
 /// Get decompiled code from SQLite cache
 #[tauri::command]
-fn get_decompile_cache(
+async fn get_decompile_cache(
     target_os: String,
     module_name: String,
     function_address: String,
 ) -> Result<Option<GhidraDecompileResult>, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    let result = conn.query_row(
-        "SELECT function_name, decompiled_code, line_mapping_json FROM ghidra_decompile_cache 
-         WHERE target_os = ?1 AND module_name = ?2 AND function_address = ?3",
-        params![target_os, module_name, function_address],
-        |row| {
-            let function_name: String = row.get(0)?;
-            let decompiled_code: String = row.get(1)?;
-            let line_mapping_json: Option<String> = row.get(2)?;
-            
-            let line_mapping: Option<std::collections::HashMap<String, String>> = line_mapping_json
-                .and_then(|json| serde_json::from_str(&json).ok());
-            
-            Ok(GhidraDecompileResult {
-                success: true,
-                function_name: if function_name.is_empty() { None } else { Some(function_name) },
-                address: Some(function_address.clone()),
-                decompiled_code: Some(decompiled_code),
-                line_mapping,
-                tokens: None,
-                error: None,
-            })
-        },
-    );
-    
-    match result {
-        Ok(r) => Ok(Some(r)),
-        Err(_) => Ok(None),
-    }
+    db::run(move |conn| Ok(load_decompile_cache(conn, &target_os, &module_name, &function_address))).await
 }
 
 /// Save xrefs to SQLite cache
 #[tauri::command]
-fn save_xref_cache(
+async fn save_xref_cache(
     target_os: String,
     module_name: String,
     function_address: String,
     function_name: String,
     xrefs_json: String,
 ) -> Result<bool, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    conn.execute(
-        "INSERT OR REPLACE INTO ghidra_xref_cache 
-         (target_os, module_name, function_address, function_name, xrefs_json, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))",
-        params![target_os, module_name, function_address, function_name, xrefs_json],
-    ).map_err(|e| e.to_string())?;
-    
-    Ok(true)
+    let xrefs_json = secure_store::seal_value("ghidra_xref_cache", "xrefs_json", &xrefs_json)?;
+    db::run(move |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO ghidra_xref_cache
+             (target_os, module_name, function_address, function_name, xrefs_json, updated_at, cache_version)
+             VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'), ?6)",
+            params![
+                target_os,
+                module_name,
+                function_address,
+                function_name,
+                xrefs_json,
+                cache_versions::stamp(conn, &target_os, &module_name),
+            ],
+        ).map_err(|e| e.to_string())?;
+        Ok(true)
+    }).await
 }
 
 /// Get xrefs from SQLite cache
 #[tauri::command]
-fn get_xref_cache(
+async fn get_xref_cache(
     target_os: String,
     module_name: String,
     function_address: String,
 ) -> Result<Option<GhidraXrefsResult>, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    let result = conn.query_row(
-        "SELECT function_name, xrefs_json FROM ghidra_xref_cache 
-         WHERE target_os = ?1 AND module_name = ?2 AND function_address = ?3",
-        params![target_os, module_name, function_address],
-        |row| {
-            let function_name: String = row.get(0)?;
-            let xrefs_json: String = row.get(1)?;
-            
-            let xrefs: Vec<XrefEntry> = serde_json::from_str(&xrefs_json).unwrap_or_default();
-            
-            Ok(GhidraXrefsResult {
-                success: true,
-                target_function: function_name,
-                target_address: function_address.clone(),
-                xrefs,
-                error: None,
-            })
-        },
-    );
-    
-    match result {
-        Ok(r) => Ok(Some(r)),
-        Err(_) => Ok(None),
-    }
+    db::run(move |conn| {
+        let result = conn.query_row(
+            "SELECT function_name, xrefs_json FROM ghidra_xref_cache
+             WHERE target_os = ?1 AND module_name = ?2 AND function_address = ?3
+             AND (cache_version IS NULL OR cache_version = ?4)",
+            params![target_os, module_name, function_address, cache_versions::stamp(conn, &target_os, &module_name)],
+            |row| {
+                let function_name: String = row.get(0)?;
+                let xrefs_json: String = row.get(1)?;
+                let xrefs_json = secure_store::open_value("ghidra_xref_cache", "xrefs_json", xrefs_json)
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, e.into()))?;
+
+                let xrefs: Vec<XrefEntry> = serde_json::from_str(&xrefs_json).unwrap_or_default();
+
+                Ok(GhidraXrefsResult {
+                    success: true,
+                    target_function: function_name,
+                    target_address: function_address.clone(),
+                    xrefs,
+                    error: None,
+                })
+            },
+        );
+
+        match result {
+            Ok(r) => Ok(Some(r)),
+            Err(_) => Ok(None),
+        }
+    }).await
 }
 
 /// Clear all Ghidra cache from SQLite database
 #[tauri::command]
-fn clear_ghidra_cache() -> Result<bool, String> {
-    let db_guard = GHIDRA_DB.lock().map_err(|e| e.to_string())?;
-    let conn = db_guard.as_ref().ok_or("Database not initialized")?;
-    
-    // Clear all cache tables
-    conn.execute("DELETE FROM ghidra_functions_cache", [])
-        .map_err(|e| format!("Failed to clear functions cache: {}", e))?;
-    
-    conn.execute("DELETE FROM ghidra_decompile_cache", [])
-        .map_err(|e| format!("Failed to clear decompile cache: {}", e))?;
-    
-    conn.execute("DELETE FROM ghidra_xref_cache", [])
-        .map_err(|e| format!("Failed to clear xref cache: {}", e))?;
-    
-    conn.execute("DELETE FROM analyzed_modules", [])
-        .map_err(|e| format!("Failed to clear analyzed modules: {}", e))?;
-    
-    conn.execute("DELETE FROM module_functions", [])
-        .map_err(|e| format!("Failed to clear module functions: {}", e))?;
-    
-    // VACUUM to reclaim space
-    conn.execute("VACUUM", [])
-        .map_err(|e| format!("Failed to vacuum database: {}", e))?;
-    
-    Ok(true)
+async fn clear_ghidra_cache() -> Result<bool, String> {
+    db::run(move |conn| {
+        // Clear all cache tables
+        conn.execute("DELETE FROM ghidra_functions_cache", [])
+            .map_err(|e| format!("Failed to clear functions cache: {}", e))?;
+
+        conn.execute("DELETE FROM ghidra_decompile_cache", [])
+            .map_err(|e| format!("Failed to clear decompile cache: {}", e))?;
+
+        conn.execute("DELETE FROM ghidra_xref_cache", [])
+            .map_err(|e| format!("Failed to clear xref cache: {}", e))?;
+
+        conn.execute("DELETE FROM ghidra_callgraph_cache", [])
+            .map_err(|e| format!("Failed to clear call graph cache: {}", e))?;
+
+        conn.execute("DELETE FROM ghidra_data_cache", [])
+            .map_err(|e| format!("Failed to clear data cache: {}", e))?;
+
+        conn.execute("DELETE FROM ghidra_search_cache", [])
+            .map_err(|e| format!("Failed to clear search cache: {}", e))?;
+        data_overlay::invalidate_all();
+
+        conn.execute("DELETE FROM analyzed_modules", [])
+            .map_err(|e| format!("Failed to clear analyzed modules: {}", e))?;
+
+        conn.execute("DELETE FROM module_functions", [])
+            .map_err(|e| format!("Failed to clear module functions: {}", e))?;
+        symbolizer::invalidate_all();
+
+        conn.execute("DELETE FROM ghidra_stale_functions", [])
+            .map_err(|e| format!("Failed to clear stale functions: {}", e))?;
+
+        // VACUUM to reclaim space
+        conn.execute("VACUUM", [])
+            .map_err(|e| format!("Failed to vacuum database: {}", e))?;
+
+        Ok(true)
+    }).await
 }
 
 /// Format C/C++ code using clang-format if available, otherwise use simple Rust formatter
@@ -6033,7 +6775,7 @@ async fn format_cpp_code(code: &str) -> Option<String> {
     if let Some(formatted) = try_clang_format(code).await {
         return Some(formatted);
     }
-    
+
     // Fall back to simple Rust-based formatter
     Some(simple_cpp_format(code))
 }
@@ -6070,27 +6812,27 @@ async fn try_clang_format(code: &str) -> Option<String> {
             None
         }
     };
-    
+
     let clang_format = clang_format?;
-    
+
     // Create a temp file with the code
     let ghidra_dir = get_ghidra_projects_dir();
     let temp_file = ghidra_dir.join("temp_format.c");
-    
+
     if let Err(_) = fs::write(&temp_file, code).await {
         return None;
     }
-    
+
     // Run clang-format
     let output = hide_console_window(&mut Command::new(&clang_format))
         .arg("-style={BasedOnStyle: LLVM, IndentWidth: 2, ColumnLimit: 100}")
         .arg(&temp_file)
         .output()
         .ok()?;
-    
+
     // Clean up temp file
     let _ = fs::remove_file(&temp_file).await;
-    
+
     if output.status.success() {
         String::from_utf8(output.stdout).ok()
     } else {
@@ -6105,35 +6847,35 @@ fn simple_cpp_format(code: &str) -> String {
     let mut result = String::new();
     let mut indent_level: i32 = 0;
     let indent_str = "  "; // 2 spaces
-    
+
     for line in code.lines() {
         let trimmed = line.trim();
-        
+
         // Skip empty lines but preserve them
         if trimmed.is_empty() {
             result.push('\n');
             continue;
         }
-        
+
         // Decrease indent before closing braces
         if trimmed.starts_with('}') || trimmed.starts_with(')') {
             indent_level = (indent_level - 1).max(0);
         }
-        
+
         // Add indentation
         for _ in 0..indent_level {
             result.push_str(indent_str);
         }
-        
+
         // Add the trimmed line
         result.push_str(trimmed);
         result.push('\n');
-        
+
         // Increase indent after opening braces
         if trimmed.ends_with('{') {
             indent_level += 1;
         }
-        
+
         // Handle single-line cases like "} else {"
         if trimmed.contains('{') && !trimmed.ends_with('{') && !trimmed.starts_with("//") {
             // Count braces
@@ -6142,7 +6884,7 @@ fn simple_cpp_format(code: &str) -> String {
             indent_level = (indent_level + opens - closes).max(0);
         }
     }
-    
+
     result
 }
 
@@ -6153,11 +6895,11 @@ async fn check_ghidra_analysis(library_name: String) -> Result<GhidraAnalysisSta
         .file_stem()
         .map(|s| s.to_string_lossy().to_string())
         .unwrap_or(library_name.clone());
-    
+
     let ghidra_dir = get_ghidra_projects_dir();
     let project_dir = ghidra_dir.join(&clean_name);
     let gpr_file = project_dir.join(format!("{}.gpr", clean_name));
-    
+
     if gpr_file.exists() {
         Ok(GhidraAnalysisStatus {
             library_path: library_name,
@@ -6185,7 +6927,7 @@ fn path_exists(path: String) -> bool {
 #[tauri::command]
 async fn read_local_text_file(file_path: String) -> Result<String, String> {
     use tokio::fs::read_to_string;
-    
+
     read_to_string(&file_path)
         .await
         .map_err(|e| format!("Failed to read file '{}': {}", file_path, e))
@@ -6195,12 +6937,12 @@ async fn read_local_text_file(file_path: String) -> Result<String, String> {
 #[tauri::command]
 async fn select_folder_dialog(title: String) -> Result<Option<String>, String> {
     use rfd::AsyncFileDialog;
-    
+
     let dialog = AsyncFileDialog::new()
         .set_title(&title);
-    
+
     let folder = dialog.pick_folder().await;
-    
+
     Ok(folder.map(|f| f.path().to_string_lossy().to_string()))
 }
 
@@ -6215,16 +6957,16 @@ async fn save_binary_file_dialog(
 ) -> Result<Option<String>, String> {
     use rfd::AsyncFileDialog;
     use tokio::fs;
-    
+
     let extensions: Vec<&str> = filter_extensions.iter().map(|s| s.as_str()).collect();
-    
+
     let dialog = AsyncFileDialog::new()
         .set_title(&title)
         .set_file_name(&default_filename)
         .add_filter(&filter_name, &extensions);
-    
+
     let file = dialog.save_file().await;
-    
+
     if let Some(file_handle) = file {
         let path = file_handle.path().to_string_lossy().to_string();
         fs::write(file_handle.path(), &data)
@@ -6240,13 +6982,13 @@ async fn save_binary_file_dialog(
 #[tauri::command]
 async fn open_pointermap_files_dialog() -> Result<Vec<PointerMapFileInfo>, String> {
     use rfd::AsyncFileDialog;
-    
+
     let dialog = AsyncFileDialog::new()
         .set_title("Select PointerMap Files")
         .add_filter("PointerMap Files", &["dptr"]);
-    
+
     let files = dialog.pick_files().await;
-    
+
     if let Some(file_handles) = files {
         let result: Vec<PointerMapFileInfo> = file_handles
             .iter()
@@ -6304,16 +7046,16 @@ async fn run_pointer_scan(
     use std::collections::{HashMap, HashSet};
     use std::sync::atomic::{AtomicU64, AtomicBool, Ordering};
     use std::sync::{Arc, Mutex};
-    
+
     // Reset cancel flag at the start
     PTRSCAN_CANCEL.store(false, Ordering::Relaxed);
-    
+
     let max_results = max_results.unwrap_or(1000) as usize;
-    
+
     if files.len() < 2 {
         return Err("At least 2 PointerMap files are required".to_string());
     }
-    
+
     // Parse target addresses
     let target_addresses: Vec<u64> = files.iter()
         .map(|file| {
@@ -6324,7 +7066,7 @@ async fn run_pointer_scan(
         })
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| format!("Invalid target address: {}", e))?;
-    
+
     // Emit initial progress - loading files
     let _ = app_handle.emit("ptr-scan-progress", serde_json::json!({
         "nodesProcessed": 0,
@@ -6333,7 +7075,7 @@ async fn run_pointer_scan(
         "totalFiles": files.len(),
         "phase": "loading"
     }));
-    
+
     // Load all files first (async)
     let mut file_data: Vec<Vec<u8>> = Vec::new();
     for (idx, file) in files.iter().enumerate() {
@@ -6344,15 +7086,15 @@ async fn run_pointer_scan(
             "totalFiles": files.len(),
             "phase": "loading"
         }));
-        
+
         let compressed_data = tokio::fs::read(&file.path)
             .await
             .map_err(|e| format!("Failed to read file {}: {}", file.path, e))?;
         file_data.push(compressed_data);
     }
-    
+
     let total_files = files.len();
-    
+
     // Emit decompressing phase
     let _ = app_handle.emit("ptr-scan-progress", serde_json::json!({
         "nodesProcessed": 0,
@@ -6361,14 +7103,14 @@ async fn run_pointer_scan(
         "totalFiles": total_files,
         "phase": "decompressing"
     }));
-    
+
     // Shared progress state - using std primitives for cross-thread access
     let nodes_counter = Arc::new(AtomicU64::new(0));
     let chains_counter = Arc::new(AtomicU64::new(0));
     let file_idx_counter = Arc::new(AtomicU64::new(0));
     let scan_complete = Arc::new(AtomicBool::new(false));
     let phase_str = Arc::new(Mutex::new(String::from("scanning")));
-    
+
     // Clone for progress thread (std::thread, not tokio)
     let nodes_for_progress = Arc::clone(&nodes_counter);
     let chains_for_progress = Arc::clone(&chains_counter);
@@ -6376,7 +7118,7 @@ async fn run_pointer_scan(
     let complete_for_progress = Arc::clone(&scan_complete);
     let phase_for_progress = Arc::clone(&phase_str);
     let app_handle_clone = app_handle.clone();
-    
+
     // Spawn progress emitter in a std::thread (not tokio) so it runs independently
     let progress_thread = std::thread::spawn(move || {
         let mut last_nodes = 0u64;
@@ -6384,16 +7126,16 @@ async fn run_pointer_scan(
         let mut last_phase = String::new();
         loop {
             std::thread::sleep(std::time::Duration::from_millis(100));
-            
+
             if complete_for_progress.load(Ordering::Relaxed) {
                 break;
             }
-            
+
             let nodes = nodes_for_progress.load(Ordering::Relaxed);
             let chains = chains_for_progress.load(Ordering::Relaxed);
             let file_idx = file_idx_for_progress.load(Ordering::Relaxed);
             let phase = phase_for_progress.lock().unwrap().clone();
-            
+
             // Emit if there's any change in nodes, chains, or phase
             if nodes != last_nodes || chains != last_chains || phase != last_phase {
                 last_nodes = nodes;
@@ -6409,19 +7151,19 @@ async fn run_pointer_scan(
             }
         }
     });
-    
+
     // Clone counters for the blocking computation
     let nodes_for_scan = Arc::clone(&nodes_counter);
     let chains_for_scan = Arc::clone(&chains_counter);
     let file_idx_for_scan = Arc::clone(&file_idx_counter);
     let complete_for_scan = Arc::clone(&scan_complete);
     let phase_for_scan = Arc::clone(&phase_str);
-    
+
     // Move heavy computation to blocking thread pool
     let result = tokio::task::spawn_blocking(move || {
         // Update phase
         *phase_for_scan.lock().unwrap() = "decompressing".to_string();
-        
+
         // Decompress and parse all files in parallel
         let all_pointer_maps: Vec<PointerMapData> = file_data
             .par_iter()
@@ -6431,16 +7173,16 @@ async fn run_pointer_scan(
                 parse_pointer_map(&data)
             })
             .collect::<Result<Vec<_>, String>>()?;
-        
+
         // Update phase to scanning
         *phase_for_scan.lock().unwrap() = "scanning".to_string();
-        
+
         // Optimized reverse map with sorted keys for binary search
         struct OptimizedReverseMap {
             sorted_targets: Vec<u64>,
             map: HashMap<u64, Vec<(u64, Option<(u32, u32)>)>>,
         }
-        
+
         // Build reverse maps in parallel
         let reverse_maps: Vec<OptimizedReverseMap> = all_pointer_maps
             .par_iter()
@@ -6456,14 +7198,14 @@ async fn run_pointer_scan(
                 OptimizedReverseMap { sorted_targets, map }
             })
             .collect();
-        
+
         #[derive(Clone, Debug, Hash, Eq, PartialEq)]
         struct NormalizedChain {
             base_module: String,
             base_offset: u32,
             offsets: Vec<i64>,
         }
-        
+
         // Parallel BFS chain finder using level-by-level parallelism
         fn find_chains_parallel(
             target_addr: u64,
@@ -6479,20 +7221,20 @@ async fn run_pointer_scan(
             let chains: Arc<Mutex<Vec<NormalizedChain>>> = Arc::new(Mutex::new(Vec::new()));
             let visited: Arc<Mutex<HashMap<u64, usize>>> = Arc::new(Mutex::new(HashMap::new()));
             let found_enough = Arc::new(AtomicBool::new(false));
-            
+
             // Start with target address
             let mut current_level: Vec<(u64, Vec<i64>)> = vec![(target_addr, vec![])];
-            
+
             for depth in 0..max_depth as usize {
                 if current_level.is_empty() || found_enough.load(Ordering::Relaxed) {
                     break;
                 }
-                
+
                 // Check for cancellation
                 if PTRSCAN_CANCEL.load(Ordering::Relaxed) {
                     break;
                 }
-                
+
                 // Process current level in parallel
                 let next_level: Vec<(u64, Vec<i64>)> = current_level
                     .par_iter()
@@ -6500,13 +7242,13 @@ async fn run_pointer_scan(
                         if found_enough.load(Ordering::Relaxed) || PTRSCAN_CANCEL.load(Ordering::Relaxed) {
                             return vec![];
                         }
-                        
+
                         let current_addr = *current_addr;
                         let current_depth = offsets.len();
-                        
+
                         // Update progress
                         nodes_counter.fetch_add(1, Ordering::Relaxed);
-                        
+
                         // Check visited
                         {
                             let mut visited_lock = visited.lock().unwrap();
@@ -6517,47 +7259,47 @@ async fn run_pointer_scan(
                             }
                             visited_lock.insert(current_addr, current_depth);
                         }
-                        
+
                         // Binary search for range
                         let range_start = current_addr.saturating_sub(max_offset as u64);
                         let start_idx = reverse_map.sorted_targets.partition_point(|&x| x < range_start);
-                        
+
                         let mut local_next: Vec<(u64, Vec<i64>)> = Vec::new();
-                        
+
                         for &pointed_addr in &reverse_map.sorted_targets[start_idx..] {
                             if pointed_addr > current_addr || found_enough.load(Ordering::Relaxed) {
                                 break;
                             }
-                            
+
                             if let Some(ptrs) = reverse_map.map.get(&pointed_addr) {
                                 let delta = (current_addr as i64) - (pointed_addr as i64);
-                                
+
                                 for (ptr_addr, static_data) in ptrs {
                                     let mut new_offsets = vec![delta];
                                     new_offsets.extend(offsets.iter().copied());
-                                    
+
                                     if new_offsets.len() > max_depth as usize {
                                         continue;
                                     }
-                                    
+
                                     if let Some((module_idx, module_offset)) = static_data {
                                         let module_name = if (*module_idx as usize) < pointer_map.modules.len() {
                                             pointer_map.modules[*module_idx as usize].name.clone()
                                         } else {
                                             format!("module_{}", module_idx)
                                         };
-                                        
+
                                         let chain = NormalizedChain {
                                             base_module: module_name,
                                             base_offset: *module_offset,
                                             offsets: new_offsets.clone(),
                                         };
-                                        
+
                                         let should_add = match candidate_filter {
                                             Some(filter) => filter.contains(&chain),
                                             None => true,
                                         };
-                                        
+
                                         if should_add {
                                             let mut chains_lock = chains.lock().unwrap();
                                             chains_lock.push(chain);
@@ -6573,22 +7315,22 @@ async fn run_pointer_scan(
                                 }
                             }
                         }
-                        
+
                         local_next
                     })
                     .collect();
-                
+
                 current_level = next_level;
             }
-            
+
             Arc::try_unwrap(chains).unwrap().into_inner().unwrap()
         }
-        
+
         // Reset counters for first file
         nodes_for_scan.store(0, Ordering::Relaxed);
         chains_for_scan.store(0, Ordering::Relaxed);
         file_idx_for_scan.store(0, Ordering::Relaxed);
-        
+
         // Find chains in first file
         let first_chains = find_chains_parallel(
             target_addresses[0],
@@ -6601,37 +7343,37 @@ async fn run_pointer_scan(
             &chains_for_scan,
             None,
         );
-        
+
         if first_chains.is_empty() {
             complete_for_scan.store(true, Ordering::Relaxed);
             return Ok(vec![]);
         }
-        
+
         // Check for cancellation after first file
         if PTRSCAN_CANCEL.load(Ordering::Relaxed) {
             complete_for_scan.store(true, Ordering::Relaxed);
             return Err("Scan cancelled".to_string());
         }
-        
+
         let mut candidate_chains: HashSet<NormalizedChain> = first_chains.into_iter().collect();
-        
+
         // Filter with subsequent files
         for (file_idx, target_addr) in target_addresses.iter().enumerate().skip(1) {
             if candidate_chains.is_empty() {
                 break;
             }
-            
+
             // Check for cancellation
             if PTRSCAN_CANCEL.load(Ordering::Relaxed) {
                 complete_for_scan.store(true, Ordering::Relaxed);
                 return Err("Scan cancelled".to_string());
             }
-            
+
             // Reset counters for this file
             nodes_for_scan.store(0, Ordering::Relaxed);
             chains_for_scan.store(0, Ordering::Relaxed);
             file_idx_for_scan.store(file_idx as u64, Ordering::Relaxed);
-            
+
             let file_chains = find_chains_parallel(
                 *target_addr,
                 &reverse_maps[file_idx],
@@ -6643,13 +7385,13 @@ async fn run_pointer_scan(
                 &chains_for_scan,
                 Some(&candidate_chains),
             );
-            
+
             let file_chain_set: HashSet<NormalizedChain> = file_chains.into_iter().collect();
             candidate_chains = candidate_chains.intersection(&file_chain_set).cloned().collect();
         }
-        
+
         complete_for_scan.store(true, Ordering::Relaxed);
-        
+
         // Convert to results
         let results: Vec<PointerScanResult> = candidate_chains
             .iter()
@@ -6659,28 +7401,28 @@ async fn run_pointer_scan(
                     module: Some(chain.base_module.clone()),
                     offset: chain.base_offset as i64,
                 }];
-                
+
                 for &offset in &chain.offsets {
                     result_chain.push(PointerChainStep {
                         module: None,
                         offset,
                     });
                 }
-                
+
                 PointerScanResult {
                     chain: result_chain,
                     final_address: format!("0x{:X}", target_addresses[0]),
                 }
             })
             .collect();
-        
+
         Ok::<Vec<PointerScanResult>, String>(results)
     }).await.map_err(|e| format!("Task join error: {}", e))??;
-    
+
     // Signal completion and wait for progress thread
     scan_complete.store(true, Ordering::Relaxed);
     let _ = progress_thread.join();
-    
+
     // Emit final progress
     let _ = app_handle.emit("ptr-scan-progress", serde_json::json!({
         "nodesProcessed": nodes_counter.load(std::sync::atomic::Ordering::Relaxed),
@@ -6689,7 +7431,7 @@ async fn run_pointer_scan(
         "totalFiles": files.len(),
         "phase": "complete"
     }));
-    
+
     Ok(result)
 }
 
@@ -6716,7 +7458,7 @@ struct PointerMapData {
 
 fn parse_pointer_map(data: &[u8]) -> Result<PointerMapData, String> {
     let mut cursor = 0;
-    
+
     // Check magic "DPTR"
     if data.len() < 8 {
         return Err("Invalid PointerMap: too short".to_string());
@@ -6725,66 +7467,66 @@ fn parse_pointer_map(data: &[u8]) -> Result<PointerMapData, String> {
         return Err("Invalid PointerMap: wrong magic".to_string());
     }
     cursor += 4;
-    
+
     // Version
     let _version = u32::from_le_bytes(data[cursor..cursor+4].try_into().unwrap());
     cursor += 4;
-    
+
     // Number of modules
     let module_count = u32::from_le_bytes(data[cursor..cursor+4].try_into().unwrap()) as usize;
     cursor += 4;
-    
+
     let mut modules = Vec::with_capacity(module_count);
     for _ in 0..module_count {
         let name_len = u32::from_le_bytes(data[cursor..cursor+4].try_into().unwrap()) as usize;
         cursor += 4;
-        
+
         let name = String::from_utf8_lossy(&data[cursor..cursor+name_len]).to_string();
         cursor += name_len;
-        
+
         let base_address = u64::from_le_bytes(data[cursor..cursor+8].try_into().unwrap());
         cursor += 8;
-        
+
         let size = i32::from_le_bytes(data[cursor..cursor+4].try_into().unwrap()) as u32;
         cursor += 4;
-        
+
         modules.push(ModuleInfo { name, base_address, size });
     }
-    
+
     // Number of unique targets
     let target_count = u64::from_le_bytes(data[cursor..cursor+8].try_into().unwrap()) as usize;
     cursor += 8;
-    
+
     // Total pointer count
     let _total_pointers = u64::from_le_bytes(data[cursor..cursor+8].try_into().unwrap());
     cursor += 8;
-    
+
     // Read pointer entries
     let mut pointers: HashMap<u64, Vec<(u64, Option<(u32, u32)>)>> = HashMap::with_capacity(target_count);
-    
+
     for _ in 0..target_count {
         if cursor + 12 > data.len() {
             break;
         }
-        
+
         let target_value = u64::from_le_bytes(data[cursor..cursor+8].try_into().unwrap());
         cursor += 8;
-        
+
         let ptr_count = u32::from_le_bytes(data[cursor..cursor+4].try_into().unwrap()) as usize;
         cursor += 4;
-        
+
         let mut ptrs = Vec::with_capacity(ptr_count);
         for _ in 0..ptr_count {
             if cursor + 9 > data.len() {
                 break;
             }
-            
+
             let ptr_addr = u64::from_le_bytes(data[cursor..cursor+8].try_into().unwrap());
             cursor += 8;
-            
+
             let has_static = data[cursor] != 0;
             cursor += 1;
-            
+
             let static_data = if has_static {
                 if cursor + 8 > data.len() {
                     break;
@@ -6797,21 +7539,24 @@ fn parse_pointer_map(data: &[u8]) -> Result<PointerMapData, String> {
             } else {
                 None
             };
-            
+
             ptrs.push((ptr_addr, static_data));
         }
-        
+
         pointers.insert(target_value, ptrs);
     }
-    
+
     Ok(PointerMapData { modules, pointers })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Reads of a loaded memory dump bypass the server
+    memory_reader::set_local_memory(memory_reader::LocalMemory { read: memory_dump::read, regions: memory_dump::regions });
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().with_handler(hotkeys::handle_shortcut).build())
         .manage(state::AppStateType::new(std::sync::Mutex::new(state::AppState::default())))
         .manage(state::DebuggerSidebarCacheType::new(std::sync::Mutex::new(state::DebuggerSidebarCache::default())))
         .invoke_handler(tauri::generate_handler![
@@ -6822,7 +7567,28 @@ pub fn run() {
             filter_memory_native,
             lookup_memory_native,
             unknown_scan_native,
+            exact_scan_native,
+            aob_scan_native,
+            group_scan_native,
+            string_scan_native,
+            scan_session::export_scan_session,
+            scan_session::import_scan_session,
+            scan_sampling::sample_scan_results,
+            scan_sampling::get_scan_statistics,
+            struct_dissector::dissect_memory,
+            struct_dissector::save_struct_definition,
+            struct_dissector::list_struct_definitions,
+            struct_dissector::delete_struct_definition,
+            memory_usage::start_memory_usage_tracking,
+            memory_usage::stop_memory_usage_tracking,
+            memory_usage::get_memory_usage_history,
+            memory_usage::get_growing_regions,
+            memory_usage::list_memory_usage_sessions,
+            memory_usage::clear_memory_usage_session,
+            memory_regions::get_memory_regions,
+            filter_unknown_scan_native,
             init_unknown_scan_progress,
+            cancel_unknown_scan,
             get_unknown_scan_progress,
             load_unknown_scan_results,
             clear_unknown_scan,
@@ -6832,7 +7598,135 @@ pub fn run() {
             get_unknown_scan_file_info,
             disassemble_memory,
             disassemble_memory_direct,
+            disassembly::disassemble_memory_structured,
+            symbolizer::resolve_symbols,
+            assembler::assemble_instructions,
+            smc_monitor::start_smc_monitor,
+            smc_monitor::stop_smc_monitor,
+            smc_monitor::scan_smc_now,
+            smc_monitor::get_smc_status,
+            smc_monitor::get_smc_events,
+            smc_monitor::clear_smc_events,
+            smc_monitor::get_stale_functions,
+            smc_monitor::clear_stale_functions,
+            jit_regions::start_jit_tracking,
+            jit_regions::stop_jit_tracking,
+            jit_regions::refresh_jit_regions,
+            jit_regions::list_jit_regions,
+            jit_regions::snapshot_jit_region,
+            jit_regions::list_jit_versions,
+            jit_regions::disassemble_jit_version,
+            jit_regions::diff_jit_versions,
+            jit_regions::add_jit_bookmark,
+            jit_regions::remove_jit_bookmark,
+            jit_regions::list_jit_bookmarks,
+            jit_regions::clear_jit_regions,
+            patches::apply_patch,
+            patches::revert_patch,
+            patches::reapply_patch,
+            patches::list_patches,
+            patches::delete_patch,
+            patches::reapply_patches_for_module,
+            breakpoints::set_breakpoint,
+            breakpoints::remove_breakpoint,
+            breakpoints::list_breakpoints,
+            breakpoints::toggle_breakpoint,
+            breakpoints::restore_breakpoints,
+            disasm_comments::get_comment_providers,
+            disasm_comments::set_comment_provider,
+            disasm_comments::clear_comment_cache,
+            struct_profiler::start_struct_profiler,
+            struct_profiler::stop_struct_profiler,
+            struct_profiler::get_struct_profile,
+            struct_profiler::clear_struct_profile,
+            watchpoints::set_watchpoint,
+            watchpoints::remove_watchpoint,
+            watchpoints::list_watchpoints,
+            signatures::infer_signatures_from_trace,
+            signatures::list_inferred_signatures,
+            signatures::delete_inferred_signature,
+            signatures::push_signature_to_ghidra,
+            registers::get_registers,
+            registers::set_register,
+            disasm_export::export_disassembly,
+            analysis_policy::get_module_analysis_policy,
+            analysis_policy::set_module_analysis_policy,
+            analysis_policy::delete_module_analysis_policy,
+            analysis_policy::list_module_analysis_policies,
+            threads::list_threads,
+            threads::get_thread_info,
+            threads::suspend_thread,
+            threads::resume_thread,
+            clock_sync::sync_target_clock,
+            clock_sync::get_clock_sync_status,
+            clock_sync::convert_target_timestamp,
+            breakpoints::export_breakpoints,
+            breakpoints::import_breakpoints,
+            native_trace::start_native_trace,
+            native_trace::stop_native_trace,
+            native_trace::get_native_trace_status,
+            native_trace::read_native_trace,
+            native_trace::seek_trace,
+            native_trace::clear_native_trace,
+            trace_diff::diff_trace_sessions,
+            latency::set_latency_instrumentation,
+            latency::get_latency_instrumentation,
+            latency::get_latency_report,
+            trace_export::export_trace_session,
+            coverage::start_coverage,
+            coverage::stop_coverage,
+            coverage::get_coverage_report,
+            coverage::list_coverage_sessions,
+            coverage::clear_coverage,
+            coverage::export_coverage_drcov,
+            region_snapshots::snapshot_region,
+            region_snapshots::compare_snapshot,
+            region_snapshots::list_snapshots,
+            region_snapshots::delete_snapshot,
+            region_snapshots::read_snapshot,
+            process_control::list_processes,
+            process_control::attach_process,
+            process_control::detach_process,
+            process_control::get_attached_process_info,
+            module_symbols::list_modules,
+            module_symbols::list_module_symbols,
+            module_symbols::clear_module_symbols,
+            server_connection::get_server_certificate,
+            server_connection::pin_server_certificate,
+            server_connection::unpin_server_certificate,
+            server_connection::list_pinned_certificates,
+            server_connection::get_http_settings,
+            server_connection::set_http_settings,
+            memory_dump::load_memory_dump,
+            memory_dump::unload_memory_dump,
+            memory_dump::get_memory_dump_info,
+            memory_dump::dump_process_memory,
+            memory_dump::cancel_memory_dump,
+            module_dump::dump_module,
+            ghidra_projects::list_ghidra_projects,
+            ghidra_projects::delete_ghidra_project,
+            ghidra_projects::prune_ghidra_projects,
+            ghidra_supervisor::get_ghidra_server_status,
+            ghidra_supervisor::set_ghidra_auto_restart,
+            ghidra_batch::ghidra_server_decompile_all,
+            ghidra_batch::cancel_ghidra_decompile_all,
+            ghidra_edits::ghidra_rename_function,
+            ghidra_edits::ghidra_rename_variable,
+            ghidra_edits::ghidra_set_comment,
+            virtual_addresses::register_virtual_address,
+            virtual_addresses::unregister_virtual_address,
+            virtual_addresses::list_virtual_addresses,
+            virtual_addresses::resolve_virtual_address,
+            virtual_addresses::read_virtual_address,
+            virtual_addresses::write_virtual_address,
             demangle_symbols,
+            secure_store::get_db_encryption_status,
+            secure_store::enable_db_encryption,
+            secure_store::disable_db_encryption,
+            secure_store::migrate_db_encryption,
+            secure_store::save_app_setting,
+            secure_store::get_app_setting,
+            secure_store::delete_app_setting,
             state::get_app_state,
             state::update_app_state,
             state::update_single_state,
@@ -6866,6 +7760,19 @@ pub fn run() {
             state::set_sidebar_ghidra_functions,
             state::set_sidebar_ghidra_data,
             state::clear_sidebar_cache,
+            // Symbol server commands
+            symbol_server::fetch_module_debug_info,
+            symbol_server::clear_symbol_cache,
+            symbol_server::configure_symbol_servers,
+            symbol_server::get_symbol_servers,
+            symbol_server::fetch_symbols,
+            build_id::get_module_build_ids,
+            build_id::get_module_build_id,
+            module_diff::diff_module_against_file,
+            hook_detector::scan_import_hooks,
+            event_bus::subscribe_event_channel,
+            event_bus::unsubscribe_event_channel,
+            event_bus::get_event_channel_stats,
             // Ghidra integration commands
             download_library_file,
             download_server_file,
@@ -6897,8 +7804,70 @@ pub fn run() {
             ghidra_server_xrefs,
             ghidra_server_function_info,
             ghidra_server_cfg,
+            cfg_export::export_cfg,
+            cfg_export::export_wasm_cfg,
             ghidra_server_data,
             ghidra_analyze_reachability,
+            is_reachable,
+            shortest_call_path,
+            ghidra_server_callgraph,
+            ghidra_search::ghidra_search,
+            ghidra_diff::ghidra_diff_modules,
+            decompiler::decompile_function,
+            decompiler::get_decompiler_settings,
+            decompiler::set_decompiler_backend,
+            frida::frida_list_processes,
+            frida::frida_hook,
+            frida::frida_unhook,
+            frida::frida_list_hooks,
+            scripting::run_script,
+            scripting::stop_script,
+            scripting::list_scripts,
+            scripting::get_script_output,
+            cheat_table::import_cheat_table,
+            cheat_table::export_cheat_table,
+            watchlist::list_watchlist,
+            watchlist::add_watch_entry,
+            watchlist::update_watch_entry,
+            watchlist::delete_watch_entry,
+            watchlist::reorder_watchlist,
+            watchlist::set_watch_frozen,
+            watchlist::refresh_watchlist,
+            hotkeys::bind_hotkey,
+            hotkeys::unbind_hotkey,
+            hotkeys::list_hotkeys,
+            remote_memory::allocate_remote_memory,
+            remote_memory::free_remote_memory,
+            remote_memory::list_remote_allocations,
+            remote_memory::find_code_cave,
+            hex_view::read_memory_page,
+            hex_view::write_memory_bytes,
+            value_codec::encode_value,
+            value_codec::decode_value,
+            scan_generations::get_scan_stats,
+            scan_generations::compact_scan,
+            disasm_cache::prefetch_disassembly,
+            disasm_cache::invalidate_disassembly_cache,
+            disasm_cache::get_disassembly_cache_stats,
+            expression::evaluate_expression,
+            objc_metadata::load_runtime_metadata,
+            objc_metadata::get_objc_class,
+            objc_metadata::get_swift_type,
+            objc_metadata::find_objc_selector,
+            art_bridge::list_dex_files,
+            art_bridge::list_dex_classes,
+            art_bridge::get_dex_class,
+            art_bridge::resolve_java_method,
+            art_bridge::set_java_breakpoint,
+            il2cpp::load_il2cpp_metadata,
+            il2cpp::search_il2cpp_classes,
+            il2cpp::get_il2cpp_class,
+            mono_runtime::load_mono_assemblies,
+            mono_runtime::search_mono_classes,
+            mono_runtime::get_mono_class,
+            mono_runtime::resolve_mono_method,
+            mono_runtime::resolve_mono_static_field,
+            mono_runtime::set_mono_breakpoint,
             read_local_text_file,
             select_folder_dialog,
             save_binary_file_dialog,
@@ -6910,6 +7879,33 @@ pub fn run() {
             save_wasm_binary,
             list_wasm_files,
             analyze_wasm_binary,
+            wasm_cfg::analyze_wasm_cfg,
+            wasm_cfg::analyze_wasm_call_graph,
+            debug_symbols::load_debug_symbols,
+            source_view::get_source_path_rules,
+            source_view::set_source_path_rules,
+            source_view::resolve_source_line,
+            source_view::list_source_files,
+            exception_rules::list_exception_rules,
+            exception_rules::add_exception_rule,
+            exception_rules::update_exception_rule,
+            exception_rules::delete_exception_rule,
+            exception_rules::reorder_exception_rules,
+            access_heatmap::start_access_heatmap,
+            access_heatmap::stop_access_heatmap,
+            access_heatmap::get_access_heatmap,
+            struct_inference::infer_structure,
+            vtables::scan_vtables,
+            vtables::get_vtables,
+            cache_versions::get_cache_status,
+            cache_versions::invalidate_caches,
+            global_search::search_everything,
+            annotations::add_annotation,
+            annotations::update_annotation,
+            annotations::delete_annotation,
+            annotations::list_annotations,
+            annotation_sync::export_annotations,
+            annotation_sync::import_annotations,
             disassemble_wasm_function,
             open_wasm_modules_directory
         ])
@@ -6917,7 +7913,11 @@ pub fn run() {
             if let Err(e) = init_ghidra_db() {
                 eprintln!("Failed to initialize Ghidra database: {e}");
             }
-            
+
+            tauri::async_runtime::spawn(event_bus::run_flusher(app.handle().clone()));
+            tauri::async_runtime::spawn(ghidra_supervisor::run(app.handle().clone()));
+            hotkeys::restore(app.handle());
+
             if let Some(window) = app.get_webview_window("main") {
                 if let Ok(monitor_opt) = window.current_monitor() {
                     if let Some(monitor) = monitor_opt {
@@ -6944,6 +7944,90 @@ pub fn run() {
             }
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                ghidra_supervisor::shutdown_all();
+                frida::shutdown_all();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fuzzy(epsilon: Option<f64>, percent: Option<f64>, rounding: Option<&str>) -> FuzzyFloatOptions {
+        FuzzyFloatOptions { epsilon, percent, rounding: rounding.map(str::to_string) }
+    }
+
+    fn matches_f32(value: f32, pattern: f32, options: Option<&FuzzyFloatOptions>) -> bool {
+        compare_values(&value.to_le_bytes(), &[], &pattern.to_le_bytes(), None, "float", "fuzzy", options)
+    }
+
+    #[test]
+    fn fuzzy_default_band() {
+        assert!(matches_f32(99.7, 100.0, None));
+        assert!(matches_f32(100.5, 100.0, None));
+        assert!(!matches_f32(100.6, 100.0, None));
+    }
+
+    #[test]
+    fn fuzzy_epsilon_band() {
+        let options = fuzzy(Some(0.01), None, None);
+        assert!(matches_f32(1.005, 1.0, Some(&options)));
+        assert!(!matches_f32(1.02, 1.0, Some(&options)));
+        assert!(!matches_f32(0.98, 1.0, Some(&options)));
+    }
+
+    #[test]
+    fn fuzzy_percent_band() {
+        let options = fuzzy(None, Some(1.0), None);
+        assert!(matches_f32(990.5, 1000.0, Some(&options)));
+        assert!(matches_f32(-1009.0, -1000.0, Some(&options)));
+        assert!(!matches_f32(1011.0, 1000.0, Some(&options)));
+        // The wider of the two bands applies
+        let options = fuzzy(Some(20.0), Some(1.0), None);
+        assert!(matches_f32(1019.0, 1000.0, Some(&options)));
+    }
+
+    #[test]
+    fn fuzzy_truncated_and_rounded() {
+        let truncated = fuzzy(None, None, Some("truncated"));
+        assert!(matches_f32(42.9, 42.0, Some(&truncated)));
+        assert!(matches_f32(42.0, 42.7, Some(&truncated)));
+        assert!(!matches_f32(41.99, 42.0, Some(&truncated)));
+        // floor() rounds negative values away from zero
+        assert!(matches_f32(-42.5, -43.0, Some(&truncated)));
+
+        let rounded = fuzzy(None, None, Some("rounded"));
+        assert!(matches_f32(41.6, 42.0, Some(&rounded)));
+        assert!(!matches_f32(41.4, 42.0, Some(&rounded)));
+    }
+
+    #[test]
+    fn fuzzy_double_and_non_float_types() {
+        let options = fuzzy(Some(0.001), None, None);
+        assert!(compare_values(&2.5001f64.to_le_bytes(), &[], &2.5002f64.to_le_bytes(), None, "double", "fuzzy", Some(&options)));
+        assert!(!compare_values(&100i32.to_le_bytes(), &[], &100i32.to_le_bytes(), None, "int32", "fuzzy", None));
+    }
+
+    #[test]
+    fn fuzzy_rejects_nan() {
+        assert!(!matches_f32(f32::NAN, 1.0, None));
+        assert!(!matches_f32(1.0, f32::NAN, None));
+        assert!(!matches_f32(f32::INFINITY, f32::INFINITY, None));
+    }
+
+    #[test]
+    fn case_insensitive_string_reads_widest_folding() {
+        let matcher = StringMatcher::new("k", false, false, true).unwrap();
+        assert_eq!(matcher.read_size(None), 4);
+        assert_eq!(matcher.match_len("\u{212A}".as_bytes()), Some(3));
+
+        let utf16 = StringMatcher::new("ab", false, true, false).unwrap();
+        assert_eq!(utf16.read_size(None), 4);
+        assert_eq!(utf16.match_len(&[b'a', 0, b'b', 0, 0, 0]), Some(4));
+    }
 }
@@ -0,0 +1,851 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tauri::{AppHandle, Emitter};
+
+use crate::memory_regions::{self, MemoryRegion, MemoryRegionFilter};
+use crate::state::{self, AppStateType, ModuleInfo, ProcessInfo};
+use crate::{disasm_cache, event_bus, RemoteMemoryRegion, SERVER_CONFIG};
+
+const MINIDUMP_SIGNATURE: &[u8; 4] = b"MDMP";
+const MODULE_LIST_STREAM: u32 = 4;
+const MEMORY_LIST_STREAM: u32 = 5;
+const SYSTEM_INFO_STREAM: u32 = 7;
+const MEMORY64_LIST_STREAM: u32 = 9;
+const MEMORY_INFO_LIST_STREAM: u32 = 16;
+const ELF_PT_LOAD: u32 = 1;
+const ELF_PT_NOTE: u32 = 4;
+const ELF_NT_FILE: u32 = 0x4649_4c45;
+
+/// Range of target memory stored in the dump file
+#[derive(Debug, Clone)]
+struct DumpSegment {
+    address: u64,
+    size: u64,                       // Size in memory
+    file_offset: u64,
+    file_size: u64,                  // Bytes present in the file; the rest reads as zero
+    protection: String,              // "rwx" style
+    file_path: Option<String>,
+}
+
+impl DumpSegment {
+    /// End address; load_memory_dump rejects segments where this overflows
+    fn end(&self) -> u64 {
+        self.address.saturating_add(self.size)
+    }
+}
+
+// Segments, modules and architecture read from a dump file
+type ParsedDump = (Vec<DumpSegment>, Vec<ModuleInfo>, Option<String>);
+
+struct LoadedDump {
+    info: MemoryDumpInfo,
+    file: Mutex<File>,
+    segments: Vec<DumpSegment>,      // Sorted by address
+}
+
+/// Summary of the loaded dump
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryDumpInfo {
+    pub path: String,
+    pub format: String,              // "raw" | "sparse" | "minidump" | "elf_core"
+    pub arch: Option<String>,
+    pub segments: usize,
+    pub total_bytes: u64,
+    pub modules: Vec<ModuleInfo>,
+}
+
+// The dump that replaces the server for memory reads and region lists
+static DUMP: Lazy<RwLock<Option<Arc<LoadedDump>>>> = Lazy::new(|| RwLock::new(None));
+
+fn loaded() -> Option<Arc<LoadedDump>> {
+    DUMP.read().ok().and_then(|d| d.clone())
+}
+
+pub fn is_loaded() -> bool {
+    loaded().is_some()
+}
+
+/// Read `len` bytes at `offset`; ranges past the end of the file are rejected
+/// before allocating, so corrupt sizes cannot request huge buffers
+fn read_file_at(file: &mut File, offset: u64, len: usize) -> Result<Vec<u8>, String> {
+    let file_len = file.metadata().map_err(|e| e.to_string())?.len();
+    if offset.checked_add(len as u64).is_none_or(|end| end > file_len) {
+        return Err(format!("Truncated dump at 0x{:x}: 0x{:x} bytes past the end of the file", offset, len));
+    }
+    let mut buf = vec![0u8; len];
+    file.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+    file.read_exact(&mut buf).map_err(|e| format!("Truncated dump at 0x{:x}: {}", offset, e))?;
+    Ok(buf)
+}
+
+fn u16_at(buf: &[u8], pos: usize, big_endian: bool) -> Option<u16> {
+    let b: [u8; 2] = buf.get(pos..pos.checked_add(2)?)?.try_into().ok()?;
+    Some(if big_endian { u16::from_be_bytes(b) } else { u16::from_le_bytes(b) })
+}
+
+fn u32_at(buf: &[u8], pos: usize, big_endian: bool) -> Option<u32> {
+    let b: [u8; 4] = buf.get(pos..pos.checked_add(4)?)?.try_into().ok()?;
+    Some(if big_endian { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) })
+}
+
+fn u64_at(buf: &[u8], pos: usize, big_endian: bool) -> Option<u64> {
+    let b: [u8; 8] = buf.get(pos..pos.checked_add(8)?)?.try_into().ok()?;
+    Some(if big_endian { u64::from_be_bytes(b) } else { u64::from_le_bytes(b) })
+}
+
+fn module_name(path: &str) -> String {
+    path.rsplit(['/', '\\']).next().unwrap_or(path).to_string()
+}
+
+/// Memory-only dump: the whole file mapped at `base`
+fn parse_raw(file: &mut File, base: u64) -> Result<ParsedDump, String> {
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    let segment = DumpSegment {
+        address: base,
+        size: len,
+        file_offset: 0,
+        file_size: len,
+        protection: "rw-".to_string(), // Unknown; reported writable so value scans include it
+        file_path: None,
+    };
+    Ok((vec![segment], Vec::new(), None))
+}
+
+fn windows_protection(protect: u32) -> &'static str {
+    match protect & 0xff {
+        0x02 => "r--",                          // PAGE_READONLY
+        0x04 | 0x08 => "rw-",                   // PAGE_READWRITE / PAGE_WRITECOPY
+        0x10 => "--x",                          // PAGE_EXECUTE
+        0x20 => "r-x",                          // PAGE_EXECUTE_READ
+        0x40 | 0x80 => "rwx",                   // PAGE_EXECUTE_READWRITE / WRITECOPY
+        _ => "---",
+    }
+}
+
+fn parse_minidump(file: &mut File) -> Result<ParsedDump, String> {
+    let header = read_file_at(file, 0, 32)?;
+    if &header[..4] != MINIDUMP_SIGNATURE {
+        return Err("Not a minidump (missing MDMP signature)".to_string());
+    }
+    let stream_count = u32_at(&header, 8, false).unwrap_or(0) as usize;
+    let directory_rva = u32_at(&header, 12, false).unwrap_or(0) as u64;
+    let directory = read_file_at(file, directory_rva, stream_count * 12)?;
+    let streams: Vec<(u32, u32, u64)> = directory.chunks_exact(12)
+        .map(|e| (u32_at(e, 0, false).unwrap_or(0), u32_at(e, 4, false).unwrap_or(0), u32_at(e, 8, false).unwrap_or(0) as u64))
+        .collect();
+    let stream = |kind: u32| streams.iter().find(|(t, _, _)| *t == kind).map(|&(_, size, rva)| (size as usize, rva));
+
+    let mut segments = Vec::new();
+    if let Some((size, rva)) = stream(MEMORY64_LIST_STREAM) {
+        let data = read_file_at(file, rva, size)?;
+        let count = (u64_at(&data, 0, false).unwrap_or(0) as usize).min(data.len() / 16);
+        let mut file_offset = u64_at(&data, 8, false).unwrap_or(0);
+        for i in 0..count {
+            let (Some(address), Some(len)) = (u64_at(&data, 16 + i * 16, false), u64_at(&data, 24 + i * 16, false)) else {
+                break;
+            };
+            segments.push(DumpSegment { address, size: len, file_offset, file_size: len, protection: "rw-".to_string(), file_path: None });
+            file_offset = file_offset.saturating_add(len);
+        }
+    } else if let Some((size, rva)) = stream(MEMORY_LIST_STREAM) {
+        let data = read_file_at(file, rva, size)?;
+        let count = (u32_at(&data, 0, false).unwrap_or(0) as usize).min(data.len() / 16);
+        for i in 0..count {
+            let pos = 4 + i * 16;
+            let (Some(address), Some(len), Some(offset)) = (u64_at(&data, pos, false), u32_at(&data, pos + 8, false), u32_at(&data, pos + 12, false)) else {
+                break;
+            };
+            segments.push(DumpSegment {
+                address,
+                size: len as u64,
+                file_offset: offset as u64,
+                file_size: len as u64,
+                protection: "rw-".to_string(),
+                file_path: None,
+            });
+        }
+    } else {
+        return Err("Minidump has no memory list".to_string());
+    }
+
+    // Page protections, when the dump recorded them
+    if let Some((size, rva)) = stream(MEMORY_INFO_LIST_STREAM) {
+        let data = read_file_at(file, rva, size)?;
+        let header_size = u32_at(&data, 0, false).unwrap_or(16) as usize;
+        let entry_size = (u32_at(&data, 4, false).unwrap_or(48) as usize).max(1);
+        let count = (u64_at(&data, 8, false).unwrap_or(0) as usize).min(data.len() / entry_size);
+        let infos: Vec<(u64, u64, u32)> = (0..count)
+            .filter_map(|i| {
+                let pos = header_size.checked_add(i * entry_size)?;
+                Some((u64_at(&data, pos, false)?, u64_at(&data, pos + 24, false)?, u32_at(&data, pos + 36, false)?))
+            })
+            .collect();
+        for segment in &mut segments {
+            if let Some(&(_, _, protect)) = infos.iter().find(|(base, len, _)| segment.address >= *base && segment.address < base.saturating_add(*len)) {
+                segment.protection = windows_protection(protect).to_string();
+            }
+        }
+    }
+
+    let arch = match stream(SYSTEM_INFO_STREAM) {
+        Some((_, rva)) => match u16_at(&read_file_at(file, rva, 2)?, 0, false) {
+            Some(0) => Some("x86"),
+            Some(5) => Some("arm"),
+            Some(9) => Some("x86_64"),
+            Some(12) => Some("arm64"),
+            _ => None,
+        },
+        None => None,
+    };
+
+    let mut modules = Vec::new();
+    if let Some((size, rva)) = stream(MODULE_LIST_STREAM) {
+        let data = read_file_at(file, rva, size)?;
+        let count = (u32_at(&data, 0, false).unwrap_or(0) as usize).min(data.len() / 108);
+        for i in 0..count {
+            let pos = 4 + i * 108;
+            let (Some(base), Some(image_size), Some(name_rva)) = (u64_at(&data, pos, false), u32_at(&data, pos + 8, false), u32_at(&data, pos + 20, false)) else {
+                break;
+            };
+            let name_len = u32_at(&read_file_at(file, name_rva as u64, 4)?, 0, false).unwrap_or(0) as usize;
+            let name_bytes = read_file_at(file, name_rva as u64 + 4, name_len)?;
+            let units: Vec<u16> = name_bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            let path = String::from_utf16_lossy(&units);
+            modules.push(ModuleInfo {
+                modulename: module_name(&path),
+                base,
+                size: image_size as u64,
+                path: Some(path),
+                is_64bit: arch.map(|a| a == "x86_64" || a == "arm64"),
+            });
+        }
+    }
+    Ok((segments, modules, arch.map(String::from)))
+}
+
+fn parse_elf_core(file: &mut File) -> Result<ParsedDump, String> {
+    let ident = read_file_at(file, 0, 64)?;
+    if &ident[..4] != b"\x7fELF" {
+        return Err("Not an ELF file".to_string());
+    }
+    let is_64 = ident[4] == 2;
+    let be = ident[5] == 2;
+    if u16_at(&ident, 16, be) != Some(4) {
+        return Err("ELF file is not a core dump".to_string());
+    }
+    let arch = match u16_at(&ident, 18, be) {
+        Some(3) => Some("x86"),
+        Some(40) => Some("arm"),
+        Some(62) => Some("x86_64"),
+        Some(183) => Some("arm64"),
+        _ => None,
+    };
+    let (phoff, phentsize, phnum) = if is_64 {
+        (u64_at(&ident, 0x20, be), u16_at(&ident, 0x36, be), u16_at(&ident, 0x38, be))
+    } else {
+        (u32_at(&ident, 0x1c, be).map(u64::from), u16_at(&ident, 0x2a, be), u16_at(&ident, 0x2c, be))
+    };
+    let (phoff, phentsize, phnum) = (phoff.unwrap_or(0), phentsize.unwrap_or(0) as usize, phnum.unwrap_or(0) as usize);
+    let headers = read_file_at(file, phoff, phentsize * phnum)?;
+
+    let mut segments = Vec::new();
+    let mut notes = Vec::new();
+    for header in headers.chunks_exact(phentsize.max(1)) {
+        let kind = u32_at(header, 0, be).unwrap_or(0);
+        let (flags, offset, vaddr, filesz, memsz) = if is_64 {
+            (u32_at(header, 4, be), u64_at(header, 8, be), u64_at(header, 16, be), u64_at(header, 32, be), u64_at(header, 40, be))
+        } else {
+            (
+                u32_at(header, 24, be),
+                u32_at(header, 4, be).map(u64::from),
+                u32_at(header, 8, be).map(u64::from),
+                u32_at(header, 16, be).map(u64::from),
+                u32_at(header, 20, be).map(u64::from),
+            )
+        };
+        let (flags, offset, vaddr, filesz, memsz) = (flags.unwrap_or(0), offset.unwrap_or(0), vaddr.unwrap_or(0), filesz.unwrap_or(0), memsz.unwrap_or(0));
+        match kind {
+            ELF_PT_LOAD if memsz > 0 => {
+                let protection = format!(
+                    "{}{}{}",
+                    if flags & 4 != 0 { 'r' } else { '-' },
+                    if flags & 2 != 0 { 'w' } else { '-' },
+                    if flags & 1 != 0 { 'x' } else { '-' },
+                );
+                segments.push(DumpSegment { address: vaddr, size: memsz, file_offset: offset, file_size: filesz, protection, file_path: None });
+            }
+            ELF_PT_NOTE => notes.push(read_file_at(file, offset, filesz as usize)?),
+            _ => {}
+        }
+    }
+
+    // NT_FILE lists the file mapped at each range: use it for modules and region paths
+    let word = if is_64 { 8 } else { 4 };
+    let read_word = |buf: &[u8], pos: usize| if is_64 { u64_at(buf, pos, be) } else { u32_at(buf, pos, be).map(u64::from) };
+    let mut mappings: Vec<(u64, u64, String)> = Vec::new();
+    for note in &notes {
+        let mut pos = 0;
+        while pos + 12 <= note.len() {
+            let namesz = u32_at(note, pos, be).unwrap_or(0) as usize;
+            let descsz = u32_at(note, pos + 4, be).unwrap_or(0) as usize;
+            let kind = u32_at(note, pos + 8, be).unwrap_or(0);
+            let desc_start = pos + 12 + namesz.div_ceil(4) * 4;
+            let Some(desc) = desc_start.checked_add(descsz).and_then(|desc_end| note.get(desc_start..desc_end)) else {
+                break;
+            };
+            if kind == ELF_NT_FILE {
+                // Each entry is (start, end, file offset); the count cannot exceed what the note holds
+                let count = read_word(desc, 0).unwrap_or(0)
+                    .min((desc.len() / (word * 3)) as u64) as usize;
+                let names_start = word * 2 + count * word * 3;
+                let names = desc.get(names_start..).unwrap_or(&[]).split(|b| *b == 0).map(|n| String::from_utf8_lossy(n).into_owned());
+                for (i, name) in names.take(count).enumerate() {
+                    let entry = word * 2 + i * word * 3;
+                    if let (Some(start), Some(end)) = (read_word(desc, entry), read_word(desc, entry + word)) {
+                        if end >= start {
+                            mappings.push((start, end, name));
+                        }
+                    }
+                }
+            }
+            pos = desc_start + descsz.div_ceil(4) * 4;
+        }
+    }
+    for segment in &mut segments {
+        segment.file_path = mappings.iter()
+            .find(|(start, end, _)| segment.address >= *start && segment.address < *end)
+            .map(|(_, _, path)| path.clone());
+    }
+
+    let mut ranges: HashMap<&str, (u64, u64)> = HashMap::new();
+    for (start, end, path) in &mappings {
+        let range = ranges.entry(path.as_str()).or_insert((*start, *end));
+        range.0 = range.0.min(*start);
+        range.1 = range.1.max(*end);
+    }
+    let mut modules: Vec<ModuleInfo> = ranges.into_iter()
+        .map(|(path, (start, end))| ModuleInfo {
+            modulename: module_name(path),
+            base: start,
+            size: end - start,
+            path: Some(path.to_string()),
+            is_64bit: Some(is_64),
+        })
+        .collect();
+    modules.sort_by_key(|m| m.base);
+    Ok((segments, modules, arch.map(String::from)))
+}
+
+/// Read from the loaded dump. None when no dump is loaded; otherwise the bytes
+/// up to the first unmapped address (empty when `address` itself is unmapped,
+/// like a failed read on the server)
+pub fn read(address: u64, size: usize) -> Option<Vec<u8>> {
+    let dump = loaded()?;
+    let mut out = Vec::with_capacity(size.min(1 << 24));
+    let mut cursor = address;
+    let end = address.saturating_add(size as u64);
+    let Ok(mut file) = dump.file.lock() else {
+        return Some(Vec::new());
+    };
+    while cursor < end {
+        let index = dump.segments.partition_point(|s| s.address <= cursor);
+        let Some(segment) = index.checked_sub(1).map(|i| &dump.segments[i]).filter(|s| cursor < s.end()) else {
+            break;
+        };
+        let offset = cursor - segment.address;
+        let take = segment.end().min(end) - cursor;
+        let in_file = segment.file_size.saturating_sub(offset).min(take);
+        if in_file > 0 {
+            match read_file_at(&mut file, segment.file_offset.saturating_add(offset), in_file as usize) {
+                Ok(bytes) => out.extend_from_slice(&bytes),
+                Err(_) => break,
+            }
+        }
+        out.resize(out.len() + (take - in_file) as usize, 0);
+        cursor += take;
+    }
+    Some(out)
+}
+
+/// Region list of the loaded dump in server format, None when no dump is loaded
+pub fn regions() -> Option<Vec<RemoteMemoryRegion>> {
+    let dump = loaded()?;
+    Some(dump.segments.iter()
+        .map(|s| RemoteMemoryRegion {
+            start: s.address,
+            end: s.end(),
+            protection: s.protection.clone(),
+            file_path: s.file_path.clone(),
+        })
+        .collect())
+}
+
+/// Open a raw dump, sparse dump (dump_process_memory), minidump or ELF core
+/// file as an offline target: memory reads, region lists, scans and
+/// disassembly are served from it until unload_memory_dump. `format` is
+/// "raw" | "sparse" | "minidump" | "elf_core" | "auto".
+#[tauri::command]
+pub async fn load_memory_dump(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+    path: String,
+    format: Option<String>,
+    base_address: Option<u64>,
+) -> Result<MemoryDumpInfo, String> {
+    let mut file = File::open(&path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let format = match format.as_deref().unwrap_or("auto") {
+        "auto" if std::path::Path::new(&sparse_index_path(&path)).exists() => "sparse",
+        "auto" => {
+            let mut magic = [0u8; 4];
+            let _ = file.read(&mut magic);
+            match &magic {
+                b"MDMP" => "minidump",
+                b"\x7fELF" => "elf_core",
+                _ => "raw",
+            }
+        }
+        "raw" => "raw",
+        "sparse" => "sparse",
+        "minidump" => "minidump",
+        "elf_core" | "core" => "elf_core",
+        other => return Err(format!("Unknown dump format: {}", other)),
+    };
+    let (mut segments, modules, arch) = match format {
+        "minidump" => parse_minidump(&mut file)?,
+        "elf_core" => parse_elf_core(&mut file)?,
+        "sparse" => parse_sparse(&mut file, &path)?,
+        _ => parse_raw(&mut file, base_address.unwrap_or(0))?,
+    };
+    // An end past the address space cannot be mapped; reads and region lists rely on address + size
+    if let Some(segment) = segments.iter().find(|s| s.address.checked_add(s.size).is_none()) {
+        return Err(format!("Dump segment at 0x{:x} with size 0x{:x} overflows the address space", segment.address, segment.size));
+    }
+    segments.sort_by_key(|s| s.address);
+
+    let info = MemoryDumpInfo {
+        path: path.clone(),
+        format: format.to_string(),
+        arch,
+        segments: segments.len(),
+        total_bytes: segments.iter().map(|s| s.size).sum(),
+        modules: modules.clone(),
+    };
+    *DUMP.write().map_err(|e| e.to_string())? = Some(Arc::new(LoadedDump { info: info.clone(), file: Mutex::new(file), segments }));
+    memory_regions::invalidate_cache();
+    disasm_cache::clear();
+
+    let process = ProcessInfo { pid: 0, processname: module_name(&path) };
+    let mut updates = HashMap::new();
+    updates.insert("attachedProcess".to_string(), serde_json::to_value(&process).map_err(|e| e.to_string())?);
+    updates.insert("attachedModules".to_string(), serde_json::to_value(&modules).map_err(|e| e.to_string())?);
+    state::update_app_state(app, state, updates).await?;
+    Ok(info)
+}
+
+/// Stop serving memory from the loaded dump
+#[tauri::command]
+pub async fn unload_memory_dump(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+) -> Result<bool, String> {
+    let Some(_) = DUMP.write().map_err(|e| e.to_string())?.take() else {
+        return Ok(false);
+    };
+    memory_regions::invalidate_cache();
+    disasm_cache::clear();
+    let mut updates = HashMap::new();
+    updates.insert("attachedProcess".to_string(), serde_json::Value::Null);
+    updates.insert("attachedModules".to_string(), serde_json::json!([]));
+    state::update_app_state(app, state, updates).await?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn get_memory_dump_info() -> Option<MemoryDumpInfo> {
+    loaded().map(|dump| dump.info.clone())
+}
+
+const DUMP_READ_CHUNK: usize = 4 * 1024 * 1024;
+const DUMP_PARALLEL_READS: usize = 8;
+const DUMP_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const SPARSE_INDEX_FORMAT: &str = "dynadbg-sparse";
+
+static DUMP_RUNNING: AtomicBool = AtomicBool::new(false);
+static DUMP_CANCEL: AtomicBool = AtomicBool::new(false);
+
+/// Regions to include in dump_process_memory (unreadable regions are always skipped)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DumpFilters {
+    #[serde(flatten)]
+    pub regions: MemoryRegionFilter,
+    #[serde(default)]
+    pub address_ranges: Vec<(u64, u64)>, // Only regions overlapping these [start, end) ranges
+    #[serde(default)]
+    pub max_region_size: Option<u64>,    // Skip larger regions (e.g. huge reserved heaps)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DumpProgress {
+    pub output_path: String,
+    pub processed_bytes: u64,
+    pub total_bytes: u64,
+    pub regions_done: usize,
+    pub regions_total: usize,
+    pub failed_bytes: u64,
+    pub finished: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryDumpResult {
+    pub output_path: String,
+    pub index_path: Option<String>,      // Sparse format: JSON index next to the data file
+    pub format: String,                  // "sparse" | "minidump"
+    pub regions: usize,
+    pub segments: usize,                 // Contiguous readable ranges written
+    pub bytes_written: u64,
+    pub failed_bytes: u64,               // Bytes of selected regions that could not be read
+    pub cancelled: bool,
+}
+
+/// One contiguous range in a sparse dump index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SparseSegment {
+    address: u64,
+    size: u64,
+    file_offset: u64,
+    protection: String,
+    file_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SparseIndex {
+    format: String,
+    version: u32,
+    arch: Option<String>,
+    target_os: Option<String>,
+    process: Option<ProcessInfo>,
+    modules: Vec<ModuleInfo>,
+    segments: Vec<SparseSegment>,
+}
+
+fn sparse_index_path(data_path: &str) -> String {
+    format!("{}.json", data_path)
+}
+
+fn parse_sparse(file: &mut File, path: &str) -> Result<ParsedDump, String> {
+    let text = std::fs::read_to_string(sparse_index_path(path)).map_err(|e| format!("Failed to read dump index: {}", e))?;
+    let index: SparseIndex = serde_json::from_str(&text).map_err(|e| format!("Invalid dump index: {}", e))?;
+    if index.format != SPARSE_INDEX_FORMAT {
+        return Err(format!("Unknown dump index format: {}", index.format));
+    }
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    let segments = index.segments.into_iter()
+        .filter(|s| s.file_offset.checked_add(s.size).is_some_and(|end| end <= len))
+        .map(|s| DumpSegment {
+            address: s.address,
+            size: s.size,
+            file_offset: s.file_offset,
+            file_size: s.size,
+            protection: s.protection,
+            file_path: s.file_path,
+        })
+        .collect();
+    Ok((segments, index.modules, index.arch))
+}
+
+fn emit_dump_progress(app: &AppHandle, progress: &DumpProgress) {
+    if !event_bus::publish(event_bus::CHANNEL_DUMP_PROGRESS, Some(&progress.output_path), progress) {
+        let _ = app.emit("dump://progress", progress);
+    }
+}
+
+/// Windows PAGE_* protection for an "rwx" string (minidump MemoryInfoList)
+fn page_protection(protection: &str) -> u32 {
+    let has = |c| protection.contains(c);
+    match (has('r'), has('w'), has('x')) {
+        (_, true, true) => 0x40,
+        (_, false, true) if has('r') => 0x20,
+        (_, false, true) => 0x10,
+        (_, true, false) => 0x04,
+        (true, false, false) => 0x02,
+        _ => 0x01,
+    }
+}
+
+fn minidump_arch(arch: Option<&str>) -> u16 {
+    match arch {
+        Some("x86") => 0,
+        Some("arm") => 5,
+        Some("arm64") | Some("aarch64") => 12,
+        _ => 9,
+    }
+}
+
+/// Write a minidump (SystemInfo, ModuleList, MemoryInfoList and
+/// Memory64List streams) whose memory is copied from the sparse data file
+fn write_minidump(
+    output: &mut File,
+    data: &mut File,
+    segments: &[SparseSegment],
+    regions: &[MemoryRegion],
+    modules: &[ModuleInfo],
+    arch: Option<&str>,
+) -> Result<u64, String> {
+    const STREAM_COUNT: usize = 4;
+    let directory_rva = 32usize;
+
+    let mut system_info = vec![0u8; 56];
+    system_info[0..2].copy_from_slice(&minidump_arch(arch).to_le_bytes());
+    system_info[6] = 1; // NumberOfProcessors
+
+    // Module entries, then their names (MINIDUMP_STRING) after the list
+    let module_list_size = 4 + modules.len() * 108;
+    let mut names = Vec::new();
+    let mut module_list = Vec::with_capacity(module_list_size);
+    module_list.extend_from_slice(&(modules.len() as u32).to_le_bytes());
+    let mut name_offsets = Vec::new();
+    for module in modules {
+        name_offsets.push(names.len());
+        let utf16: Vec<u16> = module.path.as_deref().unwrap_or(&module.modulename).encode_utf16().collect();
+        names.extend_from_slice(&((utf16.len() * 2) as u32).to_le_bytes());
+        names.extend(utf16.iter().flat_map(|u| u.to_le_bytes()));
+        names.extend_from_slice(&[0, 0]);
+    }
+
+    let mut memory_info = Vec::new();
+    memory_info.extend_from_slice(&16u32.to_le_bytes());
+    memory_info.extend_from_slice(&48u32.to_le_bytes());
+    memory_info.extend_from_slice(&(regions.len() as u64).to_le_bytes());
+    for region in regions {
+        let protect = page_protection(&region.protection);
+        let kind: u32 = match region.region_type.as_str() {
+            "image" => 0x100_0000,
+            "mapped" => 0x4_0000,
+            _ => 0x2_0000,
+        };
+        memory_info.extend_from_slice(&region.base.to_le_bytes());
+        memory_info.extend_from_slice(&region.base.to_le_bytes());
+        memory_info.extend_from_slice(&protect.to_le_bytes());
+        memory_info.extend_from_slice(&0u32.to_le_bytes());
+        memory_info.extend_from_slice(&region.size.to_le_bytes());
+        memory_info.extend_from_slice(&0x1000u32.to_le_bytes()); // MEM_COMMIT
+        memory_info.extend_from_slice(&protect.to_le_bytes());
+        memory_info.extend_from_slice(&kind.to_le_bytes());
+        memory_info.extend_from_slice(&0u32.to_le_bytes());
+    }
+
+    let system_info_rva = directory_rva + STREAM_COUNT * 12;
+    let module_list_rva = system_info_rva + system_info.len();
+    let names_rva = module_list_rva + module_list_size;
+    let memory_info_rva = names_rva + names.len();
+    let memory64_rva = memory_info_rva + memory_info.len();
+    let memory64_size = 16 + segments.len() * 16;
+    let data_rva = (memory64_rva + memory64_size) as u64;
+    if data_rva > u32::MAX as u64 {
+        return Err("Minidump metadata exceeds 4 GB".to_string());
+    }
+
+    for (module, name_offset) in modules.iter().zip(&name_offsets) {
+        let mut entry = vec![0u8; 108];
+        entry[0..8].copy_from_slice(&module.base.to_le_bytes());
+        entry[8..12].copy_from_slice(&(module.size.min(u32::MAX as u64) as u32).to_le_bytes());
+        entry[20..24].copy_from_slice(&((names_rva + name_offset) as u32).to_le_bytes());
+        module_list.extend_from_slice(&entry);
+    }
+
+    let mut memory64 = Vec::with_capacity(memory64_size);
+    memory64.extend_from_slice(&(segments.len() as u64).to_le_bytes());
+    memory64.extend_from_slice(&data_rva.to_le_bytes());
+    for segment in segments {
+        memory64.extend_from_slice(&segment.address.to_le_bytes());
+        memory64.extend_from_slice(&segment.size.to_le_bytes());
+    }
+
+    let mut header = Vec::with_capacity(data_rva as usize);
+    header.extend_from_slice(MINIDUMP_SIGNATURE);
+    header.extend_from_slice(&0xa793u32.to_le_bytes());
+    header.extend_from_slice(&(STREAM_COUNT as u32).to_le_bytes());
+    header.extend_from_slice(&(directory_rva as u32).to_le_bytes());
+    header.extend_from_slice(&0u32.to_le_bytes());
+    header.extend_from_slice(&((crate::state::AppState::current_timestamp() / 1000) as u32).to_le_bytes());
+    header.extend_from_slice(&0x2u64.to_le_bytes()); // MiniDumpWithFullMemory
+    for (kind, size, rva) in [
+        (SYSTEM_INFO_STREAM, system_info.len(), system_info_rva),
+        (MODULE_LIST_STREAM, module_list_size, module_list_rva),
+        (MEMORY_INFO_LIST_STREAM, memory_info.len(), memory_info_rva),
+        (MEMORY64_LIST_STREAM, memory64_size, memory64_rva),
+    ] {
+        header.extend_from_slice(&kind.to_le_bytes());
+        header.extend_from_slice(&(size as u32).to_le_bytes());
+        header.extend_from_slice(&(rva as u32).to_le_bytes());
+    }
+    header.extend_from_slice(&system_info);
+    header.extend_from_slice(&module_list);
+    header.extend_from_slice(&names);
+    header.extend_from_slice(&memory_info);
+    header.extend_from_slice(&memory64);
+
+    output.write_all(&header).map_err(|e| e.to_string())?;
+    data.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+    let copied = std::io::copy(data, output).map_err(|e| e.to_string())?;
+    Ok(header.len() as u64 + copied)
+}
+
+/// Dump every readable region of the target (optionally filtered) to
+/// `output_path`: "sparse" writes the raw bytes plus a JSON index at
+/// `<output_path>.json`, "minidump" a minidump. Reads run in parallel chunks
+/// and report progress on `dump://progress`; both formats reload with
+/// load_memory_dump.
+#[tauri::command]
+pub async fn dump_process_memory(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+    output_path: String,
+    format: Option<String>,
+    filters: Option<DumpFilters>,
+) -> Result<MemoryDumpResult, String> {
+    let format = format.unwrap_or_else(|| "sparse".to_string());
+    if format != "sparse" && format != "minidump" {
+        return Err(format!("Unknown dump format: {}", format));
+    }
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+    if host.is_empty() && !is_loaded() {
+        return Err("No server connection configured".to_string());
+    }
+    if DUMP_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A memory dump is already running".to_string());
+    }
+    DUMP_CANCEL.store(false, Ordering::SeqCst);
+    let result = run_dump(&app, state.inner(), &host, port, &output_path, &format, filters.unwrap_or_default()).await;
+    DUMP_RUNNING.store(false, Ordering::SeqCst);
+    result
+}
+
+async fn run_dump(
+    app: &AppHandle,
+    state: &AppStateType,
+    host: &str,
+    port: u16,
+    output_path: &str,
+    format: &str,
+    filters: DumpFilters,
+) -> Result<MemoryDumpResult, String> {
+    let (process, modules, arch, target_os) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        (
+            state_guard.attached_process.clone(),
+            state_guard.attached_modules.clone(),
+            state_guard.server_info.as_ref().map(|i| i.arch.clone()),
+            state_guard.server_info.as_ref().map(|i| i.target_os.clone()),
+        )
+    };
+    let regions: Vec<MemoryRegion> = memory_regions::get_cached_regions(Some(state), true).await?
+        .into_iter()
+        .filter(|r| r.readable && filters.regions.matches(r))
+        .filter(|r| filters.max_region_size.is_none_or(|max| r.size <= max))
+        .filter(|r| filters.address_ranges.is_empty()
+            || filters.address_ranges.iter().any(|&(start, end)| r.base < end && start < r.base + r.size))
+        .collect();
+
+    let data_path = if format == "sparse" { output_path.to_string() } else { format!("{}.data.tmp", output_path) };
+    let mut data = std::fs::OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&data_path)
+        .map_err(|e| format!("Failed to create {}: {}", data_path, e))?;
+
+    let mut progress = DumpProgress {
+        output_path: output_path.to_string(),
+        processed_bytes: 0,
+        total_bytes: regions.iter().map(|r| r.size).sum(),
+        regions_done: 0,
+        regions_total: regions.len(),
+        failed_bytes: 0,
+        finished: false,
+    };
+    emit_dump_progress(app, &progress);
+
+    let mut segments: Vec<SparseSegment> = Vec::new();
+    let mut written = 0u64;
+    let mut cancelled = false;
+    'regions: for region in &regions {
+        let chunks: Vec<(u64, usize)> = (0..region.size).step_by(DUMP_READ_CHUNK)
+            .map(|offset| (region.base + offset, (region.size - offset).min(DUMP_READ_CHUNK as u64) as usize))
+            .collect();
+        for batch in chunks.chunks(DUMP_PARALLEL_READS) {
+            if DUMP_CANCEL.load(Ordering::Relaxed) {
+                cancelled = true;
+                break 'regions;
+            }
+            let results = crate::read_chunks_parallel(host, port, batch, DUMP_READ_TIMEOUT).await;
+            for (&(address, size), bytes) in batch.iter().zip(results) {
+                let bytes = bytes.unwrap_or_default();
+                if !bytes.is_empty() {
+                    data.write_all(&bytes).map_err(|e| format!("Failed to write dump: {}", e))?;
+                    // Extend the previous segment when this chunk continues it
+                    match segments.last_mut() {
+                        Some(last) if last.address + last.size == address && last.protection == region.protection => last.size += bytes.len() as u64,
+                        _ => segments.push(SparseSegment {
+                            address,
+                            size: bytes.len() as u64,
+                            file_offset: written,
+                            protection: region.protection.clone(),
+                            file_path: region.mapped_file.clone(),
+                        }),
+                    }
+                    written += bytes.len() as u64;
+                }
+                progress.failed_bytes += (size - bytes.len().min(size)) as u64;
+                progress.processed_bytes += size as u64;
+            }
+            emit_dump_progress(app, &progress);
+        }
+        progress.regions_done += 1;
+    }
+
+    let (bytes_written, index_path) = if format == "sparse" {
+        let index = SparseIndex {
+            format: SPARSE_INDEX_FORMAT.to_string(),
+            version: 1,
+            arch: arch.clone(),
+            target_os,
+            process,
+            modules: modules.clone(),
+            segments: segments.clone(),
+        };
+        let index_path = sparse_index_path(output_path);
+        let json = serde_json::to_string_pretty(&index).map_err(|e| e.to_string())?;
+        std::fs::write(&index_path, json).map_err(|e| format!("Failed to write {}: {}", index_path, e))?;
+        (written, Some(index_path))
+    } else {
+        let mut output = File::create(output_path).map_err(|e| format!("Failed to create {}: {}", output_path, e))?;
+        let size = write_minidump(&mut output, &mut data, &segments, &regions, &modules, arch.as_deref());
+        drop(data);
+        let _ = std::fs::remove_file(&data_path);
+        (size?, None)
+    };
+
+    progress.finished = true;
+    emit_dump_progress(app, &progress);
+    Ok(MemoryDumpResult {
+        output_path: output_path.to_string(),
+        index_path,
+        format: format.to_string(),
+        regions: regions.len(),
+        segments: segments.len(),
+        bytes_written,
+        failed_bytes: progress.failed_bytes,
+        cancelled,
+    })
+}
+
+/// Stop a running dump_process_memory; what was read so far is still written
+#[tauri::command]
+pub fn cancel_memory_dump() -> bool {
+    DUMP_RUNNING.load(Ordering::SeqCst) && !DUMP_CANCEL.swap(true, Ordering::SeqCst)
+}
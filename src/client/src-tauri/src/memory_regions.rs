@@ -0,0 +1,156 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use crate::state::{AppStateType, ModuleInfo};
+use crate::{fetch_memory_regions_from_server, memory_dump, SERVER_CONFIG};
+
+/// Memory region normalized across target OSes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryRegion {
+    pub base: u64,
+    pub size: u64,
+    pub protection: String,          // "rwx" style
+    pub readable: bool,
+    pub writable: bool,
+    pub executable: bool,
+    pub region_type: String,         // "image" | "mapped" | "heap" | "stack" | "system" | "private"
+    pub mapped_file: Option<String>,
+    pub module_name: Option<String>, // Set for module-backed (image) regions
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryRegionFilter {
+    #[serde(default)]
+    pub readable_only: bool,
+    #[serde(default)]
+    pub writable_only: bool,
+    #[serde(default)]
+    pub executable_only: bool,
+    #[serde(default)]
+    pub module_backed_only: bool,
+    #[serde(default)]
+    pub exclude_module_backed: bool,
+}
+
+impl MemoryRegionFilter {
+    pub fn matches(&self, region: &MemoryRegion) -> bool {
+        (!self.readable_only || region.readable)
+            && (!self.writable_only || region.writable)
+            && (!self.executable_only || region.executable)
+            && (!self.module_backed_only || region.module_name.is_some())
+            && (!self.exclude_module_backed || region.module_name.is_none())
+    }
+}
+
+struct RegionCache {
+    pid: Option<u32>,
+    regions: Vec<MemoryRegion>,
+    fetched_at: std::time::Instant,
+}
+
+// Cached maps older than this are not used to clamp reads
+const CLAMP_MAX_AGE: std::time::Duration = std::time::Duration::from_secs(30);
+
+// Last enumerated memory map; replaced on refresh or when the attached pid changes
+static REGION_CACHE: Lazy<RwLock<Option<RegionCache>>> = Lazy::new(|| RwLock::new(None));
+
+/// Classify a region from its mapped path (Linux/Android pseudo paths included)
+/// and whether it lies inside a loaded module
+fn classify_region(path: Option<&str>, module: Option<&ModuleInfo>) -> &'static str {
+    if module.is_some() {
+        return "image";
+    }
+    match path {
+        None => "private",
+        Some("[heap]") => "heap",
+        Some(p) if p.starts_with("[stack") => "stack",
+        Some("[vdso]") | Some("[vvar]") | Some("[vsyscall]") => "system",
+        Some(p) if p.starts_with("[anon:") => "private",
+        Some(_) => "mapped",
+    }
+}
+
+async fn enumerate_regions(modules: &[ModuleInfo]) -> Result<Vec<MemoryRegion>, String> {
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+    if host.is_empty() && !memory_dump::is_loaded() {
+        return Err("No server connection configured".to_string());
+    }
+
+    let raw = fetch_memory_regions_from_server(&host, port).await?;
+    let mut regions: Vec<MemoryRegion> = raw.into_iter()
+        .filter(|r| r.end > r.start)
+        .map(|r| {
+            let module = modules.iter().find(|m| r.start >= m.base && r.start < m.base.saturating_add(m.size));
+            let protection = r.protection.to_lowercase();
+            MemoryRegion {
+                base: r.start,
+                size: r.end - r.start,
+                readable: protection.contains('r'),
+                writable: protection.contains('w'),
+                executable: protection.contains('x'),
+                protection,
+                region_type: classify_region(r.file_path.as_deref(), module).to_string(),
+                mapped_file: r.file_path,
+                module_name: module.map(|m| m.modulename.clone()),
+            }
+        })
+        .collect();
+    regions.sort_by_key(|r| r.base);
+    Ok(regions)
+}
+
+/// Forget the cached memory map (the target's memory source changed)
+pub fn invalidate_cache() {
+    if let Ok(mut cache) = REGION_CACHE.write() {
+        *cache = None;
+    }
+}
+
+/// Cached memory map of the attached process, enumerated on first use
+pub async fn get_cached_regions(state: Option<&AppStateType>, refresh: bool) -> Result<Vec<MemoryRegion>, String> {
+    let (pid, modules) = match state {
+        Some(state) => {
+            let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+            (state_guard.attached_process.as_ref().map(|p| p.pid), state_guard.attached_modules.clone())
+        }
+        None => (None, Vec::new()),
+    };
+
+    if !refresh {
+        let cache = REGION_CACHE.read().map_err(|e| e.to_string())?;
+        if let Some(cache) = cache.as_ref().filter(|c| state.is_none() || c.pid == pid) {
+            return Ok(cache.regions.clone());
+        }
+    }
+
+    let regions = enumerate_regions(&modules).await?;
+    *REGION_CACHE.write().map_err(|e| e.to_string())? = Some(RegionCache { pid, regions: regions.clone(), fetched_at: std::time::Instant::now() });
+    Ok(regions)
+}
+
+/// Bytes readable from `address` without leaving its region (None if the
+/// address is not in a recently cached memory map)
+pub fn readable_span(address: u64) -> Option<u64> {
+    let cache = REGION_CACHE.read().ok()?;
+    let cache = cache.as_ref().filter(|c| c.fetched_at.elapsed() < CLAMP_MAX_AGE)?;
+    let region = cache.regions.iter()
+        .find(|r| address >= r.base && address < r.base + r.size)?;
+    Some(region.base + region.size - address)
+}
+
+/// Enumerate the target's memory regions (cached until `refresh` or a new
+/// process is attached) with optional protection / module filters
+#[tauri::command]
+pub async fn get_memory_regions(
+    state: tauri::State<'_, AppStateType>,
+    filter: Option<MemoryRegionFilter>,
+    refresh: Option<bool>,
+) -> Result<Vec<MemoryRegion>, String> {
+    let filter = filter.unwrap_or_default();
+    let regions = get_cached_regions(Some(state.inner()), refresh.unwrap_or(false)).await?;
+    Ok(regions.into_iter().filter(|r| filter.matches(r)).collect())
+}
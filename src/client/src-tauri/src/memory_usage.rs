@@ -0,0 +1,276 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::{event_bus, fetch_memory_regions_from_server, RemoteMemoryRegion, SERVER_CONFIG};
+
+const DEFAULT_INTERVAL_MS: u64 = 1000;
+const MIN_INTERVAL_MS: u64 = 100;
+const DEFAULT_MAX_SAMPLES: usize = 3600;
+// Region changes listed per sample; the totals always cover everything
+const MAX_CHANGES_PER_SAMPLE: usize = 64;
+
+/// Size change of one region (keyed by start address) between two samples
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionChange {
+    pub start: u64,
+    pub previous_size: u64,      // 0 for new regions
+    pub size: u64,               // 0 for removed regions
+    pub protection: String,
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryUsageSample {
+    pub timestamp: u64,
+    pub region_count: usize,
+    pub total_bytes: u64,
+    pub writable_bytes: u64,
+    pub executable_bytes: u64,
+    pub anonymous_bytes: u64,    // Regions without a mapped file (heap, stacks, JIT)
+    pub changes: Vec<RegionChange>,
+}
+
+/// Region growth between the first and the latest sample of a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionGrowth {
+    pub start: u64,
+    pub end: u64,
+    pub initial_size: u64,
+    pub size: u64,
+    pub growth: i64,
+    pub protection: String,
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryUsageSessionInfo {
+    pub session_id: String,
+    pub interval_ms: u64,
+    pub is_running: bool,
+    pub sample_count: usize,
+    pub last_error: Option<String>,
+}
+
+struct TrackingSession {
+    interval_ms: u64,
+    max_samples: usize,
+    running: Arc<AtomicBool>,
+    samples: VecDeque<MemoryUsageSample>,
+    first_regions: HashMap<u64, RemoteMemoryRegion>,
+    last_regions: HashMap<u64, RemoteMemoryRegion>,
+    last_error: Option<String>,
+}
+
+impl TrackingSession {
+    fn info(&self, session_id: &str) -> MemoryUsageSessionInfo {
+        MemoryUsageSessionInfo {
+            session_id: session_id.to_string(),
+            interval_ms: self.interval_ms,
+            is_running: self.running.load(Ordering::Relaxed),
+            sample_count: self.samples.len(),
+            last_error: self.last_error.clone(),
+        }
+    }
+
+    /// Add a snapshot of the memory map, recording region-level changes
+    fn record(&mut self, regions: Vec<RemoteMemoryRegion>) -> MemoryUsageSample {
+        let current: HashMap<u64, RemoteMemoryRegion> = regions.into_iter().map(|r| (r.start, r)).collect();
+        let size = |r: &RemoteMemoryRegion| r.end.saturating_sub(r.start);
+
+        let mut changes: Vec<RegionChange> = Vec::new();
+        if !self.last_regions.is_empty() {
+            for region in current.values() {
+                let previous_size = self.last_regions.get(&region.start).map(size).unwrap_or(0);
+                if previous_size != size(region) {
+                    changes.push(RegionChange {
+                        start: region.start,
+                        previous_size,
+                        size: size(region),
+                        protection: region.protection.clone(),
+                        file_path: region.file_path.clone(),
+                    });
+                }
+            }
+            for region in self.last_regions.values().filter(|r| !current.contains_key(&r.start)) {
+                changes.push(RegionChange {
+                    start: region.start,
+                    previous_size: size(region),
+                    size: 0,
+                    protection: region.protection.clone(),
+                    file_path: region.file_path.clone(),
+                });
+            }
+            // Largest changes first
+            changes.sort_by_key(|c| std::cmp::Reverse(c.size.abs_diff(c.previous_size)));
+            changes.truncate(MAX_CHANGES_PER_SAMPLE);
+        }
+
+        let sum = |f: &dyn Fn(&RemoteMemoryRegion) -> bool| -> u64 {
+            current.values().filter(|r| f(r)).map(size).sum()
+        };
+        let sample = MemoryUsageSample {
+            timestamp: crate::state::AppState::current_timestamp(),
+            region_count: current.len(),
+            total_bytes: sum(&|_| true),
+            writable_bytes: sum(&|r| r.protection.contains('w')),
+            executable_bytes: sum(&|r| r.protection.contains('x')),
+            anonymous_bytes: sum(&|r| r.file_path.is_none()),
+            changes,
+        };
+
+        if self.first_regions.is_empty() {
+            self.first_regions = current.clone();
+        }
+        self.last_regions = current;
+        if self.samples.len() >= self.max_samples {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample.clone());
+        self.last_error = None;
+        sample
+    }
+}
+
+static MEMORY_USAGE_SESSIONS: Lazy<RwLock<HashMap<String, TrackingSession>>> = Lazy::new(|| {
+    RwLock::new(HashMap::new())
+});
+
+/// Start sampling the target's memory map every `interval_ms` under `session_id`.
+/// Restarting an existing session keeps its history.
+#[tauri::command]
+pub async fn start_memory_usage_tracking(
+    session_id: String,
+    interval_ms: Option<u64>,
+    max_samples: Option<usize>,
+) -> Result<MemoryUsageSessionInfo, String> {
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+    if host.is_empty() {
+        return Err("No server connection configured".to_string());
+    }
+
+    let interval_ms = interval_ms.unwrap_or(DEFAULT_INTERVAL_MS).max(MIN_INTERVAL_MS);
+    let running = Arc::new(AtomicBool::new(true));
+    let info = {
+        let mut sessions = MEMORY_USAGE_SESSIONS.write().map_err(|e| e.to_string())?;
+        let session = sessions.entry(session_id.clone()).or_insert_with(|| TrackingSession {
+            interval_ms,
+            max_samples: DEFAULT_MAX_SAMPLES,
+            running: running.clone(),
+            samples: VecDeque::new(),
+            first_regions: HashMap::new(),
+            last_regions: HashMap::new(),
+            last_error: None,
+        });
+        // Stop a previous sampling task of the same session
+        session.running.store(false, Ordering::Relaxed);
+        session.running = running.clone();
+        session.interval_ms = interval_ms;
+        session.max_samples = max_samples.unwrap_or(session.max_samples).max(1);
+        session.info(&session_id)
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+        while running.load(Ordering::Relaxed) {
+            interval.tick().await;
+            let result = fetch_memory_regions_from_server(&host, port).await;
+            if !running.load(Ordering::Relaxed) {
+                break;
+            }
+            let Ok(mut sessions) = MEMORY_USAGE_SESSIONS.write() else {
+                break;
+            };
+            let Some(session) = sessions.get_mut(&session_id) else {
+                break;
+            };
+            match result {
+                Ok(regions) => {
+                    let sample = session.record(regions);
+                    event_bus::publish(event_bus::CHANNEL_MEMORY_USAGE, Some(&session_id), &sample);
+                }
+                Err(e) => session.last_error = Some(e),
+            }
+        }
+    });
+
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn stop_memory_usage_tracking(session_id: String) -> Result<bool, String> {
+    let sessions = MEMORY_USAGE_SESSIONS.read().map_err(|e| e.to_string())?;
+    match sessions.get(&session_id) {
+        Some(session) => {
+            session.running.store(false, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Samples of a session, optionally only those newer than `since` (ms timestamp)
+#[tauri::command]
+pub fn get_memory_usage_history(session_id: String, since: Option<u64>) -> Result<Vec<MemoryUsageSample>, String> {
+    let sessions = MEMORY_USAGE_SESSIONS.read().map_err(|e| e.to_string())?;
+    let session = sessions.get(&session_id).ok_or("Memory usage session not found")?;
+    Ok(session.samples.iter()
+        .filter(|s| since.is_none_or(|t| s.timestamp > t))
+        .cloned()
+        .collect())
+}
+
+/// Regions that grew the most since the first sample, largest first. Their
+/// [start, end) ranges can be passed straight to the native scans.
+#[tauri::command]
+pub fn get_growing_regions(session_id: String, limit: Option<usize>) -> Result<Vec<RegionGrowth>, String> {
+    let sessions = MEMORY_USAGE_SESSIONS.read().map_err(|e| e.to_string())?;
+    let session = sessions.get(&session_id).ok_or("Memory usage session not found")?;
+    let mut growth: Vec<RegionGrowth> = session.last_regions.values()
+        .map(|r| {
+            let size = r.end.saturating_sub(r.start);
+            let initial_size = session.first_regions.get(&r.start)
+                .map(|f| f.end.saturating_sub(f.start))
+                .unwrap_or(0);
+            RegionGrowth {
+                start: r.start,
+                end: r.end,
+                initial_size,
+                size,
+                growth: size as i64 - initial_size as i64,
+                protection: r.protection.clone(),
+                file_path: r.file_path.clone(),
+            }
+        })
+        .filter(|g| g.growth > 0)
+        .collect();
+    growth.sort_by_key(|g| std::cmp::Reverse(g.growth));
+    growth.truncate(limit.unwrap_or(50));
+    Ok(growth)
+}
+
+#[tauri::command]
+pub fn list_memory_usage_sessions() -> Result<Vec<MemoryUsageSessionInfo>, String> {
+    let sessions = MEMORY_USAGE_SESSIONS.read().map_err(|e| e.to_string())?;
+    let mut infos: Vec<MemoryUsageSessionInfo> = sessions.iter().map(|(id, s)| s.info(id)).collect();
+    infos.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+    Ok(infos)
+}
+
+/// Stop a session and drop its history
+#[tauri::command]
+pub fn clear_memory_usage_session(session_id: String) -> Result<bool, String> {
+    let mut sessions = MEMORY_USAGE_SESSIONS.write().map_err(|e| e.to_string())?;
+    match sessions.remove(&session_id) {
+        Some(session) => {
+            session.running.store(false, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,212 @@
+use object::{BinaryFormat, Object, ObjectSection, ObjectSegment, SectionKind};
+use serde::{Deserialize, Serialize};
+
+use crate::{disassemble_bytes, read_memory_from_server, SERVER_CONFIG};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDiffRequest {
+    pub module_name: String,
+    pub base: u64,                    // Load address of the module in the target
+    pub local_path: String,           // On-disk / downloaded original
+    pub architecture: String,         // "x86", "x86_64", "arm", "arm64"
+    #[serde(default)]
+    pub include_data: bool,           // Also compare writable/read-only data sections
+    #[serde(default)]
+    pub max_ranges: Option<usize>,    // Stop after this many modified ranges (default 256)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifiedRange {
+    pub section: String,
+    pub address: u64,                 // Address in the target
+    pub module_offset: u64,           // Offset from module base
+    pub size: usize,
+    pub original_bytes: String,       // Hex
+    pub current_bytes: String,        // Hex
+    pub original_disassembly: Option<String>,  // Same line format as disassemble_memory
+    pub current_disassembly: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDiffResult {
+    pub success: bool,
+    pub module_name: String,
+    pub compared_bytes: u64,
+    pub relocated_words: u64,         // Differences explained by the load bias and ignored
+    pub unreadable_bytes: u64,
+    pub modified_ranges: Vec<ModifiedRange>,
+    pub truncated: bool,
+    pub error: Option<String>,
+}
+
+// Differences closer than this are reported as one range
+const MERGE_GAP: usize = 8;
+// Extra bytes disassembled after a range so the patched instruction is complete
+const DISASM_PADDING: usize = 16;
+const READ_CHUNK: usize = 1024 * 1024;
+
+/// Start of a pointer-sized window around `pos` whose file and memory values
+/// differ exactly by the load bias, i.e. the difference is an applied relocation
+fn relocation_window(file: &[u8], mem: &[u8], pos: usize, ptr_size: usize, bias: u64) -> Option<usize> {
+    let first = pos.saturating_sub(ptr_size - 1);
+    (first..=pos).find(|&start| {
+        let end = start + ptr_size;
+        if end > file.len() || end > mem.len() {
+            return false;
+        }
+        if ptr_size == 8 {
+            let f = u64::from_le_bytes(file[start..end].try_into().unwrap());
+            let m = u64::from_le_bytes(mem[start..end].try_into().unwrap());
+            m.wrapping_sub(f) == bias
+        } else {
+            let f = u32::from_le_bytes(file[start..end].try_into().unwrap());
+            let m = u32::from_le_bytes(mem[start..end].try_into().unwrap());
+            m.wrapping_sub(f) == bias as u32
+        }
+    })
+}
+
+async fn disassemble_lines(bytes: &[u8], address: u64, architecture: &str) -> Option<String> {
+    disassemble_bytes(bytes.to_vec(), address, architecture.to_string(), None)
+        .ok()
+        .and_then(|r| r.disassembly)
+}
+
+/// Compare the loaded image of a module against its original file and report
+/// modified ranges (inline hooks, patches) with disassembly of both sides.
+/// Differences that equal the load bias in a pointer-sized window are treated
+/// as relocations and ignored.
+#[tauri::command]
+pub async fn diff_module_against_file(request: ModuleDiffRequest) -> Result<ModuleDiffResult, String> {
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+
+    let mut result = ModuleDiffResult {
+        success: false,
+        module_name: request.module_name.clone(),
+        compared_bytes: 0,
+        relocated_words: 0,
+        unreadable_bytes: 0,
+        modified_ranges: Vec::new(),
+        truncated: false,
+        error: None,
+    };
+
+    if host.is_empty() {
+        result.error = Some("No server connection configured".to_string());
+        return Ok(result);
+    }
+
+    let file_data = tokio::fs::read(&request.local_path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", request.local_path, e))?;
+    let file = object::File::parse(&*file_data)
+        .map_err(|e| format!("Failed to parse {}: {}", request.local_path, e))?;
+
+    // Section addresses are relative to this: ImageBase for PE, __TEXT for
+    // Mach-O. ELF reports 0, so its lowest loaded segment is used instead
+    // (non-PIE images are mapped at their link address).
+    let image_base = match file.format() {
+        BinaryFormat::Elf => file.segments()
+            .filter(|s| s.file_range().1 > 0)
+            .map(|s| s.address())
+            .min()
+            .unwrap_or(0),
+        _ => file.relative_address_base(),
+    };
+    let bias = request.base.wrapping_sub(image_base);
+    let ptr_size = if file.is_64() { 8 } else { 4 };
+    let max_ranges = request.max_ranges.unwrap_or(256);
+
+    for section in file.sections() {
+        if result.truncated {
+            break;
+        }
+        let is_code = section.kind() == SectionKind::Text;
+        let is_data = matches!(section.kind(), SectionKind::Data | SectionKind::ReadOnlyData);
+        if !(is_code || (request.include_data && is_data)) {
+            continue;
+        }
+        let Ok(original) = section.data() else {
+            continue;
+        };
+        if original.is_empty() {
+            continue;
+        }
+
+        let section_name = section.name().unwrap_or("").to_string();
+        let module_offset = section.address().wrapping_sub(image_base);
+        let section_address = request.base.wrapping_add(module_offset);
+
+        // Read the live section in chunks; unreadable chunks are skipped
+        let mut current = vec![0u8; original.len()];
+        let mut readable = vec![false; original.len().div_ceil(READ_CHUNK)];
+        for (chunk_idx, chunk_start) in (0..original.len()).step_by(READ_CHUNK).enumerate() {
+            let size = READ_CHUNK.min(original.len() - chunk_start);
+            match read_memory_from_server(&host, port, section_address + chunk_start as u64, size).await {
+                Ok(data) if data.len() == size => {
+                    current[chunk_start..chunk_start + size].copy_from_slice(&data);
+                    readable[chunk_idx] = true;
+                    result.compared_bytes += size as u64;
+                }
+                _ => result.unreadable_bytes += size as u64,
+            }
+        }
+
+        // Collect differing byte ranges, skipping relocated pointers
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        let mut pos = 0;
+        while pos < original.len() {
+            if !readable[pos / READ_CHUNK] {
+                pos = (pos / READ_CHUNK + 1) * READ_CHUNK;
+                continue;
+            }
+            if original[pos] == current[pos] {
+                pos += 1;
+                continue;
+            }
+            if let Some(start) = relocation_window(original, &current, pos, ptr_size, bias) {
+                result.relocated_words += 1;
+                pos = start + ptr_size;
+                continue;
+            }
+            match ranges.last_mut() {
+                Some(last) if pos - last.1 <= MERGE_GAP => last.1 = pos + 1,
+                _ => ranges.push((pos, pos + 1)),
+            }
+            pos += 1;
+        }
+
+        for (start, end) in ranges {
+            if result.modified_ranges.len() >= max_ranges {
+                result.truncated = true;
+                break;
+            }
+            let address = section_address + start as u64;
+            let (original_disassembly, current_disassembly) = if is_code {
+                let disasm_end = (end + DISASM_PADDING).min(original.len());
+                (
+                    disassemble_lines(&original[start..disasm_end], address, &request.architecture).await,
+                    disassemble_lines(&current[start..disasm_end], address, &request.architecture).await,
+                )
+            } else {
+                (None, None)
+            };
+            result.modified_ranges.push(ModifiedRange {
+                section: section_name.clone(),
+                address,
+                module_offset: module_offset + start as u64,
+                size: end - start,
+                original_bytes: hex::encode(&original[start..end]),
+                current_bytes: hex::encode(&current[start..end]),
+                original_disassembly,
+                current_disassembly,
+            });
+        }
+    }
+
+    result.success = true;
+    Ok(result)
+}
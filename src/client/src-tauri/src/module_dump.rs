@@ -0,0 +1,366 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::build_id::{u16_at, u32_at, u64_at};
+use crate::state::AppStateType;
+use crate::{get_ghidra_projects_dir, read_chunks_parallel, SERVER_CONFIG};
+
+const READ_CHUNK: usize = 1024 * 1024;
+const PARALLEL_READS: usize = 8;
+const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Module image saved by dump_module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DumpedModule {
+    pub module_name: String,
+    pub base: u64,
+    pub size: u64,
+    pub local_path: String,           // Ready for analyze_with_ghidra
+    pub format: Option<String>,       // "pe" | "elf" | "macho" when recognized
+    pub unreadable_bytes: u64,        // Zero-filled pages that could not be read
+    pub fixups: Vec<String>,          // Header repairs applied, in order
+}
+
+fn put_u16(buf: &mut [u8], pos: usize, value: u16, big_endian: bool) {
+    if let Some(slot) = buf.get_mut(pos..pos + 2) {
+        slot.copy_from_slice(&if big_endian { value.to_be_bytes() } else { value.to_le_bytes() });
+    }
+}
+
+fn put_u32(buf: &mut [u8], pos: usize, value: u32, big_endian: bool) {
+    if let Some(slot) = buf.get_mut(pos..pos + 4) {
+        slot.copy_from_slice(&if big_endian { value.to_be_bytes() } else { value.to_le_bytes() });
+    }
+}
+
+fn put_u64(buf: &mut [u8], pos: usize, value: u64, big_endian: bool) {
+    if let Some(slot) = buf.get_mut(pos..pos + 8) {
+        slot.copy_from_slice(&if big_endian { value.to_be_bytes() } else { value.to_le_bytes() });
+    }
+}
+
+/// PE: the image is in mapped layout, so point every section's raw data at
+/// its RVA and make ImageBase the load address (relocations are already
+/// applied in memory, so the base relocation table becomes a no-op)
+fn fixup_pe(image: &mut [u8], base: u64, fixups: &mut Vec<String>) -> Result<(), String> {
+    let pe = u32_at(image, 0x3c, false).ok_or("Truncated DOS header")? as usize;
+    if image.get(pe..pe + 4) != Some(b"PE\0\0") {
+        return Err("Missing PE signature".to_string());
+    }
+    let section_count = u16_at(image, pe + 6, false).ok_or("Truncated COFF header")? as usize;
+    let optional_size = u16_at(image, pe + 20, false).ok_or("Truncated COFF header")? as usize;
+    let optional = pe + 24;
+    let is_64 = match u16_at(image, optional, false) {
+        Some(0x20b) => true,
+        Some(0x10b) => false,
+        _ => return Err("Unknown optional header magic".to_string()),
+    };
+
+    if is_64 {
+        put_u64(image, optional + 24, base, false);
+    } else {
+        put_u32(image, optional + 28, base as u32, false);
+    }
+    fixups.push(format!("ImageBase set to load address 0x{:x}", base));
+    let section_alignment = u32_at(image, optional + 32, false).unwrap_or(0x1000);
+    put_u32(image, optional + 36, section_alignment, false);
+    put_u32(image, optional + 64, 0, false); // CheckSum
+    fixups.push("FileAlignment set to SectionAlignment".to_string());
+
+    let image_len = image.len() as u64;
+    let table = optional + optional_size;
+    for index in 0..section_count {
+        let entry = table + index * 40;
+        let (Some(virtual_size), Some(virtual_address), Some(raw_size)) =
+            (u32_at(image, entry + 8, false), u32_at(image, entry + 12, false), u32_at(image, entry + 16, false)) else {
+            break;
+        };
+        let size = (virtual_size.max(raw_size) as u64).min(image_len.saturating_sub(virtual_address as u64));
+        put_u32(image, entry + 16, size as u32, false);
+        put_u32(image, entry + 20, virtual_address, false);
+    }
+    fixups.push(format!("{} section(s) remapped to raw offset = RVA", section_count));
+    fixups.push("Import address table holds resolved pointers".to_string());
+    Ok(())
+}
+
+/// ELF: program headers are remapped to the memory layout (offset = vaddr -
+/// first PT_LOAD, filesz = memsz); section headers are dropped because they
+/// are normally not mapped and would point at garbage
+fn fixup_elf(image: &mut [u8], base: u64, fixups: &mut Vec<String>) -> Result<(), String> {
+    let is_64 = match image.get(4) {
+        Some(2) => true,
+        Some(1) => false,
+        _ => return Err("Unknown ELF class".to_string()),
+    };
+    let be = image.get(5) == Some(&2);
+    let (phoff, phentsize, phnum) = if is_64 {
+        (u64_at(image, 0x20, be), u16_at(image, 0x36, be), u16_at(image, 0x38, be))
+    } else {
+        (u32_at(image, 0x1c, be).map(u64::from), u16_at(image, 0x2a, be), u16_at(image, 0x2c, be))
+    };
+    let (Some(phoff), Some(phentsize), Some(phnum)) = (phoff, phentsize, phnum) else {
+        return Err("Truncated ELF header".to_string());
+    };
+    let header = |index: usize| phoff as usize + index * phentsize as usize;
+    // (p_type, p_offset, p_vaddr, p_filesz, p_memsz) field offsets
+    let fields = if is_64 { (0, 8, 16, 32, 40) } else { (0, 4, 8, 16, 20) };
+    let read_word = |image: &[u8], pos: usize| {
+        if is_64 { u64_at(image, pos, be) } else { u32_at(image, pos, be).map(u64::from) }
+    };
+
+    let origin = (0..phnum as usize)
+        .filter(|&i| u32_at(image, header(i) + fields.0, be) == Some(1))
+        .filter_map(|i| read_word(image, header(i) + fields.2))
+        .min()
+        .ok_or("No PT_LOAD segments")?
+        & !0xfff;
+
+    let image_len = image.len() as u64;
+    let mut remapped = 0;
+    for index in 0..phnum as usize {
+        let entry = header(index);
+        let (Some(p_type), Some(vaddr), Some(memsz)) =
+            (u32_at(image, entry + fields.0, be), read_word(image, entry + fields.2), read_word(image, entry + fields.4)) else {
+            break;
+        };
+        if vaddr < origin || vaddr - origin >= image_len {
+            continue;
+        }
+        let offset = vaddr - origin;
+        let filesz = if p_type == 1 { memsz.min(image_len - offset) } else { read_word(image, entry + fields.3).unwrap_or(0) };
+        if is_64 {
+            put_u64(image, entry + fields.1, offset, be);
+            put_u64(image, entry + fields.3, filesz, be);
+        } else {
+            put_u32(image, entry + fields.1, offset as u32, be);
+            put_u32(image, entry + fields.3, filesz as u32, be);
+        }
+        remapped += 1;
+    }
+    fixups.push(format!("{} program header(s) remapped to the memory layout", remapped));
+
+    if is_64 {
+        put_u64(image, 0x28, 0, be);
+        put_u16(image, 0x3c, 0, be);
+        put_u16(image, 0x3e, 0, be);
+    } else {
+        put_u32(image, 0x20, 0, be);
+        put_u16(image, 0x30, 0, be);
+        put_u16(image, 0x32, 0, be);
+    }
+    fixups.push("Section headers removed".to_string());
+    if base != origin {
+        fixups.push(format!("Pointers are relocated for load address 0x{:x}; import at that base", base));
+    }
+    Ok(())
+}
+
+/// Mach-O: segment and section file offsets are remapped to their position
+/// in the memory image, and __LINKEDIT references follow __LINKEDIT
+fn fixup_macho(image: &mut [u8], base: u64, fixups: &mut Vec<String>) -> Result<(), String> {
+    let (is_64, be) = match image.get(0..4) {
+        Some([0xcf, 0xfa, 0xed, 0xfe]) => (true, false),
+        Some([0xce, 0xfa, 0xed, 0xfe]) => (false, false),
+        Some([0xfe, 0xed, 0xfa, 0xcf]) => (true, true),
+        Some([0xfe, 0xed, 0xfa, 0xce]) => (false, true),
+        _ => return Err("Unknown Mach-O magic".to_string()),
+    };
+    let ncmds = u32_at(image, 16, be).ok_or("Truncated Mach-O header")? as usize;
+    let first_command = if is_64 { 32 } else { 28 };
+    let commands: Vec<(usize, u32)> = {
+        let mut commands = Vec::with_capacity(ncmds);
+        let mut pos = first_command;
+        for _ in 0..ncmds {
+            let (Some(cmd), Some(size)) = (u32_at(image, pos, be), u32_at(image, pos + 4, be)) else {
+                break;
+            };
+            commands.push((pos, cmd));
+            if size < 8 {
+                break;
+            }
+            pos += size as usize;
+        }
+        commands
+    };
+    let segment_cmd = if is_64 { 0x19 } else { 0x1 };
+    // (vmaddr, vmsize, fileoff, filesize, nsects, first section, section size,
+    // section addr, section offset)
+    let layout = if is_64 { (24, 32, 40, 48, 64, 72, 80, 32, 48) } else { (24, 28, 32, 36, 48, 56, 68, 32, 40) };
+    let read_word = |image: &[u8], pos: usize| {
+        if is_64 { u64_at(image, pos, be) } else { u32_at(image, pos, be).map(u64::from) }
+    };
+    let write_word = |image: &mut [u8], pos: usize, value: u64| {
+        if is_64 { put_u64(image, pos, value, be) } else { put_u32(image, pos, value as u32, be) }
+    };
+
+    // The segment mapped from file offset 0 (__TEXT) is at the module base
+    let origin = commands.iter()
+        .filter(|&&(_, cmd)| cmd == segment_cmd)
+        .find(|&&(pos, _)| read_word(image, pos + layout.2) == Some(0) && read_word(image, pos + layout.3).unwrap_or(0) > 0)
+        .and_then(|&(pos, _)| read_word(image, pos + layout.0))
+        .ok_or("No __TEXT segment")?;
+
+    let image_len = image.len() as u64;
+    let mut linkedit_delta: Option<i64> = None;
+    let mut remapped = 0;
+    for &(pos, cmd) in &commands {
+        if cmd != segment_cmd {
+            continue;
+        }
+        let (Some(vmaddr), Some(vmsize), Some(fileoff)) =
+            (read_word(image, pos + layout.0), read_word(image, pos + layout.1), read_word(image, pos + layout.2)) else {
+            continue;
+        };
+        if vmaddr < origin || vmaddr - origin >= image_len {
+            continue; // __PAGEZERO or not part of the image
+        }
+        let offset = vmaddr - origin;
+        write_word(image, pos + layout.2, offset);
+        write_word(image, pos + layout.3, vmsize.min(image_len - offset));
+        if image.get(pos + 8..pos + 18) == Some(b"__LINKEDIT") {
+            linkedit_delta = Some(offset as i64 - fileoff as i64);
+        }
+        let nsects = u32_at(image, pos + layout.4, be).unwrap_or(0) as usize;
+        for index in 0..nsects {
+            let section = pos + layout.5 + index * layout.6;
+            let Some(addr) = read_word(image, section + layout.7) else {
+                break;
+            };
+            let zerofill = u32_at(image, section + layout.8, be) == Some(0);
+            if !zerofill && addr >= origin {
+                put_u32(image, section + layout.8, (addr - origin) as u32, be);
+            }
+        }
+        remapped += 1;
+    }
+    fixups.push(format!("{} segment(s) remapped to the memory layout", remapped));
+
+    if let Some(delta) = linkedit_delta.filter(|&d| d != 0) {
+        // Offset fields of commands that point into __LINKEDIT
+        let mut moved = 0;
+        for &(pos, cmd) in &commands {
+            let fields: &[usize] = match cmd {
+                0x2 => &[8, 16],                                  // LC_SYMTAB
+                0xb => &[32, 40, 48, 56, 64, 72],                 // LC_DYSYMTAB
+                0x22 | 0x8000_0022 => &[8, 16, 24, 32, 40],       // LC_DYLD_INFO(_ONLY)
+                0x1d | 0x1e | 0x26 | 0x29 | 0x2b | 0x2e
+                | 0x8000_0033 | 0x8000_0034 => &[8],              // linkedit_data_command
+                _ => &[],
+            };
+            for &field in fields {
+                if let Some(value) = u32_at(image, pos + field, be).filter(|&v| v != 0) {
+                    put_u32(image, pos + field, (value as i64 + delta) as u32, be);
+                    moved += 1;
+                }
+            }
+        }
+        fixups.push(format!("{} __LINKEDIT offset(s) moved by {}", moved, delta));
+    }
+    if base != origin {
+        fixups.push(format!("Pointers are slid to load address 0x{:x}; import at that base", base));
+    }
+    Ok(())
+}
+
+/// Read a loaded module's image from memory and save it for Ghidra. With
+/// `fixup_headers` the PE/ELF/Mach-O headers are rewritten so file offsets
+/// match the memory layout; this also works for packed or memory-only modules
+/// that download_library_file cannot fetch.
+#[tauri::command]
+pub async fn dump_module(
+    state: tauri::State<'_, AppStateType>,
+    module_name: String,
+    fixup_headers: Option<bool>,
+    project_name: Option<String>,
+    output_path: Option<String>,
+) -> Result<DumpedModule, String> {
+    let module = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        state_guard.attached_modules.iter()
+            .find(|m| m.modulename == module_name)
+            .or_else(|| state_guard.attached_modules.iter().find(|m| m.modulename.eq_ignore_ascii_case(&module_name)))
+            .cloned()
+            .ok_or_else(|| format!("Module not loaded: {}", module_name))?
+    };
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+    if host.is_empty() && !crate::memory_dump::is_loaded() {
+        return Err("No server connection configured".to_string());
+    }
+    if module.size == 0 {
+        return Err(format!("Module {} has no size", module.modulename));
+    }
+
+    let mut image = vec![0u8; module.size as usize];
+    let mut unreadable_bytes = 0u64;
+    let chunks: Vec<(u64, usize)> = (0..module.size).step_by(READ_CHUNK)
+        .map(|offset| (module.base + offset, (module.size - offset).min(READ_CHUNK as u64) as usize))
+        .collect();
+    for batch in chunks.chunks(PARALLEL_READS) {
+        let results = read_chunks_parallel(&host, port, batch, READ_TIMEOUT).await;
+        for (&(address, size), bytes) in batch.iter().zip(results) {
+            let bytes = bytes.unwrap_or_default();
+            let start = (address - module.base) as usize;
+            let len = bytes.len().min(size);
+            image[start..start + len].copy_from_slice(&bytes[..len]);
+            unreadable_bytes += (size - len) as u64;
+        }
+    }
+    if unreadable_bytes == module.size {
+        return Err(format!("Failed to read any memory of {}", module.modulename));
+    }
+
+    let format = if image.starts_with(b"MZ") {
+        Some("pe")
+    } else if image.starts_with(b"\x7fELF") {
+        Some("elf")
+    } else if matches!(image.get(0..4), Some([0xcf | 0xce, 0xfa, 0xed, 0xfe] | [0xfe, 0xed, 0xfa, 0xcf | 0xce])) {
+        Some("macho")
+    } else {
+        None
+    };
+    let mut fixups = Vec::new();
+    if fixup_headers.unwrap_or(true) {
+        let result = match format {
+            Some("pe") => fixup_pe(&mut image, module.base, &mut fixups),
+            Some("elf") => fixup_elf(&mut image, module.base, &mut fixups),
+            Some("macho") => fixup_macho(&mut image, module.base, &mut fixups),
+            _ => Err("Unrecognized image format; saved unmodified".to_string()),
+        };
+        if let Err(e) = result {
+            fixups.push(format!("Header fixup skipped: {}", e));
+        }
+    }
+
+    let local_path = match output_path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let mut dir = get_ghidra_projects_dir().join("libraries");
+            if let Some(project_name) = &project_name {
+                dir = dir.join(project_name);
+            }
+            let file_name = PathBuf::from(&module.modulename)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "unknown_module".to_string());
+            dir.join("dumped").join(file_name)
+        }
+    };
+    if let Some(parent) = local_path.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    tokio::fs::write(&local_path, &image).await.map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(DumpedModule {
+        module_name: module.modulename,
+        base: module.base,
+        size: module.size,
+        local_path: local_path.to_string_lossy().to_string(),
+        format: format.map(String::from),
+        unreadable_bytes,
+        fixups,
+    })
+}
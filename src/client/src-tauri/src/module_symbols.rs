@@ -0,0 +1,307 @@
+use ring::digest::{digest, SHA256};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::process_control::get_json;
+use crate::state::{AppStateType, CachedModuleInfo, CachedSymbolInfo, DebuggerSidebarCacheType, ModuleInfo};
+use crate::{build_id, db, demangle_name};
+
+const DEFAULT_PAGE_SIZE: usize = 5_000;
+const MAX_PAGE_SIZE: usize = 50_000;
+
+/// Module row of list_modules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListedModule {
+    #[serde(flatten)]
+    pub module: CachedModuleInfo,
+    pub module_hash: String,            // Symbol cache key: build-id when known, else hash of name/path/size
+    pub cached_symbols: Option<usize>,  // Symbols stored for this hash (None = not fetched yet)
+}
+
+/// Symbol with its demangled name; addresses are rebased on the current module base
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleSymbol {
+    #[serde(flatten)]
+    pub info: CachedSymbolInfo,
+    pub mangled_name: Option<String>,   // Original name when demangling changed it
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleSymbolsPage {
+    pub module_name: String,
+    pub module_hash: String,
+    pub total: usize,
+    pub offset: usize,
+    pub symbols: Vec<ModuleSymbol>,
+    pub next_offset: Option<usize>,     // None on the last page
+    pub from_cache: bool,               // Served from SQLite without asking the server
+}
+
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS module_symbol_sets (
+            module_hash TEXT PRIMARY KEY,
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            symbol_count INTEGER NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    // Symbols ordered by module offset; symbol_json holds the remaining fields
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS module_symbols (
+            module_hash TEXT NOT NULL,
+            idx INTEGER NOT NULL,
+            module_offset INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            mangled_name TEXT,
+            symbol_json TEXT NOT NULL,
+            PRIMARY KEY(module_hash, idx)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn target_os(state: &AppStateType) -> Result<String, String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    Ok(state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default())
+}
+
+fn module_hash(conn: &Connection, target_os: &str, module: &CachedModuleInfo) -> String {
+    if let Some(build_id) = build_id::get_stored_build_id(conn, target_os, &module.modulename) {
+        return format!("id:{}", build_id);
+    }
+    let key = format!("{}|{}|{}|{}", target_os, module.modulename, module.path.as_deref().unwrap_or(""), module.size);
+    hex::encode(&digest(&SHA256, key.as_bytes()).as_ref()[..16])
+}
+
+fn cached_count(conn: &Connection, module_hash: &str) -> Option<usize> {
+    conn.query_row(
+        "SELECT symbol_count FROM module_symbol_sets WHERE module_hash = ?1",
+        params![module_hash],
+        |row| row.get::<_, i64>(0),
+    ).ok().map(|count| count as usize)
+}
+
+async fn fetch_modules() -> Result<Vec<CachedModuleInfo>, String> {
+    let response = get_json("/api/modules").await?;
+    let modules = response["data"]["modules"].as_array().cloned().unwrap_or_default();
+    Ok(modules.into_iter().filter_map(|m| serde_json::from_value(m).ok()).collect())
+}
+
+fn parse_address(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches("0x").trim_start_matches("0X"), 16).ok()
+}
+
+/// Fetch, demangle and store every symbol of a module; returns them by offset
+async fn fetch_and_store(target_os: &str, module: &CachedModuleInfo, module_hash: &str) -> Result<Vec<ModuleSymbol>, String> {
+    let response = get_json(&format!("/api/modules/{}/symbols", module.base)).await?;
+    let raw = response["data"]["symbols"].as_array().cloned().unwrap_or_default();
+
+    let mut symbols: Vec<(u64, ModuleSymbol)> = raw.into_iter()
+        .filter_map(|s| {
+            let address = parse_address(s["address"].as_str()?)?;
+            let raw_name = s["name"].as_str()?.to_string();
+            let name = demangle_name(&raw_name);
+            let info = CachedSymbolInfo {
+                address: format!("0x{:x}", address),
+                name: name.clone(),
+                size: s["size"].as_u64().unwrap_or(0),
+                symbol_type: s["type"].as_str().unwrap_or("").to_string(),
+                scope: s["scope"].as_str().unwrap_or("").to_string(),
+                module_base: format!("0x{:x}", module.base),
+                file_name: s["file_name"].as_str().filter(|f| !f.is_empty()).map(String::from),
+                line_number: s["line_number"].as_u64().filter(|&l| l > 0).map(|l| l as u32),
+                is_external: s["is_external"].as_bool(),
+                is_private_external: s["is_private_external"].as_bool(),
+                is_weak_def: s["is_weak_def"].as_bool(),
+                is_weak_ref: s["is_weak_ref"].as_bool(),
+                is_thumb: s["is_thumb"].as_bool(),
+                section_index: s["section_index"].as_u64().map(|v| v as u32),
+                library_ordinal: s["library_ordinal"].as_u64().map(|v| v as u32),
+            };
+            let mangled_name = (name != raw_name).then_some(raw_name);
+            Some((address.wrapping_sub(module.base), ModuleSymbol { info, mangled_name }))
+        })
+        .collect();
+    symbols.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.info.name.cmp(&b.1.info.name)));
+
+    let (target_os, module_name, module_hash) = (target_os.to_string(), module.modulename.clone(), module_hash.to_string());
+    db::run(move |conn| {
+        let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        tx.execute("DELETE FROM module_symbols WHERE module_hash = ?1", params![module_hash]).map_err(|e| e.to_string())?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO module_symbols (module_hash, idx, module_offset, name, mangled_name, symbol_json)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            ).map_err(|e| e.to_string())?;
+            for (idx, (offset, symbol)) in symbols.iter().enumerate() {
+                let json = serde_json::to_string(&symbol.info).map_err(|e| e.to_string())?;
+                insert.execute(params![module_hash, idx as i64, *offset as i64, symbol.info.name, symbol.mangled_name, json])
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO module_symbol_sets (module_hash, target_os, module_name, symbol_count, updated_at)
+             VALUES (?1, ?2, ?3, ?4, datetime('now'))",
+            params![module_hash, target_os, module_name, symbols.len() as i64],
+        ).map_err(|e| e.to_string())?;
+        tx.commit().map_err(|e| e.to_string())?;
+
+        Ok(symbols.into_iter().map(|(_, symbol)| symbol).collect())
+    }).await
+}
+
+/// Module offset of a stored symbol, by demangled or mangled name; only reads
+/// what list_module_symbols already cached. Blocks on SQLite; async code
+/// calls it from a blocking thread
+pub fn stored_symbol_offset(target_os: &str, module: &ModuleInfo, name: &str) -> Option<u64> {
+    let module = CachedModuleInfo {
+        modulename: module.modulename.clone(),
+        base: module.base,
+        size: module.size,
+        path: module.path.clone(),
+        is_64bit: module.is_64bit,
+    };
+    db::run_sync(|conn| {
+        let module_hash = module_hash(conn, target_os, &module);
+        Ok(conn.query_row(
+            "SELECT module_offset FROM module_symbols WHERE module_hash = ?1 AND (name = ?2 OR mangled_name = ?2) ORDER BY idx LIMIT 1",
+            params![module_hash, name],
+            |row| row.get::<_, i64>(0),
+        ).ok().map(|offset| offset as u64))
+    }).ok().flatten()
+}
+
+/// Page of stored symbols, rebased on `base`
+fn read_page(conn: &Connection, module_hash: &str, base: u64, offset: usize, limit: usize) -> Result<Vec<ModuleSymbol>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT module_offset, mangled_name, symbol_json FROM module_symbols
+         WHERE module_hash = ?1 AND idx >= ?2 ORDER BY idx LIMIT ?3",
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![module_hash, offset as i64, limit as i64], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, String>(2)?))
+    }).map_err(|e| e.to_string())?;
+
+    let mut symbols = Vec::new();
+    for row in rows {
+        let (module_offset, mangled_name, json) = row.map_err(|e| e.to_string())?;
+        let mut info: CachedSymbolInfo = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        info.address = format!("0x{:x}", base.wrapping_add(module_offset as u64));
+        info.module_base = format!("0x{:x}", base);
+        symbols.push(ModuleSymbol { info, mangled_name });
+    }
+    Ok(symbols)
+}
+
+/// Loaded modules from the server with their symbol cache keys; also
+/// refreshes the sidebar module cache
+#[tauri::command]
+pub async fn list_modules(
+    state: tauri::State<'_, AppStateType>,
+    cache: tauri::State<'_, DebuggerSidebarCacheType>,
+) -> Result<Vec<ListedModule>, String> {
+    let target_os = target_os(&state)?;
+    let pid = state.lock().ok().and_then(|s| s.attached_process.as_ref().map(|p| p.pid));
+    let modules = fetch_modules().await?;
+
+    if let Ok(mut cache_guard) = cache.lock() {
+        cache_guard.modules = modules.clone();
+        cache_guard.cached_process_pid = pid;
+        cache_guard.last_update = crate::state::AppState::current_timestamp();
+    }
+
+    db::run(move |conn| {
+        Ok(modules.into_iter()
+            .map(|module| {
+                let module_hash = module_hash(conn, &target_os, &module);
+                let cached_symbols = cached_count(conn, &module_hash);
+                ListedModule { module, module_hash, cached_symbols }
+            })
+            .collect())
+    }).await
+}
+
+/// Demangled symbols of a module, a page at a time. The first request for a
+/// module (or `refresh`) fetches the full list from the server and stores it
+/// in SQLite; later pages and later sessions read from there.
+#[tauri::command]
+pub async fn list_module_symbols(
+    state: tauri::State<'_, AppStateType>,
+    cache: tauri::State<'_, DebuggerSidebarCacheType>,
+    module_name: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    refresh: Option<bool>,
+) -> Result<ModuleSymbolsPage, String> {
+    let target_os = target_os(&state)?;
+    let attached = state.lock().ok()
+        .and_then(|s| s.attached_modules.iter().find(|m| m.modulename == module_name).cloned());
+    let module = match attached {
+        Some(m) => CachedModuleInfo { modulename: m.modulename, base: m.base, size: m.size, path: m.path, is_64bit: m.is_64bit },
+        None => fetch_modules().await?
+            .into_iter()
+            .find(|m| m.modulename == module_name)
+            .ok_or_else(|| format!("Module not loaded: {}", module_name))?,
+    };
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let (module_hash, cached) = {
+        let (target_os, module, refresh) = (target_os.clone(), module.clone(), refresh.unwrap_or(false));
+        db::run(move |conn| {
+            let module_hash = module_hash(conn, &target_os, &module);
+            if refresh {
+                return Ok((module_hash, None));
+            }
+            let page = cached_count(conn, &module_hash)
+                .map(|total| Ok::<_, String>((total, read_page(conn, &module_hash, module.base, offset, limit)?)))
+                .transpose()?;
+            Ok((module_hash, page))
+        }).await?
+    };
+    let (total, symbols, from_cache) = match cached {
+        Some((total, page)) => (total, page, true),
+        None => {
+            let all = fetch_and_store(&target_os, &module, &module_hash).await?;
+            if let Ok(mut cache_guard) = cache.lock() {
+                cache_guard.symbols = all.iter().map(|s| s.info.clone()).collect();
+                cache_guard.cached_module_path = module.path.clone().or_else(|| Some(module.modulename.clone()));
+                cache_guard.last_update = crate::state::AppState::current_timestamp();
+            }
+            let page = all.iter().skip(offset).take(limit).cloned().collect();
+            (all.len(), page, false)
+        }
+    };
+
+    let end = offset + symbols.len();
+    Ok(ModuleSymbolsPage {
+        module_name,
+        module_hash,
+        total,
+        offset,
+        symbols,
+        next_offset: (end < total).then_some(end),
+        from_cache,
+    })
+}
+
+/// Drop stored symbols of one module hash, or of every module
+#[tauri::command]
+pub async fn clear_module_symbols(module_hash: Option<String>) -> Result<usize, String> {
+    db::run(move |conn| {
+        let removed = match &module_hash {
+            Some(hash) => {
+                conn.execute("DELETE FROM module_symbols WHERE module_hash = ?1", params![hash]).map_err(|e| e.to_string())?;
+                conn.execute("DELETE FROM module_symbol_sets WHERE module_hash = ?1", params![hash]).map_err(|e| e.to_string())?
+            }
+            None => {
+                conn.execute("DELETE FROM module_symbols", []).map_err(|e| e.to_string())?;
+                conn.execute("DELETE FROM module_symbol_sets", []).map_err(|e| e.to_string())?
+            }
+        };
+        Ok(removed)
+    }).await
+}
@@ -0,0 +1,548 @@
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::breakpoints::{self, BreakpointDefinition};
+use crate::frida;
+use crate::state::AppStateType;
+use crate::db;
+
+// Listing every class of a large game (Assembly-CSharp, UnityEngine.*) takes a while
+const LIST_TIMEOUT: Duration = Duration::from_secs(120);
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_SEARCH_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MonoTarget {
+    pub pid: Option<u32>,                // Default: the attached process
+    pub device: Option<String>,          // Frida device (default: frida-server on the connected host)
+    pub assembly: Option<String>,        // Only look classes up in this assembly
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonoAssembly {
+    pub name: String,
+    pub guid: String,                    // Module version id of the image
+    pub class_count: usize,
+    pub cached: bool,                    // Classes came from the cache instead of the agent
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonoClassInfo {
+    pub assembly: String,
+    pub name: String,                    // "Namespace.Outer/Nested"
+    pub parent: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonoMethodInfo {
+    pub name: String,
+    pub signature: String,               // "(int,string)"
+    pub flags: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonoFieldInfo {
+    pub name: String,
+    pub type_name: String,
+    pub offset: u32,                     // From the object start (header included) or the static data
+    pub is_static: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonoClassDetail {
+    pub assembly: String,
+    pub guid: String,
+    pub name: String,
+    pub methods: Vec<MonoMethodInfo>,
+    pub fields: Vec<MonoFieldInfo>,
+}
+
+/// A method's native code or a static field's storage in the live process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonoMemberAddress {
+    pub class_name: String,
+    pub member: String,
+    pub signature: Option<String>,       // Methods only
+    pub type_name: Option<String>,       // Fields only
+    pub address: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScriptAssembly {
+    name: String,
+    guid: String,
+    classes: Option<Vec<ScriptClass>>,   // None when the guid was already cached
+}
+
+#[derive(Debug, Deserialize)]
+struct ScriptClass {
+    name: String,
+    parent: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScriptMember {
+    class_name: String,
+    member: String,
+    signature: Option<String>,
+    type_name: Option<String>,
+    address: String,
+}
+
+// (assembly name, image guid)
+type LoadedAssemblies = Vec<(String, String)>;
+
+// Assemblies of each process from its last listing
+static LOADED: Lazy<Mutex<HashMap<u32, LoadedAssemblies>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+const SCRIPT: &str = r#"
+function emit(kind, payload) {
+    console.log("__PREFIX__" + JSON.stringify(Object.assign({ kind: kind }, payload)));
+}
+const request = __REQUEST__;
+try {
+    const runtime = Process.enumerateModules().find(m => m.findExportByName("mono_get_root_domain") !== null);
+    if (!runtime) {
+        throw new Error("No Mono runtime in the process");
+    }
+    const fn = (name, ret, args) => new NativeFunction(runtime.getExportByName(name), ret, args);
+    const mono = {
+        get_root_domain: fn("mono_get_root_domain", "pointer", []),
+        thread_attach: fn("mono_thread_attach", "pointer", ["pointer"]),
+        assembly_foreach: fn("mono_assembly_foreach", "void", ["pointer", "pointer"]),
+        assembly_get_image: fn("mono_assembly_get_image", "pointer", ["pointer"]),
+        image_get_name: fn("mono_image_get_name", "pointer", ["pointer"]),
+        image_get_guid: fn("mono_image_get_guid", "pointer", ["pointer"]),
+        image_get_table_rows: fn("mono_image_get_table_rows", "int", ["pointer", "int"]),
+        class_get: fn("mono_class_get", "pointer", ["pointer", "uint32"]),
+        class_get_name: fn("mono_class_get_name", "pointer", ["pointer"]),
+        class_get_namespace: fn("mono_class_get_namespace", "pointer", ["pointer"]),
+        class_get_nesting_type: fn("mono_class_get_nesting_type", "pointer", ["pointer"]),
+        class_get_parent: fn("mono_class_get_parent", "pointer", ["pointer"]),
+        class_get_methods: fn("mono_class_get_methods", "pointer", ["pointer", "pointer"]),
+        class_get_fields: fn("mono_class_get_fields", "pointer", ["pointer", "pointer"]),
+        class_vtable: fn("mono_class_vtable", "pointer", ["pointer", "pointer"]),
+        vtable_get_static_field_data: fn("mono_vtable_get_static_field_data", "pointer", ["pointer"]),
+        method_get_name: fn("mono_method_get_name", "pointer", ["pointer"]),
+        method_get_flags: fn("mono_method_get_flags", "uint32", ["pointer", "pointer"]),
+        method_signature: fn("mono_method_signature", "pointer", ["pointer"]),
+        signature_get_desc: fn("mono_signature_get_desc", "pointer", ["pointer", "int"]),
+        compile_method: fn("mono_compile_method", "pointer", ["pointer"]),
+        field_get_name: fn("mono_field_get_name", "pointer", ["pointer"]),
+        field_get_type: fn("mono_field_get_type", "pointer", ["pointer"]),
+        field_get_offset: fn("mono_field_get_offset", "uint32", ["pointer"]),
+        field_get_flags: fn("mono_field_get_flags", "uint32", ["pointer"]),
+        type_get_name: fn("mono_type_get_name", "pointer", ["pointer"]),
+    };
+    const str = p => p.isNull() ? "" : p.readUtf8String();
+    const domain = mono.get_root_domain();
+    mono.thread_attach(domain);
+
+    const images = [];
+    const collect = new NativeCallback(assembly => { images.push(mono.assembly_get_image(assembly)); }, "void", ["pointer", "pointer"]);
+    mono.assembly_foreach(collect, NULL);
+    const imageName = image => str(mono.image_get_name(image));
+    const bare = name => name.toLowerCase().replace(/\.(dll|exe)$/, "");
+    const selected = images.filter(image => !request.assembly || bare(imageName(image)) === bare(request.assembly));
+
+    function iterate(getter, klass) {
+        const iter = Memory.alloc(Process.pointerSize);
+        iter.writePointer(NULL);
+        const items = [];
+        for (let item = getter(klass, iter); !item.isNull(); item = getter(klass, iter)) {
+            items.push(item);
+        }
+        return items;
+    }
+    function className(klass) {
+        let name = str(mono.class_get_name(klass));
+        let outer = klass;
+        for (let parent = mono.class_get_nesting_type(klass); !parent.isNull(); parent = mono.class_get_nesting_type(parent)) {
+            name = str(mono.class_get_name(parent)) + "/" + name;
+            outer = parent;
+        }
+        const namespace = str(mono.class_get_namespace(outer));
+        return namespace ? namespace + "." + name : name;
+    }
+    function classes(image) {
+        const found = [];
+        // TypeDef table (2); row 1 is <Module>
+        const rows = mono.image_get_table_rows(image, 2);
+        for (let row = 2; row <= rows; row++) {
+            const klass = mono.class_get(image, 0x02000000 | row);
+            if (!klass.isNull()) {
+                found.push(klass);
+            }
+        }
+        return found;
+    }
+    function findClass(name) {
+        const exact = [];
+        const bySimpleName = [];
+        for (const image of selected) {
+            for (const klass of classes(image)) {
+                const full = className(klass);
+                if (full === name) {
+                    exact.push({ image, klass, full });
+                } else if (str(mono.class_get_name(klass)) === name) {
+                    bySimpleName.push({ image, klass, full });
+                }
+            }
+        }
+        const matches = exact.length > 0 ? exact : bySimpleName;
+        if (matches.length === 0) {
+            throw new Error("No class " + name);
+        }
+        if (matches.length > 1) {
+            throw new Error(name + " is ambiguous: " + matches.slice(0, 10).map(m => imageName(m.image) + ":" + m.full).join(", "));
+        }
+        return matches[0];
+    }
+    function describe(method) {
+        const signature = mono.method_signature(method);
+        return "(" + (signature.isNull() ? "" : str(mono.signature_get_desc(signature, 0))) + ")";
+    }
+    const typeName = field => str(mono.type_get_name(mono.field_get_type(field)));
+    const normalize = signature => signature.replace(/[\s()]/g, "");
+
+    let data;
+    if (request.op === "assemblies") {
+        data = images.map(image => {
+            const guid = str(mono.image_get_guid(image));
+            return {
+                name: imageName(image),
+                guid,
+                classes: request.known.includes(guid) ? null : classes(image).map(klass => {
+                    const parent = mono.class_get_parent(klass);
+                    return { name: className(klass), parent: parent.isNull() ? null : className(parent) };
+                }),
+            };
+        });
+    } else if (request.op === "class") {
+        const found = findClass(request.class_name);
+        data = {
+            assembly: imageName(found.image),
+            guid: str(mono.image_get_guid(found.image)),
+            name: found.full,
+            methods: iterate(mono.class_get_methods, found.klass).map(method => ({
+                name: str(mono.method_get_name(method)),
+                signature: describe(method),
+                flags: mono.method_get_flags(method, NULL),
+            })),
+            fields: iterate(mono.class_get_fields, found.klass).map(field => ({
+                name: str(mono.field_get_name(field)),
+                type_name: typeName(field),
+                offset: mono.field_get_offset(field),
+                is_static: (mono.field_get_flags(field) & 0x10) !== 0,
+            })),
+        };
+    } else if (request.op === "method") {
+        const found = findClass(request.class_name);
+        const methods = iterate(mono.class_get_methods, found.klass).filter(method =>
+            str(mono.method_get_name(method)) === request.member
+            && (request.signature === null || normalize(describe(method)) === normalize(request.signature)));
+        if (methods.length === 0) {
+            throw new Error("No method " + request.member + " in " + found.full);
+        }
+        if (methods.length > 1) {
+            throw new Error(request.member + " is overloaded; pass a signature: " + methods.map(describe).join(", "));
+        }
+        // JIT-compiles the method when it has not run yet
+        const code = mono.compile_method(methods[0]);
+        if (code.isNull()) {
+            throw new Error("mono_compile_method failed for " + found.full + ":" + request.member);
+        }
+        data = { class_name: found.full, member: request.member, signature: describe(methods[0]), type_name: null, address: code.toString() };
+    } else if (request.op === "field") {
+        const found = findClass(request.class_name);
+        const field = iterate(mono.class_get_fields, found.klass).find(f => str(mono.field_get_name(f)) === request.member);
+        if (!field) {
+            throw new Error("No field " + request.member + " in " + found.full);
+        }
+        const flags = mono.field_get_flags(field);
+        const offset = mono.field_get_offset(field);
+        if ((flags & 0x10) === 0) {
+            throw new Error(found.full + ":" + request.member + " is an instance field at offset 0x" + offset.toString(16));
+        }
+        if ((flags & 0x40) !== 0 || offset === 0xffffffff) {
+            throw new Error(found.full + ":" + request.member + " is a constant or thread static and has no fixed address");
+        }
+        const vtable = mono.class_vtable(domain, found.klass);
+        const statics = vtable.isNull() ? NULL : mono.vtable_get_static_field_data(vtable);
+        if (statics.isNull()) {
+            throw new Error("Static data of " + found.full + " is not allocated yet");
+        }
+        data = { class_name: found.full, member: request.member, signature: null, type_name: typeName(field), address: statics.add(offset).toString() };
+    }
+    emit("result", { data: data });
+} catch (e) {
+    emit("error", { error: e.message || String(e) });
+}
+"#;
+
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS mono_classes (
+            image_guid TEXT NOT NULL,
+            assembly TEXT NOT NULL,
+            full_name TEXT NOT NULL,
+            parent TEXT,
+            PRIMARY KEY(image_guid, full_name)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS mono_class_details (
+            image_guid TEXT NOT NULL,
+            full_name TEXT NOT NULL,
+            detail_json TEXT NOT NULL,
+            PRIMARY KEY(image_guid, full_name)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn target_pid(state: &AppStateType, target: &MonoTarget) -> Result<u32, String> {
+    if let Some(pid) = target.pid {
+        return Ok(pid);
+    }
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    state_guard.attached_process.as_ref().map(|p| p.pid).ok_or_else(|| "No process attached".to_string())
+}
+
+/// Run the agent script for `request` in the target process
+async fn run(state: &AppStateType, target: &MonoTarget, mut request: serde_json::Value, timeout: Duration) -> Result<serde_json::Value, String> {
+    let pid = target_pid(state, target)?;
+    request["assembly"] = serde_json::json!(target.assembly);
+    let script = SCRIPT.replace("__PREFIX__", frida::LINE_PREFIX).replace("__REQUEST__", &request.to_string());
+    frida::run_script_once(pid, target.device.as_deref(), &script, timeout).await
+}
+
+/// "Class:member" or "Class::member"; nested classes use '/'
+fn split_member(name: &str) -> Result<(String, String), String> {
+    let (class_name, member) = name.trim().rsplit_once(':').ok_or_else(|| format!("Expected Class:member, got {}", name))?;
+    let class_name = class_name.trim_end_matches(':');
+    if class_name.is_empty() || member.is_empty() {
+        return Err(format!("Expected Class:member, got {}", name));
+    }
+    Ok((class_name.to_string(), member.to_string()))
+}
+
+fn cached_guids(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn.prepare("SELECT DISTINCT image_guid FROM mono_classes").map_err(|e| e.to_string())?;
+    let guids = stmt.query_map([], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(guids)
+}
+
+fn store_classes(conn: &Connection, assembly: &ScriptAssembly, classes: &[ScriptClass]) -> Result<(), String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM mono_classes WHERE image_guid = ?1", params![assembly.guid]).map_err(|e| e.to_string())?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT OR REPLACE INTO mono_classes (image_guid, assembly, full_name, parent) VALUES (?1, ?2, ?3, ?4)",
+        ).map_err(|e| e.to_string())?;
+        for class in classes {
+            insert.execute(params![assembly.guid, assembly.name, class.name, class.parent]).map_err(|e| e.to_string())?;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+fn cached_class_count(conn: &Connection, guid: &str) -> Result<usize, String> {
+    conn.query_row("SELECT COUNT(*) FROM mono_classes WHERE image_guid = ?1", params![guid], |row| row.get::<_, i64>(0))
+        .map(|count| count as usize)
+        .map_err(|e| e.to_string())
+}
+
+/// Assemblies loaded in the target. Class lists are cached by image guid,
+/// so only assemblies not seen before (or all, with `refresh`) are
+/// enumerated by the agent.
+#[tauri::command]
+pub async fn load_mono_assemblies(
+    state: tauri::State<'_, AppStateType>,
+    target: Option<MonoTarget>,
+    refresh: Option<bool>,
+) -> Result<Vec<MonoAssembly>, String> {
+    let target = MonoTarget { assembly: None, ..target.unwrap_or_default() };
+    let known = if refresh.unwrap_or(false) { Vec::new() } else { db::run(cached_guids).await? };
+    let data = run(&state, &target, serde_json::json!({ "op": "assemblies", "known": known }), LIST_TIMEOUT).await?;
+    let assemblies: Vec<ScriptAssembly> = serde_json::from_value(data).map_err(|e| format!("Invalid agent response: {}", e))?;
+
+    let (result, assemblies) = db::run(move |conn| {
+        let mut result = Vec::with_capacity(assemblies.len());
+        for assembly in &assemblies {
+            let (class_count, cached) = match &assembly.classes {
+                Some(classes) => {
+                    store_classes(conn, assembly, classes)?;
+                    (classes.len(), false)
+                }
+                None => (cached_class_count(conn, &assembly.guid)?, true),
+            };
+            result.push(MonoAssembly { name: assembly.name.clone(), guid: assembly.guid.clone(), class_count, cached });
+        }
+        Ok((result, assemblies))
+    }).await?;
+    let pid = target_pid(&state, &target)?;
+    LOADED.lock().map_err(|e| e.to_string())?
+        .insert(pid, assemblies.into_iter().map(|a| (a.name, a.guid)).collect());
+    Ok(result)
+}
+
+/// Classes whose full name contains `query`, from the cached lists of the
+/// assemblies loaded in the target (listed first if needed)
+#[tauri::command]
+pub async fn search_mono_classes(
+    state: tauri::State<'_, AppStateType>,
+    query: String,
+    limit: Option<usize>,
+    target: Option<MonoTarget>,
+) -> Result<Vec<MonoClassInfo>, String> {
+    let target = target.unwrap_or_default();
+    let pid = target_pid(&state, &target)?;
+    let listed = LOADED.lock().map_err(|e| e.to_string())?.contains_key(&pid);
+    if !listed {
+        load_mono_assemblies(state.clone(), Some(target.clone()), None).await?;
+    }
+    let guids: Vec<String> = LOADED.lock().map_err(|e| e.to_string())?
+        .get(&pid)
+        .map(|assemblies| assemblies.iter()
+            .filter(|(name, _)| target.assembly.as_ref().is_none_or(|a| a.eq_ignore_ascii_case(name)))
+            .map(|(_, guid)| guid.clone())
+            .collect())
+        .unwrap_or_default();
+
+    let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    let pattern = format!("%{}%", query);
+    let mut classes = db::run(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT assembly, full_name, parent FROM mono_classes
+             WHERE image_guid = ?1 AND full_name LIKE ?2 ORDER BY full_name LIMIT ?3",
+        ).map_err(|e| e.to_string())?;
+        let mut classes = Vec::new();
+        for guid in guids {
+            let rows = stmt.query_map(params![guid, pattern, limit as i64], |row| {
+                Ok(MonoClassInfo { assembly: row.get(0)?, name: row.get(1)?, parent: row.get(2)? })
+            }).map_err(|e| e.to_string())?;
+            for row in rows {
+                classes.push(row.map_err(|e| e.to_string())?);
+            }
+        }
+        Ok(classes)
+    }).await?;
+    classes.sort_by(|a, b| a.name.cmp(&b.name));
+    classes.truncate(limit);
+    Ok(classes)
+}
+
+/// Cached detail of `class_name` when exactly one loaded assembly has it
+async fn cached_detail(pid: u32, class_name: &str, assembly: Option<&str>) -> Result<Option<MonoClassDetail>, String> {
+    let loaded = LOADED.lock().map_err(|e| e.to_string())?.get(&pid).cloned().unwrap_or_default();
+    let guids: Vec<String> = loaded.into_iter()
+        .filter(|(name, _)| assembly.is_none_or(|a| a.eq_ignore_ascii_case(name)))
+        .map(|(_, guid)| guid)
+        .collect();
+    let class_name = class_name.to_string();
+    let found = db::run(move |conn| {
+        let mut found = Vec::new();
+        for guid in &guids {
+            let detail: Option<String> = conn.query_row(
+                "SELECT detail_json FROM mono_class_details WHERE image_guid = ?1 AND full_name = ?2",
+                params![guid, class_name],
+                |row| row.get(0),
+            ).optional().map_err(|e| e.to_string())?;
+            found.extend(detail);
+        }
+        Ok(found)
+    }).await?;
+    match found.as_slice() {
+        [json] => Ok(serde_json::from_str(json).ok()),
+        _ => Ok(None),
+    }
+}
+
+/// Methods and fields of a class ("Namespace.Name", or a unique simple
+/// name), cached by image guid
+#[tauri::command]
+pub async fn get_mono_class(
+    state: tauri::State<'_, AppStateType>,
+    class_name: String,
+    target: Option<MonoTarget>,
+) -> Result<MonoClassDetail, String> {
+    let target = target.unwrap_or_default();
+    let pid = target_pid(&state, &target)?;
+    if let Some(detail) = cached_detail(pid, &class_name, target.assembly.as_deref()).await? {
+        return Ok(detail);
+    }
+    let data = run(&state, &target, serde_json::json!({ "op": "class", "class_name": class_name }), LOOKUP_TIMEOUT).await?;
+    let detail: MonoClassDetail = serde_json::from_value(data).map_err(|e| format!("Invalid agent response: {}", e))?;
+
+    let json = serde_json::to_string(&detail).map_err(|e| e.to_string())?;
+    let (guid, name) = (detail.guid.clone(), detail.name.clone());
+    db::run(move |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO mono_class_details (image_guid, full_name, detail_json) VALUES (?1, ?2, ?3)",
+            params![guid, name, json],
+        ).map_err(|e| e.to_string())
+    }).await?;
+    Ok(detail)
+}
+
+async fn resolve_member(state: &AppStateType, op: &str, name: &str, signature: Option<String>, target: &MonoTarget) -> Result<MonoMemberAddress, String> {
+    let (class_name, member) = split_member(name)?;
+    let request = serde_json::json!({ "op": op, "class_name": class_name, "member": member, "signature": signature });
+    let data = run(state, target, request, LOOKUP_TIMEOUT).await?;
+    let resolved: ScriptMember = serde_json::from_value(data).map_err(|e| format!("Invalid agent response: {}", e))?;
+    let address = u64::from_str_radix(resolved.address.trim_start_matches("0x"), 16)
+        .map_err(|_| format!("Invalid address from agent: {}", resolved.address))?;
+    Ok(MonoMemberAddress {
+        class_name: resolved.class_name,
+        member: resolved.member,
+        signature: resolved.signature,
+        type_name: resolved.type_name,
+        address,
+    })
+}
+
+/// Native code of `Class:method`, JIT-compiling it if it has not run yet.
+/// Overloads need `signature` ("(int,string)").
+#[tauri::command]
+pub async fn resolve_mono_method(
+    state: tauri::State<'_, AppStateType>,
+    name: String,
+    signature: Option<String>,
+    target: Option<MonoTarget>,
+) -> Result<MonoMemberAddress, String> {
+    resolve_member(&state, "method", &name, signature, &target.unwrap_or_default()).await
+}
+
+/// Address of the static field `Class:field`, for the watchlist
+#[tauri::command]
+pub async fn resolve_mono_static_field(
+    state: tauri::State<'_, AppStateType>,
+    name: String,
+    target: Option<MonoTarget>,
+) -> Result<MonoMemberAddress, String> {
+    resolve_member(&state, "field", &name, None, &target.unwrap_or_default()).await
+}
+
+/// Breakpoint on the native code of `Class:method`
+#[tauri::command]
+pub async fn set_mono_breakpoint(
+    state: tauri::State<'_, AppStateType>,
+    name: String,
+    signature: Option<String>,
+    hit_count: Option<i32>,
+    condition: Option<String>,
+    target: Option<MonoTarget>,
+) -> Result<BreakpointDefinition, String> {
+    let resolved = resolve_member(&state, "method", &name, signature, &target.unwrap_or_default()).await?;
+    breakpoints::set_breakpoint(state, resolved.address, hit_count, condition, None, None, None).await
+}
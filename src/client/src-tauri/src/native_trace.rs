@@ -0,0 +1,622 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+
+use crate::disassembly::{disassemble_structured, StructuredInstruction};
+use crate::state::{AppState, AppStateType, ExceptionData, TraceEntryData};
+use crate::symbolizer::Symbolizer;
+use crate::{
+    clock_sync, continue_execution_on_server, event_bus, fetch_exceptions_from_server,
+    read_memory_from_server, registers, server_address, single_step_on_server,
+};
+
+const DEFAULT_SPAN_ENTRIES: usize = 4096;
+const MAX_TRACE_ENTRIES: u32 = 10_000_000;
+const DEFAULT_STEP_TIMEOUT_MS: u64 = 5000;
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+const SUMMARY_INTERVAL: Duration = Duration::from_millis(250);
+// SingleStepMode::UserStep, what /api/debug/step produces
+const USER_STEP_MODE: &str = "3";
+const FILE_MAGIC: &[u8; 8] = b"DYNTRC01";
+const DEFAULT_CHECKPOINT_INTERVAL: u32 = 1000;
+const DEFAULT_CHECKPOINT_STACK_BYTES: usize = 4096;
+const MAX_CHECKPOINT_STACK_BYTES: usize = 1024 * 1024;
+const PAGE_SIZE: u64 = 0x1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NativeTraceOptions {
+    pub stop_address: Option<u64>,   // End before executing this address
+    pub span_entries: Option<usize>, // Records per compressed span
+    pub output_path: Option<String>, // Also append the spans to this file
+    #[serde(default)]
+    pub resume_on_finish: bool,      // Continue the thread when the trace ends
+    pub step_timeout_ms: Option<u64>,
+    pub checkpoint_interval: Option<u32>,     // Instructions between checkpoints (0 = none)
+    pub checkpoint_stack_bytes: Option<usize>, // Stack captured upward from SP at each checkpoint
+}
+
+/// One executed instruction. `registers` holds what changed since the previous
+/// record; the first record of every span carries the full set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NativeTraceRecord {
+    pub index: u32,
+    pub address: u64,
+    pub bytes: Vec<u8>,
+    pub depth: u32,
+    pub timestamp: u64,              // Target time (ms) of the stop before the instruction ran
+    pub registers: Vec<(String, u64)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NativeTraceStatus {
+    pub thread_id: u64,
+    pub active: bool,
+    pub entries: u32,
+    pub max_entries: u32,
+    pub spans: usize,
+    pub compressed_bytes: usize,
+    pub uncompressed_bytes: usize,
+    pub last_address: Option<u64>,
+    pub depth: u32,
+    pub started_at: u64,
+    pub elapsed_ms: u64,
+    pub steps_per_second: f64,
+    pub stop_reason: Option<String>, // "max_entries", "stop_address", "stopped" or the error that ended it
+    pub output_path: Option<String>,
+    pub checkpoints: usize,
+}
+
+/// Approximate machine state before a traced instruction ran: the exact
+/// registers, and the stack as of the nearest earlier checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceMachineState {
+    pub index: u32,
+    pub entry: TraceEntryData,       // The instruction, with the full register set
+    pub checkpoint_index: Option<u32>, // None when no checkpoint precedes `index`
+    pub stack_address: Option<u64>,  // Where `stack` starts (SP at the checkpoint)
+    pub stack: Vec<u8>,
+    pub stack_age: u32,              // Instructions run since the checkpoint; their stores are not replayed
+}
+
+struct Span {
+    first_index: u32,
+    count: u32,
+    data: Vec<u8>,                   // lz4 (size prepended) JSON array of records
+}
+
+struct Checkpoint {
+    index: u32,
+    stack_address: u64,
+    stack: Vec<u8>,                  // lz4 (size prepended)
+}
+
+struct NativeTrace {
+    status: NativeTraceStatus,
+    arch: String,
+    spans: Vec<Span>,
+    checkpoints: Vec<Checkpoint>,
+    cancel: Arc<AtomicBool>,
+    steps: Option<mpsc::UnboundedSender<serde_json::Value>>, // Step events picked up by the UI poller
+}
+
+static TRACES: Lazy<Mutex<HashMap<u64, NativeTrace>>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+/// Hand single-step events of natively traced threads to their tracer
+/// (called from add_exceptions, since the UI poller drains the same queue)
+pub fn divert_exceptions(exceptions: Vec<ExceptionData>) -> Vec<ExceptionData> {
+    let Ok(traces) = TRACES.lock() else {
+        return exceptions;
+    };
+    if traces.values().all(|t| t.steps.is_none()) {
+        return exceptions;
+    }
+    exceptions.into_iter()
+        .filter(|exception| {
+            let sender = exception.thread_id
+                .filter(|_| exception.exception_type == "singlestep")
+                .and_then(|thread_id| traces.get(&thread_id))
+                .and_then(|trace| trace.steps.as_ref());
+            match sender {
+                Some(sender) => sender.send(exception.registers.clone()).is_err(),
+                None => true,
+            }
+        })
+        .collect()
+}
+
+fn parse_hex(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches("0x").trim_start_matches("0X"), 16).ok()
+}
+
+fn register_map(values: &serde_json::Value) -> HashMap<String, u64> {
+    values.as_object()
+        .map(|object| object.iter()
+            .filter_map(|(name, value)| value.as_str().and_then(parse_hex).map(|v| (name.clone(), v)))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// PC, registers and target timestamp of a raw single_step event
+fn parse_step(arch: &str, event: &serde_json::Value) -> Option<(u64, HashMap<String, u64>, u64)> {
+    let info = &event["exception_info"];
+    let raw = if info["registers"].is_object() { &info["registers"] } else { event };
+    let registers = register_map(&registers::normalize_json(arch, raw));
+    let pc = event["address"].as_str().and_then(parse_hex)
+        .or_else(|| registers.get("pc").or_else(|| registers.get("rip")).copied())?;
+    let timestamp = info["timestamp"].as_str()
+        .and_then(clock_sync::parse_iso_timestamp)
+        .unwrap_or_else(AppState::current_timestamp);
+    Some((pc, registers, timestamp))
+}
+
+/// Wait for the stop that follows a step of `thread_id`, from either the UI
+/// poller (diverted) or the server queue
+async fn wait_for_step(
+    host: &str,
+    port: u16,
+    thread_id: u64,
+    steps: &mut mpsc::UnboundedReceiver<serde_json::Value>,
+    timeout: Duration,
+) -> Result<serde_json::Value, String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Ok(event) = steps.try_recv() {
+            return Ok(event);
+        }
+        let mut found = None;
+        for event in fetch_exceptions_from_server(host, port, "single_step", USER_STEP_MODE).await? {
+            if event["thread_id"].as_u64() == Some(thread_id) {
+                found = Some(event);
+            } else {
+                eprintln!("Native trace of thread {}: ignoring step of thread {}", thread_id, event["thread_id"]);
+            }
+        }
+        if let Some(event) = found {
+            return Ok(event);
+        }
+        if Instant::now() >= deadline {
+            return Err(format!("Timed out waiting for thread {} to step", thread_id));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+fn publish(app: &AppHandle, event: &str, status: &NativeTraceStatus) {
+    if event_bus::publish(event_bus::CHANNEL_NATIVE_TRACE, Some(&status.thread_id.to_string()), status) {
+        return;
+    }
+    for window in app.webview_windows().values() {
+        if let Err(e) = window.emit(event, status) {
+            eprintln!("Failed to emit {} event to window: {}", event, e);
+        }
+    }
+}
+
+struct Recorder {
+    thread_id: u64,
+    span_entries: usize,
+    pending: Vec<NativeTraceRecord>,
+    previous: HashMap<String, u64>,
+    file: Option<tokio::fs::File>,
+    checkpoint_interval: u32,
+    checkpoint_stack_bytes: usize,
+}
+
+fn stack_pointer(registers: &HashMap<String, u64>) -> Option<u64> {
+    ["sp", "rsp", "esp"].iter().find_map(|name| registers.get(*name).copied())
+}
+
+impl Recorder {
+    fn record(&mut self, index: u32, address: u64, bytes: Vec<u8>, depth: u32, timestamp: u64, registers: &HashMap<String, u64>) {
+        let mut changed: Vec<(String, u64)> = registers.iter()
+            .filter(|(name, value)| self.previous.get(*name) != Some(value))
+            .map(|(name, value)| (name.clone(), *value))
+            .collect();
+        changed.sort();
+        self.previous.clone_from(registers);
+        self.pending.push(NativeTraceRecord { index, address, bytes, depth, timestamp, registers: changed });
+    }
+
+    /// Capture the stack above SP every checkpoint_interval records. A read
+    /// that runs off the mapping is retried up to the end of SP's page.
+    async fn checkpoint(&self, host: &str, port: u16, index: u32, registers: &HashMap<String, u64>) -> Result<(), String> {
+        if self.checkpoint_interval == 0 || !index.is_multiple_of(self.checkpoint_interval) {
+            return Ok(());
+        }
+        let Some(sp) = stack_pointer(registers) else {
+            return Ok(());
+        };
+        let stack = match read_memory_from_server(host, port, sp, self.checkpoint_stack_bytes).await {
+            Ok(stack) => stack,
+            Err(_) => {
+                let to_page_end = (PAGE_SIZE - sp % PAGE_SIZE) as usize;
+                read_memory_from_server(host, port, sp, to_page_end.min(self.checkpoint_stack_bytes)).await.unwrap_or_default()
+            }
+        };
+        let mut traces = TRACES.lock().map_err(|e| e.to_string())?;
+        if let Some(trace) = traces.get_mut(&self.thread_id) {
+            trace.status.checkpoints += 1;
+            trace.checkpoints.push(Checkpoint { index, stack_address: sp, stack: lz4_flex::compress_prepend_size(&stack) });
+        }
+        Ok(())
+    }
+
+    /// Compress the pending records into a span; the next record starts a keyframe
+    async fn flush(&mut self) -> Result<(), String> {
+        let Some(first) = self.pending.first() else {
+            return Ok(());
+        };
+        let first_index = first.index;
+        let count = self.pending.len() as u32;
+        let json = serde_json::to_vec(&self.pending).map_err(|e| e.to_string())?;
+        let data = lz4_flex::compress_prepend_size(&json);
+        self.pending.clear();
+        self.previous.clear();
+
+        if let Some(file) = self.file.as_mut() {
+            let mut chunk = Vec::with_capacity(data.len() + 12);
+            chunk.extend_from_slice(&first_index.to_le_bytes());
+            chunk.extend_from_slice(&count.to_le_bytes());
+            chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            chunk.extend_from_slice(&data);
+            file.write_all(&chunk).await.map_err(|e| format!("Failed to write trace file: {}", e))?;
+        }
+
+        let mut traces = TRACES.lock().map_err(|e| e.to_string())?;
+        if let Some(trace) = traces.get_mut(&self.thread_id) {
+            trace.status.spans += 1;
+            trace.status.compressed_bytes += data.len();
+            trace.status.uncompressed_bytes += json.len();
+            trace.spans.push(Span { first_index, count, data });
+        }
+        Ok(())
+    }
+}
+
+fn update_status(thread_id: u64, apply: impl FnOnce(&mut NativeTraceStatus)) -> Option<NativeTraceStatus> {
+    let mut traces = TRACES.lock().ok()?;
+    let trace = traces.get_mut(&thread_id)?;
+    apply(&mut trace.status);
+    Some(trace.status.clone())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run(
+    app: AppHandle,
+    thread_id: u64,
+    max_entries: u32,
+    options: NativeTraceOptions,
+    arch: String,
+    initial: (u64, HashMap<String, u64>),
+    mut steps: mpsc::UnboundedReceiver<serde_json::Value>,
+    cancel: Arc<AtomicBool>,
+    recorder: &mut Recorder,
+) -> Result<String, String> {
+    let (host, port) = server_address()?;
+    let timeout = Duration::from_millis(options.step_timeout_ms.unwrap_or(DEFAULT_STEP_TIMEOUT_MS));
+    let started = Instant::now();
+    let mut last_summary = Instant::now();
+    let mut decoded: HashMap<u64, (Vec<u8>, Option<StructuredInstruction>)> = HashMap::new();
+    let (mut pc, mut registers) = initial;
+    let mut timestamp = AppState::current_timestamp();
+    let mut depth = 0u32;
+
+    for index in 0..max_entries {
+        if cancel.load(Ordering::Relaxed) {
+            return Ok("stopped".to_string());
+        }
+        if options.stop_address == Some(pc) {
+            return Ok("stop_address".to_string());
+        }
+
+        if let Entry::Vacant(slot) = decoded.entry(pc) {
+            let bytes = read_memory_from_server(&host, port, pc, 16).await.unwrap_or_default();
+            let insn = disassemble_structured(&bytes, pc, &arch).ok().and_then(|insns| insns.into_iter().next());
+            let bytes = insn.as_ref().map(|i| i.bytes.clone()).unwrap_or(bytes);
+            slot.insert((bytes, insn));
+        }
+        let (bytes, insn) = &decoded[&pc];
+        if insn.as_ref().is_some_and(|i| i.is_return) {
+            depth = depth.saturating_sub(1);
+        }
+        recorder.checkpoint(&host, port, index, &registers).await?;
+        recorder.record(index, pc, bytes.clone(), depth, timestamp, &registers);
+        if insn.as_ref().is_some_and(|i| i.is_call) {
+            depth += 1;
+        }
+        if recorder.pending.len() >= recorder.span_entries {
+            recorder.flush().await?;
+        }
+
+        let elapsed = started.elapsed();
+        let status = update_status(thread_id, |status| {
+            status.entries = index + 1;
+            status.last_address = Some(pc);
+            status.depth = depth;
+            status.elapsed_ms = elapsed.as_millis() as u64;
+            status.steps_per_second = (index + 1) as f64 / elapsed.as_secs_f64().max(1e-3);
+        });
+        if last_summary.elapsed() >= SUMMARY_INTERVAL {
+            if let Some(status) = status {
+                publish(&app, "native-trace-progress", &status);
+            }
+            last_summary = Instant::now();
+        }
+
+        if index + 1 == max_entries {
+            break;
+        }
+        single_step_on_server(&host, port, thread_id).await?;
+        let event = wait_for_step(&host, port, thread_id, &mut steps, timeout).await?;
+        (pc, registers, timestamp) = parse_step(&arch, &event)
+            .ok_or_else(|| format!("Malformed step event for thread {}", thread_id))?;
+    }
+    Ok("max_entries".to_string())
+}
+
+/// Single-step `thread_id` up to `max_entries` times from its current stop,
+/// recording address, opcode bytes and register deltas in lz4-compressed
+/// spans, plus a stack checkpoint every checkpoint_interval steps. Progress is published as "native-trace-progress" summaries and the
+/// end as "native-trace-complete"; the trace stays readable with
+/// read_native_trace until it is cleared or restarted.
+#[tauri::command]
+pub async fn start_native_trace(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+    thread_id: u64,
+    max_entries: u32,
+    options: Option<NativeTraceOptions>,
+) -> Result<NativeTraceStatus, String> {
+    let options = options.unwrap_or_default();
+    let max_entries = max_entries.clamp(1, MAX_TRACE_ENTRIES);
+    if TRACES.lock().map_err(|e| e.to_string())?.get(&thread_id).is_some_and(|t| t.status.active) {
+        return Err(format!("Thread {} is already being traced", thread_id));
+    }
+
+    // The thread must be stopped; its current registers seed the first keyframe
+    let dump = registers::read_dump(state.inner(), thread_id).await?;
+    let initial_registers = register_map(&dump.values);
+    let pc = initial_registers.get("pc").or_else(|| initial_registers.get("rip")).copied()
+        .ok_or("Could not read the program counter")?;
+
+    let target_os = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default()
+    };
+    let file = match &options.output_path {
+        Some(path) => {
+            let mut file = tokio::fs::File::create(path)
+                .await
+                .map_err(|e| format!("Failed to create {}: {}", path, e))?;
+            let header = serde_json::to_vec(&serde_json::json!({
+                "version": 1,
+                "thread_id": thread_id,
+                "arch": dump.arch,
+                "target_os": target_os,
+                "started_at": AppState::current_timestamp(),
+            })).map_err(|e| e.to_string())?;
+            let mut prefix = FILE_MAGIC.to_vec();
+            prefix.extend_from_slice(&(header.len() as u32).to_le_bytes());
+            prefix.extend_from_slice(&header);
+            file.write_all(&prefix).await.map_err(|e| format!("Failed to write {}: {}", path, e))?;
+            Some(file)
+        }
+        None => None,
+    };
+
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let status = NativeTraceStatus {
+        thread_id,
+        active: true,
+        entries: 0,
+        max_entries,
+        spans: 0,
+        compressed_bytes: 0,
+        uncompressed_bytes: 0,
+        last_address: Some(pc),
+        depth: 0,
+        started_at: AppState::current_timestamp(),
+        elapsed_ms: 0,
+        steps_per_second: 0.0,
+        stop_reason: None,
+        output_path: options.output_path.clone(),
+        checkpoints: 0,
+    };
+    TRACES.lock().map_err(|e| e.to_string())?.insert(thread_id, NativeTrace {
+        status: status.clone(),
+        arch: dump.arch.clone(),
+        spans: Vec::new(),
+        checkpoints: Vec::new(),
+        cancel: cancel.clone(),
+        steps: Some(sender),
+    });
+
+    let recorder = Recorder {
+        thread_id,
+        span_entries: options.span_entries.unwrap_or(DEFAULT_SPAN_ENTRIES).max(1),
+        pending: Vec::new(),
+        previous: HashMap::new(),
+        file,
+        checkpoint_interval: options.checkpoint_interval.unwrap_or(DEFAULT_CHECKPOINT_INTERVAL),
+        checkpoint_stack_bytes: options.checkpoint_stack_bytes
+            .unwrap_or(DEFAULT_CHECKPOINT_STACK_BYTES)
+            .clamp(1, MAX_CHECKPOINT_STACK_BYTES),
+    };
+    let resume = options.resume_on_finish;
+    tokio::spawn(async move {
+        let mut recorder = recorder;
+        let outcome = run(
+            app.clone(), thread_id, max_entries, options, dump.arch, (pc, initial_registers), receiver, cancel, &mut recorder,
+        ).await;
+        let mut reason = match outcome {
+            Ok(reason) => reason,
+            Err(e) => e,
+        };
+        if let Err(e) = recorder.flush().await {
+            reason = format!("{} ({})", reason, e);
+        }
+        if let Some(file) = recorder.file.as_mut() {
+            let _ = file.flush().await;
+        }
+        if resume {
+            if let Ok((host, port)) = server_address() {
+                if let Err(e) = continue_execution_on_server(&host, port, Some(thread_id)).await {
+                    eprintln!("Failed to resume thread {} after native trace: {}", thread_id, e);
+                }
+            }
+        }
+        if let Ok(mut traces) = TRACES.lock() {
+            if let Some(trace) = traces.get_mut(&thread_id) {
+                trace.steps = None;
+            }
+        }
+        if let Some(status) = update_status(thread_id, |status| {
+            status.active = false;
+            status.stop_reason = Some(reason);
+        }) {
+            publish(&app, "native-trace-complete", &status);
+        }
+    });
+    Ok(status)
+}
+
+/// Ask a running native trace to end after the current step
+#[tauri::command]
+pub fn stop_native_trace(thread_id: u64) -> Result<bool, String> {
+    let traces = TRACES.lock().map_err(|e| e.to_string())?;
+    let trace = traces.get(&thread_id).ok_or_else(|| format!("No native trace for thread {}", thread_id))?;
+    trace.cancel.store(true, Ordering::Relaxed);
+    Ok(trace.status.active)
+}
+
+/// Status of the native traces (running and finished), or of one thread
+#[tauri::command]
+pub fn get_native_trace_status(thread_id: Option<u64>) -> Result<Vec<NativeTraceStatus>, String> {
+    let traces = TRACES.lock().map_err(|e| e.to_string())?;
+    let mut statuses: Vec<NativeTraceStatus> = traces.values()
+        .filter(|t| thread_id.is_none_or(|id| t.status.thread_id == id))
+        .map(|t| t.status.clone())
+        .collect();
+    statuses.sort_by_key(|s| s.started_at);
+    Ok(statuses)
+}
+
+/// Decompress `count` records from `offset` as regular trace entries, with
+/// the full register set of every instruction reconstructed from the deltas
+#[tauri::command]
+pub fn read_native_trace(
+    state: tauri::State<'_, AppStateType>,
+    thread_id: u64,
+    offset: Option<u32>,
+    count: Option<u32>,
+) -> Result<Vec<TraceEntryData>, String> {
+    read_entries(state.inner(), thread_id, offset.unwrap_or(0), count.unwrap_or(1000))
+}
+
+pub fn read_entries(state: &AppStateType, thread_id: u64, offset: u32, count: u32) -> Result<Vec<TraceEntryData>, String> {
+    let end = offset.saturating_add(count);
+    let (arch, records) = {
+        let traces = TRACES.lock().map_err(|e| e.to_string())?;
+        let trace = traces.get(&thread_id).ok_or_else(|| format!("No native trace for thread {}", thread_id))?;
+        let mut records: Vec<(HashMap<String, u64>, NativeTraceRecord)> = Vec::new();
+        for span in trace.spans.iter().filter(|s| s.first_index < end && s.first_index + s.count > offset) {
+            let json = lz4_flex::decompress_size_prepended(&span.data).map_err(|e| e.to_string())?;
+            let span_records: Vec<NativeTraceRecord> = serde_json::from_slice(&json).map_err(|e| e.to_string())?;
+            let mut registers = HashMap::new();
+            for record in span_records {
+                registers.extend(record.registers.iter().cloned());
+                if record.index >= offset && record.index < end {
+                    records.push((registers.clone(), record));
+                }
+            }
+        }
+        (trace.arch.clone(), records)
+    };
+
+    let symbolizer = Symbolizer::from_state(state)?;
+    let mut entries = Vec::with_capacity(records.len());
+    for (registers, record) in records {
+        let insn = disassemble_structured(&record.bytes, record.address, &arch).ok().and_then(|i| i.into_iter().next());
+        let (mnemonic, operands) = insn.as_ref()
+            .map(|i| (i.mnemonic.clone(), i.operands.clone()))
+            .unwrap_or_else(|| ("??".to_string(), String::new()));
+        let registers: serde_json::Map<String, serde_json::Value> = registers.into_iter()
+            .map(|(name, value)| (name, format!("0x{:x}", value).into()))
+            .collect();
+        let mut entry = TraceEntryData {
+            id: record.index,
+            address: format!("0x{:x}", record.address),
+            instruction: format!(
+                "0x{:x}|{}|{} {}",
+                record.address,
+                record.bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>(),
+                mnemonic,
+                operands
+            ).trim_end().to_string(),
+            opcode: mnemonic,
+            operands,
+            registers: serde_json::Value::Object(registers),
+            depth: record.depth,
+            is_call: insn.as_ref().is_some_and(|i| i.is_call),
+            is_return: insn.as_ref().is_some_and(|i| i.is_return),
+            function_name: None,
+            timestamp: record.timestamp,
+            library_expression: None,
+            target_address: format!("native:{}", thread_id),
+            local_timestamp: clock_sync::local_timestamp(record.timestamp),
+        };
+        symbolizer.annotate_trace_entry(&mut entry);
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Machine state before record `index` of a native trace ("native:<thread
+/// id>" or the bare thread id), rebuilt from the register deltas and the
+/// nearest earlier stack checkpoint
+#[tauri::command]
+pub fn seek_trace(state: tauri::State<'_, AppStateType>, session_id: String, index: u32) -> Result<TraceMachineState, String> {
+    let thread_id: u64 = session_id.strip_prefix("native:").unwrap_or(&session_id).trim().parse()
+        .map_err(|_| format!("Invalid native trace session: {}", session_id))?;
+    let entry = read_entries(state.inner(), thread_id, index, 1)?
+        .pop()
+        .ok_or_else(|| format!("Native trace of thread {} has no record {}", thread_id, index))?;
+    let traces = TRACES.lock().map_err(|e| e.to_string())?;
+    let trace = traces.get(&thread_id).ok_or_else(|| format!("No native trace for thread {}", thread_id))?;
+    let checkpoint = trace.checkpoints.iter().take_while(|c| c.index <= index).last();
+    let stack = match checkpoint {
+        Some(checkpoint) => lz4_flex::decompress_size_prepended(&checkpoint.stack).map_err(|e| e.to_string())?,
+        None => Vec::new(),
+    };
+    Ok(TraceMachineState {
+        index,
+        entry,
+        checkpoint_index: checkpoint.map(|c| c.index),
+        stack_address: checkpoint.map(|c| c.stack_address),
+        stack,
+        stack_age: checkpoint.map_or(0, |c| index - c.index),
+    })
+}
+
+/// Drop a finished native trace (stops it first when still running)
+#[tauri::command]
+pub fn clear_native_trace(thread_id: u64) -> Result<bool, String> {
+    let mut traces = TRACES.lock().map_err(|e| e.to_string())?;
+    Ok(match traces.remove(&thread_id) {
+        Some(trace) => {
+            trace.cancel.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    })
+}
@@ -0,0 +1,941 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::build_id::{u16_at, u32_at, u64_at};
+use crate::state::{AppStateType, ModuleInfo};
+use crate::symbolizer::Symbolizer;
+use crate::{read_chunks_parallel, read_memory_from_server, server_address};
+
+const CHUNK: u64 = 0x10000;
+const PARALLEL_READS: usize = 8;
+const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const HEADER_READ_SIZE: usize = 0x4000;
+// Sanity limits against garbage counts in damaged or half-initialized images
+const MAX_LIST_ENTRIES: u32 = 20_000;
+const MAX_ENTRIES: usize = 200_000;
+// User-space virtual addresses are below this; higher bits of live pointers are PAC or flags
+const POINTER_MASK: u64 = 0x0000_7FFF_FFFF_FFFF;
+
+const LC_SEGMENT_64: u32 = 0x19;
+const LC_DYLD_CHAINED_FIXUPS: u32 = 0x8000_0034;
+
+/// Objective-C method; `address` is the IMP in the target's address space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcMethod {
+    pub selector: String,
+    pub types: Option<String>,           // Type encoding, e.g. "v16@0:8"
+    pub address: Option<u64>,
+    pub is_class_method: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcIvar {
+    pub name: String,
+    pub type_encoding: Option<String>,
+    pub offset: Option<u32>,
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcClass {
+    pub name: String,
+    pub address: u64,                    // Class object
+    pub superclass: Option<String>,
+    pub is_swift: bool,
+    pub instance_size: u32,
+    pub methods: Vec<ObjcMethod>,        // Instance methods, then class methods
+    pub ivars: Vec<ObjcIvar>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcCategory {
+    pub name: String,
+    pub class_name: Option<String>,      // Extended class, also when it lives in another image
+    pub methods: Vec<ObjcMethod>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwiftField {
+    pub name: String,
+    pub type_name: String,               // Mangled, with symbolic references to local types resolved
+}
+
+/// Swift vtable entry; the descriptor carries no name, so `symbol` comes from the function cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwiftMethod {
+    pub kind: String,                    // "method" | "init" | "getter" | "setter" | "modify" | "read"
+    pub is_instance: bool,
+    pub is_async: bool,
+    pub address: Option<u64>,
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwiftType {
+    pub kind: String,                    // "class" | "struct" | "enum" | "protocol" | ...
+    pub name: String,
+    pub qualified_name: String,          // Module.Outer.Name
+    pub descriptor: u64,
+    pub access_function: Option<u64>,    // Returns the type metadata
+    pub is_generic: bool,
+    pub fields: Vec<SwiftField>,
+    pub methods: Vec<SwiftMethod>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcClassSummary {
+    pub name: String,
+    pub address: u64,
+    pub superclass: Option<String>,
+    pub is_swift: bool,
+    pub method_count: usize,
+    pub ivar_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwiftTypeSummary {
+    pub kind: String,
+    pub qualified_name: String,
+    pub descriptor: u64,
+    pub field_count: usize,
+    pub method_count: usize,
+}
+
+/// Class / type tree of one image; details come from get_objc_class / get_swift_type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeMetadataSummary {
+    pub key: String,                     // Pass to the detail commands
+    pub module_name: String,
+    pub source: String,                  // "memory" | "file"
+    pub base: u64,                       // Addresses are rebased on this
+    pub classes: Vec<ObjcClassSummary>,
+    pub categories: Vec<ObjcCategory>,
+    pub swift_types: Vec<SwiftTypeSummary>,
+    pub selector_refs: usize,
+    pub warnings: Vec<String>,
+}
+
+/// A class method that implements a selector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcImplementation {
+    pub class_name: String,              // "Class" or "Class (Category)"
+    pub method: ObjcMethod,
+}
+
+struct RuntimeMetadata {
+    summary: RuntimeMetadataSummary,
+    classes: Vec<ObjcClass>,
+    swift_types: Vec<SwiftType>,
+}
+
+// Parsed images by key (module name or file path)
+static METADATA: Lazy<Mutex<HashMap<String, Arc<RuntimeMetadata>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct Segment {
+    vmaddr: u64,
+    vmsize: u64,
+    fileoff: u64,
+    filesize: u64,
+}
+
+struct Section {
+    name: String,
+    addr: u64,
+    size: u64,
+}
+
+/// How pointers stored in the file are encoded (dyld chained fixups)
+#[derive(Clone, Copy, PartialEq)]
+enum PointerFormat {
+    Plain,                               // Rebased by opcodes or already slid
+    Ptr64 { offset: bool },
+    Arm64e { offset: bool, ordinal_bits: u32 },
+}
+
+/// Pointer stored in the image: a local address (unslid) or an import
+enum Target {
+    Local(u64),
+    Import(String),
+}
+
+struct Remote {
+    host: String,
+    port: u16,
+    chunks: HashMap<u64, Option<Vec<u8>>>,
+}
+
+impl Remote {
+    async fn read(&mut self, address: u64, size: usize) -> Option<Vec<u8>> {
+        let mut out = Vec::with_capacity(size);
+        let mut pos = address;
+        while out.len() < size {
+            let chunk = pos / CHUNK * CHUNK;
+            if !self.chunks.contains_key(&chunk) {
+                let data = read_memory_from_server(&self.host, self.port, chunk, CHUNK as usize).await.ok();
+                self.chunks.insert(chunk, data.filter(|d| !d.is_empty()));
+            }
+            let from = (pos - chunk) as usize;
+            let Some(data) = self.chunks[&chunk].as_ref().filter(|d| from < d.len()) else {
+                // The chunk runs into unmapped memory; read just what was asked
+                return read_memory_from_server(&self.host, self.port, address, size).await.ok()
+                    .filter(|d| d.len() == size);
+            };
+            let take = (size - out.len()).min(data.len() - from);
+            out.extend_from_slice(&data[from..from + take]);
+            pos += take as u64;
+        }
+        Some(out)
+    }
+
+    async fn prefetch(&mut self, ranges: &[(u64, u64)]) {
+        let mut chunks: Vec<u64> = ranges.iter()
+            .flat_map(|&(start, size)| (start / CHUNK..start.saturating_add(size).div_ceil(CHUNK)).map(|c| c * CHUNK))
+            .filter(|c| !self.chunks.contains_key(c))
+            .collect();
+        chunks.sort_unstable();
+        chunks.dedup();
+        let requests: Vec<(u64, usize)> = chunks.iter().map(|&c| (c, CHUNK as usize)).collect();
+        for batch in requests.chunks(PARALLEL_READS) {
+            let results = read_chunks_parallel(&self.host, self.port, batch, READ_TIMEOUT).await;
+            for (&(chunk, _), data) in batch.iter().zip(results) {
+                self.chunks.insert(chunk, data.map(|d| d.to_vec()).filter(|d| !d.is_empty()));
+            }
+        }
+    }
+}
+
+enum Backing {
+    File(Vec<u8>),                       // The Mach-O slice
+    Memory(Remote),
+}
+
+/// 64-bit little-endian Mach-O image, read from a file or from the target
+struct Image {
+    backing: Backing,
+    segments: Vec<Segment>,
+    sections: Vec<Section>,
+    text_vmaddr: u64,
+    slide: u64,                          // Runtime address = vmaddr + slide (wrapping)
+    format: PointerFormat,
+    imports: Vec<String>,
+}
+
+fn fixed_name(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).to_string()
+}
+
+/// arm64 (or else x86_64, or else the first) slice of a universal binary
+pub(crate) fn thin_slice(data: Vec<u8>) -> Result<Vec<u8>, String> {
+    let (entry_size, is_64) = match data.get(0..4) {
+        Some([0xca, 0xfe, 0xba, 0xbe]) => (20, false),
+        Some([0xca, 0xfe, 0xba, 0xbf]) => (32, true),
+        _ => return Ok(data),
+    };
+    let count = u32_at(&data, 4, true).ok_or("Truncated universal header")? as usize;
+    let slices: Vec<(u32, u64, u64)> = (0..count.min(32))
+        .filter_map(|i| {
+            let pos = 8 + i * entry_size;
+            let cputype = u32_at(&data, pos, true)?;
+            if is_64 {
+                Some((cputype, u64_at(&data, pos + 8, true)?, u64_at(&data, pos + 16, true)?))
+            } else {
+                Some((cputype, u32_at(&data, pos + 8, true)? as u64, u32_at(&data, pos + 12, true)? as u64))
+            }
+        })
+        .collect();
+    let (_, offset, size) = slices.iter().find(|s| s.0 == 0x0100_000c)
+        .or_else(|| slices.iter().find(|s| s.0 == 0x0100_0007))
+        .or(slices.first())
+        .ok_or("Universal binary has no slices")?;
+    data.get(*offset as usize..(*offset + *size) as usize)
+        .map(|slice| slice.to_vec())
+        .ok_or_else(|| "Truncated universal binary slice".to_string())
+}
+
+impl Image {
+    /// Load commands from `header`, the start of the image
+    fn parse(header: &[u8], backing: Backing, base: Option<u64>) -> Result<Self, String> {
+        if header.get(0..4) != Some(&[0xcf, 0xfa, 0xed, 0xfe]) {
+            return Err("Not a 64-bit little-endian Mach-O image".to_string());
+        }
+        let ncmds = u32_at(header, 16, false).ok_or("Truncated Mach-O header")?;
+        let mut segments = Vec::new();
+        let mut sections = Vec::new();
+        let mut chained_fixups = None;
+        let mut pos = 32;
+        for _ in 0..ncmds {
+            let (Some(cmd), Some(size)) = (u32_at(header, pos, false), u32_at(header, pos + 4, false)) else {
+                break;
+            };
+            match cmd {
+                LC_SEGMENT_64 => {
+                    let field = |offset| u64_at(header, pos + offset, false).unwrap_or(0);
+                    segments.push(Segment { vmaddr: field(24), vmsize: field(32), fileoff: field(40), filesize: field(48) });
+                    let nsects = u32_at(header, pos + 64, false).unwrap_or(0) as usize;
+                    for s in 0..nsects.min(256) {
+                        let at = pos + 72 + s * 80;
+                        let Some(raw) = header.get(at..at + 80) else { break };
+                        sections.push(Section {
+                            name: fixed_name(&raw[0..16]),
+                            addr: u64_at(raw, 32, false).unwrap_or(0),
+                            size: u64_at(raw, 40, false).unwrap_or(0),
+                        });
+                    }
+                }
+                LC_DYLD_CHAINED_FIXUPS => chained_fixups = u32_at(header, pos + 8, false),
+                _ => {}
+            }
+            if size < 8 {
+                break;
+            }
+            pos += size as usize;
+        }
+        let text_vmaddr = segments.iter()
+            .find(|s| s.fileoff == 0 && s.filesize > 0)
+            .map(|s| s.vmaddr)
+            .ok_or("Mach-O image has no __TEXT segment")?;
+
+        let mut image = Image {
+            slide: base.map_or(0, |b| b.wrapping_sub(text_vmaddr)),
+            backing,
+            segments,
+            sections,
+            text_vmaddr,
+            format: PointerFormat::Plain,
+            imports: Vec::new(),
+        };
+        // Live images are already rebased and bound
+        if let (Some(dataoff), Backing::File(data)) = (chained_fixups, &image.backing) {
+            let (format, imports) = parse_chained_fixups(data, dataoff as usize);
+            image.format = format;
+            image.imports = imports;
+        }
+        Ok(image)
+    }
+
+    fn in_image(&self, vmaddr: u64) -> bool {
+        self.segments.iter().any(|s| vmaddr >= s.vmaddr && vmaddr < s.vmaddr.saturating_add(s.vmsize))
+    }
+
+    fn runtime(&self, vmaddr: u64) -> u64 {
+        vmaddr.wrapping_add(self.slide)
+    }
+
+    async fn read(&mut self, vmaddr: u64, size: usize) -> Option<Vec<u8>> {
+        let runtime = self.runtime(vmaddr);
+        let file_offset = self.segments.iter()
+            .find(|s| vmaddr >= s.vmaddr && vmaddr < s.vmaddr.saturating_add(s.filesize))
+            .map(|s| (s.fileoff + (vmaddr - s.vmaddr)) as usize);
+        match &mut self.backing {
+            Backing::File(data) => data.get(file_offset?..file_offset?.checked_add(size)?).map(|b| b.to_vec()),
+            Backing::Memory(remote) => remote.read(runtime, size).await,
+        }
+    }
+
+    async fn u32(&mut self, vmaddr: u64) -> Option<u32> {
+        u32_at(&self.read(vmaddr, 4).await?, 0, false)
+    }
+
+    async fn u64(&mut self, vmaddr: u64) -> Option<u64> {
+        u64_at(&self.read(vmaddr, 8).await?, 0, false)
+    }
+
+    async fn c_string(&mut self, vmaddr: u64) -> Option<String> {
+        for size in [256, 32] {
+            if let Some(bytes) = self.read(vmaddr, size).await {
+                return Some(fixed_name(&bytes));
+            }
+        }
+        None
+    }
+
+    /// Decode a stored pointer. Live (and dumped) images hold slid runtime
+    /// pointers, possibly signed; files hold chained-fixup encodings.
+    fn target(&self, raw: u64) -> Option<Target> {
+        if raw == 0 {
+            return None;
+        }
+        let unslid = (raw & POINTER_MASK).wrapping_sub(self.slide);
+        if matches!(self.backing, Backing::Memory(_)) || self.format == PointerFormat::Plain || self.in_image(unslid) {
+            return Some(Target::Local(unslid));
+        }
+        let import = |ordinal: u64| self.imports.get(ordinal as usize).cloned().map(Target::Import);
+        match self.format {
+            PointerFormat::Plain => None,
+            PointerFormat::Ptr64 { offset } => {
+                if raw >> 63 == 1 {
+                    return import(raw & 0xFF_FFFF);
+                }
+                let target = (raw & 0xF_FFFF_FFFF) | (((raw >> 36) & 0xFF) << 56);
+                Some(Target::Local(if offset { self.text_vmaddr + target } else { target }))
+            }
+            PointerFormat::Arm64e { offset, ordinal_bits } => {
+                let (auth, bind) = (raw >> 63 == 1, (raw >> 62) & 1 == 1);
+                if bind {
+                    return import(raw & ((1 << ordinal_bits) - 1));
+                }
+                if auth {
+                    return Some(Target::Local(self.text_vmaddr + (raw & 0xFFFF_FFFF)));
+                }
+                let target = raw & 0x7FF_FFFF_FFFF;
+                Some(Target::Local(if offset { self.text_vmaddr + target } else { target | (((raw >> 43) & 0xFF) << 56) }))
+            }
+        }
+    }
+
+    fn pointer(&self, raw: u64) -> Option<u64> {
+        match self.target(raw)? {
+            Target::Local(vmaddr) => Some(vmaddr),
+            Target::Import(_) => None,
+        }
+    }
+
+    async fn read_pointer(&mut self, vmaddr: u64) -> Option<u64> {
+        let raw = self.u64(vmaddr).await?;
+        self.pointer(raw)
+    }
+
+    async fn pointed_string(&mut self, vmaddr: u64) -> Option<String> {
+        let target = self.read_pointer(vmaddr).await?;
+        self.c_string(target).await
+    }
+
+    /// Target of a 32-bit relative pointer stored at `vmaddr` (None for 0)
+    async fn relative(&mut self, vmaddr: u64) -> Option<u64> {
+        let offset = self.u32(vmaddr).await? as i32;
+        (offset != 0).then(|| vmaddr.wrapping_add_signed(offset as i64))
+    }
+
+    fn sections_named(&self, name: &str) -> Vec<(u64, u64)> {
+        self.sections.iter().filter(|s| s.name == name).map(|s| (s.addr, s.size)).collect()
+    }
+
+    async fn prefetch_metadata_sections(&mut self) {
+        let ranges: Vec<(u64, u64)> = self.sections.iter()
+            .filter(|s| s.name.starts_with("__objc_") || s.name.starts_with("__swift5_"))
+            .map(|s| (s.addr.wrapping_add(self.slide), s.size))
+            .collect();
+        if let Backing::Memory(remote) = &mut self.backing {
+            remote.prefetch(&ranges).await;
+        }
+    }
+}
+
+/// Pointer format of the first segment with fixups, and the import names
+fn parse_chained_fixups(data: &[u8], dataoff: usize) -> (PointerFormat, Vec<String>) {
+    let header = |offset| u32_at(data, dataoff + offset, false).unwrap_or(0) as usize;
+    let (starts, imports_offset, symbols_offset, imports_count, imports_format) = (header(4), header(8), header(12), header(16), header(20));
+
+    let starts = dataoff + starts;
+    let seg_count = u32_at(data, starts, false).unwrap_or(0) as usize;
+    let pointer_format = (0..seg_count.min(64))
+        .filter_map(|i| u32_at(data, starts + 4 + i * 4, false).filter(|&o| o != 0))
+        .find_map(|o| u16_at(data, starts + o as usize + 6, false))
+        .unwrap_or(0);
+    let format = match pointer_format {
+        2 => PointerFormat::Ptr64 { offset: false },
+        6 => PointerFormat::Ptr64 { offset: true },
+        1 => PointerFormat::Arm64e { offset: false, ordinal_bits: 16 },
+        9 => PointerFormat::Arm64e { offset: true, ordinal_bits: 16 },
+        12 => PointerFormat::Arm64e { offset: true, ordinal_bits: 24 },
+        _ => PointerFormat::Plain,
+    };
+
+    let entry_size = match imports_format {
+        1 => 4,
+        2 => 8,
+        3 => 16,
+        _ => return (format, Vec::new()),
+    };
+    let imports = (0..imports_count.min(MAX_ENTRIES))
+        .map(|i| {
+            let at = dataoff + imports_offset + i * entry_size;
+            let name_offset = if imports_format == 3 {
+                u64_at(data, at, false).map(|v| (v >> 32) as usize)
+            } else {
+                u32_at(data, at, false).map(|v| (v >> 9) as usize)
+            };
+            name_offset
+                .and_then(|o| data.get(dataoff + symbols_offset + o..))
+                .map(fixed_name)
+                .unwrap_or_default()
+        })
+        .collect();
+    (format, imports)
+}
+
+/// Methods of the method_list_t pointed to by `slot`
+async fn method_list(image: &mut Image, slot: u64, is_class_method: bool) -> Vec<ObjcMethod> {
+    let Some(list) = image.read_pointer(slot).await else { return Vec::new() };
+    let (Some(entsize_flags), Some(count)) = (image.u32(list).await, image.u32(list + 4).await) else {
+        return Vec::new();
+    };
+    let relative = entsize_flags & 0x8000_0000 != 0;
+    // Relative lists in the shared cache point at the selector string itself
+    let direct_selectors = entsize_flags & 0x4000_0000 != 0;
+    let entsize = (entsize_flags & 0xFFFC) as u64;
+    if entsize == 0 || count > MAX_LIST_ENTRIES {
+        return Vec::new();
+    }
+
+    let mut methods = Vec::with_capacity(count as usize);
+    for i in 0..count as u64 {
+        let entry = list + 8 + i * entsize;
+        let method = if relative {
+            let selector = match image.relative(entry).await {
+                Some(name) if direct_selectors => image.c_string(name).await,
+                Some(selref) => image.pointed_string(selref).await,
+                None => None,
+            };
+            let types = match image.relative(entry + 4).await {
+                Some(types) => image.c_string(types).await,
+                None => None,
+            };
+            let imp = image.relative(entry + 8).await;
+            (selector, types, imp)
+        } else {
+            let selector = image.pointed_string(entry).await;
+            let types = image.pointed_string(entry + 8).await;
+            let imp = image.read_pointer(entry + 16).await;
+            (selector, types, imp)
+        };
+        let (Some(selector), types, imp) = method else { continue };
+        methods.push(ObjcMethod { selector, types, address: imp.map(|a| image.runtime(a)), is_class_method });
+    }
+    methods
+}
+
+async fn ivar_list(image: &mut Image, slot: u64) -> Vec<ObjcIvar> {
+    let Some(list) = image.read_pointer(slot).await else { return Vec::new() };
+    let (Some(entsize), Some(count)) = (image.u32(list).await, image.u32(list + 4).await) else {
+        return Vec::new();
+    };
+    if entsize < 32 || count > MAX_LIST_ENTRIES {
+        return Vec::new();
+    }
+    let mut ivars = Vec::with_capacity(count as usize);
+    for i in 0..count as u64 {
+        let entry = list + 8 + i * entsize as u64;
+        let Some(name) = image.pointed_string(entry + 8).await else { continue };
+        let offset = match image.read_pointer(entry).await {
+            Some(slot) => image.u32(slot).await,
+            None => None,
+        };
+        ivars.push(ObjcIvar {
+            name,
+            type_encoding: image.pointed_string(entry + 16).await,
+            offset,
+            size: image.u32(entry + 28).await.unwrap_or(0),
+        });
+    }
+    ivars
+}
+
+/// class_ro_t of a class object. Realized classes in a live process point at
+/// class_rw_t instead, which leads to the ro data (directly or via class_rw_ext_t).
+async fn class_ro(image: &mut Image, class: u64) -> Option<(u64, bool)> {
+    let raw_data = image.u64(class + 32).await?;
+    let is_swift = raw_data & 3 != 0;
+    let data = image.pointer(raw_data)? & !7;
+    let flags = image.u32(data).await?;
+    if flags & 0x8000_0000 == 0 {
+        return Some((data, is_swift));
+    }
+    let ro_or_ext = image.read_pointer(data + 8).await?;
+    let ro = if ro_or_ext & 1 == 1 { image.read_pointer(ro_or_ext & !1).await? } else { ro_or_ext };
+    Some((ro, is_swift))
+}
+
+async fn class_name(image: &mut Image, class: u64) -> Option<String> {
+    let (ro, _) = class_ro(image, class).await?;
+    image.pointed_string(ro + 24).await
+}
+
+/// Name of a class referenced from this image, including imported ones ("_OBJC_CLASS_$_NSObject")
+async fn referenced_class_name(image: &mut Image, slot: u64) -> Option<String> {
+    let raw = image.u64(slot).await?;
+    match image.target(raw)? {
+        Target::Import(symbol) => Some(symbol.trim_start_matches("_OBJC_CLASS_$_").to_string()),
+        Target::Local(class) => class_name(image, class).await,
+    }
+}
+
+async fn parse_class(image: &mut Image, class: u64) -> Option<ObjcClass> {
+    let (ro, is_swift) = class_ro(image, class).await?;
+    let name = image.pointed_string(ro + 24).await?;
+    let mut methods = method_list(image, ro + 32, false).await;
+    if let Some(metaclass) = image.read_pointer(class).await {
+        if let Some((meta_ro, _)) = class_ro(image, metaclass).await {
+            methods.extend(method_list(image, meta_ro + 32, true).await);
+        }
+    }
+    let ivars = ivar_list(image, ro + 48).await;
+    Some(ObjcClass {
+        name,
+        address: image.runtime(class),
+        superclass: referenced_class_name(image, class + 8).await,
+        is_swift,
+        instance_size: image.u32(ro + 8).await.unwrap_or(0),
+        methods,
+        ivars,
+    })
+}
+
+async fn objc_classes(image: &mut Image, warnings: &mut Vec<String>) -> Vec<ObjcClass> {
+    let mut classes = Vec::new();
+    for (addr, size) in image.sections_named("__objc_classlist") {
+        for slot in (addr..addr + size).step_by(8).take(MAX_ENTRIES) {
+            let Some(class) = image.read_pointer(slot).await else { continue };
+            match parse_class(image, class).await {
+                Some(parsed) => classes.push(parsed),
+                None => warnings.push(format!("Unreadable class at 0x{:x}", image.runtime(class))),
+            }
+        }
+    }
+    classes
+}
+
+async fn objc_categories(image: &mut Image) -> Vec<ObjcCategory> {
+    let mut categories = Vec::new();
+    for (addr, size) in image.sections_named("__objc_catlist") {
+        for slot in (addr..addr + size).step_by(8).take(MAX_ENTRIES) {
+            let Some(category) = image.read_pointer(slot).await else { continue };
+            let Some(name) = image.pointed_string(category).await else { continue };
+            let class_name = referenced_class_name(image, category + 8).await;
+            let mut methods = method_list(image, category + 16, false).await;
+            methods.extend(method_list(image, category + 24, true).await);
+            categories.push(ObjcCategory { name, class_name, methods });
+        }
+    }
+    categories
+}
+
+fn swift_kind(kind: u32) -> &'static str {
+    match kind {
+        0 => "module",
+        1 => "extension",
+        2 => "anonymous",
+        3 => "protocol",
+        4 => "opaque_type",
+        16 => "class",
+        17 => "struct",
+        18 => "enum",
+        _ => "unknown",
+    }
+}
+
+/// Target of a relative pointer that may be indirect (low bit set)
+async fn indirectable(image: &mut Image, vmaddr: u64) -> Option<u64> {
+    let target = image.relative(vmaddr).await?;
+    if target & 1 == 0 {
+        return Some(target);
+    }
+    image.read_pointer(target & !1).await
+}
+
+async fn qualified_name(image: &mut Image, descriptor: u64, name: &str) -> String {
+    let mut parts = vec![name.to_string()];
+    let mut current = descriptor;
+    for _ in 0..8 {
+        let Some(parent) = indirectable(image, current + 4).await else { break };
+        let Some(flags) = image.u32(parent).await else { break };
+        let kind = flags & 0x1F;
+        if kind != 1 && kind != 2 {
+            match image.relative(parent + 8).await {
+                Some(name) => parts.push(image.c_string(name).await.unwrap_or_default()),
+                None => break,
+            }
+        }
+        if kind == 0 {
+            break;
+        }
+        current = parent;
+    }
+    parts.reverse();
+    parts.join(".")
+}
+
+/// Mangled type name with symbolic references to types of this image replaced by their names
+async fn mangled_type_name(image: &mut Image, vmaddr: u64) -> String {
+    let Some(bytes) = image.read(vmaddr, 128).await.or(image.read(vmaddr, 16).await) else {
+        return String::new();
+    };
+    let mut out = String::new();
+    let mut i = 0;
+    while i < bytes.len() && bytes[i] != 0 {
+        match bytes[i] {
+            0x01 => {
+                let name = match image.relative(vmaddr + i as u64 + 1).await {
+                    Some(descriptor) => match image.relative(descriptor + 8).await {
+                        Some(name) => image.c_string(name).await,
+                        None => None,
+                    },
+                    None => None,
+                };
+                out.push_str(&name.unwrap_or_else(|| "?".to_string()));
+                i += 5;
+            }
+            0x02..=0x17 => {
+                out.push('?');
+                i += 5;
+            }
+            0x18..=0x1F => {
+                out.push('?');
+                i += 9;
+            }
+            b => {
+                out.push(b as char);
+                i += 1;
+            }
+        }
+    }
+    // Standard library shorthands that come up in most fields
+    let known = match out.trim_end_matches("Sg") {
+        "Si" => Some("Int"),
+        "Su" => Some("UInt"),
+        "SS" => Some("String"),
+        "Sb" => Some("Bool"),
+        "Sd" => Some("Double"),
+        "Sf" => Some("Float"),
+        _ => None,
+    };
+    match known {
+        Some(name) if out.ends_with("Sg") => format!("{}?", name),
+        Some(name) => name.to_string(),
+        None => out,
+    }
+}
+
+async fn swift_fields(image: &mut Image, descriptor: u64) -> Vec<SwiftField> {
+    let Some(fields) = image.relative(descriptor + 16).await else { return Vec::new() };
+    let (Some(record_size), Some(count)) = (image.read(fields + 10, 2).await, image.u32(fields + 12).await) else {
+        return Vec::new();
+    };
+    let record_size = u16_at(&record_size, 0, false).unwrap_or(12).max(12) as u64;
+    let mut out = Vec::new();
+    for i in 0..count.min(MAX_LIST_ENTRIES) as u64 {
+        let record = fields + 16 + i * record_size;
+        let name = match image.relative(record + 8).await {
+            Some(name) => image.c_string(name).await.unwrap_or_default(),
+            None => String::new(),
+        };
+        let type_name = match image.relative(record + 4).await {
+            Some(type_name) => mangled_type_name(image, type_name).await,
+            None => String::new(),
+        };
+        out.push(SwiftField { name, type_name });
+    }
+    out
+}
+
+/// VTable of a non-generic class descriptor. Trailing objects before it:
+/// resilient superclass, then foreign or singleton metadata initialization.
+async fn swift_vtable(image: &mut Image, descriptor: u64, flags: u32) -> Vec<SwiftMethod> {
+    let type_flags = flags >> 16;
+    if flags & 0x80 != 0 || type_flags & 0x8000 == 0 {
+        return Vec::new();
+    }
+    let mut pos = descriptor + 44;
+    if type_flags & 0x2000 != 0 {
+        pos += 4;
+    }
+    pos += match type_flags & 3 {
+        1 => 12,
+        2 => 4,
+        _ => 0,
+    };
+    let Some(size) = image.u32(pos + 4).await else { return Vec::new() };
+    const KINDS: [&str; 6] = ["method", "init", "getter", "setter", "modify", "read"];
+    let mut methods = Vec::new();
+    for i in 0..size.min(MAX_LIST_ENTRIES) as u64 {
+        let entry = pos + 8 + i * 8;
+        let Some(method_flags) = image.u32(entry).await else { break };
+        let address = image.relative(entry + 4).await.map(|a| image.runtime(a));
+        methods.push(SwiftMethod {
+            kind: KINDS.get((method_flags & 0xF) as usize).unwrap_or(&"method").to_string(),
+            is_instance: method_flags & 0x10 != 0,
+            is_async: method_flags & 0x40 != 0,
+            address,
+            symbol: None,
+        });
+    }
+    methods
+}
+
+async fn swift_types(image: &mut Image, warnings: &mut Vec<String>) -> Vec<SwiftType> {
+    let mut types = Vec::new();
+    for (addr, size) in image.sections_named("__swift5_types") {
+        for entry in (addr..addr + size).step_by(4).take(MAX_ENTRIES) {
+            let Some(offset) = image.u32(entry).await.map(|v| v as i32) else { continue };
+            let target = entry.wrapping_add_signed((offset & !3) as i64);
+            let descriptor = if offset & 3 == 1 { image.read_pointer(target).await } else { Some(target) };
+            let Some(descriptor) = descriptor else { continue };
+            let Some(flags) = image.u32(descriptor).await else {
+                warnings.push(format!("Unreadable Swift type descriptor at 0x{:x}", image.runtime(descriptor)));
+                continue;
+            };
+            let name = match image.relative(descriptor + 8).await {
+                Some(name) => image.c_string(name).await.unwrap_or_default(),
+                None => String::new(),
+            };
+            let kind = flags & 0x1F;
+            types.push(SwiftType {
+                kind: swift_kind(kind).to_string(),
+                qualified_name: qualified_name(image, descriptor, &name).await,
+                name,
+                descriptor: image.runtime(descriptor),
+                access_function: image.relative(descriptor + 12).await.map(|a| image.runtime(a)),
+                is_generic: flags & 0x80 != 0,
+                fields: swift_fields(image, descriptor).await,
+                methods: if kind == 16 { swift_vtable(image, descriptor, flags).await } else { Vec::new() },
+            });
+        }
+    }
+    types
+}
+
+fn attached_module(state: &AppStateType, name: &str) -> Result<Option<ModuleInfo>, String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    Ok(state_guard.attached_modules.iter()
+        .find(|m| m.modulename == name)
+        .or_else(|| state_guard.attached_modules.iter().find(|m| m.modulename.eq_ignore_ascii_case(name)))
+        .cloned())
+}
+
+fn cached(key: &str) -> Result<Arc<RuntimeMetadata>, String> {
+    METADATA.lock().map_err(|e| e.to_string())?
+        .get(key)
+        .cloned()
+        .ok_or_else(|| format!("No metadata loaded for {}; call load_runtime_metadata first", key))
+}
+
+/// Parse the Objective-C classes, categories and Swift types of a module,
+/// either live from the target (`module_name`) or from a Mach-O file such as
+/// a dump_module output (`local_path`, rebased on `base` or the loaded
+/// module of the same name). Results are cached per module / file.
+#[tauri::command]
+pub async fn load_runtime_metadata(
+    state: tauri::State<'_, AppStateType>,
+    module_name: Option<String>,
+    local_path: Option<String>,
+    base: Option<u64>,
+    refresh: Option<bool>,
+) -> Result<RuntimeMetadataSummary, String> {
+    let key = local_path.clone().or(module_name.clone()).ok_or("A module name or local path is required")?;
+    if !refresh.unwrap_or(false) {
+        if let Ok(metadata) = cached(&key) {
+            return Ok(metadata.summary.clone());
+        }
+    }
+
+    let mut warnings = Vec::new();
+    let (mut image, display_name, source) = match &local_path {
+        Some(path) => {
+            let data = thin_slice(std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?)?;
+            let file_name = std::path::Path::new(path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let name = module_name.clone().unwrap_or(file_name);
+            let base = match base {
+                Some(base) => Some(base),
+                None => attached_module(state.inner(), &name)?.map(|m| m.base),
+            };
+            if base.is_none() {
+                warnings.push("Module is not loaded; addresses are unslid file addresses".to_string());
+            }
+            let header = data.get(..HEADER_READ_SIZE.min(data.len())).unwrap_or_default().to_vec();
+            (Image::parse(&header, Backing::File(data), base)?, name, "file")
+        }
+        None => {
+            let name = module_name.clone().unwrap_or_default();
+            let module = attached_module(state.inner(), &name)?.ok_or_else(|| format!("Module not loaded: {}", name))?;
+            let (host, port) = server_address()?;
+            let mut remote = Remote { host, port, chunks: HashMap::new() };
+            let header = remote.read(module.base, HEADER_READ_SIZE).await
+                .ok_or_else(|| format!("Failed to read the header of {}", module.modulename))?;
+            (Image::parse(&header, Backing::Memory(remote), Some(module.base))?, module.modulename, "memory")
+        }
+    };
+    image.prefetch_metadata_sections().await;
+
+    let classes = objc_classes(&mut image, &mut warnings).await;
+    let categories = objc_categories(&mut image).await;
+    let mut swift_types = swift_types(&mut image, &mut warnings).await;
+    let selector_refs = image.sections_named("__objc_selrefs").iter().map(|(_, size)| (size / 8) as usize).sum();
+
+    if let Ok(symbolizer) = Symbolizer::load(state.inner()).await {
+        for method in swift_types.iter_mut().flat_map(|t| t.methods.iter_mut()) {
+            method.symbol = method.address.and_then(|a| symbolizer.resolve(a)).and_then(|s| s.function_name);
+        }
+    }
+
+    let summary = RuntimeMetadataSummary {
+        key: key.clone(),
+        module_name: display_name,
+        source: source.to_string(),
+        base: image.runtime(image.text_vmaddr),
+        classes: classes.iter()
+            .map(|c| ObjcClassSummary {
+                name: c.name.clone(),
+                address: c.address,
+                superclass: c.superclass.clone(),
+                is_swift: c.is_swift,
+                method_count: c.methods.len(),
+                ivar_count: c.ivars.len(),
+            })
+            .collect(),
+        categories,
+        swift_types: swift_types.iter()
+            .map(|t| SwiftTypeSummary {
+                kind: t.kind.clone(),
+                qualified_name: t.qualified_name.clone(),
+                descriptor: t.descriptor,
+                field_count: t.fields.len(),
+                method_count: t.methods.len(),
+            })
+            .collect(),
+        selector_refs,
+        warnings,
+    };
+    let metadata = Arc::new(RuntimeMetadata { summary: summary.clone(), classes, swift_types });
+    METADATA.lock().map_err(|e| e.to_string())?.insert(key, metadata);
+    Ok(summary)
+}
+
+/// Methods and ivars of one Objective-C class from a loaded image
+#[tauri::command]
+pub fn get_objc_class(key: String, name: String) -> Result<ObjcClass, String> {
+    cached(&key)?.classes.iter()
+        .find(|c| c.name == name)
+        .cloned()
+        .ok_or_else(|| format!("Class {} not found", name))
+}
+
+/// Fields and vtable of one Swift type, by qualified or plain name
+#[tauri::command]
+pub fn get_swift_type(key: String, name: String) -> Result<SwiftType, String> {
+    let metadata = cached(&key)?;
+    metadata.swift_types.iter()
+        .find(|t| t.qualified_name == name)
+        .or_else(|| metadata.swift_types.iter().find(|t| t.name == name))
+        .cloned()
+        .ok_or_else(|| format!("Swift type {} not found", name))
+}
+
+/// Every class and category method implementing `selector`
+#[tauri::command]
+pub fn find_objc_selector(key: String, selector: String) -> Result<Vec<ObjcImplementation>, String> {
+    let metadata = cached(&key)?;
+    let from_classes = metadata.classes.iter()
+        .flat_map(|c| c.methods.iter().map(move |m| (c.name.clone(), m)));
+    let from_categories = metadata.summary.categories.iter()
+        .flat_map(|c| {
+            let owner = format!("{} ({})", c.class_name.as_deref().unwrap_or("?"), c.name);
+            c.methods.iter().map(move |m| (owner.clone(), m))
+        });
+    Ok(from_classes.chain(from_categories)
+        .filter(|(_, m)| m.selector == selector)
+        .map(|(class_name, m)| ObjcImplementation { class_name, method: m.clone() })
+        .collect())
+}
@@ -0,0 +1,374 @@
+use cpp_demangle::{DemangleOptions, Symbol as CppSymbol};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::memory_regions::{self, MemoryRegion};
+use crate::state::AppStateType;
+use crate::symbolizer::Symbolizer;
+use crate::{read_memory_from_server, MemoryFilterResult, SERVER_CONFIG};
+
+const WORD: u64 = 8;
+// How far before a hit an object header is looked for
+const MAX_BACKTRACK: u64 = 64;
+// Hits inspected per call; the rest are left without a hint
+const MAX_HINTED_RESULTS: usize = 500;
+const BLOCK_SIZE: u64 = 256;
+const MAX_NAME_LENGTH: usize = 256;
+const STRING_PREVIEW: usize = 64;
+// Offsets of MonoClass::name seen in Unity's Mono builds; namespace follows it
+const MONO_CLASS_NAME_OFFSETS: [u64; 3] = [0x40, 0x48, 0x50];
+const ISA_MASK_ARM64: u64 = 0x0000_000f_ffff_fff8;
+const ISA_MASK_X86_64: u64 = 0x0000_7fff_ffff_fff8;
+const FAST_DATA_MASK: u64 = 0x0000_7fff_ffff_fff8;
+const RW_REALIZED: u32 = 1 << 31;
+
+/// Likely object containing a scan hit, from runtime header layouts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectHint {
+    pub kind: String,                 // "std_string", "il2cpp_object", "mono_object", "objc_object", "cpp_object", "heap_chunk"
+    pub type_name: Option<String>,
+    pub object_address: u64,
+    pub offset: u64,                  // Hit offset inside the object
+    pub confidence: f32,              // 0..1, how specific the matched layout is
+    pub detail: Option<String>,       // String preview, vtable location, chunk size
+}
+
+/// Cached reads of the target, bounded by the memory map so wild pointers
+/// never reach the server
+struct Probe<'a> {
+    host: String,
+    port: u16,
+    regions: &'a [MemoryRegion],
+    blocks: HashMap<u64, Option<Vec<u8>>>,
+}
+
+impl<'a> Probe<'a> {
+    fn region(&self, address: u64) -> Option<&'a MemoryRegion> {
+        let index = self.regions.partition_point(|r| r.base <= address).checked_sub(1)?;
+        self.regions.get(index).filter(|r| address < r.base + r.size)
+    }
+
+    fn is_data_pointer(&self, value: u64) -> bool {
+        value.is_multiple_of(WORD) && self.region(value).is_some_and(|r| r.readable && !r.executable)
+    }
+
+    fn is_code_pointer(&self, value: u64) -> bool {
+        self.region(value).is_some_and(|r| r.executable)
+    }
+
+    async fn bytes(&mut self, address: u64, length: usize) -> Option<Vec<u8>> {
+        let end = address.checked_add(length as u64)?;
+        if !self.region(address).is_some_and(|r| r.readable && end <= r.base + r.size) {
+            return None;
+        }
+        let mut out = Vec::with_capacity(length);
+        let mut block = address - address % BLOCK_SIZE;
+        while block < end {
+            if !self.blocks.contains_key(&block) {
+                let data = read_memory_from_server(&self.host, self.port, block, BLOCK_SIZE as usize).await.ok();
+                self.blocks.insert(block, data);
+            }
+            let data = self.blocks.get(&block)?.as_ref()?;
+            let from = address.max(block) - block;
+            let to = end.min(block + BLOCK_SIZE) - block;
+            out.extend_from_slice(data.get(from as usize..to as usize)?);
+            block += BLOCK_SIZE;
+        }
+        Some(out)
+    }
+
+    async fn word(&mut self, address: u64) -> Option<u64> {
+        let bytes = self.bytes(address, WORD as usize).await?;
+        Some(u64::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    async fn u32(&mut self, address: u64) -> Option<u32> {
+        let bytes = self.bytes(address, 4).await?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    /// NUL-terminated string whose characters all pass `accept`
+    async fn c_string(&mut self, address: u64, accept: fn(u8) -> bool) -> Option<String> {
+        let mut out = Vec::new();
+        for chunk in 0..(MAX_NAME_LENGTH as u64 / 32) {
+            let bytes = self.bytes(address + chunk * 32, 32).await?;
+            for &b in &bytes {
+                if b == 0 {
+                    return String::from_utf8(out).ok();
+                }
+                if !accept(b) {
+                    return None;
+                }
+                out.push(b);
+            }
+        }
+        None
+    }
+}
+
+fn printable(b: u8) -> bool {
+    b.is_ascii_graphic() || b == b' ' || b == b'\t'
+}
+
+fn identifier(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'`' | b'<' | b'>' | b'$' | b'-')
+}
+
+fn preview(bytes: &[u8]) -> String {
+    let text: String = bytes.iter().take(STRING_PREVIEW).map(|&b| b as char).collect();
+    if bytes.len() > STRING_PREVIEW { format!("\"{}...\"", text) } else { format!("\"{}\"", text) }
+}
+
+fn hint(kind: &str, type_name: Option<String>, base: u64, hit: u64, confidence: f32, detail: Option<String>) -> ObjectHint {
+    ObjectHint { kind: kind.to_string(), type_name, object_address: base, offset: hit - base, confidence, detail }
+}
+
+/// libstdc++ basic_string: { char* data; size_t size; union { char buf[16]; size_t capacity; } }
+async fn detect_libstdcxx_string(probe: &mut Probe<'_>, base: u64, hit: u64) -> Option<ObjectHint> {
+    if hit >= base + 32 {
+        return None;
+    }
+    let data = probe.word(base).await?;
+    let size = probe.word(base + 8).await?;
+    if data == base + 16 {
+        if size >= 16 {
+            return None;
+        }
+        let bytes = probe.bytes(data, size as usize + 1).await?;
+        let (text, terminator) = bytes.split_at(size as usize);
+        if terminator[0] != 0 || !text.iter().all(|&b| printable(b)) {
+            return None;
+        }
+        return Some(hint("std_string", Some("std::string".into()), base, hit, 0.9, Some(preview(text))));
+    }
+    let capacity = probe.word(base + 16).await?;
+    if size == 0 || size > capacity || capacity >= 1 << 32 || !probe.is_data_pointer(data & !(WORD - 1)) {
+        return None;
+    }
+    let bytes = probe.bytes(data, (size as usize).min(STRING_PREVIEW) + 1).await?;
+    let text = &bytes[..bytes.len() - 1];
+    if !text.iter().all(|&b| printable(b)) || (size as usize <= STRING_PREVIEW && bytes[size as usize] != 0) {
+        return None;
+    }
+    Some(hint("std_string", Some("std::string".into()), base, hit, 0.7, Some(preview(text))))
+}
+
+/// libc++ basic_string: short form keeps the size in the first byte (shifted
+/// left by one in the older ABI) and up to 22 characters inline; the long form
+/// of the older ABI is { capacity | 1, size, data }
+async fn detect_libcxx_string(probe: &mut Probe<'_>, base: u64, hit: u64) -> Option<ObjectHint> {
+    if hit >= base + 24 {
+        return None;
+    }
+    let header = probe.bytes(base, 24).await?;
+    let lengths = [
+        (header[0] & 1 == 0).then_some(header[0] >> 1),
+        (header[0] & 0x80 == 0).then_some(header[0] & 0x7f),
+    ];
+    for length in lengths.into_iter().flatten().map(|l| l as usize) {
+        if (1..=22).contains(&length)
+            && header[1 + length] == 0
+            && header[1..1 + length].iter().all(|&b| printable(b))
+        {
+            return Some(hint("std_string", Some("std::string (libc++)".into()), base, hit, 0.75, Some(preview(&header[1..1 + length]))));
+        }
+    }
+
+    let capacity = u64::from_le_bytes(header[0..8].try_into().ok()?);
+    let size = u64::from_le_bytes(header[8..16].try_into().ok()?);
+    let data = u64::from_le_bytes(header[16..24].try_into().ok()?);
+    if capacity & 1 == 0 || size == 0 || size >= capacity || capacity >= 1 << 32 || !probe.is_data_pointer(data & !(WORD - 1)) {
+        return None;
+    }
+    let bytes = probe.bytes(data, (size as usize).min(STRING_PREVIEW)).await?;
+    if !bytes.iter().all(|&b| printable(b)) {
+        return None;
+    }
+    Some(hint("std_string", Some("std::string (libc++)".into()), base, hit, 0.65, Some(preview(&bytes))))
+}
+
+/// "Namespace.Name" from a pair of adjacent C string pointers
+async fn qualified_name(probe: &mut Probe<'_>, name_slot: u64) -> Option<String> {
+    let name_pointer = probe.word(name_slot).await?;
+    let name = probe.c_string(name_pointer, identifier).await.filter(|n| !n.is_empty())?;
+    let namespace = match probe.word(name_slot + 8).await {
+        Some(pointer) => probe.c_string(pointer, identifier).await?,
+        None => return None,
+    };
+    Some(if namespace.is_empty() { name } else { format!("{}.{}", namespace, name) })
+}
+
+/// Il2CppObject { Il2CppClass* klass; void* monitor; }, with the class name
+/// and namespace at +0x10 / +0x18 of Il2CppClass
+async fn detect_il2cpp(probe: &mut Probe<'_>, base: u64, hit: u64) -> Option<ObjectHint> {
+    let klass = probe.word(base).await?;
+    let monitor = probe.word(base + 8).await?;
+    if !probe.is_data_pointer(klass) || (monitor != 0 && !probe.is_data_pointer(monitor)) {
+        return None;
+    }
+    let image = probe.word(klass).await?;
+    if !probe.is_data_pointer(image) {
+        return None;
+    }
+    let name = qualified_name(probe, klass + 0x10).await?;
+    Some(hint("il2cpp_object", Some(name), base, hit, 0.85, None))
+}
+
+/// MonoObject { MonoVTable* vtable; MonoThreadsSync* sync; } with
+/// MonoVTable::klass first
+async fn detect_mono(probe: &mut Probe<'_>, base: u64, hit: u64) -> Option<ObjectHint> {
+    let vtable = probe.word(base).await?;
+    let sync = probe.word(base + 8).await?;
+    if !probe.is_data_pointer(vtable) || (sync != 0 && !probe.is_data_pointer(sync)) {
+        return None;
+    }
+    let klass = probe.word(vtable).await?;
+    if !probe.is_data_pointer(klass) {
+        return None;
+    }
+    for offset in MONO_CLASS_NAME_OFFSETS {
+        if let Some(name) = qualified_name(probe, klass + offset).await {
+            return Some(hint("mono_object", Some(name), base, hit, 0.75, Some(format!("vtable 0x{:x}", vtable))));
+        }
+    }
+    None
+}
+
+/// Objective-C object: (non-pointer) isa -> class -> class_rw_t / class_ro_t -> name
+async fn detect_objc(probe: &mut Probe<'_>, base: u64, hit: u64, arch: &str) -> Option<ObjectHint> {
+    let isa_mask = if arch == "arm64" || arch == "aarch64" { ISA_MASK_ARM64 } else { ISA_MASK_X86_64 };
+    let class = probe.word(base).await? & isa_mask;
+    if !probe.is_data_pointer(class) {
+        return None;
+    }
+    let data = probe.word(class + 0x20).await? & FAST_DATA_MASK;
+    if !probe.is_data_pointer(data) {
+        return None;
+    }
+    let flags = probe.u32(data).await?;
+    let ro = if flags & RW_REALIZED != 0 {
+        let ro_or_ext = probe.word(data + 8).await?;
+        if ro_or_ext & 1 != 0 { probe.word(ro_or_ext & !1).await? } else { ro_or_ext }
+    } else {
+        data
+    };
+    if !probe.is_data_pointer(ro) {
+        return None;
+    }
+    let name_pointer = probe.word(ro + 0x18).await?;
+    let name = probe.c_string(name_pointer, identifier).await.filter(|n| !n.is_empty())?;
+    Some(hint("objc_object", Some(name), base, hit, 0.85, None))
+}
+
+/// Polymorphic C++ object: vtable in a module's read-only data whose first
+/// slot is code. The type comes from Itanium RTTI when present, otherwise
+/// from the analyzed name of the first virtual function.
+async fn detect_cpp(probe: &mut Probe<'_>, base: u64, hit: u64, symbolizer: &Symbolizer) -> Option<ObjectHint> {
+    let vtable = probe.word(base).await?;
+    let region = probe.region(vtable).filter(|r| r.readable && !r.executable && r.module_name.is_some())?;
+    let first_slot = probe.word(vtable).await?;
+    if !probe.is_code_pointer(first_slot) {
+        return None;
+    }
+    let detail = symbolizer.resolve(vtable)
+        .map(|s| format!("vtable {}+0x{:x}", s.module_name, s.module_offset))
+        .or_else(|| region.module_name.as_ref().map(|m| format!("vtable in {}", m)));
+
+    // Itanium ABI: vtable[-1] = std::type_info*, type_info+8 = mangled name
+    let mut rtti_name = None;
+    let type_info = probe.word(vtable - WORD).await;
+    if let Some(type_info) = type_info.filter(|&t| probe.is_data_pointer(t)) {
+        if let Some(mangled) = probe.word(type_info + 8).await {
+            if let Some(mangled) = probe.c_string(mangled, identifier).await {
+                rtti_name = CppSymbol::new(format!("_ZTS{}", mangled)).ok()
+                    .and_then(|s| s.demangle(&DemangleOptions::default()).ok())
+                    .map(|d| d.trim_start_matches("typeinfo name for ").to_string());
+            }
+        }
+    }
+    if let Some(name) = rtti_name {
+        return Some(hint("cpp_object", Some(name), base, hit, 0.85, detail));
+    }
+    let class_name = symbolizer.resolve(first_slot)
+        .and_then(|s| s.function_name)
+        .and_then(|f| f.rsplit_once("::").map(|(class, _)| class.to_string()));
+    Some(hint("cpp_object", class_name, base, hit, 0.6, detail))
+}
+
+/// glibc malloc chunk: the size word (with flag bits) precedes the user pointer
+async fn detect_heap_chunk(probe: &mut Probe<'_>, base: u64, hit: u64) -> Option<ObjectHint> {
+    if probe.region(base).is_none_or(|r| r.region_type != "heap") {
+        return None;
+    }
+    let size = probe.word(base - WORD).await? & !7;
+    if !(0x20..=0x10000).contains(&size) || size % 16 != 0 || hit >= base + size - WORD {
+        return None;
+    }
+    Some(hint("heap_chunk", None, base, hit, 0.3, Some(format!("malloc chunk of 0x{:x} bytes", size - WORD))))
+}
+
+async fn inspect(probe: &mut Probe<'_>, hit: u64, arch: &str, target_os: &str, symbolizer: &Symbolizer) -> Option<ObjectHint> {
+    let start = hit - hit % WORD;
+    let mut best: Option<ObjectHint> = None;
+    let mut chunk = None;
+    for back in (0..=MAX_BACKTRACK).step_by(WORD as usize) {
+        let Some(base) = start.checked_sub(back).filter(|&b| probe.region(b).is_some()) else {
+            break;
+        };
+        let mut candidates = vec![
+            detect_libstdcxx_string(probe, base, hit).await,
+            detect_libcxx_string(probe, base, hit).await,
+            detect_il2cpp(probe, base, hit).await,
+            detect_mono(probe, base, hit).await,
+            detect_cpp(probe, base, hit, symbolizer).await,
+        ];
+        if matches!(target_os, "macos" | "ios" | "darwin") {
+            candidates.push(detect_objc(probe, base, hit, arch).await);
+        }
+        // The closest header wins among equally specific layouts
+        for candidate in candidates.into_iter().flatten() {
+            if best.as_ref().is_none_or(|b| candidate.confidence > b.confidence) {
+                best = Some(candidate);
+            }
+        }
+        if chunk.is_none() && target_os == "linux" {
+            chunk = detect_heap_chunk(probe, base, hit).await;
+        }
+    }
+    best.or(chunk)
+}
+
+/// Attach an `object_hint` to scan hits that sit inside a recognizable
+/// runtime object. 64-bit targets only.
+pub async fn annotate(state: &AppStateType, results: &mut [MemoryFilterResult]) {
+    if results.is_empty() {
+        return;
+    }
+    let Ok((arch, target_os)) = state.lock().map(|s| {
+        let info = s.server_info.as_ref();
+        (
+            info.map(|i| i.arch.clone()).unwrap_or_default(),
+            info.map(|i| i.target_os.clone()).unwrap_or_default(),
+        )
+    }) else {
+        return;
+    };
+    if !matches!(arch.as_str(), "arm64" | "aarch64" | "x86_64") {
+        return;
+    }
+    let Ok((host, port)) = SERVER_CONFIG.read().map(|c| (c.host.clone(), c.port)) else {
+        return;
+    };
+    let regions = memory_regions::get_cached_regions(Some(state), false).await.unwrap_or_default();
+    let Ok(symbolizer) = Symbolizer::load(state).await else {
+        return;
+    };
+    if host.is_empty() || regions.is_empty() {
+        return;
+    }
+
+    let mut probe = Probe { host, port, regions: &regions, blocks: HashMap::new() };
+    for result in results.iter_mut().take(MAX_HINTED_RESULTS) {
+        result.object_hint = inspect(&mut probe, result.address, &arch, &target_os, &symbolizer).await;
+    }
+}
@@ -0,0 +1,326 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::state::{AppStateType, ModuleInfo};
+use crate::{cache_versions, db, read_memory_from_server, server_address, write_memory_to_server};
+
+/// A tracked byte patch; module-relative so it survives ASLR and restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchInfo {
+    pub id: i64,
+    pub target_os: String,
+    pub module_name: String,          // Empty for patches outside any module
+    pub module_offset: u64,           // Absolute address when module_name is empty
+    pub address: u64,                 // Address at the last apply / revert
+    pub original_bytes: Vec<u8>,
+    pub patched_bytes: Vec<u8>,
+    pub description: Option<String>,
+    pub applied: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchReapplyResult {
+    pub id: i64,
+    pub address: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Create the patches table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS patches (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            module_offset INTEGER NOT NULL,
+            address INTEGER NOT NULL,
+            original_bytes BLOB NOT NULL,
+            patched_bytes BLOB NOT NULL,
+            description TEXT,
+            applied INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            UNIQUE(target_os, module_name, module_offset)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+const SELECT_COLUMNS: &str = "id, target_os, module_name, module_offset, address, original_bytes, patched_bytes, description, applied, created_at, updated_at";
+
+fn row_to_patch(row: &rusqlite::Row) -> rusqlite::Result<PatchInfo> {
+    Ok(PatchInfo {
+        id: row.get(0)?,
+        target_os: row.get(1)?,
+        module_name: row.get(2)?,
+        module_offset: row.get::<_, i64>(3)? as u64,
+        address: row.get::<_, i64>(4)? as u64,
+        original_bytes: row.get(5)?,
+        patched_bytes: row.get(6)?,
+        description: row.get(7)?,
+        applied: row.get::<_, i64>(8)? != 0,
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+    })
+}
+
+fn get_patch(conn: &Connection, id: i64) -> Result<PatchInfo, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM patches WHERE id = ?1", SELECT_COLUMNS),
+        params![id],
+        row_to_patch,
+    ).map_err(|e| format!("Patch {} not found: {}", id, e))
+}
+
+/// (target_os, attached modules)
+fn target_info(state: &AppStateType) -> Result<(String, Vec<ModuleInfo>), String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    let target_os = state_guard.server_info.as_ref()
+        .map(|info| info.target_os.clone())
+        .unwrap_or_default();
+    Ok((target_os, state_guard.attached_modules.clone()))
+}
+
+/// Absolute address of a patch in the current process
+fn current_address(patch: &PatchInfo, modules: &[ModuleInfo]) -> Result<u64, String> {
+    if patch.module_name.is_empty() {
+        return Ok(patch.module_offset);
+    }
+    modules.iter()
+        .find(|m| m.modulename == patch.module_name)
+        .map(|m| m.base + patch.module_offset)
+        .ok_or_else(|| format!("Module '{}' is not loaded", patch.module_name))
+}
+
+/// Write `new_bytes` at `address` and record the bytes they replace. Patching
+/// the same spot again keeps the bytes from before the first patch.
+pub async fn apply(
+    state: &AppStateType,
+    address: u64,
+    new_bytes: &[u8],
+    description: Option<String>,
+) -> Result<PatchInfo, String> {
+    if new_bytes.is_empty() {
+        return Err("Patch is empty".to_string());
+    }
+    let (host, port) = server_address()?;
+    let (target_os, modules) = target_info(state)?;
+    let (module_name, module_offset) = modules.iter()
+        .find(|m| address >= m.base && address < m.base.saturating_add(m.size))
+        .map(|m| (m.modulename.clone(), address - m.base))
+        .unwrap_or_else(|| (String::new(), address));
+
+    let existing: Option<(i64, Vec<u8>)> = {
+        let (target_os, module_name) = (target_os.clone(), module_name.clone());
+        db::run(move |conn| {
+            conn.query_row(
+                "SELECT id, original_bytes FROM patches WHERE target_os = ?1 AND module_name = ?2 AND module_offset = ?3",
+                params![target_os, module_name, module_offset as i64],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).optional().map_err(|e| e.to_string())
+        }).await?
+    };
+
+    // Cover both the earlier patch and this one, so reverting restores every
+    // byte either of them touched
+    let previous_len = existing.as_ref().map_or(0, |(_, previous)| previous.len());
+    let original_len = previous_len.max(new_bytes.len());
+    let mut original = read_memory_from_server(&host, port, address, original_len).await?;
+    if original.len() < original_len {
+        return Err(format!("Failed to read original bytes at 0x{:x}", address));
+    }
+    original.truncate(original_len);
+    if let Some((_, previous)) = &existing {
+        // Keep the pristine bytes for the range the earlier patch covered
+        original[..previous.len()].copy_from_slice(previous);
+    }
+
+    write_memory_to_server(&host, port, address, new_bytes).await?;
+
+    let new_bytes = new_bytes.to_vec();
+    db::run(move |conn| {
+        let id = match existing {
+            Some((id, _)) => {
+                conn.execute(
+                    "UPDATE patches SET address = ?1, original_bytes = ?2, patched_bytes = ?3,
+                     description = COALESCE(?4, description), applied = 1, updated_at = datetime('now')
+                     WHERE id = ?5",
+                    params![address as i64, original, new_bytes, description, id],
+                ).map_err(|e| e.to_string())?;
+                id
+            }
+            None => {
+                conn.execute(
+                    "INSERT INTO patches (target_os, module_name, module_offset, address, original_bytes, patched_bytes, description, applied, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 1, datetime('now'), datetime('now'))",
+                    params![target_os, module_name, module_offset as i64, address as i64, original, new_bytes, description],
+                ).map_err(|e| e.to_string())?;
+                conn.last_insert_rowid()
+            }
+        };
+        if !module_name.is_empty() {
+            cache_versions::invalidate(conn, &target_os, &module_name, "patch")?;
+        }
+        get_patch(conn, id)
+    }).await
+}
+
+/// Byte ranges of the applied patches of `target_os` in the current process
+pub async fn applied_ranges(target_os: &str, modules: &[ModuleInfo]) -> Result<Vec<(u64, u64)>, String> {
+    let patches = list_patches(Some(target_os.to_string()), None).await?;
+    Ok(patches.iter()
+        .filter(|p| p.applied)
+        .filter_map(|p| {
+            let address = current_address(p, modules).ok()?;
+            let len = p.original_bytes.len().max(p.patched_bytes.len()) as u64;
+            Some((address, address.saturating_add(len)))
+        })
+        .collect())
+}
+
+async fn set_applied(id: i64, address: u64, applied: bool) -> Result<PatchInfo, String> {
+    db::run(move |conn| {
+        conn.execute(
+            "UPDATE patches SET applied = ?1, address = ?2, updated_at = datetime('now') WHERE id = ?3",
+            params![applied as i64, address as i64, id],
+        ).map_err(|e| e.to_string())?;
+        get_patch(conn, id)
+    }).await
+}
+
+/// Reverting or re-applying changes the module's code, so its decompilations
+/// and xrefs are stale
+async fn invalidate_caches(patch: &PatchInfo) -> Result<(), String> {
+    if patch.module_name.is_empty() {
+        return Ok(());
+    }
+    let (target_os, module_name) = (patch.target_os.clone(), patch.module_name.clone());
+    db::run(move |conn| cache_versions::invalidate(conn, &target_os, &module_name, "patch").map(|_| ())).await
+}
+
+async fn load_patch(id: i64) -> Result<PatchInfo, String> {
+    db::run(move |conn| get_patch(conn, id)).await
+}
+
+/// Patch memory and track the original bytes so the change can be reverted
+#[tauri::command]
+pub async fn apply_patch(
+    state: tauri::State<'_, AppStateType>,
+    address: u64,
+    new_bytes: Vec<u8>,
+    description: Option<String>,
+) -> Result<PatchInfo, String> {
+    apply(state.inner(), address, &new_bytes, description).await
+}
+
+/// Restore the original bytes of a patch (the record is kept, marked not applied)
+#[tauri::command]
+pub async fn revert_patch(state: tauri::State<'_, AppStateType>, id: i64) -> Result<PatchInfo, String> {
+    let patch = load_patch(id).await?;
+    let (host, port) = server_address()?;
+    let (_, modules) = target_info(state.inner())?;
+    let address = current_address(&patch, &modules)?;
+    write_memory_to_server(&host, port, address, &patch.original_bytes).await?;
+    invalidate_caches(&patch).await?;
+    set_applied(id, address, false).await
+}
+
+/// Write the patched bytes of a reverted patch again
+#[tauri::command]
+pub async fn reapply_patch(state: tauri::State<'_, AppStateType>, id: i64) -> Result<PatchInfo, String> {
+    let patch = load_patch(id).await?;
+    let (host, port) = server_address()?;
+    let (_, modules) = target_info(state.inner())?;
+    let address = current_address(&patch, &modules)?;
+    write_memory_to_server(&host, port, address, &patch.patched_bytes).await?;
+    invalidate_caches(&patch).await?;
+    set_applied(id, address, true).await
+}
+
+#[tauri::command]
+pub async fn list_patches(target_os: Option<String>, module_name: Option<String>) -> Result<Vec<PatchInfo>, String> {
+    db::run(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM patches
+             WHERE (?1 IS NULL OR target_os = ?1) AND (?2 IS NULL OR module_name = ?2)
+             ORDER BY module_name, module_offset",
+            SELECT_COLUMNS
+        )).map_err(|e| e.to_string())?;
+        let rows = stmt.query_map(params![target_os, module_name], row_to_patch)
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }).await
+}
+
+/// Delete a patch record, optionally restoring its original bytes first
+#[tauri::command]
+pub async fn delete_patch(state: tauri::State<'_, AppStateType>, id: i64, revert: Option<bool>) -> Result<bool, String> {
+    let patch = load_patch(id).await?;
+    if revert.unwrap_or(false) && patch.applied {
+        let (host, port) = server_address()?;
+        let (_, modules) = target_info(state.inner())?;
+        let address = current_address(&patch, &modules)?;
+        write_memory_to_server(&host, port, address, &patch.original_bytes).await?;
+        invalidate_caches(&patch).await?;
+    }
+    let deleted = db::run(move |conn| {
+        conn.execute("DELETE FROM patches WHERE id = ?1", params![id]).map_err(|e| e.to_string())
+    }).await?;
+    Ok(deleted > 0)
+}
+
+/// Re-apply the applied patches of the module loaded at `base_address` (after
+/// the target restarted). Patches whose original bytes no longer match are
+/// skipped, since the module was likely rebuilt.
+#[tauri::command]
+pub async fn reapply_patches_for_module(
+    state: tauri::State<'_, AppStateType>,
+    base_address: u64,
+    module_name: Option<String>,
+) -> Result<Vec<PatchReapplyResult>, String> {
+    let (host, port) = server_address()?;
+    let (target_os, modules) = target_info(state.inner())?;
+    let module_name = match module_name {
+        Some(name) => name,
+        None => modules.iter()
+            .find(|m| m.base == base_address)
+            .map(|m| m.modulename.clone())
+            .ok_or_else(|| format!("No module loaded at 0x{:x}", base_address))?,
+    };
+
+    let patches: Vec<PatchInfo> = list_patches(Some(target_os), Some(module_name)).await?
+        .into_iter()
+        .filter(|p| p.applied)
+        .collect();
+
+    let mut results = Vec::new();
+    for patch in patches {
+        let address = base_address + patch.module_offset;
+        let outcome = async {
+            let current = read_memory_from_server(&host, port, address, patch.original_bytes.len()).await?;
+            // The original can be longer than the patch when a shorter patch
+            // replaced a longer one at the same offset
+            if current.starts_with(&patch.patched_bytes) {
+                return Ok(());
+            }
+            if current != patch.original_bytes {
+                return Err("Bytes differ from the recorded original".to_string());
+            }
+            write_memory_to_server(&host, port, address, &patch.patched_bytes).await
+        }.await;
+        if outcome.is_ok() {
+            set_applied(patch.id, address, true).await?;
+        }
+        results.push(PatchReapplyResult {
+            id: patch.id,
+            address,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+    Ok(results)
+}
@@ -0,0 +1,259 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::state::{self, AppInfo, AppStateType, ModuleInfo, ProcessInfo};
+use crate::{
+    continue_execution_on_server, remove_breakpoint_on_server, remove_watchpoint_on_server,
+    server_address_with_token, server_connection,
+};
+
+// Icons fetched per list_processes call
+const MAX_ICONS: usize = 64;
+
+/// Row of list_processes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessEntry {
+    pub pid: u32,
+    pub processname: String,
+    pub score: Option<i64>,           // Fuzzy match score when filtered (higher is better)
+    pub icon: Option<String>,         // data:image/png;base64 URI, when requested and available
+    pub arch: Option<String>,         // Server architecture; the server does not report per-process arch
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachedProcessInfo {
+    pub process: ProcessInfo,
+    pub app_info: Option<AppInfo>,
+    pub modules: Vec<ModuleInfo>,
+    pub attempts: u32,                // Attach requests sent (more than 1 after network retries)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetachResult {
+    pub pid: Option<u32>,
+    pub breakpoints_removed: usize,
+    pub watchpoints_removed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Request to the server with the auth token attached
+fn request(method: reqwest::Method, path: &str) -> Result<reqwest::RequestBuilder, String> {
+    let (host, port, token) = server_address_with_token()?;
+    let mut request = server_connection::client()?
+        .request(method, format!("{}{}", server_connection::base_url(&host, port), path));
+    if let Some(token) = &token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    Ok(request)
+}
+
+pub(crate) async fn get_json(path: &str) -> Result<serde_json::Value, String> {
+    let response = server_connection::send(request(reqwest::Method::GET, path)?).await?;
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    response.json().await.map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+/// Case-insensitive fuzzy score of `name` for `query`: exact > prefix >
+/// substring > in-order subsequence (tighter is better); None when the
+/// characters of the query do not all appear in order
+fn fuzzy_score(name: &str, query: &str) -> Option<i64> {
+    let name = name.to_lowercase();
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Some(0);
+    }
+    if name == query {
+        return Some(10_000);
+    }
+    if name.starts_with(&query) {
+        return Some(8_000 - name.len() as i64);
+    }
+    if let Some(position) = name.find(&query) {
+        return Some(6_000 - position as i64 - name.len() as i64);
+    }
+    let mut score = 3_000i64;
+    let mut last: Option<usize> = None;
+    let mut chars = name.char_indices();
+    for q in query.chars() {
+        let (index, _) = chars.by_ref().find(|(_, c)| *c == q)?;
+        if let Some(last) = last {
+            score -= (index - last - 1) as i64 * 10;
+        }
+        last = Some(index);
+    }
+    Some(score - name.len() as i64)
+}
+
+async fn fetch_icon(pid: u32) -> Result<Option<String>, String> {
+    let response = server_connection::send(request(reqwest::Method::GET, &format!("/api/processes/{}/icon", pid))?).await?;
+    match response.status() {
+        status if status.is_success() => {
+            let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+            Ok(Some(format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(&bytes))))
+        }
+        reqwest::StatusCode::NOT_IMPLEMENTED => Err("Icons are not supported by this server".to_string()),
+        _ => Ok(None),
+    }
+}
+
+/// Name, icon and arch the server reports for the attached process
+async fn fetch_app_info(pid: u32) -> Option<AppInfo> {
+    let info = get_json("/api/process/info").await.ok()?;
+    let data = &info["data"];
+    Some(AppInfo {
+        name: data["name"].as_str()?.to_string(),
+        pid,
+        icon: data["icon"].as_str().map(String::from),
+        arch: data["arch"].as_str().map(String::from),
+    })
+}
+
+/// Processes of the target, optionally fuzzy-filtered by name (best matches
+/// first) and with icons where the server can extract them
+#[tauri::command]
+pub async fn list_processes(
+    state: tauri::State<'_, AppStateType>,
+    filter: Option<String>,
+    include_icons: Option<bool>,
+) -> Result<Vec<ProcessEntry>, String> {
+    let arch = state.lock().ok().and_then(|s| s.server_info.as_ref().map(|info| info.arch.clone()));
+    let processes: Vec<ProcessInfo> = serde_json::from_value(get_json("/api/processes").await?)
+        .map_err(|e| format!("Failed to parse process list: {}", e))?;
+
+    let query = filter.unwrap_or_default();
+    let mut entries: Vec<ProcessEntry> = processes.into_iter()
+        .filter_map(|p| {
+            let score = if query.trim().is_empty() {
+                None
+            } else {
+                Some(fuzzy_score(&p.processname, &query).or_else(|| {
+                    p.pid.to_string().starts_with(query.trim()).then_some(0)
+                })?)
+            };
+            Some(ProcessEntry { pid: p.pid, processname: p.processname, score, icon: None, arch: arch.clone() })
+        })
+        .collect();
+    if query.trim().is_empty() {
+        entries.sort_by_key(|e| e.pid);
+    } else {
+        entries.sort_by(|a, b| b.score.cmp(&a.score).then(a.pid.cmp(&b.pid)));
+    }
+
+    if include_icons.unwrap_or(false) {
+        for entry in entries.iter_mut().take(MAX_ICONS) {
+            match fetch_icon(entry.pid).await {
+                Ok(icon) => entry.icon = icon,
+                Err(_) => break, // Unsupported or unreachable: the rest would fail too
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Attach to a process and load its app info and module list into the
+/// shared state (which restores stored breakpoints for the loaded modules)
+#[tauri::command]
+pub async fn attach_process(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+    pid: u32,
+) -> Result<AttachedProcessInfo, String> {
+    let (response, attempts) = server_connection::send_with_attempts(request(reqwest::Method::POST, &format!("/api/processes/{}/attach", pid))?).await?;
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() || body["success"].as_bool() == Some(false) {
+        return Err(body["message"].as_str().map(String::from).unwrap_or_else(|| format!("Server error: {}", status)));
+    }
+
+    let processname = get_json("/api/processes").await.ok()
+        .and_then(|list| serde_json::from_value::<Vec<ProcessInfo>>(list).ok())
+        .and_then(|list| list.into_iter().find(|p| p.pid == pid))
+        .map(|p| p.processname)
+        .unwrap_or_else(|| pid.to_string());
+    let app_info = fetch_app_info(pid).await;
+    let modules: Vec<ModuleInfo> = get_json("/api/modules").await.ok()
+        .and_then(|m| m["data"]["modules"].as_array().cloned())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|m| serde_json::from_value(m).ok())
+        .collect();
+
+    let process = ProcessInfo { pid, processname };
+    let mut updates = HashMap::new();
+    updates.insert("attachedProcess".to_string(), serde_json::to_value(&process).map_err(|e| e.to_string())?);
+    updates.insert("attachedAppInfo".to_string(), serde_json::to_value(&app_info).map_err(|e| e.to_string())?);
+    updates.insert("attachedModules".to_string(), serde_json::to_value(&modules).map_err(|e| e.to_string())?);
+    state::update_app_state(app, state, updates).await?;
+
+    Ok(AttachedProcessInfo { process, app_info, modules, attempts })
+}
+
+/// Release the attached process. The server has no detach endpoint, so this
+/// removes every breakpoint and watchpoint set from this client, stops
+/// coverage sessions, resumes all threads and clears the attached state.
+#[tauri::command]
+pub async fn detach_process(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+) -> Result<DetachResult, String> {
+    let (pid, breakpoints, watchpoints) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let breakpoints: Vec<String> = state_guard.active_breakpoints.iter()
+            .chain(&state_guard.software_breakpoints)
+            .cloned()
+            .collect();
+        let watchpoints: Vec<String> = state_guard.watchpoints.iter().map(|w| w.address.clone()).collect();
+        (state_guard.attached_process.as_ref().map(|p| p.pid), breakpoints, watchpoints)
+    };
+    let (host, port, _) = server_address_with_token()?;
+    let parse = |text: &str| u64::from_str_radix(text.trim().trim_start_matches("0x"), 16).ok();
+
+    crate::coverage::stop_all().await;
+    let mut result = DetachResult { pid, breakpoints_removed: 0, watchpoints_removed: 0, errors: Vec::new() };
+    for address in breakpoints.iter().filter_map(|a| parse(a)) {
+        match remove_breakpoint_on_server(&host, port, address).await {
+            Ok(()) => result.breakpoints_removed += 1,
+            Err(e) => result.errors.push(format!("Breakpoint 0x{:x}: {}", address, e)),
+        }
+    }
+    for address in watchpoints.iter().filter_map(|a| parse(a)) {
+        match remove_watchpoint_on_server(&host, port, address).await {
+            Ok(()) => result.watchpoints_removed += 1,
+            Err(e) => result.errors.push(format!("Watchpoint 0x{:x}: {}", address, e)),
+        }
+    }
+    if let Err(e) = continue_execution_on_server(&host, port, None).await {
+        result.errors.push(format!("Resume: {}", e));
+    }
+
+    let mut updates = HashMap::new();
+    updates.insert("attachedProcess".to_string(), serde_json::Value::Null);
+    updates.insert("attachedAppInfo".to_string(), serde_json::Value::Null);
+    updates.insert("attachedModules".to_string(), serde_json::json!([]));
+    updates.insert("activeBreakpoints".to_string(), serde_json::json!([]));
+    updates.insert("softwareBreakpoints".to_string(), serde_json::json!([]));
+    updates.insert("watchpoints".to_string(), serde_json::json!([]));
+    updates.insert("isInBreakState".to_string(), serde_json::json!(false));
+    state::update_app_state(app, state, updates).await?;
+    Ok(result)
+}
+
+/// Attached process with fresh app info from the server
+#[tauri::command]
+pub async fn get_attached_process_info(
+    state: tauri::State<'_, AppStateType>,
+) -> Result<Option<AttachedProcessInfo>, String> {
+    let (process, modules) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        (state_guard.attached_process.clone(), state_guard.attached_modules.clone())
+    };
+    let Some(process) = process else {
+        return Ok(None);
+    };
+    let app_info = fetch_app_info(process.pid).await;
+    Ok(Some(AttachedProcessInfo { process, app_info, modules, attempts: 0 }))
+}
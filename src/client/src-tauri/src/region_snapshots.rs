@@ -0,0 +1,288 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::state::AppState;
+use crate::{read_memory_from_server, server_address};
+
+// Region is read and stored in chunks of this size
+const CHUNK_SIZE: u64 = 1024 * 1024;
+const MAX_SNAPSHOT_BYTES: u64 = 1024 * 1024 * 1024;
+const DEFAULT_PAGE_SIZE: usize = 1000;
+// Byte changes kept per comparison for paging; the total is still counted
+const MAX_STORED_CHANGES: usize = 4_000_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub address: u64,
+    pub size: u64,
+    pub label: Option<String>,
+    pub created_at: u64,
+    pub stored_bytes: u64,            // Compressed size on disk
+    pub unreadable_bytes: u64,        // Chunks that could not be read (compared as missing)
+    pub path: String,
+}
+
+/// One byte that differs from the snapshot
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SnapshotByteChange {
+    pub address: u64,
+    pub old: u8,
+    pub new: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub snapshot_id: String,
+    pub compared_at: u64,
+    pub changed_bytes: u64,           // All changes, including those past the stored limit
+    pub changed_runs: u64,            // Contiguous ranges of changed bytes
+    pub unreadable_bytes: u64,        // Skipped because either side was unreadable
+    pub offset: usize,
+    pub changes: Vec<SnapshotByteChange>,
+    pub has_more: bool,
+    pub truncated: bool,              // More than MAX_STORED_CHANGES changes; later ones are not pageable
+}
+
+struct Comparison {
+    compared_at: u64,
+    changed_bytes: u64,
+    changed_runs: u64,
+    unreadable_bytes: u64,
+    changes: Vec<SnapshotByteChange>,
+}
+
+// Snapshots by id, with the latest comparison of each for paging
+static SNAPSHOTS: Lazy<Mutex<HashMap<String, SnapshotInfo>>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+static COMPARISONS: Lazy<Mutex<HashMap<String, Comparison>>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+static NEXT_SNAPSHOT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn snapshot_dir() -> PathBuf {
+    std::env::temp_dir().join("dynadbg_snapshots")
+}
+
+/// Chunk record: offset (u64), length (u32), readable (u8), compressed
+/// length (u32), lz4 data
+fn write_chunk<W: Write>(out: &mut W, offset: u64, len: u32, data: Option<&[u8]>) -> std::io::Result<u64> {
+    let compressed = data.map(lz4_flex::compress_prepend_size).unwrap_or_default();
+    out.write_all(&offset.to_le_bytes())?;
+    out.write_all(&len.to_le_bytes())?;
+    out.write_all(&[data.is_some() as u8])?;
+    out.write_all(&(compressed.len() as u32).to_le_bytes())?;
+    out.write_all(&compressed)?;
+    Ok(17 + compressed.len() as u64)
+}
+
+// (offset in the region, length, data when it was readable)
+type StoredChunk = (u64, u32, Option<Vec<u8>>);
+
+/// Next chunk of a snapshot file
+fn read_chunk<R: Read>(input: &mut R) -> std::io::Result<Option<StoredChunk>> {
+    let mut header = [0u8; 17];
+    match input.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let offset = u64::from_le_bytes(header[0..8].try_into().unwrap_or_default());
+    let len = u32::from_le_bytes(header[8..12].try_into().unwrap_or_default());
+    let readable = header[12] != 0;
+    let compressed_len = u32::from_le_bytes(header[13..17].try_into().unwrap_or_default()) as usize;
+    let mut compressed = vec![0u8; compressed_len];
+    input.read_exact(&mut compressed)?;
+    let data = if readable {
+        let data = lz4_flex::decompress_size_prepended(&compressed)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Some(data)
+    } else {
+        None
+    };
+    Ok(Some((offset, len, data)))
+}
+
+/// Store a compressed copy of a memory region in a temp file for a later
+/// compare_snapshot. Chunks that cannot be read are recorded as missing.
+#[tauri::command]
+pub async fn snapshot_region(address: u64, size: u64, label: Option<String>) -> Result<SnapshotInfo, String> {
+    if size == 0 || size > MAX_SNAPSHOT_BYTES {
+        return Err(format!("Snapshot size must be between 1 and {} bytes", MAX_SNAPSHOT_BYTES));
+    }
+    let (host, port) = server_address()?;
+    let dir = snapshot_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let id = format!("snap{}_{:x}", NEXT_SNAPSHOT_ID.fetch_add(1, Ordering::Relaxed), address);
+    let path = dir.join(format!("{}.bin", id));
+    let file = std::fs::File::create(&path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut out = std::io::BufWriter::new(file);
+
+    let mut stored_bytes = 0;
+    let mut unreadable_bytes = 0;
+    let mut offset = 0;
+    while offset < size {
+        let len = CHUNK_SIZE.min(size - offset);
+        let data = read_memory_from_server(&host, port, address + offset, len as usize).await
+            .ok()
+            .filter(|data| data.len() as u64 == len);
+        if data.is_none() {
+            unreadable_bytes += len;
+        }
+        stored_bytes += write_chunk(&mut out, offset, len as u32, data.as_deref())
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        offset += len;
+    }
+    out.flush().map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    if unreadable_bytes == size {
+        let _ = std::fs::remove_file(&path);
+        return Err(format!("Region 0x{:x}..0x{:x} is not readable", address, address + size));
+    }
+
+    let info = SnapshotInfo {
+        id: id.clone(),
+        address,
+        size,
+        label,
+        created_at: AppState::current_timestamp(),
+        stored_bytes,
+        unreadable_bytes,
+        path: path.to_string_lossy().to_string(),
+    };
+    SNAPSHOTS.lock().map_err(|e| e.to_string())?.insert(id, info.clone());
+    Ok(info)
+}
+
+/// Compare the region of a snapshot with its current contents. Returns the
+/// byte changes (address, old, new) from `offset`, `limit` at a time. The
+/// first page (offset 0) or `refresh` re-reads the target; later pages are
+/// served from that comparison.
+#[tauri::command]
+pub async fn compare_snapshot(
+    snapshot_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    refresh: Option<bool>,
+) -> Result<SnapshotDiff, String> {
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+    let info = SNAPSHOTS.lock().map_err(|e| e.to_string())?.get(&snapshot_id).cloned()
+        .ok_or_else(|| format!("Snapshot {} not found", snapshot_id))?;
+    let cached = COMPARISONS.lock().map_err(|e| e.to_string())?.contains_key(&snapshot_id);
+
+    if refresh.unwrap_or(offset == 0) || !cached {
+        let comparison = compare(&info).await?;
+        COMPARISONS.lock().map_err(|e| e.to_string())?.insert(snapshot_id.clone(), comparison);
+    }
+
+    let comparisons = COMPARISONS.lock().map_err(|e| e.to_string())?;
+    let comparison = comparisons.get(&snapshot_id).ok_or("Comparison was discarded")?;
+    let changes: Vec<SnapshotByteChange> = comparison.changes.iter().skip(offset).take(limit).copied().collect();
+    Ok(SnapshotDiff {
+        snapshot_id,
+        compared_at: comparison.compared_at,
+        changed_bytes: comparison.changed_bytes,
+        changed_runs: comparison.changed_runs,
+        unreadable_bytes: comparison.unreadable_bytes,
+        offset,
+        has_more: offset + changes.len() < comparison.changes.len(),
+        changes,
+        truncated: comparison.changed_bytes > comparison.changes.len() as u64,
+    })
+}
+
+/// Stream the snapshot file against the target, one chunk at a time
+async fn compare(info: &SnapshotInfo) -> Result<Comparison, String> {
+    let (host, port) = server_address()?;
+    let file = std::fs::File::open(&info.path).map_err(|e| format!("Failed to open {}: {}", info.path, e))?;
+    let mut input = std::io::BufReader::new(file);
+    let mut comparison = Comparison {
+        compared_at: AppState::current_timestamp(),
+        changed_bytes: 0,
+        changed_runs: 0,
+        unreadable_bytes: 0,
+        changes: Vec::new(),
+    };
+    let mut last_changed: Option<u64> = None;
+    while let Some((offset, len, old)) = read_chunk(&mut input).map_err(|e| format!("Failed to read {}: {}", info.path, e))? {
+        let new = read_memory_from_server(&host, port, info.address + offset, len as usize).await
+            .ok()
+            .filter(|data| data.len() == len as usize);
+        let (Some(old), Some(new)) = (old, new) else {
+            comparison.unreadable_bytes += len as u64;
+            continue;
+        };
+        for (i, (&old, &new)) in old.iter().zip(&new).enumerate() {
+            if old == new {
+                continue;
+            }
+            let address = info.address + offset + i as u64;
+            comparison.changed_bytes += 1;
+            if last_changed != Some(address.wrapping_sub(1)) {
+                comparison.changed_runs += 1;
+            }
+            last_changed = Some(address);
+            if comparison.changes.len() < MAX_STORED_CHANGES {
+                comparison.changes.push(SnapshotByteChange { address, old, new });
+            }
+        }
+    }
+    Ok(comparison)
+}
+
+#[tauri::command]
+pub fn list_snapshots() -> Result<Vec<SnapshotInfo>, String> {
+    let snapshots = SNAPSHOTS.lock().map_err(|e| e.to_string())?;
+    let mut list: Vec<SnapshotInfo> = snapshots.values().cloned().collect();
+    list.sort_by_key(|s| s.created_at);
+    Ok(list)
+}
+
+/// Drop a snapshot and its temp file
+#[tauri::command]
+pub fn delete_snapshot(snapshot_id: String) -> Result<bool, String> {
+    COMPARISONS.lock().map_err(|e| e.to_string())?.remove(&snapshot_id);
+    let Some(info) = SNAPSHOTS.lock().map_err(|e| e.to_string())?.remove(&snapshot_id) else {
+        return Ok(false);
+    };
+    let _ = std::fs::remove_file(&info.path);
+    Ok(true)
+}
+
+/// Bytes as captured in a snapshot (for showing context around a change);
+/// None when that part of the region was unreadable
+#[tauri::command]
+pub fn read_snapshot(snapshot_id: String, address: u64, size: usize) -> Result<Option<Vec<u8>>, String> {
+    let info = SNAPSHOTS.lock().map_err(|e| e.to_string())?.get(&snapshot_id).cloned()
+        .ok_or_else(|| format!("Snapshot {} not found", snapshot_id))?;
+    if address < info.address || address + size as u64 > info.address + info.size {
+        return Err("Range is outside the snapshot".to_string());
+    }
+    let file = std::fs::File::open(&info.path).map_err(|e| format!("Failed to open {}: {}", info.path, e))?;
+    let mut input = std::io::BufReader::new(file);
+    let start = address - info.address;
+    let end = start + size as u64;
+    let mut result = Vec::with_capacity(size);
+    while let Some((offset, len, data)) = read_chunk(&mut input).map_err(|e| e.to_string())? {
+        let chunk_end = offset + len as u64;
+        if chunk_end <= start {
+            continue;
+        }
+        if offset >= end {
+            break;
+        }
+        let Some(data) = data else {
+            return Ok(None);
+        };
+        let from = start.max(offset) - offset;
+        let to = end.min(chunk_end) - offset;
+        result.extend_from_slice(&data[from as usize..to as usize]);
+    }
+    Ok(Some(result))
+}
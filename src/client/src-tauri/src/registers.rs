@@ -0,0 +1,342 @@
+use serde::{Deserialize, Serialize};
+
+use crate::state::AppStateType;
+use crate::{read_register_from_server, write_register_to_server, SERVER_CONFIG};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterValue {
+    pub name: String,                 // Canonical name (x29 rather than fp, rflags rather than eflags)
+    pub value: String,                // "0x..."
+    pub bits: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagBit {
+    pub name: String,                 // N/Z/C/V on ARM64, CF/ZF/... on x86
+    pub bit: u32,
+    pub set: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterDump {
+    pub thread_id: u64,
+    pub arch: String,
+    pub registers: Vec<RegisterValue>,
+    pub flags: Vec<FlagBit>,
+    pub unavailable: Vec<String>,     // Known registers the server could not provide (FP/SIMD, ...)
+    pub values: serde_json::Value,    // Flat name -> "0x..." map including aliases, as consumed by reachability analysis
+}
+
+fn is_arm64(arch: &str) -> bool {
+    matches!(arch, "arm64" | "aarch64")
+}
+
+const X86_FLAGS: [(&str, u32); 9] = [
+    ("CF", 0), ("PF", 2), ("AF", 4), ("ZF", 6), ("SF", 7),
+    ("TF", 8), ("IF", 9), ("DF", 10), ("OF", 11),
+];
+
+const ARM64_FLAGS: [(&str, u32); 4] = [("N", 31), ("Z", 30), ("C", 29), ("V", 28)];
+
+const X86_64_GPRS: [&str; 16] = [
+    "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp",
+    "r8", "r9", "r10", "r11", "r12", "r13", "r14", "r15",
+];
+
+const X86_SEGMENTS: [&str; 6] = ["cs", "ss", "ds", "es", "fs", "gs"];
+
+/// Canonical register names of an architecture, in display order
+fn canonical_registers(arch: &str, target_os: &str) -> Vec<String> {
+    if is_arm64(arch) {
+        let mut names: Vec<String> = (0..=30).map(|i| format!("x{}", i)).collect();
+        names.extend(["sp", "pc", "cpsr"].map(String::from));
+        names
+    } else {
+        let mut names: Vec<String> = X86_64_GPRS.map(String::from).to_vec();
+        names.extend(["rip", "rflags"].map(String::from));
+        names.extend(X86_SEGMENTS.map(String::from));
+        if target_os == "linux" || target_os == "android" {
+            names.extend(["fs_base", "gs_base"].map(String::from));
+        }
+        names
+    }
+}
+
+/// FP/SIMD state; the register API only exposes general purpose registers
+fn vector_registers(arch: &str) -> Vec<String> {
+    if is_arm64(arch) {
+        let mut names: Vec<String> = (0..32).map(|i| format!("v{}", i)).collect();
+        names.extend(["fpsr", "fpcr"].map(String::from));
+        names
+    } else {
+        let mut names: Vec<String> = (0..16).map(|i| format!("xmm{}", i)).collect();
+        names.push("mxcsr".to_string());
+        names
+    }
+}
+
+fn is_vector_register(arch: &str, name: &str) -> bool {
+    let index = |prefix: &str| name.strip_prefix(prefix).and_then(|n| n.parse::<u32>().ok());
+    if is_arm64(arch) {
+        ["v", "q", "d", "s", "h", "b"].iter().any(|p| index(p).is_some_and(|i| i < 32))
+            || matches!(name, "fpsr" | "fpcr")
+    } else {
+        ["xmm", "ymm", "zmm", "st", "mm"].iter().any(|p| index(p).is_some())
+            || matches!(name, "mxcsr" | "fpsw" | "fpcw")
+    }
+}
+
+/// Map an alias or sub-register to (canonical register, width in bits).
+/// Writing a 32-bit view zero-extends on both architectures.
+fn canonical_name(arch: &str, name: &str) -> Option<(String, u32)> {
+    let name = name.trim().to_lowercase();
+    if is_arm64(arch) {
+        match name.as_str() {
+            "fp" => return Some(("x29".to_string(), 64)),
+            "lr" => return Some(("x30".to_string(), 64)),
+            "sp" | "pc" => return Some((name, 64)),
+            "cpsr" | "pstate" | "nzcv" => return Some(("cpsr".to_string(), 32)),
+            _ => {}
+        }
+        let (prefix, rest) = name.split_at(1.min(name.len()));
+        let index = rest.parse::<u32>().ok().filter(|i| *i <= 30)?;
+        match prefix {
+            "x" => Some((format!("x{}", index), 64)),
+            "w" => Some((format!("x{}", index), 32)),
+            _ => None,
+        }
+    } else {
+        match name.as_str() {
+            "pc" | "rip" | "eip" => return Some(("rip".to_string(), if name == "eip" { 32 } else { 64 })),
+            "eflags" | "rflags" | "flags" => return Some(("rflags".to_string(), 64)),
+            "fs_base" | "gs_base" => return Some((name, 64)),
+            _ => {}
+        }
+        if X86_64_GPRS.contains(&name.as_str()) {
+            return Some((name, 64));
+        }
+        if X86_SEGMENTS.contains(&name.as_str()) {
+            return Some((name, 16));
+        }
+        // eax..edi, esp, ebp and r8d..r15d
+        if let Some(number) = name.strip_prefix('r').and_then(|n| n.strip_suffix('d')) {
+            let candidate = format!("r{}", number);
+            return X86_64_GPRS.contains(&candidate.as_str()).then_some((candidate, 32));
+        }
+        let candidate = format!("r{}", name.strip_prefix('e')?);
+        X86_64_GPRS.contains(&candidate.as_str()).then_some((candidate, 32))
+    }
+}
+
+/// Name the server expects for a canonical register (Darwin keeps fp/lr/cpsr,
+/// ptrace targets use x29/x30/pstate)
+fn server_name(arch: &str, target_os: &str, canonical: &str) -> String {
+    if is_arm64(arch) {
+        let darwin = matches!(target_os, "macos" | "ios" | "darwin");
+        match canonical {
+            "x29" if darwin => return "fp".to_string(),
+            "x30" if darwin => return "lr".to_string(),
+            "cpsr" if !darwin => return "pstate".to_string(),
+            _ => {}
+        }
+    }
+    canonical.to_string()
+}
+
+/// Look up a single condition flag by name, returning (flags register, bit)
+fn flag_bit(arch: &str, name: &str) -> Option<(&'static str, u32)> {
+    let upper = name.trim().to_uppercase();
+    if is_arm64(arch) {
+        ARM64_FLAGS.iter().find(|(n, _)| *n == upper).map(|(_, bit)| ("cpsr", *bit))
+    } else {
+        X86_FLAGS.iter().find(|(n, _)| *n == upper).map(|(_, bit)| ("rflags", *bit))
+    }
+}
+
+fn decompose_flags(arch: &str, value: u64) -> Vec<FlagBit> {
+    let table: &[(&str, u32)] = if is_arm64(arch) { &ARM64_FLAGS } else { &X86_FLAGS };
+    table.iter()
+        .map(|(name, bit)| FlagBit { name: name.to_string(), bit: *bit, set: value >> bit & 1 == 1 })
+        .collect()
+}
+
+fn aliases(arch: &str, canonical: &str) -> &'static [&'static str] {
+    match (is_arm64(arch), canonical) {
+        (true, "x29") => &["fp"],
+        (true, "x30") => &["lr"],
+        (true, "cpsr") => &["pstate"],
+        (false, "rip") => &["pc"],
+        (false, "rflags") => &["eflags"],
+        _ => &[],
+    }
+}
+
+fn parse_value(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_u64(),
+        serde_json::Value::String(s) => {
+            let s = s.trim();
+            match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => s.parse().ok(),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Rewrite a register JSON object (from exceptions, the UI or get_registers)
+/// into canonical lowercase names with "0x..." values plus the usual aliases
+pub fn normalize_json(arch: &str, registers: &serde_json::Value) -> serde_json::Value {
+    let mut out = serde_json::Map::new();
+    let Some(object) = registers.as_object() else {
+        return serde_json::Value::Object(out);
+    };
+    for (name, value) in object {
+        let Some(value) = parse_value(value) else {
+            continue;
+        };
+        match canonical_name(arch, name) {
+            Some((canonical, bits)) if bits == 64 || canonical == "cpsr" || canonical == name.to_lowercase() => {
+                out.insert(canonical, format!("0x{:x}", value).into());
+            }
+            // 32-bit views (w0, eax) never override the full register
+            Some((canonical, bits)) => {
+                out.entry(canonical).or_insert_with(|| format!("0x{:x}", value & ((1u64 << bits) - 1)).into());
+            }
+            None => {
+                out.entry(name.to_lowercase()).or_insert_with(|| format!("0x{:x}", value).into());
+            }
+        }
+    }
+    let canonical: Vec<(String, serde_json::Value)> = out.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    for (name, value) in canonical {
+        for alias in aliases(arch, &name) {
+            out.entry(alias.to_string()).or_insert(value.clone());
+        }
+    }
+    serde_json::Value::Object(out)
+}
+
+/// String form of `normalize_json`; unparsable input is passed through
+pub fn normalize_json_str(arch: &str, registers_json: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(registers_json) {
+        Ok(value) if value.is_object() && !arch.is_empty() => normalize_json(arch, &value).to_string(),
+        _ => registers_json.to_string(),
+    }
+}
+
+fn target(state: &AppStateType) -> Result<(String, u16, String, String), String> {
+    let (arch, target_os) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let info = state_guard.server_info.as_ref().ok_or("Not connected to a server")?;
+        (info.arch.clone(), info.target_os.clone())
+    };
+    if !is_arm64(&arch) && arch != "x86_64" {
+        return Err(format!("Register access is not supported for {}", arch));
+    }
+    let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+    if config.host.is_empty() {
+        return Err("No server connection configured".to_string());
+    }
+    Ok((config.host.clone(), config.port, arch, target_os))
+}
+
+/// Read every general purpose register of a stopped thread
+#[tauri::command]
+pub async fn get_registers(state: tauri::State<'_, AppStateType>, thread_id: u64) -> Result<RegisterDump, String> {
+    read_dump(state.inner(), thread_id).await
+}
+
+pub async fn read_dump(state: &AppStateType, thread_id: u64) -> Result<RegisterDump, String> {
+    let (host, port, arch, target_os) = target(state)?;
+    let names = canonical_registers(&arch, &target_os);
+
+    let tasks: Vec<_> = names.iter()
+        .map(|name| {
+            let host = host.clone();
+            let remote = server_name(&arch, &target_os, name);
+            tokio::spawn(async move { read_register_from_server(&host, port, thread_id, &remote).await })
+        })
+        .collect();
+
+    let mut registers = Vec::new();
+    let mut unavailable = Vec::new();
+    let mut first_error = None;
+    for (name, task) in names.into_iter().zip(tasks) {
+        match task.await.map_err(|e| e.to_string()).and_then(|r| r) {
+            Ok(value) => {
+                let bits = if name == "cpsr" || (name.len() == 2 && X86_SEGMENTS.contains(&name.as_str())) { 32 } else { 64 };
+                registers.push(RegisterValue { name, value: format!("0x{:x}", value), bits });
+            }
+            Err(e) => {
+                first_error.get_or_insert(e);
+                unavailable.push(name);
+            }
+        }
+    }
+    if registers.is_empty() {
+        return Err(first_error.unwrap_or_else(|| "Failed to read registers".to_string()));
+    }
+    unavailable.extend(vector_registers(&arch));
+
+    let flags_name = if is_arm64(&arch) { "cpsr" } else { "rflags" };
+    let flags = registers.iter()
+        .find(|r| r.name == flags_name)
+        .and_then(|r| parse_value(&serde_json::json!(r.value)))
+        .map(|value| decompose_flags(&arch, value))
+        .unwrap_or_default();
+
+    let raw: serde_json::Map<String, serde_json::Value> = registers.iter()
+        .map(|r| (r.name.clone(), serde_json::json!(r.value)))
+        .collect();
+    let values = normalize_json(&arch, &serde_json::Value::Object(raw));
+
+    Ok(RegisterDump { thread_id, arch, registers, flags, unavailable, values })
+}
+
+/// Write a register, a 32-bit view (w0, eax) or a single condition flag (Z, CF).
+/// `value` accepts hex ("0x...") or decimal.
+#[tauri::command]
+pub async fn set_register(
+    state: tauri::State<'_, AppStateType>,
+    thread_id: u64,
+    name: String,
+    value: String,
+) -> Result<String, String> {
+    let (host, port, arch, target_os) = target(&state)?;
+    let value = parse_value(&serde_json::json!(value))
+        .ok_or_else(|| format!("Invalid register value '{}'", value))?;
+
+    if let Some((flags_register, bit)) = flag_bit(&arch, &name) {
+        if value > 1 {
+            return Err(format!("Flag {} only takes 0 or 1", name.to_uppercase()));
+        }
+        let remote = server_name(&arch, &target_os, flags_register);
+        let current = read_register_from_server(&host, port, thread_id, &remote).await?;
+        let updated = (current & !(1u64 << bit)) | (value << bit);
+        write_register_to_server(&host, port, thread_id, &remote, updated).await?;
+        return Ok(format!("0x{:x}", updated));
+    }
+
+    if is_vector_register(&arch, &name.to_lowercase()) {
+        return Err(format!("{} is an FP/SIMD register; the server only exposes general purpose registers", name));
+    }
+    let (canonical, bits) = canonical_name(&arch, &name)
+        .filter(|(canonical, _)| canonical_registers(&arch, &target_os).contains(canonical))
+        .ok_or_else(|| format!("Unknown register '{}' for {}", name, arch))?;
+    let value = match bits {
+        16 if X86_SEGMENTS.contains(&canonical.as_str()) => value & 0xffff,
+        bits if bits < 64 => {
+            if value >> bits != 0 {
+                return Err(format!("Value 0x{:x} does not fit in {}-bit {}", value, bits, name));
+            }
+            value
+        }
+        _ => value,
+    };
+
+    let remote = server_name(&arch, &target_os, &canonical);
+    write_register_to_server(&host, port, thread_id, &remote, value).await?;
+    Ok(format!("0x{:x}", value))
+}
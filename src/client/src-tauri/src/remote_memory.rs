@@ -0,0 +1,190 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::state::{AppState, AppStateType};
+use crate::{memory_regions, read_chunks_parallel, server_address_with_token, server_connection};
+
+const READ_CHUNK_SIZE: usize = 1024 * 1024;
+const PARALLEL_READS: usize = 8;
+// Caves reported per call, largest first
+const MAX_CAVES: usize = 256;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteAllocation {
+    pub address: u64,
+    pub size: u64,
+    pub protection: String,
+    pub near_address: Option<u64>,
+    pub created_at: u64,
+}
+
+/// A run of padding bytes inside a module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeCave {
+    pub address: u64,
+    pub size: u64,
+    pub module_offset: u64,
+    pub fill_byte: u8,                   // 0x00 or 0xCC
+    pub executable: bool,                // Lies in an executable region (usable for code as-is)
+    pub protection: String,
+}
+
+// Allocations made this session by address; the server needs the size to free
+static ALLOCATIONS: Lazy<Mutex<HashMap<u64, RemoteAllocation>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+async fn post(endpoint: &str, body: serde_json::Value) -> Result<serde_json::Value, String> {
+    let (host, port, auth_token) = server_address_with_token()?;
+    let url = format!("{}/api/{}", server_connection::base_url(&host, port), endpoint);
+    let mut request = server_connection::client()?.post(&url).json(&body);
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let response = server_connection::send(request).await?;
+    let status = response.status();
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    if !status.is_success() || json["success"].as_bool() != Some(true) {
+        return Err(json["error"].as_str().map(str::to_string).unwrap_or_else(|| format!("Server error: {}", status)));
+    }
+    Ok(json)
+}
+
+/// Allocate memory in the target. `protection` is "r", "rw", "rx" or "rwx"
+/// (default); `near_address` asks for a block reachable from it by a rel32
+/// jump where the OS allows it (check the returned address).
+#[tauri::command]
+pub async fn allocate_remote_memory(
+    size: u64,
+    protection: Option<String>,
+    near_address: Option<u64>,
+) -> Result<RemoteAllocation, String> {
+    if size == 0 {
+        return Err("Allocation size must be greater than 0".to_string());
+    }
+    let protection = protection.unwrap_or_else(|| "rwx".to_string()).to_ascii_lowercase();
+    if protection.is_empty() || !protection.chars().all(|c| matches!(c, 'r' | 'w' | 'x')) {
+        return Err(format!("Invalid protection '{}': use a combination of r, w and x", protection));
+    }
+    let response = post("memory/allocate", serde_json::json!({
+        "size": size,
+        "protection": protection,
+        "near_address": near_address,
+    })).await?;
+    let address = response["address"].as_u64().ok_or("Server returned no address")?;
+    let allocation = RemoteAllocation {
+        address,
+        size,
+        protection,
+        near_address,
+        created_at: AppState::current_timestamp(),
+    };
+    ALLOCATIONS.lock().map_err(|e| e.to_string())?.insert(address, allocation.clone());
+    memory_regions::invalidate_cache();
+    Ok(allocation)
+}
+
+/// Free a block returned by allocate_remote_memory
+#[tauri::command]
+pub async fn free_remote_memory(address: u64) -> Result<bool, String> {
+    let size = ALLOCATIONS.lock().map_err(|e| e.to_string())?
+        .get(&address)
+        .map(|a| a.size)
+        .ok_or_else(|| format!("0x{:x} was not allocated by allocate_remote_memory", address))?;
+    post("memory/free", serde_json::json!({ "address": address, "size": size })).await?;
+    ALLOCATIONS.lock().map_err(|e| e.to_string())?.remove(&address);
+    memory_regions::invalidate_cache();
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn list_remote_allocations() -> Result<Vec<RemoteAllocation>, String> {
+    let mut allocations: Vec<RemoteAllocation> = ALLOCATIONS.lock().map_err(|e| e.to_string())?.values().cloned().collect();
+    allocations.sort_by_key(|a| a.address);
+    Ok(allocations)
+}
+
+/// Runs of 0x00 / 0xCC in `data` (read from `start`) of at least `min_size`
+/// bytes; `open` carries a run across chunk boundaries as (start, fill byte)
+fn collect_runs(data: &[u8], start: u64, min_size: u64, open: &mut Option<(u64, u8)>, runs: &mut Vec<(u64, u64, u8)>) {
+    for (i, &byte) in data.iter().enumerate() {
+        let address = start + i as u64;
+        match *open {
+            Some((_, fill)) if fill == byte => continue,
+            Some((run_start, fill)) => {
+                if address - run_start >= min_size {
+                    runs.push((run_start, address - run_start, fill));
+                }
+                *open = None;
+            }
+            None => {}
+        }
+        if byte == 0x00 || byte == 0xCC {
+            *open = Some((address, byte));
+        }
+    }
+}
+
+/// Find padding runs (0x00 / 0xCC) of at least `min_size` bytes inside a
+/// loaded module, for trampolines and hook stubs. Caves in executable
+/// regions come first, then by size.
+#[tauri::command]
+pub async fn find_code_cave(
+    state: tauri::State<'_, AppStateType>,
+    module: String,
+    min_size: u64,
+) -> Result<Vec<CodeCave>, String> {
+    if min_size == 0 {
+        return Err("min_size must be greater than 0".to_string());
+    }
+    let module = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        state_guard.attached_modules.iter()
+            .find(|m| m.modulename == module)
+            .or_else(|| state_guard.attached_modules.iter().find(|m| m.modulename.eq_ignore_ascii_case(&module)))
+            .cloned()
+            .ok_or_else(|| format!("Module '{}' is not loaded", module))?
+    };
+    let (host, port, _) = server_address_with_token()?;
+    let module_end = module.base.saturating_add(module.size);
+    let regions: Vec<memory_regions::MemoryRegion> = memory_regions::get_cached_regions(Some(state.inner()), false).await?
+        .into_iter()
+        .filter(|r| r.readable && r.base < module_end && r.base.saturating_add(r.size) > module.base)
+        .collect();
+
+    let mut caves = Vec::new();
+    for region in &regions {
+        let start = region.base.max(module.base);
+        let end = region.base.saturating_add(region.size).min(module_end);
+        let chunks: Vec<(u64, usize)> = (start..end).step_by(READ_CHUNK_SIZE)
+            .map(|address| (address, READ_CHUNK_SIZE.min((end - address) as usize)))
+            .collect();
+        let mut runs = Vec::new();
+        let mut open = None;
+        for batch in chunks.chunks(PARALLEL_READS) {
+            for (&(address, _), data) in batch.iter().zip(read_chunks_parallel(&host, port, batch, std::time::Duration::from_secs(5)).await) {
+                match data {
+                    Some(data) => collect_runs(&data, address, min_size, &mut open, &mut runs),
+                    None => open = None, // An unreadable chunk ends any run
+                }
+            }
+        }
+        if let Some((run_start, fill)) = open {
+            if end - run_start >= min_size {
+                runs.push((run_start, end - run_start, fill));
+            }
+        }
+        caves.extend(runs.into_iter().map(|(address, size, fill_byte)| CodeCave {
+            address,
+            size,
+            module_offset: address - module.base,
+            fill_byte,
+            executable: region.executable,
+            protection: region.protection.clone(),
+        }));
+    }
+    caves.sort_by_key(|c| (!c.executable, std::cmp::Reverse(c.size)));
+    caves.truncate(MAX_CAVES);
+    Ok(caves)
+}
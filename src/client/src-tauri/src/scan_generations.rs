@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::scan_sampling::STATS_FILE_NAME;
+use crate::{
+    get_latest_scan_generation, get_scan_generation_dir, get_unknown_scan_temp_dir, list_scan_region_files,
+    read_scan_region_file, validate_scan_id, write_scan_region_file, UNKNOWN_SCAN_PROGRESS,
+};
+
+// Region files with fewer hits than this are merged with their neighbours
+const SMALL_REGION_ADDRESSES: u64 = 64 * 1024;
+// Merged files stop growing at this many addresses
+const MERGED_REGION_ADDRESSES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanGenerationStats {
+    pub generation: u32,
+    pub region_files: usize,
+    pub addresses: u64,
+    pub disk_bytes: u64,                 // Region files plus cached statistics
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanStats {
+    pub scan_id: String,
+    pub latest_generation: u32,
+    pub generations: Vec<ScanGenerationStats>, // Oldest first; dropped generations are absent
+    pub disk_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCompactResult {
+    pub removed_generations: Vec<u32>,
+    pub files_before: usize,             // Region files of the latest generation
+    pub files_after: usize,
+    pub duplicates_removed: u64,
+    pub bytes_freed: u64,
+    pub stats: ScanStats,
+}
+
+/// Hit count from a region file header, without decompressing it
+fn region_file_count(path: &Path) -> u64 {
+    let mut header = [0u8; 24];
+    let read = std::fs::File::open(path).and_then(|mut f| f.read_exact(&mut header));
+    match read {
+        Ok(()) => u64::from_le_bytes(header[16..24].try_into().unwrap()),
+        Err(_) => 0, // Header-only file of a region without hits
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+fn generation_stats(scan_id: &str, generation: u32) -> Option<ScanGenerationStats> {
+    let dir = get_scan_generation_dir(scan_id, generation);
+    let files = list_scan_region_files(&dir);
+    let stats_file = dir.join(STATS_FILE_NAME);
+    if files.is_empty() && !stats_file.exists() {
+        return None;
+    }
+    Some(ScanGenerationStats {
+        generation,
+        region_files: files.len(),
+        addresses: files.iter().map(|p| region_file_count(p)).sum(),
+        disk_bytes: files.iter().map(|p| file_size(p)).sum::<u64>() + file_size(&stats_file),
+    })
+}
+
+fn scan_stats(scan_id: &str) -> Result<ScanStats, String> {
+    if !get_unknown_scan_temp_dir(scan_id).exists() {
+        return Err("Scan data not found".to_string());
+    }
+    let latest_generation = get_latest_scan_generation(scan_id);
+    let generations: Vec<ScanGenerationStats> = (0..=latest_generation)
+        .filter_map(|generation| generation_stats(scan_id, generation))
+        .collect();
+    Ok(ScanStats {
+        scan_id: scan_id.to_string(),
+        latest_generation,
+        disk_bytes: generations.iter().map(|g| g.disk_bytes).sum(),
+        generations,
+    })
+}
+
+/// Files of a generation; generation 0 shares the scan directory with the gen_N subdirectories
+fn remove_generation(scan_id: &str, generation: u32) -> u64 {
+    let dir = get_scan_generation_dir(scan_id, generation);
+    let mut files = list_scan_region_files(&dir);
+    files.push(dir.join(STATS_FILE_NAME));
+    let freed = files.iter().map(|p| file_size(p)).sum();
+    if generation == 0 {
+        for path in files {
+            let _ = std::fs::remove_file(path);
+        }
+    } else {
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    freed
+}
+
+/// (start, end) from a "region_<start>_<end>.bin" name
+fn region_file_range(path: &Path) -> Option<(u64, u64)> {
+    let stem = path.file_stem()?.to_str()?.strip_prefix("region_")?;
+    let (start, end) = stem.split_once('_')?;
+    Some((u64::from_str_radix(start, 16).ok()?, u64::from_str_radix(end, 16).ok()?))
+}
+
+/// Merge runs of small neighbouring region files of one generation into
+/// larger ones, dropping files without hits and duplicate addresses.
+/// Returns (files after, duplicates removed).
+fn merge_small_regions(dir: &Path) -> Result<(usize, u64), String> {
+    // Runs of adjacent files (in address order) to merge into one
+    let mut groups: Vec<Vec<(PathBuf, u64)>> = Vec::new();
+    let mut group_count = 0u64;
+    for path in list_scan_region_files(dir) {
+        let count = region_file_count(&path);
+        let small = count < SMALL_REGION_ADDRESSES;
+        match groups.last_mut() {
+            Some(group) if small && group_count + count <= MERGED_REGION_ADDRESSES
+                && group.last().is_some_and(|(_, c)| *c < SMALL_REGION_ADDRESSES) => {
+                group.push((path, count));
+                group_count += count;
+            }
+            _ => {
+                groups.push(vec![(path, count)]);
+                group_count = count;
+            }
+        }
+    }
+
+    let mut files_after = 0;
+    let mut duplicates = 0u64;
+    for group in groups {
+        if group.iter().all(|(_, count)| *count == 0) {
+            for (path, _) in &group {
+                let _ = std::fs::remove_file(path);
+            }
+            continue;
+        }
+        files_after += 1;
+        if group.len() == 1 {
+            continue;
+        }
+
+        let mut layout: Option<(usize, usize, u64)> = None;
+        let mut addresses: Vec<u64> = Vec::new();
+        let mut values: Vec<u8> = Vec::new();
+        for (path, _) in &group {
+            let region = read_scan_region_file(path).ok_or_else(|| format!("Failed to read {}", path.display()))?;
+            let (data_size, _, _) = *layout.get_or_insert((region.data_size, region.alignment, region.start_addr));
+            if region.data_size != data_size {
+                return Err(format!("{} has a different value size", path.display()));
+            }
+            for (i, &address) in region.addresses.iter().enumerate() {
+                // Files are in address order, so a duplicate is always the previous entry
+                if addresses.last() == Some(&address) {
+                    duplicates += 1;
+                    continue;
+                }
+                addresses.push(address);
+                values.extend_from_slice(region.values.get(i * data_size..(i + 1) * data_size).unwrap_or(&vec![0; data_size]));
+            }
+        }
+        let Some((data_size, alignment, start_addr)) = layout else { continue };
+        let first = region_file_range(&group[0].0).map(|(s, _)| s).unwrap_or(start_addr);
+        let last = group.last().and_then(|(p, _)| region_file_range(p)).map(|(_, e)| e)
+            .unwrap_or_else(|| addresses.last().map_or(first, |a| a + data_size as u64));
+
+        // Write under a temporary name so an interrupted merge leaves the originals intact
+        let merged = dir.join(format!("region_{:016x}_{:016x}.bin", first, last));
+        let temp = dir.join(format!("region_{:016x}_{:016x}.merging", first, last));
+        write_scan_region_file(&temp, data_size, alignment, start_addr, &addresses, &values)
+            .map_err(|e| format!("Failed to write merged region file: {}", e))?;
+        for (path, _) in &group {
+            let _ = std::fs::remove_file(path);
+        }
+        std::fs::rename(&temp, &merged).map_err(|e| format!("Failed to replace region files: {}", e))?;
+    }
+    Ok((files_after, duplicates))
+}
+
+/// Per-generation hit counts and disk usage of a scan
+#[tauri::command]
+pub fn get_scan_stats(scan_id: String) -> Result<ScanStats, String> {
+    validate_scan_id(&scan_id)?;
+    scan_stats(&scan_id)
+}
+
+/// Drop every generation but the latest and merge its small region files.
+/// The latest generation keeps its number.
+#[tauri::command]
+pub async fn compact_scan(scan_id: String) -> Result<ScanCompactResult, String> {
+    validate_scan_id(&scan_id)?;
+    let scanning = UNKNOWN_SCAN_PROGRESS.read().map_err(|e| e.to_string())?
+        .get(&scan_id)
+        .is_some_and(|p| p.is_scanning);
+    if scanning {
+        return Err("Scan is still running".to_string());
+    }
+    tokio::task::spawn_blocking(move || {
+        let before = scan_stats(&scan_id)?;
+        let latest = before.latest_generation;
+        let removed_generations: Vec<u32> = before.generations.iter()
+            .map(|g| g.generation)
+            .filter(|&g| g != latest)
+            .collect();
+        for &generation in &removed_generations {
+            remove_generation(&scan_id, generation);
+        }
+
+        let latest_dir = get_scan_generation_dir(&scan_id, latest);
+        let files_before = list_scan_region_files(&latest_dir).len();
+        let (files_after, duplicates_removed) = merge_small_regions(&latest_dir)?;
+        if duplicates_removed > 0 {
+            // Cached statistics counted the duplicates
+            let _ = std::fs::remove_file(latest_dir.join(STATS_FILE_NAME));
+        }
+
+        let stats = scan_stats(&scan_id)?;
+        Ok(ScanCompactResult {
+            removed_generations,
+            files_before,
+            files_after,
+            duplicates_removed,
+            bytes_freed: before.disk_bytes.saturating_sub(stats.disk_bytes),
+            stats,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
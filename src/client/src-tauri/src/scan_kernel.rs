@@ -0,0 +1,305 @@
+use rayon::prelude::*;
+
+use crate::{compare_values, value_codec, FuzzyFloatOptions};
+
+// Values per rayon task; small enough to balance, large enough to amortize the split
+const BLOCK_VALUES: usize = 64 * 1024;
+
+/// One next-scan filter, resolved once per request
+#[derive(Debug, Clone, Copy)]
+pub struct FilterSpec<'a> {
+    pub data_type: &'a str,
+    pub filter_method: &'a str,
+    pub pattern: &'a [u8],
+    pub pattern_max: Option<&'a [u8]>,
+    pub fuzzy: Option<&'a FuzzyFloatOptions>,
+}
+
+/// Owned counterpart of FilterSpec, for moving a filter onto a blocking thread
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub data_type: String,
+    pub filter_method: String,
+    pub pattern: Vec<u8>,
+    pub pattern_max: Option<Vec<u8>>,
+    pub fuzzy: Option<FuzzyFloatOptions>,
+}
+
+impl Filter {
+    pub fn spec(&self) -> FilterSpec<'_> {
+        FilterSpec {
+            data_type: &self.data_type,
+            filter_method: &self.filter_method,
+            pattern: &self.pattern,
+            pattern_max: self.pattern_max.as_deref(),
+            fuzzy: self.fuzzy.as_ref(),
+        }
+    }
+}
+
+/// Fixed-size little-endian value the typed kernels are instantiated for
+trait Scalar: Copy + PartialOrd + Send + Sync + 'static {
+    const SIZE: usize;
+    fn read(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            const SIZE: usize = std::mem::size_of::<$t>();
+            #[inline(always)]
+            fn read(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes[..Self::SIZE].try_into().unwrap())
+            }
+        }
+    )*};
+}
+
+impl_scalar!(i8, u8, i16, u16, i32, u32, i64, u64, f32, f64);
+
+#[derive(Clone, Copy)]
+enum Predicate<T> {
+    Range(T, T),
+    GreaterOrEqual(T),
+    LessThan(T),
+    Increased,
+    Decreased,
+}
+
+impl<T: Scalar> Predicate<T> {
+    fn from_spec(spec: &FilterSpec) -> Option<Option<Self>> {
+        let value = |bytes: &[u8]| (bytes.len() >= T::SIZE).then(|| T::read(bytes));
+        // Outer None: not a typed method; inner None: can never match
+        Some(match spec.filter_method {
+            "range" => spec.pattern_max.and_then(|max| Some(Predicate::Range(value(spec.pattern)?, value(max)?))),
+            "greater_or_equal" => value(spec.pattern).map(Predicate::GreaterOrEqual),
+            "less_than" => value(spec.pattern).map(Predicate::LessThan),
+            "increased" => Some(Predicate::Increased),
+            "decreased" => Some(Predicate::Decreased),
+            _ => return None,
+        })
+    }
+}
+
+fn needs_old(filter_method: &str) -> bool {
+    matches!(filter_method, "changed" | "unchanged" | "increased" | "decreased")
+}
+
+/// Split `count` values into blocks processed in parallel. `block(start, out)`
+/// writes the kept indices of its range to the front of `out` (one slot per
+/// value) and returns how many it kept; the blocks are then compacted in order.
+fn parallel_blocks(count: usize, block: impl Fn(usize, &mut [usize]) -> usize + Send + Sync) -> Vec<usize> {
+    let mut hits = vec![0usize; count];
+    let kept: Vec<usize> = hits.par_chunks_mut(BLOCK_VALUES)
+        .enumerate()
+        .map(|(b, out)| block(b * BLOCK_VALUES, out))
+        .collect();
+    let mut len = 0;
+    for (b, n) in kept.into_iter().enumerate() {
+        hits.copy_within(b * BLOCK_VALUES..b * BLOCK_VALUES + n, len);
+        len += n;
+    }
+    hits.truncate(len);
+    hits
+}
+
+/// Write `start + k` for each true `kept` item. Every index is written and
+/// the length only advances on a hit, so there is no branch to mispredict
+/// when about half the values pass.
+fn collect_hits(start: usize, out: &mut [usize], kept: impl Iterator<Item = bool>) -> usize {
+    let mut len = 0;
+    for (k, keep) in kept.enumerate() {
+        out[len] = start + k;
+        len += keep as usize;
+    }
+    len
+}
+
+fn parallel_indices(count: usize, keep: impl Fn(usize) -> bool + Send + Sync) -> Vec<usize> {
+    parallel_blocks(count, |start, out| {
+        let end = start + out.len();
+        collect_hits(start, out, (start..end).map(&keep))
+    })
+}
+
+fn values<T: Scalar>(bytes: &[u8], start: usize, len: usize) -> impl Iterator<Item = T> + '_ {
+    bytes[start * T::SIZE..(start + len) * T::SIZE].chunks_exact(T::SIZE).map(T::read)
+}
+
+/// Typed kernel; each predicate gets its own instantiation of the inner loop.
+/// PartialOrd is false for NaN on either side, as in compare_values.
+fn typed<T: Scalar>(predicate: Option<Predicate<T>>, new_values: &[u8], old_values: &[u8], count: usize) -> Vec<usize> {
+    match predicate {
+        None => Vec::new(),
+        // Non-short-circuit & keeps the loop free of branches
+        Some(Predicate::Range(min, max)) => by_new(new_values, count, |n: T| (n >= min) & (n <= max)),
+        Some(Predicate::GreaterOrEqual(v)) => by_new(new_values, count, |n: T| n >= v),
+        Some(Predicate::LessThan(v)) => by_new(new_values, count, |n: T| n < v),
+        Some(Predicate::Increased) => by_old(new_values, old_values, count, |n: T, o: T| n > o),
+        Some(Predicate::Decreased) => by_old(new_values, old_values, count, |n: T, o: T| n < o),
+    }
+}
+
+fn by_new<T: Scalar>(new_values: &[u8], count: usize, test: impl Fn(T) -> bool + Send + Sync) -> Vec<usize> {
+    parallel_blocks(count, |start, out| {
+        let len = out.len();
+        collect_hits(start, out, values(new_values, start, len).map(&test))
+    })
+}
+
+fn by_old<T: Scalar>(new_values: &[u8], old_values: &[u8], count: usize, test: impl Fn(T, T) -> bool + Send + Sync) -> Vec<usize> {
+    parallel_blocks(count, |start, out| {
+        let len = out.len();
+        let pairs = values(new_values, start, len).zip(values(old_values, start, len));
+        collect_hits(start, out, pairs.map(|(n, o)| test(n, o)))
+    })
+}
+
+/// Aligned occurrences of `pattern` in `new_values`, found with memchr's
+/// SIMD substring search; a misaligned hit skips to the next value boundary
+fn exact_bytes(pattern: &[u8], new_values: &[u8], data_size: usize, count: usize) -> Vec<usize> {
+    if pattern.len() != data_size {
+        return Vec::new();
+    }
+    let finder = memchr::memmem::Finder::new(pattern);
+    parallel_blocks(count, |first, out| {
+        let haystack = &new_values[first * data_size..(first + out.len()) * data_size];
+        let mut len = 0;
+        let mut pos = 0;
+        while let Some(found) = finder.find(&haystack[pos..]) {
+            let at = pos + found;
+            if at % data_size == 0 {
+                out[len] = first + at / data_size;
+                len += 1;
+                pos = at + data_size;
+            } else {
+                pos = (at / data_size + 1) * data_size;
+            }
+            if pos >= haystack.len() {
+                break;
+            }
+        }
+        len
+    })
+}
+
+/// Indices of the `data_size` windows in `new_values` that `is_match` accepts;
+/// the kernel for string and regex matchers, whose matches vary in length
+pub fn matching_indices(new_values: &[u8], data_size: usize, is_match: impl Fn(&[u8]) -> bool + Send + Sync) -> Vec<usize> {
+    let data_size = data_size.max(1);
+    parallel_indices(new_values.len() / data_size, |i| is_match(&new_values[i * data_size..(i + 1) * data_size]))
+}
+
+/// "changed" / "unchanged"; power-of-two sizes compare as one integer
+fn same_bytes(new_values: &[u8], old_values: &[u8], data_size: usize, count: usize, want_same: bool) -> Vec<usize> {
+    match data_size {
+        1 => by_old(new_values, old_values, count, |n: u8, o: u8| (n == o) == want_same),
+        2 => by_old(new_values, old_values, count, |n: u16, o: u16| (n == o) == want_same),
+        4 => by_old(new_values, old_values, count, |n: u32, o: u32| (n == o) == want_same),
+        8 => by_old(new_values, old_values, count, |n: u64, o: u64| (n == o) == want_same),
+        _ => parallel_indices(count, |i| {
+            let range = i * data_size..(i + 1) * data_size;
+            (new_values[range.clone()] == old_values[range]) == want_same
+        }),
+    }
+}
+
+/// Per-value compare_values loop, the path used before the typed kernels.
+/// Handles every data type and method; kept as the reference for tests and benchmarks.
+pub fn filter_indices_per_value(spec: &FilterSpec, new_values: &[u8], old_values: &[u8], data_size: usize) -> Vec<usize> {
+    let count = new_values.len() / data_size.max(1);
+    (0..count)
+        .filter(|&i| {
+            let new_val = &new_values[i * data_size..(i + 1) * data_size];
+            let old_val = old_values.get(i * data_size..(i + 1) * data_size).unwrap_or(&[]);
+            compare_values(new_val, old_val, spec.pattern, spec.pattern_max, spec.data_type, spec.filter_method, spec.fuzzy)
+        })
+        .collect()
+}
+
+/// Indices of the values in `new_values` (packed, `data_size` bytes each)
+/// that pass the filter, with `old_values` packed the same way. The data
+/// type and method are dispatched once to a monomorphized kernel that runs
+/// on rayon; types without a kernel fall back to compare_values in parallel.
+pub fn filter_indices(spec: &FilterSpec, new_values: &[u8], old_values: &[u8], data_size: usize) -> Vec<usize> {
+    let data_size = data_size.max(1);
+    let count = new_values.len() / data_size;
+    let old_missing = needs_old(spec.filter_method) && old_values.len() < count * data_size;
+    // String types match text shorter than the stored window, so a fixed-size
+    // exact compare does not apply to them
+    let is_builtin = value_codec::ValueType::parse(spec.data_type).is_none()
+        && !matches!(spec.data_type, "string" | "utf16" | "regex");
+    if count == 0 {
+        return Vec::new();
+    }
+
+    if is_builtin && !old_missing {
+        match spec.filter_method {
+            "exact" => return exact_bytes(spec.pattern, new_values, data_size, count),
+            "changed" => return same_bytes(new_values, old_values, data_size, count, false),
+            "unchanged" => return same_bytes(new_values, old_values, data_size, count, true),
+            _ => {}
+        }
+        macro_rules! dispatch {
+            ($($name:literal => $t:ty),*) => {
+                // Strides wider than the type (e.g. group spans) take the fallback
+                match spec.data_type {
+                    $($name if data_size == <$t>::SIZE => {
+                        if let Some(predicate) = Predicate::<$t>::from_spec(spec) {
+                            return typed::<$t>(predicate, new_values, old_values, count);
+                        }
+                    })*
+                    _ => {}
+                }
+            };
+        }
+        dispatch!(
+            "int8" => i8, "uint8" => u8, "int16" => i16, "uint16" => u16, "int32" => i32,
+            "uint32" => u32, "int64" => i64, "uint64" => u64, "float" => f32, "double" => f64
+        );
+    }
+
+    parallel_indices(count, |i| {
+        let new_val = &new_values[i * data_size..(i + 1) * data_size];
+        let old_val = old_values.get(i * data_size..(i + 1) * data_size).unwrap_or(&[]);
+        compare_values(new_val, old_val, spec.pattern, spec.pattern_max, spec.data_type, spec.filter_method, spec.fuzzy)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kernels_match_per_value_filter() {
+        // Few distinct byte values so exact / unchanged hits (and misaligned
+        // exact hits) actually occur
+        let bytes = |seed: u64| -> Vec<u8> {
+            let mut state = seed;
+            (0..4096 * 8).map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                [0u8, 1, 0x80, 0xff][(state >> 62) as usize]
+            }).collect()
+        };
+        let (new_values, old_values) = (bytes(1), bytes(2));
+        for (data_type, size) in [("int8", 1), ("uint16", 2), ("int32", 4), ("uint64", 8), ("float", 4), ("double", 8)] {
+            let pattern = &new_values[size * 3..size * 4];
+            let pattern_max = &new_values[size * 7..size * 8];
+            for method in ["exact", "range", "greater_or_equal", "less_than", "changed", "unchanged", "increased", "decreased"] {
+                let spec = FilterSpec { data_type, filter_method: method, pattern, pattern_max: Some(pattern_max), fuzzy: None };
+                assert_eq!(
+                    filter_indices(&spec, &new_values, &old_values, size),
+                    filter_indices_per_value(&spec, &new_values, &old_values, size),
+                    "{} {}", data_type, method,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matching_indices_tests_whole_windows() {
+        let new_values = b"ab\0\0zzzzab\0\0xab\0";
+        let kept = matching_indices(new_values, 4, |value| value.starts_with(b"ab"));
+        assert_eq!(kept, vec![0, 2]);
+    }
+}
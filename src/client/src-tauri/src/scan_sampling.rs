@@ -0,0 +1,370 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    get_latest_scan_generation, get_scan_generation_dir, list_scan_region_files, read_scan_region_file, validate_scan_id,
+    value_codec, MemoryFilterResult,
+};
+
+const DEFAULT_SAMPLE_SIZE: usize = 1000;
+const DEFAULT_MAX_REGION_FILES: usize = 64;
+const DEFAULT_HISTOGRAM_BUCKETS: usize = 32;
+const MAX_HISTOGRAM_BUCKETS: usize = 4096;
+const TOP_VALUE_COUNT: usize = 16;
+// Address residues are reported modulo this, to check the alignment choice
+const ALIGNMENT_MODULUS: u64 = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSampleRequest {
+    pub scan_id: String,
+    #[serde(default)]
+    pub generation: Option<u32>,          // Default: latest generation
+    pub data_type: String,                // How values are interpreted for the histogram
+    #[serde(default)]
+    pub sample_size: Option<usize>,
+    #[serde(default)]
+    pub max_region_files: Option<usize>,  // Upper bound on region files decompressed
+    #[serde(default)]
+    pub histogram_buckets: Option<usize>, // Clamped to 1..=4096
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanValueCount {
+    pub value: Vec<u8>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSampleResult {
+    pub success: bool,
+    pub scan_id: String,
+    pub generation: u32,
+    pub total_count: u64,                 // Exact, from the region file headers
+    pub region_files: usize,
+    pub region_files_sampled: usize,
+    pub stride: u64,                      // Approximate distance between samples
+    pub samples: Vec<MemoryFilterResult>,
+    pub histogram: Vec<HistogramBucket>,  // Over finite numeric values of the samples
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub zero_count: u64,
+    pub non_finite_count: u64,            // NaN / infinity (float types only)
+    pub top_values: Vec<ScanValueCount>,
+    pub address_residues: Vec<u64>,       // Sample counts by address % 8
+    pub error: Option<String>,
+}
+
+/// Hit count of a region file, read from its header without decompressing
+fn read_region_count(path: &Path) -> Option<u64> {
+    let mut header = [0u8; 24];
+    let mut file = std::fs::File::open(path).ok()?;
+    let read = file.read(&mut header).ok()?;
+    if read < 24 {
+        // Header-only file: region without hits
+        return Some(0);
+    }
+    Some(u64::from_le_bytes(header[16..24].try_into().ok()?))
+}
+
+/// Numeric interpretation of a scanned value for statistics
+fn value_as_f64(bytes: &[u8], data_type: &str) -> Option<f64> {
+    Some(match data_type {
+        "int8" => *bytes.first()? as i8 as f64,
+        "uint8" => *bytes.first()? as f64,
+        "int16" => i16::from_le_bytes(bytes.get(..2)?.try_into().ok()?) as f64,
+        "uint16" => u16::from_le_bytes(bytes.get(..2)?.try_into().ok()?) as f64,
+        "int32" => i32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as f64,
+        "uint32" => u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as f64,
+        "int64" => i64::from_le_bytes(bytes.get(..8)?.try_into().ok()?) as f64,
+        "uint64" => u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?) as f64,
+        "float" => f32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as f64,
+        "double" => f64::from_le_bytes(bytes.get(..8)?.try_into().ok()?),
+        _ => match value_codec::decode_number(data_type, bytes)? {
+            value_codec::Number::Int(v) => v as f64,
+            value_codec::Number::Float(v) => v,
+        },
+    })
+}
+
+/// Return an evenly spread preview of a scan's results plus value statistics.
+/// Only the region file headers are read in full; at most `max_region_files`
+/// files are decompressed to pick every Nth result.
+#[tauri::command]
+pub async fn sample_scan_results(request: ScanSampleRequest) -> Result<ScanSampleResult, String> {
+    validate_scan_id(&request.scan_id)?;
+    let generation = request.generation.unwrap_or_else(|| get_latest_scan_generation(&request.scan_id));
+    let dir = get_scan_generation_dir(&request.scan_id, generation);
+    if !dir.exists() {
+        return Err("Scan data not found".to_string());
+    }
+
+    let sample_size = request.sample_size.unwrap_or(DEFAULT_SAMPLE_SIZE).max(1);
+    let max_files = request.max_region_files.unwrap_or(DEFAULT_MAX_REGION_FILES).max(1);
+    let bucket_count = request.histogram_buckets.unwrap_or(DEFAULT_HISTOGRAM_BUCKETS).clamp(1, MAX_HISTOGRAM_BUCKETS);
+
+    let regions: Vec<(PathBuf, u64)> = list_scan_region_files(&dir)
+        .into_iter()
+        .filter_map(|p| read_region_count(&p).map(|c| (p, c)))
+        .collect();
+    let total_count: u64 = regions.iter().map(|(_, c)| c).sum();
+
+    let mut result = ScanSampleResult {
+        success: true,
+        scan_id: request.scan_id.clone(),
+        generation,
+        total_count,
+        region_files: regions.len(),
+        region_files_sampled: 0,
+        stride: 0,
+        samples: Vec::new(),
+        histogram: Vec::new(),
+        min: None,
+        max: None,
+        mean: None,
+        zero_count: 0,
+        non_finite_count: 0,
+        top_values: Vec::new(),
+        address_residues: vec![0; ALIGNMENT_MODULUS as usize],
+        error: None,
+    };
+    if total_count == 0 {
+        return Ok(result);
+    }
+
+    // Global sample positions (middle of each stride), grouped by region file
+    let points = (sample_size as u64).min(total_count);
+    result.stride = total_count / points;
+    let mut per_file: Vec<(usize, Vec<u64>)> = Vec::new();
+    let mut file_idx = 0;
+    let mut file_start = 0u64;
+    for k in 0..points {
+        let global = ((2 * k + 1) * total_count) / (2 * points);
+        while global >= file_start + regions[file_idx].1 {
+            file_start += regions[file_idx].1;
+            file_idx += 1;
+        }
+        match per_file.last_mut() {
+            Some((idx, local)) if *idx == file_idx => local.push(global - file_start),
+            _ => per_file.push((file_idx, vec![global - file_start])),
+        }
+    }
+
+    // Too many files: keep an evenly spaced subset so the cost stays bounded
+    if per_file.len() > max_files {
+        let step = per_file.len() as f64 / max_files as f64;
+        per_file = (0..max_files)
+            .map(|i| per_file[(i as f64 * step) as usize].clone())
+            .collect();
+    }
+
+    for (idx, local_indices) in &per_file {
+        let Some(region) = read_scan_region_file(&regions[*idx].0) else {
+            continue;
+        };
+        result.region_files_sampled += 1;
+        let data_size = region.data_size.max(1);
+        for &i in local_indices {
+            let i = i as usize;
+            let (Some(&address), Some(value)) = (region.addresses.get(i), region.values.get(i * data_size..(i + 1) * data_size)) else {
+                continue;
+            };
+            result.samples.push(MemoryFilterResult { address, value: value.to_vec(), pointer: None, data_item: None, object_hint: None });
+        }
+    }
+
+    // Statistics over the samples
+    let mut value_counts: HashMap<Vec<u8>, u64> = HashMap::new();
+    let mut numbers: Vec<f64> = Vec::new();
+    for sample in &result.samples {
+        result.address_residues[(sample.address % ALIGNMENT_MODULUS) as usize] += 1;
+        *value_counts.entry(sample.value.clone()).or_insert(0) += 1;
+        if sample.value.iter().all(|&b| b == 0) {
+            result.zero_count += 1;
+        }
+        match value_as_f64(&sample.value, &request.data_type) {
+            Some(v) if v.is_finite() => numbers.push(v),
+            Some(_) => result.non_finite_count += 1,
+            None => {}
+        }
+    }
+
+    let mut top: Vec<ScanValueCount> = value_counts.into_iter()
+        .map(|(value, count)| ScanValueCount { value, count })
+        .collect();
+    top.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    top.truncate(TOP_VALUE_COUNT);
+    result.top_values = top;
+
+    if !numbers.is_empty() {
+        let min = numbers.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = numbers.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        result.min = Some(min);
+        result.max = Some(max);
+        result.mean = Some(numbers.iter().sum::<f64>() / numbers.len() as f64);
+
+        let width = (max - min) / bucket_count as f64;
+        result.histogram = (0..bucket_count)
+            .map(|i| HistogramBucket {
+                lower: min + width * i as f64,
+                upper: if i + 1 == bucket_count { max } else { min + width * (i + 1) as f64 },
+                count: 0,
+            })
+            .collect();
+        for v in numbers {
+            let bucket = if width > 0.0 { (((v - min) / width) as usize).min(bucket_count - 1) } else { 0 };
+            result.histogram[bucket].count += 1;
+        }
+    }
+
+    Ok(result)
+}
+
+// Distinct values beyond this are no longer tracked; distinct_values becomes a lower bound
+const MAX_TRACKED_DISTINCT: usize = 1_000_000;
+pub(crate) const STATS_FILE_NAME: &str = "stats.json";
+
+/// Summary of one scan generation, stored next to its region files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanStatistics {
+    pub scan_id: String,
+    pub generation: u32,
+    pub data_type: String,
+    pub count: u64,
+    pub distinct_values: u64,
+    pub distinct_exact: bool,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub zero_count: u64,
+    pub top_values: Vec<ScanValueCount>,
+    pub computed_at: u64,
+}
+
+/// Incrementally collects ScanStatistics while region files are written
+pub struct ScanStatsAccumulator {
+    data_type: String,
+    data_size: usize,
+    count: u64,
+    zero_count: u64,
+    min: Option<f64>,
+    max: Option<f64>,
+    value_counts: HashMap<Vec<u8>, u64>,
+    distinct_overflow: bool,
+}
+
+impl ScanStatsAccumulator {
+    pub fn new(data_type: &str, data_size: usize) -> Self {
+        Self {
+            data_type: data_type.to_string(),
+            data_size: data_size.max(1),
+            count: 0,
+            zero_count: 0,
+            min: None,
+            max: None,
+            value_counts: HashMap::new(),
+            distinct_overflow: false,
+        }
+    }
+
+    /// Add the packed values of one region
+    pub fn add_values(&mut self, values: &[u8]) {
+        for value in values.chunks_exact(self.data_size) {
+            self.count += 1;
+            if value.iter().all(|&b| b == 0) {
+                self.zero_count += 1;
+            }
+            if let Some(v) = value_as_f64(value, &self.data_type).filter(|v| v.is_finite()) {
+                self.min = Some(self.min.map_or(v, |m| m.min(v)));
+                self.max = Some(self.max.map_or(v, |m| m.max(v)));
+            }
+            if let Some(count) = self.value_counts.get_mut(value) {
+                *count += 1;
+            } else if self.value_counts.len() < MAX_TRACKED_DISTINCT {
+                self.value_counts.insert(value.to_vec(), 1);
+            } else {
+                self.distinct_overflow = true;
+            }
+        }
+    }
+
+    pub fn finish(self, scan_id: &str, generation: u32) -> ScanStatistics {
+        let distinct_values = self.value_counts.len() as u64;
+        let mut top: Vec<ScanValueCount> = self.value_counts.into_iter()
+            .map(|(value, count)| ScanValueCount { value, count })
+            .collect();
+        top.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        top.truncate(TOP_VALUE_COUNT);
+        ScanStatistics {
+            scan_id: scan_id.to_string(),
+            generation,
+            data_type: self.data_type,
+            count: self.count,
+            distinct_values,
+            distinct_exact: !self.distinct_overflow,
+            min: self.min,
+            max: self.max,
+            zero_count: self.zero_count,
+            top_values: top,
+            computed_at: crate::state::AppState::current_timestamp(),
+        }
+    }
+}
+
+/// Persist statistics in the generation directory
+pub fn save_scan_statistics(stats: &ScanStatistics) -> Result<(), String> {
+    let path = get_scan_generation_dir(&stats.scan_id, stats.generation).join(STATS_FILE_NAME);
+    let json = serde_json::to_vec(stats).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Statistics of a scan generation (default: latest). Stored statistics are
+/// returned as-is; otherwise they are computed from the region files and stored.
+#[tauri::command]
+pub async fn get_scan_statistics(
+    scan_id: String,
+    generation: Option<u32>,
+    data_type: Option<String>,
+) -> Result<ScanStatistics, String> {
+    validate_scan_id(&scan_id)?;
+    let generation = generation.unwrap_or_else(|| get_latest_scan_generation(&scan_id));
+    let dir = get_scan_generation_dir(&scan_id, generation);
+    if !dir.exists() {
+        return Err("Scan data not found".to_string());
+    }
+
+    let stats_path = dir.join(STATS_FILE_NAME);
+    if let Some(stats) = std::fs::read(&stats_path).ok().and_then(|d| serde_json::from_slice::<ScanStatistics>(&d).ok()) {
+        if data_type.as_ref().is_none_or(|t| *t == stats.data_type) {
+            return Ok(stats);
+        }
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let data_type = data_type.unwrap_or_default();
+        let mut accumulator: Option<ScanStatsAccumulator> = None;
+        for path in list_scan_region_files(&dir) {
+            let Some(region) = read_scan_region_file(&path) else {
+                continue;
+            };
+            accumulator
+                .get_or_insert_with(|| ScanStatsAccumulator::new(&data_type, region.data_size))
+                .add_values(&region.values);
+        }
+        let stats = accumulator
+            .unwrap_or_else(|| ScanStatsAccumulator::new(&data_type, 1))
+            .finish(&scan_id, generation);
+        save_scan_statistics(&stats)?;
+        Ok(stats)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
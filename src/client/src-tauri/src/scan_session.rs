@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+use crate::state::AppStateType;
+use crate::{
+    get_latest_scan_generation, get_scan_generation_dir, get_unknown_scan_temp_dir, list_scan_region_files,
+    read_scan_region_file, validate_scan_id,
+};
+
+// .ddscan layout: magic, u32 metadata length + metadata JSON, u32 entry count,
+// then per entry: u32 name length + UTF-8 relative path, u64 data length + data.
+// Region files are already lz4-compressed, so entries are stored as-is.
+const DDSCAN_MAGIC: &[u8; 8] = b"DDSCAN\0\x01";
+
+/// Metadata stored in a .ddscan archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSessionMetadata {
+    pub scan_id: String,
+    pub data_type: Option<String>,
+    pub data_size: Option<usize>,
+    pub alignment: Option<usize>,
+    pub generation: u32,
+    pub total_addresses: u64,
+    pub process_name: Option<String>,
+    pub process_pid: Option<u32>,
+    pub target_os: Option<String>,
+    pub exported_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSessionImportResult {
+    pub success: bool,
+    pub scan_id: String,
+    pub metadata: ScanSessionMetadata,
+    pub temp_dir: String,
+}
+
+/// All .bin files of a scan as (relative name, absolute path), generations included
+fn collect_scan_files(scan_dir: &Path, latest_generation: u32) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    for generation in 0..=latest_generation {
+        let (prefix, dir) = if generation == 0 {
+            (String::new(), scan_dir.to_path_buf())
+        } else {
+            (format!("gen_{}/", generation), scan_dir.join(format!("gen_{}", generation)))
+        };
+        for path in list_scan_region_files(&dir) {
+            if let Some(name) = path.file_name() {
+                files.push((format!("{}{}", prefix, name.to_string_lossy()), path));
+            }
+        }
+    }
+    files
+}
+
+/// Export a scan (all generations) to a single portable .ddscan file
+#[tauri::command]
+pub async fn export_scan_session(
+    state: tauri::State<'_, AppStateType>,
+    scan_id: String,
+    path: String,
+    data_type: Option<String>,
+) -> Result<ScanSessionMetadata, String> {
+    validate_scan_id(&scan_id)?;
+    let scan_dir = get_unknown_scan_temp_dir(&scan_id);
+    if !scan_dir.exists() {
+        return Err("Scan data not found".to_string());
+    }
+
+    let (process_name, process_pid, target_os) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        (
+            state_guard.attached_process.as_ref().map(|p| p.processname.clone()),
+            state_guard.attached_process.as_ref().map(|p| p.pid),
+            state_guard.server_info.as_ref().map(|s| s.target_os.clone()),
+        )
+    };
+
+    let generation = get_latest_scan_generation(&scan_id);
+    let files = collect_scan_files(&scan_dir, generation);
+
+    // Layout and hit count come from the latest generation's region headers
+    let mut data_size = None;
+    let mut alignment = None;
+    let mut total_addresses = 0u64;
+    for path in list_scan_region_files(&get_scan_generation_dir(&scan_id, generation)) {
+        if let Some(region) = read_scan_region_file(&path) {
+            data_size.get_or_insert(region.data_size);
+            alignment.get_or_insert(region.alignment);
+            total_addresses += region.addresses.len() as u64;
+        }
+    }
+
+    let metadata = ScanSessionMetadata {
+        scan_id: scan_id.clone(),
+        data_type,
+        data_size,
+        alignment,
+        generation,
+        total_addresses,
+        process_name,
+        process_pid,
+        target_os,
+        exported_at: crate::state::AppState::current_timestamp(),
+    };
+    let metadata_json = serde_json::to_vec(&metadata).map_err(|e| e.to_string())?;
+
+    let out = std::fs::File::create(&path)
+        .map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut writer = std::io::BufWriter::new(out);
+    let write_result: std::io::Result<()> = (|| {
+        writer.write_all(DDSCAN_MAGIC)?;
+        writer.write_all(&(metadata_json.len() as u32).to_le_bytes())?;
+        writer.write_all(&metadata_json)?;
+        writer.write_all(&(files.len() as u32).to_le_bytes())?;
+        for (name, file_path) in &files {
+            let data = std::fs::read(file_path)?;
+            writer.write_all(&(name.len() as u32).to_le_bytes())?;
+            writer.write_all(name.as_bytes())?;
+            writer.write_all(&(data.len() as u64).to_le_bytes())?;
+            writer.write_all(&data)?;
+        }
+        writer.flush()
+    })();
+    write_result.map_err(|e| format!("Failed to write scan session: {}", e))?;
+
+    Ok(metadata)
+}
+
+/// Import a .ddscan file as a new scan. The original scan_id is reused unless
+/// a scan with that ID already exists locally.
+#[tauri::command]
+pub async fn import_scan_session(path: String) -> Result<ScanSessionImportResult, String> {
+    let file = std::fs::File::open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let file_len = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut reader = std::io::BufReader::new(file);
+
+    // Lengths come from the file; none may claim more than is left of it
+    let checked_len = |r: &mut std::io::BufReader<std::fs::File>, len: u64| -> Result<u64, String> {
+        let position = r.stream_position().map_err(|e| e.to_string())?;
+        if len > file_len.saturating_sub(position) {
+            return Err("Truncated scan session: entry longer than the file".to_string());
+        }
+        Ok(len)
+    };
+
+    let read_u32 = |r: &mut dyn Read| -> Result<u32, String> {
+        let mut b = [0u8; 4];
+        r.read_exact(&mut b).map_err(|e| format!("Truncated scan session: {}", e))?;
+        Ok(u32::from_le_bytes(b))
+    };
+
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic).map_err(|e| format!("Truncated scan session: {}", e))?;
+    if &magic != DDSCAN_MAGIC {
+        return Err("Not a DynaDbg scan session file".to_string());
+    }
+
+    let metadata_len = read_u32(&mut reader)? as u64;
+    let mut metadata_json = vec![0u8; checked_len(&mut reader, metadata_len)? as usize];
+    reader.read_exact(&mut metadata_json).map_err(|e| format!("Truncated scan session: {}", e))?;
+    let mut metadata: ScanSessionMetadata = serde_json::from_slice(&metadata_json)
+        .map_err(|e| format!("Invalid scan session metadata: {}", e))?;
+    validate_scan_id(&metadata.scan_id).map_err(|e| format!("{} in scan session", e))?;
+
+    let scan_id = if get_unknown_scan_temp_dir(&metadata.scan_id).exists() {
+        crate::new_scan_id(&format!("{}_import", metadata.scan_id))
+    } else {
+        metadata.scan_id.clone()
+    };
+    let scan_dir = get_unknown_scan_temp_dir(&scan_id);
+    std::fs::create_dir_all(&scan_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let entry_count = read_u32(&mut reader)?;
+    for _ in 0..entry_count {
+        let name_len = read_u32(&mut reader)? as u64;
+        let mut name = vec![0u8; checked_len(&mut reader, name_len)? as usize];
+        reader.read_exact(&mut name).map_err(|e| format!("Truncated scan session: {}", e))?;
+        let name = String::from_utf8(name).map_err(|_| "Invalid entry name in scan session".to_string())?;
+
+        // Only "region_*.bin" and "gen_N/region_*.bin" are valid entries
+        let relative = Path::new(&name);
+        if relative.is_absolute() || relative.components().any(|c| !matches!(c, std::path::Component::Normal(_))) {
+            return Err(format!("Invalid entry path in scan session: {}", name));
+        }
+
+        let mut len = [0u8; 8];
+        reader.read_exact(&mut len).map_err(|e| format!("Truncated scan session: {}", e))?;
+        let len = checked_len(&mut reader, u64::from_le_bytes(len))?;
+
+        let target = scan_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut out = std::fs::File::create(&target)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        let copied = std::io::copy(&mut reader.by_ref().take(len), &mut out)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        if copied != len {
+            return Err("Truncated scan session: entry data ends early".to_string());
+        }
+        drop(out);
+
+        // Filters walk region addresses in order and group them into reads
+        if let Some(region) = read_scan_region_file(&target) {
+            if !region.addresses.windows(2).all(|pair| pair[0] < pair[1]) {
+                let _ = std::fs::remove_dir_all(&scan_dir);
+                return Err(format!("Invalid scan session: addresses in {} are not ascending", name));
+            }
+        }
+    }
+
+    metadata.scan_id = scan_id.clone();
+    Ok(ScanSessionImportResult {
+        success: true,
+        scan_id,
+        metadata,
+        temp_dir: scan_dir.to_string_lossy().to_string(),
+    })
+}
@@ -0,0 +1,465 @@
+use mlua::{ChunkMode, HookTriggers, Lua, LuaOptions, LuaSerdeExt, MultiValue, StdLib, Table, Value};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::state::{AppState, AppStateType};
+use crate::{
+    breakpoints, clear_unknown_scan, disassembly, filter_unknown_scan_native, ghidra_search,
+    ghidra_server_decompile, ghidra_server_xrefs, read_memory, read_unknown_scan_results, run_aob_scan,
+    run_exact_scan, server_address, virtual_addresses, write_memory_to_server, AobScanRequest,
+    ExactScanRequest, UnknownScanFilterRequest,
+};
+
+const MEMORY_LIMIT: usize = 256 * 1024 * 1024;
+// Instructions between checks of the stop flag
+const STOP_CHECK_INTERVAL: u32 = 10_000;
+// Output lines kept per script for get_script_output
+const MAX_OUTPUT_LINES: usize = 1000;
+// Accessor suffix and scan data type of the fixed-size values scripts can read and write
+const VALUE_TYPES: &[(&str, &str)] = &[
+    ("u8", "uint8"), ("i8", "int8"), ("u16", "uint16"), ("i16", "int16"),
+    ("u32", "uint32"), ("i32", "int32"), ("u64", "uint64"), ("i64", "int64"),
+    ("f32", "float"), ("f64", "double"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptState {
+    Running,
+    Finished,
+    Failed,
+    Stopped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptInfo {
+    pub id: u32,
+    pub name: String,
+    pub state: ScriptState,
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub error: Option<String>,
+    pub result: Option<serde_json::Value>, // Value the chunk returned
+}
+
+/// Emitted as "script://output" for each print/dbg.log line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptOutput {
+    pub id: u32,
+    pub level: String,                     // "log" | "error"
+    pub text: String,
+}
+
+struct RunningScript {
+    info: ScriptInfo,
+    stop: Arc<AtomicBool>,
+    output: Vec<ScriptOutput>,
+}
+
+static NEXT_SCRIPT_ID: AtomicU32 = AtomicU32::new(1);
+static SCRIPTS: Lazy<Mutex<HashMap<u32, RunningScript>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn lua_error(message: impl Into<String>) -> mlua::Error {
+    mlua::Error::RuntimeError(message.into())
+}
+
+fn emit_output(app: &AppHandle, id: u32, level: &str, text: String) {
+    let output = ScriptOutput { id, level: level.to_string(), text };
+    let _ = app.emit("script://output", &output);
+    if let Ok(mut scripts) = SCRIPTS.lock() {
+        if let Some(script) = scripts.get_mut(&id) {
+            if script.output.len() >= MAX_OUTPUT_LINES {
+                script.output.remove(0);
+            }
+            script.output.push(output);
+        }
+    }
+}
+
+/// Little-endian bytes of a Lua number for a scan data type
+fn encode_value(data_type: &str, value: &Value) -> mlua::Result<Vec<u8>> {
+    let integer = || match value {
+        Value::Integer(i) => Ok(*i),
+        Value::Number(n) if n.fract() == 0.0 => Ok(*n as i64),
+        _ => Err(lua_error(format!("Expected an integer for {}", data_type))),
+    };
+    let number = || match value {
+        Value::Integer(i) => Ok(*i as f64),
+        Value::Number(n) => Ok(*n),
+        _ => Err(lua_error(format!("Expected a number for {}", data_type))),
+    };
+    Ok(match data_type {
+        "int8" | "uint8" => vec![integer()? as u8],
+        "int16" | "uint16" => (integer()? as u16).to_le_bytes().to_vec(),
+        "int32" | "uint32" => (integer()? as u32).to_le_bytes().to_vec(),
+        "int64" | "uint64" => integer()?.to_le_bytes().to_vec(),
+        "float" => (number()? as f32).to_le_bytes().to_vec(),
+        "double" => number()?.to_le_bytes().to_vec(),
+        _ => return Err(lua_error(format!("Unsupported data type '{}'", data_type))),
+    })
+}
+
+fn decode_value(data_type: &str, bytes: &[u8]) -> Value<'static> {
+    let mut raw = [0u8; 8];
+    raw[..bytes.len().min(8)].copy_from_slice(&bytes[..bytes.len().min(8)]);
+    match data_type {
+        "uint8" => Value::Integer(raw[0] as i64),
+        "int8" => Value::Integer(raw[0] as i8 as i64),
+        "uint16" => Value::Integer(u16::from_le_bytes([raw[0], raw[1]]) as i64),
+        "int16" => Value::Integer(i16::from_le_bytes([raw[0], raw[1]]) as i64),
+        "uint32" => Value::Integer(u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as i64),
+        "int32" => Value::Integer(i32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as i64),
+        "float" => Value::Number(f32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as f64),
+        "double" => Value::Number(f64::from_le_bytes(raw)),
+        _ => Value::Integer(i64::from_le_bytes(raw)), // 64-bit integers wrap like Lua's own
+    }
+}
+
+fn data_size(data_type: &str) -> usize {
+    match data_type {
+        "int8" | "uint8" => 1,
+        "int16" | "uint16" => 2,
+        "int32" | "uint32" | "float" => 4,
+        _ => 8,
+    }
+}
+
+async fn read_bytes(address: u64, size: usize) -> mlua::Result<Vec<u8>> {
+    let response = read_memory(address, size).await.map_err(lua_error)?;
+    match response.data {
+        Some(data) if response.success => Ok(data),
+        _ => Err(lua_error(response.error.unwrap_or_else(|| format!("Failed to read memory at 0x{:x}", address)))),
+    }
+}
+
+async fn write_bytes(address: u64, data: &[u8]) -> mlua::Result<()> {
+    if virtual_addresses::is_virtual(address) {
+        return virtual_addresses::write(address, data).await.map_err(lua_error);
+    }
+    let (host, port) = server_address().map_err(lua_error)?;
+    write_memory_to_server(&host, port, address, data).await.map_err(lua_error)
+}
+
+/// `{ {start, end}, ... }` address ranges of a scan options table
+fn scan_ranges(options: &Option<Table>) -> mlua::Result<Vec<(u64, u64)>> {
+    let Some(ranges) = options.as_ref().map(|o| o.get::<_, Option<Vec<Vec<u64>>>>("ranges")).transpose()?.flatten() else {
+        return Ok(Vec::new());
+    };
+    ranges.into_iter()
+        .map(|range| match range.as_slice() {
+            [start, end] => Ok((*start, *end)),
+            _ => Err(lua_error("Ranges must be {start, end} pairs")),
+        })
+        .collect()
+}
+
+/// The `dbg` table: memory, scans, breakpoints, disassembly and Ghidra
+/// queries. The sandbox has no io, os, package or debug libraries.
+fn install_api(lua: &Lua, app: AppHandle, id: u32, stop: Arc<AtomicBool>) -> mlua::Result<()> {
+    let api = lua.create_table()?;
+
+    let log_app = app.clone();
+    let log = lua.create_function(move |lua, values: MultiValue| {
+        let text = values.into_iter()
+            .map(|value| lua.coerce_string(value).ok().flatten().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| "nil".to_string()))
+            .collect::<Vec<_>>()
+            .join("\t");
+        emit_output(&log_app, id, "log", text);
+        Ok(())
+    })?;
+    lua.globals().set("print", log.clone())?;
+    api.set("log", log)?;
+
+    let sleep_stop = stop.clone();
+    api.set("sleep", lua.create_async_function(move |_, ms: u64| {
+        let stop = sleep_stop.clone();
+        async move {
+            let deadline = tokio::time::Instant::now() + Duration::from_millis(ms);
+            while tokio::time::Instant::now() < deadline {
+                if stop.load(Ordering::Relaxed) {
+                    return Err(lua_error("Script stopped"));
+                }
+                tokio::time::sleep_until(deadline.min(tokio::time::Instant::now() + Duration::from_millis(50))).await;
+            }
+            Ok(())
+        }
+    })?)?;
+
+    api.set("read", lua.create_async_function(|lua, (address, size): (u64, usize)| async move {
+        lua.create_string(read_bytes(address, size).await?)
+    })?)?;
+    api.set("write", lua.create_async_function(|_, (address, data): (u64, mlua::String)| async move {
+        write_bytes(address, data.as_bytes()).await
+    })?)?;
+    for (suffix, data_type) in VALUE_TYPES {
+        api.set(format!("read_{}", suffix), lua.create_async_function(move |_, address: u64| async move {
+            Ok(decode_value(data_type, &read_bytes(address, data_size(data_type)).await?))
+        })?)?;
+        api.set(format!("write_{}", suffix), lua.create_async_function(move |_, (address, value): (u64, Value)| async move {
+            write_bytes(address, &encode_value(data_type, &value)?).await
+        })?)?;
+    }
+
+    api.set("scan", lua.create_async_function(|lua, (data_type, value, options): (String, Value, Option<Table>)| async move {
+        let response = run_exact_scan(None, ExactScanRequest {
+            pattern: hex::encode(encode_value(&data_type, &value)?),
+            data_type,
+            address_ranges: scan_ranges(&options)?,
+            alignment: options.as_ref().map(|o| o.get::<_, Option<usize>>("alignment")).transpose()?.flatten().unwrap_or(0),
+            scan_id: None,
+        }).await.map_err(lua_error)?;
+        lua.to_value(&response)
+    })?)?;
+    api.set("aob_scan", lua.create_async_function(|lua, (pattern, options): (String, Option<Table>)| async move {
+        let response = run_aob_scan(None, AobScanRequest {
+            pattern,
+            address_ranges: scan_ranges(&options)?,
+            alignment: options.as_ref().map(|o| o.get::<_, Option<usize>>("alignment")).transpose()?.flatten().unwrap_or(1),
+            scan_id: None,
+        }).await.map_err(lua_error)?;
+        lua.to_value(&response)
+    })?)?;
+    let scan_app = app.clone();
+    api.set("next_scan", lua.create_async_function(move |lua, (scan_id, method, data_type, value): (String, String, String, Option<Value>)| {
+        let app = scan_app.clone();
+        async move {
+            let pattern = match value {
+                Some(value) => hex::encode(encode_value(&data_type, &value)?),
+                None => String::new(),
+            };
+            let request = UnknownScanFilterRequest { scan_id, filter_method: method, data_type, pattern, pattern_max: None, fuzzy: None, case_insensitive: false };
+            lua.to_value(&filter_unknown_scan_native(app, request).await.map_err(lua_error)?)
+        }
+    })?)?;
+    api.set("scan_results", lua.create_async_function(|lua, (scan_id, offset, limit): (String, Option<usize>, Option<usize>)| async move {
+        let lookup = read_unknown_scan_results(scan_id, offset.unwrap_or(0), limit.unwrap_or(100)).await.map_err(lua_error)?;
+        lua.to_value(&lookup.results)
+    })?)?;
+    api.set("clear_scan", lua.create_function(|_, scan_id: String| clear_unknown_scan(scan_id).map_err(lua_error))?)?;
+
+    let breakpoint_app = app.clone();
+    api.set("set_breakpoint", lua.create_async_function(move |lua, (address, options): (u64, Option<Table>)| {
+        let app = breakpoint_app.clone();
+        async move {
+            let option = |key: &str| -> mlua::Result<Option<Value>> {
+                options.as_ref().map(|o| o.get::<_, Value>(key)).transpose().map(|v| v.filter(|v| !v.is_nil()))
+            };
+            let hit_count = option("hit_count")?.map(|v| lua.from_value::<i32>(v)).transpose()?;
+            let condition = option("condition")?.map(|v| lua.from_value::<String>(v)).transpose()?;
+            let is_software = option("software")?.map(|v| lua.from_value::<bool>(v)).transpose()?;
+            let definition = breakpoints::set_breakpoint(app.state(), address, hit_count, condition, is_software, Some(true), None)
+                .await
+                .map_err(lua_error)?;
+            lua.to_value(&definition)
+        }
+    })?)?;
+    let breakpoint_app = app.clone();
+    api.set("remove_breakpoint", lua.create_async_function(move |_, address: u64| {
+        let app = breakpoint_app.clone();
+        async move {
+            breakpoints::remove_breakpoint(app.state(), None, Some(address)).await.map_err(lua_error)
+        }
+    })?)?;
+
+    let target_app = app.clone();
+    api.set("modules", lua.create_function(move |lua, ()| {
+        let state = target_app.state::<AppStateType>();
+        let modules = state.lock().map_err(|e| lua_error(e.to_string()))?.attached_modules.clone();
+        lua.to_value(&modules)
+    })?)?;
+    let target_app = app.clone();
+    api.set("disassemble", lua.create_async_function(move |lua, (address, count): (u64, Option<usize>)| {
+        let app = target_app.clone();
+        async move {
+            let count = count.unwrap_or(16).clamp(1, 4096);
+            let architecture = app.state::<AppStateType>().lock()
+                .ok()
+                .and_then(|s| s.server_info.as_ref().map(|info| info.arch.clone()))
+                .unwrap_or_else(|| "arm64".to_string());
+            let data = read_bytes(address, count * 16).await?;
+            let mut instructions = disassembly::disassemble_structured(&data, address, &architecture).map_err(lua_error)?;
+            instructions.truncate(count);
+            lua.to_value(&instructions)
+        }
+    })?)?;
+
+    api.set("ghidra_decompile", lua.create_async_function(|_, (project_path, offset): (String, String)| async move {
+        let result = ghidra_server_decompile(project_path, offset).await.map_err(lua_error)?;
+        match result.decompiled_code {
+            Some(code) if result.success => Ok(code),
+            _ => Err(lua_error(result.error.unwrap_or_else(|| "Decompilation failed".to_string()))),
+        }
+    })?)?;
+    api.set("ghidra_xrefs", lua.create_async_function(|lua, (project_path, offset): (String, String)| async move {
+        lua.to_value(&ghidra_server_xrefs(project_path, offset).await.map_err(lua_error)?)
+    })?)?;
+    let search_app = app.clone();
+    api.set("ghidra_search", lua.create_async_function(move |lua, (project_path, query, kind): (String, String, Option<String>)| {
+        let app = search_app.clone();
+        async move {
+            let kind = kind.unwrap_or_else(|| "string".to_string());
+            let result = ghidra_search::ghidra_search(app.state(), project_path, query, kind, None, None)
+                .await
+                .map_err(lua_error)?;
+            lua.to_value(&result)
+        }
+    })?)?;
+
+    lua.globals().set("dbg", api)?;
+    lua.set_hook(HookTriggers::new().every_nth_instruction(STOP_CHECK_INTERVAL), move |_, _| {
+        if stop.load(Ordering::Relaxed) {
+            return Err(lua_error("Script stopped"));
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+// Wraps the base library's load; an env argument is passed on only when given
+// (load treats an explicit nil env differently from a missing one)
+const TEXT_ONLY_LOAD: &str = r#"
+local load = ...
+return function(chunk, name, _, ...)
+    if select('#', ...) > 0 then
+        return load(chunk, name, "t", ...)
+    end
+    return load(chunk, name, "t")
+end
+"#;
+
+/// The base library is always loaded: drop the functions that read files and
+/// only let `load` compile source text (precompiled bytecode is unverified)
+fn restrict_base_library(lua: &Lua) -> mlua::Result<()> {
+    let globals = lua.globals();
+    globals.set("dofile", Value::Nil)?;
+    globals.set("loadfile", Value::Nil)?;
+    let load: mlua::Function = globals.get("load")?;
+    let text_only: mlua::Function = lua.load(TEXT_ONLY_LOAD).set_mode(ChunkMode::Text).call(load)?;
+    globals.set("load", text_only)
+}
+
+async fn execute(
+    app: AppHandle,
+    id: u32,
+    name: String,
+    source: String,
+    globals: Vec<(String, serde_json::Value)>,
+    stop: Arc<AtomicBool>,
+) -> Result<Option<serde_json::Value>, String> {
+    let libraries = StdLib::TABLE | StdLib::STRING | StdLib::MATH | StdLib::UTF8 | StdLib::COROUTINE;
+    let lua = Lua::new_with(libraries, LuaOptions::default()).map_err(|e| e.to_string())?;
+    lua.set_memory_limit(MEMORY_LIMIT).map_err(|e| e.to_string())?;
+    restrict_base_library(&lua).map_err(|e| e.to_string())?;
+    install_api(&lua, app, id, stop).map_err(|e| e.to_string())?;
+    for (global, value) in globals {
+        let value = lua.to_value(&value).map_err(|e| e.to_string())?;
+        lua.globals().set(global, value).map_err(|e| e.to_string())?;
+    }
+    let value: Value = lua.load(&source).set_name(name).set_mode(ChunkMode::Text).eval_async().await.map_err(|e| e.to_string())?;
+    Ok(lua.from_value::<serde_json::Value>(value).ok().filter(|v| !v.is_null()))
+}
+
+/// Script source and default name for a file path or inline source
+pub async fn load_source(path_or_source: String) -> Result<(String, String), String> {
+    let path = std::path::Path::new(&path_or_source);
+    if !path_or_source.contains('\n') && path.is_file() {
+        let source = tokio::fs::read_to_string(path).await.map_err(|e| format!("Failed to read script: {}", e))?;
+        return Ok((source, path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()));
+    }
+    Ok((path_or_source, "script".to_string()))
+}
+
+/// Start a script with extra Lua globals. The handle resolves to its final
+/// info once it finishes, fails or is stopped.
+pub fn start(
+    app: AppHandle,
+    source: String,
+    name: String,
+    globals: Vec<(String, serde_json::Value)>,
+) -> Result<(ScriptInfo, tauri::async_runtime::JoinHandle<Option<ScriptInfo>>), String> {
+    let id = NEXT_SCRIPT_ID.fetch_add(1, Ordering::SeqCst);
+    let info = ScriptInfo {
+        id,
+        name,
+        state: ScriptState::Running,
+        started_at: AppState::current_timestamp(),
+        finished_at: None,
+        error: None,
+        result: None,
+    };
+    let stop = Arc::new(AtomicBool::new(false));
+    SCRIPTS.lock().map_err(|e| e.to_string())?.insert(id, RunningScript { info: info.clone(), stop: stop.clone(), output: Vec::new() });
+
+    let task_app = app.clone();
+    let chunk_name = info.name.clone();
+    // The Lua state is not Sync, so the script's future stays on one blocking thread
+    let handle = tauri::async_runtime::spawn_blocking(move || {
+        let outcome = tokio::runtime::Handle::current().block_on(execute(task_app.clone(), id, chunk_name, source, globals, stop.clone()));
+        if let Err(e) = &outcome {
+            emit_output(&task_app, id, "error", e.clone());
+        }
+        let finished = {
+            let mut scripts = SCRIPTS.lock().ok()?;
+            let script = scripts.get_mut(&id)?;
+            script.info.finished_at = Some(AppState::current_timestamp());
+            match outcome {
+                Ok(result) => {
+                    script.info.state = ScriptState::Finished;
+                    script.info.result = result;
+                }
+                Err(_) if stop.load(Ordering::Relaxed) => script.info.state = ScriptState::Stopped,
+                Err(e) => {
+                    script.info.state = ScriptState::Failed;
+                    script.info.error = Some(e);
+                }
+            }
+            script.info.clone()
+        };
+        let _ = task_app.emit("script://finished", &finished);
+        Some(finished)
+    });
+    Ok((info, handle))
+}
+
+/// Run a Lua script (a file path or the source itself) in a sandbox with the
+/// `dbg` debugger API. Returns at once; output arrives as "script://output"
+/// and the end as "script://finished".
+#[tauri::command]
+pub async fn run_script(app: AppHandle, path_or_source: String, name: Option<String>) -> Result<ScriptInfo, String> {
+    let (source, default_name) = load_source(path_or_source).await?;
+    let (info, _) = start(app, source, name.unwrap_or(default_name), Vec::new())?;
+    Ok(info)
+}
+
+/// Ask a running script to stop (at its next instruction check or sleep)
+#[tauri::command]
+pub fn stop_script(id: u32) -> Result<bool, String> {
+    let scripts = SCRIPTS.lock().map_err(|e| e.to_string())?;
+    let Some(script) = scripts.get(&id).filter(|s| s.info.state == ScriptState::Running) else {
+        return Ok(false);
+    };
+    script.stop.store(true, Ordering::Relaxed);
+    Ok(true)
+}
+
+/// Scripts started this session, newest first
+#[tauri::command]
+pub fn list_scripts() -> Result<Vec<ScriptInfo>, String> {
+    let mut scripts: Vec<ScriptInfo> = SCRIPTS.lock().map_err(|e| e.to_string())?.values().map(|s| s.info.clone()).collect();
+    scripts.sort_by_key(|s| std::cmp::Reverse(s.id));
+    Ok(scripts)
+}
+
+/// Buffered output of a script (the last MAX_OUTPUT_LINES lines)
+#[tauri::command]
+pub fn get_script_output(id: u32) -> Result<Vec<ScriptOutput>, String> {
+    Ok(SCRIPTS.lock().map_err(|e| e.to_string())?
+        .get(&id)
+        .map(|s| s.output.clone())
+        .unwrap_or_default())
+}
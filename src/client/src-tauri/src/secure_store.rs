@@ -0,0 +1,201 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::db;
+
+// Key handling and sealing live in dynadbg-core so the CLI can read
+// encrypted cache rows; this module adds the migration and the commands
+pub use dynadbg_core::secure_store::{init, open_value, seal_value};
+use dynadbg_core::secure_store::{
+    load_or_create_key, open_with, seal_with, DB_CRYPTO, ENCRYPTABLE_COLUMNS, KEY_CHECK_PLAINTEXT, SEALED_PREFIX,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbEncryptionStatus {
+    pub enabled: bool,
+    pub key_available: bool,
+    pub key_source: Option<String>,   // "env" | "keychain" | "file"
+    pub encrypted_tables: Vec<String>,
+    pub encryptable_tables: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbMigrationResult {
+    pub encrypted_values: usize,
+    pub decrypted_values: usize,
+    pub failed_values: usize,          // Sealed values that could not be opened with the current key
+}
+
+/// Bring every encryptable column in line with the current configuration:
+/// seal plaintext values of designated tables and open sealed values of the rest
+fn migrate(conn: &Connection) -> Result<DbMigrationResult, String> {
+    let crypto = DB_CRYPTO.read().map_err(|e| e.to_string())?;
+    let mut result = DbMigrationResult { encrypted_values: 0, decrypted_values: 0, failed_values: 0 };
+
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    for (table, columns) in ENCRYPTABLE_COLUMNS {
+        let designated = crypto.tables.iter().any(|t| t == table);
+        for column in columns.iter() {
+            let aad = format!("{}.{}", table, column);
+            let rows: Vec<(i64, String)> = {
+                let mut stmt = tx.prepare(&format!("SELECT rowid, {} FROM {} WHERE {} IS NOT NULL", column, table, column))
+                    .map_err(|e| e.to_string())?;
+                let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                    .map_err(|e| e.to_string())?;
+                rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())?
+            };
+            for (rowid, value) in rows {
+                let sealed = value.starts_with(SEALED_PREFIX);
+                let updated = match (designated, sealed, crypto.key.as_ref()) {
+                    (true, false, Some(key)) => {
+                        result.encrypted_values += 1;
+                        seal_with(key, &aad, &value)?
+                    }
+                    (false, true, Some(key)) => match open_with(key, &aad, &value) {
+                        Ok(plaintext) => {
+                            result.decrypted_values += 1;
+                            plaintext
+                        }
+                        Err(_) => {
+                            result.failed_values += 1;
+                            continue;
+                        }
+                    },
+                    (_, true, None) => {
+                        result.failed_values += 1;
+                        continue;
+                    }
+                    _ => continue,
+                };
+                tx.execute(&format!("UPDATE {} SET {} = ?1 WHERE rowid = ?2", table, column), params![updated, rowid])
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+fn save_tables_setting(conn: &Connection, tables: &[String]) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO db_encryption_settings (key, value) VALUES ('tables', ?1)",
+        params![serde_json::to_string(tables).map_err(|e| e.to_string())?],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_db_encryption_status() -> Result<DbEncryptionStatus, String> {
+    let crypto = DB_CRYPTO.read().map_err(|e| e.to_string())?;
+    Ok(DbEncryptionStatus {
+        enabled: !crypto.tables.is_empty(),
+        key_available: crypto.key.is_some(),
+        key_source: crypto.key_source.clone(),
+        encrypted_tables: crypto.tables.clone(),
+        encryptable_tables: ENCRYPTABLE_COLUMNS.iter().map(|(t, _)| t.to_string()).collect(),
+    })
+}
+
+/// Turn on encryption for `tables` (all encryptable tables by default),
+/// creating a key if none exists, and encrypt the existing rows
+#[tauri::command]
+pub async fn enable_db_encryption(tables: Option<Vec<String>>) -> Result<DbMigrationResult, String> {
+    let tables = match tables {
+        Some(tables) => {
+            if let Some(unknown) = tables.iter().find(|t| !ENCRYPTABLE_COLUMNS.iter().any(|(name, _)| name == t)) {
+                return Err(format!("Table '{}' cannot be encrypted", unknown));
+            }
+            tables
+        }
+        None => ENCRYPTABLE_COLUMNS.iter().map(|(t, _)| t.to_string()).collect(),
+    };
+
+    db::run(move |conn| {
+        {
+            let mut crypto = DB_CRYPTO.write().map_err(|e| e.to_string())?;
+            if crypto.key.is_none() {
+                if !crypto.tables.is_empty() {
+                    return Err("Database is already encrypted with a key that is not available".to_string());
+                }
+                let (key, source) = load_or_create_key()?;
+                conn.execute(
+                    "INSERT OR REPLACE INTO db_encryption_settings (key, value) VALUES ('key_check', ?1)",
+                    params![seal_with(&key, "key_check", KEY_CHECK_PLAINTEXT)?],
+                ).map_err(|e| e.to_string())?;
+                crypto.key = Some(key);
+                crypto.key_source = Some(source.to_string());
+            }
+            save_tables_setting(conn, &tables)?;
+            crypto.tables = tables;
+        }
+        migrate(conn)
+    }).await
+}
+
+/// Decrypt every stored value and turn encryption off. The key is kept so the
+/// database can be re-encrypted later.
+#[tauri::command]
+pub async fn disable_db_encryption() -> Result<DbMigrationResult, String> {
+    db::run(move |conn| {
+        if DB_CRYPTO.read().map_err(|e| e.to_string())?.key.is_none() {
+            return Err("Database encryption key unavailable".to_string());
+        }
+        DB_CRYPTO.write().map_err(|e| e.to_string())?.tables.clear();
+        save_tables_setting(conn, &[])?;
+        migrate(conn)
+    }).await
+}
+
+/// Re-apply the encryption configuration to existing rows, e.g. for a
+/// database written by an older version or restored from a backup
+#[tauri::command]
+pub async fn migrate_db_encryption() -> Result<DbMigrationResult, String> {
+    db::run(migrate).await
+}
+
+fn put_setting(conn: &Connection, key: &str, value: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value, updated_at) VALUES (?1, ?2, datetime('now'))",
+        params![key, seal_value("app_settings", "value", value)?],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn remove_setting(conn: &Connection, key: &str) -> Result<bool, String> {
+    let deleted = conn.execute("DELETE FROM app_settings WHERE key = ?1", params![key])
+        .map_err(|e| e.to_string())?;
+    Ok(deleted > 0)
+}
+
+/// Store `value` under `key`, or remove the setting when it is None
+pub async fn store_setting(key: &str, value: Option<String>) -> Result<(), String> {
+    let key = key.to_string();
+    db::run(move |conn| match value {
+        Some(value) => put_setting(conn, &key, &value),
+        None => remove_setting(conn, &key).map(|_| ()),
+    }).await
+}
+
+/// Store a frontend setting (server profiles, auth tokens, ...); encrypted when
+/// app_settings is designated
+#[tauri::command]
+pub async fn save_app_setting(key: String, value: String) -> Result<bool, String> {
+    db::run(move |conn| put_setting(conn, &key, &value).map(|_| true)).await
+}
+
+#[tauri::command]
+pub async fn get_app_setting(key: String) -> Result<Option<String>, String> {
+    db::run(move |conn| {
+        let value: Option<String> = conn.query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        ).ok();
+        value.map(|v| open_value("app_settings", "value", v)).transpose()
+    }).await
+}
+
+#[tauri::command]
+pub async fn delete_app_setting(key: String) -> Result<bool, String> {
+    db::run(move |conn| remove_setting(conn, &key)).await
+}
@@ -0,0 +1,110 @@
+use base64::{engine::general_purpose, Engine as _};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::db;
+
+// HTTP client, TLS pinning and retry policy live in dynadbg-core so the CLI
+// shares them; this module adds the commands and the pin table
+pub use dynadbg_core::server_connection::*;
+
+/// Certificate presented by a server, for the user to confirm before pinning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerCertificate {
+    pub host: String,
+    pub port: u16,
+    pub fingerprint: String,         // SHA-256 of the DER certificate, "AB:CD:..."
+    pub pinned: bool,                // Matches the fingerprint pinned for host:port
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedCertificate {
+    pub host: String,
+    pub port: u16,
+    pub fingerprint: String,
+    pub pinned_at: String,
+}
+
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS server_certificate_pins (
+            host TEXT NOT NULL,
+            port INTEGER NOT NULL,
+            fingerprint TEXT NOT NULL,
+            certificate_der TEXT NOT NULL,
+            pinned_at TEXT NOT NULL,
+            PRIMARY KEY(host, port)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_http_settings() -> HttpSettings {
+    settings()
+}
+
+/// Replace the pool/timeout/retry settings; the shared client is rebuilt
+#[tauri::command]
+pub fn set_http_settings(settings: HttpSettings) -> Result<HttpSettings, String> {
+    apply_settings(settings.clone())?;
+    Ok(settings)
+}
+
+/// Certificate fingerprint of a TLS server, to confirm before pinning it
+#[tauri::command]
+pub async fn get_server_certificate(host: String, port: u16) -> Result<ServerCertificate, String> {
+    let der = fetch_certificate(&host, port).await?;
+    let fingerprint = fingerprint(&der);
+    let pinned = pinned(&host, port).is_some_and(|pin| pin == fingerprint);
+    Ok(ServerCertificate { host, port, fingerprint, pinned })
+}
+
+/// Pin the certificate a server presents now; fails unless it has the
+/// fingerprint the user confirmed
+#[tauri::command]
+pub async fn pin_server_certificate(host: String, port: u16, fingerprint: String) -> Result<ServerCertificate, String> {
+    let der = fetch_certificate(&host, port).await?;
+    let actual = self::fingerprint(&der);
+    if actual != normalize_fingerprint(&fingerprint) {
+        return Err(format!("Certificate fingerprint mismatch: server presented {}", actual));
+    }
+    {
+        let (host, actual) = (host.clone(), actual.clone());
+        db::run(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO server_certificate_pins (host, port, fingerprint, certificate_der, pinned_at)
+                 VALUES (?1, ?2, ?3, ?4, datetime('now'))",
+                params![host, port, actual, general_purpose::STANDARD.encode(&der)],
+            ).map_err(|e| e.to_string())
+        }).await?;
+    }
+    pin(&host, port, &actual)?;
+    Ok(ServerCertificate { host, port, fingerprint: actual, pinned: true })
+}
+
+#[tauri::command]
+pub async fn unpin_server_certificate(host: String, port: u16) -> Result<bool, String> {
+    unpin(&host, port)?;
+    let removed = db::run(move |conn| {
+        conn.execute(
+            "DELETE FROM server_certificate_pins WHERE host = ?1 AND port = ?2",
+            params![host, port],
+        ).map_err(|e| e.to_string())
+    }).await?;
+    Ok(removed > 0)
+}
+
+#[tauri::command]
+pub async fn list_pinned_certificates() -> Result<Vec<PinnedCertificate>, String> {
+    db::run(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT host, port, fingerprint, pinned_at FROM server_certificate_pins ORDER BY host, port",
+        ).map_err(|e| e.to_string())?;
+        let rows = stmt.query_map([], |row| {
+            Ok(PinnedCertificate { host: row.get(0)?, port: row.get(1)?, fingerprint: row.get(2)?, pinned_at: row.get(3)? })
+        }).map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }).await
+}
@@ -0,0 +1,451 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::expression::RegisterSet;
+use crate::memory_regions::{self, MemoryRegion};
+use crate::state::{AppStateType, TraceEntryData};
+use crate::symbolizer::Symbolizer;
+use crate::{db, server_connection, GHIDRA_SERVER_PORTS};
+
+// Instructions of a callee examined for argument register reads
+const MAX_BODY_INSTRUCTIONS: usize = 64;
+
+/// Inferred type of one parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferredParameter {
+    pub index: usize,
+    pub register: String,
+    pub param_type: String,           // pointer, int, long, float, double
+    pub confidence: f64,              // Share of samples agreeing with param_type
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferredSignature {
+    pub target_os: String,
+    pub module_name: String,
+    pub function_offset: u64,
+    pub function_name: Option<String>,
+    pub parameters: Vec<InferredParameter>,
+    pub confidence: f64,              // Confidence in the parameter count
+    pub sample_count: usize,
+    pub pushed_to_ghidra: bool,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignaturePushResult {
+    pub success: bool,
+    pub function_name: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Create the inferred signature table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS inferred_signatures (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            function_offset INTEGER NOT NULL,
+            function_name TEXT,
+            parameters_json TEXT NOT NULL,
+            confidence REAL NOT NULL,
+            sample_count INTEGER NOT NULL,
+            pushed_to_ghidra INTEGER NOT NULL DEFAULT 0,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name, function_offset)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Integer and floating point argument registers of the calling convention
+fn argument_registers(arch: &str, target_os: &str) -> (&'static [&'static str], &'static [&'static str]) {
+    match arch {
+        "x86_64" if target_os == "windows" => (&["rcx", "rdx", "r8", "r9"], &["xmm0", "xmm1", "xmm2", "xmm3"]),
+        "x86_64" => (
+            &["rdi", "rsi", "rdx", "rcx", "r8", "r9"],
+            &["xmm0", "xmm1", "xmm2", "xmm3", "xmm4", "xmm5", "xmm6", "xmm7"],
+        ),
+        _ => (
+            &["x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7"],
+            &["v0", "v1", "v2", "v3", "v4", "v5", "v6", "v7"],
+        ),
+    }
+}
+
+/// Full register a sub-register belongs to (w1 -> x1, edi -> rdi, d0 -> v0)
+fn canonical_register(name: &str) -> Option<String> {
+    let name = name.trim().to_lowercase();
+    if name.len() < 2 {
+        return None;
+    }
+    let numbered = |prefixes: &[char], rest: &str| -> Option<u32> {
+        if prefixes.iter().any(|p| name.starts_with(*p)) { rest.parse().ok() } else { None }
+    };
+    if let Some(n) = numbered(&['x', 'w'], &name[1..]).filter(|n| *n <= 30) {
+        return Some(format!("x{}", n));
+    }
+    if let Some(n) = numbered(&['b', 'h', 's', 'd', 'q', 'v'], &name[1..]).filter(|n| *n <= 31) {
+        return Some(format!("v{}", n));
+    }
+    if let Some(n) = name.strip_prefix("xmm").and_then(|n| n.parse::<u32>().ok()) {
+        return Some(format!("xmm{}", n));
+    }
+    if let Some(rest) = name.strip_prefix('r') {
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        if !digits.is_empty() && matches!(&rest[digits.len()..], "" | "d" | "w" | "b") {
+            return Some(format!("r{}", digits));
+        }
+    }
+    let full = match name.as_str() {
+        "rdi" | "edi" | "di" | "dil" => "rdi",
+        "rsi" | "esi" | "si" | "sil" => "rsi",
+        "rdx" | "edx" | "dx" | "dl" => "rdx",
+        "rcx" | "ecx" | "cx" | "cl" => "rcx",
+        "rax" | "eax" | "ax" | "al" => "rax",
+        _ => return None,
+    };
+    Some(full.to_string())
+}
+
+/// Registers read and written by one instruction, from its mnemonic and
+/// operand text. Approximate; enough to see which arguments a callee consumes.
+fn register_usage(opcode: &str, operands: &str) -> (Vec<String>, Vec<String>) {
+    let opcode = opcode.to_lowercase();
+    let tokens = |text: &str| -> Vec<String> {
+        text.split(|c: char| !c.is_ascii_alphanumeric())
+            .filter_map(canonical_register)
+            .collect()
+    };
+    let parts: Vec<&str> = operands.splitn(2, ',').collect();
+    let (first, rest) = (parts.first().copied().unwrap_or(""), parts.get(1).copied().unwrap_or(""));
+
+    let reads_only = opcode.starts_with("st") && !opcode.starts_with("stos")
+        || matches!(opcode.as_str(), "cmp" | "cmn" | "tst" | "test" | "cbz" | "cbnz" | "tbz" | "tbnz" | "push" | "br" | "blr" | "fcmp" | "ucomiss" | "ucomisd" | "comiss" | "comisd")
+        || opcode.starts_with("b.")
+        || first.contains('[');
+    if reads_only {
+        return (tokens(operands), Vec::new());
+    }
+    // `xor eax, eax` style zeroing reads nothing
+    if matches!(opcode.as_str(), "xor" | "eor" | "sub" | "pxor" | "xorps") && tokens(first) == tokens(rest) {
+        return (Vec::new(), tokens(first));
+    }
+    let overwrites = matches!(
+        opcode.as_str(),
+        "mov" | "movz" | "movn" | "movabs" | "lea" | "movzx" | "movsx" | "movsxd" | "movss" | "movsd" | "movaps" | "movups"
+            | "movq" | "movd" | "pop" | "adr" | "adrp" | "fmov" | "cvtsi2sd" | "cvtsi2ss"
+    ) || opcode.starts_with("ld") || opcode.starts_with("set");
+    if opcode.starts_with("ld") && opcode.ends_with('p') {
+        // ldp x0, x1, [x2]: the second operand is also a destination
+        let (second, address) = rest.split_once(',').unwrap_or((rest, ""));
+        let mut writes = tokens(first);
+        writes.extend(tokens(second));
+        return (tokens(address), writes);
+    }
+    let mut reads = tokens(rest);
+    if !overwrites {
+        reads.extend(tokens(first));
+    }
+    (reads, tokens(first))
+}
+
+/// Argument registers a callee reads before writing them, from its first traced instructions
+fn arguments_read(body: &[&TraceEntryData]) -> (HashSet<String>, HashSet<String>) {
+    let mut read = HashSet::new();
+    let mut written = HashSet::new();
+    let mut memory_bases = HashSet::new();
+    for entry in body.iter().take(MAX_BODY_INSTRUCTIONS) {
+        let (reads, writes) = register_usage(&entry.opcode, &entry.operands);
+        for register in reads {
+            if !written.contains(&register) {
+                if let Some(start) = entry.operands.find('[') {
+                    let inner = &entry.operands[start..];
+                    if inner.split(|c: char| !c.is_ascii_alphanumeric()).filter_map(canonical_register).any(|r| r == register) {
+                        memory_bases.insert(register.clone());
+                    }
+                }
+                read.insert(register);
+            }
+        }
+        written.extend(writes);
+        if entry.is_call || entry.is_return {
+            break;
+        }
+    }
+    (read, memory_bases)
+}
+
+fn classify_integer(value: u64, regions: &[MemoryRegion]) -> &'static str {
+    if value >= 0x10000 && regions.iter().any(|r| r.readable && value >= r.base && value < r.base + r.size) {
+        "pointer"
+    } else if value <= u32::MAX as u64 || ((value as i64) < 0 && (value as i64) >= i32::MIN as i64) {
+        "int"
+    } else {
+        "long"
+    }
+}
+
+/// Evidence gathered from every traced call of one function
+#[derive(Default)]
+struct Samples {
+    calls: usize,
+    counts: Vec<(usize, usize)>,                              // (integer params, float params) per call
+    types: BTreeMap<usize, BTreeMap<&'static str, usize>>,   // Parameter index -> type votes
+    float_width: BTreeMap<usize, &'static str>,
+}
+
+fn confidence(agreeing: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    // Agreement, discounted while there are few samples
+    let agreement = agreeing as f64 / total as f64;
+    (agreement * total as f64 / (total as f64 + 2.0) * 100.0).round() / 100.0
+}
+
+fn load_signatures(conn: &Connection, target_os: &str, module_name: Option<&str>) -> Result<Vec<InferredSignature>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT target_os, module_name, function_offset, function_name, parameters_json, confidence, sample_count, pushed_to_ghidra, updated_at
+         FROM inferred_signatures WHERE target_os = ?1 AND (?2 IS NULL OR module_name = ?2)
+         ORDER BY module_name, function_offset",
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![target_os, module_name], |row| {
+        let parameters_json: String = row.get(4)?;
+        Ok(InferredSignature {
+            target_os: row.get(0)?,
+            module_name: row.get(1)?,
+            function_offset: row.get::<_, i64>(2)? as u64,
+            function_name: row.get(3)?,
+            parameters: serde_json::from_str(&parameters_json).unwrap_or_default(),
+            confidence: row.get(5)?,
+            sample_count: row.get::<_, i64>(6)? as usize,
+            pushed_to_ghidra: row.get::<_, i64>(7)? != 0,
+            updated_at: row.get(8)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+/// Infer parameter counts and rough types of the functions called in the
+/// trace store (optionally one trace session). A callee's parameters are the
+/// argument registers it reads before writing; integer arguments are typed
+/// from their values at the call. Results are merged into the signature table.
+#[tauri::command]
+pub async fn infer_signatures_from_trace(
+    state: tauri::State<'_, AppStateType>,
+    target_address: Option<String>,
+    min_samples: Option<usize>,
+) -> Result<Vec<InferredSignature>, String> {
+    let (entries, arch, target_os) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let entries: Vec<TraceEntryData> = state_guard.trace_store.iter()
+            .filter(|e| target_address.as_ref().is_none_or(|t| &e.target_address == t))
+            .cloned()
+            .collect();
+        let info = state_guard.server_info.as_ref();
+        (
+            entries,
+            info.map(|i| i.arch.clone()).unwrap_or_default(),
+            info.map(|i| i.target_os.clone()).unwrap_or_default(),
+        )
+    };
+    let (int_registers, float_registers) = argument_registers(&arch, &target_os);
+    let regions = memory_regions::get_cached_regions(Some(state.inner()), false).await.unwrap_or_default();
+    let symbolizer = Symbolizer::load(state.inner()).await?;
+
+    let mut functions: BTreeMap<u64, Samples> = BTreeMap::new();
+    for (i, call) in entries.iter().enumerate().filter(|(_, e)| e.is_call) {
+        // The callee's first instruction directly follows the call, one level deeper
+        let Some(entry) = entries.get(i + 1).filter(|e| e.depth > call.depth && e.target_address == call.target_address) else {
+            continue;
+        };
+        let Ok(function) = u64::from_str_radix(entry.address.trim_start_matches("0x"), 16) else {
+            continue;
+        };
+        let body: Vec<&TraceEntryData> = entries[i + 1..].iter()
+            .take_while(|e| e.depth >= entry.depth && e.target_address == call.target_address)
+            .filter(|e| e.depth == entry.depth)
+            .collect();
+        let (read, memory_bases) = arguments_read(&body);
+        let int_count = int_registers.iter().rposition(|r| read.contains(*r)).map_or(0, |i| i + 1);
+        let float_count = float_registers.iter().rposition(|r| read.contains(*r)).map_or(0, |i| i + 1);
+
+        let registers = RegisterSet::from_json(&entry.registers);
+        let samples = functions.entry(function).or_default();
+        samples.calls += 1;
+        samples.counts.push((int_count, float_count));
+        for (index, register) in int_registers.iter().enumerate().take(int_count) {
+            let param_type = if memory_bases.contains(*register) {
+                "pointer"
+            } else {
+                match registers.get(register) {
+                    Some(value) => classify_integer(value, &regions),
+                    None => continue,
+                }
+            };
+            *samples.types.entry(index).or_default().entry(param_type).or_default() += 1;
+        }
+        for index in 0..float_count {
+            // Single precision when the callee only touches the s-register view
+            let single = body.iter().take(MAX_BODY_INSTRUCTIONS)
+                .any(|e| e.operands.to_lowercase().split(|c: char| !c.is_ascii_alphanumeric()).any(|t| t == format!("s{}", index)))
+                || body.iter().take(MAX_BODY_INSTRUCTIONS).any(|e| e.opcode.ends_with("ss"));
+            samples.float_width.insert(index, if single { "float" } else { "double" });
+        }
+    }
+
+    let min_samples = min_samples.unwrap_or(1).max(1);
+    let mut results = Vec::new();
+    for (function, samples) in functions.into_iter().filter(|(_, s)| s.calls >= min_samples) {
+        let Some(symbol) = symbolizer.resolve(function) else {
+            continue;
+        };
+        let int_count = samples.counts.iter().map(|c| c.0).max().unwrap_or(0);
+        let float_count = samples.counts.iter().map(|c| c.1).max().unwrap_or(0);
+        let agreeing = samples.counts.iter().filter(|c| c.0 == int_count && c.1 == float_count).count();
+
+        let mut parameters = Vec::new();
+        for (index, register) in int_registers.iter().enumerate().take(int_count) {
+            let votes = samples.types.get(&index);
+            let total: usize = votes.map(|v| v.values().sum()).unwrap_or(0);
+            let (param_type, count) = votes
+                .and_then(|v| v.iter().max_by_key(|(_, c)| **c).map(|(t, c)| (*t, *c)))
+                .unwrap_or(("long", 0));
+            parameters.push(InferredParameter {
+                index: parameters.len(),
+                register: register.to_string(),
+                param_type: param_type.to_string(),
+                confidence: confidence(count, total),
+            });
+        }
+        for (index, register) in float_registers.iter().enumerate().take(float_count) {
+            parameters.push(InferredParameter {
+                index: parameters.len(),
+                register: register.to_string(),
+                param_type: samples.float_width.get(&index).copied().unwrap_or("double").to_string(),
+                confidence: confidence(samples.calls, samples.calls),
+            });
+        }
+
+        results.push(InferredSignature {
+            target_os: target_os.clone(),
+            module_name: symbol.module_name.clone(),
+            function_offset: symbol.module_offset,
+            function_name: symbol.function_name.clone().filter(|_| symbol.function_offset == Some(0)),
+            parameters,
+            confidence: confidence(agreeing, samples.calls),
+            sample_count: samples.calls,
+            pushed_to_ghidra: false,
+            updated_at: String::new(),
+        });
+    }
+
+    db::run(move |conn| {
+        for signature in &results {
+            // Merge with earlier runs: keep whichever inference saw more calls
+            let previous: Option<i64> = conn.query_row(
+                "SELECT sample_count FROM inferred_signatures WHERE target_os = ?1 AND module_name = ?2 AND function_offset = ?3",
+                params![signature.target_os, signature.module_name, signature.function_offset as i64],
+                |row| row.get(0),
+            ).ok();
+            if previous.is_some_and(|count| count as usize > signature.sample_count) {
+                continue;
+            }
+            conn.execute(
+                "INSERT OR REPLACE INTO inferred_signatures
+                 (target_os, module_name, function_offset, function_name, parameters_json, confidence, sample_count, pushed_to_ghidra, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, datetime('now'))",
+                params![
+                    signature.target_os,
+                    signature.module_name,
+                    signature.function_offset as i64,
+                    signature.function_name,
+                    serde_json::to_string(&signature.parameters).map_err(|e| e.to_string())?,
+                    signature.confidence,
+                    signature.sample_count as i64,
+                ],
+            ).map_err(|e| e.to_string())?;
+        }
+        let stored = load_signatures(conn, &target_os, None)?;
+        Ok(results.into_iter()
+            .filter_map(|r| stored.iter().find(|s| s.module_name == r.module_name && s.function_offset == r.function_offset).cloned())
+            .collect())
+    }).await
+}
+
+#[tauri::command]
+pub async fn list_inferred_signatures(target_os: String, module_name: Option<String>) -> Result<Vec<InferredSignature>, String> {
+    db::run(move |conn| {
+        load_signatures(conn, &target_os, module_name.as_deref())
+    }).await
+}
+
+#[tauri::command]
+pub async fn delete_inferred_signature(target_os: String, module_name: String, function_offset: u64) -> Result<bool, String> {
+    db::run(move |conn| {
+        let deleted = conn.execute(
+            "DELETE FROM inferred_signatures WHERE target_os = ?1 AND module_name = ?2 AND function_offset = ?3",
+            params![target_os, module_name, function_offset as i64],
+        ).map_err(|e| e.to_string())?;
+        Ok(deleted > 0)
+    }).await
+}
+
+/// Apply an inferred signature to the function in the project's running
+/// Ghidra server (creating the function if Ghidra has none there)
+#[tauri::command]
+pub async fn push_signature_to_ghidra(
+    project_path: String,
+    target_os: String,
+    module_name: String,
+    function_offset: u64,
+) -> Result<SignaturePushResult, String> {
+    let signature = {
+        let (target_os, module_name) = (target_os.clone(), module_name.clone());
+        db::run(move |conn| {
+            load_signatures(conn, &target_os, Some(&module_name))?
+                .into_iter()
+                .find(|s| s.function_offset == function_offset)
+                .ok_or_else(|| format!("No inferred signature for {}+0x{:x}", module_name, function_offset))
+        }).await?
+    };
+    let port = {
+        let ports = GHIDRA_SERVER_PORTS.lock().map_err(|e| e.to_string())?;
+        ports.get(&project_path).copied()
+    };
+    let port = port.ok_or("Ghidra server not running for this project")?;
+
+    let types: Vec<&str> = signature.parameters.iter().map(|p| p.param_type.as_str()).collect();
+    let types_json = serde_json::to_string(&types).map_err(|e| e.to_string())?;
+    let url = reqwest::Url::parse_with_params(
+        &format!("http://127.0.0.1:{}/set_signature", port),
+        &[("offset", format!("0x{:x}", function_offset)), ("params", types_json)],
+    ).map_err(|e| e.to_string())?;
+    let result: SignaturePushResult = server_connection::local_client()
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ghidra server: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    if result.success {
+        db::run(move |conn| {
+            conn.execute(
+                "UPDATE inferred_signatures SET pushed_to_ghidra = 1 WHERE target_os = ?1 AND module_name = ?2 AND function_offset = ?3",
+                params![target_os, module_name, function_offset as i64],
+            ).map_err(|e| e.to_string())?;
+            // The cached decompilation predates the new signature
+            conn.execute(
+                "DELETE FROM ghidra_decompile_cache WHERE target_os = ?1 AND module_name = ?2 AND function_address = ?3",
+                params![target_os, module_name, format!("0x{:x}", function_offset)],
+            ).map_err(|e| e.to_string())?;
+            Ok(())
+        }).await?;
+    }
+    Ok(result)
+}
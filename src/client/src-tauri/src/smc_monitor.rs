@@ -0,0 +1,551 @@
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+use crate::state::{AppState, AppStateType};
+use crate::symbolizer::Symbolizer;
+use crate::{
+    coverage, db, event_bus, fnv1a, memory_regions, patches, read_memory_from_server, remove_watchpoint_on_server,
+    server_address, set_watchpoint_on_server, watchpoints,
+};
+
+const PAGE_SIZE: u64 = 0x1000;
+const READ_CHUNK: u64 = 0x10_0000;
+const DEFAULT_INTERVAL_MS: u64 = 1000;
+const MIN_INTERVAL_MS: u64 = 200;
+const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_EVENTS: usize = 10_000;
+// Pages kept verbatim after their first change, so later writes to them can
+// be located byte-exactly (and a watchpoint placed on the written word)
+const MAX_HOT_PAGES: usize = 256;
+// Owner of the writer watchpoints in the shared debug register budget
+const WATCHPOINT_OWNER: &str = "smc_monitor";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmcMonitorRequest {
+    #[serde(default)]
+    pub module_names: Option<Vec<String>>, // Modules to hash (default: all module images)
+    #[serde(default)]
+    pub include_anonymous: bool,           // Also hash anonymous executable regions (JIT code)
+    #[serde(default)]
+    pub interval_ms: Option<u64>,
+    #[serde(default)]
+    pub max_bytes: Option<u64>,            // Upper bound on hashed bytes per pass
+    #[serde(default)]
+    pub watch_writers: bool,               // Arm write watchpoints on changed code to record the writer PC
+}
+
+/// Executable bytes that changed between two hashing passes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmcEvent {
+    pub id: u64,
+    pub page: u64,
+    pub address: u64,                      // First changed byte (page start when the old bytes were not kept)
+    pub changed_bytes: Option<usize>,      // Known for pages that changed before
+    pub module_name: Option<String>,
+    pub module_offset: Option<u64>,
+    pub function_name: Option<String>,
+    pub function_offset: Option<u64>,      // Module offset of the containing function
+    pub detected_at: u64,
+    pub writer_pc: Option<u64>,
+    pub writer_thread_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmcMonitorStatus {
+    pub running: bool,
+    pub interval_ms: u64,
+    pub monitored_pages: usize,
+    pub scan_count: u64,
+    pub event_count: usize,
+    pub armed_watchpoints: Vec<u64>,
+    pub last_scan_at: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleFunction {
+    pub module_name: String,
+    pub function_address: String,
+    pub detected_at: String,
+}
+
+struct Monitor {
+    request: SmcMonitorRequest,
+    running: Arc<AtomicBool>,
+    interval_ms: u64,
+    hashes: HashMap<u64, u64>,
+    hot: HashMap<u64, Vec<u8>>,
+    masks: HashMap<u64, Vec<(usize, usize)>>, // Debugger-owned byte ranges masked in each page
+    events: Vec<SmcEvent>,
+    next_id: u64,
+    armed: Vec<u64>,                       // Watchpoint addresses (8-byte aligned)
+    disarms: u64,                          // Bumped by disarm_all so in-flight arming is dropped
+    seen_exceptions: HashSet<String>,
+    scan_count: u64,
+    last_scan_at: Option<u64>,
+    last_error: Option<String>,
+}
+
+impl Monitor {
+    fn status(&self) -> SmcMonitorStatus {
+        SmcMonitorStatus {
+            running: self.running.load(Ordering::Relaxed),
+            interval_ms: self.interval_ms,
+            monitored_pages: self.hashes.len(),
+            scan_count: self.scan_count,
+            event_count: self.events.len(),
+            armed_watchpoints: self.armed.clone(),
+            last_scan_at: self.last_scan_at,
+            last_error: self.last_error.clone(),
+        }
+    }
+}
+
+static MONITOR: Lazy<Mutex<Monitor>> = Lazy::new(|| {
+    Mutex::new(Monitor {
+        request: SmcMonitorRequest::default(),
+        running: Arc::new(AtomicBool::new(false)),
+        interval_ms: DEFAULT_INTERVAL_MS,
+        hashes: HashMap::new(),
+        hot: HashMap::new(),
+        masks: HashMap::new(),
+        events: Vec::new(),
+        next_id: 1,
+        armed: Vec::new(),
+        disarms: 0,
+        seen_exceptions: HashSet::new(),
+        scan_count: 0,
+        last_scan_at: None,
+        last_error: None,
+    })
+});
+
+// Held while watchpoints are armed or removed, so stopping cannot miss
+// watchpoints a pass is still arming
+static ARMING: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+fn lock_monitor() -> Result<std::sync::MutexGuard<'static, Monitor>, String> {
+    MONITOR.lock().map_err(|e| e.to_string())
+}
+
+fn normalize_address(address: &str) -> String {
+    match u64::from_str_radix(address.trim().trim_start_matches("0x"), 16) {
+        Ok(value) => format!("0x{:x}", value),
+        Err(_) => address.to_string(),
+    }
+}
+
+/// Create the stale-function table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ghidra_stale_functions (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            function_address TEXT NOT NULL,
+            detected_at TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name, function_address)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Drop the stale flag once a function has been decompiled again
+pub fn clear_stale_function(conn: &Connection, target_os: &str, module_name: &str, function_address: &str) {
+    let _ = conn.execute(
+        "DELETE FROM ghidra_stale_functions WHERE target_os = ?1 AND module_name = ?2 AND function_address = ?3",
+        params![target_os, module_name, normalize_address(function_address)],
+    );
+}
+
+async fn mark_stale(target_os: &str, events: &[SmcEvent]) {
+    let stale: Vec<(String, u64)> = events.iter()
+        .filter_map(|event| Some((event.module_name.clone()?, event.function_offset?)))
+        .collect();
+    if stale.is_empty() {
+        return;
+    }
+    let target_os = target_os.to_string();
+    let _ = db::run(move |conn| {
+        for (module_name, offset) in stale {
+            let _ = conn.execute(
+                "INSERT OR REPLACE INTO ghidra_stale_functions (target_os, module_name, function_address, detected_at)
+                 VALUES (?1, ?2, ?3, datetime('now'))",
+                params![target_os, module_name, format!("0x{:x}", offset)],
+            );
+        }
+        Ok(())
+    }).await;
+}
+
+/// Code bytes the debugger writes itself: software breakpoints (the user's
+/// and coverage's) and applied patches, as sorted [start, end) ranges
+async fn debugger_owned_ranges(state: &AppStateType) -> Result<Vec<(u64, u64)>, String> {
+    let (target_os, arch, modules, breakpoints) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let info = state_guard.server_info.as_ref();
+        (
+            info.map(|i| i.target_os.clone()).unwrap_or_default(),
+            info.map(|i| i.arch.clone()).unwrap_or_default(),
+            state_guard.attached_modules.clone(),
+            state_guard.software_breakpoints.clone(),
+        )
+    };
+    // int3 is one byte; BRK and the other fixed-width encodings are four
+    let breakpoint_len = if matches!(arch.as_str(), "x86_64" | "x86") { 1 } else { 4 };
+    let mut ranges: Vec<(u64, u64)> = breakpoints.iter()
+        .filter_map(|a| u64::from_str_radix(a.trim().trim_start_matches("0x"), 16).ok())
+        .chain(coverage::installed_breakpoints())
+        .map(|a| (a, a.saturating_add(breakpoint_len)))
+        .collect();
+    ranges.extend(patches::applied_ranges(&target_os, &modules).await?);
+    ranges.sort_unstable();
+    Ok(ranges)
+}
+
+/// Page-relative ranges of `owned` that fall inside the page
+fn page_mask(owned: &[(u64, u64)], page: u64, len: usize) -> Vec<(usize, usize)> {
+    let page_end = page + len as u64;
+    owned.iter()
+        .filter(|(start, end)| *start < page_end && *end > page)
+        .map(|(start, end)| ((start.max(&page) - page) as usize, (end.min(&page_end) - page) as usize))
+        .collect()
+}
+
+/// Compare one page against the baseline; returns (first changed byte, changed byte count).
+/// Bytes under `mask` are zeroed first, so breakpoints and patches are not reported.
+fn compare_page(monitor: &mut Monitor, page: u64, data: &[u8], mask: &[(usize, usize)]) -> Option<(u64, Option<usize>)> {
+    let masked;
+    let data = if mask.is_empty() {
+        data
+    } else {
+        let mut copy = data.to_vec();
+        for (start, end) in mask {
+            copy[*start..*end].fill(0);
+        }
+        masked = copy;
+        &masked
+    };
+    let mask_changed = if mask.is_empty() {
+        monitor.masks.remove(&page).is_some()
+    } else {
+        monitor.masks.insert(page, mask.to_vec()).as_deref() != Some(mask)
+    };
+    let hash = fnv1a(data);
+    let previous = monitor.hashes.insert(page, hash)?;
+    if previous == hash {
+        return None;
+    }
+    if mask_changed {
+        // A breakpoint or patch was added or removed: the page gets a new baseline
+        if let Some(old) = monitor.hot.get_mut(&page) {
+            *old = data.to_vec();
+        }
+        return None;
+    }
+    let change = match monitor.hot.get(&page) {
+        Some(old) => {
+            let diffs: Vec<usize> = old.iter().zip(data).enumerate()
+                .filter(|(_, (a, b))| a != b)
+                .map(|(i, _)| i)
+                .collect();
+            (page + diffs.first().copied().unwrap_or(0) as u64, Some(diffs.len()))
+        }
+        None => (page, None),
+    };
+    if monitor.hot.contains_key(&page) || monitor.hot.len() < MAX_HOT_PAGES {
+        monitor.hot.insert(page, data.to_vec());
+    }
+    Some(change)
+}
+
+/// Fill writer PCs from watchpoint hits on the armed addresses
+fn collect_writers(monitor: &mut Monitor, state: &AppStateType) -> Result<(), String> {
+    if monitor.armed.is_empty() {
+        return Ok(());
+    }
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    for exception in state_guard.exception_store.iter().filter(|e| e.exception_type == "watchpoint") {
+        let Some(memory_address) = exception.memory_address else {
+            continue;
+        };
+        let Some(&armed) = monitor.armed.iter().find(|a| (**a..**a + 8).contains(&memory_address)) else {
+            continue;
+        };
+        let key = format!("{}:{}:{}", exception.timestamp, exception.address, memory_address);
+        if !monitor.seen_exceptions.insert(key) {
+            continue;
+        }
+        let pc = exception.pc
+            .or_else(|| u64::from_str_radix(exception.address.trim_start_matches("0x"), 16).ok());
+        let page = armed & !(PAGE_SIZE - 1);
+        if let Some(event) = monitor.events.iter_mut().rev().find(|e| e.page == page && e.writer_pc.is_none()) {
+            event.writer_pc = pc;
+            event.writer_thread_id = exception.thread_id;
+        }
+    }
+    Ok(())
+}
+
+/// One hashing pass over the monitored executable regions; returns new events
+async fn scan_once(state: &AppStateType) -> Result<Vec<SmcEvent>, String> {
+    let (host, port) = server_address()?;
+    let request = lock_monitor()?.request.clone();
+    let max_bytes = request.max_bytes.unwrap_or(DEFAULT_MAX_BYTES);
+
+    let regions = memory_regions::get_cached_regions(Some(state), true).await?;
+    let owned = debugger_owned_ranges(state).await?;
+    let disarms = lock_monitor()?.disarms;
+    let mut budget = max_bytes;
+    let mut changes = Vec::new();
+    for region in regions.iter().filter(|r| r.executable && r.readable) {
+        let selected = match &region.module_name {
+            Some(name) => request.module_names.as_ref().is_none_or(|names| names.contains(name)),
+            None => request.include_anonymous && region.mapped_file.is_none(),
+        };
+        if !selected || budget == 0 {
+            continue;
+        }
+        let end = region.base + region.size.min(budget);
+        budget -= end - region.base;
+
+        let mut chunk_start = region.base;
+        while chunk_start < end {
+            let chunk_size = (end - chunk_start).min(READ_CHUNK);
+            let Ok(data) = read_memory_from_server(&host, port, chunk_start, chunk_size as usize).await else {
+                chunk_start += chunk_size;
+                continue;
+            };
+            let mut monitor = lock_monitor()?;
+            for (i, page_data) in data.chunks(PAGE_SIZE as usize).enumerate() {
+                let page = chunk_start + i as u64 * PAGE_SIZE;
+                let mask = page_mask(&owned, page, page_data.len());
+                if let Some(change) = compare_page(&mut monitor, page, page_data, &mask) {
+                    changes.push((page, change));
+                }
+            }
+            drop(monitor);
+            chunk_start += chunk_size;
+        }
+    }
+
+    let symbolizer = Symbolizer::load(state).await?;
+    let target_os = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?
+        .server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default();
+
+    let (new_events, to_arm) = {
+        let mut monitor = lock_monitor()?;
+        let now = AppState::current_timestamp();
+        let mut new_events = Vec::new();
+        for (page, (address, changed_bytes)) in changes {
+            let symbol = symbolizer.resolve(address);
+            let event = SmcEvent {
+                id: monitor.next_id,
+                page,
+                address,
+                changed_bytes,
+                module_name: symbol.as_ref().map(|s| s.module_name.clone()),
+                module_offset: symbol.as_ref().map(|s| s.module_offset),
+                function_name: symbol.as_ref().and_then(|s| s.function_name.clone()),
+                function_offset: symbol.as_ref()
+                    .and_then(|s| s.function_offset.map(|delta| s.module_offset - delta)),
+                detected_at: now,
+                writer_pc: None,
+                writer_thread_id: None,
+            };
+            monitor.next_id += 1;
+            new_events.push(event);
+        }
+        monitor.events.extend(new_events.iter().cloned());
+        let overflow = monitor.events.len().saturating_sub(MAX_EVENTS);
+        monitor.events.drain(..overflow);
+
+        // Watch the word that was written; the next write to it reports the writer
+        let mut to_arm = Vec::new();
+        if request.watch_writers {
+            for event in new_events.iter().filter(|e| e.changed_bytes.is_some()) {
+                let address = event.address & !7;
+                if monitor.armed.len() + to_arm.len() < watchpoints::TOOL_WATCHPOINTS_DEFAULT
+                    && !monitor.armed.contains(&address) && !to_arm.contains(&address)
+                {
+                    to_arm.push(address);
+                }
+            }
+        }
+        collect_writers(&mut monitor, state)?;
+        monitor.scan_count += 1;
+        monitor.last_scan_at = Some(now);
+        monitor.last_error = None;
+        (new_events, to_arm)
+    };
+
+    if !to_arm.is_empty() {
+        let _arming = ARMING.lock().await;
+        // Skip arming when the monitor was stopped while this pass ran
+        if lock_monitor()?.disarms == disarms {
+            let granted = watchpoints::reserve(state, WATCHPOINT_OWNER, to_arm.len())?;
+            for address in to_arm.into_iter().take(granted) {
+                if set_watchpoint_on_server(&host, port, address, 8, "w").await.is_ok() {
+                    lock_monitor()?.armed.push(address);
+                } else {
+                    watchpoints::release(WATCHPOINT_OWNER, 1);
+                }
+            }
+        }
+    }
+
+    mark_stale(&target_os, &new_events).await;
+    for event in &new_events {
+        event_bus::publish(event_bus::CHANNEL_SMC, None, event);
+    }
+    Ok(new_events)
+}
+
+async fn disarm_all() -> Result<(), String> {
+    let _arming = ARMING.lock().await;
+    let armed = {
+        let mut monitor = lock_monitor()?;
+        monitor.disarms += 1;
+        std::mem::take(&mut monitor.armed)
+    };
+    if armed.is_empty() {
+        return Ok(());
+    }
+    watchpoints::release(WATCHPOINT_OWNER, armed.len());
+    let (host, port) = server_address()?;
+    for address in armed {
+        let _ = remove_watchpoint_on_server(&host, port, address).await;
+    }
+    Ok(())
+}
+
+/// Start periodic hashing of executable pages. The first pass records the
+/// baseline; later passes report changed pages as events.
+#[tauri::command]
+pub async fn start_smc_monitor(app: AppHandle, request: SmcMonitorRequest) -> Result<SmcMonitorStatus, String> {
+    server_address()?;
+    let interval_ms = request.interval_ms.unwrap_or(DEFAULT_INTERVAL_MS).max(MIN_INTERVAL_MS);
+    let running = Arc::new(AtomicBool::new(true));
+    let status = {
+        let mut monitor = lock_monitor()?;
+        monitor.running.store(false, Ordering::Relaxed);
+        monitor.running = running.clone();
+        monitor.interval_ms = interval_ms;
+        monitor.request = request;
+        monitor.hashes.clear();
+        monitor.hot.clear();
+        monitor.masks.clear();
+        monitor.status()
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+        while running.load(Ordering::Relaxed) {
+            interval.tick().await;
+            let state = app.state::<AppStateType>();
+            let result = scan_once(state.inner()).await;
+            if !running.load(Ordering::Relaxed) {
+                break;
+            }
+            if let Err(e) = result {
+                if let Ok(mut monitor) = MONITOR.lock() {
+                    monitor.last_error = Some(e);
+                }
+            }
+        }
+    });
+
+    Ok(status)
+}
+
+/// Stop hashing and remove the writer watchpoints; events are kept
+#[tauri::command]
+pub async fn stop_smc_monitor() -> Result<SmcMonitorStatus, String> {
+    lock_monitor()?.running.store(false, Ordering::Relaxed);
+    disarm_all().await?;
+    Ok(lock_monitor()?.status())
+}
+
+/// Run a single pass now (also works without a running monitor)
+#[tauri::command]
+pub async fn scan_smc_now(
+    state: tauri::State<'_, AppStateType>,
+    request: Option<SmcMonitorRequest>,
+) -> Result<Vec<SmcEvent>, String> {
+    if let Some(request) = request {
+        let mut monitor = lock_monitor()?;
+        if !monitor.running.load(Ordering::Relaxed) {
+            monitor.request = request;
+        }
+    }
+    scan_once(state.inner()).await
+}
+
+#[tauri::command]
+pub fn get_smc_status() -> Result<SmcMonitorStatus, String> {
+    Ok(lock_monitor()?.status())
+}
+
+/// Events with an id greater than `since_id`, oldest first
+#[tauri::command]
+pub fn get_smc_events(
+    state: tauri::State<'_, AppStateType>,
+    since_id: Option<u64>,
+    limit: Option<usize>,
+) -> Result<Vec<SmcEvent>, String> {
+    let mut monitor = lock_monitor()?;
+    collect_writers(&mut monitor, state.inner())?;
+    let events: Vec<SmcEvent> = monitor.events.iter()
+        .filter(|e| since_id.is_none_or(|id| e.id > id))
+        .cloned()
+        .collect();
+    let start = events.len().saturating_sub(limit.unwrap_or(usize::MAX));
+    Ok(events[start..].to_vec())
+}
+
+/// Drop recorded events and the baseline (the next pass starts a new one)
+#[tauri::command]
+pub fn clear_smc_events() -> Result<(), String> {
+    let mut monitor = lock_monitor()?;
+    monitor.events.clear();
+    monitor.hashes.clear();
+    monitor.hot.clear();
+    monitor.masks.clear();
+    monitor.seen_exceptions.clear();
+    Ok(())
+}
+
+/// Functions whose code changed after they were analyzed
+#[tauri::command]
+pub async fn get_stale_functions(target_os: String, module_name: Option<String>) -> Result<Vec<StaleFunction>, String> {
+    db::run(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT module_name, function_address, detected_at FROM ghidra_stale_functions
+             WHERE target_os = ?1 AND (?2 IS NULL OR module_name = ?2)
+             ORDER BY detected_at DESC",
+        ).map_err(|e| e.to_string())?;
+        let rows = stmt.query_map(params![target_os, module_name], |row| {
+            Ok(StaleFunction {
+                module_name: row.get(0)?,
+                function_address: row.get(1)?,
+                detected_at: row.get(2)?,
+            })
+        }).map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+pub async fn clear_stale_functions(target_os: String, module_name: Option<String>) -> Result<usize, String> {
+    db::run(move |conn| {
+        conn.execute(
+            "DELETE FROM ghidra_stale_functions WHERE target_os = ?1 AND (?2 IS NULL OR module_name = ?2)",
+            params![target_os, module_name],
+        ).map_err(|e| e.to_string())
+    }).await
+}
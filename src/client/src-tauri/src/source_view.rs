@@ -0,0 +1,153 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::state::AppStateType;
+use crate::symbolizer::Symbolizer;
+use crate::{db, debug_symbols};
+
+/// Rewrites a path recorded in debug info (build machine) to a local one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourcePathRule {
+    pub from: String,                    // Prefix of the recorded path
+    pub to: String,                      // Local directory that replaces it
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedSourceLine {
+    pub address: u64,
+    pub module_name: String,
+    pub module_offset: u64,
+    pub function_name: Option<String>,
+    pub file: String,                    // As recorded in the debug info
+    pub local_path: Option<String>,      // Existing local file after path mapping
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceFileEntry {
+    pub file: String,
+    pub local_path: Option<String>,
+    pub line_count: usize,               // Distinct lines with code
+}
+
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS source_path_rules (
+            idx INTEGER PRIMARY KEY,
+            from_prefix TEXT NOT NULL,
+            to_prefix TEXT NOT NULL
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn stored_rules(conn: &Connection) -> Result<Vec<SourcePathRule>, String> {
+    let mut stmt = conn.prepare("SELECT from_prefix, to_prefix FROM source_path_rules ORDER BY idx")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], |row| Ok(SourcePathRule { from: row.get(0)?, to: row.get(1)? }))
+        .map_err(|e| e.to_string())?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+/// First rule whose prefix matches and whose result exists, else the recorded
+/// path when it exists locally. Separators are normalized, and Windows drive
+/// paths compare case-insensitively.
+fn map_path(file: &str, rules: &[SourcePathRule]) -> Option<String> {
+    let normalized = file.replace('\\', "/");
+    let windows = normalized.as_bytes().get(1) == Some(&b':');
+    for rule in rules {
+        let from = rule.from.replace('\\', "/");
+        let from = from.trim_end_matches('/');
+        if from.is_empty() || normalized.len() < from.len() || !normalized.is_char_boundary(from.len()) {
+            continue;
+        }
+        let (head, rest) = normalized.split_at(from.len());
+        let matches = if windows { head.eq_ignore_ascii_case(from) } else { head == from };
+        if !matches || !(rest.is_empty() || rest.starts_with('/')) {
+            continue;
+        }
+        let mapped = Path::new(&rule.to).join(rest.trim_start_matches('/'));
+        if mapped.is_file() {
+            return Some(mapped.to_string_lossy().into_owned());
+        }
+    }
+    Path::new(file).is_file().then(|| file.to_string())
+}
+
+#[tauri::command]
+pub async fn get_source_path_rules() -> Result<Vec<SourcePathRule>, String> {
+    db::run(stored_rules).await
+}
+
+/// Replace the path mapping rules; they are tried in order
+#[tauri::command]
+pub async fn set_source_path_rules(rules: Vec<SourcePathRule>) -> Result<Vec<SourcePathRule>, String> {
+    db::run(move |conn| {
+        let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        tx.execute("DELETE FROM source_path_rules", []).map_err(|e| e.to_string())?;
+        for (idx, rule) in rules.iter().filter(|r| !r.from.trim().is_empty()).enumerate() {
+            tx.execute(
+                "INSERT INTO source_path_rules (idx, from_prefix, to_prefix) VALUES (?1, ?2, ?3)",
+                params![idx as i64, rule.from.trim(), rule.to.trim()],
+            ).map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+        stored_rules(conn)
+    }).await
+}
+
+/// Source line of an address from the loaded debug symbols (None without
+/// a line table entry), for highlighting the current line while stepping
+#[tauri::command]
+pub async fn resolve_source_line(
+    state: tauri::State<'_, AppStateType>,
+    address: u64,
+) -> Result<Option<ResolvedSourceLine>, String> {
+    let symbolizer = Symbolizer::load(state.inner()).await?;
+    let Some(symbol) = symbolizer.resolve(address) else {
+        return Ok(None);
+    };
+    let Some(location) = symbol.source else {
+        return Ok(None);
+    };
+    let rules = db::run(stored_rules).await?;
+    Ok(Some(ResolvedSourceLine {
+        address,
+        module_name: symbol.module_name,
+        module_offset: symbol.module_offset,
+        function_name: symbol.function_name,
+        local_path: map_path(&location.file, &rules),
+        file: location.file,
+        line: location.line,
+        column: location.column,
+    }))
+}
+
+/// Source files named by a module's line table (target_os defaults to the
+/// connected target's)
+#[tauri::command]
+pub async fn list_source_files(
+    state: tauri::State<'_, AppStateType>,
+    module_name: String,
+    target_os: Option<String>,
+) -> Result<Vec<SourceFileEntry>, String> {
+    let target_os = match target_os {
+        Some(target_os) => target_os,
+        None => {
+            let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+            state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default()
+        }
+    };
+    let rules = db::run(stored_rules).await?;
+    crate::symbolizer::preload(&target_os, vec![module_name.clone()]).await;
+    let mut files: Vec<SourceFileEntry> = debug_symbols::source_files(&target_os, &module_name)
+        .into_iter()
+        .filter(|(_, line_count)| *line_count > 0)
+        .map(|(file, line_count)| SourceFileEntry { local_path: map_path(&file, &rules), file, line_count })
+        .collect();
+    files.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok(files)
+}
@@ -3,9 +3,14 @@ use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 use tauri::{AppHandle, Manager, Emitter};
 
+use crate::event_bus;
+
+// app_settings key of the dbgsrv auth token
+pub const AUTH_TOKEN_SETTING: &str = "auth-token";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExceptionData {
-    pub exception_type: String, // "watchpoint", "breakpoint", "singlestep"
+    pub exception_type: String, // "watchpoint", "breakpoint", "singlestep", "frida_hook"
     pub address: String,
     pub instruction: Option<String>,
     pub timestamp: String,
@@ -17,6 +22,10 @@ pub struct ExceptionData {
     pub bytecode: Option<String>,
     pub opcode: Option<String>,
     pub pc: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_timestamp: Option<u64>, // `timestamp` on the local clock (ms), once the target clock is synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hook: Option<crate::frida::FridaHookHit>, // Set for "frida_hook" exceptions
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +43,8 @@ pub struct TraceEntryData {
     pub timestamp: u64,
     pub library_expression: Option<String>,
     pub target_address: String, // trace session identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_timestamp: Option<u64>, // `timestamp` on the local clock, once the target clock is synced
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -430,6 +441,33 @@ pub async fn update_app_state(
         }
     }
 
+    // The auth token is kept in app_settings (sealed when encrypted), not the webview
+    if let Some((_, value)) = changed_fields.iter().find(|(field, _)| field == "authToken") {
+        let token = value.as_str().map(|s| s.to_string());
+        if let Err(e) = crate::secure_store::store_setting(AUTH_TOKEN_SETTING, token).await {
+            eprintln!("Failed to store auth token: {}", e);
+        }
+    }
+
+    // A new module set may contain modules with stored breakpoints
+    if changed_fields.iter().any(|(field, value)| field == "attachedModules" && value.as_array().is_some_and(|m| !m.is_empty())) {
+        let app = app.clone();
+        tokio::spawn(async move {
+            let state = app.state::<AppStateType>();
+            match crate::breakpoints::restore(state.inner()).await {
+                Ok(results) if !results.is_empty() => {
+                    for window in app.webview_windows().values() {
+                        if let Err(e) = window.emit("breakpoints-restored", &results) {
+                            eprintln!("Failed to emit breakpoints-restored event to window: {}", e);
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to restore breakpoints: {}", e),
+            }
+        });
+    }
+
     for (field, value) in changed_fields {
         let event = StateUpdateEvent {
             field: field.clone(),
@@ -491,12 +529,35 @@ pub async fn add_exceptions(
     state: tauri::State<'_, AppStateType>,
     exceptions: Vec<ExceptionData>
 ) -> Result<(), String> {
+    // Exceptions an ignore rule matches, and conditional breakpoints whose
+    // condition is false, never reach the store
+    let exceptions = crate::native_trace::divert_exceptions(exceptions);
+    let exceptions = crate::coverage::divert_exceptions(state.inner(), exceptions).await;
+    let exceptions = crate::exception_rules::filter_exceptions(&app, state.inner(), exceptions).await;
+    let mut exceptions = crate::breakpoints::filter_exceptions(state.inner(), exceptions).await;
+    if exceptions.is_empty() {
+        return Ok(());
+    }
+    
     {
         let mut state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        crate::watchpoints::tag_exceptions(&mut state_guard, &mut exceptions);
+        crate::threads::observe_exceptions(&state_guard, &exceptions);
+        for exception in exceptions.iter_mut() {
+            exception.local_timestamp = crate::clock_sync::parse_iso_timestamp(&exception.timestamp)
+                .and_then(crate::clock_sync::local_timestamp);
+        }
         state_guard.exception_store.extend(exceptions.clone());
         state_guard.touch();
     }
     
+    if event_bus::is_subscribed(event_bus::CHANNEL_EXCEPTIONS) {
+        for exception in &exceptions {
+            event_bus::publish(event_bus::CHANNEL_EXCEPTIONS, None, exception);
+        }
+        return Ok(());
+    }
+    
     for window in app.webview_windows().values() {
         if let Err(e) = window.emit("exceptions-added", &exceptions) {
             eprintln!("Failed to emit exceptions-added event to window: {}", e);
@@ -659,6 +720,11 @@ pub async fn add_trace_entry(
     state: tauri::State<'_, AppStateType>,
     mut entry: TraceEntryData,
 ) -> Result<(), String> {
+    if let Ok(symbolizer) = crate::symbolizer::Symbolizer::load(state.inner()).await {
+        symbolizer.annotate_trace_entry(&mut entry);
+    }
+    entry.local_timestamp = crate::clock_sync::local_timestamp(entry.timestamp);
+    
     let session_complete;
     let current_count;
     let total_count;
@@ -718,16 +784,21 @@ pub async fn add_trace_entry(
         return Ok(());
     }
     
-    for window in app.webview_windows().values() {
-        if let Err(e) = window.emit("trace-entry-added", &entry) {
-            eprintln!("Failed to emit trace-entry-added event to window: {}", e);
-        }
-        
-        if let Err(e) = window.emit("trace-progress", &serde_json::json!({
-            "current": current_count,
-            "total": total_count
-        })) {
-            eprintln!("Failed to emit trace-progress event to window: {}", e);
+    let progress = serde_json::json!({
+        "current": current_count,
+        "total": total_count
+    });
+    if event_bus::publish(event_bus::CHANNEL_TRACE, None, &entry) {
+        event_bus::publish(event_bus::CHANNEL_TRACE_PROGRESS, None, &progress);
+    } else {
+        for window in app.webview_windows().values() {
+            if let Err(e) = window.emit("trace-entry-added", &entry) {
+                eprintln!("Failed to emit trace-entry-added event to window: {}", e);
+            }
+            
+            if let Err(e) = window.emit("trace-progress", &progress) {
+                eprintln!("Failed to emit trace-progress event to window: {}", e);
+            }
         }
     }
     
@@ -748,8 +819,17 @@ pub async fn add_trace_entry(
 pub async fn add_trace_entries_batch(
     app: AppHandle,
     state: tauri::State<'_, AppStateType>,
-    entries: Vec<TraceEntryData>,
+    mut entries: Vec<TraceEntryData>,
 ) -> Result<(), String> {
+    if let Ok(symbolizer) = crate::symbolizer::Symbolizer::load(state.inner()).await {
+        for entry in entries.iter_mut() {
+            symbolizer.annotate_trace_entry(entry);
+        }
+    }
+    for entry in entries.iter_mut() {
+        entry.local_timestamp = crate::clock_sync::local_timestamp(entry.timestamp);
+    }
+    
     let session_complete;
     let current_count;
     let total_count;
@@ -812,16 +892,24 @@ pub async fn add_trace_entries_batch(
     }
     
     if !added_entries.is_empty() {
-        for window in app.webview_windows().values() {
-            if let Err(e) = window.emit("trace-entries-added", &added_entries) {
-                eprintln!("Failed to emit trace-entries-added event to window: {}", e);
+        let progress = serde_json::json!({
+            "current": current_count,
+            "total": total_count
+        });
+        if event_bus::is_subscribed(event_bus::CHANNEL_TRACE) {
+            for entry in &added_entries {
+                event_bus::publish(event_bus::CHANNEL_TRACE, None, entry);
             }
-            
-            if let Err(e) = window.emit("trace-progress", &serde_json::json!({
-                "current": current_count,
-                "total": total_count
-            })) {
-                eprintln!("Failed to emit trace-progress event to window: {}", e);
+            event_bus::publish(event_bus::CHANNEL_TRACE_PROGRESS, None, &progress);
+        } else {
+            for window in app.webview_windows().values() {
+                if let Err(e) = window.emit("trace-entries-added", &added_entries) {
+                    eprintln!("Failed to emit trace-entries-added event to window: {}", e);
+                }
+                
+                if let Err(e) = window.emit("trace-progress", &progress) {
+                    eprintln!("Failed to emit trace-progress event to window: {}", e);
+                }
             }
         }
     }
@@ -0,0 +1,476 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::state::{AppStateType, ModuleInfo};
+use crate::{db, read_memory_from_server, value_codec, SERVER_CONFIG};
+
+/// One field of a user-defined struct layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: String,           // int8..uint64, float, double, bool, pointer, string, utf16, bytes, or a struct name
+    #[serde(default)]
+    pub offset: Option<u64>,          // Default: directly after the previous field
+    #[serde(default)]
+    pub count: Option<usize>,         // Array length
+    #[serde(default)]
+    pub size: Option<usize>,          // Byte length for string/utf16/bytes
+    #[serde(default)]
+    pub pointee: Option<String>,      // For pointers: type that is followed and dissected
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub size: Option<usize>,          // Default: end of the last field
+    pub fields: Vec<StructField>,
+}
+
+/// JSON accepted by dissect_memory: a single struct, or a root name plus the
+/// structs it references
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum StructDefinitionInput {
+    Set { root: String, structs: Vec<StructDefinition> },
+    Single(StructDefinition),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointerTarget {
+    pub address: u64,
+    pub module_name: Option<String>,
+    pub module_offset: Option<u64>,
+    pub readable: bool,
+}
+
+/// Node of the typed tree returned by dissect_memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DissectedNode {
+    pub name: String,
+    pub field_type: String,
+    pub address: u64,
+    pub offset: u64,                  // Offset from the enclosing struct
+    pub size: usize,
+    pub raw: String,                  // Hex bytes
+    pub value: Option<String>,        // Formatted value for primitives
+    pub pointer_target: Option<PointerTarget>,
+    pub children: Vec<DissectedNode>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredStructDefinition {
+    pub target_os: String,
+    pub module_name: String,          // Empty for definitions not tied to a module
+    pub name: String,
+    pub definition_json: String,
+    pub updated_at: String,
+}
+
+const DEFAULT_MAX_DEPTH: u32 = 3;
+// Arrays larger than this are truncated in the tree
+const MAX_ARRAY_ELEMENTS: usize = 256;
+const MAX_STRUCT_SIZE: usize = 1024 * 1024;
+
+fn primitive_size(field_type: &str, pointer_size: usize) -> Option<usize> {
+    Some(match field_type {
+        "int8" | "uint8" | "bool" => 1,
+        "int16" | "uint16" => 2,
+        "int32" | "uint32" | "float" => 4,
+        "int64" | "uint64" | "double" => 8,
+        "pointer" => pointer_size,
+        other => return value_codec::data_size(other),
+    })
+}
+
+fn format_primitive(bytes: &[u8], field_type: &str) -> Option<String> {
+    Some(match field_type {
+        "int8" => (*bytes.first()? as i8).to_string(),
+        "uint8" => bytes.first()?.to_string(),
+        "bool" => (*bytes.first()? != 0).to_string(),
+        "int16" => i16::from_le_bytes(bytes.get(..2)?.try_into().ok()?).to_string(),
+        "uint16" => u16::from_le_bytes(bytes.get(..2)?.try_into().ok()?).to_string(),
+        "int32" => i32::from_le_bytes(bytes.get(..4)?.try_into().ok()?).to_string(),
+        "uint32" => u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?).to_string(),
+        "int64" => i64::from_le_bytes(bytes.get(..8)?.try_into().ok()?).to_string(),
+        "uint64" => u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?).to_string(),
+        "float" => f32::from_le_bytes(bytes.get(..4)?.try_into().ok()?).to_string(),
+        "double" => f64::from_le_bytes(bytes.get(..8)?.try_into().ok()?).to_string(),
+        "string" => {
+            let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+            String::from_utf8_lossy(&bytes[..end]).to_string()
+        }
+        "utf16" => {
+            let units: Vec<u16> = bytes.chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .take_while(|&u| u != 0)
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        other => return value_codec::ValueType::parse(other).and_then(|_| value_codec::decode(other, bytes).ok()),
+    })
+}
+
+struct Dissector {
+    host: String,
+    port: u16,
+    pointer_size: usize,
+    max_depth: u32,
+    modules: Vec<ModuleInfo>,
+    structs: HashMap<String, StructDefinition>,
+}
+
+impl Dissector {
+    /// Size of one element of a field
+    fn element_size(&self, field: &StructField, depth: u32) -> Result<usize, String> {
+        if let Some(size) = primitive_size(&field.field_type, self.pointer_size) {
+            return Ok(size);
+        }
+        match field.field_type.as_str() {
+            "string" | "bytes" => Ok(field.size.unwrap_or(1)),
+            "utf16" => Ok(field.size.unwrap_or(2)),
+            name => {
+                let def = self.structs.get(name).ok_or(format!("Unknown type: {}", name))?;
+                self.struct_size(def, depth + 1)
+            }
+        }
+    }
+
+    /// Field offsets (explicit or sequential) and the struct size
+    fn layout(&self, def: &StructDefinition, depth: u32) -> Result<(Vec<u64>, usize), String> {
+        if depth > 32 {
+            return Err(format!("Struct {} nests too deeply (recursive by value?)", def.name));
+        }
+        let mut offsets = Vec::with_capacity(def.fields.len());
+        let mut cursor = 0u64;
+        let mut end = 0u64;
+        for field in &def.fields {
+            let offset = field.offset.unwrap_or(cursor);
+            // Sizes, counts and offsets come from user JSON
+            cursor = (self.element_size(field, depth)? as u64)
+                .checked_mul(field.count.unwrap_or(1) as u64)
+                .and_then(|total| offset.checked_add(total))
+                .ok_or_else(|| format!("Field {}.{} is too large", def.name, field.name))?;
+            offsets.push(offset);
+            end = end.max(cursor);
+        }
+        let size = def.size.unwrap_or(0).max(usize::try_from(end).unwrap_or(usize::MAX));
+        if size > MAX_STRUCT_SIZE {
+            return Err(format!("Struct {} is too large ({} bytes)", def.name, size));
+        }
+        Ok((offsets, size))
+    }
+
+    fn struct_size(&self, def: &StructDefinition, depth: u32) -> Result<usize, String> {
+        self.layout(def, depth).map(|(_, size)| size)
+    }
+
+    fn resolve_module(&self, address: u64) -> (Option<String>, Option<u64>) {
+        match self.modules.iter().find(|m| address >= m.base && address < m.base.saturating_add(m.size)) {
+            Some(m) => (Some(m.modulename.clone()), Some(address - m.base)),
+            None => (None, None),
+        }
+    }
+
+    /// Dissect one element (primitive, string, nested struct or pointer) from `data`
+    fn dissect_element<'a>(
+        &'a self,
+        field: &'a StructField,
+        name: String,
+        address: u64,
+        offset: u64,
+        data: &'a [u8],
+        depth: u32,
+    ) -> Pin<Box<dyn Future<Output = DissectedNode> + Send + 'a>> {
+        Box::pin(async move {
+            let size = data.len();
+            let mut node = DissectedNode {
+                name,
+                field_type: field.field_type.clone(),
+                address,
+                offset,
+                size,
+                raw: hex::encode(data),
+                value: None,
+                pointer_target: None,
+                children: Vec::new(),
+                error: None,
+            };
+
+            match field.field_type.as_str() {
+                "pointer" => {
+                    let target = if self.pointer_size == 8 {
+                        data.get(..8).and_then(|b| b.try_into().ok()).map(u64::from_le_bytes)
+                    } else {
+                        data.get(..4).and_then(|b| b.try_into().ok()).map(u32::from_le_bytes).map(u64::from)
+                    }.unwrap_or(0);
+                    node.value = Some(format!("0x{:x}", target));
+                    if target == 0 {
+                        return node;
+                    }
+                    let (module_name, module_offset) = self.resolve_module(target);
+                    let mut readable = false;
+
+                    if let Some(pointee) = field.pointee.as_deref() {
+                        if depth < self.max_depth {
+                            let pointee_field = StructField {
+                                name: "*".to_string(),
+                                field_type: pointee.to_string(),
+                                offset: None,
+                                count: None,
+                                size: field.size,
+                                pointee: None,
+                            };
+                            match self.element_size(&pointee_field, 0) {
+                                Ok(pointee_size) => {
+                                    if let Ok(bytes) = read_memory_from_server(&self.host, self.port, target, pointee_size).await {
+                                        if bytes.len() == pointee_size {
+                                            readable = true;
+                                            let child = self.dissect_element(&pointee_field, format!("*{}", node.name), target, 0, &bytes, depth + 1).await;
+                                            node.children.push(child);
+                                        }
+                                    }
+                                }
+                                Err(e) => node.error = Some(e),
+                            }
+                        }
+                    } else {
+                        readable = read_memory_from_server(&self.host, self.port, target, 1).await.is_ok_and(|b| !b.is_empty());
+                    }
+
+                    node.pointer_target = Some(PointerTarget { address: target, module_name, module_offset, readable });
+                }
+                t if primitive_size(t, self.pointer_size).is_some() || matches!(t, "string" | "utf16") => {
+                    node.value = format_primitive(data, t);
+                }
+                "bytes" => {}
+                struct_name => match self.structs.get(struct_name) {
+                    Some(def) => match self.dissect_struct(def, address, data, depth).await {
+                        Ok(children) => node.children = children,
+                        Err(e) => node.error = Some(e),
+                    },
+                    None => node.error = Some(format!("Unknown type: {}", struct_name)),
+                },
+            }
+            node
+        })
+    }
+
+    /// Dissect all fields of a struct whose bytes are in `data`
+    fn dissect_struct<'a>(
+        &'a self,
+        def: &'a StructDefinition,
+        address: u64,
+        data: &'a [u8],
+        depth: u32,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<DissectedNode>, String>> + Send + 'a>> {
+        Box::pin(async move {
+            let (offsets, _) = self.layout(def, 0)?;
+            let too_large = || format!("Field offsets of {} overflow", def.name);
+            let mut nodes = Vec::with_capacity(def.fields.len());
+            for (field, &offset) in def.fields.iter().zip(&offsets) {
+                let element_size = self.element_size(field, 0)?;
+                let start = usize::try_from(offset).map_err(|_| too_large())?;
+                let field_address = address.wrapping_add(offset);
+
+                let Some(count) = field.count else {
+                    let end = start.checked_add(element_size).ok_or_else(too_large)?;
+                    let bytes = data.get(start..end).unwrap_or(&[]);
+                    nodes.push(self.dissect_element(field, field.name.clone(), field_address, offset, bytes, depth).await);
+                    continue;
+                };
+
+                // Arrays get a parent node with one child per element
+                let total = element_size.checked_mul(count).ok_or_else(too_large)?;
+                let end = start.checked_add(total).ok_or_else(too_large)?;
+                let mut array = DissectedNode {
+                    name: field.name.clone(),
+                    field_type: format!("{}[{}]", field.field_type, count),
+                    address: field_address,
+                    offset,
+                    size: total,
+                    raw: hex::encode(data.get(start..end).unwrap_or(&[])),
+                    value: None,
+                    pointer_target: None,
+                    children: Vec::new(),
+                    error: None,
+                };
+                // Each element lies within start..end, so these cannot overflow
+                for i in 0..count.min(MAX_ARRAY_ELEMENTS) {
+                    let element_offset = i * element_size;
+                    let element_start = start + element_offset;
+                    let bytes = data.get(element_start..element_start + element_size).unwrap_or(&[]);
+                    array.children.push(
+                        self.dissect_element(field, format!("[{}]", i), field_address.wrapping_add(element_offset as u64), element_offset as u64, bytes, depth).await,
+                    );
+                }
+                nodes.push(array);
+            }
+            Ok(nodes)
+        })
+    }
+}
+
+async fn load_stored_structs(target_os: &str, module_name: &str) -> Vec<StructDefinition> {
+    list_struct_definitions(target_os.to_string(), Some(module_name.to_string()))
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|s| serde_json::from_str(&s.definition_json).ok())
+        .collect()
+}
+
+/// Read memory at `address` and apply a struct layout, returning a typed tree.
+/// Types referenced but not included in the JSON are looked up in the stored
+/// definitions for target_os/module_name (and the module-independent ones).
+#[tauri::command]
+pub async fn dissect_memory(
+    state: tauri::State<'_, AppStateType>,
+    address: u64,
+    struct_definition_json: String,
+    target_os: Option<String>,
+    module_name: Option<String>,
+    pointer_size: Option<usize>,
+    max_depth: Option<u32>,
+) -> Result<DissectedNode, String> {
+    let (host, port) = {
+        let config = SERVER_CONFIG.read().map_err(|e| e.to_string())?;
+        (config.host.clone(), config.port)
+    };
+    if host.is_empty() {
+        return Err("No server connection configured".to_string());
+    }
+
+    let input: StructDefinitionInput = serde_json::from_str(&struct_definition_json)
+        .map_err(|e| format!("Invalid struct definition: {}", e))?;
+    let (root, definitions) = match input {
+        StructDefinitionInput::Set { root, structs } => (root, structs),
+        StructDefinitionInput::Single(def) => (def.name.clone(), vec![def]),
+    };
+
+    let mut structs: HashMap<String, StructDefinition> = HashMap::new();
+    if let Some(os) = &target_os {
+        for def in load_stored_structs(os, "").await {
+            structs.insert(def.name.clone(), def);
+        }
+        if let Some(module) = &module_name {
+            for def in load_stored_structs(os, module).await {
+                structs.insert(def.name.clone(), def);
+            }
+        }
+    }
+    for def in definitions {
+        structs.insert(def.name.clone(), def);
+    }
+
+    let (modules, server_arch) = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        (state_guard.attached_modules.clone(), state_guard.server_info.as_ref().map(|s| s.arch.clone()))
+    };
+    let default_pointer_size = match server_arch.as_deref() {
+        Some("x86") | Some("arm") => 4,
+        _ => 8,
+    };
+
+    let dissector = Dissector {
+        host,
+        port,
+        pointer_size: pointer_size.unwrap_or(default_pointer_size),
+        max_depth: max_depth.unwrap_or(DEFAULT_MAX_DEPTH),
+        modules,
+        structs,
+    };
+
+    let def = dissector.structs.get(&root).ok_or(format!("Unknown struct: {}", root))?;
+    let size = dissector.struct_size(def, 0)?;
+    let data = read_memory_from_server(&dissector.host, dissector.port, address, size).await?;
+    if data.len() < size {
+        return Err(format!("Only {} of {} bytes readable at 0x{:x}", data.len(), size, address));
+    }
+
+    let root_field = StructField {
+        name: root.clone(),
+        field_type: root.clone(),
+        offset: None,
+        count: None,
+        size: None,
+        pointee: None,
+    };
+    Ok(dissector.dissect_element(&root_field, root.clone(), address, 0, &data, 0).await)
+}
+
+/// Store a struct definition for a target OS, optionally tied to a module
+#[tauri::command]
+pub async fn save_struct_definition(
+    target_os: String,
+    module_name: Option<String>,
+    definition_json: String,
+) -> Result<String, String> {
+    let def: StructDefinition = serde_json::from_str(&definition_json)
+        .map_err(|e| format!("Invalid struct definition: {}", e))?;
+    db::run(move |conn| {
+        store_definition(conn, &target_os, &module_name.unwrap_or_default(), &def.name, &definition_json)?;
+        Ok(def.name)
+    }).await
+}
+
+/// Insert or replace a definition (module_name "" for any module)
+pub fn store_definition(conn: &Connection, target_os: &str, module_name: &str, name: &str, definition_json: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO struct_definitions (target_os, module_name, name, definition_json, updated_at)
+         VALUES (?1, ?2, ?3, ?4, datetime('now'))",
+        params![
+            target_os,
+            module_name,
+            name,
+            crate::secure_store::seal_value("struct_definitions", "definition_json", definition_json)?,
+        ],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// List stored struct definitions (all modules when module_name is None)
+#[tauri::command]
+pub async fn list_struct_definitions(
+    target_os: String,
+    module_name: Option<String>,
+) -> Result<Vec<StoredStructDefinition>, String> {
+    db::run(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT target_os, module_name, name, definition_json, updated_at FROM struct_definitions
+             WHERE target_os = ?1 AND (?2 IS NULL OR module_name = ?2)
+             ORDER BY module_name, name",
+        ).map_err(|e| e.to_string())?;
+        let rows = stmt.query_map(params![target_os, module_name], |row| {
+            Ok(StoredStructDefinition {
+                target_os: row.get(0)?,
+                module_name: row.get(1)?,
+                name: row.get(2)?,
+                definition_json: crate::secure_store::open_value("struct_definitions", "definition_json", row.get(3)?)
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, e.into()))?,
+                updated_at: row.get(4)?,
+            })
+        }).map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+pub async fn delete_struct_definition(
+    target_os: String,
+    module_name: Option<String>,
+    name: String,
+) -> Result<bool, String> {
+    db::run(move |conn| {
+        let deleted = conn.execute(
+            "DELETE FROM struct_definitions WHERE target_os = ?1 AND module_name = ?2 AND name = ?3",
+            params![target_os, module_name.unwrap_or_default(), name],
+        ).map_err(|e| e.to_string())?;
+        Ok(deleted > 0)
+    }).await
+}
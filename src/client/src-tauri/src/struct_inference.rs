@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::memory_regions::{self, MemoryRegion};
+use crate::state::AppStateType;
+use crate::struct_dissector::{StructDefinition, StructField};
+use crate::symbolizer::Symbolizer;
+use crate::{read_memory_from_server, server_address};
+
+const DEFAULT_SAMPLES: u32 = 5;
+const MAX_SAMPLES: u32 = 64;
+const DEFAULT_INTERVAL_MS: u64 = 200;
+const MAX_STRUCT_SIZE: u64 = 64 * 1024;
+// Shortest printable run taken as an inline string
+const MIN_STRING_LENGTH: usize = 4;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InferStructureOptions {
+    #[serde(default)]
+    pub samples: Option<u32>,              // Snapshots taken (default 5)
+    #[serde(default)]
+    pub interval_ms: Option<u64>,          // Between snapshots (default 200)
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub pointer_size: Option<usize>,       // Default: from the target architecture
+}
+
+/// Classified slot of the sampled memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferredField {
+    pub offset: u64,
+    pub size: usize,
+    pub kind: String,                      // "vtable", "pointer", "code_pointer", "string", "double", "float", "int", "zero"
+    pub changes: u32,                      // Snapshots whose value differed from the previous one
+    pub value: String,                     // Latest value, formatted for the kind
+    pub detail: Option<String>,            // Symbol or region of pointers
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferredStructure {
+    pub address: u64,
+    pub samples: u32,
+    pub fields: Vec<InferredField>,
+    pub definition: StructDefinition,      // Ready for dissect_memory / save_struct_definition
+}
+
+fn region(regions: &[MemoryRegion], address: u64) -> Option<&MemoryRegion> {
+    let index = regions.partition_point(|r| r.base <= address).checked_sub(1)?;
+    regions.get(index).filter(|r| address < r.base + r.size)
+}
+
+fn read_slot(data: &[u8], offset: usize, width: usize) -> u64 {
+    let mut raw = [0u8; 8];
+    raw[..width].copy_from_slice(&data[offset..offset + width]);
+    u64::from_le_bytes(raw)
+}
+
+/// Finite, non-denormal and within a range game and UI state usually has
+fn plausible_f32(value: f32) -> bool {
+    value == 0.0 || (value.is_normal() && (1e-6..1e9).contains(&value.abs()))
+}
+
+fn plausible_f64(value: f64) -> bool {
+    value == 0.0 || (value.is_normal() && (1e-9..1e12).contains(&value.abs()))
+}
+
+/// Length of the NUL-terminated printable run at `offset`, NUL included
+fn inline_string(data: &[u8], offset: usize) -> Option<usize> {
+    let run = data[offset..].iter().take_while(|&&b| b.is_ascii_graphic() || b == b' ').count();
+    (run >= MIN_STRING_LENGTH && data.get(offset + run) == Some(&0)).then_some(run + 1)
+}
+
+struct Classifier<'a> {
+    snapshots: &'a [Vec<u8>],
+    regions: &'a [MemoryRegion],
+    pointer_size: usize,
+}
+
+impl Classifier<'_> {
+    fn latest(&self) -> &[u8] {
+        self.snapshots.last().map(Vec::as_slice).unwrap_or_default()
+    }
+
+    fn values(&self, offset: usize, width: usize) -> Vec<u64> {
+        self.snapshots.iter().map(|s| read_slot(s, offset, width)).collect()
+    }
+
+    fn changes(values: &[u64]) -> u32 {
+        values.windows(2).filter(|w| w[0] != w[1]).count() as u32
+    }
+
+    /// "pointer" or "code_pointer" when every sampled value is null or points
+    /// into a readable mapping (and at least one is not null)
+    fn pointer_kind(&self, values: &[u64]) -> Option<&'static str> {
+        if values.iter().all(|&v| v == 0) {
+            return None;
+        }
+        let mut code = true;
+        for &value in values.iter().filter(|&&v| v != 0) {
+            let target = region(self.regions, value).filter(|r| r.readable)?;
+            code &= target.executable;
+        }
+        Some(if code { "code_pointer" } else { "pointer" })
+    }
+
+    fn classify(&self, offset: usize, vtables: &[usize]) -> (InferredField, usize) {
+        let latest = self.latest();
+        let field = |size: usize, kind: &str, values: &[u64], value: String| InferredField {
+            offset: offset as u64,
+            size,
+            kind: kind.to_string(),
+            changes: Self::changes(values),
+            value,
+            detail: None,
+        };
+
+        if let Some(length) = inline_string(latest, offset) {
+            let values = self.values(offset, 4);
+            let text = String::from_utf8_lossy(&latest[offset..offset + length - 1]).to_string();
+            // Keep later slots 4-aligned; the bytes after the NUL belong to the buffer
+            let size = length.next_multiple_of(4).min(latest.len() - offset);
+            return (field(size, "string", &values, text), size);
+        }
+
+        let width = self.pointer_size;
+        if offset.is_multiple_of(width) && offset + width <= latest.len() {
+            let values = self.values(offset, width);
+            let current = *values.last().unwrap_or(&0);
+            if vtables.contains(&offset) {
+                return (field(width, "vtable", &values, format!("0x{:x}", current)), width);
+            }
+            if let Some(kind) = self.pointer_kind(&values) {
+                return (field(width, kind, &values, format!("0x{:x}", current)), width);
+            }
+        }
+
+        if offset.is_multiple_of(8) && offset + 8 <= latest.len() {
+            let values = self.values(offset, 8);
+            let doubles: Vec<f64> = values.iter().map(|&v| f64::from_bits(v)).collect();
+            if doubles.iter().any(|&d| d != 0.0) && doubles.iter().all(|&d| plausible_f64(d)) {
+                let current = *doubles.last().unwrap_or(&0.0);
+                return (field(8, "double", &values, current.to_string()), 8);
+            }
+        }
+
+        let width = 4.min(latest.len() - offset);
+        let values = self.values(offset, width);
+        if values.iter().all(|&v| v == 0) {
+            return (field(width, "zero", &values, "0".to_string()), width);
+        }
+        if width == 4 {
+            let floats: Vec<f32> = values.iter().map(|&v| f32::from_bits(v as u32)).collect();
+            if floats.iter().all(|&f| plausible_f32(f)) {
+                let current = *floats.last().unwrap_or(&0.0);
+                return (field(4, "float", &values, current.to_string()), 4);
+            }
+        }
+        let current = *values.last().unwrap_or(&0);
+        let value = if width == 4 { (current as u32 as i32).to_string() } else { current.to_string() };
+        (field(width, "int", &values, value), width)
+    }
+}
+
+/// Slots that hold a stable pointer into a module's read-only data whose
+/// first entry is code
+async fn find_vtables(host: &str, port: u16, classifier: &Classifier<'_>) -> Vec<usize> {
+    let width = classifier.pointer_size;
+    let latest = classifier.latest();
+    let mut vtables = Vec::new();
+    for offset in (0..latest.len().saturating_sub(width - 1)).step_by(width) {
+        let values = classifier.values(offset, width);
+        let vtable = values[0];
+        if vtable == 0 || values.iter().any(|&v| v != vtable) {
+            continue;
+        }
+        let in_module_data = region(classifier.regions, vtable)
+            .is_some_and(|r| r.readable && !r.executable && r.module_name.is_some());
+        if !in_module_data {
+            continue;
+        }
+        let Ok(slot) = read_memory_from_server(host, port, vtable, width).await else {
+            continue;
+        };
+        if slot.len() == width && region(classifier.regions, read_slot(&slot, 0, width)).is_some_and(|r| r.executable) {
+            vtables.push(offset);
+        }
+    }
+    vtables
+}
+
+/// Dissector layout of the classified slots; runs of zero slots become padding
+fn to_definition(name: &str, size: usize, fields: &[InferredField]) -> StructDefinition {
+    let mut result: Vec<StructField> = Vec::new();
+    let mut i = 0;
+    while i < fields.len() {
+        let field = &fields[i];
+        let (name, field_type, byte_size, consumed) = match field.kind.as_str() {
+            "zero" => {
+                let run = fields[i..].iter().take_while(|f| f.kind == "zero").count();
+                let length: usize = fields[i..i + run].iter().map(|f| f.size).sum();
+                (format!("pad_0x{:x}", field.offset), "bytes", Some(length), run)
+            }
+            "vtable" => (format!("vtable_0x{:x}", field.offset), "pointer", None, 1),
+            "pointer" => (format!("ptr_0x{:x}", field.offset), "pointer", None, 1),
+            "code_pointer" => (format!("fn_0x{:x}", field.offset), "pointer", None, 1),
+            "string" => (format!("str_0x{:x}", field.offset), "string", Some(field.size), 1),
+            "double" => (format!("field_0x{:x}", field.offset), "double", None, 1),
+            "float" => (format!("field_0x{:x}", field.offset), "float", None, 1),
+            _ if field.size == 4 => (format!("field_0x{:x}", field.offset), "int32", None, 1),
+            _ => (format!("pad_0x{:x}", field.offset), "bytes", Some(field.size), 1),
+        };
+        result.push(StructField {
+            name,
+            field_type: field_type.to_string(),
+            offset: Some(field.offset),
+            count: None,
+            size: byte_size,
+            pointee: None,
+        });
+        i += consumed;
+    }
+    StructDefinition { name: name.to_string(), size: Some(size), fields: result }
+}
+
+/// Sample `size` bytes at `address` a few times and guess a layout: vtables
+/// and pointers from the memory map, inline strings, floats and ints from
+/// value plausibility. A starting point for the struct dissector.
+#[tauri::command]
+pub async fn infer_structure(
+    state: tauri::State<'_, AppStateType>,
+    address: u64,
+    size: u64,
+    options: Option<InferStructureOptions>,
+) -> Result<InferredStructure, String> {
+    let options = options.unwrap_or_default();
+    if size == 0 || size > MAX_STRUCT_SIZE {
+        return Err(format!("Struct size must be between 1 and {} bytes", MAX_STRUCT_SIZE));
+    }
+    let (host, port) = server_address()?;
+    let samples = options.samples.unwrap_or(DEFAULT_SAMPLES).clamp(1, MAX_SAMPLES);
+    let interval = Duration::from_millis(options.interval_ms.unwrap_or(DEFAULT_INTERVAL_MS));
+
+    let mut snapshots = Vec::with_capacity(samples as usize);
+    for sample in 0..samples {
+        if sample > 0 {
+            tokio::time::sleep(interval).await;
+        }
+        let data = read_memory_from_server(&host, port, address, size as usize).await?;
+        if data.len() < size as usize {
+            return Err(format!("Only {} of {} bytes readable at 0x{:x}", data.len(), size, address));
+        }
+        snapshots.push(data);
+    }
+
+    let server_arch = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?
+        .server_info.as_ref().map(|s| s.arch.clone());
+    let pointer_size = options.pointer_size.filter(|p| matches!(p, 4 | 8)).unwrap_or(match server_arch.as_deref() {
+        Some("x86") | Some("arm") => 4,
+        _ => 8,
+    });
+    let regions = memory_regions::get_cached_regions(Some(state.inner()), false).await.unwrap_or_default();
+    let classifier = Classifier { snapshots: &snapshots, regions: &regions, pointer_size };
+    let vtables = find_vtables(&host, port, &classifier).await;
+
+    let mut fields = Vec::new();
+    let mut offset = 0;
+    while offset < size as usize {
+        let (field, consumed) = classifier.classify(offset, &vtables);
+        fields.push(field);
+        offset += consumed;
+    }
+
+    let symbolizer = Symbolizer::load(state.inner()).await?;
+    for field in fields.iter_mut().filter(|f| matches!(f.kind.as_str(), "vtable" | "pointer" | "code_pointer")) {
+        let target = u64::from_str_radix(field.value.trim_start_matches("0x"), 16).unwrap_or(0);
+        field.detail = symbolizer.resolve(target).map(|s| s.display)
+            .or_else(|| region(&regions, target).map(|r| r.module_name.clone().unwrap_or_else(|| r.region_type.clone())));
+    }
+
+    let name = options.name.unwrap_or_else(|| format!("struct_0x{:x}", address));
+    let definition = to_definition(&name, size as usize, &fields);
+    Ok(InferredStructure { address, samples, fields, definition })
+}
@@ -0,0 +1,515 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+use crate::expression::{self, RegisterSet};
+use crate::state::{AppState, AppStateType};
+use crate::struct_dissector::{StructDefinition, StructField};
+use crate::symbolizer::Symbolizer;
+use crate::{remove_watchpoint_on_server, server_address, set_watchpoint_on_server, watchpoints};
+
+const WORD_SIZE: u64 = 8;
+const CACHE_LINE: u64 = 64;
+const DEFAULT_DWELL_MS: u64 = 500;
+const MIN_DWELL_MS: u64 = 50;
+const MAX_STRUCT_SIZE: u64 = 64 * 1024;
+// Owner of the profiler's watchpoints in the shared debug register budget
+const WATCHPOINT_OWNER: &str = "struct_profiler";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StructProfileRequest {
+    pub base: u64,
+    pub size: u64,
+    #[serde(default)]
+    pub dwell_ms: Option<u64>,             // Time each set of watchpoints stays armed
+    #[serde(default)]
+    pub max_watchpoints: Option<usize>,    // Watchpoints used at once (default 2, at most 4)
+    #[serde(default)]
+    pub access: Option<String>,            // "rw" (default) or "w"
+}
+
+/// Instruction that touched a field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldAccessor {
+    pub address: u64,
+    pub symbol: Option<String>,
+    pub instruction: Option<String>,
+    pub reads: u64,
+    pub writes: u64,
+}
+
+/// Observed usage of one offset of the struct
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldUsage {
+    pub offset: u64,
+    pub size: Option<usize>,               // Access width decoded from the instruction
+    pub float: bool,                       // Accessed through FP/SIMD registers
+    pub exact: bool,                       // Offset computed from the operand, not just the armed word
+    pub reads: u64,
+    pub writes: u64,
+    pub accessors: Vec<FieldAccessor>,
+}
+
+/// How long each word of the struct has been watched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordCoverage {
+    pub offset: u64,
+    pub watched_ms: u64,
+    pub hits: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructProfilerStatus {
+    pub running: bool,
+    pub base: u64,
+    pub size: u64,
+    pub dwell_ms: u64,
+    pub max_watchpoints: usize,
+    pub armed: Vec<u64>,
+    pub rotations: u64,
+    pub hit_count: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructProfile {
+    pub status: StructProfilerStatus,
+    pub fields: Vec<FieldUsage>,
+    pub coverage: Vec<WordCoverage>,
+    pub definition: StructDefinition,      // Layout inferred from the observed accesses
+}
+
+struct Profiler {
+    request: StructProfileRequest,
+    running: Arc<AtomicBool>,
+    dwell_ms: u64,
+    max_watchpoints: usize,
+    armed: Vec<u64>,
+    armed_at: u64,
+    cursor: usize,
+    fields: BTreeMap<u64, FieldUsage>,
+    coverage: BTreeMap<u64, WordCoverage>,
+    seen_exceptions: HashSet<String>,
+    rotations: u64,
+    hit_count: u64,
+    last_error: Option<String>,
+}
+
+impl Profiler {
+    fn status(&self) -> StructProfilerStatus {
+        StructProfilerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            base: self.request.base,
+            size: self.request.size,
+            dwell_ms: self.dwell_ms,
+            max_watchpoints: self.max_watchpoints,
+            armed: self.armed.clone(),
+            rotations: self.rotations,
+            hit_count: self.hit_count,
+            last_error: self.last_error.clone(),
+        }
+    }
+
+    /// Watchable words of the struct, ordered so consecutive picks land in
+    /// different cache lines
+    fn rotation_order(&self) -> Vec<u64> {
+        let start = self.request.base & !(WORD_SIZE - 1);
+        let end = self.request.base + self.request.size;
+        let mut words: Vec<u64> = (start..end).step_by(WORD_SIZE as usize).collect();
+        words.sort_by_key(|w| ((w % CACHE_LINE) / WORD_SIZE, *w));
+        words
+    }
+}
+
+static PROFILER: Lazy<Mutex<Profiler>> = Lazy::new(|| {
+    Mutex::new(Profiler {
+        request: StructProfileRequest::default(),
+        running: Arc::new(AtomicBool::new(false)),
+        dwell_ms: DEFAULT_DWELL_MS,
+        max_watchpoints: watchpoints::TOOL_WATCHPOINTS_DEFAULT,
+        armed: Vec::new(),
+        armed_at: 0,
+        cursor: 0,
+        fields: BTreeMap::new(),
+        coverage: BTreeMap::new(),
+        seen_exceptions: HashSet::new(),
+        rotations: 0,
+        hit_count: 0,
+        last_error: None,
+    })
+});
+
+fn lock_profiler() -> Result<std::sync::MutexGuard<'static, Profiler>, String> {
+    PROFILER.lock().map_err(|e| e.to_string())
+}
+
+/// Decoded memory access of an instruction as reported with a watchpoint hit
+struct Access {
+    address: Option<u64>,                  // Address of the accessing instruction
+    text: Option<String>,                  // "mnemonic operands"
+    write: bool,
+    size: Option<usize>,
+    float: bool,
+    operand: Option<String>,               // Memory operand as an expression over registers
+}
+
+/// Parse the server's "0xADDRESS|BYTECODE|OPCODE" line
+fn decode_access(instruction: Option<&str>) -> Access {
+    let mut access = Access { address: None, text: None, write: false, size: None, float: false, operand: None };
+    let Some(line) = instruction.and_then(|i| i.lines().next()) else {
+        return access;
+    };
+    let parts: Vec<&str> = line.split('|').collect();
+    access.address = parts.first().and_then(|a| u64::from_str_radix(a.trim().trim_start_matches("0x"), 16).ok());
+    let Some(text) = parts.get(2).map(|t| t.trim().to_lowercase()) else {
+        return access;
+    };
+    let (mnemonic, operands) = text.split_once(' ').unwrap_or((text.as_str(), ""));
+    let first = operands.split(',').next().unwrap_or("").trim();
+
+    if let (Some(open), Some(close)) = (operands.find('['), operands.find(']')) {
+        let inner = &operands[open + 1..close.max(open + 1)];
+        if operands.contains(" ptr ") {
+            // x86: "dword ptr [rbx + rcx*4 + 0x10]"; segment-relative operands are left alone
+            access.write = first.contains('[') && !matches!(mnemonic, "cmp" | "test" | "push" | "ucomiss" | "ucomisd");
+            // Longest names first: "word ptr" is also a suffix of "dword ptr"
+            access.size = ["xmmword", "qword", "dword", "word", "byte"].iter()
+                .zip([16, 8, 4, 2, 1])
+                .find(|(name, _)| operands.contains(&format!("{} ptr", name)))
+                .map(|(_, size)| size);
+            access.float = operands.contains("xmm");
+            if !operands[..open].contains(':') {
+                access.operand = Some(inner.to_string());
+            }
+        } else {
+            // ARM64: "ldr w0, [x1, #0x10]", "str x2, [x3], #8"
+            access.write = mnemonic.starts_with("st");
+            let register = first.chars().next().unwrap_or(' ');
+            access.float = matches!(register, 'b' | 'h' | 's' | 'd' | 'q' | 'v');
+            // Width suffix after ld/st (ldurb -> "rb", ldrsh -> "rsh")
+            let suffix = mnemonic.get(2..).unwrap_or("").replacen("ur", "r", 1);
+            access.size = match (suffix.as_str(), register) {
+                ("rb" | "rsb", _) => Some(1),
+                ("rh" | "rsh", _) => Some(2),
+                ("rsw", _) => Some(4),
+                (_, 'w' | 's') => Some(4),
+                (_, 'x' | 'd') => Some(8),
+                (_, 'b') => Some(1),
+                (_, 'h') => Some(2),
+                (_, 'q') => Some(16),
+                _ => None,
+            };
+            if mnemonic.ends_with('p') {
+                access.size = access.size.map(|s| s * 2);
+            }
+            let pieces: Vec<&str> = inner.split(',').map(|p| p.trim()).collect();
+            let post_index = operands[close + 1..].trim_start().starts_with(',');
+            access.operand = match pieces.as_slice() {
+                [base] => Some(base.to_string()),
+                [base, imm] if imm.starts_with('#') && !post_index => Some(format!("{} + {}", base, &imm[1..])),
+                _ => None,
+            };
+        }
+    }
+    access.text = Some(text);
+    access
+}
+
+/// Turn new watchpoint hits inside the struct into field usage
+fn collect_hits(profiler: &mut Profiler, state: &AppStateType) -> Result<(), String> {
+    let base = profiler.request.base;
+    let end = base + profiler.request.size;
+    let hits: Vec<(String, u64, u64, Access, RegisterSet)> = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        state_guard.exception_store.iter()
+            .filter(|e| e.exception_type == "watchpoint")
+            .filter_map(|e| {
+                let memory_address = e.memory_address?;
+                if memory_address + WORD_SIZE <= base || memory_address >= end {
+                    return None;
+                }
+                let key = format!("{}:{}:{}", e.timestamp, e.address, memory_address);
+                let pc = e.pc.or_else(|| u64::from_str_radix(e.address.trim_start_matches("0x"), 16).ok())?;
+                Some((key, memory_address, pc, decode_access(e.instruction.as_deref()), RegisterSet::from_json(&e.registers)))
+            })
+            .collect()
+    };
+
+    for (key, memory_address, pc, access, registers) in hits {
+        if !profiler.seen_exceptions.insert(key) {
+            continue;
+        }
+        // The reported address is only word-precise on some targets; prefer the operand
+        // when it evaluates into the same word (registers may already be clobbered on x86)
+        let word = memory_address & !(WORD_SIZE - 1);
+        let computed = access.operand.as_deref()
+            .and_then(|operand| expression::parse(operand).ok())
+            .and_then(|expr| expression::evaluate_registers(&expr, &registers).ok())
+            .map(|value| value as u64)
+            .filter(|address| (word..word + WORD_SIZE).contains(address) && *address >= base && *address < end);
+        let exact = computed.is_some();
+        let offset = computed.unwrap_or(memory_address.max(base)) - base;
+
+        profiler.hit_count += 1;
+        if let Some(coverage) = profiler.coverage.get_mut(&(word.max(base) - base)) {
+            coverage.hits += 1;
+        }
+        let field = profiler.fields.entry(offset).or_insert_with(|| FieldUsage {
+            offset,
+            size: None,
+            float: false,
+            exact,
+            reads: 0,
+            writes: 0,
+            accessors: Vec::new(),
+        });
+        field.exact |= exact;
+        field.float |= access.float;
+        field.size = field.size.max(access.size);
+        if access.write { field.writes += 1 } else { field.reads += 1 }
+
+        let accessor_address = access.address.unwrap_or(pc);
+        let accessor = match field.accessors.iter_mut().position(|a| a.address == accessor_address) {
+            Some(index) => &mut field.accessors[index],
+            None => {
+                field.accessors.push(FieldAccessor {
+                    address: accessor_address,
+                    symbol: None,
+                    instruction: access.text.clone(),
+                    reads: 0,
+                    writes: 0,
+                });
+                field.accessors.last_mut().unwrap()
+            }
+        };
+        if access.write { accessor.writes += 1 } else { accessor.reads += 1 }
+    }
+    Ok(())
+}
+
+/// Layout suggested by the observed accesses; untouched ranges become padding
+pub fn infer_definition(name: &str, size: u64, fields: &[FieldUsage], pointer_size: usize) -> StructDefinition {
+    let mut result = Vec::new();
+    let mut next = 0u64;
+    for field in fields {
+        if field.offset < next {
+            continue;
+        }
+        if field.offset > next {
+            result.push(StructField {
+                name: format!("pad_0x{:x}", next),
+                field_type: "bytes".to_string(),
+                offset: Some(next),
+                count: None,
+                size: Some((field.offset - next) as usize),
+                pointee: None,
+            });
+        }
+        let width = field.size.unwrap_or(1).min(8);
+        let field_type = match (width, field.float) {
+            (4, true) => "float",
+            (8, true) => "double",
+            (w, false) if w == pointer_size && field.offset % pointer_size as u64 == 0 && field.reads > field.writes => "pointer",
+            (1, _) => "uint8",
+            (2, _) => "uint16",
+            (4, _) => "uint32",
+            _ => "uint64",
+        };
+        result.push(StructField {
+            name: format!("field_0x{:x}", field.offset),
+            field_type: field_type.to_string(),
+            offset: Some(field.offset),
+            count: None,
+            size: None,
+            pointee: None,
+        });
+        next = field.offset + width as u64;
+    }
+    StructDefinition {
+        name: name.to_string(),
+        size: Some(size as usize),
+        fields: result,
+    }
+}
+
+/// Move the watchpoints to the next words of the rotation
+async fn rotate(state: &AppStateType) -> Result<(), String> {
+    let (host, port) = server_address()?;
+    let (previous, next, access) = {
+        let mut profiler = lock_profiler()?;
+        collect_hits(&mut profiler, state)?;
+        let now = AppState::current_timestamp();
+        let watched = now.saturating_sub(profiler.armed_at);
+        let base = profiler.request.base;
+        for word in profiler.armed.clone() {
+            if let Some(coverage) = profiler.coverage.get_mut(&(word.max(base) - base)) {
+                coverage.watched_ms += watched;
+            }
+        }
+        let order = profiler.rotation_order();
+        let count = profiler.max_watchpoints.min(order.len());
+        let next: Vec<u64> = (0..count).map(|i| order[(profiler.cursor + i) % order.len()]).collect();
+        profiler.cursor = (profiler.cursor + count) % order.len().max(1);
+        profiler.rotations += 1;
+        let access = profiler.request.access.clone().unwrap_or_else(|| "rw".to_string());
+        (std::mem::take(&mut profiler.armed), next, access)
+    };
+
+    watchpoints::release(WATCHPOINT_OWNER, previous.len());
+    for address in previous {
+        let _ = remove_watchpoint_on_server(&host, port, address).await;
+    }
+    let mut armed = Vec::new();
+    let mut errors = Vec::new();
+    let granted = watchpoints::reserve(state, WATCHPOINT_OWNER, next.len())?;
+    if granted < next.len() {
+        errors.push(format!("Only {} of {} debug registers free", granted, next.len()));
+    }
+    for address in next.into_iter().take(granted) {
+        match set_watchpoint_on_server(&host, port, address, WORD_SIZE as usize, &access).await {
+            Ok(_) => armed.push(address),
+            Err(e) => {
+                watchpoints::release(WATCHPOINT_OWNER, 1);
+                errors.push(format!("0x{:x}: {}", address, e));
+            }
+        }
+    }
+    let mut profiler = lock_profiler()?;
+    profiler.armed = armed;
+    profiler.armed_at = AppState::current_timestamp();
+    profiler.last_error = if errors.is_empty() { None } else { Some(errors.join("; ")) };
+    Ok(())
+}
+
+async fn disarm_all() -> Result<(), String> {
+    let armed = std::mem::take(&mut lock_profiler()?.armed);
+    if armed.is_empty() {
+        return Ok(());
+    }
+    watchpoints::release(WATCHPOINT_OWNER, armed.len());
+    let (host, port) = server_address()?;
+    for address in armed {
+        let _ = remove_watchpoint_on_server(&host, port, address).await;
+    }
+    Ok(())
+}
+
+/// Start profiling field usage of the struct at `base`. A few watchpoints are
+/// rotated across the struct's words; hits accumulate until cleared.
+#[tauri::command]
+pub async fn start_struct_profiler(app: AppHandle, request: StructProfileRequest) -> Result<StructProfilerStatus, String> {
+    server_address()?;
+    if request.size == 0 || request.size > MAX_STRUCT_SIZE {
+        return Err(format!("Struct size must be between 1 and {} bytes", MAX_STRUCT_SIZE));
+    }
+    if let Some(access) = request.access.as_deref() {
+        if !matches!(access, "rw" | "w") {
+            return Err(format!("Unsupported access type '{}' (expected rw or w)", access));
+        }
+    }
+    disarm_all().await?;
+
+    let dwell_ms = request.dwell_ms.unwrap_or(DEFAULT_DWELL_MS).max(MIN_DWELL_MS);
+    let running = Arc::new(AtomicBool::new(true));
+    let status = {
+        let mut profiler = lock_profiler()?;
+        profiler.running.store(false, Ordering::Relaxed);
+        profiler.running = running.clone();
+        profiler.dwell_ms = dwell_ms;
+        profiler.max_watchpoints = request.max_watchpoints
+            .unwrap_or(watchpoints::TOOL_WATCHPOINTS_DEFAULT)
+            .clamp(1, watchpoints::MAX_HARDWARE_WATCHPOINTS);
+        let restarted = profiler.request.base != request.base || profiler.request.size != request.size;
+        profiler.request = request;
+        if restarted {
+            profiler.fields.clear();
+            profiler.seen_exceptions.clear();
+            profiler.hit_count = 0;
+            profiler.rotations = 0;
+            let base = profiler.request.base;
+            profiler.coverage = profiler.rotation_order().into_iter()
+                .map(|word| {
+                    let offset = word.max(base) - base;
+                    (offset, WordCoverage { offset, watched_ms: 0, hits: 0 })
+                })
+                .collect();
+        }
+        profiler.cursor = 0;
+        profiler.status()
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(dwell_ms));
+        while running.load(Ordering::Relaxed) {
+            interval.tick().await;
+            if !running.load(Ordering::Relaxed) {
+                break;
+            }
+            let state = app.state::<AppStateType>();
+            if let Err(e) = rotate(state.inner()).await {
+                if let Ok(mut profiler) = PROFILER.lock() {
+                    profiler.last_error = Some(e);
+                }
+            }
+        }
+    });
+
+    Ok(status)
+}
+
+/// Stop rotating and remove the profiler's watchpoints; collected usage is kept
+#[tauri::command]
+pub async fn stop_struct_profiler(state: tauri::State<'_, AppStateType>) -> Result<StructProfilerStatus, String> {
+    {
+        let mut profiler = lock_profiler()?;
+        profiler.running.store(false, Ordering::Relaxed);
+        collect_hits(&mut profiler, state.inner())?;
+    }
+    disarm_all().await?;
+    Ok(lock_profiler()?.status())
+}
+
+/// Field usage collected so far, with accessor symbols and an inferred layout
+#[tauri::command]
+pub fn get_struct_profile(state: tauri::State<'_, AppStateType>, name: Option<String>) -> Result<StructProfile, String> {
+    let (status, mut fields, coverage, size) = {
+        let mut profiler = lock_profiler()?;
+        collect_hits(&mut profiler, state.inner())?;
+        (
+            profiler.status(),
+            profiler.fields.values().cloned().collect::<Vec<_>>(),
+            profiler.coverage.values().cloned().collect::<Vec<_>>(),
+            profiler.request.size,
+        )
+    };
+
+    let symbolizer = Symbolizer::from_state(state.inner())?;
+    for accessor in fields.iter_mut().flat_map(|f| f.accessors.iter_mut()) {
+        accessor.symbol = symbolizer.resolve(accessor.address).map(|s| s.display);
+    }
+    let pointer_size = match state.lock().map_err(|e| format!("Failed to lock state: {}", e))?
+        .server_info.as_ref().map(|s| s.arch.clone()).as_deref()
+    {
+        Some("x86") | Some("arm") => 4,
+        _ => 8,
+    };
+    let name = name.unwrap_or_else(|| format!("struct_0x{:x}", status.base));
+    let definition = infer_definition(&name, size, &fields, pointer_size);
+    Ok(StructProfile { status, fields, coverage, definition })
+}
+
+#[tauri::command]
+pub fn clear_struct_profile() -> Result<(), String> {
+    let mut profiler = lock_profiler()?;
+    profiler.fields.clear();
+    profiler.seen_exceptions.clear();
+    profiler.hit_count = 0;
+    for coverage in profiler.coverage.values_mut() {
+        coverage.watched_ms = 0;
+        coverage.hits = 0;
+    }
+    Ok(())
+}
@@ -0,0 +1,452 @@
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+use crate::debug_symbols::{self, DebugSymbolsLoadResult};
+use crate::state::AppStateType;
+use crate::{db, event_bus, server_connection};
+
+const DEFAULT_DEBUGINFOD_URL: &str = "https://debuginfod.elfutils.org";
+const DEFAULT_MS_SYMBOL_SERVER: &str = "https://msdl.microsoft.com/download/symbols";
+// Bytes between download progress events
+const PROGRESS_STEP: u64 = 256 * 1024;
+
+/// Module identity used to look up debug info on symbol servers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolModuleRequest {
+    pub module_name: String,
+    #[serde(default)]
+    pub build_id: Option<String>,   // ELF GNU build-id (hex) for debuginfod
+    #[serde(default)]
+    pub pdb_name: Option<String>,   // PDB file name from the PE CodeView record
+    #[serde(default)]
+    pub pdb_guid: Option<String>,   // CodeView GUID (32 hex digits, no dashes)
+    #[serde(default)]
+    pub pdb_age: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolFetchResult {
+    pub module_name: String,
+    pub success: bool,
+    pub local_path: Option<String>,
+    pub source: Option<String>,     // Server URL the file came from, or "cache"
+    pub kind: Option<String>,       // "dwarf" | "pdb"
+    pub error: Option<String>,
+    pub symbols: Option<DebugSymbolsLoadResult>, // Set when the file was loaded
+    pub load_error: Option<String>,     // Downloaded but could not be parsed
+}
+
+/// Servers tried in order; an empty list means the defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolServerUrls {
+    pub symbol_servers: Vec<String>,    // symsrv layout (Microsoft symbol server style)
+    pub debuginfod: Vec<String>,
+}
+
+/// Payload of "symbols://progress" / the symbol-progress channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolFetchProgress {
+    pub module_name: String,
+    pub url: Option<String>,            // Download in progress
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,       // From Content-Length
+    pub finished: bool,
+    pub result: Option<SymbolFetchResult>, // Set once finished
+    pub symbols: Option<DebugSymbolsLoadResult>, // Set when the file was loaded
+    pub load_error: Option<String>,     // Downloaded but could not be parsed
+}
+
+// Modules with a fetch_symbols download in flight
+static RUNNING_FETCHES: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| {
+    Mutex::new(HashSet::new())
+});
+
+/// Removes a module from RUNNING_FETCHES when dropped, also if the fetch panics
+struct RunningFetch(String);
+
+impl Drop for RunningFetch {
+    fn drop(&mut self) {
+        if let Ok(mut running) = RUNNING_FETCHES.lock() {
+            running.remove(&self.0);
+        }
+    }
+}
+
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS symbol_server_urls (
+            kind TEXT NOT NULL,
+            idx INTEGER NOT NULL,
+            url TEXT NOT NULL,
+            PRIMARY KEY(kind, idx)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn stored_urls(conn: &Connection, kind: &str) -> Vec<String> {
+    conn.prepare("SELECT url FROM symbol_server_urls WHERE kind = ?1 ORDER BY idx")
+        .and_then(|mut stmt| {
+            let rows = stmt.query_map(params![kind], |row| row.get::<_, String>(0))?;
+            Ok(rows.filter_map(|r| r.ok()).collect())
+        })
+        .unwrap_or_default()
+}
+
+/// Configured servers, with the defaults filled in for empty lists
+async fn configured_servers() -> SymbolServerUrls {
+    let (symbol_servers, debuginfod) = db::run(|conn| Ok((stored_urls(conn, "symsrv"), stored_urls(conn, "debuginfod"))))
+        .await
+        .unwrap_or_default();
+    SymbolServerUrls {
+        symbol_servers: if symbol_servers.is_empty() { vec![DEFAULT_MS_SYMBOL_SERVER.to_string()] } else { symbol_servers },
+        debuginfod: if debuginfod.is_empty() { debuginfod_servers() } else { debuginfod },
+    }
+}
+
+/// Local cache directory for downloaded debug files
+pub fn get_symbol_cache_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("DynaDbg")
+        .join("symbols")
+}
+
+/// debuginfod servers from DEBUGINFOD_URLS (space separated), falling back to elfutils
+fn debuginfod_servers() -> Vec<String> {
+    let urls: Vec<String> = std::env::var("DEBUGINFOD_URLS")
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|u| u.trim_end_matches('/').to_string())
+        .collect();
+    if urls.is_empty() {
+        vec![DEFAULT_DEBUGINFOD_URL.to_string()]
+    } else {
+        urls
+    }
+}
+
+/// Called with (url, downloaded bytes, Content-Length) as a download advances
+type DownloadProgress<'a> = &'a mut (dyn FnMut(&str, u64, Option<u64>) + Send);
+
+async fn download_to(client: &reqwest::Client, url: &str, path: &PathBuf, progress: DownloadProgress<'_>) -> Result<(), String> {
+    let mut resp = client.get(url).send().await
+        .map_err(|e| format!("Network error: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Server error: {}", resp.status()));
+    }
+    let total = resp.content_length();
+    let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+    progress(url, 0, total);
+    while let Some(chunk) = resp.chunk().await.map_err(|e| format!("Failed to read response: {}", e))? {
+        bytes.extend_from_slice(&chunk);
+        progress(url, bytes.len() as u64, total);
+    }
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await
+            .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+    }
+    // Write to a temp name first so an interrupted download never looks cached
+    let tmp_path = path.with_extension("partial");
+    tokio::fs::write(&tmp_path, &bytes).await
+        .map_err(|e| format!("Failed to write {}: {}", tmp_path.display(), e))?;
+    tokio::fs::rename(&tmp_path, path).await
+        .map_err(|e| format!("Failed to move {} into cache: {}", path.display(), e))?;
+    Ok(())
+}
+
+/// Fetch separate DWARF debug info for an ELF build-id via debuginfod
+async fn fetch_debuginfod(
+    client: &reqwest::Client,
+    build_id: &str,
+    servers: &[String],
+    progress: DownloadProgress<'_>,
+) -> Result<(PathBuf, String), String> {
+    let build_id = build_id.trim().to_lowercase();
+    if build_id.is_empty() || !build_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid build-id: {}", build_id));
+    }
+
+    let path = get_symbol_cache_dir().join("debuginfod").join(&build_id).join("debuginfo");
+    if path.exists() {
+        return Ok((path, "cache".to_string()));
+    }
+
+    let mut last_error = "No debuginfod servers configured".to_string();
+    for server in servers {
+        let url = format!("{}/buildid/{}/debuginfo", server, build_id);
+        match download_to(client, &url, &path, progress).await {
+            Ok(()) => return Ok((path, server.clone())),
+            Err(e) => last_error = format!("{}: {}", server, e),
+        }
+    }
+    Err(last_error)
+}
+
+/// Fetch a PDB from a symsrv-style server (<server>/<pdb>/<GUID><AGE>/<pdb>)
+async fn fetch_ms_pdb(
+    client: &reqwest::Client,
+    pdb_name: &str,
+    guid: &str,
+    age: u32,
+    servers: &[String],
+    progress: DownloadProgress<'_>,
+) -> Result<(PathBuf, String), String> {
+    // CodeView records often hold the build machine's full path; only the file
+    // name is used, so the cache path stays under the cache directory
+    let pdb_name = pdb_name.rsplit(['/', '\\']).next().unwrap_or_default();
+    if Path::new(pdb_name).file_name() != Some(OsStr::new(pdb_name)) {
+        return Err(format!("Invalid PDB name: {:?}", pdb_name));
+    }
+    let guid = guid.replace('-', "");
+    if guid.is_empty() || !guid.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid PDB GUID: {:?}", guid));
+    }
+    let signature = format!("{}{:x}", guid.to_uppercase(), age);
+    let path = get_symbol_cache_dir().join(pdb_name).join(&signature).join(pdb_name);
+    if path.exists() {
+        return Ok((path, "cache".to_string()));
+    }
+
+    let mut last_error = "No symbol servers configured".to_string();
+    for server in servers {
+        let url = format!("{}/{}/{}/{}", server, pdb_name, signature, pdb_name);
+        match download_to(client, &url, &path, progress).await {
+            Ok(()) => return Ok((path, server.clone())),
+            Err(e) => last_error = format!("{}: {}", server, e),
+        }
+    }
+    Err(last_error)
+}
+
+/// Download the debug file of one module: PDB by name/GUID/age when known,
+/// else separate DWARF by build-id
+async fn fetch_one(
+    client: &reqwest::Client,
+    module: SymbolModuleRequest,
+    debuginfod: &[String],
+    symsrv: &[String],
+    progress: DownloadProgress<'_>,
+) -> SymbolFetchResult {
+    let fetched = if let (Some(pdb_name), Some(guid)) = (&module.pdb_name, &module.pdb_guid) {
+        fetch_ms_pdb(client, pdb_name, guid, module.pdb_age.unwrap_or(1), symsrv, progress).await
+            .map(|(path, source)| (path, source, "pdb"))
+    } else if let Some(build_id) = &module.build_id {
+        fetch_debuginfod(client, build_id, debuginfod, progress).await
+            .map(|(path, source)| (path, source, "dwarf"))
+    } else {
+        Err("Module has no build-id or PDB signature".to_string())
+    };
+
+    match fetched {
+        Ok((path, source, kind)) => SymbolFetchResult {
+            module_name: module.module_name,
+            success: true,
+            local_path: Some(path.to_string_lossy().to_string()),
+            source: Some(source),
+            kind: Some(kind.to_string()),
+            error: None,
+            symbols: None,
+            load_error: None,
+        },
+        Err(e) => SymbolFetchResult {
+            module_name: module.module_name,
+            success: false,
+            local_path: None,
+            source: None,
+            kind: None,
+            error: Some(e),
+            symbols: None,
+            load_error: None,
+        },
+    }
+}
+
+/// Parse a downloaded debug file into the symbol store
+async fn load_fetched(target_os: &str, result: &mut SymbolFetchResult) {
+    if let Some(path) = &result.local_path {
+        match debug_symbols::load(target_os, &result.module_name, path).await {
+            Ok(symbols) => result.symbols = Some(symbols),
+            Err(e) => result.load_error = Some(e),
+        }
+    }
+}
+
+/// Download debug info for the given modules, preferring the local cache,
+/// and load it (unless `load` is false). ELF modules are looked up by
+/// build-id on debuginfod, PE modules by PDB name/GUID/age on the Microsoft
+/// symbol server.
+#[tauri::command]
+pub async fn fetch_module_debug_info(
+    state: tauri::State<'_, AppStateType>,
+    modules: Vec<SymbolModuleRequest>,
+    debuginfod_urls: Option<Vec<String>>,
+    symbol_server_urls: Option<Vec<String>>,
+    load: Option<bool>,
+) -> Result<Vec<SymbolFetchResult>, String> {
+    let target_os = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default()
+    };
+    let client = server_connection::external_client();
+    let configured = configured_servers().await;
+    let debuginfod = debuginfod_urls
+        .map(|urls| urls.into_iter().map(|u| u.trim_end_matches('/').to_string()).collect())
+        .unwrap_or(configured.debuginfod);
+    let symsrv: Vec<String> = symbol_server_urls
+        .map(|urls| urls.into_iter().map(|u| u.trim_end_matches('/').to_string()).collect())
+        .unwrap_or(configured.symbol_servers);
+
+    let mut results = Vec::with_capacity(modules.len());
+    for module in modules {
+        let mut result = fetch_one(&client, module, &debuginfod, &symsrv, &mut |_, _, _| {}).await;
+        if load.unwrap_or(true) {
+            load_fetched(&target_os, &mut result).await;
+        }
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+/// Store the servers fetch_symbols and fetch_module_debug_info use; returns
+/// the effective lists (defaults for empty ones)
+#[tauri::command]
+pub async fn configure_symbol_servers(urls: SymbolServerUrls) -> Result<SymbolServerUrls, String> {
+    db::run(move |conn| {
+        let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        tx.execute("DELETE FROM symbol_server_urls", []).map_err(|e| e.to_string())?;
+        for (kind, list) in [("symsrv", &urls.symbol_servers), ("debuginfod", &urls.debuginfod)] {
+            let list = list.iter().map(|u| u.trim().trim_end_matches('/')).filter(|u| !u.is_empty());
+            for (idx, url) in list.enumerate() {
+                tx.execute(
+                    "INSERT INTO symbol_server_urls (kind, idx, url) VALUES (?1, ?2, ?3)",
+                    params![kind, idx as i64, url],
+                ).map_err(|e| e.to_string())?;
+            }
+        }
+        tx.commit().map_err(|e| e.to_string())
+    }).await?;
+    Ok(configured_servers().await)
+}
+
+#[tauri::command]
+pub async fn get_symbol_servers() -> SymbolServerUrls {
+    configured_servers().await
+}
+
+fn emit_progress(app: &AppHandle, progress: &SymbolFetchProgress) {
+    if !event_bus::publish(event_bus::CHANNEL_SYMBOL_PROGRESS, Some(&progress.module_name), progress) {
+        let _ = app.emit("symbols://progress", progress);
+    }
+}
+
+/// Fill a request's build-id / PDB signature from module_build_ids
+async fn with_stored_identity(target_os: &str, mut module: SymbolModuleRequest) -> SymbolModuleRequest {
+    if module.build_id.is_some() || module.pdb_guid.is_some() {
+        return module;
+    }
+    let (target_os, module_name) = (target_os.to_string(), module.module_name.clone());
+    let stored = db::run(move |conn| {
+        conn.query_row(
+            "SELECT format, build_id, pdb_name, pdb_age FROM module_build_ids WHERE target_os = ?1 AND module_name = ?2",
+            params![target_os, module_name],
+            |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?, row.get::<_, Option<u32>>(3)?)),
+        ).map_err(|e| e.to_string())
+    }).await;
+    match stored {
+        Ok((Some(format), guid, pdb_name, pdb_age)) if format == "pe" => {
+            module.pdb_guid = Some(guid);
+            module.pdb_name = module.pdb_name.or(pdb_name);
+            module.pdb_age = module.pdb_age.or(pdb_age);
+        }
+        Ok((Some(format), build_id, _, _)) if format == "elf" => module.build_id = Some(build_id),
+        _ => {}
+    }
+    module
+}
+
+/// Start downloading a module's PDB / DWARF in the background from the
+/// configured servers, then load it (unless `load` is false). The build-id
+/// or PDB signature defaults to the one get_module_build_ids stored. Progress
+/// and the result are reported on "symbols://progress".
+#[tauri::command]
+pub async fn fetch_symbols(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+    module: SymbolModuleRequest,
+    load: Option<bool>,
+) -> Result<bool, String> {
+    let target_os = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        state_guard.server_info.as_ref().map(|info| info.target_os.clone()).unwrap_or_default()
+    };
+    let module = with_stored_identity(&target_os, module).await;
+    if module.build_id.is_none() && module.pdb_guid.is_none() {
+        return Err(format!("No build-id or PDB signature known for {}; identify it with get_module_build_ids first", module.module_name));
+    }
+    if !RUNNING_FETCHES.lock().map_err(|e| e.to_string())?.insert(module.module_name.clone()) {
+        return Ok(false);
+    }
+    let running = RunningFetch(module.module_name.clone());
+
+    tokio::spawn(async move {
+        let _running = running;
+        let module_name = module.module_name.clone();
+        let servers = configured_servers().await;
+        let mut progress = SymbolFetchProgress {
+            module_name: module_name.clone(),
+            url: None,
+            downloaded_bytes: 0,
+            total_bytes: None,
+            finished: false,
+            result: None,
+            symbols: None,
+            load_error: None,
+        };
+        emit_progress(&app, &progress);
+
+        let client = server_connection::external_client();
+        let mut reported = 0u64;
+        let mut result = fetch_one(&client, module, &servers.debuginfod, &servers.symbol_servers, &mut |url, downloaded, total| {
+            if progress.url.as_deref() != Some(url) {
+                progress.url = Some(url.to_string());
+            } else if downloaded < reported + PROGRESS_STEP && Some(downloaded) != total {
+                return;
+            }
+            reported = downloaded;
+            progress.downloaded_bytes = downloaded;
+            progress.total_bytes = total;
+            emit_progress(&app, &progress);
+        }).await;
+
+        if load.unwrap_or(true) {
+            load_fetched(&target_os, &mut result).await;
+        }
+        progress.symbols = result.symbols.clone();
+        progress.load_error = result.load_error.clone();
+        progress.finished = true;
+        progress.result = Some(result);
+        emit_progress(&app, &progress);
+    });
+    Ok(true)
+}
+
+/// Remove all downloaded debug files
+#[tauri::command]
+pub fn clear_symbol_cache() -> Result<bool, String> {
+    let dir = get_symbol_cache_dir();
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)
+            .map_err(|e| format!("Failed to clear symbol cache: {}", e))?;
+    }
+    Ok(true)
+}
@@ -0,0 +1,291 @@
+use once_cell::sync::Lazy;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use rusqlite::Connection;
+
+use crate::debug_symbols::{self, SourceLocation};
+use crate::state::{AppStateType, ModuleInfo, TraceEntryData};
+use crate::{db, secure_store};
+
+// Ghidra's names for functions it found without a symbol; IL2CPP and debug-symbol names replace them
+pub const AUTO_NAME_PREFIXES: [&str; 4] = ["FUN_", "sub_", "thunk_FUN_", "LAB_"];
+
+struct FunctionSymbol {
+    offset: u64,
+    size: u64,
+    name: String,
+}
+
+/// Address resolved against the module list and the Ghidra function cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedSymbol {
+    pub address: u64,
+    pub module_name: String,
+    pub module_base: u64,
+    pub module_offset: u64,
+    pub function_name: Option<String>,
+    pub function_offset: Option<u64>,
+    pub display: String,             // "foo::bar+0x10", or "libfoo.so+0x1234" without a function
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceLocation>, // From loaded debug symbols
+}
+
+type FunctionTable = Arc<Vec<FunctionSymbol>>;
+
+// Sorted function tables per (target_os, module_name); dropped when the
+// module's functions are saved again
+static FUNCTION_TABLES: Lazy<RwLock<HashMap<(String, String), FunctionTable>>> = Lazy::new(|| {
+    RwLock::new(HashMap::new())
+});
+
+fn parse_offset(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches("0x"), 16).ok()
+}
+
+/// Functions of a module from module_functions, falling back to the JSON
+/// functions cache, with debug-symbol functions laid over them
+fn query_function_table(conn: &Connection, target_os: &str, module_name: &str) -> Vec<FunctionSymbol> {
+    let mut functions: Vec<FunctionSymbol> = conn.prepare(
+        "SELECT f.name, f.address, f.size FROM module_functions f
+         JOIN analyzed_modules m ON f.module_id = m.id
+         WHERE m.target_os = ?1 AND m.module_name = ?2",
+    )
+    .and_then(|mut stmt| {
+        let rows = stmt.query_map(params![target_os, module_name], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?))
+        })?;
+        Ok(rows.filter_map(|r| r.ok())
+            .filter_map(|(name, address, size)| Some(FunctionSymbol { offset: parse_offset(&address)?, size: size as u64, name }))
+            .collect())
+    })
+    .unwrap_or_default();
+
+    if functions.is_empty() {
+        let json: Option<String> = conn.query_row(
+            "SELECT functions_json FROM ghidra_functions_cache WHERE target_os = ?1 AND module_name = ?2",
+            params![target_os, module_name],
+            |row| row.get(0),
+        ).ok();
+        let entries: Vec<serde_json::Value> = json
+            .and_then(|json| secure_store::open_value("ghidra_functions_cache", "functions_json", json).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        functions = entries.iter()
+            .filter_map(|f| Some(FunctionSymbol {
+                offset: parse_offset(f.get("address")?.as_str()?)?,
+                size: f.get("size").and_then(|s| s.as_u64()).unwrap_or(0),
+                name: f.get("name")?.as_str()?.to_string(),
+            }))
+            .collect();
+    }
+
+    let mut by_offset: HashMap<u64, usize> = functions.iter().enumerate().map(|(i, f)| (f.offset, i)).collect();
+    for (offset, size, name) in debug_symbols::stored_functions(conn, target_os, module_name) {
+        match by_offset.get(&offset) {
+            Some(&index) => {
+                let function = &mut functions[index];
+                if AUTO_NAME_PREFIXES.iter().any(|p| function.name.starts_with(p)) {
+                    function.name = name;
+                }
+                if function.size == 0 {
+                    function.size = size;
+                }
+            }
+            None => {
+                by_offset.insert(offset, functions.len());
+                functions.push(FunctionSymbol { offset, size, name });
+            }
+        }
+    }
+
+    functions.sort_by_key(|f| f.offset);
+    functions
+}
+
+fn function_table(target_os: &str, module_name: &str) -> FunctionTable {
+    let key = (target_os.to_string(), module_name.to_string());
+    if let Some(table) = FUNCTION_TABLES.read().ok().and_then(|t| t.get(&key).cloned()) {
+        return table;
+    }
+    // Async callers preload their modules; a miss here runs on a sync
+    // command or blocking thread
+    let functions = db::run_sync(|conn| Ok(query_function_table(conn, target_os, module_name))).unwrap_or_default();
+    let table = Arc::new(functions);
+    if let Ok(mut tables) = FUNCTION_TABLES.write() {
+        tables.insert(key, table.clone());
+    }
+    table
+}
+
+fn function_table_cached(target_os: &str, module_name: &str) -> bool {
+    FUNCTION_TABLES.read().map(|t| t.contains_key(&(target_os.to_string(), module_name.to_string()))).unwrap_or(false)
+}
+
+/// Load the function and line tables of modules that are not cached yet on a
+/// blocking thread, so lookups from async code afterwards stay in memory
+pub async fn preload(target_os: &str, module_names: Vec<String>) {
+    let missing: Vec<String> = module_names.into_iter()
+        .filter(|name| !function_table_cached(target_os, name) || !debug_symbols::line_table_cached(target_os, name))
+        .collect();
+    if missing.is_empty() {
+        return;
+    }
+    let target_os = target_os.to_string();
+    let _ = db::run(move |conn| {
+        for module_name in &missing {
+            if !function_table_cached(&target_os, module_name) {
+                let table = Arc::new(query_function_table(conn, &target_os, module_name));
+                if let Ok(mut tables) = FUNCTION_TABLES.write() {
+                    tables.insert((target_os.clone(), module_name.clone()), table);
+                }
+            }
+            debug_symbols::preload_line_table(conn, &target_os, module_name);
+        }
+        Ok(())
+    }).await;
+}
+
+/// (offset, size, name) of every known function of a module, by offset
+pub fn module_functions(target_os: &str, module_name: &str) -> Vec<(u64, u64, String)> {
+    function_table(target_os, module_name).iter()
+        .map(|f| (f.offset, f.size, f.name.clone()))
+        .collect()
+}
+
+/// Forget the cached function table of a module (after its functions changed)
+pub fn invalidate_module(target_os: &str, module_name: &str) {
+    if let Ok(mut tables) = FUNCTION_TABLES.write() {
+        tables.remove(&(target_os.to_string(), module_name.to_string()));
+    }
+}
+
+pub fn invalidate_all() {
+    if let Ok(mut tables) = FUNCTION_TABLES.write() {
+        tables.clear();
+    }
+}
+
+/// "function" / "function+0x10" for a module offset, from the cached function table
+pub fn symbol_for_offset(target_os: &str, module_name: &str, offset: u64) -> Option<String> {
+    let table = function_table(target_os, module_name);
+    let index = table.partition_point(|f| f.offset <= offset).checked_sub(1)?;
+    let function = table.get(index).filter(|f| f.size == 0 || offset < f.offset + f.size)?;
+    let delta = offset - function.offset;
+    Some(if delta == 0 { function.name.clone() } else { format!("{}+0x{:x}", function.name, delta) })
+}
+
+/// Module offset of "function" / "function+0x10" (inverse of `symbol_for_offset`)
+pub fn offset_for_symbol(target_os: &str, module_name: &str, symbol: &str) -> Option<u64> {
+    let (name, delta) = match symbol.rsplit_once('+') {
+        Some((name, delta)) if delta.trim().starts_with("0x") => (name.trim(), parse_offset(delta)?),
+        _ => (symbol.trim(), 0),
+    };
+    let table = function_table(target_os, module_name);
+    table.iter().find(|f| f.name == name).map(|f| f.offset + delta)
+}
+
+/// Snapshot of the loaded modules that resolves absolute addresses; shared by
+/// the disassembler and the tracer
+pub struct Symbolizer {
+    target_os: String,
+    modules: Vec<ModuleInfo>,
+}
+
+impl Symbolizer {
+    pub fn from_state(state: &AppStateType) -> Result<Self, String> {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let target_os = state_guard.server_info.as_ref()
+            .map(|info| info.target_os.clone())
+            .unwrap_or_default();
+        let mut modules = state_guard.attached_modules.clone();
+        modules.sort_by_key(|m| m.base);
+        Ok(Self { target_os, modules })
+    }
+
+    /// `from_state` with the tables of every loaded module preloaded; what
+    /// async commands use
+    pub async fn load(state: &AppStateType) -> Result<Self, String> {
+        let symbolizer = Self::from_state(state)?;
+        let module_names = symbolizer.modules.iter().map(|m| m.modulename.clone()).collect();
+        preload(&symbolizer.target_os, module_names).await;
+        Ok(symbolizer)
+    }
+
+    fn module_for(&self, address: u64) -> Option<&ModuleInfo> {
+        let index = self.modules.partition_point(|m| m.base <= address);
+        let module = self.modules.get(index.checked_sub(1)?)?;
+        (address < module.base.saturating_add(module.size)).then_some(module)
+    }
+
+    pub fn resolve(&self, address: u64) -> Option<ResolvedSymbol> {
+        let module = self.module_for(address)?;
+        let module_offset = address - module.base;
+
+        let table = function_table(&self.target_os, &module.modulename);
+        let index = table.partition_point(|f| f.offset <= module_offset);
+        let function = index.checked_sub(1)
+            .and_then(|i| table.get(i))
+            .filter(|f| f.size == 0 || module_offset < f.offset + f.size);
+
+        let (function_name, function_offset, display) = match function {
+            Some(f) => {
+                let delta = module_offset - f.offset;
+                let display = if delta == 0 { f.name.clone() } else { format!("{}+0x{:x}", f.name, delta) };
+                (Some(f.name.clone()), Some(delta), display)
+            }
+            None => (None, None, format!("{}+0x{:x}", module.modulename, module_offset)),
+        };
+
+        Some(ResolvedSymbol {
+            address,
+            module_name: module.modulename.clone(),
+            module_base: module.base,
+            module_offset,
+            function_name,
+            function_offset,
+            display,
+            source: debug_symbols::source_location(&self.target_os, &module.modulename, module_offset).map(|(location, _)| location),
+        })
+    }
+
+    /// Absolute [start, end) of the cached function containing `address`
+    /// (None when it is unknown or has no size)
+    pub fn function_range(&self, address: u64) -> Option<(u64, u64)> {
+        let module = self.module_for(address)?;
+        let module_offset = address - module.base;
+        let table = function_table(&self.target_os, &module.modulename);
+        let index = table.partition_point(|f| f.offset <= module_offset).checked_sub(1)?;
+        let function = table.get(index).filter(|f| f.size > 0 && module_offset < f.offset + f.size)?;
+        Some((module.base + function.offset, module.base + function.offset + function.size))
+    }
+
+    /// Fill the function name / library expression of a trace entry when the
+    /// server did not provide them
+    pub fn annotate_trace_entry(&self, entry: &mut TraceEntryData) {
+        if entry.function_name.is_some() && entry.library_expression.is_some() {
+            return;
+        }
+        let Some(symbol) = parse_offset(&entry.address).and_then(|a| self.resolve(a)) else {
+            return;
+        };
+        if entry.library_expression.is_none() {
+            entry.library_expression = Some(format!("{}+0x{:x}", symbol.module_name, symbol.module_offset));
+        }
+        if entry.function_name.is_none() {
+            entry.function_name = symbol.function_name;
+        }
+    }
+}
+
+/// Resolve addresses to module / function symbols (None outside any module)
+#[tauri::command]
+pub fn resolve_symbols(
+    state: tauri::State<'_, AppStateType>,
+    addresses: Vec<u64>,
+) -> Result<Vec<Option<ResolvedSymbol>>, String> {
+    let symbolizer = Symbolizer::from_state(state.inner())?;
+    Ok(addresses.into_iter().map(|a| symbolizer.resolve(a)).collect())
+}
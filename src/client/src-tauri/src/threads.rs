@@ -0,0 +1,178 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::state::{AppState, AppStateType, ExceptionData};
+use crate::{continue_execution_on_server, fetch_threads_from_server, server_address};
+
+/// Thread of the attached process, merged with what was seen of it before
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadEntry {
+    pub thread_id: u64,
+    pub name: String,
+    pub state: String,                // "Running", "Stopped", ... or "Exited" for cached-only threads
+    pub pc: Option<u64>,
+    pub sp: Option<u64>,
+    pub fp: Option<u64>,
+    pub suspend_count: i64,
+    pub last_exception_pc: Option<u64>, // PC of the latest debug event on this thread
+    pub last_seen: u64,               // Milliseconds; when the server last listed it
+    pub alive: bool,                  // Present in the latest list
+}
+
+// Threads per pid, kept across refreshes so names and last PCs survive
+// enumeration failures and thread exit
+static THREAD_CACHE: Lazy<Mutex<HashMap<u32, HashMap<u64, ThreadEntry>>>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+fn current_pid(state: &AppStateType) -> Result<u32, String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    state_guard.attached_process.as_ref().map(|p| p.pid).ok_or_else(|| "Process not attached".to_string())
+}
+
+fn parse_hex(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim().trim_start_matches("0x").trim_start_matches("0X"), 16).ok()
+}
+
+/// Record the PC of debug events per thread (called from add_exceptions)
+pub fn observe_exceptions(state: &AppState, exceptions: &[ExceptionData]) {
+    let Some(pid) = state.attached_process.as_ref().map(|p| p.pid) else {
+        return;
+    };
+    let Ok(mut cache) = THREAD_CACHE.lock() else {
+        return;
+    };
+    let threads = cache.entry(pid).or_default();
+    for exception in exceptions {
+        let Some(thread_id) = exception.thread_id else {
+            continue;
+        };
+        let pc = exception.pc.or_else(|| parse_hex(&exception.address));
+        let entry = threads.entry(thread_id).or_insert_with(|| ThreadEntry {
+            thread_id,
+            name: String::new(),
+            state: "Stopped".to_string(),
+            pc,
+            sp: None,
+            fp: None,
+            suspend_count: 0,
+            last_exception_pc: None,
+            last_seen: 0,
+            alive: true,
+        });
+        entry.last_exception_pc = pc;
+    }
+}
+
+async fn refresh(pid: u32) -> Result<Vec<ThreadEntry>, String> {
+    let (host, port) = server_address()?;
+    let listed = fetch_threads_from_server(&host, port).await?;
+    let now = AppState::current_timestamp();
+
+    let mut cache = THREAD_CACHE.lock().map_err(|e| e.to_string())?;
+    let threads = cache.entry(pid).or_default();
+    for entry in threads.values_mut() {
+        entry.alive = false;
+    }
+    for thread in &listed {
+        let Some(thread_id) = thread["thread_id"].as_u64() else {
+            continue;
+        };
+        let name = thread["name"].as_str().unwrap_or_default().to_string();
+        let entry = threads.entry(thread_id).or_insert_with(|| ThreadEntry {
+            thread_id,
+            name: String::new(),
+            state: String::new(),
+            pc: None,
+            sp: None,
+            fp: None,
+            suspend_count: 0,
+            last_exception_pc: None,
+            last_seen: now,
+            alive: true,
+        });
+        // Names are often unreadable once a thread is stopped; keep the last good one
+        if !name.is_empty() && name != "Unknown" {
+            entry.name = name;
+        }
+        entry.state = thread["state"].as_str().unwrap_or("Unknown").to_string();
+        entry.pc = thread["pc"].as_str().and_then(parse_hex).or(entry.pc);
+        entry.sp = thread["sp"].as_str().and_then(parse_hex).or(entry.sp);
+        entry.fp = thread["fp"].as_str().and_then(parse_hex).or(entry.fp);
+        entry.suspend_count = thread["suspend_count"].as_i64().unwrap_or(0);
+        entry.last_seen = now;
+        entry.alive = true;
+    }
+    for entry in threads.values_mut().filter(|e| !e.alive) {
+        entry.state = "Exited".to_string();
+    }
+
+    let mut entries: Vec<ThreadEntry> = threads.values().cloned().collect();
+    entries.sort_by_key(|e| (!e.alive, e.thread_id));
+    Ok(entries)
+}
+
+fn cached(pid: u32) -> Vec<ThreadEntry> {
+    let mut entries: Vec<ThreadEntry> = THREAD_CACHE.lock()
+        .ok()
+        .and_then(|cache| cache.get(&pid).map(|threads| threads.values().cloned().collect()))
+        .unwrap_or_default();
+    entries.sort_by_key(|e| (!e.alive, e.thread_id));
+    entries
+}
+
+/// Threads of the attached process. Threads that exited since the last
+/// refresh stay listed with alive = false; `include_exited` = false drops them.
+#[tauri::command]
+pub async fn list_threads(
+    state: tauri::State<'_, AppStateType>,
+    include_exited: Option<bool>,
+) -> Result<Vec<ThreadEntry>, String> {
+    let pid = current_pid(state.inner())?;
+    let mut entries = refresh(pid).await?;
+    if !include_exited.unwrap_or(true) {
+        entries.retain(|e| e.alive);
+    }
+    Ok(entries)
+}
+
+/// One thread, refreshed from the server when reachable
+#[tauri::command]
+pub async fn get_thread_info(state: tauri::State<'_, AppStateType>, thread_id: u64) -> Result<ThreadEntry, String> {
+    let pid = current_pid(state.inner())?;
+    let entries = match refresh(pid).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Thread refresh failed, using cache: {}", e);
+            cached(pid)
+        }
+    };
+    entries.into_iter()
+        .find(|e| e.thread_id == thread_id)
+        .ok_or_else(|| format!("Thread {} not found", thread_id))
+}
+
+/// The server has no per-thread suspend; break the whole process instead
+#[tauri::command]
+pub async fn suspend_thread(thread_id: u64) -> Result<bool, String> {
+    Err(format!(
+        "Suspending thread {} is not supported by the server; suspend the process or set a breakpoint instead",
+        thread_id
+    ))
+}
+
+/// Resume a thread stopped at a debug event
+#[tauri::command]
+pub async fn resume_thread(state: tauri::State<'_, AppStateType>, thread_id: u64) -> Result<bool, String> {
+    let pid = current_pid(state.inner())?;
+    let (host, port) = server_address()?;
+    continue_execution_on_server(&host, port, Some(thread_id)).await?;
+    if let Ok(mut cache) = THREAD_CACHE.lock() {
+        if let Some(entry) = cache.get_mut(&pid).and_then(|threads| threads.get_mut(&thread_id)) {
+            entry.state = "Running".to_string();
+        }
+    }
+    Ok(true)
+}
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::state::{AppStateType, TraceEntryData};
+use crate::symbolizer::Symbolizer;
+use crate::trace_sources;
+
+// Entries searched ahead on each side for the point where the traces rejoin
+const DEFAULT_RESYNC_WINDOW: usize = 2048;
+const MAX_DIVERGENCES: usize = 500;
+const MAX_REGISTER_DIFFS: usize = 2000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TraceDiffOptions {
+    #[serde(default)]
+    pub ignore_registers: Vec<String>, // E.g. "sp", "fp" when the runs used different stacks
+    pub resync_window: Option<usize>,
+    #[serde(default)]
+    pub absolute_addresses: bool,      // Align on raw addresses instead of module + offset
+}
+
+/// Point where the two traces stop executing the same instructions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceDivergence {
+    pub index_a: usize,
+    pub index_b: usize,
+    pub after: Option<String>,         // Last common instruction before the split
+    pub function_name: Option<String>,
+    pub address_a: Option<String>,     // First instruction of each side after the split
+    pub address_b: Option<String>,
+    pub skipped_a: usize,              // Instructions only in A until the traces rejoin
+    pub skipped_b: usize,
+    pub rejoined: bool,                // False when no common instruction follows in the window
+}
+
+/// Control-flow edge out of a branch point taken in only one trace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniqueBranch {
+    pub from: String,
+    pub to: String,
+    pub only_in: String,               // "a" or "b"
+    pub count: usize,
+    pub function_name: Option<String>,
+}
+
+/// Register that starts to differ at an aligned instruction (repeats of the
+/// same difference are not listed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterDiff {
+    pub index_a: usize,
+    pub index_b: usize,
+    pub address: String,
+    pub register: String,
+    pub value_a: String,
+    pub value_b: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceDiffResult {
+    pub session_a: String,
+    pub session_b: String,
+    pub entries_a: usize,
+    pub entries_b: usize,
+    pub matched: usize,                // Aligned instruction pairs
+    pub identical: bool,               // Same path and no register differences
+    pub divergences: Vec<TraceDivergence>,
+    pub unique_branches: Vec<UniqueBranch>,
+    pub register_diffs: Vec<RegisterDiff>,
+    pub truncated: bool,               // Divergences or register diffs were capped
+}
+
+/// Alignment key: "module+0xoffset" when known so runs with different ASLR
+/// slides still line up
+fn keys(entries: &[TraceEntryData], absolute: bool) -> Vec<String> {
+    entries.iter()
+        .map(|e| match (&e.library_expression, absolute) {
+            (Some(expression), false) => expression.clone(),
+            _ => e.address.to_lowercase(),
+        })
+        .collect()
+}
+
+/// Nearest pair (i, j) at or after (from_a, from_b) with equal keys, by i + j
+fn resync(a: &[String], b: &[String], from_a: usize, from_b: usize, window: usize) -> Option<(usize, usize)> {
+    let end_a = (from_a + window).min(a.len());
+    let end_b = (from_b + window).min(b.len());
+    let mut first_in_b: HashMap<&str, usize> = HashMap::new();
+    for (j, key) in b.iter().enumerate().take(end_b).skip(from_b) {
+        first_in_b.entry(key.as_str()).or_insert(j);
+    }
+    let mut best: Option<(usize, usize)> = None;
+    for (i, key) in a.iter().enumerate().take(end_a).skip(from_a) {
+        if best.is_some_and(|(bi, bj)| i - from_a >= bi - from_a + bj - from_b) {
+            break;
+        }
+        if let Some(&j) = first_in_b.get(key.as_str()) {
+            if best.is_none_or(|(bi, bj)| i + j < bi + bj) {
+                best = Some((i, j));
+            }
+        }
+    }
+    best
+}
+
+fn register_values(entry: &TraceEntryData, ignore: &HashSet<String>) -> HashMap<String, String> {
+    entry.registers.as_object()
+        .map(|object| object.iter()
+            .filter(|(name, _)| !ignore.contains(&name.to_lowercase()))
+            .filter_map(|(name, value)| {
+                let text = match value {
+                    serde_json::Value::String(s) => s.to_lowercase(),
+                    serde_json::Value::Number(n) => format!("0x{:x}", n.as_u64()?),
+                    _ => return None,
+                };
+                Some((name.to_lowercase(), text))
+            })
+            .collect())
+        .unwrap_or_default()
+}
+
+/// (from, to) -> count over consecutive entries
+fn edges(keys: &[String]) -> HashMap<(&str, &str), usize> {
+    let mut edges = HashMap::new();
+    for pair in keys.windows(2) {
+        *edges.entry((pair[0].as_str(), pair[1].as_str())).or_insert(0) += 1;
+    }
+    edges
+}
+
+/// Align two recorded traces by their instruction sequence and report where
+/// they split, the branch edges only one of them took and the registers that
+/// differ on the common path. Sessions are named as in trace_sources::load:
+/// "current", "native:<thread id>" or a trace file path.
+#[tauri::command]
+pub async fn diff_trace_sessions(
+    state: tauri::State<'_, AppStateType>,
+    session_a: String,
+    session_b: String,
+    options: Option<TraceDiffOptions>,
+) -> Result<TraceDiffResult, String> {
+    let options = options.unwrap_or_default();
+    let mut a = trace_sources::load(state.inner(), &session_a).await?;
+    let mut b = trace_sources::load(state.inner(), &session_b).await?;
+    let symbolizer = Symbolizer::load(state.inner()).await?;
+    for entry in a.iter_mut().chain(b.iter_mut()) {
+        symbolizer.annotate_trace_entry(entry);
+    }
+
+    let ignore: HashSet<String> = options.ignore_registers.iter().map(|r| r.to_lowercase()).collect();
+    let window = options.resync_window.unwrap_or(DEFAULT_RESYNC_WINDOW).max(1);
+    let keys_a = keys(&a, options.absolute_addresses);
+    let keys_b = keys(&b, options.absolute_addresses);
+
+    let mut divergences = Vec::new();
+    let mut register_diffs = Vec::new();
+    let mut truncated = false;
+    let mut matched = 0;
+    let mut differing: HashSet<String> = HashSet::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if keys_a[i] == keys_b[j] {
+            matched += 1;
+            let values_a = register_values(&a[i], &ignore);
+            let values_b = register_values(&b[j], &ignore);
+            for (name, value_a) in &values_a {
+                let Some(value_b) = values_b.get(name) else {
+                    continue;
+                };
+                if value_a == value_b {
+                    differing.remove(name);
+                } else if differing.insert(name.clone()) {
+                    if register_diffs.len() >= MAX_REGISTER_DIFFS {
+                        truncated = true;
+                        continue;
+                    }
+                    register_diffs.push(RegisterDiff {
+                        index_a: i,
+                        index_b: j,
+                        address: keys_a[i].clone(),
+                        register: name.clone(),
+                        value_a: value_a.clone(),
+                        value_b: value_b.clone(),
+                    });
+                }
+            }
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        let rejoin = resync(&keys_a, &keys_b, i, j, window);
+        let (next_i, next_j) = rejoin.unwrap_or((a.len(), b.len()));
+        if divergences.len() < MAX_DIVERGENCES {
+            let previous = i.checked_sub(1).map(|p| &a[p]);
+            divergences.push(TraceDivergence {
+                index_a: i,
+                index_b: j,
+                after: i.checked_sub(1).map(|p| keys_a[p].clone()),
+                function_name: previous.and_then(|e| e.function_name.clone()),
+                address_a: Some(keys_a[i].clone()),
+                address_b: Some(keys_b[j].clone()),
+                skipped_a: next_i - i,
+                skipped_b: next_j - j,
+                rejoined: rejoin.is_some(),
+            });
+        } else {
+            truncated = true;
+        }
+        // Register state is not comparable across the split
+        differing.clear();
+        i = next_i;
+        j = next_j;
+    }
+    if i < a.len() || j < b.len() {
+        // One trace ends early
+        divergences.push(TraceDivergence {
+            index_a: i,
+            index_b: j,
+            after: i.checked_sub(1).and_then(|p| keys_a.get(p).cloned()),
+            function_name: i.checked_sub(1).and_then(|p| a.get(p)).and_then(|e| e.function_name.clone()),
+            address_a: keys_a.get(i).cloned(),
+            address_b: keys_b.get(j).cloned(),
+            skipped_a: a.len() - i,
+            skipped_b: b.len() - j,
+            rejoined: false,
+        });
+    }
+
+    // Branch points: locations with more than one successor over both traces
+    let edges_a = edges(&keys_a);
+    let edges_b = edges(&keys_b);
+    let mut successors: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for &(from, to) in edges_a.keys().chain(edges_b.keys()) {
+        successors.entry(from).or_default().insert(to);
+    }
+    let function_at: HashMap<&str, Option<String>> = keys_a.iter().zip(&a).chain(keys_b.iter().zip(&b))
+        .map(|(key, entry)| (key.as_str(), entry.function_name.clone()))
+        .collect();
+    let mut unique_branches: Vec<UniqueBranch> = Vec::new();
+    for (edges, other, side) in [(&edges_a, &edges_b, "a"), (&edges_b, &edges_a, "b")] {
+        for (&(from, to), &count) in edges {
+            if other.contains_key(&(from, to)) || successors.get(from).is_none_or(|s| s.len() < 2) {
+                continue;
+            }
+            unique_branches.push(UniqueBranch {
+                from: from.to_string(),
+                to: to.to_string(),
+                only_in: side.to_string(),
+                count,
+                function_name: function_at.get(from).cloned().flatten(),
+            });
+        }
+    }
+    unique_branches.sort_by(|x, y| x.from.cmp(&y.from).then(x.only_in.cmp(&y.only_in)).then(x.to.cmp(&y.to)));
+
+    Ok(TraceDiffResult {
+        identical: divergences.is_empty() && register_diffs.is_empty(),
+        session_a,
+        session_b,
+        entries_a: a.len(),
+        entries_b: b.len(),
+        matched,
+        divergences,
+        unique_branches,
+        register_diffs,
+        truncated,
+    })
+}
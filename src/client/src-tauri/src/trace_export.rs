@@ -0,0 +1,334 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+
+use crate::clock_sync;
+use crate::state::{AppStateType, TraceEntryData};
+use crate::symbolizer::Symbolizer;
+use crate::trace_sources;
+
+// Entries pulled from the session per write batch
+const EXPORT_CHUNK: usize = 8192;
+const BINARY_MAGIC: &[u8; 8] = b"DYNTRX01";
+const BINARY_VERSION: u32 = 1;
+// Offset of the u64 entry count in the binary header, patched when done
+const BINARY_COUNT_OFFSET: u64 = 12;
+
+const FLAG_CALL: u8 = 1;
+const FLAG_RETURN: u8 = 2;
+const FLAG_TEXT: u8 = 4;          // Instruction stored as text (bytes unknown)
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceExportResult {
+    pub path: String,
+    pub format: String,
+    pub entries: usize,
+    pub bytes_written: u64,
+}
+
+enum Format {
+    Chrome,
+    Text,
+    Binary,
+}
+
+impl Format {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "json" | "chrome" | "perfetto" => Ok(Format::Chrome),
+            "text" | "txt" => Ok(Format::Text),
+            "binary" | "bin" => Ok(Format::Binary),
+            other => Err(format!("Unknown trace export format: {} (expected json, text or binary)", other)),
+        }
+    }
+}
+
+fn parse_hex(text: &str) -> Option<u64> {
+    let text = text.trim();
+    u64::from_str_radix(text.strip_prefix("0x").unwrap_or(text), 16).ok()
+}
+
+/// Instruction bytes of "0xADDRESS|BYTES|MNEMONIC OPERANDS"
+fn instruction_bytes(instruction: &str) -> Vec<u8> {
+    let hex: String = instruction.split('|').nth(1).unwrap_or("").chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    if !hex.len().is_multiple_of(2) {
+        return Vec::new();
+    }
+    (0..hex.len()).step_by(2).filter_map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok()).collect()
+}
+
+fn instruction_text(entry: &TraceEntryData) -> String {
+    format!("{} {}", entry.opcode, entry.operands).trim_end().to_string()
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_signed(out: &mut Vec<u8>, value: i64) {
+    write_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+/// Writes one session as it is read; only the previous entry is kept
+struct Exporter {
+    format: Format,
+    out: BufWriter<File>,
+    session: String,
+    count: usize,
+    // Chrome: timestamps made strictly increasing and open function spans
+    last_ts: f64,
+    base_depth: Option<u32>,
+    open_spans: Vec<String>,
+    pending: Option<(f64, serde_json::Value)>,
+    // Binary: previous values for delta coding
+    last_address: u64,
+    last_timestamp: u64,
+    register_ids: HashMap<String, u64>,
+    last_registers: HashMap<String, u64>,
+}
+
+impl Exporter {
+    fn new(format: Format, file: File, session: &str) -> Self {
+        Exporter {
+            format,
+            out: BufWriter::new(file),
+            session: session.to_string(),
+            count: 0,
+            last_ts: f64::MIN,
+            base_depth: None,
+            open_spans: Vec::new(),
+            pending: None,
+            last_address: 0,
+            last_timestamp: 0,
+            register_ids: HashMap::new(),
+            last_registers: HashMap::new(),
+        }
+    }
+
+    fn begin(&mut self) -> std::io::Result<()> {
+        match self.format {
+            Format::Chrome => {
+                write!(self.out, "{{\"displayTimeUnit\":\"ns\",\"traceEvents\":[")?;
+                let metadata = serde_json::json!({
+                    "ph": "M", "pid": 1, "tid": 1, "name": "process_name",
+                    "args": { "name": format!("DynaDbg trace {}", self.session) },
+                });
+                write!(self.out, "\n{}", metadata)
+            }
+            Format::Text => writeln!(self.out, "# DynaDbg trace {}\n# index  time  depth  address  instruction  ; location", self.session),
+            Format::Binary => {
+                self.out.write_all(BINARY_MAGIC)?;
+                self.out.write_all(&BINARY_VERSION.to_le_bytes())?;
+                self.out.write_all(&0u64.to_le_bytes())?;
+                let mut session = Vec::new();
+                write_bytes(&mut session, self.session.as_bytes());
+                self.out.write_all(&session)
+            }
+        }
+    }
+
+    fn write(&mut self, entry: &TraceEntryData) -> std::io::Result<()> {
+        match self.format {
+            Format::Chrome => self.write_chrome(entry)?,
+            Format::Text => self.write_text(entry)?,
+            Format::Binary => self.write_binary(entry)?,
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    fn chrome_event(&mut self, event: serde_json::Value) -> std::io::Result<()> {
+        write!(self.out, ",\n{}", event)
+    }
+
+    /// One "X" event per instruction lasting until the next one, inside B/E
+    /// spans that follow the call depth
+    fn write_chrome(&mut self, entry: &TraceEntryData) -> std::io::Result<()> {
+        // Local clock when synced, so the export lines up with host-side traces
+        let ms = entry.local_timestamp.unwrap_or(entry.timestamp);
+        let ts = (ms as f64 * 1000.0).max(self.last_ts + 0.001);
+        self.last_ts = ts;
+        if let Some((start, mut event)) = self.pending.take() {
+            event["dur"] = serde_json::json!(ts - start);
+            self.chrome_event(event)?;
+        }
+
+        let base = *self.base_depth.get_or_insert(entry.depth);
+        let depth = entry.depth.saturating_sub(base) as usize;
+        while self.open_spans.len() > depth {
+            let name = self.open_spans.pop().unwrap_or_default();
+            self.chrome_event(serde_json::json!({ "ph": "E", "pid": 1, "tid": 1, "ts": ts, "name": name }))?;
+        }
+        while self.open_spans.len() < depth {
+            let name = entry.function_name.clone()
+                .or_else(|| entry.library_expression.clone())
+                .unwrap_or_else(|| entry.address.clone());
+            self.chrome_event(serde_json::json!({ "ph": "B", "pid": 1, "tid": 1, "ts": ts, "name": name }))?;
+            self.open_spans.push(name);
+        }
+
+        let mut args = serde_json::json!({
+            "address": entry.address,
+            "instruction": instruction_text(entry),
+            "target_ms": entry.timestamp,
+        });
+        if let Some(expression) = &entry.library_expression {
+            args["location"] = serde_json::json!(expression);
+        }
+        self.pending = Some((ts, serde_json::json!({
+            "ph": "X", "pid": 1, "tid": 1, "ts": ts, "cat": "instruction",
+            "name": entry.opcode, "args": args,
+        })));
+        Ok(())
+    }
+
+    fn write_text(&mut self, entry: &TraceEntryData) -> std::io::Result<()> {
+        let time = match entry.local_timestamp {
+            Some(local) => clock_sync::iso_timestamp(local),
+            None => entry.timestamp.to_string(),
+        };
+        let location = entry.library_expression.as_deref().or(entry.function_name.as_deref()).unwrap_or("");
+        writeln!(
+            self.out,
+            "{:>8}  {}  {:>3}  {}{}  {}{}{}",
+            self.count,
+            time,
+            entry.depth,
+            "  ".repeat(entry.depth.min(32) as usize),
+            entry.address,
+            instruction_text(entry),
+            if location.is_empty() { "" } else { "  ; " },
+            location
+        )
+    }
+
+    /// Record: flags, address and timestamp deltas, depth, instruction bytes
+    /// (or text), then the registers that changed. A register name is written
+    /// once, the first time its id appears.
+    fn write_binary(&mut self, entry: &TraceEntryData) -> std::io::Result<()> {
+        let address = parse_hex(&entry.address).unwrap_or(0);
+        let bytes = instruction_bytes(&entry.instruction);
+        let mut flags = 0u8;
+        if entry.is_call {
+            flags |= FLAG_CALL;
+        }
+        if entry.is_return {
+            flags |= FLAG_RETURN;
+        }
+        if bytes.is_empty() {
+            flags |= FLAG_TEXT;
+        }
+
+        let mut record = vec![flags];
+        write_signed(&mut record, address.wrapping_sub(self.last_address) as i64);
+        write_signed(&mut record, entry.timestamp.wrapping_sub(self.last_timestamp) as i64);
+        write_varint(&mut record, entry.depth as u64);
+        if bytes.is_empty() {
+            write_bytes(&mut record, instruction_text(entry).as_bytes());
+        } else {
+            write_bytes(&mut record, &bytes);
+        }
+        self.last_address = address;
+        self.last_timestamp = entry.timestamp;
+
+        let mut changed: Vec<(String, u64)> = entry.registers.as_object()
+            .map(|registers| registers.iter()
+                .filter_map(|(name, value)| {
+                    let value = match value {
+                        serde_json::Value::String(s) => parse_hex(s)?,
+                        serde_json::Value::Number(n) => n.as_u64()?,
+                        _ => return None,
+                    };
+                    Some((name.to_lowercase(), value))
+                })
+                .filter(|(name, value)| self.last_registers.get(name) != Some(value))
+                .collect())
+            .unwrap_or_default();
+        changed.sort();
+        write_varint(&mut record, changed.len() as u64);
+        for (name, value) in changed {
+            let next_id = self.register_ids.len() as u64;
+            let id = *self.register_ids.entry(name.clone()).or_insert(next_id);
+            write_varint(&mut record, id);
+            if id == next_id {
+                write_bytes(&mut record, name.as_bytes());
+            }
+            write_varint(&mut record, value);
+            self.last_registers.insert(name, value);
+        }
+        self.out.write_all(&record)
+    }
+
+    fn finish(mut self) -> std::io::Result<u64> {
+        match self.format {
+            Format::Chrome => {
+                let ts = self.last_ts.max(0.0) + 0.001;
+                if let Some((start, mut event)) = self.pending.take() {
+                    event["dur"] = serde_json::json!(ts - start);
+                    self.chrome_event(event)?;
+                }
+                while let Some(name) = self.open_spans.pop() {
+                    self.chrome_event(serde_json::json!({ "ph": "E", "pid": 1, "tid": 1, "ts": ts, "name": name }))?;
+                }
+                writeln!(self.out, "\n]}}")?;
+            }
+            Format::Text => {}
+            Format::Binary => {
+                let mut file = self.out.into_inner().map_err(|e| e.into_error())?;
+                let size = file.stream_position()?;
+                file.seek(SeekFrom::Start(BINARY_COUNT_OFFSET))?;
+                file.write_all(&(self.count as u64).to_le_bytes())?;
+                file.sync_all()?;
+                return Ok(size);
+            }
+        }
+        self.out.flush()?;
+        self.out.get_ref().metadata().map(|m| m.len())
+    }
+}
+
+/// Write a recorded trace to `path` as Chrome trace event JSON (opens in
+/// chrome://tracing and Perfetto), a text listing or the compact binary format.
+/// Sessions are named as in trace_sources::load; entries are read and written
+/// in batches so multi-million entry traces are never held twice.
+#[tauri::command]
+pub async fn export_trace_session(
+    state: tauri::State<'_, AppStateType>,
+    session_id: String,
+    format: String,
+    path: String,
+) -> Result<TraceExportResult, String> {
+    let parsed = Format::parse(&format)?;
+    let file = File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let symbolizer = Symbolizer::load(state.inner()).await?;
+    let mut exporter = Exporter::new(parsed, file, &session_id);
+    exporter.begin().map_err(|e| format!("Failed to write {}: {}", path, e))?;
+
+    let entries = trace_sources::for_each_chunk(state.inner(), &session_id, EXPORT_CHUNK, |chunk| {
+        for entry in chunk {
+            if entry.library_expression.is_none() {
+                let mut entry = entry.clone();
+                symbolizer.annotate_trace_entry(&mut entry);
+                exporter.write(&entry)
+            } else {
+                exporter.write(entry)
+            }
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        }
+        Ok(())
+    })
+    .await?;
+
+    let bytes_written = exporter.finish().map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(TraceExportResult { path, format: format.to_lowercase(), entries, bytes_written })
+}
@@ -0,0 +1,191 @@
+use tokio::io::AsyncReadExt;
+
+use crate::clock_sync;
+use crate::native_trace;
+use crate::state::{AppStateType, TraceEntryData};
+
+// Layout of the server's .dyntrace files (see traceFileParser.ts)
+const DYNTRACE_MAGIC: &[u8; 7] = b"DYNATRC";
+const DYNTRACE_HEADER_SIZE: usize = 32;
+const DYNTRACE_ENTRY_SIZE: usize = 1920;
+const DYNTRACE_INSTRUCTION_SIZE: usize = 64;
+const DYNTRACE_ARCH_ARM64: u32 = 1;
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap_or_default())
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap_or_default())
+}
+
+/// Mnemonic and operands of "0xADDRESS|BYTES|MNEMONIC OPERANDS"
+fn split_instruction(instruction: &str) -> (String, String) {
+    let text = instruction.splitn(3, '|').nth(2).unwrap_or(instruction).trim();
+    match text.split_once(' ') {
+        Some((opcode, operands)) => (opcode.to_string(), operands.trim().to_string()),
+        None => (text.to_string(), String::new()),
+    }
+}
+
+/// Entry count of a .dyntrace header, or an error for other files
+fn dyntrace_count(header: &[u8]) -> Result<usize, String> {
+    if header.len() < DYNTRACE_HEADER_SIZE || &header[..7] != DYNTRACE_MAGIC {
+        return Err("Not a DynaDbg trace file".to_string());
+    }
+    let architecture = u32_at(header, 16);
+    if architecture != DYNTRACE_ARCH_ARM64 {
+        return Err(format!("Unsupported trace file architecture {}", architecture));
+    }
+    Ok(u32_at(header, 12) as usize)
+}
+
+/// One .dyntrace entry; `depth` carries the call depth across entries
+fn dyntrace_entry(raw: &[u8], index: usize, depth: &mut u32, session: &str) -> TraceEntryData {
+    // The server records microseconds; trace entries use milliseconds
+    let timestamp = u64_at(raw, 0) / 1000;
+    let pc = u64_at(raw, 8);
+    let mut registers = serde_json::Map::new();
+    for i in 0..30 {
+        registers.insert(format!("x{}", i), format!("0x{:x}", u64_at(raw, 16 + i * 8)).into());
+    }
+    for (i, name) in ["lr", "sp", "cpsr"].iter().enumerate() {
+        registers.insert(name.to_string(), format!("0x{:x}", u64_at(raw, 256 + i * 8)).into());
+    }
+    registers.insert("pc".to_string(), format!("0x{:x}", pc).into());
+    registers.insert("fp".to_string(), registers["x29"].clone());
+
+    let text = &raw[284..284 + DYNTRACE_INSTRUCTION_SIZE];
+    let text = &text[..text.iter().position(|&b| b == 0).unwrap_or(text.len())];
+    let instruction = String::from_utf8_lossy(text).to_string();
+    let (opcode, operands) = split_instruction(&instruction);
+    let is_call = matches!(opcode.to_lowercase().as_str(), "bl" | "blr" | "blx");
+    let is_return = matches!(opcode.to_lowercase().as_str(), "ret" | "eret");
+    if is_return {
+        *depth = depth.saturating_sub(1);
+    }
+    let entry = TraceEntryData {
+        id: index as u32 + 1,
+        address: format!("0x{:x}", pc),
+        instruction,
+        opcode,
+        operands,
+        registers: serde_json::Value::Object(registers),
+        depth: *depth,
+        is_call,
+        is_return,
+        function_name: None,
+        timestamp,
+        library_expression: None,
+        target_address: session.to_string(),
+        local_timestamp: clock_sync::local_timestamp(timestamp),
+    };
+    if is_call {
+        *depth += 1;
+    }
+    entry
+}
+
+/// Entries of a server-written ARM64 .dyntrace file
+pub fn parse_dyntrace(bytes: &[u8], session: &str) -> Result<Vec<TraceEntryData>, String> {
+    let count = dyntrace_count(bytes)?;
+    let mut depth = 0u32;
+    Ok(bytes[DYNTRACE_HEADER_SIZE..].chunks_exact(DYNTRACE_ENTRY_SIZE)
+        .take(count)
+        .enumerate()
+        .map(|(index, raw)| dyntrace_entry(raw, index, &mut depth, session))
+        .collect())
+}
+
+/// Entries of a recorded trace. `session` is "current" (the active trace
+/// session), "native:<thread id>" (a native trace) or the path of a .dyntrace
+/// file or a JSON array of trace entries.
+pub async fn load(state: &AppStateType, session: &str) -> Result<Vec<TraceEntryData>, String> {
+    if session == "current" {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        return Ok(state_guard.trace_store.clone());
+    }
+    if let Some(thread_id) = session.strip_prefix("native:") {
+        let thread_id = thread_id.trim().parse::<u64>()
+            .map_err(|_| format!("Invalid native trace session: {}", session))?;
+        return native_trace::read_entries(state, thread_id, 0, u32::MAX);
+    }
+
+    let bytes = tokio::fs::read(session)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", session, e))?;
+    if bytes.starts_with(DYNTRACE_MAGIC) {
+        return parse_dyntrace(&bytes, session);
+    }
+    serde_json::from_slice(&bytes).map_err(|e| format!("Unrecognized trace file {}: {}", session, e))
+}
+
+/// Entries of `session` handed to `sink` at most `chunk` at a time, so long
+/// sessions are never copied whole. Returns the number of entries visited.
+pub async fn for_each_chunk<F>(state: &AppStateType, session: &str, chunk: usize, mut sink: F) -> Result<usize, String>
+where
+    F: FnMut(&[TraceEntryData]) -> Result<(), String>,
+{
+    let chunk = chunk.max(1);
+    let mut visited = 0;
+    if session == "current" {
+        loop {
+            let entries: Vec<TraceEntryData> = {
+                let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+                state_guard.trace_store.iter().skip(visited).take(chunk).cloned().collect()
+            };
+            if entries.is_empty() {
+                return Ok(visited);
+            }
+            sink(&entries)?;
+            visited += entries.len();
+        }
+    }
+    if let Some(thread_id) = session.strip_prefix("native:") {
+        let thread_id = thread_id.trim().parse::<u64>()
+            .map_err(|_| format!("Invalid native trace session: {}", session))?;
+        loop {
+            let entries = native_trace::read_entries(state, thread_id, visited as u32, chunk as u32)?;
+            if entries.is_empty() {
+                return Ok(visited);
+            }
+            sink(&entries)?;
+            visited += entries.len();
+        }
+    }
+
+    let mut file = tokio::io::BufReader::new(
+        tokio::fs::File::open(session).await.map_err(|e| format!("Failed to open {}: {}", session, e))?,
+    );
+    let mut header = vec![0u8; DYNTRACE_HEADER_SIZE];
+    let header_len = file.read(&mut header).await.map_err(|e| e.to_string())?;
+    if !header[..header_len].starts_with(DYNTRACE_MAGIC) {
+        // JSON arrays are parsed whole
+        let entries = load(state, session).await?;
+        for part in entries.chunks(chunk) {
+            sink(part)?;
+        }
+        return Ok(entries.len());
+    }
+    file.read_exact(&mut header[header_len..]).await.map_err(|e| e.to_string())?;
+    let count = dyntrace_count(&header)?;
+    let mut depth = 0u32;
+    let mut raw = vec![0u8; DYNTRACE_ENTRY_SIZE];
+    let mut entries = Vec::with_capacity(chunk.min(count));
+    while visited + entries.len() < count {
+        if file.read_exact(&mut raw).await.is_err() {
+            break; // Truncated file: keep what was written
+        }
+        entries.push(dyntrace_entry(&raw, visited + entries.len(), &mut depth, session));
+        if entries.len() == chunk {
+            sink(&entries)?;
+            visited += entries.len();
+            entries.clear();
+        }
+    }
+    if !entries.is_empty() {
+        sink(&entries)?;
+        visited += entries.len();
+    }
+    Ok(visited)
+}
@@ -0,0 +1,13 @@
+pub use dynadbg_core::value_codec::*;
+
+/// Encode text as `data_type` bytes (the form scan patterns and writes use)
+#[tauri::command]
+pub fn encode_value(data_type: String, text: String) -> Result<Vec<u8>, String> {
+    encode(&data_type, &text)
+}
+
+/// Decode `data_type` bytes to display text
+#[tauri::command]
+pub fn decode_value(data_type: String, bytes: Vec<u8>) -> Result<String, String> {
+    decode(&data_type, &bytes)
+}
@@ -0,0 +1,282 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+use crate::{get_data_size, memory_regions, read_memory_from_server, server_address, write_memory_to_server};
+
+// Handles live above every 48-bit user-space address but below 2^53, so the
+// frontend can carry them as plain JS numbers
+pub const VIRTUAL_ADDRESS_BASE: u64 = 0x1F_0000_0000_0000;
+const VIRTUAL_ADDRESS_END: u64 = 0x20_0000_0000_0000;
+// Space per handle; handle + n addresses n bytes past the resolved address
+const HANDLE_STRIDE: u64 = 0x1_0000;
+
+/// Value-level transform applied on top of the resolved address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValueTransform {
+    Linear { scale: f64, offset: f64 },   // shown = raw * scale + offset
+    Xor { key: u64 },                     // shown = raw ^ key
+    BitField { shift: u32, width: u32 },  // shown = (raw >> shift) & mask
+}
+
+/// How a virtual address resolves: an optional module-relative base followed
+/// by a pointer chain (`[[base] + o0] + o1 ...`), then an optional transform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirtualAddressSpec {
+    #[serde(default)]
+    pub module_name: Option<String>,    // base_address is relative to this module
+    #[serde(default)]
+    pub base_address: u64,
+    #[serde(default)]
+    pub offsets: Vec<i64>,
+    #[serde(default)]
+    pub pointer_size: Option<usize>,    // Default 8
+    #[serde(default)]
+    pub value_type: Option<String>,     // Required with a transform (int8..uint64, float, double)
+    #[serde(default)]
+    pub transform: Option<ValueTransform>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirtualAddressInfo {
+    pub handle: u64,
+    pub name: String,
+    pub spec: VirtualAddressSpec,
+}
+
+static VIRTUAL_ADDRESSES: Lazy<RwLock<BTreeMap<u64, VirtualAddressInfo>>> = Lazy::new(|| {
+    RwLock::new(BTreeMap::new())
+});
+
+// Handles are never reused, so a stale handle cannot alias a newer expression
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(VIRTUAL_ADDRESS_BASE);
+
+pub fn is_virtual(address: u64) -> bool {
+    (VIRTUAL_ADDRESS_BASE..VIRTUAL_ADDRESS_END).contains(&address)
+}
+
+fn lookup(address: u64) -> Result<(VirtualAddressInfo, u64), String> {
+    let handle = address - (address - VIRTUAL_ADDRESS_BASE) % HANDLE_STRIDE;
+    let registry = VIRTUAL_ADDRESSES.read().map_err(|e| e.to_string())?;
+    let info = registry.get(&handle).cloned()
+        .ok_or_else(|| format!("Unknown virtual address 0x{:x}", address))?;
+    Ok((info, address - handle))
+}
+
+/// Lowest mapped address of a module, from the cached memory map
+async fn module_base(module_name: &str) -> Result<u64, String> {
+    let find = |regions: &[memory_regions::MemoryRegion]| {
+        regions.iter()
+            .filter(|r| {
+                r.module_name.as_deref() == Some(module_name)
+                    || r.mapped_file.as_deref()
+                        .is_some_and(|p| p.rsplit(['/', '\\']).next() == Some(module_name))
+            })
+            .map(|r| r.base)
+            .min()
+    };
+    if let Some(base) = find(&memory_regions::get_cached_regions(None, false).await?) {
+        return Ok(base);
+    }
+    find(&memory_regions::get_cached_regions(None, true).await?)
+        .ok_or_else(|| format!("Module '{}' is not loaded", module_name))
+}
+
+/// Concrete address the spec currently points at
+async fn resolve_spec(spec: &VirtualAddressSpec) -> Result<u64, String> {
+    let (host, port) = server_address()?;
+    let pointer_size = spec.pointer_size.unwrap_or(8);
+    let mut address = match &spec.module_name {
+        Some(module) => module_base(module).await?.wrapping_add(spec.base_address),
+        None => spec.base_address,
+    };
+    for offset in &spec.offsets {
+        let bytes = read_memory_from_server(&host, port, address, pointer_size).await?;
+        if bytes.len() < pointer_size {
+            return Err(format!("Failed to read pointer at 0x{:x}", address));
+        }
+        let pointer = if pointer_size == 4 {
+            u32::from_le_bytes(bytes[..4].try_into().unwrap()) as u64
+        } else {
+            u64::from_le_bytes(bytes[..8].try_into().unwrap())
+        };
+        if pointer == 0 {
+            return Err(format!("Null pointer at 0x{:x}", address));
+        }
+        address = pointer.wrapping_add(*offset as u64);
+    }
+    Ok(address)
+}
+
+fn decode(value_type: &str, bytes: &[u8]) -> f64 {
+    let mut buf = [0u8; 8];
+    buf[..bytes.len().min(8)].copy_from_slice(&bytes[..bytes.len().min(8)]);
+    match value_type {
+        "int8" => bytes[0] as i8 as f64,
+        "uint8" => bytes[0] as f64,
+        "int16" => i16::from_le_bytes([buf[0], buf[1]]) as f64,
+        "uint16" => u16::from_le_bytes([buf[0], buf[1]]) as f64,
+        "int32" => i32::from_le_bytes(buf[..4].try_into().unwrap()) as f64,
+        "uint32" => u32::from_le_bytes(buf[..4].try_into().unwrap()) as f64,
+        "int64" => i64::from_le_bytes(buf) as f64,
+        "uint64" => u64::from_le_bytes(buf) as f64,
+        "float" => f32::from_le_bytes(buf[..4].try_into().unwrap()) as f64,
+        "double" => f64::from_le_bytes(buf),
+        _ => 0.0,
+    }
+}
+
+fn encode(value_type: &str, value: f64) -> Vec<u8> {
+    match value_type {
+        "int8" | "uint8" => vec![value.round() as i64 as u8],
+        "int16" | "uint16" => (value.round() as i64 as u16).to_le_bytes().to_vec(),
+        "int32" | "uint32" => (value.round() as i64 as u32).to_le_bytes().to_vec(),
+        "int64" => (value.round() as i64).to_le_bytes().to_vec(),
+        "uint64" => (value.round() as u64).to_le_bytes().to_vec(),
+        "float" => (value as f32).to_le_bytes().to_vec(),
+        _ => value.to_le_bytes().to_vec(),
+    }
+}
+
+fn raw_bits(value_type: &str, bytes: &[u8]) -> u64 {
+    let mut buf = [0u8; 8];
+    let size = get_data_size(value_type).min(bytes.len());
+    buf[..size].copy_from_slice(&bytes[..size]);
+    u64::from_le_bytes(buf)
+}
+
+fn bits_to_bytes(value_type: &str, bits: u64) -> Vec<u8> {
+    bits.to_le_bytes()[..get_data_size(value_type)].to_vec()
+}
+
+fn bit_mask(width: u32) -> u64 {
+    if width >= 64 { u64::MAX } else { (1u64 << width) - 1 }
+}
+
+/// Raw bytes at the target -> displayed value bytes
+fn apply_transform(value_type: &str, transform: &ValueTransform, raw: &[u8]) -> Vec<u8> {
+    match transform {
+        ValueTransform::Linear { scale, offset } => encode(value_type, decode(value_type, raw) * scale + offset),
+        ValueTransform::Xor { key } => bits_to_bytes(value_type, raw_bits(value_type, raw) ^ key),
+        ValueTransform::BitField { shift, width } => {
+            bits_to_bytes(value_type, (raw_bits(value_type, raw) >> shift) & bit_mask(*width))
+        }
+    }
+}
+
+/// Displayed value bytes -> raw bytes to store (`current` is needed for bit fields)
+fn invert_transform(value_type: &str, transform: &ValueTransform, shown: &[u8], current: &[u8]) -> Result<Vec<u8>, String> {
+    Ok(match transform {
+        ValueTransform::Linear { scale, offset } => {
+            if *scale == 0.0 {
+                return Err("Cannot write through a transform with scale 0".to_string());
+            }
+            encode(value_type, (decode(value_type, shown) - offset) / scale)
+        }
+        ValueTransform::Xor { key } => bits_to_bytes(value_type, raw_bits(value_type, shown) ^ key),
+        ValueTransform::BitField { shift, width } => {
+            let mask = bit_mask(*width) << shift;
+            let merged = (raw_bits(value_type, current) & !mask) | ((raw_bits(value_type, shown) << shift) & mask);
+            bits_to_bytes(value_type, merged)
+        }
+    })
+}
+
+/// Transform and value type when the access covers exactly the transformed value
+fn transform_for(spec: &VirtualAddressSpec, delta: u64, size: usize) -> Option<(&str, &ValueTransform)> {
+    let value_type = spec.value_type.as_deref()?;
+    let transform = spec.transform.as_ref()?;
+    (delta == 0 && size == get_data_size(value_type)).then_some((value_type, transform))
+}
+
+/// Read through a virtual address (handle + delta)
+pub async fn read(address: u64, size: usize) -> Result<Vec<u8>, String> {
+    let (info, delta) = lookup(address)?;
+    let (host, port) = server_address()?;
+    let target = resolve_spec(&info.spec).await?.wrapping_add(delta);
+    let raw = read_memory_from_server(&host, port, target, size).await?;
+    match transform_for(&info.spec, delta, size) {
+        Some((value_type, transform)) if raw.len() >= size => Ok(apply_transform(value_type, transform, &raw)),
+        _ => Ok(raw),
+    }
+}
+
+/// Write through a virtual address (handle + delta)
+pub async fn write(address: u64, data: &[u8]) -> Result<(), String> {
+    let (info, delta) = lookup(address)?;
+    let (host, port) = server_address()?;
+    let target = resolve_spec(&info.spec).await?.wrapping_add(delta);
+    let bytes = match transform_for(&info.spec, delta, data.len()) {
+        Some((value_type, transform)) => {
+            let current = if matches!(transform, ValueTransform::BitField { .. }) {
+                read_memory_from_server(&host, port, target, data.len()).await?
+            } else {
+                Vec::new()
+            };
+            invert_transform(value_type, transform, data, &current)?
+        }
+        None => data.to_vec(),
+    };
+    write_memory_to_server(&host, port, target, &bytes).await
+}
+
+/// Register an expression under a synthetic address that can be read, written,
+/// watched and frozen like a real one
+#[tauri::command]
+pub fn register_virtual_address(name: String, spec: VirtualAddressSpec) -> Result<VirtualAddressInfo, String> {
+    if spec.transform.is_some() {
+        let value_type = spec.value_type.as_deref().ok_or("A transform needs a value_type")?;
+        if !matches!(value_type, "int8" | "uint8" | "int16" | "uint16" | "int32" | "uint32" | "int64" | "uint64" | "float" | "double") {
+            return Err(format!("Unsupported value type '{}'", value_type));
+        }
+    }
+    if let Some(ValueTransform::BitField { shift, width }) = &spec.transform {
+        if *shift >= 64 || *width == 0 {
+            return Err("Bit field needs shift < 64 and a non-zero width".to_string());
+        }
+    }
+    if spec.pointer_size.is_some_and(|s| s != 4 && s != 8) {
+        return Err("pointer_size must be 4 or 8".to_string());
+    }
+
+    let handle = NEXT_HANDLE.fetch_add(HANDLE_STRIDE, Ordering::Relaxed);
+    if handle >= VIRTUAL_ADDRESS_END {
+        return Err("Virtual address space exhausted".to_string());
+    }
+    let info = VirtualAddressInfo { handle, name, spec };
+    VIRTUAL_ADDRESSES.write().map_err(|e| e.to_string())?.insert(handle, info.clone());
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn unregister_virtual_address(handle: u64) -> Result<bool, String> {
+    let mut registry = VIRTUAL_ADDRESSES.write().map_err(|e| e.to_string())?;
+    Ok(registry.remove(&handle).is_some())
+}
+
+#[tauri::command]
+pub fn list_virtual_addresses() -> Result<Vec<VirtualAddressInfo>, String> {
+    let registry = VIRTUAL_ADDRESSES.read().map_err(|e| e.to_string())?;
+    Ok(registry.values().cloned().collect())
+}
+
+/// Concrete address a virtual address currently resolves to
+#[tauri::command]
+pub async fn resolve_virtual_address(address: u64) -> Result<u64, String> {
+    let (info, delta) = lookup(address)?;
+    Ok(resolve_spec(&info.spec).await?.wrapping_add(delta))
+}
+
+#[tauri::command]
+pub async fn read_virtual_address(address: u64, size: usize) -> Result<Vec<u8>, String> {
+    read(address, size).await
+}
+
+#[tauri::command]
+pub async fn write_virtual_address(address: u64, data: Vec<u8>) -> Result<bool, String> {
+    write(address, &data).await?;
+    Ok(true)
+}
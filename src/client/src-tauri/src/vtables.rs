@@ -0,0 +1,516 @@
+use cpp_demangle::{DemangleOptions, Symbol as CppSymbol};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::memory_regions::{self, MemoryRegion};
+use crate::state::{AppStateType, ModuleInfo};
+use crate::symbolizer::Symbolizer;
+use crate::{data_overlay, db, read_memory_from_server, server_address};
+
+const WORD: u64 = 8;
+const READ_CHUNK: u64 = 0x10_0000;
+const BLOCK_SIZE: u64 = 256;
+const MAX_SCAN_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_METHODS: usize = 512;
+const MAX_NAME_LENGTH: usize = 512;
+const MAX_BASES: u32 = 64;
+// Slots a vtable needs to be accepted without RTTI or a Ghidra label
+const MIN_HEURISTIC_METHODS: usize = 2;
+// Itanium offset-to-top of secondary vtables; larger values are not a vtable prefix
+const MAX_OFFSET_TO_TOP: i64 = 1 << 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirtualMethod {
+    pub index: u32,
+    pub address: Option<u64>,         // Resolved against the loaded modules
+    pub module_name: String,          // Empty when the target is outside any module
+    pub module_offset: u64,           // Absolute address when module_name is empty
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VtableInfo {
+    pub address: Option<u64>,         // Address point (first method slot) in the attached process
+    pub module_offset: u64,
+    pub class_name: Option<String>,
+    pub rtti: Option<String>,         // "itanium" | "msvc" when parsed from RTTI
+    pub source: String,               // "rtti", "ghidra" or "heuristic"
+    pub offset_to_top: i64,           // Non-zero for secondary vtables of multiple inheritance
+    pub base_classes: Vec<String>,    // Direct bases (Itanium) or all bases (MSVC), from RTTI
+    pub methods: Vec<VirtualMethod>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassNode {
+    pub name: String,
+    pub bases: Vec<String>,
+    pub derived: Vec<String>,
+    pub vtables: Vec<u64>,            // Module offsets of the class's vtables
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleVtables {
+    pub module_name: String,
+    pub vtables: Vec<VtableInfo>,
+    pub classes: Vec<ClassNode>,
+}
+
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cpp_vtables (
+            target_os TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            module_offset INTEGER NOT NULL,
+            class_name TEXT,
+            rtti TEXT,
+            source TEXT NOT NULL,
+            offset_to_top INTEGER NOT NULL,
+            base_classes TEXT NOT NULL,
+            methods TEXT NOT NULL,
+            PRIMARY KEY(target_os, module_name, module_offset)
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// The module's data regions, read up front, plus cached block reads of
+/// anything else the RTTI points to
+struct Image {
+    host: String,
+    port: u16,
+    regions: Vec<MemoryRegion>,
+    chunks: Vec<(u64, Vec<u8>)>,
+    blocks: HashMap<u64, Option<Vec<u8>>>,
+}
+
+impl Image {
+    fn region(&self, address: u64) -> Option<&MemoryRegion> {
+        let index = self.regions.partition_point(|r| r.base <= address).checked_sub(1)?;
+        self.regions.get(index).filter(|r| address < r.base + r.size)
+    }
+
+    fn is_code(&self, value: u64) -> bool {
+        value != 0 && self.region(value).is_some_and(|r| r.executable)
+    }
+
+    fn is_data(&self, value: u64) -> bool {
+        value.is_multiple_of(WORD) && self.region(value).is_some_and(|r| r.readable && !r.executable)
+    }
+
+    async fn bytes(&mut self, address: u64, length: usize) -> Option<Vec<u8>> {
+        let end = address.checked_add(length as u64)?;
+        for (base, data) in &self.chunks {
+            if address >= *base && end <= base + data.len() as u64 {
+                return Some(data[(address - base) as usize..(end - base) as usize].to_vec());
+            }
+        }
+        if !self.region(address).is_some_and(|r| r.readable && end <= r.base + r.size) {
+            return None;
+        }
+        let mut out = Vec::with_capacity(length);
+        let mut block = address - address % BLOCK_SIZE;
+        while block < end {
+            if !self.blocks.contains_key(&block) {
+                let data = read_memory_from_server(&self.host, self.port, block, BLOCK_SIZE as usize).await.ok();
+                self.blocks.insert(block, data);
+            }
+            let data = self.blocks.get(&block)?.as_ref()?;
+            let from = address.max(block) - block;
+            let to = end.min(block + BLOCK_SIZE) - block;
+            out.extend_from_slice(data.get(from as usize..to as usize)?);
+            block += BLOCK_SIZE;
+        }
+        Some(out)
+    }
+
+    async fn word(&mut self, address: u64) -> Option<u64> {
+        Some(u64::from_le_bytes(self.bytes(address, WORD as usize).await?.try_into().ok()?))
+    }
+
+    async fn u32(&mut self, address: u64) -> Option<u32> {
+        Some(u32::from_le_bytes(self.bytes(address, 4).await?.try_into().ok()?))
+    }
+
+    /// NUL-terminated string of symbol-name characters
+    async fn name(&mut self, address: u64) -> Option<String> {
+        let mut out = Vec::new();
+        for chunk in 0..(MAX_NAME_LENGTH as u64 / 32) {
+            for b in self.bytes(address + chunk * 32, 32).await? {
+                if b == 0 {
+                    return String::from_utf8(out).ok().filter(|s| !s.is_empty());
+                }
+                if !b.is_ascii_graphic() {
+                    return None;
+                }
+                out.push(b);
+            }
+        }
+        None
+    }
+}
+
+struct Rtti {
+    kind: &'static str,
+    class_name: String,
+    bases: Vec<String>,
+    offset_to_top: i64,
+}
+
+/// Class name of an Itanium std::type_info (its +8 slot is the mangled name)
+async fn itanium_type_name(image: &mut Image, type_info: u64) -> Option<String> {
+    if !image.is_data(type_info) {
+        return None;
+    }
+    let name_ptr = image.word(type_info + WORD).await?;
+    let mangled = image.name(name_ptr).await?;
+    // GCC marks types with internal linkage with a leading '*'
+    let demangled = CppSymbol::new(format!("_ZTS{}", mangled.trim_start_matches('*'))).ok()?
+        .demangle(&DemangleOptions::default()).ok()?;
+    Some(demangled.trim_start_matches("typeinfo name for ").to_string())
+}
+
+/// Itanium ABI: vtable[-1] is the type_info, vtable[-2] the offset to top.
+/// Bases come from __si_class_type_info (one base at +16) or
+/// __vmi_class_type_info (flags, count, then {type_info*, offset_flags}).
+async fn itanium_rtti(image: &mut Image, address_point: u64) -> Option<Rtti> {
+    let type_info = image.word(address_point - WORD).await?;
+    let offset_to_top = image.word(address_point - 2 * WORD).await? as i64;
+    if !(-MAX_OFFSET_TO_TOP..=0).contains(&offset_to_top) {
+        return None;
+    }
+    let class_name = itanium_type_name(image, type_info).await?;
+    let mut bases = Vec::new();
+    let single = image.word(type_info + 2 * WORD).await;
+    if let Some(base) = single {
+        if let Some(name) = itanium_type_name(image, base).await {
+            bases.push(name);
+        }
+    }
+    if bases.is_empty() {
+        let flags = image.u32(type_info + 2 * WORD).await.unwrap_or(u32::MAX);
+        let count = image.u32(type_info + 2 * WORD + 4).await.unwrap_or(0);
+        if flags < 4 && (1..=MAX_BASES).contains(&count) {
+            for i in 0..count as u64 {
+                let Some(base) = image.word(type_info + 3 * WORD + i * 2 * WORD).await else {
+                    break;
+                };
+                if let Some(name) = itanium_type_name(image, base).await {
+                    bases.push(name);
+                }
+            }
+        }
+    }
+    Some(Rtti { kind: "itanium", class_name, bases, offset_to_top })
+}
+
+/// ".?AVBar@Foo@@" -> "Foo::Bar"; templated names are returned undecorated only up to the prefix
+fn msvc_type_name(decorated: &str) -> Option<String> {
+    let rest = decorated.strip_prefix(".?A")?;
+    let rest = rest.get(1..)?;
+    if rest.contains("?$") {
+        return Some(rest.trim_end_matches('@').to_string());
+    }
+    let parts: Vec<&str> = rest.trim_end_matches('@').split('@').filter(|p| !p.is_empty()).collect();
+    (!parts.is_empty()).then(|| parts.into_iter().rev().collect::<Vec<_>>().join("::"))
+}
+
+/// MSVC x64: vtable[-1] is the RTTICompleteObjectLocator, whose fields are
+/// image-relative; its pSelf field pointing back at it validates the match
+async fn msvc_rtti(image: &mut Image, address_point: u64, module_base: u64) -> Option<Rtti> {
+    let locator = image.word(address_point - WORD).await?;
+    if !image.is_data(locator) || image.u32(locator).await? != 1 {
+        return None;
+    }
+    let self_rva = image.u32(locator + 20).await? as u64;
+    if module_base + self_rva != locator {
+        return None;
+    }
+    let offset = image.u32(locator + 4).await? as i64;
+    let type_descriptor = module_base + image.u32(locator + 12).await? as u64;
+    let class_name = msvc_type_name(&image.name(type_descriptor + 16).await?)?;
+
+    let hierarchy = module_base + image.u32(locator + 16).await? as u64;
+    let count = image.u32(hierarchy + 8).await?.min(MAX_BASES);
+    let array = module_base + image.u32(hierarchy + 12).await? as u64;
+    let mut bases = Vec::new();
+    // Entry 0 describes the class itself
+    for i in 1..count as u64 {
+        let Some(descriptor_rva) = image.u32(array + i * 4).await else {
+            break;
+        };
+        let Some(base_type_rva) = image.u32(module_base + descriptor_rva as u64).await else {
+            continue;
+        };
+        if let Some(name) = image.name(module_base + base_type_rva as u64 + 16).await.as_deref().and_then(msvc_type_name) {
+            if !bases.contains(&name) {
+                bases.push(name);
+            }
+        }
+    }
+    Some(Rtti { kind: "msvc", class_name, bases, offset_to_top: -offset })
+}
+
+/// Class name from a Ghidra vtable label ("Foo::vtable", "Foo::vftable", "vtable for Foo")
+fn label_class(label: &str) -> Option<String> {
+    let name = label.strip_prefix("vtable for ")
+        .or_else(|| label.strip_suffix("::vftable"))
+        .or_else(|| label.strip_suffix("::vtable"))
+        .or_else(|| label.strip_prefix("_ZTV").map(|_| label))?;
+    if name.starts_with("_ZTV") {
+        return CppSymbol::new(name).ok()?
+            .demangle(&DemangleOptions::default()).ok()
+            .map(|d| {
+                // cpp_demangle renders vtables as "{vtable(ns::Foo)}"
+                let d = d.trim_start_matches("vtable for ");
+                d.strip_prefix("{vtable(").and_then(|d| d.strip_suffix(")}")).unwrap_or(d).to_string()
+            });
+    }
+    Some(name.to_string())
+}
+
+fn methods_of(slots: &[u64], symbolizer: &Symbolizer) -> Vec<VirtualMethod> {
+    slots.iter().enumerate().map(|(index, &address)| {
+        let symbol = symbolizer.resolve(address);
+        VirtualMethod {
+            index: index as u32,
+            address: Some(address),
+            module_name: symbol.as_ref().map(|s| s.module_name.clone()).unwrap_or_default(),
+            module_offset: symbol.as_ref().map_or(address, |s| s.module_offset),
+            name: symbol.and_then(|s| s.function_name),
+        }
+    }).collect()
+}
+
+fn hierarchy(vtables: &[VtableInfo]) -> Vec<ClassNode> {
+    let mut classes: BTreeMap<String, ClassNode> = BTreeMap::new();
+    let node = |name: &str| ClassNode { name: name.to_string(), bases: Vec::new(), derived: Vec::new(), vtables: Vec::new() };
+    for vtable in vtables {
+        let Some(name) = &vtable.class_name else {
+            continue;
+        };
+        let class = classes.entry(name.clone()).or_insert_with(|| node(name));
+        class.vtables.push(vtable.module_offset);
+        for base in &vtable.base_classes {
+            if !class.bases.contains(base) {
+                class.bases.push(base.clone());
+            }
+        }
+    }
+    let edges: Vec<(String, String)> = classes.values()
+        .flat_map(|c| c.bases.iter().map(|b| (b.clone(), c.name.clone())))
+        .collect();
+    for (base, derived) in edges {
+        let class = classes.entry(base.clone()).or_insert_with(|| node(&base));
+        if !class.derived.contains(&derived) {
+            class.derived.push(derived);
+        }
+    }
+    classes.into_values().collect()
+}
+
+fn save(conn: &Connection, target_os: &str, module_name: &str, vtables: &[VtableInfo]) -> Result<(), String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM cpp_vtables WHERE target_os = ?1 AND module_name = ?2", params![target_os, module_name])
+        .map_err(|e| e.to_string())?;
+    for vtable in vtables {
+        tx.execute(
+            "INSERT INTO cpp_vtables (target_os, module_name, module_offset, class_name, rtti, source, offset_to_top, base_classes, methods)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                target_os,
+                module_name,
+                vtable.module_offset as i64,
+                vtable.class_name,
+                vtable.rtti,
+                vtable.source,
+                vtable.offset_to_top,
+                serde_json::to_string(&vtable.base_classes).map_err(|e| e.to_string())?,
+                serde_json::to_string(&vtable.methods).map_err(|e| e.to_string())?,
+            ],
+        ).map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Stored vtables with addresses resolved against the loaded modules
+fn load(conn: &Connection, target_os: &str, module_name: &str, modules: &[ModuleInfo]) -> Result<Vec<VtableInfo>, String> {
+    let base_of = |name: &str| modules.iter().find(|m| m.modulename == name).map(|m| m.base);
+    let mut stmt = conn.prepare(
+        "SELECT module_offset, class_name, rtti, source, offset_to_top, base_classes, methods FROM cpp_vtables
+         WHERE target_os = ?1 AND module_name = ?2 ORDER BY module_offset",
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![target_os, module_name], |row| {
+        Ok((
+            row.get::<_, i64>(0)? as u64,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, i64>(4)?,
+            row.get::<_, String>(5)?,
+            row.get::<_, String>(6)?,
+        ))
+    }).map_err(|e| e.to_string())?;
+    let mut vtables = Vec::new();
+    for row in rows {
+        let (module_offset, class_name, rtti, source, offset_to_top, bases, methods) = row.map_err(|e| e.to_string())?;
+        let mut methods: Vec<VirtualMethod> = serde_json::from_str(&methods).unwrap_or_default();
+        for method in methods.iter_mut() {
+            method.address = if method.module_name.is_empty() {
+                Some(method.module_offset)
+            } else {
+                base_of(&method.module_name).map(|base| base + method.module_offset)
+            };
+        }
+        vtables.push(VtableInfo {
+            address: base_of(module_name).map(|base| base + module_offset),
+            module_offset,
+            class_name,
+            rtti,
+            source,
+            offset_to_top,
+            base_classes: serde_json::from_str(&bases).unwrap_or_default(),
+            methods,
+        });
+    }
+    Ok(vtables)
+}
+
+fn target(state: &AppStateType) -> Result<(String, String, Vec<ModuleInfo>), String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    let info = state_guard.server_info.as_ref();
+    Ok((
+        info.map(|i| i.target_os.clone()).unwrap_or_default(),
+        info.map(|i| i.arch.clone()).unwrap_or_default(),
+        state_guard.attached_modules.clone(),
+    ))
+}
+
+/// Find the C++ vtables in a loaded module's data: runs of code pointers in
+/// its read-only data, confirmed by MSVC or Itanium RTTI, plus vtables Ghidra
+/// labeled. Results (classes, bases, virtual methods) replace the module's
+/// stored ones. 64-bit targets only.
+#[tauri::command]
+pub async fn scan_vtables(state: tauri::State<'_, AppStateType>, module_name: String) -> Result<ModuleVtables, String> {
+    let (target_os, arch, modules) = target(state.inner())?;
+    if !matches!(arch.as_str(), "arm64" | "aarch64" | "x86_64") {
+        return Err("Vtable scanning supports 64-bit targets only".to_string());
+    }
+    let module = modules.iter().find(|m| m.modulename == module_name)
+        .ok_or_else(|| format!("Module '{}' is not loaded", module_name))?
+        .clone();
+    let (host, port) = server_address()?;
+    let regions = memory_regions::get_cached_regions(Some(state.inner()), false).await?;
+
+    let mut image = Image { host: host.clone(), port, regions, chunks: Vec::new(), blocks: HashMap::new() };
+    let data_regions: Vec<(u64, u64)> = image.regions.iter()
+        .filter(|r| r.readable && !r.executable && r.base >= module.base && r.base < module.base + module.size)
+        .map(|r| (r.base, r.size.min(module.base + module.size - r.base)))
+        .collect();
+    let mut budget = MAX_SCAN_BYTES;
+    for (base, size) in data_regions {
+        let mut start = base;
+        while start < base + size && budget > 0 {
+            let length = (base + size - start).min(READ_CHUNK).min(budget);
+            if let Ok(data) = read_memory_from_server(&host, port, start, length as usize).await {
+                image.chunks.push((start, data));
+            }
+            start += length;
+            budget -= length;
+        }
+    }
+
+    // Address point -> code pointer slots
+    let mut candidates: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+    for (base, data) in &image.chunks {
+        let words: Vec<u64> = data.chunks_exact(WORD as usize)
+            .map(|w| u64::from_le_bytes(w.try_into().unwrap_or_default()))
+            .collect();
+        let mut i = 0;
+        while i < words.len() {
+            let run = words[i..].iter().take_while(|&&w| image.is_code(w)).count();
+            if run > 0 {
+                candidates.insert(base + i as u64 * WORD, words[i..i + run.min(MAX_METHODS)].to_vec());
+                i += run;
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    // Ghidra labels: the label may sit on the vtable object, before the address point
+    let mut labels: HashMap<u64, String> = HashMap::new();
+    for (offset, item) in data_overlay::items(&target_os, &module_name).await {
+        let Some(class_name) = item.name.as_deref().and_then(label_class) else {
+            continue;
+        };
+        let start = module.base + offset;
+        if let Some(point) = (0..3).map(|i| start + i * WORD).find(|a| candidates.contains_key(a)) {
+            labels.insert(point, class_name);
+        }
+    }
+
+    let windows = target_os == "windows";
+    let mut vtables = Vec::new();
+    let symbolizer = Symbolizer::load(state.inner()).await?;
+    for (address_point, slots) in candidates {
+        let mut rtti = None;
+        if windows {
+            rtti = msvc_rtti(&mut image, address_point, module.base).await;
+        }
+        if rtti.is_none() {
+            rtti = itanium_rtti(&mut image, address_point).await;
+        }
+        let label = labels.get(&address_point).cloned();
+        let (class_name, kind, source, bases, offset_to_top) = match (rtti, label) {
+            (Some(rtti), _) => (Some(rtti.class_name), Some(rtti.kind.to_string()), "rtti", rtti.bases, rtti.offset_to_top),
+            (None, Some(label)) => (Some(label), None, "ghidra", Vec::new(), 0),
+            (None, None) => {
+                // -fno-rtti Itanium vtables keep a zero offset-to-top and type_info slot
+                let prefix = (image.word(address_point - WORD).await, image.word(address_point - 2 * WORD).await);
+                if windows || slots.len() < MIN_HEURISTIC_METHODS || prefix != (Some(0), Some(0)) {
+                    continue;
+                }
+                (None, None, "heuristic", Vec::new(), 0)
+            }
+        };
+        vtables.push(VtableInfo {
+            address: Some(address_point),
+            module_offset: address_point - module.base,
+            class_name,
+            rtti: kind,
+            source: source.to_string(),
+            offset_to_top,
+            base_classes: bases,
+            methods: methods_of(&slots, &symbolizer),
+        });
+    }
+
+    let vtables = {
+        let (target_os, module_name) = (target_os.clone(), module_name.clone());
+        db::run(move |conn| {
+            save(conn, &target_os, &module_name, &vtables)?;
+            Ok(vtables)
+        }).await?
+    };
+    let classes = hierarchy(&vtables);
+    Ok(ModuleVtables { module_name, vtables, classes })
+}
+
+/// Vtables stored by an earlier scan_vtables (target_os defaults to the
+/// connected target's); method addresses feed set_breakpoint and the decompiler
+#[tauri::command]
+pub async fn get_vtables(
+    state: tauri::State<'_, AppStateType>,
+    module_name: String,
+    target_os: Option<String>,
+) -> Result<ModuleVtables, String> {
+    let (connected_os, _, modules) = target(state.inner())?;
+    let vtables = {
+        let (target_os, module_name) = (target_os.unwrap_or(connected_os), module_name.clone());
+        db::run(move |conn| load(conn, &target_os, &module_name, &modules)).await?
+    };
+    let classes = hierarchy(&vtables);
+    Ok(ModuleVtables { module_name, vtables, classes })
+}
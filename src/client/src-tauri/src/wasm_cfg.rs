@@ -0,0 +1,306 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use wasmparser::{Operator, Parser, Payload, TypeRef};
+
+use crate::wasm_debug_info::WasmDebugInfo;
+use crate::wasm_disasm;
+use crate::{GhidraCfgBlock, GhidraCfgEdge, GhidraCfgInstruction, GhidraCfgResult};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasmCallGraphNode {
+    pub index: u32,
+    pub name: Option<String>,
+    pub imported: bool,
+    pub callees: Vec<u32>,               // Direct calls, deduplicated
+    pub indirect_calls: u32,             // call_indirect / call_ref sites
+}
+
+struct Instruction<'a> {
+    start: usize,                        // Module file offsets
+    end: usize,
+    op: Operator<'a>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum FrameKind {
+    Function,
+    Block,
+    Loop,
+    If,
+}
+
+enum Target {
+    Instruction(usize),
+    Exit,
+}
+
+/// Operators of every function body, by function index, plus the number
+/// of imported functions
+type Bodies<'a> = (u32, Vec<(u32, Vec<Instruction<'a>>)>);
+
+fn function_bodies(binary: &[u8], only: Option<u32>) -> Result<Bodies<'_>, String> {
+    let mut imported = 0u32;
+    let mut next_index = None;
+    let mut bodies = Vec::new();
+    for payload in Parser::new(0).parse_all(binary) {
+        match payload.map_err(|e| format!("Invalid WASM module: {}", e))? {
+            Payload::ImportSection(reader) => {
+                for import in reader.into_iter().flatten() {
+                    if matches!(import.ty, TypeRef::Func(_)) {
+                        imported += 1;
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                let index = *next_index.get_or_insert(imported);
+                next_index = Some(index + 1);
+                if only.is_some_and(|wanted| wanted != index) {
+                    continue;
+                }
+                let mut reader = body.get_operators_reader().map_err(|e| e.to_string())?;
+                let mut instructions = Vec::new();
+                while !reader.eof() {
+                    let start = reader.original_position();
+                    let op = reader.read().map_err(|e| format!("Failed to decode func[{}] at 0x{:x}: {}", index, start, e))?;
+                    instructions.push(Instruction { start, end: reader.original_position(), op });
+                }
+                bodies.push((index, instructions));
+            }
+            _ => {}
+        }
+    }
+    Ok((imported, bodies))
+}
+
+/// Index of the matching `end` of every block/loop/if/try, and of the `else`
+/// of every if that has one
+fn match_frames(instructions: &[Instruction]) -> (HashMap<usize, usize>, HashMap<usize, usize>) {
+    let mut ends = HashMap::new();
+    let mut elses = HashMap::new();
+    let mut open = Vec::new();
+    for (i, insn) in instructions.iter().enumerate() {
+        match insn.op {
+            Operator::Block { .. } | Operator::Loop { .. } | Operator::If { .. } | Operator::Try { .. } | Operator::TryTable { .. } => open.push(i),
+            Operator::Else => {
+                if let Some(&start) = open.last() {
+                    elses.insert(start, i);
+                }
+            }
+            Operator::End | Operator::Delegate { .. } => {
+                if let Some(start) = open.pop() {
+                    ends.insert(start, i);
+                }
+            }
+            _ => {}
+        }
+    }
+    (ends, elses)
+}
+
+/// Basic blocks of one function from its structured control flow. Branches
+/// to a loop go to the `loop`, branches to a block or if go to its `end`.
+/// Exception edges (try/catch, throw) are not drawn.
+fn build_cfg(binary: &[u8], instructions: &[Instruction], debug_info: &WasmDebugInfo, function_index: u32) -> (Vec<GhidraCfgBlock>, Vec<GhidraCfgEdge>) {
+    let last = instructions.len().saturating_sub(1);
+    let (ends, elses) = match_frames(instructions);
+    let end_of = |start: usize| ends.get(&start).copied().unwrap_or(last);
+
+    // Successors of each instruction that ends a basic block
+    let mut exits: HashMap<usize, Vec<(Target, &'static str)>> = HashMap::new();
+    let mut stack: Vec<(FrameKind, usize)> = vec![(FrameKind::Function, 0)];
+    let branch = |stack: &[(FrameKind, usize)], depth: u32| -> Target {
+        match stack.len().checked_sub(1 + depth as usize).map(|d| stack[d]) {
+            Some((FrameKind::Loop, start)) => Target::Instruction(start),
+            Some((FrameKind::Function, _)) | None => Target::Instruction(last),
+            Some((_, start)) => Target::Instruction(end_of(start)),
+        }
+    };
+    for (i, insn) in instructions.iter().enumerate() {
+        let successors = match &insn.op {
+            Operator::Block { .. } | Operator::Try { .. } | Operator::TryTable { .. } => {
+                stack.push((FrameKind::Block, i));
+                None
+            }
+            Operator::Loop { .. } => {
+                stack.push((FrameKind::Loop, i));
+                None
+            }
+            Operator::If { .. } => {
+                let otherwise = elses.get(&i).map_or(end_of(i), |&e| e + 1);
+                stack.push((FrameKind::If, i));
+                Some(vec![(Target::Instruction(i + 1), "conditional-true"), (Target::Instruction(otherwise), "conditional-false")])
+            }
+            Operator::Else | Operator::Catch { .. } | Operator::CatchAll => {
+                let start = stack.last().map_or(0, |&(_, start)| start);
+                Some(vec![(Target::Instruction(end_of(start)), "unconditional")])
+            }
+            Operator::End | Operator::Delegate { .. } => {
+                stack.pop();
+                None
+            }
+            Operator::Br { relative_depth } => Some(vec![(branch(&stack, *relative_depth), "unconditional")]),
+            Operator::BrIf { relative_depth } | Operator::BrOnNull { relative_depth } | Operator::BrOnNonNull { relative_depth } => {
+                Some(vec![(branch(&stack, *relative_depth), "conditional-true"), (Target::Instruction(i + 1), "conditional-false")])
+            }
+            Operator::BrTable { targets } => {
+                let mut depths: Vec<u32> = targets.targets().flatten().collect();
+                depths.push(targets.default());
+                depths.sort_unstable();
+                depths.dedup();
+                Some(depths.into_iter().map(|depth| (branch(&stack, depth), "unconditional")).collect())
+            }
+            Operator::Return | Operator::Unreachable | Operator::ReturnCall { .. } | Operator::ReturnCallIndirect { .. }
+            | Operator::ReturnCallRef { .. } | Operator::Throw { .. } | Operator::Rethrow { .. } | Operator::ThrowRef => {
+                Some(vec![(Target::Exit, "normal")])
+            }
+            _ => None,
+        };
+        if let Some(successors) = successors {
+            exits.insert(i, successors);
+        }
+    }
+
+    let mut leaders: BTreeSet<usize> = BTreeSet::from([0]);
+    for (&i, successors) in &exits {
+        leaders.insert(i + 1);
+        for (target, _) in successors {
+            if let Target::Instruction(t) = target {
+                leaders.insert(*t);
+            }
+        }
+    }
+    // Blocks a loop header starts
+    for (i, insn) in instructions.iter().enumerate() {
+        if matches!(insn.op, Operator::Loop { .. }) {
+            leaders.insert(i);
+        }
+    }
+    let leaders: Vec<usize> = leaders.into_iter().filter(|&l| l <= last).collect();
+    let block_of = |i: usize| leaders.partition_point(|&l| l <= i) - 1;
+    let id = |i: usize| format!("block_0x{:x}", instructions[leaders[i]].start);
+
+    let locals = debug_info.local_names.get(&function_index);
+    let mut blocks = Vec::with_capacity(leaders.len());
+    let mut edges = Vec::new();
+    for (b, &first) in leaders.iter().enumerate() {
+        let end = leaders.get(b + 1).copied().unwrap_or(instructions.len());
+        let tail = end - 1;
+        let block_instructions = instructions[first..end].iter().map(|insn| {
+            let (opcode, operands, _, _) = wasm_disasm::describe(&insn.op);
+            GhidraCfgInstruction {
+                address: format!("0x{:x}", insn.start),
+                bytes: hex::encode(&binary[insn.start..insn.end]),
+                opcode,
+                operands: wasm_disasm::name_operands(&insn.op, operands, locals, debug_info),
+            }
+        }).collect();
+        let successors = match exits.get(&tail) {
+            Some(successors) => successors.iter()
+                .filter_map(|(target, kind)| match target {
+                    Target::Instruction(t) => Some((block_of(*t), *kind)),
+                    Target::Exit => None,
+                })
+                .collect(),
+            None if end < instructions.len() => vec![(b + 1, "normal")],
+            None => Vec::new(),
+        };
+        let is_exit = tail == last || exits.get(&tail).is_some_and(|s| s.iter().any(|(t, _)| matches!(t, Target::Exit)));
+        let mut successor_ids: Vec<String> = Vec::new();
+        for (to, kind) in successors {
+            edges.push(GhidraCfgEdge { from: id(b), to: id(to), edge_type: kind.to_string() });
+            if !successor_ids.contains(&id(to)) {
+                successor_ids.push(id(to));
+            }
+        }
+        blocks.push(GhidraCfgBlock {
+            id: id(b),
+            start_address: format!("0x{:x}", instructions[first].start),
+            end_address: format!("0x{:x}", instructions[tail].end - 1),
+            instructions: block_instructions,
+            successors: successor_ids,
+            predecessors: Vec::new(),
+            is_entry: b == 0,
+            is_exit,
+        });
+    }
+    for edge in &edges {
+        if let Some(block) = blocks.iter_mut().find(|block| block.id == edge.to) {
+            if !block.predecessors.contains(&edge.from) {
+                block.predecessors.push(edge.from.clone());
+            }
+        }
+    }
+    (blocks, edges)
+}
+
+/// Control flow graph of a defined function, in the shape of the Ghidra CFG
+/// view. Addresses are module file offsets, like WasmFunctionInfo.code_offset.
+#[tauri::command]
+pub async fn analyze_wasm_cfg(binary_data: Vec<u8>, function_index: u32) -> Result<GhidraCfgResult, String> {
+    tokio::task::spawn_blocking(move || {
+        let debug_info = WasmDebugInfo::names(&binary_data);
+        let (imported, bodies) = function_bodies(&binary_data, Some(function_index))?;
+        if function_index < imported {
+            return Err(format!("func[{}] is imported and has no body", function_index));
+        }
+        let (_, instructions) = bodies.first().ok_or_else(|| format!("No function with index {}", function_index))?;
+        if instructions.is_empty() {
+            return Err(format!("func[{}] has an empty body", function_index));
+        }
+        let (blocks, edges) = build_cfg(&binary_data, instructions, &debug_info, function_index);
+        Ok(GhidraCfgResult {
+            success: true,
+            function_name: Some(debug_info.function_names.get(&function_index).cloned()
+                .unwrap_or_else(|| format!("func[{}]", function_index))),
+            function_offset: Some(format!("0x{:x}", instructions[0].start)),
+            blocks,
+            edges,
+            error: None,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Direct call edges of every function, imported ones included as leaves
+#[tauri::command]
+pub async fn analyze_wasm_call_graph(binary_data: Vec<u8>) -> Result<Vec<WasmCallGraphNode>, String> {
+    tokio::task::spawn_blocking(move || {
+        let debug_info = WasmDebugInfo::names(&binary_data);
+        let (imported, bodies) = function_bodies(&binary_data, None)?;
+        let mut nodes: Vec<WasmCallGraphNode> = (0..imported)
+            .map(|index| WasmCallGraphNode {
+                index,
+                name: debug_info.function_names.get(&index).cloned(),
+                imported: true,
+                callees: Vec::new(),
+                indirect_calls: 0,
+            })
+            .collect();
+        for (index, instructions) in &bodies {
+            let mut callees = Vec::new();
+            let mut indirect_calls = 0;
+            for insn in instructions {
+                match insn.op {
+                    Operator::Call { function_index } | Operator::ReturnCall { function_index } if !callees.contains(&function_index) => {
+                        callees.push(function_index);
+                    }
+                    Operator::CallIndirect { .. } | Operator::ReturnCallIndirect { .. } | Operator::CallRef { .. } | Operator::ReturnCallRef { .. } => {
+                        indirect_calls += 1;
+                    }
+                    _ => {}
+                }
+            }
+            nodes.push(WasmCallGraphNode {
+                index: *index,
+                name: debug_info.function_names.get(index).cloned(),
+                imported: false,
+                callees,
+                indirect_calls,
+            });
+        }
+        Ok(nodes)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,241 @@
+use gimli::{EndianSlice, LittleEndian};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use wasmparser::{BinaryReader, KnownCustom, Name, Parser, Payload, TypeRef};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WasmSourceLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,                     // 0 when unknown
+}
+
+struct LineRow {
+    offset: u32,                         // Module file offset
+    file: usize,                         // Index into `files`
+    line: u32,
+    column: u32,
+}
+
+/// Names and source lines recovered from a module's custom sections
+#[derive(Default)]
+pub struct WasmDebugInfo {
+    pub function_names: HashMap<u32, String>,
+    pub local_names: HashMap<u32, HashMap<u32, String>>, // Function index -> local index -> name
+    pub global_names: HashMap<u32, String>,
+    pub source_mapping_url: Option<String>,
+    pub has_dwarf: bool,
+    files: Vec<String>,
+    file_ids: HashMap<String, usize>,
+    lines: Vec<LineRow>,                 // Sorted by offset
+}
+
+impl WasmDebugInfo {
+    /// Names from the "name" section, falling back to import and export
+    /// names for functions
+    pub fn names(binary: &[u8]) -> Self {
+        Self::scan(binary).0
+    }
+
+    /// Names plus source lines from `source_map` (source map v3 JSON, or a
+    /// path to one) when given, else from DWARF .debug_line
+    pub fn parse(binary: &[u8], source_map: Option<&str>) -> Self {
+        let (mut info, dwarf_sections, code_start) = Self::scan(binary);
+        let from_source_map = source_map.and_then(|map| info.read_source_map(map).ok()).unwrap_or(false);
+        if !from_source_map && info.has_dwarf {
+            info.read_dwarf_lines(&dwarf_sections, code_start);
+        }
+        info.lines.sort_by_key(|row| row.offset);
+        info
+    }
+
+    /// Names, plus the DWARF sections and Code section start for `parse`
+    fn scan(binary: &[u8]) -> (Self, HashMap<String, &[u8]>, u32) {
+        let mut info = WasmDebugInfo::default();
+        let mut fallback_names: HashMap<u32, String> = HashMap::new();
+        let mut imported_functions = 0u32;
+        let mut dwarf_sections: HashMap<String, &[u8]> = HashMap::new();
+        let mut code_start = 0u32;
+
+        for payload in Parser::new(0).parse_all(binary) {
+            match payload {
+                Ok(Payload::ImportSection(reader)) => {
+                    for import in reader.into_iter().flatten() {
+                        if matches!(import.ty, TypeRef::Func(_)) {
+                            fallback_names.insert(imported_functions, format!("{}.{}", import.module, import.name));
+                            imported_functions += 1;
+                        }
+                    }
+                }
+                Ok(Payload::ExportSection(reader)) => {
+                    for export in reader.into_iter().flatten() {
+                        if export.kind == wasmparser::ExternalKind::Func {
+                            fallback_names.insert(export.index, export.name.to_string());
+                        }
+                    }
+                }
+                Ok(Payload::CodeSectionStart { range, .. }) => code_start = range.start as u32,
+                Ok(Payload::CustomSection(reader)) => match reader.as_known() {
+                    KnownCustom::Name(names) => info.read_names(names),
+                    _ if reader.name() == "sourceMappingURL" => {
+                        info.source_mapping_url = BinaryReader::new(reader.data(), 0).read_string().ok().map(str::to_string);
+                    }
+                    _ if reader.name().starts_with(".debug_") => {
+                        dwarf_sections.insert(reader.name().to_string(), reader.data());
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        for (index, name) in fallback_names {
+            info.function_names.entry(index).or_insert(name);
+        }
+        info.has_dwarf = dwarf_sections.contains_key(".debug_line");
+        (info, dwarf_sections, code_start)
+    }
+
+    fn read_names(&mut self, reader: wasmparser::NameSectionReader) {
+        for name in reader.into_iter().flatten() {
+            match name {
+                Name::Function(map) => {
+                    for naming in map.into_iter().flatten() {
+                        self.function_names.insert(naming.index, naming.name.to_string());
+                    }
+                }
+                Name::Local(map) => {
+                    for function in map.into_iter().flatten() {
+                        let locals = self.local_names.entry(function.index).or_default();
+                        for naming in function.names.into_iter().flatten() {
+                            locals.insert(naming.index, naming.name.to_string());
+                        }
+                    }
+                }
+                Name::Global(map) => {
+                    for naming in map.into_iter().flatten() {
+                        self.global_names.insert(naming.index, naming.name.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn file_index(&mut self, file: String) -> usize {
+        if let Some(&index) = self.file_ids.get(&file) {
+            return index;
+        }
+        self.files.push(file.clone());
+        self.file_ids.insert(file, self.files.len() - 1);
+        self.files.len() - 1
+    }
+
+    /// Source map v3 for a module: a single line whose generated columns are
+    /// module file offsets. Returns whether any mapping was read.
+    fn read_source_map(&mut self, source_map: &str) -> Result<bool, String> {
+        let text = if source_map.trim_start().starts_with('{') {
+            source_map.to_string()
+        } else {
+            std::fs::read_to_string(source_map).map_err(|e| format!("Failed to read source map: {}", e))?
+        };
+        let map: serde_json::Value = serde_json::from_str(&text).map_err(|e| format!("Invalid source map: {}", e))?;
+        let root = map["sourceRoot"].as_str().unwrap_or_default();
+        let sources: Vec<String> = map["sources"].as_array().map(|sources| {
+            sources.iter().map(|s| format!("{}{}", root, s.as_str().unwrap_or_default())).collect()
+        }).unwrap_or_default();
+        let mappings = map["mappings"].as_str().ok_or("Source map has no mappings")?;
+
+        // Fields are deltas from the previous segment
+        let (mut offset, mut source, mut line, mut column) = (0i64, 0i64, 0i64, 0i64);
+        let before = self.lines.len();
+        let first_line = mappings.split(';').next().unwrap_or_default();
+        for segment in first_line.split(',').filter(|s| !s.is_empty()) {
+            let fields = decode_vlq(segment).filter(|f| !f.is_empty()).ok_or("Invalid source map mappings")?;
+            offset += fields[0];
+            if fields.len() < 4 {
+                continue;
+            }
+            source += fields[1];
+            line += fields[2];
+            column += fields[3];
+            let Some(file) = sources.get(source as usize) else { continue };
+            let file = self.file_index(file.clone());
+            self.lines.push(LineRow { offset: offset as u32, file, line: line as u32 + 1, column: column as u32 + 1 });
+        }
+        Ok(self.lines.len() > before)
+    }
+
+    /// Rows of every DWARF line program; wasm DWARF addresses are offsets
+    /// from the start of the Code section's contents
+    fn read_dwarf_lines(&mut self, sections: &HashMap<String, &[u8]>, code_start: u32) {
+        let load = |id: gimli::SectionId| -> Result<EndianSlice<LittleEndian>, gimli::Error> {
+            Ok(EndianSlice::new(sections.get(id.name()).copied().unwrap_or(&[]), LittleEndian))
+        };
+        let Ok(dwarf) = gimli::Dwarf::load(load) else { return };
+        let mut units = dwarf.units();
+        while let Ok(Some(header)) = units.next() {
+            let Ok(unit) = dwarf.unit(header) else { continue };
+            let Some(program) = unit.line_program.clone() else { continue };
+            let mut rows = program.rows();
+            while let Ok(Some((header, row))) = rows.next_row() {
+                let Some(line) = row.line().filter(|_| !row.end_sequence()) else { continue };
+                let Some(entry) = row.file(header) else { continue };
+                let mut path = dwarf.attr_string(&unit, entry.path_name())
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                if !path.starts_with('/') {
+                    if let Some(directory) = entry.directory(header).and_then(|d| dwarf.attr_string(&unit, d).ok()) {
+                        path = format!("{}/{}", directory.to_string_lossy().trim_end_matches('/'), path);
+                    }
+                }
+                let column = match row.column() {
+                    gimli::ColumnType::LeftEdge => 0,
+                    gimli::ColumnType::Column(column) => column.get() as u32,
+                };
+                let file = self.file_index(path);
+                self.lines.push(LineRow { offset: code_start + row.address() as u32, file, line: line.get() as u32, column });
+            }
+        }
+    }
+
+    /// Source line of the instruction at module file offset `offset`
+    pub fn location(&self, offset: u32) -> Option<WasmSourceLocation> {
+        let index = self.lines.partition_point(|row| row.offset <= offset).checked_sub(1)?;
+        let row = &self.lines[index];
+        Some(WasmSourceLocation { file: self.files[row.file].clone(), line: row.line, column: row.column })
+    }
+
+    /// `func[N]` with the function's name appended when known
+    pub fn function_label(&self, index: u32) -> String {
+        match self.function_names.get(&index) {
+            Some(name) => format!("func[{}] <{}>", index, name),
+            None => format!("func[{}]", index),
+        }
+    }
+}
+
+/// Base64 VLQ fields of one source map segment
+fn decode_vlq(segment: &str) -> Option<Vec<i64>> {
+    let mut fields = Vec::new();
+    let mut value = 0i64;
+    let mut shift = 0;
+    for c in segment.bytes() {
+        let digit = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        } as i64;
+        value |= (digit & 0x1f) << shift;
+        if digit & 0x20 != 0 {
+            shift += 5;
+            continue;
+        }
+        fields.push(if value & 1 != 0 { -(value >> 1) } else { value >> 1 });
+        value = 0;
+        shift = 0;
+    }
+    Some(fields)
+}
@@ -0,0 +1,386 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use wasmparser::{BinaryReader, BlockType, BrTable, HeapType, Ieee32, Ieee64, MemArg, Operator, RefType, TryTable, ValType, V128};
+
+use crate::disassembly::StructuredInstruction;
+use crate::wasm_debug_info::WasmDebugInfo;
+
+// Dotted prefixes of the text format ("i32.add", "memory.atomic.notify", ...)
+const DOTTED_PREFIXES: &[&str] = &[
+    "i32", "i64", "f32", "f64", "v128", "i8x16", "i16x8", "i32x4", "i64x2", "f32x4", "f64x2",
+    "local", "global", "memory", "table", "elem", "data", "ref", "atomic", "struct", "array", "any", "extern", "i31",
+];
+
+/// WASM instruction info with structured details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasmInstructionInfo {
+    pub offset: u32,           // Byte offset within function
+    pub bytes: Vec<u8>,        // Raw instruction bytes
+    pub mnemonic: String,      // Instruction name
+    pub operands: String,      // Formatted operands
+    pub is_branch: bool,       // Is this a branch/control flow instruction
+    pub depth_change: i32,     // Block nesting change (+1 for block/loop/if, -1 for end)
+    pub source: Option<crate::wasm_debug_info::WasmSourceLocation>, // Set where the source line changes
+}
+
+/// One decoded operator; `op` is None for a byte that does not start a valid one
+pub struct Decoded<'a> {
+    pub offset: usize,                   // Relative to the decoded slice
+    pub size: usize,
+    pub op: Option<Operator<'a>>,
+}
+
+macro_rules! define_visit_name {
+    ($( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $(($($ann:tt)*))? )*) => {
+        /// `VisitOperator` method of an operator, e.g. "visit_i32_load8_s"
+        fn visit_name(op: &Operator) -> &'static str {
+            match op {
+                $( Operator::$op { .. } => stringify!($visit), )*
+                _ => "visit_unknown",
+            }
+        }
+    };
+}
+wasmparser::for_each_operator!(define_visit_name);
+
+macro_rules! define_operand_fields {
+    ($( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $(($($ann:tt)*))? )*) => {
+        /// (field name, text) of each immediate of an operator
+        fn operand_fields(op: &Operator) -> Vec<(&'static str, String)> {
+            match op {
+                $( Operator::$op $({ $($arg),* })? => vec![$($( (stringify!($arg), (&&Operand($arg)).text()) ),*)?], )*
+                _ => Vec::new(),
+            }
+        }
+    };
+}
+wasmparser::for_each_operator!(define_operand_fields);
+
+// Immediates are formatted by `OperandText` where the type has a text
+// form, else by `Debug` (method resolution tries `&Operand` first)
+struct Operand<'a, T>(&'a T);
+
+trait OperandText {
+    fn text(&self) -> String;
+}
+
+trait OperandDebug {
+    fn text(&self) -> String;
+}
+
+impl<T: Debug> OperandDebug for Operand<'_, T> {
+    fn text(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+macro_rules! display_operands {
+    ($($ty:ty),*) => {
+        $(impl OperandText for &Operand<'_, $ty> {
+            fn text(&self) -> String {
+                self.0.to_string()
+            }
+        })*
+    };
+}
+display_operands!(u8, u32, i32, i64, ValType, RefType);
+
+impl OperandText for &Operand<'_, Ieee32> {
+    fn text(&self) -> String {
+        format!("{:?}", f32::from_bits(self.0.bits()))
+    }
+}
+
+impl OperandText for &Operand<'_, Ieee64> {
+    fn text(&self) -> String {
+        format!("{:?}", f64::from_bits(self.0.bits()))
+    }
+}
+
+impl OperandText for &Operand<'_, V128> {
+    fn text(&self) -> String {
+        format!("0x{}", self.0.bytes().iter().rev().map(|b| format!("{:02x}", b)).collect::<String>())
+    }
+}
+
+impl OperandText for &Operand<'_, [u8; 16]> {
+    fn text(&self) -> String {
+        self.0.iter().map(|lane| lane.to_string()).collect::<Vec<_>>().join(" ")
+    }
+}
+
+impl OperandText for &Operand<'_, MemArg> {
+    fn text(&self) -> String {
+        let memarg = self.0;
+        let mut text = format!("offset={} align={}", memarg.offset, 1u64 << memarg.align);
+        if memarg.memory != 0 {
+            text.push_str(&format!(" mem[{}]", memarg.memory));
+        }
+        text
+    }
+}
+
+impl OperandText for &Operand<'_, BlockType> {
+    fn text(&self) -> String {
+        match self.0 {
+            BlockType::Empty => String::new(),
+            BlockType::Type(ty) => ty.to_string(),
+            BlockType::FuncType(index) => format!("type[{}]", index),
+        }
+    }
+}
+
+impl OperandText for &Operand<'_, HeapType> {
+    fn text(&self) -> String {
+        match self.0 {
+            HeapType::Abstract { shared, ty } => {
+                let name = format!("{:?}", ty).to_lowercase();
+                if *shared { format!("shared {}", name) } else { name }
+            }
+            HeapType::Concrete(index) => format!("type[{}]", index),
+        }
+    }
+}
+
+impl OperandText for &Operand<'_, BrTable<'_>> {
+    fn text(&self) -> String {
+        let mut labels: Vec<String> = self.0.targets().flatten().map(|depth| depth.to_string()).collect();
+        labels.push(format!("default={}", self.0.default()));
+        labels.join(" ")
+    }
+}
+
+impl OperandText for &Operand<'_, TryTable> {
+    fn text(&self) -> String {
+        let mut parts = vec![(&&Operand(&self.0.ty)).text()];
+        for catch in &self.0.catches {
+            parts.push(match catch {
+                wasmparser::Catch::One { tag, label } => format!("(catch tag[{}] {})", tag, label),
+                wasmparser::Catch::OneRef { tag, label } => format!("(catch_ref tag[{}] {})", tag, label),
+                wasmparser::Catch::All { label } => format!("(catch_all {})", label),
+                wasmparser::Catch::AllRef { label } => format!("(catch_all_ref {})", label),
+            });
+        }
+        parts.retain(|p| !p.is_empty());
+        parts.join(" ")
+    }
+}
+
+/// Text-format mnemonic of an operator ("i32.atomic.rmw8.add_u", "br_if")
+pub fn mnemonic(op: &Operator) -> String {
+    let name = visit_name(op).trim_start_matches("visit_");
+    if name == "typed_select" {
+        return "select".to_string();
+    }
+    for prefix in DOTTED_PREFIXES {
+        let Some(rest) = name.strip_prefix(prefix).and_then(|r| r.strip_prefix('_')) else { continue };
+        let mut rest = rest.to_string();
+        if let Some(atomic) = rest.strip_prefix("atomic_") {
+            rest = format!("atomic.{}", atomic);
+        }
+        // "rmw8_add_u" -> "rmw8.add_u", "rmw_cmpxchg" -> "rmw.cmpxchg"
+        if let Some(position) = rest.find("rmw") {
+            let digits = rest[position + 3..].bytes().take_while(|b| b.is_ascii_digit()).count();
+            let separator = position + 3 + digits;
+            if rest.as_bytes().get(separator) == Some(&b'_') {
+                rest.replace_range(separator..separator + 1, ".");
+            }
+        }
+        return format!("{}.{}", prefix, rest);
+    }
+    name.to_string()
+}
+
+/// Immediates of an operator, with index spaces spelled out ("func[3]")
+pub fn operands(op: &Operator) -> String {
+    operand_fields(op)
+        .into_iter()
+        .map(|(field, text)| match field {
+            "function_index" => format!("func[{}]", text),
+            "type_index" | "struct_type_index" | "array_type_index" | "array_type_index_dst" | "array_type_index_src" => format!("type[{}]", text),
+            "table" | "table_index" | "dst_table" | "src_table" => format!("table[{}]", text),
+            "tag_index" => format!("tag[{}]", text),
+            "data_index" | "array_data_index" => format!("data[{}]", text),
+            "elem_index" | "array_elem_index" => format!("elem[{}]", text),
+            "mem" | "dst_mem" | "src_mem" => format!("mem[{}]", text),
+            _ => text,
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_call(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::Call { .. } | Operator::CallIndirect { .. } | Operator::CallRef { .. }
+            | Operator::ReturnCall { .. } | Operator::ReturnCallIndirect { .. } | Operator::ReturnCallRef { .. }
+    )
+}
+
+fn is_return(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::Return | Operator::ReturnCall { .. } | Operator::ReturnCallIndirect { .. } | Operator::ReturnCallRef { .. }
+    )
+}
+
+fn is_jump(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::Br { .. } | Operator::BrIf { .. } | Operator::BrTable { .. } | Operator::BrOnNull { .. } | Operator::BrOnNonNull { .. }
+            | Operator::BrOnCast { .. } | Operator::BrOnCastFail { .. } | Operator::If { .. } | Operator::Throw { .. }
+            | Operator::Rethrow { .. } | Operator::ThrowRef | Operator::Delegate { .. }
+    )
+}
+
+/// (mnemonic, operands, is_branch, block nesting change)
+pub fn describe(op: &Operator) -> (String, String, bool, i32) {
+    let depth_change = match op {
+        Operator::Block { .. } | Operator::Loop { .. } | Operator::If { .. } | Operator::Try { .. } | Operator::TryTable { .. } => 1,
+        Operator::End | Operator::Delegate { .. } => -1,
+        _ => 0,
+    };
+    let is_branch = is_jump(op) || is_call(op) || is_return(op) || matches!(op, Operator::Loop { .. });
+    (mnemonic(op), operands(op), is_branch, depth_change)
+}
+
+/// Operands with the local, global or function name appended when known
+pub fn name_operands(op: &Operator, operands: String, locals: Option<&HashMap<u32, String>>, debug_info: &WasmDebugInfo) -> String {
+    let name = match op {
+        Operator::Call { function_index } | Operator::ReturnCall { function_index } => {
+            return debug_info.function_label(*function_index);
+        }
+        Operator::RefFunc { function_index } => debug_info.function_names.get(function_index),
+        Operator::GlobalGet { global_index } | Operator::GlobalSet { global_index } => debug_info.global_names.get(global_index),
+        Operator::LocalGet { local_index } | Operator::LocalSet { local_index } | Operator::LocalTee { local_index } => {
+            locals.and_then(|names| names.get(local_index))
+        }
+        _ => None,
+    };
+    match name {
+        Some(name) => format!("{} <{}>", operands, name),
+        None => operands,
+    }
+}
+
+/// Decode up to `limit` operators from `data`. A byte that does not start
+/// a valid operator is returned on its own and decoding resumes after it.
+/// With `stop_at_end`, decoding ends at the `end` closing the function.
+pub fn decode(data: &[u8], limit: usize, stop_at_end: bool) -> Vec<Decoded<'_>> {
+    let mut decoded = Vec::new();
+    let mut offset = 0;
+    let mut depth = 0i32;
+    while offset < data.len() && decoded.len() < limit {
+        let mut reader = BinaryReader::new(&data[offset..], offset);
+        match reader.read_operator() {
+            Ok(op) => {
+                let size = reader.original_position() - offset;
+                let (_, _, _, depth_change) = describe(&op);
+                depth += depth_change;
+                decoded.push(Decoded { offset, size, op: Some(op) });
+                offset += size;
+                if stop_at_end && depth < 0 {
+                    break;
+                }
+            }
+            Err(_) => {
+                decoded.push(Decoded { offset, size: 1, op: None });
+                offset += 1;
+            }
+        }
+    }
+    decoded
+}
+
+/// WasmInstructionInfo of a decoded operator, naming its operands when `names` is given
+pub fn instruction_info(
+    data: &[u8],
+    decoded: &Decoded,
+    names: Option<(&WasmDebugInfo, Option<&HashMap<u32, String>>)>,
+) -> WasmInstructionInfo {
+    let bytes = data[decoded.offset..decoded.offset + decoded.size].to_vec();
+    let Some(op) = &decoded.op else {
+        return WasmInstructionInfo {
+            offset: decoded.offset as u32,
+            operands: format!("0x{:02x}", bytes[0]),
+            bytes,
+            mnemonic: "unknown".to_string(),
+            is_branch: false,
+            depth_change: 0,
+            source: None,
+        };
+    };
+    let (mnemonic, operands, is_branch, depth_change) = describe(op);
+    let operands = match names {
+        Some((debug_info, locals)) => name_operands(op, operands, locals, debug_info),
+        None => operands,
+    };
+    WasmInstructionInfo { offset: decoded.offset as u32, bytes, mnemonic, operands, is_branch, depth_change, source: None }
+}
+
+/// "address|bytes|text" lines of the disassembly view; `indent` nests block bodies
+pub fn format_lines(instructions: &[WasmInstructionInfo], base_address: u64, indent: bool) -> Vec<String> {
+    let mut depth = 0i32;
+    instructions.iter().map(|insn| {
+        let opens = insn.depth_change > 0;
+        if insn.depth_change < 0 || insn.mnemonic == "else" || insn.mnemonic == "catch" || insn.mnemonic == "catch_all" {
+            depth = (depth - 1).max(0);
+        }
+        let prefix = if indent { "  ".repeat(depth as usize) } else { String::new() };
+        if opens || insn.mnemonic == "else" || insn.mnemonic == "catch" || insn.mnemonic == "catch_all" {
+            depth += 1;
+        }
+
+        let bytes_display = insn.bytes.iter().take(8).map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(" ");
+        let bytes_suffix = if insn.bytes.len() > 8 { ".." } else { "" };
+        let branch_hint = if insn.is_branch { " <-" } else { "" };
+        let text = if insn.operands.is_empty() {
+            format!("{}{}{}", prefix, insn.mnemonic, branch_hint)
+        } else {
+            format!("{}{} {}{}", prefix, insn.mnemonic, insn.operands, branch_hint)
+        };
+        format!("0x{:08x}|{}{}|{}", base_address + insn.offset as u64, bytes_display, bytes_suffix, text)
+    }).collect()
+}
+
+/// Structured instructions for `disassemble_structured`, stopping at the
+/// first undecodable byte unless `skip_unknown` (then it becomes an "unknown"
+/// entry). Calls carry a function index, not an address, so there are no
+/// branch targets.
+pub fn structured(data: &[u8], address: u64, skip_unknown: bool) -> Vec<StructuredInstruction> {
+    decode(data, usize::MAX, false).iter().take_while(|decoded| skip_unknown || decoded.op.is_some()).map(|decoded| {
+        let info = instruction_info(data, decoded, None);
+        let op = decoded.op.as_ref();
+        let call = op.is_some_and(is_call);
+        let ret = op.is_some_and(is_return);
+        let jump = op.is_some_and(is_jump);
+        let mut groups = Vec::new();
+        if jump {
+            groups.push("jump".to_string());
+        }
+        if call {
+            groups.push("call".to_string());
+        }
+        if ret {
+            groups.push("ret".to_string());
+        }
+        StructuredInstruction {
+            address: address + decoded.offset as u64,
+            size: decoded.size,
+            bytes: info.bytes,
+            mnemonic: info.mnemonic,
+            operands: info.operands,
+            groups,
+            is_branch: jump || call || ret,
+            is_call: call,
+            is_return: ret,
+            branch_target: None,
+            branch_symbol: None,
+            regs_read: Vec::new(),
+            regs_write: Vec::new(),
+            comments: Vec::new(),
+        }
+    }).collect()
+}
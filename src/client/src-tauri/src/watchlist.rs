@@ -0,0 +1,504 @@
+use bytes::Bytes;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::state::{AppStateType, ModuleInfo};
+use crate::{db, expression, get_data_size, read_chunks_parallel, server_address, value_codec, write_memory_to_server};
+
+// Values closer than this are fetched in one read
+const CHUNK_GAP_THRESHOLD: u64 = 4096;
+const MAX_CHUNK_SIZE: usize = 1024 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A saved address; module-relative (optionally through a pointer chain) so
+/// it survives ASLR and restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEntry {
+    pub id: i64,
+    pub target_os: String,
+    pub process_name: String,
+    pub label: String,
+    pub module_name: String,          // Empty for absolute addresses
+    pub module_offset: u64,           // Absolute address when module_name is empty
+    pub pointer_offsets: Vec<i64>,    // Dereference, then add each offset in turn
+    pub data_type: String,            // Scan data type, "string" or "bytes"
+    pub size: usize,
+    pub display_format: String,       // "dec" | "hex"
+    pub frozen: bool,
+    pub freeze_value: Option<Vec<u8>>, // Written back by refresh_watchlist while frozen
+    pub sort_order: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEntryInput {
+    pub label: String,
+    pub address: String,              // "0x...", "module+0x...", or "module+0x10 → [0x18] → [0x20]"
+    pub data_type: String,
+    #[serde(default)]
+    pub size: Option<usize>,          // Required for string / bytes
+    #[serde(default)]
+    pub display_format: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchValue {
+    pub id: i64,
+    pub address: Option<u64>,         // None when the module is not loaded or a pointer is invalid
+    pub bytes: Option<Vec<u8>>,
+    pub value: Option<String>,        // Formatted per data type and display format
+    pub frozen: bool,
+    pub error: Option<String>,
+}
+
+/// Create the watchlist table (called from init_ghidra_db)
+pub fn init(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS watchlist (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            target_os TEXT NOT NULL,
+            process_name TEXT NOT NULL,
+            label TEXT NOT NULL,
+            module_name TEXT NOT NULL,
+            module_offset INTEGER NOT NULL,
+            pointer_offsets TEXT NOT NULL,
+            data_type TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            display_format TEXT NOT NULL,
+            frozen INTEGER NOT NULL DEFAULT 0,
+            freeze_value BLOB,
+            sort_order INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    ).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_watchlist_target ON watchlist(target_os, process_name)",
+        [],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+const SELECT_COLUMNS: &str = "id, target_os, process_name, label, module_name, module_offset, pointer_offsets, data_type, size, display_format, frozen, freeze_value, sort_order, created_at, updated_at";
+
+fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<WatchEntry> {
+    Ok(WatchEntry {
+        id: row.get(0)?,
+        target_os: row.get(1)?,
+        process_name: row.get(2)?,
+        label: row.get(3)?,
+        module_name: row.get(4)?,
+        module_offset: row.get::<_, i64>(5)? as u64,
+        pointer_offsets: serde_json::from_str(&row.get::<_, String>(6)?).unwrap_or_default(),
+        data_type: row.get(7)?,
+        size: row.get::<_, i64>(8)? as usize,
+        display_format: row.get(9)?,
+        frozen: row.get::<_, i64>(10)? != 0,
+        freeze_value: row.get(11)?,
+        sort_order: row.get(12)?,
+        created_at: row.get(13)?,
+        updated_at: row.get(14)?,
+    })
+}
+
+fn get_entry(conn: &Connection, id: i64) -> Result<WatchEntry, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM watchlist WHERE id = ?1", SELECT_COLUMNS),
+        params![id],
+        row_to_entry,
+    ).map_err(|e| format!("Watch entry {} not found: {}", id, e))
+}
+
+async fn load_entry(id: i64) -> Result<WatchEntry, String> {
+    db::run(move |conn| get_entry(conn, id)).await
+}
+
+/// (target_os, process name, attached modules); entries are kept per process
+fn target_info(state: &AppStateType) -> Result<(String, String, Vec<ModuleInfo>), String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    let target_os = state_guard.server_info.as_ref()
+        .map(|info| info.target_os.clone())
+        .unwrap_or_default();
+    let process_name = state_guard.attached_process.as_ref()
+        .map(|p| p.processname.clone())
+        .unwrap_or_default();
+    Ok((target_os, process_name, state_guard.attached_modules.clone()))
+}
+
+fn parse_hex(text: &str) -> Option<u64> {
+    let text = text.trim();
+    u64::from_str_radix(text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")).unwrap_or(text), 16).ok()
+}
+
+fn parse_step(text: &str) -> Result<i64, String> {
+    let step = text.trim().trim_start_matches('[').trim_end_matches(']').trim();
+    let value = match step.strip_prefix('-') {
+        Some(rest) => parse_hex(rest).map(|v| -(v as i64)),
+        None => parse_hex(step.trim_start_matches('+')).map(|v| v as i64),
+    };
+    value.ok_or_else(|| format!("Invalid pointer offset '{}'", text.trim()))
+}
+
+fn relative_to_module(absolute: u64, offsets: Vec<i64>, modules: &[ModuleInfo]) -> (String, u64, Vec<i64>) {
+    modules.iter()
+        .find(|m| absolute >= m.base && absolute < m.base.saturating_add(m.size))
+        .map(|m| (m.modulename.clone(), absolute - m.base, offsets.clone()))
+        .unwrap_or((String::new(), absolute, offsets))
+}
+
+/// (module name, offset, pointer offsets) of an address expression; absolute
+/// addresses inside a loaded module are stored relative to it. Besides
+/// `module+offset→[offset]...`, evaluator expressions such as
+/// `[[libgame.so+0x1234]+0x10]` or `player_ptr+8` are accepted.
+fn parse_address(address: &str, target_os: &str, modules: &[ModuleInfo]) -> Result<(String, u64, Vec<i64>), String> {
+    let normalized = address.replace("->", "→");
+    let mut parts = normalized.split('→');
+    let base = parts.next().unwrap_or_default().replace(' ', "");
+    let offsets = parts.map(parse_step).collect::<Result<Vec<_>, _>>();
+    if let (Some(absolute), Ok(offsets)) = (parse_hex(&base), &offsets) {
+        return Ok(relative_to_module(absolute, offsets.clone(), modules));
+    }
+    let arrow_form = offsets.and_then(|offsets| {
+        let (module, offset) = base.rsplit_once('+').ok_or_else(|| format!("Invalid address '{}'", address))?;
+        let offset = parse_hex(offset).ok_or_else(|| format!("Invalid module offset in '{}'", address))?;
+        Ok((module.trim_matches('"').to_string(), offset, offsets))
+    });
+    let Err(arrow_error) = arrow_form else { return arrow_form };
+
+    let Ok(expr) = expression::parse(address) else { return Err(arrow_error) };
+    let (base, offsets) = expression::pointer_chain(&expr);
+    let absolute = expression::evaluate_static(base, &expression::Symbols::new(target_os, modules))?;
+    Ok(relative_to_module(absolute.as_int() as u64, offsets, modules))
+}
+
+fn value_size(data_type: &str, size: Option<usize>) -> Result<usize, String> {
+    match data_type {
+        "int8" | "uint8" | "int16" | "uint16" | "int32" | "uint32" | "int64" | "uint64" | "float" | "double" => Ok(get_data_size(data_type)),
+        "string" | "bytes" => size.filter(|s| *s > 0).ok_or_else(|| format!("A size is required for {} entries", data_type)),
+        other => value_codec::data_size(other).ok_or_else(|| format!("Unsupported data type '{}'", other)),
+    }
+}
+
+/// Text of a value as the address list shows it
+fn format_value(data_type: &str, display_format: &str, bytes: &[u8]) -> String {
+    let mut raw = [0u8; 8];
+    raw[..bytes.len().min(8)].copy_from_slice(&bytes[..bytes.len().min(8)]);
+    let unsigned = u64::from_le_bytes(raw);
+    let hex = display_format == "hex";
+    if !hex && value_codec::ValueType::parse(data_type).is_some() {
+        return value_codec::decode(data_type, bytes).unwrap_or_else(|_| "??".to_string());
+    }
+    match data_type {
+        "float" => f32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]).to_string(),
+        "double" => f64::from_le_bytes(raw).to_string(),
+        "string" => String::from_utf8_lossy(bytes.split(|&b| b == 0).next().unwrap_or(&[])).to_string(),
+        "bytes" => bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" "),
+        _ if hex => format!("0x{:X}", unsigned),
+        "int8" => (raw[0] as i8).to_string(),
+        "int16" => i16::from_le_bytes([raw[0], raw[1]]).to_string(),
+        "int32" => i32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]).to_string(),
+        "int64" => (unsigned as i64).to_string(),
+        _ => unsigned.to_string(),
+    }
+}
+
+/// Little-endian bytes of a value typed into the address list
+fn parse_value(data_type: &str, size: usize, text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim();
+    if value_codec::ValueType::parse(data_type).is_some() {
+        return value_codec::encode(data_type, text);
+    }
+    let invalid = || format!("Invalid {} value '{}'", data_type, text);
+    let integer = || -> Result<u64, String> {
+        if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            return u64::from_str_radix(hex, 16).map_err(|_| invalid());
+        }
+        text.parse::<i64>().map(|v| v as u64).or_else(|_| text.parse::<u64>()).map_err(|_| invalid())
+    };
+    let mut bytes = match data_type {
+        "float" => text.parse::<f32>().map_err(|_| invalid())?.to_le_bytes().to_vec(),
+        "double" => text.parse::<f64>().map_err(|_| invalid())?.to_le_bytes().to_vec(),
+        "string" => text.as_bytes().to_vec(),
+        "bytes" => hex::decode(text.replace(' ', "")).map_err(|_| invalid())?,
+        _ => integer()?.to_le_bytes()[..size].to_vec(),
+    };
+    bytes.resize(size, 0);
+    Ok(bytes)
+}
+
+fn pointer_size(modules: &[ModuleInfo]) -> usize {
+    match modules.first().and_then(|m| m.is_64bit) {
+        Some(false) => 4,
+        _ => 8,
+    }
+}
+
+/// Resolve the current address of each entry, following pointer chains one
+/// level at a time with a batched read per level
+async fn resolve_addresses(
+    host: &str,
+    port: u16,
+    entries: &[WatchEntry],
+    modules: &[ModuleInfo],
+) -> Vec<Result<u64, String>> {
+    let ptr_size = pointer_size(modules);
+    let mut addresses: Vec<Result<u64, String>> = entries.iter().map(|entry| {
+        if entry.module_name.is_empty() {
+            return Ok(entry.module_offset);
+        }
+        modules.iter()
+            .find(|m| m.modulename == entry.module_name)
+            .map(|m| m.base + entry.module_offset)
+            .ok_or_else(|| format!("Module '{}' is not loaded", entry.module_name))
+    }).collect();
+
+    let depth = entries.iter().map(|e| e.pointer_offsets.len()).max().unwrap_or(0);
+    for level in 0..depth {
+        let pending: Vec<usize> = (0..entries.len())
+            .filter(|&i| level < entries[i].pointer_offsets.len() && addresses[i].is_ok())
+            .collect();
+        let reads: Vec<(u64, usize)> = pending.iter()
+            .map(|&i| (*addresses[i].as_ref().unwrap_or(&0), ptr_size))
+            .collect();
+        let data = read_chunks_parallel(host, port, &reads, READ_TIMEOUT).await;
+        for (&i, bytes) in pending.iter().zip(data) {
+            let pointer = bytes.filter(|b| b.len() >= ptr_size).map(|b| {
+                let mut raw = [0u8; 8];
+                raw[..ptr_size].copy_from_slice(&b[..ptr_size]);
+                u64::from_le_bytes(raw)
+            });
+            addresses[i] = match pointer {
+                Some(p) if p != 0 => Ok(p.wrapping_add_signed(entries[i].pointer_offsets[level])),
+                Some(_) => Err(format!("Null pointer at level {}", level + 1)),
+                None => Err(format!("Unreadable pointer at level {}", level + 1)),
+            };
+        }
+    }
+    addresses
+}
+
+/// Read (address, size) values, merging nearby ones into shared chunks
+async fn read_values(host: &str, port: u16, targets: &[(u64, usize)]) -> Vec<Option<Vec<u8>>> {
+    let mut order: Vec<usize> = (0..targets.len()).collect();
+    order.sort_by_key(|&i| targets[i].0);
+    // (start, size, members)
+    let mut chunks: Vec<(u64, usize, Vec<usize>)> = Vec::new();
+    for i in order {
+        let (address, size) = targets[i];
+        if let Some(last) = chunks.last_mut() {
+            let gap = address.saturating_sub(last.0 + last.1 as u64);
+            let new_size = last.1.max((address - last.0) as usize + size);
+            if gap <= CHUNK_GAP_THRESHOLD && new_size <= MAX_CHUNK_SIZE {
+                last.1 = new_size;
+                last.2.push(i);
+                continue;
+            }
+        }
+        chunks.push((address, size, vec![i]));
+    }
+    let reads: Vec<(u64, usize)> = chunks.iter().map(|c| (c.0, c.1)).collect();
+    let data: Vec<Option<Bytes>> = read_chunks_parallel(host, port, &reads, READ_TIMEOUT).await;
+
+    let mut values = vec![None; targets.len()];
+    for ((start, _, members), bytes) in chunks.iter().zip(data) {
+        let Some(bytes) = bytes else {
+            continue;
+        };
+        for &i in members {
+            let (address, size) = targets[i];
+            let offset = (address - start) as usize;
+            values[i] = bytes.get(offset..offset + size).map(|b| b.to_vec());
+        }
+    }
+    values
+}
+
+async fn entries_for_target(state: &AppStateType) -> Result<Vec<WatchEntry>, String> {
+    let (target_os, process_name, _) = target_info(state)?;
+    db::run(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM watchlist WHERE target_os = ?1 AND process_name = ?2 ORDER BY sort_order, id",
+            SELECT_COLUMNS
+        )).map_err(|e| e.to_string())?;
+        let rows = stmt.query_map(params![target_os, process_name], row_to_entry)
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }).await
+}
+
+/// Saved addresses of the attached process
+#[tauri::command]
+pub async fn list_watchlist(state: tauri::State<'_, AppStateType>) -> Result<Vec<WatchEntry>, String> {
+    entries_for_target(state.inner()).await
+}
+
+#[tauri::command]
+pub async fn add_watch_entry(state: tauri::State<'_, AppStateType>, entry: WatchEntryInput) -> Result<WatchEntry, String> {
+    let (target_os, process_name, modules) = target_info(state.inner())?;
+    let size = value_size(&entry.data_type, entry.size)?;
+    db::run(move |conn| {
+        // Symbol names in the expression are looked up in SQLite
+        let (module_name, module_offset, pointer_offsets) = parse_address(&entry.address, &target_os, &modules)?;
+        conn.execute(
+            "INSERT INTO watchlist (target_os, process_name, label, module_name, module_offset, pointer_offsets, data_type, size, display_format, sort_order, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9,
+                     (SELECT COALESCE(MAX(sort_order), -1) + 1 FROM watchlist WHERE target_os = ?1 AND process_name = ?2),
+                     datetime('now'), datetime('now'))",
+            params![
+                target_os,
+                process_name,
+                entry.label,
+                module_name,
+                module_offset as i64,
+                serde_json::to_string(&pointer_offsets).map_err(|e| e.to_string())?,
+                entry.data_type,
+                size as i64,
+                entry.display_format.unwrap_or_else(|| "dec".to_string()),
+            ],
+        ).map_err(|e| e.to_string())?;
+        get_entry(conn, conn.last_insert_rowid())
+    }).await
+}
+
+/// Replace an entry's label, address, type and format; a type or size change
+/// drops its freeze value
+#[tauri::command]
+pub async fn update_watch_entry(state: tauri::State<'_, AppStateType>, id: i64, entry: WatchEntryInput) -> Result<WatchEntry, String> {
+    let (target_os, _, modules) = target_info(state.inner())?;
+    let size = value_size(&entry.data_type, entry.size)?;
+    db::run(move |conn| {
+        // Symbol names in the expression are looked up in SQLite
+        let (module_name, module_offset, pointer_offsets) = parse_address(&entry.address, &target_os, &modules)?;
+        let existing = get_entry(conn, id)?;
+        let retyped = existing.data_type != entry.data_type || existing.size != size;
+        conn.execute(
+            "UPDATE watchlist SET label = ?1, module_name = ?2, module_offset = ?3, pointer_offsets = ?4, data_type = ?5, size = ?6,
+                    display_format = ?7, frozen = frozen AND NOT ?8, freeze_value = CASE WHEN ?8 THEN NULL ELSE freeze_value END,
+                    updated_at = datetime('now')
+             WHERE id = ?9",
+            params![
+                entry.label,
+                module_name,
+                module_offset as i64,
+                serde_json::to_string(&pointer_offsets).map_err(|e| e.to_string())?,
+                entry.data_type,
+                size as i64,
+                entry.display_format.unwrap_or(existing.display_format),
+                retyped,
+                id,
+            ],
+        ).map_err(|e| e.to_string())?;
+        get_entry(conn, id)
+    }).await
+}
+
+#[tauri::command]
+pub async fn delete_watch_entry(id: i64) -> Result<bool, String> {
+    db::run(move |conn| {
+        let deleted = conn.execute("DELETE FROM watchlist WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        Ok(deleted > 0)
+    }).await
+}
+
+/// Store the list order of the given entries (first id gets position 0)
+#[tauri::command]
+pub async fn reorder_watchlist(ids: Vec<i64>) -> Result<(), String> {
+    db::run(move |conn| {
+        for (position, id) in ids.iter().enumerate() {
+            conn.execute("UPDATE watchlist SET sort_order = ?1 WHERE id = ?2", params![position as i64, id])
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }).await
+}
+
+/// Freeze or unfreeze an entry. Freezing holds `value` (text in the entry's
+/// type) or, without one, the value currently in memory.
+pub async fn set_frozen(state: &AppStateType, id: i64, frozen: bool, value: Option<String>) -> Result<WatchEntry, String> {
+    let entry = load_entry(id).await?;
+    let freeze_value = match (frozen, value) {
+        (false, _) => entry.freeze_value.clone(),
+        (true, Some(text)) => Some(parse_value(&entry.data_type, entry.size, &text)?),
+        (true, None) => {
+            let (host, port) = server_address()?;
+            let (_, _, modules) = target_info(state)?;
+            let address = resolve_addresses(&host, port, std::slice::from_ref(&entry), &modules).await.remove(0)?;
+            let current = read_values(&host, port, &[(address, entry.size)]).await.remove(0);
+            Some(current.ok_or_else(|| format!("Failed to read memory at 0x{:x}", address))?)
+        }
+    };
+    db::run(move |conn| {
+        conn.execute(
+            "UPDATE watchlist SET frozen = ?1, freeze_value = ?2, updated_at = datetime('now') WHERE id = ?3",
+            params![frozen as i64, freeze_value, id],
+        ).map_err(|e| e.to_string())?;
+        get_entry(conn, id)
+    }).await
+}
+
+/// Flip an entry's freeze flag, holding the value currently in memory
+pub async fn toggle_frozen(state: &AppStateType, id: i64) -> Result<WatchEntry, String> {
+    let frozen = load_entry(id).await?.frozen;
+    set_frozen(state, id, !frozen, None).await
+}
+
+#[tauri::command]
+pub async fn set_watch_frozen(
+    state: tauri::State<'_, AppStateType>,
+    id: i64,
+    frozen: bool,
+    value: Option<String>,
+) -> Result<WatchEntry, String> {
+    set_frozen(state.inner(), id, frozen, value).await
+}
+
+/// Read the current values of the attached process's entries in batched
+/// chunks. Frozen entries whose value drifted are written back first.
+#[tauri::command]
+pub async fn refresh_watchlist(state: tauri::State<'_, AppStateType>) -> Result<Vec<WatchValue>, String> {
+    let entries = entries_for_target(state.inner()).await?;
+    if entries.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (host, port) = server_address()?;
+    let (_, _, modules) = target_info(state.inner())?;
+    let addresses = resolve_addresses(&host, port, &entries, &modules).await;
+    let targets: Vec<(u64, usize)> = entries.iter().zip(&addresses)
+        .filter_map(|(entry, address)| address.as_ref().ok().map(|a| (*a, entry.size)))
+        .collect();
+    let mut read = read_values(&host, port, &targets).await.into_iter();
+
+    let mut values = Vec::with_capacity(entries.len());
+    for (entry, address) in entries.iter().zip(addresses) {
+        let address = match address {
+            Ok(address) => address,
+            Err(e) => {
+                values.push(WatchValue { id: entry.id, address: None, bytes: None, value: None, frozen: entry.frozen, error: Some(e) });
+                continue;
+            }
+        };
+        let mut bytes = read.next().flatten();
+        let mut error = bytes.is_none().then(|| format!("Failed to read memory at 0x{:x}", address));
+        if let (true, Some(held), Some(current)) = (entry.frozen, &entry.freeze_value, &bytes) {
+            if held != current {
+                match write_memory_to_server(&host, port, address, held).await {
+                    Ok(()) => bytes = Some(held.clone()),
+                    Err(e) => error = Some(format!("Failed to hold frozen value: {}", e)),
+                }
+            }
+        }
+        values.push(WatchValue {
+            id: entry.id,
+            address: Some(address),
+            value: bytes.as_deref().map(|b| format_value(&entry.data_type, &entry.display_format, b)),
+            bytes,
+            frozen: entry.frozen,
+            error,
+        });
+    }
+    Ok(values)
+}
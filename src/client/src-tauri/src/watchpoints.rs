@@ -0,0 +1,203 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::clock_sync::iso_timestamp;
+use crate::state::{AppState, AppStateType, ExceptionData, StateUpdateEvent, WatchpointAccessType, WatchpointInfo};
+use crate::{remove_watchpoint_on_server, server_address, set_watchpoint_on_server};
+
+// Hardware debug registers available for data watchpoints (x86 DR0-DR3; the
+// architectural minimum on ARM64)
+pub const MAX_HARDWARE_WATCHPOINTS: usize = 4;
+
+// Debug registers a background tool arms at once by default. They are scarce,
+// so tools leave the rest to the user.
+pub const TOOL_WATCHPOINTS_DEFAULT: usize = 2;
+
+// Debug registers held by background tools (SMC monitor, ...), by owner.
+// They come out of the same budget as the user's watchpoints.
+static TOOL_WATCHPOINTS: Lazy<Mutex<HashMap<&'static str, usize>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn tool_watchpoints_in_use() -> usize {
+    TOOL_WATCHPOINTS.lock().map(|tools| tools.values().sum()).unwrap_or(0)
+}
+
+/// Reserve up to `wanted` debug registers for `owner`; returns how many were
+/// granted. Call `release` for registers that could not be armed or are removed.
+pub fn reserve(state: &AppStateType, owner: &'static str, wanted: usize) -> Result<usize, String> {
+    let user = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?.watchpoints.len();
+    let mut tools = TOOL_WATCHPOINTS.lock().map_err(|e| e.to_string())?;
+    let in_use = user + tools.values().sum::<usize>();
+    let granted = wanted.min(MAX_HARDWARE_WATCHPOINTS.saturating_sub(in_use));
+    *tools.entry(owner).or_insert(0) += granted;
+    Ok(granted)
+}
+
+/// Return `count` debug registers reserved by `owner`
+pub fn release(owner: &'static str, count: usize) {
+    if let Ok(mut tools) = TOOL_WATCHPOINTS.lock() {
+        if let Some(held) = tools.get_mut(owner) {
+            *held = held.saturating_sub(count);
+        }
+    }
+}
+
+fn parse_address(address: &str) -> Option<u64> {
+    u64::from_str_radix(address.trim().trim_start_matches("0x"), 16).ok()
+}
+
+/// Check a watch range against what the target's debug registers can express
+pub fn validate(arch: &str, address: u64, size: u32, kind: &WatchpointAccessType) -> Result<(), String> {
+    if !matches!(size, 1 | 2 | 4 | 8) {
+        return Err(format!("Invalid watchpoint size {} (expected 1, 2, 4 or 8)", size));
+    }
+    match arch {
+        "x86_64" | "x86" => {
+            // DR7 LEN only encodes naturally aligned 1/2/4/8 byte ranges, and
+            // RW has no read-only mode
+            if size == 8 && arch == "x86" {
+                return Err("8-byte watchpoints need a 64-bit target".to_string());
+            }
+            if !address.is_multiple_of(size as u64) {
+                return Err(format!("x86 watchpoints must be aligned to their size (0x{:x} is not {}-byte aligned)", address, size));
+            }
+            if matches!(kind, WatchpointAccessType::Read) {
+                return Err("x86 cannot watch reads only; use rw".to_string());
+            }
+        }
+        // The byte-select mask covers bytes of one aligned doubleword
+        "arm64" | "aarch64" if (address & 7) + size as u64 > 8 => {
+            return Err(format!("ARM64 watchpoints cannot cross an 8-byte boundary (0x{:x} + {})", address, size));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Fill `watchpoint_id` of watchpoint hits from the watchpoints in state and
+/// count the hits. ARM64 reports the accessed address, which can start below
+/// the watched bytes, so the enclosing doubleword is accepted as a fallback.
+pub fn tag_exceptions(state: &mut AppState, exceptions: &mut [ExceptionData]) {
+    for exception in exceptions.iter_mut().filter(|e| e.exception_type == "watchpoint" && e.watchpoint_id.is_none()) {
+        let Some(memory_address) = exception.memory_address else {
+            continue;
+        };
+        let ranges: Vec<(usize, u64, u64)> = state.watchpoints.iter().enumerate()
+            .filter_map(|(i, w)| parse_address(&w.address).map(|a| (i, a, a + w.size as u64)))
+            .collect();
+        let owner = ranges.iter()
+            .find(|(_, start, end)| (*start..*end).contains(&memory_address))
+            .or_else(|| ranges.iter().find(|(_, start, _)| start & !7 == memory_address & !7));
+        if let Some((index, _, _)) = owner {
+            let watchpoint = &mut state.watchpoints[*index];
+            watchpoint.hit_count += 1;
+            exception.watchpoint_id = Some(watchpoint.id.clone());
+        }
+    }
+}
+
+fn emit_watchpoints(app: &AppHandle, watchpoints: &[WatchpointInfo]) {
+    let event = StateUpdateEvent {
+        field: "watchpoints".to_string(),
+        value: serde_json::to_value(watchpoints).unwrap_or_default(),
+        timestamp: AppState::current_timestamp(),
+    };
+    for window in app.webview_windows().values() {
+        if let Err(e) = window.emit("state-updated", &event) {
+            eprintln!("Failed to emit state update event to window: {}", e);
+        }
+    }
+}
+
+/// Set a hardware watchpoint and record it in state. `kind` is r, w or rw.
+#[tauri::command]
+pub async fn set_watchpoint(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+    address: u64,
+    size: u32,
+    kind: String,
+    description: Option<String>,
+) -> Result<WatchpointInfo, String> {
+    let access = kind.to_lowercase();
+    let access_type: WatchpointAccessType = serde_json::from_value(serde_json::json!(access))
+        .map_err(|_| format!("Unknown watchpoint kind '{}' (expected r, w or rw)", kind))?;
+    let (host, port) = server_address()?;
+    {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let arch = state_guard.server_info.as_ref().map(|s| s.arch.clone()).unwrap_or_default();
+        validate(&arch, address, size, &access_type)?;
+        if state_guard.watchpoints.iter().any(|w| parse_address(&w.address) == Some(address)) {
+            return Err(format!("A watchpoint is already set at 0x{:x}", address));
+        }
+        if state_guard.watchpoints.len() + tool_watchpoints_in_use() >= MAX_HARDWARE_WATCHPOINTS {
+            return Err(format!("All {} hardware watchpoints are in use", MAX_HARDWARE_WATCHPOINTS));
+        }
+    }
+
+    let id = set_watchpoint_on_server(&host, port, address, size as usize, &access).await?;
+    let watchpoint = WatchpointInfo {
+        id,
+        address: format!("0x{:x}", address),
+        size,
+        access_type,
+        hit_count: 0,
+        created_at: iso_timestamp(AppState::current_timestamp()),
+        description,
+    };
+
+    let watchpoints = {
+        let mut state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        state_guard.watchpoints.push(watchpoint.clone());
+        state_guard.touch();
+        state_guard.watchpoints.clone()
+    };
+    emit_watchpoints(&app, &watchpoints);
+    Ok(watchpoint)
+}
+
+/// Remove a watchpoint by id or address
+#[tauri::command]
+pub async fn remove_watchpoint(
+    app: AppHandle,
+    state: tauri::State<'_, AppStateType>,
+    id: Option<String>,
+    address: Option<u64>,
+) -> Result<bool, String> {
+    let (host, port) = server_address()?;
+    let target = {
+        let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        state_guard.watchpoints.iter()
+            .find(|w| match (&id, address) {
+                (Some(id), _) => &w.id == id,
+                (None, Some(address)) => parse_address(&w.address) == Some(address),
+                (None, None) => false,
+            })
+            .and_then(|w| parse_address(&w.address).map(|a| (w.id.clone(), a)))
+    };
+    let Some((target_id, target_address)) = target.or_else(|| address.map(|a| (String::new(), a))) else {
+        return Err("Watchpoint not found".to_string());
+    };
+
+    remove_watchpoint_on_server(&host, port, target_address).await?;
+
+    let watchpoints = {
+        let mut state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+        let before = state_guard.watchpoints.len();
+        state_guard.watchpoints.retain(|w| w.id != target_id && parse_address(&w.address) != Some(target_address));
+        if state_guard.watchpoints.len() == before {
+            return Ok(false);
+        }
+        state_guard.touch();
+        state_guard.watchpoints.clone()
+    };
+    emit_watchpoints(&app, &watchpoints);
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn list_watchpoints(state: tauri::State<'_, AppStateType>) -> Result<Vec<WatchpointInfo>, String> {
+    let state_guard = state.lock().map_err(|e| format!("Failed to lock state: {}", e))?;
+    Ok(state_guard.watchpoints.clone())
+}
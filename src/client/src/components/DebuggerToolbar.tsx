@@ -523,103 +523,73 @@ export const DebuggerToolbar: React.FC<DebuggerToolbarProps> = ({
 
   // Handle go to address
   const handleGoToAddress = useCallback(async () => {
-    if (gotoAddress.trim()) {
-      // Check if it's a library+offset expression
-      const isLibExpr = isLibraryExpression(gotoAddress);
-
-      if (isLibExpr) {
-        // Check if we have connection info for async symbol loading
-        const serverInfo =
-          connectionHost && connectionPort
-            ? {
-                ip: connectionHost,
-                port: connectionPort,
-              }
-            : null;
-
-        if (serverInfo && attachedModules.length > 0) {
-          // Use async version that can load symbols on-demand
-          const normalizedAddress = await normalizeAddressStringAsync(
-            gotoAddress,
-            attachedModules,
-            serverInfo
-          );
-
-          if (normalizedAddress) {
-            console.log(
-              `Parsed library+offset expression "${gotoAddress}" to address ${normalizedAddress}`
-            );
-            addToHistory(gotoAddress);
-            // Use setAssemblyAddressWithHistory to track navigation for Back button
-            uiActions.setAssemblyAddressWithHistory(normalizedAddress);
-            if (onGoToAddress) {
-              onGoToAddress(normalizedAddress);
-            }
-          } else {
-            setSnackbar({
-              open: true,
-              message: `Failed to parse library+offset expression: ${gotoAddress}. Make sure the module is loaded.`,
-              severity: "error",
-            });
-            console.error(
-              `Failed to parse library+offset expression: ${gotoAddress}`
-            );
-          }
-        } else {
-          // Fallback to sync version (library+offset only, no function lookup)
-          const normalizedAddress = normalizeAddressString(
-            gotoAddress,
-            attachedModules
-          );
+    if (!gotoAddress.trim()) {
+      return;
+    }
 
-          if (normalizedAddress) {
-            console.log(
-              `Parsed library+offset expression "${gotoAddress}" to address ${normalizedAddress}`
-            );
-            addToHistory(gotoAddress);
-            // Use setAssemblyAddressWithHistory to track navigation for Back button
-            uiActions.setAssemblyAddressWithHistory(normalizedAddress);
-            if (onGoToAddress) {
-              onGoToAddress(normalizedAddress);
+    // Library+offset expressions and plain addresses keep their existing
+    // parsing (hex without 0x); anything else goes to the expression evaluator
+    let normalizedAddress: string | null = null;
+    if (isLibraryExpression(gotoAddress)) {
+      const serverInfo =
+        connectionHost && connectionPort
+          ? {
+              ip: connectionHost,
+              port: connectionPort,
             }
-          } else {
-            setSnackbar({
-              open: true,
-              message: `Failed to parse library+offset expression: ${gotoAddress}. Make sure the module is loaded.`,
-              severity: "error",
-            });
-            console.error(
-              `Failed to parse library+offset expression: ${gotoAddress}`
-            );
-          }
-        }
+          : null;
+      if (serverInfo && attachedModules.length > 0) {
+        // Use async version that can load symbols on-demand
+        normalizedAddress = await normalizeAddressStringAsync(
+          gotoAddress,
+          attachedModules,
+          serverInfo
+        );
       } else {
-        // Direct address - normalize and navigate
-        const normalizedAddress = normalizeAddressString(gotoAddress);
+        // Fallback to sync version (library+offset only, no function lookup)
+        normalizedAddress = normalizeAddressString(gotoAddress, attachedModules);
+      }
+    } else {
+      normalizedAddress = normalizeAddressString(gotoAddress);
+    }
 
-        if (normalizedAddress) {
-          addToHistory(gotoAddress); // Add to history
-          // Use setAssemblyAddressWithHistory to track navigation for Back button
-          uiActions.setAssemblyAddressWithHistory(normalizedAddress);
-          if (onGoToAddress) {
-            onGoToAddress(normalizedAddress);
-          }
-        } else {
-          // Show error for invalid address format
-          setSnackbar({
-            open: true,
-            message: `Invalid address format: ${gotoAddress}`,
-            severity: "error",
-          });
-          console.error(`Invalid address format: ${gotoAddress}`);
+    let evaluatorError: string | null = null;
+    if (!normalizedAddress) {
+      try {
+        const result = await apiClient.evaluateExpression(gotoAddress);
+        if (result.address !== undefined && result.address !== null) {
+          normalizedAddress = `0x${result.address.toString(16)}`;
         }
+      } catch (error) {
+        evaluatorError = String(error);
       }
     }
+
+    if (normalizedAddress) {
+      console.log(`Resolved "${gotoAddress}" to address ${normalizedAddress}`);
+      addToHistory(gotoAddress);
+      // Use setAssemblyAddressWithHistory to track navigation for Back button
+      uiActions.setAssemblyAddressWithHistory(normalizedAddress);
+      if (onGoToAddress) {
+        onGoToAddress(normalizedAddress);
+      }
+    } else {
+      const message = evaluatorError
+        ? `Failed to evaluate "${gotoAddress}": ${evaluatorError}`
+        : `Invalid address: ${gotoAddress}. Make sure the module is loaded.`;
+      setSnackbar({
+        open: true,
+        message,
+        severity: "error",
+      });
+      console.error(message);
+    }
   }, [
     gotoAddress,
     onGoToAddress,
     attachedModules,
     addToHistory,
+    apiClient,
     connectionHost,
     connectionPort,
     uiActions,
@@ -677,15 +677,14 @@ export const GhidraAnalyzer: React.FC<GhidraAnalyzerProps> = ({
     addLog("info", "Loading Ghidra project... This may take 30-60 seconds.");
 
     try {
-      const port = 18462;
-      const result = await invoke<boolean>("start_ghidra_server", {
+      // The backend picks a free port and reports it back
+      const port = await invoke<number>("start_ghidra_server", {
         projectPath: libInfo.projectPath,
         libraryName: libraryName,
         ghidraPath: ghidraPath,
-        port: port,
       });
 
-      if (result) {
+      if (port) {
         // Wait for server to be ready (poll for up to 120 seconds)
         let ready = false;
         let lastLogCount = 0;
@@ -21,7 +21,7 @@ import {
 } from "@mui/icons-material";
 
 import { getApiClient, ServerInfo, ProcessInfo } from "../lib/api";
-import { useLocalStorage } from "../hooks/useLocalStorage";
+import { useAppSetting } from "../hooks/useAppSetting";
 import { useAppState } from "../hooks/useAppState";
 
 // Legacy props for backward compatibility (optional)
@@ -45,9 +45,9 @@ export function ServerConnection({
   const connectionPort = system.connectionPort;
 
   const isCompactHeight = useMediaQuery("(max-height: 800px)");
-  // Save connection settings to localStorage
-  const [savedHost, setSavedHost] = useLocalStorage("server-host", "localhost");
-  const [savedPort, setSavedPort] = useLocalStorage("server-port", 8080);
+  // Save connection settings to the backend's app settings
+  const [savedHost, setSavedHost] = useAppSetting("server-host", "localhost");
+  const [savedPort, setSavedPort] = useAppSetting("server-port", 8080);
 
   const [host, setHost] = useState(savedHost || connectionHost || "localhost");
   const [port, setPort] = useState(
@@ -113,7 +113,7 @@ export function ServerConnection({
     };
   }, [handleConnectionStateChange]);
 
-  // Sync with global state and initialize from localStorage
+  // Sync with global state and initialize from saved settings
   useEffect(() => {
     console.log("[ServerConnection] Syncing state:", {
       connectionHost,
@@ -130,9 +130,9 @@ export function ServerConnection({
       );
       setHost(connectionHost);
     } else if (savedHost) {
-      // Fallback to localStorage if global store is not yet initialized
+      // Fallback to saved settings if global store is not yet initialized
       console.log(
-        "[ServerConnection] Using savedHost from localStorage:",
+        "[ServerConnection] Using savedHost from app settings:",
         savedHost
       );
       setHost(savedHost);
@@ -145,9 +145,9 @@ export function ServerConnection({
       );
       setPort(connectionPort.toString());
     } else if (savedPort) {
-      // Fallback to localStorage if global store is not yet initialized
+      // Fallback to saved settings if global store is not yet initialized
       console.log(
-        "[ServerConnection] Using savedPort from localStorage:",
+        "[ServerConnection] Using savedPort from app settings:",
         savedPort
       );
       setPort(savedPort.toString());
@@ -0,0 +1,47 @@
+import { useState, useEffect } from "react";
+import { getApiClient } from "../lib/api";
+
+// Like useLocalStorage, but persisted in the backend's app_settings table.
+// A value still in localStorage from older builds is moved there on first load.
+export function useAppSetting<T>(key: string, initialValue: T) {
+  const [storedValue, setStoredValue] = useState<T>(initialValue);
+
+  useEffect(() => {
+    let cancelled = false;
+    const load = async () => {
+      const client = getApiClient();
+      try {
+        let item = await client.getAppSetting(key);
+        const legacy = window.localStorage.getItem(key);
+        if (item === null && legacy !== null) {
+          await client.saveAppSetting(key, legacy);
+          item = legacy;
+        }
+        if (legacy !== null) {
+          window.localStorage.removeItem(key);
+        }
+        if (item !== null && !cancelled) {
+          setStoredValue(JSON.parse(item));
+        }
+      } catch (error) {
+        console.error(`Error reading app setting "${key}":`, error);
+      }
+    };
+    load();
+    return () => {
+      cancelled = true;
+    };
+  }, [key]);
+
+  const setValue = (value: T | ((val: T) => T)) => {
+    const valueToStore = value instanceof Function ? value(storedValue) : value;
+    setStoredValue(valueToStore);
+    getApiClient()
+      .saveAppSetting(key, JSON.stringify(valueToStore))
+      .catch((error) =>
+        console.error(`Error setting app setting "${key}":`, error)
+      );
+  };
+
+  return [storedValue, setValue] as const;
+}
@@ -1,6 +1,7 @@
 import { useState, useCallback, useRef, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { useUIStore } from "../stores/uiStore";
+import type { LatencyBreakdown } from "../lib/api";
 
 export interface GhidraAnalysisStatus {
   library_path: string;
@@ -30,6 +31,7 @@ export interface GhidraDecompileResult {
   line_mapping: Record<string, string> | null; // line number (as string) -> offset (hex string)
   tokens?: GhidraTokenInfo[] | null; // Token information for syntax highlighting
   error: string | null;
+  timing?: LatencyBreakdown; // Present while latency instrumentation is on
 }
 
 export interface XrefEntry {
@@ -394,59 +396,23 @@ export const useGhidraAnalysis = () => {
       functionAddress: string,
       ghidraPath: string
     ): Promise<GhidraDecompileResult | null> => {
-      if (!ghidraPath) {
-        return {
-          success: false,
-          function_name: "",
-          address: functionAddress,
-          decompiled_code: null,
-          line_mapping: null,
-          error: "Ghidra path not provided",
-        };
-      }
-
       const libInfo = getAnalyzedLibraryInfo(libraryPath);
-      if (!libInfo) {
-        return {
-          success: false,
-          function_name: "",
-          address: functionAddress,
-          decompiled_code: null,
-          line_mapping: null,
-          error: "Library not analyzed. Please analyze with Ghidra first.",
-        };
-      }
 
       setIsDecompiling(true);
 
       try {
-        // Extract library filename
-        const pathParts = libraryPath.split(/[/\\]/);
-        const libraryName = pathParts[pathParts.length - 1];
-
-        // Check if server is running for this project
-        if (serverRunning && serverProjectPath === libInfo.projectPath) {
-          // Use fast server mode
-          console.log("[Ghidra] Using server mode for decompile");
-          const result = await invoke<GhidraDecompileResult>(
-            "ghidra_server_decompile",
-            {
-              projectPath: libInfo.projectPath,
-              functionAddress: functionAddress,
-            }
-          );
-          setLastDecompileResult(result);
-          setIsDecompiling(false);
-          return result;
-        }
-
-        // Fallback to regular mode
-        const result = await invoke<GhidraDecompileResult>("ghidra_decompile", {
-          projectPath: libInfo.projectPath,
-          libraryName: libraryName,
-          functionAddress: functionAddress,
-          ghidraPath: ghidraPath,
-        });
+        // The backend picks the project's decompiler: Ghidra (server or
+        // headless) when available, else radare2/rizin or Capstone pseudo-code
+        const result = await invoke<GhidraDecompileResult>(
+          "decompile_function",
+          {
+            libraryPath: libraryPath,
+            functionAddress: functionAddress,
+            projectPath: libInfo?.projectPath,
+            localPath: libInfo?.localPath,
+            ghidraPath: ghidraPath || undefined,
+          }
+        );
 
         setLastDecompileResult(result);
         setIsDecompiling(false);
@@ -466,7 +432,7 @@ export const useGhidraAnalysis = () => {
         return errorResult;
       }
     },
-    [getAnalyzedLibraryInfo, serverRunning, serverProjectPath]
+    [getAnalyzedLibraryInfo]
   );
 
   // Check analysis status (used to verify if analysis exists on disk)
@@ -915,23 +881,22 @@ export const useGhidraAnalysis = () => {
       projectPath: string,
       libraryName: string,
       ghidraPath: string,
-      port: number = 18462
+      port?: number
     ): Promise<boolean> => {
       try {
-        const result = await invoke<boolean>("start_ghidra_server", {
+        // The backend picks a free port when the preferred one is taken
+        const chosenPort = await invoke<number>("start_ghidra_server", {
           projectPath,
           libraryName,
           ghidraPath,
           port,
         });
-        if (result) {
-          updateToolsState({
-            ghidraServerStatus: "running",
-            ghidraServerPort: port,
-            ghidraServerProjectPath: projectPath,
-          });
-        }
-        return result;
+        updateToolsState({
+          ghidraServerStatus: "running",
+          ghidraServerPort: chosenPort,
+          ghidraServerProjectPath: projectPath,
+        });
+        return true;
       } catch (e) {
         console.error("Failed to start Ghidra server:", e);
         return false;
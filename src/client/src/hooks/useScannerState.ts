@@ -399,6 +399,7 @@ export const useScannerState = () => {
   const [memoryRegionsLoaded] = useState(true); // Always true for manual mode
   const [isSettingsLocked, setIsSettingsLocked] = useState(false); // Lock settings after first scan
   const progressIntervalRef = useRef<number | null>(null);
+  const unknownProgressUnlistenRef = useRef<(() => void) | null>(null);
 
   const apiClient = getApiClient();
 
@@ -689,23 +690,20 @@ export const useScannerState = () => {
     [apiClient, uiActions]
   );
 
-  // Function to start unknown scan progress polling
-  const startUnknownScanProgressPolling = useCallback(
-    (
+  // Follow unknown scan progress through backend-pushed events
+  const startUnknownScanProgressListener = useCallback(
+    async (
       scanId: string,
       onComplete: (scanId: string, totalAddresses: number) => void
     ) => {
-      if (progressIntervalRef.current) {
-        clearInterval(progressIntervalRef.current);
-      }
+      unknownProgressUnlistenRef.current?.();
+      unknownProgressUnlistenRef.current = null;
 
-      console.log(`Starting unknown scan progress polling for: ${scanId}`);
-
-      progressIntervalRef.current = setInterval(async () => {
-        try {
-          const progress = await apiClient.getUnknownScanProgress(scanId);
-          console.log(`Unknown scan progress:`, progress);
+      console.log(`Listening for unknown scan progress: ${scanId}`);
 
+      const unlisten = await apiClient.onUnknownScanProgress(
+        scanId,
+        (progress) => {
           setScannerState((prev) => ({
             ...prev,
             scanProgress: progress.progress_percentage,
@@ -721,20 +719,19 @@ export const useScannerState = () => {
             isScanning: progress.is_scanning,
           });
 
-          // If scan is complete, stop polling and load results
-          if (!progress.is_scanning && progressIntervalRef.current) {
+          // If scan is complete, stop listening and load results
+          if (!progress.is_scanning) {
             console.log(
               `Unknown scan completed: ${progress.found_count} addresses found`
             );
-            clearInterval(progressIntervalRef.current);
-            progressIntervalRef.current = null;
+            unknownProgressUnlistenRef.current?.();
+            unknownProgressUnlistenRef.current = null;
 
             onComplete(scanId, progress.found_count);
           }
-        } catch (error) {
-          console.error("Failed to get unknown scan progress:", error);
         }
-      }, 100); // Poll every 100ms
+      );
+      unknownProgressUnlistenRef.current = unlisten;
     },
     [apiClient, uiActions]
   );
@@ -905,6 +902,8 @@ export const useScannerState = () => {
       clearInterval(progressIntervalRef.current);
       progressIntervalRef.current = null;
     }
+    unknownProgressUnlistenRef.current?.();
+    unknownProgressUnlistenRef.current = null;
   }, []);
 
   // Clean up interval on unmount
@@ -1412,7 +1411,7 @@ export const useScannerState = () => {
     scannerState.scanId,
     apiClient,
     startProgressPolling,
-    startUnknownScanProgressPolling,
+    startUnknownScanProgressListener,
     stopProgressPolling,
     performUnknownScanStreaming,
     ui.scannerState.scanSettings,
@@ -1,9 +1,10 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { useEffect, useState, useCallback, useRef } from "react";
+import type { FridaHookHit } from "../lib/api";
 
 export interface TauriExceptionData {
-  exception_type: string; // "watchpoint", "breakpoint", "singlestep"
+  exception_type: string; // "watchpoint", "breakpoint", "singlestep", "frida_hook"
   address: string;
   instruction?: string;
   timestamp: string;
@@ -15,6 +16,7 @@ export interface TauriExceptionData {
   bytecode?: string;
   opcode?: string;
   pc?: number;
+  hook?: FridaHookHit; // Set for "frida_hook" exceptions
 }
 
 export interface TauriTraceEntryData {
@@ -31,6 +33,7 @@ export interface TauriTraceEntryData {
   timestamp: number;
   library_expression?: string;
   target_address: string;
+  local_timestamp?: number; // timestamp on the local clock, after sync_target_clock
 }
 
 export interface TauriTraceSession {
@@ -1,11 +1,14 @@
 // API client for communicating with the backend server
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import {
   FilterRequest,
   FilterResponse,
   FilterProgressResponse,
   ExceptionInfo,
 } from "../types/index";
+import type { TauriTraceEntryData } from "../hooks/useTauriExceptionStore";
+import type { GhidraCfgResult } from "../hooks/useGhidraAnalysis";
 
 // Native memory filter types (for Tauri commands)
 export interface NativeMemoryFilterRequest {
@@ -14,12 +17,58 @@ export interface NativeMemoryFilterRequest {
   pattern: string; // Hex-encoded pattern for comparison (min for range)
   pattern_max?: string; // Hex-encoded max pattern for range filter
   data_type: string; // "int8", "uint8", "int16", etc.
-  filter_method: string; // "exact", "range", "greater_or_equal", "less_than", "changed", "unchanged", "increased", "decreased"
+  filter_method: string; // "exact", "range", "greater_or_equal", "less_than", "changed", "unchanged", "increased", "decreased", "fuzzy"
+  fuzzy?: FuzzyFloatOptions; // For "fuzzy" (float / double)
+}
+
+// Without a rounding mode a value matches within max(epsilon, percent of the pattern)
+export interface FuzzyFloatOptions {
+  epsilon?: number; // Absolute band (0.5 when neither band is set)
+  percent?: number; // e.g. 1 for +-1% of the pattern
+  rounding?: "truncated" | "rounded"; // floor(value) / round(value) == int(pattern)
+}
+
+export interface NativePointerDerefStep {
+  target: number;
+  readable: boolean;
+  protection?: string;
+  module_name?: string;
+  module_offset?: number;
+  preview_hex?: string;
+  preview_ascii?: string;
 }
 
 export interface NativeMemoryFilterResult {
   address: number;
   value: number[]; // New value at the address as byte array
+  pointer?: NativePointerDerefStep[]; // Dereference chain for "pointer" lookups
+  data_item?: DataItemRef; // Ghidra data item containing the address
+  object_hint?: ObjectHint; // Likely containing object (detectObjects)
+}
+
+export interface ObjectHint {
+  kind:
+    | "std_string"
+    | "il2cpp_object"
+    | "mono_object"
+    | "objc_object"
+    | "cpp_object"
+    | "heap_chunk";
+  type_name?: string;
+  object_address: number;
+  offset: number; // Hit offset inside the object
+  confidence: number;
+  detail?: string;
+}
+
+export interface DataItemRef {
+  module_name: string;
+  item_offset: number;
+  offset_in_item: number;
+  name?: string;
+  data_type: string;
+  category: string;
+  value?: string;
 }
 
 export interface NativeMemoryFilterResponse {
@@ -27,6 +76,7 @@ export interface NativeMemoryFilterResponse {
   results: NativeMemoryFilterResult[];
   total_processed: number;
   error?: string;
+  timing?: LatencyBreakdown; // Present while latency instrumentation is on
 }
 
 // Native unknown scan types (for Tauri commands)
@@ -129,6 +179,8 @@ export interface DisassembleRequest {
   address: number;
   size: number;
   architecture: string;
+  symbolicate?: boolean; // Annotate call/jump targets with "; symbol"
+  comments?: boolean; // Structured output only: run the comment providers
 }
 
 export interface DisassembleResponse {
@@ -136,116 +188,319 @@ export interface DisassembleResponse {
   disassembly?: string;
   instructions_count: number;
   error?: string;
+  timing?: LatencyBreakdown; // Present while latency instrumentation is on
 }
 
-export interface MemoryReadRequest {
+export interface StructuredInstruction {
   address: number;
   size: number;
+  bytes: number[];
+  mnemonic: string;
+  operands: string;
+  groups: string[];
+  is_branch: boolean;
+  is_call: boolean;
+  is_return: boolean;
+  branch_target?: number; // Direct targets only
+  branch_symbol?: string; // Set when symbolicate was requested
+  regs_read: string[];
+  regs_write: string[];
+  comments?: InstructionComment[];
 }
 
-export interface MemoryReadResponse {
-  success: boolean;
-  data?: number[] | Uint8Array;
-  error?: string;
+export interface InstructionComment {
+  provider: string; // "symbols" | "pc_relative_data" | "bookmarks" | "xrefs" | "trace_hits" | "source_lines"
+  text: string;
 }
 
-export interface NewsItem {
-  id: string;
-  date: string;
-  title: string;
-  body: string;
-  type: "update" | "info" | "warning" | "announcement";
-  link: string;
+export interface CommentProviderConfig {
+  name: string;
+  enabled: boolean;
+  ttl_ms: number;
 }
 
-export interface NewsResponse {
-  latest_version: string;
-  force_update: boolean;
-  news: NewsItem[];
+export interface AssembleRequest {
+  architecture: string;
+  address: number;
+  source: string; // One instruction per line or separated by ';'
+  pad_with_nops?: boolean;
+  write?: boolean;
+  force?: boolean; // Write even if the patch ends inside an instruction
 }
 
-export interface WatchpointInfo {
-  id: string;
+export interface AssembledInstruction {
   address: number;
-  size: number;
-  access_type: string; // "r", "w", "rw", "x", "rx", "wx", "rwx"
-  hit_count: number;
-  created_at: string; // ISO 8601 timestamp
+  text: string;
+  bytes: number[];
+}
+
+export interface AssembleResponse {
+  bytes: number[];
+  instructions: AssembledInstruction[];
+  padding: number;
+  original_size?: number;
+  original_bytes?: number[];
+  fits?: boolean;
+  written: boolean;
+  warnings: string[];
+}
+
+export interface PatchInfo {
+  id: number;
+  target_os: string;
+  module_name: string; // Empty for patches outside any module
+  module_offset: number;
+  address: number;
+  original_bytes: number[];
+  patched_bytes: number[];
   description?: string;
+  applied: boolean;
+  created_at: string;
+  updated_at: string;
 }
 
-export interface ConnectionState {
-  connected: boolean;
-  host: string;
-  port: number;
-  serverInfo?: ServerInfo;
-  lastError?: string;
+export interface BreakpointDefinition {
+  id: number;
+  target_os: string;
+  module_name: string; // Empty for breakpoints outside any module
+  module_offset: number;
+  hit_count: number;
+  condition?: string;
+  action: BreakpointAction;
+  enabled: boolean;
+  is_software: boolean;
+  created_at: string;
+  updated_at: string;
+  address?: number; // Resolved against the loaded modules
+  installed: boolean;
+  condition_passes: number;
+  condition_skips: number; // Hits auto-continued because the condition was false
 }
 
-export interface ProcessState {
-  attached: boolean;
-  pid?: number;
-  name?: string;
-  processInfo?: ProcessInfo;
-  appInfo?: AppInfo;
-  modules?: ModuleInfo[];
-  symbols?: { [moduleBase: string]: SymbolInfo[] };
+// "log" records the hit and resumes; "continue" only counts it
+export type BreakpointAction = "break" | "log" | "continue";
+
+export interface BreakpointImportResult {
+  imported: number;
+  updated: number;
+  skipped: number;
+  errors: string[];
+  restored: { id: number; address?: number; success: boolean; error?: string }[];
 }
 
-export interface AppInfo {
+export interface ModuleAnalysisPolicy {
+  target_os: string; // "*" applies to every OS
+  module_pattern: string; // '*' and '?' wildcards
+  mode: "never" | "symbols" | "full";
+  auto_start_server: boolean;
+  prefetch_decompiles: boolean;
+  builtin: boolean; // Default for system libraries, not stored
+  updated_at?: string;
+}
+
+export interface ClockSyncStatus {
+  server: string;
+  offset_us: number; // target - local
+  drift_ppm: number;
+  rtt_us: number;
+  samples: { local_us: number; offset_us: number; rtt_us: number }[];
+  synced_at: number;
+}
+
+export interface ConvertedTimestamp {
+  target_ms: number;
+  local_ms: number;
+  local_iso: string;
+  offset_ms: number;
+  synced: boolean; // False before the first sync (identity conversion)
+}
+
+export interface NativeTraceOptions {
+  stop_address?: number; // End before executing this address
+  span_entries?: number; // Records per compressed span
+  output_path?: string;
+  resume_on_finish?: boolean;
+  step_timeout_ms?: number;
+  checkpoint_interval?: number; // Instructions between stack checkpoints (0 = none, default 1000)
+  checkpoint_stack_bytes?: number; // Stack captured above SP per checkpoint (default 4096)
+}
+
+export interface NativeTraceStatus {
+  thread_id: number;
+  active: boolean;
+  entries: number;
+  max_entries: number;
+  spans: number;
+  compressed_bytes: number;
+  uncompressed_bytes: number;
+  last_address?: number;
+  depth: number;
+  started_at: number;
+  elapsed_ms: number;
+  steps_per_second: number;
+  stop_reason?: string; // "max_entries", "stop_address", "stopped" or an error
+  output_path?: string;
+  checkpoints: number;
+}
+
+// State before a traced instruction: exact registers, stack from the nearest earlier checkpoint
+export interface TraceMachineState {
+  index: number;
+  entry: TauriTraceEntryData;
+  checkpoint_index?: number;
+  stack_address?: number; // SP at the checkpoint
+  stack: number[];
+  stack_age: number; // Instructions since the checkpoint (their stores are not replayed)
+}
+
+export interface TraceDiffOptions {
+  ignore_registers?: string[]; // E.g. ["sp", "fp"] when the runs used different stacks
+  resync_window?: number;
+  absolute_addresses?: boolean; // Align on raw addresses instead of module + offset
+}
+
+export interface TraceDivergence {
+  index_a: number;
+  index_b: number;
+  after?: string; // Last common instruction before the split
+  function_name?: string;
+  address_a?: string;
+  address_b?: string;
+  skipped_a: number;
+  skipped_b: number;
+  rejoined: boolean;
+}
+
+export interface TraceDiffResult {
+  session_a: string;
+  session_b: string;
+  entries_a: number;
+  entries_b: number;
+  matched: number;
+  identical: boolean;
+  divergences: TraceDivergence[];
+  unique_branches: {
+    from: string;
+    to: string;
+    only_in: "a" | "b";
+    count: number;
+    function_name?: string;
+  }[];
+  register_diffs: {
+    index_a: number;
+    index_b: number;
+    address: string;
+    register: string;
+    value_a: string;
+    value_b: string;
+  }[];
+  truncated: boolean;
+}
+
+export type TraceExportFormat = "json" | "text" | "binary";
+
+export interface TraceExportResult {
+  path: string;
+  format: string;
+  entries: number;
+  bytes_written: number;
+}
+
+export interface CoverageOptions {
+  basic_blocks?: boolean; // Default: blocks for functions whose CFG is cached
+  max_points?: number;
+}
+
+export interface FunctionCoverage {
   name: string;
-  pid: number;
-  icon?: string;
-  arch?: string;
-  bundleIdentifier?: string;
+  offset: number;
+  blocks: number;
+  blocks_hit: number;
+  hit: boolean;
+  first_hit?: number;
 }
 
-export interface ProcessInfo {
+export interface CoverageReport {
+  module_name: string;
+  module_base: number;
+  running: boolean;
+  started_at: number;
+  stopped_at?: number;
+  total_functions: number;
+  hit_functions: number;
+  function_percentage: number;
+  total_blocks: number;
+  hit_blocks: number;
+  block_percentage: number;
+  pending: number; // Breakpoints still armed
+  install_failed: number; // Points the server could not set (slot limits)
+  functions: FunctionCoverage[];
+}
+
+export interface SnapshotInfo {
+  id: string;
+  address: number;
+  size: number;
+  label?: string;
+  created_at: number;
+  stored_bytes: number; // Compressed size on disk
+  unreadable_bytes: number;
+  path: string;
+}
+
+export interface SnapshotDiff {
+  snapshot_id: string;
+  compared_at: number;
+  changed_bytes: number;
+  changed_runs: number; // Contiguous ranges of changed bytes
+  unreadable_bytes: number;
+  offset: number;
+  changes: { address: number; old: number; new: number }[];
+  has_more: boolean;
+  truncated: boolean; // Too many changes to page through all of them
+}
+
+export interface ProcessEntry {
   pid: number;
   processname: string;
+  score?: number; // Fuzzy match score when filtered (higher is better)
+  icon?: string; // data: URI
+  arch?: string; // Server architecture
 }
 
-export interface ModuleInfo {
-  name?: string; // deprecated, use modulename
+export interface AttachedProcessInfo {
+  process: ProcessInfo;
+  app_info?: AppInfo;
+  modules: ModuleInfo[];
+  attempts: number;
+}
+
+export interface DetachResult {
+  pid?: number;
+  breakpoints_removed: number;
+  watchpoints_removed: number;
+  errors: string[];
+}
+
+export interface ListedModule {
   modulename: string;
-  base_address?: string; // deprecated, use base
   base: number;
   size: number;
   path?: string;
   is_64bit?: boolean;
+  module_hash: string; // Symbol cache key
+  cached_symbols?: number; // Symbols already stored for this hash
 }
 
-export interface InstalledAppInfo {
-  bundleIdentifier: string;
-  displayName: string;
-  bundleVersion: string;
-  bundlePath: string;
-  executableName?: string;
-  executablePath?: string;
-  minimumOSVersion?: string;
-  dataContainerPath?: string;
-  iconFile?: string;
-}
-
-export interface SpawnAppResult {
-  success: boolean;
-  pid?: number;
-  bundleIdentifier?: string;
-  suspended?: boolean;
-  error?: string;
-  warning?: string;
-}
-
-export interface SymbolInfo {
+export interface ModuleSymbol {
   address: string;
-  name: string;
+  name: string; // Demangled
   size: number;
-  type: string;
+  symbol_type: string;
   scope: string;
   module_base: string;
   file_name?: string;
   line_number?: number;
-  // Extended Mach-O metadata (iOS/macOS only)
   is_external?: boolean;
   is_private_external?: boolean;
   is_weak_def?: boolean;
@@ -253,657 +508,3052 @@ export interface SymbolInfo {
   is_thumb?: boolean;
   section_index?: number;
   library_ordinal?: number;
-  source?: string; // "symtab" or "export_trie"
+  mangled_name?: string;
 }
 
-export interface ThreadInfo {
-  thread_id: number;
-  name: string;
-  pc: string;
-  sp: string;
-  fp: string;
-  state: string;
-  suspend_count: number;
+export interface ModuleSymbolsPage {
+  module_name: string;
+  module_hash: string;
+  total: number;
+  offset: number;
+  symbols: ModuleSymbol[];
+  next_offset?: number; // Absent on the last page
+  from_cache: boolean;
 }
 
-export interface NetworkConnection {
-  protocol: string;
-  local_address: string;
-  local_port: number;
-  remote_address: string;
-  remote_port: number;
-  state: string;
-  inode?: string;
+export interface ServerTlsOptions {
+  useTls?: boolean;
+  acceptSelfSigned?: boolean; // Requires a pinned certificate
 }
 
-export interface ApiResponse<T> {
-  success: boolean;
-  data?: T;
+export interface HttpSettings {
+  timeout_ms: number;
+  connect_timeout_ms: number;
+  max_concurrent: number; // Requests in flight to the server at once
+  max_retries: number; // Extra attempts after a transient failure
+  retry_backoff_ms: number; // First retry delay; doubles on every attempt
+  pool_max_idle: number;
+}
+
+export interface ServerCertificate {
+  host: string;
+  port: number;
+  fingerprint: string; // SHA-256, "AB:CD:..."
+  pinned: boolean;
+}
+
+export interface PinnedCertificate {
+  host: string;
+  port: number;
+  fingerprint: string;
+  pinned_at: string;
+}
+
+export interface MemoryDumpInfo {
+  path: string;
+  format: "raw" | "sparse" | "minidump" | "elf_core";
+  arch?: string;
+  segments: number;
+  total_bytes: number;
+  modules: ModuleInfo[];
+}
+
+export interface DumpFilters {
+  readable_only?: boolean;
+  writable_only?: boolean;
+  executable_only?: boolean;
+  module_backed_only?: boolean;
+  exclude_module_backed?: boolean;
+  address_ranges?: [number, number][]; // [start, end)
+  max_region_size?: number;
+}
+
+export interface DumpProgress {
+  output_path: string;
+  processed_bytes: number;
+  total_bytes: number;
+  regions_done: number;
+  regions_total: number;
+  failed_bytes: number;
+  finished: boolean;
+}
+
+export interface MemoryDumpResult {
+  output_path: string;
+  index_path?: string;
+  format: "sparse" | "minidump";
+  regions: number;
+  segments: number;
+  bytes_written: number;
+  failed_bytes: number;
+  cancelled: boolean;
+}
+
+export interface DumpedModule {
+  module_name: string;
+  base: number;
+  size: number;
+  local_path: string; // Pass to analyze_with_ghidra
+  format?: "pe" | "elf" | "macho";
+  unreadable_bytes: number;
+  fixups: string[];
+}
+
+export interface GhidraProjectInfo {
+  name: string; // e.g. "libfoo" or "game/libfoo"
+  project_path: string;
+  size_bytes: number;
+  last_used: number; // Unix seconds
+  modules: string[];
+  server_running: boolean;
+}
+
+export interface PruneResult {
+  deleted: string[];
+  freed_bytes: number;
+  remaining_bytes: number;
+  skipped: string[];
+}
+
+export interface GhidraServerStatus {
+  project_path: string;
+  library_name: string;
+  port: number;
+  state: "running" | "down" | "restarting" | "failed";
+  exit_code?: number;
+  restarts: number;
+  last_logs: string[];
   message?: string;
 }
 
-export interface SimpleResponse {
-  success: boolean;
-  message: string;
+export interface AutoRestartSettings {
+  enabled: boolean;
+  max_restarts: number;
+  initial_backoff_ms: number;
 }
 
-// Script Execution Types
-export type ScriptJobStatus =
-  | "pending"
-  | "running"
-  | "completed"
-  | "failed"
-  | "cancelled";
+export interface DecompileAllProgress {
+  project_path: string;
+  module_name: string;
+  total: number;
+  done: number;
+  decompiled: number;
+  cached: number; // Skipped: already cached and not stale
+  failed: number;
+  current?: string;
+  finished: boolean;
+  cancelled: boolean;
+}
 
-export interface ExecuteScriptResponse {
+export interface GhidraEditResult {
   success: boolean;
-  job_id: string;
-  message: string;
+  function_offset?: string;
+  old_name?: string;
+  new_name?: string;
+  error?: string;
+  invalidated: number; // Cache rows dropped or rewritten
 }
 
-export interface ScriptFileUpload {
-  filename: string;
-  data_base64: string;
-  mime_type?: string;
+export interface CallGraphViewNode {
+  name: string;
+  offset: string;
+  depth: number; // Call distance from the root
+  indirect_calls: number;
+  address_taken: boolean;
 }
 
-export interface ScriptStatusResponse {
+export interface CallGraphView {
   success: boolean;
-  job_id: string;
-  status: ScriptJobStatus;
-  output: string;
+  root?: string;
+  nodes: CallGraphViewNode[];
+  edges: { from: string; to: string }[];
+  truncated: boolean;
   error?: string;
-  trace_callback_registered: boolean;
-  files: ScriptFileUpload[];
 }
 
-export interface ScriptCancelResponse {
-  success: boolean;
-  message: string;
+export type GhidraSearchKind =
+  | "string"
+  | "function_name"
+  | "immediate_value"
+  | "byte_pattern";
+
+export interface SearchHit {
+  offset: string;
+  text: string; // String value, function name or instruction
+  function_name?: string;
+  function_offset?: string;
+  references: { from_offset: string; from_function?: string; ref_type: string }[];
 }
 
-export interface ScriptDisableResponse {
+export interface GhidraSearchResult {
   success: boolean;
-  message: string;
+  results: SearchHit[];
+  truncated: boolean;
+  error?: string;
+  cached: boolean;
 }
 
-export interface ScanProgressResponse {
-  scan_id: string;
-  progress_percentage: number;
-  scanned_bytes: number;
-  total_bytes: number;
-  is_scanning: boolean;
-  current_region?: string;
+export interface FunctionFingerprint {
+  name: string;
+  offset: string;
+  size: number;
+  instructions?: number;
+  blocks?: number;
+  mnemonic_hash?: string;
+  byte_hash?: string;
 }
 
-class ApiClient {
-  private baseUrl: string = "";
-  private authToken: string | null = null;
-  private serverSessionId: string | null = null;
-  private connectionListeners: ((
-    connected: boolean,
-    error?: string
-  ) => void)[] = [];
-  private healthCheckInterval: number | null = null;
-  private isHealthCheckRunning: boolean = false;
+export interface FunctionMatch {
+  name_a: string;
+  name_b: string;
+  offset_a: string;
+  offset_b: string;
+  size_a: number;
+  size_b: number;
+  match_kind: "name" | "bytes" | "mnemonics" | "position";
+}
 
-  constructor(baseUrl?: string) {
-    if (baseUrl) {
-      this.baseUrl = baseUrl;
-    }
-  }
+export interface ModuleDiffResult {
+  module_a: string;
+  module_b: string;
+  source: "server" | "database";
+  added: FunctionFingerprint[];
+  removed: FunctionFingerprint[];
+  changed: FunctionMatch[];
+  unchanged: number;
+  // exact entries have identical bytes: offsets inside translate by the same delta
+  translations: { offset_a: string; offset_b: string; size: number; exact: boolean }[];
+}
 
-  getBaseUrl(): string {
-    return this.baseUrl;
-  }
+export type DecompilerBackend = "auto" | "ghidra" | "radare2" | "capstone";
 
-  updateConnection(host: string, port: number) {
-    const newBaseUrl = `http://${host}:${port}`;
-    // Only clear auth token when actually changing to a different server
-    if (this.baseUrl !== newBaseUrl) {
-      this.baseUrl = newBaseUrl;
-      // Clear auth token when changing connection
-      this.authToken = null;
-      this.serverSessionId = null;
-      // Also set connection for Tauri backend
-      this.setTauriServerConnection(host, port);
-      // Stop any existing health check
-      this.stopHealthCheck();
-    }
-  }
+export interface DecompilerSettings {
+  project_path: string;
+  backend: DecompilerBackend;
+  tool_path?: string; // radare2/rizin binary
+  backends: { name: string; available: boolean; reason?: string }[];
+}
 
-  // Add connection listener for UI updates
-  addConnectionListener(
-    listener: (connected: boolean, error?: string) => void
+export interface FridaProcess {
+  pid: number;
+  name: string;
+}
+
+export interface FridaHookHit {
+  hook_id: number;
+  args: string[];
+  retval?: string;
+  backtrace: string[];
+}
+
+export interface FridaHookOptions {
+  capture_args?: number; // Argument registers to record (default 4)
+  backtrace?: boolean;
+  trace_count?: number; // Record hits as a trace session instead of exceptions
+}
+
+export interface FridaHookInfo {
+  id: number;
+  pid: number;
+  device: string;
+  module_name: string;
+  offset: string;
+  address?: string;
+  hits: number;
+  active: boolean;
+}
+
+export type ScriptState = "running" | "finished" | "failed" | "stopped";
+
+export interface ScriptInfo {
+  id: number;
+  name: string;
+  state: ScriptState;
+  started_at: number;
+  finished_at?: number;
+  error?: string;
+  result?: unknown; // Value the chunk returned
+}
+
+export interface ScriptOutput {
+  id: number;
+  level: "log" | "error";
+  text: string;
+}
+
+export interface CheatTableHotkey {
+  keys: string; // Accelerator, e.g. "Ctrl+F1"
+  action: string; // "toggle_freeze" | "freeze" | "unfreeze" | "set_value" | "increase_value" | "decrease_value"
+  value?: string;
+  description?: string;
+}
+
+export interface CheatTableEntry {
+  description: string;
+  address: string; // "0x..." or pointer chain "base+0x10 → [0x18] → [0x20]"
+  library_expression?: string; // "module + 0x..."
+  value_type: string; // ScanValueType; "ptr" for pointer chains
+  ptr_value_type?: string;
+  size?: number;
+  display_format?: "dec" | "hex";
+  hotkeys: CheatTableHotkey[];
+  group?: string;
+  resolved: boolean; // Address is absolute in the current process
+}
+
+export interface CheatTableImport {
+  entries: CheatTableEntry[];
+  skipped: string[]; // Scripts and unsupported entries, by description
+}
+
+export interface WatchEntry {
+  id: number;
+  target_os: string;
+  process_name: string;
+  label: string;
+  module_name: string; // Empty for absolute addresses
+  module_offset: number; // Absolute address when module_name is empty
+  pointer_offsets: number[];
+  data_type: string;
+  size: number;
+  display_format: "dec" | "hex";
+  frozen: boolean;
+  freeze_value?: number[];
+  sort_order: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface WatchEntryInput {
+  label: string;
+  address: string; // "0x...", "module+0x...", or "module+0x10 → [0x18] → [0x20]"
+  data_type: string;
+  size?: number; // Required for string / bytes
+  display_format?: "dec" | "hex";
+}
+
+export interface WatchValue {
+  id: number;
+  address?: number;
+  bytes?: number[];
+  value?: string;
+  frozen: boolean;
+  error?: string;
+}
+
+export type HotkeyAction =
+  | { kind: "toggle_freeze"; entry_id: number } // Watch list entry
+  | { kind: "run_script"; path: string }
+  | { kind: "pause" }
+  | { kind: "resume" }
+  | { kind: "snapshot"; address: number; size: number };
+
+export interface HotkeyBinding {
+  accelerator: string; // e.g. "Ctrl+Shift+F1"
+  action: HotkeyAction;
+}
+
+export interface HotkeyTriggered {
+  accelerator: string;
+  action: HotkeyAction;
+  success: boolean;
+  error?: string;
+}
+
+export interface RemoteAllocation {
+  address: number;
+  size: number;
+  protection: string; // "r" | "rw" | "rx" | "rwx"
+  near_address?: number;
+  created_at: number;
+}
+
+export interface CodeCave {
+  address: number;
+  size: number;
+  module_offset: number;
+  fill_byte: number; // 0x00 or 0xCC
+  executable: boolean;
+  protection: string;
+}
+
+export interface HexPage {
+  address: number;
+  size: number;
+  generation: number; // Pass back on the next read to get a diff
+  readable: boolean;
+  data?: number[]; // Omitted when nothing changed since `generation`
+  changed: number[]; // Bitmap, bit (i % 8) of byte i / 8 set if byte i changed
+  changed_count: number;
+}
+
+export interface ScanGenerationStats {
+  generation: number;
+  region_files: number;
+  addresses: number;
+  disk_bytes: number;
+}
+
+export interface ScanStats {
+  scan_id: string;
+  latest_generation: number;
+  generations: ScanGenerationStats[]; // Oldest first; dropped generations are absent
+  disk_bytes: number;
+}
+
+export interface ScanCompactResult {
+  removed_generations: number[];
+  files_before: number;
+  files_after: number;
+  duplicates_removed: number;
+  bytes_freed: number;
+  stats: ScanStats;
+}
+
+export interface DisassemblyCacheStats {
+  blocks: number;
+  hits: number; // Pages served without a read
+  misses: number;
+}
+
+export interface ExpressionResult {
+  address?: number; // Operand of the outermost dereference, else the integer value
+  value_type: string; // "u32", "f32", ...; "int" / "float" without a dereference or cast
+  value: string;
+  hex?: string; // Integers only
+}
+
+export interface ObjcMethod {
+  selector: string;
+  types?: string; // Type encoding, e.g. "v16@0:8"
+  address?: number; // IMP
+  is_class_method: boolean;
+}
+
+export interface ObjcIvar {
+  name: string;
+  type_encoding?: string;
+  offset?: number;
+  size: number;
+}
+
+export interface ObjcClass {
+  name: string;
+  address: number;
+  superclass?: string;
+  is_swift: boolean;
+  instance_size: number;
+  methods: ObjcMethod[];
+  ivars: ObjcIvar[];
+}
+
+export interface ObjcCategory {
+  name: string;
+  class_name?: string;
+  methods: ObjcMethod[];
+}
+
+export interface SwiftField {
+  name: string;
+  type_name: string; // Mangled
+}
+
+export interface SwiftMethod {
+  kind: string; // "method" | "init" | "getter" | "setter" | "modify" | "read"
+  is_instance: boolean;
+  is_async: boolean;
+  address?: number;
+  symbol?: string;
+}
+
+export interface SwiftType {
+  kind: string;
+  name: string;
+  qualified_name: string;
+  descriptor: number;
+  access_function?: number;
+  is_generic: boolean;
+  fields: SwiftField[];
+  methods: SwiftMethod[];
+}
+
+export interface ObjcClassSummary {
+  name: string;
+  address: number;
+  superclass?: string;
+  is_swift: boolean;
+  method_count: number;
+  ivar_count: number;
+}
+
+export interface SwiftTypeSummary {
+  kind: string;
+  qualified_name: string;
+  descriptor: number;
+  field_count: number;
+  method_count: number;
+}
+
+export interface RuntimeMetadataSummary {
+  key: string; // Pass to the detail calls
+  module_name: string;
+  source: string; // "memory" | "file"
+  base: number;
+  classes: ObjcClassSummary[];
+  categories: ObjcCategory[];
+  swift_types: SwiftTypeSummary[];
+  selector_refs: number;
+  warnings: string[];
+}
+
+export interface ObjcImplementation {
+  class_name: string; // "Class" or "Class (Category)"
+  method: ObjcMethod;
+}
+
+export interface DexFileInfo {
+  address: number;
+  size: number;
+  version: string; // "035".."041", or "cdex" for compact dex
+  location?: string; // Mapping it was found in
+  class_count: number;
+  method_count: number;
+}
+
+export interface DexMethod {
+  method_index: number;
+  name: string;
+  signature: string; // "(ILjava/lang/String;)V"
+  access_flags: number;
+  code_offset?: number;
+  is_direct: boolean;
+}
+
+export interface DexClassSummary {
+  descriptor: string; // "Lcom/example/Foo;"
+  name: string; // "com.example.Foo"
+  superclass?: string;
+  access_flags: number;
+  method_count: number;
+}
+
+export interface DexClass {
+  dex_address: number;
+  descriptor: string;
+  name: string;
+  superclass?: string;
+  access_flags: number;
+  methods: DexMethod[];
+}
+
+export interface JavaMethodEntry {
+  class_name: string;
+  method: DexMethod;
+  dex_address: number;
+  art_method?: number;
+  entry_point?: number;
+  entry_kind: string; // "aot" | "jit" | "interpreter" | "nterp" | "jni_trampoline" | "resolution" | "compiled" | "unresolved"
+  entry_symbol?: string;
+  jni_function?: number; // Registered implementation of a native method
+  candidates: number; // More than one is ambiguous
+}
+
+export interface Il2CppMethod {
+  name: string;
+  module_offset?: number; // Absent for abstract / uninstantiated generic methods
+  token: number;
+  flags: number;
+  parameter_count: number;
+}
+
+export interface Il2CppField {
+  name: string;
+  type_name?: string;
+  offset?: number; // Instance offset, or offset in the static fields block
+  is_static: boolean;
+}
+
+export interface Il2CppClass {
+  type_index: number;
+  full_name: string;
+  namespace: string;
+  name: string;
+  image: string;
+  parent?: string;
+  flags: number;
+  methods: Il2CppMethod[];
+  fields: Il2CppField[];
+}
+
+export interface Il2CppClassSummary {
+  type_index: number;
+  full_name: string;
+  image: string;
+  method_count: number;
+  field_count: number;
+}
+
+export interface Il2CppLoadResult {
+  module_name: string;
+  metadata_version: number;
+  images: number;
+  classes: number;
+  methods: number;
+  methods_with_address: number;
+  fields: number;
+  fields_with_offset: number;
+  functions_added: number; // New or renamed function list entries
+  warnings: string[];
+}
+
+export interface MonoTarget {
+  pid?: number; // Default: the attached process
+  device?: string; // Frida device (default: frida-server on the connected host)
+  assembly?: string;
+}
+
+export interface MonoAssembly {
+  name: string;
+  guid: string; // Module version id; class lists are cached by it
+  class_count: number;
+  cached: boolean;
+}
+
+export interface MonoClassInfo {
+  assembly: string;
+  name: string; // "Namespace.Outer/Nested"
+  parent?: string;
+}
+
+export interface MonoClassDetail {
+  assembly: string;
+  guid: string;
+  name: string;
+  methods: { name: string; signature: string; flags: number }[];
+  fields: { name: string; type_name: string; offset: number; is_static: boolean }[];
+}
+
+export interface MonoMemberAddress {
+  class_name: string;
+  member: string;
+  signature?: string; // Methods only
+  type_name?: string; // Fields only
+  address: number;
+}
+
+export interface WasmCallGraphNode {
+  index: number;
+  name?: string;
+  imported: boolean;
+  callees: number[]; // Direct calls
+  indirect_calls: number;
+}
+
+export interface SymbolModuleRequest {
+  module_name: string;
+  build_id?: string; // ELF GNU build-id (hex) for debuginfod
+  pdb_name?: string;
+  pdb_guid?: string; // CodeView GUID (32 hex digits, no dashes)
+  pdb_age?: number;
+}
+
+export interface SymbolFetchResult {
+  module_name: string;
+  success: boolean;
+  local_path?: string;
+  source?: string; // Server URL the file came from, or "cache"
+  kind?: "dwarf" | "pdb";
+  error?: string;
+  symbols?: DebugSymbolsLoadResult; // Set when the file was loaded
+  load_error?: string;
+}
+
+export interface SymbolServerUrls {
+  symbol_servers: string[]; // symsrv layout (Microsoft symbol server style)
+  debuginfod: string[];
+}
+
+// Payload of "symbols://progress"
+export interface SymbolFetchProgress {
+  module_name: string;
+  url?: string;
+  downloaded_bytes: number;
+  total_bytes?: number;
+  finished: boolean;
+  result?: SymbolFetchResult;
+  symbols?: DebugSymbolsLoadResult;
+  load_error?: string;
+}
+
+export interface DebugSymbolsLoadResult {
+  module_name: string;
+  kind: "pdb" | "dwarf";
+  function_count: number;
+  line_count: number;
+  source_file_count: number;
+  type_count: number;
+}
+
+export interface SourcePathRule {
+  from: string; // Prefix of the path recorded in the debug info
+  to: string; // Local directory that replaces it
+}
+
+export interface ResolvedSourceLine {
+  address: number;
+  module_name: string;
+  module_offset: number;
+  function_name?: string;
+  file: string;
+  local_path?: string; // Existing local file after path mapping
+  line: number;
+  column: number;
+}
+
+export interface SourceFileEntry {
+  file: string;
+  local_path?: string;
+  line_count: number;
+}
+
+export type ExceptionRuleAction = "ignore" | "log" | "break" | "script";
+
+// The first enabled rule that matches an incoming exception decides its fate
+export interface ExceptionRule {
+  id: number;
+  name: string;
+  exception_types: string[]; // Empty matches every type
+  module_name: string; // Empty matches any address
+  address_start?: number; // Module-relative when module_name is set
+  address_end?: number; // Exclusive
+  action: ExceptionRuleAction;
+  script?: string; // Lua path or source; returning true keeps the thread stopped
+  enabled: boolean;
+  sort_order: number;
+  created_at: string;
+  updated_at: string;
+  hits: number; // Matches since the app started
+}
+
+export interface ExceptionRuleInput {
+  name: string;
+  exception_types?: string[];
+  module_name?: string;
+  address_start?: number;
+  address_end?: number;
+  action: ExceptionRuleAction;
+  script?: string;
+  enabled?: boolean;
+}
+
+export interface HeatmapRegion {
+  address: number;
+  size: number;
+}
+
+export interface AccessHeatmapStatus {
+  running: boolean;
+  address: number;
+  size: number;
+  granularity: number; // Bytes per cell
+  interval_ms: number;
+  samples: number; // Snapshots compared against their predecessor
+  last_sample_at?: number;
+  last_error?: string;
+}
+
+export interface HeatmapCell {
+  address: number;
+  changes: number;
+}
+
+export interface AccessHeatmap {
+  status: AccessHeatmapStatus;
+  cells: number[]; // Per cell: 255 = changed in every sample, 0 = never
+  hottest: HeatmapCell[];
+}
+
+export interface VirtualMethod {
+  index: number;
+  address?: number; // Resolved against the loaded modules
+  module_name: string; // Empty when the target is outside any module
+  module_offset: number;
+  name?: string;
+}
+
+export interface VtableInfo {
+  address?: number; // Address point (first method slot) in the attached process
+  module_offset: number;
+  class_name?: string;
+  rtti?: "itanium" | "msvc";
+  source: "rtti" | "ghidra" | "heuristic";
+  offset_to_top: number;
+  base_classes: string[];
+  methods: VirtualMethod[];
+}
+
+export interface ClassNode {
+  name: string;
+  bases: string[];
+  derived: string[];
+  vtables: number[]; // Module offsets of the class's vtables
+}
+
+export interface ModuleVtables {
+  module_name: string;
+  vtables: VtableInfo[];
+  classes: ClassNode[];
+}
+
+export interface CacheVersion {
+  module_hash?: string; // Build-id / UUID / PDB signature when identified
+  analyzed_at?: number; // Unix seconds of the last Ghidra analysis
+  patch_counter: number;
+  stamp: string;
+}
+
+export interface CacheTableStatus {
+  table: string;
+  entries: number;
+  stale: number; // Rows stamped with an older version (served as misses)
+}
+
+export interface CacheStatus {
+  target_os: string;
+  module_name: string;
+  version: CacheVersion;
+  invalidations: number;
+  last_reason?: "patch" | "rename" | "reanalysis";
+  invalidated_at?: string;
+  tables: CacheTableStatus[];
+}
+
+export type SearchEverythingKind =
+  | "function"
+  | "decompiled"
+  | "comment"
+  | "data"
+  | "watch";
+
+export interface SearchEverythingHit {
+  kind: SearchEverythingKind;
+  target_os: string;
+  module_name: string;
+  address: string; // Module offset (hex)
+  name?: string; // Function, data label or watch label
+  snippet: string; // Text around the match
+}
+
+export interface SearchEverythingResult {
+  hits: SearchEverythingHit[];
+  truncated: boolean;
+  indexed: number;
+}
+
+export type AnnotationKind = "bookmark" | "note";
+
+export interface Annotation {
+  id: number;
+  target_os: string;
+  module_name: string; // Empty for annotations outside any module
+  module_offset: number; // Absolute address when module_name is empty
+  address?: number; // In the attached process; undefined while the module is not loaded
+  kind: AnnotationKind;
+  title: string;
+  body: string; // Markdown
+  color?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface AnnotationInput {
+  address?: number; // Resolved against the attached modules
+  module_name?: string; // With module_offset, for modules not loaded
+  module_offset?: number;
+  kind: AnnotationKind;
+  title?: string;
+  body?: string;
+  color?: string;
+}
+
+export type AnnotationMergeStrategy = "ours" | "theirs" | "newest";
+
+// Sync file written by exportAnnotations (offsets are hex, module-relative)
+export interface AnnotationFile {
+  format: "dynadbg-annotations";
+  version: number;
+  exported_at: string;
+  target_os: string;
+  module_name: string;
+  module_hash?: string;
+  names: { offset: string; name: string; updated_at?: string }[];
+  comments: { offset: string; title: string; body: string; updated_at?: string }[];
+  bookmarks: { offset: string; title: string; color?: string; updated_at?: string }[];
+  structs: { name: string; global: boolean; definition: unknown; updated_at?: string }[];
+}
+
+export interface AnnotationExportSummary {
+  path: string;
+  target_os: string;
+  module_name: string;
+  names: number;
+  comments: number;
+  bookmarks: number;
+  structs: number;
+}
+
+export interface MergeCounts {
+  added: number;
+  updated: number; // Conflicts resolved in favour of the file
+  skipped: number; // Identical, kept ours, or nothing to attach to
+}
+
+export interface AnnotationImportResult {
+  target_os: string;
+  module_name: string;
+  names: MergeCounts;
+  comments: MergeCounts;
+  bookmarks: MergeCounts;
+  structs: MergeCounts;
+  warnings: string[];
+}
+
+export interface LatencyBreakdown {
+  operation: string;
+  total_us: number;
+  stages: { name: string; us: number }[]; // "network", "parse", "serialize", ...
+  bytes?: number;
+  cache_hit?: boolean;
+}
+
+export interface LatencyOperationSummary {
+  operation: string;
+  count: number;
+  p50_us: number;
+  p95_us: number;
+  max_us: number;
+  cache_hits: number;
+  cache_misses: number;
+  stages: {
+    name: string;
+    count: number;
+    p50_us: number;
+    p95_us: number;
+    max_us: number;
+  }[];
+}
+
+export interface RegisterDump {
+  thread_id: number;
+  arch: string;
+  registers: { name: string; value: string; bits: number }[];
+  flags: { name: string; bit: number; set: boolean }[]; // NZCV or RFLAGS bits
+  unavailable: string[]; // Registers the server cannot provide (FP/SIMD, ...)
+  values: Record<string, string>; // Canonical names plus aliases (fp, lr, pc, ...)
+}
+
+export interface ResolvedSymbol {
+  address: number;
+  module_name: string;
+  module_base: number;
+  module_offset: number;
+  function_name?: string;
+  function_offset?: number;
+  display: string;
+  source?: SourceLocation; // From loaded debug symbols
+}
+
+export interface SourceLocation {
+  file: string;
+  line: number;
+  column: number; // 0 when unknown
+}
+
+// Synthetic handles from register_virtual_address (see virtual_addresses.rs)
+export const VIRTUAL_ADDRESS_BASE = 0x1f000000000000;
+export const VIRTUAL_ADDRESS_END = 0x20000000000000;
+
+export function isVirtualAddress(address: number): boolean {
+  return address >= VIRTUAL_ADDRESS_BASE && address < VIRTUAL_ADDRESS_END;
+}
+
+export interface MemoryReadRequest {
+  address: number;
+  size: number;
+}
+
+export interface MemoryReadResponse {
+  success: boolean;
+  data?: number[] | Uint8Array;
+  error?: string;
+  timing?: LatencyBreakdown; // Present while latency instrumentation is on
+}
+
+export interface NewsItem {
+  id: string;
+  date: string;
+  title: string;
+  body: string;
+  type: "update" | "info" | "warning" | "announcement";
+  link: string;
+}
+
+export interface NewsResponse {
+  latest_version: string;
+  force_update: boolean;
+  news: NewsItem[];
+}
+
+export interface WatchpointInfo {
+  id: string;
+  address: number;
+  size: number;
+  access_type: string; // "r", "w", "rw", "x", "rx", "wx", "rwx"
+  hit_count: number;
+  created_at: string; // ISO 8601 timestamp
+  description?: string;
+}
+
+export interface ConnectionState {
+  connected: boolean;
+  host: string;
+  port: number;
+  serverInfo?: ServerInfo;
+  lastError?: string;
+}
+
+export interface ProcessState {
+  attached: boolean;
+  pid?: number;
+  name?: string;
+  processInfo?: ProcessInfo;
+  appInfo?: AppInfo;
+  modules?: ModuleInfo[];
+  symbols?: { [moduleBase: string]: SymbolInfo[] };
+}
+
+export interface AppInfo {
+  name: string;
+  pid: number;
+  icon?: string;
+  arch?: string;
+  bundleIdentifier?: string;
+}
+
+export interface ProcessInfo {
+  pid: number;
+  processname: string;
+}
+
+export interface ModuleInfo {
+  name?: string; // deprecated, use modulename
+  modulename: string;
+  base_address?: string; // deprecated, use base
+  base: number;
+  size: number;
+  path?: string;
+  is_64bit?: boolean;
+}
+
+export interface InstalledAppInfo {
+  bundleIdentifier: string;
+  displayName: string;
+  bundleVersion: string;
+  bundlePath: string;
+  executableName?: string;
+  executablePath?: string;
+  minimumOSVersion?: string;
+  dataContainerPath?: string;
+  iconFile?: string;
+}
+
+export interface SpawnAppResult {
+  success: boolean;
+  pid?: number;
+  bundleIdentifier?: string;
+  suspended?: boolean;
+  error?: string;
+  warning?: string;
+}
+
+export interface SymbolInfo {
+  address: string;
+  name: string;
+  size: number;
+  type: string;
+  scope: string;
+  module_base: string;
+  file_name?: string;
+  line_number?: number;
+  // Extended Mach-O metadata (iOS/macOS only)
+  is_external?: boolean;
+  is_private_external?: boolean;
+  is_weak_def?: boolean;
+  is_weak_ref?: boolean;
+  is_thumb?: boolean;
+  section_index?: number;
+  library_ordinal?: number;
+  source?: string; // "symtab" or "export_trie"
+}
+
+export interface ThreadInfo {
+  thread_id: number;
+  name: string;
+  pc: string;
+  sp: string;
+  fp: string;
+  state: string;
+  suspend_count: number;
+}
+
+// list_threads entry; cached names and PCs survive refreshes and thread exit
+export interface ThreadEntry {
+  thread_id: number;
+  name: string;
+  state: string; // "Running", "Stopped", ... or "Exited"
+  pc?: number;
+  sp?: number;
+  fp?: number;
+  suspend_count: number;
+  last_exception_pc?: number;
+  last_seen: number;
+  alive: boolean;
+}
+
+export interface NetworkConnection {
+  protocol: string;
+  local_address: string;
+  local_port: number;
+  remote_address: string;
+  remote_port: number;
+  state: string;
+  inode?: string;
+}
+
+export interface ApiResponse<T> {
+  success: boolean;
+  data?: T;
+  message?: string;
+}
+
+export interface SimpleResponse {
+  success: boolean;
+  message: string;
+}
+
+// Script Execution Types
+export type ScriptJobStatus =
+  | "pending"
+  | "running"
+  | "completed"
+  | "failed"
+  | "cancelled";
+
+export interface ExecuteScriptResponse {
+  success: boolean;
+  job_id: string;
+  message: string;
+}
+
+export interface ScriptFileUpload {
+  filename: string;
+  data_base64: string;
+  mime_type?: string;
+}
+
+export interface ScriptStatusResponse {
+  success: boolean;
+  job_id: string;
+  status: ScriptJobStatus;
+  output: string;
+  error?: string;
+  trace_callback_registered: boolean;
+  files: ScriptFileUpload[];
+}
+
+export interface ScriptCancelResponse {
+  success: boolean;
+  message: string;
+}
+
+export interface ScriptDisableResponse {
+  success: boolean;
+  message: string;
+}
+
+export interface ScanProgressResponse {
+  scan_id: string;
+  progress_percentage: number;
+  scanned_bytes: number;
+  total_bytes: number;
+  is_scanning: boolean;
+  current_region?: string;
+}
+
+class ApiClient {
+  private baseUrl: string = "";
+  private tlsOptions: ServerTlsOptions = {};
+  private authToken: string | null = null;
+  private serverSessionId: string | null = null;
+  private connectionListeners: ((
+    connected: boolean,
+    error?: string
+  ) => void)[] = [];
+  private healthCheckInterval: number | null = null;
+  private isHealthCheckRunning: boolean = false;
+
+  constructor(baseUrl?: string) {
+    if (baseUrl) {
+      this.baseUrl = baseUrl;
+    }
+  }
+
+  getBaseUrl(): string {
+    return this.baseUrl;
+  }
+
+  // TLS options persist across updateConnection calls that omit them
+  updateConnection(host: string, port: number, tls?: ServerTlsOptions) {
+    if (tls) {
+      this.tlsOptions = { ...this.tlsOptions, ...tls };
+    }
+    const scheme = this.tlsOptions.useTls ? "https" : "http";
+    const newBaseUrl = `${scheme}://${host}:${port}`;
+    // Only clear auth token when actually changing to a different server
+    if (this.baseUrl !== newBaseUrl) {
+      this.baseUrl = newBaseUrl;
+      // Clear auth token when changing connection
+      this.authToken = null;
+      this.serverSessionId = null;
+      // Also set connection for Tauri backend
+      this.setTauriServerConnection(host, port, this.tlsOptions);
+      // Stop any existing health check
+      this.stopHealthCheck();
+    }
+  }
+
+  // Add connection listener for UI updates
+  addConnectionListener(
+    listener: (connected: boolean, error?: string) => void
   ) {
     this.connectionListeners.push(listener);
   }
 
-  removeConnectionListener(
-    listener: (connected: boolean, error?: string) => void
-  ) {
-    const index = this.connectionListeners.indexOf(listener);
-    if (index > -1) {
-      this.connectionListeners.splice(index, 1);
-    }
+  removeConnectionListener(
+    listener: (connected: boolean, error?: string) => void
+  ) {
+    const index = this.connectionListeners.indexOf(listener);
+    if (index > -1) {
+      this.connectionListeners.splice(index, 1);
+    }
+  }
+
+  private notifyConnectionState(connected: boolean, error?: string) {
+    this.connectionListeners.forEach((listener) => listener(connected, error));
+  }
+
+  private async request<T>(
+    endpoint: string,
+    options?: RequestInit,
+    requireAuth: boolean = true
+  ): Promise<T> {
+    const url = `${this.baseUrl}${endpoint}`;
+    const headers: { [key: string]: string } = {
+      "Content-Type": "application/json",
+      ...((options?.headers as { [key: string]: string }) || {}),
+    };
+
+    // Add auth token if required and available
+    if (requireAuth && this.authToken) {
+      headers["Authorization"] = `Bearer ${this.authToken}`;
+    }
+
+    const startTime = performance.now();
+
+    try {
+      // Add detailed timing for single step endpoint
+      const isSingleStep = endpoint === "/api/debug/step";
+      if (isSingleStep) {
+        console.log(`[API TIMING] fetch() starting at ${Date.now()}ms`);
+      }
+
+      const response = await fetch(url, {
+        ...options,
+        headers,
+      });
+
+      const fetchEndTime = performance.now();
+      if (isSingleStep) {
+        console.log(
+          `[API TIMING] fetch() completed at ${Date.now()}ms, took ${(fetchEndTime - startTime).toFixed(2)}ms`
+        );
+      }
+
+      if (!response.ok) {
+        // Check for authentication errors
+        if (response.status === 401) {
+          this.authToken = null;
+          this.serverSessionId = null;
+          this.notifyConnectionState(
+            false,
+            "Authentication failed - server may have restarted"
+          );
+          throw new Error("Authentication failed - please reconnect");
+        } else if (response.status === 403) {
+          this.notifyConnectionState(false, "Server access denied");
+          throw new Error("Server access denied");
+        }
+
+        throw new Error(`HTTP ${response.status}: ${response.statusText}`);
+      }
+
+      const jsonStartTime = performance.now();
+      const jsonResult = await response.json();
+      if (isSingleStep) {
+        console.log(
+          `[API TIMING] json() parsing took ${(performance.now() - jsonStartTime).toFixed(2)}ms, total: ${(performance.now() - startTime).toFixed(2)}ms`
+        );
+      }
+      return jsonResult;
+    } catch (error) {
+      // Handle network errors as potential disconnections
+      if (error instanceof TypeError && error.message.includes("fetch")) {
+        this.notifyConnectionState(
+          false,
+          "Network error - server may be unreachable"
+        );
+        // Stop health check on network errors
+        this.stopHealthCheck();
+      } else if (
+        error instanceof Error &&
+        error.message.includes("ERR_CONNECTION_REFUSED")
+      ) {
+        this.notifyConnectionState(
+          false,
+          "Connection refused - server may have stopped"
+        );
+        this.stopHealthCheck();
+      }
+      throw error;
+    }
+  }
+
+  async logout(): Promise<void> {
+    if (this.authToken) {
+      try {
+        await this.request<any>("/api/auth/logout", {
+          method: "POST",
+        });
+      } catch (error) {
+        // Ignore logout errors
+        console.warn("Logout request failed:", error);
+      }
+    }
+
+    this.authToken = null;
+    this.serverSessionId = null;
+    // Stop health check when logging out
+    this.stopHealthCheck();
+  }
+
+  isAuthenticated(): boolean {
+    return this.authToken !== null;
+  }
+
+  getServerSessionId(): string | null {
+    return this.serverSessionId;
+  }
+
+  // Set authentication info for new windows
+  setAuthenticationInfo(authToken: string, serverSessionId?: string) {
+    this.authToken = authToken;
+    this.serverSessionId = serverSessionId || null;
+    console.log("Authentication info set:", {
+      hasToken: !!authToken,
+      hasSessionId: !!serverSessionId,
+    });
+  }
+
+  // Get current authentication info
+  getAuthenticationInfo(): {
+    authToken: string | null;
+    serverSessionId: string | null;
+  } {
+    return {
+      authToken: this.authToken,
+      serverSessionId: this.serverSessionId,
+    };
+  }
+
+  // Health check
+  async healthCheck(): Promise<any> {
+    return this.request<any>("/health", undefined, false);
+  }
+
+  // Start periodic health check
+  startHealthCheck(intervalMs: number = 5000) {
+    if (this.healthCheckInterval) {
+      this.stopHealthCheck();
+    }
+
+    this.isHealthCheckRunning = true;
+    this.healthCheckInterval = window.setInterval(async () => {
+      if (!this.isHealthCheckRunning) {
+        return;
+      }
+
+      try {
+        await this.healthCheck();
+        // Health check succeeded, no action needed
+      } catch (error) {
+        console.warn("Health check failed:", error);
+        // Notify listeners about connection loss
+        this.notifyConnectionState(false, "Server connection lost");
+        this.stopHealthCheck();
+      }
+    }, intervalMs);
+  }
+
+  // Stop health check
+  stopHealthCheck() {
+    this.isHealthCheckRunning = false;
+    if (this.healthCheckInterval) {
+      window.clearInterval(this.healthCheckInterval);
+      this.healthCheckInterval = null;
+    }
+  }
+
+  async getServerInfo(): Promise<ServerInfo> {
+    return this.request<ServerInfo>("/api/server/info");
+  }
+
+  // Process management
+  async enumerateProcesses(): Promise<ProcessInfo[]> {
+    return this.request<ProcessInfo[]>("/api/processes");
+  }
+
+  async attachProcess(pid: number): Promise<SimpleResponse> {
+    return this.request<SimpleResponse>(`/api/processes/${pid}/attach`, {
+      method: "POST",
+    });
+  }
+
+  async getProcessInfo(): Promise<ApiResponse<AppInfo>> {
+    return this.request<ApiResponse<AppInfo>>("/api/process/info");
+  }
+
+  async changeProcessState(doPlay: boolean): Promise<SimpleResponse> {
+    return this.request<SimpleResponse>("/api/process/state", {
+      method: "PUT",
+      body: JSON.stringify({ do_play: doPlay }),
+    });
+  }
+
+  async getProcessIcon(pid: number): Promise<string | null> {
+    try {
+      const response = await fetch(
+        `${this.baseUrl}/api/processes/${pid}/icon`,
+        {
+          headers: this.authToken
+            ? { Authorization: `Bearer ${this.authToken}` }
+            : {},
+        }
+      );
+
+      if (response.status === 401) {
+        this.authToken = null;
+        this.serverSessionId = null;
+        this.notifyConnectionState(
+          false,
+          "Authentication failed - server may have restarted"
+        );
+        return null;
+      }
+
+      if (response.ok) {
+        const blob = await response.blob();
+        return URL.createObjectURL(blob);
+      }
+      return null;
+    } catch (error) {
+      if (error instanceof TypeError && error.message.includes("fetch")) {
+        this.notifyConnectionState(
+          false,
+          "Network error - server may be unreachable"
+        );
+        this.stopHealthCheck();
+      }
+      console.debug(`Failed to get icon for process ${pid}:`, error);
+      return null;
+    }
+  }
+
+  // Module management
+  async enumerateModules(): Promise<ApiResponse<{ modules: ModuleInfo[] }>> {
+    return this.request<ApiResponse<{ modules: ModuleInfo[] }>>("/api/modules");
+  }
+
+  // Thread management
+  async enumerateThreads(): Promise<ApiResponse<{ threads: ThreadInfo[] }>> {
+    return this.request<ApiResponse<{ threads: ThreadInfo[] }>>("/api/threads");
+  }
+
+  // Network connections
+  async enumerateNetwork(): Promise<
+    ApiResponse<{ connections: NetworkConnection[] }>
+  > {
+    return this.request<ApiResponse<{ connections: NetworkConnection[] }>>(
+      "/api/network"
+    );
+  }
+
+  async enumerateSymbols(
+    moduleBase: string
+  ): Promise<ApiResponse<{ symbols: SymbolInfo[] }>> {
+    const baseAddress = parseInt(moduleBase.replace("0x", ""), 16);
+    return this.request<ApiResponse<{ symbols: SymbolInfo[] }>>(
+      `/api/modules/${baseAddress}/symbols`
+    );
+  }
+
+  async enumerateSymbolsForModule(module: ModuleInfo): Promise<SymbolInfo[]> {
+    try {
+      const baseAddress =
+        module.base_address || `0x${module.base.toString(16)}`;
+      const response = await this.enumerateSymbols(baseAddress);
+      return response.data?.symbols || [];
+    } catch (error) {
+      const moduleName = module.name || module.modulename;
+      console.error(
+        `Failed to enumerate symbols for module ${moduleName}:`,
+        error
+      );
+      return [];
+    }
+  }
+
+  async enumerateAllSymbols(
+    modules: ModuleInfo[]
+  ): Promise<{ [moduleBase: string]: SymbolInfo[] }> {
+    const symbolsMap: { [moduleBase: string]: SymbolInfo[] } = {};
+
+    for (const module of modules) {
+      try {
+        const symbols = await this.enumerateSymbolsForModule(module);
+        const baseKey = module.base_address || `0x${module.base.toString(16)}`;
+        symbolsMap[baseKey] = symbols;
+      } catch (error) {
+        const moduleName = module.name || module.modulename;
+        const baseKey = module.base_address || `0x${module.base.toString(16)}`;
+        console.error(
+          `Failed to enumerate symbols for module ${moduleName}:`,
+          error
+        );
+        symbolsMap[baseKey] = [];
+      }
+    }
+
+    return symbolsMap;
+  }
+
+  // Memory operations
+  async readMemory(
+    address: string,
+    size: number,
+    usePtrace: boolean = false
+  ): Promise<ArrayBuffer> {
+    // Convert hex address to decimal for API compatibility
+    let numericAddress: number;
+    if (address.startsWith("0x") || address.startsWith("0X")) {
+      numericAddress = parseInt(address, 16);
+    } else {
+      numericAddress = parseInt(address, 10);
+    }
+
+    if (isNaN(numericAddress) || numericAddress < 0) {
+      throw new Error(`Invalid address format: ${address}`);
+    }
+
+    // Virtual addresses are resolved by the Tauri backend
+    if (isVirtualAddress(numericAddress)) {
+      const data = await invoke<number[]>("read_virtual_address", {
+        address: numericAddress,
+        size,
+      });
+      return new Uint8Array(data).buffer;
+    }
+
+    try {
+      const headers: { [key: string]: string } = {};
+      if (this.authToken) {
+        headers["Authorization"] = `Bearer ${this.authToken}`;
+      }
+
+      const usePtraceParam = usePtrace ? "&use_ptrace=true" : "";
+      const response = await fetch(
+        `${this.baseUrl}/api/memory/read?address=${numericAddress}&size=${size}${usePtraceParam}`,
+        { headers }
+      );
+
+      if (!response.ok) {
+        // Handle authentication errors
+        if (response.status === 401) {
+          this.authToken = null;
+          this.serverSessionId = null;
+          this.notifyConnectionState(
+            false,
+            "Authentication failed - server may have restarted"
+          );
+          throw new Error("Authentication failed - please reconnect");
+        } else if (response.status === 403) {
+          this.notifyConnectionState(false, "Server access denied");
+          throw new Error("Server access denied");
+        }
+
+        const errorText = await response.text();
+        throw new Error(
+          `HTTP ${response.status}: ${response.statusText} - ${errorText}`
+        );
+      }
+
+      return response.arrayBuffer();
+    } catch (error) {
+      // Handle network errors
+      if (error instanceof TypeError && error.message.includes("fetch")) {
+        this.notifyConnectionState(
+          false,
+          "Network error - server may be unreachable"
+        );
+        this.stopHealthCheck();
+      }
+      console.error(
+        `Failed to read memory at ${address} (${numericAddress}):`,
+        error
+      );
+      throw error;
+    }
+  }
+
+  async writeMemory(address: string, buffer: ArrayBuffer): Promise<string> {
+    const numericAddress = parseInt(address.replace("0x", ""), 16);
+    if (isVirtualAddress(numericAddress)) {
+      await invoke("write_virtual_address", {
+        address: numericAddress,
+        data: Array.from(new Uint8Array(buffer)),
+      });
+      return "ok";
+    }
+    const response = await this.request<any>("/api/memory/write", {
+      method: "POST",
+      body: JSON.stringify({
+        address: parseInt(address.replace("0x", ""), 16),
+        buffer: Array.from(new Uint8Array(buffer)),
+      }),
+    });
+    return typeof response === "string" ? response : JSON.stringify(response);
+  }
+
+  async enumerateRegions(
+    includeFilePath: boolean = true
+  ): Promise<{ regions: any[] }> {
+    return this.request<{ regions: any[] }>(
+      `/api/memory/regions?include_file_path=${includeFilePath}`
+    );
+  }
+
+  // Memory analysis
+  async memoryScan(scanRequest: any): Promise<any> {
+    return this.request<any>("/api/memory/scan", {
+      method: "POST",
+      body: JSON.stringify(scanRequest),
+    });
+  }
+
+  // YARA memory scan
+  async yaraScan(scanRequest: {
+    rule: string;
+    address_ranges: [number, number][];
+    scan_id: string;
+    align: number;
+    do_suspend: boolean;
+  }): Promise<{
+    success: boolean;
+    message: string;
+    scan_id: string;
+    matches: {
+      rule_name: string;
+      address: number;
+      length: number;
+      pattern_id: string;
+      matched_data: string;
+    }[];
+    total_matches: number;
+    scanned_bytes: number;
+  }> {
+    return this.request("/api/memory/yara", {
+      method: "POST",
+      body: JSON.stringify(scanRequest),
+    });
+  }
+
+  async memoryFilter(filterRequest: FilterRequest): Promise<FilterResponse> {
+    return this.request<FilterResponse>("/api/memory/filter", {
+      method: "POST",
+      body: JSON.stringify(filterRequest),
+    });
+  }
+
+  async getScanProgress(
+    scanId: string
+  ): Promise<ApiResponse<ScanProgressResponse>> {
+    return this.request<ApiResponse<ScanProgressResponse>>(
+      "/api/memory/scan/progress",
+      {
+        method: "POST",
+        body: JSON.stringify({ scan_id: scanId }),
+      }
+    );
+  }
+
+  async getFilterProgress(
+    filterId: string
+  ): Promise<ApiResponse<FilterProgressResponse>> {
+    return this.request<ApiResponse<FilterProgressResponse>>(
+      "/api/memory/filter/progress",
+      {
+        method: "POST",
+        body: JSON.stringify({ filter_id: filterId }),
+      }
+    );
+  }
+
+  async getScanResults(scanId: string): Promise<any> {
+    return this.request<any>("/api/memory/scan/results", {
+      method: "POST",
+      body: JSON.stringify({ scan_id: scanId }),
+    });
+  }
+
+  async getFilterResults(scanId: string): Promise<any> {
+    return this.request<any>("/api/memory/filter/results", {
+      method: "POST",
+      body: JSON.stringify({ scan_id: scanId }),
+    });
+  }
+
+  async stopScan(scanId: string): Promise<SimpleResponse> {
+    return this.request<SimpleResponse>("/api/memory/scan/stop", {
+      method: "POST",
+      body: JSON.stringify({ scan_id: scanId }),
+    });
+  }
+
+  async clearScan(scanId: string): Promise<SimpleResponse> {
+    return this.request<SimpleResponse>("/api/memory/scan/clear", {
+      method: "POST",
+      body: JSON.stringify({ scan_id: scanId }),
+    });
+  }
+
+  // Tauri-specific disassembly (using local Rust implementation)
+  async disassembleMemoryLocal(
+    request: DisassembleRequest
+  ): Promise<DisassembleResponse> {
+    try {
+      return await invoke<DisassembleResponse>("disassemble_memory", {
+        request,
+      });
+    } catch (error) {
+      return {
+        success: false,
+        instructions_count: 0,
+        error: error instanceof Error ? error.message : "Unknown error",
+      };
+    }
+  }
+
+  // Structured disassembly (instruction objects with Capstone detail info)
+  async disassembleMemoryStructured(
+    request: DisassembleRequest
+  ): Promise<StructuredInstruction[]> {
+    return await invoke<StructuredInstruction[]>(
+      "disassemble_memory_structured",
+      { request }
+    );
+  }
+
+  async assembleInstructions(
+    request: AssembleRequest
+  ): Promise<AssembleResponse> {
+    return await invoke<AssembleResponse>("assemble_instructions", {
+      request,
+    });
+  }
+
+  async applyPatch(
+    address: number,
+    newBytes: number[],
+    description?: string
+  ): Promise<PatchInfo> {
+    return await invoke<PatchInfo>("apply_patch", {
+      address,
+      newBytes,
+      description,
+    });
+  }
+
+  async revertPatch(id: number): Promise<PatchInfo> {
+    return await invoke<PatchInfo>("revert_patch", { id });
+  }
+
+  async listPatches(
+    targetOs?: string,
+    moduleName?: string
+  ): Promise<PatchInfo[]> {
+    return await invoke<PatchInfo[]>("list_patches", { targetOs, moduleName });
+  }
+
+  // Persisted breakpoints (restored automatically when modules load)
+  async setStoredBreakpoint(request: {
+    address: number;
+    hitCount?: number;
+    condition?: string;
+    isSoftware?: boolean;
+    enabled?: boolean;
+    action?: BreakpointAction;
+  }): Promise<BreakpointDefinition> {
+    return await invoke<BreakpointDefinition>("set_breakpoint", request);
+  }
+
+  async removeStoredBreakpoint(request: {
+    id?: number;
+    address?: number;
+  }): Promise<boolean> {
+    return await invoke<boolean>("remove_breakpoint", request);
+  }
+
+  async listStoredBreakpoints(): Promise<BreakpointDefinition[]> {
+    return await invoke<BreakpointDefinition[]>("list_breakpoints");
+  }
+
+  async toggleStoredBreakpoint(
+    id: number,
+    enabled?: boolean
+  ): Promise<BreakpointDefinition> {
+    return await invoke<BreakpointDefinition>("toggle_breakpoint", {
+      id,
+      enabled,
+    });
+  }
+
+  // Shareable JSON of the stored breakpoints (module + offset / symbol)
+  async exportBreakpoints(request: {
+    targetOs?: string;
+    ids?: number[];
+    outputPath?: string;
+  } = {}): Promise<string> {
+    return await invoke<string>("export_breakpoints", request);
+  }
+
+  async importBreakpoints(request: {
+    json?: string;
+    path?: string;
+    targetOs?: string;
+    replace?: boolean;
+  }): Promise<BreakpointImportResult> {
+    return await invoke<BreakpointImportResult>("import_breakpoints", request);
+  }
+
+  async getRegisters(threadId: number): Promise<RegisterDump> {
+    return await invoke<RegisterDump>("get_registers", { threadId });
+  }
+
+  // name may be a register, a 32-bit view (w0, eax) or a flag (Z, CF)
+  async setRegister(
+    threadId: number,
+    name: string,
+    value: string
+  ): Promise<string> {
+    return await invoke<string>("set_register", { threadId, name, value });
+  }
+
+  // Listing of a range, or of the containing function when size is omitted
+  async exportDisassembly(request: {
+    address: number;
+    size?: number;
+    whole_function?: boolean;
+    format: "text" | "json" | "html";
+    architecture?: string;
+    comments?: boolean;
+    output_path?: string;
+  }): Promise<string> {
+    return await invoke<string>("export_disassembly", { request });
+  }
+
+  async getModuleAnalysisPolicy(
+    moduleName: string,
+    targetOs?: string
+  ): Promise<ModuleAnalysisPolicy> {
+    return await invoke<ModuleAnalysisPolicy>("get_module_analysis_policy", {
+      moduleName,
+      targetOs,
+    });
+  }
+
+  async setModuleAnalysisPolicy(
+    targetOs: string,
+    modulePattern: string,
+    mode: ModuleAnalysisPolicy["mode"],
+    autoStartServer?: boolean,
+    prefetchDecompiles?: boolean
+  ): Promise<ModuleAnalysisPolicy> {
+    return await invoke<ModuleAnalysisPolicy>("set_module_analysis_policy", {
+      targetOs,
+      modulePattern,
+      mode,
+      autoStartServer,
+      prefetchDecompiles,
+    });
+  }
+
+  async deleteModuleAnalysisPolicy(
+    targetOs: string,
+    modulePattern: string
+  ): Promise<boolean> {
+    return await invoke<boolean>("delete_module_analysis_policy", {
+      targetOs,
+      modulePattern,
+    });
+  }
+
+  async listModuleAnalysisPolicies(): Promise<ModuleAnalysisPolicy[]> {
+    return await invoke<ModuleAnalysisPolicy[]>("list_module_analysis_policies");
+  }
+
+  async listThreads(includeExited?: boolean): Promise<ThreadEntry[]> {
+    return await invoke<ThreadEntry[]>("list_threads", { includeExited });
+  }
+
+  async getThreadInfo(threadId: number): Promise<ThreadEntry> {
+    return await invoke<ThreadEntry>("get_thread_info", { threadId });
+  }
+
+  async resumeThread(threadId: number): Promise<boolean> {
+    return await invoke<boolean>("resume_thread", { threadId });
+  }
+
+  // Clock-sync handshake with dbgsrv; repeat later to refine the drift
+  async syncTargetClock(rounds?: number): Promise<ClockSyncStatus> {
+    return await invoke<ClockSyncStatus>("sync_target_clock", { rounds });
+  }
+
+  async getClockSyncStatus(): Promise<ClockSyncStatus | null> {
+    return await invoke<ClockSyncStatus | null>("get_clock_sync_status");
+  }
+
+  async convertTargetTimestamp(
+    timestamp: number | string
+  ): Promise<ConvertedTimestamp> {
+    return await invoke<ConvertedTimestamp>("convert_target_timestamp", {
+      timestamp,
+    });
+  }
+
+  // Backend-driven single-step tracing; progress arrives as
+  // "native-trace-progress" / "native-trace-complete" events
+  async startNativeTrace(
+    threadId: number,
+    maxEntries: number,
+    options?: NativeTraceOptions
+  ): Promise<NativeTraceStatus> {
+    return await invoke<NativeTraceStatus>("start_native_trace", {
+      threadId,
+      maxEntries,
+      options,
+    });
+  }
+
+  async stopNativeTrace(threadId: number): Promise<boolean> {
+    return await invoke<boolean>("stop_native_trace", { threadId });
+  }
+
+  async getNativeTraceStatus(threadId?: number): Promise<NativeTraceStatus[]> {
+    return await invoke<NativeTraceStatus[]>("get_native_trace_status", {
+      threadId,
+    });
+  }
+
+  async readNativeTrace(
+    threadId: number,
+    offset?: number,
+    count?: number
+  ): Promise<TauriTraceEntryData[]> {
+    return await invoke<TauriTraceEntryData[]>("read_native_trace", {
+      threadId,
+      offset,
+      count,
+    });
+  }
+
+  // Session: "native:<threadId>"
+  async seekTrace(sessionId: string, index: number): Promise<TraceMachineState> {
+    return await invoke<TraceMachineState>("seek_trace", { sessionId, index });
+  }
+
+  async clearNativeTrace(threadId: number): Promise<boolean> {
+    return await invoke<boolean>("clear_native_trace", { threadId });
+  }
+
+  // Sessions: "current", "native:<threadId>" or a .dyntrace / JSON file path
+  async diffTraceSessions(
+    sessionA: string,
+    sessionB: string,
+    options?: TraceDiffOptions
+  ): Promise<TraceDiffResult> {
+    return await invoke<TraceDiffResult>("diff_trace_sessions", {
+      sessionA,
+      sessionB,
+      options,
+    });
+  }
+
+  // Process list / attach through the Rust backend (retries network failures)
+  async listProcesses(
+    filter?: string,
+    includeIcons?: boolean
+  ): Promise<ProcessEntry[]> {
+    return await invoke<ProcessEntry[]>("list_processes", {
+      filter,
+      includeIcons,
+    });
+  }
+
+  async attachProcessNative(pid: number): Promise<AttachedProcessInfo> {
+    return await invoke<AttachedProcessInfo>("attach_process", { pid });
+  }
+
+  async detachProcess(): Promise<DetachResult> {
+    return await invoke<DetachResult>("detach_process");
+  }
+
+  async getAttachedProcessInfo(): Promise<AttachedProcessInfo | null> {
+    return await invoke<AttachedProcessInfo | null>(
+      "get_attached_process_info"
+    );
+  }
+
+  // Module / symbol enumeration with demangling and a persistent symbol cache
+  async listModules(): Promise<ListedModule[]> {
+    return await invoke<ListedModule[]>("list_modules");
+  }
+
+  async listModuleSymbols(
+    moduleName: string,
+    offset?: number,
+    limit?: number,
+    refresh?: boolean
+  ): Promise<ModuleSymbolsPage> {
+    return await invoke<ModuleSymbolsPage>("list_module_symbols", {
+      moduleName,
+      offset,
+      limit,
+      refresh,
+    });
+  }
+
+  async clearModuleSymbols(moduleHash?: string): Promise<number> {
+    return await invoke<number>("clear_module_symbols", { moduleHash });
+  }
+
+  // Offline target: serve memory reads, regions and scans from a dump file
+  async loadMemoryDump(
+    path: string,
+    format?: "auto" | "raw" | "sparse" | "minidump" | "elf_core",
+    baseAddress?: number
+  ): Promise<MemoryDumpInfo> {
+    return await invoke<MemoryDumpInfo>("load_memory_dump", {
+      path,
+      format,
+      baseAddress,
+    });
+  }
+
+  async unloadMemoryDump(): Promise<boolean> {
+    return await invoke<boolean>("unload_memory_dump");
+  }
+
+  async getMemoryDumpInfo(): Promise<MemoryDumpInfo | null> {
+    return await invoke<MemoryDumpInfo | null>("get_memory_dump_info");
+  }
+
+  // Progress is reported on "dump://progress" (DumpProgress)
+  async dumpProcessMemory(
+    outputPath: string,
+    format?: "sparse" | "minidump",
+    filters?: DumpFilters
+  ): Promise<MemoryDumpResult> {
+    return await invoke<MemoryDumpResult>("dump_process_memory", {
+      outputPath,
+      format,
+      filters,
+    });
+  }
+
+  async cancelMemoryDump(): Promise<boolean> {
+    return await invoke<boolean>("cancel_memory_dump");
+  }
+
+  // Save a loaded module's memory image, with headers repaired for Ghidra
+  async dumpModule(
+    moduleName: string,
+    fixupHeaders: boolean = true,
+    projectName?: string,
+    outputPath?: string
+  ): Promise<DumpedModule> {
+    return await invoke<DumpedModule>("dump_module", {
+      moduleName,
+      fixupHeaders,
+      projectName,
+      outputPath,
+    });
+  }
+
+  // Ghidra project disk usage
+  async listGhidraProjects(): Promise<GhidraProjectInfo[]> {
+    return await invoke<GhidraProjectInfo[]>("list_ghidra_projects");
+  }
+
+  async deleteGhidraProject(name: string): Promise<number> {
+    return await invoke<number>("delete_ghidra_project", { name });
+  }
+
+  async pruneGhidraProjects(
+    maxAgeDays?: number,
+    maxTotalBytes?: number
+  ): Promise<PruneResult> {
+    return await invoke<PruneResult>("prune_ghidra_projects", {
+      maxAgeDays,
+      maxTotalBytes,
+    });
+  }
+
+  // Changes are also pushed on "ghidra-server://status"
+  async getGhidraServerStatus(): Promise<GhidraServerStatus[]> {
+    return await invoke<GhidraServerStatus[]>("get_ghidra_server_status");
+  }
+
+  async setGhidraAutoRestart(
+    enabled: boolean,
+    maxRestarts?: number,
+    initialBackoffMs?: number
+  ): Promise<AutoRestartSettings> {
+    return await invoke<AutoRestartSettings>("set_ghidra_auto_restart", {
+      enabled,
+      maxRestarts,
+      initialBackoffMs,
+    });
+  }
+
+  // Pre-warm the decompile cache; progress on progressEvent
+  // (default "ghidra://decompile-progress")
+  async ghidraServerDecompileAll(
+    projectPath: string,
+    maxFunctions?: number,
+    progressEvent?: string,
+    refresh?: boolean
+  ): Promise<DecompileAllProgress> {
+    return await invoke<DecompileAllProgress>("ghidra_server_decompile_all", {
+      projectPath,
+      maxFunctions,
+      progressEvent,
+      refresh,
+    });
+  }
+
+  async cancelGhidraDecompileAll(projectPath: string): Promise<boolean> {
+    return await invoke<boolean>("cancel_ghidra_decompile_all", {
+      projectPath,
+    });
+  }
+
+  // Edits are saved into the Ghidra project; stale cache rows are dropped
+  async ghidraRenameFunction(
+    projectPath: string,
+    offset: string,
+    newName: string
+  ): Promise<GhidraEditResult> {
+    return await invoke<GhidraEditResult>("ghidra_rename_function", {
+      projectPath,
+      offset,
+      newName,
+    });
+  }
+
+  async ghidraRenameVariable(
+    projectPath: string,
+    offset: string,
+    oldName: string,
+    newName?: string,
+    newType?: string
+  ): Promise<GhidraEditResult> {
+    return await invoke<GhidraEditResult>("ghidra_rename_variable", {
+      projectPath,
+      offset,
+      oldName,
+      newName,
+      newType,
+    });
+  }
+
+  async ghidraSetComment(
+    projectPath: string,
+    offset: string,
+    comment: string,
+    commentType?: "eol" | "pre" | "post" | "plate" | "repeatable"
+  ): Promise<GhidraEditResult> {
+    return await invoke<GhidraEditResult>("ghidra_set_comment", {
+      projectPath,
+      offset,
+      comment,
+      commentType,
+    });
+  }
+
+  // Whole-module call graph, or the neighbourhood of rootOffset
+  async ghidraServerCallgraph(
+    projectPath: string,
+    rootOffset?: string,
+    depth?: number,
+    direction?: "callees" | "callers" | "both",
+    refresh?: boolean
+  ): Promise<CallGraphView> {
+    return await invoke<CallGraphView>("ghidra_server_callgraph", {
+      projectPath,
+      rootOffset,
+      depth,
+      direction,
+      refresh,
+    });
+  }
+
+  async ghidraSearch(
+    projectPath: string,
+    query: string,
+    kind: GhidraSearchKind,
+    limit?: number,
+    refresh?: boolean
+  ): Promise<GhidraSearchResult> {
+    return await invoke<GhidraSearchResult>("ghidra_search", {
+      projectPath,
+      query,
+      kind,
+      limit,
+      refresh,
+    });
+  }
+
+  async ghidraDiffModules(
+    projectA: string,
+    projectB: string
+  ): Promise<ModuleDiffResult> {
+    return await invoke<ModuleDiffResult>("ghidra_diff_modules", {
+      projectA,
+      projectB,
+    });
+  }
+
+  async getDecompilerSettings(
+    libraryPath: string,
+    projectPath?: string,
+    ghidraPath?: string
+  ): Promise<DecompilerSettings> {
+    return await invoke<DecompilerSettings>("get_decompiler_settings", {
+      libraryPath,
+      projectPath,
+      ghidraPath,
+    });
+  }
+
+  async setDecompilerBackend(
+    projectPath: string,
+    backend: DecompilerBackend,
+    toolPath?: string
+  ): Promise<void> {
+    return await invoke<void>("set_decompiler_backend", {
+      projectPath,
+      backend,
+      toolPath,
+    });
+  }
+
+  // device: "local" | "usb" | "host:port"; default is frida-server on the connected host
+  async fridaListProcesses(device?: string): Promise<FridaProcess[]> {
+    return await invoke<FridaProcess[]>("frida_list_processes", { device });
+  }
+
+  async fridaHook(
+    pid: number,
+    moduleName: string,
+    offset: string,
+    device?: string,
+    options?: FridaHookOptions
+  ): Promise<FridaHookInfo> {
+    return await invoke<FridaHookInfo>("frida_hook", {
+      pid,
+      moduleName,
+      offset,
+      device,
+      options,
+    });
+  }
+
+  async fridaUnhook(id: number): Promise<boolean> {
+    return await invoke<boolean>("frida_unhook", { id });
+  }
+
+  async fridaListHooks(): Promise<FridaHookInfo[]> {
+    return await invoke<FridaHookInfo[]>("frida_list_hooks");
+  }
+
+  // Lua scripting
+  async runScript(pathOrSource: string, name?: string): Promise<ScriptInfo> {
+    return await invoke<ScriptInfo>("run_script", { pathOrSource, name });
+  }
+
+  async stopScript(id: number): Promise<boolean> {
+    return await invoke<boolean>("stop_script", { id });
+  }
+
+  async listScripts(): Promise<ScriptInfo[]> {
+    return await invoke<ScriptInfo[]>("list_scripts");
+  }
+
+  async getScriptOutput(id: number): Promise<ScriptOutput[]> {
+    return await invoke<ScriptOutput[]>("get_script_output", { id });
+  }
+
+  // Cheat Engine tables
+  async importCheatTable(path: string): Promise<CheatTableImport> {
+    return await invoke<CheatTableImport>("import_cheat_table", { path });
+  }
+
+  async exportCheatTable(
+    path: string,
+    entries: CheatTableEntry[]
+  ): Promise<number> {
+    return await invoke<number>("export_cheat_table", { path, entries });
+  }
+
+  // Saved address list (persisted per target process)
+  async listWatchlist(): Promise<WatchEntry[]> {
+    return await invoke<WatchEntry[]>("list_watchlist");
+  }
+
+  async addWatchEntry(entry: WatchEntryInput): Promise<WatchEntry> {
+    return await invoke<WatchEntry>("add_watch_entry", { entry });
+  }
+
+  async updateWatchEntry(
+    id: number,
+    entry: WatchEntryInput
+  ): Promise<WatchEntry> {
+    return await invoke<WatchEntry>("update_watch_entry", { id, entry });
+  }
+
+  async deleteWatchEntry(id: number): Promise<boolean> {
+    return await invoke<boolean>("delete_watch_entry", { id });
+  }
+
+  async reorderWatchlist(ids: number[]): Promise<void> {
+    return await invoke<void>("reorder_watchlist", { ids });
+  }
+
+  async setWatchFrozen(
+    id: number,
+    frozen: boolean,
+    value?: string
+  ): Promise<WatchEntry> {
+    return await invoke<WatchEntry>("set_watch_frozen", { id, frozen, value });
+  }
+
+  async refreshWatchlist(): Promise<WatchValue[]> {
+    return await invoke<WatchValue[]>("refresh_watchlist");
+  }
+
+  // Global hotkeys (work while the window is unfocused)
+  async bindHotkey(
+    accelerator: string,
+    action: HotkeyAction
+  ): Promise<HotkeyBinding> {
+    return await invoke<HotkeyBinding>("bind_hotkey", { accelerator, action });
+  }
+
+  async unbindHotkey(accelerator: string): Promise<boolean> {
+    return await invoke<boolean>("unbind_hotkey", { accelerator });
+  }
+
+  async listHotkeys(): Promise<HotkeyBinding[]> {
+    return await invoke<HotkeyBinding[]>("list_hotkeys");
+  }
+
+  // Remote memory allocation and code caves
+  async allocateRemoteMemory(
+    size: number,
+    protection?: string,
+    nearAddress?: number
+  ): Promise<RemoteAllocation> {
+    return await invoke<RemoteAllocation>("allocate_remote_memory", {
+      size,
+      protection,
+      nearAddress,
+    });
+  }
+
+  async freeRemoteMemory(address: number): Promise<boolean> {
+    return await invoke<boolean>("free_remote_memory", { address });
+  }
+
+  async listRemoteAllocations(): Promise<RemoteAllocation[]> {
+    return await invoke<RemoteAllocation[]>("list_remote_allocations");
+  }
+
+  async findCodeCave(module: string, minSize: number): Promise<CodeCave[]> {
+    return await invoke<CodeCave[]>("find_code_cave", { module, minSize });
+  }
+
+  // Hex editor
+  async readMemoryPage(
+    address: number,
+    size: number,
+    generation?: number
+  ): Promise<HexPage> {
+    return await invoke<HexPage>("read_memory_page", {
+      address,
+      size,
+      generation,
+    });
+  }
+
+  async writeMemoryBytes(
+    address: number,
+    bytes: number[],
+    recordPatch?: boolean
+  ): Promise<PatchInfo | null> {
+    return await invoke<PatchInfo | null>("write_memory_bytes", {
+      address,
+      bytes,
+      recordPatch,
+    });
+  }
+
+  // Value codec; dataType also accepts "float16", "int16be".."uint64be",
+  // "pointer_pac[:vaBits]", "bitfield:offset:width" and "fixed:int.frac" / "ufixed:int.frac"
+  async encodeValue(dataType: string, text: string): Promise<number[]> {
+    return await invoke<number[]>("encode_value", { dataType, text });
+  }
+
+  async decodeValue(dataType: string, bytes: number[]): Promise<string> {
+    return await invoke<string>("decode_value", { dataType, bytes });
+  }
+
+  // Scan generations
+  async getScanStats(scanId: string): Promise<ScanStats> {
+    return await invoke<ScanStats>("get_scan_stats", { scanId });
+  }
+
+  async compactScan(scanId: string): Promise<ScanCompactResult> {
+    return await invoke<ScanCompactResult>("compact_scan", { scanId });
+  }
+
+  // Disassembly cache; pages are also invalidated by writes made through the client
+  async prefetchDisassembly(
+    centerAddress: number,
+    architecture: string,
+    pages?: number
+  ): Promise<number> {
+    return await invoke<number>("prefetch_disassembly", {
+      centerAddress,
+      pages,
+      architecture,
+    });
+  }
+
+  async invalidateDisassemblyCache(address?: number, size?: number): Promise<void> {
+    await invoke("invalidate_disassembly_cache", { address, size });
+  }
+
+  async getDisassemblyCacheStats(): Promise<DisassemblyCacheStats> {
+    return await invoke<DisassemblyCacheStats>("get_disassembly_cache_stats");
+  }
+
+  // Expression evaluator, e.g. "[[libgame.so+0x1234]+0x10]:f32"; registers is
+  // an optional register dump for the current thread
+  async evaluateExpression(
+    expr: string,
+    registers?: Record<string, unknown>
+  ): Promise<ExpressionResult> {
+    return await invoke<ExpressionResult>("evaluate_expression", {
+      expr,
+      registers,
+    });
+  }
+
+  // Objective-C / Swift metadata of a Mach-O module, read live (moduleName)
+  // or from a file such as a module dump (localPath)
+  async loadRuntimeMetadata(options: {
+    moduleName?: string;
+    localPath?: string;
+    base?: number;
+    refresh?: boolean;
+  }): Promise<RuntimeMetadataSummary> {
+    return await invoke<RuntimeMetadataSummary>("load_runtime_metadata", options);
+  }
+
+  async getObjcClass(key: string, name: string): Promise<ObjcClass> {
+    return await invoke<ObjcClass>("get_objc_class", { key, name });
+  }
+
+  async getSwiftType(key: string, name: string): Promise<SwiftType> {
+    return await invoke<SwiftType>("get_swift_type", { key, name });
+  }
+
+  async findObjcSelector(
+    key: string,
+    selector: string
+  ): Promise<ObjcImplementation[]> {
+    return await invoke<ObjcImplementation[]>("find_objc_selector", {
+      key,
+      selector,
+    });
+  }
+
+  // Android java layer: dex images are parsed from target memory and java
+  // methods resolved to their ArtMethod and native entry point
+  async listDexFiles(refresh?: boolean): Promise<DexFileInfo[]> {
+    return await invoke<DexFileInfo[]>("list_dex_files", { refresh });
   }
 
-  private notifyConnectionState(connected: boolean, error?: string) {
-    this.connectionListeners.forEach((listener) => listener(connected, error));
+  async listDexClasses(
+    dexAddress: number,
+    filter?: string
+  ): Promise<DexClassSummary[]> {
+    return await invoke<DexClassSummary[]>("list_dex_classes", {
+      dexAddress,
+      filter,
+    });
   }
 
-  private async request<T>(
-    endpoint: string,
-    options?: RequestInit,
-    requireAuth: boolean = true
-  ): Promise<T> {
-    const url = `${this.baseUrl}${endpoint}`;
-    const headers: { [key: string]: string } = {
-      "Content-Type": "application/json",
-      ...((options?.headers as { [key: string]: string }) || {}),
-    };
-
-    // Add auth token if required and available
-    if (requireAuth && this.authToken) {
-      headers["Authorization"] = `Bearer ${this.authToken}`;
-    }
+  async getDexClass(className: string, dexAddress?: number): Promise<DexClass> {
+    return await invoke<DexClass>("get_dex_class", { className, dexAddress });
+  }
 
-    const startTime = performance.now();
+  async resolveJavaMethod(
+    className: string,
+    methodName: string,
+    signature?: string,
+    dexAddress?: number
+  ): Promise<JavaMethodEntry[]> {
+    return await invoke<JavaMethodEntry[]>("resolve_java_method", {
+      className,
+      methodName,
+      signature,
+      dexAddress,
+    });
+  }
 
-    try {
-      // Add detailed timing for single step endpoint
-      const isSingleStep = endpoint === "/api/debug/step";
-      if (isSingleStep) {
-        console.log(`[API TIMING] fetch() starting at ${Date.now()}ms`);
-      }
+  async setJavaBreakpoint(options: {
+    className: string;
+    methodName: string;
+    signature?: string;
+    dexAddress?: number;
+    hitCount?: number;
+    condition?: string;
+    force?: boolean;
+  }): Promise<BreakpointDefinition> {
+    return await invoke<BreakpointDefinition>("set_java_breakpoint", options);
+  }
 
-      const response = await fetch(url, {
-        ...options,
-        headers,
-      });
+  // IL2CPP: parse downloaded global-metadata.dat + binary; methods are added
+  // to the module's function list
+  async loadIl2CppMetadata(
+    metadataPath: string,
+    binaryPath: string,
+    moduleName?: string
+  ): Promise<Il2CppLoadResult> {
+    return await invoke<Il2CppLoadResult>("load_il2cpp_metadata", {
+      metadataPath,
+      binaryPath,
+      moduleName,
+    });
+  }
 
-      const fetchEndTime = performance.now();
-      if (isSingleStep) {
-        console.log(
-          `[API TIMING] fetch() completed at ${Date.now()}ms, took ${(fetchEndTime - startTime).toFixed(2)}ms`
-        );
-      }
+  async searchIl2CppClasses(
+    targetOs: string,
+    moduleName: string,
+    query: string,
+    limit?: number
+  ): Promise<Il2CppClassSummary[]> {
+    return await invoke<Il2CppClassSummary[]>("search_il2cpp_classes", {
+      targetOs,
+      moduleName,
+      query,
+      limit,
+    });
+  }
 
-      if (!response.ok) {
-        // Check for authentication errors
-        if (response.status === 401) {
-          this.authToken = null;
-          this.serverSessionId = null;
-          this.notifyConnectionState(
-            false,
-            "Authentication failed - server may have restarted"
-          );
-          throw new Error("Authentication failed - please reconnect");
-        } else if (response.status === 403) {
-          this.notifyConnectionState(false, "Server access denied");
-          throw new Error("Server access denied");
-        }
+  async getIl2CppClass(
+    targetOs: string,
+    moduleName: string,
+    fullName: string
+  ): Promise<Il2CppClass> {
+    return await invoke<Il2CppClass>("get_il2cpp_class", {
+      targetOs,
+      moduleName,
+      fullName,
+    });
+  }
 
-        throw new Error(`HTTP ${response.status}: ${response.statusText}`);
-      }
+  // Mono: live introspection through a Frida agent in the target
+  async loadMonoAssemblies(target?: MonoTarget, refresh?: boolean): Promise<MonoAssembly[]> {
+    return await invoke<MonoAssembly[]>("load_mono_assemblies", { target, refresh });
+  }
 
-      const jsonStartTime = performance.now();
-      const jsonResult = await response.json();
-      if (isSingleStep) {
-        console.log(
-          `[API TIMING] json() parsing took ${(performance.now() - jsonStartTime).toFixed(2)}ms, total: ${(performance.now() - startTime).toFixed(2)}ms`
-        );
-      }
-      return jsonResult;
-    } catch (error) {
-      // Handle network errors as potential disconnections
-      if (error instanceof TypeError && error.message.includes("fetch")) {
-        this.notifyConnectionState(
-          false,
-          "Network error - server may be unreachable"
-        );
-        // Stop health check on network errors
-        this.stopHealthCheck();
-      } else if (
-        error instanceof Error &&
-        error.message.includes("ERR_CONNECTION_REFUSED")
-      ) {
-        this.notifyConnectionState(
-          false,
-          "Connection refused - server may have stopped"
-        );
-        this.stopHealthCheck();
-      }
-      throw error;
-    }
+  async searchMonoClasses(query: string, limit?: number, target?: MonoTarget): Promise<MonoClassInfo[]> {
+    return await invoke<MonoClassInfo[]>("search_mono_classes", { query, limit, target });
   }
 
-  async logout(): Promise<void> {
-    if (this.authToken) {
-      try {
-        await this.request<any>("/api/auth/logout", {
-          method: "POST",
-        });
-      } catch (error) {
-        // Ignore logout errors
-        console.warn("Logout request failed:", error);
-      }
-    }
+  async getMonoClass(className: string, target?: MonoTarget): Promise<MonoClassDetail> {
+    return await invoke<MonoClassDetail>("get_mono_class", { className, target });
+  }
 
-    this.authToken = null;
-    this.serverSessionId = null;
-    // Stop health check when logging out
-    this.stopHealthCheck();
+  // "Player:get_health"; overloads need a signature like "(int,string)"
+  async resolveMonoMethod(name: string, signature?: string, target?: MonoTarget): Promise<MonoMemberAddress> {
+    return await invoke<MonoMemberAddress>("resolve_mono_method", { name, signature, target });
   }
 
-  isAuthenticated(): boolean {
-    return this.authToken !== null;
+  async resolveMonoStaticField(name: string, target?: MonoTarget): Promise<MonoMemberAddress> {
+    return await invoke<MonoMemberAddress>("resolve_mono_static_field", { name, target });
   }
 
-  getServerSessionId(): string | null {
-    return this.serverSessionId;
+  async setMonoBreakpoint(options: {
+    name: string;
+    signature?: string;
+    hitCount?: number;
+    condition?: string;
+    target?: MonoTarget;
+  }): Promise<BreakpointDefinition> {
+    return await invoke<BreakpointDefinition>("set_mono_breakpoint", options);
   }
 
-  // Set authentication info for new windows
-  setAuthenticationInfo(authToken: string, serverSessionId?: string) {
-    this.authToken = authToken;
-    this.serverSessionId = serverSessionId || null;
-    console.log("Authentication info set:", {
-      hasToken: !!authToken,
-      hasSessionId: !!serverSessionId,
-    });
+  // WASM: CFG in the Ghidra graph view shape (addresses are module offsets)
+  async analyzeWasmCfg(binaryData: number[], functionIndex: number): Promise<GhidraCfgResult> {
+    return await invoke<GhidraCfgResult>("analyze_wasm_cfg", { binaryData, functionIndex });
   }
 
-  // Get current authentication info
-  getAuthenticationInfo(): {
-    authToken: string | null;
-    serverSessionId: string | null;
-  } {
-    return {
-      authToken: this.authToken,
-      serverSessionId: this.serverSessionId,
-    };
+  // CFG export as "dot" or "svg"; also written to outputPath when given
+  async exportCfg(cfg: GhidraCfgResult, format: "dot" | "svg", outputPath?: string): Promise<string> {
+    return await invoke<string>("export_cfg", { function: cfg, format, outputPath });
   }
 
-  // Health check
-  async healthCheck(): Promise<any> {
-    return this.request<any>("/health", undefined, false);
+  async exportWasmCfg(binaryData: number[], functionIndex: number, format: "dot" | "svg", outputPath?: string): Promise<string> {
+    return await invoke<string>("export_wasm_cfg", { binaryData, functionIndex, format, outputPath });
   }
 
-  // Start periodic health check
-  startHealthCheck(intervalMs: number = 5000) {
-    if (this.healthCheckInterval) {
-      this.stopHealthCheck();
-    }
+  async analyzeWasmCallGraph(binaryData: number[]): Promise<WasmCallGraphNode[]> {
+    return await invoke<WasmCallGraphNode[]>("analyze_wasm_call_graph", { binaryData });
+  }
 
-    this.isHealthCheckRunning = true;
-    this.healthCheckInterval = window.setInterval(async () => {
-      if (!this.isHealthCheckRunning) {
-        return;
-      }
+  // Debug symbols: PDB or DWARF file, e.g. a local_path from fetch_module_debug_info
+  async loadDebugSymbols(modulePath: string, moduleName?: string): Promise<DebugSymbolsLoadResult> {
+    return await invoke<DebugSymbolsLoadResult>("load_debug_symbols", { modulePath, moduleName });
+  }
 
-      try {
-        await this.healthCheck();
-        // Health check succeeded, no action needed
-      } catch (error) {
-        console.warn("Health check failed:", error);
-        // Notify listeners about connection loss
-        this.notifyConnectionState(false, "Server connection lost");
-        this.stopHealthCheck();
-      }
-    }, intervalMs);
+  async configureSymbolServers(urls: SymbolServerUrls): Promise<SymbolServerUrls> {
+    return await invoke<SymbolServerUrls>("configure_symbol_servers", { urls });
   }
 
-  // Stop health check
-  stopHealthCheck() {
-    this.isHealthCheckRunning = false;
-    if (this.healthCheckInterval) {
-      window.clearInterval(this.healthCheckInterval);
-      this.healthCheckInterval = null;
-    }
+  async getSymbolServers(): Promise<SymbolServerUrls> {
+    return await invoke<SymbolServerUrls>("get_symbol_servers");
   }
 
-  async getServerInfo(): Promise<ServerInfo> {
-    return this.request<ServerInfo>("/api/server/info");
+  // Runs in the background; false when the module is already being fetched.
+  // Progress is reported on "symbols://progress" (SymbolFetchProgress)
+  async fetchSymbols(module: SymbolModuleRequest, load?: boolean): Promise<boolean> {
+    return await invoke<boolean>("fetch_symbols", { module, load });
   }
 
-  // Process management
-  async enumerateProcesses(): Promise<ProcessInfo[]> {
-    return this.request<ProcessInfo[]>("/api/processes");
+  // Source view: local_path can be read with the read_local_text_file command
+  async resolveSourceLine(address: number): Promise<ResolvedSourceLine | null> {
+    return await invoke<ResolvedSourceLine | null>("resolve_source_line", { address });
   }
 
-  async attachProcess(pid: number): Promise<SimpleResponse> {
-    return this.request<SimpleResponse>(`/api/processes/${pid}/attach`, {
-      method: "POST",
-    });
+  async listSourceFiles(moduleName: string, targetOs?: string): Promise<SourceFileEntry[]> {
+    return await invoke<SourceFileEntry[]>("list_source_files", { moduleName, targetOs });
   }
 
-  async getProcessInfo(): Promise<ApiResponse<AppInfo>> {
-    return this.request<ApiResponse<AppInfo>>("/api/process/info");
+  async getSourcePathRules(): Promise<SourcePathRule[]> {
+    return await invoke<SourcePathRule[]>("get_source_path_rules");
   }
 
-  async changeProcessState(doPlay: boolean): Promise<SimpleResponse> {
-    return this.request<SimpleResponse>("/api/process/state", {
-      method: "PUT",
-      body: JSON.stringify({ do_play: doPlay }),
-    });
+  async setSourcePathRules(rules: SourcePathRule[]): Promise<SourcePathRule[]> {
+    return await invoke<SourcePathRule[]>("set_source_path_rules", { rules });
   }
 
-  async getProcessIcon(pid: number): Promise<string | null> {
-    try {
-      const response = await fetch(
-        `${this.baseUrl}/api/processes/${pid}/icon`,
-        {
-          headers: this.authToken
-            ? { Authorization: `Bearer ${this.authToken}` }
-            : {},
-        }
-      );
+  async listExceptionRules(): Promise<ExceptionRule[]> {
+    return await invoke<ExceptionRule[]>("list_exception_rules");
+  }
 
-      if (response.status === 401) {
-        this.authToken = null;
-        this.serverSessionId = null;
-        this.notifyConnectionState(
-          false,
-          "Authentication failed - server may have restarted"
-        );
-        return null;
-      }
+  async addExceptionRule(rule: ExceptionRuleInput): Promise<ExceptionRule> {
+    return await invoke<ExceptionRule>("add_exception_rule", { rule });
+  }
 
-      if (response.ok) {
-        const blob = await response.blob();
-        return URL.createObjectURL(blob);
-      }
-      return null;
-    } catch (error) {
-      if (error instanceof TypeError && error.message.includes("fetch")) {
-        this.notifyConnectionState(
-          false,
-          "Network error - server may be unreachable"
-        );
-        this.stopHealthCheck();
-      }
-      console.debug(`Failed to get icon for process ${pid}:`, error);
-      return null;
-    }
+  async updateExceptionRule(
+    id: number,
+    rule: ExceptionRuleInput
+  ): Promise<ExceptionRule> {
+    return await invoke<ExceptionRule>("update_exception_rule", { id, rule });
   }
 
-  // Module management
-  async enumerateModules(): Promise<ApiResponse<{ modules: ModuleInfo[] }>> {
-    return this.request<ApiResponse<{ modules: ModuleInfo[] }>>("/api/modules");
+  async deleteExceptionRule(id: number): Promise<boolean> {
+    return await invoke<boolean>("delete_exception_rule", { id });
   }
 
-  // Thread management
-  async enumerateThreads(): Promise<ApiResponse<{ threads: ThreadInfo[] }>> {
-    return this.request<ApiResponse<{ threads: ThreadInfo[] }>>("/api/threads");
+  async reorderExceptionRules(ids: number[]): Promise<void> {
+    return await invoke<void>("reorder_exception_rules", { ids });
   }
 
-  // Network connections
-  async enumerateNetwork(): Promise<
-    ApiResponse<{ connections: NetworkConnection[] }>
-  > {
-    return this.request<ApiResponse<{ connections: NetworkConnection[] }>>(
-      "/api/network"
-    );
+  async startAccessHeatmap(
+    region: HeatmapRegion,
+    granularity: number,
+    intervalMs?: number
+  ): Promise<AccessHeatmapStatus> {
+    return await invoke<AccessHeatmapStatus>("start_access_heatmap", {
+      region,
+      granularity,
+      intervalMs,
+    });
   }
 
-  async enumerateSymbols(
-    moduleBase: string
-  ): Promise<ApiResponse<{ symbols: SymbolInfo[] }>> {
-    const baseAddress = parseInt(moduleBase.replace("0x", ""), 16);
-    return this.request<ApiResponse<{ symbols: SymbolInfo[] }>>(
-      `/api/modules/${baseAddress}/symbols`
-    );
+  async stopAccessHeatmap(): Promise<AccessHeatmapStatus> {
+    return await invoke<AccessHeatmapStatus>("stop_access_heatmap");
   }
 
-  async enumerateSymbolsForModule(module: ModuleInfo): Promise<SymbolInfo[]> {
-    try {
-      const baseAddress =
-        module.base_address || `0x${module.base.toString(16)}`;
-      const response = await this.enumerateSymbols(baseAddress);
-      return response.data?.symbols || [];
-    } catch (error) {
-      const moduleName = module.name || module.modulename;
-      console.error(
-        `Failed to enumerate symbols for module ${moduleName}:`,
-        error
-      );
-      return [];
-    }
+  async getAccessHeatmap(top?: number): Promise<AccessHeatmap> {
+    return await invoke<AccessHeatmap>("get_access_heatmap", { top });
+  }
+
+  async scanVtables(moduleName: string): Promise<ModuleVtables> {
+    return await invoke<ModuleVtables>("scan_vtables", { moduleName });
   }
 
-  async enumerateAllSymbols(
-    modules: ModuleInfo[]
-  ): Promise<{ [moduleBase: string]: SymbolInfo[] }> {
-    const symbolsMap: { [moduleBase: string]: SymbolInfo[] } = {};
+  async getVtables(
+    moduleName: string,
+    targetOs?: string
+  ): Promise<ModuleVtables> {
+    return await invoke<ModuleVtables>("get_vtables", { moduleName, targetOs });
+  }
 
-    for (const module of modules) {
-      try {
-        const symbols = await this.enumerateSymbolsForModule(module);
-        const baseKey = module.base_address || `0x${module.base.toString(16)}`;
-        symbolsMap[baseKey] = symbols;
-      } catch (error) {
-        const moduleName = module.name || module.modulename;
-        const baseKey = module.base_address || `0x${module.base.toString(16)}`;
-        console.error(
-          `Failed to enumerate symbols for module ${moduleName}:`,
-          error
-        );
-        symbolsMap[baseKey] = [];
-      }
-    }
+  async getCacheStatus(
+    moduleName: string,
+    targetOs?: string
+  ): Promise<CacheStatus> {
+    return await invoke<CacheStatus>("get_cache_status", {
+      moduleName,
+      targetOs,
+    });
+  }
 
-    return symbolsMap;
+  async invalidateCaches(
+    moduleName: string,
+    reason: "patch" | "rename" | "reanalysis",
+    targetOs?: string
+  ): Promise<CacheStatus> {
+    return await invoke<CacheStatus>("invalidate_caches", {
+      moduleName,
+      reason,
+      targetOs,
+    });
   }
 
-  // Memory operations
-  async readMemory(
-    address: string,
-    size: number,
-    usePtrace: boolean = false
-  ): Promise<ArrayBuffer> {
-    // Convert hex address to decimal for API compatibility
-    let numericAddress: number;
-    if (address.startsWith("0x") || address.startsWith("0X")) {
-      numericAddress = parseInt(address, 16);
-    } else {
-      numericAddress = parseInt(address, 10);
+  async searchEverything(
+    query: string,
+    options?: {
+      kinds?: SearchEverythingKind[];
+      moduleName?: string;
+      targetOs?: string;
+      limit?: number;
     }
+  ): Promise<SearchEverythingResult> {
+    return await invoke<SearchEverythingResult>("search_everything", {
+      query,
+      kinds: options?.kinds,
+      moduleName: options?.moduleName,
+      targetOs: options?.targetOs,
+      limit: options?.limit,
+    });
+  }
 
-    if (isNaN(numericAddress) || numericAddress < 0) {
-      throw new Error(`Invalid address format: ${address}`);
-    }
+  async addAnnotation(annotation: AnnotationInput): Promise<Annotation> {
+    return await invoke<Annotation>("add_annotation", { annotation });
+  }
 
-    try {
-      const headers: { [key: string]: string } = {};
-      if (this.authToken) {
-        headers["Authorization"] = `Bearer ${this.authToken}`;
-      }
+  async updateAnnotation(
+    id: number,
+    annotation: AnnotationInput
+  ): Promise<Annotation> {
+    return await invoke<Annotation>("update_annotation", { id, annotation });
+  }
 
-      const usePtraceParam = usePtrace ? "&use_ptrace=true" : "";
-      const response = await fetch(
-        `${this.baseUrl}/api/memory/read?address=${numericAddress}&size=${size}${usePtraceParam}`,
-        { headers }
-      );
+  async deleteAnnotation(id: number): Promise<boolean> {
+    return await invoke<boolean>("delete_annotation", { id });
+  }
 
-      if (!response.ok) {
-        // Handle authentication errors
-        if (response.status === 401) {
-          this.authToken = null;
-          this.serverSessionId = null;
-          this.notifyConnectionState(
-            false,
-            "Authentication failed - server may have restarted"
-          );
-          throw new Error("Authentication failed - please reconnect");
-        } else if (response.status === 403) {
-          this.notifyConnectionState(false, "Server access denied");
-          throw new Error("Server access denied");
-        }
+  async listAnnotations(options?: {
+    moduleName?: string;
+    start?: number; // Module offsets with moduleName, else addresses
+    end?: number;
+    kind?: AnnotationKind;
+    targetOs?: string;
+  }): Promise<Annotation[]> {
+    return await invoke<Annotation[]>("list_annotations", {
+      moduleName: options?.moduleName,
+      start: options?.start,
+      end: options?.end,
+      kind: options?.kind,
+      targetOs: options?.targetOs,
+    });
+  }
 
-        const errorText = await response.text();
-        throw new Error(
-          `HTTP ${response.status}: ${response.statusText} - ${errorText}`
-        );
-      }
+  async exportAnnotations(
+    moduleName: string,
+    path: string,
+    targetOs?: string
+  ): Promise<AnnotationExportSummary> {
+    return await invoke<AnnotationExportSummary>("export_annotations", {
+      moduleName,
+      path,
+      targetOs,
+    });
+  }
 
-      return response.arrayBuffer();
-    } catch (error) {
-      // Handle network errors
-      if (error instanceof TypeError && error.message.includes("fetch")) {
-        this.notifyConnectionState(
-          false,
-          "Network error - server may be unreachable"
-        );
-        this.stopHealthCheck();
-      }
-      console.error(
-        `Failed to read memory at ${address} (${numericAddress}):`,
-        error
-      );
-      throw error;
-    }
+  async importAnnotations(
+    path: string,
+    mergeStrategy: AnnotationMergeStrategy,
+    options?: { moduleName?: string; targetOs?: string }
+  ): Promise<AnnotationImportResult> {
+    return await invoke<AnnotationImportResult>("import_annotations", {
+      path,
+      mergeStrategy,
+      moduleName: options?.moduleName,
+      targetOs: options?.targetOs,
+    });
   }
 
-  async writeMemory(address: string, buffer: ArrayBuffer): Promise<string> {
-    const response = await this.request<any>("/api/memory/write", {
-      method: "POST",
-      body: JSON.stringify({
-        address: parseInt(address.replace("0x", ""), 16),
-        buffer: Array.from(new Uint8Array(buffer)),
-      }),
+  async snapshotRegion(
+    address: number,
+    size: number,
+    label?: string
+  ): Promise<SnapshotInfo> {
+    return await invoke<SnapshotInfo>("snapshot_region", {
+      address,
+      size,
+      label,
     });
-    return typeof response === "string" ? response : JSON.stringify(response);
   }
 
-  async enumerateRegions(
-    includeFilePath: boolean = true
-  ): Promise<{ regions: any[] }> {
-    return this.request<{ regions: any[] }>(
-      `/api/memory/regions?include_file_path=${includeFilePath}`
-    );
+  // Offset 0 (or refresh) re-reads the target; later pages reuse that comparison
+  async compareSnapshot(
+    snapshotId: string,
+    offset?: number,
+    limit?: number,
+    refresh?: boolean
+  ): Promise<SnapshotDiff> {
+    return await invoke<SnapshotDiff>("compare_snapshot", {
+      snapshotId,
+      offset,
+      limit,
+      refresh,
+    });
   }
 
-  // Memory analysis
-  async memoryScan(scanRequest: any): Promise<any> {
-    return this.request<any>("/api/memory/scan", {
-      method: "POST",
-      body: JSON.stringify(scanRequest),
+  async listSnapshots(): Promise<SnapshotInfo[]> {
+    return await invoke<SnapshotInfo[]>("list_snapshots");
+  }
+
+  async deleteSnapshot(snapshotId: string): Promise<boolean> {
+    return await invoke<boolean>("delete_snapshot", { snapshotId });
+  }
+
+  async readSnapshot(
+    snapshotId: string,
+    address: number,
+    size: number
+  ): Promise<number[] | null> {
+    return await invoke<number[] | null>("read_snapshot", {
+      snapshotId,
+      address,
+      size,
     });
   }
 
-  // YARA memory scan
-  async yaraScan(scanRequest: {
-    rule: string;
-    address_ranges: [number, number][];
-    scan_id: string;
-    align: number;
-    do_suspend: boolean;
-  }): Promise<{
-    success: boolean;
-    message: string;
-    scan_id: string;
-    matches: {
-      rule_name: string;
-      address: number;
-      length: number;
-      pattern_id: string;
-      matched_data: string;
-    }[];
-    total_matches: number;
-    scanned_bytes: number;
-  }> {
-    return this.request("/api/memory/yara", {
-      method: "POST",
-      body: JSON.stringify(scanRequest),
+  // One-shot breakpoints at every function (or cached CFG block) of a module
+  async startCoverage(
+    moduleName: string,
+    options?: CoverageOptions
+  ): Promise<CoverageReport> {
+    return await invoke<CoverageReport>("start_coverage", {
+      moduleName,
+      options,
     });
   }
 
-  async memoryFilter(filterRequest: FilterRequest): Promise<FilterResponse> {
-    return this.request<FilterResponse>("/api/memory/filter", {
-      method: "POST",
-      body: JSON.stringify(filterRequest),
+  async stopCoverage(moduleName: string): Promise<CoverageReport> {
+    return await invoke<CoverageReport>("stop_coverage", { moduleName });
+  }
+
+  async getCoverageReport(
+    moduleName: string,
+    includeFunctions?: boolean
+  ): Promise<CoverageReport> {
+    return await invoke<CoverageReport>("get_coverage_report", {
+      moduleName,
+      includeFunctions,
     });
   }
 
-  async getScanProgress(
-    scanId: string
-  ): Promise<ApiResponse<ScanProgressResponse>> {
-    return this.request<ApiResponse<ScanProgressResponse>>(
-      "/api/memory/scan/progress",
-      {
-        method: "POST",
-        body: JSON.stringify({ scan_id: scanId }),
-      }
-    );
+  async listCoverageSessions(): Promise<CoverageReport[]> {
+    return await invoke<CoverageReport[]>("list_coverage_sessions");
   }
 
-  async getFilterProgress(
-    filterId: string
-  ): Promise<ApiResponse<FilterProgressResponse>> {
-    return this.request<ApiResponse<FilterProgressResponse>>(
-      "/api/memory/filter/progress",
-      {
-        method: "POST",
-        body: JSON.stringify({ filter_id: filterId }),
-      }
-    );
+  async clearCoverage(moduleName: string): Promise<boolean> {
+    return await invoke<boolean>("clear_coverage", { moduleName });
   }
 
-  async getScanResults(scanId: string): Promise<any> {
-    return this.request<any>("/api/memory/scan/results", {
-      method: "POST",
-      body: JSON.stringify({ scan_id: scanId }),
-    });
+  // DrCov file for Lighthouse; returns the number of blocks written
+  async exportCoverageDrcov(moduleName: string, path: string): Promise<number> {
+    return await invoke<number>("export_coverage_drcov", { moduleName, path });
   }
 
-  async getFilterResults(scanId: string): Promise<any> {
-    return this.request<any>("/api/memory/filter/results", {
-      method: "POST",
-      body: JSON.stringify({ scan_id: scanId }),
+  // "json" is Chrome trace event format (chrome://tracing, Perfetto)
+  async exportTraceSession(
+    sessionId: string,
+    format: TraceExportFormat,
+    path: string
+  ): Promise<TraceExportResult> {
+    return await invoke<TraceExportResult>("export_trace_session", {
+      sessionId,
+      format,
+      path,
     });
   }
 
-  async stopScan(scanId: string): Promise<SimpleResponse> {
-    return this.request<SimpleResponse>("/api/memory/scan/stop", {
-      method: "POST",
-      body: JSON.stringify({ scan_id: scanId }),
-    });
+  // Adds a `timing` breakdown to read/disassemble/decompile-cache/lookup results
+  async setLatencyInstrumentation(
+    enabled: boolean,
+    clear?: boolean
+  ): Promise<void> {
+    await invoke("set_latency_instrumentation", { enabled, clear });
   }
 
-  async clearScan(scanId: string): Promise<SimpleResponse> {
-    return this.request<SimpleResponse>("/api/memory/scan/clear", {
-      method: "POST",
-      body: JSON.stringify({ scan_id: scanId }),
+  async getLatencyInstrumentation(): Promise<boolean> {
+    return await invoke<boolean>("get_latency_instrumentation");
+  }
+
+  async getLatencyReport(
+    operation?: string
+  ): Promise<LatencyOperationSummary[]> {
+    return await invoke<LatencyOperationSummary[]>("get_latency_report", {
+      operation,
     });
   }
 
-  // Tauri-specific disassembly (using local Rust implementation)
-  async disassembleMemoryLocal(
-    request: DisassembleRequest
-  ): Promise<DisassembleResponse> {
-    try {
-      return await invoke<DisassembleResponse>("disassemble_memory", {
-        request,
-      });
-    } catch (error) {
-      return {
-        success: false,
-        instructions_count: 0,
-        error: error instanceof Error ? error.message : "Unknown error",
-      };
-    }
+  async getCommentProviders(): Promise<CommentProviderConfig[]> {
+    return await invoke<CommentProviderConfig[]>("get_comment_providers");
+  }
+
+  async setCommentProvider(
+    name: string,
+    enabled?: boolean,
+    ttlMs?: number
+  ): Promise<CommentProviderConfig[]> {
+    return await invoke<CommentProviderConfig[]>("set_comment_provider", {
+      name,
+      enabled,
+      ttlMs,
+    });
   }
 
   // Set server connection for Tauri backend
-  async setTauriServerConnection(host: string, port: number): Promise<void> {
+  async setTauriServerConnection(
+    host: string,
+    port: number,
+    tls?: ServerTlsOptions
+  ): Promise<void> {
     try {
-      await invoke("set_server_connection", { host, port });
+      await invoke("set_server_connection", {
+        host,
+        port,
+        useTls: tls?.useTls,
+        acceptSelfSigned: tls?.acceptSelfSigned,
+      });
     } catch (error) {
       console.error("Failed to set Tauri server connection:", error);
     }
   }
 
+  // Pool/timeout/retry policy of the Rust backend's shared HTTP client
+  async getHttpSettings(): Promise<HttpSettings> {
+    return await invoke<HttpSettings>("get_http_settings");
+  }
+
+  async setHttpSettings(settings: HttpSettings): Promise<HttpSettings> {
+    return await invoke<HttpSettings>("set_http_settings", { settings });
+  }
+
+  // Settings kept in the backend's app_settings table (encrypted when enabled)
+  async getAppSetting(key: string): Promise<string | null> {
+    return await invoke<string | null>("get_app_setting", { key });
+  }
+
+  async saveAppSetting(key: string, value: string): Promise<boolean> {
+    return await invoke<boolean>("save_app_setting", { key, value });
+  }
+
+  async deleteAppSetting(key: string): Promise<boolean> {
+    return await invoke<boolean>("delete_app_setting", { key });
+  }
+
+  // TLS certificate pinning (used by the Rust backend's server connections)
+  async getServerCertificate(
+    host: string,
+    port: number
+  ): Promise<ServerCertificate> {
+    return await invoke<ServerCertificate>("get_server_certificate", {
+      host,
+      port,
+    });
+  }
+
+  async pinServerCertificate(
+    host: string,
+    port: number,
+    fingerprint: string
+  ): Promise<ServerCertificate> {
+    return await invoke<ServerCertificate>("pin_server_certificate", {
+      host,
+      port,
+      fingerprint,
+    });
+  }
+
+  async unpinServerCertificate(host: string, port: number): Promise<boolean> {
+    return await invoke<boolean>("unpin_server_certificate", { host, port });
+  }
+
+  async listPinnedCertificates(): Promise<PinnedCertificate[]> {
+    return await invoke<PinnedCertificate[]>("list_pinned_certificates");
+  }
+
   // Read memory using Tauri backend
   async readMemoryLocal(
     address: number,
@@ -940,12 +3590,18 @@ class ApiClient {
   // Native memory lookup using Tauri backend (reads current values for addresses)
   async lookupMemoryNative(
     addresses: number[],
-    dataType: string
+    dataType: string,
+    pointerDepth?: number,
+    pointerSize?: number,
+    detectObjects?: boolean
   ): Promise<NativeMemoryFilterResponse> {
     try {
       return await invoke<NativeMemoryFilterResponse>("lookup_memory_native", {
         addresses,
-        data_type: dataType,
+        dataType,
+        pointerDepth,
+        pointerSize,
+        detectObjects,
       });
     } catch (error) {
       return {
@@ -1003,7 +3659,7 @@ class ApiClient {
   // ============================================================================
 
   // Native unknown scan using Tauri backend (scans memory ranges for unknown initial value)
-  // Results are stored in temp files, use getUnknownScanProgress to poll progress
+  // Results are stored in temp files, use onUnknownScanProgress to follow progress
   async unknownScanNative(
     request: NativeUnknownScanRequest
   ): Promise<NativeUnknownScanResponse> {
@@ -1022,50 +3678,25 @@ class ApiClient {
     }
   }
 
-  // Initialize unknown scan progress (call before starting scan to prevent race condition)
-  async initUnknownScanProgress(
+  // Subscribe to progress of an unknown scan, pushed by the backend as
+  // `scan://progress` events; call the returned function to unsubscribe
+  async onUnknownScanProgress(
     scanId: string,
-    totalBytes: number
-  ): Promise<void> {
-    try {
-      await invoke("init_unknown_scan_progress", {
-        scanId: scanId,
-        totalBytes: totalBytes,
-      });
-    } catch (error) {
-      console.error("Failed to init unknown scan progress:", error);
-    }
-  }
-
-  // Get unknown scan progress
-  async getUnknownScanProgress(
-    scanId: string
-  ): Promise<NativeUnknownScanProgress> {
-    try {
-      return await invoke<NativeUnknownScanProgress>(
-        "get_unknown_scan_progress",
-        {
-          scanId: scanId,
-        }
-      );
-    } catch (error) {
-      return {
-        scan_id: scanId,
-        progress_percentage: 0,
-        processed_bytes: 0,
-        total_bytes: 0,
-        found_count: 0,
-        is_scanning: false,
-        current_region: undefined,
-      };
-    }
+    handler: (progress: NativeUnknownScanProgress) => void
+  ): Promise<UnlistenFn> {
+    return await listen<NativeUnknownScanProgress>("scan://progress", (event) => {
+      if (event.payload.scan_id === scanId) {
+        handler(event.payload);
+      }
+    });
   }
 
   // Load unknown scan results from temp files (for display)
   async loadUnknownScanResults(
     scanId: string,
     offset: number,
-    limit: number
+    limit: number,
+    detectObjects?: boolean
   ): Promise<NativeUnknownScanLookupResponse> {
     try {
       return await invoke<NativeUnknownScanLookupResponse>(
@@ -1074,6 +3705,7 @@ class ApiClient {
           scanId: scanId,
           offset,
           limit,
+          detectObjects,
         }
       );
     } catch (error) {
@@ -762,6 +762,7 @@ struct ServerInfo {
     arch: String,
     pid: u32,
     mode: String,
+    time_us: i64, // Wall clock when the request was handled (client clock sync)
 }
 
 pub async fn server_info_handler() -> Result<impl warp::Reply, warp::Rejection> {
@@ -791,6 +792,7 @@ pub async fn server_info_handler() -> Result<impl warp::Reply, warp::Rejection>
         arch: arch.to_string(),
         pid: pid,
         mode: std::env::var("DBGSRV_RUNNING_MODE").unwrap_or_else(|_| "unknown".to_string()),
+        time_us: chrono::Utc::now().timestamp_micros(),
     };
 
     Ok(warp::reply::json(&server_info))
@@ -1216,6 +1218,71 @@ pub async fn write_memory_handler(
     }
 }
 
+pub async fn allocate_memory_handler(
+    pid_state: Arc<Mutex<Option<i32>>>,
+    allocate: request::AllocateMemoryRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let pid = *pid_state.lock().unwrap();
+    let Some(pid) = pid else {
+        return Ok(warp::reply::with_status(
+            warp::reply::json(&request::AllocateMemoryResponse {
+                success: false,
+                address: None,
+                error: Some("Pid not set".to_string()),
+            }),
+            StatusCode::BAD_REQUEST,
+        ));
+    };
+    let protection = allocate.protection.as_deref().unwrap_or("rwx");
+    let mask = protection.chars().fold(0, |mask, c| match c {
+        'r' => mask | 1,
+        'w' => mask | 2,
+        'x' => mask | 4,
+        _ => mask,
+    });
+    match native_bridge::allocate_process_memory(pid, allocate.size, mask, allocate.near_address.unwrap_or(0)) {
+        Ok(address) => Ok(warp::reply::with_status(
+            warp::reply::json(&request::AllocateMemoryResponse {
+                success: true,
+                address: Some(address),
+                error: None,
+            }),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&request::AllocateMemoryResponse {
+                success: false,
+                address: None,
+                error: Some(format!("Allocation failed: {}", e)),
+            }),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )),
+    }
+}
+
+pub async fn free_memory_handler(
+    pid_state: Arc<Mutex<Option<i32>>>,
+    free: request::FreeMemoryRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let pid = *pid_state.lock().unwrap();
+    let Some(pid) = pid else {
+        return Ok(warp::reply::with_status(
+            warp::reply::json(&json!({ "success": false, "error": "Pid not set" })),
+            StatusCode::BAD_REQUEST,
+        ));
+    };
+    match native_bridge::free_process_memory(pid, free.address, free.size) {
+        Ok(()) => Ok(warp::reply::with_status(
+            warp::reply::json(&json!({ "success": true })),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&json!({ "success": false, "error": format!("Free failed: {}", e) })),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )),
+    }
+}
+
 pub async fn memory_scan_handler(
     pid_state: Arc<Mutex<Option<i32>>>,
     scan_request: request::MemoryScanRequest,
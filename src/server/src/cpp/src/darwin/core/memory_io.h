@@ -90,10 +90,35 @@ extern "C"
     ssize_t write_memory_native(int pid, mach_vm_address_t address, mach_vm_size_t size,
                                 unsigned char* buffer);
 
+    /**
+     * Allocate memory in target process using Mach VM API
+     * @param pid Target process ID
+     * @param size Number of bytes to allocate
+     * @param protection MEMORY_PROT_* bits
+     * @param near_address Address to search upward from (0 for anywhere)
+     * @return Address of the allocation, or 0 on error
+     */
+    uintptr_t allocate_memory_native(int pid, size_t size, int protection,
+                                     uintptr_t near_address);
+
+    /**
+     * Free memory allocated with allocate_memory_native
+     * @param pid Target process ID
+     * @param address Address of the allocation
+     * @param size Size of the allocation
+     * @return 0 on success, or -1 on error
+     */
+    int free_memory_native(int pid, uintptr_t address, size_t size);
+
 #ifdef __cplusplus
 }
 #endif
 
+// Protection bits for allocate_memory_native
+#define MEMORY_PROT_READ 1
+#define MEMORY_PROT_WRITE 2
+#define MEMORY_PROT_EXEC 4
+
 // =============================================================================
 // Internal C++ helper functions
 // =============================================================================
@@ -193,3 +193,60 @@ ssize_t write_memory_native(int pid, mach_vm_address_t address, mach_vm_size_t s
     }
     return static_cast<ssize_t>(size);
 }
+
+// =============================================================================
+// Memory Allocation Operations
+// =============================================================================
+
+uintptr_t allocate_memory_native(int pid, size_t size, int protection, uintptr_t near_address)
+{
+    mach_port_t task = get_task_port_for_pid(pid);
+    if (task == MACH_PORT_NULL)
+    {
+        debug_log(LOG_ERROR, "allocate_memory_native: No task port for pid %d", pid);
+        return 0;
+    }
+
+    // With VM_FLAGS_ANYWHERE the address is a hint: the kernel searches upward from it
+    mach_vm_address_t address = near_address;
+    kern_return_t kr = mach_vm_allocate(task, &address, size, VM_FLAGS_ANYWHERE);
+    if (kr != KERN_SUCCESS)
+    {
+        debug_log(LOG_ERROR, "mach_vm_allocate failed: %d (%s) size %zu", kr,
+                  mach_error_string(kr), size);
+        return 0;
+    }
+
+    vm_prot_t prot = VM_PROT_NONE;
+    if (protection & MEMORY_PROT_READ) prot |= VM_PROT_READ;
+    if (protection & MEMORY_PROT_WRITE) prot |= VM_PROT_WRITE;
+    if (protection & MEMORY_PROT_EXEC) prot |= VM_PROT_EXECUTE;
+    kr = mach_vm_protect(task, address, size, false, prot);
+    if (kr != KERN_SUCCESS)
+    {
+        // W^X targets refuse RWX; the allocation stays read/write
+        debug_log(LOG_WARN, "mach_vm_protect failed: %d (%s) at 0x%llx", kr,
+                  mach_error_string(kr), address);
+    }
+
+    return static_cast<uintptr_t>(address);
+}
+
+int free_memory_native(int pid, uintptr_t address, size_t size)
+{
+    mach_port_t task = get_task_port_for_pid(pid);
+    if (task == MACH_PORT_NULL)
+    {
+        debug_log(LOG_ERROR, "free_memory_native: No task port for pid %d", pid);
+        return -1;
+    }
+
+    kern_return_t kr = mach_vm_deallocate(task, address, size);
+    if (kr != KERN_SUCCESS)
+    {
+        debug_log(LOG_ERROR, "mach_vm_deallocate failed: %d (%s) at 0x%lx", kr,
+                  mach_error_string(kr), address);
+        return -1;
+    }
+    return 0;
+}
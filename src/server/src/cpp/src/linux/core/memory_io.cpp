@@ -392,3 +392,52 @@ ssize_t write_memory_native(int pid, void* address, size_t size, unsigned char*
         return total_written;
     }
 }
+
+// =============================================================================
+// Memory Allocation
+// =============================================================================
+
+uintptr_t allocate_memory_native(int pid, size_t size, int protection, uintptr_t near_address)
+{
+    if (pid != get_pid_native())
+    {
+        // Mapping into another process needs a remote mmap syscall, which is not implemented
+        debug_log(LOG_ERROR, "allocate_memory_native: only supported in embedded mode (pid %d)\n",
+                  pid);
+        errno = ENOTSUP;
+        return 0;
+    }
+
+    int prot = PROT_NONE;
+    if (protection & MEMORY_PROT_READ) prot |= PROT_READ;
+    if (protection & MEMORY_PROT_WRITE) prot |= PROT_WRITE;
+    if (protection & MEMORY_PROT_EXEC) prot |= PROT_EXEC;
+
+    // Without MAP_FIXED the address is only a hint
+    void* result = mmap(reinterpret_cast<void*>(near_address), size, prot,
+                        MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+    if (result == MAP_FAILED)
+    {
+        debug_log(LOG_ERROR, "mmap failed with error %d (%s)\n", errno, strerror(errno));
+        return 0;
+    }
+    return reinterpret_cast<uintptr_t>(result);
+}
+
+int free_memory_native(int pid, uintptr_t address, size_t size)
+{
+    if (pid != get_pid_native())
+    {
+        debug_log(LOG_ERROR, "free_memory_native: only supported in embedded mode (pid %d)\n",
+                  pid);
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    if (munmap(reinterpret_cast<void*>(address), size) != 0)
+    {
+        debug_log(LOG_ERROR, "munmap failed with error %d (%s)\n", errno, strerror(errno));
+        return -1;
+    }
+    return 0;
+}
@@ -91,3 +91,87 @@ SSIZE_T write_memory_native(int pid, void* address, size_t size, unsigned char*
     CloseHandle(processHandle);
     return bytesWritten;
 }
+
+// =============================================================================
+// Memory Allocation Functions
+// =============================================================================
+
+static DWORD page_protection(int protection)
+{
+    bool write = protection & MEMORY_PROT_WRITE;
+    if (protection & MEMORY_PROT_EXEC)
+    {
+        return write ? PAGE_EXECUTE_READWRITE : PAGE_EXECUTE_READ;
+    }
+    return write ? PAGE_READWRITE : PAGE_READONLY;
+}
+
+uintptr_t allocate_memory_native(int pid, size_t size, int protection, uintptr_t near_address)
+{
+    HANDLE processHandle =
+        OpenProcess(PROCESS_VM_OPERATION | PROCESS_QUERY_INFORMATION, FALSE, pid);
+    if (processHandle == NULL)
+    {
+        debug_log(LOG_ERROR, "Failed to open process %d for allocation. Error code: %lu", pid,
+                  GetLastError());
+        return 0;
+    }
+
+    DWORD flProtect = page_protection(protection);
+    LPVOID result = NULL;
+    if (near_address != 0)
+    {
+        // Walk free regions upward from the hint, staying within rel32 reach
+        SYSTEM_INFO sysInfo;
+        GetSystemInfo(&sysInfo);
+        uintptr_t granularity = sysInfo.dwAllocationGranularity;
+        uintptr_t limit = near_address + 0x7FFF0000;
+        uintptr_t address = (near_address + granularity - 1) & ~(granularity - 1);
+        MEMORY_BASIC_INFORMATION mbi;
+        while (result == NULL && address < limit &&
+               VirtualQueryEx(processHandle, (LPCVOID)address, &mbi, sizeof(mbi)) != 0)
+        {
+            uintptr_t regionEnd = (uintptr_t)mbi.BaseAddress + mbi.RegionSize;
+            if (mbi.State == MEM_FREE && regionEnd - address >= size)
+            {
+                result = VirtualAllocEx(processHandle, (LPVOID)address, size,
+                                        MEM_COMMIT | MEM_RESERVE, flProtect);
+            }
+            address = (regionEnd + granularity - 1) & ~(granularity - 1);
+        }
+    }
+    if (result == NULL)
+    {
+        result = VirtualAllocEx(processHandle, NULL, size, MEM_COMMIT | MEM_RESERVE, flProtect);
+    }
+    if (result == NULL)
+    {
+        debug_log(LOG_ERROR, "VirtualAllocEx failed for process %d. Error code: %lu", pid,
+                  GetLastError());
+    }
+
+    CloseHandle(processHandle);
+    return (uintptr_t)result;
+}
+
+int free_memory_native(int pid, uintptr_t address, size_t size)
+{
+    HANDLE processHandle = OpenProcess(PROCESS_VM_OPERATION, FALSE, pid);
+    if (processHandle == NULL)
+    {
+        debug_log(LOG_ERROR, "Failed to open process %d for free. Error code: %lu", pid,
+                  GetLastError());
+        return -1;
+    }
+
+    // MEM_RELEASE frees the whole allocation; the size must be 0
+    BOOL ok = VirtualFreeEx(processHandle, (LPVOID)address, 0, MEM_RELEASE);
+    if (!ok)
+    {
+        debug_log(LOG_ERROR, "VirtualFreeEx failed for process %d at 0x%p. Error code: %lu", pid,
+                  (void*)address, GetLastError());
+    }
+
+    CloseHandle(processHandle);
+    return ok ? 0 : -1;
+}
@@ -57,4 +57,34 @@ extern "C" NATIVE_API SSIZE_T read_memory_native_with_method(int pid, uintptr_t
 extern "C" NATIVE_API SSIZE_T write_memory_native(int pid, void* address, size_t size,
                                                   unsigned char* buffer);
 
+// =============================================================================
+// Memory Allocation Functions
+// =============================================================================
+
+// Protection bits for allocate_memory_native
+#define MEMORY_PROT_READ 1
+#define MEMORY_PROT_WRITE 2
+#define MEMORY_PROT_EXEC 4
+
+/**
+ * Allocate memory in a process
+ * @param pid Process ID
+ * @param size Number of bytes to allocate
+ * @param protection MEMORY_PROT_* bits
+ * @param near_address Preferred location (0 for anywhere); the first free
+ *        region at or above it within 2GB is used when one exists
+ * @return Address of the allocation, or 0 on error
+ */
+extern "C" NATIVE_API uintptr_t allocate_memory_native(int pid, size_t size, int protection,
+                                                       uintptr_t near_address);
+
+/**
+ * Free memory allocated with allocate_memory_native
+ * @param pid Process ID
+ * @param address Address returned by allocate_memory_native
+ * @param size Size of the allocation (unused; the whole allocation is released)
+ * @return 0 on success, or -1 on error
+ */
+extern "C" NATIVE_API int free_memory_native(int pid, uintptr_t address, size_t size);
+
 #endif  // WINDOWS_MEMORY_IO_H
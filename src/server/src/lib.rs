@@ -29,7 +29,7 @@ fn main() {
             
             logger::init_log(None);
             log::info!("memory_spy has started listening on host {} and port {}.", host, port);
-            serve::serve(1, host, port).await;
+            serve::serve(1, host, port, None).await;
         });
     });
 }
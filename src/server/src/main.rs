@@ -54,6 +54,22 @@ async fn main() {
                 .value_name("FILE")
                 .help("Sets the log file path (appends to existing file)"),
         )
+        .arg(
+            Arg::new("tls-cert")
+                .long("tls-cert")
+                .num_args(1)
+                .value_name("FILE")
+                .requires("tls-key")
+                .help("Serve HTTPS with this PEM certificate (requires --tls-key)"),
+        )
+        .arg(
+            Arg::new("tls-key")
+                .long("tls-key")
+                .num_args(1)
+                .value_name("FILE")
+                .requires("tls-cert")
+                .help("PEM private key for --tls-cert"),
+        )
         .arg(
             Arg::new("wasm")
                 .long("wasm")
@@ -84,6 +100,11 @@ async fn main() {
         .get_one("log-file")
         .map(|s: &String| s.to_string());
 
+    let tls: Option<(String, String)> = matches
+        .get_one::<String>("tls-cert")
+        .zip(matches.get_one::<String>("tls-key"))
+        .map(|(cert, key)| (cert.to_string(), key.to_string()));
+
     let wasm_mode = matches.get_flag("wasm");
     let wasm_ws_port: u16 = matches
         .get_one::<String>("wasm-ws-port")
@@ -98,8 +119,10 @@ async fn main() {
     }
 
     println!(
-        "DynaDbg server has started listening on host {} and port {}.",
-        host, port
+        "DynaDbg server has started listening on host {} and port {}{}.",
+        host,
+        port,
+        if tls.is_some() { " (HTTPS)" } else { "" }
     );
 
     logger::init_log(log_file.as_deref());
@@ -122,5 +145,5 @@ async fn main() {
         log::info!("Dynamic library loaded successfully");
     }
 
-    serve::serve(0, host, port).await;
+    serve::serve(0, host, port, tls).await;
 }
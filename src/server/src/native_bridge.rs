@@ -110,6 +110,15 @@ extern "C" {
         size: libc::size_t,
         buffer: *const u8,
     ) -> libc::ssize_t;
+    #[link_name = "allocate_memory_native"]
+    pub fn allocate_memory_native_static(
+        pid: i32,
+        size: libc::size_t,
+        protection: c_int,
+        near_address: libc::uintptr_t,
+    ) -> libc::uintptr_t;
+    #[link_name = "free_memory_native"]
+    pub fn free_memory_native_static(pid: i32, address: libc::uintptr_t, size: libc::size_t) -> c_int;
     #[link_name = "suspend_process"]
     pub fn suspend_process_static(pid: i32) -> bool;
     #[link_name = "resume_process"]
@@ -410,6 +419,8 @@ wrap_native_fn!(free_region_info(regions: *mut RegionInfo, count: usize) -> ());
 wrap_native_fn!(read_memory_native(pid: libc::c_int, address: libc::uintptr_t, size: libc::size_t, buffer: *mut u8) -> libc::ssize_t);
 wrap_native_fn!(read_memory_native_with_method(pid: libc::c_int, address: libc::uintptr_t, size: libc::size_t, buffer: *mut u8, mode: libc::c_int) -> libc::ssize_t);
 wrap_native_fn!(write_memory_native(pid: i32, address: libc::uintptr_t, size: libc::size_t, buffer: *const u8) -> libc::ssize_t);
+wrap_native_fn!(allocate_memory_native(pid: i32, size: libc::size_t, protection: c_int, near_address: libc::uintptr_t) -> libc::uintptr_t);
+wrap_native_fn!(free_memory_native(pid: i32, address: libc::uintptr_t, size: libc::size_t) -> c_int);
 wrap_native_fn!(suspend_process(pid: i32) -> bool);
 wrap_native_fn!(resume_process(pid: i32) -> bool);
 wrap_native_fn!(native_init(mode: i32) -> libc::c_int);
@@ -722,6 +733,31 @@ pub fn set_watchpoint(pid: i32, address: usize, size: usize, type_: i32) -> Resu
     }
 }
 
+/// Allocate `size` bytes in the target; `protection` is a mask of 1 (read),
+/// 2 (write) and 4 (execute). `near_address` is a placement hint (0 for none).
+pub fn allocate_process_memory(
+    pid: i32,
+    size: usize,
+    protection: i32,
+    near_address: usize,
+) -> Result<usize, Error> {
+    let result = unsafe { allocate_memory_native(pid, size, protection, near_address) };
+    if result != 0 {
+        Ok(result)
+    } else {
+        Err(Error::last_os_error())
+    }
+}
+
+pub fn free_process_memory(pid: i32, address: usize, size: usize) -> Result<(), Error> {
+    let result = unsafe { free_memory_native(pid, address, size) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(Error::last_os_error())
+    }
+}
+
 pub fn remove_watchpoint(address: usize) -> Result<i32, Error> {
     let result = unsafe { remove_watchpoint_native(address) };
     if result == 0 {
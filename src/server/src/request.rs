@@ -26,6 +26,26 @@ pub struct WriteMemoryRequest {
     pub buffer: Vec<u8>,
 }
 
+#[derive(Deserialize)]
+pub struct AllocateMemoryRequest {
+    pub size: usize,
+    pub protection: Option<String>, // "r", "rw", "rx" or "rwx" (default)
+    pub near_address: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct AllocateMemoryResponse {
+    pub success: bool,
+    pub address: Option<usize>,
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct FreeMemoryRequest {
+    pub address: usize,
+    pub size: usize,
+}
+
 #[derive(Deserialize, Clone)]
 pub struct MemoryScanRequest {
     pub pattern: String,
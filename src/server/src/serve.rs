@@ -9,7 +9,8 @@ use crate::native_bridge;
 use crate::request;
 use crate::wasm_bridge;
 
-pub async fn serve(mode: i32, host: IpAddr, port: u16) {
+/// Run the API server; `tls` is (certificate, private key) PEM paths for HTTPS
+pub async fn serve(mode: i32, host: IpAddr, port: u16, tls: Option<(String, String)>) {
     // Initialize WASM bridge if in WASM mode
     if wasm_bridge::is_wasm_mode() {
         if let Err(e) = wasm_bridge::init_wasm_bridge().await {
@@ -124,6 +125,26 @@ pub async fn serve(mode: i32, host: IpAddr, port: u16) {
             api::write_memory_handler(pid_state, write_memory).await
         });
 
+    let allocate_memory = api
+        .and(warp::path!("memory" / "allocate"))
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api::with_auth())
+        .and(api::with_state(pid_state.clone()))
+        .and_then(|allocate, pid_state| async move {
+            api::allocate_memory_handler(pid_state, allocate).await
+        });
+
+    let free_memory = api
+        .and(warp::path!("memory" / "free"))
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api::with_auth())
+        .and(api::with_state(pid_state.clone()))
+        .and_then(|free, pid_state| async move {
+            api::free_memory_handler(pid_state, free).await
+        });
+
     let enum_regions = api
         .and(warp::path!("memory" / "regions"))
         .and(warp::get())
@@ -696,6 +717,8 @@ pub async fn serve(mode: i32, host: IpAddr, port: u16) {
     // Group 2: Memory routes
     let memory_routes = read_memory
         .or(write_memory)
+        .or(allocate_memory)
+        .or(free_memory)
         .or(enum_regions)
         .or(yara_scan)
         .or(memory_scan)
@@ -790,5 +813,15 @@ pub async fn serve(mode: i32, host: IpAddr, port: u16) {
         }
     }
     
-    warp::serve(routes).run((host, port)).await;
+    match tls {
+        Some((cert_path, key_path)) => {
+            warp::serve(routes)
+                .tls()
+                .cert_path(cert_path)
+                .key_path(key_path)
+                .run((host, port))
+                .await
+        }
+        None => warp::serve(routes).run((host, port)).await,
+    }
 }